@@ -0,0 +1,174 @@
+use crate::cf::{ChangeSet, TableMutation};
+use crate::err::Error;
+use crate::sql::array::Array;
+use crate::sql::object::Object;
+use crate::sql::value::Value;
+use crate::vs::{conv, Versionstamp};
+
+// The name of the connector which is reported in the source of each change event
+const CONNECTOR: &str = "surrealdb";
+
+/// The source of the changes which are formatted as Debezium change events
+pub struct Source<'a> {
+	pub ns: &'a str,
+	pub db: &'a str,
+	// Whether the change feed includes the original value of updated records
+	pub store_diff: bool,
+	// The timestamps of the datastore ticks, with the versionstamp at each tick,
+	// which are used to find the time at which each change was committed
+	pub ticks: &'a [(u64, Versionstamp)],
+	// The time at which the changes are formatted, in milliseconds
+	pub now_ms: u64,
+}
+
+impl Source<'_> {
+	/// Get the time of the last tick before the versionstamp, in milliseconds
+	fn ts_ms(&self, vs: &Versionstamp) -> Option<u64> {
+		self.ticks.iter().rev().find(|(_, tick)| tick <= vs).map(|(ts, _)| ts * 1000)
+	}
+
+	/// Format the record changes of a change set as Debezium change event envelopes.
+	///
+	/// Each envelope has the `before` and `after` values of the record, the `op`, which is
+	/// `c` for a create, `u` for an update, and `d` for a delete, the `source` metadata of
+	/// the change, and the `ts_ms` at which the change was formatted. Creates can only be
+	/// told apart from updates, and the `before` value of an update is only known, when the
+	/// change feed includes original values. Table definitions are not change events, so
+	/// they are skipped.
+	pub fn envelopes(&self, set: ChangeSet) -> Result<Vec<(u64, Value)>, Error> {
+		let ChangeSet(vs, muts) = set;
+		let since = conv::versionstamp_to_u64(&vs);
+		let mut out = Vec::new();
+		for tb in muts.0 {
+			for m in tb.1 {
+				let (op, before, after) = match m {
+					TableMutation::Set(_, v) if self.store_diff => ("c", Value::Null, v),
+					TableMutation::Set(_, v) => ("u", Value::Null, v),
+					TableMutation::SetWithDiff(_, v, ops) => {
+						// The operations recreate the original value from the current value
+						let mut before = v.clone();
+						before.patch(Value::Array(Array(
+							ops.into_iter().map(|op| Value::Object(Object::from(op))).collect(),
+						)))?;
+						("u", before, v)
+					}
+					TableMutation::DelWithOriginal(_, v) => ("d", v, Value::Null),
+					// Deletes do not store the deleted value, so only the id is known
+					TableMutation::Del(id) => {
+						let before = Value::from(map! { "id" => Value::from(id) });
+						("d", before, Value::Null)
+					}
+					TableMutation::Def(_) => continue,
+				};
+				let source = map! {
+					"version" => Value::from(crate::env::VERSION),
+					"connector" => Value::from(CONNECTOR),
+					"name" => Value::from(CONNECTOR),
+					"ts_ms" => self.ts_ms(&vs).map(Value::from).unwrap_or(Value::Null),
+					"snapshot" => Value::from("false"),
+					"ns" => Value::from(self.ns),
+					"db" => Value::from(self.db),
+					"table" => Value::from(tb.0.as_str()),
+					"versionstamp" => Value::from(since),
+				};
+				let envelope = map! {
+					"before" => before,
+					"after" => after,
+					"source" => Value::from(source),
+					"op" => Value::from(op),
+					"ts_ms" => Value::from(self.now_ms),
+				};
+				out.push((since, Value::from(envelope)));
+			}
+		}
+		Ok(out)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::cf::{DatabaseMutation, TableMutations};
+	use crate::sql::statements::DefineTableStatement;
+	use crate::sql::{Idiom, Thing};
+	use crate::syn::value;
+
+	fn source(store_diff: bool, ticks: &[(u64, Versionstamp)]) -> Source<'_> {
+		Source {
+			ns: "test",
+			db: "test",
+			store_diff,
+			ticks,
+			now_ms: 1_700_000_000_123,
+		}
+	}
+
+	fn set(vs: u64, muts: Vec<TableMutation>) -> ChangeSet {
+		let tb = TableMutations("person".to_string(), muts);
+		ChangeSet(conv::u64_to_versionstamp(vs), DatabaseMutation(vec![tb]))
+	}
+
+	#[test]
+	fn envelopes_for_changes() {
+		let id = Thing::from(("person", "one"));
+		let before = value("{ id: person:one, name: 'one' }").unwrap();
+		let after = value("{ id: person:one, name: 'uno', age: 30 }").unwrap();
+		let ticks = [(1_700_000_000, conv::u64_to_versionstamp(2))];
+		let src = source(true, &ticks);
+		let res = src
+			.envelopes(set(
+				3,
+				vec![
+					TableMutation::Def(DefineTableStatement::default()),
+					TableMutation::Set(id.clone(), before.clone()),
+					TableMutation::SetWithDiff(
+						id.clone(),
+						after.clone(),
+						after.diff(&before, Idiom::default()),
+					),
+					TableMutation::Del(id),
+				],
+			))
+			.unwrap();
+		assert_eq!(res.len(), 3);
+		assert!(res.iter().all(|(vs, _)| *vs == 3));
+		let expected = value(&format!(
+			"[
+				{{ before: NULL, after: {before}, op: 'c' }},
+				{{ before: {before}, after: {after}, op: 'u' }},
+				{{ before: {{ id: person:one }}, after: NULL, op: 'd' }}
+			]"
+		))
+		.unwrap();
+		let Value::Array(expected) = expected else {
+			unreachable!()
+		};
+		for ((_, v), e) in res.iter().zip(expected.iter()) {
+			assert_eq!(v.pick(&["before".into()]), e.pick(&["before".into()]));
+			assert_eq!(v.pick(&["after".into()]), e.pick(&["after".into()]));
+			assert_eq!(v.pick(&["op".into()]), e.pick(&["op".into()]));
+			assert_eq!(v.pick(&["ts_ms".into()]), Value::from(1_700_000_000_123u64));
+		}
+		let source = res[0].1.pick(&["source".into()]);
+		let expected = value(&format!(
+			"{{ version: '{}', connector: 'surrealdb', name: 'surrealdb', ts_ms: 1700000000000, snapshot: 'false', ns: 'test', db: 'test', table: 'person', versionstamp: 3 }}",
+			crate::env::VERSION
+		))
+		.unwrap();
+		assert_eq!(source, expected);
+	}
+
+	#[test]
+	fn envelopes_without_original_values() {
+		let id = Thing::from(("person", "one"));
+		let after = value("{ id: person:one }").unwrap();
+		// There is no tick before the change
+		let ticks = [(1_700_000_000, conv::u64_to_versionstamp(5))];
+		let src = source(false, &ticks);
+		let res = src.envelopes(set(3, vec![TableMutation::Set(id, after.clone())])).unwrap();
+		assert_eq!(res[0].1.pick(&["op".into()]), Value::from("u"));
+		assert_eq!(res[0].1.pick(&["before".into()]), Value::Null);
+		assert_eq!(res[0].1.pick(&["after".into()]), after);
+		assert_eq!(res[0].1.pick(&["source".into(), "ts_ms".into()]), Value::Null);
+	}
+}
@@ -1,84 +1,127 @@
+use crate::cnf::NORMAL_FETCH_SIZE;
 use crate::err::Error;
 use crate::key::change;
 #[cfg(debug_assertions)]
 use crate::key::debug::sprint_key;
-use crate::kvs::Transaction;
+use crate::kvs::scheduler::Job;
+use crate::kvs::{Limit, ScanPage, Transaction};
+use crate::sql::statements::{DefineDatabaseStatement, DefineTableStatement};
+use crate::sql::{Datetime, Duration, Object, Value};
 use crate::vs;
 use crate::vs::Versionstamp;
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap};
 use std::str;
+use std::time;
 
 // gc_all_at deletes all change feed entries that become stale at the given timestamp.
+// At most `limit` change feed entries are deleted from each database, and the number
+// of deleted entries is returned.
 #[allow(unused)]
-pub async fn gc_all_at(tx: &mut Transaction, ts: u64, limit: Option<u32>) -> Result<(), Error> {
+pub async fn gc_all_at(tx: &mut Transaction, ts: u64, limit: Option<u32>) -> Result<u64, Error> {
 	let nses = tx.all_ns().await?;
 	let nses = nses.as_ref();
+	let mut deleted = 0;
 	for ns in nses {
-		gc_ns(tx, ns.name.as_str(), limit, ts).await?;
+		deleted += gc_ns(tx, ns.name.as_str(), limit, ts).await?;
 	}
-	Ok(())
+	Ok(deleted)
 }
 
-// gc_ns deletes all change feed entries in the given namespace that are older than the given watermark.
+// gc_ns deletes all change feed entries in the given namespace that are older than the retention of their table.
 #[allow(unused)]
 pub async fn gc_ns(
 	tx: &mut Transaction,
 	ns: &str,
 	limit: Option<u32>,
 	ts: u64,
-) -> Result<(), Error> {
+) -> Result<u64, Error> {
+	let limit = limit.unwrap_or(100);
 	let dbs = tx.all_db(ns).await?;
 	let dbs = dbs.as_ref();
+	let mut deleted = 0;
 	for db in dbs {
-		// We get the expiration of the change feed defined on the database
-		let db_cf_expiry = match &db.changefeed {
-			None => 0,
-			Some(cf) => cf.expiry.as_secs(),
-		};
+		let tbs = tx.all_tb(ns, db.name.as_str()).await?;
+		// The retention of the change feed entries of each table, in seconds.
+		// Entries of tables which no longer exist use the database retention.
+		let retentions: BTreeMap<&str, u64> =
+			tbs.iter().map(|tb| (tb.name.as_str(), retention(db, Some(tb)))).collect();
+		let fallback = retention(db, None);
+		let min = retentions.values().copied().fold(fallback, u64::min);
+		let max = retentions.values().copied().fold(fallback, u64::max);
 		#[cfg(debug_assertions)]
 		trace!(
-			"Performing garbage collection on ns {} db {} for ts {}. The cf expiration is {}",
+			"Performing garbage collection on ns {} db {} for ts {}. The cf retention is between {} and {}",
 			ns,
 			db.name,
 			ts,
-			db_cf_expiry
+			min,
+			max
 		);
-		let tbs = tx.all_tb(ns, db.name.as_str()).await?;
-		let tbs = tbs.as_ref();
-		let max_tb_cf_expiry = tbs.iter().fold(0, |acc, tb| match &tb.changefeed {
-			None => acc,
-			Some(cf) => {
-				if cf.expiry.is_zero() {
-					acc
-				} else {
-					acc.max(cf.expiry.as_secs())
-				}
+		if ts < min {
+			continue;
+		}
+		// Find the watermark for each distinct retention
+		let mut watermarks: HashMap<u64, Option<Versionstamp>> = HashMap::new();
+		for r in retentions.values().copied().chain([fallback]) {
+			if let Entry::Vacant(e) = watermarks.entry(r) {
+				let vs = match ts.checked_sub(r) {
+					Some(w) => tx.get_versionstamp_from_timestamp(w, ns, &db.name, true).await?,
+					None => None,
+				};
+				e.insert(vs);
 			}
-		});
-		let cf_expiry = db_cf_expiry.max(max_tb_cf_expiry);
-		if ts < cf_expiry {
+		}
+		// Delete the change feed entries which are older than the retention of their table
+		let Some(end) = watermarks[&min] else {
 			continue;
+		};
+		let beg = change::prefix_ts(ns, db.name.as_str(), vs::u64_to_versionstamp(0));
+		let end = change::prefix_ts(ns, db.name.as_str(), end);
+		let mut stale: BTreeMap<String, Vec<Vec<u8>>> = BTreeMap::new();
+		let mut count = 0;
+		let mut next_page = Some(ScanPage::from(beg..end));
+		while let Some(page) = next_page {
+			let res = tx.scan_paged(page, NORMAL_FETCH_SIZE).await?;
+			next_page = res.next_page;
+			for (k, _) in res.values {
+				let dec = change::Cf::decode(&k)?;
+				let r = retentions.get(dec.tb).copied().unwrap_or(fallback);
+				if watermarks[&r].is_some_and(|w| dec.vs < w) {
+					stale.entry(dec.tb.to_owned()).or_default().push(k);
+					count += 1;
+					if count >= limit {
+						next_page = None;
+						break;
+					}
+				}
+			}
 		}
-		// We only want to retain the expiry window, so we are going to delete everything before
-		let watermark_ts = ts - cf_expiry;
-		#[cfg(debug_assertions)]
-		trace!("The watermark is {} after removing {cf_expiry} from {ts}", watermark_ts);
-		let watermark_vs =
-			tx.get_versionstamp_from_timestamp(watermark_ts, ns, db.name.as_str(), true).await?;
-		if let Some(watermark_vs) = watermark_vs {
-			gc_db(tx, ns, db.name.as_str(), watermark_vs, limit).await?;
+		let scheduler = tx.scheduler();
+		for (tb, keys) in stale {
+			scheduler.admit(Job::ChangeFeedCleanup, ns, &db.name, &tb, keys.len() as u64).await;
+			for k in keys {
+				tx.del(k).await?;
+			}
+		}
+		deleted += count as u64;
+		// Delete the versionstamp mappings which no change feed needs any longer
+		if let Some(w) = ts.checked_sub(max) {
+			gc_ts(tx, ns, db.name.as_str(), w, limit).await?;
 		}
 	}
-	Ok(())
+	Ok(deleted)
 }
 
-// gc_db deletes all change feed entries in the given database that are older than the given watermark.
+// gc_db deletes at most `limit` change feed entries in the given database that are older than the given watermark.
+#[allow(unused)]
 pub async fn gc_db(
 	tx: &mut Transaction,
 	ns: &str,
 	db: &str,
 	watermark: Versionstamp,
 	limit: Option<u32>,
-) -> Result<(), Error> {
+) -> Result<u64, Error> {
 	let beg: Vec<u8> = change::prefix_ts(ns, db, vs::u64_to_versionstamp(0));
 	let end = change::prefix_ts(ns, db, watermark);
 	#[cfg(debug_assertions)]
@@ -93,7 +136,89 @@ pub async fn gc_db(
 
 	let limit = limit.unwrap_or(100);
 
-	tx.delr(beg..end, limit).await?;
+	let res = tx.getr(beg..end, limit).await?;
+	let deleted = res.len() as u64;
+	for (k, _) in res {
+		tx.del(k).await?;
+	}
 
+	Ok(deleted)
+}
+
+// gc_ts deletes at most `limit` timestamp to versionstamp mappings which are older than the given timestamp.
+// The latest mapping at or before the timestamp is retained, as it is needed to resolve the timestamp itself.
+async fn gc_ts(tx: &mut Transaction, ns: &str, db: &str, ts: u64, limit: u32) -> Result<(), Error> {
+	let beg = crate::key::database::ts::prefix(ns, db);
+	let end = crate::key::database::ts::new(ns, db, ts.saturating_add(1));
+	let mut res = tx.getr(beg..end.into(), limit.saturating_add(1)).await?;
+	if res.len() <= limit as usize {
+		res.pop();
+	}
+	for (k, _) in res.into_iter().take(limit as usize) {
+		tx.del(k).await?;
+	}
 	Ok(())
 }
+
+/// The retention of the change feed entries of a table, in seconds
+pub(super) fn retention(db: &DefineDatabaseStatement, tb: Option<&DefineTableStatement>) -> u64 {
+	let db = db.changefeed.as_ref().map(|cf| cf.expiry.as_secs()).unwrap_or(0);
+	let tb = tb.and_then(|tb| tb.changefeed.as_ref()).map(|cf| cf.expiry.as_secs()).unwrap_or(0);
+	db.max(tb)
+}
+
+/// Returns the size of the change feed of a table, and how far
+/// the removal of its stale entries is behind its retention
+pub(crate) async fn table_stats(
+	tx: &mut Transaction,
+	ns: &str,
+	db: &DefineDatabaseStatement,
+	tb: &DefineTableStatement,
+) -> Result<Value, Error> {
+	// Count the change feed entries of the table
+	let mut entries: u64 = 0;
+	let mut oldest: Option<Versionstamp> = None;
+	let mut next_page = Some(ScanPage {
+		range: change::prefix(ns, &db.name)..change::suffix(ns, &db.name),
+		limit: Limit::Unlimited,
+	});
+	while let Some(page) = next_page {
+		let res = tx.scan_paged(page, NORMAL_FETCH_SIZE).await?;
+		next_page = res.next_page;
+		for (k, _) in res.values {
+			let dec = change::Cf::decode(&k)?;
+			if dec.tb == tb.name.as_str() {
+				entries += 1;
+				oldest.get_or_insert(dec.vs);
+			}
+		}
+	}
+	// Find the time at which the oldest entry was written
+	let mut written: Option<u64> = None;
+	if let Some(oldest) = oldest {
+		let beg = crate::key::database::ts::prefix(ns, &db.name);
+		let end = crate::key::database::ts::suffix(ns, &db.name);
+		for (k, v) in tx.getr(beg..end, u32::MAX).await? {
+			if v.as_slice() > oldest.as_slice() {
+				break;
+			}
+			written = Some(crate::key::database::ts::Ts::decode(&k)?.ts);
+		}
+	}
+	// The time by which the oldest entry has outlived the retention
+	let retention = time::Duration::from_secs(retention(db, Some(tb)));
+	let (oldest, lag) = match written {
+		Some(ts) => {
+			let dt = chrono::DateTime::from_timestamp(ts as i64, 0).unwrap_or_default();
+			let age = (chrono::Utc::now() - dt).to_std().unwrap_or_default();
+			(Value::from(Datetime::from(dt)), age.saturating_sub(retention))
+		}
+		None => (Value::None, time::Duration::ZERO),
+	};
+	Ok(Value::from(Object::from(map! {
+		"retention" => Value::from(Duration::from(retention)),
+		"entries" => Value::from(entries),
+		"oldest" => oldest,
+		"lag" => Value::from(Duration::from(lag)),
+	})))
+}
@@ -0,0 +1,106 @@
+//! Reads of tables as they were at an earlier time.
+//!
+//! When the change feed of a table includes the original records, using
+//! `CHANGEFEED ... INCLUDE ORIGINAL`, each change records the value which the
+//! record had before it was changed. The records as they were at an earlier
+//! time are found by taking the first change to each record since that time,
+//! and undoing it. Records which have not changed since that time are read as
+//! they currently are. The time is mapped to the change feed using the ticks of
+//! the datastore, so the records are those at the last tick before the time.
+use super::gc::retention;
+use super::{TableMutation, TableMutations};
+use crate::cnf::NORMAL_FETCH_SIZE;
+use crate::err::Error;
+use crate::key::change;
+use crate::kvs::{ScanPage, Transaction};
+use crate::sql::{Array, Datetime, Id, Object, Value};
+use chrono::Utc;
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
+
+/// The records of a table which have changed since a time, along with
+/// their values at that time. Records which did not exist have no value.
+pub(crate) async fn changed(
+	tx: &mut Transaction,
+	ns: &str,
+	db: &str,
+	tb: &str,
+	at: &Datetime,
+) -> Result<BTreeMap<Id, Option<Value>>, Error> {
+	// Get the database and table definitions
+	let dbv = tx.get_and_cache_db(ns, db).await?;
+	let tbv = tx.get_and_cache_tb(ns, db, tb).await?;
+	// Check that the change feed includes the original records
+	match dbv.changefeed.as_ref().or(tbv.changefeed.as_ref()) {
+		Some(cf) if cf.store_diff => (),
+		_ => {
+			return Err(Error::VersionNoChangeFeed {
+				value: tb.to_owned(),
+			})
+		}
+	}
+	// Nothing has changed since a time in the future
+	let now = Utc::now();
+	if at.0 > now {
+		return Ok(BTreeMap::new());
+	}
+	let outside = || Error::VersionOutsideRetention {
+		value: tb.to_owned(),
+		version: at.to_string(),
+	};
+	// Check that the changes since the time have been retained
+	let ts = u64::try_from(at.0.timestamp()).map_err(|_| outside())?;
+	let now = now.timestamp() as u64;
+	if ts < now.saturating_sub(retention(&dbv, Some(&tbv))) {
+		return Err(outside());
+	}
+	// Find the first change after the time
+	let Some(vs) = tx.get_versionstamp_from_timestamp(ts, ns, db, true).await? else {
+		return Err(outside());
+	};
+	// Undo the first change to each record since the time
+	let mut out = BTreeMap::new();
+	let beg = change::prefix_ts(ns, db, vs);
+	let end = change::suffix(ns, db);
+	let mut next_page = Some(ScanPage::from(beg..end));
+	while let Some(page) = next_page {
+		let res = tx.scan_paged(page, NORMAL_FETCH_SIZE).await?;
+		next_page = res.next_page;
+		for (k, v) in res.values {
+			let dec = change::Cf::decode(&k)?;
+			if dec.tb != tb {
+				continue;
+			}
+			let muts: TableMutations = v.into();
+			for m in muts.1 {
+				let (id, before) = match m {
+					// The record was created
+					TableMutation::Set(id, _) => (id, None),
+					// The record was updated
+					TableMutation::SetWithDiff(id, mut v, ops) => {
+						// The operations recreate the original value from the current value
+						v.patch(Value::Array(Array(
+							ops.into_iter().map(|op| Value::Object(Object::from(op))).collect(),
+						)))?;
+						(id, Some(v))
+					}
+					// The record was deleted
+					TableMutation::DelWithOriginal(id, v) => (id, Some(v)),
+					// The record was deleted before the original records were included
+					TableMutation::Del(_) => {
+						return Err(Error::VersionNoChangeFeed {
+							value: tb.to_owned(),
+						})
+					}
+					// The table was defined again since the time, so the
+					// change feed might not contain every change to it
+					TableMutation::Def(_) => return Err(outside()),
+				};
+				if let Entry::Vacant(e) = out.entry(id.id) {
+					e.insert(before);
+				}
+			}
+		}
+	}
+	Ok(out)
+}
@@ -1,4 +1,6 @@
+pub(crate) mod debezium;
 pub(crate) mod gc;
+pub(crate) mod history;
 pub(crate) mod mutations;
 pub(crate) mod reader;
 pub(crate) mod writer;
@@ -13,7 +13,7 @@ use std::collections::{BTreeMap, HashMap};
 use std::fmt::{self, Display, Formatter};
 
 // Mutation is a single mutation to a table.
-#[revisioned(revision = 2)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[non_exhaustive]
 pub enum TableMutation {
@@ -28,6 +28,9 @@ pub enum TableMutation {
 	/// Example, ("mytb:tobie", {{"note": "surreal"}}, [{"op": "add", "path": "/note", "value": "surreal"}], false)
 	/// Means that we have already applied the add "/note" operation to achieve the recorded result
 	SetWithDiff(Thing, Value, Vec<Operation>),
+	#[revision(start = 3)]
+	/// Includes the ID, and the original value of the record before it was deleted
+	DelWithOriginal(Thing, Value),
 }
 
 impl From<DefineTableStatement> for Value {
@@ -112,6 +115,10 @@ impl TableMutation {
 				h.insert("delete".to_string(), Value::Object(o));
 				h
 			}
+			TableMutation::DelWithOriginal(_thing, original) => {
+				h.insert("delete".to_string(), original);
+				h
+			}
 			TableMutation::Def(t) => {
 				h.insert("define_table".to_string(), Value::from(t));
 				h
@@ -151,6 +158,7 @@ impl Display for TableMutation {
 			TableMutation::Set(id, v) => write!(f, "SET {} {}", id, v),
 			TableMutation::SetWithDiff(id, _previous, v) => write!(f, "SET {} {:?}", id, v),
 			TableMutation::Del(id) => write!(f, "DEL {}", id),
+			TableMutation::DelWithOriginal(id, _original) => write!(f, "DEL {}", id),
 			TableMutation::Def(t) => write!(f, "{}", t),
 		}
 	}
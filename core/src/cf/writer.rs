@@ -27,7 +27,7 @@ pub struct Buffer {
 	pub b: HashMap<ChangeKey, TableMutations>,
 }
 
-#[derive(Hash, Eq, PartialEq, Debug)]
+#[derive(Clone, Hash, Eq, PartialEq, Debug)]
 #[non_exhaustive]
 pub struct ChangeKey {
 	pub ns: String,
@@ -64,6 +64,22 @@ impl Writer {
 		}
 	}
 
+	/// The number of buffered mutations of each table, for rolling back to later
+	pub(crate) fn savepoint(&self) -> HashMap<ChangeKey, usize> {
+		self.buf.b.iter().map(|(k, v)| (k.clone(), v.1.len())).collect()
+	}
+
+	/// Discard the mutations which were buffered after a savepoint
+	pub(crate) fn rollback(&mut self, sp: &HashMap<ChangeKey, usize>) {
+		self.buf.b.retain(|k, v| match sp.get(k) {
+			Some(len) => {
+				v.1.truncate(*len);
+				true
+			}
+			None => false,
+		});
+	}
+
 	#[allow(clippy::too_many_arguments)]
 	pub(crate) fn record_cf_change(
 		&mut self,
@@ -99,6 +115,13 @@ impl Writer {
 					false => TableMutation::Set(id, current.into_owned()),
 				},
 			);
+		} else if store_difference && previous.is_some() {
+			self.buf.push(
+				ns.to_string(),
+				db.to_string(),
+				tb.to_string(),
+				TableMutation::DelWithOriginal(id, previous.into_owned()),
+			);
 		} else {
 			self.buf.push(ns.to_string(), db.to_string(), tb.to_string(), TableMutation::Del(id));
 		}
@@ -35,6 +35,110 @@ pub const SERVER_NAME: &str = "SurrealDB";
 /// Datastore processor batch size for scan operations
 pub const PROCESSOR_BATCH_SIZE: u32 = 50;
 
+/// The number of keys which are fetched at once when internally scanning or deleting a range of keys
+pub const NORMAL_FETCH_SIZE: u32 = 1000;
+
+/// The maximum number of expired records which are deleted from each table on each tick
+pub static EXPIRY_BATCH_SIZE: Lazy<u32> = lazy_env_parse!("SURREAL_EXPIRY_BATCH_SIZE", u32, 1000);
+
+/// The maximum number of stale change feed entries which are deleted from each database on each tick
+pub static CHANGEFEED_GC_BATCH_SIZE: Lazy<u32> =
+	lazy_env_parse!("SURREAL_CHANGEFEED_GC_BATCH_SIZE", u32, 1000);
+
+/// The number of records which are converted in each transaction when backfilling an altered field
+pub static BACKFILL_BATCH_SIZE: Lazy<u32> =
+	lazy_env_parse!("SURREAL_BACKFILL_BATCH_SIZE", u32, 1000);
+
+/// The maximum number of queued record enrichments which are processed each time the node ticks
+pub static ENRICHMENT_BATCH_SIZE: Lazy<u32> =
+	lazy_env_parse!("SURREAL_ENRICHMENT_BATCH_SIZE", u32, 1000);
+
+/// The number of record enrichments which are computed concurrently
+pub static ENRICHMENT_WORKERS: Lazy<usize> =
+	lazy_env_parse!("SURREAL_ENRICHMENT_WORKERS", usize, 4);
+
+/// The maximum number of records with stale computed fields which are recomputed from each table on each tick
+pub static COMPUTED_BATCH_SIZE: Lazy<u32> =
+	lazy_env_parse!("SURREAL_COMPUTED_BATCH_SIZE", u32, 1000);
+
+/// The number of times a failed record enrichment is retried, unless the enrichment specifies otherwise
+pub static ENRICHMENT_RETRIES: Lazy<u32> = lazy_env_parse!("SURREAL_ENRICHMENT_RETRIES", u32, 3);
+
+/// The number of seconds for which the results of scheduled data quality checks are kept
+pub static CHECK_RESULT_RETENTION: Lazy<u64> =
+	lazy_env_parse!("SURREAL_CHECK_RESULT_RETENTION", u64, 7 * 24 * 60 * 60);
+
+/// The number of keys which are migrated in each transaction when upgrading the storage format
+pub static MIGRATION_BATCH_SIZE: Lazy<u32> =
+	lazy_env_parse!("SURREAL_MIGRATION_BATCH_SIZE", u32, 1000);
+
+/// The number of bytes of encoded query results which are buffered before being written to an outfile
+pub const OUTFILE_BUFFER_SIZE: usize = 1024 * 1024;
+
+/// The comma-separated object storage schemes which `INTO OUTFILE` may write to, where `file` must be listed to allow writing to the local filesystem
+pub static OUTFILE_SCHEMES: Lazy<String> =
+	lazy_env_parse!("SURREAL_OUTFILE_SCHEMES", String, String::from("s3"));
+
+/// The number of records which are sent together when the results of a statement are streamed
+pub const STREAM_BATCH_SIZE: usize = 1000;
+
+/// The disk usage in bytes of an embedded datastore above which a warning is logged, or 0 for no limit
+pub static STORAGE_SOFT_LIMIT: Lazy<u64> = lazy_env_parse!("SURREAL_STORAGE_SOFT_LIMIT", u64, 0);
+
+/// The disk usage in bytes of an embedded datastore above which writes are rejected, or 0 for no limit
+pub static STORAGE_HARD_LIMIT: Lazy<u64> = lazy_env_parse!("SURREAL_STORAGE_HARD_LIMIT", u64, 0);
+
+/// How often the disk usage of an embedded datastore is measured
+pub const STORAGE_USAGE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Whether values are compressed before they are sent to the TiKV or FoundationDB storage engines
+pub static KV_COMPRESSION: Lazy<bool> = lazy_env_parse!("SURREAL_KV_COMPRESSION", bool, false);
+
+/// Whether values stored in the TiKV or FoundationDB storage engines include a checksum which is verified when read
+pub static KV_CHECKSUMS: Lazy<bool> = lazy_env_parse!("SURREAL_KV_CHECKSUMS", bool, false);
+
+/// The size in bytes above which values stored in the FoundationDB storage engine are split across multiple keys, or 0 to never split values
+pub static KV_CHUNK_SIZE: Lazy<usize> = lazy_env_parse!("SURREAL_KV_CHUNK_SIZE", usize, 0);
+
+/// The maximum size in bytes of a stored record, or 0 for no limit
+pub static MAX_RECORD_SIZE: Lazy<usize> = lazy_env_parse!("SURREAL_MAX_RECORD_SIZE", usize, 0);
+
+/// The number of records which are read in each transaction when exporting a database
+pub static EXPORT_BATCH_SIZE: Lazy<u32> = lazy_env_parse!("SURREAL_EXPORT_BATCH_SIZE", u32, 1000);
+
+/// The maximum number of records per second which background work can write to each table, or 0 for no limit
+pub static BACKGROUND_WRITE_RATE: Lazy<u64> =
+	lazy_env_parse!("SURREAL_BACKGROUND_WRITE_RATE", u64, 0);
+
+/// The maximum number of client queries which can execute at once on this node, or 0 for no limit
+pub static MAX_CONCURRENT_QUERIES: Lazy<usize> =
+	lazy_env_parse!("SURREAL_MAX_CONCURRENT_QUERIES", usize, 0);
+
+/// The number of times an update which only appends to arrays or increments numbers is retried after a conflict
+pub static MAX_MERGE_RETRIES: Lazy<u32> = lazy_env_parse!("SURREAL_MAX_MERGE_RETRIES", u32, 10);
+
+/// The clock difference in milliseconds, between a node and the datastore or another node, above which a warning is logged
+pub static MAX_CLOCK_SKEW: Lazy<u64> = lazy_env_parse!("SURREAL_MAX_CLOCK_SKEW", u64, 1000);
+
+/// The minimum number of characters in a system user password
+pub static PASSWORD_MIN_LENGTH: Lazy<usize> =
+	lazy_env_parse!("SURREAL_PASSWORD_MIN_LENGTH", usize, 0);
+
+/// Whether system user passwords must contain lowercase, uppercase, numeric, and symbol characters
+pub static PASSWORD_COMPLEXITY: Lazy<bool> =
+	lazy_env_parse!("SURREAL_PASSWORD_COMPLEXITY", bool, false);
+
+/// The algorithm used to hash system user passwords, either `argon2` or `bcrypt`
+pub static PASSWORD_HASH_ALGORITHM: Lazy<String> =
+	lazy_env_parse!("SURREAL_PASSWORD_HASH_ALGORITHM", String, String::from("argon2"));
+
+/// The cost used when hashing system user passwords, or 0 to use the default cost of the algorithm
+pub static PASSWORD_HASH_COST: Lazy<u32> = lazy_env_parse!("SURREAL_PASSWORD_HASH_COST", u32, 0);
+
+/// The number of days after which a system user password must be changed, or 0 to never expire
+pub static PASSWORD_EXPIRY_DAYS: Lazy<u32> =
+	lazy_env_parse!("SURREAL_PASSWORD_EXPIRY_DAYS", u32, 0);
+
 /// Forward all signup/signin query errors to a client trying authenticate to a scope. Do not use in production.
 pub static INSECURE_FORWARD_SCOPE_ERRORS: Lazy<bool> =
 	lazy_env_parse!("SURREAL_INSECURE_FORWARD_SCOPE_ERRORS", bool, false);
@@ -51,3 +155,34 @@ pub static INSECURE_FORWARD_SCOPE_ERRORS: Lazy<bool> =
 /// If the environment variable is not present or cannot be parsed, a default value of 50,000 is used.
 pub static EXTERNAL_SORTING_BUFFER_LIMIT: Lazy<usize> =
 	lazy_env_parse!("SURREAL_EXTERNAL_SORTING_BUFFER_LIMIT", usize, 50_000);
+
+/// The number of statements which are executed together when streaming an import (defaults to 1000)
+pub static IMPORT_BATCH_SIZE: Lazy<usize> =
+	lazy_env_parse!("SURREAL_IMPORT_BATCH_SIZE", usize, 1000);
+
+/// The maximum number of results of `SELECT ... CACHE` statements which are kept in memory (defaults to 1000)
+pub static SELECT_CACHE_SIZE: Lazy<usize> =
+	lazy_env_parse!("SURREAL_SELECT_CACHE_SIZE", usize, 1000);
+
+/// The size of the chunks which the content of a blob is split into when stored in the datastore (defaults to 64 KiB)
+pub static BLOB_CHUNK_SIZE: Lazy<usize> =
+	lazy_env_parse!("SURREAL_BLOB_CHUNK_SIZE", usize, 64 * 1024);
+
+/// Whether the content of blobs is stored in the object store, instead of in the datastore
+pub static BLOB_OBJECT_STORE: Lazy<bool> =
+	lazy_env_parse!("SURREAL_BLOB_OBJECT_STORE", bool, false);
+
+/// The interval in seconds between the garbage collection of unreferenced blobs (defaults to 1 hour)
+pub static BLOB_GC_INTERVAL: Lazy<u64> = lazy_env_parse!("SURREAL_BLOB_GC_INTERVAL", u64, 3600);
+
+/// How many seconds a blob is kept after it was stored, before it can be collected if no record references it (defaults to 1 hour)
+pub static BLOB_GC_GRACE_PERIOD: Lazy<u64> =
+	lazy_env_parse!("SURREAL_BLOB_GC_GRACE_PERIOD", u64, 3600);
+
+/// The number of records which an `INSERT ... SELECT` statement selects and inserts at a time (defaults to 1000)
+pub static INSERT_SELECT_BATCH_SIZE: Lazy<u32> =
+	lazy_env_parse!("SURREAL_INSERT_SELECT_BATCH_SIZE", u32, 1000);
+
+/// The maximum number of keys of each index which are read when preloading a database at startup (defaults to 100,000)
+pub static PRELOAD_INDEX_LIMIT: Lazy<u32> =
+	lazy_env_parse!("SURREAL_PRELOAD_INDEX_LIMIT", u32, 100_000);
@@ -1,15 +1,17 @@
 use crate::ctx::canceller::Canceller;
 use crate::ctx::reason::Reason;
 use crate::dbs::capabilities::FuncTarget;
-#[cfg(feature = "http")]
+#[cfg(any(feature = "http", not(target_arch = "wasm32")))]
 use crate::dbs::capabilities::NetTarget;
-use crate::dbs::{Capabilities, Notification};
+use crate::dbs::{Capabilities, Entropy, Notification};
 use crate::err::Error;
 use crate::idx::planner::executor::QueryExecutor;
 use crate::idx::planner::{IterationStage, QueryPlanner};
 use crate::idx::trees::store::IndexStores;
 use crate::sql::value::Value;
+use crate::sql::Datetime;
 use channel::Sender;
+use rand::RngCore;
 use std::borrow::Cow;
 use std::collections::HashMap;
 #[cfg(any(
@@ -36,7 +38,7 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 use trice::Instant;
-#[cfg(feature = "http")]
+#[cfg(any(feature = "http", not(target_arch = "wasm32")))]
 use url::Url;
 
 impl<'a> From<Value> for Cow<'a, Value> {
@@ -72,6 +74,8 @@ pub struct Context<'a> {
 	index_stores: IndexStores,
 	// Capabilities
 	capabilities: Arc<Capabilities>,
+	// The captured or replayed source of randomness and time
+	entropy: Option<Arc<Entropy>>,
 	#[cfg(any(
 		feature = "kv-surrealkv",
 		feature = "kv-file",
@@ -145,6 +149,7 @@ impl<'a> Context<'a> {
 			query_executor: None,
 			iteration_stage: None,
 			capabilities: Arc::new(capabilities),
+			entropy: None,
 			index_stores,
 			#[cfg(any(
 				feature = "kv-surrealkv",
@@ -182,6 +187,7 @@ impl<'a> Context<'a> {
 			query_executor: None,
 			iteration_stage: None,
 			capabilities: Arc::new(Capabilities::default()),
+			entropy: None,
 			index_stores: IndexStores::default(),
 			#[cfg(any(
 				feature = "kv-surrealkv",
@@ -216,6 +222,7 @@ impl<'a> Context<'a> {
 			query_executor: parent.query_executor.clone(),
 			iteration_stage: parent.iteration_stage.clone(),
 			capabilities: parent.capabilities.clone(),
+			entropy: parent.entropy.clone(),
 			index_stores: parent.index_stores.clone(),
 			#[cfg(any(
 				feature = "kv-surrealkv",
@@ -402,6 +409,33 @@ impl<'a> Context<'a> {
 		)
 	}
 
+	//
+	// Entropy
+	//
+
+	/// Set the source of randomness and time for this context
+	pub(crate) fn add_entropy(&mut self, entropy: Arc<Entropy>) {
+		self.entropy = Some(entropy);
+	}
+
+	/// Run a function with the random number generator for this context, which
+	/// is seeded when the query is being captured or replayed
+	pub(crate) fn rng<T>(&self, f: impl FnOnce(&mut dyn RngCore) -> T) -> T {
+		match &self.entropy {
+			Some(v) => v.rng(f),
+			None => f(&mut rand::thread_rng()),
+		}
+	}
+
+	/// Get the current time, which is recorded when the query
+	/// is being captured, and repeated when it is being replayed
+	pub(crate) fn now(&self) -> Datetime {
+		match &self.entropy {
+			Some(v) => v.now(),
+			None => Datetime::default(),
+		}
+	}
+
 	//
 	// Capabilities
 	//
@@ -440,7 +474,7 @@ impl<'a> Context<'a> {
 	}
 
 	/// Check if a network target is allowed
-	#[cfg(feature = "http")]
+	#[cfg(any(feature = "http", not(target_arch = "wasm32")))]
 	pub fn check_allowed_net(&self, target: &Url) -> Result<(), Error> {
 		match target.host() {
 			Some(host)
@@ -0,0 +1,261 @@
+//! Deterministic capture and replay of queries.
+//!
+//! When a capture is enabled on a datastore, every query which is executed by a
+//! client is recorded, together with its session, its parameters, the seed for
+//! the random number generator which was used for the query, and every reading
+//! of the clock which was taken while running the query. Functions such as
+//! `rand()`, `time::now()`, and generated record ids use this entropy, so that
+//! replaying the captured queries against a fresh datastore reproduces the same
+//! records, and the same results, as the original execution.
+use crate::err::Error;
+use crate::sql::{Datetime, Object, Value};
+use crate::syn;
+use chrono::Utc;
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use super::{Response, Session, Variables};
+
+/// The file extension of capture files
+const EXTENSION: &str = "jsonl";
+
+/// The source of randomness and time for a single query
+pub(crate) struct Entropy {
+	// The seed of the random number generator
+	seed: u64,
+	// The seeded random number generator
+	rng: Mutex<StdRng>,
+	// The clock readings, and the number which have been taken
+	clock: Mutex<(Vec<Datetime>, usize)>,
+}
+
+impl Entropy {
+	/// Create a new entropy source, which replays the given clock readings
+	/// before it falls back to reading, and recording, the system clock
+	pub(crate) fn new(seed: u64, readings: Vec<Datetime>) -> Self {
+		Self {
+			seed,
+			rng: Mutex::new(StdRng::seed_from_u64(seed)),
+			clock: Mutex::new((readings, 0)),
+		}
+	}
+
+	/// Run a function with the seeded random number generator
+	pub(crate) fn rng<T>(&self, f: impl FnOnce(&mut dyn RngCore) -> T) -> T {
+		f(&mut *self.rng.lock().unwrap())
+	}
+
+	/// Read the clock
+	pub(crate) fn now(&self) -> Datetime {
+		let mut clock = self.clock.lock().unwrap();
+		let (readings, taken) = &mut *clock;
+		if *taken == readings.len() {
+			readings.push(Datetime::default());
+		}
+		*taken += 1;
+		readings[*taken - 1].clone()
+	}
+
+	/// Get the clock readings which have been taken
+	fn readings(&self) -> Vec<Datetime> {
+		let clock = self.clock.lock().unwrap();
+		clock.0[..clock.1].to_vec()
+	}
+}
+
+/// A query which has been captured, so that it can be replayed
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Captured {
+	/// The id of the session which executed the query
+	pub id: Option<String>,
+	/// The namespace which was selected for the session
+	pub ns: Option<String>,
+	/// The database which was selected for the session
+	pub db: Option<String>,
+	/// The SurrealQL text of the query
+	pub sql: String,
+	/// The parameters of the query, as a SurrealQL object
+	pub vars: Option<String>,
+	/// The seed of the random number generator
+	pub seed: u64,
+	/// The clock readings which were taken while running the query
+	pub clock: Vec<Datetime>,
+	/// The result of each statement, unless they were streamed to the client
+	pub results: Option<Vec<Result<String, String>>>,
+}
+
+impl Captured {
+	/// Record a query, and the entropy which was used to run it
+	pub(crate) fn new(sql: String, sess: &Session, vars: &Variables, entropy: &Entropy) -> Self {
+		Self {
+			id: sess.id.clone(),
+			ns: sess.ns.clone(),
+			db: sess.db.clone(),
+			sql,
+			vars: vars.as_ref().map(|v| Object::from(v.clone()).to_string()),
+			seed: entropy.seed,
+			clock: entropy.readings(),
+			results: None,
+		}
+	}
+
+	/// Record the results of the query
+	pub(crate) fn with_results(mut self, res: Result<&[Response], &Error>) -> Self {
+		self.results = match res {
+			// Streamed responses are not collected
+			Ok([]) => None,
+			Ok(v) => Some(Self::outcomes(v)),
+			Err(e) => Some(vec![Err(e.to_string())]),
+		};
+		self
+	}
+
+	/// Format the result of each statement, as they are recorded in a capture
+	pub fn outcomes(res: &[Response]) -> Vec<Result<String, String>> {
+		res.iter()
+			.map(|r| match &r.result {
+				Ok(v) => Ok(v.to_string()),
+				Err(e) => Err(e.to_string()),
+			})
+			.collect()
+	}
+
+	/// The session with which the query is replayed
+	pub(crate) fn session(&self) -> Session {
+		Session {
+			id: self.id.clone(),
+			ns: self.ns.clone(),
+			db: self.db.clone(),
+			..Session::owner()
+		}
+	}
+
+	/// The parameters with which the query is replayed
+	pub(crate) fn variables(&self) -> Result<Variables, Error> {
+		match &self.vars {
+			Some(v) => match syn::value(v)? {
+				Value::Object(v) => Ok(Some(v.0)),
+				_ => Ok(Some(BTreeMap::new())),
+			},
+			None => Ok(None),
+		}
+	}
+
+	/// The entropy with which the query is replayed
+	pub(crate) fn entropy(&self) -> Entropy {
+		Entropy::new(self.seed, self.clock.clone())
+	}
+}
+
+/// Records the queries which are executed on a datastore
+pub struct Capture {
+	file: Mutex<File>,
+}
+
+impl Capture {
+	/// Start a new capture file in the given directory
+	pub fn new(dir: &Path) -> Result<Self, Error> {
+		fs::create_dir_all(dir)?;
+		let name = format!("{}.{EXTENSION}", Utc::now().format("%Y%m%dT%H%M%S%.6fZ"));
+		let file = OpenOptions::new().create_new(true).append(true).open(dir.join(name))?;
+		Ok(Self {
+			file: Mutex::new(file),
+		})
+	}
+
+	/// Append a captured query to the capture file
+	pub(crate) fn write(&self, cap: &Captured) -> Result<(), Error> {
+		let mut line = serde_json::to_string(cap).map_err(|e| Error::Internal(e.to_string()))?;
+		line.push('\n');
+		self.file.lock().unwrap().write_all(line.as_bytes())?;
+		Ok(())
+	}
+
+	/// Read the captured queries from a capture file, or from all
+	/// of the capture files in a directory, in the order of capture
+	pub fn read(path: &Path) -> Result<Vec<Captured>, Error> {
+		let files = match path.is_dir() {
+			true => {
+				let mut files = fs::read_dir(path)?
+					.map(|e| e.map(|e| e.path()))
+					.collect::<Result<Vec<PathBuf>, _>>()?;
+				files.retain(|p| p.extension().is_some_and(|e| e == EXTENSION));
+				files.sort();
+				files
+			}
+			false => vec![path.to_path_buf()],
+		};
+		let mut out = Vec::new();
+		for file in files {
+			for line in BufReader::new(File::open(&file)?).lines() {
+				let line = line?;
+				if line.trim().is_empty() {
+					continue;
+				}
+				let cap = serde_json::from_str(&line).map_err(|e| {
+					Error::Internal(format!("Invalid capture in '{}': {e}", file.display()))
+				})?;
+				out.push(cap);
+			}
+		}
+		Ok(out)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::Datastore;
+	use rand::Rng;
+
+	#[test]
+	fn entropy_is_replayed() {
+		let rec = Entropy::new(7, vec![]);
+		let a: Vec<u64> = (0..3).map(|_| rec.rng(|r| r.gen())).collect();
+		let t = (rec.now(), rec.now());
+		let rep = Entropy::new(7, rec.readings());
+		let b: Vec<u64> = (0..3).map(|_| rep.rng(|r| r.gen())).collect();
+		assert_eq!(a, b);
+		assert_eq!((rep.now(), rep.now()), t);
+		// Once the readings run out the clock is read again
+		assert!(rep.now() >= t.1);
+		assert_eq!(rep.readings().len(), 3);
+	}
+
+	#[tokio::test]
+	async fn capture_and_replay() {
+		let dir = temp_dir::TempDir::new().unwrap();
+		let ds = Datastore::new("memory")
+			.await
+			.unwrap()
+			.with_capture(Some(Capture::new(dir.path()).unwrap()));
+		let ses = Session::owner().with_ns("test").with_db("test");
+		let sql = "
+			CREATE person SET at = time::now(), score = rand::int(0, 1000000), token = rand::uuid::v4(), n = $n;
+			CREATE person:ulid() SET name = rand::string(10);
+			SELECT * FROM person ORDER BY at;
+		";
+		let mut vars = BTreeMap::new();
+		vars.insert("n".to_string(), Value::from(3));
+		let res = ds.execute(sql, &ses, Some(vars)).await.unwrap();
+		// Queries which are executed by the datastore itself are not captured
+		let sess = Session::owner().with_ns("test").with_db("test");
+		ds.execute_background("CREATE other", &sess, None).await.unwrap();
+		let caps = Capture::read(dir.path()).unwrap();
+		assert_eq!(caps.len(), 1);
+		assert_eq!(caps[0].ns.as_deref(), Some("test"));
+		assert_eq!(caps[0].vars.as_deref(), Some("{ n: 3 }"));
+		assert_eq!(caps[0].results, Some(Captured::outcomes(&res)));
+		// Replaying the query creates the same records
+		let fresh = Datastore::new("memory").await.unwrap();
+		let out = fresh.replay(&caps[0]).await.unwrap();
+		assert_eq!(Captured::outcomes(&out), Captured::outcomes(&res));
+	}
+}
@@ -1,6 +1,8 @@
+use std::collections::BTreeSet;
 use std::sync::Arc;
 
 use channel::Receiver;
+use channel::Sender;
 use futures::lock::Mutex;
 use futures::StreamExt;
 use reblessive::TreeStack;
@@ -11,30 +13,49 @@ use trice::Instant;
 #[cfg(target_arch = "wasm32")]
 use wasm_bindgen_futures::spawn_local as spawn;
 
+use crate::cnf::MAX_MERGE_RETRIES;
+use crate::ctx::reason::Reason;
 use crate::ctx::Context;
 use crate::dbs::response::Response;
 use crate::dbs::Force;
 use crate::dbs::Notification;
 use crate::dbs::Options;
 use crate::dbs::QueryType;
+use crate::dbs::ReadOnly;
+use crate::dbs::Rollback;
+use crate::dbs::Streamed;
 use crate::dbs::Transaction;
 use crate::err::Error;
 use crate::iam::Action;
+use crate::iam::Level;
 use crate::iam::ResourceKind;
+use crate::kvs::is_conflict;
 use crate::kvs::lq_structs::TrackedResult;
+use crate::kvs::Merge;
+use crate::kvs::OperationCounter;
 use crate::kvs::TransactionType;
 use crate::kvs::{Datastore, LockType::*, TransactionType::*};
 use crate::sql::paths::DB;
 use crate::sql::paths::NS;
 use crate::sql::query::Query;
 use crate::sql::statement::Statement;
+use crate::sql::statements::DefineScopeStatement;
 use crate::sql::value::Value;
 use crate::sql::Base;
+use crate::sql::Consistency;
+use crate::sql::Isolation;
 
 pub(crate) struct Executor<'a> {
 	err: bool,
+	isolation_err: Option<Error>,
 	kvs: &'a Datastore,
 	txn: Option<Transaction>,
+	stream: Option<Sender<Streamed>>,
+	background: bool,
+	readonly: Option<ReadOnly>,
+	ops: Option<Arc<OperationCounter>>,
+	atomic: bool,
+	notifications: Vec<Notification>,
 }
 
 impl<'a> Executor<'a> {
@@ -43,6 +64,84 @@ impl<'a> Executor<'a> {
 			kvs,
 			txn: None,
 			err: false,
+			isolation_err: None,
+			stream: None,
+			background: false,
+			readonly: None,
+			ops: kvs.counts_operations().then(Default::default),
+			atomic: false,
+			notifications: vec![],
+		}
+	}
+
+	/// Send each response to the specified channel as soon as it
+	/// is finalised, instead of collecting all of the responses
+	pub fn with_stream(mut self, stream: Option<Sender<Streamed>>) -> Executor<'a> {
+		self.stream = stream;
+		self
+	}
+
+	/// Mark the transactions of this executor as performing background
+	/// work, so that their writes are not counted as client requests
+	pub fn with_background(mut self, background: bool) -> Executor<'a> {
+		self.background = background;
+		self
+	}
+
+	/// Execute every statement in a read-only transaction, with
+	/// the specified limits, so that no data can be modified
+	pub fn with_readonly(mut self, readonly: Option<ReadOnly>) -> Executor<'a> {
+		self.readonly = readonly;
+		self
+	}
+
+	/// Output finalised responses, either by streaming them
+	/// to the response channel, or by collecting them
+	fn output(&self, out: &mut Vec<Response>, res: impl IntoIterator<Item = Response>) {
+		match &self.stream {
+			Some(chn) => {
+				for v in res {
+					// The receiver may have been dropped, in which
+					// case the remaining responses are discarded
+					let _ = chn.try_send(Streamed::Response(v));
+				}
+			}
+			None => out.extend(res),
+		}
+	}
+
+	/// Compute a statement. When responses are streamed, the records of a SELECT
+	/// statement which runs in its own read-only transaction are also sent to the
+	/// response channel in batches, as they are produced. Records are never sent
+	/// before a transaction which can write has been committed, so the records of
+	/// any other statement are only sent with its response.
+	async fn compute(
+		&self,
+		stack: &mut TreeStack,
+		ctx: &Context<'_>,
+		opt: &Options,
+		stm: &Statement,
+		loc: bool,
+	) -> Result<Value, Error> {
+		let txn = self.txn();
+		// Only stream from transactions which have nothing to commit
+		let readonly = loc && !txn.lock().await.is_writeable();
+		match (stm, &self.stream) {
+			(Statement::Select(stm), Some(chn)) if readonly => {
+				let (rows, batches) = channel::bounded(1);
+				let (res, _) = futures::join!(
+					stack.enter(|stk| stm.compute_stream(stk, ctx, opt, &txn, rows)).finish(),
+					async {
+						while let Ok(v) = batches.recv().await {
+							if chn.send(Streamed::Rows(v)).await.is_err() {
+								break;
+							}
+						}
+					}
+				);
+				res
+			}
+			_ => stack.enter(|stk| stm.compute(stk, ctx, opt, &txn, None)).finish().await,
 		}
 	}
 
@@ -56,10 +155,21 @@ impl<'a> Executor<'a> {
 	///   - couldn't create transaction (sets err flag)
 	///   - a transaction has already begun
 	async fn begin(&mut self, write: TransactionType) -> bool {
+		// Read-only queries can never write
+		let write = match self.readonly {
+			Some(_) => Read,
+			None => write,
+		};
 		match self.txn.as_ref() {
 			Some(_) => false,
 			None => match self.kvs.transaction(write, Optimistic).await {
-				Ok(v) => {
+				Ok(mut v) => {
+					if self.background {
+						v.set_background();
+					}
+					if let Some(ops) = &self.ops {
+						v.count_operations(ops.clone());
+					}
 					self.txn = Some(Arc::new(Mutex::new(v)));
 					true
 				}
@@ -71,6 +181,27 @@ impl<'a> Executor<'a> {
 		}
 	}
 
+	/// Sets the read consistency of the current transaction. This is only a hint,
+	/// so the statement still runs if the storage engine rejects it.
+	async fn set_consistency(&mut self, consistency: Consistency) {
+		if let Some(txn) = self.txn.as_ref() {
+			if let Err(e) = txn.lock().await.set_consistency(consistency).await {
+				warn!("Unable to set the read consistency of a transaction: {e}");
+			}
+		}
+	}
+
+	/// Sets the isolation level of the current transaction. The statements of the
+	/// transaction fail if the storage engine can not guarantee the isolation level.
+	async fn set_isolation(&mut self, isolation: Isolation) {
+		if let Some(txn) = self.txn.as_ref() {
+			if let Err(e) = txn.lock().await.set_isolation(isolation).await {
+				self.err = true;
+				self.isolation_err = Some(e);
+			}
+		}
+	}
+
 	/// Commits the transaction if it is local.
 	///
 	/// # Return
@@ -96,6 +227,20 @@ impl<'a> Executor<'a> {
 										txn.consume_pending_live_queries();
 									// Track the live queries in the data store
 									self.kvs.handle_postprocessing_of_statements(&lqs).await?;
+									// Track the records of windowed live queries in the data store
+									self.kvs
+										.handle_window_events(txn.consume_window_events())
+										.await;
+									// Compact the notifications of live queries in the data store
+									self.kvs
+										.handle_compact_events(txn.consume_compact_events())
+										.await?;
+									// Count the records of counting live queries in the data store
+									self.kvs
+										.handle_count_events(txn.consume_count_events())
+										.await?;
+									// Publish the messages of hooks to any subscribers
+									self.kvs.handle_publications(txn.consume_publications());
 									Ok(())
 								}
 								Err(e) => Err(e),
@@ -135,6 +280,8 @@ impl<'a> Executor<'a> {
 			time: v.time,
 			result: Err(Error::QueryCancelled),
 			query_type: QueryType::Other,
+			operations: v.operations,
+			rollback: v.rollback,
 		}
 	}
 
@@ -152,11 +299,45 @@ impl<'a> Executor<'a> {
 					Err(e) => Err(e),
 				},
 				query_type: QueryType::Other,
+				operations: v.operations,
+				rollback: v.rollback,
 			},
 			_ => v,
 		}
 	}
 
+	/// Commit the running transaction, and output the responses
+	/// which were buffered while the transaction was running
+	async fn commit_buffered(
+		&mut self,
+		ctx: &Context<'_>,
+		recv: &Receiver<Notification>,
+		buf: &mut Vec<Response>,
+		out: &mut Vec<Response>,
+		live_queries: &mut Vec<TrackedResult>,
+	) {
+		let commit_error = self.commit(true).await.err();
+		let res: Vec<_> = buf.drain(..).map(|v| self.buf_commit(v, &commit_error)).collect();
+		self.flush(ctx, recv.clone()).await;
+		// Send the notifications of the statements of an atomic script
+		let notifications = std::mem::take(&mut self.notifications);
+		if let (false, Some(chn)) = (self.err, ctx.notifications()) {
+			spawn(async move {
+				for notification in notifications {
+					if chn.send(notification).await.is_err() {
+						break;
+					}
+				}
+			});
+		}
+		if let Some(lqs) = self.consume_committed_live_query_registrations().await {
+			live_queries.extend(lqs);
+		}
+		self.output(out, res);
+		debug_assert!(self.txn.is_none(), "commit(true) should have unset txn");
+		self.txn = None;
+	}
+
 	/// Consume the live query notifications
 	async fn clear(&self, _: &Context<'_>, mut rcv: Receiver<Notification>) {
 		spawn(async move {
@@ -207,6 +388,26 @@ impl<'a> Executor<'a> {
 		opt.set_db(Some(db.into()));
 	}
 
+	/// Fetch the definition of the scope which the session is authenticated
+	/// with, as it restricts the kinds of statement which the session may run
+	async fn scope(&self, opt: &Options) -> Result<Option<DefineScopeStatement>, Error> {
+		match opt.auth.level() {
+			Level::Scope(ns, db, sc) => {
+				let mut txn = self.kvs.transaction(Read, Optimistic).await?;
+				let res = txn.get_sc(ns, db, sc).await;
+				txn.cancel().await?;
+				match res {
+					Ok(v) => Ok(Some(v)),
+					Err(Error::ScNotFound {
+						..
+					}) => Ok(None),
+					Err(e) => Err(e),
+				}
+			}
+			_ => Ok(None),
+		}
+	}
+
 	#[instrument(level = "debug", name = "executor", skip_all)]
 	pub async fn execute(
 		&mut self,
@@ -220,7 +421,10 @@ impl<'a> Executor<'a> {
 		// Create a notification channel
 		let (send, recv) = channel::unbounded();
 		// Set the notification channel
-		let mut opt = opt.new_with_sender(send);
+		let opt = opt.new_with_sender(send.clone());
+		// Restrict the statements to the kinds which the session scope allows
+		let scope = self.scope(&opt).await?.map(Arc::new);
+		let mut opt = opt.with_scope(scope);
 		// Initialise buffer of responses
 		let mut buf: Vec<Response> = vec![];
 		// Initialise array of responses
@@ -233,17 +437,49 @@ impl<'a> Executor<'a> {
 			// Reset errors
 			if self.txn.is_none() {
 				self.err = false;
+				self.isolation_err = None;
 			}
 			// Get the statement start time
 			let now = Instant::now();
+			// Get the storage operations counted before the statement
+			let before = self.ops.as_ref().map(|v| v.snapshot());
 			// Check if this is a LIVE statement
 			let is_stm_live = matches!(stm, Statement::Live(_));
 			// Check if this is a KILL statement
 			let is_stm_kill = matches!(stm, Statement::Kill(_));
 			// Check if this is a RETURN statement
 			let is_stm_output = matches!(stm, Statement::Output(_));
+			// Check that the session scope allows this kind of statement. The
+			// statements nested within it are checked when they are computed.
+			let allowed = stm.kind().map_or(Ok(()), |kind| opt.check_statement(kind));
+			// Run the statements of an atomic script in a single transaction
+			let atomic = self.atomic
+				&& !matches!(
+					stm,
+					Statement::Option(_)
+						| Statement::Begin(_)
+						| Statement::Cancel(_)
+						| Statement::Commit(_)
+						| Statement::Use(_)
+				);
+			if atomic {
+				self.begin(Write).await;
+			}
+			// Set a savepoint, so that the changes of the statement can be
+			// undone, and buffer its notifications until they are kept
+			let savepoint = match (atomic, &self.txn) {
+				(true, Some(txn)) if !self.err => {
+					txn.lock().await.set_savepoint();
+					let (send, recv) = channel::unbounded();
+					opt.sender = Some(send);
+					Some(recv)
+				}
+				_ => None,
+			};
 			// Process a single statement
 			let res = match stm {
+				// The statement is not allowed, so is not processed
+				_ if allowed.is_err() => allowed.map(|_| Value::None),
 				// Specify runtime options
 				Statement::Option(mut stm) => {
 					// Allowed to run?
@@ -252,6 +488,21 @@ impl<'a> Executor<'a> {
 					stm.name.0.make_ascii_uppercase();
 					// Process the option
 					opt = match stm.name.0.as_str() {
+						"ATOMIC" => {
+							// Commit the statements which ran atomically
+							if self.atomic && !stm.what && self.txn.is_some() {
+								self.commit_buffered(
+									&ctx,
+									&recv,
+									&mut buf,
+									&mut out,
+									&mut live_queries,
+								)
+								.await;
+							}
+							self.atomic = stm.what;
+							opt
+						}
 						"IMPORT" => opt.with_import(stm.what),
 						"FORCE" => opt.with_force(if stm.what {
 							Force::All
@@ -264,31 +515,27 @@ impl<'a> Executor<'a> {
 					continue;
 				}
 				// Begin a new transaction
-				Statement::Begin(_) => {
+				Statement::Begin(stm) => {
 					self.begin(Write).await;
+					if let Some(isolation) = stm.isolation {
+						self.set_isolation(isolation).await;
+					}
 					continue;
 				}
 				// Cancel a running transaction
 				Statement::Cancel(_) => {
 					self.cancel(true).await;
 					self.clear(&ctx, recv.clone()).await;
+					self.notifications.clear();
 					buf = buf.into_iter().map(|v| self.buf_cancel(v)).collect();
-					out.append(&mut buf);
+					self.output(&mut out, buf.drain(..));
 					debug_assert!(self.txn.is_none(), "cancel(true) should have unset txn");
 					self.txn = None;
 					continue;
 				}
 				// Commit a running transaction
 				Statement::Commit(_) => {
-					let commit_error = self.commit(true).await.err();
-					buf = buf.into_iter().map(|v| self.buf_commit(v, &commit_error)).collect();
-					self.flush(&ctx, recv.clone()).await;
-					if let Some(lqs) = self.consume_committed_live_query_registrations().await {
-						live_queries.extend(lqs);
-					}
-					out.append(&mut buf);
-					debug_assert!(self.txn.is_none(), "commit(true) should have unset txn");
-					self.txn = None;
+					self.commit_buffered(&ctx, &recv, &mut buf, &mut out, &mut live_queries).await;
 					continue;
 				}
 				// Switch to a different NS or DB
@@ -361,14 +608,13 @@ impl<'a> Executor<'a> {
 						}
 					}
 				}
-				// Process all other normal statements
-				_ => match self.err {
-					// This transaction has failed
-					true => Err(Error::QueryNotExecuted),
-					// Compute the statement normally
+				// Run a statement in a transaction which is always cancelled
+				Statement::DryRun(stm) => match self.txn.is_some() {
+					// A dry run can not be undone within a larger transaction
+					true => Err(Error::DryRunInTransaction),
 					false => {
 						// Create a transaction
-						let loc = self.begin(stm.writeable().into()).await;
+						let loc = self.begin(Write).await;
 						// Check the transaction
 						match self.err {
 							// We failed to create a transaction
@@ -377,30 +623,9 @@ impl<'a> Executor<'a> {
 							false => {
 								let mut ctx = Context::new(&ctx);
 								// Process the statement
-								let res = match stm.timeout() {
-									// There is a timeout clause
-									Some(timeout) => {
-										// Set statement timeout or propagate the error
-										if let Err(err) = ctx.add_timeout(timeout) {
-											Err(err)
-										} else {
-											let txn = self.txn();
-											// Process the statement
-											let res = stack
-												.enter(|stk| {
-													stm.compute(stk, &ctx, &opt, &txn, None)
-												})
-												.finish()
-												.await;
-											// Catch statement timeout
-											match ctx.is_timedout() {
-												true => Err(Error::QueryTimedout),
-												false => res,
-											}
-										}
-									}
-									// There is no timeout clause
-									None => {
+								let res = match stm.timeout().map(|v| ctx.add_timeout(v)) {
+									Some(Err(err)) => Err(err),
+									_ => {
 										let txn = self.txn();
 										stack
 											.enter(|stk| stm.compute(stk, &ctx, &opt, &txn, None))
@@ -408,50 +633,181 @@ impl<'a> Executor<'a> {
 											.await
 									}
 								};
-								// Catch global timeout
+								// Catch statement timeout
 								let res = match ctx.is_timedout() {
 									true => Err(Error::QueryTimedout),
 									false => res,
 								};
-								// Finalise transaction and return the result.
-								if res.is_ok() && stm.writeable() {
-									if let Err(e) = self.commit(loc).await {
+								// Discard the changes and any live query notifications
+								self.cancel(loc).await;
+								self.clear(&ctx, recv.clone()).await;
+								res
+							}
+						}
+					}
+				},
+				// Process all other normal statements
+				_ => match self.err {
+					// This transaction has failed
+					true => Err(match &self.isolation_err {
+						Some(e) => Error::QueryNotExecutedDetail {
+							message: e.to_string(),
+						},
+						None => Error::QueryNotExecuted,
+					}),
+					// Compute the statement normally
+					false => {
+						// Updates which can be retried after a conflict, if
+						// they are not part of a larger transaction
+						let merges = stm.merges().filter(|_| self.txn.is_none());
+						// Updates which only change CRDT fields can also be
+						// retried, if they are not part of a larger transaction
+						let crdt = matches!(stm, Statement::Update(_)) && self.txn.is_none();
+						let mut retries = 0;
+						loop {
+							// Create a transaction
+							let loc = self.begin(stm.writeable().into()).await;
+							// Apply the read consistency of the statement to its own transaction
+							if let (true, Some(c)) = (loc, stm.consistency()) {
+								self.set_consistency(c).await;
+							}
+							// Check the transaction
+							break match self.err {
+								// We failed to create a transaction
+								true => Err(Error::TxFailure),
+								// The transaction began successfully
+								false => {
+									let mut ctx = Context::new(&ctx);
+									// Process the statement
+									let res = match stm.timeout() {
+										// There is a timeout clause
+										Some(timeout) => {
+											// Set statement timeout or propagate the error
+											if let Err(err) = ctx.add_timeout(timeout) {
+												Err(err)
+											} else {
+												// Process the statement
+												let res = self
+													.compute(&mut stack, &ctx, &opt, &stm, loc)
+													.await;
+												// Catch statement timeout
+												match ctx.is_timedout() {
+													true => Err(Error::QueryTimedout),
+													false => res,
+												}
+											}
+										}
+										// There is no timeout clause
+										None => {
+											self.compute(&mut stack, &ctx, &opt, &stm, loc).await
+										}
+									};
+									// Catch global timeout or cancellation
+									let res = match ctx.done() {
+										Some(Reason::Timedout) => Err(Error::QueryTimedout),
+										Some(Reason::Canceled) => Err(Error::QueryCancelled),
+										None => res,
+									};
+									// Catch results which exceed the row limit
+									let res = match &self.readonly {
+										Some(v) => v.check(res),
+										None => res,
+									};
+									// Finalise transaction and return the result.
+									if res.is_ok() && stm.writeable() {
+										let merges = match merges.clone() {
+											None if crdt
+												&& self.txn().lock().await.is_crdt_merge() =>
+											{
+												Some(BTreeSet::from([Merge::Crdt]))
+											}
+											ops => ops,
+										};
+										if let Err(e) = self.commit(loc).await {
+											// Clear live query notification details
+											self.clear(&ctx, recv.clone()).await;
+											// Retry updates which only append to arrays or
+											// increment numbers, re-reading the records, and
+											// applying the update again
+											if let Some(ops) = &merges {
+												if retries < *MAX_MERGE_RETRIES && is_conflict(&e) {
+													self.kvs.conflicts().record_retry(ops);
+													self.err = false;
+													retries += 1;
+													continue;
+												}
+												self.kvs.conflicts().record_merge(ops, false);
+											}
+											// The commit failed
+											Err(Error::QueryNotExecutedDetail {
+												message: e.to_string(),
+											})
+										} else {
+											// Record updates which committed after a retry
+											if let Some(ops) =
+												merges.as_ref().filter(|_| retries > 0)
+											{
+												self.kvs.conflicts().record_merge(ops, true);
+											}
+											// Flush the live query change notifications
+											self.flush(&ctx, recv.clone()).await;
+											if let Some(lqs) = self
+												.consume_committed_live_query_registrations()
+												.await
+											{
+												live_queries.extend(lqs);
+											}
+											res
+										}
+									} else {
+										self.cancel(loc).await;
 										// Clear live query notification details
 										self.clear(&ctx, recv.clone()).await;
-										// The commit failed
-										Err(Error::QueryNotExecutedDetail {
-											message: e.to_string(),
-										})
-									} else {
-										// Flush the live query change notifications
-										self.flush(&ctx, recv.clone()).await;
-										if let Some(lqs) =
-											self.consume_committed_live_query_registrations().await
-										{
-											live_queries.extend(lqs);
-										}
+										// Return an error
 										res
 									}
-								} else {
-									self.cancel(loc).await;
-									// Clear live query notification details
-									self.clear(&ctx, recv.clone()).await;
-									// Return an error
-									res
 								}
-							}
+							};
 						}
 					}
 				},
 			};
+			// Undo the changes of a statement which failed in an atomic script
+			let rollback = match (savepoint, self.txn.clone()) {
+				(Some(rcv), Some(txn)) => {
+					opt.sender = Some(send.clone());
+					let mut txn = txn.lock().await;
+					match &res {
+						Ok(_) => {
+							txn.release_savepoint();
+							while let Ok(notification) = rcv.try_recv() {
+								self.notifications.push(notification);
+							}
+							None
+						}
+						Err(_) => match txn.rollback_to_savepoint().await {
+							Ok(keys) => Some(Rollback {
+								keys,
+							}),
+							Err(e) => {
+								warn!("Unable to roll back the changes of a failed statement: {e}");
+								None
+							}
+						},
+					}
+				}
+				_ => None,
+			};
 			// Produce the response
 			let res = Response {
 				// Get the statement end time
 				time: now.elapsed(),
 				// TODO: Replace with `inspect_err` once stable.
 				result: res.map_err(|e| {
-					// Mark the error.
-					self.err = true;
+					// Mark the error, unless the statement was rolled back
+					if rollback.is_none() {
+						self.err = true;
+					}
 					e
 				}),
 				query_type: match (is_stm_live, is_stm_kill) {
@@ -469,6 +825,10 @@ impl<'a> Executor<'a> {
 					}
 					_ => QueryType::Other,
 				},
+				// Get the storage operations of the statement
+				operations: before.zip(self.ops.as_ref()).map(|(b, v)| v.snapshot() - b),
+				// Get the changes which were rolled back
+				rollback,
 			};
 			// Output the response
 			if self.txn.is_some() {
@@ -477,9 +837,13 @@ impl<'a> Executor<'a> {
 				}
 				buf.push(res);
 			} else {
-				out.push(res)
+				self.output(&mut out, [res])
 			}
 		}
+		// Commit the statements which ran atomically
+		if self.atomic && self.txn.is_some() {
+			self.commit_buffered(&ctx, &recv, &mut buf, &mut out, &mut live_queries).await;
+		}
 		// Return responses
 		Ok((out, live_queries))
 	}
@@ -487,7 +851,8 @@ impl<'a> Executor<'a> {
 
 #[cfg(test)]
 mod tests {
-	use crate::{dbs::Session, iam::Role, kvs::Datastore};
+	use crate::dbs::{Session, Streamed};
+	use crate::{err::Error, iam::Role, kvs::Datastore, sql::Value, syn::Parse};
 
 	#[tokio::test]
 	async fn check_execute_option_permissions() {
@@ -567,6 +932,165 @@ mod tests {
 		}
 	}
 
+	#[tokio::test]
+	async fn check_execute_stream() {
+		use futures::StreamExt;
+		let ds = Datastore::new("memory").await.unwrap();
+		let ses = Session::owner().with_ns("NS").with_db("DB");
+		let sql = "
+			CREATE person:one;
+			BEGIN;
+			CREATE person:two;
+			CREATE person:three;
+			COMMIT;
+			SELECT * FROM person;
+		";
+		let res: Vec<_> = ds.execute_stream(sql, &ses, None).unwrap().collect().await;
+		assert_eq!(res.len(), 5);
+		for res in res[..3].iter() {
+			assert!(matches!(res, Ok(Streamed::Response(v)) if v.result.is_ok()), "{res:?}");
+		}
+		// The records of the SELECT statement are streamed before its response
+		let Ok(Streamed::Rows(rows)) = &res[3] else {
+			panic!("{:?}", res[3]);
+		};
+		assert_eq!(
+			Value::from(rows.clone()).to_string(),
+			"[{ id: person:one }, { id: person:three }, { id: person:two }]"
+		);
+		let Ok(Streamed::Response(last)) = &res[4] else {
+			panic!("{:?}", res[4]);
+		};
+		assert_eq!(last.result.as_ref().unwrap().to_string(), "[]");
+		// Errors which prevent the query from running are streamed
+		let ses = Session::default().with_ns("NS").with_db("DB");
+		let ds = ds.with_auth_enabled(true);
+		let res: Vec<_> = ds.execute_stream("INFO FOR DB", &ses, None).unwrap().collect().await;
+		assert_eq!(res.len(), 1);
+		assert!(res[0].is_err());
+	}
+
+	#[tokio::test]
+	async fn check_execute_stream_batches() {
+		use futures::StreamExt;
+		let ds = Datastore::new("memory").await.unwrap();
+		let ses = Session::owner().with_ns("NS").with_db("DB");
+		ds.execute("CREATE |person:1..2500|", &ses, None).await.unwrap();
+		let sql = "
+			SELECT * FROM person START 100;
+			SELECT * FROM person ORDER BY id DESC LIMIT 1;
+			SELECT * FROM person START 2499 FOR UPDATE;
+			BEGIN;
+			SELECT * FROM person LIMIT 1;
+			COMMIT;
+		";
+		let res: Vec<_> = ds.execute_stream(sql, &ses, None).unwrap().collect().await;
+		let res: Vec<_> = res
+			.into_iter()
+			.map(|v| match v.unwrap() {
+				Streamed::Rows(v) => format!("rows {}", v.len()),
+				Streamed::Response(v) => format!("response {}", v.result.unwrap()),
+			})
+			.collect();
+		assert_eq!(
+			res,
+			[
+				"rows 900",
+				"rows 1000",
+				"rows 500",
+				"response []",
+				"response [{ id: person:2500 }]",
+				"response [{ id: person:2500 }]",
+				"response [{ id: person:1 }]",
+			]
+		);
+	}
+
+	#[tokio::test]
+	async fn check_execute_scope_statements() {
+		let ds = Datastore::new("memory").await.unwrap().with_auth_enabled(true);
+		let ses = Session::owner().with_ns("NS").with_db("DB");
+		let sql = "
+			DEFINE SCOPE reader ALLOW SELECT, CREATE;
+			DEFINE SCOPE writer DENY INFO, DEFINE;
+			DEFINE TABLE post PERMISSIONS FULL;
+		";
+		for res in ds.execute(sql, &ses, None).await.unwrap() {
+			res.result.unwrap();
+		}
+		let rid = Value::from("user:one");
+		// Only the allowed kinds of statement can be run
+		let ses = Session::for_scope("NS", "DB", "reader", rid.clone());
+		let sql = "CREATE post:one; SELECT * FROM post; UPDATE post; INFO FOR DB; 1 + 1";
+		let res = ds.execute(sql, &ses, None).await.unwrap();
+		assert!(res[0].result.is_ok());
+		assert!(res[1].result.is_ok());
+		let err = res[2].result.as_ref().unwrap_err().to_string();
+		assert_eq!(err, "Sessions of the scope 'reader' are not allowed to run UPDATE statements");
+		assert!(res[3].result.is_err());
+		assert_eq!(res[4].result.as_ref().unwrap(), &Value::from(2));
+		// The denied kinds of statement can not be run
+		let ses = Session::for_scope("NS", "DB", "writer", rid);
+		let sql = "UPDATE post:one SET n = 1; DEFINE TABLE other; INFO FOR DB";
+		let res = ds.execute(sql, &ses, None).await.unwrap();
+		assert!(res[0].result.is_ok());
+		assert!(matches!(res[1].result, Err(Error::StatementNotAllowed { .. })));
+		assert!(matches!(res[2].result, Err(Error::StatementNotAllowed { .. })));
+	}
+
+	#[tokio::test]
+	async fn check_execute_scope_nested_statements() {
+		let ds = Datastore::new("memory").await.unwrap().with_auth_enabled(true);
+		let ses = Session::owner().with_ns("NS").with_db("DB");
+		let sql = "
+			DEFINE SCOPE reader ALLOW SELECT, CREATE;
+			DEFINE SCOPE writer DENY DEFINE;
+			DEFINE TABLE post PERMISSIONS FULL;
+			DEFINE EVENT count ON post WHEN $event = 'CREATE' THEN (UPDATE counter:one SET n += 1);
+			DEFINE FUNCTION fn::touch() { UPDATE post:one SET n = 1; };
+			CREATE post:one;
+		";
+		for res in ds.execute(sql, &ses, None).await.unwrap() {
+			res.result.unwrap();
+		}
+		let rid = Value::from("user:one");
+		// The statements nested within an allowed statement are checked
+		let ses = Session::for_scope("NS", "DB", "reader", rid.clone());
+		let sql = "
+			SELECT * FROM (UPDATE post:one SET n = 1);
+			CREATE post:two SET n = (DELETE post:one);
+			CREATE post:three SET n = fn::touch();
+			CREATE post:four;
+		";
+		let res = ds.execute(sql, &ses, None).await.unwrap();
+		assert!(matches!(res[0].result, Err(Error::StatementNotAllowed { .. })));
+		assert!(matches!(res[1].result, Err(Error::StatementNotAllowed { .. })));
+		assert!(matches!(res[2].result, Err(Error::StatementNotAllowed { .. })));
+		// The statements of events are not restricted by the scope
+		assert!(res[3].result.is_ok());
+		// The statements nested within blocks are checked
+		let ses = Session::for_scope("NS", "DB", "writer", rid);
+		let sql = "
+			IF true { DEFINE TABLE other };
+			FOR $x IN [1, 2] { DEFINE TABLE other };
+			RETURN (DEFINE TABLE other);
+			IF true { UPDATE post:one SET n = 2 };
+		";
+		let res = ds.execute(sql, &ses, None).await.unwrap();
+		assert!(matches!(res[0].result, Err(Error::StatementNotAllowed { .. })));
+		assert!(matches!(res[1].result, Err(Error::StatementNotAllowed { .. })));
+		assert!(matches!(res[2].result, Err(Error::StatementNotAllowed { .. })));
+		assert!(res[3].result.is_ok());
+		// The statements which were not allowed made no changes
+		let ses = Session::owner().with_ns("NS").with_db("DB");
+		let sql = "SELECT VALUE n FROM post:one; SELECT VALUE n FROM counter:one; INFO FOR DB";
+		let mut res = ds.execute(sql, &ses, None).await.unwrap();
+		assert_eq!(res.remove(0).result.unwrap(), Value::parse("[2]"));
+		assert_eq!(res.remove(0).result.unwrap(), Value::parse("[2]"));
+		let info = res.remove(0).result.unwrap().to_string();
+		assert!(!info.contains("other"), "{info}");
+	}
+
 	#[tokio::test]
 	async fn check_execute_timeout() {
 		// With small timeout
@@ -1,3 +1,4 @@
+use crate::cnf::STREAM_BATCH_SIZE;
 use crate::ctx::Canceller;
 use crate::ctx::Context;
 #[cfg(not(target_arch = "wasm32"))]
@@ -5,6 +6,7 @@ use crate::dbs::distinct::AsyncDistinct;
 use crate::dbs::distinct::SyncDistinct;
 use crate::dbs::plan::Plan;
 use crate::dbs::result::Results;
+use crate::dbs::store::SampleCollector;
 use crate::dbs::Statement;
 use crate::dbs::{Options, Transaction};
 use crate::doc::Document;
@@ -12,11 +14,15 @@ use crate::err::Error;
 use crate::idx::docids::DocId;
 use crate::idx::planner::executor::IteratorRef;
 use crate::idx::planner::IterationStage;
+use crate::key::thing;
+use crate::sql::datetime::Datetime;
 use crate::sql::edges::Edges;
+use crate::sql::output::Output;
 use crate::sql::range::Range;
 use crate::sql::table::Table;
 use crate::sql::thing::Thing;
 use crate::sql::value::Value;
+use channel::Sender;
 use reblessive::{tree::Stk, TreeStack};
 use std::mem;
 
@@ -24,6 +30,7 @@ use std::mem;
 pub(crate) enum Iterable {
 	Value(Value),
 	Table(Table),
+	Partition(Table, u16),
 	Thing(Thing),
 	Range(Range),
 	Edges(Edges),
@@ -31,6 +38,9 @@ pub(crate) enum Iterable {
 	Mergeable(Thing, Value),
 	Relatable(Thing, Thing, Thing),
 	Index(Table, IteratorRef),
+	EdgesIndex(Edges, IteratorRef),
+	TableVersion(Table, Datetime),
+	ThingVersion(Thing, Datetime),
 }
 
 pub(crate) struct Processed {
@@ -60,12 +70,22 @@ pub(crate) struct Iterator {
 	limit: Option<usize>,
 	// Iterator start value
 	start: Option<usize>,
+	// Iterator sample size
+	sample: Option<usize>,
 	// Iterator runtime error
 	error: Option<Error>,
 	// Iterator output results
 	results: Results,
 	// Iterator input values
 	entries: Vec<Iterable>,
+	// Iterator processed records
+	scanned: u64,
+	// Iterator matched records
+	matched: u64,
+	// Iterator stream of result batches
+	stream: Option<Sender<Vec<Value>>>,
+	// Iterator results which were streamed
+	streamed: usize,
 }
 
 impl Clone for Iterator {
@@ -74,9 +94,14 @@ impl Clone for Iterator {
 			run: self.run.clone(),
 			limit: self.limit,
 			start: self.start,
+			sample: self.sample,
 			error: None,
 			results: Results::default(),
 			entries: self.entries.clone(),
+			scanned: 0,
+			matched: 0,
+			stream: None,
+			streamed: 0,
 		}
 	}
 }
@@ -87,6 +112,12 @@ impl Iterator {
 		Self::default()
 	}
 
+	/// Sends the results in batches as they are produced, when
+	/// the statement does not need all of them to be collected
+	pub fn stream(&mut self, chn: Sender<Vec<Value>>) {
+		self.stream = Some(chn);
+	}
+
 	/// Ingests an iterable for processing
 	pub fn ingest(&mut self, val: Iterable) {
 		self.entries.push(val)
@@ -110,9 +141,9 @@ impl Iterator {
 					Statement::Create(_) => {
 						let id = match data.rid(stk, ctx, opt, txn).await? {
 							// Generate a new id from the id field
-							Some(id) => id.generate(&v, false)?,
+							Some(id) => id.generate(ctx, &v, false)?,
 							// Generate a new random table id
-							None => v.generate(),
+							None => v.generate(ctx),
 						};
 						self.ingest(Iterable::Thing(id))
 					}
@@ -125,7 +156,7 @@ impl Iterator {
 				None => match stm {
 					Statement::Create(_) => {
 						// Generate a new random table id
-						self.ingest(Iterable::Thing(v.generate()))
+						self.ingest(Iterable::Thing(v.generate(ctx)))
 					}
 					_ => {
 						// Ingest the table for scanning
@@ -296,6 +327,8 @@ impl Iterator {
 		self.setup_limit(stk, &cancel_ctx, opt, txn, stm).await?;
 		// Process the query START clause
 		self.setup_start(stk, &cancel_ctx, opt, txn, stm).await?;
+		// Process the query SAMPLE clause
+		self.setup_sample(stk, &cancel_ctx, opt, txn, stm).await?;
 		// Prepare the results with possible optimisations on groups
 		self.results = self.results.prepare(
 			#[cfg(any(
@@ -309,8 +342,38 @@ impl Iterator {
 			ctx,
 			stm,
 		)?;
+		// Only retain a random sample of the records, unless they are grouped
+		if let Some(size) = self.sample {
+			if !matches!(self.results, Results::Groups(_)) {
+				self.results = Results::Sample(SampleCollector::new(size));
+			}
+		}
+		// Only stream the results if none of them depend on the others
+		if self.stream.is_some() {
+			if stm.group().is_some()
+				|| stm.order().is_some()
+				|| stm.split().is_some()
+				|| stm.fetch().is_some()
+				|| stm.explain().is_some()
+				|| self.sample.is_some()
+				|| matches!(stm.output(), Some(Output::Count))
+			{
+				self.stream = None;
+			} else {
+				self.results = Results::Memory(Default::default());
+			}
+		}
 		// Extract the expected behaviour depending on the presence of EXPLAIN with or without FULL
 		let mut plan = Plan::new(ctx, stm, &self.entries, &self.results);
+		// Check if a SELECT statement has to scan a whole table
+		let table_scan = match self.entries.as_slice() {
+			[Iterable::Table(tb)]
+				if stm.is_select() && (stm.conds().is_some() || stm.order().is_some()) =>
+			{
+				Some(tb.0.clone())
+			}
+			_ => None,
+		};
 		if plan.do_iterate {
 			// Process prepared values
 			if let Some(qp) = ctx.get_query_planner() {
@@ -327,6 +390,20 @@ impl Iterator {
 			if let Some(e) = self.error.take() {
 				return Err(e);
 			}
+			// Send any remaining streamed results
+			self.send_batch().await?;
+			// Record the shape of the query, for suggesting indexes
+			if let Some(tb) = table_scan {
+				txn.lock().await.record_table_scan(
+					opt.ns(),
+					opt.db(),
+					&tb,
+					stm.conds(),
+					stm.order(),
+					self.scanned,
+					self.matched,
+				);
+			}
 			// Process any SPLIT clause
 			self.output_split(stk, ctx, opt, txn, stm).await?;
 			// Process any GROUP clause
@@ -334,6 +411,11 @@ impl Iterator {
 				self.results = Results::Memory(g.output(stk, ctx, opt, txn, stm).await?);
 			}
 
+			// Process any SAMPLE clause
+			if let Some(size) = self.sample {
+				self.results.sample(size)?;
+			}
+
 			// Process any ORDER clause
 			if let Some(orders) = stm.order() {
 				self.results.sort(orders);
@@ -361,6 +443,13 @@ impl Iterator {
 			}
 		}
 
+		// Output the number of records instead of the records
+		if let Some(Output::Count) = stm.output() {
+			return Ok(Value::from(map! {
+				"count".to_string() => Value::from(results.len()),
+			}));
+		}
+
 		// Output the results
 		Ok(results.into())
 	}
@@ -395,6 +484,21 @@ impl Iterator {
 		Ok(())
 	}
 
+	#[inline]
+	async fn setup_sample(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		stm: &Statement<'_>,
+	) -> Result<(), Error> {
+		if let Some(v) = stm.sample() {
+			self.sample = Some(v.process(stk, ctx, opt, txn, None).await?);
+		}
+		Ok(())
+	}
+
 	#[inline]
 	async fn output_split(
 		&mut self,
@@ -452,6 +556,16 @@ impl Iterator {
 		if let Some(fetchs) = stm.fetch() {
 			for fetch in fetchs.iter() {
 				let mut values = self.results.take()?;
+				// Read the fetched records ahead of time, so they are not requested one at a time
+				let mut targets = Vec::new();
+				for obj in &values {
+					obj.fetch_targets(fetch, &mut targets);
+				}
+				let keys = targets
+					.into_iter()
+					.map(|v| thing::new(opt.ns(), opt.db(), &v.tb, &v.id).into())
+					.collect();
+				txn.lock().await.prefetch(keys).await?;
 				// Loop over each result value
 				for obj in &mut values {
 					// Fetch the value at the path
@@ -459,6 +573,8 @@ impl Iterator {
 				}
 				self.results = values.into();
 			}
+			// Clear any records which were read ahead of time but not fetched
+			txn.lock().await.clear_prefetched();
 		}
 		Ok(())
 	}
@@ -611,6 +727,8 @@ impl Iterator {
 		stm: &Statement<'_>,
 		res: Result<Value, Error>,
 	) {
+		// Count the processed record
+		self.scanned += 1;
 		// Process the result
 		match res {
 			Err(Error::Ignore) => {
@@ -622,6 +740,7 @@ impl Iterator {
 				return;
 			}
 			Ok(v) => {
+				self.matched += 1;
 				if let Err(e) = self.results.push(stk, ctx, opt, txn, stm, v).await {
 					self.error = Some(e);
 					self.run.cancel();
@@ -629,17 +748,63 @@ impl Iterator {
 				}
 			}
 		}
+		// Stop a read-only statement once it has collected more rows than
+		// it can return, rather than once all of the rows are collected.
+		// Only the rows skipped by START are never returned, as sorting
+		// the rows needs all of them, but groups are checked at the end.
+		if let Some(limit) = opt.row_limit {
+			let rows = (self.matched as usize).saturating_sub(self.start.unwrap_or(0));
+			if rows > limit && !matches!(self.results, Results::Groups(_)) {
+				self.error = Some(Error::RowLimitExceeded {
+					limit,
+				});
+				self.run.cancel();
+				return;
+			}
+		}
+		// Send the results once a batch is ready
+		if self.stream.is_some() && self.results.len() >= STREAM_BATCH_SIZE {
+			if let Err(e) = self.send_batch().await {
+				self.error = Some(e);
+				self.run.cancel();
+				return;
+			}
+		}
 		// Check if we can exit
-		if stm.group().is_none() && stm.order().is_none() {
+		if stm.group().is_none() && stm.order().is_none() && stm.sample().is_none() {
+			let len = self.streamed + self.results.len();
 			if let Some(l) = self.limit {
 				if let Some(s) = self.start {
-					if self.results.len() == l + s {
+					if len == l + s {
 						self.run.cancel()
 					}
-				} else if self.results.len() == l {
+				} else if len == l {
 					self.run.cancel()
 				}
 			}
 		}
 	}
+
+	/// Send the collected results to the stream, if there is one,
+	/// keeping only the results within the START and LIMIT clauses
+	async fn send_batch(&mut self) -> Result<(), Error> {
+		if let Some(chn) = &self.stream {
+			let rows = self.results.take()?;
+			let base = self.streamed;
+			self.streamed += rows.len();
+			// Work out which of the results fall within the clauses
+			let start = self.start.unwrap_or(0);
+			let end = self.limit.map_or(usize::MAX, |l| start.saturating_add(l));
+			let rows: Vec<Value> = rows
+				.into_iter()
+				.skip(start.saturating_sub(base))
+				.take(end.saturating_sub(base.max(start)))
+				.collect();
+			// Stop iterating if the results are no longer received
+			if !rows.is_empty() && chn.send(rows).await.is_err() {
+				self.run.cancel();
+			}
+		}
+		Ok(())
+	}
 }
@@ -2,6 +2,7 @@
 //! In this module we essentially manage the entire lifecycle of a database request acting as the
 //! glue between the API and the response. In this module we use channels as a transport layer
 //! and executors to process the operations. This module also gives a `context` to the transaction.
+mod capture;
 mod distinct;
 mod executor;
 mod group;
@@ -10,9 +11,12 @@ mod notification;
 mod options;
 mod plan;
 mod processor;
+mod publication;
+mod readonly;
 mod response;
 mod result;
 mod session;
+mod snapshot;
 mod statement;
 mod store;
 mod transaction;
@@ -23,12 +27,17 @@ pub mod lifecycle;
 pub mod node;
 
 pub use self::capabilities::Capabilities;
+pub use self::capture::{Capture, Captured};
 pub use self::lifecycle::*;
 pub use self::notification::*;
 pub use self::options::*;
+pub use self::publication::*;
+pub use self::readonly::ReadOnly;
 pub use self::response::*;
 pub use self::session::*;
+pub use self::snapshot::SessionSnapshot;
 
+pub(crate) use self::capture::Entropy;
 pub(crate) use self::executor::*;
 pub(crate) use self::iterator::*;
 pub(crate) use self::statement::*;
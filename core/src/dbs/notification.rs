@@ -5,7 +5,7 @@ use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Debug, Display};
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 4)]
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "UPPERCASE")]
 #[non_exhaustive]
@@ -13,6 +13,15 @@ pub enum Action {
 	Create,
 	Update,
 	Delete,
+	/// The aggregated result of a LIVE query window which has closed
+	#[revision(start = 2)]
+	Window,
+	/// The LIVE query was killed, because its user is no longer allowed to select from the table
+	#[revision(start = 3)]
+	Killed,
+	/// The number of records matching a counting LIVE query changed
+	#[revision(start = 4)]
+	Count,
 }
 
 impl Display for Action {
@@ -21,6 +30,9 @@ impl Display for Action {
 			Action::Create => write!(f, "CREATE"),
 			Action::Update => write!(f, "UPDATE"),
 			Action::Delete => write!(f, "DELETE"),
+			Action::Window => write!(f, "WINDOW"),
+			Action::Killed => write!(f, "KILLED"),
+			Action::Count => write!(f, "COUNT"),
 		}
 	}
 }
@@ -31,7 +43,7 @@ impl Display for Action {
 pub struct Notification {
 	/// The id of the LIVE query to which this notification belongs
 	pub id: Uuid,
-	/// The CREATE / UPDATE / DELETE action which caused this notification
+	/// The CREATE / UPDATE / DELETE / WINDOW / KILLED action which caused this notification
 	pub action: Action,
 	/// The resulting notification content, usually the altered record content
 	pub result: Value,
@@ -4,7 +4,8 @@ use crate::dbs::Notification;
 use crate::err::Error;
 use crate::iam::{Action, Auth, ResourceKind, Role};
 use crate::sql::{
-	statements::define::DefineIndexStatement, statements::define::DefineTableStatement, Base,
+	statements::define::DefineIndexStatement, statements::define::DefineScopeStatement,
+	statements::define::DefineTableStatement, Base, StatementKind,
 };
 use channel::Sender;
 use std::sync::Arc;
@@ -41,6 +42,8 @@ pub struct Options {
 	pub strict: bool,
 	/// Should we process field queries?
 	pub import: bool,
+	/// Should we queue record enrichments?
+	pub enrich: bool,
 	/// Should we process function futures?
 	pub futures: bool,
 	/// Should we process variable field projections?
@@ -49,6 +52,12 @@ pub struct Options {
 	pub sender: Option<Sender<Notification>>,
 	/// Datastore capabilities
 	pub capabilities: Arc<Capabilities>,
+	/// The scope of the session, which restricts the kinds of statement it can run
+	pub scope: Option<Arc<DefineScopeStatement>>,
+	/// Is this a read-only query, which must have no side effects?
+	pub readonly: bool,
+	/// The maximum number of rows which each statement of a read-only query can collect
+	pub row_limit: Option<usize>,
 }
 
 #[derive(Clone, Debug)]
@@ -89,12 +98,16 @@ impl Options {
 			force: Force::None,
 			strict: false,
 			import: false,
+			enrich: true,
 			futures: false,
 			projections: false,
 			auth_enabled: true,
 			sender: None,
 			auth: Arc::new(Auth::default()),
 			capabilities: Arc::new(Capabilities::default()),
+			scope: None,
+			readonly: false,
+			row_limit: None,
 		}
 	}
 
@@ -197,6 +210,12 @@ impl Options {
 		self
 	}
 
+	/// Specify if we should queue record enrichments
+	pub fn with_enrichments(mut self, enrich: bool) -> Self {
+		self.enrich = enrich;
+		self
+	}
+
 	/// Specify if we should process futures
 	pub fn with_futures(mut self, futures: bool) -> Self {
 		self.futures = futures;
@@ -221,6 +240,25 @@ impl Options {
 		self
 	}
 
+	/// Specify the scope which restricts the kinds of statement
+	/// which can be run, with support for chaining.
+	pub fn with_scope(mut self, scope: Option<Arc<DefineScopeStatement>>) -> Self {
+		self.scope = scope;
+		self
+	}
+
+	/// Specify if this is a read-only query
+	pub fn with_readonly(mut self, readonly: bool) -> Self {
+		self.readonly = readonly;
+		self
+	}
+
+	/// Specify the maximum number of rows which each statement can collect
+	pub fn with_row_limit(mut self, row_limit: Option<usize>) -> Self {
+		self.row_limit = row_limit;
+		self
+	}
+
 	// --------------------------------------------------
 
 	/// Create a new Options object for a subquery
@@ -229,6 +267,7 @@ impl Options {
 			sender: self.sender.clone(),
 			auth: self.auth.clone(),
 			capabilities: self.capabilities.clone(),
+			scope: self.scope.clone(),
 			ns: self.ns.clone(),
 			db: self.db.clone(),
 			force: self.force.clone(),
@@ -243,6 +282,7 @@ impl Options {
 			sender: self.sender.clone(),
 			auth: self.auth.clone(),
 			capabilities: self.capabilities.clone(),
+			scope: self.scope.clone(),
 			ns: self.ns.clone(),
 			db: self.db.clone(),
 			force,
@@ -256,6 +296,7 @@ impl Options {
 			sender: self.sender.clone(),
 			auth: self.auth.clone(),
 			capabilities: self.capabilities.clone(),
+			scope: self.scope.clone(),
 			ns: self.ns.clone(),
 			db: self.db.clone(),
 			force: self.force.clone(),
@@ -270,6 +311,7 @@ impl Options {
 			sender: self.sender.clone(),
 			auth: self.auth.clone(),
 			capabilities: self.capabilities.clone(),
+			scope: self.scope.clone(),
 			ns: self.ns.clone(),
 			db: self.db.clone(),
 			force: self.force.clone(),
@@ -284,6 +326,7 @@ impl Options {
 			sender: self.sender.clone(),
 			auth: self.auth.clone(),
 			capabilities: self.capabilities.clone(),
+			scope: self.scope.clone(),
 			ns: self.ns.clone(),
 			db: self.db.clone(),
 			force: self.force.clone(),
@@ -298,6 +341,7 @@ impl Options {
 			sender: self.sender.clone(),
 			auth: self.auth.clone(),
 			capabilities: self.capabilities.clone(),
+			scope: self.scope.clone(),
 			ns: self.ns.clone(),
 			db: self.db.clone(),
 			force: self.force.clone(),
@@ -311,6 +355,7 @@ impl Options {
 		Self {
 			auth: self.auth.clone(),
 			capabilities: self.capabilities.clone(),
+			scope: self.scope.clone(),
 			ns: self.ns.clone(),
 			db: self.db.clone(),
 			force: self.force.clone(),
@@ -341,6 +386,7 @@ impl Options {
 			sender: self.sender.clone(),
 			auth: self.auth.clone(),
 			capabilities: self.capabilities.clone(),
+			scope: self.scope.clone(),
 			ns: self.ns.clone(),
 			db: self.db.clone(),
 			force: self.force.clone(),
@@ -419,6 +465,27 @@ impl Options {
 		self.auth.is_allowed(action, &res).map_err(Error::IamError)
 	}
 
+	/// Check if the session scope allows a kind of statement to run. The statements
+	/// of definitions, such as events and fields, run without permissions and are
+	/// not restricted by the scope.
+	pub fn check_statement(&self, kind: StatementKind) -> Result<(), Error> {
+		match &self.scope {
+			Some(sc) if self.perms => sc.check(Some(kind)),
+			_ => Ok(()),
+		}
+	}
+
+	/// Check that an operation with effects outside of the transaction, which
+	/// could not be prevented by a read-only transaction, is not run read-only
+	pub fn check_readonly(&self, value: &str) -> Result<(), Error> {
+		match self.readonly {
+			true => Err(Error::ReadOnlyNotAllowed {
+				value: value.to_owned(),
+			}),
+			false => Ok(()),
+		}
+	}
+
 	/// Whether or not to check table permissions
 	///
 	/// TODO: This method is called a lot during data operations, so we decided to bypass the system's authorization mechanism.
@@ -97,6 +97,13 @@ impl ExplainItem {
 				name: "Iterate Table".into(),
 				details: vec![("table", Value::from(t.0.to_owned()))],
 			},
+			Iterable::Partition(t, p) => Self {
+				name: "Iterate Partition".into(),
+				details: vec![
+					("table", Value::from(t.0.to_owned())),
+					("partition", Value::from(*p)),
+				],
+			},
 			Iterable::Thing(t) => Self {
 				name: "Iterate Thing".into(),
 				details: vec![("thing", Value::Thing(t.to_owned()))],
@@ -113,6 +120,20 @@ impl ExplainItem {
 				name: "Iterate Edges".into(),
 				details: vec![("from", Value::Thing(e.from.to_owned()))],
 			},
+			Iterable::TableVersion(t, v) => Self {
+				name: "Iterate Table Version".into(),
+				details: vec![
+					("table", Value::from(t.0.to_owned())),
+					("version", Value::from(v.to_owned())),
+				],
+			},
+			Iterable::ThingVersion(t, v) => Self {
+				name: "Iterate Thing Version".into(),
+				details: vec![
+					("thing", Value::Thing(t.to_owned())),
+					("version", Value::from(v.to_owned())),
+				],
+			},
 			Iterable::Mergeable(t, v) => Self {
 				name: "Iterate Mergeable".into(),
 				details: vec![("thing", Value::Thing(t.to_owned())), ("value", v.to_owned())],
@@ -137,6 +158,22 @@ impl ExplainItem {
 					details,
 				}
 			}
+			Iterable::EdgesIndex(e, ir) => {
+				let t = &e.what.0[0];
+				let mut details = vec![
+					("from", Value::Thing(e.from.to_owned())),
+					("table", Value::from(t.0.to_owned())),
+				];
+				if let Some(qp) = ctx.get_query_planner() {
+					if let Some(exe) = qp.get_query_executor(&t.0) {
+						details.push(("plan", exe.explain(*ir)));
+					}
+				}
+				Self {
+					name: "Iterate Edges Index".into(),
+					details,
+				}
+			}
 		}
 	}
 
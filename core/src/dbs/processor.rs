@@ -1,3 +1,4 @@
+use crate::cf::history;
 use crate::cnf::PROCESSOR_BATCH_SIZE;
 use crate::ctx::Context;
 #[cfg(not(target_arch = "wasm32"))]
@@ -7,10 +8,12 @@ use crate::dbs::{Iterable, Iterator, Operable, Options, Processed, Statement, Tr
 use crate::err::Error;
 use crate::idx::planner::executor::IteratorRef;
 use crate::idx::planner::IterationStage;
+use crate::key::table::pt;
 use crate::key::{graph, thing};
-use crate::kvs::ScanPage;
+use crate::kvs::{Key, ScanPage, Val};
 use crate::sql::dir::Dir;
-use crate::sql::{Edges, Range, Table, Thing, Value};
+use crate::sql::paths::{IN, OUT};
+use crate::sql::{Datetime, Edges, Range, Table, Thing, Value};
 #[cfg(not(target_arch = "wasm32"))]
 use channel::Sender;
 use reblessive::tree::Stk;
@@ -56,7 +59,7 @@ impl Iterable {
 
 	fn iteration_stage_check(&self, ctx: &Context<'_>) -> bool {
 		match self {
-			Iterable::Table(tb) | Iterable::Index(tb, _) => {
+			Iterable::Table(tb) | Iterable::Partition(tb, _) | Iterable::Index(tb, _) => {
 				if let Some(IterationStage::BuildKnn) = ctx.get_iteration_stage() {
 					if let Some(qp) = ctx.get_query_planner() {
 						if let Some(exe) = qp.get_query_executor(tb) {
@@ -139,6 +142,18 @@ impl<'a> Processor<'a> {
 					}
 					self.process_table(stk, ctx, opt, txn, stm, &v).await?
 				}
+				Iterable::Partition(v, p) => {
+					if let Some(qp) = ctx.get_query_planner() {
+						if let Some(exe) = qp.get_query_executor(&v.0) {
+							// We set the query executor matching the current table in the Context
+							// Avoiding search in the hashmap of the query planner for each doc
+							let mut ctx = Context::new(ctx);
+							ctx.set_query_executor(exe.clone());
+							return self.process_partition(stk, &ctx, opt, txn, stm, &v, p).await;
+						}
+					}
+					self.process_partition(stk, ctx, opt, txn, stm, &v, p).await?
+				}
 				Iterable::Range(v) => self.process_range(stk, ctx, opt, txn, stm, v).await?,
 				Iterable::Edges(e) => self.process_edge(stk, ctx, opt, txn, stm, e).await?,
 				Iterable::Index(t, ir) => {
@@ -148,10 +163,32 @@ impl<'a> Processor<'a> {
 							// Avoiding search in the hashmap of the query planner for each doc
 							let mut ctx = Context::new(ctx);
 							ctx.set_query_executor(exe.clone());
-							return self.process_index(stk, &ctx, opt, txn, stm, &t, ir).await;
+							return self
+								.process_index(stk, &ctx, opt, txn, stm, &t, ir, None)
+								.await;
 						}
 					}
-					self.process_index(stk, ctx, opt, txn, stm, &t, ir).await?
+					self.process_index(stk, ctx, opt, txn, stm, &t, ir, None).await?
+				}
+				Iterable::EdgesIndex(e, ir) => {
+					let t = e.what.0[0].clone();
+					if let Some(qp) = ctx.get_query_planner() {
+						if let Some(exe) = qp.get_query_executor(&t.0) {
+							// We set the query executor matching the edge table in the Context
+							let mut ctx = Context::new(ctx);
+							ctx.set_query_executor(exe.clone());
+							return self
+								.process_index(stk, &ctx, opt, txn, stm, &t, ir, Some(&e))
+								.await;
+						}
+					}
+					self.process_index(stk, ctx, opt, txn, stm, &t, ir, Some(&e)).await?
+				}
+				Iterable::TableVersion(v, at) => {
+					self.process_table_version(stk, ctx, opt, txn, stm, &v, &at).await?
+				}
+				Iterable::ThingVersion(v, at) => {
+					self.process_thing_version(stk, ctx, opt, txn, stm, v, &at).await?
 				}
 				Iterable::Mergeable(v, o) => {
 					self.process_mergeable(stk, ctx, opt, txn, stm, v, o).await?
@@ -198,6 +235,7 @@ impl<'a> Processor<'a> {
 		// Fetch the data from the store
 		let key = thing::new(opt.ns(), opt.db(), &v.tb, &v.id);
 		let val = txn.clone().lock().await.get(key).await?;
+		record_read(txn, opt, &v.tb, &val).await;
 		// Parse the data from the store
 		let val = Operable::Value(match val {
 			Some(v) => Value::from(v),
@@ -254,6 +292,7 @@ impl<'a> Processor<'a> {
 		// Fetch the data from the store
 		let key = thing::new(opt.ns(), opt.db(), &v.tb, &v.id);
 		let val = txn.clone().lock().await.get(key).await?;
+		record_read(txn, opt, &v.tb, &val).await;
 		// Parse the data from the store
 		let x = match val {
 			Some(v) => Value::from(v),
@@ -290,6 +329,7 @@ impl<'a> Processor<'a> {
 		// Fetch the data from the store
 		let key = thing::new(opt.ns(), opt.db(), &v.tb, &v.id);
 		let val = txn.clone().lock().await.get(key).await?;
+		record_read(txn, opt, &v.tb, &val).await;
 		// Parse the data from the store
 		let x = match val {
 			Some(v) => Value::from(v),
@@ -334,6 +374,7 @@ impl<'a> Processor<'a> {
 			let res = txn.clone().lock().await.scan_paged(page, PROCESSOR_BATCH_SIZE).await?;
 			next_page = res.next_page;
 			let res = res.values;
+			record_scan(txn, opt, &v.0, &res).await;
 			// If no results then break
 			if res.is_empty() {
 				break;
@@ -365,6 +406,198 @@ impl<'a> Processor<'a> {
 		Ok(())
 	}
 
+	#[allow(clippy::too_many_arguments)]
+	async fn process_partition(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		stm: &Statement<'_>,
+		v: &Table,
+		p: u16,
+	) -> Result<(), Error> {
+		// Check that the table exists
+		txn.lock().await.check_ns_db_tb(opt.ns(), opt.db(), v, opt.strict).await?;
+		// Prepare the start and end keys
+		let beg = pt::partition_prefix(opt.ns(), opt.db(), v, p);
+		let end = pt::partition_suffix(opt.ns(), opt.db(), v, p);
+		// Loop until no more keys
+		let mut next_page = Some(ScanPage::from(beg..end));
+		while let Some(page) = next_page {
+			// Check if the context is finished
+			if ctx.is_done() {
+				break;
+			}
+			// Get the next batch of partition entries
+			let res = txn.clone().lock().await.scan_paged(page, PROCESSOR_BATCH_SIZE).await?;
+			next_page = res.next_page;
+			let res = res.values;
+			// If no results then break
+			if res.is_empty() {
+				break;
+			}
+			// Loop over results
+			for (k, _) in res.into_iter() {
+				// Check the context
+				if ctx.is_done() {
+					break;
+				}
+				// Fetch the record in this partition
+				let key: pt::Pt = (&k).into();
+				let rid = Thing::from((key.tb, key.id));
+				let key = thing::new(opt.ns(), opt.db(), &rid.tb, &rid.id);
+				let val = txn.clone().lock().await.get(key).await?;
+				record_read(txn, opt, &rid.tb, &val).await;
+				// Parse the data from the store
+				let val = Operable::Value(match val {
+					Some(v) => Value::from(v),
+					None => continue,
+				});
+				// Process the record
+				let pro = Processed {
+					ir: None,
+					rid: Some(rid),
+					doc_id: None,
+					val,
+				};
+				self.process(stk, ctx, opt, txn, stm, pro).await?;
+			}
+			continue;
+		}
+		// Everything ok
+		Ok(())
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	async fn process_thing_version(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		stm: &Statement<'_>,
+		v: Thing,
+		at: &Datetime,
+	) -> Result<(), Error> {
+		// Check that the table exists
+		txn.lock().await.check_ns_db_tb(opt.ns(), opt.db(), &v.tb, opt.strict).await?;
+		// Get the records which have changed since the version
+		let mut changed =
+			history::changed(&mut *txn.lock().await, opt.ns(), opt.db(), &v.tb, at).await?;
+		// Fetch the data at the version
+		let val = match changed.remove(&v.id) {
+			Some(val) => val,
+			None => {
+				let key = thing::new(opt.ns(), opt.db(), &v.tb, &v.id);
+				txn.clone().lock().await.get(key).await?.map(Value::from)
+			}
+		};
+		// Parse the data from the store
+		let val = Operable::Value(val.unwrap_or(Value::None));
+		// Process the document record
+		let pro = Processed {
+			ir: None,
+			rid: Some(v),
+			doc_id: None,
+			val,
+		};
+		self.process(stk, ctx, opt, txn, stm, pro).await?;
+		// Everything ok
+		Ok(())
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	async fn process_table_version(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		stm: &Statement<'_>,
+		v: &Table,
+		at: &Datetime,
+	) -> Result<(), Error> {
+		// Check that the table exists
+		txn.lock().await.check_ns_db_tb(opt.ns(), opt.db(), v, opt.strict).await?;
+		// Get the records which have changed since the version
+		let mut changed =
+			history::changed(&mut *txn.lock().await, opt.ns(), opt.db(), v, at).await?;
+		// Prepare the start and end keys
+		let beg = thing::prefix(opt.ns(), opt.db(), v);
+		let end = thing::suffix(opt.ns(), opt.db(), v);
+		// Loop until no more keys
+		let mut next_page = Some(ScanPage::from(beg..end));
+		while let Some(page) = next_page {
+			// Check if the context is finished
+			if ctx.is_done() {
+				break;
+			}
+			// Get the next batch of key-value entries
+			let res = txn.clone().lock().await.scan_paged(page, PROCESSOR_BATCH_SIZE).await?;
+			next_page = res.next_page;
+			// Loop over results
+			for (k, val) in res.values.into_iter() {
+				// Check the context
+				if ctx.is_done() {
+					break;
+				}
+				// Parse the data from the store
+				let key: thing::Thing = (&k).into();
+				// Process the changed records which come before this record
+				while let Some(e) = changed.first_entry() {
+					if *e.key() >= key.id {
+						break;
+					}
+					let (id, val) = e.remove_entry();
+					if let Some(val) = val {
+						let rid = Thing::from((v.0.clone(), id));
+						self.process_version(stk, ctx, opt, txn, stm, rid, val).await?;
+					}
+				}
+				// Use the value of the record at the version, if it has changed
+				let val = match changed.remove(&key.id) {
+					Some(Some(val)) => val,
+					Some(None) => continue,
+					None => (&val).into(),
+				};
+				let rid = Thing::from((key.tb, key.id));
+				self.process_version(stk, ctx, opt, txn, stm, rid, val).await?;
+			}
+		}
+		// Process the changed records which come after every current record
+		if !ctx.is_done() {
+			for (id, val) in changed {
+				if let Some(val) = val {
+					let rid = Thing::from((v.0.clone(), id));
+					self.process_version(stk, ctx, opt, txn, stm, rid, val).await?;
+				}
+			}
+		}
+		// Everything ok
+		Ok(())
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	async fn process_version(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		stm: &Statement<'_>,
+		rid: Thing,
+		val: Value,
+	) -> Result<(), Error> {
+		let pro = Processed {
+			ir: None,
+			rid: Some(rid),
+			doc_id: None,
+			val: Operable::Value(val),
+		};
+		self.process(stk, ctx, opt, txn, stm, pro).await
+	}
+
 	async fn process_range(
 		&mut self,
 		stk: &mut Stk,
@@ -407,6 +640,7 @@ impl<'a> Processor<'a> {
 			next_page = res.next_page;
 			// Get the next batch of key-value entries
 			let res = res.values;
+			record_scan(txn, opt, &v.tb, &res).await;
 			// If there are key-value entries then fetch them
 			if res.is_empty() {
 				break;
@@ -532,6 +766,15 @@ impl<'a> Processor<'a> {
 				if res.is_empty() {
 					break;
 				}
+				// Read the records ahead of time, so they are not requested one at a time
+				let keys = res
+					.iter()
+					.map(|(k, _)| {
+						let gra = graph::Graph::decode(k)?;
+						Ok(thing::new(opt.ns(), opt.db(), gra.ft, &gra.fk).into())
+					})
+					.collect::<Result<Vec<_>, Error>>()?;
+				txn.lock().await.prefetch(keys).await?;
 				// Loop over results
 				for (k, _) in res.into_iter() {
 					// Check the context
@@ -543,6 +786,7 @@ impl<'a> Processor<'a> {
 					// Fetch the data from the store
 					let key = thing::new(opt.ns(), opt.db(), gra.ft, &gra.fk);
 					let val = txn.lock().await.get(key).await?;
+					record_read(txn, opt, gra.ft, &val).await;
 					let rid = Thing::from((gra.ft, gra.fk));
 					// Parse the data from the store
 					let val = Operable::Value(match val {
@@ -575,6 +819,7 @@ impl<'a> Processor<'a> {
 		stm: &Statement<'_>,
 		table: &Table,
 		ir: IteratorRef,
+		edges: Option<&Edges>,
 	) -> Result<(), Error> {
 		// Check that the table exists
 		txn.lock().await.check_ns_db_tb(opt.ns(), opt.db(), &table.0, opt.strict).await?;
@@ -602,12 +847,24 @@ impl<'a> Processor<'a> {
 						// Fetch the data from the store
 						let key = thing::new(opt.ns(), opt.db(), &table.0, &thing.id);
 						let val = txn.lock().await.get(key.clone()).await?;
+						record_read(txn, opt, &table.0, &val).await;
 						let rid = Thing::from((key.tb, key.id));
 						// Parse the data from the store
-						let val = Operable::Value(match val {
+						let val = match val {
 							Some(v) => Value::from(v),
 							None => Value::None,
-						});
+						};
+						// Skip edges which are not connected to the traversed record
+						if let Some(e) = edges {
+							let end = match e.dir {
+								Dir::In => val.pick(&*OUT),
+								_ => val.pick(&*IN),
+							};
+							if end != Value::Thing(e.from.clone()) {
+								continue;
+							}
+						}
+						let val = Operable::Value(val);
 						// Process the document record
 						let pro = Processed {
 							ir: Some(ir),
@@ -635,3 +892,16 @@ impl<'a> Processor<'a> {
 		})
 	}
 }
+
+/// Count a record which was fetched in the read statistics of its table
+async fn record_read(txn: &Transaction, opt: &Options, tb: &str, val: &Option<Val>) {
+	if let Some(v) = val {
+		txn.lock().await.record_reads(opt.ns(), opt.db(), tb, 1, v.len());
+	}
+}
+
+/// Count a batch of records which were scanned in the read statistics of their table
+async fn record_scan(txn: &Transaction, opt: &Options, tb: &str, res: &[(Key, Val)]) {
+	let bytes = res.iter().map(|(_, v)| v.len()).sum();
+	txn.lock().await.record_reads(opt.ns(), opt.db(), tb, res.len(), bytes);
+}
@@ -0,0 +1,39 @@
+use crate::dbs::Action;
+use crate::sql::{Object, Thing, Value};
+use std::fmt::{self, Display};
+
+/// A message which a hook publishes to a channel, once the
+/// transaction which created, updated, or deleted a record commits
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct Publication {
+	/// The namespace of the hook which published this message
+	pub ns: String,
+	/// The database of the hook which published this message
+	pub db: String,
+	/// The channel which this message is published to
+	pub channel: String,
+	/// The record which was changed
+	pub id: Thing,
+	/// The CREATE / UPDATE / DELETE action which caused this message
+	pub action: Action,
+}
+
+impl From<Publication> for Value {
+	fn from(v: Publication) -> Self {
+		let obj: Object = map! {
+			"channel".to_string() => v.channel.into(),
+			"table".to_string() => v.id.tb.clone().into(),
+			"id".to_string() => v.id.into(),
+			"action".to_string() => v.action.to_string().into(),
+		}
+		.into();
+		obj.into()
+	}
+}
+
+impl Display for Publication {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}", Value::from(self.clone()))
+	}
+}
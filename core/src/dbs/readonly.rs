@@ -0,0 +1,74 @@
+use crate::err::Error;
+use crate::sql::Value;
+use std::time::Duration;
+
+/// The limits which are enforced when a query is executed in read-only
+/// transactions, independently of the permissions of the session, so
+/// that the query can never modify any data.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct ReadOnly {
+	/// The maximum duration of the query
+	pub timeout: Option<Duration>,
+	/// The maximum number of rows which each statement can return. The rows are
+	/// counted as they are collected, so a statement which would need to collect
+	/// more rows than this, such as to sort them, fails without collecting them.
+	pub limit: Option<usize>,
+}
+
+impl ReadOnly {
+	/// Create read-only limits, without any timeout or row limit
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Set the maximum duration of the query
+	pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+		self.timeout = timeout;
+		self
+	}
+
+	/// Set the maximum number of rows which each statement can return
+	pub fn with_limit(mut self, limit: Option<usize>) -> Self {
+		self.limit = limit;
+		self
+	}
+
+	/// Check that the result of a statement is within the row limit, including
+	/// the rows nested in its result, which are not counted as they are collected
+	pub(crate) fn check(&self, res: Result<Value, Error>) -> Result<Value, Error> {
+		match (res, self.limit) {
+			(Ok(v), Some(limit)) if rows(&v) > limit => Err(Error::RowLimitExceeded {
+				limit,
+			}),
+			(res, _) => res,
+		}
+	}
+}
+
+/// Count the rows of a result. Each value in an array is a row, unless it is
+/// itself an array, such as the result of a subquery, and the rows of an object
+/// are the rows of any arrays within it, so that results can not avoid the limit
+/// by being nested.
+fn rows(value: &Value) -> usize {
+	match value {
+		Value::Array(v) => v
+			.iter()
+			.map(|v| match v {
+				Value::Array(_) => rows(v),
+				_ => 1,
+			})
+			.sum(),
+		Value::Object(_) => nested(value).max(1),
+		_ => 1,
+	}
+}
+
+/// Count the rows of any arrays within a value
+fn nested(value: &Value) -> usize {
+	match value {
+		Value::Array(_) => rows(value),
+		Value::Object(v) => v.values().map(nested).sum(),
+		_ => 0,
+	}
+}
@@ -1,4 +1,5 @@
 use crate::err::Error;
+use crate::kvs::Operations;
 use crate::sql::value::Value;
 use revision::revisioned;
 use revision::Revisioned;
@@ -28,6 +29,35 @@ pub struct Response {
 	pub result: Result<Value, Error>,
 	// Record the query type in case processing the response is necessary (such as tracking live queries).
 	pub query_type: QueryType,
+	/// The storage operations which the statement performed, if the datastore counts them
+	pub operations: Option<Operations>,
+	/// The changes which were rolled back when the statement failed in an atomic script
+	pub rollback: Option<Rollback>,
+}
+
+/// The changes of a statement which were rolled back to the savepoint before it.
+///
+/// When a script runs with `OPTION ATOMIC`, every statement runs in the same
+/// transaction. A statement which fails has its changes undone, while the
+/// changes of the other statements are committed together at the end.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct Rollback {
+	/// The number of keys which were restored to their values before the statement
+	pub keys: usize,
+}
+
+/// A part of the output of a query, as it is streamed by `Datastore::execute_stream`.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Streamed {
+	/// A batch of the records of the SELECT statement which is running, sent as
+	/// they are produced. The response of the statement only contains any records
+	/// which were not sent in batches, and if it is an error, the batches which
+	/// were sent before it should be discarded.
+	Rows(Vec<Value>),
+	/// The response of a statement, once it has been finalised
+	Response(Response),
 }
 
 impl Response {
@@ -57,7 +87,12 @@ impl Serialize for Response {
 	where
 		S: serde::Serializer,
 	{
-		let mut val = serializer.serialize_struct(TOKEN, 3)?;
+		let len = if self.rollback.is_some() {
+			4
+		} else {
+			3
+		};
+		let mut val = serializer.serialize_struct(TOKEN, len)?;
 		val.serialize_field("time", self.speed().as_str())?;
 		match &self.result {
 			Ok(v) => {
@@ -69,6 +104,9 @@ impl Serialize for Response {
 				val.serialize_field("result", &Value::from(e.to_string()))?;
 			}
 		}
+		if let Some(v) = &self.rollback {
+			val.serialize_field("rollback", v)?;
+		}
 		val.end()
 	}
 }
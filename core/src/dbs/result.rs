@@ -10,7 +10,7 @@ use crate::dbs::plan::Explanation;
 	feature = "kv-speedb"
 ))]
 use crate::dbs::store::file_store::FileCollector;
-use crate::dbs::store::MemoryCollector;
+use crate::dbs::store::{MemoryCollector, SampleCollector};
 use crate::dbs::{Options, Statement, Transaction};
 use crate::err::Error;
 use crate::sql::{Orders, Value};
@@ -29,6 +29,7 @@ pub(super) enum Results {
 	))]
 	File(Box<FileCollector>),
 	Groups(GroupsCollector),
+	Sample(SampleCollector),
 }
 
 impl Results {
@@ -90,6 +91,9 @@ impl Results {
 			Self::Groups(g) => {
 				g.push(stk, ctx, opt, txn, stm, val).await?;
 			}
+			Self::Sample(s) => {
+				s.push(val);
+			}
 		}
 		Ok(())
 	}
@@ -123,10 +127,27 @@ impl Results {
 				feature = "kv-speedb"
 			))]
 			Self::File(f) => f.start_limit(start, limit),
-			Self::Groups(_) => {}
+			Self::Groups(_) | Self::Sample(_) => {}
 		}
 	}
 
+	/// Reduce the results to a uniform random sample of the specified size
+	pub(super) fn sample(&mut self, size: usize) -> Result<(), Error> {
+		let values = match self {
+			Self::None | Self::Groups(_) => return Ok(()),
+			Self::Sample(s) => s.take_vec(),
+			_ => {
+				let mut s = SampleCollector::new(size);
+				for v in self.take()? {
+					s.push(v);
+				}
+				s.take_vec()
+			}
+		};
+		*self = Self::Memory(values.into());
+		Ok(())
+	}
+
 	pub(super) fn len(&self) -> usize {
 		match self {
 			Self::None => 0,
@@ -141,6 +162,7 @@ impl Results {
 			))]
 			Self::File(e) => e.len(),
 			Self::Groups(g) => g.len(),
+			Self::Sample(s) => s.len(),
 		}
 	}
 
@@ -156,6 +178,7 @@ impl Results {
 				feature = "kv-speedb"
 			))]
 			Self::File(f) => f.take_vec()?,
+			Self::Sample(s) => s.take_vec(),
 			_ => vec![],
 		})
 	}
@@ -180,6 +203,9 @@ impl Results {
 			Self::Groups(g) => {
 				g.explain(exp);
 			}
+			Self::Sample(s) => {
+				s.explain(exp);
+			}
 		}
 	}
 }
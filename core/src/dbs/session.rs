@@ -2,6 +2,7 @@ use crate::ctx::Context;
 use crate::iam::Auth;
 use crate::iam::{Level, Role};
 use crate::sql::value::Value;
+use crate::sql::Priority;
 use chrono::Utc;
 use std::sync::Arc;
 
@@ -31,6 +32,8 @@ pub struct Session {
 	pub sd: Option<Value>,
 	/// The current expiration time of the session
 	pub exp: Option<i64>,
+	/// The priority with which the queries of the session are admitted
+	pub pr: Priority,
 }
 
 impl Session {
@@ -58,6 +61,12 @@ impl Session {
 		self
 	}
 
+	/// Set the priority with which the queries of the session are admitted
+	pub fn with_pr(mut self, pr: Priority) -> Session {
+		self.pr = pr;
+		self
+	}
+
 	/// Retrieves the selected namespace
 	pub(crate) fn ns(&self) -> Option<Arc<str>> {
 		self.ns.as_deref().map(Into::into)
@@ -147,6 +156,7 @@ impl Session {
 			tk: None,
 			sd: Some(rid),
 			exp: None,
+			pr: Priority::Normal,
 		}
 	}
 
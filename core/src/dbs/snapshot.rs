@@ -0,0 +1,168 @@
+//! Snapshots of a [`Session`], which can be saved and restored later.
+//!
+//! A snapshot holds the authentication, the selected namespace and database,
+//! and the parameters of a session, but not the details of the connection
+//! which created it. Snapshots are sealed with a key, so that a snapshot which
+//! was changed after it was saved, or which was sealed with a different key,
+//! can not be restored:
+//!
+//! ```text
+//! {version}{signature}{snapshot}
+//! ```
+//!
+//! The version is a single byte, and the signature is the HMAC-SHA256 of the
+//! version followed by the encoded snapshot. A restored session should be
+//! checked against the current definitions with [`crate::iam::verify::session`]
+//! before it is used, as the definitions may have changed since it was saved.
+use crate::dbs::Session;
+use crate::err::Error;
+use crate::iam::Auth;
+use crate::sql::Priority;
+use crate::sql::Value;
+use hmac::{Hmac, Mac};
+use revision::revisioned;
+use revision::Revisioned;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// The version of the sealed snapshot format
+const VERSION: u8 = 1;
+
+/// The length of the signature of a sealed snapshot
+const SIGNATURE_LENGTH: usize = 32;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// The state of a [`Session`] and its parameters
+#[revisioned(revision = 2)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct SessionSnapshot {
+	/// The session [`Auth`] information
+	pub au: Auth,
+	/// Whether realtime queries are supported
+	pub rt: bool,
+	/// The selected namespace
+	pub ns: Option<String>,
+	/// The selected database
+	pub db: Option<String>,
+	/// The selected authentication scope
+	pub sc: Option<String>,
+	/// The scope authentication token
+	pub tk: Option<Value>,
+	/// The scope authentication data
+	pub sd: Option<Value>,
+	/// The expiration time of the session
+	pub exp: Option<i64>,
+	/// The parameters which were set on the session
+	pub vars: BTreeMap<String, Value>,
+	/// The priority with which the queries of the session are admitted
+	#[revision(start = 2)]
+	pub pr: Priority,
+}
+
+impl SessionSnapshot {
+	/// Take a snapshot of a session and its parameters
+	pub fn new(sess: &Session, vars: BTreeMap<String, Value>) -> Self {
+		Self {
+			au: sess.au.as_ref().clone(),
+			rt: sess.rt,
+			ns: sess.ns.clone(),
+			db: sess.db.clone(),
+			sc: sess.sc.clone(),
+			tk: sess.tk.clone(),
+			sd: sess.sd.clone(),
+			exp: sess.exp,
+			vars,
+			pr: sess.pr,
+		}
+	}
+
+	/// The session which this snapshot was taken of
+	pub fn session(&self) -> Session {
+		Session {
+			au: Arc::new(self.au.clone()),
+			rt: self.rt,
+			ns: self.ns.clone(),
+			db: self.db.clone(),
+			sc: self.sc.clone(),
+			tk: self.tk.clone(),
+			sd: self.sd.clone(),
+			exp: self.exp,
+			pr: self.pr,
+			..Session::default()
+		}
+	}
+
+	/// Encode and sign this snapshot with a key
+	pub fn seal(&self, key: &[u8]) -> Result<Vec<u8>, Error> {
+		let mut snapshot = Vec::new();
+		self.serialize_revisioned(&mut snapshot)?;
+		let mut out = Vec::with_capacity(1 + SIGNATURE_LENGTH + snapshot.len());
+		out.push(VERSION);
+		out.extend_from_slice(&Self::sign(key, &snapshot).finalize().into_bytes());
+		out.extend_from_slice(&snapshot);
+		Ok(out)
+	}
+
+	/// Check the signature of a sealed snapshot, and decode it
+	pub fn open(key: &[u8], sealed: &[u8]) -> Result<Self, Error> {
+		let (signature, snapshot) = match sealed.split_first() {
+			Some((&VERSION, rest)) if rest.len() >= SIGNATURE_LENGTH => {
+				rest.split_at(SIGNATURE_LENGTH)
+			}
+			_ => return Err(Error::InvalidSessionSnapshot),
+		};
+		// Check the signature before the snapshot is decoded
+		Self::sign(key, snapshot)
+			.verify_slice(signature)
+			.map_err(|_| Error::InvalidSessionSnapshot)?;
+		Self::deserialize_revisioned(&mut &snapshot[..]).map_err(|_| Error::InvalidSessionSnapshot)
+	}
+
+	fn sign(key: &[u8], snapshot: &[u8]) -> HmacSha256 {
+		let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length");
+		mac.update(&[VERSION]);
+		mac.update(snapshot);
+		mac
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::iam::Role;
+
+	const KEY: &[u8] = b"secret";
+
+	fn snapshot() -> SessionSnapshot {
+		let sess = Session::editor().with_ns("test").with_db("test");
+		SessionSnapshot::new(&sess, map! { "name".to_string() => Value::from("Tobie") })
+	}
+
+	#[test]
+	fn sealed_snapshots_are_restored() {
+		let snapshot = snapshot();
+		let sealed = snapshot.seal(KEY).unwrap();
+		let opened = SessionSnapshot::open(KEY, &sealed).unwrap();
+		assert_eq!(opened, snapshot);
+		let sess = opened.session();
+		assert!(sess.au.has_role(&Role::Editor));
+		assert_eq!(sess.ns.as_deref(), Some("test"));
+		assert_eq!(opened.vars.get("name"), Some(&Value::from("Tobie")));
+	}
+
+	#[test]
+	fn changed_snapshots_are_rejected() {
+		let mut sealed = snapshot().seal(KEY).unwrap();
+		// The snapshot is sealed with another key
+		assert!(SessionSnapshot::open(b"other", &sealed).is_err());
+		// The snapshot is truncated
+		assert!(SessionSnapshot::open(KEY, &sealed[..20]).is_err());
+		// The snapshot is changed
+		*sealed.last_mut().unwrap() ^= 1;
+		assert!(SessionSnapshot::open(KEY, &sealed).is_err());
+	}
+}
@@ -7,6 +7,7 @@ use crate::sql::idiom::Idioms;
 use crate::sql::limit::Limit;
 use crate::sql::order::Orders;
 use crate::sql::output::Output;
+use crate::sql::sample::Sample;
 use crate::sql::split::Splits;
 use crate::sql::start::Start;
 use crate::sql::statements::create::CreateStatement;
@@ -185,6 +186,14 @@ impl<'a> Statement<'a> {
 			_ => None,
 		}
 	}
+	/// Returns any SAMPLE clause if specified
+	#[inline]
+	pub fn sample(&self) -> Option<&Sample> {
+		match self {
+			Statement::Select(v) => v.sample.as_ref(),
+			_ => None,
+		}
+	}
 	/// Returns any LIMIT clause if specified
 	#[inline]
 	pub fn limit(&self) -> Option<&Limit> {
@@ -219,6 +228,14 @@ impl<'a> Statement<'a> {
 			_ => false,
 		}
 	}
+	/// Returns whether the selected records are locked with FOR UPDATE
+	#[inline]
+	pub fn lock(&self) -> bool {
+		match self {
+			Statement::Select(v) => v.lock,
+			_ => false,
+		}
+	}
 	/// Returns any EXPLAIN clause if specified
 	#[inline]
 	pub fn explain(&self) -> Option<&Explain> {
@@ -1,6 +1,7 @@
 use crate::dbs::plan::Explanation;
 use crate::sql::value::Value;
 use crate::sql::Orders;
+use rand::Rng;
 use std::mem;
 
 #[derive(Default)]
@@ -43,6 +44,50 @@ impl MemoryCollector {
 	}
 }
 
+/// Collects a uniform random sample of a fixed number
+/// of values, using reservoir sampling, so that memory
+/// usage is bounded regardless of the number of values.
+pub(super) struct SampleCollector {
+	size: usize,
+	seen: usize,
+	values: Vec<Value>,
+}
+
+impl SampleCollector {
+	pub(super) fn new(size: usize) -> Self {
+		Self {
+			size,
+			seen: 0,
+			values: Vec::new(),
+		}
+	}
+
+	pub(super) fn push(&mut self, val: Value) {
+		self.seen += 1;
+		if self.values.len() < self.size {
+			self.values.push(val);
+		} else {
+			// Replace an existing value with a probability of size / seen
+			let i = rand::thread_rng().gen_range(0..self.seen);
+			if let Some(v) = self.values.get_mut(i) {
+				*v = val;
+			}
+		}
+	}
+
+	pub(super) fn len(&self) -> usize {
+		self.values.len()
+	}
+
+	pub(super) fn take_vec(&mut self) -> Vec<Value> {
+		mem::take(&mut self.values)
+	}
+
+	pub(super) fn explain(&self, exp: &mut Explanation) {
+		exp.add_collector("Sample", vec![("size", self.size.into())]);
+	}
+}
+
 impl From<Vec<Value>> for MemoryCollector {
 	fn from(values: Vec<Value>) -> Self {
 		Self(values)
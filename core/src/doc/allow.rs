@@ -3,7 +3,9 @@ use crate::dbs::Statement;
 use crate::dbs::{Options, Transaction};
 use crate::doc::Document;
 use crate::err::Error;
+use crate::iam::Level;
 use crate::sql::permission::Permission;
+use crate::sql::statements::Privilege;
 use reblessive::tree::Stk;
 
 impl<'a> Document<'a> {
@@ -21,6 +23,35 @@ impl<'a> Document<'a> {
 			if opt.check_perms(stm.into()) {
 				// Get the table
 				let tb = self.tb(opt, txn).await?;
+				// Check if a user-defined role grants this action, as long as
+				// the user is defined on the database which defines the role
+				let roles = match opt.auth.level() {
+					Level::Database(ns, db) if ns == opt.ns() && db == opt.db() => {
+						opt.auth.custom_roles()
+					}
+					_ => vec![],
+				};
+				if !roles.is_empty() {
+					let privilege = if stm.is_delete() {
+						Privilege::Delete
+					} else if stm.is_select() {
+						Privilege::Select
+					} else if self.is_new() {
+						Privilege::Create
+					} else {
+						Privilege::Update
+					};
+					let mut run = txn.lock().await;
+					if run
+						.all_db_roles(opt.ns(), opt.db())
+						.await?
+						.iter()
+						.filter(|r| roles.contains(&r.name.as_str()))
+						.any(|r| r.allows(&tb.name, privilege))
+					{
+						return Ok(());
+					}
+				}
 				// Get the permission clause
 				let perms = if stm.is_delete() {
 					&tb.permissions.delete
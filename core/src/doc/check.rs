@@ -14,6 +14,12 @@ impl<'a> Document<'a> {
 		txn: &Transaction,
 		stm: &Statement<'_>,
 	) -> Result<(), Error> {
+		// Check if record has expired, although expired
+		// records can still be deleted, which is how the
+		// expired records are removed in the background
+		if !stm.is_delete() {
+			self.expired(ctx, opt, txn, stm).await?;
+		}
 		// Check where condition
 		if let Some(cond) = stm.conds() {
 			// Check if the expression is truthy
@@ -4,6 +4,7 @@ use crate::dbs::{Options, Transaction};
 use crate::doc::Document;
 use crate::err::Error;
 use crate::sql::idiom::Idiom;
+use crate::sql::UnknownFields;
 use reblessive::tree::Stk;
 
 impl<'a> Document<'a> {
@@ -39,15 +40,55 @@ impl<'a> Document<'a> {
 					}
 				}
 			}
-			// Loop over every field in the document
-			for fd in self.current.doc.every(None, true, true).iter() {
-				if !keys.contains(fd) {
-					match fd {
-						fd if fd.is_id() => continue,
-						fd if fd.is_in() => continue,
-						fd if fd.is_out() => continue,
-						fd if fd.is_meta() => continue,
-						fd => self.current.doc.to_mut().del(stk, ctx, opt, txn, fd).await?,
+			// Find the fields in the document which are not defined
+			let mut unknown: Vec<Idiom> = vec![];
+			for fd in self.current.doc.every(None, true, true).into_iter() {
+				match &fd {
+					fd if keys.contains(fd) => continue,
+					fd if fd.is_id() => continue,
+					fd if fd.is_in() => continue,
+					fd if fd.is_out() => continue,
+					fd if fd.is_meta() => continue,
+					fd if fd.is_crdt() => continue,
+					fd if tb.audit && fd.is_audit() => continue,
+					// The parent of this field is already unknown
+					fd if unknown.iter().any(|v| fd.starts_with(v)) => continue,
+					_ => (),
+				}
+				// Fields which have been quarantined are left alone
+				if let UnknownFields::Quarantine(into) = &tb.unknown {
+					if fd.starts_with(into) {
+						continue;
+					}
+				}
+				unknown.push(fd);
+			}
+			// Process the fields which are not defined
+			match &tb.unknown {
+				UnknownFields::Drop => {
+					for fd in unknown.iter() {
+						self.current.doc.to_mut().del(stk, ctx, opt, txn, fd).await?;
+					}
+				}
+				UnknownFields::Reject => {
+					if !unknown.is_empty() {
+						return Err(Error::FieldUnknown {
+							thing: self.id.unwrap().to_string(),
+							fields: unknown
+								.iter()
+								.map(|v| format!("`{v}`"))
+								.collect::<Vec<_>>()
+								.join(", "),
+						});
+					}
+				}
+				UnknownFields::Quarantine(into) => {
+					for fd in unknown.iter() {
+						let doc = self.current.doc.to_mut();
+						let val = doc.pick(fd);
+						doc.del(stk, ctx, opt, txn, fd).await?;
+						let path = [&into[..], &fd[..]].concat();
+						doc.set(stk, ctx, opt, txn, &path, val).await?;
 					}
 				}
 			}
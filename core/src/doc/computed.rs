@@ -0,0 +1,178 @@
+use crate::ctx::Context;
+use crate::dbs::Statement;
+use crate::dbs::{Options, Transaction};
+use crate::doc::Document;
+use crate::err::Error;
+use crate::sql::statements::DefineFieldStatement;
+use crate::sql::{Expression, Idiom, Part, Thing, Value};
+use reblessive::tree::Stk;
+use std::collections::BTreeSet;
+
+impl<'a> Document<'a> {
+	pub async fn computed(
+		&self,
+		_ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		_stm: &Statement<'_>,
+	) -> Result<(), Error> {
+		// Get the record id
+		let rid = self.id.as_ref().unwrap();
+		// Get the computed field definitions
+		let fds = self.fd(opt, txn).await?;
+		let fds: Vec<_> = fds.iter().filter(|fd| fd.computed).collect();
+		// The computed fields of this record have now been computed
+		if !fds.is_empty() {
+			let key = crate::key::table::cs::new(opt.ns(), opt.db(), &rid.tb, &rid.id);
+			txn.lock().await.del(key).await?;
+		}
+		// Check if changed
+		if !self.changed() {
+			return Ok(());
+		}
+		// Claim transaction
+		let mut run = txn.lock().await;
+		// Mark the records which depend on this record as stale
+		let beg = crate::key::table::cd::prefix(opt.ns(), opt.db(), &rid.tb, &rid.id);
+		let end = crate::key::table::cd::suffix(opt.ns(), opt.db(), &rid.tb, &rid.id);
+		for (k, _) in run.getr(beg..end, u32::MAX).await? {
+			let key = crate::key::table::cd::Cd::decode(&k)?;
+			if key.ft != rid.tb || key.fk != rid.id {
+				let key = crate::key::table::cs::new(opt.ns(), opt.db(), key.ft, &key.fk);
+				run.set(key, *rid).await?;
+			}
+		}
+		// Calculate the old and new dependencies of the computed fields
+		if fds.is_empty() {
+			return Ok(());
+		}
+		let o = dependencies(&fds, &self.initial.doc);
+		let n = dependencies(&fds, &self.current.doc);
+		// Remove any dependencies which no longer apply
+		for v in o.difference(&n) {
+			let key =
+				crate::key::table::cd::new(opt.ns(), opt.db(), &v.tb, &v.id, &rid.tb, &rid.id);
+			run.del(key).await?;
+		}
+		// Store any new dependencies
+		for v in n.difference(&o) {
+			let key =
+				crate::key::table::cd::new(opt.ns(), opt.db(), &v.tb, &v.id, &rid.tb, &rid.id);
+			run.set(key, *rid).await?;
+		}
+		// Carry on
+		Ok(())
+	}
+
+	pub async fn recompute(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		_stm: &Statement<'_>,
+	) -> Result<(), Error> {
+		// Check if this is a record
+		let rid = match self.id {
+			Some(rid) => rid,
+			None => return Ok(()),
+		};
+		// Get the computed field definitions
+		let fds = self.fd(opt, txn).await?;
+		if !fds.iter().any(|fd| fd.computed) {
+			return Ok(());
+		}
+		// Check if the computed fields are stale
+		let key: Vec<u8> = crate::key::table::cs::new(opt.ns(), opt.db(), &rid.tb, &rid.id).into();
+		if !txn.lock().await.exi(key).await? {
+			return Ok(());
+		}
+		// Recompute the stale values, which are written
+		// back to the record in the background
+		for fd in fds.iter().filter(|fd| fd.computed) {
+			let Some(expr) = &fd.value else {
+				continue;
+			};
+			for (k, val) in self.current.doc.walk(&fd.name).into_iter() {
+				// Configure the context
+				let mut ctx = Context::new(ctx);
+				ctx.add_value("value", &val);
+				ctx.add_value("after", &val);
+				ctx.add_value("before", &val);
+				// Process the VALUE clause
+				let mut val = expr.compute(stk, &ctx, opt, txn, Some(&self.current)).await?;
+				// Check for a TYPE clause
+				if let Some(kind) = &fd.kind {
+					val = val.coerce_to(kind)?;
+				}
+				// Set the value of the field
+				match val {
+					Value::None => self.current.doc.to_mut().del(stk, &ctx, opt, txn, &k).await?,
+					_ => self.current.doc.to_mut().set(stk, &ctx, opt, txn, &k, val).await?,
+				};
+			}
+		}
+		// Carry on
+		Ok(())
+	}
+}
+
+/// Find the records which the computed fields of a record depend on, which are
+/// the records linked from the fields which the VALUE clauses of the fields use
+fn dependencies(fds: &[&DefineFieldStatement], doc: &Value) -> BTreeSet<Thing> {
+	let mut fields = Vec::new();
+	for fd in fds.iter() {
+		if let Some(v) = &fd.value {
+			used_fields(v, &mut fields);
+		}
+	}
+	let mut out = BTreeSet::new();
+	for field in fields.iter() {
+		links(&doc.pick(field), &mut out);
+	}
+	out
+}
+
+/// Collect the top-level fields of the document which an expression uses
+fn used_fields(v: &Value, out: &mut Vec<Idiom>) {
+	match v {
+		Value::Idiom(v) => {
+			if let Some(p @ Part::Field(_)) = v.0.first() {
+				let field = Idiom(vec![p.clone()]);
+				if !out.contains(&field) {
+					out.push(field);
+				}
+			}
+		}
+		Value::Array(v) => v.iter().for_each(|v| used_fields(v, out)),
+		Value::Object(v) => v.values().for_each(|v| used_fields(v, out)),
+		Value::Function(v) => v.args().iter().for_each(|v| used_fields(v, out)),
+		Value::Expression(v) => match v.as_ref() {
+			Expression::Unary {
+				v,
+				..
+			} => used_fields(v, out),
+			Expression::Binary {
+				l,
+				r,
+				..
+			} => {
+				used_fields(l, out);
+				used_fields(r, out);
+			}
+		},
+		_ => (),
+	}
+}
+
+/// Collect the record links within a value
+fn links(v: &Value, out: &mut BTreeSet<Thing>) {
+	match v {
+		Value::Thing(v) => {
+			out.insert(v.clone());
+		}
+		Value::Array(v) => v.iter().for_each(|v| links(v, out)),
+		Value::Object(v) => v.values().for_each(|v| links(v, out)),
+		_ => (),
+	}
+}
@@ -0,0 +1,65 @@
+use crate::dbs::Options;
+use crate::doc::Document;
+use crate::err::Error;
+use crate::sql::paths::CRDT;
+use crate::sql::statements::DefineFieldStatement;
+use crate::sql::{Crdt, Datetime, Idiom, Part, Value};
+use ulid::Ulid;
+
+impl<'a> Document<'a> {
+	/// Record the value of a CRDT field in the state of the field,
+	/// returning the value which is computed from the merged state
+	pub(super) fn crdt(
+		&mut self,
+		opt: &Options,
+		crdt: &Crdt,
+		field: &Idiom,
+		key: &Idiom,
+		val: Value,
+	) -> Result<Value, Error> {
+		// Get the path of the state of the field
+		let path: Vec<Part> = CRDT.iter().chain(key.iter()).cloned().collect();
+		// Get the state which was stored in the record
+		let stored = self.initial.doc.pick(&path);
+		// Get any state which was written to the record,
+		// for instance when a change is replicated
+		let written = match self.current.doc.pick(&path) {
+			Value::None => stored.clone(),
+			v => v,
+		};
+		// The value which the change was made to
+		let before = crdt.value(&written);
+		// Merge the written state into the stored state
+		let mut state = crdt.join(stored, written);
+		// Record the change, unless the field was not set
+		if !val.is_none() && val != before {
+			let node = opt.id()?.to_string();
+			let at = Datetime::default().0.timestamp_millis();
+			state = crdt
+				.write(state, &before, val.clone(), &node, at, || Ulid::new().to_string())
+				.map_err(|check| Error::FieldCrdt {
+					thing: self.id.as_ref().unwrap().to_string(),
+					value: val.to_string(),
+					field: field.clone(),
+					check,
+				})?;
+		}
+		// Store the merged state in the record
+		let val = crdt.value(&state);
+		self.current.doc.to_mut().put(&path, state);
+		// Output the merged value
+		Ok(val)
+	}
+
+	/// Check if this document only changed the top-level CRDT fields, in which
+	/// case the change can be merged again, if the transaction conflicts
+	pub(super) fn only_crdt(&self, fds: &[DefineFieldStatement]) -> bool {
+		let Value::Object(chg) = self.initial.doc.changed(self.current.doc.as_ref()) else {
+			return false;
+		};
+		chg.keys().all(|k| {
+			let k = Part::from(k.as_str());
+			k == CRDT[0] || fds.iter().any(|fd| fd.crdt.is_some() && fd.name.0 == [k.clone()])
+		})
+	}
+}
@@ -23,6 +23,12 @@ impl<'a> Document<'a> {
 		self.erase(ctx, opt, stm).await?;
 		// Purge index data
 		self.index(stk, ctx, opt, txn, stm).await?;
+		// Purge expiry data
+		self.expire(ctx, opt, txn, stm).await?;
+		// Purge computed field dependencies
+		self.computed(ctx, opt, txn, stm).await?;
+		// Purge record partition
+		self.partition(ctx, opt, txn, stm).await?;
 		// Purge record data
 		self.purge(stk, ctx, opt, txn, stm).await?;
 		// Run table queries
@@ -33,6 +39,8 @@ impl<'a> Document<'a> {
 		self.changefeeds(ctx, opt, txn, stm).await?;
 		// Run event queries
 		self.event(stk, ctx, opt, txn, stm).await?;
+		// Publish to hook channels
+		self.hook(opt, txn, stm).await?;
 		// Yield document
 		self.pluck(stk, ctx, opt, txn, stm).await
 	}
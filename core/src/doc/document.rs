@@ -6,8 +6,10 @@ use crate::iam::Action;
 use crate::iam::ResourceKind;
 use crate::idx::docids::DocId;
 use crate::idx::planner::executor::IteratorRef;
+use crate::sql::statements::define::DefineEnrichmentStatement;
 use crate::sql::statements::define::DefineEventStatement;
 use crate::sql::statements::define::DefineFieldStatement;
+use crate::sql::statements::define::DefineHookStatement;
 use crate::sql::statements::define::DefineIndexStatement;
 use crate::sql::statements::define::DefineTableStatement;
 use crate::sql::statements::live::LiveStatement;
@@ -200,6 +202,28 @@ impl<'a> Document<'a> {
 		// Get the event definitions
 		txn.clone().lock().await.all_tb_events(opt.ns(), opt.db(), &id.tb).await
 	}
+	/// Get the hooks for this document
+	pub async fn hk(
+		&self,
+		opt: &Options,
+		txn: &Transaction,
+	) -> Result<Arc<[DefineHookStatement]>, Error> {
+		// Get the record id
+		let id = self.id.as_ref().unwrap();
+		// Get the hook definitions
+		txn.clone().lock().await.all_tb_hooks(opt.ns(), opt.db(), &id.tb).await
+	}
+	/// Get the enrichments for this document
+	pub async fn en(
+		&self,
+		opt: &Options,
+		txn: &Transaction,
+	) -> Result<Arc<[DefineEnrichmentStatement]>, Error> {
+		// Get the record id
+		let id = self.id.as_ref().unwrap();
+		// Get the enrichment definitions
+		txn.clone().lock().await.all_tb_enrichments(opt.ns(), opt.db(), &id.tb).await
+	}
 	/// Get the fields for this document
 	pub async fn fd(
 		&self,
@@ -0,0 +1,36 @@
+use crate::dbs::{Options, Transaction};
+use crate::doc::Document;
+use crate::err::Error;
+use crate::kvs::enrichment::Enrichment;
+
+impl<'a> Document<'a> {
+	pub async fn enrich(&self, opt: &Options, txn: &Transaction) -> Result<(), Error> {
+		// Check import
+		if opt.import {
+			return Ok(());
+		}
+		// Check if this write is an enrichment result
+		if !opt.enrich {
+			return Ok(());
+		}
+		// Check if changed
+		if !self.changed() {
+			return Ok(());
+		}
+		// Get the enrichment definitions
+		let ens = self.en(opt, txn).await?;
+		if ens.is_empty() {
+			return Ok(());
+		}
+		// Get the record id
+		let id = self.id.as_ref().unwrap();
+		// Queue a job for each enrichment, replacing any job which has not yet run
+		let mut run = txn.lock().await;
+		for en in ens.iter() {
+			let key = crate::key::table::ej::new(opt.ns(), opt.db(), &id.tb, &en.name, &id.id);
+			run.set(key, Enrichment::default()).await?;
+		}
+		// Carry on
+		Ok(())
+	}
+}
@@ -0,0 +1,97 @@
+use crate::ctx::Context;
+use crate::dbs::Statement;
+use crate::dbs::{Options, Transaction};
+use crate::doc::Document;
+use crate::err::Error;
+use crate::sql::statements::DefineFieldStatement;
+use crate::sql::{Datetime, Value};
+use std::collections::BTreeSet;
+
+impl<'a> Document<'a> {
+	pub async fn expire(
+		&self,
+		_ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		_stm: &Statement<'_>,
+	) -> Result<(), Error> {
+		// Check if changed
+		if !self.changed() {
+			return Ok(());
+		}
+		// Check if the table is a view
+		if self.tb(opt, txn).await?.drop {
+			return Ok(());
+		}
+		// Get the fields which define an expiry time
+		let fds = self.fd(opt, txn).await?;
+		let fds: Vec<_> = fds.iter().filter(|fd| fd.expire).collect();
+		if fds.is_empty() {
+			return Ok(());
+		}
+		// Calculate the old and new expiry times
+		let o = expiries(&fds, &self.initial.doc);
+		let n = expiries(&fds, &self.current.doc);
+		if o == n {
+			return Ok(());
+		}
+		// Claim transaction
+		let mut run = txn.lock().await;
+		// Get the record id
+		let rid = self.id.as_ref().unwrap();
+		// Remove any expiry entries which no longer apply
+		for ts in o.difference(&n) {
+			let key = crate::key::root::ex::new(opt.ns(), opt.db(), &rid.tb, *ts, &rid.id);
+			run.del(key).await?;
+		}
+		// Store any new expiry entries
+		for ts in n.difference(&o) {
+			let key = crate::key::root::ex::new(opt.ns(), opt.db(), &rid.tb, *ts, &rid.id);
+			run.set(key, *rid).await?;
+		}
+		// Carry on
+		Ok(())
+	}
+
+	pub async fn expired(
+		&self,
+		_ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		_stm: &Statement<'_>,
+	) -> Result<(), Error> {
+		// Check if this is a record
+		if self.id.is_some() {
+			// Check if any expiry time has passed
+			let now = Datetime::default();
+			for fd in self.fd(opt, txn).await?.iter().filter(|fd| fd.expire) {
+				if let Value::Datetime(v) = self.current.doc.pick(&fd.name) {
+					if v <= now {
+						// Ignore this expired record
+						return Err(Error::Ignore);
+					}
+				}
+			}
+		}
+		// Carry on
+		Ok(())
+	}
+}
+
+/// Calculate the expiry times of a record for the specified fields
+fn expiries(fds: &[&DefineFieldStatement], doc: &Value) -> BTreeSet<u64> {
+	fds.iter().filter_map(|fd| timestamp(&doc.pick(&fd.name))).collect()
+}
+
+/// Convert an expiry time to nanoseconds since the unix epoch, as used in expiry keys
+pub(crate) fn timestamp(v: &Value) -> Option<u64> {
+	match v {
+		Value::Datetime(v) => Some(match v.0.timestamp_nanos_opt() {
+			Some(ns) => ns.max(0) as u64,
+			// Datetimes outside of the supported range either expire immediately or never
+			None if v.0.timestamp() < 0 => 0,
+			None => u64::MAX,
+		}),
+		_ => None,
+	}
+}
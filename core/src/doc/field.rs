@@ -22,11 +22,15 @@ impl<'a> Document<'a> {
 			return Ok(());
 		}
 		// Get the record id
-		let rid = self.id.as_ref().unwrap();
+		let rid = self.id.unwrap();
 		// Get the user applied input
 		let inp = self.initial.doc.changed(self.current.doc.as_ref());
+		// Get the field definitions
+		let fds = self.fd(opt, txn).await?;
+		// The fields which did not pass their ASSERT clause
+		let mut invalid = Vec::new();
 		// Loop through all field statements
-		for fd in self.fd(opt, txn).await?.iter() {
+		for fd in fds.iter() {
 			// Loop over each field in document
 			for (k, mut val) in self.current.doc.walk(&fd.name).into_iter() {
 				// Get the initial value
@@ -117,14 +121,26 @@ impl<'a> Document<'a> {
 					ctx.add_value("value", &val);
 					ctx.add_value("after", &val);
 					ctx.add_value("before", &old);
-					// Process the ASSERT clause
-					if !expr.compute(stk, &ctx, opt, txn, Some(&self.current)).await?.is_truthy() {
-						return Err(Error::FieldValue {
+					// Process the ASSERT clause, collecting
+					// the failures to output them together
+					match expr.compute(stk, &ctx, opt, txn, Some(&self.current)).await {
+						Ok(v) if v.is_truthy() => (),
+						Ok(_) => invalid.push(Error::FieldValue {
 							thing: rid.to_string(),
 							field: fd.name.clone(),
 							value: val.to_string(),
 							check: expr.to_string(),
-						});
+						}),
+						Err(Error::AssertFailed {
+							value,
+							expected,
+						}) => invalid.push(Error::FieldAssert {
+							thing: rid.to_string(),
+							field: fd.name.clone(),
+							value,
+							expected,
+						}),
+						Err(e) => return Err(e),
 					}
 				}
 				// Check for a PERMISSIONS clause
@@ -169,6 +185,10 @@ impl<'a> Document<'a> {
 						}
 					}
 				}
+				// Merge the value into the state of a CRDT field
+				if let Some(crdt) = &fd.crdt {
+					val = self.crdt(opt, crdt, &fd.name, &k, val)?;
+				}
 				// Set the value of the field
 				match val {
 					Value::None => self.current.doc.to_mut().del(stk, ctx, opt, txn, &k).await?,
@@ -176,7 +196,38 @@ impl<'a> Document<'a> {
 				};
 			}
 		}
+		// Check if any fields did not pass their ASSERT clause
+		if invalid.len() > 1 {
+			return Err(Error::FieldsInvalid {
+				thing: rid.to_string(),
+				fields: invalid.iter().filter_map(invalid_field).collect::<Vec<_>>().join(", "),
+				errors: invalid.iter().map(ToString::to_string).collect::<Vec<_>>().join("; "),
+			});
+		}
+		if let Some(e) = invalid.pop() {
+			return Err(e);
+		}
+		// Track whether the record only changed CRDT fields
+		if self.changed() {
+			let only = !self.is_new() && self.only_crdt(&fds);
+			txn.lock().await.track_crdt(only);
+		}
 		// Carry on
 		Ok(())
 	}
 }
+
+/// Get the name of the field which did not pass its ASSERT clause
+fn invalid_field(e: &Error) -> Option<String> {
+	match e {
+		Error::FieldValue {
+			field,
+			..
+		}
+		| Error::FieldAssert {
+			field,
+			..
+		} => Some(format!("`{field}`")),
+		_ => None,
+	}
+}
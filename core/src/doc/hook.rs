@@ -0,0 +1,50 @@
+use crate::dbs::Statement;
+use crate::dbs::{Action, Options, Publication, Transaction};
+use crate::doc::Document;
+use crate::err::Error;
+
+impl<'a> Document<'a> {
+	pub async fn hook(
+		&self,
+		opt: &Options,
+		txn: &Transaction,
+		stm: &Statement<'_>,
+	) -> Result<(), Error> {
+		// Check import
+		if opt.import {
+			return Ok(());
+		}
+		// Check if changed
+		if !self.changed() {
+			return Ok(());
+		}
+		// Get the hook definitions
+		let hks = self.hk(opt, txn).await?;
+		if hks.is_empty() {
+			return Ok(());
+		}
+		// Get the record id
+		let id = self.id.as_ref().unwrap();
+		// Get the hook action
+		let action = if stm.is_delete() {
+			Action::Delete
+		} else if self.is_new() {
+			Action::Create
+		} else {
+			Action::Update
+		};
+		// Buffer the messages until the transaction commits
+		let mut run = txn.lock().await;
+		for hk in hks.iter() {
+			run.push_publication(Publication {
+				ns: opt.ns().to_owned(),
+				db: opt.db().to_owned(),
+				channel: hk.channel.clone().to_raw(),
+				id: (*id).clone(),
+				action: action.clone(),
+			});
+		}
+		// Carry on
+		Ok(())
+	}
+}
@@ -7,6 +7,7 @@ use crate::idx::ft::FtIndex;
 use crate::idx::trees::mtree::MTreeIndex;
 use crate::idx::IndexKeyBase;
 use crate::key;
+use crate::kvs::scheduler::Job;
 use crate::kvs::TransactionType;
 use crate::sql::array::Array;
 use crate::sql::index::{Index, MTreeParams, SearchParams};
@@ -46,6 +47,15 @@ impl<'a> Document<'a> {
 		}
 		// Get the record id
 		let rid = self.id.as_ref().unwrap();
+		// Index builds are background work, which is admitted by the scheduler
+		if targeted_force {
+			let scheduler = {
+				let mut run = txn.lock().await;
+				run.set_background();
+				run.scheduler()
+			};
+			scheduler.admit(Job::IndexBuild, opt.ns(), opt.db(), &rid.tb, 1).await;
+		}
 		// Loop through all index statements
 		for ix in ixs.iter() {
 			// Calculate old values
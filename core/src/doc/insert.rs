@@ -67,6 +67,12 @@ impl<'a> Document<'a> {
 		self.allow(stk, ctx, opt, txn, stm).await?;
 		// Store index data
 		self.index(stk, ctx, opt, txn, stm).await?;
+		// Store expiry data
+		self.expire(ctx, opt, txn, stm).await?;
+		// Store computed field dependencies
+		self.computed(ctx, opt, txn, stm).await?;
+		// Store record partition
+		self.partition(ctx, opt, txn, stm).await?;
 		// Store record data
 		self.store(ctx, opt, txn, stm).await?;
 		// Run table queries
@@ -77,6 +83,10 @@ impl<'a> Document<'a> {
 		self.changefeeds(ctx, opt, txn, stm).await?;
 		// Run event queries
 		self.event(stk, ctx, opt, txn, stm).await?;
+		// Publish to hook channels
+		self.hook(opt, txn, stm).await?;
+		// Queue record enrichments
+		self.enrich(opt, txn).await?;
 		// Yield document
 		self.pluck(stk, ctx, opt, txn, stm).await
 	}
@@ -103,6 +113,12 @@ impl<'a> Document<'a> {
 		self.allow(stk, ctx, opt, txn, stm).await?;
 		// Store index data
 		self.index(stk, ctx, opt, txn, stm).await?;
+		// Store expiry data
+		self.expire(ctx, opt, txn, stm).await?;
+		// Store computed field dependencies
+		self.computed(ctx, opt, txn, stm).await?;
+		// Store record partition
+		self.partition(ctx, opt, txn, stm).await?;
 		// Store record data
 		self.store(ctx, opt, txn, stm).await?;
 		// Run table queries
@@ -113,6 +129,10 @@ impl<'a> Document<'a> {
 		self.changefeeds(ctx, opt, txn, stm).await?;
 		// Run event queries
 		self.event(stk, ctx, opt, txn, stm).await?;
+		// Publish to hook channels
+		self.hook(opt, txn, stm).await?;
+		// Queue record enrichments
+		self.enrich(opt, txn).await?;
 		// Yield document
 		self.pluck(stk, ctx, opt, txn, stm).await
 	}
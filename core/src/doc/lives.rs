@@ -7,12 +7,16 @@ use crate::doc::CursorDoc;
 use crate::doc::Document;
 use crate::err::Error;
 use crate::fflags::FFLAGS;
+use crate::kvs::lq_compact::CompactEvent;
+use crate::kvs::lq_count::CountEvent;
+use crate::kvs::lq_window::WindowEvent;
+use crate::sql::paths::CRDT;
 use crate::sql::paths::META;
 use crate::sql::paths::SC;
 use crate::sql::paths::SD;
 use crate::sql::paths::TK;
 use crate::sql::permission::Permission;
-use crate::sql::statements::LiveStatement;
+use crate::sql::statements::{LiveAction, LiveStatement};
 use crate::sql::Value;
 use channel::Sender;
 use reblessive::tree::Stk;
@@ -104,6 +108,34 @@ impl<'a> Document<'a> {
 		Ok(())
 	}
 
+	/// Check whether a version of the document is matched by the WHERE clause
+	/// of a LIVE query, and can be viewed by the user who created the query
+	async fn lq_matches(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		stm: &Statement<'_>,
+		doc: &CursorDoc<'_>,
+	) -> Result<bool, Error> {
+		// Check that this version of the document exists
+		if doc.doc.is_none() {
+			return Ok(false);
+		}
+		// Check the WHERE clause, and then any PERMISSIONS
+		match self.lq_check(stk, ctx, opt, txn, stm, doc).await {
+			Err(Error::Ignore) => return Ok(false),
+			Err(e) => return Err(e),
+			Ok(_) => (),
+		}
+		match self.lq_allow(stk, ctx, opt, txn, stm, doc).await {
+			Err(Error::Ignore) => Ok(false),
+			Err(e) => Err(e),
+			Ok(_) => Ok(true),
+		}
+	}
+
 	/// Process live query for notifications
 	pub(crate) async fn check_lqs_and_send_notifications(
 		&self,
@@ -128,13 +160,19 @@ impl<'a> Document<'a> {
 			// Create a new statement
 			let lq = Statement::from(*lv);
 			// Get the event action
-			let met = if is_delete {
-				Value::from("DELETE")
+			let action = if is_delete {
+				LiveAction::Delete
 			} else if self.is_new() {
-				Value::from("CREATE")
+				LiveAction::Create
 			} else {
-				Value::from("UPDATE")
+				LiveAction::Update
 			};
+			// Check the LIVE query listens for this action
+			if !lv.listens_for(action) {
+				trace!("live query did not listen for {action} changes, skipping");
+				continue;
+			}
+			let met = Value::from(action.to_string());
 			// Check if this is a delete statement
 			let doc = match is_delete {
 				true => &self.initial,
@@ -177,6 +215,35 @@ impl<'a> Document<'a> {
 			lqctx.add_value("value", self.current.doc.deref());
 			lqctx.add_value("after", self.current.doc.deref());
 			lqctx.add_value("before", self.initial.doc.deref());
+			let default_node_id = Uuid::default();
+			let node_id = opt.id().unwrap_or(default_node_id);
+			// This bool is deprecated since lq v2 on cf
+			// We check against defaults because clients register live queries with their local node id
+			// But the cf scanner uses the server node id, which is different from the client
+			let node_matches_live_query =
+				node_id == default_node_id || lv.node.0 == default_node_id || node_id == lv.node.0;
+			trace!(
+				"Notification node matches live query: {} ({} != {})",
+				node_matches_live_query,
+				node_id,
+				lv.node.0
+			);
+			// Counting live queries only send the number of
+			// matching records, so a record which starts or
+			// stops matching is buffered in the transaction
+			// until committed.
+			if lv.count {
+				if node_matches_live_query {
+					let before =
+						self.lq_matches(stk, &lqctx, &lqopt, txn, &lq, &self.initial).await?;
+					let after = !is_delete
+						&& self.lq_matches(stk, &lqctx, &lqopt, txn, &lq, &self.current).await?;
+					if before != after {
+						txn.lock().await.push_count_event(CountEvent::Change(lv.id, after));
+					}
+				}
+				continue;
+			}
 			// First of all, let's check to see if the WHERE
 			// clause of the LIVE query is matched by this
 			// document. If it is then we can continue.
@@ -203,70 +270,88 @@ impl<'a> Document<'a> {
 			// Finally, let's check what type of statement
 			// caused this LIVE query to run, and send the
 			// relevant notification based on the statement.
-			let default_node_id = Uuid::default();
-			let node_id = opt.id().unwrap_or(default_node_id);
-			// This bool is deprecated since lq v2 on cf
-			// We check against defaults because clients register live queries with their local node id
-			// But the cf scanner uses the server node id, which is different from the client
-			let node_matches_live_query =
-				node_id == default_node_id || lv.node.0 == default_node_id || node_id == lv.node.0;
-			trace!(
-				"Notification node matches live query: {} ({} != {})",
-				node_matches_live_query,
-				node_id,
-				lv.node.0
-			);
-			if is_delete {
+			// Windowed live queries aggregate the matching
+			// records when each window closes, so the record
+			// is buffered in the transaction until committed.
+			if lv.window.is_some() {
+				if node_matches_live_query && !is_delete {
+					if let Some(id) = self.id {
+						// Remove metadata fields before aggregating
+						let mut value = self.current.doc.deref().clone();
+						value.del(stk, &lqctx, &lqopt, txn, &*META).await?;
+						value.del(stk, &lqctx, &lqopt, txn, &*CRDT).await?;
+						txn.lock().await.push_window_event(WindowEvent {
+							ns: opt.ns().to_owned(),
+							db: opt.db().to_owned(),
+							tb: id.tb.clone(),
+							stm: (*lv).clone(),
+							value,
+						});
+					}
+				}
+				continue;
+			}
+			if !node_matches_live_query {
+				continue;
+			}
+			let notification = if is_delete {
 				// Send a DELETE notification
-				if node_matches_live_query {
-					sender
-						.send(Notification {
-							id: lv.id,
-							action: Action::Delete,
-							result: {
-								// Ensure futures are run
-								let lqopt: &Options = &lqopt.new_with_futures(true);
-								// Output the full document before any changes were applied
-								let mut value =
-									doc.doc.compute(stk, &lqctx, lqopt, txn, Some(doc)).await?;
+				Notification {
+					id: lv.id,
+					action: Action::Delete,
+					result: {
+						// Ensure futures are run
+						let lqopt: &Options = &lqopt.new_with_futures(true);
+						// Output the projected fields of the document before any
+						// changes were applied, or the full document for DIFF queries
+						let mut value = match lv.expr.len() {
+							0 => doc.doc.compute(stk, &lqctx, lqopt, txn, Some(doc)).await?,
+							_ => lv.expr.compute(stk, &lqctx, lqopt, txn, Some(doc), false).await?,
+						};
 
-								// TODO(SUR-349): We need an empty object instead of Value::None for serialisation
-								if value.is_none() {
-									value = Value::Object(Default::default());
-								}
-								// Remove metadata fields on output
-								value.del(stk, &lqctx, lqopt, txn, &*META).await?;
-								// Output result
-								value
-							},
-						})
-						.await?;
+						// TODO(SUR-349): We need an empty object instead of Value::None for serialisation
+						if value.is_none() {
+							value = Value::Object(Default::default());
+						}
+						// Remove metadata fields on output
+						value.del(stk, &lqctx, lqopt, txn, &*META).await?;
+						value.del(stk, &lqctx, lqopt, txn, &*CRDT).await?;
+						// Output result
+						value
+					},
 				}
 			} else if self.is_new() {
 				// Send a CREATE notification
-				if node_matches_live_query {
-					trace!("Sending lq create notification");
-					sender
-						.send(Notification {
-							id: lv.id,
-							action: Action::Create,
-							result: self.pluck(stk, &lqctx, &lqopt, txn, &lq).await?,
-						})
-						.await?;
+				trace!("Sending lq create notification");
+				Notification {
+					id: lv.id,
+					action: Action::Create,
+					result: self.pluck(stk, &lqctx, &lqopt, txn, &lq).await?,
 				}
 			} else {
 				// Send a UPDATE notification
-				if node_matches_live_query {
-					trace!("Sending lq update notification");
-					sender
-						.send(Notification {
-							id: lv.id,
-							action: Action::Update,
-							result: self.pluck(stk, &lqctx, &lqopt, txn, &lq).await?,
-						})
-						.await?;
+				trace!("Sending lq update notification");
+				Notification {
+					id: lv.id,
+					action: Action::Update,
+					result: self.pluck(stk, &lqctx, &lqopt, txn, &lq).await?,
 				}
 			};
+			// Compacted live queries coalesce the notifications
+			// of each record, so the notification is buffered in
+			// the transaction until committed.
+			if let (Some(compact), Some(id)) = (&lv.compact, self.id) {
+				txn.lock().await.push_compact_event(CompactEvent {
+					ns: opt.ns().to_owned(),
+					db: opt.db().to_owned(),
+					tb: id.tb.clone(),
+					rid: id.clone(),
+					size: compact.0,
+					notification,
+				});
+				continue;
+			}
+			sender.send(notification).await?;
 		}
 		trace!("Ended check_lqs_and_send_notifications");
 		Ok(())
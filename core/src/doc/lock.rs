@@ -0,0 +1,25 @@
+use crate::dbs::Statement;
+use crate::dbs::{Options, Transaction};
+use crate::doc::Document;
+use crate::err::Error;
+
+impl<'a> Document<'a> {
+	pub async fn lock(
+		&self,
+		opt: &Options,
+		txn: &Transaction,
+		stm: &Statement<'_>,
+	) -> Result<(), Error> {
+		// Check if the statement locks records
+		if !stm.lock() {
+			return Ok(());
+		}
+		// Check if this is a record
+		let Some(rid) = self.id.as_ref() else {
+			return Ok(());
+		};
+		// Acquire write intent on the record
+		let key = crate::key::thing::new(opt.ns(), opt.db(), &rid.tb, &rid.id);
+		txn.lock().await.lock(key).await
+	}
+}
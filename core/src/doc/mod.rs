@@ -5,6 +5,7 @@
 //! - `initial`: value before the transaction
 //! - `id`: traditionally an integer but can be an object or collection such as an array
 pub(crate) use self::document::*;
+pub(crate) use self::expire::timestamp;
 
 mod document; // The entry point for a document to be processed
 
@@ -24,14 +25,21 @@ mod alter; // Modifies and updates the fields in this document
 mod changefeeds; // Processes any change feeds relevant for this document
 mod check; // Checks whether the WHERE clauses matches this document
 mod clean; // Ensures records adhere to the table schema
+mod computed; // Tracks the dependencies of computed fields, and recomputes stale fields
+mod crdt; // Merges the values of CRDT fields into their replicated state
 mod edges; // Attempts to store the edge data for this document
 mod empty; // Checks whether the specified document actually exists
+mod enrich; // Queues any table enrichments relevant for this document
 mod erase; // Removes all content and field data for this document
 mod event; // Processes any table events relevant for this document
+mod expire; // Stores the expiry time of this document, and checks if it has expired
 mod field; // Processes any schema-defined fields for this document
+mod hook; // Publishes messages for any table hooks relevant for this document
 mod index; // Attempts to store the index data for this document
 mod lives; // Processes any live queries relevant for this document
+mod lock; // Acquires write intent on this document for a SELECT ... FOR UPDATE
 mod merge; // Merges any field changes for an INSERT statement
+mod partition; // Stores this document in the partition of its table
 mod pluck; // Pulls the projected expressions from the document
 mod purge; // Deletes this document, and any edges or indexes
 mod relation; // Checks whether the record is the right kind for the table
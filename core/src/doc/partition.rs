@@ -0,0 +1,52 @@
+use crate::ctx::Context;
+use crate::dbs::Statement;
+use crate::dbs::{Options, Transaction};
+use crate::doc::Document;
+use crate::err::Error;
+
+impl<'a> Document<'a> {
+	pub async fn partition(
+		&self,
+		_ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		_stm: &Statement<'_>,
+	) -> Result<(), Error> {
+		// Check if changed
+		if !self.changed() {
+			return Ok(());
+		}
+		// Get the table
+		let tb = self.tb(opt, txn).await?;
+		// Check if the table is a view
+		if tb.drop {
+			return Ok(());
+		}
+		// Check if the table is partitioned
+		let Some(pt) = &tb.partition else {
+			return Ok(());
+		};
+		// Calculate the old and new partitions
+		let o = pt.of_doc(&self.initial.doc)?;
+		let n = pt.of_doc(&self.current.doc)?;
+		if o == n {
+			return Ok(());
+		}
+		// Claim transaction
+		let mut run = txn.lock().await;
+		// Get the record id
+		let rid = self.id.as_ref().unwrap();
+		// Remove the record from its old partition
+		if let Some(p) = o {
+			let key = crate::key::table::pt::new(opt.ns(), opt.db(), &rid.tb, p, &rid.id);
+			run.del(key).await?;
+		}
+		// Store the record in its new partition
+		if let Some(p) = n {
+			let key = crate::key::table::pt::new(opt.ns(), opt.db(), &rid.tb, p, &rid.id);
+			run.set(key, *rid).await?;
+		}
+		// Carry on
+		Ok(())
+	}
+}
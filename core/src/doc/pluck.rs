@@ -6,6 +6,7 @@ use crate::err::Error;
 use crate::iam::Action;
 use crate::sql::idiom::Idiom;
 use crate::sql::output::Output;
+use crate::sql::paths::CRDT;
 use crate::sql::paths::META;
 use crate::sql::permission::Permission;
 use crate::sql::value::Value;
@@ -42,6 +43,10 @@ impl<'a> Document<'a> {
 					// Output the full document before any changes were applied
 					self.initial.doc.compute(stk, ctx, opt, txn, Some(&self.initial)).await
 				}
+				Output::Count => {
+					// Only the number of records is output
+					return Ok(Value::None);
+				}
 				Output::Fields(v) => {
 					// Configure the context
 					let mut ctx = Context::new(ctx);
@@ -116,6 +121,7 @@ impl<'a> Document<'a> {
 		}
 		// Remove metadata fields on output
 		out.cut(&*META);
+		out.cut(&*CRDT);
 		// Output result
 		Ok(out)
 	}
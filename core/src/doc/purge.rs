@@ -3,6 +3,8 @@ use crate::dbs::Statement;
 use crate::dbs::{Options, Transaction};
 use crate::doc::Document;
 use crate::err::Error;
+use crate::key::graph;
+use crate::kvs::stats::Write;
 use crate::sql::dir::Dir;
 use crate::sql::edges::Edges;
 use crate::sql::paths::EDGE;
@@ -11,6 +13,7 @@ use crate::sql::paths::OUT;
 use crate::sql::statements::DeleteStatement;
 use crate::sql::table::Tables;
 use crate::sql::value::{Value, Values};
+use crate::sql::{OnDelete, Relation, TableType, Thing};
 use reblessive::tree::Stk;
 
 impl<'a> Document<'a> {
@@ -35,6 +38,7 @@ impl<'a> Document<'a> {
 			// Purge the record data
 			let key = crate::key::thing::new(opt.ns(), opt.db(), &rid.tb, &rid.id);
 			run.del(key).await?;
+			run.record_write(opt.ns(), opt.db(), &rid.tb, Write::Delete, 0);
 			// Purge the record edges
 			match (
 				self.initial.doc.pick(&*EDGE),
@@ -58,6 +62,40 @@ impl<'a> Document<'a> {
 					run.del(key).await?;
 				}
 				_ => {
+					// Check that no edges reject deleting this record
+					for tb in run.all_tb(opt.ns(), opt.db()).await?.iter() {
+						if let TableType::Relation(Relation {
+							on_delete: OnDelete::Reject,
+							..
+						}) = &tb.kind
+						{
+							for dir in [Dir::Out, Dir::In] {
+								let beg = graph::ftprefix(
+									opt.ns(),
+									opt.db(),
+									&rid.tb,
+									&rid.id,
+									&dir,
+									&tb.name,
+								);
+								let end = graph::ftsuffix(
+									opt.ns(),
+									opt.db(),
+									&rid.tb,
+									&rid.id,
+									&dir,
+									&tb.name,
+								);
+								if let Some((k, _)) = run.getr(beg..end, 1).await?.pop() {
+									let gra = graph::Graph::decode(&k)?;
+									return Err(Error::EdgeDeleteRejected {
+										thing: rid.to_string(),
+										edge: Thing::from((gra.ft, gra.fk)).to_string(),
+									});
+								}
+							}
+						}
+					}
 					// Release the transaction
 					drop(run);
 					// Setup the delete statement
@@ -5,17 +5,25 @@ use crate::dbs::Transaction;
 use crate::dbs::Workable;
 use crate::doc::Document;
 use crate::err::Error;
+use crate::iam::Level;
+use crate::sql::paths::CREATED_AT;
+use crate::sql::paths::CREATED_BY;
 use crate::sql::paths::EDGE;
 use crate::sql::paths::IN;
 use crate::sql::paths::OUT;
+use crate::sql::paths::UPDATED_AT;
+use crate::sql::paths::UPDATED_BY;
 use crate::sql::value::Value;
+use crate::sql::Datetime;
+use crate::syn;
+use chrono::DateTime;
 
 impl<'a> Document<'a> {
 	pub async fn reset(
 		&mut self,
 		_ctx: &Context<'_>,
-		_opt: &Options,
-		_txn: &Transaction,
+		opt: &Options,
+		txn: &Transaction,
 		_stm: &Statement<'_>,
 	) -> Result<(), Error> {
 		// Get the record id
@@ -34,6 +42,34 @@ impl<'a> Document<'a> {
 			self.current.doc.to_mut().put(&*IN, self.initial.doc.pick(&*IN));
 			self.current.doc.to_mut().put(&*OUT, self.initial.doc.pick(&*OUT));
 		}
+		// This table maintains audit fields, so reset fields
+		if self.tb(opt, txn).await?.audit {
+			// Get the current time from the datastore clock
+			let ts = txn.lock().await.clock().await;
+			let now: Value = Datetime::from(
+				DateTime::from_timestamp_millis(ts.value as i64).unwrap_or_default(),
+			)
+			.into();
+			// Get the authenticated user or record
+			let by = match (opt.auth.level(), opt.auth.id()) {
+				(_, "") => Value::None,
+				(Level::Scope(..), id) => match syn::thing(id) {
+					Ok(v) => v.into(),
+					Err(_) => id.into(),
+				},
+				(_, id) => id.into(),
+			};
+			// The creation fields are only set when the record is created
+			if self.is_new() {
+				self.current.doc.to_mut().put(&*CREATED_AT, now.clone());
+				self.current.doc.to_mut().put(&*CREATED_BY, by.clone());
+			} else {
+				self.current.doc.to_mut().put(&*CREATED_AT, self.initial.doc.pick(&*CREATED_AT));
+				self.current.doc.to_mut().put(&*CREATED_BY, self.initial.doc.pick(&*CREATED_BY));
+			}
+			self.current.doc.to_mut().put(&*UPDATED_AT, now);
+			self.current.doc.to_mut().put(&*UPDATED_BY, by);
+		}
 		// Carry on
 		Ok(())
 	}
@@ -8,7 +8,7 @@ use reblessive::tree::Stk;
 
 impl<'a> Document<'a> {
 	pub async fn select(
-		&self,
+		&mut self,
 		stk: &mut Stk,
 		ctx: &Context<'_>,
 		opt: &Options,
@@ -17,10 +17,14 @@ impl<'a> Document<'a> {
 	) -> Result<Value, Error> {
 		// Check if record exists
 		self.empty(ctx, opt, txn, stm).await?;
+		// Recompute stale computed fields
+		self.recompute(stk, ctx, opt, txn, stm).await?;
 		// Check where clause
 		self.check(stk, ctx, opt, txn, stm).await?;
 		// Check if allowed
 		self.allow(stk, ctx, opt, txn, stm).await?;
+		// Lock document if specified
+		self.lock(opt, txn, stm).await?;
 		// Yield document
 		self.pluck(stk, ctx, opt, txn, stm).await
 	}
@@ -1,9 +1,11 @@
+use crate::cnf::MAX_RECORD_SIZE;
 use crate::ctx::Context;
 use crate::dbs::Statement;
 use crate::dbs::{Options, Transaction};
 use crate::doc::Document;
 use crate::err::Error;
 use crate::key::key_req::KeyRequirements;
+use crate::kvs::stats::Write;
 
 impl<'a> Document<'a> {
 	pub async fn store(
@@ -27,10 +29,20 @@ impl<'a> Document<'a> {
 		let rid = self.id.as_ref().unwrap();
 		// Store the record data
 		let key = crate::key::thing::new(opt.ns(), opt.db(), &rid.tb, &rid.id);
+		let val: Vec<u8> = self.into();
+		let size = val.len();
+		// Check the size of the record
+		if *MAX_RECORD_SIZE > 0 && size > *MAX_RECORD_SIZE {
+			return Err(Error::RecordTooLarge {
+				thing: rid.to_string(),
+				size,
+				limit: *MAX_RECORD_SIZE,
+			});
+		}
 		//
 		match stm {
 			// This is a CREATE statement so try to insert the key
-			Statement::Create(_) => match run.put(key.key_category(), key, self).await {
+			Statement::Create(_) => match run.put(key.key_category(), key, val).await {
 				// The key already exists, so return an error
 				Err(Error::TxKeyAlreadyExistsCategory(_)) => Err(Error::RecordExists {
 					thing: rid.to_string(),
@@ -41,8 +53,14 @@ impl<'a> Document<'a> {
 				Ok(v) => Ok(v),
 			},
 			// This is not a CREATE statement, so update the key
-			_ => run.set(key, self).await,
+			_ => run.set(key, val).await,
 		}?;
+		// Count the write in the table statistics
+		let kind = match self.is_new() {
+			true => Write::Create,
+			false => Write::Update,
+		};
+		run.record_write(opt.ns(), opt.db(), &rid.tb, kind, size);
 		// Carry on
 		Ok(())
 	}
@@ -33,6 +33,12 @@ impl<'a> Document<'a> {
 		self.store(ctx, opt, txn, stm).await?;
 		// Store index data
 		self.index(stk, ctx, opt, txn, stm).await?;
+		// Store expiry data
+		self.expire(ctx, opt, txn, stm).await?;
+		// Store computed field dependencies
+		self.computed(ctx, opt, txn, stm).await?;
+		// Store record partition
+		self.partition(ctx, opt, txn, stm).await?;
 		// Run table queries
 		self.table(stk, ctx, opt, txn, stm).await?;
 		// Run lives queries
@@ -41,6 +47,10 @@ impl<'a> Document<'a> {
 		self.changefeeds(ctx, opt, txn, stm).await?;
 		// Run event queries
 		self.event(stk, ctx, opt, txn, stm).await?;
+		// Publish to hook channels
+		self.hook(opt, txn, stm).await?;
+		// Queue record enrichments
+		self.enrich(opt, txn).await?;
 		// Yield document
 		self.pluck(stk, ctx, opt, txn, stm).await
 	}
@@ -0,0 +1,378 @@
+use super::Error;
+use crate::iam::Error as IamError;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// A stable classification of errors, which allows clients to
+/// reliably branch on the kind of an error, without needing to
+/// inspect the error message, which may change between versions.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+	/// The query could not be parsed
+	Parse,
+	/// The request could not be authenticated
+	Auth,
+	/// The authenticated user is not permitted to perform the request
+	Permission,
+	/// The transaction conflicted with another concurrent transaction, and can be retried
+	Conflict,
+	/// The request did not complete within the configured timeout
+	Timeout,
+	/// The request exceeded a size or computation limit
+	Quota,
+	/// The data did not satisfy a schema constraint, such as a unique index or field assertion
+	Constraint,
+	/// The requested resource does not exist
+	NotFound,
+	/// Any other kind of error
+	Other,
+}
+
+impl ErrorKind {
+	/// The name of this kind of error, as used in RPC and HTTP error responses
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			Self::Parse => "parse",
+			Self::Auth => "auth",
+			Self::Permission => "permission",
+			Self::Conflict => "conflict",
+			Self::Timeout => "timeout",
+			Self::Quota => "quota",
+			Self::Constraint => "constraint",
+			Self::NotFound => "not_found",
+			Self::Other => "other",
+		}
+	}
+}
+
+impl fmt::Display for ErrorKind {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(self.as_str())
+	}
+}
+
+impl Error {
+	/// Classify this error into one of a stable set of error kinds
+	pub fn kind(&self) -> ErrorKind {
+		match self {
+			Error::InvalidQuery(_) => ErrorKind::Parse,
+			Error::InvalidAuth
+			| Error::ExpiredSession
+			| Error::InvalidSessionSnapshot
+			| Error::InvalidPass
+			| Error::PasswordMustChange
+			| Error::PasswordExpired
+			| Error::InvalidSignup
+			| Error::UnknownAuth
+			| Error::MissingTokenHeader(_)
+			| Error::MissingTokenClaim(_)
+			| Error::MissingUserOrPass
+			| Error::NoSigninTarget
+			| Error::NoRecordFound
+			| Error::SigninQueryFailed
+			| Error::SignupQueryFailed
+			| Error::ScopeNoSignin
+			| Error::ScopeNoSignup
+			| Error::NoScopeFound => ErrorKind::Auth,
+			Error::IamError(IamError::NotAllowed {
+				..
+			})
+			| Error::NsNotAllowed {
+				..
+			}
+			| Error::DbNotAllowed {
+				..
+			}
+			| Error::TablePermissions {
+				..
+			}
+			| Error::ParamPermissions {
+				..
+			}
+			| Error::FunctionPermissions {
+				..
+			}
+			| Error::ScriptingNotAllowed
+			| Error::FunctionNotAllowed(_)
+			| Error::StatementNotAllowed {
+				..
+			}
+			| Error::NetTargetNotAllowed(_)
+			| Error::TxReadonly
+			| Error::ReadOnlyNotAllowed {
+				..
+			} => ErrorKind::Permission,
+			Error::TxConflict => ErrorKind::Conflict,
+			Error::QueryTimedout => ErrorKind::Timeout,
+			Error::TxTooLarge
+			| Error::TxKeyTooLarge
+			| Error::TxValueTooLarge
+			| Error::DiskQuotaExceeded {
+				..
+			}
+			| Error::RecordTooLarge {
+				..
+			}
+			| Error::RowLimitExceeded {
+				..
+			}
+			| Error::ComputationDepthExceeded => ErrorKind::Quota,
+			Error::RecordExists {
+				..
+			}
+			| Error::IndexExists {
+				..
+			}
+			| Error::TableCheck {
+				..
+			}
+			| Error::FieldCheck {
+				..
+			}
+			| Error::ParamCheck {
+				..
+			}
+			| Error::FieldValue {
+				..
+			}
+			| Error::FieldUnknown {
+				..
+			}
+			| Error::FieldCrdt {
+				..
+			}
+			| Error::FieldReadonly {
+				..
+			}
+			| Error::FieldAssert {
+				..
+			}
+			| Error::FieldsInvalid {
+				..
+			}
+			| Error::AssertFailed {
+				..
+			}
+			| Error::EdgeDeleteRejected {
+				..
+			}
+			| Error::PasswordPolicy(_)
+			| Error::TxKeyAlreadyExistsCategory(_)
+			| Error::TxConditionNotMet => ErrorKind::Constraint,
+			Error::NsNotFound {
+				..
+			}
+			| Error::DbNotFound {
+				..
+			}
+			| Error::TbNotFound {
+				..
+			}
+			| Error::CfNotFound {
+				..
+			}
+			| Error::FdNotFound {
+				..
+			}
+			| Error::IxNotFound {
+				..
+			}
+			| Error::EvNotFound {
+				..
+			}
+			| Error::HkNotFound {
+				..
+			}
+			| Error::EnNotFound {
+				..
+			}
+			| Error::FcNotFound {
+				..
+			}
+			| Error::PaNotFound {
+				..
+			}
+			| Error::RoNotFound {
+				..
+			}
+			| Error::MlNotFound {
+				..
+			}
+			| Error::BlNotFound {
+				..
+			}
+			| Error::AzNotFound {
+				..
+			}
+			| Error::CkNotFound {
+				..
+			}
+			| Error::DcNotFound {
+				..
+			}
+			| Error::ScNotFound {
+				..
+			}
+			| Error::LvNotFound {
+				..
+			}
+			| Error::UserRootNotFound {
+				..
+			}
+			| Error::UserNsNotFound {
+				..
+			}
+			| Error::UserDbNotFound {
+				..
+			} => ErrorKind::NotFound,
+			_ => ErrorKind::Other,
+		}
+	}
+
+	/// Structured details about the resource involved in this error, such
+	/// as the table, field, index, or record, where these are known
+	pub fn details(&self) -> BTreeMap<&'static str, String> {
+		let mut out = BTreeMap::new();
+		match self {
+			Error::TablePermissions {
+				table,
+			}
+			| Error::TableIsView {
+				table,
+			} => {
+				out.insert("table", table.clone());
+			}
+			Error::TbNotFound {
+				value,
+			}
+			| Error::CfNotFound {
+				value,
+			} => {
+				out.insert("table", value.clone());
+			}
+			Error::FdNotFound {
+				value,
+			} => {
+				out.insert("field", value.clone());
+			}
+			Error::IxNotFound {
+				value,
+			} => {
+				out.insert("index", value.clone());
+			}
+			Error::NsNotFound {
+				value,
+			} => {
+				out.insert("ns", value.clone());
+			}
+			Error::DbNotFound {
+				value,
+			} => {
+				out.insert("db", value.clone());
+			}
+			Error::RecordExists {
+				thing,
+			}
+			| Error::RecordTooLarge {
+				thing,
+				..
+			}
+			| Error::EdgeDeleteRejected {
+				thing,
+				..
+			}
+			| Error::TableCheck {
+				thing,
+				..
+			} => {
+				out.insert("record", thing.clone());
+			}
+			Error::IndexExists {
+				thing,
+				index,
+				value,
+			} => {
+				out.insert("table", thing.tb.clone());
+				out.insert("record", thing.to_string());
+				out.insert("index", index.clone());
+				out.insert("key", value.clone());
+			}
+			Error::FieldCheck {
+				thing,
+				field,
+				..
+			}
+			| Error::FieldValue {
+				thing,
+				field,
+				..
+			}
+			| Error::FieldAssert {
+				thing,
+				field,
+				..
+			}
+			| Error::FieldCrdt {
+				thing,
+				field,
+				..
+			}
+			| Error::FieldReadonly {
+				thing,
+				field,
+			} => {
+				out.insert("record", thing.clone());
+				out.insert("field", field.to_string());
+			}
+			Error::FieldUnknown {
+				thing,
+				fields,
+			} => {
+				out.insert("record", thing.clone());
+				out.insert("fields", fields.clone());
+			}
+			Error::FieldsInvalid {
+				thing,
+				fields,
+				errors,
+			} => {
+				out.insert("record", thing.clone());
+				out.insert("fields", fields.clone());
+				out.insert("errors", errors.clone());
+			}
+			_ => {}
+		}
+		out
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sql::Thing;
+
+	#[test]
+	fn error_kinds_and_details() {
+		assert_eq!(Error::QueryTimedout.kind(), ErrorKind::Timeout);
+		assert_eq!(Error::TxConflict.kind(), ErrorKind::Conflict);
+		assert_eq!(Error::Tx("Storage failure".to_owned()).kind(), ErrorKind::Other);
+		assert_eq!(Error::ExpiredSession.kind(), ErrorKind::Auth);
+		let err = Error::IndexExists {
+			thing: Thing::from(("person", "tobie")),
+			index: "email".to_owned(),
+			value: "'tobie@surrealdb.com'".to_owned(),
+		};
+		assert_eq!(err.kind(), ErrorKind::Constraint);
+		let details = err.details();
+		assert_eq!(details.get("table").map(String::as_str), Some("person"));
+		assert_eq!(details.get("index").map(String::as_str), Some("email"));
+		assert_eq!(details.get("key").map(String::as_str), Some("'tobie@surrealdb.com'"));
+		let err = Error::TbNotFound {
+			value: "person".to_owned(),
+		};
+		assert_eq!(err.kind(), ErrorKind::NotFound);
+		assert_eq!(err.kind().to_string(), "not_found");
+		assert_eq!(err.details().get("table").map(String::as_str), Some("person"));
+	}
+}
@@ -6,6 +6,7 @@ use crate::sql::idiom::Idiom;
 use crate::sql::index::Distance;
 use crate::sql::thing::Thing;
 use crate::sql::value::Value;
+use crate::sql::StatementKind;
 use crate::sql::TableType;
 use crate::syn::error::RenderedError as RenderedParserError;
 use crate::vs::Error as VersionstampError;
@@ -22,6 +23,7 @@ use bincode::Error as BincodeError;
 use ext_sort::SortError;
 use fst::Error as FstError;
 use jsonwebtoken::errors::Error as JWTError;
+#[cfg(feature = "obs")]
 use object_store::Error as ObjectStoreError;
 use revision::Error as RevisionError;
 use serde::Serialize;
@@ -31,6 +33,10 @@ use storekey::decode::Error as DecodeError;
 use storekey::encode::Error as EncodeError;
 use thiserror::Error;
 
+mod kind;
+
+pub use self::kind::ErrorKind;
+
 /// An error originating from an embedded SurrealDB database.
 #[derive(Error, Debug)]
 #[non_exhaustive]
@@ -79,6 +85,10 @@ pub enum Error {
 	#[error("There was an error when starting a new datastore transaction")]
 	TxFailure,
 
+	/// The transaction failed to commit because of a conflict with another transaction
+	#[error("Failed to commit transaction due to a read or write conflict. This transaction can be retried")]
+	TxConflict,
+
 	/// The transaction was already cancelled or committed
 	#[error("Couldn't update a finished transaction")]
 	TxFinished,
@@ -87,6 +97,16 @@ pub enum Error {
 	#[error("Couldn't write to a read only transaction")]
 	TxReadonly,
 
+	/// The versionstamp of the transaction is only assigned by the storage engine when it commits
+	#[error("The versionstamp of a transaction is not available before it commits with this storage engine")]
+	VersionstampUnavailable,
+
+	/// The storage engine can not guarantee the isolation level which was requested for a transaction
+	#[error("The storage engine does not support transactions with {value}")]
+	TxIsolationUnsupported {
+		value: String,
+	},
+
 	/// The conditional value in the request was not equal
 	#[error("Value being checked was not correct")]
 	TxConditionNotMet,
@@ -108,6 +128,23 @@ pub enum Error {
 	#[error("Transaction is too large")]
 	TxTooLarge,
 
+	/// The datastore is using more disk space than the configured hard limit
+	#[error("The datastore is using {usage} bytes of disk space, exceeding the limit of {limit} bytes. Only reads and deletes are allowed")]
+	DiskQuotaExceeded {
+		usage: u64,
+		limit: u64,
+	},
+
+	/// The record is larger than the configured maximum record size
+	#[error(
+		"The record `{thing}` is {size} bytes, exceeding the maximum record size of {limit} bytes"
+	)]
+	RecordTooLarge {
+		thing: String,
+		size: usize,
+		limit: usize,
+	},
+
 	/// No namespace has been selected
 	#[error("Specify a namespace to use")]
 	NsEmpty,
@@ -157,6 +194,10 @@ pub enum Error {
 	#[error("Remote HTTP request functions are not enabled")]
 	HttpDisabled,
 
+	/// The object store is not enabled
+	#[error("The object store is not enabled")]
+	ObsDisabled,
+
 	/// it is not possible to set a variable with the specified name
 	#[error("'{name}' is a protected variable and cannot be set")]
 	InvalidParam {
@@ -193,6 +234,19 @@ pub enum Error {
 		value: String,
 	},
 
+	/// The SAMPLE clause must evaluate to a positive integer
+	#[error("Found {value} but the SAMPLE clause must evaluate to a positive integer")]
+	InvalidSample {
+		value: String,
+	},
+
+	/// The INTO OUTFILE clause did not specify a writable object storage location
+	#[error("Unable to write query results to '{path}': {message}")]
+	InvalidOutfile {
+		path: String,
+		message: String,
+	},
+
 	/// The START clause must evaluate to a positive integer
 	#[error("Found {value} but the START clause must evaluate to a positive integer")]
 	InvalidStart {
@@ -267,6 +321,18 @@ pub enum Error {
 	#[error("The query was not executed because it exceeded the timeout")]
 	QueryTimedout,
 
+	/// A statement of a read-only query returned more rows than the limit
+	#[error("The query returned more than {limit} rows, which is the limit for read-only queries")]
+	RowLimitExceeded {
+		limit: usize,
+	},
+
+	/// A read-only query tried to run something with side effects
+	#[error("Unable to run {value} in a read-only query")]
+	ReadOnlyNotAllowed {
+		value: String,
+	},
+
 	/// The query did not execute, because the transaction was cancelled
 	#[error("The query was not executed due to a cancelled transaction")]
 	QueryCancelled,
@@ -281,6 +347,10 @@ pub enum Error {
 		message: String,
 	},
 
+	/// A dry run can not be undone within a larger transaction
+	#[error("A DRY RUN statement can not be run within a transaction")]
+	DryRunInTransaction,
+
 	/// The permissions do not allow for changing to the specified namespace
 	#[error("You don't have permission to change to the {ns} namespace")]
 	NsNotAllowed {
@@ -335,6 +405,18 @@ pub enum Error {
 		value: String,
 	},
 
+	/// The requested hook does not exist
+	#[error("The hook '{value}' does not exist")]
+	HkNotFound {
+		value: String,
+	},
+
+	/// The requested enrichment does not exist
+	#[error("The enrichment '{value}' does not exist")]
+	EnNotFound {
+		value: String,
+	},
+
 	/// The requested function does not exist
 	#[error("The function 'fn::{value}' does not exist")]
 	FcNotFound {
@@ -353,6 +435,12 @@ pub enum Error {
 		value: String,
 	},
 
+	/// The requested blob does not exist
+	#[error("The blob '{value}' does not exist")]
+	BlNotFound {
+		value: String,
+	},
+
 	/// The requested scope does not exist
 	#[error("The scope '{value}' does not exist")]
 	ScNotFound {
@@ -383,12 +471,24 @@ pub enum Error {
 		value: String,
 	},
 
+	/// The requested role does not exist
+	#[error("The role '{value}' does not exist")]
+	RoNotFound {
+		value: String,
+	},
+
 	/// The requested table does not exist
 	#[error("The table '{value}' does not exist")]
 	TbNotFound {
 		value: String,
 	},
 
+	/// The requested table does not have a change feed
+	#[error("The table '{value}' does not have a change feed")]
+	CfNotFound {
+		value: String,
+	},
+
 	/// The requested live query does not exist
 	#[error("The live query '{value}' does not exist")]
 	LvNotFound {
@@ -407,6 +507,25 @@ pub enum Error {
 		value: String,
 	},
 
+	/// The requested check does not exist
+	#[error("The check '{value}' does not exist")]
+	CkNotFound {
+		value: String,
+	},
+
+	/// The requested dictionary does not exist
+	#[error("The dictionary '{value}' does not exist")]
+	DcNotFound {
+		value: String,
+	},
+
+	/// The dictionary used by an analyzer filter is of the wrong kind
+	#[error("The dictionary '{value}' is not a {kind} dictionary")]
+	DcInvalidKind {
+		value: String,
+		kind: String,
+	},
+
 	/// The requested analyzer does not exist
 	#[error("The index '{value}' does not exist")]
 	IxNotFound {
@@ -487,6 +606,32 @@ pub enum Error {
 		value: String,
 	},
 
+	/// Can not execute LIVE statement using the specified window
+	#[error("Can not execute LIVE statement using window '{value}': {message}")]
+	LiveWindow {
+		value: String,
+		message: String,
+	},
+
+	/// Can not execute SELECT statement at a version using the specified value
+	#[error("Can not execute SELECT statement with VERSION using value '{value}'")]
+	SelectVersionStatement {
+		value: String,
+	},
+
+	/// The table does not have a change feed from which earlier versions can be read
+	#[error("The table '{value}' can not be read at an earlier version, as it does not have a change feed which includes the original records")]
+	VersionNoChangeFeed {
+		value: String,
+	},
+
+	/// The requested version is outside of the retention of the change feed
+	#[error("The table '{value}' can not be read at '{version}', as this is outside of the retention of its change feed")]
+	VersionOutsideRetention {
+		value: String,
+		version: String,
+	},
+
 	/// Can not execute KILL statement using the specified id
 	#[error("Can not execute KILL statement using id '{value}'")]
 	KillStatement {
@@ -543,6 +688,13 @@ pub enum Error {
 		target_type: TableType,
 	},
 
+	/// The specified record can not be deleted, as it is an endpoint of an edge which rejects deletion
+	#[error("Unable to delete record `{thing}`, as it is referenced by the edge `{edge}`")]
+	EdgeDeleteRejected {
+		thing: String,
+		edge: String,
+	},
+
 	/// The specified field did not conform to the field type check
 	#[error("Found {value} for field `{field}`, with record `{thing}`, but expected a {check}")]
 	FieldCheck {
@@ -552,6 +704,14 @@ pub enum Error {
 		check: String,
 	},
 
+	/// The specified param did not conform to the param type check
+	#[error("Found {value} for param ${name}, but expected a {check}")]
+	ParamCheck {
+		name: String,
+		value: String,
+		check: String,
+	},
+
 	/// The specified field did not conform to the field ASSERT clause
 	#[error("Found {value} for field `{field}`, with record `{thing}`, but field must conform to: {check}")]
 	FieldValue {
@@ -561,6 +721,48 @@ pub enum Error {
 		check: String,
 	},
 
+	/// The specified field did not pass a validation function in the field ASSERT clause
+	#[error("Found {value} for field `{field}`, with record `{thing}`, but expected {expected}")]
+	FieldAssert {
+		thing: String,
+		value: String,
+		field: Idiom,
+		expected: String,
+	},
+
+	/// Several fields of the record did not conform to their field ASSERT clauses
+	#[error("Found invalid values for fields {fields} on record `{thing}`: {errors}")]
+	FieldsInvalid {
+		thing: String,
+		fields: String,
+		errors: String,
+	},
+
+	/// A value did not pass a validation function
+	#[error("Found {value}, but expected {expected}")]
+	AssertFailed {
+		value: String,
+		expected: String,
+	},
+
+	/// The record has fields which are not defined on a SCHEMAFULL table which rejects unknown fields
+	#[error(
+		"Found undefined fields {fields} on record `{thing}`, but the table rejects unknown fields"
+	)]
+	FieldUnknown {
+		thing: String,
+		fields: String,
+	},
+
+	/// The specified value could not be merged into the state of a CRDT field
+	#[error("Found {value} for field `{field}`, with record `{thing}`, but expected {check}")]
+	FieldCrdt {
+		thing: String,
+		value: String,
+		field: Idiom,
+		check: String,
+	},
+
 	/// The specified field did not conform to the field ASSERT clause
 	#[error(
 		"Found changed value for field `{field}`, with record `{thing}`, but field is readonly"
@@ -570,6 +772,18 @@ pub enum Error {
 		field: Idiom,
 	},
 
+	/// The specified field was defined with an EXPIRE clause, but is not a datetime
+	#[error("The field `{field}` must be of type `datetime` or `option<datetime>` to use the EXPIRE clause")]
+	FieldExpire {
+		field: Idiom,
+	},
+
+	/// The specified field was defined with a COMPUTED clause, but has no VALUE clause
+	#[error("The field `{field}` must have a VALUE clause to use the COMPUTED clause")]
+	FieldComputed {
+		field: Idiom,
+	},
+
 	/// Found a record id for the record but we are creating a specific record
 	#[error("Found {value} for the id field, but a specific record has been specified")]
 	IdMismatch {
@@ -690,6 +904,7 @@ pub enum Error {
 	Utf8Error(#[from] FromUtf8Error),
 
 	/// Represents an underlying error with the Object Store
+	#[cfg(feature = "obs")]
 	#[error("Object Store error: {0}")]
 	ObsError(#[from] ObjectStoreError),
 
@@ -726,6 +941,30 @@ pub enum Error {
 	#[error("Versionstamp in key is corrupted: {0}")]
 	CorruptedVersionstampInKey(#[from] VersionstampError),
 
+	/// A value read from the storage engine failed its integrity check
+	#[error("The value stored for the key '{key}' is corrupted: {reason}")]
+	CorruptedValue {
+		key: String,
+		reason: String,
+	},
+
+	/// A chunk of a backup could not be read
+	#[error("The backup chunk is invalid: {reason}")]
+	InvalidSyncChunk {
+		reason: String,
+	},
+
+	/// The audit log of definitions can not be deleted
+	#[error("The audit log of DEFINE and REMOVE statements can not be deleted")]
+	DefinitionLogProtected,
+
+	/// The datastore was written by a newer release, with a storage format which this release can not read
+	#[error("The datastore uses storage format version {found}, but this release of SurrealDB only supports storage format versions up to {supported}. Datastores can not be downgraded, so upgrade SurrealDB to open this datastore")]
+	UnsupportedStorageVersion {
+		found: u16,
+		supported: u16,
+	},
+
 	/// Invalid level
 	#[error("Invalid level '{0}'")]
 	InvalidLevel(String),
@@ -745,6 +984,13 @@ pub enum Error {
 	#[error("Function '{0}' is not allowed to be executed")]
 	FunctionNotAllowed(String),
 
+	/// The scope of the session does not allow this kind of statement
+	#[error("Sessions of the scope '{scope}' are not allowed to run {kind} statements")]
+	StatementNotAllowed {
+		scope: String,
+		kind: StatementKind,
+	},
+
 	/// Network target is not allowed
 	#[error("Access to network target '{0}' is not allowed")]
 	NetTargetNotAllowed(String),
@@ -782,6 +1028,18 @@ pub enum Error {
 	#[error("The password did not verify")]
 	InvalidPass,
 
+	/// The password does not satisfy the configured password policy
+	#[error("The password does not satisfy the password policy, as {0}")]
+	PasswordPolicy(String),
+
+	/// The password must be changed before signing in
+	#[error("The password must be changed, by signing in with a new password")]
+	PasswordMustChange,
+
+	/// The password has expired and must be changed before signing in
+	#[error("The password has expired, and must be changed by signing in with a new password")]
+	PasswordExpired,
+
 	/// There was an error with authentication
 	#[error("There was a problem with authentication")]
 	InvalidAuth,
@@ -816,6 +1074,18 @@ pub enum Error {
 		value: String,
 	},
 
+	/// The requested check already exists
+	#[error("The check '{value}' already exists")]
+	CkAlreadyExists {
+		value: String,
+	},
+
+	/// The requested dictionary already exists
+	#[error("The dictionary '{value}' already exists")]
+	DcAlreadyExists {
+		value: String,
+	},
+
 	/// The requested database already exists
 	#[error("The database '{value}' already exists")]
 	DbAlreadyExists {
@@ -828,6 +1098,18 @@ pub enum Error {
 		value: String,
 	},
 
+	/// The requested hook already exists
+	#[error("The hook '{value}' already exists")]
+	HkAlreadyExists {
+		value: String,
+	},
+
+	/// The requested enrichment already exists
+	#[error("The enrichment '{value}' already exists")]
+	EnAlreadyExists {
+		value: String,
+	},
+
 	/// The requested field already exists
 	#[error("The field '{value}' already exists")]
 	FdAlreadyExists {
@@ -864,6 +1146,24 @@ pub enum Error {
 		value: String,
 	},
 
+	/// The requested role already exists
+	#[error("The role '{value}' already exists")]
+	RoAlreadyExists {
+		value: String,
+	},
+
+	/// The requested role has the name of a built-in role
+	#[error("The role '{value}' is a built-in role, and can not be defined")]
+	RoReserved {
+		value: String,
+	},
+
+	/// The requested role is defined on a database, so it can only be granted to its users
+	#[error("The role '{value}' is defined on a database, and can only be granted to the users of that database")]
+	RoInvalidLevel {
+		value: String,
+	},
+
 	/// The requested scope already exists
 	#[error("The scope '{value}' already exists")]
 	ScAlreadyExists {
@@ -876,6 +1176,13 @@ pub enum Error {
 		value: String,
 	},
 
+	/// The number of partitions of a table is out of range
+	#[error("The table '{value}' must have between 1 and {max} partitions")]
+	TbInvalidPartitions {
+		value: String,
+		max: u16,
+	},
+
 	/// The requested namespace token already exists
 	#[error("The namespace token '{value}' already exists")]
 	NtAlreadyExists {
@@ -921,6 +1228,11 @@ pub enum Error {
 	#[error("The session has expired")]
 	ExpiredSession,
 
+	/// The session snapshot was changed after it was sealed,
+	/// or was sealed with a different key
+	#[error("The session snapshot is invalid or was sealed with a different key")]
+	InvalidSessionSnapshot,
+
 	/// The session has an invalid duration
 	#[error("The session has an invalid duration")]
 	InvalidSessionDuration,
@@ -1001,6 +1313,7 @@ impl From<tikv::Error> for Error {
 			}
 			tikv::Error::KeyError(ke) if ke.abort.contains("KeyTooLarge") => Error::TxKeyTooLarge,
 			tikv::Error::RegionError(re) if re.raft_entry_too_large.is_some() => Error::TxTooLarge,
+			tikv::Error::KeyError(ke) if ke.conflict.is_some() => Error::TxConflict,
 			_ => Error::Tx(e.to_string()),
 		}
 	}
@@ -1009,21 +1322,30 @@ impl From<tikv::Error> for Error {
 #[cfg(feature = "kv-speedb")]
 impl From<speedb::Error> for Error {
 	fn from(e: speedb::Error) -> Error {
-		Error::Tx(e.to_string())
+		match e.kind() {
+			speedb::ErrorKind::Busy | speedb::ErrorKind::TryAgain => Error::TxConflict,
+			_ => Error::Tx(e.to_string()),
+		}
 	}
 }
 
 #[cfg(feature = "kv-rocksdb")]
 impl From<rocksdb::Error> for Error {
 	fn from(e: rocksdb::Error) -> Error {
-		Error::Tx(e.to_string())
+		match e.kind() {
+			rocksdb::ErrorKind::Busy | rocksdb::ErrorKind::TryAgain => Error::TxConflict,
+			_ => Error::Tx(e.to_string()),
+		}
 	}
 }
 
 #[cfg(feature = "kv-surrealkv")]
 impl From<surrealkv::Error> for Error {
 	fn from(e: surrealkv::Error) -> Error {
-		Error::Tx(e.to_string())
+		match e {
+			surrealkv::Error::TransactionWriteConflict => Error::TxConflict,
+			_ => Error::Tx(e.to_string()),
+		}
 	}
 }
 
@@ -1083,4 +1405,13 @@ pub enum LiveQueryCause {
 	#[doc(hidden)]
 	#[error("The Live Query must have a change feed that includes relative changes")]
 	ChangeFeedNoOriginal,
+	#[doc(hidden)]
+	#[error("The Live Query can not use a WINDOW clause when processed from a change feed")]
+	WindowNotSupported,
+	#[doc(hidden)]
+	#[error("The Live Query can not use a COMPACT clause when processed from a change feed")]
+	CompactNotSupported,
+	#[doc(hidden)]
+	#[error("The Live Query can not count its records when processed from a change feed")]
+	CountNotSupported,
 }
@@ -0,0 +1,68 @@
+use crate::err::Error;
+use crate::sql::{Array, Regex, Value};
+
+/// Checks that a string matches a regular expression
+pub fn matches((val, regex): (String, Regex)) -> Result<Value, Error> {
+	match regex.0.is_match(&val) {
+		true => Ok(Value::Bool(true)),
+		false => Err(Error::AssertFailed {
+			value: Value::from(val).to_string(),
+			expected: format!("a value matching {regex}"),
+		}),
+	}
+}
+
+/// Checks that the length of a string, array, object, or bytes value is within a range
+pub fn len_between((val, min, max): (Value, i64, i64)) -> Result<Value, Error> {
+	let len = match &val {
+		Value::Strand(v) => v.as_str().chars().count(),
+		Value::Array(v) => v.len(),
+		Value::Object(v) => v.len(),
+		Value::Bytes(v) => v.len(),
+		_ => {
+			return Err(Error::AssertFailed {
+				value: val.to_string(),
+				expected: "a string, array, object, or bytes value".to_owned(),
+			})
+		}
+	};
+	match (min..=max).contains(&(len as i64)) {
+		true => Ok(Value::Bool(true)),
+		false => Err(Error::AssertFailed {
+			value: val.to_string(),
+			expected: format!("a length between {min} and {max}"),
+		}),
+	}
+}
+
+/// Checks that a value is equal to one of the specified values
+pub fn one_of((val, options): (Value, Array)) -> Result<Value, Error> {
+	match options.contains(&val) {
+		true => Ok(Value::Bool(true)),
+		false => Err(Error::AssertFailed {
+			value: val.to_string(),
+			expected: format!("one of {options}"),
+		}),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn assert_failures() {
+		let regex: Regex = "^[a-z]+$".parse().unwrap();
+		assert_eq!(matches(("tobie".to_owned(), regex.clone())).unwrap(), Value::Bool(true));
+		let err = matches(("Tobie".to_owned(), regex)).unwrap_err();
+		assert_eq!(err.to_string(), "Found 'Tobie', but expected a value matching /^[a-z]+$/");
+		assert!(len_between((Value::from("abc"), 1, 3)).is_ok());
+		let err = len_between((Value::from("abcd"), 1, 3)).unwrap_err();
+		assert_eq!(err.to_string(), "Found 'abcd', but expected a length between 1 and 3");
+		assert!(len_between((Value::from(1), 1, 3)).is_err());
+		let options = Array::from(vec![Value::from("a"), Value::from("b")]);
+		assert!(one_of((Value::from("a"), options.clone())).is_ok());
+		let err = one_of((Value::from("c"), options)).unwrap_err();
+		assert_eq!(err.to_string(), "Found 'c', but expected one of ['a', 'b']");
+	}
+}
@@ -0,0 +1,59 @@
+use crate::dbs::{Options, Transaction};
+use crate::err::Error;
+use crate::kvs::blob;
+use crate::sql::{Bytes, Value};
+
+/// Stores the bytes as a blob, returning the id of the blob
+pub async fn put(
+	(opt, txn): (Option<&Options>, Option<&Transaction>),
+	(data,): (Bytes,),
+) -> Result<Value, Error> {
+	match (opt, txn) {
+		(Some(opt), Some(txn)) => {
+			// Valid options?
+			opt.valid_for_db()?;
+			// Store the blob
+			let bl = txn.lock().await.put_blob(opt.ns(), opt.db(), &data).await?;
+			Ok(Value::from(bl))
+		}
+		_ => Ok(Value::None),
+	}
+}
+
+/// Returns the bytes of a blob, or NONE if the blob does not exist
+pub async fn get(
+	(opt, txn): (Option<&Options>, Option<&Transaction>),
+	(bl,): (String,),
+) -> Result<Value, Error> {
+	match (opt, txn) {
+		(Some(opt), Some(txn)) if blob::is_id(&bl) => {
+			// Valid options?
+			opt.valid_for_db()?;
+			// Fetch the blob
+			match txn.lock().await.get_blob_data(opt.ns(), opt.db(), &bl).await? {
+				Some(v) => Ok(Value::Bytes(Bytes::from(v))),
+				None => Ok(Value::None),
+			}
+		}
+		_ => Ok(Value::None),
+	}
+}
+
+/// Returns the size in bytes of a blob, or NONE if the blob does not exist
+pub async fn size(
+	(opt, txn): (Option<&Options>, Option<&Transaction>),
+	(bl,): (String,),
+) -> Result<Value, Error> {
+	match (opt, txn) {
+		(Some(opt), Some(txn)) if blob::is_id(&bl) => {
+			// Valid options?
+			opt.valid_for_db()?;
+			// Fetch the blob definition
+			match txn.lock().await.get_blob(opt.ns(), opt.db(), &bl).await? {
+				Some(v) => Ok(Value::from(v.size)),
+				None => Ok(Value::None),
+			}
+		}
+		_ => Ok(Value::None),
+	}
+}
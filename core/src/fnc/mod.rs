@@ -9,6 +9,8 @@ use reblessive::tree::Stk;
 
 pub mod args;
 pub mod array;
+pub mod assert;
+pub mod blob;
 pub mod bytes;
 pub mod count;
 pub mod crypto;
@@ -22,13 +24,16 @@ pub mod not;
 pub mod object;
 pub mod operate;
 pub mod parse;
+pub mod path;
 pub mod rand;
 pub mod script;
 pub mod search;
 pub mod session;
 pub mod sleep;
 pub mod string;
+pub mod system;
 pub mod time;
+pub mod tx;
 pub mod r#type;
 pub mod util;
 pub mod vector;
@@ -43,20 +48,27 @@ pub async fn run(
 	name: &str,
 	args: Vec<Value>,
 ) -> Result<Value, Error> {
-	if name.eq("sleep")
+	if is_async(name) {
+		stk.run(|stk| asynchronous(stk, ctx, Some(opt), Some(txn), doc, name, args)).await
+	} else {
+		synchronous(ctx, name, args)
+	}
+}
+
+/// Check if a function is run asynchronously, with access to the datastore
+pub(crate) fn is_async(name: &str) -> bool {
+	name.eq("sleep")
+		|| name.starts_with("blob::")
 		|| name.starts_with("search")
 		|| name.starts_with("http")
+		|| name.starts_with("system")
+		|| name.starts_with("tx::")
 		|| name.starts_with("type::field")
 		|| name.starts_with("type::fields")
 		|| name.starts_with("crypto::argon2")
 		|| name.starts_with("crypto::bcrypt")
 		|| name.starts_with("crypto::pbkdf2")
 		|| name.starts_with("crypto::scrypt")
-	{
-		stk.run(|stk| asynchronous(stk, ctx, Some(opt), Some(txn), doc, name, args)).await
-	} else {
-		synchronous(ctx, name, args)
-	}
 }
 
 /// Each function is specified by its name (a string literal) followed by its path. The path
@@ -132,6 +144,10 @@ pub fn synchronous(ctx: &Context<'_>, name: &str, args: Vec<Value>) -> Result<Va
 		"array::sort::asc" => array::sort::asc,
 		"array::sort::desc" => array::sort::desc,
 		//
+		"assert::len_between" => assert::len_between,
+		"assert::matches" => assert::matches,
+		"assert::one_of" => assert::one_of,
+		//
 		"bytes::len" => bytes::len,
 		//
 		"count" => count::count,
@@ -216,18 +232,20 @@ pub fn synchronous(ctx: &Context<'_>, name: &str, args: Vec<Value>) -> Result<Va
 		"parse::url::query" => parse::url::query,
 		"parse::url::scheme" => parse::url::scheme,
 		//
-		"rand" => rand::rand,
-		"rand::bool" => rand::bool,
-		"rand::enum" => rand::r#enum,
-		"rand::float" => rand::float,
-		"rand::guid" => rand::guid,
-		"rand::int" => rand::int,
-		"rand::string" => rand::string,
-		"rand::time" => rand::time,
-		"rand::ulid" => rand::ulid,
-		"rand::uuid::v4" => rand::uuid::v4,
-		"rand::uuid::v7" => rand::uuid::v7,
-		"rand::uuid" => rand::uuid,
+		"path" => path::path,
+		//
+		"rand" => rand::rand(ctx),
+		"rand::bool" => rand::bool(ctx),
+		"rand::enum" => rand::r#enum(ctx),
+		"rand::float" => rand::float(ctx),
+		"rand::guid" => rand::guid(ctx),
+		"rand::int" => rand::int(ctx),
+		"rand::string" => rand::string(ctx),
+		"rand::time" => rand::time(ctx),
+		"rand::ulid" => rand::ulid(ctx),
+		"rand::uuid::v4" => rand::uuid::v4(ctx),
+		"rand::uuid::v7" => rand::uuid::v7(ctx),
+		"rand::uuid" => rand::uuid(ctx),
 		//
 		"session::db" => session::db(ctx),
 		"session::id" => session::id(ctx),
@@ -297,7 +315,7 @@ pub fn synchronous(ctx: &Context<'_>, name: &str, args: Vec<Value>) -> Result<Va
 		"time::nano" => time::nano,
 		"time::micros" => time::micros,
 		"time::millis" => time::millis,
-		"time::now" => time::now,
+		"time::now" => time::now(ctx),
 		"time::round" => time::round,
 		"time::second" => time::second,
 		"time::timezone" => time::timezone,
@@ -400,6 +418,10 @@ pub async fn asynchronous(
 	dispatch!(
 		name,
 		args,
+		"blob::get" => blob::get((opt, txn)).await,
+		"blob::put" => blob::put((opt, txn)).await,
+		"blob::size" => blob::size((opt, txn)).await,
+		//
 		"crypto::argon2::compare" => (cpu_intensive) crypto::argon2::cmp.await,
 		"crypto::argon2::generate" => (cpu_intensive) crypto::argon2::gen.await,
 		"crypto::bcrypt::compare" => (cpu_intensive) crypto::bcrypt::cmp.await,
@@ -420,9 +442,28 @@ pub async fn asynchronous(
 		"search::score" => search::score((ctx, txn, doc)).await,
 		"search::highlight" => search::highlight((ctx,txn, doc)).await,
 		"search::offsets" => search::offsets((ctx, txn, doc)).await,
+		"search::suggest" => search::suggest((stk, ctx, txn, opt)).await,
 		//
 		"sleep" => sleep::sleep(ctx).await,
 		//
+		"system::activity" => system::activity((opt, txn)).await,
+		"system::background" => system::background((opt, txn)).await,
+		"system::checks" => system::checks((opt, txn)).await,
+		"system::connections" => system::connections((opt, txn)).await,
+		"system::disk_usage" => system::disk_usage((opt, txn)).await,
+		"system::hot_keys" => system::hot_keys((opt, txn)).await,
+		"system::live_queries" => system::live_queries((opt, txn)).await,
+		"system::merges" => system::merges((opt, txn)).await,
+		"system::prefetch" => system::prefetch((opt, txn)).await,
+		"system::priority" => system::priority((opt, txn)).await,
+		"system::queries" => system::queries((opt, txn)).await,
+		"system::tables" => system::tables((opt, txn)).await,
+		"system::throttle" => system::throttle((opt, txn)).await,
+		"system::transactions" => system::transactions((opt, txn)).await,
+		//
+		"tx::timestamp" => tx::timestamp((opt, txn)).await,
+		"tx::versionstamp" => tx::versionstamp((opt, txn)).await,
+		//
 		"type::field" => r#type::field((stk,ctx, opt, txn, doc)).await,
 		"type::fields" => r#type::fields((stk,ctx, opt, txn, doc)).await,
 	)
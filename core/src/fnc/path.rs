@@ -0,0 +1,8 @@
+use crate::err::Error;
+use crate::sql::Value;
+
+/// Returns the paths of a graph traversal. The paths are found when the fields
+/// of a SELECT statement are computed, so there is no traversal anywhere else.
+pub fn path(_: ()) -> Result<Value, Error> {
+	Ok(Value::None)
+}
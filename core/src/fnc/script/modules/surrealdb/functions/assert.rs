@@ -0,0 +1,13 @@
+use super::run;
+use crate::fnc::script::modules::impl_module_def;
+
+#[non_exhaustive]
+pub struct Package;
+
+impl_module_def!(
+	Package,
+	"assert",
+	"len_between" => run,
+	"matches" => run,
+	"one_of" => run
+);
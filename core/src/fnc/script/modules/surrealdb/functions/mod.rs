@@ -9,6 +9,8 @@ use reblessive::tree::Stk;
 use super::query::{QueryContext, QUERY_DATA_PROP_NAME};
 
 mod array;
+mod assert;
+mod blob;
 mod bytes;
 mod crypto;
 mod duration;
@@ -23,7 +25,9 @@ mod rand;
 mod search;
 mod session;
 mod string;
+mod system;
 mod time;
+mod tx;
 mod r#type;
 mod vector;
 
@@ -34,6 +38,8 @@ impl_module_def!(
 	Package,
 	"", // root path
 	"array" => (array::Package),
+	"assert" => (assert::Package),
+	"blob" => (blob::Package),
 	"bytes" => (bytes::Package),
 	"count" => run,
 	"crypto" => (crypto::Package),
@@ -46,13 +52,16 @@ impl_module_def!(
 	"object" => (object::Package),
 	"not" => run,
 	"parse" => (parse::Package),
+	"path" => run,
 	"rand" => (rand::Package),
 	"array" => (array::Package),
 	"search" => (search::Package),
 	"session" => (session::Package),
 	"sleep" => fut Async,
 	"string" => (string::Package),
+	"system" => (system::Package),
 	"time" => (time::Package),
+	"tx" => (tx::Package),
 	"type" => (r#type::Package),
 	"vector" => (vector::Package)
 );
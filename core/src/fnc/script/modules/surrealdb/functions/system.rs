@@ -0,0 +1,25 @@
+use super::fut;
+use crate::fnc::script::modules::impl_module_def;
+use js::prelude::Async;
+
+#[non_exhaustive]
+pub struct Package;
+
+impl_module_def!(
+	Package,
+	"system",
+	"activity" => fut Async,
+	"background" => fut Async,
+	"checks" => fut Async,
+	"connections" => fut Async,
+	"disk_usage" => fut Async,
+	"hot_keys" => fut Async,
+	"live_queries" => fut Async,
+	"merges" => fut Async,
+	"prefetch" => fut Async,
+	"priority" => fut Async,
+	"queries" => fut Async,
+	"tables" => fut Async,
+	"throttle" => fut Async,
+	"transactions" => fut Async
+);
@@ -2,9 +2,14 @@ use crate::ctx::Context;
 use crate::dbs::{Options, Transaction};
 use crate::doc::CursorDoc;
 use crate::err::Error;
+use crate::iam::Action;
 use crate::idx::ft::analyzer::Analyzer;
+use crate::idx::ft::FtIndex;
 use crate::idx::planner::executor::QueryExecutor;
-use crate::sql::{Thing, Value};
+use crate::idx::IndexKeyBase;
+use crate::kvs::TransactionType;
+use crate::sql::index::Index;
+use crate::sql::{Permission, Thing, Value};
 use reblessive::tree::Stk;
 
 fn get_execution_context<'a>(
@@ -31,14 +36,77 @@ pub async fn analyze(
 	(az, val): (Value, Value),
 ) -> Result<Value, Error> {
 	if let (Some(txn), Some(opt), Value::Strand(az), Value::Strand(val)) = (txn, opt, az, val) {
-		let az: Analyzer =
-			txn.lock().await.get_db_analyzer(opt.ns(), opt.db(), az.as_str()).await?.into();
+		let az = {
+			let mut tx = txn.lock().await;
+			let az = tx.get_db_analyzer(opt.ns(), opt.db(), az.as_str()).await?;
+			Analyzer::new(&mut tx, opt.ns(), opt.db(), az).await?
+		};
 		az.analyze(stk, ctx, opt, txn, val.0).await
 	} else {
 		Ok(Value::None)
 	}
 }
 
+pub async fn suggest(
+	(stk, ctx, txn, opt): (&mut Stk, &Context<'_>, Option<&Transaction>, Option<&Options>),
+	(index, input, limit): (String, String, Option<i64>),
+) -> Result<Value, Error> {
+	if let (Some(txn), Some(opt)) = (txn, opt) {
+		// The index is referenced as `table.index`
+		let Some((tb, ix)) = index.split_once('.') else {
+			return Err(Error::InvalidArguments {
+				name: String::from("search::suggest"),
+				message: String::from(
+					"The first argument should be an index reference in the form `table.index`.",
+				),
+			});
+		};
+		let limit = match limit {
+			Some(v) if v < 0 => {
+				return Err(Error::InvalidArguments {
+					name: String::from("search::suggest"),
+					message: String::from("The third argument should be a positive number."),
+				})
+			}
+			Some(v) => v as usize,
+			None => 10,
+		};
+		// Fetch the table and the index definitions
+		let (tb, ix) = {
+			let mut run = txn.lock().await;
+			let tb = run.get_and_cache_tb(opt.ns(), opt.db(), tb).await?;
+			let ix = run.get_and_cache_tb_index(opt.ns(), opt.db(), &tb.name, ix).await?;
+			(tb, ix)
+		};
+		// The indexed terms reveal the contents of the table
+		if opt.check_perms(Action::View) && !matches!(tb.permissions.select, Permission::Full) {
+			return Err(Error::TablePermissions {
+				table: tb.name.to_raw(),
+			});
+		}
+		let Index::Search(p) = &ix.index else {
+			return Err(Error::InvalidArguments {
+				name: String::from("search::suggest"),
+				message: format!("The index '{}' is not a full-text search index.", ix.name),
+			});
+		};
+		let ft = FtIndex::new(
+			ctx.get_index_stores(),
+			opt,
+			txn,
+			p.az.as_str(),
+			IndexKeyBase::new(opt, &ix),
+			p,
+			TransactionType::Read,
+		)
+		.await?;
+		let res = ft.suggest(stk, ctx, opt, txn, input, limit).await?;
+		Ok(res.into_iter().map(Value::from).collect::<Vec<_>>().into())
+	} else {
+		Ok(Value::None)
+	}
+}
+
 pub async fn score(
 	(ctx, txn, doc): (&Context<'_>, Option<&Transaction>, Option<&CursorDoc<'_>>),
 	(match_ref,): (Value,),
@@ -0,0 +1,329 @@
+use crate::cnf::NORMAL_FETCH_SIZE;
+use crate::dbs::{Options, Transaction};
+use crate::err::Error;
+use crate::iam::{Action, ResourceKind};
+use crate::kvs::scheduler::{Job, Priority};
+use crate::sql::{Base, Object, TableType, Value};
+
+/// Returns the totals of the queries, transactions, and conflicts on this node, and the latency of a storage read
+pub async fn activity(
+	(opt, txn): (Option<&Options>, Option<&Transaction>),
+	_: (),
+) -> Result<Value, Error> {
+	match (opt, txn) {
+		(Some(opt), Some(txn)) => {
+			// Allowed to run?
+			opt.is_allowed(Action::View, ResourceKind::Any, &Base::Root)?;
+			// Fetch the activity of this node
+			txn.lock().await.activity().await
+		}
+		_ => Ok(Value::None),
+	}
+}
+
+/// Returns the disk usage and limits of the datastore, if it is stored in a local directory
+pub async fn disk_usage(
+	(opt, txn): (Option<&Options>, Option<&Transaction>),
+	_: (),
+) -> Result<Value, Error> {
+	match (opt, txn) {
+		(Some(opt), Some(txn)) => {
+			// Allowed to run?
+			opt.is_allowed(Action::View, ResourceKind::Any, &Base::Root)?;
+			// Fetch the disk usage
+			Ok(txn.lock().await.disk_usage())
+		}
+		_ => Ok(Value::None),
+	}
+}
+
+/// Returns the key prefixes which have been involved in the most transaction conflicts
+pub async fn hot_keys(
+	(opt, txn): (Option<&Options>, Option<&Transaction>),
+	_: (),
+) -> Result<Value, Error> {
+	match (opt, txn) {
+		(Some(opt), Some(txn)) => {
+			// Allowed to run?
+			opt.is_allowed(Action::View, ResourceKind::Any, &Base::Root)?;
+			// Fetch the conflict statistics
+			Ok(txn.lock().await.hot_keys())
+		}
+		_ => Ok(Value::None),
+	}
+}
+
+/// Returns the recorded results of the data quality checks in the current database
+pub async fn checks(
+	(opt, txn): (Option<&Options>, Option<&Transaction>),
+	_: (),
+) -> Result<Value, Error> {
+	match (opt, txn) {
+		(Some(opt), Some(txn)) => {
+			// Valid options?
+			opt.valid_for_db()?;
+			// Allowed to run?
+			opt.is_allowed(Action::View, ResourceKind::Database, &Base::Db)?;
+			// Claim transaction
+			let mut run = txn.lock().await;
+			// Fetch the results of each check, in the order they ran
+			let (ns, db) = (opt.ns(), opt.db());
+			let mut out = Vec::new();
+			for ck in run.all_db_checks(ns, db).await?.iter() {
+				let beg = crate::key::database::cr::prefix(ns, db, &ck.name);
+				let end = crate::key::database::cr::suffix(ns, db, &ck.name);
+				for (_, v) in run.getr(beg..end, u32::MAX).await? {
+					out.push(Value::from(v));
+				}
+			}
+			Ok(out.into())
+		}
+		_ => Ok(Value::None),
+	}
+}
+
+/// Returns the client connections which are open on this node
+pub async fn connections(
+	(opt, txn): (Option<&Options>, Option<&Transaction>),
+	_: (),
+) -> Result<Value, Error> {
+	match (opt, txn) {
+		(Some(opt), Some(txn)) => {
+			// Allowed to run?
+			opt.is_allowed(Action::View, ResourceKind::Any, &Base::Root)?;
+			// Fetch the open connections
+			Ok(txn.lock().await.connections())
+		}
+		_ => Ok(Value::None),
+	}
+}
+
+/// Returns the transactions which are open on this node
+pub async fn transactions(
+	(opt, txn): (Option<&Options>, Option<&Transaction>),
+	_: (),
+) -> Result<Value, Error> {
+	match (opt, txn) {
+		(Some(opt), Some(txn)) => {
+			// Allowed to run?
+			opt.is_allowed(Action::View, ResourceKind::Any, &Base::Root)?;
+			// Fetch the open transactions
+			Ok(txn.lock().await.transactions())
+		}
+		_ => Ok(Value::None),
+	}
+}
+
+/// Returns the live queries which are registered on this node
+pub async fn live_queries(
+	(opt, txn): (Option<&Options>, Option<&Transaction>),
+	_: (),
+) -> Result<Value, Error> {
+	match (opt, txn) {
+		(Some(opt), Some(txn)) => {
+			// Allowed to run?
+			opt.is_allowed(Action::View, ResourceKind::Any, &Base::Root)?;
+			// Fetch the live queries of this node
+			let lqs = txn.lock().await.scan_ndlq(&opt.id()?, NORMAL_FETCH_SIZE).await?;
+			Ok(lqs
+				.into_iter()
+				.map(|lq| {
+					Value::from(Object::from(map! {
+						"id" => Value::Uuid(lq.lq),
+						"ns" => Value::from(lq.ns),
+						"db" => Value::from(lq.db),
+						"tb" => Value::from(lq.tb),
+					}))
+				})
+				.collect::<Vec<_>>()
+				.into())
+		}
+		_ => Ok(Value::None),
+	}
+}
+
+/// Returns the queries which are executing on this node
+pub async fn queries(
+	(opt, txn): (Option<&Options>, Option<&Transaction>),
+	_: (),
+) -> Result<Value, Error> {
+	match (opt, txn) {
+		(Some(opt), Some(txn)) => {
+			// Allowed to run?
+			opt.is_allowed(Action::View, ResourceKind::Any, &Base::Root)?;
+			// Fetch the executing queries
+			Ok(txn.lock().await.queries())
+		}
+		_ => Ok(Value::None),
+	}
+}
+
+/// Returns the tables in the current database, with the number of fields, indexes, events and live queries on each
+pub async fn tables(
+	(opt, txn): (Option<&Options>, Option<&Transaction>),
+	_: (),
+) -> Result<Value, Error> {
+	match (opt, txn) {
+		(Some(opt), Some(txn)) => {
+			// Valid options?
+			opt.valid_for_db()?;
+			// Allowed to run?
+			opt.is_allowed(Action::View, ResourceKind::Table, &Base::Db)?;
+			// Claim transaction
+			let mut run = txn.lock().await;
+			// Fetch the table definitions
+			let (ns, db) = (opt.ns(), opt.db());
+			let mut out = Vec::new();
+			for tb in run.all_tb(ns, db).await?.iter() {
+				let fields = run.all_tb_fields(ns, db, &tb.name).await?.len();
+				let indexes = run.all_tb_indexes(ns, db, &tb.name).await?.len();
+				let events = run.all_tb_events(ns, db, &tb.name).await?.len();
+				let lives = run.all_tb_lives(ns, db, &tb.name).await?.len();
+				let kind = match tb.kind {
+					TableType::Any => "any",
+					TableType::Normal => "normal",
+					TableType::Relation(_) => "relation",
+				};
+				out.push(Value::from(Object::from(map! {
+					"name" => Value::from(tb.name.to_raw()),
+					"kind" => Value::from(kind),
+					"schemafull" => Value::from(tb.full),
+					"view" => Value::from(tb.view.is_some()),
+					"changefeed" => Value::from(tb.changefeed.is_some()),
+					"fields" => Value::from(fields),
+					"indexes" => Value::from(indexes),
+					"events" => Value::from(events),
+					"live_queries" => Value::from(lives),
+				})));
+			}
+			Ok(out.into())
+		}
+		_ => Ok(Value::None),
+	}
+}
+
+/// Returns the number of commutative updates which have been retried after a conflict, for each kind of operation
+pub async fn merges(
+	(opt, txn): (Option<&Options>, Option<&Transaction>),
+	_: (),
+) -> Result<Value, Error> {
+	match (opt, txn) {
+		(Some(opt), Some(txn)) => {
+			// Allowed to run?
+			opt.is_allowed(Action::View, ResourceKind::Any, &Base::Root)?;
+			// Fetch the merge statistics
+			Ok(txn.lock().await.merges())
+		}
+		_ => Ok(Value::None),
+	}
+}
+
+/// Returns the number of records which were read in batches, and the requests to the datastore which this saved
+pub async fn prefetch(
+	(opt, txn): (Option<&Options>, Option<&Transaction>),
+	_: (),
+) -> Result<Value, Error> {
+	match (opt, txn) {
+		(Some(opt), Some(txn)) => {
+			// Allowed to run?
+			opt.is_allowed(Action::View, ResourceKind::Any, &Base::Root)?;
+			// Fetch the batched read statistics
+			Ok(txn.lock().await.prefetches())
+		}
+		_ => Ok(Value::None),
+	}
+}
+
+/// Returns the background work configuration, and the share of writes performed by background work
+pub async fn background(
+	(opt, txn): (Option<&Options>, Option<&Transaction>),
+	_: (),
+) -> Result<Value, Error> {
+	match (opt, txn) {
+		(Some(opt), Some(txn)) => {
+			// Allowed to run?
+			opt.is_allowed(Action::View, ResourceKind::Any, &Base::Root)?;
+			// Fetch the background work statistics
+			Ok(txn.lock().await.background_stats())
+		}
+		_ => Ok(Value::None),
+	}
+}
+
+/// Limits the records per second which background work can write to a table, or to all tables
+pub async fn throttle(
+	(opt, txn): (Option<&Options>, Option<&Transaction>),
+	(table, rate): (Value, Value),
+) -> Result<Value, Error> {
+	match (opt, txn) {
+		(Some(opt), Some(txn)) => {
+			// Allowed to run?
+			opt.is_allowed(Action::Edit, ResourceKind::Any, &Base::Root)?;
+			// Check the specified rate
+			let rate = match rate {
+				Value::None | Value::Null => None,
+				Value::Number(v) if v.is_int() && v.to_int() >= 0 => Some(v.to_int() as u64),
+				_ => {
+					return Err(Error::InvalidArguments {
+						name: String::from("system::throttle"),
+						message: String::from(
+							"The second argument must be a positive integer, or NONE.",
+						),
+					})
+				}
+			};
+			let scheduler = txn.lock().await.scheduler();
+			match table {
+				// Set the limit for all tables without a specific limit
+				Value::None | Value::Null => scheduler.set_rate(rate.unwrap_or(0)),
+				// Set the limit for a specific table
+				Value::Strand(_) | Value::Table(_) => {
+					opt.valid_for_db()?;
+					let tb = table.as_raw_string();
+					scheduler.set_table_rate(opt.ns(), opt.db(), &tb, rate)
+				}
+				_ => {
+					return Err(Error::InvalidArguments {
+						name: String::from("system::throttle"),
+						message: String::from("The first argument must be a table name, or NONE."),
+					})
+				}
+			}
+			Ok(Value::None)
+		}
+		_ => Ok(Value::None),
+	}
+}
+
+/// Sets the priority with which a kind of background work is admitted
+pub async fn priority(
+	(opt, txn): (Option<&Options>, Option<&Transaction>),
+	(job, priority): (String, String),
+) -> Result<Value, Error> {
+	match (opt, txn) {
+		(Some(opt), Some(txn)) => {
+			// Allowed to run?
+			opt.is_allowed(Action::Edit, ResourceKind::Any, &Base::Root)?;
+			// Check the specified job and priority
+			let Some(job) = Job::from_name(&job) else {
+				return Err(Error::InvalidArguments {
+					name: String::from("system::priority"),
+					message: format!(
+						"Unknown background job '{job}'. Expected 'index', 'expiry', 'changefeed', 'backfill', 'enrichment', or 'computed'."
+					),
+				});
+			};
+			let Some(priority) = Priority::from_name(&priority) else {
+				return Err(Error::InvalidArguments {
+					name: String::from("system::priority"),
+					message: format!(
+						"Unknown priority '{priority}'. Expected 'low', 'normal', or 'high'."
+					),
+				});
+			};
+			txn.lock().await.scheduler().set_priority(job, priority);
+			Ok(Value::None)
+		}
+		_ => Ok(Value::None),
+	}
+}
@@ -0,0 +1,39 @@
+use crate::dbs::{Options, Transaction};
+use crate::err::Error;
+use crate::sql::{Datetime, Value};
+use crate::vs::to_u128_be;
+use chrono::DateTime;
+
+/// Returns the versionstamp of the changes which the current transaction makes to the database
+pub async fn versionstamp(
+	(opt, txn): (Option<&Options>, Option<&Transaction>),
+	_: (),
+) -> Result<Value, Error> {
+	match (opt, txn) {
+		(Some(opt), Some(txn)) => {
+			// Valid options?
+			opt.valid_for_db()?;
+			// Fetch the versionstamp
+			let vs = txn.lock().await.commit_versionstamp(opt.ns(), opt.db()).await?;
+			Ok(Value::from(to_u128_be(vs)))
+		}
+		_ => Ok(Value::None),
+	}
+}
+
+/// Returns the time of the current transaction, which is the same for every call within it
+pub async fn timestamp(
+	(_, txn): (Option<&Options>, Option<&Transaction>),
+	_: (),
+) -> Result<Value, Error> {
+	match txn {
+		Some(txn) => {
+			let ts = txn.lock().await.commit_timestamp().await;
+			match DateTime::from_timestamp_millis(ts as i64) {
+				Some(v) => Ok(Datetime::from(v).into()),
+				None => Err(Error::Internal(format!("Invalid transaction timestamp {ts}"))),
+			}
+		}
+		_ => Ok(Value::None),
+	}
+}
@@ -87,6 +87,11 @@ impl Auth {
 	pub fn has_role(&self, role: &Role) -> bool {
 		self.actor.has_role(role)
 	}
+
+	/// Returns the names of the user-defined roles of the current actor
+	pub fn custom_roles(&self) -> Vec<&str> {
+		self.actor.custom_roles()
+	}
 }
 
 impl std::convert::From<(&DefineUserStatement, Level)> for Auth {
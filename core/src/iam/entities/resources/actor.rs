@@ -61,6 +61,17 @@ impl Actor {
 		self.roles.contains(role)
 	}
 
+	/// Returns the names of the user-defined roles assigned to the actor.
+	pub fn custom_roles(&self) -> Vec<&str> {
+		self.roles
+			.iter()
+			.filter_map(|r| match r {
+				Role::Custom(v) => Some(v.as_str()),
+				_ => None,
+			})
+			.collect()
+	}
+
 	// Cedar policy helpers
 	pub fn cedar_attrs(&self) -> HashMap<String, RestrictedExpression> {
 		[
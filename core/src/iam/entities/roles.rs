@@ -5,8 +5,8 @@ use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
-// In the future, we will allow for custom roles. For now, provide predefined roles.
-#[revisioned(revision = 1)]
+// The predefined roles, along with any user-defined roles created with DEFINE ROLE
+#[revisioned(revision = 2)]
 #[derive(Hash, Clone, Default, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -15,6 +15,8 @@ pub enum Role {
 	Viewer,
 	Editor,
 	Owner,
+	#[revision(start = 2)]
+	Custom(String),
 }
 
 impl std::fmt::Display for Role {
@@ -23,6 +25,7 @@ impl std::fmt::Display for Role {
 			Self::Viewer => write!(f, "Viewer"),
 			Self::Editor => write!(f, "Editor"),
 			Self::Owner => write!(f, "Owner"),
+			Self::Custom(v) => write!(f, "{v}"),
 		}
 	}
 }
@@ -41,7 +44,7 @@ impl FromStr for Role {
 
 impl std::convert::From<&Ident> for Role {
 	fn from(id: &Ident) -> Self {
-		Role::from_str(id).unwrap()
+		Role::from_str(id).unwrap_or_else(|_| Self::Custom(id.to_raw()))
 	}
 }
 
@@ -9,7 +9,9 @@ pub mod clear;
 pub mod entities;
 #[cfg(feature = "jwks")]
 pub mod jwks;
+pub mod password;
 pub mod policies;
+pub mod session;
 pub mod signin;
 pub mod signup;
 pub mod token;
@@ -0,0 +1,244 @@
+use crate::cnf::{
+	PASSWORD_COMPLEXITY, PASSWORD_EXPIRY_DAYS, PASSWORD_HASH_ALGORITHM, PASSWORD_HASH_COST,
+	PASSWORD_MIN_LENGTH,
+};
+use crate::err::Error;
+use crate::kvs::{Datastore, LockType::*, TransactionType::*};
+use crate::sql::statements::DefineUserStatement;
+use crate::sql::{Base, Datetime};
+use argon2::{
+	password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+	Argon2, Params,
+};
+use chrono::{DateTime, Duration, Utc};
+use rand::rngs::OsRng;
+
+/// The algorithm used to hash system user passwords
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Hasher {
+	#[default]
+	Argon2,
+	Bcrypt,
+}
+
+/// The rules which system user passwords must satisfy
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct Policy {
+	/// The minimum number of characters in a password
+	pub min_length: usize,
+	/// Whether passwords must contain lowercase, uppercase, numeric, and symbol characters
+	pub complexity: bool,
+	/// The algorithm used to hash passwords
+	pub hasher: Hasher,
+	/// The hashing cost, or 0 to use the default cost of the algorithm
+	pub cost: u32,
+	/// The number of days after which passwords expire, or 0 to never expire
+	pub expiry_days: u32,
+}
+
+impl Policy {
+	/// The password policy configured for this server
+	pub fn current() -> Self {
+		Self {
+			min_length: *PASSWORD_MIN_LENGTH,
+			complexity: *PASSWORD_COMPLEXITY,
+			hasher: match PASSWORD_HASH_ALGORITHM.to_lowercase().as_str() {
+				"bcrypt" => Hasher::Bcrypt,
+				_ => Hasher::Argon2,
+			},
+			cost: *PASSWORD_HASH_COST,
+			expiry_days: *PASSWORD_EXPIRY_DAYS,
+		}
+	}
+
+	/// Check that a password satisfies this policy
+	pub fn check(&self, pass: &str) -> Result<(), Error> {
+		if pass.chars().count() < self.min_length {
+			return Err(Error::PasswordPolicy(format!(
+				"it must contain at least {} characters",
+				self.min_length
+			)));
+		}
+		if self.complexity
+			&& !(pass.chars().any(char::is_lowercase)
+				&& pass.chars().any(char::is_uppercase)
+				&& pass.chars().any(|c| c.is_ascii_digit())
+				&& pass.chars().any(|c| !c.is_alphanumeric()))
+		{
+			return Err(Error::PasswordPolicy(
+				"it must contain lowercase, uppercase, numeric, and symbol characters".to_string(),
+			));
+		}
+		Ok(())
+	}
+
+	/// Hash a password using the configured algorithm and cost
+	pub fn hash(&self, pass: &str) -> Result<String, Error> {
+		match self.hasher {
+			Hasher::Argon2 => {
+				let argon = match self.cost {
+					0 => Argon2::default(),
+					cost => Argon2::new(
+						argon2::Algorithm::default(),
+						argon2::Version::default(),
+						Params::new(Params::DEFAULT_M_COST, cost, Params::DEFAULT_P_COST, None)
+							.map_err(|e| Error::PasswordPolicy(e.to_string()))?,
+					),
+				};
+				argon
+					.hash_password(pass.as_bytes(), &SaltString::generate(&mut OsRng))
+					.map(|v| v.to_string())
+					.map_err(|e| Error::PasswordPolicy(e.to_string()))
+			}
+			Hasher::Bcrypt => {
+				let cost = match self.cost {
+					0 => bcrypt::DEFAULT_COST,
+					cost => cost,
+				};
+				bcrypt::hash(pass, cost).map_err(|e| Error::PasswordPolicy(e.to_string()))
+			}
+		}
+	}
+
+	/// The time at which the password of a user expires, if ever. The passwords of
+	/// users which were stored before password changes were tracked have expired.
+	pub fn expiry(&self, user: &DefineUserStatement) -> Option<Datetime> {
+		match (self.expiry_days, &user.pass_changed) {
+			(0, _) => None,
+			(_, None) => Some(Datetime(DateTime::UNIX_EPOCH)),
+			(days, Some(v)) => Some(Datetime(v.0 + Duration::days(days as i64))),
+		}
+	}
+
+	/// Check that a user is able to sign in without changing their password
+	pub fn check_user(&self, user: &DefineUserStatement) -> Result<(), Error> {
+		if user.must_change {
+			return Err(Error::PasswordMustChange);
+		}
+		match self.expiry(user) {
+			Some(v) if v.0 <= Utc::now() => Err(Error::PasswordExpired),
+			_ => Ok(()),
+		}
+	}
+}
+
+/// Verify a password against either an Argon2 or a bcrypt hash
+pub fn verify(pass: &str, hash: &str) -> Result<(), Error> {
+	let valid = match hash.starts_with("$2") {
+		true => bcrypt::verify(pass, hash).unwrap_or(false),
+		false => match PasswordHash::new(hash) {
+			Ok(hash) => Argon2::default().verify_password(pass.as_ref(), &hash).is_ok(),
+			Err(_) => false,
+		},
+	};
+	match valid {
+		true => Ok(()),
+		false => Err(Error::InvalidPass),
+	}
+}
+
+/// Change the password of a system user, which has already been verified
+pub async fn rotate(
+	kvs: &Datastore,
+	ns: Option<&str>,
+	db: Option<&str>,
+	mut user: DefineUserStatement,
+	pass: &str,
+	newpass: &str,
+) -> Result<DefineUserStatement, Error> {
+	let policy = Policy::current();
+	// Check the new password against the policy
+	policy.check(newpass)?;
+	// The new password must be different
+	if pass == newpass {
+		return Err(Error::PasswordPolicy(
+			"it must be different from the current password".to_string(),
+		));
+	}
+	// Update the user credentials
+	user.hash = policy.hash(newpass)?;
+	user.must_change = false;
+	user.pass_changed = Some(Datetime::default());
+	// Store the updated user
+	let key: Vec<u8> = match (&user.base, ns, db) {
+		(Base::Root, _, _) => crate::key::root::us::new(&user.name).into(),
+		(Base::Ns, Some(ns), _) => crate::key::namespace::us::new(ns, &user.name).into(),
+		(Base::Db, Some(ns), Some(db)) => crate::key::database::us::new(ns, db, &user.name).into(),
+		_ => return Err(Error::InvalidAuth),
+	};
+	let mut tx = kvs.transaction(Write, Optimistic).await?;
+	match tx.set(key, user.clone()).await {
+		Ok(_) => tx.commit().await?,
+		Err(e) => {
+			tx.cancel().await?;
+			return Err(e);
+		}
+	}
+	Ok(user)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_policy_check() {
+		let policy = Policy {
+			min_length: 8,
+			complexity: true,
+			..Default::default()
+		};
+		assert!(matches!(policy.check("Sh0rt!"), Err(Error::PasswordPolicy(_))));
+		assert!(matches!(policy.check("alllowercase1!"), Err(Error::PasswordPolicy(_))));
+		assert!(matches!(policy.check("NoDigitsHere!"), Err(Error::PasswordPolicy(_))));
+		assert!(matches!(policy.check("NoSymbols123"), Err(Error::PasswordPolicy(_))));
+		assert!(policy.check("Val1d-Passw0rd").is_ok());
+		assert!(Policy::default().check("").is_ok());
+	}
+
+	#[test]
+	fn test_policy_hash() {
+		let argon = Policy {
+			cost: 3,
+			..Default::default()
+		};
+		let hash = argon.hash("test").unwrap();
+		assert!(hash.starts_with("$argon2id$v=19$m=19456,t=3,p=1$"));
+		assert!(verify("test", &hash).is_ok());
+		assert!(verify("nonmatching", &hash).is_err());
+		let bcrypt = Policy {
+			hasher: Hasher::Bcrypt,
+			cost: 4,
+			..Default::default()
+		};
+		let hash = bcrypt.hash("test").unwrap();
+		assert!(hash.starts_with("$2b$04$"));
+		assert!(verify("test", &hash).is_ok());
+		assert!(verify("nonmatching", &hash).is_err());
+		// An invalid hash never verifies
+		assert!(verify("test", "invalid").is_err());
+	}
+
+	#[test]
+	fn test_policy_expiry() {
+		let policy = Policy {
+			expiry_days: 30,
+			..Default::default()
+		};
+		let mut user = DefineUserStatement::from((Base::Root, "test", "test"));
+		user.pass_changed = Some(Datetime(Utc::now() - Duration::days(10)));
+		assert!(policy.check_user(&user).is_ok());
+		assert!(Policy::default().expiry(&user).is_none());
+		user.pass_changed = Some(Datetime(Utc::now() - Duration::days(31)));
+		assert!(matches!(policy.check_user(&user), Err(Error::PasswordExpired)));
+		assert!(Policy::default().check_user(&user).is_ok());
+		// Users stored before password changes were tracked must change their password
+		user.pass_changed = None;
+		assert!(matches!(policy.check_user(&user), Err(Error::PasswordExpired)));
+		assert!(Policy::default().check_user(&user).is_ok());
+		user.must_change = true;
+		assert!(matches!(Policy::default().check_user(&user), Err(Error::PasswordMustChange)));
+	}
+}
@@ -0,0 +1,163 @@
+use crate::err::Error;
+use crate::kvs::{Datastore, LockType::*, Transaction, TransactionType::*};
+use crate::sql::statements::info::InfoStructure;
+use crate::sql::{Datetime, Object, Thing, Uuid, Value};
+use chrono::{DateTime, Utc};
+use derive::Store;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+
+/// A session which was issued to a scope user at signin or signup,
+/// which remains valid until it expires or is killed.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
+#[non_exhaustive]
+pub struct ScopeSession {
+	/// The id of the session, which is stored in the token as the `jti` claim
+	pub id: Uuid,
+	/// The scope which the session was issued for
+	pub sc: String,
+	/// The record which the session is authenticated as
+	pub rid: Thing,
+	/// The time at which the session was issued
+	pub issued_at: Datetime,
+	/// The time at which the session expires
+	pub expires_at: Option<Datetime>,
+	/// The IP address of the client which signed in or signed up
+	pub ip: Option<String>,
+}
+
+impl ScopeSession {
+	pub(crate) fn new(
+		id: uuid::Uuid,
+		sc: &str,
+		rid: Thing,
+		exp: Option<i64>,
+		ip: Option<String>,
+	) -> Self {
+		Self {
+			id: id.into(),
+			sc: sc.to_owned(),
+			rid,
+			issued_at: Datetime::default(),
+			expires_at: exp.and_then(|v| DateTime::from_timestamp(v, 0)).map(Datetime),
+			ip,
+		}
+	}
+
+	/// Check if this session has expired
+	pub fn expired(&self) -> bool {
+		match &self.expires_at {
+			Some(exp) => exp.0 < Utc::now(),
+			None => false,
+		}
+	}
+}
+
+impl InfoStructure for ScopeSession {
+	fn structure(self) -> Value {
+		let Self {
+			id,
+			sc,
+			rid,
+			issued_at,
+			expires_at,
+			ip,
+		} = self;
+		let mut acc = Object::default();
+
+		acc.insert("id".to_string(), id.into());
+
+		acc.insert("scope".to_string(), sc.into());
+
+		acc.insert("record".to_string(), rid.into());
+
+		acc.insert("issued_at".to_string(), issued_at.into());
+
+		if let Some(expires_at) = expires_at {
+			acc.insert("expires_at".to_string(), expires_at.into());
+		}
+
+		if let Some(ip) = ip {
+			acc.insert("ip".to_string(), ip.into());
+		}
+
+		Value::Object(acc)
+	}
+}
+
+/// Store a newly issued scope session
+pub(crate) async fn create(
+	kvs: &Datastore,
+	ns: &str,
+	db: &str,
+	se: &ScopeSession,
+) -> Result<(), Error> {
+	let id = se.id.to_raw();
+	let key = crate::key::database::se::new(ns, db, &id);
+	let mut tx = kvs.transaction(Write, Optimistic).await?;
+	match tx.set(key, se.clone()).await {
+		Ok(_) => tx.commit().await,
+		Err(e) => {
+			tx.cancel().await?;
+			Err(e)
+		}
+	}
+}
+
+/// Check that a scope session has not been killed
+pub(crate) async fn exists(
+	tx: &mut Transaction,
+	ns: &str,
+	db: &str,
+	id: &str,
+) -> Result<bool, Error> {
+	let key: Vec<u8> = crate::key::database::se::new(ns, db, id).into();
+	tx.exi(key).await
+}
+
+/// Fetch all of the scope sessions which have not expired
+pub(crate) async fn all(
+	tx: &mut Transaction,
+	ns: &str,
+	db: &str,
+	sc: &str,
+) -> Result<Vec<ScopeSession>, Error> {
+	let beg = crate::key::database::se::prefix(ns, db);
+	let end = crate::key::database::se::suffix(ns, db);
+	let val = tx.getr(beg..end, u32::MAX).await?;
+	Ok(val
+		.into_iter()
+		.map(|(_, v)| ScopeSession::from(v))
+		.filter(|v| v.sc == sc && !v.expired())
+		.collect())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::dbs::Session;
+
+	#[tokio::test]
+	async fn expired_sessions_are_deleted_on_tick() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let ses = Session::owner().with_ns("test").with_db("test");
+		ds.execute("DEFINE NAMESPACE test; DEFINE DATABASE test;", &ses, None).await.unwrap();
+		let now = Utc::now().timestamp();
+		let rid = Thing::from(("user", "tobie"));
+		let expired =
+			ScopeSession::new(uuid::Uuid::new_v4(), "sc", rid.clone(), Some(now - 60), None);
+		let active =
+			ScopeSession::new(uuid::Uuid::new_v4(), "sc", rid.clone(), Some(now + 60), None);
+		let forever = ScopeSession::new(uuid::Uuid::new_v4(), "sc", rid, None, None);
+		for se in [&expired, &active, &forever] {
+			create(&ds, "test", "test", se).await.unwrap();
+		}
+		ds.tick_at(now as u64).await.unwrap();
+		let mut tx = ds.transaction(Read, Optimistic).await.unwrap();
+		assert!(!exists(&mut tx, "test", "test", &expired.id.to_raw()).await.unwrap());
+		assert!(exists(&mut tx, "test", "test", &active.id.to_raw()).await.unwrap());
+		assert!(exists(&mut tx, "test", "test", &forever.id.to_raw()).await.unwrap());
+		tx.cancel().await.unwrap();
+	}
+}
@@ -1,8 +1,10 @@
+use super::password::{self, Policy};
 use super::verify::{verify_creds_legacy, verify_db_creds, verify_ns_creds, verify_root_creds};
 use super::{Actor, Level};
 use crate::cnf::{INSECURE_FORWARD_SCOPE_ERRORS, SERVER_NAME};
 use crate::dbs::Session;
 use crate::err::Error;
+use crate::iam::session::ScopeSession;
 use crate::iam::token::{Claims, HEADER};
 use crate::iam::Auth;
 use crate::kvs::{Datastore, LockType::*, TransactionType::*};
@@ -48,8 +50,9 @@ pub async fn signin(
 					let db = db.to_raw_string();
 					let user = user.to_raw_string();
 					let pass = pass.to_raw_string();
+					let newpass = vars.get("newpass").map(Value::to_raw_string);
 					// Attempt to signin to database
-					super::signin::db(kvs, session, ns, db, user, pass).await
+					super::signin::db(kvs, session, ns, db, user, pass, newpass).await
 				}
 				_ => Err(Error::MissingUserOrPass),
 			}
@@ -67,8 +70,9 @@ pub async fn signin(
 					let ns = ns.to_raw_string();
 					let user = user.to_raw_string();
 					let pass = pass.to_raw_string();
+					let newpass = vars.get("newpass").map(Value::to_raw_string);
 					// Attempt to signin to namespace
-					super::signin::ns(kvs, session, ns, user, pass).await
+					super::signin::ns(kvs, session, ns, user, pass, newpass).await
 				}
 				_ => Err(Error::MissingUserOrPass),
 			}
@@ -85,8 +89,9 @@ pub async fn signin(
 					// Process the provided values
 					let user = user.to_raw_string();
 					let pass = pass.to_raw_string();
+					let newpass = vars.get("newpass").map(Value::to_raw_string);
 					// Attempt to signin to root
-					super::signin::root(kvs, session, user, pass).await
+					super::signin::root(kvs, session, user, pass, newpass).await
 				}
 				_ => Err(Error::MissingUserOrPass),
 			}
@@ -151,22 +156,34 @@ pub async fn sc(
 									}
 									.timestamp(),
 								);
+								// Create the session identifier
+								let jti = Uuid::new_v4();
 								let val = Claims {
 									iss: Some(SERVER_NAME.to_owned()),
 									iat: Some(Utc::now().timestamp()),
 									nbf: Some(Utc::now().timestamp()),
 									exp,
-									jti: Some(Uuid::new_v4().to_string()),
+									jti: Some(jti.to_string()),
 									ns: Some(ns.to_owned()),
 									db: Some(db.to_owned()),
 									sc: Some(sc.to_owned()),
 									id: Some(rid.to_raw()),
+									session: Some(true),
 									..Claims::default()
 								};
 								// Log the authenticated scope info
 								trace!("Signing in to scope `{}`", sc);
 								// Create the authentication token
 								let enc = encode(&HEADER, &val, &key);
+								// Store the session so that it can be listed and killed
+								let se = ScopeSession::new(
+									jti,
+									&sc,
+									rid.clone(),
+									exp,
+									session.ip.clone(),
+								);
+								super::session::create(kvs, &ns, &db, &se).await?;
 								// Set the authentication on the session
 								session.tk = Some(val.into());
 								session.ns = Some(ns.to_owned());
@@ -209,6 +226,7 @@ pub async fn db(
 	db: String,
 	user: String,
 	pass: String,
+	newpass: Option<String>,
 ) -> Result<Option<String>, Error> {
 	let verify_creds = if kvs.is_auth_level_enabled() {
 		verify_db_creds(kvs, &ns, &db, &user, &pass).await
@@ -221,6 +239,17 @@ pub async fn db(
 	};
 	match verify_creds {
 		Ok(u) => {
+			let policy = Policy::current();
+			// Change the password if a new password was specified
+			let u = match newpass {
+				Some(newpass) => {
+					password::rotate(kvs, Some(&ns), Some(&db), u, &pass, &newpass).await?
+				}
+				None => {
+					policy.check_user(&u)?;
+					u
+				}
+			};
 			// Create the authentication key
 			let key = EncodingKey::from_secret(u.code.as_ref());
 			// Create the authentication claim
@@ -234,6 +263,7 @@ pub async fn db(
 				ns: Some(ns.to_owned()),
 				db: Some(db.to_owned()),
 				id: Some(user),
+				pass_exp: policy.expiry(&u).map(|v| v.0.timestamp()),
 				..Claims::default()
 			};
 			// Log the authenticated database info
@@ -247,6 +277,7 @@ pub async fn db(
 			// TODO(gguillemas): Enforce expiration once session lifetime can be customized.
 			session.exp = None;
 			session.au = Arc::new((&u, Level::Database(ns.to_owned(), db.to_owned())).into());
+			session.pr = u.priority;
 			// Check the authentication token
 			match enc {
 				// The auth token was created successfully
@@ -264,6 +295,7 @@ pub async fn ns(
 	ns: String,
 	user: String,
 	pass: String,
+	newpass: Option<String>,
 ) -> Result<Option<String>, Error> {
 	let verify_creds = if kvs.is_auth_level_enabled() {
 		verify_ns_creds(kvs, &ns, &user, &pass).await
@@ -276,6 +308,15 @@ pub async fn ns(
 	};
 	match verify_creds {
 		Ok(u) => {
+			let policy = Policy::current();
+			// Change the password if a new password was specified
+			let u = match newpass {
+				Some(newpass) => password::rotate(kvs, Some(&ns), None, u, &pass, &newpass).await?,
+				None => {
+					policy.check_user(&u)?;
+					u
+				}
+			};
 			// Create the authentication key
 			let key = EncodingKey::from_secret(u.code.as_ref());
 			// Create the authentication claim
@@ -288,6 +329,7 @@ pub async fn ns(
 				jti: Some(Uuid::new_v4().to_string()),
 				ns: Some(ns.to_owned()),
 				id: Some(user),
+				pass_exp: policy.expiry(&u).map(|v| v.0.timestamp()),
 				..Claims::default()
 			};
 			// Log the authenticated namespace info
@@ -300,6 +342,7 @@ pub async fn ns(
 			// TODO(gguillemas): Enforce expiration once session lifetime can be customized.
 			session.exp = None;
 			session.au = Arc::new((&u, Level::Namespace(ns.to_owned())).into());
+			session.pr = u.priority;
 			// Check the authentication token
 			match enc {
 				// The auth token was created successfully
@@ -317,6 +360,7 @@ pub async fn root(
 	session: &mut Session,
 	user: String,
 	pass: String,
+	newpass: Option<String>,
 ) -> Result<Option<String>, Error> {
 	let verify_creds = if kvs.is_auth_level_enabled() {
 		verify_root_creds(kvs, &user, &pass).await
@@ -329,6 +373,15 @@ pub async fn root(
 	};
 	match verify_creds {
 		Ok(u) => {
+			let policy = Policy::current();
+			// Change the password if a new password was specified
+			let u = match newpass {
+				Some(newpass) => password::rotate(kvs, None, None, u, &pass, &newpass).await?,
+				None => {
+					policy.check_user(&u)?;
+					u
+				}
+			};
 			// Create the authentication key
 			let key = EncodingKey::from_secret(u.code.as_ref());
 			// Create the authentication claim
@@ -340,6 +393,7 @@ pub async fn root(
 				exp,
 				jti: Some(Uuid::new_v4().to_string()),
 				id: Some(user),
+				pass_exp: policy.expiry(&u).map(|v| v.0.timestamp()),
 				..Claims::default()
 			};
 			// Log the authenticated root info
@@ -351,6 +405,7 @@ pub async fn root(
 			// TODO(gguillemas): Enforce expiration once session lifetime can be customized.
 			session.exp = None;
 			session.au = Arc::new((&u, Level::Root).into());
+			session.pr = u.priority;
 			// Check the authentication token
 			match enc {
 				// The auth token was created successfully
@@ -514,6 +569,7 @@ mod tests {
 				"test".to_string(),
 				"user".to_string(),
 				"pass".to_string(),
+				None,
 			)
 			.await;
 
@@ -553,6 +609,7 @@ mod tests {
 				"test".to_string(),
 				"user".to_string(),
 				"pass".to_string(),
+				None,
 			)
 			.await;
 
@@ -585,6 +642,7 @@ mod tests {
 				"test".to_string(),
 				"user".to_string(),
 				"invalid".to_string(),
+				None,
 			)
 			.await;
 
@@ -607,9 +665,15 @@ mod tests {
 				ns: Some("test".to_string()),
 				..Default::default()
 			};
-			let res =
-				ns(&ds, &mut sess, "test".to_string(), "user".to_string(), "pass".to_string())
-					.await;
+			let res = ns(
+				&ds,
+				&mut sess,
+				"test".to_string(),
+				"user".to_string(),
+				"pass".to_string(),
+				None,
+			)
+			.await;
 
 			assert!(res.is_ok(), "Failed to signin with credentials: {:?}", res);
 			assert_eq!(sess.ns, Some("test".to_string()));
@@ -637,9 +701,15 @@ mod tests {
 				ns: Some("test".to_string()),
 				..Default::default()
 			};
-			let res =
-				ns(&ds, &mut sess, "test".to_string(), "user".to_string(), "pass".to_string())
-					.await;
+			let res = ns(
+				&ds,
+				&mut sess,
+				"test".to_string(),
+				"user".to_string(),
+				"pass".to_string(),
+				None,
+			)
+			.await;
 
 			assert!(res.is_ok(), "Failed to signin with credentials: {:?}", res);
 			assert_eq!(sess.ns, Some("test".to_string()));
@@ -661,9 +731,15 @@ mod tests {
 			let mut sess = Session {
 				..Default::default()
 			};
-			let res =
-				ns(&ds, &mut sess, "test".to_string(), "user".to_string(), "invalid".to_string())
-					.await;
+			let res = ns(
+				&ds,
+				&mut sess,
+				"test".to_string(),
+				"user".to_string(),
+				"invalid".to_string(),
+				None,
+			)
+			.await;
 
 			assert!(res.is_err(), "Unexpected successful signin: {:?}", res);
 		}
@@ -683,7 +759,7 @@ mod tests {
 			let mut sess = Session {
 				..Default::default()
 			};
-			let res = root(&ds, &mut sess, "user".to_string(), "pass".to_string()).await;
+			let res = root(&ds, &mut sess, "user".to_string(), "pass".to_string(), None).await;
 
 			assert!(res.is_ok(), "Failed to signin with credentials: {:?}", res);
 			assert_eq!(sess.au.id(), "user");
@@ -708,7 +784,7 @@ mod tests {
 			let mut sess = Session {
 				..Default::default()
 			};
-			let res = root(&ds, &mut sess, "user".to_string(), "pass".to_string()).await;
+			let res = root(&ds, &mut sess, "user".to_string(), "pass".to_string(), None).await;
 
 			assert!(res.is_ok(), "Failed to signin with credentials: {:?}", res);
 			assert_eq!(sess.au.id(), "user");
@@ -728,9 +804,165 @@ mod tests {
 			let mut sess = Session {
 				..Default::default()
 			};
-			let res = root(&ds, &mut sess, "user".to_string(), "invalid".to_string()).await;
+			let res = root(&ds, &mut sess, "user".to_string(), "invalid".to_string(), None).await;
 
 			assert!(res.is_err(), "Unexpected successful signin: {:?}", res);
 		}
 	}
+
+	#[tokio::test]
+	async fn test_signin_password_must_change() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = Session::owner().with_ns("test").with_db("test");
+		ds.execute("DEFINE USER user ON DB PASSWORD 'pass' PASSWORD MUST CHANGE", &sess, None)
+			.await
+			.unwrap();
+
+		// Signin without changing the password
+		let mut sess = Session::default();
+		let mut vars: HashMap<&str, Value> = HashMap::new();
+		vars.insert("ns", "test".into());
+		vars.insert("db", "test".into());
+		vars.insert("user", "user".into());
+		vars.insert("pass", "pass".into());
+		let res = signin(&ds, &mut sess, vars.clone().into()).await;
+		assert!(matches!(res, Err(Error::PasswordMustChange)), "Unexpected result: {:?}", res);
+
+		// Signin with the same password
+		vars.insert("newpass", "pass".into());
+		let res = signin(&ds, &mut sess, vars.clone().into()).await;
+		assert!(matches!(res, Err(Error::PasswordPolicy(_))), "Unexpected result: {:?}", res);
+
+		// Signin with a new password
+		vars.insert("newpass", "newpass".into());
+		let res = signin(&ds, &mut sess, vars.clone().into()).await;
+		assert!(res.is_ok(), "Failed to signin with credentials: {:?}", res);
+		assert_eq!(sess.au.id(), "user");
+
+		// The old password is no longer valid
+		vars.remove("newpass");
+		let res = signin(&ds, &mut Session::default(), vars.clone().into()).await;
+		assert!(res.is_err(), "Unexpected successful signin: {:?}", res);
+
+		// The new password no longer needs to be changed
+		vars.insert("pass", "newpass".into());
+		let res = signin(&ds, &mut Session::default(), vars.into()).await;
+		assert!(res.is_ok(), "Failed to signin with credentials: {:?}", res);
+	}
+
+	#[tokio::test]
+	async fn test_signin_scope_session() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = Session::owner().with_ns("test").with_db("test");
+		ds.execute(
+			r#"
+			DEFINE SCOPE user SESSION 1h
+				SIGNIN (
+					SELECT * FROM user WHERE name = $user AND crypto::argon2::compare(pass, $pass)
+				);
+
+			CREATE user:test CONTENT {
+				name: 'user',
+				pass: crypto::argon2::generate('pass')
+			}
+			"#,
+			&sess,
+			None,
+		)
+		.await
+		.unwrap();
+
+		// Signin with the user
+		let mut vars: HashMap<&str, Value> = HashMap::new();
+		vars.insert("user", "user".into());
+		vars.insert("pass", "pass".into());
+		let mut user = Session {
+			ip: Some("127.0.0.1".to_string()),
+			..Default::default()
+		};
+		let tk = sc(
+			&ds,
+			&mut user,
+			"test".to_string(),
+			"test".to_string(),
+			"user".to_string(),
+			vars.into(),
+		)
+		.await
+		.unwrap()
+		.unwrap();
+
+		// The session is listed for the scope
+		let res = &mut ds.execute("INFO FOR SCOPE user", &sess, None).await.unwrap();
+		let sessions = res.remove(0).result.unwrap().pick(&["sessions".into()]);
+		let Value::Array(sessions) = sessions else {
+			panic!("Expected an array of sessions, got {sessions}");
+		};
+		assert_eq!(sessions.len(), 1);
+		let id = sessions[0].pick(&["id".into()]);
+		assert_eq!(sessions[0].pick(&["record".into()]).to_string(), "user:test");
+		assert_eq!(sessions[0].pick(&["ip".into()]).to_raw_string(), "127.0.0.1");
+
+		// The token can be used while the session is active
+		let res = crate::iam::verify::token(&ds, &mut Session::default(), &tk).await;
+		assert!(res.is_ok(), "Failed to authenticate with token: {:?}", res);
+		let res = crate::iam::verify::session(&ds, &user).await;
+		assert!(res.is_ok(), "Failed to restore the session: {:?}", res);
+
+		// The scope user is able to kill their own session
+		let vars = map! { "id".to_string() => id };
+		let res = &mut ds.execute("KILL SESSION $id", &user, Some(vars)).await.unwrap();
+		assert!(res.remove(0).result.is_ok());
+
+		// The token can no longer be used
+		let res = crate::iam::verify::token(&ds, &mut Session::default(), &tk).await;
+		assert!(res.is_err(), "Unexpected successful authentication: {:?}", res);
+
+		// The session can no longer be restored
+		let res = crate::iam::verify::session(&ds, &user).await;
+		assert!(res.is_err(), "Unexpected successful restore: {:?}", res);
+	}
+
+	#[tokio::test]
+	async fn test_signin_scope_session_expiry() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = Session::owner().with_ns("test").with_db("test");
+		ds.execute(
+			r#"
+			DEFINE SCOPE user SESSION 1h
+				SIGNIN (
+					SELECT * FROM user WHERE name = $user AND crypto::argon2::compare(pass, $pass)
+				);
+
+			CREATE user:test CONTENT {
+				name: 'user',
+				pass: crypto::argon2::generate('pass')
+			}
+			"#,
+			&sess,
+			None,
+		)
+		.await
+		.unwrap();
+
+		// Signin with the user
+		let mut vars: HashMap<&str, Value> = HashMap::new();
+		vars.insert("user", "user".into());
+		vars.insert("pass", "pass".into());
+		let mut user = Session::default();
+		sc(&ds, &mut user, "test".to_string(), "test".to_string(), "user".to_string(), vars.into())
+			.await
+			.unwrap();
+		let jti = user.tk.as_ref().unwrap().pick(&["jti".into()]).to_raw_string();
+		let mut tx = ds.transaction(Read, Optimistic).await.unwrap();
+		assert!(crate::iam::session::exists(&mut tx, "test", "test", &jti).await.unwrap());
+		tx.cancel().await.unwrap();
+
+		// The session is deleted once it has expired
+		let ts = (Utc::now() + Duration::hours(2)).timestamp() as u64;
+		ds.tick_at(ts).await.unwrap();
+		let mut tx = ds.transaction(Read, Optimistic).await.unwrap();
+		assert!(!crate::iam::session::exists(&mut tx, "test", "test", &jti).await.unwrap());
+		tx.cancel().await.unwrap();
+	}
 }
@@ -1,6 +1,7 @@
 use crate::cnf::{INSECURE_FORWARD_SCOPE_ERRORS, SERVER_NAME};
 use crate::dbs::Session;
 use crate::err::Error;
+use crate::iam::session::ScopeSession;
 use crate::iam::token::{Claims, HEADER};
 use crate::iam::Auth;
 use crate::iam::{Actor, Level};
@@ -91,22 +92,34 @@ pub async fn sc(
 									}
 									.timestamp(),
 								);
+								// Create the session identifier
+								let jti = Uuid::new_v4();
 								let val = Claims {
 									iss: Some(SERVER_NAME.to_owned()),
 									iat: Some(Utc::now().timestamp()),
 									nbf: Some(Utc::now().timestamp()),
-									jti: Some(Uuid::new_v4().to_string()),
+									jti: Some(jti.to_string()),
 									exp,
 									ns: Some(ns.to_owned()),
 									db: Some(db.to_owned()),
 									sc: Some(sc.to_owned()),
 									id: Some(rid.to_raw()),
+									session: Some(true),
 									..Claims::default()
 								};
 								// Log the authenticated scope info
 								trace!("Signing up to scope `{}`", sc);
 								// Create the authentication token
 								let enc = encode(&HEADER, &val, &key);
+								// Store the session so that it can be listed and killed
+								let se = ScopeSession::new(
+									jti,
+									&sc,
+									rid.clone(),
+									exp,
+									session.ip.clone(),
+								);
+								super::session::create(kvs, &ns, &db, &se).await?;
 								// Set the authentication on the session
 								session.tk = Some(val.into());
 								session.ns = Some(ns.to_owned());
@@ -63,6 +63,20 @@ pub struct Claims {
 	#[serde(alias = "https://surrealdb.com/roles")]
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub roles: Option<Vec<String>>,
+	#[serde(alias = "pe")]
+	#[serde(alias = "PE")]
+	#[serde(rename = "PE")]
+	#[serde(alias = "https://surrealdb.com/pe")]
+	#[serde(alias = "https://surrealdb.com/password_expiry")]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub pass_exp: Option<i64>,
+	#[serde(alias = "se")]
+	#[serde(alias = "SE")]
+	#[serde(rename = "SE")]
+	#[serde(alias = "https://surrealdb.com/se")]
+	#[serde(alias = "https://surrealdb.com/session")]
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub session: Option<bool>,
 
 	#[serde(flatten)]
 	#[serde(skip_serializing_if = "Option::is_none")]
@@ -117,6 +131,14 @@ impl From<Claims> for Value {
 		if let Some(role) = v.roles {
 			out.insert("RL".to_string(), role.into());
 		}
+		// Add PE field if set
+		if let Some(pe) = v.pass_exp {
+			out.insert("PE".to_string(), pe.into());
+		}
+		// Add SE field if set
+		if let Some(se) = v.session {
+			out.insert("SE".to_string(), se.into());
+		}
 		// Add custom claims if set
 		if let Some(custom_claims) = v.custom_claims {
 			for (claim, value) in custom_claims {
@@ -2,11 +2,10 @@ use crate::dbs::Session;
 use crate::err::Error;
 #[cfg(feature = "jwks")]
 use crate::iam::jwks;
-use crate::iam::{token::Claims, Actor, Auth, Level, Role};
+use crate::iam::{password::Policy, token::Claims, Actor, Auth, Level, Role};
 use crate::kvs::{Datastore, LockType::*, TransactionType::*};
-use crate::sql::{statements::DefineUserStatement, Algorithm, Value};
+use crate::sql::{statements::DefineUserStatement, Algorithm, Part, Value};
 use crate::syn;
-use argon2::{Argon2, PasswordHash, PasswordVerifier};
 use chrono::Utc;
 use jsonwebtoken::{decode, DecodingKey, Header, Validation};
 use once_cell::sync::Lazy;
@@ -121,10 +120,13 @@ pub async fn basic(
 		// DB signin
 		(Some(ns), Some(db)) => match verify_db_creds(kvs, ns, db, user, pass).await {
 			Ok(u) => {
+				// Check that the password does not need to be changed
+				Policy::current().check_user(&u)?;
 				debug!("Authenticated as database user '{}'", user);
 				// TODO(gguillemas): Enforce expiration once session lifetime can be customized.
 				session.exp = None;
 				session.au = Arc::new((&u, Level::Database(ns.to_owned(), db.to_owned())).into());
+				session.pr = u.priority;
 				Ok(())
 			}
 			Err(err) => Err(err),
@@ -132,10 +134,13 @@ pub async fn basic(
 		// NS signin
 		(Some(ns), None) => match verify_ns_creds(kvs, ns, user, pass).await {
 			Ok(u) => {
+				// Check that the password does not need to be changed
+				Policy::current().check_user(&u)?;
 				debug!("Authenticated as namespace user '{}'", user);
 				// TODO(gguillemas): Enforce expiration once session lifetime can be customized.
 				session.exp = None;
 				session.au = Arc::new((&u, Level::Namespace(ns.to_owned())).into());
+				session.pr = u.priority;
 				Ok(())
 			}
 			Err(err) => Err(err),
@@ -143,10 +148,13 @@ pub async fn basic(
 		// Root signin
 		(None, None) => match verify_root_creds(kvs, user, pass).await {
 			Ok(u) => {
+				// Check that the password does not need to be changed
+				Policy::current().check_user(&u)?;
 				debug!("Authenticated as root user '{}'", user);
 				// TODO(gguillemas): Enforce expiration once session lifetime can be customized.
 				session.exp = None;
 				session.au = Arc::new((&u, Level::Root).into());
+				session.pr = u.priority;
 				Ok(())
 			}
 			Err(err) => Err(err),
@@ -166,25 +174,34 @@ pub async fn basic_legacy(
 	trace!("Attempting legacy basic authentication");
 
 	match verify_creds_legacy(kvs, session.ns.as_ref(), session.db.as_ref(), user, pass).await {
-		Ok((au, _)) if au.is_root() => {
+		Ok((au, u)) if au.is_root() => {
+			// Check that the password does not need to be changed
+			Policy::current().check_user(&u)?;
 			debug!("Authenticated as root user '{}'", user);
 			// TODO(gguillemas): Enforce expiration once session lifetime can be customized.
 			session.exp = None;
 			session.au = Arc::new(au);
+			session.pr = u.priority;
 			Ok(())
 		}
-		Ok((au, _)) if au.is_ns() => {
+		Ok((au, u)) if au.is_ns() => {
+			// Check that the password does not need to be changed
+			Policy::current().check_user(&u)?;
 			debug!("Authenticated as namespace user '{}'", user);
 			// TODO(gguillemas): Enforce expiration once session lifetime can be customized.
 			session.exp = None;
 			session.au = Arc::new(au);
+			session.pr = u.priority;
 			Ok(())
 		}
-		Ok((au, _)) if au.is_db() => {
+		Ok((au, u)) if au.is_db() => {
+			// Check that the password does not need to be changed
+			Policy::current().check_user(&u)?;
 			debug!("Authenticated as database user '{}'", user);
 			// TODO(gguillemas): Enforce expiration once session lifetime can be customized.
 			session.exp = None;
 			session.au = Arc::new(au);
+			session.pr = u.priority;
 			Ok(())
 		}
 		Ok(_) => Err(Error::InvalidAuth),
@@ -274,6 +291,18 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 			let cf = config_alg(Algorithm::Hs512, de.code)?;
 			// Verify the token
 			decode::<Claims>(token, &cf.0, &cf.1)?;
+			// Check that the session has not been killed, for
+			// tokens which were issued with a tracked session
+			if token_data.claims.session == Some(true) {
+				let tracked = match &token_data.claims.jti {
+					Some(jti) => super::session::exists(&mut tx, &ns, &db, jti).await?,
+					None => false,
+				};
+				if !tracked {
+					trace!("The session for the authentication token has been killed");
+					return Err(Error::InvalidAuth);
+				}
+			}
 			// Log the success
 			debug!("Authenticated to scope `{}`", sc);
 			// Set the session
@@ -359,6 +388,7 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 			session.ns = Some(ns.to_owned());
 			session.db = Some(db.to_owned());
 			session.exp = token_data.claims.exp;
+			session.pr = de.priority;
 			session.au = Arc::new(Auth::new(Actor::new(
 				id.to_string(),
 				de.roles.iter().map(|r| r.into()).collect(),
@@ -428,6 +458,7 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 			session.tk = Some(value);
 			session.ns = Some(ns.to_owned());
 			session.exp = token_data.claims.exp;
+			session.pr = de.priority;
 			session.au = Arc::new(Auth::new(Actor::new(
 				id.to_string(),
 				de.roles.iter().map(|r| r.into()).collect(),
@@ -457,6 +488,7 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 			// Set the session
 			session.tk = Some(value);
 			session.exp = token_data.claims.exp;
+			session.pr = de.priority;
 			session.au = Arc::new(Auth::new(Actor::new(
 				id.to_string(),
 				de.roles.iter().map(|r| r.into()).collect(),
@@ -469,6 +501,67 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 	}
 }
 
+/// Check that a session which was restored from a snapshot is still valid against the current
+/// definitions, so that a user, token, or scope which was removed or changed since the snapshot
+/// was taken can no longer be used.
+pub async fn session(kvs: &Datastore, session: &Session) -> Result<(), Error> {
+	// Check that the session has not expired
+	if session.expired() {
+		return Err(Error::ExpiredSession);
+	}
+	// Create a new readonly transaction
+	let mut tx = kvs.transaction(Read, Optimistic).await?;
+	// System sessions are not authenticated with a definition
+	if session.au.id() == "system_auth" {
+		return Ok(());
+	}
+	// The actor id of a session which signed in is the escaped name of the definition
+	let name = match syn::idiom(session.au.id()).map(|v| v.0) {
+		Ok(v) => match v.as_slice() {
+			[Part::Field(v)] => v.to_raw(),
+			_ => session.au.id().to_owned(),
+		},
+		Err(_) => session.au.id().to_owned(),
+	};
+	// Whether the session was authenticated with a token definition, rather than a user
+	let token = matches!(&session.tk, Some(Value::Object(v)) if v.contains_key("TK"));
+	// Check that the definition which authenticated the session still exists
+	let user = match session.au.level() {
+		Level::No => return Ok(()),
+		Level::Root => tx.get_root_user(&name).await?,
+		Level::Namespace(ns) if token => {
+			tx.get_ns_token(ns, &name).await?;
+			return Ok(());
+		}
+		Level::Namespace(ns) => tx.get_ns_user(ns, &name).await?,
+		Level::Database(ns, db) if token => {
+			tx.get_db_token(ns, db, &name).await?;
+			return Ok(());
+		}
+		Level::Database(ns, db) => tx.get_db_user(ns, db, &name).await?,
+		Level::Scope(ns, db, sc) => {
+			tx.get_sc(ns, db, sc).await?;
+			// Check that a tracked session has not been killed
+			let tk = session.tk.as_ref().unwrap_or(&Value::None);
+			if tk.pick(&[Part::from("SE")]).is_true() {
+				let tracked = match tk.pick(&[Part::from("jti")]) {
+					Value::Strand(jti) => super::session::exists(&mut tx, ns, db, &jti).await?,
+					_ => false,
+				};
+				if !tracked {
+					return Err(Error::InvalidAuth);
+				}
+			}
+			return Ok(());
+		}
+	};
+	// Check that the user still has the same roles
+	match Auth::from((&user, session.au.level().clone())) == *session.au {
+		true => Ok(()),
+		false => Err(Error::InvalidAuth),
+	}
+}
+
 pub async fn verify_root_creds(
 	ds: &Datastore,
 	user: &str,
@@ -527,13 +620,8 @@ pub async fn verify_db_creds(
 }
 
 fn verify_pass(pass: &str, hash: &str) -> Result<(), Error> {
-	// Compute the hash and verify the password
-	let hash = PasswordHash::new(hash).unwrap();
-	// Attempt to verify the password using Argon2
-	match Argon2::default().verify_password(pass.as_ref(), &hash) {
-		Ok(_) => Ok(()),
-		_ => Err(Error::InvalidPass),
-	}
+	// Verify the password using the algorithm of the hash
+	super::password::verify(pass, hash)
 }
 
 // TODO(gguillemas): Remove this method once the legacy authentication is deprecated in v2.0.0
@@ -583,6 +671,7 @@ mod tests {
 	use super::*;
 	use crate::iam::token::HEADER;
 	use argon2::password_hash::{PasswordHasher, SaltString};
+	use argon2::Argon2;
 	use chrono::Duration;
 	use jsonwebtoken::{encode, EncodingKey};
 
@@ -805,6 +894,21 @@ mod tests {
 		}
 	}
 
+	#[tokio::test]
+	async fn test_basic_legacy_password_must_change() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = Session::owner().with_ns("test").with_db("test");
+		ds.execute("DEFINE USER user ON DB PASSWORD 'pass' PASSWORD MUST CHANGE", &sess, None)
+			.await
+			.unwrap();
+
+		let mut sess = Session::default().with_ns("test").with_db("test");
+		let res = basic_legacy(&ds, &mut sess, "user", "pass").await;
+
+		assert!(matches!(res, Err(Error::PasswordMustChange)), "Unexpected result: {:?}", res);
+		assert!(sess.au.is_anon());
+	}
+
 	#[tokio::test]
 	async fn test_token_ns() {
 		let secret = "jwt_secret";
@@ -1522,6 +1626,54 @@ mod tests {
 		}
 	}
 
+	#[tokio::test]
+	async fn test_token_scope_session() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = Session::owner().with_ns("test").with_db("test");
+		ds.execute("DEFINE SCOPE test SESSION 1h", &sess, None).await.unwrap();
+		// Sign tokens with the key of the scope
+		let mut tx = ds.transaction(Read, Optimistic).await.unwrap();
+		let sc = tx.get_sc("test", "test", "test").await.unwrap();
+		tx.cancel().await.unwrap();
+		let key = EncodingKey::from_secret(sc.code.as_ref());
+		let claims = Claims {
+			iat: Some(Utc::now().timestamp()),
+			nbf: Some(Utc::now().timestamp()),
+			exp: Some((Utc::now() + Duration::hours(1)).timestamp()),
+			jti: Some(uuid::Uuid::new_v4().to_string()),
+			ns: Some("test".to_string()),
+			db: Some("test".to_string()),
+			sc: Some("test".to_string()),
+			id: Some("user:test".to_string()),
+			..Claims::default()
+		};
+
+		//
+		// Test a token which was issued without a tracked session
+		//
+		{
+			let enc = encode(&HEADER, &claims, &key).unwrap();
+			let mut sess = Session::default();
+			let res = token(&ds, &mut sess, &enc).await;
+
+			assert!(res.is_ok(), "Failed to signin with token: {:?}", res);
+			assert!(sess.au.is_scope());
+		}
+
+		//
+		// Test a token whose tracked session does not exist
+		//
+		{
+			let mut claims = claims.clone();
+			claims.session = Some(true);
+			let enc = encode(&HEADER, &claims, &key).unwrap();
+			let mut sess = Session::default();
+			let res = token(&ds, &mut sess, &enc).await;
+
+			assert!(res.is_err(), "Unexpected success signing in with token: {:?}", res);
+		}
+	}
+
 	#[test]
 	fn test_verify_pass() {
 		let salt = SaltString::generate(&mut rand::thread_rng());
@@ -1,10 +1,13 @@
 use crate::err::Error;
 use crate::idx::ft::analyzer::tokenizer::Tokens;
 use crate::idx::ft::offsets::Position;
+use crate::kvs::Transaction;
 use crate::sql::filter::Filter as SqlFilter;
 use crate::sql::language::Language;
+use crate::sql::statements::DictionaryKind;
 use deunicode::deunicode;
 use rust_stemmers::{Algorithm, Stemmer};
+use std::collections::{HashMap, HashSet};
 
 #[derive(Clone, Copy)]
 pub(super) enum FilteringStage {
@@ -18,11 +21,18 @@ pub(super) enum Filter {
 	EdgeNgram(u16, u16),
 	Lowercase,
 	Uppercase,
+	Stopwords(HashSet<String>),
+	Synonyms(HashMap<String, String>),
 }
 
-impl From<SqlFilter> for Filter {
-	fn from(f: SqlFilter) -> Self {
-		match f {
+impl Filter {
+	async fn try_from(
+		tx: &mut Transaction,
+		ns: &str,
+		db: &str,
+		f: SqlFilter,
+	) -> Result<Self, Error> {
+		let f = match f {
 			SqlFilter::Ascii => Filter::Ascii,
 			SqlFilter::EdgeNgram(min, max) => Filter::EdgeNgram(min, max),
 			SqlFilter::Lowercase => Filter::Lowercase,
@@ -50,17 +60,56 @@ impl From<SqlFilter> for Filter {
 				Filter::Stemmer(a)
 			}
 			SqlFilter::Uppercase => Filter::Uppercase,
-		}
+			SqlFilter::Stopwords(name) => match tx.get_db_dictionary(ns, db, &name).await?.kind {
+				DictionaryKind::Stopwords(terms) => {
+					Filter::Stopwords(terms.into_iter().map(|t| t.0).collect())
+				}
+				_ => {
+					return Err(Error::DcInvalidKind {
+						value: name.to_raw(),
+						kind: "stopwords".to_owned(),
+					})
+				}
+			},
+			SqlFilter::Synonyms(name) => match tx.get_db_dictionary(ns, db, &name).await?.kind {
+				DictionaryKind::Synonyms(groups) => {
+					// Every term of a group is replaced with the first term of the group
+					let mut synonyms = HashMap::new();
+					for group in groups {
+						let mut terms = group.into_iter();
+						if let Some(first) = terms.next() {
+							for term in terms {
+								synonyms.insert(term.0, first.0.clone());
+							}
+						}
+					}
+					Filter::Synonyms(synonyms)
+				}
+				_ => {
+					return Err(Error::DcInvalidKind {
+						value: name.to_raw(),
+						kind: "synonyms".to_owned(),
+					})
+				}
+			},
+		};
+		Ok(f)
 	}
-}
 
-impl Filter {
-	pub(super) fn from(fs: Option<Vec<SqlFilter>>) -> Option<Vec<Filter>> {
+	pub(super) async fn from(
+		tx: &mut Transaction,
+		ns: &str,
+		db: &str,
+		fs: Option<Vec<SqlFilter>>,
+	) -> Result<Option<Vec<Filter>>, Error> {
 		if let Some(fs) = fs {
-			let r = fs.into_iter().map(|f| f.into()).collect();
-			Some(r)
+			let mut r = Vec::with_capacity(fs.len());
+			for f in fs {
+				r.push(Self::try_from(tx, ns, db, f).await?);
+			}
+			Ok(Some(r))
 		} else {
-			None
+			Ok(None)
 		}
 	}
 
@@ -95,6 +144,8 @@ impl Filter {
 			Filter::Ngram(min, max) => Self::ngram(c, *min, *max),
 			Filter::Stemmer(s) => Self::stem(s, c),
 			Filter::Uppercase => Self::uppercase(c),
+			Filter::Stopwords(s) => Self::stopwords(s, c),
+			Filter::Synonyms(s) => Self::synonyms(s, c),
 		}
 	}
 
@@ -119,6 +170,23 @@ impl Filter {
 		Self::check_term(c, c.to_lowercase())
 	}
 
+	#[inline]
+	fn stopwords(s: &HashSet<String>, c: &str) -> FilterResult {
+		if s.contains(c) {
+			FilterResult::Ignore
+		} else {
+			FilterResult::Term(Term::Unchanged)
+		}
+	}
+
+	#[inline]
+	fn synonyms(s: &HashMap<String, String>, c: &str) -> FilterResult {
+		match s.get(c) {
+			Some(t) => FilterResult::Term(Term::NewTerm(t.clone(), 0)),
+			None => FilterResult::Term(Term::Unchanged),
+		}
+	}
+
 	#[inline]
 	fn deunicode(c: &str) -> FilterResult {
 		Self::check_term(c, deunicode(c))
@@ -196,8 +264,7 @@ mod tests {
 
 	#[tokio::test]
 	async fn test_arabic_stemmer() {
-		let input =
-			"الكلاب تحب الجري في الحديقة، لكن كلبي الصغير يفضل النوم في سريره بدلاً من الجري";
+		let input = "الكلاب تحب الجري في الحديقة، لكن كلبي الصغير يفضل النوم في سريره بدلاً من الجري";
 		let output = vec![
 			"كلاب", "تحب", "الجر", "في", "حديق", "لكن", "كلب", "صغير", "يفضل", "نوم", "في", "سرير",
 			"بدل", "من", "الجر",
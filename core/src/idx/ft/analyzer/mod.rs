@@ -7,6 +7,7 @@ use crate::idx::ft::doclength::DocLength;
 use crate::idx::ft::offsets::{Offset, OffsetRecords};
 use crate::idx::ft::postings::TermFrequency;
 use crate::idx::ft::terms::{TermId, TermLen, Terms};
+use crate::kvs;
 use crate::sql::statements::DefineAnalyzerStatement;
 use crate::sql::tokenizer::Tokenizer as SqlTokenizer;
 use crate::sql::Value;
@@ -25,16 +26,6 @@ pub(crate) struct Analyzer {
 	filters: Option<Vec<Filter>>,
 }
 
-impl From<DefineAnalyzerStatement> for Analyzer {
-	fn from(az: DefineAnalyzerStatement) -> Self {
-		Self {
-			function: az.function.map(|i| i.0),
-			tokenizers: az.tokenizers,
-			filters: Filter::from(az.filters),
-		}
-	}
-}
-
 pub(in crate::idx) type TermsList = Vec<Option<(TermId, TermLen)>>;
 
 pub(in crate::idx) struct TermsSet {
@@ -59,6 +50,20 @@ impl TermsSet {
 }
 
 impl Analyzer {
+	/// Build an analyzer, loading any dictionaries which are used by its filters
+	pub(crate) async fn new(
+		tx: &mut kvs::Transaction,
+		ns: &str,
+		db: &str,
+		az: DefineAnalyzerStatement,
+	) -> Result<Self, Error> {
+		Ok(Self {
+			function: az.function.map(|i| i.0),
+			tokenizers: az.tokenizers,
+			filters: Filter::from(tx, ns, db, az.filters).await?,
+		})
+	}
+
 	pub(super) async fn extract_querying_terms(
 		&self,
 		stk: &mut Stk,
@@ -309,6 +314,23 @@ impl Analyzer {
 		Ok(Tokens::new(input))
 	}
 
+	/// Extracts the term which is being typed, which is the last term of the input
+	pub(in crate::idx) async fn extract_suggesting_term(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		content: String,
+	) -> Result<Option<String>, Error> {
+		let tokens =
+			self.generate_tokens(stk, ctx, opt, txn, FilteringStage::Querying, content).await?;
+		match tokens.list().last() {
+			Some(token) => Ok(Some(tokens.get_token_string(token)?.to_owned())),
+			None => Ok(None),
+		}
+	}
+
 	/// Used for exposing the analyzer as the native function `search::analyze`
 	pub(crate) async fn analyze(
 		&self,
@@ -346,7 +368,7 @@ mod tests {
 		let Some(Statement::Define(DefineStatement::Analyzer(az))) = stmt.0 .0.pop() else {
 			panic!()
 		};
-		let a: Analyzer = az.into();
+		let a = Analyzer::new(&mut *txn.lock().await, "testns", "testdb", az).await.unwrap();
 
 		let mut stack = reblessive::TreeStack::new();
 
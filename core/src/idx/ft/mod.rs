@@ -4,6 +4,7 @@ mod highlighter;
 mod offsets;
 mod postings;
 pub(super) mod scorer;
+mod suggester;
 pub(super) mod termdocs;
 pub(crate) mod terms;
 
@@ -17,6 +18,7 @@ use crate::idx::ft::highlighter::{Highlighter, Offseter};
 use crate::idx::ft::offsets::Offsets;
 use crate::idx::ft::postings::Postings;
 use crate::idx::ft::scorer::BM25Scorer;
+use crate::idx::ft::suggester::{Suggester, Suggestion};
 use crate::idx::ft::termdocs::{TermDocs, TermsDocs};
 use crate::idx::ft::terms::{TermId, TermLen, Terms};
 use crate::idx::trees::btree::BStatistics;
@@ -26,7 +28,6 @@ use crate::kvs;
 use crate::kvs::{Key, TransactionType};
 use crate::sql::index::SearchParams;
 use crate::sql::scoring::Scoring;
-use crate::sql::statements::DefineAnalyzerStatement;
 use crate::sql::{Idiom, Object, Thing, Value};
 use reblessive::tree::Stk;
 use revision::revisioned;
@@ -108,12 +109,13 @@ impl FtIndex {
 	) -> Result<Self, Error> {
 		let mut tx = txn.lock().await;
 		let az = tx.get_db_analyzer(opt.ns(), opt.db(), az).await?;
+		let az = Analyzer::new(&mut tx, opt.ns(), opt.db(), az).await?;
 		Self::with_analyzer(ixs, &mut tx, az, index_key_base, p, tt).await
 	}
 	async fn with_analyzer(
 		ixs: &IndexStores,
 		run: &mut kvs::Transaction,
-		az: DefineAnalyzerStatement,
+		az: Analyzer,
 		index_key_base: IndexKeyBase,
 		p: &SearchParams,
 		tt: TransactionType,
@@ -165,7 +167,7 @@ impl FtIndex {
 			index_key_base,
 			bm25,
 			highlighting: p.hl,
-			analyzer: Arc::new(az.into()),
+			analyzer: Arc::new(az),
 			doc_ids,
 			doc_lengths,
 			postings,
@@ -350,6 +352,41 @@ impl FtIndex {
 		Ok(res)
 	}
 
+	/// Suggests the indexed terms which are the closest to the term being typed
+	pub(crate) async fn suggest(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		input: String,
+		limit: usize,
+	) -> Result<Vec<String>, Error> {
+		let Some(input) = self.analyzer.extract_suggesting_term(stk, ctx, opt, txn, input).await?
+		else {
+			return Ok(vec![]);
+		};
+		let suggester = Suggester::new(&input);
+		let mut tx = txn.lock().await;
+		let terms = self.terms.read().await.all_terms(&mut tx).await?;
+		let mut suggestions = Vec::new();
+		for (term_id, term) in terms {
+			if let Some(distance) = suggester.distance(&term) {
+				let docs = match self.term_docs.get_docs(&mut tx, term_id).await? {
+					Some(docs) => docs.len(),
+					None => continue,
+				};
+				suggestions.push(Suggestion {
+					term,
+					distance,
+					docs,
+				});
+			}
+		}
+		Suggestion::sort(&mut suggestions);
+		Ok(suggestions.into_iter().take(limit).map(|s| s.term).collect())
+	}
+
 	pub(super) async fn get_terms_docs(
 		&self,
 		tx: &mut kvs::Transaction,
@@ -507,6 +544,7 @@ impl HitsIterator {
 mod tests {
 	use crate::ctx::Context;
 	use crate::dbs::{Options, Transaction};
+	use crate::idx::ft::analyzer::Analyzer;
 	use crate::idx::ft::scorer::{BM25Scorer, Score};
 	use crate::idx::ft::{FtIndex, HitsIterator};
 	use crate::idx::IndexKeyBase;
@@ -573,10 +611,11 @@ mod tests {
 		let tx = ds.transaction(tt, Optimistic).await.unwrap();
 		let txn = Arc::new(Mutex::new(tx));
 		let mut tx = txn.lock().await;
+		let analyzer = Analyzer::new(&mut tx, "testns", "testdb", az.clone()).await.unwrap();
 		let fti = FtIndex::with_analyzer(
 			ctx.get_index_stores(),
 			&mut tx,
-			az.clone(),
+			analyzer,
 			IndexKeyBase::default(),
 			&SearchParams {
 				az: az.name.clone(),
@@ -0,0 +1,113 @@
+/// A bounded edit-distance automaton, which matches the terms of
+/// the index that are within a maximum number of edits of a prefix.
+///
+/// Each candidate term is fed one character at a time through the rows
+/// of an optimal string alignment matrix, and is rejected as soon as
+/// every state of the automaton exceeds the maximum distance.
+pub(super) struct Suggester {
+	input: Vec<char>,
+	max_distance: usize,
+}
+
+impl Suggester {
+	pub(super) fn new(input: &str) -> Self {
+		let input: Vec<char> = input.chars().collect();
+		// Short inputs tolerate fewer edits, to keep the suggestions relevant
+		let max_distance = match input.len() {
+			0..=2 => 0,
+			3..=5 => 1,
+			_ => 2,
+		};
+		Self {
+			input,
+			max_distance,
+		}
+	}
+
+	/// Returns the smallest edit distance between the input and any prefix of
+	/// the candidate, or `None` if the candidate can not be within the bound.
+	pub(super) fn distance(&self, candidate: &str) -> Option<usize> {
+		let n = self.input.len();
+		// The first row of the matrix, matching the empty prefix of the candidate
+		let mut prev2: Vec<usize> = Vec::new();
+		let mut prev: Vec<usize> = (0..=n).collect();
+		let mut best = prev[n];
+		let mut last: Option<char> = None;
+		for c in candidate.chars() {
+			let mut row = Vec::with_capacity(n + 1);
+			row.push(prev[0] + 1);
+			for j in 1..=n {
+				let cost = usize::from(self.input[j - 1] != c);
+				let mut d = (prev[j] + 1).min(row[j - 1] + 1).min(prev[j - 1] + cost);
+				// Adjacent characters which were swapped count as a single edit
+				if j > 1 && Some(self.input[j - 1]) == last && self.input[j - 2] == c {
+					d = d.min(prev2[j - 2] + 1);
+				}
+				row.push(d);
+			}
+			best = best.min(row[n]);
+			// Stop once no state of the automaton can come back within the bound,
+			// bearing in mind that a transposition can reach back to the previous row
+			let row_min = row.iter().min().copied().unwrap_or(usize::MAX);
+			let prev_min = prev.iter().min().copied().unwrap_or(usize::MAX);
+			if row_min > self.max_distance && prev_min >= self.max_distance {
+				break;
+			}
+			last = Some(c);
+			prev2 = prev;
+			prev = row;
+		}
+		(best <= self.max_distance).then_some(best)
+	}
+}
+
+/// A term matched by the suggester, alongside the number of documents containing it
+pub(super) struct Suggestion {
+	pub(super) term: String,
+	pub(super) distance: usize,
+	pub(super) docs: u64,
+}
+
+impl Suggestion {
+	/// Order by closest distance, then by most documents, then by shortest, then alphabetically
+	pub(super) fn sort(suggestions: &mut [Suggestion]) {
+		suggestions.sort_by(|a, b| {
+			a.distance
+				.cmp(&b.distance)
+				.then(b.docs.cmp(&a.docs))
+				.then(a.term.len().cmp(&b.term.len()))
+				.then(a.term.cmp(&b.term))
+		});
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Suggester;
+
+	#[test]
+	fn test_distance() {
+		let s = Suggester::new("jonh");
+		// Transpositions count as a single edit
+		assert_eq!(s.distance("john"), Some(1));
+		assert_eq!(s.distance("johnny"), Some(1));
+		// Prefixes of the candidate are matched exactly
+		assert_eq!(s.distance("jonhson"), Some(0));
+		assert_eq!(s.distance("jon"), Some(1));
+		assert_eq!(s.distance("jane"), None);
+		assert_eq!(s.distance("mary"), None);
+	}
+
+	#[test]
+	fn test_bound() {
+		// Short inputs only match exact prefixes
+		let s = Suggester::new("jo");
+		assert_eq!(s.distance("john"), Some(0));
+		assert_eq!(s.distance("ja"), None);
+		// Longer inputs tolerate two edits
+		let s = Suggester::new("surealdb");
+		assert_eq!(s.distance("surrealdb"), Some(1));
+		assert_eq!(s.distance("sureldb"), Some(1));
+		assert_eq!(s.distance("sqlite"), None);
+	}
+}
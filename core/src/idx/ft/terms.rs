@@ -3,6 +3,7 @@ use crate::idx::trees::bkeys::FstKeys;
 use crate::idx::trees::btree::{BState, BState1, BState1skip, BStatistics, BTree, BTreeStore};
 use crate::idx::trees::store::{IndexStores, TreeNodeProvider};
 use crate::idx::{IndexKeyBase, VersionedSerdeState};
+use crate::key::index::bu::Bu;
 use crate::kvs::{Key, Transaction, TransactionType, Val};
 use revision::{revisioned, Revisioned};
 use roaring::RoaringTreemap;
@@ -117,6 +118,20 @@ impl Terms {
 		Ok(())
 	}
 
+	/// Fetch every term of the index, alongside its term id
+	pub(super) async fn all_terms(
+		&self,
+		tx: &mut Transaction,
+	) -> Result<Vec<(TermId, String)>, Error> {
+		let range = self.index_key_base.new_bu_range();
+		let mut res = Vec::new();
+		for (k, v) in tx.getr(range, u32::MAX).await? {
+			let term_id = Bu::decode(&k)?.term_id;
+			res.push((term_id, String::from_utf8(v)?));
+		}
+		Ok(res)
+	}
+
 	pub(super) async fn statistics(&self, tx: &mut Transaction) -> Result<BStatistics, Error> {
 		self.btree.statistics(tx, &self.store).await
 	}
@@ -25,6 +25,7 @@ use crate::sql::statements::DefineIndexStatement;
 use revision::Revisioned;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
+use std::ops::Range;
 use std::sync::Arc;
 
 #[derive(Debug, Clone, Default)]
@@ -175,6 +176,22 @@ impl IndexKeyBase {
 		.into()
 	}
 
+	fn new_bu_range(&self) -> Range<Key> {
+		let beg = crate::key::index::bu::prefix(
+			self.inner.ns.as_str(),
+			self.inner.db.as_str(),
+			self.inner.tb.as_str(),
+			self.inner.ix.as_str(),
+		);
+		let end = crate::key::index::bu::suffix(
+			self.inner.ns.as_str(),
+			self.inner.db.as_str(),
+			self.inner.tb.as_str(),
+			self.inner.ix.as_str(),
+		);
+		beg..end
+	}
+
 	fn new_vm_key(&self, node_id: Option<NodeId>) -> Key {
 		Vm::new(
 			self.inner.ns.as_str(),
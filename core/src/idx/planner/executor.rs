@@ -307,9 +307,15 @@ impl QueryExecutor {
 			IndexOperator::Equality(value) => Some(ThingIterator::IndexEqual(
 				IndexEqualThingIterator::new(opt.ns(), opt.db(), &ix.what, &ix.name, value),
 			)),
-			IndexOperator::Union(value) => Some(ThingIterator::IndexUnion(
-				IndexUnionThingIterator::new(opt.ns(), opt.db(), &ix.what, &ix.name, value),
-			)),
+			IndexOperator::Union(value) | IndexOperator::Complement(value, _) => {
+				Some(ThingIterator::IndexUnion(IndexUnionThingIterator::new(
+					opt.ns(),
+					opt.db(),
+					&ix.what,
+					&ix.name,
+					value,
+				)))
+			}
 			IndexOperator::Join(ios) => {
 				let iterators = self.build_iterators(opt, it_ref, ios).await?;
 				let index_join = Box::new(IndexJoinThingIterator::new(opt, ix, iterators));
@@ -365,7 +371,7 @@ impl QueryExecutor {
 			IndexOperator::Equality(value) => Some(ThingIterator::UniqueEqual(
 				UniqueEqualThingIterator::new(opt.ns(), opt.db(), &ix.what, &ix.name, value),
 			)),
-			IndexOperator::Union(value) => {
+			IndexOperator::Union(value) | IndexOperator::Complement(value, _) => {
 				Some(ThingIterator::UniqueUnion(UniqueUnionThingIterator::new(opt, ix, value)))
 			}
 			IndexOperator::Join(ios) => {
@@ -12,8 +12,9 @@ use crate::idx::planner::executor::{
 };
 use crate::idx::planner::plan::{Plan, PlanBuilder};
 use crate::idx::planner::tree::Tree;
+use crate::sql::dir::Dir;
 use crate::sql::with::With;
-use crate::sql::{Cond, Expression, Table, Thing};
+use crate::sql::{Cond, Edges, Expression, Idiom, Operator, Partition, Table, Thing, Value};
 use reblessive::tree::Stk;
 use std::collections::{HashMap, HashSet};
 use std::sync::atomic::{AtomicU8, Ordering};
@@ -100,13 +101,17 @@ impl<'a> QueryPlanner<'a> {
 							self.fallbacks.push(fallback);
 						}
 						self.add(t.clone(), None, exe, it);
-						it.ingest(Iterable::Table(t));
+						if is_knn {
+							it.ingest(Iterable::Table(t));
+						} else {
+							it.ingest(self.table_iterable(txn, t).await?);
+						}
 						is_table_iterator = true;
 					}
 				}
 			}
 			None => {
-				it.ingest(Iterable::Table(t));
+				it.ingest(self.table_iterable(txn, t).await?);
 			}
 		}
 		if is_knn && is_table_iterator {
@@ -117,6 +122,126 @@ impl<'a> QueryPlanner<'a> {
 		Ok(())
 	}
 
+	/// Get the iterable for scanning a table, which only scans a single partition
+	/// of a partitioned table when the condition selects a single partition value
+	async fn table_iterable(&self, txn: &Transaction, t: Table) -> Result<Iterable, Error> {
+		if let Some(cond) = self.cond {
+			let tb = match txn.lock().await.get_tb(self.opt.ns(), self.opt.db(), &t).await {
+				Ok(tb) => tb,
+				Err(Error::TbNotFound {
+					..
+				}) => return Ok(Iterable::Table(t)),
+				Err(e) => return Err(e),
+			};
+			if let Some(pt) = &tb.partition {
+				if let Some(v) = Self::partition_value(&cond.0, &pt.field) {
+					return Ok(Iterable::Partition(t, pt.of(v)?));
+				}
+			}
+		}
+		Ok(Iterable::Table(t))
+	}
+
+	/// Find a value which the condition requires the partition field to be equal to
+	fn partition_value<'b>(cond: &'b Value, field: &Idiom) -> Option<&'b Value> {
+		match cond {
+			Value::Expression(e) => match e.as_ref() {
+				Expression::Binary {
+					l,
+					o: Operator::And,
+					r,
+				} => Self::partition_value(l, field).or_else(|| Self::partition_value(r, field)),
+				Expression::Binary {
+					l: Value::Idiom(i),
+					o: Operator::Equal | Operator::Exact,
+					r,
+				} if i == field && Partition::prunes(r) => Some(r),
+				Expression::Binary {
+					l,
+					o: Operator::Equal | Operator::Exact,
+					r: Value::Idiom(i),
+				} if i == field && Partition::prunes(l) => Some(l),
+				_ => None,
+			},
+			_ => None,
+		}
+	}
+
+	/// Add the iterables for a graph traversal, using the indexes
+	/// of the edge table when the traversal is filtered by a condition
+	pub(crate) async fn add_edges_iterables(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &Context<'_>,
+		txn: &Transaction,
+		e: Edges,
+		it: &mut Iterator,
+	) -> Result<(), Error> {
+		// Only single direction traversals of a single edge table can use an index
+		let t = match (&e.dir, &e.what.0[..]) {
+			(Dir::In | Dir::Out, [t]) if self.cond.is_some() => t.clone(),
+			_ => {
+				it.ingest(Iterable::Edges(e));
+				return Ok(());
+			}
+		};
+		// Each table has a single executor, which is already in use
+		if self.executors.contains_key(&t.0) {
+			it.ingest(Iterable::Edges(e));
+			return Ok(());
+		}
+		if let Some(tree) = Tree::build(stk, ctx, self.opt, txn, &t, self.cond, self.with).await? {
+			if tree.knn_expressions.is_empty() {
+				let mut exe = InnerQueryExecutor::new(
+					stk,
+					ctx,
+					self.opt,
+					txn,
+					&t,
+					tree.index_map,
+					tree.knn_expressions,
+				)
+				.await?;
+				let irs = match PlanBuilder::build(tree.root, self.with, tree.with_indexes)? {
+					Plan::SingleIndex(exp, io) => {
+						if io.require_distinct() {
+							self.requires_distinct = true;
+						}
+						vec![exe.add_iterator(IteratorEntry::Single(exp, io))]
+					}
+					Plan::SingleIndexRange(ixn, rq) => {
+						vec![exe.add_iterator(IteratorEntry::Range(rq.exps, ixn, rq.from, rq.to))]
+					}
+					Plan::MultiIndex(non_range_indexes, ranges_indexes) => {
+						self.requires_distinct = true;
+						let mut irs = Vec::new();
+						for (exp, io) in non_range_indexes {
+							irs.push(exe.add_iterator(IteratorEntry::Single(exp, io)));
+						}
+						for (ixn, rq) in ranges_indexes {
+							let ie = IteratorEntry::Range(rq.exps, ixn, rq.from, rq.to);
+							irs.push(exe.add_iterator(ie));
+						}
+						irs
+					}
+					Plan::TableIterator(_) => vec![],
+				};
+				if !irs.is_empty() {
+					self.executors.insert(t.0.clone(), exe.into());
+					for ir in irs {
+						it.ingest(Iterable::EdgesIndex(e.clone(), ir));
+					}
+					if self.iteration_workflow.is_empty() {
+						self.iteration_workflow = vec![IterationStage::Iterate(None)];
+					}
+					return Ok(());
+				}
+			}
+		}
+		it.ingest(Iterable::Edges(e));
+		Ok(())
+	}
+
 	fn add(
 		&mut self,
 		tb: Table,
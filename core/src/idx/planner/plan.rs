@@ -176,6 +176,8 @@ pub(super) struct IndexOption {
 pub(super) enum IndexOperator {
 	Equality(Value),
 	Union(Array),
+	/// The remaining values of a field, and the values which were excluded
+	Complement(Array, Array),
 	Join(Vec<IndexOption>),
 	RangePart(Operator, Value),
 	Matches(String, Option<MatchRef>),
@@ -193,7 +195,7 @@ impl IndexOption {
 	}
 
 	pub(super) fn require_distinct(&self) -> bool {
-		matches!(self.op.as_ref(), IndexOperator::Union(_))
+		matches!(self.op.as_ref(), IndexOperator::Union(_) | IndexOperator::Complement(_, _))
 	}
 
 	pub(super) fn ix_ref(&self) -> IndexRef {
@@ -235,6 +237,12 @@ impl IndexOption {
 				e.insert("operator", Value::from("union"));
 				e.insert("value", Value::Array(a.clone()));
 			}
+			IndexOperator::Complement(a, x) => {
+				e.insert("operator", Value::from("complement"));
+				e.insert("strategy", Value::from("enumeration"));
+				e.insert("value", Value::Array(a.clone()));
+				e.insert("excluded", Value::Array(x.clone()));
+			}
 			IndexOperator::Join(ios) => {
 				e.insert("operator", Value::from("join"));
 				let mut joins = Vec::with_capacity(ios.len());
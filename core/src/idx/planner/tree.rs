@@ -275,6 +275,16 @@ impl<'a> TreeBuilder<'a> {
 		e: &Expression,
 	) -> Result<Node, Error> {
 		match e {
+			Expression::Unary {
+				o: Operator::Not,
+				v,
+			} => {
+				// A negated comparison is evaluated as the opposite comparison
+				if let Some((l, o, r)) = Self::negate(v) {
+					return self.eval_binary(stk, group, e, l, &o, r).await;
+				}
+				Ok(Node::Unsupported("unary expressions not supported".to_string()))
+			}
 			Expression::Unary {
 				..
 			} => Ok(Node::Unsupported("unary expressions not supported".to_string())),
@@ -282,51 +292,91 @@ impl<'a> TreeBuilder<'a> {
 				l,
 				o,
 				r,
-			} => {
-				// Did we already compute the same expression?
-				if let Some(re) = self.resolved_expressions.get(e).cloned() {
-					return Ok(re.into());
-				}
-				let exp = Arc::new(e.clone());
-				let left = Arc::new(stk.run(|stk| self.eval_value(stk, group, l)).await?);
-				let right = Arc::new(stk.run(|stk| self.eval_value(stk, group, r)).await?);
-				let mut io = None;
-				if let Some((id, local_irs, remote_irs)) = left.is_indexed_field() {
-					io = self.lookup_index_options(
-						o,
-						id,
-						&right,
-						&exp,
-						IdiomPosition::Left,
-						local_irs,
-						remote_irs,
-					)?;
-				} else if let Some((id, local_irs, remote_irs)) = right.is_indexed_field() {
-					io = self.lookup_index_options(
-						o,
-						id,
-						&left,
-						&exp,
-						IdiomPosition::Right,
-						local_irs,
-						remote_irs,
-					)?;
-				} else if let Some(id) = left.is_non_indexed_field() {
-					self.eval_knn(id, &right, &exp)?;
-				} else if let Some(id) = right.is_non_indexed_field() {
-					self.eval_knn(id, &left, &exp)?;
-				}
-				let re = ResolvedExpression {
-					group,
-					exp: exp.clone(),
-					io,
-					left: left.clone(),
-					right: right.clone(),
-				};
-				self.resolved_expressions.insert(exp, re.clone());
-				Ok(re.into())
-			}
+			} => self.eval_binary(stk, group, e, l, o, r).await,
+		}
+	}
+
+	/// Returns the opposite comparison of a negated `!(a = b)` expression
+	fn negate(v: &Value) -> Option<(&Value, Operator, &Value)> {
+		let e = match v {
+			Value::Expression(e) => e.as_ref(),
+			Value::Subquery(s) => match s.as_ref() {
+				Subquery::Value(Value::Expression(e)) => e.as_ref(),
+				_ => return None,
+			},
+			_ => return None,
+		};
+		if let Expression::Binary {
+			l,
+			o,
+			r,
+		} = e
+		{
+			let o = match o {
+				Operator::Equal => Operator::NotEqual,
+				Operator::NotEqual => Operator::Equal,
+				Operator::Inside => Operator::NotInside,
+				Operator::NotInside => Operator::Inside,
+				Operator::Contain => Operator::NotContain,
+				Operator::NotContain => Operator::Contain,
+				_ => return None,
+			};
+			return Some((l, o, r));
+		}
+		None
+	}
+
+	async fn eval_binary(
+		&mut self,
+		stk: &mut Stk,
+		group: GroupRef,
+		e: &Expression,
+		l: &Value,
+		o: &Operator,
+		r: &Value,
+	) -> Result<Node, Error> {
+		// Did we already compute the same expression?
+		if let Some(re) = self.resolved_expressions.get(e).cloned() {
+			return Ok(re.into());
+		}
+		let exp = Arc::new(e.clone());
+		let left = Arc::new(stk.run(|stk| self.eval_value(stk, group, l)).await?);
+		let right = Arc::new(stk.run(|stk| self.eval_value(stk, group, r)).await?);
+		let mut io = None;
+		if let Some((id, local_irs, remote_irs)) = left.is_indexed_field() {
+			io = self.lookup_index_options(
+				o,
+				id,
+				&right,
+				&exp,
+				IdiomPosition::Left,
+				local_irs,
+				remote_irs,
+			)?;
+		} else if let Some((id, local_irs, remote_irs)) = right.is_indexed_field() {
+			io = self.lookup_index_options(
+				o,
+				id,
+				&left,
+				&exp,
+				IdiomPosition::Right,
+				local_irs,
+				remote_irs,
+			)?;
+		} else if let Some(id) = left.is_non_indexed_field() {
+			self.eval_knn(id, &right, &exp)?;
+		} else if let Some(id) = right.is_non_indexed_field() {
+			self.eval_knn(id, &left, &exp)?;
 		}
+		let re = ResolvedExpression {
+			group,
+			exp: exp.clone(),
+			io,
+			left: left.clone(),
+			right: right.clone(),
+		};
+		self.resolved_expressions.insert(exp, re.clone());
+		Ok(re.into())
 	}
 
 	#[allow(clippy::too_many_arguments)]
@@ -343,7 +393,9 @@ impl<'a> TreeBuilder<'a> {
 		if let Some(remote_irs) = remote_irs {
 			let mut remote_ios = Vec::with_capacity(remote_irs.len());
 			for (id, irs) in remote_irs.iter() {
-				if let Some(io) = self.lookup_index_option(irs.as_slice(), o, id, node, exp, p)? {
+				if let Some(io) =
+					self.lookup_index_option(irs.as_slice(), o, id, node, exp, p, None)?
+				{
 					remote_ios.push(io);
 				} else {
 					return Ok(None);
@@ -355,10 +407,37 @@ impl<'a> TreeBuilder<'a> {
 			}
 			return Ok(None);
 		}
-		let io = self.lookup_index_option(local_irs.as_slice(), o, id, node, exp, p)?;
+		let domain = self.resolve_domain(id);
+		let io =
+			self.lookup_index_option(local_irs.as_slice(), o, id, node, exp, p, domain.as_ref())?;
 		Ok(io)
 	}
 
+	/// Returns the values which a field is limited to by an `ASSERT $value IN [...]`
+	/// clause, when there are few enough of them to be scanned one by one
+	fn resolve_domain(&self, id: &Idiom) -> Option<Array> {
+		let schema = self.schemas.get(self.table)?;
+		let fd = schema.fields.iter().find(|fd| fd.name.eq(id))?;
+		let Some(Value::Expression(e)) = &fd.assert else {
+			return None;
+		};
+		let a = match e.as_ref() {
+			Expression::Binary {
+				l: Value::Param(v),
+				o: Operator::Inside,
+				r: Value::Array(a),
+			}
+			| Expression::Binary {
+				l: Value::Array(a),
+				o: Operator::Contain,
+				r: Value::Param(v),
+			} if v.as_str() == "value" => a,
+			_ => return None,
+		};
+		(a.len() <= MAX_DOMAIN_SIZE && a.is_static()).then(|| a.clone())
+	}
+
+	#[allow(clippy::too_many_arguments)]
 	fn lookup_index_option(
 		&mut self,
 		irs: &[IndexRef],
@@ -367,12 +446,13 @@ impl<'a> TreeBuilder<'a> {
 		n: &Node,
 		e: &Arc<Expression>,
 		p: IdiomPosition,
+		domain: Option<&Array>,
 	) -> Result<Option<IndexOption>, Error> {
 		for ir in irs {
 			if let Some(ix) = self.index_map.definitions.get(*ir as usize) {
 				let op = match &ix.index {
-					Index::Idx => Self::eval_index_operator(op, n, p),
-					Index::Uniq => Self::eval_index_operator(op, n, p),
+					Index::Idx | Index::Uniq => Self::eval_index_operator(op, n, p)
+						.or_else(|| Self::eval_complement_operator(op, n, p, domain?)),
 					Index::Search {
 						..
 					} => Self::eval_matches_operator(op, n),
@@ -479,6 +559,24 @@ impl<'a> TreeBuilder<'a> {
 		}
 	}
 
+	/// Converts a negative comparison into a union of the remaining values of a field,
+	/// when the values which the field is limited to are known
+	fn eval_complement_operator(
+		op: &Operator,
+		n: &Node,
+		p: IdiomPosition,
+		domain: &Array,
+	) -> Option<IndexOperator> {
+		let excluded = match (op, n.is_computed()?, p) {
+			(Operator::NotEqual, v, _) => Array::from(vec![v.clone()]),
+			(Operator::NotInside, Value::Array(a), IdiomPosition::Left) => a.clone(),
+			(Operator::NotContain, Value::Array(a), IdiomPosition::Right) => a.clone(),
+			_ => return None,
+		};
+		let remaining = domain.iter().filter(|v| !excluded.contains(v)).cloned().collect();
+		Some(IndexOperator::Complement(remaining, excluded))
+	}
+
 	async fn eval_subquery(&mut self, stk: &mut Stk, s: &Subquery) -> Result<Node, Error> {
 		self.group_sequence += 1;
 		match s {
@@ -488,6 +586,9 @@ impl<'a> TreeBuilder<'a> {
 	}
 }
 
+/// The largest number of values which a negative comparison is converted into
+const MAX_DOMAIN_SIZE: usize = 64;
+
 pub(super) type IndexRef = u16;
 /// For each expression a possible index option
 #[derive(Default)]
@@ -0,0 +1,91 @@
+//! Stores a chunk of the content of a blob
+use crate::key::error::KeyCategory;
+use crate::key::key_req::KeyRequirements;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+
+/// Bc stores the content of a blob in chunks, which are ordered by their
+/// position, so that a range scan of a blob returns its content in order.
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+#[non_exhaustive]
+pub struct Bc<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	_d: u8,
+	_e: u8,
+	pub bl: &'a str,
+	pub ch: u32,
+}
+
+pub fn new<'a>(ns: &'a str, db: &'a str, bl: &'a str, ch: u32) -> Bc<'a> {
+	Bc::new(ns, db, bl, ch)
+}
+
+pub fn prefix(ns: &str, db: &str, bl: &str) -> Vec<u8> {
+	let mut k = super::all::new(ns, db).encode().unwrap();
+	k.extend_from_slice(b"!bc");
+	k.extend_from_slice(bl.as_bytes());
+	k.push(0x00);
+	k
+}
+
+pub fn suffix(ns: &str, db: &str, bl: &str) -> Vec<u8> {
+	let mut k = prefix(ns, db, bl);
+	// Sort after every chunk position, which is encoded in 4 bytes
+	k.extend_from_slice(&[0xff; 5]);
+	k
+}
+
+impl KeyRequirements for Bc<'_> {
+	fn key_category(&self) -> KeyCategory {
+		KeyCategory::DatabaseBlobChunk
+	}
+}
+
+impl<'a> Bc<'a> {
+	pub fn new(ns: &'a str, db: &'a str, bl: &'a str, ch: u32) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'!',
+			_d: b'b',
+			_e: b'c',
+			bl,
+			ch,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Bc::new(
+			"testns",
+			"testdb",
+			"testbl",
+			1,
+		);
+		let enc = Bc::encode(&val).unwrap();
+		assert_eq!(enc, b"/*testns\x00*testdb\x00!bctestbl\x00\x00\x00\x00\x01");
+		let dec = Bc::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+
+	#[test]
+	fn test_prefix() {
+		let val = super::prefix("testns", "testdb", "testbl");
+		assert_eq!(val, b"/*testns\0*testdb\0!bctestbl\0");
+		let key = super::new("testns", "testdb", "testbl", u32::MAX).encode().unwrap();
+		assert!(val < key && key < super::suffix("testns", "testdb", "testbl"));
+	}
+}
@@ -0,0 +1,87 @@
+//! Stores the definition of a content addressed blob
+use crate::key::error::KeyCategory;
+use crate::key::key_req::KeyRequirements;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+#[non_exhaustive]
+pub struct Bl<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	_d: u8,
+	_e: u8,
+	pub bl: &'a str,
+}
+
+pub fn new<'a>(ns: &'a str, db: &'a str, bl: &'a str) -> Bl<'a> {
+	Bl::new(ns, db, bl)
+}
+
+pub fn prefix(ns: &str, db: &str) -> Vec<u8> {
+	let mut k = super::all::new(ns, db).encode().unwrap();
+	k.extend_from_slice(&[b'!', b'b', b'l', 0x00]);
+	k
+}
+
+pub fn suffix(ns: &str, db: &str) -> Vec<u8> {
+	let mut k = super::all::new(ns, db).encode().unwrap();
+	k.extend_from_slice(&[b'!', b'b', b'l', 0xff]);
+	k
+}
+
+impl KeyRequirements for Bl<'_> {
+	fn key_category(&self) -> KeyCategory {
+		KeyCategory::DatabaseBlob
+	}
+}
+
+impl<'a> Bl<'a> {
+	pub fn new(ns: &'a str, db: &'a str, bl: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'!',
+			_d: b'b',
+			_e: b'l',
+			bl,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Bl::new(
+			"testns",
+			"testdb",
+			"testbl",
+		);
+		let enc = Bl::encode(&val).unwrap();
+		assert_eq!(enc, b"/*testns\x00*testdb\x00!bltestbl\x00");
+		let dec = Bl::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+
+	#[test]
+	fn test_prefix() {
+		let val = super::prefix("testns", "testdb");
+		assert_eq!(val, b"/*testns\0*testdb\0!bl\0");
+	}
+
+	#[test]
+	fn test_suffix() {
+		let val = super::suffix("testns", "testdb");
+		assert_eq!(val, b"/*testns\0*testdb\0!bl\xff");
+	}
+}
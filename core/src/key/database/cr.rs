@@ -0,0 +1,93 @@
+//! Stores the result of a scheduled data quality check, ordered by the time it ran
+use crate::key::error::KeyCategory;
+use crate::key::key_req::KeyRequirements;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+
+/// Cr is used to find the results of a check, and when it last ran.
+///
+/// The time is stored as seconds since the unix epoch, so that a range
+/// scan from a point in time returns the results of the later runs.
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+#[non_exhaustive]
+pub struct Cr<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	_d: u8,
+	_e: u8,
+	pub ck: &'a str,
+	pub ts: u64,
+}
+
+pub fn new<'a>(ns: &'a str, db: &'a str, ck: &'a str, ts: u64) -> Cr<'a> {
+	Cr::new(ns, db, ck, ts)
+}
+
+pub fn prefix(ns: &str, db: &str, ck: &str) -> Vec<u8> {
+	let mut k = super::all::new(ns, db).encode().unwrap();
+	k.extend_from_slice(b"!cr");
+	k.extend_from_slice(ck.as_bytes());
+	k.push(0x00);
+	k
+}
+
+pub fn suffix(ns: &str, db: &str, ck: &str) -> Vec<u8> {
+	let mut k = prefix(ns, db, ck);
+	// Sort after every timestamp, which is encoded in 8 bytes
+	k.extend_from_slice(&[0xff; 9]);
+	k
+}
+
+impl KeyRequirements for Cr<'_> {
+	fn key_category(&self) -> KeyCategory {
+		KeyCategory::DatabaseCheckResult
+	}
+}
+
+impl<'a> Cr<'a> {
+	pub fn new(ns: &'a str, db: &'a str, ck: &'a str, ts: u64) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'!',
+			_d: b'c',
+			_e: b'r',
+			ck,
+			ts,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Cr::new(
+			"testns",
+			"testdb",
+			"testck",
+			1,
+		);
+		let enc = Cr::encode(&val).unwrap();
+		assert_eq!(enc, b"/*testns\x00*testdb\x00!crtestck\x00\x00\x00\x00\x00\x00\x00\x00\x01");
+		let dec = Cr::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+
+	#[test]
+	fn test_prefix() {
+		let val = super::prefix("testns", "testdb", "testck");
+		assert_eq!(val, b"/*testns\0*testdb\0!crtestck\0");
+		let key = super::new("testns", "testdb", "testck", u64::MAX).encode().unwrap();
+		assert!(val < key && key < super::suffix("testns", "testdb", "testck"));
+	}
+}
@@ -1,9 +1,16 @@
 pub mod all;
 pub mod az;
+pub mod bc;
+pub mod bl;
+pub mod ck;
+pub mod cr;
+pub mod dc;
 pub mod fc;
 pub mod ml;
 pub mod pa;
+pub mod ro;
 pub mod sc;
+pub mod se;
 pub mod tb;
 pub mod ti;
 pub mod tk;
@@ -0,0 +1,86 @@
+use crate::key::error::KeyCategory;
+use crate::key::key_req::KeyRequirements;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+#[non_exhaustive]
+pub struct Ro<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	_d: u8,
+	_e: u8,
+	pub role: &'a str,
+}
+
+pub fn new<'a>(ns: &'a str, db: &'a str, role: &'a str) -> Ro<'a> {
+	Ro::new(ns, db, role)
+}
+
+pub fn prefix(ns: &str, db: &str) -> Vec<u8> {
+	let mut k = super::all::new(ns, db).encode().unwrap();
+	k.extend_from_slice(&[b'!', b'r', b'o', 0x00]);
+	k
+}
+
+pub fn suffix(ns: &str, db: &str) -> Vec<u8> {
+	let mut k = super::all::new(ns, db).encode().unwrap();
+	k.extend_from_slice(&[b'!', b'r', b'o', 0xff]);
+	k
+}
+
+impl KeyRequirements for Ro<'_> {
+	fn key_category(&self) -> KeyCategory {
+		KeyCategory::DatabaseRole
+	}
+}
+
+impl<'a> Ro<'a> {
+	pub fn new(ns: &'a str, db: &'a str, role: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'!',
+			_d: b'r',
+			_e: b'o',
+			role,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Ro::new(
+			"testns",
+			"testdb",
+			"testrole",
+		);
+		let enc = Ro::encode(&val).unwrap();
+		assert_eq!(enc, b"/*testns\x00*testdb\x00!rotestrole\x00");
+		let dec = Ro::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+
+	#[test]
+	fn test_prefix() {
+		let val = super::prefix("testns", "testdb");
+		assert_eq!(val, b"/*testns\0*testdb\0!ro\0");
+	}
+
+	#[test]
+	fn test_suffix() {
+		let val = super::suffix("testns", "testdb");
+		assert_eq!(val, b"/*testns\0*testdb\0!ro\xff");
+	}
+}
@@ -8,14 +8,22 @@ pub enum KeyCategory {
 	Unknown,
 	/// crate::key::root::all                /
 	Root,
+	/// crate::key::root::dd                 /!dd{ns}{db}{ts}{id}
+	DefinitionLog,
+	/// crate::key::root::ex                 /!ex{ts}{ns}{db}{tb}{id}
+	Expiry,
 	/// crate::key::root::hb                 /!hb{ts}/{nd}
 	Heartbeat,
+	/// crate::key::root::mg                 /!mg
+	Migration,
 	/// crate::key::root::nd                 /!nd{nd}
 	Node,
 	/// crate::key::root::ni                 /!ni
 	NamespaceIdentifier,
 	/// crate::key::root::ns                 /!ns{ns}
 	Namespace,
+	/// crate::key::root::sv                 /!sv
+	StorageVersion,
 	/// crate::key::root::us                 /!us{us}
 	User,
 	///
@@ -41,6 +49,16 @@ pub enum KeyCategory {
 	DatabaseRoot,
 	/// crate::key::database::az             /*{ns}*{db}!az{az}
 	DatabaseAnalyzer,
+	/// crate::key::database::bl             /*{ns}*{db}!bl{bl}
+	DatabaseBlob,
+	/// crate::key::database::bc             /*{ns}*{db}!bc{bl}{ch}
+	DatabaseBlobChunk,
+	/// crate::key::database::ck             /*{ns}*{db}!ck{ck}
+	DatabaseCheck,
+	/// crate::key::database::cr             /*{ns}*{db}!cr{ck}{ts}
+	DatabaseCheckResult,
+	/// crate::key::database::dc             /*{ns}*{db}!dc{dc}
+	DatabaseDictionary,
 	/// crate::key::database::fc             /*{ns}*{db}!fn{fc}
 	DatabaseFunction,
 	/// crate::key::database::lg             /*{ns}*{db}!lg{lg}
@@ -49,8 +67,12 @@ pub enum KeyCategory {
 	DatabaseModel,
 	/// crate::key::database::pa             /*{ns}*{db}!pa{pa}
 	DatabaseParameter,
+	/// crate::key::database::ro             /*{ns}*{db}!ro{ro}
+	DatabaseRole,
 	/// crate::key::database::sc             /*{ns}*{db}!sc{sc}
 	DatabaseScope,
+	/// crate::key::database::se             /*{ns}*{db}!se{se}
+	DatabaseSession,
 	/// crate::key::database::tb             /*{ns}*{db}!tb{tb}
 	DatabaseTable,
 	/// crate::key::database::ti             /+{ns id}*{db id}!ti
@@ -71,16 +93,30 @@ pub enum KeyCategory {
 	///
 	/// crate::key::table::all               /*{ns}*{db}*{tb}
 	TableRoot,
+	/// crate::key::table::bf                /*{ns}*{db}*{tb}!bf{fd}
+	TableBackfill,
+	/// crate::key::table::cd                /*{ns}*{db}*{tb}!cd{id}{ft}{fk}
+	TableComputedDependency,
+	/// crate::key::table::cs                /*{ns}*{db}*{tb}!cs{id}
+	TableComputedStale,
+	/// crate::key::table::ej                /*{ns}*{db}*{tb}!ej{en}{id}
+	TableEnrichmentJob,
+	/// crate::key::table::en                /*{ns}*{db}*{tb}!en{en}
+	TableEnrichment,
 	/// crate::key::table::ev                /*{ns}*{db}*{tb}!ev{ev}
 	TableEvent,
 	/// crate::key::table::fd                /*{ns}*{db}*{tb}!fd{fd}
 	TableField,
+	/// crate::key::table::hk                /*{ns}*{db}*{tb}!hk{hk}
+	TableHook,
 	/// crate::key::table::ft                /*{ns}*{db}*{tb}!ft{ft}
 	TableView, // (ft = foreign table = view)
 	/// crate::key::table::ix                /*{ns}*{db}*{tb}!ix{ix}
 	IndexDefinition,
 	/// crate::key::table::lq                /*{ns}*{db}*{tb}!lq{lq}
 	TableLiveQuery,
+	/// crate::key::table::pt                /*{ns}*{db}*{tb}!pt{pt}{id}
+	TablePartition,
 	///
 	/// crate::key::index::all               /*{ns}*{db}*{tb}+{ix}
 	IndexRoot,
@@ -124,10 +160,14 @@ impl Display for KeyCategory {
 		let name = match self {
 			KeyCategory::Unknown => "Unknown",
 			KeyCategory::Root => "Root",
+			KeyCategory::DefinitionLog => "DefinitionLog",
+			KeyCategory::Expiry => "Expiry",
 			KeyCategory::Heartbeat => "Heartbeat",
+			KeyCategory::Migration => "Migration",
 			KeyCategory::Node => "Node",
 			KeyCategory::NamespaceIdentifier => "NamespaceIdentifier",
 			KeyCategory::Namespace => "Namespace",
+			KeyCategory::StorageVersion => "StorageVersion",
 			KeyCategory::User => "User",
 			KeyCategory::NodeRoot => "NodeRoot",
 			KeyCategory::NodeLiveQuery => "NodeLiveQuery",
@@ -139,11 +179,18 @@ impl Display for KeyCategory {
 			KeyCategory::NamespaceUser => "NamespaceUser",
 			KeyCategory::DatabaseRoot => "DatabaseRoot",
 			KeyCategory::DatabaseAnalyzer => "DatabaseAnalyzer",
+			KeyCategory::DatabaseBlob => "DatabaseBlob",
+			KeyCategory::DatabaseBlobChunk => "DatabaseBlobChunk",
+			KeyCategory::DatabaseCheck => "DatabaseCheck",
+			KeyCategory::DatabaseCheckResult => "DatabaseCheckResult",
+			KeyCategory::DatabaseDictionary => "DatabaseDictionary",
 			KeyCategory::DatabaseFunction => "DatabaseFunction",
 			KeyCategory::DatabaseLog => "DatabaseLog",
 			KeyCategory::DatabaseModel => "DatabaseModel",
 			KeyCategory::DatabaseParameter => "DatabaseParameter",
+			KeyCategory::DatabaseRole => "DatabaseRole",
 			KeyCategory::DatabaseScope => "DatabaseScope",
+			KeyCategory::DatabaseSession => "DatabaseSession",
 			KeyCategory::DatabaseTable => "DatabaseTable",
 			KeyCategory::DatabaseTableIdentifier => "DatabaseTableIdentifier",
 			KeyCategory::DatabaseToken => "DatabaseToken",
@@ -153,11 +200,18 @@ impl Display for KeyCategory {
 			KeyCategory::ScopeRoot => "ScopeRoot",
 			KeyCategory::ScopeToken => "ScopeToken",
 			KeyCategory::TableRoot => "TableRoot",
+			KeyCategory::TableBackfill => "TableBackfill",
+			KeyCategory::TableComputedDependency => "TableComputedDependency",
+			KeyCategory::TableComputedStale => "TableComputedStale",
+			KeyCategory::TableEnrichmentJob => "TableEnrichmentJob",
+			KeyCategory::TableEnrichment => "TableEnrichment",
 			KeyCategory::TableEvent => "TableEvent",
 			KeyCategory::TableField => "TableField",
+			KeyCategory::TableHook => "TableHook",
 			KeyCategory::TableView => "TableView",
 			KeyCategory::IndexDefinition => "IndexDefinition",
 			KeyCategory::TableLiveQuery => "TableLiveQuery",
+			KeyCategory::TablePartition => "TablePartition",
 			KeyCategory::IndexRoot => "IndexRoot",
 			KeyCategory::IndexTermDocList => "IndexTermDocList",
 			KeyCategory::IndexBTreeNode => "IndexBTreeNode",
@@ -23,6 +23,18 @@ pub struct Bu<'a> {
 	pub term_id: TermId,
 }
 
+pub fn prefix(ns: &str, db: &str, tb: &str, ix: &str) -> Vec<u8> {
+	let mut k = super::all::new(ns, db, tb, ix).encode().unwrap();
+	k.extend_from_slice(&[b'!', b'b', b'u', 0x00]);
+	k
+}
+
+pub fn suffix(ns: &str, db: &str, tb: &str, ix: &str) -> Vec<u8> {
+	let mut k = super::all::new(ns, db, tb, ix).encode().unwrap();
+	k.extend_from_slice(&[b'!', b'b', b'u', 0xff]);
+	k
+}
+
 impl KeyRequirements for Bu<'_> {
 	fn key_category(&self) -> KeyCategory {
 		KeyCategory::IndexTerms
@@ -68,4 +80,16 @@ mod tests {
 		let dec = Bu::decode(&enc).unwrap();
 		assert_eq!(val, dec);
 	}
+
+	#[test]
+	fn test_prefix() {
+		let val = super::prefix("testns", "testdb", "testtb", "testix");
+		assert_eq!(val, b"/*testns\0*testdb\0*testtb\0+testix\0!bu\0");
+	}
+
+	#[test]
+	fn test_suffix() {
+		let val = super::suffix("testns", "testdb", "testtb", "testix");
+		assert_eq!(val, b"/*testns\0*testdb\0*testtb\0+testix\0!bu\xff");
+	}
 }
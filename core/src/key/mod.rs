@@ -1,11 +1,16 @@
 //! How the keys are structured in the key value store
 ///
 /// crate::key::root::all                /
+/// crate::key::root::dd                 /!dd{ns}{db}{ts}{id}
+/// crate::key::root::ex                 /!ex{ts}{ns}{db}{tb}{id}
 /// crate::key::root::hb                 /!hb{ts}/{nd}
+/// crate::key::root::mg                 /!mg
 /// crate::key::root::nd                 /!nd{nd}
 /// crate::key::root::ni                 /!ni
 /// crate::key::root::ns                 /!ns{ns}
+/// crate::key::root::sv                 /!sv
 /// crate::key::root::us                 /!us{us}
+/// crate::kvs::chunk                    /!ck{key}{chunk}
 ///
 /// crate::key::node::all                /${nd}
 /// crate::key::node::lq                 /${nd}!lq{lq}{ns}{db}
@@ -19,10 +24,17 @@
 ///
 /// crate::key::database::all            /*{ns}*{db}
 /// crate::key::database::az             /*{ns}*{db}!az{az}
+/// crate::key::database::bc             /*{ns}*{db}!bc{bl}{ch}
+/// crate::key::database::bl             /*{ns}*{db}!bl{bl}
+/// crate::key::database::ck             /*{ns}*{db}!ck{ck}
+/// crate::key::database::cr             /*{ns}*{db}!cr{ck}{ts}
+/// crate::key::database::dc             /*{ns}*{db}!dc{dc}
 /// crate::key::database::fc             /*{ns}*{db}!fn{fc}
 /// crate::key::database::lg             /*{ns}*{db}!lg{lg}
 /// crate::key::database::pa             /*{ns}*{db}!pa{pa}
+/// crate::key::database::ro             /*{ns}*{db}!ro{ro}
 /// crate::key::database::sc             /*{ns}*{db}!sc{sc}
+/// crate::key::database::se             /*{ns}*{db}!se{se}
 /// crate::key::database::tb             /*{ns}*{db}!tb{tb}
 /// crate::key::database::ti             /+{ns id}*{db id}!ti
 /// crate::key::database::tk             /*{ns}*{db}!tk{tk}
@@ -34,11 +46,18 @@
 /// crate::key::scope::tk                /*{ns}*{db}±{sc}!tk{tk}
 ///
 /// crate::key::table::all               /*{ns}*{db}*{tb}
+/// crate::key::table::bf                /*{ns}*{db}*{tb}!bf{fd}
+/// crate::key::table::cd                /*{ns}*{db}*{tb}!cd{id}{ft}{fk}
+/// crate::key::table::cs                /*{ns}*{db}*{tb}!cs{id}
+/// crate::key::table::ej                /*{ns}*{db}*{tb}!ej{en}{id}
+/// crate::key::table::en                /*{ns}*{db}*{tb}!en{en}
 /// crate::key::table::ev                /*{ns}*{db}*{tb}!ev{ev}
 /// crate::key::table::fd                /*{ns}*{db}*{tb}!fd{fd}
 /// crate::key::table::ft                /*{ns}*{db}*{tb}!ft{ft}
+/// crate::key::table::hk                /*{ns}*{db}*{tb}!hk{hk}
 /// crate::key::table::ix                /*{ns}*{db}*{tb}!ix{ix}
 /// crate::key::table::lq                /*{ns}*{db}*{tb}!lq{lq}
+/// crate::key::table::pt                /*{ns}*{db}*{tb}!pt{pt}{id}
 ///
 /// crate::key::index::all               /*{ns}*{db}*{tb}+{ix}
 /// crate::key::index::bc                /*{ns}*{db}*{tb}+{ix}!bc{id}
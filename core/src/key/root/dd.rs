@@ -0,0 +1,96 @@
+//! Stores the audit log of the DEFINE and REMOVE statements which were run
+use crate::key::error::KeyCategory;
+use crate::key::key_req::KeyRequirements;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+// The entries are stored outside of the namespace and database which they
+// belong to, so that they are kept when the namespace or database is removed.
+// Each entry is suffixed by the time at which the statement was run, and then
+// by a unique id, so that the entries of a database are kept in order.
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+#[non_exhaustive]
+pub struct Dd<'a> {
+	__: u8,
+	_a: u8,
+	_b: u8,
+	_c: u8,
+	pub ns: &'a str,
+	pub db: &'a str,
+	pub ts: u64,
+	#[serde(with = "uuid::serde::compact")]
+	pub id: Uuid,
+}
+
+pub fn new<'a>(ns: &'a str, db: &'a str, ts: u64, id: Uuid) -> Dd<'a> {
+	Dd::new(ns, db, ts, id)
+}
+
+/// Returns the prefix of the whole audit log
+pub fn all() -> Vec<u8> {
+	let mut k = crate::key::root::all::new().encode().unwrap();
+	k.extend_from_slice(&[b'!', b'd', b'd']);
+	k
+}
+
+/// Returns the prefix of the audit log of a database
+pub fn prefix(ns: &str, db: &str) -> Vec<u8> {
+	let mut k = all();
+	k.extend_from_slice(ns.as_bytes());
+	k.push(0x00);
+	k.extend_from_slice(db.as_bytes());
+	k.push(0x00);
+	k
+}
+
+/// Returns the suffix of the audit log of a database
+pub fn suffix(ns: &str, db: &str) -> Vec<u8> {
+	let mut k = prefix(ns, db);
+	k.push(0xff);
+	k
+}
+
+impl KeyRequirements for Dd<'_> {
+	fn key_category(&self) -> KeyCategory {
+		KeyCategory::DefinitionLog
+	}
+}
+
+impl<'a> Dd<'a> {
+	pub fn new(ns: &'a str, db: &'a str, ts: u64, id: Uuid) -> Self {
+		Self {
+			__: b'/',
+			_a: b'!',
+			_b: b'd',
+			_c: b'd',
+			ns,
+			db,
+			ts,
+			id,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Dd::new(
+			"testns",
+			"testdb",
+			123,
+			Uuid::from_bytes([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]),
+		);
+		let enc = Dd::encode(&val).unwrap();
+		assert_eq!(
+			enc,
+			b"/!ddtestns\x00testdb\x00\x00\x00\x00\x00\x00\x00\x00\x7b\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e\x0f\x10"
+		);
+		assert!(enc.starts_with(&prefix("testns", "testdb")));
+		let dec = Dd::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+}
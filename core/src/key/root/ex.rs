@@ -0,0 +1,102 @@
+//! Stores a record expiry entry, ordered by expiry time
+use crate::key::error::KeyCategory;
+use crate::key::key_req::KeyRequirements;
+use crate::sql::id::Id;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+
+/// Ex is used to find the records which have expired.
+///
+/// The entries of all namespaces, databases, and tables are stored together,
+/// ordered by the expiry time, as nanoseconds since the unix epoch, so that a
+/// single range scan up to the current time returns all of the expired records.
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+#[non_exhaustive]
+pub struct Ex<'a> {
+	__: u8,
+	_a: u8,
+	_b: u8,
+	_c: u8,
+	pub ts: u64,
+	pub ns: &'a str,
+	pub db: &'a str,
+	pub tb: &'a str,
+	pub id: Id,
+}
+
+pub fn new<'a>(ns: &'a str, db: &'a str, tb: &'a str, ts: u64, id: &Id) -> Ex<'a> {
+	Ex::new(ns, db, tb, ts, id.to_owned())
+}
+
+pub fn prefix() -> Vec<u8> {
+	let mut k = crate::key::root::all::new().encode().unwrap();
+	k.extend_from_slice(&[b'!', b'e', b'x']);
+	k
+}
+
+/// Returns the key after all of the entries which expire at or before the specified time
+pub fn suffix(ts: u64) -> Vec<u8> {
+	let mut k = prefix();
+	match ts.checked_add(1) {
+		Some(ts) => k.extend_from_slice(&ts.to_be_bytes()),
+		None => k.push(0xff),
+	}
+	k
+}
+
+impl KeyRequirements for Ex<'_> {
+	fn key_category(&self) -> KeyCategory {
+		KeyCategory::Expiry
+	}
+}
+
+impl<'a> Ex<'a> {
+	pub fn new(ns: &'a str, db: &'a str, tb: &'a str, ts: u64, id: Id) -> Self {
+		Self {
+			__: b'/',
+			_a: b'!',
+			_b: b'e',
+			_c: b'x',
+			ts,
+			ns,
+			db,
+			tb,
+			id,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Ex::new(
+			"testns",
+			"testdb",
+			"testtb",
+			1,
+			Id::from("testid"),
+		);
+		let enc = Ex::encode(&val).unwrap();
+		assert_eq!(
+			enc,
+			b"/!ex\x00\x00\x00\x00\x00\x00\x00\x01testns\x00testdb\x00testtb\x00\x00\x00\x00\x01testid\x00"
+		);
+
+		let dec = Ex::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+
+	#[test]
+	fn test_suffix() {
+		use super::*;
+		let val = suffix(1);
+		assert_eq!(val, b"/!ex\0\0\0\0\0\0\0\x02");
+		let key = new("testns", "testdb", "testtb", 1, &Id::from("testid")).encode().unwrap();
+		assert!(key < val);
+		let key = new("testns", "testdb", "testtb", 2, &Id::from("testid")).encode().unwrap();
+		assert!(key > val);
+	}
+}
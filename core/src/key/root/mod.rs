@@ -1,6 +1,10 @@
 pub mod all;
+pub mod dd;
+pub mod ex;
 pub mod hb;
+pub mod mg;
 pub mod nd;
 pub mod ni;
 pub mod ns;
+pub mod sv;
 pub mod us;
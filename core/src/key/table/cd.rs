@@ -0,0 +1,134 @@
+//! Stores a dependency of a computed field on a record
+use crate::key::error::KeyCategory;
+use crate::key::key_req::KeyRequirements;
+use crate::sql::id::Id;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+struct Prefix<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	pub tb: &'a str,
+	_d: u8,
+	_e: u8,
+	_f: u8,
+	pub id: Id,
+}
+
+impl<'a> Prefix<'a> {
+	fn new(ns: &'a str, db: &'a str, tb: &'a str, id: &Id) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb,
+			_d: b'!',
+			_e: b'c',
+			_f: b'd',
+			id: id.to_owned(),
+		}
+	}
+}
+
+/// Cd is used to find the records with computed fields which depend on a record.
+///
+/// The entry is stored alongside the record which is depended on, so that the
+/// dependent records can be found, and marked as stale, whenever it changes.
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+#[non_exhaustive]
+pub struct Cd<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	pub tb: &'a str,
+	_d: u8,
+	_e: u8,
+	_f: u8,
+	pub id: Id,
+	pub ft: &'a str,
+	pub fk: Id,
+}
+
+pub fn new<'a>(ns: &'a str, db: &'a str, tb: &'a str, id: &Id, ft: &'a str, fk: &Id) -> Cd<'a> {
+	Cd::new(ns, db, tb, id.to_owned(), ft, fk.to_owned())
+}
+
+pub fn prefix(ns: &str, db: &str, tb: &str, id: &Id) -> Vec<u8> {
+	let mut k = Prefix::new(ns, db, tb, id).encode().unwrap();
+	k.extend_from_slice(&[0x00]);
+	k
+}
+
+pub fn suffix(ns: &str, db: &str, tb: &str, id: &Id) -> Vec<u8> {
+	let mut k = Prefix::new(ns, db, tb, id).encode().unwrap();
+	k.extend_from_slice(&[0xff]);
+	k
+}
+
+impl KeyRequirements for Cd<'_> {
+	fn key_category(&self) -> KeyCategory {
+		KeyCategory::TableComputedDependency
+	}
+}
+
+impl<'a> Cd<'a> {
+	pub fn new(ns: &'a str, db: &'a str, tb: &'a str, id: Id, ft: &'a str, fk: Id) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb,
+			_d: b'!',
+			_e: b'c',
+			_f: b'd',
+			id,
+			ft,
+			fk,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Cd::new(
+			"testns",
+			"testdb",
+			"testtb",
+			Id::from("testid"),
+			"testft",
+			Id::from("testfk"),
+		);
+		let enc = Cd::encode(&val).unwrap();
+		assert_eq!(
+			enc,
+			b"/*testns\x00*testdb\x00*testtb\x00!cd\x00\x00\x00\x01testid\x00testft\x00\x00\x00\x00\x01testfk\x00"
+		);
+
+		let dec = Cd::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+
+	#[test]
+	fn test_prefix() {
+		let val = super::prefix("testns", "testdb", "testtb", &super::Id::from("testid"));
+		assert_eq!(val, b"/*testns\0*testdb\0*testtb\0!cd\0\0\0\x01testid\0\0");
+	}
+}
@@ -0,0 +1,98 @@
+//! Stores a pending record enrichment job
+use crate::key::error::KeyCategory;
+use crate::key::key_req::KeyRequirements;
+use crate::sql::id::Id;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+
+/// Ej is used to find the records in a table which are waiting to be enriched.
+///
+/// There is at most one job for each enrichment of a record, so writing to a
+/// record several times before it is enriched only computes the enrichment once.
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+#[non_exhaustive]
+pub struct Ej<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	pub tb: &'a str,
+	_d: u8,
+	_e: u8,
+	_f: u8,
+	pub en: &'a str,
+	pub id: Id,
+}
+
+pub fn new<'a>(ns: &'a str, db: &'a str, tb: &'a str, en: &'a str, id: &Id) -> Ej<'a> {
+	Ej::new(ns, db, tb, en, id.to_owned())
+}
+
+pub fn prefix(ns: &str, db: &str, tb: &str) -> Vec<u8> {
+	let mut k = super::all::new(ns, db, tb).encode().unwrap();
+	k.extend_from_slice(&[b'!', b'e', b'j', 0x00]);
+	k
+}
+
+pub fn suffix(ns: &str, db: &str, tb: &str) -> Vec<u8> {
+	let mut k = super::all::new(ns, db, tb).encode().unwrap();
+	k.extend_from_slice(&[b'!', b'e', b'j', 0xff]);
+	k
+}
+
+impl KeyRequirements for Ej<'_> {
+	fn key_category(&self) -> KeyCategory {
+		KeyCategory::TableEnrichmentJob
+	}
+}
+
+impl<'a> Ej<'a> {
+	pub fn new(ns: &'a str, db: &'a str, tb: &'a str, en: &'a str, id: Id) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb,
+			_d: b'!',
+			_e: b'e',
+			_f: b'j',
+			en,
+			id,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Ej::new(
+			"testns",
+			"testdb",
+			"testtb",
+			"testen",
+			Id::from("testid"),
+		);
+		let enc = Ej::encode(&val).unwrap();
+		assert_eq!(
+			enc,
+			b"/*testns\x00*testdb\x00*testtb\x00!ejtesten\x00\x00\x00\x00\x01testid\x00"
+		);
+
+		let dec = Ej::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+
+	#[test]
+	fn test_prefix() {
+		let val = super::prefix("testns", "testdb", "testtb");
+		assert_eq!(val, b"/*testns\0*testdb\0*testtb\0!ej\0");
+	}
+}
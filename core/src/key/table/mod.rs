@@ -1,6 +1,13 @@
 pub mod all;
+pub mod bf;
+pub mod cd;
+pub mod cs;
+pub mod ej;
+pub mod en;
 pub mod ev;
 pub mod fd;
 pub mod ft;
+pub mod hk;
 pub mod ix;
 pub mod lq;
+pub mod pt;
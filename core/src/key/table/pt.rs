@@ -0,0 +1,109 @@
+//! Stores the records of each partition of a partitioned table
+use crate::key::error::KeyCategory;
+use crate::key::key_req::KeyRequirements;
+use crate::sql::id::Id;
+use derive::Key;
+use serde::{Deserialize, Serialize};
+
+/// Pt is used to find the records in a single partition of a table.
+///
+/// Each partition has its own key prefix, so that the records in a
+/// partition can be scanned without scanning the rest of the table.
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Key)]
+#[non_exhaustive]
+pub struct Pt<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: &'a str,
+	_b: u8,
+	pub db: &'a str,
+	_c: u8,
+	pub tb: &'a str,
+	_d: u8,
+	_e: u8,
+	_f: u8,
+	pub pt: u16,
+	pub id: Id,
+}
+
+pub fn new<'a>(ns: &'a str, db: &'a str, tb: &'a str, pt: u16, id: &Id) -> Pt<'a> {
+	Pt::new(ns, db, tb, pt, id.to_owned())
+}
+
+/// Returns the key before all of the partitions of a table
+pub fn prefix(ns: &str, db: &str, tb: &str) -> Vec<u8> {
+	let mut k = super::all::new(ns, db, tb).encode().unwrap();
+	k.extend_from_slice(b"!pt");
+	k
+}
+
+/// Returns the key before all of the records in a partition
+pub fn partition_prefix(ns: &str, db: &str, tb: &str, pt: u16) -> Vec<u8> {
+	let mut k = prefix(ns, db, tb);
+	k.extend_from_slice(&pt.to_be_bytes());
+	k
+}
+
+/// Returns the key after all of the records in a partition
+pub fn partition_suffix(ns: &str, db: &str, tb: &str, pt: u16) -> Vec<u8> {
+	let mut k = partition_prefix(ns, db, tb, pt);
+	k.push(0xff);
+	k
+}
+
+impl KeyRequirements for Pt<'_> {
+	fn key_category(&self) -> KeyCategory {
+		KeyCategory::TablePartition
+	}
+}
+
+impl<'a> Pt<'a> {
+	pub fn new(ns: &'a str, db: &'a str, tb: &'a str, pt: u16, id: Id) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb,
+			_d: b'!',
+			_e: b'p',
+			_f: b't',
+			pt,
+			id,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	#[test]
+	fn key() {
+		use super::*;
+		#[rustfmt::skip]
+		let val = Pt::new(
+			"testns",
+			"testdb",
+			"testtb",
+			3,
+			Id::from("testid"),
+		);
+		let enc = Pt::encode(&val).unwrap();
+		assert_eq!(enc, b"/*testns\x00*testdb\x00*testtb\x00!pt\x00\x03\x00\x00\x00\x01testid\x00");
+
+		let dec = Pt::decode(&enc).unwrap();
+		assert_eq!(val, dec);
+	}
+
+	#[test]
+	fn test_partition_range() {
+		use super::*;
+		let beg = partition_prefix("testns", "testdb", "testtb", 3);
+		let end = partition_suffix("testns", "testdb", "testtb", 3);
+		let key = new("testns", "testdb", "testtb", 3, &Id::from("testid")).encode().unwrap();
+		assert!(beg < key && key < end);
+		let key = new("testns", "testdb", "testtb", 4, &Id::from("testid")).encode().unwrap();
+		assert!(key > end);
+	}
+}
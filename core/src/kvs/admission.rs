@@ -0,0 +1,201 @@
+//! Admission control for client queries.
+//!
+//! The number of client queries which execute at once on a node can be
+//! limited. Once the limit is reached, further queries wait to be admitted,
+//! and are admitted in order of the priority of their session, and then in
+//! the order in which they arrived. The priority of a session is taken from
+//! the `PRIORITY` clause of the user which signed in, so that batch and
+//! analytics users can be defined with `PRIORITY LOW`, and their queries
+//! queue behind interactive application traffic instead of delaying it.
+use crate::cnf::MAX_CONCURRENT_QUERIES;
+use crate::sql::{Object, Priority, Value};
+use channel::{Receiver, Sender};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+#[derive(Default)]
+struct State {
+	// The number of queries which have been admitted, and have not yet finished
+	running: usize,
+	// The queries which are waiting to be admitted at each priority, in arrival order
+	waiting: [VecDeque<Sender<()>>; 3],
+}
+
+impl State {
+	/// Hand a slot to the waiting query with the highest priority, returning whether there was one
+	fn handover(&mut self) -> bool {
+		for queue in self.waiting.iter_mut().rev() {
+			while let Some(send) = queue.pop_front() {
+				// The query may have stopped waiting
+				if send.try_send(()).is_ok() {
+					return true;
+				}
+			}
+		}
+		false
+	}
+}
+
+pub(crate) struct Admission {
+	// The number of queries which can execute at once, or 0 for no limit
+	limit: AtomicUsize,
+	// The queries which are executing, or waiting to execute
+	state: Mutex<State>,
+}
+
+impl Default for Admission {
+	fn default() -> Self {
+		Self {
+			limit: AtomicUsize::new(*MAX_CONCURRENT_QUERIES),
+			state: Mutex::new(State::default()),
+		}
+	}
+}
+
+/// Allows a query to execute, until it is dropped
+pub(crate) struct Permit {
+	admission: Arc<Admission>,
+}
+
+impl Drop for Permit {
+	fn drop(&mut self) {
+		let mut state = self.admission.state.lock().unwrap();
+		if !state.handover() {
+			state.running -= 1;
+		}
+	}
+}
+
+/// Stops a query from waiting, even if the admission is cancelled
+struct Waiting<'a> {
+	admission: &'a Arc<Admission>,
+	recv: Receiver<()>,
+}
+
+impl Drop for Waiting<'_> {
+	fn drop(&mut self) {
+		let mut state = self.admission.state.lock().unwrap();
+		// No further slot can be handed to this query
+		self.recv.close();
+		// Pass on a slot which was handed over, but never used
+		if self.recv.try_recv().is_ok() && !state.handover() {
+			state.running -= 1;
+		}
+	}
+}
+
+impl Admission {
+	/// Wait until a query with the specified priority is allowed to execute
+	pub(crate) async fn admit(self: &Arc<Self>, priority: Priority) -> Permit {
+		let recv = {
+			let mut state = self.state.lock().unwrap();
+			let limit = self.limit.load(Ordering::Relaxed);
+			if limit == 0 || state.running < limit {
+				state.running += 1;
+				None
+			} else {
+				let (send, recv) = channel::bounded(1);
+				state.waiting[priority as usize].push_back(send);
+				Some(recv)
+			}
+		};
+		if let Some(recv) = recv {
+			let waiting = Waiting {
+				admission: self,
+				recv,
+			};
+			// The slot of a finished query is handed over to this query
+			let _ = waiting.recv.recv().await;
+		}
+		Permit {
+			admission: self.clone(),
+		}
+	}
+
+	/// Set the number of queries which can execute at once, or 0 for no limit
+	pub(crate) fn set_limit(&self, limit: usize) {
+		self.limit.store(limit, Ordering::Relaxed);
+		// Admit any waiting queries which the new limit allows
+		let mut state = self.state.lock().unwrap();
+		while (limit == 0 || state.running < limit) && state.handover() {
+			state.running += 1;
+		}
+	}
+
+	/// Output the limit, and the number of executing and waiting queries
+	pub(crate) fn output(&self) -> Value {
+		let state = self.state.lock().unwrap();
+		let waiting = |p: Priority| state.waiting[p as usize].len();
+		Value::from(Object::from(map! {
+			"limit" => Value::from(self.limit.load(Ordering::Relaxed)),
+			"running" => Value::from(state.running),
+			"queued" => Value::from(Object::from(map! {
+				"low" => Value::from(waiting(Priority::Low)),
+				"normal" => Value::from(waiting(Priority::Normal)),
+				"high" => Value::from(waiting(Priority::High)),
+			})),
+		}))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::time::Duration;
+
+	#[tokio::test]
+	async fn unlimited_admission() {
+		let admission = Arc::new(Admission::default());
+		admission.set_limit(0);
+		let a = admission.admit(Priority::Low).await;
+		let b = admission.admit(Priority::Low).await;
+		assert_eq!(admission.state.lock().unwrap().running, 2);
+		drop((a, b));
+		assert_eq!(admission.state.lock().unwrap().running, 0);
+	}
+
+	#[tokio::test]
+	async fn priority_admission() {
+		let admission = Arc::new(Admission::default());
+		admission.set_limit(1);
+		let running = admission.admit(Priority::Normal).await;
+		// A low priority query arrives before a high priority query
+		let (send, recv) = channel::unbounded();
+		let mut queries = vec![];
+		for priority in [Priority::Low, Priority::High] {
+			let admission = admission.clone();
+			let send = send.clone();
+			queries.push(tokio::spawn(async move {
+				let _permit = admission.admit(priority).await;
+				send.send(priority).await.unwrap();
+			}));
+			tokio::time::sleep(Duration::from_millis(20)).await;
+		}
+		let out = admission.output();
+		assert_eq!(out.pick(&["queued".into(), "low".into()]), Value::from(1));
+		assert_eq!(out.pick(&["queued".into(), "high".into()]), Value::from(1));
+		// The high priority query is admitted first
+		drop(running);
+		assert_eq!(recv.recv().await.unwrap(), Priority::High);
+		assert_eq!(recv.recv().await.unwrap(), Priority::Low);
+		for query in queries {
+			query.await.unwrap();
+		}
+		assert_eq!(admission.state.lock().unwrap().running, 0);
+	}
+
+	#[tokio::test]
+	async fn cancelled_admission() {
+		let admission = Arc::new(Admission::default());
+		admission.set_limit(1);
+		let running = admission.admit(Priority::Normal).await;
+		// A query stops waiting before it is admitted
+		let waiting = admission.admit(Priority::High);
+		assert!(tokio::time::timeout(Duration::from_millis(20), waiting).await.is_err());
+		drop(running);
+		// The slot is not lost
+		let _permit = admission.admit(Priority::Low).await;
+		assert_eq!(admission.state.lock().unwrap().running, 1);
+	}
+}
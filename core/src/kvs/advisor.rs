@@ -0,0 +1,294 @@
+//! Suggested indexes, based on the shape of the queries which are executed.
+//!
+//! Whenever a SELECT statement scans a whole table, because none of the indexes
+//! on the table can be used for its WHERE clause, the fields which it filters and
+//! orders by are recorded, along with the number of records which were scanned,
+//! and the number of records which matched the query. An index on the fields which
+//! are compared for equality, followed by a field which is compared as a range,
+//! and then the fields which are ordered, could be used by the query instead. The
+//! records which were scanned, but which did not match, estimate the scanning which
+//! the index would have avoided. These suggestions are available, ranked by their
+//! estimated benefit, using the `INFO FOR ADVISOR` statement.
+use crate::err::Error;
+use crate::kvs::Transaction;
+use crate::sql::statements::DefineIndexStatement;
+use crate::sql::{
+	Cond, Datetime, Expression, Ident, Idiom, Idioms, Object, Operator, Orders, Part, Subquery,
+	Value,
+};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// The maximum number of suggested indexes which are tracked
+const MAX_TRACKED_SUGGESTIONS: usize = 1000;
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct Suggestion {
+	ns: String,
+	db: String,
+	tb: String,
+	fields: Vec<Idiom>,
+}
+
+#[derive(Clone)]
+struct Counter {
+	// The number of queries which could have used the index
+	queries: u64,
+	// The number of records which were scanned by the queries
+	scanned: u64,
+	// The number of records which matched the queries
+	matched: u64,
+	// The time at which a query last scanned the table
+	last: Datetime,
+}
+
+impl Counter {
+	/// The number of records which the index would have avoided scanning
+	fn benefit(&self) -> u64 {
+		self.scanned.saturating_sub(self.matched)
+	}
+}
+
+#[derive(Default)]
+pub(crate) struct QueryAdvisor {
+	// The observed queries for each suggested index
+	suggestions: Mutex<HashMap<Suggestion, Counter>>,
+}
+
+impl QueryAdvisor {
+	/// Record a query which scanned a whole table
+	#[allow(clippy::too_many_arguments)]
+	pub(super) fn record(
+		&self,
+		ns: &str,
+		db: &str,
+		tb: &str,
+		cond: Option<&Cond>,
+		order: Option<&Orders>,
+		scanned: u64,
+		matched: u64,
+	) {
+		let fields = fields(cond, order);
+		if fields.is_empty() {
+			return;
+		}
+		let suggestion = Suggestion {
+			ns: ns.to_owned(),
+			db: db.to_owned(),
+			tb: tb.to_owned(),
+			fields,
+		};
+		let mut suggestions = self.suggestions.lock().unwrap();
+		let counter = match suggestions.get_mut(&suggestion) {
+			Some(counter) => counter,
+			None => {
+				// Make space by evicting the least beneficial suggestion
+				if suggestions.len() >= MAX_TRACKED_SUGGESTIONS {
+					if let Some(k) =
+						suggestions.iter().min_by_key(|(_, c)| c.benefit()).map(|(k, _)| k.clone())
+					{
+						suggestions.remove(&k);
+					}
+				}
+				suggestions.entry(suggestion).or_insert(Counter {
+					queries: 0,
+					scanned: 0,
+					matched: 0,
+					last: Datetime::default(),
+				})
+			}
+		};
+		counter.queries += 1;
+		counter.scanned += scanned;
+		counter.matched += matched;
+		counter.last = Datetime::default();
+	}
+
+	/// Output the suggested indexes for a database, ordered by their estimated benefit
+	pub(super) async fn output(
+		&self,
+		run: &mut Transaction,
+		ns: &str,
+		db: &str,
+	) -> Result<Value, Error> {
+		// Take a snapshot, so that the lock is not held while reading the indexes
+		let mut out: Vec<_> = {
+			let suggestions = self.suggestions.lock().unwrap();
+			suggestions
+				.iter()
+				.filter(|(s, _)| s.ns == ns && s.db == db)
+				.map(|(s, c)| (s.clone(), c.clone()))
+				.collect()
+		};
+		out.sort_by(|a, b| b.1.benefit().cmp(&a.1.benefit()).then(b.1.queries.cmp(&a.1.queries)));
+		let mut res = Vec::with_capacity(out.len());
+		for (s, c) in out {
+			// Skip the suggestion if an index has since been defined on the leading field
+			let indexes = run.all_tb_indexes(ns, db, &s.tb).await?;
+			if indexes.iter().any(|ix| ix.cols.0.first() == s.fields.first()) {
+				continue;
+			}
+			let stm = DefineIndexStatement {
+				name: Ident(name(&s.tb, &s.fields)),
+				what: Ident(s.tb.clone()),
+				cols: Idioms(s.fields.clone()),
+				..Default::default()
+			};
+			res.push(Value::from(Object::from(map! {
+				"table" => Value::from(s.tb),
+				"fields" => Value::from(s.fields.iter().map(|v| Value::from(v.to_string())).collect::<Vec<_>>()),
+				"statement" => Value::from(stm.to_string()),
+				"queries" => Value::from(c.queries),
+				"scanned" => Value::from(c.scanned),
+				"matched" => Value::from(c.matched),
+				"benefit" => Value::from(c.benefit()),
+				"last" => Value::from(c.last),
+			})));
+		}
+		Ok(res.into())
+	}
+}
+
+/// The fields which an index must contain, in order, to be used by a query
+fn fields(cond: Option<&Cond>, order: Option<&Orders>) -> Vec<Idiom> {
+	let mut equality = Vec::new();
+	let mut range = Vec::new();
+	if let Some(cond) = cond {
+		predicates(&cond.0, &mut equality, &mut range);
+	}
+	let mut fields = Vec::new();
+	for v in equality {
+		push(&mut fields, v);
+	}
+	// Only a single field can be scanned as a range within an index
+	if let Some(v) = range.into_iter().find(|v| !fields.contains(v)) {
+		fields.push(v);
+	}
+	if let Some(order) = order {
+		for v in order.0.iter().filter(|v| !v.random && is_field(&v.order)) {
+			push(&mut fields, v.order.clone());
+		}
+	}
+	fields
+}
+
+/// Collect the fields which are compared with a value in the top-level AND conditions
+fn predicates(v: &Value, equality: &mut Vec<Idiom>, range: &mut Vec<Idiom>) {
+	match v {
+		Value::Subquery(v) => {
+			if let Subquery::Value(v) = v.as_ref() {
+				predicates(v, equality, range);
+			}
+		}
+		Value::Expression(v) => {
+			if let Expression::Binary {
+				l,
+				o,
+				r,
+			} = v.as_ref()
+			{
+				match (o, l, r) {
+					(Operator::And, l, r) => {
+						predicates(l, equality, range);
+						predicates(r, equality, range);
+					}
+					(o, Value::Idiom(i), v) if is_field(i) && !matches!(v, Value::Idiom(_)) => {
+						match o {
+							Operator::Equal | Operator::Exact | Operator::Contain => {
+								push(equality, i.clone())
+							}
+							Operator::LessThan
+							| Operator::LessThanOrEqual
+							| Operator::MoreThan
+							| Operator::MoreThanOrEqual => push(range, i.clone()),
+							_ => {}
+						}
+					}
+					(o, v, Value::Idiom(i)) if is_field(i) && !matches!(v, Value::Idiom(_)) => {
+						match o {
+							Operator::Equal | Operator::Exact | Operator::Inside => {
+								push(equality, i.clone())
+							}
+							Operator::LessThan
+							| Operator::LessThanOrEqual
+							| Operator::MoreThan
+							| Operator::MoreThanOrEqual => push(range, i.clone()),
+							_ => {}
+						}
+					}
+					_ => {}
+				}
+			}
+		}
+		_ => {}
+	}
+}
+
+/// Check if an idiom is a plain field path, such as `address.city`
+fn is_field(i: &Idiom) -> bool {
+	!i.0.is_empty() && i.0.iter().all(|p| matches!(p, Part::Field(_)))
+}
+
+fn push(fields: &mut Vec<Idiom>, i: Idiom) {
+	if !fields.contains(&i) {
+		fields.push(i);
+	}
+}
+
+/// The name of a suggested index, such as `person_age_name`
+fn name(tb: &str, fields: &[Idiom]) -> String {
+	let mut name = tb.to_owned();
+	for p in fields.iter().flat_map(|i| i.0.iter()) {
+		if let Part::Field(f) = p {
+			name.push('_');
+			name.push_str(f);
+		}
+	}
+	name
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sql::statements::SelectStatement;
+	use crate::sql::Query;
+	use crate::syn;
+
+	fn select(sql: &str) -> SelectStatement {
+		let Query(mut stms) = syn::parse(sql).unwrap();
+		match stms.0.remove(0) {
+			crate::sql::Statement::Select(v) => v,
+			_ => panic!("expected a SELECT statement"),
+		}
+	}
+
+	fn suggested(sql: &str) -> Vec<String> {
+		let stm = select(sql);
+		fields(stm.cond.as_ref(), stm.order.as_ref()).iter().map(|v| v.to_string()).collect()
+	}
+
+	#[test]
+	fn equality_before_range_and_order() {
+		assert_eq!(
+			suggested("SELECT * FROM person WHERE age > 18 AND country = 'UK' ORDER BY name"),
+			vec!["country", "age", "name"]
+		);
+	}
+
+	#[test]
+	fn only_top_level_conditions() {
+		assert_eq!(
+			suggested("SELECT * FROM person WHERE (age > 18 OR admin = true) AND country = $c"),
+			vec!["country"]
+		);
+		assert!(suggested("SELECT * FROM person WHERE age > other").is_empty());
+		assert!(suggested("SELECT * FROM person").is_empty());
+	}
+
+	#[test]
+	fn names_nested_fields() {
+		let stm = select("SELECT * FROM person WHERE address.city = 'London'");
+		let fields = fields(stm.cond.as_ref(), None);
+		assert_eq!(name("person", &fields), "person_address_city");
+	}
+}
@@ -0,0 +1,128 @@
+use crate::ctx::Context;
+use crate::dbs::{Options, Transaction};
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::sql::statements::{CreateStatement, UpdateStatement};
+use crate::sql::{Data, Id, Idiom, Kind, Object, Operator, Table, Thing, Value, Values};
+use derive::Store;
+use reblessive::tree::Stk;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+/// The conversion of the existing records of a table, after
+/// the type of one of its fields has been altered.
+///
+/// The records are converted in batches by the datastore, and
+/// the progress is stored after each batch, so that a backfill
+/// resumes from where it left off if the node is restarted.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
+#[non_exhaustive]
+pub struct Backfill {
+	/// The field which is being converted
+	pub field: Idiom,
+	/// The type which the field is converted to
+	pub kind: Kind,
+	/// The expression which computes the new value from `$value`
+	pub with: Option<Value>,
+	/// The id of the last record which was processed
+	pub cursor: Option<Id>,
+	/// The number of records which have been processed
+	pub processed: u64,
+	/// The number of records which have been converted
+	pub converted: u64,
+	/// The number of records which were moved to the quarantine table
+	pub rejected: u64,
+	/// Whether all of the records in the table have been processed
+	pub complete: bool,
+}
+
+impl Backfill {
+	/// The table which records that can not be converted are written to
+	pub(crate) fn quarantine(tb: &str) -> String {
+		format!("{tb}_quarantine")
+	}
+
+	/// Convert the field of a single record, and store the result
+	pub(crate) async fn process(
+		&mut self,
+		stk: &mut Stk,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		rid: Thing,
+		doc: Value,
+	) -> Result<(), Error> {
+		let value = doc.pick(&self.field);
+		// Compute and convert the new value
+		let res = match self.convert(stk, ctx, opt, txn, &rid, &doc, value.clone()).await {
+			// Update the record with the converted value
+			Ok(v) => UpdateStatement {
+				what: Values(vec![Value::from(rid.clone())]),
+				data: Some(Data::SetExpression(vec![(self.field.clone(), Operator::Equal, v)])),
+				..Default::default()
+			}
+			.compute(stk, ctx, opt, txn, None)
+			.await
+			.map(|_| ()),
+			Err(e) => Err(e),
+		};
+		// Move any value which can not be converted to the quarantine table
+		match res {
+			Ok(_) => self.converted += 1,
+			Err(e) => {
+				CreateStatement {
+					what: Values(vec![Value::Table(Table(Self::quarantine(&rid.tb)))]),
+					data: Some(Data::ContentExpression(Value::from(Object::from(map! {
+						"record" => Value::from(rid.clone()),
+						"field" => Value::from(self.field.to_string()),
+						"value" => value,
+						"error" => Value::from(e.to_string()),
+					})))),
+					..Default::default()
+				}
+				.compute(stk, ctx, opt, txn, None)
+				.await?;
+				self.rejected += 1;
+			}
+		}
+		self.processed += 1;
+		self.cursor = Some(rid.id);
+		Ok(())
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	async fn convert(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		rid: &Thing,
+		doc: &Value,
+		value: Value,
+	) -> Result<Value, Error> {
+		let value = match &self.with {
+			Some(expr) => {
+				let mut ctx = Context::new(ctx);
+				ctx.add_value("value", value);
+				let doc = CursorDoc::new(None, Some(rid), None, Cow::Borrowed(doc));
+				expr.compute(stk, &ctx, opt, txn, Some(&doc)).await?
+			}
+			None => value,
+		};
+		value.convert_to(&self.kind)
+	}
+
+	/// The progress of this backfill
+	pub(crate) fn progress(&self) -> Value {
+		Value::from(Object::from(map! {
+			"kind" => Value::from(self.kind.to_string()),
+			"processed" => Value::from(self.processed),
+			"converted" => Value::from(self.converted),
+			"rejected" => Value::from(self.rejected),
+			"complete" => Value::from(self.complete),
+		}))
+	}
+}
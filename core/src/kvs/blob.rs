@@ -0,0 +1,217 @@
+//! Content addressed blobs, for storing file attachments.
+//!
+//! A blob is identified by the SHA-256 hash of its content, so storing the same
+//! content twice results in a single blob. The content of a blob is split into
+//! chunks which are stored in the datastore, or is stored as a single object in
+//! the object store. Records reference a blob by storing its id in a field, and
+//! the blobs which are not referenced by any record are garbage collected.
+use crate::cnf::{BLOB_CHUNK_SIZE, BLOB_OBJECT_STORE};
+use crate::err::Error;
+use crate::kvs::Transaction;
+use crate::sql::Value;
+use derive::Store;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+
+/// The definition of a stored blob
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
+#[non_exhaustive]
+pub struct Blob {
+	/// The size of the content in bytes
+	pub size: u64,
+	/// The number of chunks which the content is stored in
+	pub chunks: u32,
+	/// The time at which the blob was last stored, in seconds since the unix epoch
+	pub stored: u64,
+	/// Whether the content is stored in the object store
+	pub external: bool,
+}
+
+/// Returns the id of a blob with the specified content
+pub fn hash(data: &[u8]) -> String {
+	hex::encode(Sha256::digest(data))
+}
+
+/// Returns the path of a blob in the object store
+pub fn path(ns: &str, db: &str, bl: &str) -> String {
+	format!("blob/{ns}/{db}/{bl}")
+}
+
+/// Checks whether a string could be the id of a blob
+pub fn is_id(v: &str) -> bool {
+	v.len() == 64 && v.bytes().all(|c| c.is_ascii_digit() || (b'a'..=b'f').contains(&c))
+}
+
+/// Stores the content of a blob in the object store
+#[cfg(feature = "obs")]
+async fn put_object(ns: &str, db: &str, bl: &str, data: &[u8]) -> Result<(), Error> {
+	crate::obs::put(&path(ns, db, bl), data.to_vec()).await
+}
+
+#[cfg(not(feature = "obs"))]
+async fn put_object(_: &str, _: &str, _: &str, _: &[u8]) -> Result<(), Error> {
+	Err(Error::ObsDisabled)
+}
+
+/// Retrieves the content of a blob from the object store
+#[cfg(feature = "obs")]
+async fn get_object(ns: &str, db: &str, bl: &str) -> Result<Vec<u8>, Error> {
+	crate::obs::get(&path(ns, db, bl)).await
+}
+
+#[cfg(not(feature = "obs"))]
+async fn get_object(_: &str, _: &str, _: &str) -> Result<Vec<u8>, Error> {
+	Err(Error::ObsDisabled)
+}
+
+/// Deletes the content of a blob from the object store
+#[cfg(feature = "obs")]
+pub(crate) async fn del_object(ns: &str, db: &str, bl: &str) -> Result<(), Error> {
+	crate::obs::del(&path(ns, db, bl)).await
+}
+
+#[cfg(not(feature = "obs"))]
+pub(crate) async fn del_object(_: &str, _: &str, _: &str) -> Result<(), Error> {
+	Err(Error::ObsDisabled)
+}
+
+impl Transaction {
+	/// Store a blob, returning its id
+	pub async fn put_blob(&mut self, ns: &str, db: &str, data: &[u8]) -> Result<String, Error> {
+		let bl = hash(data);
+		let key = crate::key::database::bl::new(ns, db, &bl);
+		let stored = self.commit_timestamp().await / 1000;
+		// The content of an existing blob is the same, so only its time is updated
+		if let Some(val) = self.get(key.clone()).await? {
+			let mut blob = Blob::from(val);
+			blob.stored = stored;
+			self.set(key, blob).await?;
+			return Ok(bl);
+		}
+		let blob = match *BLOB_OBJECT_STORE {
+			true => {
+				put_object(ns, db, &bl, data).await?;
+				Blob {
+					size: data.len() as u64,
+					chunks: 0,
+					stored,
+					external: true,
+				}
+			}
+			false => {
+				let mut chunks = 0;
+				for (ch, v) in data.chunks((*BLOB_CHUNK_SIZE).max(1)).enumerate() {
+					let key = crate::key::database::bc::new(ns, db, &bl, ch as u32);
+					self.set(key, v.to_vec()).await?;
+					chunks += 1;
+				}
+				Blob {
+					size: data.len() as u64,
+					chunks,
+					stored,
+					external: false,
+				}
+			}
+		};
+		self.set(key, blob).await?;
+		Ok(bl)
+	}
+
+	/// Retrieve the definition of a blob
+	pub async fn get_blob(&mut self, ns: &str, db: &str, bl: &str) -> Result<Option<Blob>, Error> {
+		let key = crate::key::database::bl::new(ns, db, bl);
+		Ok(self.get(key).await?.map(Blob::from))
+	}
+
+	/// Retrieve a chunk of the content of a blob which is stored in the datastore
+	pub async fn get_blob_chunk(
+		&mut self,
+		ns: &str,
+		db: &str,
+		bl: &str,
+		ch: u32,
+	) -> Result<Vec<u8>, Error> {
+		let key = crate::key::database::bc::new(ns, db, bl, ch);
+		match self.get(key).await? {
+			Some(v) => Ok(v),
+			None => Err(Error::BlNotFound {
+				value: bl.to_owned(),
+			}),
+		}
+	}
+
+	/// Retrieve the whole content of a blob
+	pub async fn get_blob_data(
+		&mut self,
+		ns: &str,
+		db: &str,
+		bl: &str,
+	) -> Result<Option<Vec<u8>>, Error> {
+		let Some(blob) = self.get_blob(ns, db, bl).await? else {
+			return Ok(None);
+		};
+		if blob.external {
+			return Ok(Some(get_object(ns, db, bl).await?));
+		}
+		let mut out = Vec::with_capacity(blob.size as usize);
+		for ch in 0..blob.chunks {
+			out.extend(self.get_blob_chunk(ns, db, bl, ch).await?);
+		}
+		Ok(Some(out))
+	}
+
+	/// Delete a blob, returning whether its content is in the object store,
+	/// in which case it is for the caller to delete once this transaction commits
+	pub async fn del_blob(&mut self, ns: &str, db: &str, bl: &str) -> Result<bool, Error> {
+		let external = match self.get_blob(ns, db, bl).await? {
+			Some(blob) => blob.external,
+			None => return Ok(false),
+		};
+		self.del(crate::key::database::bl::new(ns, db, bl)).await?;
+		let beg = crate::key::database::bc::prefix(ns, db, bl);
+		let end = crate::key::database::bc::suffix(ns, db, bl);
+		self.delr(beg..end, u32::MAX).await?;
+		Ok(external)
+	}
+}
+
+/// Removes the ids of the blobs which a value references from the set
+pub(crate) fn unmark(v: &Value, set: &mut HashSet<String>) {
+	match v {
+		Value::Strand(v) => {
+			set.remove(v.as_str());
+		}
+		Value::Object(v) => v.values().for_each(|v| unmark(v, set)),
+		Value::Array(v) => v.iter().for_each(|v| unmark(v, set)),
+		_ => {}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn blob_ids() {
+		let id = hash(b"hello");
+		assert_eq!(id, "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824");
+		assert!(is_id(&id));
+		assert!(!is_id("2CF24DBA"));
+		assert!(!is_id(&id.to_uppercase()));
+	}
+
+	#[test]
+	fn unmark_nested_references() {
+		let a = hash(b"a");
+		let b = hash(b"b");
+		let mut set: HashSet<String> = [a.clone(), b.clone()].into_iter().collect();
+		let v = Value::from(vec![Value::from(map! {
+			"file".to_string() => Value::from(a.as_str()),
+		})]);
+		unmark(&v, &mut set);
+		assert_eq!(set.into_iter().collect::<Vec<_>>(), vec![b]);
+	}
+}
@@ -1,14 +1,19 @@
 use crate::idg::u32::U32;
 use crate::kvs::kv::Key;
 use crate::sql::statements::DefineAnalyzerStatement;
+use crate::sql::statements::DefineCheckStatement;
 use crate::sql::statements::DefineDatabaseStatement;
+use crate::sql::statements::DefineDictionaryStatement;
+use crate::sql::statements::DefineEnrichmentStatement;
 use crate::sql::statements::DefineEventStatement;
 use crate::sql::statements::DefineFieldStatement;
 use crate::sql::statements::DefineFunctionStatement;
+use crate::sql::statements::DefineHookStatement;
 use crate::sql::statements::DefineIndexStatement;
 use crate::sql::statements::DefineModelStatement;
 use crate::sql::statements::DefineNamespaceStatement;
 use crate::sql::statements::DefineParamStatement;
+use crate::sql::statements::DefineRoleStatement;
 use crate::sql::statements::DefineScopeStatement;
 use crate::sql::statements::DefineTableStatement;
 use crate::sql::statements::DefineTokenStatement;
@@ -30,13 +35,17 @@ pub enum Entry {
 	Tb(Arc<DefineTableStatement>),
 	// Multi definitions
 	Azs(Arc<[DefineAnalyzerStatement]>),
+	Cks(Arc<[DefineCheckStatement]>),
 	Dbs(Arc<[DefineDatabaseStatement]>),
+	Dcs(Arc<[DefineDictionaryStatement]>),
 	Dts(Arc<[DefineTokenStatement]>),
 	Dus(Arc<[DefineUserStatement]>),
 	Evs(Arc<[DefineEventStatement]>),
+	Ens(Arc<[DefineEnrichmentStatement]>),
 	Fcs(Arc<[DefineFunctionStatement]>),
 	Fds(Arc<[DefineFieldStatement]>),
 	Fts(Arc<[DefineTableStatement]>),
+	Hks(Arc<[DefineHookStatement]>),
 	Ixs(Arc<[DefineIndexStatement]>),
 	Lvs(Arc<[LiveStatement]>),
 	Mls(Arc<[DefineModelStatement]>),
@@ -44,6 +53,7 @@ pub enum Entry {
 	Nts(Arc<[DefineTokenStatement]>),
 	Nus(Arc<[DefineUserStatement]>),
 	Pas(Arc<[DefineParamStatement]>),
+	Ros(Arc<[DefineRoleStatement]>),
 	Scs(Arc<[DefineScopeStatement]>),
 	Sts(Arc<[DefineTokenStatement]>),
 	Tbs(Arc<[DefineTableStatement]>),
@@ -0,0 +1,165 @@
+#![cfg(any(feature = "kv-fdb", test))]
+#![cfg_attr(not(feature = "kv-fdb"), allow(dead_code))]
+
+//! Splitting of large values across multiple keys, for storage engines which limit the size of values.
+//!
+//! FoundationDB rejects values which are larger than 100KB. When a chunk size is configured, values
+//! which are larger than the chunk size are split into chunks, which are each written to their own
+//! key, and the key of the value holds a short header recording the number of chunks. The chunks are
+//! stored under `/!ck`, followed by the key of the value, which is encoded so that the chunks of the
+//! keys in a range are themselves found in a single range, and so that the chunks of a key never share
+//! a range with the chunks of another key. Values are split after they are compressed and checksummed,
+//! so the chunks are joined back together before the value is decoded. Chunked values are read
+//! whether or not a chunk size is configured, so the option can be changed on an existing cluster.
+use crate::cnf::KV_CHUNK_SIZE;
+use crate::err::Error;
+use crate::key::debug::sprint_key;
+use crate::kvs::codec::MAGIC;
+use crate::kvs::{Key, Val};
+use std::ops::Range;
+
+/// The header flag which marks a chunked value
+const CHUNKED: u8 = 0b100;
+
+/// The prefix of the keys which store the chunks of values
+const PREFIX: &[u8] = b"/!ck";
+
+/// The size of a chunked value's header
+const HEADER_SIZE: usize = MAGIC.len() + 1 + 4;
+
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct Chunker {
+	// The size above which values are split, or 0 to never split values
+	size: usize,
+}
+
+impl Chunker {
+	/// The chunker configured with the `SURREAL_KV_CHUNK_SIZE` environment variable
+	pub(crate) fn from_env() -> Chunker {
+		Chunker {
+			size: *KV_CHUNK_SIZE,
+		}
+	}
+
+	/// Split an encoded value, returning the value to write to the key, and the chunks to write
+	pub(crate) fn split(&self, key: &[u8], val: Val) -> (Val, Vec<(Key, Val)>) {
+		if self.size == 0 || val.len() <= self.size {
+			return (val, vec![]);
+		}
+		let chunks: Vec<(Key, Val)> = val
+			.chunks(self.size)
+			.enumerate()
+			.map(|(i, v)| (chunk_key(key, i as u32), v.to_vec()))
+			.collect();
+		let mut head = Vec::with_capacity(HEADER_SIZE);
+		head.extend_from_slice(&MAGIC);
+		head.push(CHUNKED);
+		head.extend_from_slice(&(chunks.len() as u32).to_be_bytes());
+		(head, chunks)
+	}
+}
+
+/// The number of chunks, if the value is the header of a chunked value
+pub(crate) fn chunks(val: &[u8]) -> Option<u32> {
+	match val.len() == HEADER_SIZE && val.starts_with(&MAGIC) && val[MAGIC.len()] == CHUNKED {
+		true => Some(u32::from_be_bytes(val[MAGIC.len() + 1..].try_into().unwrap())),
+		false => None,
+	}
+}
+
+/// Join the chunks of a value, checking that none of them are missing
+pub(crate) fn join(key: &[u8], count: u32, chunks: Vec<Val>) -> Result<Val, Error> {
+	if chunks.len() != count as usize {
+		return Err(Error::CorruptedValue {
+			key: sprint_key(&key),
+			reason: format!("found {} of its {count} chunks", chunks.len()),
+		});
+	}
+	Ok(chunks.concat())
+}
+
+/// The range which contains every chunk of a key, and no chunks of any other key
+pub(crate) fn range(key: &[u8]) -> Range<Key> {
+	let beg = encode(key);
+	let mut end = beg.clone();
+	end.push(0xff);
+	beg..end
+}
+
+/// The range which contains the chunks of every key in a range
+pub(crate) fn range_of(beg: &[u8], end: &[u8]) -> Range<Key> {
+	encode(beg)..encode(end)
+}
+
+/// The key which stores a chunk of a value
+fn chunk_key(key: &[u8], index: u32) -> Key {
+	let mut k = encode(key);
+	k.extend_from_slice(&index.to_be_bytes());
+	k
+}
+
+/// Encode a key after the chunk prefix, escaping null bytes and adding a null terminator, which
+/// keeps keys in order and ensures that no encoded key starts with another encoded key. As values
+/// never have 2^24 or more chunks, the first byte of a chunk index is never 0xff, so the chunks of
+/// a key can not be mistaken for the chunks of a longer key, whose escaped null byte is 0x00 0xff.
+fn encode(key: &[u8]) -> Key {
+	let mut k = Vec::with_capacity(PREFIX.len() + key.len() + 5);
+	k.extend_from_slice(PREFIX);
+	for b in key {
+		k.push(*b);
+		if *b == 0x00 {
+			k.push(0xff);
+		}
+	}
+	k.push(0x00);
+	k
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const CHUNKER: Chunker = Chunker {
+		size: 10,
+	};
+
+	#[test]
+	fn values_are_split_and_joined() {
+		let val = b"surreal".repeat(10);
+		let (head, parts) = CHUNKER.split(b"key", val.clone());
+		assert_eq!(parts.len(), 7);
+		assert_eq!(chunks(&head), Some(7));
+		let rng = range(b"key");
+		assert!(parts.iter().all(|(k, _)| rng.contains(k)));
+		let parts = parts.into_iter().map(|(_, v)| v).collect::<Vec<_>>();
+		assert_eq!(join(b"key", 7, parts.clone()).unwrap(), val);
+		assert!(join(b"key", 8, parts).is_err());
+	}
+
+	#[test]
+	fn small_values_are_not_split() {
+		let (head, parts) = CHUNKER.split(b"key", b"small".to_vec());
+		assert_eq!(head, b"small");
+		assert!(parts.is_empty());
+		let (_, parts) = Chunker::default().split(b"key", b"surreal".repeat(10));
+		assert!(parts.is_empty());
+		assert_eq!(chunks(&MAGIC), None);
+	}
+
+	#[test]
+	fn chunk_ranges_do_not_overlap() {
+		let keys: [&[u8]; 5] = [b"a", b"a\x00", b"a\x00\x00", b"a\x01", b"ab"];
+		for a in keys {
+			let rng = range(a);
+			for b in keys.iter().filter(|b| **b != a) {
+				assert!(!rng.contains(&chunk_key(b, 0)), "{a:?} {b:?}");
+				assert!(!rng.contains(&chunk_key(b, 1 << 20)), "{a:?} {b:?}");
+			}
+			// The chunks of the keys in a range are in the chunk range
+			for (beg, end) in [(&b"a"[..], &b"b"[..]), (&b"a\x00"[..], &b"a\x01"[..])] {
+				let inside = a >= beg && a < end;
+				assert_eq!(range_of(beg, end).contains(&chunk_key(a, 3)), inside, "{a:?}");
+			}
+		}
+	}
+}
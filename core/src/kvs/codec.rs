@@ -0,0 +1,155 @@
+#![cfg(any(feature = "kv-tikv", feature = "kv-fdb", test))]
+
+//! Compression and checksums for the values stored in the distributed storage engines.
+//!
+//! When enabled, values are written with a short header, followed by a CRC32 checksum, and
+//! then the value itself, which is compressed with Snappy when this reduces its size. This
+//! reduces the amount of data sent to the storage nodes, and ensures that any corruption in
+//! the storage layer or on the network is reported when the value is read, rather than being
+//! returned to a query. Values written before these options were enabled have no header, and
+//! are read unchanged, so the options can be enabled on an existing cluster.
+use crate::cnf::{KV_CHECKSUMS, KV_COMPRESSION};
+use crate::err::Error;
+use crate::key::debug::sprint_key;
+use crate::kvs::Val;
+
+/// The bytes which mark the start of an encoded value
+pub(super) const MAGIC: [u8; 2] = [0xfe, 0xc5];
+
+/// The header is followed by a checksum of the value
+const CHECKSUM: u8 = 0b01;
+
+/// The value is compressed
+const COMPRESSED: u8 = 0b10;
+
+/// The size below which values are not worth compressing
+const MIN_COMPRESSION_SIZE: usize = 256;
+
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct Codec {
+	// Whether values are compressed when written
+	compress: bool,
+	// Whether values are written with a checksum
+	checksum: bool,
+}
+
+impl Codec {
+	/// The codec configured with the `SURREAL_KV_*` environment variables
+	#[allow(dead_code)]
+	pub(crate) fn from_env() -> Codec {
+		Codec {
+			compress: *KV_COMPRESSION,
+			checksum: *KV_CHECKSUMS,
+		}
+	}
+
+	/// Encode a value before it is written to the storage engine
+	pub(crate) fn encode(&self, val: Val) -> Val {
+		// Values which could be mistaken for an encoded value are always encoded
+		if !self.compress && !self.checksum && !val.starts_with(&MAGIC) {
+			return val;
+		}
+		let mut flags = 0;
+		let mut body = val;
+		if self.compress && body.len() >= MIN_COMPRESSION_SIZE {
+			if let Ok(v) = snap::raw::Encoder::new().compress_vec(&body) {
+				if v.len() < body.len() {
+					flags |= COMPRESSED;
+					body = v;
+				}
+			}
+		}
+		if self.checksum {
+			flags |= CHECKSUM;
+		}
+		let mut out = Vec::with_capacity(body.len() + 7);
+		out.extend_from_slice(&MAGIC);
+		out.push(flags);
+		if self.checksum {
+			out.extend_from_slice(&crc32fast::hash(&body).to_be_bytes());
+		}
+		out.extend_from_slice(&body);
+		out
+	}
+
+	/// Decode a value read from the storage engine, verifying its checksum
+	pub(crate) fn decode(&self, key: &[u8], val: Val) -> Result<Val, Error> {
+		if !val.starts_with(&MAGIC) {
+			return Ok(val);
+		}
+		let corrupted = |reason: &str| Error::CorruptedValue {
+			key: sprint_key(&key),
+			reason: reason.to_owned(),
+		};
+		let flags = match val.get(MAGIC.len()) {
+			Some(v) if v & !(CHECKSUM | COMPRESSED) == 0 => *v,
+			_ => return Err(corrupted("the header is invalid")),
+		};
+		let mut body = &val[MAGIC.len() + 1..];
+		if flags & CHECKSUM != 0 {
+			if body.len() < 4 {
+				return Err(corrupted("the checksum is missing"));
+			}
+			let (crc, rest) = body.split_at(4);
+			if crc != crc32fast::hash(rest).to_be_bytes() {
+				return Err(corrupted("the checksum does not match"));
+			}
+			body = rest;
+		}
+		match flags & COMPRESSED {
+			0 => Ok(body.to_vec()),
+			_ => snap::raw::Decoder::new()
+				.decompress_vec(body)
+				.map_err(|_| corrupted("the value could not be decompressed")),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const ALL: Codec = Codec {
+		compress: true,
+		checksum: true,
+	};
+
+	#[test]
+	fn values_are_decoded_unchanged() {
+		let large = b"surreal".repeat(100);
+		let codecs = [
+			Codec::default(),
+			ALL,
+			Codec {
+				compress: true,
+				checksum: false,
+			},
+			Codec {
+				compress: false,
+				checksum: true,
+			},
+		];
+		for codec in codecs {
+			for val in [vec![], b"small".to_vec(), large.clone(), MAGIC.to_vec()] {
+				let enc = codec.encode(val.clone());
+				assert_eq!(codec.decode(b"key", enc).unwrap(), val, "{codec:?}");
+			}
+		}
+		assert!(ALL.encode(large.clone()).len() < large.len());
+	}
+
+	#[test]
+	fn corrupted_values_are_detected() {
+		let mut enc = ALL.encode(b"surreal".repeat(100));
+		let last = enc.len() - 1;
+		enc[last] ^= 0xff;
+		assert!(matches!(Codec::default().decode(b"key", enc), Err(Error::CorruptedValue { .. })));
+		let enc = ALL.encode(b"small".to_vec());
+		assert!(ALL.decode(b"key", enc[..5].to_vec()).is_err());
+	}
+
+	#[test]
+	fn unencoded_values_are_read() {
+		assert_eq!(ALL.decode(b"key", b"plain".to_vec()).unwrap(), b"plain");
+	}
+}
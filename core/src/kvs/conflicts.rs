@@ -0,0 +1,345 @@
+//! Statistics about the key prefixes involved in transaction conflicts.
+//!
+//! Every write transaction records the key prefixes which it modifies. When a
+//! transaction fails to commit because of a conflict with another transaction,
+//! the conflict is counted against each of those prefixes. Prefixes identify the
+//! namespace, database, table, and index involved, so that frequently contended
+//! parts of a schema can be identified, and redesigned where necessary. These
+//! statistics are available from the `system::hot_keys` view, for example with
+//! `SELECT * FROM system::hot_keys WHERE tb = 'person'`.
+//!
+//! Updates which only append to arrays, or increment numbers, produce the same
+//! result regardless of the order in which they are applied. When such an update
+//! conflicts with another transaction, it is retried automatically, re-reading the
+//! records and applying the update again. Updates which only change CRDT fields
+//! are retried in the same way, merging the state of the fields once again. The
+//! retries for each kind of operation are available using the `system::merges()`
+//! function.
+use crate::err::Error;
+use crate::sql::{Datetime, Object, Value};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// The maximum number of key prefixes for which conflicts are tracked
+const MAX_TRACKED_PREFIXES: usize = 1000;
+
+/// A warning is logged whenever the conflict count for a prefix reaches a power of this number
+const WARNING_THRESHOLD: u64 = 10;
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct Prefix {
+	kind: &'static str,
+	ns: Option<String>,
+	db: Option<String>,
+	tb: Option<String>,
+	ix: Option<String>,
+}
+
+impl Prefix {
+	/// Describe the key prefix, as used in log messages
+	fn describe(&self) -> String {
+		let mut out = match (&self.tb, &self.ix) {
+			(Some(tb), Some(ix)) => format!("index '{ix}' on table '{tb}'"),
+			(Some(tb), None) => format!("{} keys on table '{tb}'", self.kind),
+			_ => format!("{} keys", self.kind),
+		};
+		if let (Some(ns), Some(db)) = (&self.ns, &self.db) {
+			out.push_str(&format!(" in database '{db}' of namespace '{ns}'"));
+		} else if let Some(ns) = &self.ns {
+			out.push_str(&format!(" in namespace '{ns}'"));
+		}
+		out
+	}
+}
+
+struct Counter {
+	conflicts: u64,
+	last: Datetime,
+}
+
+/// A commutative operation, which can be reapplied after a conflict
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub(crate) enum Merge {
+	/// A value is appended to an array
+	Append,
+	/// A number is incremented or decremented
+	Increment,
+	/// The state of a CRDT field is merged
+	Crdt,
+}
+
+impl Merge {
+	fn name(&self) -> &'static str {
+		match self {
+			Self::Append => "append",
+			Self::Increment => "increment",
+			Self::Crdt => "crdt",
+		}
+	}
+}
+
+#[derive(Default)]
+struct Merges {
+	// The number of times updates were retried after a conflict
+	retries: u64,
+	// The number of updates which committed after being retried
+	merged: u64,
+	// The number of updates which still conflicted after every retry
+	failed: u64,
+}
+
+#[derive(Default)]
+pub(crate) struct ConflictStats {
+	// The total number of conflicting transactions
+	total: AtomicU64,
+	// The number of conflicts for each key prefix
+	prefixes: Mutex<HashMap<Prefix, Counter>>,
+	// The retries of commutative updates for each kind of operation
+	merges: Mutex<BTreeMap<Merge, Merges>>,
+}
+
+impl ConflictStats {
+	/// Record a transaction conflict involving the specified raw key prefixes
+	pub(super) fn record(&self, keys: &HashSet<Vec<u8>>) {
+		self.total.fetch_add(1, Ordering::Relaxed);
+		let mut prefixes = self.prefixes.lock().unwrap();
+		for key in keys {
+			let prefix = decode(key);
+			let counter = match prefixes.get_mut(&prefix) {
+				Some(counter) => counter,
+				None => {
+					// Make space by evicting the least contended prefix
+					if prefixes.len() >= MAX_TRACKED_PREFIXES {
+						if let Some(k) =
+							prefixes.iter().min_by_key(|(_, c)| c.conflicts).map(|(k, _)| k.clone())
+						{
+							prefixes.remove(&k);
+						}
+					}
+					prefixes.entry(prefix.clone()).or_insert(Counter {
+						conflicts: 0,
+						last: Datetime::default(),
+					})
+				}
+			};
+			counter.conflicts += 1;
+			counter.last = Datetime::default();
+			if is_power_of(counter.conflicts, WARNING_THRESHOLD) {
+				warn!(
+					"Transactions have conflicted {} times when writing {}. Frequently updated keys, such as sequential index entries or counters, can cause contention between concurrent transactions.",
+					counter.conflicts,
+					prefix.describe()
+				);
+			}
+		}
+	}
+
+	/// Output the tracked key prefixes, ordered by the number of conflicts
+	pub(crate) fn output(&self) -> Value {
+		let prefixes = self.prefixes.lock().unwrap();
+		let mut out: Vec<_> = prefixes.iter().collect();
+		out.sort_by_key(|(_, c)| std::cmp::Reverse(c.conflicts));
+		out.into_iter()
+			.map(|(p, c)| {
+				Value::from(Object::from(map! {
+					"kind" => Value::from(p.kind),
+					"ns" => Value::from(p.ns.clone()),
+					"db" => Value::from(p.db.clone()),
+					"tb" => Value::from(p.tb.clone()),
+					"ix" => Value::from(p.ix.clone()),
+					"conflicts" => Value::from(c.conflicts),
+					"last" => Value::from(c.last.clone()),
+				}))
+			})
+			.collect::<Vec<_>>()
+			.into()
+	}
+
+	/// Record that an update performing the specified operations is retried after a conflict
+	pub(crate) fn record_retry(&self, ops: &BTreeSet<Merge>) {
+		let mut merges = self.merges.lock().unwrap();
+		for op in ops {
+			merges.entry(*op).or_default().retries += 1;
+		}
+	}
+
+	/// Record whether a retried update performing the specified operations eventually committed
+	pub(crate) fn record_merge(&self, ops: &BTreeSet<Merge>, merged: bool) {
+		let mut merges = self.merges.lock().unwrap();
+		for op in ops {
+			let counter = merges.entry(*op).or_default();
+			match merged {
+				true => counter.merged += 1,
+				false => counter.failed += 1,
+			}
+		}
+	}
+
+	/// Output the retries of commutative updates for each kind of operation
+	pub(crate) fn merges(&self) -> Value {
+		let merges = self.merges.lock().unwrap();
+		merges
+			.iter()
+			.map(|(op, m)| {
+				(
+					op.name().to_string(),
+					Value::from(Object::from(map! {
+						"retries" => Value::from(m.retries),
+						"merged" => Value::from(m.merged),
+						"failed" => Value::from(m.failed),
+					})),
+				)
+			})
+			.collect::<BTreeMap<_, _>>()
+			.into()
+	}
+
+	/// The total number of conflicting transactions
+	pub(crate) fn total(&self) -> u64 {
+		self.total.load(Ordering::Relaxed)
+	}
+}
+
+fn is_power_of(v: u64, base: u64) -> bool {
+	let mut n = 1;
+	while n < v {
+		n = n.saturating_mul(base);
+	}
+	n == v
+}
+
+/// Check whether a commit error was caused by a conflict with another transaction
+pub(crate) fn is_conflict(err: &Error) -> bool {
+	matches!(err, Error::TxConflict)
+}
+
+/// Find the length of the prefix of a key which identifies the namespace,
+/// database, table, and index which the key belongs to.
+pub(super) fn prefix_len(key: &[u8]) -> usize {
+	// Find the end of the null-terminated name starting at the specified position
+	let name = |from: usize| key[from..].iter().position(|&b| b == 0).map(|i| from + i + 1);
+	// Keys outside of a namespace are grouped together
+	if !key.starts_with(b"/*") {
+		return key.len().min(2);
+	}
+	// Each level is made up of a separator followed by a name. If the separator
+	// after a name does not lead to a deeper level, the prefix ends after it.
+	let mut end = 1;
+	for sep in [b'*', b'*', b'*', b'+'] {
+		match key.get(end) {
+			Some(&b) if b == sep => match name(end + 1) {
+				Some(i) => end = i,
+				None => return key.len(),
+			},
+			Some(_) => return end + 1,
+			None => return end,
+		}
+	}
+	end
+}
+
+/// Decode a raw key prefix, as produced by [`prefix_len`]
+fn decode(key: &[u8]) -> Prefix {
+	let mut prefix = Prefix {
+		kind: "system",
+		ns: None,
+		db: None,
+		tb: None,
+		ix: None,
+	};
+	if !key.starts_with(b"/*") {
+		return prefix;
+	}
+	let parts: Vec<String> =
+		key[2..].split(|&b| b == 0).map(|s| String::from_utf8_lossy(s).into_owned()).collect();
+	let name = |i: usize, sep: char| {
+		parts.get(i).and_then(|s: &String| s.strip_prefix(sep)).map(str::to_owned)
+	};
+	prefix.kind = "namespace";
+	prefix.ns = parts.first().cloned();
+	let Some(db) = name(1, '*') else {
+		return prefix;
+	};
+	prefix.kind = "database";
+	prefix.db = Some(db);
+	let Some(tb) = name(2, '*') else {
+		return prefix;
+	};
+	prefix.tb = Some(tb);
+	prefix.kind = match parts.get(3).and_then(|s| s.chars().next()) {
+		Some('+') => {
+			prefix.ix = name(3, '+');
+			"index"
+		}
+		Some('*') => "record",
+		Some('~') => "graph",
+		Some('!') => "definition",
+		_ => "table",
+	};
+	prefix
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::key::index::Index;
+	use crate::key::table::fd;
+	use crate::key::thing;
+	use crate::sql::{Array, Id, Part};
+
+	fn prefix(key: Vec<u8>) -> Prefix {
+		decode(&key[..prefix_len(&key)])
+	}
+
+	#[test]
+	fn decode_key_prefixes() {
+		let p = prefix(thing::new("ns", "db", "tb", &Id::from("id")).into());
+		assert_eq!((p.kind, p.tb.as_deref(), p.ix.as_deref()), ("record", Some("tb"), None));
+		let fd = Array::from(vec!["a"]);
+		let p = prefix(Index::new("ns", "db", "tb", "ix", &fd, None).into());
+		assert_eq!((p.kind, p.tb.as_deref(), p.ix.as_deref()), ("index", Some("tb"), Some("ix")));
+		let p = prefix(fd::new("ns", "db", "tb", "fd").into());
+		assert_eq!(
+			(p.kind, p.db.as_deref(), p.tb.as_deref()),
+			("definition", Some("db"), Some("tb"))
+		);
+		let p = prefix(crate::key::database::tb::new("ns", "db", "tb").into());
+		assert_eq!((p.kind, p.db.as_deref(), p.tb.as_deref()), ("database", Some("db"), None));
+		let p = prefix(crate::key::root::nd::Nd::new(uuid::Uuid::nil()).into());
+		assert_eq!((p.kind, p.ns), ("system", None));
+	}
+
+	#[test]
+	fn record_conflicts() {
+		let stats = ConflictStats::default();
+		let key: Vec<u8> = thing::new("ns", "db", "tb", &Id::from("id")).into();
+		let keys = HashSet::from([key[..prefix_len(&key)].to_vec()]);
+		stats.record(&keys);
+		stats.record(&keys);
+		assert_eq!(stats.total(), 2);
+		let Value::Array(out) = stats.output() else {
+			panic!("Expected an array");
+		};
+		assert_eq!(out.len(), 1);
+		assert_eq!(out[0].pick(&[Part::from("conflicts")]), Value::from(2u64));
+		assert!(is_conflict(&Error::TxConflict));
+		assert!(!is_conflict(&Error::Tx("Resource busy: ".to_owned())));
+		assert!(!is_conflict(&Error::TxFinished));
+	}
+
+	#[test]
+	fn record_merges() {
+		let stats = ConflictStats::default();
+		let ops = BTreeSet::from([Merge::Append, Merge::Increment]);
+		stats.record_retry(&ops);
+		stats.record_retry(&ops);
+		stats.record_merge(&ops, true);
+		stats.record_retry(&BTreeSet::from([Merge::Increment]));
+		stats.record_merge(&BTreeSet::from([Merge::Increment]), false);
+		assert_eq!(
+			stats.merges().to_string(),
+			"{ append: { failed: 0, merged: 1, retries: 2 }, increment: { failed: 1, merged: 1, retries: 3 } }"
+		);
+	}
+}
@@ -0,0 +1,437 @@
+//! A conformance suite for storage engines.
+//!
+//! The layers above the key-value store rely on every storage engine behaving
+//! in the same way. This module runs the behaviour which the key-value tests
+//! check against any datastore, so that a new storage engine can be validated
+//! with a single call to [`run`], and the returned [`Report`] lists the result
+//! of each check.
+//!
+//! A storage engine must support the following, which are required checks:
+//! - `raw`: reading, writing, and deleting single keys, including writes and
+//!   deletes which only succeed if the key has an expected value
+//! - `scan`: scanning a range of keys in key order, with a limit, and in pages
+//! - `delr`: deleting a range of keys, in batches
+//! - `cancel`: discarding the writes of a cancelled transaction
+//! - `snapshot`: reading a snapshot, which excludes the uncommitted writes of
+//!   other transactions
+//! - `multireader`: running several read transactions at once
+//! - `timestamp_to_versionstamp`: assigning increasing versionstamps to
+//!   increasing timestamps
+//!
+//! A storage engine may support the following, which are optional checks:
+//! - `multiwriter_different_keys`: committing several write transactions which
+//!   are open at once, and write to different keys
+//! - `multiwriter_same_keys_conflict`: rejecting the commit of a transaction
+//!   which wrote to a key which another transaction has since written to
+//!
+//! The optional checks are skipped for storage engines which do not allow
+//! several write transactions to be open at once.
+//!
+//! The checks write keys below a prefix which no other keys use, and in a
+//! dedicated namespace and database, and these keys are removed afterwards.
+use crate::err::Error;
+use crate::key::error::KeyCategory;
+use crate::kvs::{Datastore, EngineCapabilities, Key, LockType::*, ScanPage, TransactionType::*};
+use std::fmt::{self, Display};
+use std::ops::Range;
+
+/// The prefix of the keys which the checks write
+const PREFIX: &[u8] = b"\x00conformance/";
+
+/// The namespace and database of the versionstamps which the checks write
+const NS: &str = "__conformance";
+const DB: &str = "__conformance";
+
+/// Whether a storage engine must pass a check
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Requirement {
+	Required,
+	Optional,
+}
+
+/// The outcome of a check
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Outcome {
+	Passed,
+	Failed(String),
+	/// The storage engine does not support the behaviour which is checked
+	Skipped,
+}
+
+/// A check of the behaviour of a storage engine
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct Check {
+	pub name: &'static str,
+	pub requirement: Requirement,
+	pub outcome: Outcome,
+}
+
+/// The outcome of running the conformance suite against a datastore
+#[derive(Debug, Default)]
+#[non_exhaustive]
+pub struct Report {
+	pub checks: Vec<Check>,
+}
+
+impl Report {
+	/// Check whether the datastore passed every required check
+	pub fn is_conformant(&self) -> bool {
+		self.checks.iter().all(|c| {
+			c.requirement == Requirement::Optional || !matches!(c.outcome, Outcome::Failed(_))
+		})
+	}
+
+	/// The checks which the datastore failed
+	pub fn failures(&self) -> impl Iterator<Item = &Check> {
+		self.checks.iter().filter(|c| matches!(c.outcome, Outcome::Failed(_)))
+	}
+
+	fn record(
+		&mut self,
+		name: &'static str,
+		requirement: Requirement,
+		res: Option<Result<(), Error>>,
+	) {
+		let outcome = match res {
+			Some(Ok(())) => Outcome::Passed,
+			Some(Err(e)) => Outcome::Failed(e.to_string()),
+			None => Outcome::Skipped,
+		};
+		self.checks.push(Check {
+			name,
+			requirement,
+			outcome,
+		});
+	}
+}
+
+impl Display for Report {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		for c in self.checks.iter() {
+			let requirement = match c.requirement {
+				Requirement::Required => "required",
+				Requirement::Optional => "optional",
+			};
+			match &c.outcome {
+				Outcome::Passed => writeln!(f, "passed  {} ({requirement})", c.name)?,
+				Outcome::Skipped => writeln!(f, "skipped {} ({requirement})", c.name)?,
+				Outcome::Failed(e) => writeln!(f, "failed  {} ({requirement}): {e}", c.name)?,
+			}
+		}
+		Ok(())
+	}
+}
+
+/// Run every check against the datastore
+pub async fn run(ds: &Datastore) -> Report {
+	use Requirement::*;
+	// Check whether several write transactions can be open at once
+	let writers = ds.engine_capabilities().contains(EngineCapabilities::CONCURRENT_WRITERS);
+	// Remove any keys which an earlier run left behind
+	if let Err(e) = clear(ds).await {
+		warn!("Unable to remove the keys of the conformance checks: {e}");
+	}
+	let mut report = Report::default();
+	report.record("raw", Required, Some(raw(ds).await));
+	report.record("scan", Required, Some(scan(ds).await));
+	report.record("delr", Required, Some(delr(ds).await));
+	report.record("cancel", Required, Some(cancel(ds).await));
+	report.record("snapshot", Required, Some(snapshot(ds).await));
+	report.record("multireader", Required, Some(multireader(ds).await));
+	report.record("timestamp_to_versionstamp", Required, Some(timestamp_to_versionstamp(ds).await));
+	report.record(
+		"multiwriter_different_keys",
+		Optional,
+		match writers {
+			true => Some(multiwriter_different_keys(ds).await),
+			false => None,
+		},
+	);
+	report.record(
+		"multiwriter_same_keys_conflict",
+		Optional,
+		match writers {
+			true => Some(multiwriter_same_keys_conflict(ds).await),
+			false => None,
+		},
+	);
+	// Remove the keys which the checks wrote
+	if let Err(e) = clear(ds).await {
+		warn!("Unable to remove the keys of the conformance checks: {e}");
+	}
+	report
+}
+
+/// The key with the specified name below the prefix of the checks
+fn key(name: &str) -> Key {
+	let mut k = PREFIX.to_vec();
+	k.extend_from_slice(name.as_bytes());
+	k
+}
+
+/// The range of keys which start with the specified name
+fn range(name: &str) -> Range<Key> {
+	let beg = key(name);
+	let mut end = beg.clone();
+	end.push(0xff);
+	beg..end
+}
+
+/// Fail a check with the specified reason, unless the condition holds
+fn ensure(cond: bool, reason: &str) -> Result<(), Error> {
+	match cond {
+		true => Ok(()),
+		false => Err(Error::Ds(reason.to_owned())),
+	}
+}
+
+/// Remove the keys and versionstamps which the checks write
+async fn clear(ds: &Datastore) -> Result<(), Error> {
+	let mut tx = ds.transaction(Write, Optimistic).await?;
+	let mut end = PREFIX.to_vec();
+	end.push(0xff);
+	tx.delr(PREFIX.to_vec()..end, 1000).await?;
+	let beg = crate::key::database::ts::prefix(NS, DB);
+	let end = crate::key::database::ts::suffix(NS, DB);
+	tx.delr(beg..end, 1000).await?;
+	tx.commit().await
+}
+
+async fn raw(ds: &Datastore) -> Result<(), Error> {
+	// Put a key, which can not be put again
+	let mut tx = ds.transaction(Write, Optimistic).await?;
+	tx.put(KeyCategory::Unknown, key("raw"), "one").await?;
+	tx.commit().await?;
+	let mut tx = ds.transaction(Write, Optimistic).await?;
+	ensure(
+		tx.put(KeyCategory::Unknown, key("raw"), "two").await.is_err(),
+		"a key which exists was put again",
+	)?;
+	tx.cancel().await?;
+	// Read the key
+	let mut tx = ds.transaction(Read, Optimistic).await?;
+	ensure(tx.exi(key("raw")).await?, "a key which was written does not exist")?;
+	ensure(!tx.exi(key("none")).await?, "a key which was never written exists")?;
+	ensure(tx.get(key("raw")).await? == Some(b"one".to_vec()), "a key has the wrong value")?;
+	ensure(tx.get(key("none")).await?.is_none(), "a key which was never written has a value")?;
+	tx.cancel().await?;
+	// Overwrite the key
+	let mut tx = ds.transaction(Write, Optimistic).await?;
+	tx.set(key("raw"), "two").await?;
+	tx.commit().await?;
+	// Write the key only if it has the expected value
+	let mut tx = ds.transaction(Write, Optimistic).await?;
+	ensure(
+		tx.putc(key("raw"), "tre", Some("one")).await.is_err(),
+		"a key was written although it did not have the expected value",
+	)?;
+	tx.cancel().await?;
+	let mut tx = ds.transaction(Write, Optimistic).await?;
+	tx.putc(key("raw"), "tre", Some("two")).await?;
+	tx.commit().await?;
+	let mut tx = ds.transaction(Read, Optimistic).await?;
+	ensure(tx.get(key("raw")).await? == Some(b"tre".to_vec()), "a key was not overwritten")?;
+	tx.cancel().await?;
+	// Delete the key only if it has the expected value
+	let mut tx = ds.transaction(Write, Optimistic).await?;
+	ensure(
+		tx.delc(key("raw"), Some("two")).await.is_err(),
+		"a key was deleted although it did not have the expected value",
+	)?;
+	tx.cancel().await?;
+	let mut tx = ds.transaction(Write, Optimistic).await?;
+	tx.delc(key("raw"), Some("tre")).await?;
+	tx.commit().await?;
+	// Write and delete another key
+	let mut tx = ds.transaction(Write, Optimistic).await?;
+	tx.set(key("del"), "one").await?;
+	tx.commit().await?;
+	let mut tx = ds.transaction(Write, Optimistic).await?;
+	tx.del(key("del")).await?;
+	tx.commit().await?;
+	// Check that both keys were deleted
+	let mut tx = ds.transaction(Read, Optimistic).await?;
+	ensure(tx.get(key("raw")).await?.is_none(), "a key was not deleted")?;
+	ensure(tx.get(key("del")).await?.is_none(), "a key was not deleted")?;
+	tx.cancel().await
+}
+
+async fn scan(ds: &Datastore) -> Result<(), Error> {
+	// Write the keys in reverse order
+	let mut tx = ds.transaction(Write, Optimistic).await?;
+	for i in (0..25).rev() {
+		tx.set(key(&format!("scan{i:03}")), i.to_string()).await?;
+	}
+	tx.commit().await?;
+	let mut tx = ds.transaction(Read, Optimistic).await?;
+	// Scan the full range
+	let all = tx.scan(range("scan"), u32::MAX).await?;
+	ensure(all.len() == 25, "a scan did not return every key in the range")?;
+	ensure(all.windows(2).all(|w| w[0].0 < w[1].0), "a scan did not return the keys in order")?;
+	ensure(
+		all[0] == (key("scan000"), b"0".to_vec()),
+		"a scan did not return the values of the keys",
+	)?;
+	// Scan a range which excludes its end
+	let val = tx.scan(key("scan005")..key("scan010"), u32::MAX).await?;
+	ensure(val.len() == 5, "a scan did not stop before the end of the range")?;
+	ensure(val[0].0 == key("scan005"), "a scan did not start at the beginning of the range")?;
+	// Scan a limited number of keys
+	let val = tx.scan(range("scan"), 10).await?;
+	ensure(val.len() == 10, "a scan returned more keys than its limit")?;
+	// Scan the full range in pages which are smaller than the range
+	let mut next = Some(ScanPage::from(range("scan")));
+	let mut keys = vec![];
+	while let Some(page) = next {
+		let res = tx.scan_paged(page, 10).await?;
+		ensure(res.values.len() <= 10, "a page of a scan returned more keys than its limit")?;
+		next = res.next_page;
+		keys.extend(res.values.into_iter().map(|(k, _)| k));
+	}
+	ensure(
+		keys.iter().eq(all.iter().map(|(k, _)| k)),
+		"a paged scan did not return every key in the range once",
+	)?;
+	tx.cancel().await
+}
+
+async fn delr(ds: &Datastore) -> Result<(), Error> {
+	let mut tx = ds.transaction(Write, Optimistic).await?;
+	for i in 0..25 {
+		tx.set(key(&format!("delr{i:03}")), i.to_string()).await?;
+	}
+	tx.commit().await?;
+	// Delete a range which is larger than the batch size
+	let mut tx = ds.transaction(Write, Optimistic).await?;
+	tx.delr(key("delr010")..range("delr").end, 4).await?;
+	tx.commit().await?;
+	let mut tx = ds.transaction(Read, Optimistic).await?;
+	let val = tx.scan(range("delr"), u32::MAX).await?;
+	ensure(val.len() == 10, "a range delete did not delete exactly the keys in the range")?;
+	tx.cancel().await
+}
+
+async fn cancel(ds: &Datastore) -> Result<(), Error> {
+	let mut tx = ds.transaction(Write, Optimistic).await?;
+	tx.set(key("cancel"), "one").await?;
+	tx.cancel().await?;
+	let mut tx = ds.transaction(Read, Optimistic).await?;
+	ensure(tx.get(key("cancel")).await?.is_none(), "a cancelled write was kept")?;
+	tx.cancel().await
+}
+
+async fn snapshot(ds: &Datastore) -> Result<(), Error> {
+	let mut tx = ds.transaction(Write, Optimistic).await?;
+	tx.set(key("snapshot"), "one").await?;
+	tx.commit().await?;
+	// Read the key before it is changed
+	let mut tx1 = ds.transaction(Read, Optimistic).await?;
+	ensure(tx1.get(key("snapshot")).await? == Some(b"one".to_vec()), "a key has the wrong value")?;
+	// Change the key, without committing
+	let mut txw = ds.transaction(Write, Optimistic).await?;
+	txw.set(key("snapshot"), "two").await?;
+	// Uncommitted writes are not visible to other transactions
+	let mut tx2 = ds.transaction(Read, Optimistic).await?;
+	ensure(
+		tx2.get(key("snapshot")).await? == Some(b"one".to_vec()),
+		"an uncommitted write was visible to another transaction",
+	)?;
+	txw.commit().await?;
+	// Committed writes are not visible to transactions which began earlier
+	ensure(
+		tx1.get(key("snapshot")).await? == Some(b"one".to_vec()),
+		"a write was visible to a transaction which began before it committed",
+	)?;
+	tx1.cancel().await?;
+	tx2.cancel().await?;
+	// Committed writes are visible to transactions which begin later
+	let mut tx = ds.transaction(Read, Optimistic).await?;
+	ensure(
+		tx.get(key("snapshot")).await? == Some(b"two".to_vec()),
+		"a committed write was not visible",
+	)?;
+	tx.cancel().await
+}
+
+async fn multireader(ds: &Datastore) -> Result<(), Error> {
+	let mut tx = ds.transaction(Write, Optimistic).await?;
+	tx.set(key("multireader"), "one").await?;
+	tx.commit().await?;
+	let mut txs = vec![];
+	for _ in 0..3 {
+		txs.push(ds.transaction(Read, Optimistic).await?);
+	}
+	for tx in txs.iter_mut() {
+		ensure(
+			tx.get(key("multireader")).await? == Some(b"one".to_vec()),
+			"a key has the wrong value",
+		)?;
+	}
+	for mut tx in txs {
+		tx.cancel().await?;
+	}
+	Ok(())
+}
+
+async fn timestamp_to_versionstamp(ds: &Datastore) -> Result<(), Error> {
+	let mut vs = vec![];
+	for ts in 0..3 {
+		let mut tx = ds.transaction(Write, Optimistic).await?;
+		tx.set_timestamp_for_versionstamp(ts, NS, DB, true).await?;
+		tx.commit().await?;
+		let mut tx = ds.transaction(Read, Optimistic).await?;
+		let v = tx.get_versionstamp_from_timestamp(ts, NS, DB, true).await?;
+		tx.cancel().await?;
+		ensure(v.is_some(), "a timestamp has no versionstamp")?;
+		vs.extend(v);
+	}
+	ensure(
+		vs.windows(2).all(|w| w[0] < w[1]),
+		"the versionstamps of later timestamps did not increase",
+	)
+}
+
+async fn multiwriter_different_keys(ds: &Datastore) -> Result<(), Error> {
+	let mut txs = vec![];
+	for i in 0..3 {
+		let mut tx = ds.transaction(Write, Optimistic).await?;
+		tx.set(key(&format!("multiwriter{i}")), i.to_string()).await?;
+		txs.push(tx);
+	}
+	for mut tx in txs {
+		tx.commit().await?;
+	}
+	let mut tx = ds.transaction(Read, Optimistic).await?;
+	for i in 0..3 {
+		ensure(
+			tx.get(key(&format!("multiwriter{i}"))).await? == Some(i.to_string().into_bytes()),
+			"a write of a concurrent transaction was lost",
+		)?;
+	}
+	tx.cancel().await
+}
+
+async fn multiwriter_same_keys_conflict(ds: &Datastore) -> Result<(), Error> {
+	let mut tx = ds.transaction(Write, Optimistic).await?;
+	tx.set(key("conflict"), "zero").await?;
+	tx.commit().await?;
+	// Write to the same key in two transactions
+	let mut tx1 = ds.transaction(Write, Optimistic).await?;
+	tx1.set(key("conflict"), "one").await?;
+	let mut tx2 = ds.transaction(Write, Optimistic).await?;
+	tx2.set(key("conflict"), "two").await?;
+	// Only the first transaction to commit succeeds
+	tx1.commit().await?;
+	ensure(
+		matches!(tx2.commit().await, Err(Error::TxConflict)),
+		"a conflicting write was not rejected as a conflict",
+	)?;
+	let mut tx = ds.transaction(Read, Optimistic).await?;
+	ensure(
+		tx.get(key("conflict")).await? == Some(b"one".to_vec()),
+		"a conflicting write overwrote a committed write",
+	)?;
+	tx.cancel().await
+}
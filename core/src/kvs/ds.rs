@@ -1,4 +1,4 @@
-use std::collections::{BTreeMap, BTreeSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 #[cfg(any(
 	feature = "kv-surrealkv",
 	feature = "kv-file",
@@ -9,6 +9,7 @@ use std::collections::{BTreeMap, BTreeSet};
 ))]
 use std::env;
 use std::fmt;
+use std::ops::Range;
 #[cfg(any(
 	feature = "kv-surrealkv",
 	feature = "kv-file",
@@ -18,15 +19,16 @@ use std::fmt;
 	feature = "kv-speedb"
 ))]
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 #[cfg(not(target_arch = "wasm32"))]
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use channel::{Receiver, Sender};
-use futures::{lock::Mutex, Future};
+use futures::{future, lock::Mutex, stream, Future, FutureExt, Stream, StreamExt};
 use reblessive::{tree::Stk, TreeStack};
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, RwLock};
 use tracing::instrument;
 use tracing::trace;
 
@@ -35,28 +37,58 @@ use wasmtimer::std::{SystemTime, UNIX_EPOCH};
 
 use super::tx::Transaction;
 use crate::cf;
+use crate::cnf::{
+	BACKFILL_BATCH_SIZE, BLOB_GC_GRACE_PERIOD, BLOB_GC_INTERVAL, CHANGEFEED_GC_BATCH_SIZE,
+	CHECK_RESULT_RETENTION, COMPUTED_BATCH_SIZE, ENRICHMENT_BATCH_SIZE, ENRICHMENT_RETRIES,
+	ENRICHMENT_WORKERS, EXPIRY_BATCH_SIZE, EXPORT_BATCH_SIZE, MAX_CLOCK_SKEW, MIGRATION_BATCH_SIZE,
+	NORMAL_FETCH_SIZE,
+};
 use crate::ctx::Context;
 #[cfg(feature = "jwks")]
 use crate::dbs::capabilities::NetTarget;
+use crate::dbs::capabilities::Targets;
 use crate::dbs::{
-	node::Timestamp, Attach, Capabilities, Executor, Notification, Options, Response, Session,
+	node::Timestamp, Action as NotificationAction, Attach, Capabilities, Capture, Captured,
+	Entropy, Executor, Notification, Options, Publication, ReadOnly, Response, Session, Streamed,
 	Variables,
 };
 use crate::err::Error;
 #[cfg(feature = "jwks")]
 use crate::iam::jwks::JwksCache;
-use crate::iam::{Action, Auth, Error as IamError, Resource, Role};
+use crate::iam::{Action, Auth, Error as IamError, Resource, ResourceKind, Role};
 use crate::idx::trees::store::IndexStores;
 use crate::key::root::hb::Hb;
+use crate::kvs::admission::Admission;
+use crate::kvs::advisor::QueryAdvisor;
+use crate::kvs::backfill::Backfill;
+use crate::kvs::blob::{self, Blob};
 use crate::kvs::clock::SizedClock;
 #[allow(unused_imports)]
 use crate::kvs::clock::SystemClock;
+use crate::kvs::conflicts::ConflictStats;
+use crate::kvs::engine::EngineCapabilities;
+use crate::kvs::enrichment::Enrichment;
+use crate::kvs::kv::Add;
+use crate::kvs::lq_auth;
 use crate::kvs::lq_cf::LiveQueryTracker;
+use crate::kvs::lq_compact::{CompactEvent, LiveCompactTracker};
+use crate::kvs::lq_count::{CountEvent, LiveCountTracker};
 use crate::kvs::lq_structs::{LqValue, TrackedResult, UnreachableLqType};
 use crate::kvs::lq_v2_fut::process_lq_notifications;
-use crate::kvs::{LockType, LockType::*, TransactionType, TransactionType::*};
+use crate::kvs::lq_window::{LiveWindowTracker, WindowEvent};
+use crate::kvs::metrics::Metrics;
+use crate::kvs::results::ResultCache;
+use crate::kvs::scheduler::{Job, Scheduler};
+use crate::kvs::stats::{Pending, TableStats};
+use crate::kvs::version::{
+	self, Change, Migration, Progress, MIGRATIONS, STORAGE_VERSION, UNVERSIONED,
+};
+use crate::kvs::{Key, LockType, LockType::*, ScanPage, TransactionType, TransactionType::*, Val};
 use crate::options::EngineOptions;
-use crate::sql::{self, statements::DefineUserStatement, Base, Query, Uuid, Value};
+use crate::sql::statements::{
+	DefineCheckStatement, DefineDatabaseStatement, DefineTableStatement, DefineUserStatement,
+};
+use crate::sql::{self, Base, Object, Query, Uuid, Value};
 use crate::syn;
 use crate::vs::{conv, Oracle, Versionstamp};
 
@@ -64,9 +96,6 @@ use crate::vs::{conv, Oracle, Versionstamp};
 const HEARTBEAT_BATCH_SIZE: u32 = 1000;
 const LQ_CHANNEL_SIZE: usize = 100;
 
-// The batch size used for non-paged operations (i.e. if there are more results, they are ignored)
-const NON_PAGED_BATCH_SIZE: u32 = 100_000;
-
 /// The underlying datastore instance which stores the dataset.
 #[allow(dead_code)]
 #[non_exhaustive]
@@ -94,6 +123,8 @@ pub struct Datastore {
 	versionstamp_oracle: Arc<Mutex<Oracle>>,
 	// Whether this datastore enables live query notifications to subscribers
 	pub(super) notification_channel: Option<(Sender<Notification>, Receiver<Notification>)>,
+	// Whether this datastore publishes the messages of hooks to subscribers
+	publication_channel: Option<broadcast::Sender<Publication>>,
 	// Clock for tracking time. It is read only and accessible to all transactions. It is behind a mutex as tests may write to it.
 	clock: Arc<SizedClock>,
 	// The index store cache
@@ -112,6 +143,32 @@ pub struct Datastore {
 	// The temporary directory
 	temporary_directory: Arc<PathBuf>,
 	pub(crate) lq_cf_store: Arc<RwLock<LiveQueryTracker>>,
+	// The open windows of the windowed live queries owned by this node
+	lq_windows: Arc<Mutex<LiveWindowTracker>>,
+	// The pending notifications of the live queries owned by this node which compact their notifications
+	lq_compactions: Arc<Mutex<LiveCompactTracker>>,
+	// The number of matching records of the counting live queries owned by this node
+	lq_counts: Arc<Mutex<LiveCountTracker>>,
+	// Statistics about the keys involved in transaction conflicts
+	conflicts: Arc<ConflictStats>,
+	// The shape of the queries which scanned whole tables, for suggesting indexes
+	advisor: Arc<QueryAdvisor>,
+	// The admission control for background work
+	scheduler: Arc<Scheduler>,
+	// The open connections and transactions on this node
+	metrics: Arc<Metrics>,
+	// The admission control for client queries
+	admission: Arc<Admission>,
+	// The read and write statistics of each table
+	stats: Arc<TableStats>,
+	// The results of SELECT statements with a CACHE clause
+	results: Arc<ResultCache>,
+	// The capture of the queries which are executed, for replaying them later
+	capture: Option<Arc<Capture>>,
+	// The time at which unreferenced blobs were last garbage collected
+	blob_gc: Arc<AtomicU64>,
+	// Whether the storage operations of each statement are counted
+	count_operations: bool,
 }
 
 /// We always want to be circulating the live query information
@@ -136,6 +193,31 @@ pub(super) enum Inner {
 	SurrealKV(super::surrealkv::Datastore),
 }
 
+impl Inner {
+	/// The primitives which the storage engine supports natively
+	fn engine_capabilities(&self) -> EngineCapabilities {
+		match self {
+			#[cfg(feature = "kv-rocksdb")]
+			Inner::RocksDB(_) => EngineCapabilities::CONCURRENT_WRITERS,
+			#[cfg(feature = "kv-speedb")]
+			Inner::SpeeDB(_) => EngineCapabilities::CONCURRENT_WRITERS,
+			#[cfg(feature = "kv-surrealkv")]
+			Inner::SurrealKV(_) => EngineCapabilities::CONCURRENT_WRITERS,
+			#[cfg(feature = "kv-tikv")]
+			Inner::TiKV(_) => EngineCapabilities::BATCHED_READS | EngineCapabilities::CONCURRENT_WRITERS,
+			#[cfg(feature = "kv-fdb")]
+			Inner::FoundationDB(_) => {
+				EngineCapabilities::VERSIONSTAMPED_KEYS
+					| EngineCapabilities::RANGE_DELETE
+					| EngineCapabilities::BATCHED_READS
+					| EngineCapabilities::CONCURRENT_WRITERS
+			}
+			#[allow(unreachable_patterns)]
+			_ => EngineCapabilities::none(),
+		}
+	}
+}
+
 impl fmt::Display for Datastore {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		#![allow(unused_variables)]
@@ -377,6 +459,7 @@ impl Datastore {
 			query_timeout: None,
 			transaction_timeout: None,
 			notification_channel: None,
+			publication_channel: None,
 			capabilities: Capabilities::default(),
 			engine_options: EngineOptions::default(),
 			versionstamp_oracle: Arc::new(Mutex::new(Oracle::systime_counter())),
@@ -394,10 +477,23 @@ impl Datastore {
 			))]
 			temporary_directory: Arc::new(env::temp_dir()),
 			lq_cf_store: Arc::new(RwLock::new(LiveQueryTracker::new())),
+			lq_windows: Arc::new(Mutex::new(LiveWindowTracker::default())),
+			lq_compactions: Arc::new(Mutex::new(LiveCompactTracker::default())),
+			lq_counts: Arc::new(Mutex::new(LiveCountTracker::default())),
+			blob_gc: Arc::new(AtomicU64::new(0)),
+			conflicts: Arc::new(ConflictStats::default()),
+			advisor: Arc::new(QueryAdvisor::default()),
+			scheduler: Arc::new(Scheduler::default()),
+			metrics: Arc::new(Metrics::default()),
+			admission: Arc::new(Admission::default()),
+			stats: Arc::new(TableStats::default()),
+			results: Arc::new(ResultCache::default()),
+			capture: None,
+			count_operations: false,
 		})
 	}
 
-	/// Specify whether this Datastore should run in strict mode
+	/// Specify the id of this node in the cluster
 	pub fn with_node_id(mut self, id: Uuid) -> Self {
 		self.id = id;
 		self
@@ -412,6 +508,7 @@ impl Datastore {
 	/// Specify whether this datastore should enable live query notifications
 	pub fn with_notifications(mut self) -> Self {
 		self.notification_channel = Some(channel::bounded(LQ_CHANNEL_SIZE));
+		self.publication_channel = Some(broadcast::channel(LQ_CHANNEL_SIZE).0);
 		self
 	}
 
@@ -459,6 +556,26 @@ impl Datastore {
 		self
 	}
 
+	/// Capture the queries which are executed on this Datastore, so that they can be replayed
+	pub fn with_capture(mut self, capture: Option<Capture>) -> Self {
+		self.capture = capture.map(Arc::new);
+		self
+	}
+
+	/// Count the storage operations of each statement, and record them in its response,
+	/// so that tests can check that queries do not read or write more than expected
+	pub fn with_operation_counts(mut self, enabled: bool) -> Self {
+		self.count_operations = enabled;
+		self
+	}
+
+	/// Limit the number of client queries which can execute at once, or 0 for no limit,
+	/// so that further queries are admitted in order of the priority of their session
+	pub fn with_query_concurrency(self, limit: usize) -> Self {
+		self.admission.set_limit(limit);
+		self
+	}
+
 	/// Set the engine options for the datastore
 	pub fn with_engine_options(mut self, engine_options: EngineOptions) -> Self {
 		self.engine_options = engine_options;
@@ -490,6 +607,16 @@ impl Datastore {
 		false
 	}
 
+	/// The capabilities which are allowed for this Datastore
+	pub fn capabilities(&self) -> &Capabilities {
+		&self.capabilities
+	}
+
+	/// The primitives which the storage engine of this Datastore supports natively
+	pub fn engine_capabilities(&self) -> EngineCapabilities {
+		self.inner.engine_capabilities()
+	}
+
 	/// Is authentication level enabled for this Datastore?
 	/// TODO(gguillemas): Remove this method once the legacy authentication is deprecated in v2.0.0
 	pub fn is_auth_level_enabled(&self) -> bool {
@@ -507,6 +634,26 @@ impl Datastore {
 		&self.jwks_cache
 	}
 
+	/// Whether the storage operations of each statement are counted
+	pub(crate) fn counts_operations(&self) -> bool {
+		self.count_operations
+	}
+
+	/// The statistics about transaction conflicts in this datastore
+	pub(crate) fn conflicts(&self) -> &ConflictStats {
+		&self.conflicts
+	}
+
+	/// Record that a client connection has been opened, so that it is listed by `system::connections()`
+	pub fn register_connection(&self, id: uuid::Uuid, protocol: &str) {
+		self.metrics.connect(id, protocol)
+	}
+
+	/// Record that a client connection has been closed
+	pub fn deregister_connection(&self, id: &uuid::Uuid) {
+		self.metrics.disconnect(id)
+	}
+
 	/// Setup the initial credentials
 	/// Trigger the `unreachable definition` compilation error, probably due to this issue:
 	/// https://github.com/rust-lang/rust/issues/111370
@@ -559,7 +706,10 @@ impl Datastore {
 	// In tests, it should be outside any other transaction - in isolation.
 	// We cannot easily systematise this, since we aren't counting transactions created.
 	pub async fn bootstrap(&self) -> Result<(), Error> {
-		// First we clear unreachable state that could exist by upgrading from
+		// First we upgrade the storage format, refusing datastores written by a newer release
+		trace!("Checking the storage format version");
+		self.upgrade_storage(MIGRATIONS, STORAGE_VERSION).await?;
+		// Then we clear unreachable state that could exist by upgrading from
 		// previous beta versions
 		trace!("Clearing unreachable state");
 		let mut tx = self.transaction(Write, Optimistic).await?;
@@ -628,6 +778,137 @@ impl Datastore {
 		Ok(())
 	}
 
+	// storage_version reads the storage format version of the datastore, returning None when the datastore is empty.
+	async fn storage_version(&self) -> Result<Option<u16>, Error> {
+		let mut tx = self.transaction(Read, Optimistic).await?;
+		let res = match tx.get(crate::key::root::sv::Sv::new()).await {
+			Ok(Some(v)) => version::decode(&v).map(Some),
+			// Datastores which were written before the storage format was versioned
+			Ok(None) => match tx.getr(vec![0x00]..vec![0xff], 1).await {
+				Ok(v) if v.is_empty() => Ok(None),
+				Ok(_) => Ok(Some(UNVERSIONED)),
+				Err(e) => Err(e),
+			},
+			Err(e) => Err(e),
+		};
+		tx.cancel().await?;
+		res
+	}
+
+	// upgrade_storage applies the migrations which upgrade the datastore to the target storage format version.
+	pub(crate) async fn upgrade_storage(
+		&self,
+		migrations: &[Migration],
+		target: u16,
+	) -> Result<(), Error> {
+		let current = match self.storage_version().await? {
+			Some(v) => v,
+			// A new datastore is written with the target version
+			None => {
+				let mut tx = self.transaction(Write, Optimistic).await?;
+				tx.set(crate::key::root::sv::Sv::new(), version::encode(target)).await?;
+				return tx.commit().await;
+			}
+		};
+		// Datastores can not be downgraded
+		if current > target {
+			return Err(Error::UnsupportedStorageVersion {
+				found: current,
+				supported: target,
+			});
+		}
+		for m in migrations.iter().filter(|m| m.version > current && m.version <= target) {
+			info!("Upgrading the storage format to version {}: {}", m.version, m.name);
+			while !self.migrate_batch(m).await? {}
+		}
+		if current < target {
+			let mut tx = self.transaction(Write, Optimistic).await?;
+			tx.set(crate::key::root::sv::Sv::new(), version::encode(target)).await?;
+			tx.commit().await?;
+		}
+		Ok(())
+	}
+
+	// migrate_batch migrates the next batch of keys, returning whether the migration has completed.
+	async fn migrate_batch(&self, m: &Migration) -> Result<bool, Error> {
+		let mut tx = self.transaction(Write, Optimistic).await?;
+		tx.set_background();
+		match self.migrate_batch_impl(&mut tx, m).await {
+			Ok(done) => {
+				tx.commit().await?;
+				Ok(done)
+			}
+			Err(e) => {
+				tx.cancel().await?;
+				Err(e)
+			}
+		}
+	}
+
+	async fn migrate_batch_impl(&self, tx: &mut Transaction, m: &Migration) -> Result<bool, Error> {
+		// Resume from the progress of this migration, if it was interrupted
+		let key = crate::key::root::mg::Mg::new();
+		let mut pg = match tx.get(key.clone()).await? {
+			Some(v) => Progress::from(v),
+			None => Progress::default(),
+		};
+		if pg.version != m.version {
+			pg = Progress {
+				version: m.version,
+				..Progress::default()
+			};
+		}
+		// Fetch the keys after the last key which was processed
+		let range = (m.range)();
+		let beg = match &pg.cursor {
+			Some(k) => {
+				let mut k = k.clone();
+				k.push(0x00);
+				k
+			}
+			None => range.start,
+		};
+		let res = tx.getr(beg..range.end, *MIGRATION_BATCH_SIZE).await?;
+		let done = res.len() < *MIGRATION_BATCH_SIZE as usize;
+		for (k, v) in res {
+			match (m.migrate)(&k, v)? {
+				Change::Keep => {}
+				Change::Delete => {
+					tx.del(k.clone()).await?;
+					pg.changed += 1;
+				}
+				Change::Replace(nk, nv) => {
+					if nk != k {
+						tx.del(k.clone()).await?;
+					}
+					tx.set(nk, nv).await?;
+					pg.changed += 1;
+				}
+			}
+			pg.processed += 1;
+			pg.cursor = Some(k);
+		}
+		// Store the progress, or the new version once the migration has completed
+		match done {
+			true => {
+				info!(
+					"Upgraded the storage format to version {}, changing {} of {} keys",
+					m.version, pg.changed, pg.processed
+				);
+				tx.del(key).await?;
+				tx.set(crate::key::root::sv::Sv::new(), version::encode(m.version)).await?;
+			}
+			false => {
+				info!(
+					"Upgrading the storage format to version {}, {} keys processed",
+					m.version, pg.processed
+				);
+				tx.set(key, pg).await?;
+			}
+		}
+		Ok(done)
+	}
+
 	// Node registration + "mark" stage of mark-and-sweep gc
 	pub async fn register_remove_and_archive(
 		&self,
@@ -636,10 +917,31 @@ impl Datastore {
 	) -> Result<Vec<BootstrapOperationResult>, Error> {
 		trace!("Registering node {}", node_id);
 		let timestamp = tx.clock().await;
+		// A node which is already registered has restarted with a persisted node id
+		let restarted = tx.get_nd(node_id.0).await?.is_some();
+		if restarted {
+			// Replace the heartbeats of the previous process, so that they do not expire this node
+			let end_of_time = Timestamp {
+				// We remove one, because the scan range adds one
+				value: u64::MAX - 1,
+			};
+			for hb in tx.scan_hb(&end_of_time, HEARTBEAT_BATCH_SIZE).await? {
+				if hb.nd == node_id.0 {
+					tx.del_hb(hb.hb, hb.nd).await?;
+				}
+			}
+			// The membership of the previous process is registered again below
+			tx.del_nd(node_id.0).await?;
+		}
 		self.register_membership(tx, node_id, timestamp).await?;
 		// Determine the timeout for when a cluster node is expired
 		let ts_expired = (&timestamp - &sql::duration::Duration::from_secs(5))?;
-		let dead = self.remove_dead_nodes(tx, &ts_expired).await?;
+		let mut dead = self.remove_dead_nodes(tx, &ts_expired).await?;
+		// The live queries of the previous process can no longer be notified, so reclaim them now
+		if restarted {
+			info!("Node {node_id} has restarted, so the live queries it held are being removed");
+			dead.push(*node_id);
+		}
 		trace!("Archiving dead nodes: {:?}", dead);
 		self.archive_dead_lqs(tx, &dead, node_id).await
 	}
@@ -691,7 +993,7 @@ impl Datastore {
 		for nd in nodes.iter() {
 			trace!("Archiving node {}", &nd);
 			// Scan on node prefix for LQ space
-			let node_lqs = tx.scan_ndlq(nd, NON_PAGED_BATCH_SIZE).await?;
+			let node_lqs = tx.scan_ndlq(nd, NORMAL_FETCH_SIZE).await?;
 			trace!("Found {} LQ entries for {:?}", node_lqs.len(), nd);
 			for lq in node_lqs {
 				trace!("Archiving query {:?}", &lq);
@@ -731,7 +1033,7 @@ impl Datastore {
 
 	pub async fn clear_unreachable_state(&self, tx: &mut Transaction) -> Result<(), Error> {
 		// Scan nodes
-		let cluster = tx.scan_nd(NON_PAGED_BATCH_SIZE).await?;
+		let cluster = tx.scan_nd(NORMAL_FETCH_SIZE).await?;
 		trace!("Found {} nodes", cluster.len());
 		let mut unreachable_nodes = BTreeMap::new();
 		for cl in &cluster {
@@ -742,7 +1044,7 @@ impl Datastore {
 			// We remove one, because the scan range adds one
 			value: u64::MAX - 1,
 		};
-		let hbs = tx.scan_hb(&end_of_time, NON_PAGED_BATCH_SIZE).await?;
+		let hbs = tx.scan_hb(&end_of_time, NORMAL_FETCH_SIZE).await?;
 		trace!("Found {} heartbeats", hbs.len());
 		for hb in hbs {
 			match unreachable_nodes.remove(&hb.nd.to_string()) {
@@ -768,7 +1070,7 @@ impl Datastore {
 		for cl in &cluster {
 			let nds = tx.scan_ndlq(&uuid::Uuid::parse_str(&cl.name).map_err(|e| {
                 Error::Unimplemented(format!("cluster id was not uuid when parsing to aggregate cluster live queries: {:?}", e))
-            })?, NON_PAGED_BATCH_SIZE).await?;
+            })?, NORMAL_FETCH_SIZE).await?;
 			nd_lq_set.extend(nds.into_iter().map(UnreachableLqType::Nd));
 		}
 		trace!("Found {} node live queries", nd_lq_set.len());
@@ -777,7 +1079,7 @@ impl Datastore {
 		let mut tb_lq_set: BTreeSet<UnreachableLqType> = BTreeSet::new();
 		for ndlq in &nd_lq_set {
 			let lq = ndlq.get_inner();
-			let tbs = tx.scan_tblq(&lq.ns, &lq.db, &lq.tb, NON_PAGED_BATCH_SIZE).await?;
+			let tbs = tx.scan_tblq(&lq.ns, &lq.db, &lq.tb, NORMAL_FETCH_SIZE).await?;
 			tb_lq_set.extend(tbs.into_iter().map(UnreachableLqType::Tb));
 		}
 		trace!("Found {} table live queries", tb_lq_set.len());
@@ -809,7 +1111,7 @@ impl Datastore {
 
 		// Find all the LQs we own, so that we can get the ns/ds from provided uuids
 		// We may improve this in future by tracking in web layer
-		let lqs = tx.scan_ndlq(&self.id, NON_PAGED_BATCH_SIZE).await?;
+		let lqs = tx.scan_ndlq(&self.id, NORMAL_FETCH_SIZE).await?;
 		let mut hits = vec![];
 		for lq_value in lqs {
 			if live_queries.contains(&lq_value.lq) {
@@ -870,7 +1172,7 @@ impl Datastore {
 	) -> Result<Vec<Hb>, Error> {
 		let dead = tx.scan_hb(ts, HEARTBEAT_BATCH_SIZE).await?;
 		// Delete the heartbeat and everything nested
-		tx.delr_hb(dead.clone(), NON_PAGED_BATCH_SIZE).await?;
+		tx.delr_hb(dead.clone(), NORMAL_FETCH_SIZE).await?;
 		for dead_node in dead.clone() {
 			tx.del_nd(dead_node.nd).await?;
 		}
@@ -895,6 +1197,14 @@ impl Datastore {
 		trace!("Ticking at timestamp {} ({:?})", ts, conv::u64_to_versionstamp(ts));
 		let _vs = self.save_timestamp_for_versionstamp(ts).await?;
 		self.garbage_collect_stale_change_feeds(ts).await?;
+		self.delete_expired_records(ts).await?;
+		self.delete_expired_sessions(ts).await?;
+		self.run_scheduled_checks(ts).await?;
+		self.backfill_altered_fields().await?;
+		self.enrich_queued_records().await?;
+		self.recompute_stale_fields().await?;
+		self.garbage_collect_blobs(ts).await?;
+		self.revalidate_live_queries().await?;
 		// TODO Add LQ GC
 		// TODO Add Node GC?
 		Ok(())
@@ -931,6 +1241,166 @@ impl Datastore {
 		process_lq_notifications(self, stk, opt).await
 	}
 
+	/// Close any live query windows which have ended, and send their notifications
+	pub async fn process_lq_windows(&self) -> Result<(), Error> {
+		let ts = self.clock.now().await.value;
+		self.process_lq_windows_at(ts).await
+	}
+
+	// process_lq_windows_at is the utility function that is called by process_lq_windows.
+	// It is handy for testing, because it allows you to specify the timestamp in
+	// milliseconds, without depending on a system clock.
+	pub async fn process_lq_windows_at(&self, ts: u64) -> Result<(), Error> {
+		// Windows are closed even without a notification channel, so they don't accumulate
+		let closed = self.lq_windows.lock().await.close(ts);
+		let Some(channel) = &self.notification_channel else {
+			return Ok(());
+		};
+		let opt = Options::default().with_id(self.id.0);
+		let mut stack = TreeStack::new();
+		for win in closed {
+			let txn = self.transaction(Read, Optimistic).await?.enclose();
+			// Check that the live query has not been killed
+			let key = crate::key::table::lq::new(&win.ns, &win.db, &win.tb, win.stm.id.0);
+			let key: Vec<u8> = key.into();
+			if !txn.lock().await.exi(key).await? {
+				txn.lock().await.cancel().await?;
+				self.lq_windows.lock().await.remove(&win.stm.id);
+				continue;
+			}
+			let id = win.stm.id;
+			let res = stack.enter(|stk| win.compute(stk, &opt, &txn)).finish().await;
+			txn.lock().await.cancel().await?;
+			match res {
+				Ok(result) => {
+					channel
+						.0
+						.send(Notification::new(id, NotificationAction::Window, result))
+						.await?
+				}
+				Err(e) => warn!("Unable to process the window of live query {id}: {e}"),
+			}
+		}
+		Ok(())
+	}
+
+	/// Add the records which matched windowed live queries in a committed transaction
+	pub(crate) async fn handle_window_events(&self, events: Vec<WindowEvent>) {
+		if events.is_empty() {
+			return;
+		}
+		let ts = self.clock.now().await.value;
+		let mut windows = self.lq_windows.lock().await;
+		for ev in events {
+			windows.push(ts, ev);
+		}
+	}
+
+	/// Send any compacted live query notifications whose window has ended
+	pub async fn process_lq_compactions(&self) -> Result<(), Error> {
+		let ts = self.clock.now().await.value;
+		self.process_lq_compactions_at(ts).await
+	}
+
+	// process_lq_compactions_at is the utility function that is called by process_lq_compactions.
+	// It is handy for testing, because it allows you to specify the timestamp in
+	// milliseconds, without depending on a system clock.
+	pub async fn process_lq_compactions_at(&self, ts: u64) -> Result<(), Error> {
+		// Notifications are taken even without a notification channel, so they don't accumulate
+		let due = self.lq_compactions.lock().await.flush(ts);
+		if due.is_empty() {
+			return Ok(());
+		}
+		let Some(channel) = &self.notification_channel else {
+			return Ok(());
+		};
+		// Check that the live queries have not been killed
+		let mut live = HashMap::new();
+		let mut txn = self.transaction(Read, Optimistic).await?;
+		for v in due.iter() {
+			let id = v.notification.id;
+			if !live.contains_key(&id) {
+				let key = crate::key::table::lq::new(&v.ns, &v.db, &v.tb, id.0);
+				let key: Vec<u8> = key.into();
+				let exists = match txn.exi(key).await {
+					Ok(v) => v,
+					Err(e) => {
+						txn.cancel().await?;
+						return Err(e);
+					}
+				};
+				live.insert(id, exists);
+			}
+		}
+		txn.cancel().await?;
+		for v in due {
+			let id = v.notification.id;
+			if live.get(&id).copied().unwrap_or_default() {
+				channel.0.send(v.notification).await?;
+			} else {
+				self.lq_compactions.lock().await.remove(&id);
+			}
+		}
+		Ok(())
+	}
+
+	/// Add the notifications of live queries which compact their notifications in a committed transaction
+	pub(crate) async fn handle_compact_events(
+		&self,
+		events: Vec<CompactEvent>,
+	) -> Result<(), Error> {
+		if events.is_empty() {
+			return Ok(());
+		}
+		let ts = self.clock.now().await.value;
+		let mut send = Vec::new();
+		{
+			let mut compactions = self.lq_compactions.lock().await;
+			for ev in events {
+				send.extend(compactions.push(ts, ev));
+			}
+		}
+		if let Some(channel) = &self.notification_channel {
+			for notification in send {
+				channel.0.send(notification).await?;
+			}
+		}
+		Ok(())
+	}
+
+	/// Apply the changes to the number of records matching counting live queries in a committed transaction
+	pub(crate) async fn handle_count_events(&self, events: Vec<CountEvent>) -> Result<(), Error> {
+		if events.is_empty() {
+			return Ok(());
+		}
+		let mut send = Vec::new();
+		{
+			let mut counts = self.lq_counts.lock().await;
+			for ev in events {
+				send.extend(counts.push(ev));
+			}
+		}
+		if let Some(channel) = &self.notification_channel {
+			for (id, count) in send {
+				channel
+					.0
+					.send(Notification::new(id, NotificationAction::Count, count.into()))
+					.await?;
+			}
+		}
+		Ok(())
+	}
+
+	/// Publish the messages of hooks in a committed transaction to any subscribers
+	pub(crate) fn handle_publications(&self, msgs: Vec<Publication>) {
+		if let Some(channel) = &self.publication_channel {
+			for msg in msgs {
+				// The message is dropped if there are no subscribers
+				let _ = channel.send(msg);
+			}
+		}
+	}
+
 	/// Add and kill live queries being track on the datastore
 	/// These get polled by the change feed tick
 	pub(crate) async fn handle_postprocessing_of_statements(
@@ -977,6 +1447,7 @@ impl Datastore {
 	// garbage_collect_stale_change_feeds deletes all change feed entries that are older than the watermarks.
 	pub(crate) async fn garbage_collect_stale_change_feeds(&self, ts: u64) -> Result<(), Error> {
 		let mut tx = self.transaction(Write, Optimistic).await?;
+		tx.set_background();
 		if let Err(e) = self.garbage_collect_stale_change_feeds_impl(ts, &mut tx).await {
 			return match tx.cancel().await {
                 Ok(_) => {
@@ -995,86 +1466,850 @@ impl Datastore {
 		ts: u64,
 		tx: &mut Transaction,
 	) -> Result<(), Error> {
-		// TODO Make gc batch size/limit configurable?
-		cf::gc_all_at(tx, ts, Some(100)).await?;
+		cf::gc_all_at(tx, ts, Some(*CHANGEFEED_GC_BATCH_SIZE)).await?;
 		tx.commit().await?;
 		Ok(())
 	}
 
-	// Creates a heartbeat entry for the member indicating to the cluster
-	// that the node is alive.
-	// This is the preferred way of creating heartbeats inside the database, so try to use this.
-	pub async fn heartbeat(&self) -> Result<(), Error> {
+	// delete_expired_records deletes all records with a field defined with the EXPIRE clause,
+	// where the expiry time has passed at the given timestamp.
+	pub(crate) async fn delete_expired_records(&self, ts: u64) -> Result<(), Error> {
+		// Find the expiry entries which have passed
+		let mut tx = self.transaction(Read, Optimistic).await?;
+		let res = self.find_expired_records(ts, &mut tx).await;
+		tx.cancel().await?;
+		let now = Value::from(sql::Datetime::from(
+			chrono::DateTime::from_timestamp(ts as i64, 0).unwrap_or_default(),
+		));
+		// Delete the expired records from each table
+		for (ns, db, tb, fds, keys) in res? {
+			if !fds.is_empty() {
+				// Wait until the records can be deleted without starving other work
+				self.scheduler.admit(Job::RecordExpiry, &ns, &db, &tb, keys.len() as u64).await;
+				// Only delete the records if they have not been updated in the meantime
+				let cond = fds
+					.iter()
+					.map(|fd| format!("({fd} != NONE AND {fd} <= $now)"))
+					.collect::<Vec<_>>()
+					.join(" OR ");
+				let things: Vec<Value> = keys
+					.iter()
+					.map(|k| {
+						let key: crate::key::root::ex::Ex = k.into();
+						Value::from(sql::Thing::from((key.tb, key.id)))
+					})
+					.collect();
+				let sess = Session::owner().with_ns(&ns).with_db(&db);
+				let vars = map! {
+					"things".to_string() => Value::from(things),
+					"now".to_string() => now.clone(),
+				};
+				let sql = format!("DELETE $things WHERE {cond}");
+				let res = self.execute_background(&sql, &sess, Some(vars)).await?;
+				if let Some(Err(e)) = res.into_iter().next().map(|r| r.result) {
+					warn!("Failed to delete expired records in namespace '{ns}' and database '{db}': {e}");
+					continue;
+				}
+			}
+			// Remove the expiry entries, which may remain if the records have been removed
+			let mut tx = self.transaction(Write, Optimistic).await?;
+			tx.set_background();
+			for key in keys {
+				tx.del(key).await?;
+			}
+			tx.commit().await?;
+		}
+		Ok(())
+	}
+
+	// revalidate_live_queries kills the live queries owned by this node whose users are no
+	// longer allowed to select from their tables, and notifies the users why.
+	pub(crate) async fn revalidate_live_queries(&self) -> Result<(), Error> {
+		// Find the live queries whose permissions have been revoked
+		let mut tx = self.transaction(Read, Optimistic).await?;
+		let res = self.find_revoked_live_queries(&mut tx).await;
+		tx.cancel().await?;
+		let revoked = res?;
+		if revoked.is_empty() {
+			return Ok(());
+		}
+		// Kill the live queries
 		let mut tx = self.transaction(Write, Optimistic).await?;
-		let timestamp = tx.clock().await;
-		self.heartbeat_full(&mut tx, timestamp, self.id).await?;
-		tx.commit().await
+		for (lq, _) in revoked.iter() {
+			tx.del_ndlq(lq.nd.0, lq.lq.0, &lq.ns, &lq.db).await?;
+			tx.del_tblq(&lq.ns, &lq.db, &lq.tb, lq.lq.0).await?;
+		}
+		tx.commit().await?;
+		// Discard the counters of the killed live queries
+		{
+			let mut counts = self.lq_counts.lock().await;
+			for (lq, _) in revoked.iter() {
+				counts.remove(&lq.lq);
+			}
+		}
+		// Notify the users that their live queries were killed
+		for (lq, reason) in revoked {
+			warn!(
+				"Killed live query {} on table '{}' in namespace '{}' and database '{}': {reason}",
+				lq.lq, lq.tb, lq.ns, lq.db
+			);
+			if let Some(channel) = &self.notification_channel {
+				let notification =
+					Notification::new(lq.lq, NotificationAction::Killed, Value::from(reason));
+				channel.0.send(notification).await?;
+			}
+		}
+		Ok(())
 	}
 
-	// Creates a heartbeat entry for the member indicating to the cluster
-	// that the node is alive. Intended for testing.
-	// This includes all dependencies that are hard to control and is done in such a way for testing.
-	// Inside the database, try to use the heartbeat() function instead.
-	pub async fn heartbeat_full(
+	async fn find_revoked_live_queries(
 		&self,
 		tx: &mut Transaction,
-		timestamp: Timestamp,
-		node_id: Uuid,
-	) -> Result<(), Error> {
-		tx.set_hb(timestamp, node_id.0).await
+	) -> Result<Vec<(LqValue, &'static str)>, Error> {
+		let mut out = Vec::new();
+		for lq in tx.scan_ndlq(&self.id, NORMAL_FETCH_SIZE).await? {
+			let lv = match tx.get_tb_live(&lq.ns, &lq.db, &lq.tb, &lq.lq).await {
+				Ok(v) => v,
+				Err(Error::LvNotFound {
+					..
+				}) => continue,
+				Err(e) => return Err(e),
+			};
+			if let Some(reason) = lq_auth::revoked(tx, &lq.ns, &lq.db, &lq.tb, &lv).await? {
+				out.push((lq, reason));
+			}
+		}
+		Ok(out)
 	}
 
-	// -----
-	// End cluster helpers, storage functions here
-	// -----
+	// garbage_collect_blobs deletes the blobs which no record references, and which were
+	// stored before the grace period, at most once every interval.
+	pub(crate) async fn garbage_collect_blobs(&self, ts: u64) -> Result<(), Error> {
+		let last = self.blob_gc.load(Ordering::Relaxed);
+		if last != 0 && ts < last + *BLOB_GC_INTERVAL {
+			return Ok(());
+		}
+		self.blob_gc.store(ts, Ordering::Relaxed);
+		// Find the blobs which are not referenced
+		let mut tx = self.transaction(Read, Optimistic).await?;
+		let res = self.find_unreferenced_blobs(ts, &mut tx).await;
+		tx.cancel().await?;
+		for (ns, db, bls) in res? {
+			let mut tx = self.transaction(Write, Optimistic).await?;
+			tx.set_background();
+			let mut external = Vec::new();
+			for bl in bls {
+				// Skip the blobs which have been stored again in the meantime
+				match tx.get_blob(&ns, &db, &bl).await? {
+					Some(v) if v.stored + *BLOB_GC_GRACE_PERIOD <= ts => (),
+					_ => continue,
+				}
+				if tx.del_blob(&ns, &db, &bl).await? {
+					external.push(bl);
+				}
+			}
+			tx.commit().await?;
+			// The content in the object store is deleted once the blobs are gone
+			for bl in external {
+				if let Err(e) = blob::del_object(&ns, &db, &bl).await {
+					warn!("Failed to delete the content of blob '{bl}' from the object store: {e}");
+				}
+			}
+		}
+		Ok(())
+	}
 
-	/// Create a new transaction on this datastore
-	///
-	/// ```rust,no_run
-	/// use surrealdb_core::kvs::{Datastore, TransactionType::*, LockType::*};
-	/// use surrealdb_core::err::Error;
-	///
-	/// #[tokio::main]
-	/// async fn main() -> Result<(), Error> {
-	///     let ds = Datastore::new("file://database.db").await?;
-	///     let mut tx = ds.transaction(Write, Optimistic).await?;
-	///     tx.cancel().await?;
-	///     Ok(())
-	/// }
-	/// ```
-	pub async fn transaction(
+	async fn find_unreferenced_blobs(
 		&self,
-		write: TransactionType,
-		lock: LockType,
-	) -> Result<Transaction, Error> {
-		#![allow(unused_variables)]
-		let write = match write {
-			Read => false,
-			Write => true,
-		};
-
-		let lock = match lock {
-			Pessimistic => true,
-			Optimistic => false,
-		};
+		ts: u64,
+		tx: &mut Transaction,
+	) -> Result<Vec<(String, String, HashSet<String>)>, Error> {
+		let mut out = Vec::new();
+		for ns in tx.all_ns().await?.iter() {
+			let ns = ns.name.as_str();
+			for db in tx.all_db(ns).await?.iter() {
+				let db = db.name.as_str();
+				// Find the blobs which were stored before the grace period
+				let mut bls = HashSet::new();
+				let beg = crate::key::database::bl::prefix(ns, db);
+				let end = crate::key::database::bl::suffix(ns, db);
+				let mut nxt = Some(ScanPage::from(beg..end));
+				while let Some(page) = nxt {
+					let res = tx.scan_paged(page, NORMAL_FETCH_SIZE).await?;
+					nxt = res.next_page;
+					for (k, v) in res.values {
+						let k: crate::key::database::bl::Bl = (&k).into();
+						if Blob::from(v).stored + *BLOB_GC_GRACE_PERIOD <= ts {
+							bls.insert(k.bl.to_owned());
+						}
+					}
+				}
+				// Remove the blobs which are referenced by any record
+				for tb in tx.all_tb(ns, db).await?.iter() {
+					let tb = tb.name.to_raw();
+					let beg = crate::key::thing::prefix(ns, db, &tb);
+					let end = crate::key::thing::suffix(ns, db, &tb);
+					let mut nxt = Some(ScanPage::from(beg..end));
+					while let Some(page) = nxt.filter(|_| !bls.is_empty()) {
+						let res = tx.scan_paged(page, NORMAL_FETCH_SIZE).await?;
+						nxt = res.next_page;
+						for (_, v) in res.values {
+							blob::unmark(&Value::from(v), &mut bls);
+						}
+					}
+				}
+				if !bls.is_empty() {
+					out.push((ns.to_owned(), db.to_owned(), bls));
+				}
+			}
+		}
+		Ok(out)
+	}
 
-		let inner = match &self.inner {
-			#[cfg(feature = "kv-mem")]
-			Inner::Mem(v) => {
-				let tx = v.transaction(write, lock).await?;
-				super::tx::Inner::Mem(tx)
+	// recompute_stale_fields writes the recomputed values of COMPUTED fields back to the records
+	// whose fields depend on records which have changed since the fields were computed.
+	pub(crate) async fn recompute_stale_fields(&self) -> Result<(), Error> {
+		// Find the records with stale computed fields
+		let mut tx = self.transaction(Read, Optimistic).await?;
+		let res = self.find_stale_records(&mut tx).await;
+		tx.cancel().await?;
+		// Recompute the stale records of each table
+		for (ns, db, tb, stale, discard) in res? {
+			if !stale.is_empty() {
+				// Wait until the records can be updated without starving other work
+				self.scheduler.admit(Job::FieldRecompute, &ns, &db, &tb, stale.len() as u64).await;
+				// Updating the records recomputes their fields, and removes their stale entries,
+				// but the records which have been deleted in the meantime are not created again
+				let sess = Session::owner().with_ns(&ns).with_db(&db);
+				let vars = map! {
+					"things".to_string() => Value::from(stale),
+				};
+				let sql = "UPDATE $things WHERE id != NONE RETURN NONE";
+				let res = self.execute_background(sql, &sess, Some(vars)).await?;
+				if let Some(Err(e)) = res.into_iter().next().map(|r| r.result) {
+					warn!("Failed to recompute the computed fields of records in table '{tb}' in namespace '{ns}' and database '{db}': {e}");
+				}
 			}
-			#[cfg(feature = "kv-rocksdb")]
-			Inner::RocksDB(v) => {
-				let tx = v.transaction(write, lock).await?;
-				super::tx::Inner::RocksDB(tx)
+			// Remove the stale entries of records which no longer need recomputing
+			if !discard.is_empty() {
+				let mut tx = self.transaction(Write, Optimistic).await?;
+				tx.set_background();
+				for key in discard {
+					tx.del(key).await?;
+				}
+				tx.commit().await?;
 			}
-			#[cfg(feature = "kv-speedb")]
-			Inner::SpeeDB(v) => {
-				let tx = v.transaction(write, lock).await?;
-				super::tx::Inner::SpeeDB(tx)
+		}
+		Ok(())
+	}
+
+	async fn find_stale_records(
+		&self,
+		tx: &mut Transaction,
+	) -> Result<Vec<(String, String, String, Vec<Value>, Vec<Key>)>, Error> {
+		let mut out = Vec::new();
+		for ns in tx.all_ns().await?.iter() {
+			let ns = ns.name.as_str();
+			for db in tx.all_db(ns).await?.iter() {
+				let db = db.name.as_str();
+				for tb in tx.all_tb(ns, db).await?.iter() {
+					let tb = tb.name.as_str();
+					let beg = crate::key::table::cs::prefix(ns, db, tb);
+					let end = crate::key::table::cs::suffix(ns, db, tb);
+					let res = tx.getr(beg..end, *COMPUTED_BATCH_SIZE).await?;
+					if res.is_empty() {
+						continue;
+					}
+					// Records only need recomputing if they exist, and still have computed fields
+					let computed = tx.all_tb_fields(ns, db, tb).await?.iter().any(|fd| fd.computed);
+					let mut stale = Vec::new();
+					let mut discard = Vec::new();
+					for (k, _) in res {
+						let key = crate::key::table::cs::Cs::decode(&k)?;
+						let rid: Vec<u8> = crate::key::thing::new(ns, db, tb, &key.id).into();
+						if computed && tx.exi(rid).await? {
+							stale.push(Value::from(sql::Thing::from((tb, key.id))));
+						} else {
+							discard.push(k);
+						}
+					}
+					out.push((ns.to_owned(), db.to_owned(), tb.to_owned(), stale, discard));
+				}
 			}
-			#[cfg(feature = "kv-indxdb")]
+		}
+		Ok(out)
+	}
+
+	// delete_expired_sessions deletes all scope sessions which expired before the given timestamp.
+	pub(crate) async fn delete_expired_sessions(&self, ts: u64) -> Result<(), Error> {
+		// Find the sessions which have expired
+		let mut tx = self.transaction(Read, Optimistic).await?;
+		let res = self.find_expired_sessions(ts, &mut tx).await;
+		tx.cancel().await?;
+		let keys = res?;
+		if keys.is_empty() {
+			return Ok(());
+		}
+		// Delete the expired sessions
+		let mut tx = self.transaction(Write, Optimistic).await?;
+		tx.set_background();
+		for key in keys {
+			if let Err(e) = tx.del(key).await {
+				tx.cancel().await?;
+				return Err(e);
+			}
+		}
+		tx.commit().await
+	}
+
+	async fn find_expired_sessions(
+		&self,
+		ts: u64,
+		tx: &mut Transaction,
+	) -> Result<Vec<Key>, Error> {
+		let mut out = vec![];
+		for ns in tx.all_ns().await?.iter() {
+			let ns = ns.name.as_str();
+			for db in tx.all_db(ns).await?.iter() {
+				let db = db.name.as_str();
+				let beg = crate::key::database::se::prefix(ns, db);
+				let end = crate::key::database::se::suffix(ns, db);
+				for (k, v) in tx.getr(beg..end, u32::MAX).await? {
+					let se = crate::iam::session::ScopeSession::from(v);
+					if se.expires_at.is_some_and(|v| v.0.timestamp() < ts as i64) {
+						out.push(k);
+					}
+				}
+			}
+		}
+		Ok(out)
+	}
+
+	// run_scheduled_checks runs the data quality checks which are due at the given timestamp,
+	// and records whether the result of each check passed its assertion.
+	pub(crate) async fn run_scheduled_checks(&self, ts: u64) -> Result<(), Error> {
+		// Find the checks which are due to run
+		let mut tx = self.transaction(Read, Optimistic).await?;
+		let res = self.find_due_checks(ts, &mut tx).await;
+		tx.cancel().await?;
+		// Run each check, without affecting the other checks
+		for (ns, db, ck) in res? {
+			if let Err(e) = self.run_check(ts, &ns, &db, &ck).await {
+				warn!(
+					"Failed to run check '{}' in namespace '{ns}' and database '{db}': {e}",
+					ck.name
+				);
+			}
+		}
+		Ok(())
+	}
+
+	async fn find_due_checks(
+		&self,
+		ts: u64,
+		tx: &mut Transaction,
+	) -> Result<Vec<(String, String, DefineCheckStatement)>, Error> {
+		let mut out = Vec::new();
+		for ns in tx.all_ns().await?.iter() {
+			let ns = ns.name.as_str();
+			for db in tx.all_db(ns).await?.iter() {
+				let db = db.name.as_str();
+				for ck in tx.all_db_checks(ns, db).await?.iter() {
+					// A check is due if it has not run within its schedule
+					let since = (ts + 1).saturating_sub(ck.schedule.as_secs());
+					let beg: Key = crate::key::database::cr::new(ns, db, &ck.name, since).into();
+					let end = crate::key::database::cr::suffix(ns, db, &ck.name);
+					if tx.getr(beg..end, 1).await?.is_empty() {
+						out.push((ns.to_owned(), db.to_owned(), ck.clone()));
+					}
+				}
+			}
+		}
+		Ok(out)
+	}
+
+	// run_check runs a data quality check, records the result, and notifies the webhook of any failure.
+	async fn run_check(
+		&self,
+		ts: u64,
+		ns: &str,
+		db: &str,
+		ck: &DefineCheckStatement,
+	) -> Result<(), Error> {
+		let sess = Session::owner().with_ns(ns).with_db(db);
+		let time = Value::from(sql::Datetime::from(
+			chrono::DateTime::from_timestamp(ts as i64, 0).unwrap_or_default(),
+		));
+		let mut res = map! {
+			"check".to_string() => Value::from(ck.name.to_raw()),
+			"time".to_string() => time,
+		};
+		// A check which could not be evaluated has failed
+		let passed = match self.evaluate_check(&sess, ck).await {
+			Ok((value, passed)) => {
+				res.insert("value".to_string(), value);
+				passed
+			}
+			Err(e) => {
+				res.insert("error".to_string(), Value::from(e.to_string()));
+				false
+			}
+		};
+		let status = if passed {
+			"passed"
+		} else {
+			"failed"
+		};
+		res.insert("status".to_string(), Value::from(status));
+		let res = Value::from(res);
+		// Record the result, and remove the results which are no longer retained
+		let mut tx = self.transaction(Write, Optimistic).await?;
+		tx.set_background();
+		tx.set(crate::key::database::cr::new(ns, db, &ck.name, ts), res.clone()).await?;
+		let beg = crate::key::database::cr::prefix(ns, db, &ck.name);
+		let end: Key = crate::key::database::cr::new(
+			ns,
+			db,
+			&ck.name,
+			ts.saturating_sub(*CHECK_RESULT_RETENTION),
+		)
+		.into();
+		tx.delr(beg..end, NORMAL_FETCH_SIZE).await?;
+		tx.commit().await?;
+		// Notify the webhook of the failure
+		if let (false, Some(webhook)) = (passed, &ck.webhook) {
+			let vars = map! {
+				"webhook".to_string() => Value::from(webhook.as_str()),
+				"failure".to_string() => res,
+			};
+			let res = self
+				.execute_background("http::post($webhook, $failure)", &sess, Some(vars))
+				.await?;
+			if let Some(Err(e)) = res.into_iter().next().map(|r| r.result) {
+				warn!(
+					"Failed to notify the webhook of check '{}' in namespace '{ns}' and database '{db}': {e}",
+					ck.name
+				);
+			}
+		}
+		Ok(())
+	}
+
+	// evaluate_check runs the query of a check, and returns its outcome and whether it passed the assertion.
+	async fn evaluate_check(
+		&self,
+		sess: &Session,
+		ck: &DefineCheckStatement,
+	) -> Result<(Value, bool), Error> {
+		// Run the query of the check
+		let res = self.execute_background(&ck.query.to_string(), sess, None).await?;
+		let value = match res.into_iter().next() {
+			Some(r) => ck.outcome(r.result?),
+			None => Value::None,
+		};
+		// Check the outcome against the assertion
+		let vars = map! {
+			"value".to_string() => value.clone(),
+		};
+		let sql = format!("RETURN {}", ck.assert);
+		let res = self.execute_background(&sql, sess, Some(vars)).await?;
+		let passed = match res.into_iter().next() {
+			Some(r) => r.result?.is_truthy(),
+			None => false,
+		};
+		Ok((value, passed))
+	}
+
+	// backfill_altered_fields converts the existing records of any fields which have been altered.
+	pub(crate) async fn backfill_altered_fields(&self) -> Result<(), Error> {
+		// Find the backfills which have not yet completed
+		let mut tx = self.transaction(Read, Optimistic).await?;
+		let res = self.find_pending_backfills(&mut tx).await;
+		tx.cancel().await?;
+		// Convert the records of each field in batches
+		for (ns, db, tb, fd) in res? {
+			loop {
+				match self.backfill_batch(&ns, &db, &tb, &fd).await {
+					Ok(false) => continue,
+					Ok(true) => break,
+					Err(e) => {
+						warn!("Failed to backfill field '{fd}' on table '{tb}' in namespace '{ns}' and database '{db}': {e}");
+						break;
+					}
+				}
+			}
+		}
+		Ok(())
+	}
+
+	async fn find_pending_backfills(
+		&self,
+		tx: &mut Transaction,
+	) -> Result<Vec<(String, String, String, String)>, Error> {
+		let mut out = Vec::new();
+		for ns in tx.all_ns().await?.iter() {
+			let ns = ns.name.as_str();
+			for db in tx.all_db(ns).await?.iter() {
+				let db = db.name.as_str();
+				for tb in tx.all_tb(ns, db).await?.iter() {
+					let tb = tb.name.as_str();
+					let beg = crate::key::table::bf::prefix(ns, db, tb);
+					let end = crate::key::table::bf::suffix(ns, db, tb);
+					for (k, v) in tx.getr(beg..end, u32::MAX).await? {
+						if !Backfill::from(v).complete {
+							let key = crate::key::table::bf::Bf::decode(&k)?;
+							out.push((
+								ns.to_owned(),
+								db.to_owned(),
+								tb.to_owned(),
+								key.fd.to_owned(),
+							));
+						}
+					}
+				}
+			}
+		}
+		Ok(out)
+	}
+
+	// backfill_batch converts the next batch of records of an altered field, returning whether the backfill has completed.
+	async fn backfill_batch(&self, ns: &str, db: &str, tb: &str, fd: &str) -> Result<bool, Error> {
+		let mut tx = self.transaction(Write, Optimistic).await?;
+		tx.set_background();
+		// Check that the backfill still exists
+		let key = crate::key::table::bf::new(ns, db, tb, fd);
+		let mut bf = match tx.get(key.clone()).await? {
+			Some(v) => Backfill::from(v),
+			None => {
+				tx.cancel().await?;
+				return Ok(true);
+			}
+		};
+		// Fetch the records after the last record which was processed
+		let beg = match &bf.cursor {
+			Some(id) => {
+				let mut k: Vec<u8> = crate::key::thing::new(ns, db, tb, id).into();
+				k.push(0x00);
+				k
+			}
+			None => crate::key::thing::prefix(ns, db, tb),
+		};
+		let end = crate::key::thing::suffix(ns, db, tb);
+		let res = tx.getr(beg..end, *BACKFILL_BATCH_SIZE).await?;
+		// Wait until the records can be written without starving other work
+		self.scheduler.admit(Job::FieldBackfill, ns, db, tb, res.len() as u64).await;
+		bf.complete = res.len() < *BACKFILL_BATCH_SIZE as usize;
+		// Convert each record using the session of the database owner
+		let sess = Session::owner().with_ns(ns).with_db(db);
+		let opt = Options::default()
+			.with_id(self.id.0)
+			.with_ns(sess.ns())
+			.with_db(sess.db())
+			.with_auth(sess.au.clone())
+			.with_strict(self.strict)
+			.with_auth_enabled(self.auth_enabled);
+		let mut ctx = Context::default();
+		ctx.add_capabilities(self.capabilities.clone());
+		let ctx = sess.context(ctx);
+		let txn = tx.enclose();
+		let mut stack = TreeStack::new();
+		for (k, v) in res {
+			let key: crate::key::thing::Thing = (&k).into();
+			let rid = sql::Thing::from((key.tb, key.id));
+			let doc: Value = (&v).into();
+			let res = stack.enter(|stk| bf.process(stk, &ctx, &opt, &txn, rid, doc)).finish().await;
+			if let Err(e) = res {
+				txn.lock().await.cancel().await?;
+				return Err(e);
+			}
+		}
+		// Store the progress of the backfill
+		let mut run = txn.lock().await;
+		run.set(key, bf.clone()).await?;
+		run.commit().await?;
+		Ok(bf.complete)
+	}
+
+	// enrich_queued_records computes the enrichments of records which have been written, using a pool of concurrent workers.
+	pub(crate) async fn enrich_queued_records(&self) -> Result<(), Error> {
+		// Find the enrichments which are waiting to be computed
+		let mut tx = self.transaction(Read, Optimistic).await?;
+		let res = self.find_queued_enrichments(&mut tx).await;
+		tx.cancel().await?;
+		// Compute each enrichment in its own transaction
+		stream::iter(res?)
+			.for_each_concurrent(*ENRICHMENT_WORKERS, |(ns, db, tb, en, id)| async move {
+				if let Err(e) = self.enrich_record(&ns, &db, &tb, &en, &id).await {
+					warn!("Failed to enrich record '{id}' with enrichment '{en}' on table '{tb}' in namespace '{ns}' and database '{db}': {e}");
+				}
+			})
+			.await;
+		Ok(())
+	}
+
+	async fn find_queued_enrichments(
+		&self,
+		tx: &mut Transaction,
+	) -> Result<Vec<(String, String, String, String, sql::Id)>, Error> {
+		let mut out = Vec::new();
+		for ns in tx.all_ns().await?.iter() {
+			let ns = ns.name.as_str();
+			for db in tx.all_db(ns).await?.iter() {
+				let db = db.name.as_str();
+				for tb in tx.all_tb(ns, db).await?.iter() {
+					let tb = tb.name.as_str();
+					let limit = (*ENRICHMENT_BATCH_SIZE).saturating_sub(out.len() as u32);
+					if limit == 0 {
+						return Ok(out);
+					}
+					let beg = crate::key::table::ej::prefix(ns, db, tb);
+					let end = crate::key::table::ej::suffix(ns, db, tb);
+					for (k, _) in tx.getr(beg..end, limit).await? {
+						let key = crate::key::table::ej::Ej::decode(&k)?;
+						out.push((
+							ns.to_owned(),
+							db.to_owned(),
+							tb.to_owned(),
+							key.en.to_owned(),
+							key.id,
+						));
+					}
+				}
+			}
+		}
+		Ok(out)
+	}
+
+	// enrich_record computes a queued enrichment of a record, and writes the result back to the record.
+	async fn enrich_record(
+		&self,
+		ns: &str,
+		db: &str,
+		tb: &str,
+		en: &str,
+		id: &sql::Id,
+	) -> Result<(), Error> {
+		let mut tx = self.transaction(Write, Optimistic).await?;
+		tx.set_background();
+		// Check that the job has not already been processed
+		let key = crate::key::table::ej::new(ns, db, tb, en, id);
+		let mut job = match tx.get(key.clone()).await? {
+			Some(v) => Enrichment::from(v),
+			None => return tx.cancel().await,
+		};
+		// Discard the job if the enrichment or the record have since been removed
+		let def = match tx.get_tb_enrichment(ns, db, tb, en).await {
+			Ok(v) => Some(v),
+			Err(Error::EnNotFound {
+				..
+			}) => None,
+			Err(e) => {
+				tx.cancel().await?;
+				return Err(e);
+			}
+		};
+		let doc = tx.get(crate::key::thing::new(ns, db, tb, id)).await?;
+		let (def, doc) = match (def, doc) {
+			(Some(def), Some(doc)) => (def, Value::from(&doc)),
+			_ => {
+				tx.del(key).await?;
+				return tx.commit().await;
+			}
+		};
+		// Wait until the record can be written without starving other work
+		self.scheduler.admit(Job::RecordEnrichment, ns, db, tb, 1).await;
+		// Compute the enrichment using the session of the database owner, without queueing it again
+		let sess = Session::owner().with_ns(ns).with_db(db);
+		let opt = Options::default()
+			.with_id(self.id.0)
+			.with_ns(sess.ns())
+			.with_db(sess.db())
+			.with_auth(sess.au.clone())
+			.with_strict(self.strict)
+			.with_auth_enabled(self.auth_enabled)
+			.with_enrichments(false);
+		let mut ctx = Context::default();
+		ctx.add_capabilities(self.capabilities.clone());
+		let ctx = sess.context(ctx);
+		let rid = sql::Thing::from((tb.to_owned(), id.to_owned()));
+		let txn = tx.enclose();
+		let mut stack = TreeStack::new();
+		let res = stack
+			.enter(|stk| Enrichment::compute(stk, &ctx, &opt, &txn, &def, &rid, doc))
+			.finish()
+			.await;
+		let err = match res {
+			Ok(_) => {
+				let mut run = txn.lock().await;
+				run.del(key).await?;
+				run.commit().await?;
+				return Ok(());
+			}
+			Err(e) => {
+				txn.lock().await.cancel().await?;
+				e
+			}
+		};
+		// Record the failure, and retry the job the next time the node ticks
+		job.attempts += 1;
+		job.error = Some(err.to_string());
+		let mut tx = self.transaction(Write, Optimistic).await?;
+		tx.set_background();
+		if job.attempts <= def.retry.unwrap_or(*ENRICHMENT_RETRIES) {
+			tx.set(key, job).await?;
+			return tx.commit().await;
+		}
+		// Move the job to the dead-letter table once it has been retried too many times
+		let txn = tx.enclose();
+		let res = stack.enter(|stk| job.fail(stk, &ctx, &opt, &txn, &def, &rid)).finish().await;
+		let mut run = txn.lock().await;
+		if let Err(e) = res {
+			run.cancel().await?;
+			return Err(e);
+		}
+		run.del(key).await?;
+		run.commit().await?;
+		Ok(())
+	}
+
+	async fn find_expired_records(
+		&self,
+		ts: u64,
+		tx: &mut Transaction,
+	) -> Result<Vec<(String, String, String, Vec<sql::Idiom>, Vec<Key>)>, Error> {
+		let now = ts.saturating_mul(1_000_000_000);
+		// Find the expiry entries which have passed, in order of expiry time
+		let beg = crate::key::root::ex::prefix();
+		let end = crate::key::root::ex::suffix(now);
+		let res = tx.getr(beg..end, *EXPIRY_BATCH_SIZE).await?;
+		// Group the expiry entries by table
+		let mut tbs: BTreeMap<(String, String, String), Vec<Key>> = BTreeMap::new();
+		for (k, _) in res {
+			let key: crate::key::root::ex::Ex = (&k).into();
+			let tb = (key.ns.to_owned(), key.db.to_owned(), key.tb.to_owned());
+			tbs.entry(tb).or_default().push(k);
+		}
+		let mut out = Vec::with_capacity(tbs.len());
+		for ((ns, db, tb), keys) in tbs {
+			// The fields of a removed table are gone, so its expiry entries are only removed
+			let fds = tx.all_tb_fields(&ns, &db, &tb).await?;
+			let fds = fds.iter().filter(|fd| fd.expire).map(|fd| fd.name.clone()).collect();
+			out.push((ns, db, tb, fds, keys));
+		}
+		Ok(out)
+	}
+
+	// Creates a heartbeat entry for the member indicating to the cluster
+	// that the node is alive.
+	// This is the preferred way of creating heartbeats inside the database, so try to use this.
+	pub async fn heartbeat(&self) -> Result<(), Error> {
+		let mut tx = self.transaction(Write, Optimistic).await?;
+		let timestamp = tx.clock().await;
+		// Check that the clock of this node agrees with the clock of the datastore
+		let now = SystemTime::now().duration_since(UNIX_EPOCH).map_err(|e| {
+			Error::Internal(format!("Clock may have gone backwards: {:?}", e.duration()))
+		})?;
+		let skew = (now.as_millis() as u64).abs_diff(timestamp.value);
+		if skew > *MAX_CLOCK_SKEW {
+			warn!("The clock of node {} differs from the datastore clock by {skew}ms", self.id);
+		}
+		// Replace the previous heartbeats of this node
+		let end_of_time = Timestamp {
+			// We remove one, because the scan range adds one
+			value: u64::MAX - 1,
+		};
+		for hb in tx.scan_hb(&end_of_time, HEARTBEAT_BATCH_SIZE).await? {
+			if hb.nd != self.id.0 {
+				// Heartbeats from the future indicate that the clocks of the nodes have drifted apart
+				if hb.hb.value > timestamp.value + *MAX_CLOCK_SKEW {
+					let ahead = hb.hb.value - timestamp.value;
+					warn!("The clock of node {} is {ahead}ms ahead of node {}", hb.nd, self.id);
+				}
+				continue;
+			}
+			if hb.hb > timestamp {
+				warn!("The clock of node {} has gone backwards since its last heartbeat", self.id);
+			}
+			tx.del_hb(hb.hb, hb.nd).await?;
+		}
+		// The node may have been removed while it was unreachable
+		if tx.get_nd(self.id.0).await?.is_none() {
+			tx.set_nd(self.id.0).await?;
+		}
+		self.heartbeat_full(&mut tx, timestamp, self.id).await?;
+		tx.commit().await
+	}
+
+	// Creates a heartbeat entry for the member indicating to the cluster
+	// that the node is alive. Intended for testing.
+	// This includes all dependencies that are hard to control and is done in such a way for testing.
+	// Inside the database, try to use the heartbeat() function instead.
+	pub async fn heartbeat_full(
+		&self,
+		tx: &mut Transaction,
+		timestamp: Timestamp,
+		node_id: Uuid,
+	) -> Result<(), Error> {
+		tx.set_hb(timestamp, node_id.0).await
+	}
+
+	// -----
+	// End cluster helpers, storage functions here
+	// -----
+
+	/// Create a new transaction on this datastore
+	///
+	/// ```rust,no_run
+	/// use surrealdb_core::kvs::{Datastore, TransactionType::*, LockType::*};
+	/// use surrealdb_core::err::Error;
+	///
+	/// #[tokio::main]
+	/// async fn main() -> Result<(), Error> {
+	///     let ds = Datastore::new("file://database.db").await?;
+	///     let mut tx = ds.transaction(Write, Optimistic).await?;
+	///     tx.cancel().await?;
+	///     Ok(())
+	/// }
+	/// ```
+	pub async fn transaction(
+		&self,
+		write: TransactionType,
+		lock: LockType,
+	) -> Result<Transaction, Error> {
+		#![allow(unused_variables)]
+		let write = match write {
+			Read => false,
+			Write => true,
+		};
+
+		let lock = match lock {
+			Pessimistic => true,
+			Optimistic => false,
+		};
+
+		#[cfg(feature = "failpoints")]
+		super::fail::eval(&self.to_string(), "begin").await?;
+
+		// Take the epoch of the cached results before the transaction begins
+		let epoch = self.results.epoch();
+
+		let inner = match &self.inner {
+			#[cfg(feature = "kv-mem")]
+			Inner::Mem(v) => {
+				let tx = v.transaction(write, lock).await?;
+				super::tx::Inner::Mem(tx)
+			}
+			#[cfg(feature = "kv-rocksdb")]
+			Inner::RocksDB(v) => {
+				let tx = v.transaction(write, lock).await?;
+				super::tx::Inner::RocksDB(tx)
+			}
+			#[cfg(feature = "kv-speedb")]
+			Inner::SpeeDB(v) => {
+				let tx = v.transaction(write, lock).await?;
+				super::tx::Inner::SpeeDB(tx)
+			}
+			#[cfg(feature = "kv-indxdb")]
 			Inner::IndxDB(v) => {
 				let tx = v.transaction(write, lock).await?;
 				super::tx::Inner::IndxDB(tx)
@@ -1104,12 +2339,37 @@ impl Datastore {
 		#[allow(unreachable_code)]
 		Ok(Transaction {
 			inner,
+			engine: self.inner.engine_capabilities(),
 			cache: super::cache::Cache::default(),
 			cf: cf::Writer::new(),
 			vso: self.versionstamp_oracle.clone(),
 			clock: self.clock.clone(),
 			prepared_async_events: (Arc::new(send), Arc::new(recv)),
 			engine_options: self.engine_options,
+			conflicts: self.conflicts.clone(),
+			advisor: self.advisor.clone(),
+			written: HashSet::new(),
+			prefetched: HashMap::new(),
+			window_events: Vec::new(),
+			compact_events: Vec::new(),
+			count_events: Vec::new(),
+			publications: Vec::new(),
+			scheduler: self.scheduler.clone(),
+			metrics: self.metrics.clone(),
+			admission: self.admission.clone(),
+			tracked: self.metrics.begin(write),
+			stats: self.stats.clone(),
+			pending: Pending::default(),
+			results: self.results.clone(),
+			epoch,
+			writes: 0,
+			background: false,
+			crdt: None,
+			write,
+			versionstamps: HashMap::new(),
+			timestamp: None,
+			ops: None,
+			savepoint: None,
 		})
 	}
 
@@ -1165,6 +2425,195 @@ impl Datastore {
 		ast: Query,
 		sess: &Session,
 		vars: Variables,
+	) -> Result<Vec<Response>, Error> {
+		self.process_with_stream(ast, sess, vars, None, false, None, None).await
+	}
+
+	/// Parse and execute an SQL query in read-only transactions, with the
+	/// specified timeout and row limit, regardless of the session permissions
+	///
+	/// ```rust,no_run
+	/// use std::time::Duration;
+	/// use surrealdb_core::kvs::Datastore;
+	/// use surrealdb_core::err::Error;
+	/// use surrealdb_core::dbs::{ReadOnly, Session};
+	///
+	/// #[tokio::main]
+	/// async fn main() -> Result<(), Error> {
+	///     let ds = Datastore::new("memory").await?;
+	///     let ses = Session::owner();
+	///     let lim = ReadOnly::new().with_timeout(Some(Duration::from_secs(5))).with_limit(Some(1000));
+	///     let ast = "USE NS test DB test; SELECT * FROM person;";
+	///     let res = ds.execute_readonly(ast, &ses, None, lim).await?;
+	///     Ok(())
+	/// }
+	/// ```
+	#[instrument(level = "debug", skip_all)]
+	pub async fn execute_readonly(
+		&self,
+		txt: &str,
+		sess: &Session,
+		vars: Variables,
+		readonly: ReadOnly,
+	) -> Result<Vec<Response>, Error> {
+		// Parse the SQL query text
+		let ast = syn::parse(txt)?;
+		// Process the AST
+		self.process_with_stream(ast, sess, vars, None, false, None, Some(readonly)).await
+	}
+
+	/// Execute an SQL query on behalf of a background task, rather than a client
+	pub(crate) async fn execute_background(
+		&self,
+		txt: &str,
+		sess: &Session,
+		vars: Variables,
+	) -> Result<Vec<Response>, Error> {
+		let ast = syn::parse(txt)?;
+		self.process_with_stream(ast, sess, vars, None, true, None, None).await
+	}
+
+	/// Replay a captured query, with the same session, parameters, randomness, and clock
+	/// readings as when it was captured. The query is executed with owner permissions.
+	///
+	/// ```rust,no_run
+	/// use std::path::Path;
+	/// use surrealdb_core::kvs::Datastore;
+	/// use surrealdb_core::err::Error;
+	/// use surrealdb_core::dbs::Capture;
+	///
+	/// #[tokio::main]
+	/// async fn main() -> Result<(), Error> {
+	///     let ds = Datastore::new("memory").await?;
+	///     for cap in Capture::read(Path::new("capture"))? {
+	///         let res = ds.replay(&cap).await?;
+	///     }
+	///     Ok(())
+	/// }
+	/// ```
+	#[instrument(level = "debug", skip_all)]
+	pub async fn replay(&self, cap: &Captured) -> Result<Vec<Response>, Error> {
+		let ast = syn::parse(&cap.sql)?;
+		let vars = cap.variables()?;
+		let sess = cap.session();
+		self.process_with_stream(ast, &sess, vars, None, false, Some(cap.entropy()), None).await
+	}
+
+	/// Parse and execute an SQL query, streaming each statement response as it completes
+	///
+	/// The records of a SELECT statement are also streamed in batches as they are
+	/// produced, as long as the statement runs in its own read-only transaction,
+	/// so that no records are sent before the changes which they depend on have
+	/// been committed. The records of statements which can write, such as a SELECT
+	/// with FOR UPDATE, of statements within an explicit transaction, and of
+	/// statements with clauses such as ORDER BY or GROUP BY which need all of
+	/// the records at once, are only sent with the response of the statement.
+	/// Responses for statements within an explicit transaction are only
+	/// yielded once the transaction has been committed or cancelled. Any
+	/// error which prevents the query from being run is yielded as an error.
+	///
+	/// ```rust,no_run
+	/// use futures::StreamExt;
+	/// use surrealdb_core::kvs::Datastore;
+	/// use surrealdb_core::err::Error;
+	/// use surrealdb_core::dbs::{Session, Streamed};
+	///
+	/// #[tokio::main]
+	/// async fn main() -> Result<(), Error> {
+	///     let ds = Datastore::new("memory").await?;
+	///     let ses = Session::owner();
+	///     let ast = "USE NS test DB test; SELECT * FROM person;";
+	///     let mut stream = ds.execute_stream(ast, &ses, None)?;
+	///     while let Some(res) = stream.next().await {
+	///         match res? {
+	///             Streamed::Rows(rows) => println!("{} records", rows.len()),
+	///             Streamed::Response(res) => println!("{:?}", res.result),
+	///             _ => (),
+	///         }
+	///     }
+	///     Ok(())
+	/// }
+	/// ```
+	#[instrument(level = "debug", skip_all)]
+	pub fn execute_stream<'a>(
+		&'a self,
+		txt: &str,
+		sess: &'a Session,
+		vars: Variables,
+	) -> Result<impl Stream<Item = Result<Streamed, Error>> + 'a, Error> {
+		// Parse the SQL query text
+		let ast = syn::parse(txt)?;
+		// Process the AST
+		Ok(self.process_stream(ast, sess, vars))
+	}
+
+	/// Execute a pre-parsed SQL query, streaming each statement response as it completes
+	///
+	/// ```rust,no_run
+	/// use futures::StreamExt;
+	/// use surrealdb_core::kvs::Datastore;
+	/// use surrealdb_core::err::Error;
+	/// use surrealdb_core::dbs::Session;
+	/// use surrealdb_core::sql::parse;
+	///
+	/// #[tokio::main]
+	/// async fn main() -> Result<(), Error> {
+	///     let ds = Datastore::new("memory").await?;
+	///     let ses = Session::owner();
+	///     let ast = parse("USE NS test DB test; SELECT * FROM person;")?;
+	///     let res: Vec<_> = ds.process_stream(ast, &ses, None).collect().await;
+	///     Ok(())
+	/// }
+	/// ```
+	#[instrument(level = "debug", skip_all)]
+	pub fn process_stream<'a>(
+		&'a self,
+		ast: Query,
+		sess: &'a Session,
+		vars: Variables,
+	) -> impl Stream<Item = Result<Streamed, Error>> + 'a {
+		// Create the response channel
+		let (send, recv) = channel::unbounded();
+		// Run the query, only yielding any overall error, as
+		// the responses themselves are sent down the channel
+		let run = self
+			.process_with_stream(ast, sess, vars, Some(send), false, None, None)
+			.into_stream()
+			.filter_map(|res| future::ready(res.err().map(Err)));
+		// The channel closes once the query has completed
+		stream::select(recv.map(Ok), run)
+	}
+
+	/// Coerce the query variables to the types of the database params
+	/// which they share a name with, so that a variable of the wrong
+	/// type is reported to the client, rather than used in the query
+	async fn check_variables(&self, sess: &Session, vars: Variables) -> Result<Variables, Error> {
+		match (sess.ns.as_deref(), sess.db.as_deref(), vars) {
+			(Some(ns), Some(db), Some(mut vars)) if !vars.is_empty() => {
+				let mut tx = self.transaction(Read, Optimistic).await?;
+				let pas = tx.all_db_params(ns, db).await;
+				tx.cancel().await?;
+				for pa in pas?.iter().filter(|pa| pa.kind.is_some()) {
+					if let Some(v) = vars.remove(pa.name.as_str()) {
+						vars.insert(pa.name.to_raw(), pa.check(v)?);
+					}
+				}
+				Ok(Some(vars))
+			}
+			(_, _, vars) => Ok(vars),
+		}
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	async fn process_with_stream(
+		&self,
+		ast: Query,
+		sess: &Session,
+		vars: Variables,
+		stream: Option<Sender<Streamed>>,
+		background: bool,
+		entropy: Option<Entropy>,
+		readonly: Option<ReadOnly>,
 	) -> Result<Vec<Response>, Error> {
 		// Check if the session has expired
 		if sess.expired() {
@@ -1188,9 +2637,14 @@ impl Datastore {
 			.with_live(sess.live())
 			.with_auth(sess.au.clone())
 			.with_strict(self.strict)
-			.with_auth_enabled(self.auth_enabled);
+			.with_auth_enabled(self.auth_enabled)
+			.with_readonly(readonly.is_some())
+			.with_row_limit(readonly.and_then(|v| v.limit));
 		// Create a new query executor
-		let mut exe = Executor::new(self);
+		let mut exe = Executor::new(self)
+			.with_stream(stream)
+			.with_background(background)
+			.with_readonly(readonly);
 		// Create a default context
 		let mut ctx = Context::from_ds(
 			self.query_timeout,
@@ -1215,16 +2669,62 @@ impl Datastore {
 			))]
 			self.temporary_directory.clone(),
 		)?;
+		// Apply the timeout of a read-only query
+		if let Some(timeout) = readonly.and_then(|v| v.timeout) {
+			ctx.add_timeout(timeout)?;
+		}
+		// Network requests can not be undone by a read-only transaction
+		if readonly.is_some() {
+			ctx.add_capabilities(self.capabilities.clone().with_network_targets(Targets::None));
+		}
 		// Setup the notification channel
 		if let Some(channel) = &self.notification_channel {
 			ctx.add_notifications(Some(&channel.0));
 		}
+		// Capture the queries which clients execute
+		let capture = match (&self.capture, &entropy) {
+			(Some(capture), None) if !background => Some((capture, ast.to_string(), vars.clone())),
+			_ => None,
+		};
+		// Seed the randomness and time of the query
+		let entropy = match (entropy, &capture) {
+			(Some(v), _) => Some(Arc::new(v)),
+			(None, Some(_)) => Some(Arc::new(Entropy::new(rand::random(), vec![]))),
+			(None, None) => None,
+		};
+		if let Some(v) = &entropy {
+			ctx.add_entropy(v.clone());
+		}
+		// Check the query variables against any typed params
+		let vars = self.check_variables(sess, vars).await?;
+		// Track the query, so that it can be listed and killed
+		let running = match background {
+			true => None,
+			false => Some(self.metrics.run(sess, ast.to_string(), ctx.add_cancel())),
+		};
+		// Wait until the query is admitted, in order of the priority of the session
+		let _permit = match &running {
+			Some(running) => {
+				let permit = self.admission.admit(sess.pr).await;
+				running.admit();
+				Some(permit)
+			}
+			None => None,
+		};
 		// Start an execution context
 		let ctx = sess.context(ctx);
 		// Store the query variables
 		let ctx = vars.attach(ctx)?;
 		// Process all statements
 		let res = exe.execute(ctx, opt, ast).await;
+		// Record the query, along with the entropy which it used
+		if let (Some((capture, sql, vars)), Some(entropy)) = (capture, &entropy) {
+			let res = res.as_ref().map(|(v, _)| v.as_slice());
+			let cap = Captured::new(sql, sess, &vars, entropy).with_results(res);
+			if let Err(e) = capture.write(&cap) {
+				warn!("Unable to capture query: {e}");
+			}
+		}
 		match res {
 			Ok((responses, lives)) => {
 				// Register live queries
@@ -1416,6 +2916,12 @@ impl Datastore {
 		self.notification_channel.as_ref().map(|v| v.1.clone())
 	}
 
+	/// Subscribe to the messages which hooks publish. Each subscriber receives every
+	/// message, and a subscriber which falls too far behind misses the oldest messages.
+	pub fn publications(&self) -> Option<broadcast::Receiver<Publication>> {
+		self.publication_channel.as_ref().map(|v| v.subscribe())
+	}
+
 	/// Performs a database import from SQL
 	#[instrument(level = "debug", skip(self, sess, sql))]
 	pub async fn import(&self, sql: &str, sess: &Session) -> Result<Vec<Response>, Error> {
@@ -1424,29 +2930,389 @@ impl Datastore {
 	}
 
 	/// Performs a full database export as SQL
+	///
+	/// The export does not hold a single transaction open for its whole
+	/// duration, as this can exceed the transaction limits of the storage
+	/// engine. Instead the records are read in many short transactions. On
+	/// storage engines which support reading at a fixed version (TiKV and
+	/// FoundationDB) these transactions are all pinned to the same snapshot.
+	/// Elsewhere, if every exported table has a change feed, the records which
+	/// change during the export are reconciled using the change feed, so that
+	/// the export reflects the state of the database when it completes.
+	/// Otherwise the export falls back to reading in a single transaction.
 	#[instrument(level = "debug", skip(self, sess, chn))]
 	pub async fn export(
 		&self,
 		sess: &Session,
 		chn: Sender<Vec<u8>>,
-	) -> Result<impl Future<Output = Result<(), Error>>, Error> {
+	) -> Result<impl Future<Output = Result<(), Error>> + '_, Error> {
+		self.export_tables(sess, Vec::new(), chn).await
+	}
+
+	/// Performs a database export as SQL, of only the specified tables
+	///
+	/// The export contains the definitions and records of the specified
+	/// tables, without the definitions of the database itself, so that the
+	/// tables can be refreshed in another database. If no tables are
+	/// specified, the whole database is exported.
+	#[instrument(level = "debug", skip(self, sess, chn))]
+	pub async fn export_tables(
+		&self,
+		sess: &Session,
+		tables: Vec<String>,
+		chn: Sender<Vec<u8>>,
+	) -> Result<impl Future<Output = Result<(), Error>> + '_, Error> {
 		// Check if the session has expired
 		if sess.expired() {
 			return Err(Error::ExpiredSession);
 		}
 		// Retrieve the provided NS and DB
 		let (ns, db) = crate::iam::check::check_ns_db(sess)?;
-		// Create a new readonly transaction
-		let mut txn = self.transaction(Read, Optimistic).await?;
+		// Check that the selected tables exist, before the export starts
+		if !tables.is_empty() {
+			let mut txn = self.transaction(Read, Optimistic).await?;
+			let mut res = Ok(());
+			for tb in tables.iter() {
+				if let Err(e) = txn.get_tb(&ns, &db, tb).await {
+					res = Err(e);
+					break;
+				}
+			}
+			txn.cancel().await?;
+			res?;
+		}
+		// Take a stable read version, if supported
+		let version = self.read_version().await?;
 		// Return an async export job
 		Ok(async move {
-			// Process the export
-			txn.export(&ns, &db, chn).await?;
-			// Everything ok
-			Ok(())
+			match version {
+				// Read the records pinned to the read version
+				Some(version) => self.export_pinned(&ns, &db, &tables, version, chn).await,
+				// Reconcile the records using the change feed
+				None => self.export_reconciled(&ns, &db, &tables, chn).await,
+			}
 		})
 	}
 
+	/// Exports the records in short transactions pinned to a read version
+	async fn export_pinned(
+		&self,
+		ns: &str,
+		db: &str,
+		tables: &[String],
+		version: u64,
+		chn: Sender<Vec<u8>>,
+	) -> Result<(), Error> {
+		// Output the definitions
+		let mut txn = self.transaction(Read, Optimistic).await?;
+		let tbs = txn.export_definitions(ns, db, tables, &chn).await;
+		txn.cancel().await?;
+		let tbs = tbs?;
+		if tbs.is_empty() {
+			return Ok(());
+		}
+		// Output the records of each table
+		super::tx::export_begin(&chn).await?;
+		for tb in tbs.iter() {
+			super::tx::export_table_data(&chn, &tb.name).await?;
+			let mut beg = crate::key::thing::prefix(ns, db, &tb.name);
+			let end = crate::key::thing::suffix(ns, db, &tb.name);
+			loop {
+				let res =
+					self.scan_at(version, beg.clone()..end.clone(), *EXPORT_BATCH_SIZE).await?;
+				let last = res.len() < *EXPORT_BATCH_SIZE as usize;
+				// Continue after the last key of this batch
+				if let Some((k, _)) = res.last() {
+					beg = k.clone().add(0x00);
+				}
+				for (k, v) in res.iter() {
+					chn.send(super::tx::export_record(k, v)).await?;
+				}
+				if last {
+					break;
+				}
+			}
+			chn.send(bytes!("")).await?;
+		}
+		super::tx::export_commit(&chn).await
+	}
+
+	/// Exports the records in short transactions, reconciling any
+	/// changes made during the export using the change feed
+	async fn export_reconciled(
+		&self,
+		ns: &str,
+		db: &str,
+		tables: &[String],
+		chn: Sender<Vec<u8>>,
+	) -> Result<(), Error> {
+		let mut txn = self.transaction(Read, Optimistic).await?;
+		// Changes can only be reconciled if every exported table has a change feed
+		let reconcilable = match txn.get(crate::key::namespace::db::new(ns, db)).await? {
+			Some(v) => {
+				DefineDatabaseStatement::from(v).changefeed.is_some()
+					|| txn
+						.all_tb(ns, db)
+						.await?
+						.iter()
+						.filter(|tb| tables.is_empty() || tables.contains(&tb.name.0))
+						.all(|tb| tb.changefeed.is_some())
+			}
+			None => false,
+		};
+		if !reconcilable {
+			// Fall back to reading everything in a single transaction
+			let res = txn.export(ns, db, tables, chn).await;
+			txn.cancel().await?;
+			return res;
+		}
+		// Changes made after this versionstamp are reconciled
+		let start = match txn.get(crate::key::database::vs::new(ns, db)).await? {
+			Some(v) => match <[u8; 10]>::try_from(v.as_slice()) {
+				Ok(vs) => crate::vs::try_to_u64_be(vs)?,
+				Err(e) => return Err(Error::Ds(e.to_string())),
+			},
+			None => 0,
+		};
+		// Output the definitions
+		let tbs = txn.export_definitions(ns, db, tables, &chn).await;
+		txn.cancel().await?;
+		let tbs = tbs?;
+		if tbs.is_empty() {
+			return Ok(());
+		}
+		// Output the records of each table
+		super::tx::export_begin(&chn).await?;
+		for tb in tbs.iter() {
+			super::tx::export_table_data(&chn, &tb.name).await?;
+			let beg = crate::key::thing::prefix(ns, db, &tb.name);
+			let end = crate::key::thing::suffix(ns, db, &tb.name);
+			let mut nxt = Some(ScanPage::from(beg..end));
+			while let Some(page) = nxt {
+				let mut txn = self.transaction(Read, Optimistic).await?;
+				let res = txn.scan_paged(page, *EXPORT_BATCH_SIZE).await;
+				txn.cancel().await?;
+				let res = res?;
+				nxt = res.next_page;
+				for (k, v) in res.values.iter() {
+					chn.send(super::tx::export_record(k, v)).await?;
+				}
+			}
+			chn.send(bytes!("")).await?;
+		}
+		// Output the final state of the records which changed during the export
+		let mut txn = self.transaction(Read, Optimistic).await?;
+		let res = self.export_changes(&mut txn, ns, db, &tbs, start, &chn).await;
+		txn.cancel().await?;
+		res?;
+		super::tx::export_commit(&chn).await
+	}
+
+	/// Outputs the current state of the records which changed after the specified versionstamp
+	async fn export_changes(
+		&self,
+		txn: &mut Transaction,
+		ns: &str,
+		db: &str,
+		tbs: &[DefineTableStatement],
+		start: u64,
+		chn: &Sender<Vec<u8>>,
+	) -> Result<(), Error> {
+		// Collect the records which changed
+		let mut changed = BTreeSet::new();
+		let beg = crate::key::change::prefix_ts(ns, db, crate::vs::u64_to_versionstamp(start + 1));
+		let end = crate::key::change::suffix(ns, db);
+		let mut nxt = Some(ScanPage::from(beg..end));
+		while let Some(page) = nxt {
+			let res = txn.scan_paged(page, *EXPORT_BATCH_SIZE).await?;
+			nxt = res.next_page;
+			for (_, v) in res.values {
+				let muts: cf::TableMutations = v.into();
+				if !tbs.iter().any(|tb| tb.name.0 == muts.0) {
+					continue;
+				}
+				for m in muts.1 {
+					match m {
+						cf::TableMutation::Set(id, _)
+						| cf::TableMutation::SetWithDiff(id, _, _)
+						| cf::TableMutation::Del(id)
+						| cf::TableMutation::DelWithOriginal(id, _) => {
+							changed.insert(id);
+						}
+						_ => {}
+					}
+				}
+			}
+		}
+		if changed.is_empty() {
+			return Ok(());
+		}
+		// Output the current state of each changed record
+		chn.send(bytes!("-- ------------------------------")).await?;
+		chn.send(bytes!("-- CHANGES DURING EXPORT")).await?;
+		chn.send(bytes!("-- ------------------------------")).await?;
+		chn.send(bytes!("")).await?;
+		for id in changed {
+			let key = crate::key::thing::new(ns, db, &id.tb, &id.id);
+			match txn.get(key.clone()).await? {
+				Some(v) => chn.send(super::tx::export_record(&key.into(), &v)).await?,
+				None => chn.send(bytes!(format!("DELETE {id};"))).await?,
+			}
+		}
+		chn.send(bytes!("")).await?;
+		Ok(())
+	}
+
+	/// Reads the changes to a table from its change feed, starting from the specified
+	/// versionstamp, formatted as Debezium change event envelopes. Each envelope is
+	/// returned with the versionstamp of its change, so that the changes can be read
+	/// in chunks, with the next call starting from the last versionstamp + 1.
+	#[instrument(level = "debug", skip(self, sess))]
+	pub async fn changes(
+		&self,
+		sess: &Session,
+		tb: &str,
+		since: u64,
+		limit: u32,
+	) -> Result<Vec<(u64, Value)>, Error> {
+		// Retrieve the provided NS and DB
+		let (ns, db) = crate::iam::check::check_ns_db(sess)?;
+		// Check the permissions level
+		self.check(sess, Action::View, ResourceKind::Table.on_db(&ns, &db))?;
+		// The time at which the changes are read
+		let now = SystemTime::now().duration_since(UNIX_EPOCH).map_err(|e| {
+			Error::Internal(format!("Clock may have gone backwards: {:?}", e.duration()))
+		})?;
+		let mut txn = self.transaction(Read, Optimistic).await?;
+		let res = async {
+			// Check if the change feed includes original values
+			let dbv = txn.get_db(&ns, &db).await?;
+			let tbv = txn.get_tb(&ns, &db, tb).await?;
+			let store_diff = match dbv.changefeed.as_ref().or(tbv.changefeed.as_ref()) {
+				Some(cf) => cf.store_diff,
+				None => {
+					return Err(Error::CfNotFound {
+						value: tb.to_owned(),
+					})
+				}
+			};
+			// Read the versionstamp of each tick
+			let beg = crate::key::database::ts::prefix(&ns, &db);
+			let end = crate::key::database::ts::suffix(&ns, &db);
+			let mut ticks = Vec::new();
+			for (k, v) in txn.getr(beg..end, u32::MAX).await? {
+				let ts = crate::key::database::ts::Ts::decode(&k)?.ts;
+				if let Ok(vs) = Versionstamp::try_from(v.as_slice()) {
+					ticks.push((ts, vs));
+				}
+			}
+			// Read the changes to the table
+			let since = sql::statements::show::ShowSince::Versionstamp(since);
+			let sets = cf::read(&mut txn, &ns, &db, Some(tb), since, Some(limit)).await?;
+			let source = cf::debezium::Source {
+				ns: &ns,
+				db: &db,
+				store_diff,
+				ticks: &ticks,
+				now_ms: now.as_millis() as u64,
+			};
+			let mut out = Vec::new();
+			for set in sets {
+				out.extend(source.envelopes(set)?);
+			}
+			Ok(out)
+		}
+		.await;
+		txn.cancel().await?;
+		res
+	}
+
+	/// Lists the databases which the session is allowed to view, with their
+	/// comments and labels, and the number and stored size of their records
+	pub async fn databases(&self, sess: &Session) -> Result<Vec<Value>, Error> {
+		// Check if the session has expired
+		if sess.expired() {
+			return Err(Error::ExpiredSession);
+		}
+		let mut txn = self.transaction(Read, Optimistic).await?;
+		let res = async {
+			let mut out = Vec::new();
+			for ns in txn.all_ns().await?.iter() {
+				for db in txn.all_db(&ns.name).await?.iter() {
+					// Skip the databases which the session is not allowed to view
+					let res = ResourceKind::Table.on_db(&ns.name, &db.name);
+					if self.check(sess, Action::View, res).is_err() {
+						continue;
+					}
+					// Count the records and their stored size in each table
+					let tbs = txn.all_tb(&ns.name, &db.name).await?;
+					let mut records = 0u64;
+					let mut size = 0u64;
+					for tb in tbs.iter() {
+						let beg = crate::key::thing::prefix(&ns.name, &db.name, &tb.name);
+						let end = crate::key::thing::suffix(&ns.name, &db.name, &tb.name);
+						let mut next_page = Some(ScanPage::from(beg..end));
+						while let Some(page) = next_page {
+							let res = txn.scan_paged(page, NORMAL_FETCH_SIZE).await?;
+							next_page = res.next_page;
+							for (k, v) in res.values.iter() {
+								records += 1;
+								size += (k.len() + v.len()) as u64;
+							}
+						}
+					}
+					let mut obj = Object::default();
+					obj.insert("namespace".to_owned(), ns.name.to_raw().into());
+					obj.insert("database".to_owned(), db.name.to_raw().into());
+					if let Some(v) = &db.comment {
+						obj.insert("comment".to_owned(), v.clone().into());
+					}
+					if let Some(v) = &db.labels {
+						obj.insert("labels".to_owned(), v.clone().into());
+					}
+					obj.insert("tables".to_owned(), tbs.len().into());
+					obj.insert("records".to_owned(), records.into());
+					obj.insert("size".to_owned(), size.into());
+					out.push(obj.into());
+				}
+			}
+			Ok(out)
+		}
+		.await;
+		txn.cancel().await?;
+		res
+	}
+
+	/// Takes a stable read version of the datastore, on storage engines which support pinning reads to a version
+	async fn read_version(&self) -> Result<Option<u64>, Error> {
+		match &self.inner {
+			#[cfg(feature = "kv-tikv")]
+			Inner::TiKV(v) => v.read_version().await.map(Some),
+			#[cfg(feature = "kv-fdb")]
+			Inner::FoundationDB(v) => v.read_version().await.map(Some),
+			#[allow(unreachable_patterns)]
+			_ => Ok(None),
+		}
+	}
+
+	/// Scans a range of keys, as of a read version taken with `read_version`
+	#[allow(unused_variables)]
+	async fn scan_at(
+		&self,
+		version: u64,
+		rng: Range<Key>,
+		limit: u32,
+	) -> Result<Vec<(Key, Val)>, Error> {
+		match &self.inner {
+			#[cfg(feature = "kv-tikv")]
+			Inner::TiKV(v) => v.scan_at(version, rng, limit).await,
+			#[cfg(feature = "kv-fdb")]
+			Inner::FoundationDB(v) => v.scan_at(version, rng, limit).await,
+			#[allow(unreachable_patterns)]
+			_ => Err(Error::Unreachable("Datastore::scan_at")),
+		}
+	}
+
 	/// Checks the required permissions level for this session
 	#[instrument(level = "debug", skip(self, sess))]
 	pub fn check(&self, sess: &Session, action: Action, resource: Resource) -> Result<(), Error> {
@@ -1515,4 +3381,177 @@ mod test {
 		assert_eq!(res, Value::Number(Number::Int(2)));
 		Ok(())
 	}
+
+	#[tokio::test]
+	async fn export_reconciles_changes() -> Result<(), Error> {
+		let dbs = Datastore::new("memory").await?;
+		let ses = Session::owner().with_ns("test").with_db("test");
+		let sql = "
+			DEFINE DATABASE test CHANGEFEED 1h;
+			CREATE person:one SET name = 'one';
+			CREATE person:two SET name = 'two';
+		";
+		for res in dbs.execute(sql, &ses, None).await? {
+			res.result?;
+		}
+		// Make changes while the records are being exported
+		let (snd, rcv) = channel::bounded(1);
+		let export = dbs.export(&ses, snd).await?;
+		let output = async {
+			let mut out = String::new();
+			let mut changed = false;
+			while let Ok(v) = rcv.recv().await {
+				let v = String::from_utf8(v).unwrap();
+				if !changed && v.starts_with("-- TABLE DATA") {
+					let sql = "
+						UPDATE person:one SET name = 'uno';
+						DELETE person:two;
+						CREATE person:three SET name = 'three';
+					";
+					for res in dbs.execute(sql, &ses, None).await? {
+						res.result?;
+					}
+					changed = true;
+				}
+				out.push_str(&v);
+			}
+			Ok::<_, Error>(out)
+		};
+		let (res, out) = futures::join!(export, output);
+		res?;
+		let out = out?;
+		assert!(out.contains("-- CHANGES DURING EXPORT"));
+		// The export reflects the changes which were made
+		let dbs = Datastore::new("memory").await?;
+		for res in dbs.execute(&out, &ses, None).await? {
+			res.result?;
+		}
+		let mut res = dbs.execute("SELECT VALUE name FROM person", &ses, None).await?;
+		let val = res.remove(0).result?;
+		assert_eq!(val.to_string(), "['uno', 'three']");
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn export_round_trips_escaped_names() -> Result<(), Error> {
+		let dbs = Datastore::new("memory").await?;
+		let ses = Session::owner().with_ns("test").with_db("test");
+		let sql = "
+			DEFINE TABLE `select`;
+			DEFINE FIELD `from` ON `select` TYPE string;
+			DEFINE INDEX `table` ON `select` FIELDS `from`;
+			CREATE `select`:⟨rand⟩ SET `from` = 'one', `1st` = true, `a⟩b` = 1;
+			CREATE `select`:⟨a\\b⟩ SET `from` = 'two';
+		";
+		for res in dbs.execute(sql, &ses, None).await? {
+			res.result?;
+		}
+		let (snd, rcv) = channel::bounded(1);
+		let export = dbs.export(&ses, snd).await?;
+		let output = async {
+			let mut out = String::new();
+			while let Ok(v) = rcv.recv().await {
+				out.push_str(&String::from_utf8(v).unwrap());
+			}
+			out
+		};
+		let (res, out) = futures::join!(export, output);
+		res?;
+		// The export can be imported into an empty datastore
+		let sql = "SELECT * FROM `select` ORDER BY `from`; INFO FOR TABLE `select`";
+		let mut exp = dbs.execute(sql, &ses, None).await?;
+		let dbs = Datastore::new("memory").await?;
+		for res in dbs.import(&out, &ses).await? {
+			res.result?;
+		}
+		let mut res = dbs.execute(sql, &ses, None).await?;
+		assert_eq!(res.remove(0).result?, exp.remove(0).result?);
+		assert_eq!(res.remove(0).result?, exp.remove(0).result?);
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn export_selected_tables() -> Result<(), Error> {
+		let dbs = Datastore::new("memory").await?;
+		let ses = Session::owner().with_ns("test").with_db("test");
+		let sql = "
+			DEFINE FUNCTION fn::greet() { RETURN 'hello'; };
+			DEFINE FIELD name ON person TYPE string;
+			DEFINE INDEX name ON person FIELDS name;
+			CREATE person:one SET name = 'one';
+			CREATE invoice:one SET total = 10;
+			CREATE other:one;
+		";
+		for res in dbs.execute(sql, &ses, None).await? {
+			res.result?;
+		}
+		let (snd, rcv) = channel::bounded(1);
+		let tables = vec!["person".to_owned(), "invoice".to_owned()];
+		let export = dbs.export_tables(&ses, tables, snd).await?;
+		let output = async {
+			let mut out = String::new();
+			while let Ok(v) = rcv.recv().await {
+				out.push_str(&String::from_utf8(v).unwrap());
+			}
+			out
+		};
+		let (res, out) = futures::join!(export, output);
+		res?;
+		// Only the selected tables, and their definitions, are exported
+		assert!(out.contains("DEFINE INDEX name ON person FIELDS name"));
+		assert!(!out.contains("fn::greet"));
+		assert!(!out.contains("other"));
+		let dbs = Datastore::new("memory").await?;
+		for res in dbs.import(&out, &ses).await? {
+			res.result?;
+		}
+		let sql = "SELECT VALUE id FROM person, invoice, other";
+		let mut res = dbs.execute(sql, &ses, None).await?;
+		let val = res.remove(0).result?;
+		assert_eq!(val.to_string(), "[person:one, invoice:one]");
+		// Selecting a table which does not exist fails
+		let (snd, _) = channel::bounded(1);
+		let tables = vec!["missing".to_owned()];
+		let res = dbs.export_tables(&ses, tables, snd).await;
+		assert!(matches!(res, Err(Error::TbNotFound { .. })));
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn changes_as_debezium_envelopes() -> Result<(), Error> {
+		let dbs = Datastore::new("memory").await?;
+		let ses = Session::owner().with_ns("test").with_db("test");
+		let sql = "
+			DEFINE TABLE person CHANGEFEED 1h INCLUDE ORIGINAL;
+			DEFINE TABLE other CHANGEFEED 1h;
+			CREATE person:one SET name = 'one';
+			CREATE other:one;
+			UPDATE person:one SET name = 'uno';
+			DELETE person:one;
+		";
+		for res in dbs.execute(sql, &ses, None).await? {
+			res.result?;
+		}
+		let res = dbs.changes(&ses, "person", 0, 100).await?;
+		let ops: Vec<_> = res.iter().map(|(_, v)| v.pick(&["op".into()])).collect();
+		assert_eq!(ops, vec![Value::from("c"), Value::from("u"), Value::from("d")]);
+		assert_eq!(
+			res[1].1.pick(&["before".into()]),
+			syn::value("{ id: person:one, name: 'one' }")?
+		);
+		assert_eq!(
+			res[1].1.pick(&["after".into()]),
+			syn::value("{ id: person:one, name: 'uno' }")?
+		);
+		assert_eq!(res[1].1.pick(&["source".into(), "table".into()]), Value::from("person"));
+		// The changes can be read from the versionstamp after the last change
+		let next = res[1].0 + 1;
+		let res = dbs.changes(&ses, "person", next, 100).await?;
+		assert_eq!(res.len(), 1);
+		assert_eq!(res[0].1.pick(&["op".into()]), Value::from("d"));
+		// Tables without a change feed can not be read
+		dbs.execute("DEFINE TABLE plain", &ses, None).await?;
+		assert!(dbs.changes(&ses, "plain", 0, 100).await.is_err());
+		Ok(())
+	}
 }
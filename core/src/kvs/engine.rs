@@ -0,0 +1,88 @@
+//! The primitives which each storage engine supports natively.
+//!
+//! The higher layers check these capabilities to choose how to perform an
+//! operation, instead of checking which storage engine is in use, so that the
+//! behaviour of each storage engine is described in a single place.
+use crate::sql::Value;
+use std::fmt::{self, Display};
+use std::ops::BitOr;
+
+/// A set of the primitives which a storage engine supports natively
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub struct EngineCapabilities(u8);
+
+impl EngineCapabilities {
+	/// The storage engine writes versionstamped keys itself, assigning the
+	/// versionstamp when the transaction commits. The versionstamp of a
+	/// transaction can not be read before the transaction commits.
+	pub const VERSIONSTAMPED_KEYS: Self = Self(1 << 0);
+	/// The storage engine deletes a range of keys in a single request,
+	/// without reading the keys first.
+	pub const RANGE_DELETE: Self = Self(1 << 1);
+	/// The storage engine reads many keys in a single request, so reading
+	/// keys together ahead of time saves network round trips.
+	pub const BATCHED_READS: Self = Self(1 << 2);
+	/// The storage engine allows several write transactions to be open at
+	/// once, instead of waiting for each write transaction to finish.
+	pub const CONCURRENT_WRITERS: Self = Self(1 << 3);
+
+	/// The name of each capability, in the order in which they are output
+	const NAMES: [(Self, &'static str); 4] = [
+		(Self::VERSIONSTAMPED_KEYS, "versionstamped_keys"),
+		(Self::RANGE_DELETE, "range_delete"),
+		(Self::BATCHED_READS, "batched_reads"),
+		(Self::CONCURRENT_WRITERS, "concurrent_writers"),
+	];
+
+	/// A storage engine which supports none of the capabilities
+	pub const fn none() -> Self {
+		Self(0)
+	}
+
+	/// Check whether all of the specified capabilities are supported
+	pub const fn contains(self, other: Self) -> bool {
+		self.0 & other.0 == other.0
+	}
+
+	/// The names of the supported capabilities
+	pub fn names(self) -> impl Iterator<Item = &'static str> {
+		Self::NAMES.into_iter().filter(move |(v, _)| self.contains(*v)).map(|(_, n)| n)
+	}
+}
+
+impl BitOr for EngineCapabilities {
+	type Output = Self;
+
+	fn bitor(self, other: Self) -> Self {
+		Self(self.0 | other.0)
+	}
+}
+
+impl Display for EngineCapabilities {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}", self.names().collect::<Vec<_>>().join(", "))
+	}
+}
+
+impl From<EngineCapabilities> for Value {
+	fn from(v: EngineCapabilities) -> Self {
+		v.names().map(Value::from).collect::<Vec<_>>().into()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn contains_and_names() {
+		let caps = EngineCapabilities::RANGE_DELETE | EngineCapabilities::BATCHED_READS;
+		assert!(caps.contains(EngineCapabilities::RANGE_DELETE));
+		assert!(!caps.contains(EngineCapabilities::VERSIONSTAMPED_KEYS));
+		assert!(!caps
+			.contains(EngineCapabilities::RANGE_DELETE | EngineCapabilities::VERSIONSTAMPED_KEYS));
+		assert!(caps.contains(EngineCapabilities::none()));
+		assert_eq!(caps.to_string(), "range_delete, batched_reads");
+		assert_eq!(Value::from(EngineCapabilities::none()), Value::from(Vec::<Value>::new()));
+	}
+}
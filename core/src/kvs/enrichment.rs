@@ -0,0 +1,94 @@
+use crate::ctx::Context;
+use crate::dbs::{Options, Transaction};
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::sql::statements::{CreateStatement, DefineEnrichmentStatement, UpdateStatement};
+use crate::sql::{Data, Object, Operator, Table, Thing, Value, Values};
+use derive::Store;
+use reblessive::tree::Stk;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+/// A queued enrichment of a record.
+///
+/// A job is queued by the document pipeline whenever a record is created or
+/// updated, and is processed by the datastore in the background. The result
+/// is written back to the record without queueing the enrichment again. When
+/// the computation fails, the job is kept and retried, until it has failed
+/// more times than the enrichment allows, and it is moved to the dead-letter
+/// table of the table.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
+#[non_exhaustive]
+pub struct Enrichment {
+	/// The number of times the enrichment has failed
+	pub attempts: u32,
+	/// The error of the last failed attempt
+	pub error: Option<String>,
+}
+
+impl Enrichment {
+	/// The table which enrichments that have failed too many times are written to
+	pub(crate) fn dead_letter(tb: &str) -> String {
+		format!("{tb}_dead_letter")
+	}
+
+	/// Compute the enrichment of a record, and write the result back to the record
+	pub(crate) async fn compute(
+		stk: &mut Stk,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		en: &DefineEnrichmentStatement,
+		rid: &Thing,
+		doc: Value,
+	) -> Result<(), Error> {
+		// Compute the enrichment with the current record
+		let val = {
+			let mut ctx = Context::new(ctx);
+			ctx.add_value("after", doc.clone());
+			ctx.add_value("value", doc.clone());
+			let doc = CursorDoc::new(None, Some(rid), None, Cow::Borrowed(&doc));
+			en.call.compute(stk, &ctx, opt, txn, Some(&doc)).await?
+		};
+		// Write the result into the field, or merge it into the record
+		let data = match &en.into {
+			Some(field) => Data::SetExpression(vec![(field.clone(), Operator::Equal, val)]),
+			None => Data::MergeExpression(val),
+		};
+		UpdateStatement {
+			what: Values(vec![Value::from(rid.clone())]),
+			data: Some(data),
+			..Default::default()
+		}
+		.compute(stk, ctx, opt, txn, None)
+		.await?;
+		Ok(())
+	}
+
+	/// Record that the enrichment of a record has failed too many times
+	pub(crate) async fn fail(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		en: &DefineEnrichmentStatement,
+		rid: &Thing,
+	) -> Result<(), Error> {
+		CreateStatement {
+			what: Values(vec![Value::Table(Table(Self::dead_letter(&rid.tb)))]),
+			data: Some(Data::ContentExpression(Value::from(Object::from(map! {
+				"record" => Value::from(rid.clone()),
+				"enrichment" => Value::from(en.name.to_raw()),
+				"attempts" => Value::from(self.attempts),
+				"error" => self.error.clone().map(Value::from).unwrap_or_default(),
+			})))),
+			..Default::default()
+		}
+		.compute(stk, ctx, opt, txn, None)
+		.await?;
+		Ok(())
+	}
+}
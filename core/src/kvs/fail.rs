@@ -0,0 +1,270 @@
+//! Failpoint injection for the key-value store backends.
+//!
+//! Failpoints are named hooks placed on the transaction operations of every
+//! storage engine. When a failpoint is configured, the hook can return an error,
+//! delay the operation, panic, or truncate the results of a range scan. This
+//! allows retry logic in the bootstrap process and in the executor to be tested
+//! deterministically, without needing to break the underlying datastore.
+//!
+//! Each operation checks two failpoints: a generic one (`kvs::<op>`) which applies
+//! to every storage engine, and an engine specific one (`<engine>::<op>`), for
+//! instance `kvs::commit` and `rocksdb::commit`. The engine names are the same
+//! as those displayed by the datastore, such as `memory`, `rocksdb`, or `tikv`. The supported operations are
+//! `begin`, `commit`, `cancel`, `get`, `set`, `put`, `del`, and `scan`.
+//!
+//! Failpoints can be configured programmatically using [`cfg`], or through the
+//! `SURREAL_FAILPOINTS` environment variable, which accepts a list of
+//! `name=actions` pairs separated by `;`, for example:
+//!
+//! ```text
+//! SURREAL_FAILPOINTS="kvs::commit=20%return(conflict);tikv::scan=sleep(100)"
+//! ```
+//!
+//! An action list is made up of one or more actions separated by `->`. Each
+//! action can be prefixed with a probability (`P%`) and a maximum number of
+//! times it can be triggered (`N*`). The available actions are:
+//!
+//! - `off`: do nothing
+//! - `return` or `return(message)`: fail the operation with an error
+//! - `sleep(ms)`: delay the operation by the specified number of milliseconds
+//! - `panic` or `panic(message)`: panic with the specified message
+//! - `partial(n)`: truncate the results of a range scan to `n` entries
+use crate::err::Error;
+use once_cell::sync::Lazy;
+use rand::Rng;
+use std::collections::HashMap;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::RwLock;
+use std::time::Duration;
+
+/// The environment variable used to configure failpoints at startup
+pub const FAILPOINTS_ENV: &str = "SURREAL_FAILPOINTS";
+
+/// The registry of all currently configured failpoints
+static REGISTRY: Lazy<RwLock<HashMap<String, Vec<Action>>>> = Lazy::new(|| {
+	let mut registry = HashMap::new();
+	if let Ok(v) = std::env::var(FAILPOINTS_ENV) {
+		match parse_list(&v) {
+			Ok(list) => registry.extend(list),
+			Err(e) => warn!("Ignoring invalid {FAILPOINTS_ENV} value: {e}"),
+		}
+	}
+	RwLock::new(registry)
+});
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Task {
+	Off,
+	Return(Option<String>),
+	Sleep(u64),
+	Panic(Option<String>),
+	Partial(usize),
+}
+
+#[derive(Debug)]
+pub(crate) struct Action {
+	task: Task,
+	freq: f32,
+	count: Option<AtomicUsize>,
+}
+
+impl Action {
+	/// Check whether this action should trigger, consuming one of its remaining counts
+	fn trigger(&self) -> bool {
+		if self.freq < 1.0 && !rand::thread_rng().gen_bool(self.freq as f64) {
+			return false;
+		}
+		match &self.count {
+			Some(count) => {
+				count.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |v| v.checked_sub(1)).is_ok()
+			}
+			None => true,
+		}
+	}
+}
+
+/// The outcome of evaluating a failpoint, for operations which need to act on it
+#[derive(Debug, PartialEq)]
+pub(crate) enum Outcome {
+	/// Continue with the operation as normal
+	Continue,
+	/// Truncate the results of a range scan
+	Partial(usize),
+}
+
+fn parse_list(list: &str) -> Result<Vec<(String, Vec<Action>)>, Error> {
+	list.split(';')
+		.map(str::trim)
+		.filter(|s| !s.is_empty())
+		.map(|pair| match pair.split_once('=') {
+			Some((name, actions)) => Ok((name.trim().to_owned(), parse_actions(actions)?)),
+			None => Err(invalid(pair)),
+		})
+		.collect()
+}
+
+fn parse_actions(actions: &str) -> Result<Vec<Action>, Error> {
+	actions.split("->").map(|a| parse_action(a.trim())).collect()
+}
+
+/// Split an action at a prefix separator, ignoring any separator within the task argument
+fn prefix(rest: &str, c: char) -> Option<(&str, &str)> {
+	match rest.find(c) {
+		Some(i) if !rest[..i].contains('(') => Some((&rest[..i], &rest[i + 1..])),
+		_ => None,
+	}
+}
+
+fn parse_action(action: &str) -> Result<Action, Error> {
+	let mut rest = action;
+	// Parse the optional probability prefix
+	let mut freq = 1.0;
+	if let Some((p, r)) = prefix(rest, '%') {
+		freq = p.parse::<f32>().map_err(|_| invalid(action))? / 100.0;
+		if !(0.0..=1.0).contains(&freq) {
+			return Err(invalid(action));
+		}
+		rest = r;
+	}
+	// Parse the optional count prefix
+	let mut count = None;
+	if let Some((c, r)) = prefix(rest, '*') {
+		let c = c.parse::<usize>().map_err(|_| invalid(action))?;
+		count = Some(AtomicUsize::new(c));
+		rest = r;
+	}
+	// Parse the task and its argument
+	let (name, arg) = match rest.split_once('(') {
+		Some((name, arg)) => match arg.strip_suffix(')') {
+			Some(arg) => (name, Some(arg.to_owned())),
+			None => return Err(invalid(action)),
+		},
+		None => (rest, None),
+	};
+	let task = match (name, arg) {
+		("off", None) => Task::Off,
+		("return", arg) => Task::Return(arg),
+		("panic", arg) => Task::Panic(arg),
+		("sleep", Some(ms)) => Task::Sleep(ms.parse().map_err(|_| invalid(action))?),
+		("partial", Some(n)) => Task::Partial(n.parse().map_err(|_| invalid(action))?),
+		_ => return Err(invalid(action)),
+	};
+	Ok(Action {
+		task,
+		freq,
+		count,
+	})
+}
+
+fn invalid(value: &str) -> Error {
+	Error::Ds(format!("Invalid failpoint configuration: '{value}'"))
+}
+
+/// Configure the actions for a failpoint, replacing any existing configuration
+pub fn cfg(name: &str, actions: &str) -> Result<(), Error> {
+	let actions = parse_actions(actions)?;
+	REGISTRY.write().unwrap().insert(name.to_owned(), actions);
+	Ok(())
+}
+
+/// Configure failpoints from a `;` separated list of `name=actions` pairs
+pub fn cfg_list(list: &str) -> Result<(), Error> {
+	let list = parse_list(list)?;
+	REGISTRY.write().unwrap().extend(list);
+	Ok(())
+}
+
+/// Remove the configuration for a failpoint
+pub fn remove(name: &str) {
+	REGISTRY.write().unwrap().remove(name);
+}
+
+/// Remove the configuration for all failpoints
+pub fn teardown() {
+	REGISTRY.write().unwrap().clear();
+}
+
+/// List the names of all configured failpoints
+pub fn list() -> Vec<String> {
+	REGISTRY.read().unwrap().keys().cloned().collect()
+}
+
+/// Find the task which should be run for a failpoint, if any
+fn task(name: &str) -> Option<Task> {
+	let registry = REGISTRY.read().unwrap();
+	let actions = registry.get(name)?;
+	actions.iter().find(|a| a.trigger()).map(|a| a.task.clone())
+}
+
+/// Evaluate the generic and engine specific failpoints for an operation
+pub(crate) async fn eval(engine: &str, op: &str) -> Result<Outcome, Error> {
+	let mut outcome = Outcome::Continue;
+	for name in [format!("kvs::{op}"), format!("{engine}::{op}")] {
+		let Some(task) = task(&name) else {
+			continue;
+		};
+		trace!("Triggered failpoint {name}: {task:?}");
+		match task {
+			Task::Off => {}
+			Task::Return(msg) => {
+				return Err(Error::Tx(match msg {
+					Some(msg) => format!("Failpoint {name} triggered: {msg}"),
+					None => format!("Failpoint {name} triggered"),
+				}))
+			}
+			Task::Sleep(ms) => {
+				#[cfg(target_arch = "wasm32")]
+				wasmtimer::tokio::sleep(Duration::from_millis(ms)).await;
+				#[cfg(not(target_arch = "wasm32"))]
+				tokio::time::sleep(Duration::from_millis(ms)).await;
+			}
+			Task::Panic(msg) => match msg {
+				Some(msg) => panic!("Failpoint {name} triggered: {msg}"),
+				None => panic!("Failpoint {name} triggered"),
+			},
+			Task::Partial(n) => outcome = Outcome::Partial(n),
+		}
+	}
+	Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parse_failpoint_actions() {
+		let a = parse_action("return").unwrap();
+		assert_eq!(a.task, Task::Return(None));
+		let a = parse_action("return(conflict)").unwrap();
+		assert_eq!(a.task, Task::Return(Some("conflict".to_owned())));
+		let a = parse_action("25%3*sleep(100)").unwrap();
+		assert_eq!(a.task, Task::Sleep(100));
+		assert_eq!(a.freq, 0.25);
+		assert!(a.count.is_some());
+		let a = parse_action("partial(2)").unwrap();
+		assert_eq!(a.task, Task::Partial(2));
+		assert!(parse_action("sleep").is_err());
+		assert!(parse_action("explode").is_err());
+		assert!(parse_action("150%return").is_err());
+		let a = parse_action("return(50% *off*)").unwrap();
+		assert_eq!(a.task, Task::Return(Some("50% *off*".to_owned())));
+		let list = parse_list("kvs::commit=return; rocksdb::scan=1*partial(1)->off").unwrap();
+		assert_eq!(list.len(), 2);
+		assert_eq!(list[1].1.len(), 2);
+	}
+
+	#[tokio::test]
+	async fn eval_failpoint_counts() {
+		cfg("test::commit", "2*return(boom)").unwrap();
+		assert!(eval("test", "commit").await.is_err());
+		assert!(eval("test", "commit").await.is_err());
+		assert_eq!(eval("test", "commit").await.unwrap(), Outcome::Continue);
+		cfg("test::scan", "partial(1)").unwrap();
+		assert_eq!(eval("test", "scan").await.unwrap(), Outcome::Partial(1));
+		remove("test::commit");
+		remove("test::scan");
+		assert_eq!(eval("test", "scan").await.unwrap(), Outcome::Continue);
+	}
+}
@@ -4,9 +4,11 @@ use crate::err::Error;
 use crate::kvs::Check;
 use crate::kvs::Key;
 use crate::kvs::Val;
+use crate::sql::Consistency;
+use crate::sql::Isolation;
 use crate::vs::{u64_to_versionstamp, Versionstamp};
 use foundationdb::options;
-use futures::TryStreamExt;
+use futures::{StreamExt, TryStreamExt};
 use std::ops::Range;
 use std::sync::Arc;
 // We use it to work-around the fact that foundationdb-rs' Transaction
@@ -18,6 +20,8 @@ use std::sync::Arc;
 //
 // We use mutex from the futures crate instead of the std's due to https://rust-lang.github.io/wg-async/vision/submitted_stories/status_quo/alan_thinks_he_needs_async_locks.html.
 use crate::key::error::KeyCategory;
+use crate::kvs::chunk::{self, Chunker};
+use crate::kvs::codec::Codec;
 use foundationdb::options::MutationType;
 use futures::lock::Mutex;
 use once_cell::sync::Lazy;
@@ -30,6 +34,10 @@ use once_cell::sync::Lazy;
 pub struct Datastore {
 	db: foundationdb::Database,
 	_fdbnet: Arc<foundationdb::api::NetworkAutoStop>,
+	// How values are compressed and checksummed
+	codec: Codec,
+	// How large values are split into chunks
+	chunker: Chunker,
 }
 
 #[non_exhaustive]
@@ -38,10 +46,18 @@ pub struct Transaction {
 	done: bool,
 	/// Should this transaction lock?
 	lock: bool,
+	/// The requested consistency of the reads
+	consistency: Option<Consistency>,
+	/// The requested isolation level of the transaction
+	isolation: Option<Isolation>,
 	/// Is the transaction writeable?
 	write: bool,
 	/// Should we check unhandled transactions?
 	check: Check,
+	/// How values are compressed and checksummed
+	codec: Codec,
+	/// How large values are split into chunks
+	chunker: Chunker,
 	/// The underlying datastore transaction
 	inner: Arc<Mutex<Option<foundationdb::Transaction>>>,
 }
@@ -101,6 +117,8 @@ impl Datastore {
 				Ok(Datastore {
 					db,
 					_fdbnet,
+					codec: Codec::from_env(),
+					chunker: Chunker::from_env(),
 				})
 			}
 			Err(e) => Err(Error::Ds(e.to_string())),
@@ -120,11 +138,55 @@ impl Datastore {
 				check,
 				write,
 				lock,
+				consistency: None,
+				isolation: None,
+				codec: self.codec,
+				chunker: self.chunker,
 				inner: Arc::new(Mutex::new(Some(inner))),
 			}),
 			Err(e) => Err(Error::Tx(e.to_string())),
 		}
 	}
+	/// Fetch the current read version, which later reads can be pinned to
+	pub(crate) async fn read_version(&self) -> Result<u64, Error> {
+		let inner = self.db.create_trx().map_err(|e| Error::Tx(e.to_string()))?;
+		let res = inner
+			.get_read_version()
+			.await
+			.map_err(|e| Error::Tx(format!("Unable to get read version from FDB: {}", e)))?;
+		Ok(res as u64)
+	}
+	/// Scan a range of keys, as of a read version fetched with `read_version`.
+	/// This fails once the read version is older than the FDB MVCC window.
+	pub(crate) async fn scan_at(
+		&self,
+		version: u64,
+		rng: Range<Key>,
+		limit: u32,
+	) -> Result<Vec<(Key, Val)>, Error> {
+		// Create a snapshot transaction at the specified read version
+		let inner = self.db.create_trx().map_err(|e| Error::Tx(e.to_string()))?;
+		inner.set_read_version(version as i64);
+		// Scan the keys
+		let opt = foundationdb::RangeOption {
+			limit: Some(limit.try_into().unwrap()),
+			..foundationdb::RangeOption::from((rng.start.as_slice(), rng.end.as_slice()))
+		};
+		let mut stream = inner.get_ranges_keyvalues(opt, true);
+		let mut res: Vec<(Key, Val)> = vec![];
+		loop {
+			match stream.try_next().await {
+				Ok(Some(v)) => {
+					let val =
+						decode(&inner, true, &self.codec, v.key(), Val::from(v.value())).await?;
+					res.push((Key::from(v.key()), val))
+				}
+				Ok(None) => break,
+				Err(e) => return Err(Error::Tx(format!("GetRanges failed: {}", e))),
+			}
+		}
+		Ok(res)
+	}
 }
 
 impl Transaction {
@@ -144,8 +206,47 @@ impl Transaction {
 	/// use the standard fdb inner to make transactions Serializable.
 	/// In case the inner is write, we assume the user never wants to lose serializability
 	/// so we go with the standard fdb serializable inner in that case too.
+	/// An explicitly requested consistency overrides this for read-only transactions,
+	/// and an explicitly requested isolation level overrides this for all transactions.
 	fn snapshot(&self) -> bool {
-		!self.write && !self.lock
+		if let Some(isolation) = self.isolation {
+			return isolation < Isolation::Serializable;
+		}
+		match self.consistency {
+			Some(Consistency::Eventual) => !self.write,
+			Some(Consistency::Strong) => false,
+			_ => !self.write && !self.lock,
+		}
+	}
+	/// Set the consistency of the reads, which must happen before anything is read.
+	/// Eventually consistent reads are snapshot reads, which can use a cached read
+	/// version, while strongly consistent reads are serializable.
+	pub(crate) async fn set_consistency(&mut self, consistency: Consistency) -> Result<(), Error> {
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Use a possibly stale read version, to avoid fetching a new read version
+		if consistency == Consistency::Eventual && !self.write {
+			let inner = self.inner.lock().await;
+			let inner = inner.as_ref().unwrap();
+			inner
+				.set_option(options::TransactionOption::CausalReadRisky)
+				.map_err(|e| Error::Tx(format!("Unable to set the read consistency: {}", e)))?;
+		}
+		self.consistency = Some(consistency);
+		Ok(())
+	}
+	/// Set the isolation level of the transaction, which must happen before anything is read.
+	/// Serializable transactions use the standard reads, and weaker isolation levels
+	/// use snapshot reads, which do not conflict with concurrent writes.
+	pub(crate) async fn set_isolation(&mut self, isolation: Isolation) -> Result<(), Error> {
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		self.isolation = Some(isolation);
+		Ok(())
 	}
 	/// Cancel a transaction
 	pub(crate) async fn cancel(&mut self) -> Result<(), Error> {
@@ -198,6 +299,12 @@ impl Transaction {
 		};
 		match r {
 			Ok(_r) => {}
+			// The FoundationDB errors for transactions, keys, and values which are too large
+			Err(e) if e.code() == 2101 => return Err(Error::TxTooLarge),
+			Err(e) if e.code() == 2102 => return Err(Error::TxKeyTooLarge),
+			Err(e) if e.code() == 2103 => return Err(Error::TxValueTooLarge),
+			// The FoundationDB error for a transaction which conflicted with another transaction
+			Err(e) if e.code() == 1020 => return Err(Error::TxConflict),
 			Err(e) => {
 				return Err(Error::Tx(format!("Transaction commit error: {}", e)));
 			}
@@ -249,11 +356,68 @@ impl Transaction {
 		// make the transaction serializable, we use the inverse of it to enable the snapshot isolation
 		// on the get request.
 		// See https://apple.github.io/foundationdb/api-c.html#snapshot-reads for more information on how the snapshot get is supposed to work in FDB.
-		inner
+		let res = inner
 			.get(key, self.snapshot())
 			.await
-			.map(|v| v.as_ref().map(|v| v.to_vec()))
-			.map_err(|e| Error::Tx(format!("Unable to get kv from FoundationDB: {}", e)))
+			.map_err(|e| Error::Tx(format!("Unable to get kv from FoundationDB: {}", e)))?;
+		// Decode the value
+		match res {
+			Some(v) => decode(inner, self.snapshot(), &self.codec, key, v.to_vec()).await.map(Some),
+			None => Ok(None),
+		}
+	}
+	/// Fetch many keys from the database, reading them in parallel
+	pub(crate) async fn getm(&mut self, keys: Vec<Key>) -> Result<Vec<Option<Val>>, Error> {
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Get the keys
+		let inner = self.inner.lock().await;
+		let inner = inner.as_ref().unwrap();
+		let res = futures::future::try_join_all(
+			keys.iter().map(|key| inner.get(&key[..], self.snapshot())),
+		)
+		.await
+		.map_err(|e| Error::Tx(format!("Unable to get kv from FoundationDB: {}", e)))?;
+		// Decode the values
+		let snapshot = self.snapshot();
+		let codec = &self.codec;
+		futures::future::try_join_all(keys.iter().zip(res).map(|(key, v)| async move {
+			match v {
+				Some(v) => decode(inner, snapshot, codec, key, v.to_vec()).await.map(Some),
+				None => Ok(None),
+			}
+		}))
+		.await
+	}
+	/// Acquire write intent on a key, by adding read and write conflict ranges covering the key
+	pub(crate) async fn lock<K>(&mut self, key: K) -> Result<(), Error>
+	where
+		K: Into<Key>,
+	{
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.write {
+			return Err(Error::TxReadonly);
+		}
+		// The conflict range covers only this key
+		let key: Vec<u8> = key.into();
+		let mut end = key.clone();
+		end.push(0x00);
+		let inner = self.inner.lock().await;
+		let inner = inner.as_ref().unwrap();
+		// A snapshot read does not conflict by itself, so the read conflict is added explicitly
+		for ty in [options::ConflictRangeType::Read, options::ConflictRangeType::Write] {
+			inner
+				.add_conflict_range(&key, &end, ty)
+				.map_err(|e| Error::Tx(format!("Unable to lock kv in FoundationDB: {}", e)))?;
+		}
+		// Return result
+		Ok(())
 	}
 	/// Obtain a new change timestamp for a key
 	/// which is replaced with the current timestamp when the transaction is committed.
@@ -295,11 +459,10 @@ impl Transaction {
 		// Set the key
 		let key: Vec<u8> = key.into();
 		let key = &key[..];
-		let val: Vec<u8> = val.into();
-		let val = &val[..];
+		let val: Vec<u8> = self.codec.encode(val.into());
 		let inner = self.inner.lock().await;
 		let inner = inner.as_ref().unwrap();
-		inner.set(key, val);
+		write(inner, &self.chunker, key, val);
 		// Return result
 		Ok(())
 	}
@@ -337,11 +500,10 @@ impl Transaction {
 		}
 		// Set the key
 		let key: &[u8] = &key[..];
-		let val: Vec<u8> = val.into();
-		let val: &[u8] = &val[..];
+		let val: Vec<u8> = self.codec.encode(val.into());
 		let inner = self.inner.lock().await;
 		let inner = inner.as_ref().unwrap();
-		inner.set(key, val);
+		write(inner, &self.chunker, key, val);
 		// Return result
 		Ok(())
 	}
@@ -363,8 +525,7 @@ impl Transaction {
 		let key: Vec<u8> = key.into();
 		let key: &[u8] = key.as_slice();
 		// Get the val
-		let val: Vec<u8> = val.into();
-		let val: &[u8] = val.as_slice();
+		let val: Vec<u8> = self.codec.encode(val.into());
 		// Get the check
 		let chk = chk.map(Into::into);
 		// Delete the key
@@ -377,9 +538,14 @@ impl Transaction {
 		// See https://apple.github.io/foundationdb/api-c.html#snapshot-reads for more information on how the snapshot get is supposed to work in FDB.
 		let res = inner.get(key, false).await;
 		let res = res.map_err(|e| Error::Tx(format!("Unable to get kv from FoundationDB: {}", e)));
+		let res = match res {
+			Ok(Some(v)) => decode(inner, false, &self.codec, key, v.to_vec()).await.map(Some),
+			Ok(None) => Ok(None),
+			Err(e) => Err(e),
+		};
 		match (res, chk) {
-			(Ok(Some(v)), Some(w)) if *v.as_ref() == w => inner.set(key, val),
-			(Ok(None), None) => inner.set(key, val),
+			(Ok(Some(v)), Some(w)) if v == w => write(inner, &self.chunker, key, val),
+			(Ok(None), None) => write(inner, &self.chunker, key, val),
 			(Err(e), _) => return Err(e),
 			_ => return Err(Error::TxConditionNotMet),
 		};
@@ -421,7 +587,8 @@ impl Transaction {
 		k.append(&mut posbs);
 
 		let key: &[u8] = &k[..];
-		let val: Vec<u8> = val.into();
+		// The key is not known until the transaction is committed, so the value is never chunked
+		let val: Vec<u8> = self.codec.encode(val.into());
 		let val: &[u8] = &val[..];
 		let inner = self.inner.lock().await;
 		let inner = inner.as_ref().unwrap();
@@ -447,7 +614,7 @@ impl Transaction {
 		let key: &[u8] = key.as_slice();
 		let inner = self.inner.lock().await;
 		let inner = inner.as_ref().unwrap();
-		inner.clear(key);
+		clear(inner, key);
 		// Return result
 		Ok(())
 	}
@@ -476,9 +643,14 @@ impl Transaction {
 			.get(key, false)
 			.await
 			.map_err(|e| Error::Tx(format!("FoundationDB inner failure: {}", e)));
+		let res = match res {
+			Ok(Some(v)) => decode(inner, false, &self.codec, key, v.to_vec()).await.map(Some),
+			Ok(None) => Ok(None),
+			Err(e) => Err(e),
+		};
 		match (res, chk) {
-			(Ok(Some(v)), Some(w)) if *v.as_ref() == w => inner.clear(key),
-			(Ok(None), None) => inner.clear(key),
+			(Ok(Some(v)), Some(w)) if v == w => clear(inner, key),
+			(Ok(None), None) => clear(inner, key),
 			_ => return Err(Error::TxConditionNotMet),
 		};
 		// Return result
@@ -522,8 +694,10 @@ impl Transaction {
 			let x = stream.try_next().await;
 			match x {
 				Ok(Some(v)) => {
-					let x = (Key::from(v.key()), Val::from(v.value()));
-					res.push(x)
+					let val =
+						decode(inner, self.snapshot(), &self.codec, v.key(), Val::from(v.value()))
+							.await?;
+					res.push((Key::from(v.key()), val))
 				}
 				Ok(None) => break,
 				Err(e) => return Err(Error::Tx(format!("GetRanges failed: {}", e))),
@@ -550,6 +724,54 @@ impl Transaction {
 		let inner = self.inner.lock().await;
 		let inner = inner.as_ref().unwrap();
 		inner.clear_range(begin, end);
+		// Delete the chunks of the values in the range
+		let rng = chunk::range_of(begin, end);
+		inner.clear_range(&rng.start, &rng.end);
 		Ok(())
 	}
 }
+
+/// Write an encoded value, splitting it into chunks if it is too large
+fn write(inner: &foundationdb::Transaction, chunker: &Chunker, key: &[u8], val: Val) {
+	// Delete the chunks of the previous value
+	let rng = chunk::range(key);
+	inner.clear_range(&rng.start, &rng.end);
+	// Write the value, or the header and the chunks
+	let (val, chunks) = chunker.split(key, val);
+	for (k, v) in chunks {
+		inner.set(&k, &v);
+	}
+	inner.set(key, &val);
+}
+
+/// Delete a value, along with its chunks
+fn clear(inner: &foundationdb::Transaction, key: &[u8]) {
+	let rng = chunk::range(key);
+	inner.clear_range(&rng.start, &rng.end);
+	inner.clear(key);
+}
+
+/// Decode a value, first reading and joining its chunks if it was split into chunks
+async fn decode(
+	inner: &foundationdb::Transaction,
+	snapshot: bool,
+	codec: &Codec,
+	key: &[u8],
+	val: Val,
+) -> Result<Val, Error> {
+	let val = match chunk::chunks(&val) {
+		Some(count) => {
+			let rng = chunk::range(key);
+			let opt = foundationdb::RangeOption::from((rng.start.as_slice(), rng.end.as_slice()));
+			let res: Vec<Val> = inner
+				.get_ranges_keyvalues(opt, snapshot)
+				.map(|v| v.map(|v| Val::from(v.value())))
+				.try_collect()
+				.await
+				.map_err(|e| Error::Tx(format!("Unable to get kv from FoundationDB: {}", e)))?;
+			chunk::join(key, count, res)?
+		}
+		None => val,
+	};
+	codec.decode(key, val)
+}
@@ -0,0 +1,148 @@
+//! An append-only audit log of the DEFINE and REMOVE statements which were run.
+//!
+//! Each statement is recorded in the transaction in which it is applied, along
+//! with the time at which it was run, the node which ran it, and the user which
+//! ran it, so the schema of a table can be followed over time with the
+//! `INFO FOR HISTORY OF TABLE` statement. The entries are stored under their own
+//! prefix, which can not be deleted by normal delete operations, so the log is
+//! kept when a table, database, or namespace is removed.
+use crate::dbs::{Options, Transaction};
+use crate::err::Error;
+use crate::key::root::dd;
+use crate::kvs::Key;
+use crate::sql::{Array, Base, Datetime, Ident, Uuid, Value};
+use chrono::DateTime;
+use derive::Store;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+/// An entry in the audit log of definitions
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
+#[non_exhaustive]
+pub struct Definition {
+	/// The time at which the statement was run, in milliseconds since the unix epoch
+	pub time: u64,
+	/// The node which ran the statement
+	pub node: Uuid,
+	/// The user which ran the statement
+	pub actor: String,
+	/// The level of the user which ran the statement
+	pub level: String,
+	/// The table which the statement applies to, if any
+	pub table: Option<String>,
+	/// The statement which was run
+	pub statement: String,
+}
+
+impl From<Definition> for Value {
+	fn from(v: Definition) -> Self {
+		let time = DateTime::from_timestamp_millis(v.time as i64).unwrap_or_default();
+		let mut out: BTreeMap<String, Value> = map! {
+			"time".to_string() => Datetime::from(time).into(),
+			"node".to_string() => v.node.into(),
+			"actor".to_string() => v.actor.into(),
+			"level".to_string() => v.level.into(),
+			"statement".to_string() => v.statement.into(),
+		};
+		if let Some(tb) = v.table {
+			out.insert("table".to_string(), tb.into());
+		}
+		out.into()
+	}
+}
+
+/// Record a statement in the audit log of the selected database
+pub(crate) async fn record(
+	opt: &Options,
+	txn: &Transaction,
+	table: Option<&Ident>,
+	statement: String,
+) -> Result<(), Error> {
+	// Statements at the root and namespace levels are recorded with empty names
+	let (ns, db) = match opt.selected_base()? {
+		Base::Db => (opt.ns(), opt.db()),
+		Base::Ns => (opt.ns(), ""),
+		_ => ("", ""),
+	};
+	let mut run = txn.lock().await;
+	let time = run.commit_timestamp().await;
+	let entry = Definition {
+		time,
+		node: opt.id()?.into(),
+		actor: opt.auth.id().to_owned(),
+		level: opt.auth.level().to_string(),
+		table: table.map(|tb| tb.to_raw()),
+		statement,
+	};
+	let key = dd::new(ns, db, time, uuid::Uuid::new_v4());
+	run.set(key, entry).await
+}
+
+impl crate::kvs::Transaction {
+	/// Retrieve the entries of the audit log for a table, in the order they were recorded
+	pub(crate) async fn definition_history(
+		&mut self,
+		ns: &str,
+		db: &str,
+		tb: &str,
+	) -> Result<Value, Error> {
+		let beg = dd::prefix(ns, db);
+		let end = dd::suffix(ns, db);
+		let mut out = Array::new();
+		for (_, v) in self.getr(beg..end, u32::MAX).await? {
+			let entry = Definition::from(v);
+			if entry.table.as_deref() == Some(tb) {
+				out.push(entry.into());
+			}
+		}
+		Ok(out.into())
+	}
+}
+
+/// Checks whether a key is part of the audit log
+pub(crate) fn is_protected(key: &[u8]) -> bool {
+	key.starts_with(&dd::all())
+}
+
+/// Splits a range of keys which is being deleted, so that it excludes the audit log
+pub(crate) fn unprotected(rng: Range<Key>) -> Vec<Range<Key>> {
+	let beg = dd::all();
+	let mut end = beg.clone();
+	end.push(0xff);
+	// The range does not overlap the audit log
+	if rng.end <= beg || rng.start >= end {
+		return vec![rng];
+	}
+	let mut out = Vec::new();
+	if rng.start < beg {
+		out.push(rng.start..beg);
+	}
+	if rng.end > end {
+		out.push(end..rng.end);
+	}
+	out
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn ranges_exclude_the_audit_log() {
+		let all: Range<Key> = vec![0x00]..vec![0xff];
+		assert_eq!(
+			unprotected(all),
+			vec![vec![0x00]..b"/!dd".to_vec(), b"/!dd\xff".to_vec()..vec![0xff]]
+		);
+		let tb: Range<Key> =
+			b"/*ns\x00*db\x00*tb\x00".to_vec()..b"/*ns\x00*db\x00*tb\x00\xff".to_vec();
+		assert_eq!(unprotected(tb.clone()), vec![tb]);
+		let log: Range<Key> = b"/!ddns\x00".to_vec()..b"/!ddns\x00\xff".to_vec();
+		assert!(unprotected(log).is_empty());
+		assert!(is_protected(b"/!ddns\x00db\x00"));
+		assert!(!is_protected(b"/!ns"));
+	}
+}
@@ -0,0 +1,196 @@
+//! Imports SurrealQL which is received incrementally, such as from a request body.
+//!
+//! The received bytes are parsed into statements as soon as each statement is
+//! complete, and the statements are executed in batches, so that only a single
+//! batch of statements is held in memory, regardless of the size of the import.
+//! Statements within a `BEGIN` and `COMMIT` are always executed in the same batch,
+//! and any `OPTION` and `USE` statements are applied to every subsequent batch.
+use crate::cnf::IMPORT_BATCH_SIZE;
+use crate::dbs::{Response, Session};
+use crate::err::Error;
+use crate::kvs::Datastore;
+use crate::sql::{Query, Statement, Statements};
+use crate::syn::parser::{Parser, PartialResult};
+use reblessive::Stack;
+
+pub struct Importer<'a> {
+	// The datastore into which the statements are imported
+	ds: &'a Datastore,
+	// The session which is performing the import
+	sess: &'a Session,
+	// The received bytes which have not yet been parsed
+	buffer: Vec<u8>,
+	// The parsed statements which have not yet been executed
+	batch: Vec<Statement>,
+	// The OPTION and USE statements which have been imported
+	options: Vec<Statement>,
+	// The OPTION and USE statements which apply to the batch
+	preamble: Vec<Statement>,
+	// Whether the batch ends within a transaction
+	transaction: bool,
+	// Whether the responses of the statements are kept
+	keep: bool,
+	// The responses of the executed statements
+	results: Vec<Response>,
+}
+
+impl Datastore {
+	/// Start an import which is received incrementally
+	pub fn importer<'a>(&'a self, sess: &'a Session) -> Importer<'a> {
+		Importer {
+			ds: self,
+			sess,
+			buffer: Vec::new(),
+			batch: Vec::new(),
+			options: Vec::new(),
+			preamble: Vec::new(),
+			transaction: false,
+			keep: true,
+			results: Vec::new(),
+		}
+	}
+}
+
+impl<'a> Importer<'a> {
+	/// Set whether the responses of the imported statements are returned
+	pub fn with_results(mut self, keep: bool) -> Self {
+		self.keep = keep;
+		self
+	}
+
+	/// Import the next part of the SurrealQL which has been received
+	pub async fn push(&mut self, bytes: &[u8]) -> Result<(), Error> {
+		self.buffer.extend_from_slice(bytes);
+		// A statement can only be completed by a semicolon
+		if bytes.contains(&b';') {
+			self.parse(false).await?;
+		}
+		Ok(())
+	}
+
+	/// Import any statement which is not followed by a semicolon, at the end of a file
+	pub async fn complete(&mut self) -> Result<(), Error> {
+		self.parse(true).await
+	}
+
+	/// Import any remaining statements, and return the responses
+	pub async fn finish(mut self) -> Result<Vec<Response>, Error> {
+		self.complete().await?;
+		self.flush().await?;
+		Ok(self.results)
+	}
+
+	/// Parse and import the complete statements which have been received
+	async fn parse(&mut self, end: bool) -> Result<(), Error> {
+		for v in self.statements()? {
+			self.add(v).await?;
+		}
+		// At the end of a file the final statement may not be followed by a semicolon
+		if end {
+			let rest = std::mem::take(&mut self.buffer);
+			let query = crate::syn::parse(&String::from_utf8_lossy(&rest))?;
+			for v in query.0 .0 {
+				self.add(v).await?;
+			}
+		}
+		Ok(())
+	}
+
+	/// Remove the complete statements from the received bytes
+	fn statements(&mut self) -> Result<Vec<Statement>, Error> {
+		let mut statements = Vec::new();
+		let mut used = 0;
+		let mut parser = Parser::new(&[]);
+		let mut stack = Stack::new();
+		while used < self.buffer.len() {
+			parser = parser.change_source(&self.buffer[used..]);
+			parser.reset();
+			match stack.enter(|stk| parser.parse_partial_statement(stk)).finish() {
+				PartialResult::Ready {
+					value: Ok(v),
+					used: length,
+				} => {
+					statements.push(v);
+					used += length;
+				}
+				PartialResult::Ready {
+					value: Err(e),
+					..
+				} => {
+					let source = String::from_utf8_lossy(&self.buffer[used..]);
+					return Err(Error::InvalidQuery(e.render_on(&source)));
+				}
+				PartialResult::Pending {
+					..
+				} => break,
+			}
+		}
+		self.buffer.drain(..used);
+		Ok(statements)
+	}
+
+	/// Add a statement to the batch, executing the batch once it is full
+	async fn add(&mut self, stm: Statement) -> Result<(), Error> {
+		match stm {
+			Statement::Option(_) | Statement::Use(_) => self.options.push(stm.clone()),
+			Statement::Begin(_) => self.transaction = true,
+			Statement::Commit(_) | Statement::Cancel(_) => self.transaction = false,
+			_ => (),
+		}
+		self.batch.push(stm);
+		// Batches can not be split within a transaction
+		if !self.transaction && self.batch.len() >= *IMPORT_BATCH_SIZE {
+			self.flush().await?;
+		}
+		Ok(())
+	}
+
+	/// Execute the statements in the batch
+	async fn flush(&mut self) -> Result<(), Error> {
+		if self.batch.is_empty() {
+			return Ok(());
+		}
+		// The USE statements in the preamble have already returned a response
+		let skip = self.preamble.iter().filter(|v| matches!(v, Statement::Use(_))).count();
+		let mut statements = std::mem::replace(&mut self.preamble, self.options.clone());
+		statements.append(&mut self.batch);
+		let res = self.ds.process(Query(Statements(statements)), self.sess, None).await?;
+		if self.keep {
+			self.results.extend(res.into_iter().skip(skip));
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sql::Value;
+	use crate::syn::Parse;
+
+	#[tokio::test]
+	async fn statements_are_imported_across_chunks() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = Session::owner().with_ns("test").with_db("test");
+		let sql = "OPTION IMPORT;\nBEGIN;\nCREATE person:one SET name = 'Tobie; Morgan';\nCOMMIT;\nCREATE person:two;\nSELECT VALUE id FROM person";
+		let mut importer = ds.importer(&sess);
+		for chunk in sql.as_bytes().chunks(7) {
+			importer.push(chunk).await.unwrap();
+		}
+		let mut res = importer.finish().await.unwrap();
+		assert_eq!(res.len(), 3);
+		let tmp = res.pop().unwrap().result.unwrap();
+		assert_eq!(tmp, Value::parse("[person:one, person:two]"));
+	}
+
+	#[tokio::test]
+	async fn invalid_statements_are_rejected() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = Session::owner().with_ns("test").with_db("test");
+		let mut importer = ds.importer(&sess);
+		let res = importer
+			.push(b"CREATE person:one;\nCREATE person SET name = ;\nCREATE person:two;\n")
+			.await;
+		assert!(matches!(res, Err(Error::InvalidQuery(_))));
+	}
+}
@@ -0,0 +1,221 @@
+//! Checks of the integrity of a datastore, which can be run before a node serves any traffic.
+//!
+//! The storage format version is checked, along with the keys which the cluster uses to
+//! track its nodes, their heartbeats, and their live queries. These keys are read when a
+//! node is bootstrapped, so a corrupted value would otherwise prevent the node starting,
+//! and an orphaned key would never be cleaned up. Keys which can not be decoded are
+//! reported as corrupted, and keys which refer to a node or a live query which does not
+//! exist are reported as orphaned. Corrupted and orphaned keys can be repaired by deleting
+//! them, as they are recreated when nodes register themselves and live queries are started.
+use crate::cnf::NORMAL_FETCH_SIZE;
+use crate::dbs::node::{ClusterMembership, Timestamp};
+use crate::err::Error;
+use crate::key::debug::sprint_key;
+use crate::key::{node, root, table};
+use crate::kvs::version::{self, STORAGE_VERSION};
+use crate::kvs::{Datastore, Key, LockType::*, ScanPage, Transaction, TransactionType::*, Val};
+use crate::sql::statements::LiveStatement;
+use revision::Revisioned;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::ops::Range;
+
+/// A problem which was found by an integrity check
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct Issue {
+	/// The key which has the problem
+	pub key: Key,
+	/// A description of the problem
+	pub problem: String,
+	/// Whether the problem can be repaired by deleting the key
+	pub repairable: bool,
+	/// Whether the problem has been repaired
+	pub repaired: bool,
+}
+
+impl Issue {
+	fn corrupted(key: &[u8], what: &str, err: impl fmt::Display) -> Self {
+		Self {
+			key: key.to_vec(),
+			problem: format!("the {what} is corrupted: {err}"),
+			repairable: true,
+			repaired: false,
+		}
+	}
+
+	fn orphaned(key: &[u8], problem: impl Into<String>) -> Self {
+		Self {
+			key: key.to_vec(),
+			problem: problem.into(),
+			repairable: true,
+			repaired: false,
+		}
+	}
+}
+
+impl fmt::Display for Issue {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}: {}", sprint_key(&self.key), self.problem)?;
+		if self.repaired {
+			f.write_str(" (repaired)")?;
+		}
+		Ok(())
+	}
+}
+
+impl Datastore {
+	/// Check the integrity of the datastore, returning the problems which were found. When
+	/// `repair` is set, the keys which have problems are deleted where this is possible.
+	pub async fn check_integrity(&self, repair: bool) -> Result<Vec<Issue>, Error> {
+		let mut tx = self.transaction(Read, Optimistic).await?;
+		let res = check(&mut tx).await;
+		tx.cancel().await?;
+		let mut issues = res?;
+		if repair && issues.iter().any(|i| i.repairable) {
+			let mut tx = self.transaction(Write, Optimistic).await?;
+			for issue in issues.iter().filter(|i| i.repairable) {
+				if let Err(e) = tx.del(issue.key.clone()).await {
+					tx.cancel().await?;
+					return Err(e);
+				}
+			}
+			tx.commit().await?;
+			for issue in issues.iter_mut().filter(|i| i.repairable) {
+				issue.repaired = true;
+			}
+		}
+		Ok(issues)
+	}
+}
+
+async fn check(tx: &mut Transaction) -> Result<Vec<Issue>, Error> {
+	let mut issues = Vec::new();
+	// Check the storage format version
+	let sv = Key::from(root::sv::Sv::new());
+	if let Some(v) = tx.get(sv.clone()).await? {
+		match version::decode(&v) {
+			Ok(v) if v > STORAGE_VERSION => issues.push(Issue {
+				key: sv,
+				problem: format!(
+					"the storage format version {v} was written by a newer release, which supports up to version {STORAGE_VERSION}"
+				),
+				repairable: false,
+				repaired: false,
+			}),
+			Ok(_) => {}
+			Err(e) => issues.push(Issue {
+				key: sv,
+				problem: format!("the storage format version is corrupted: {e}"),
+				repairable: false,
+				repaired: false,
+			}),
+		}
+	}
+	// Check the registered nodes
+	let mut nodes = HashSet::new();
+	for (k, v) in scan(tx, root::nd::Nd::prefix()..root::nd::Nd::suffix()).await? {
+		let nd = match root::nd::Nd::decode(&k) {
+			Ok(nd) => nd.nd,
+			Err(e) => {
+				issues.push(Issue::corrupted(&k, "node key", e));
+				continue;
+			}
+		};
+		match ClusterMembership::deserialize_revisioned(&mut v.as_slice()) {
+			Ok(cm) if cm.name == nd.to_string() => {
+				nodes.insert(nd);
+			}
+			Ok(cm) => issues.push(Issue::corrupted(
+				&k,
+				"node",
+				format!("it is registered under the name {}", cm.name),
+			)),
+			Err(e) => issues.push(Issue::corrupted(&k, "node", e)),
+		}
+	}
+	// Check the node heartbeats
+	let end = root::hb::Hb::suffix(&Timestamp::from(u64::MAX - 1));
+	for (k, _) in scan(tx, root::hb::Hb::prefix()..end).await? {
+		match root::hb::Hb::decode(&k) {
+			Ok(hb) if nodes.contains(&hb.nd) => {}
+			Ok(hb) => issues.push(Issue::orphaned(
+				&k,
+				format!("the heartbeat is for node {}, which is not registered", hb.nd),
+			)),
+			Err(e) => issues.push(Issue::corrupted(&k, "heartbeat key", e)),
+		}
+	}
+	// Check the live queries of each table
+	let mut lives = HashMap::new();
+	for ns in tx.all_ns().await?.iter() {
+		for db in tx.all_db(&ns.name).await?.iter() {
+			for tb in tx.all_tb(&ns.name, &db.name).await?.iter() {
+				let rng = table::lq::prefix(&ns.name, &db.name, &tb.name)
+					..table::lq::suffix(&ns.name, &db.name, &tb.name);
+				for (k, v) in scan(tx, rng).await? {
+					let lq = match table::lq::Lq::decode(&k) {
+						Ok(lq) => lq.lq,
+						Err(e) => {
+							issues.push(Issue::corrupted(&k, "table live query key", e));
+							continue;
+						}
+					};
+					match LiveStatement::deserialize_revisioned(&mut v.as_slice()) {
+						Ok(lv) if nodes.contains(&lv.node.0) => {
+							lives.insert((lv.node.0, lq), k);
+						}
+						Ok(lv) => issues.push(Issue::orphaned(
+							&k,
+							format!(
+								"the live query is on node {}, which is not registered",
+								lv.node
+							),
+						)),
+						Err(e) => issues.push(Issue::corrupted(&k, "table live query", e)),
+					}
+				}
+			}
+		}
+	}
+	// Check the live queries of each node
+	for (k, v) in scan(tx, vec![b'/', b'$']..vec![b'/', b'%']).await? {
+		let lq = match node::lq::Lq::decode(&k) {
+			Ok(lq) => lq,
+			Err(e) => {
+				issues.push(Issue::corrupted(&k, "node live query key", e));
+				continue;
+			}
+		};
+		if let Err(e) = std::str::from_utf8(&v) {
+			issues.push(Issue::corrupted(&k, "node live query", e));
+		} else if !nodes.contains(&lq.nd) {
+			issues.push(Issue::orphaned(
+				&k,
+				format!("the live query is on node {}, which is not registered", lq.nd),
+			));
+		} else if lives.remove(&(lq.nd, lq.lq)).is_none() {
+			issues.push(Issue::orphaned(
+				&k,
+				format!("the live query {} is not defined on any table", lq.lq),
+			));
+		}
+	}
+	// Any remaining table live queries are not tracked by their node
+	for ((nd, _), k) in lives {
+		issues.push(Issue::orphaned(&k, format!("the live query is not tracked by node {nd}")));
+	}
+	Ok(issues)
+}
+
+/// Read every key and value in a range
+async fn scan(tx: &mut Transaction, rng: Range<Key>) -> Result<Vec<(Key, Val)>, Error> {
+	let mut out = Vec::new();
+	let mut next_page = Some(ScanPage::from(rng));
+	while let Some(page) = next_page {
+		let res = tx.scan_paged(page, NORMAL_FETCH_SIZE).await?;
+		next_page = res.next_page;
+		out.extend(res.values);
+	}
+	Ok(out)
+}
@@ -0,0 +1,83 @@
+use crate::err::Error;
+use crate::iam::{Level, Role};
+use crate::kvs::Transaction;
+use crate::sql::paths::OBJ_PATH_TOKEN;
+use crate::sql::permission::Permission;
+use crate::sql::statements::{LiveStatement, Privilege};
+use crate::sql::{Part, Value};
+use crate::syn;
+
+/// Checks whether the user who started a live query is still allowed to select
+/// from its table, returning the reason why not if the live query should be killed.
+///
+/// The table permissions are checked against each record when a notification is
+/// sent, but the authentication of the live query is only checked when it starts.
+/// A scope user whose scope, session, or record is removed, or a user who can no
+/// longer select anything from the table, would otherwise keep receiving notifications.
+pub(crate) async fn revoked(
+	tx: &mut Transaction,
+	ns: &str,
+	db: &str,
+	tb: &str,
+	lv: &LiveStatement,
+) -> Result<Option<&'static str>, Error> {
+	let Some(auth) = &lv.auth else {
+		return Ok(None);
+	};
+	// The predefined roles can select from any table
+	if auth.is_anon() || [Role::Viewer, Role::Editor, Role::Owner].iter().any(|r| auth.has_role(r))
+	{
+		return Ok(None);
+	}
+	// Check that the table still exists
+	let tb = match tx.get_tb(ns, db, tb).await {
+		Ok(v) => v,
+		Err(Error::TbNotFound {
+			..
+		}) => return Ok(Some("The table has been removed")),
+		Err(e) => return Err(e),
+	};
+	// Check that the scope user is still authenticated
+	if let Level::Scope(_, _, sc) = auth.level() {
+		match tx.get_sc(ns, db, sc).await {
+			Ok(_) => (),
+			Err(Error::ScNotFound {
+				..
+			}) => return Ok(Some("The scope has been removed")),
+			Err(e) => return Err(e),
+		}
+		let sess = lv.session.as_ref().unwrap_or(&Value::None);
+		let tk = sess.pick(&[Part::from(OBJ_PATH_TOKEN)]);
+		if tk.pick(&[Part::from("SE")]).is_true() {
+			let tracked = match tk.pick(&[Part::from("jti")]) {
+				Value::Strand(jti) => crate::iam::session::exists(tx, ns, db, jti.as_str()).await?,
+				_ => false,
+			};
+			if !tracked {
+				return Ok(Some("The session has been killed"));
+			}
+		}
+		if let Ok(rid) = syn::thing(auth.id()) {
+			let key: Vec<u8> = crate::key::thing::new(ns, db, &rid.tb, &rid.id).into();
+			if !tx.exi(key).await? {
+				return Ok(Some("The authenticated record has been deleted"));
+			}
+		}
+	}
+	// Check that a role or the table permissions allow the selection
+	let roles = auth.custom_roles();
+	if !roles.is_empty()
+		&& tx
+			.all_db_roles(ns, db)
+			.await?
+			.iter()
+			.filter(|r| roles.contains(&r.name.as_str()))
+			.any(|r| r.allows(&tb.name, Privilege::Select))
+	{
+		return Ok(None);
+	}
+	match tb.permissions.select {
+		Permission::None => Ok(Some("The table permissions no longer allow the selection")),
+		_ => Ok(None),
+	}
+}
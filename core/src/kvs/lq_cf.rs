@@ -358,6 +358,10 @@ mod test {
 				archived: None,
 				session: None,
 				auth: None,
+				window: None,
+				when: None,
+				compact: None,
+				count: false,
 			},
 		}
 	}
@@ -0,0 +1,183 @@
+use crate::dbs::{Action, Notification};
+use crate::sql::{Thing, Uuid};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// A notification of a live query which compacts its notifications. This is
+/// buffered in the transaction, and is only compacted once the transaction commits.
+#[derive(Debug)]
+pub(crate) struct CompactEvent {
+	pub(crate) ns: String,
+	pub(crate) db: String,
+	pub(crate) tb: String,
+	/// The record which the notification is for
+	pub(crate) rid: Thing,
+	/// The compaction window of the live query
+	pub(crate) size: Duration,
+	pub(crate) notification: Notification,
+}
+
+/// A compacted notification whose window has ended
+#[derive(Debug)]
+pub(crate) struct CompactedNotification {
+	pub(crate) ns: String,
+	pub(crate) db: String,
+	pub(crate) tb: String,
+	pub(crate) notification: Notification,
+}
+
+struct PendingState {
+	ns: String,
+	db: String,
+	tb: String,
+	// The time at which the notification is sent, in milliseconds
+	send: u64,
+	notification: Notification,
+}
+
+/// Tracks the UPDATE notifications which are waiting to be compacted, for the
+/// live queries owned by this node.
+///
+/// The first UPDATE notification for a record opens a window, and any later
+/// UPDATE notifications for the same record within the window replace its
+/// result, so that a single notification with the latest state of the record
+/// is sent when the window ends. CREATE and DELETE notifications are never
+/// compacted, and any pending UPDATE notification for the record is sent
+/// before them, so that the notifications of a record stay in order.
+#[derive(Default)]
+pub(crate) struct LiveCompactTracker {
+	pending: BTreeMap<(Uuid, Thing), PendingState>,
+}
+
+impl LiveCompactTracker {
+	/// Add a committed notification, at the specified time in milliseconds,
+	/// returning the notifications which should be sent straight away
+	pub(crate) fn push(&mut self, ts: u64, ev: CompactEvent) -> Vec<Notification> {
+		let CompactEvent {
+			ns,
+			db,
+			tb,
+			rid,
+			size,
+			notification,
+		} = ev;
+		let key = (notification.id, rid);
+		match notification.action {
+			Action::Update if !size.is_zero() => {
+				match self.pending.get_mut(&key) {
+					Some(state) => state.notification.result = notification.result,
+					None => {
+						self.pending.insert(
+							key,
+							PendingState {
+								ns,
+								db,
+								tb,
+								send: ts + size.as_millis() as u64,
+								notification,
+							},
+						);
+					}
+				}
+				vec![]
+			}
+			_ => {
+				let mut out = Vec::with_capacity(2);
+				if let Some(state) = self.pending.remove(&key) {
+					out.push(state.notification);
+				}
+				out.push(notification);
+				out
+			}
+		}
+	}
+
+	/// Take all of the notifications whose window ended at or before the specified time in milliseconds
+	pub(crate) fn flush(&mut self, ts: u64) -> Vec<CompactedNotification> {
+		let mut due = Vec::new();
+		self.pending.retain(|_, state| {
+			if state.send > ts {
+				return true;
+			}
+			due.push((
+				state.send,
+				CompactedNotification {
+					ns: std::mem::take(&mut state.ns),
+					db: std::mem::take(&mut state.db),
+					tb: std::mem::take(&mut state.tb),
+					notification: state.notification.clone(),
+				},
+			));
+			false
+		});
+		// Send the notifications in the order their windows ended
+		due.sort_by_key(|(send, _)| *send);
+		due.into_iter().map(|(_, v)| v).collect()
+	}
+
+	/// Discard the pending notifications of a live query which no longer exists
+	pub(crate) fn remove(&mut self, id: &Uuid) {
+		self.pending.retain(|(lq, _), _| lq != id);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sql::Value;
+
+	fn event(id: Uuid, rid: &str, action: Action, value: i64) -> CompactEvent {
+		CompactEvent {
+			ns: "test".to_owned(),
+			db: "test".to_owned(),
+			tb: "person".to_owned(),
+			rid: Thing::from(("person", rid)),
+			size: Duration::from_millis(50),
+			notification: Notification::new(id, action, Value::from(value)),
+		}
+	}
+
+	#[test]
+	fn updates_are_compacted() {
+		let id = Uuid::new_v4();
+		let mut tracker = LiveCompactTracker::default();
+		assert!(tracker.push(1_000, event(id, "one", Action::Update, 1)).is_empty());
+		assert!(tracker.push(1_010, event(id, "two", Action::Update, 2)).is_empty());
+		assert!(tracker.push(1_040, event(id, "one", Action::Update, 3)).is_empty());
+		// The window is still open
+		assert!(tracker.flush(1_049).is_empty());
+		// The latest state of the first record is sent when its window ends
+		let sent = tracker.flush(1_050);
+		assert_eq!(sent.len(), 1);
+		assert_eq!(sent[0].notification.result, Value::from(3));
+		// A new window is opened by the next update
+		assert!(tracker.push(1_055, event(id, "one", Action::Update, 4)).is_empty());
+		let sent = tracker.flush(1_200);
+		let values: Vec<Value> = sent.into_iter().map(|v| v.notification.result).collect();
+		assert_eq!(values, vec![Value::from(2), Value::from(4)]);
+		assert!(tracker.pending.is_empty());
+	}
+
+	#[test]
+	fn creates_and_deletes_are_not_compacted() {
+		let id = Uuid::new_v4();
+		let mut tracker = LiveCompactTracker::default();
+		let sent = tracker.push(1_000, event(id, "one", Action::Create, 1));
+		assert_eq!(sent.len(), 1);
+		assert!(tracker.push(1_010, event(id, "one", Action::Update, 2)).is_empty());
+		// The pending update is sent before the delete
+		let sent = tracker.push(1_020, event(id, "one", Action::Delete, 3));
+		let actions: Vec<Action> = sent.into_iter().map(|v| v.action).collect();
+		assert_eq!(actions, vec![Action::Update, Action::Delete]);
+		assert!(tracker.flush(2_000).is_empty());
+	}
+
+	#[test]
+	fn removed_live_queries() {
+		let id = Uuid::new_v4();
+		let mut tracker = LiveCompactTracker::default();
+		tracker.push(1_000, event(id, "one", Action::Update, 1));
+		tracker.remove(&id);
+		assert!(tracker.flush(2_000).is_empty());
+	}
+}
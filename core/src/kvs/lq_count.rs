@@ -0,0 +1,84 @@
+use crate::sql::Uuid;
+use std::collections::HashMap;
+
+/// A change to the number of records matching a live query which counts its
+/// records. This is buffered in the transaction, and is only applied to the
+/// counter once the transaction commits.
+#[derive(Debug)]
+pub(crate) enum CountEvent {
+	/// The live query was started, with the number of records which matched
+	Start(Uuid, u64),
+	/// The number of records matching the live query increased or decreased by one
+	Change(Uuid, bool),
+	/// The live query was killed
+	Stop(Uuid),
+}
+
+/// Tracks the number of matching records for the counting live queries owned
+/// by this node.
+///
+/// The number of matching records is counted once when the live query starts,
+/// and is then maintained from the record changes which are committed, so that
+/// only the changing number needs to be sent, instead of the records.
+#[derive(Default)]
+pub(crate) struct LiveCountTracker {
+	counts: HashMap<Uuid, u64>,
+}
+
+impl LiveCountTracker {
+	/// Apply a committed change, returning the live query and
+	/// its new number of matching records if it should be sent
+	pub(crate) fn push(&mut self, ev: CountEvent) -> Option<(Uuid, u64)> {
+		match ev {
+			CountEvent::Start(id, count) => {
+				self.counts.insert(id, count);
+				Some((id, count))
+			}
+			CountEvent::Change(id, added) => {
+				let count = self.counts.get_mut(&id)?;
+				*count = match added {
+					true => count.saturating_add(1),
+					false => count.saturating_sub(1),
+				};
+				Some((id, *count))
+			}
+			CountEvent::Stop(id) => {
+				self.counts.remove(&id);
+				None
+			}
+		}
+	}
+
+	/// Discard the counter of a live query which no longer exists
+	pub(crate) fn remove(&mut self, id: &Uuid) {
+		self.counts.remove(id);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn counts_are_maintained() {
+		let id = Uuid::new_v4();
+		let mut tracker = LiveCountTracker::default();
+		// Changes before the live query is started are ignored
+		assert_eq!(tracker.push(CountEvent::Change(id, true)), None);
+		assert_eq!(tracker.push(CountEvent::Start(id, 2)), Some((id, 2)));
+		assert_eq!(tracker.push(CountEvent::Change(id, true)), Some((id, 3)));
+		assert_eq!(tracker.push(CountEvent::Change(id, false)), Some((id, 2)));
+		assert_eq!(tracker.push(CountEvent::Stop(id)), None);
+		assert_eq!(tracker.push(CountEvent::Change(id, true)), None);
+	}
+
+	#[test]
+	fn removed_live_queries() {
+		let id = Uuid::new_v4();
+		let mut tracker = LiveCountTracker::default();
+		tracker.push(CountEvent::Start(id, 0));
+		tracker.remove(&id);
+		assert_eq!(tracker.push(CountEvent::Change(id, false)), None);
+		assert!(tracker.counts.is_empty());
+	}
+}
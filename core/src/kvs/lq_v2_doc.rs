@@ -40,6 +40,17 @@ pub(in crate::kvs) fn construct_document(
 			);
 			Ok(Some(doc))
 		}
+		TableMutation::DelWithOriginal(id, original) => {
+			let doc = Document::new_artificial(
+				None,
+				Some(id),
+				None,
+				Cow::Owned(Value::None),
+				Cow::Borrowed(original),
+				Workable::Normal,
+			);
+			Ok(Some(doc))
+		}
 		TableMutation::Def(_) => Ok(None),
 		TableMutation::SetWithDiff(id, current_value, operations) => {
 			// We need a previous value otherwise the Value::compute function won't work correctly
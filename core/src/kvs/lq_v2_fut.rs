@@ -169,8 +169,10 @@ async fn process_change_set_for_notifications(
 							channel::bounded(notification_capacity);
 						if doc.initial_doc().is_none()
 							&& doc.current_doc().is_none()
-							&& !matches!(mutation, TableMutation::Del(_))
-						{
+							&& !matches!(
+								mutation,
+								TableMutation::Del(_) | TableMutation::DelWithOriginal(..)
+							) {
 							// If we have a None to None mutation, and it isn't delete, then it indicates a bad document
 							panic!("Doc was wrong and the mutation was {:?}", mutation);
 						}
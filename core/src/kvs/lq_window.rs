@@ -0,0 +1,251 @@
+use crate::ctx::Context;
+use crate::dbs::{Options, Transaction};
+use crate::err::Error;
+use crate::sql::paths::{SC, SD, TK};
+use crate::sql::statements::{LiveStatement, SelectStatement};
+use crate::sql::{Groups, Uuid, Value, Values};
+use reblessive::tree::Stk;
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::Arc;
+
+/// A record which matched a windowed live query. This is buffered in the
+/// transaction, and is only added to a window once the transaction commits.
+#[derive(Debug)]
+pub(crate) struct WindowEvent {
+	pub(crate) ns: String,
+	pub(crate) db: String,
+	pub(crate) tb: String,
+	pub(crate) stm: LiveStatement,
+	pub(crate) value: Value,
+}
+
+/// A live query window which has closed, with the records which it contained
+#[derive(Debug)]
+pub(crate) struct ClosedWindow {
+	pub(crate) ns: String,
+	pub(crate) db: String,
+	pub(crate) tb: String,
+	pub(crate) stm: LiveStatement,
+	pub(crate) values: Vec<Value>,
+}
+
+impl ClosedWindow {
+	/// Aggregate the records of this window using the projections of the live query
+	pub(crate) async fn compute(
+		self,
+		stk: &mut Stk,
+		opt: &Options,
+		txn: &Transaction,
+	) -> Result<Value, Error> {
+		// Ensure that a session and auth info exist on the LIVE query
+		let (Some(sess), Some(auth)) = (self.stm.session, self.stm.auth) else {
+			return Ok(Value::None);
+		};
+		// Process the window using the session
+		// of the user who created the LIVE query
+		let mut ctx = Context::background();
+		ctx.add_value("auth", sess.pick(SD.as_ref()));
+		ctx.add_value("scope", sess.pick(SC.as_ref()));
+		ctx.add_value("token", sess.pick(TK.as_ref()));
+		ctx.add_value("session", &sess);
+		let opt = opt
+			.new_with_perms(true)
+			.with_ns(Some(self.ns.into()))
+			.with_db(Some(self.db.into()))
+			.with_auth(Arc::from(auth));
+		// Group all of the records in the window together
+		let stm = SelectStatement {
+			expr: self.stm.expr,
+			what: Values(vec![Value::from(self.values)]),
+			group: Some(Groups(vec![])),
+			fetch: self.stm.fetch,
+			..Default::default()
+		};
+		match stm.compute(stk, &ctx, &opt, txn, None).await? {
+			Value::Array(mut v) if !v.is_empty() => Ok(v.remove(0)),
+			_ => Ok(Value::None),
+		}
+	}
+}
+
+struct WindowState {
+	ns: String,
+	db: String,
+	tb: String,
+	stm: LiveStatement,
+	// The size of each window, in milliseconds
+	size: u64,
+	// The interval at which windows close, in milliseconds
+	step: u64,
+	// The time at which the current window closes, in milliseconds
+	close: u64,
+	// The records in the current and any later windows, ordered by time
+	events: VecDeque<(u64, Value)>,
+}
+
+/// Tracks the open windows of the windowed live queries owned by this node.
+///
+/// Windows are aligned to the unix epoch, so the state of a live query can
+/// be discarded as soon as it holds no records, and recreated identically
+/// when the next matching record is committed.
+#[derive(Default)]
+pub(crate) struct LiveWindowTracker {
+	windows: BTreeMap<Uuid, WindowState>,
+}
+
+impl LiveWindowTracker {
+	/// Add a committed record, at the specified time in milliseconds
+	pub(crate) fn push(&mut self, ts: u64, ev: WindowEvent) {
+		let WindowEvent {
+			ns,
+			db,
+			tb,
+			stm,
+			value,
+		} = ev;
+		let Some(window) = stm.window else {
+			return;
+		};
+		let size = window.size.as_millis() as u64;
+		let step = window.step().as_millis() as u64;
+		if size == 0 || step == 0 {
+			return;
+		}
+		let state = self.windows.entry(stm.id).or_insert_with(|| WindowState {
+			ns,
+			db,
+			tb,
+			stm,
+			size,
+			step,
+			close: (ts / step + 1) * step,
+			events: VecDeque::new(),
+		});
+		state.events.push_back((ts, value));
+	}
+
+	/// Close all of the windows which ended at or before the specified time in milliseconds
+	pub(crate) fn close(&mut self, ts: u64) -> Vec<ClosedWindow> {
+		let mut out = Vec::new();
+		self.windows.retain(|_, state| {
+			while state.close <= ts {
+				// Collect the records which fall within this window
+				let open = state.close.saturating_sub(state.size);
+				let values: Vec<Value> = state
+					.events
+					.iter()
+					.filter(|(t, _)| *t >= open && *t < state.close)
+					.map(|(_, v)| v.clone())
+					.collect();
+				if !values.is_empty() {
+					out.push(ClosedWindow {
+						ns: state.ns.clone(),
+						db: state.db.clone(),
+						tb: state.tb.clone(),
+						stm: state.stm.clone(),
+						values,
+					});
+				}
+				// Discard the records which no later window contains
+				state.close += state.step;
+				let open = state.close.saturating_sub(state.size);
+				while state.events.front().is_some_and(|(t, _)| *t < open) {
+					state.events.pop_front();
+				}
+				if state.events.is_empty() {
+					return false;
+				}
+			}
+			true
+		});
+		out
+	}
+
+	/// Discard the windows of a live query which no longer exists
+	pub(crate) fn remove(&mut self, id: &Uuid) {
+		self.windows.remove(id);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sql::Window;
+	use std::time::Duration;
+
+	fn event(id: Uuid, window: Window, value: i64) -> WindowEvent {
+		WindowEvent {
+			ns: "test".to_owned(),
+			db: "test".to_owned(),
+			tb: "clicks".to_owned(),
+			stm: LiveStatement {
+				id,
+				window: Some(window),
+				..Default::default()
+			},
+			value: Value::from(value),
+		}
+	}
+
+	#[test]
+	fn tumbling_windows() {
+		let id = Uuid::new_v4();
+		let window = Window {
+			size: Duration::from_secs(60),
+			every: None,
+		};
+		let mut tracker = LiveWindowTracker::default();
+		tracker.push(1_000, event(id, window, 1));
+		tracker.push(59_999, event(id, window, 2));
+		tracker.push(60_000, event(id, window, 3));
+		// The first window is still open
+		assert!(tracker.close(59_999).is_empty());
+		// The first window closes with the records it contains
+		let closed = tracker.close(60_000);
+		assert_eq!(closed.len(), 1);
+		assert_eq!(closed[0].values, vec![Value::from(1), Value::from(2)]);
+		// Empty windows are skipped, and the state is discarded once empty
+		let closed = tracker.close(300_000);
+		assert_eq!(closed.len(), 1);
+		assert_eq!(closed[0].values, vec![Value::from(3)]);
+		assert!(tracker.windows.is_empty());
+	}
+
+	#[test]
+	fn sliding_windows() {
+		let id = Uuid::new_v4();
+		let window = Window {
+			size: Duration::from_secs(180),
+			every: Some(Duration::from_secs(60)),
+		};
+		let mut tracker = LiveWindowTracker::default();
+		tracker.push(30_000, event(id, window, 1));
+		tracker.push(90_000, event(id, window, 2));
+		// Each record is contained in three consecutive windows
+		let closed = tracker.close(240_000);
+		let values: Vec<Vec<Value>> = closed.into_iter().map(|w| w.values).collect();
+		assert_eq!(
+			values,
+			vec![
+				vec![Value::from(1)],
+				vec![Value::from(1), Value::from(2)],
+				vec![Value::from(1), Value::from(2)],
+				vec![Value::from(2)],
+			]
+		);
+		assert!(tracker.windows.is_empty());
+	}
+
+	#[test]
+	fn removed_windows() {
+		let id = Uuid::new_v4();
+		let window = Window {
+			size: Duration::from_secs(60),
+			every: None,
+		};
+		let mut tracker = LiveWindowTracker::default();
+		tracker.push(1_000, event(id, window, 1));
+		tracker.remove(&id);
+		assert!(tracker.close(60_000).is_empty());
+	}
+}
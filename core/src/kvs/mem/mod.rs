@@ -150,6 +150,22 @@ impl Transaction {
 		// Return result
 		Ok(res)
 	}
+	/// Acquire write intent on a key
+	pub(crate) fn lock<K>(&mut self, _key: K) -> Result<(), Error>
+	where
+		K: Into<Key>,
+	{
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.write {
+			return Err(Error::TxReadonly);
+		}
+		// Write transactions are serialized, so keys are already locked
+		Ok(())
+	}
 	/// Obtain a new change timestamp for a key
 	/// which is replaced with the current timestamp when the transaction is committed.
 	/// NOTE: This should be called when composing the change feed entries for this transaction,
@@ -0,0 +1,381 @@
+//! Server metrics which can be queried with SurrealQL.
+//!
+//! The client connections and transactions which are open on this node are
+//! tracked, so that they can be listed using the `system::connections()` and
+//! `system::transactions()` functions, alongside the `system::live_queries()`
+//! and `system::tables()` functions. Each function returns an array of objects,
+//! so dashboards can be built with statements such as
+//! `SELECT * FROM system::transactions() WHERE elapsed > 1s`, instead of
+//! scraping a metrics endpoint.
+//!
+//! The queries which are executing on this node are listed with the
+//! `system::queries()` function, and a runaway query can be stopped with
+//! `KILL QUERY <id>`, which cancels the context in which it is executing.
+//! Queries which are waiting to be admitted are listed as queued, along with
+//! the priority of their session.
+//!
+//! The records which are linked from FETCH clauses, and the records which are
+//! reached by graph expansions, are read together in batches. The number of
+//! batched reads, and the number of records which were read in them, are shown
+//! by the `system::prefetch()` function, along with the number of requests to
+//! the datastore which were saved by reading the records together.
+//!
+//! The totals of the queries and transactions which have run on this node are
+//! shown by the `system::activity()` function, along with the number of open
+//! connections, transactions, and queries, so that a client such as
+//! `surreal top` can compute the rate of queries between two samples.
+use crate::ctx::Canceller;
+use crate::dbs::Session;
+use crate::sql::{Datetime, Duration, Object, Priority, Value};
+use chrono::Utc;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use trice::Instant;
+use uuid::Uuid;
+
+struct Connection {
+	// The protocol of the connection
+	protocol: String,
+	// The time at which the connection was opened
+	opened: Datetime,
+	// The instant at which the connection was opened
+	started: Instant,
+}
+
+struct Transaction {
+	// Whether the transaction can write
+	write: bool,
+	// Whether the transaction performs background work
+	background: bool,
+	// The instant at which the transaction began
+	started: Instant,
+}
+
+struct Query {
+	// The session which is executing the query
+	session: Option<String>,
+	// The namespace in which the query is executing
+	ns: Option<String>,
+	// The database in which the query is executing
+	db: Option<String>,
+	// The text of the query
+	text: String,
+	// The instant at which the query started
+	started: Instant,
+	// The priority of the session which is executing the query
+	priority: Priority,
+	// Whether the query is waiting to be admitted
+	queued: bool,
+	// Whether the query has been killed
+	cancelled: bool,
+	// Cancels the execution of the query
+	canceller: Canceller,
+}
+
+#[derive(Default)]
+pub(crate) struct Metrics {
+	// The identifier of the next transaction
+	next: AtomicU64,
+	// The open client connections
+	connections: Mutex<BTreeMap<Uuid, Connection>>,
+	// The open transactions
+	transactions: Mutex<BTreeMap<u64, Transaction>>,
+	// The executing queries
+	queries: Mutex<BTreeMap<Uuid, Query>>,
+	// The number of queries which have been executed
+	executed: AtomicU64,
+	// The number of batched reads of records
+	batches: AtomicU64,
+	// The number of records read in batches
+	batched: AtomicU64,
+}
+
+/// Removes a transaction from the open transactions when it is dropped
+pub(crate) struct Tracked {
+	metrics: Arc<Metrics>,
+	id: u64,
+}
+
+impl Drop for Tracked {
+	fn drop(&mut self) {
+		self.metrics.transactions.lock().unwrap().remove(&self.id);
+	}
+}
+
+impl Tracked {
+	/// Mark the transaction as performing background work
+	pub(crate) fn set_background(&self) {
+		if let Some(tx) = self.metrics.transactions.lock().unwrap().get_mut(&self.id) {
+			tx.background = true;
+		}
+	}
+}
+
+/// Removes a query from the executing queries when it is dropped
+pub(crate) struct Running {
+	metrics: Arc<Metrics>,
+	id: Uuid,
+}
+
+impl Drop for Running {
+	fn drop(&mut self) {
+		self.metrics.queries.lock().unwrap().remove(&self.id);
+	}
+}
+
+impl Running {
+	/// Mark the query as admitted, after waiting in the queue
+	pub(crate) fn admit(&self) {
+		if let Some(v) = self.metrics.queries.lock().unwrap().get_mut(&self.id) {
+			v.queued = false;
+		}
+	}
+}
+
+impl Metrics {
+	/// Record that a client connection has been opened
+	pub(crate) fn connect(&self, id: Uuid, protocol: &str) {
+		self.connections.lock().unwrap().insert(
+			id,
+			Connection {
+				protocol: protocol.to_owned(),
+				opened: Datetime(Utc::now()),
+				started: Instant::now(),
+			},
+		);
+	}
+
+	/// Record that a client connection has been closed
+	pub(crate) fn disconnect(&self, id: &Uuid) {
+		self.connections.lock().unwrap().remove(id);
+	}
+
+	/// Record that a transaction has begun, until the returned value is dropped
+	pub(crate) fn begin(self: &Arc<Self>, write: bool) -> Tracked {
+		let id = self.next.fetch_add(1, Ordering::Relaxed);
+		self.transactions.lock().unwrap().insert(
+			id,
+			Transaction {
+				write,
+				background: false,
+				started: Instant::now(),
+			},
+		);
+		Tracked {
+			metrics: self.clone(),
+			id,
+		}
+	}
+
+	/// Record that a query is executing, until the returned value is dropped
+	pub(crate) fn run(
+		self: &Arc<Self>,
+		sess: &Session,
+		text: String,
+		canceller: Canceller,
+	) -> Running {
+		let id = Uuid::new_v4();
+		self.executed.fetch_add(1, Ordering::Relaxed);
+		self.queries.lock().unwrap().insert(
+			id,
+			Query {
+				session: sess.id.clone(),
+				ns: sess.ns.clone(),
+				db: sess.db.clone(),
+				text,
+				started: Instant::now(),
+				priority: sess.pr,
+				queued: true,
+				cancelled: false,
+				canceller,
+			},
+		);
+		Running {
+			metrics: self.clone(),
+			id,
+		}
+	}
+
+	/// Cancel an executing query, returning whether the query was found
+	pub(crate) fn cancel(&self, id: &Uuid) -> bool {
+		match self.queries.lock().unwrap().get_mut(id) {
+			Some(v) => {
+				v.cancelled = true;
+				v.canceller.cancel();
+				true
+			}
+			None => false,
+		}
+	}
+
+	/// Record that a number of records were read in a single batch
+	#[allow(dead_code)]
+	pub(crate) fn prefetch(&self, records: usize) {
+		self.batches.fetch_add(1, Ordering::Relaxed);
+		self.batched.fetch_add(records as u64, Ordering::Relaxed);
+	}
+
+	/// Output the batched reads of records, and the requests which they saved
+	pub(crate) fn prefetches(&self) -> Value {
+		let batches = self.batches.load(Ordering::Relaxed);
+		let records = self.batched.load(Ordering::Relaxed);
+		Value::from(Object::from(map! {
+			"batches" => Value::from(batches),
+			"records" => Value::from(records),
+			"saved" => Value::from(records.saturating_sub(batches)),
+		}))
+	}
+
+	/// Output the totals of the queries and transactions which have run, and the
+	/// number of connections, transactions, and queries which are open
+	pub(crate) fn activity(&self) -> Object {
+		Object::from(map! {
+			"queries" => Value::from(self.executed.load(Ordering::Relaxed)),
+			"transactions" => Value::from(self.next.load(Ordering::Relaxed)),
+			"open_connections" => Value::from(self.connections.lock().unwrap().len()),
+			"open_transactions" => Value::from(self.transactions.lock().unwrap().len()),
+			"open_queries" => Value::from(self.queries.lock().unwrap().len()),
+		})
+	}
+
+	/// Output the open client connections
+	pub(crate) fn connections(&self) -> Value {
+		let now = Instant::now();
+		self.connections
+			.lock()
+			.unwrap()
+			.iter()
+			.map(|(id, v)| {
+				Value::from(Object::from(map! {
+					"id" => Value::Uuid(id.to_owned().into()),
+					"protocol" => Value::from(v.protocol.clone()),
+					"opened" => Value::from(v.opened.clone()),
+					"elapsed" => Value::from(Duration(now.saturating_duration_since(v.started))),
+				}))
+			})
+			.collect::<Vec<_>>()
+			.into()
+	}
+
+	/// Output the open transactions
+	pub(crate) fn transactions(&self) -> Value {
+		let now = Instant::now();
+		self.transactions
+			.lock()
+			.unwrap()
+			.iter()
+			.map(|(id, v)| {
+				Value::from(Object::from(map! {
+					"id" => Value::from(*id),
+					"write" => Value::from(v.write),
+					"background" => Value::from(v.background),
+					"elapsed" => Value::from(Duration(now.saturating_duration_since(v.started))),
+				}))
+			})
+			.collect::<Vec<_>>()
+			.into()
+	}
+
+	/// Output the executing queries
+	pub(crate) fn queries(&self) -> Value {
+		let now = Instant::now();
+		let opt = |v: &Option<String>| v.clone().map(Value::from).unwrap_or_default();
+		self.queries
+			.lock()
+			.unwrap()
+			.iter()
+			.map(|(id, v)| {
+				Value::from(Object::from(map! {
+					"id" => Value::Uuid(id.to_owned().into()),
+					"session" => opt(&v.session),
+					"ns" => opt(&v.ns),
+					"db" => opt(&v.db),
+					"text" => Value::from(v.text.clone()),
+					"elapsed" => Value::from(Duration(now.saturating_duration_since(v.started))),
+					"priority" => Value::from(v.priority.to_string()),
+					"state" => Value::from(match (v.cancelled, v.queued) {
+						(true, _) => "cancelling",
+						(false, true) => "queued",
+						(false, false) => "running",
+					}),
+				}))
+			})
+			.collect::<Vec<_>>()
+			.into()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn transactions_are_removed_when_dropped() {
+		let metrics = Arc::new(Metrics::default());
+		let a = metrics.begin(true);
+		let b = metrics.begin(false);
+		b.set_background();
+		assert_eq!(metrics.transactions.lock().unwrap().len(), 2);
+		drop(a);
+		let tx = metrics.transactions.lock().unwrap();
+		assert_eq!(tx.len(), 1);
+		assert!(tx.values().all(|v| !v.write && v.background));
+	}
+
+	#[test]
+	fn connections_are_removed_when_closed() {
+		let metrics = Metrics::default();
+		let id = Uuid::new_v4();
+		metrics.connect(id, "ws");
+		assert!(matches!(metrics.connections(), Value::Array(v) if v.len() == 1));
+		metrics.disconnect(&id);
+		assert!(matches!(metrics.connections(), Value::Array(v) if v.is_empty()));
+	}
+
+	#[test]
+	fn queries_are_cancelled_and_removed_when_finished() {
+		let metrics = Arc::new(Metrics::default());
+		let cancelled = Arc::new(std::sync::atomic::AtomicBool::new(false));
+		let running = metrics.run(
+			&Session::owner(),
+			"SLEEP 1h".to_owned(),
+			Canceller::new(cancelled.clone()),
+		);
+		assert!(!metrics.cancel(&Uuid::new_v4()));
+		assert!(metrics.cancel(&running.id));
+		assert!(cancelled.load(Ordering::Relaxed));
+		assert!(metrics.queries.lock().unwrap().values().all(|v| v.cancelled));
+		drop(running);
+		assert!(matches!(metrics.queries(), Value::Array(v) if v.is_empty()));
+	}
+
+	#[test]
+	fn activity_counts_finished_queries() {
+		let metrics = Arc::new(Metrics::default());
+		let cancelled = Arc::new(std::sync::atomic::AtomicBool::new(false));
+		let running =
+			metrics.run(&Session::owner(), "INFO FOR ROOT".to_owned(), Canceller::new(cancelled));
+		let tx = metrics.begin(false);
+		let out = Value::from(metrics.activity());
+		assert_eq!(out.pick(&[crate::sql::Part::from("open_queries")]), Value::from(1));
+		assert_eq!(out.pick(&[crate::sql::Part::from("open_transactions")]), Value::from(1));
+		drop(running);
+		drop(tx);
+		let out = Value::from(metrics.activity());
+		assert_eq!(out.pick(&[crate::sql::Part::from("queries")]), Value::from(1));
+		assert_eq!(out.pick(&[crate::sql::Part::from("transactions")]), Value::from(1));
+		assert_eq!(out.pick(&[crate::sql::Part::from("open_queries")]), Value::from(0));
+	}
+
+	#[test]
+	fn prefetches_count_saved_requests() {
+		let metrics = Metrics::default();
+		metrics.prefetch(10);
+		metrics.prefetch(1);
+		let out = metrics.prefetches();
+		assert_eq!(out.pick(&[crate::sql::Part::from("batches")]), Value::from(2));
+		assert_eq!(out.pick(&[crate::sql::Part::from("records")]), Value::from(11));
+		assert_eq!(out.pick(&[crate::sql::Part::from("saved")]), Value::from(9));
+	}
+}
@@ -12,20 +12,49 @@
 //! - `speedb`: [SpeedyDB](https://github.com/speedb-io/speedb) fork of rocksDB making it faster (Redis is using speedb but this is not acid transactions)
 //! - `tikv`: [TiKV](https://github.com/tikv/tikv) a distributed, and transactional key-value database
 //! - `mem`: in-memory database
+mod admission;
+mod advisor;
+pub mod blob;
 mod cache;
+mod chunk;
 mod clock;
+mod codec;
+mod conflicts;
+pub mod conformance;
 mod ds;
+mod engine;
+#[cfg(feature = "failpoints")]
+pub mod fail;
 mod fdb;
+mod import;
 mod indxdb;
+mod integrity;
 mod kv;
 mod mem;
+mod ops;
+mod preload;
+mod quota;
+mod results;
 mod rocksdb;
+mod savepoint;
 mod speedb;
 mod surrealkv;
+mod sync;
 mod tikv;
 mod tx;
 
+pub(crate) mod backfill;
+pub(crate) mod enrichment;
+pub(crate) mod history;
+pub(crate) mod lq_auth;
+pub(crate) mod lq_compact;
+pub(crate) mod lq_count;
 pub(crate) mod lq_structs;
+pub(crate) mod lq_window;
+pub(crate) mod metrics;
+pub(crate) mod scheduler;
+pub(crate) mod stats;
+pub(crate) mod version;
 
 mod lq_cf;
 mod lq_v2_doc;
@@ -34,5 +63,14 @@ mod lq_v2_fut;
 mod tests;
 
 pub use self::ds::*;
+pub use self::engine::EngineCapabilities;
+pub use self::import::Importer;
+pub use self::integrity::Issue;
 pub use self::kv::*;
+pub use self::ops::Operations;
+pub use self::preload::Preloaded;
+pub use self::sync::{SyncChunk, SyncDecoder, SyncReader};
 pub use self::tx::*;
+
+pub(crate) use self::conflicts::{is_conflict, Merge};
+pub(crate) use self::ops::OperationCounter;
@@ -0,0 +1,131 @@
+//! Counts of the operations which statements perform on the storage engine.
+//!
+//! When a datastore counts operations, the response of each statement records
+//! the number of keys which the statement fetched, scanned, written, and
+//! deleted. Tests can compare these against a budget, so that a change which
+//! makes a query read far more than it needs to, such as fetching a definition
+//! for every row, fails a test instead of going unnoticed.
+use std::fmt::{self, Display};
+use std::ops::Sub;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// A kind of operation on the storage engine
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum Op {
+	/// A key was fetched, or checked for existence
+	Get,
+	/// A range of keys was scanned, counted once per request
+	Scan,
+	/// A key was written
+	Put,
+	/// A key, or a range of keys, was deleted
+	Del,
+}
+
+/// The operations which have been performed by the transactions of a query
+#[derive(Debug, Default)]
+pub(crate) struct OperationCounter {
+	gets: AtomicU64,
+	scans: AtomicU64,
+	puts: AtomicU64,
+	dels: AtomicU64,
+}
+
+impl OperationCounter {
+	/// Count a number of operations of the specified kind
+	pub(crate) fn add(&self, op: Op, count: u64) {
+		let v = match op {
+			Op::Get => &self.gets,
+			Op::Scan => &self.scans,
+			Op::Put => &self.puts,
+			Op::Del => &self.dels,
+		};
+		v.fetch_add(count, Ordering::Relaxed);
+	}
+
+	/// The operations which have been counted so far
+	pub(crate) fn snapshot(&self) -> Operations {
+		Operations {
+			gets: self.gets.load(Ordering::Relaxed),
+			scans: self.scans.load(Ordering::Relaxed),
+			puts: self.puts.load(Ordering::Relaxed),
+			dels: self.dels.load(Ordering::Relaxed),
+		}
+	}
+}
+
+/// The number of operations which a statement performed on the storage engine
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub struct Operations {
+	/// The number of keys which were fetched
+	pub gets: u64,
+	/// The number of range scan requests
+	pub scans: u64,
+	/// The number of keys which were written
+	pub puts: u64,
+	/// The number of keys, or ranges of keys, which were deleted
+	pub dels: u64,
+}
+
+impl Operations {
+	/// A budget of operations, for comparing with the operations of a statement
+	pub const fn budget(gets: u64, scans: u64, puts: u64, dels: u64) -> Self {
+		Self {
+			gets,
+			scans,
+			puts,
+			dels,
+		}
+	}
+
+	/// Check that no kind of operation was performed more often than the budget allows
+	pub fn within(&self, budget: &Operations) -> bool {
+		self.gets <= budget.gets
+			&& self.scans <= budget.scans
+			&& self.puts <= budget.puts
+			&& self.dels <= budget.dels
+	}
+}
+
+impl Sub for Operations {
+	type Output = Self;
+
+	fn sub(self, other: Self) -> Self {
+		Self {
+			gets: self.gets.saturating_sub(other.gets),
+			scans: self.scans.saturating_sub(other.scans),
+			puts: self.puts.saturating_sub(other.puts),
+			dels: self.dels.saturating_sub(other.dels),
+		}
+	}
+}
+
+impl Display for Operations {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(
+			f,
+			"{} gets, {} scans, {} puts, {} dels",
+			self.gets, self.scans, self.puts, self.dels
+		)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn operations_are_compared_with_a_budget() {
+		let counter = OperationCounter::default();
+		counter.add(Op::Get, 3);
+		let before = counter.snapshot();
+		counter.add(Op::Get, 2);
+		counter.add(Op::Scan, 1);
+		let ops = counter.snapshot() - before;
+		assert_eq!(ops, Operations::budget(2, 1, 0, 0));
+		assert_eq!(ops.to_string(), "2 gets, 1 scans, 0 puts, 0 dels");
+		assert!(ops.within(&Operations::budget(2, 1, 0, 0)));
+		assert!(!ops.within(&Operations::budget(1, 10, 10, 10)));
+	}
+}
@@ -0,0 +1,120 @@
+//! Preloading of the definitions and indexes of a database, before a node serves any traffic.
+//!
+//! Directly after a node starts, the first queries against a database read the definitions of
+//! its tables, fields, and indexes, and the keys of the indexes which they scan, from cold
+//! storage, so their latency is much higher than once the node has warmed up. Preloading reads
+//! the definitions of a database, and optionally the keys of its indexes, when the node starts,
+//! so that they are held in the block cache of the storage engine, and in the node-level cache
+//! when one is configured. The keys of each index are read in order, up to a limit, so the
+//! start of each index is warmed first.
+use crate::cnf::{NORMAL_FETCH_SIZE, PRELOAD_INDEX_LIMIT};
+use crate::err::Error;
+use crate::kvs::{Datastore, Limit, LockType::*, ScanPage, Transaction, TransactionType::*};
+use std::fmt;
+
+/// The number of definitions and index keys which were preloaded for a database
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct Preloaded {
+	/// The number of tables in the database
+	pub tables: usize,
+	/// The number of definitions which were read, including the tables
+	pub definitions: usize,
+	/// The number of index keys which were read
+	pub index_keys: usize,
+}
+
+impl fmt::Display for Preloaded {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(
+			f,
+			"{} definitions of {} tables, and {} index keys",
+			self.definitions, self.tables, self.index_keys
+		)
+	}
+}
+
+impl Datastore {
+	/// Read the definitions of a database, and the keys of its indexes when `indexes`
+	/// is set, so that they are cached before the database is queried
+	pub async fn preload(&self, ns: &str, db: &str, indexes: bool) -> Result<Preloaded, Error> {
+		let mut tx = self.transaction(Read, Optimistic).await?;
+		let res = preload(&mut tx, ns, db, indexes).await;
+		tx.cancel().await?;
+		res
+	}
+}
+
+async fn preload(
+	tx: &mut Transaction,
+	ns: &str,
+	db: &str,
+	indexes: bool,
+) -> Result<Preloaded, Error> {
+	let mut out = Preloaded::default();
+	// The definitions of the database
+	out.definitions += tx.all_db_functions(ns, db).await?.len();
+	out.definitions += tx.all_db_params(ns, db).await?.len();
+	out.definitions += tx.all_db_analyzers(ns, db).await?.len();
+	// The definitions of each table
+	let tbs = tx.all_tb(ns, db).await?;
+	out.tables = tbs.len();
+	out.definitions += tbs.len();
+	for tb in tbs.iter() {
+		out.definitions += tx.all_tb_fields(ns, db, &tb.name).await?.len();
+		out.definitions += tx.all_tb_events(ns, db, &tb.name).await?.len();
+		out.definitions += tx.all_tb_views(ns, db, &tb.name).await?.len();
+		let ixs = tx.all_tb_indexes(ns, db, &tb.name).await?;
+		out.definitions += ixs.len();
+		if !indexes {
+			continue;
+		}
+		// The keys of each index, from the start of the index
+		for ix in ixs.iter() {
+			let beg = crate::key::index::all::new(ns, db, &tb.name, &ix.name).encode()?;
+			let mut end = beg.clone();
+			end.push(0xff);
+			let mut next_page = Some(ScanPage {
+				range: beg..end,
+				limit: Limit::Limited(*PRELOAD_INDEX_LIMIT),
+			});
+			while let Some(page) = next_page {
+				let res = tx.scan_paged(page, NORMAL_FETCH_SIZE).await?;
+				next_page = res.next_page;
+				if res.values.is_empty() {
+					break;
+				}
+				out.index_keys += res.values.len();
+			}
+		}
+	}
+	Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::dbs::Session;
+
+	#[tokio::test]
+	async fn definitions_and_indexes_are_preloaded() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let ses = Session::owner().with_ns("test").with_db("test");
+		let sql = "
+			DEFINE TABLE user SCHEMAFULL;
+			DEFINE FIELD name ON user TYPE string;
+			DEFINE INDEX user_name ON user FIELDS name;
+			CREATE user:1 SET name = 'Tobie';
+			CREATE user:2 SET name = 'Jaime';
+		";
+		for res in ds.execute(sql, &ses, None).await.unwrap() {
+			res.result.unwrap();
+		}
+		let res = ds.preload("test", "test", false).await.unwrap();
+		assert_eq!(res.tables, 1);
+		assert_eq!(res.definitions, 3);
+		assert_eq!(res.index_keys, 0);
+		let res = ds.preload("test", "test", true).await.unwrap();
+		assert_eq!(res.index_keys, 2);
+	}
+}
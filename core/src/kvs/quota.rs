@@ -0,0 +1,169 @@
+#![cfg(any(feature = "kv-rocksdb", feature = "kv-speedb"))]
+
+//! Disk usage quotas for the storage engines which persist data to a local directory.
+//!
+//! The size of the datastore directory is measured periodically in the background.
+//! When the soft limit is exceeded a warning is logged, and when the hard limit is
+//! exceeded any writes are rejected, while reads and deletes are still allowed, so
+//! that data can be removed before the volume fills up and the datastore is corrupted.
+use crate::cnf::{STORAGE_HARD_LIMIT, STORAGE_SOFT_LIMIT, STORAGE_USAGE_INTERVAL};
+use crate::err::Error;
+use crate::sql::{Object, Value};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+
+pub(crate) struct Quota {
+	// The directory in which the datastore is stored
+	path: PathBuf,
+	// The disk usage above which a warning is logged, or 0 for no limit
+	soft: u64,
+	// The disk usage above which writes are rejected, or 0 for no limit
+	hard: u64,
+	// The last measured disk usage
+	usage: AtomicU64,
+	// Whether the soft limit was exceeded when last measured
+	exceeded: AtomicBool,
+}
+
+impl Quota {
+	/// Start measuring the disk usage of the datastore stored at the specified path
+	pub(crate) fn monitor(path: &str) -> Arc<Quota> {
+		let quota = Arc::new(Self::new(path, *STORAGE_SOFT_LIMIT, *STORAGE_HARD_LIMIT));
+		// Measure the disk usage before any writes are made
+		quota.measure();
+		// Measure the disk usage periodically until the datastore is dropped
+		let weak = Arc::downgrade(&quota);
+		let res =
+			std::thread::Builder::new().name("surrealdb-disk-usage".to_owned()).spawn(move || {
+				loop {
+					std::thread::sleep(STORAGE_USAGE_INTERVAL);
+					match weak.upgrade() {
+						Some(quota) => quota.measure(),
+						None => break,
+					}
+				}
+			});
+		if let Err(e) = res {
+			warn!("Unable to monitor the disk usage of the datastore: {e}");
+		}
+		quota
+	}
+
+	fn new(path: &str, soft: u64, hard: u64) -> Quota {
+		Quota {
+			path: PathBuf::from(path),
+			soft,
+			hard,
+			usage: AtomicU64::new(0),
+			exceeded: AtomicBool::new(false),
+		}
+	}
+
+	/// Measure the current disk usage of the datastore
+	fn measure(&self) {
+		let usage = match size(&self.path) {
+			Ok(v) => v,
+			Err(e) => {
+				warn!("Unable to measure the disk usage of '{}': {e}", self.path.display());
+				return;
+			}
+		};
+		self.record(usage);
+	}
+
+	/// Store the measured disk usage, and warn when the soft limit is first exceeded
+	fn record(&self, usage: u64) {
+		self.usage.store(usage, Ordering::Relaxed);
+		let exceeded = self.soft > 0 && usage >= self.soft;
+		if self.exceeded.swap(exceeded, Ordering::Relaxed) != exceeded {
+			match exceeded {
+				true => warn!(
+					"The datastore at '{}' is using {usage} bytes of disk space, exceeding the soft limit of {} bytes",
+					self.path.display(),
+					self.soft
+				),
+				false => info!(
+					"The datastore at '{}' is using {usage} bytes of disk space, within the soft limit of {} bytes",
+					self.path.display(),
+					self.soft
+				),
+			}
+		}
+	}
+
+	/// Check that data can be written without exceeding the hard limit
+	pub(crate) fn check(&self) -> Result<(), Error> {
+		let usage = self.usage.load(Ordering::Relaxed);
+		if self.hard > 0 && usage >= self.hard {
+			return Err(Error::DiskQuotaExceeded {
+				usage,
+				limit: self.hard,
+			});
+		}
+		Ok(())
+	}
+
+	/// Output the disk usage of the datastore
+	pub(crate) fn usage(&self) -> Value {
+		let limit = |v: u64| match v {
+			0 => Value::None,
+			v => Value::from(v),
+		};
+		Value::from(Object::from(map! {
+			"path" => Value::from(self.path.display().to_string()),
+			"usage" => Value::from(self.usage.load(Ordering::Relaxed)),
+			"soft_limit" => limit(self.soft),
+			"hard_limit" => limit(self.hard),
+			"exceeded" => Value::from(self.exceeded.load(Ordering::Relaxed)),
+		}))
+	}
+}
+
+/// Calculate the total size of the files within a directory
+fn size(path: &Path) -> io::Result<u64> {
+	let mut total = 0;
+	for entry in std::fs::read_dir(path)? {
+		let entry = entry?;
+		let meta = entry.metadata()?;
+		if meta.is_dir() {
+			total += size(&entry.path())?;
+		} else {
+			total += meta.len();
+		}
+	}
+	Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn writes_are_rejected_above_the_hard_limit() {
+		let quota = Quota::new("test", 100, 200);
+		quota.record(150);
+		assert!(quota.check().is_ok());
+		assert!(quota.exceeded.load(Ordering::Relaxed));
+		quota.record(250);
+		assert!(matches!(
+			quota.check(),
+			Err(Error::DiskQuotaExceeded {
+				usage: 250,
+				limit: 200
+			})
+		));
+		quota.record(50);
+		assert!(quota.check().is_ok());
+		assert!(!quota.exceeded.load(Ordering::Relaxed));
+	}
+
+	#[test]
+	fn no_limits_are_applied_by_default() {
+		let quota = Quota::new("test", 0, 0);
+		quota.record(u64::MAX);
+		assert!(quota.check().is_ok());
+		assert!(!quota.exceeded.load(Ordering::Relaxed));
+	}
+}
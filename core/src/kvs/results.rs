@@ -0,0 +1,116 @@
+//! The results of `SELECT ... CACHE` statements, which are kept in memory.
+//!
+//! A result is stored under the text of the statement, along with the values of the
+//! parameters which it uses, the namespace and database, and the authenticated user.
+//! Each result remembers the tables which the statement selects from, and is removed
+//! when a transaction which wrote to one of these tables finishes, or when its time to
+//! live expires. Every transaction which writes to a table moves the cache on to a new
+//! epoch, and a transaction only stores a result if none of its tables were written to
+//! since the transaction began, as the result could otherwise be out of date. Results
+//! are neither stored nor used by a transaction which has itself written to one of the
+//! tables, as the result would then include, or be missing, uncommitted changes.
+//!
+//! Results are only removed by the transactions which run on this node. When several
+//! nodes share a distributed datastore, writes made through the other nodes are not
+//! seen, so a cached result can be out of date for up to its time to live.
+use crate::cnf::SELECT_CACHE_SIZE;
+use crate::kvs::stats::{Pending, Table};
+use crate::sql::Value;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+struct Entry {
+	// The tables which the statement selects from
+	tables: Vec<Table>,
+	// The time at which the result expires
+	expiry: Instant,
+	// The result of the statement
+	value: Value,
+}
+
+#[derive(Default)]
+struct Inner {
+	// The current epoch, which moves on whenever a table is written to
+	epoch: u64,
+	// The epoch in which each table was last written to
+	written: HashMap<Table, u64>,
+	// The stored results
+	entries: HashMap<String, Entry>,
+}
+
+#[derive(Default)]
+pub(crate) struct ResultCache {
+	inner: Mutex<Inner>,
+}
+
+impl ResultCache {
+	/// The current epoch, which a transaction takes when it begins
+	pub(super) fn epoch(&self) -> u64 {
+		self.inner.lock().unwrap().epoch
+	}
+
+	/// Fetch a stored result, if it has not expired
+	pub(super) fn get(&self, key: &str, pending: &Pending) -> Option<Value> {
+		let mut inner = self.inner.lock().unwrap();
+		let entry = inner.entries.get(key)?;
+		if entry.expiry <= Instant::now() {
+			inner.entries.remove(key);
+			return None;
+		}
+		if pending.written().any(|t| entry.tables.contains(t)) {
+			return None;
+		}
+		Some(entry.value.clone())
+	}
+
+	/// Store a result, unless its tables were written to since the transaction began
+	pub(super) fn insert(
+		&self,
+		key: String,
+		tables: Vec<Table>,
+		ttl: Duration,
+		epoch: u64,
+		pending: &Pending,
+		value: Value,
+	) {
+		if pending.written().any(|t| tables.contains(t)) {
+			return;
+		}
+		let mut inner = self.inner.lock().unwrap();
+		if tables.iter().any(|t| inner.written.get(t).is_some_and(|e| *e > epoch)) {
+			return;
+		}
+		if inner.entries.len() >= *SELECT_CACHE_SIZE && !inner.entries.contains_key(&key) {
+			let now = Instant::now();
+			inner.entries.retain(|_, e| e.expiry > now);
+			if inner.entries.len() >= *SELECT_CACHE_SIZE {
+				return;
+			}
+		}
+		let expiry = Instant::now() + ttl;
+		inner.entries.insert(
+			key,
+			Entry {
+				tables,
+				expiry,
+				value,
+			},
+		);
+	}
+
+	/// Remove the results of the tables which a transaction wrote to
+	pub(super) fn invalidate(&self, pending: &Pending) {
+		let written: Vec<&Table> = pending.written().collect();
+		if written.is_empty() {
+			return;
+		}
+		let mut inner = self.inner.lock().unwrap();
+		inner.epoch += 1;
+		let epoch = inner.epoch;
+		for table in written.iter() {
+			inner.written.insert((*table).clone(), epoch);
+		}
+		inner.entries.retain(|_, e| !e.tables.iter().any(|t| written.contains(&t)));
+	}
+}
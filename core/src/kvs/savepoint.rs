@@ -0,0 +1,97 @@
+//! Savepoints within a transaction.
+//!
+//! A savepoint records the previous value of each key which is written after
+//! it is set, along with the amount of work which the transaction has queued
+//! for when it commits. Rolling back to the savepoint restores those keys, and
+//! discards the work queued since, without cancelling the transaction. Scripts
+//! which run with `OPTION ATOMIC` set a savepoint before each statement, so
+//! that a statement which fails leaves no partial changes behind.
+use std::collections::{HashMap, HashSet};
+
+use crate::cf::writer::ChangeKey;
+use crate::err::Error;
+use crate::kvs::{Key, Transaction, Val};
+
+#[derive(Default)]
+pub(super) struct Savepoint {
+	/// The previous values of the keys written since the savepoint
+	undo: Vec<(Key, Option<Val>)>,
+	/// The keys which have been written since the savepoint
+	seen: HashSet<Key>,
+	/// The number of buffered changefeed mutations of each table
+	changes: HashMap<ChangeKey, usize>,
+	/// The number of queued live query registrations
+	live_queries: usize,
+	/// The number of queued windowed live query events
+	window_events: usize,
+	/// The number of queued compacting live query events
+	compact_events: usize,
+	/// The number of queued counting live query events
+	count_events: usize,
+	/// The number of queued hook publications
+	publications: usize,
+}
+
+impl Transaction {
+	/// Set a savepoint, replacing any previous savepoint of the transaction
+	pub(crate) fn set_savepoint(&mut self) {
+		self.savepoint = Some(Savepoint {
+			changes: self.cf.savepoint(),
+			live_queries: self.prepared_async_events.1.len(),
+			window_events: self.window_events.len(),
+			compact_events: self.compact_events.len(),
+			count_events: self.count_events.len(),
+			publications: self.publications.len(),
+			..Default::default()
+		});
+	}
+
+	/// Keep the changes made since the savepoint, and stop recording them
+	pub(crate) fn release_savepoint(&mut self) {
+		self.savepoint = None;
+	}
+
+	/// Undo the changes made since the savepoint, returning the number of keys restored
+	pub(crate) async fn rollback_to_savepoint(&mut self) -> Result<usize, Error> {
+		let Some(sp) = self.savepoint.take() else {
+			return Err(Error::Unreachable("Transaction::rollback_to_savepoint"));
+		};
+		// Restore the keys in the reverse order they were written
+		let count = sp.undo.len();
+		for (key, val) in sp.undo.into_iter().rev() {
+			match val {
+				Some(val) => self.set(key, val).await?,
+				None => self._del(key).await?,
+			}
+		}
+		// Discard any values read since the savepoint
+		self.cache.clear();
+		self.prefetched.clear();
+		// Discard the work queued since the savepoint
+		self.cf.rollback(&sp.changes);
+		let lqs: Vec<_> = self.consume_pending_live_queries();
+		for lq in lqs.into_iter().take(sp.live_queries) {
+			let _ = self.prepared_async_events.0.try_send(lq);
+		}
+		self.window_events.truncate(sp.window_events);
+		self.compact_events.truncate(sp.compact_events);
+		self.count_events.truncate(sp.count_events);
+		self.publications.truncate(sp.publications);
+		Ok(count)
+	}
+
+	/// Record the value of a key before it is first written after the savepoint
+	pub(super) async fn journal(&mut self, key: &Key) -> Result<(), Error> {
+		match &self.savepoint {
+			Some(sp) if !sp.seen.contains(key) => {
+				let val = self.get(key.clone()).await?;
+				if let Some(sp) = &mut self.savepoint {
+					sp.seen.insert(key.clone());
+					sp.undo.push((key.clone(), val));
+				}
+				Ok(())
+			}
+			_ => Ok(()),
+		}
+	}
+}
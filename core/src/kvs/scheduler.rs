@@ -0,0 +1,397 @@
+//! Admission control for background work.
+//!
+//! Background subsystems, such as index builds and record expiry sweeps,
+//! request admission before writing records to a table. Each table can be
+//! limited to a number of background records per second, with a default
+//! limit which applies to all other tables. While a job is waiting to write
+//! to a table, jobs with a lower priority are held back from that table, so
+//! that more important background work is not starved by less important work.
+//! The configuration can be changed at runtime, and is available, along with
+//! the share of writes performed by background work, using the
+//! `system::background()` function.
+use crate::cnf::BACKGROUND_WRITE_RATE;
+use crate::sql::{Duration, Object, Value};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time;
+use trice::Instant;
+
+/// The interval at which a job which is held back by a higher priority job checks again
+const PRIORITY_INTERVAL: time::Duration = time::Duration::from_millis(10);
+
+/// A kind of background work which writes records
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub(crate) enum Job {
+	/// Building the entries of a newly defined index
+	IndexBuild,
+	/// Deleting records with an expired field
+	RecordExpiry,
+	/// Deleting change feed entries which are older than their retention
+	ChangeFeedCleanup,
+	/// Converting existing records after the type of a field is altered
+	FieldBackfill,
+	/// Writing the results of enrichments back to records
+	RecordEnrichment,
+	/// Writing recomputed values back to records with stale computed fields
+	FieldRecompute,
+}
+
+impl Job {
+	const ALL: [Job; 6] = [
+		Job::IndexBuild,
+		Job::RecordExpiry,
+		Job::ChangeFeedCleanup,
+		Job::FieldBackfill,
+		Job::RecordEnrichment,
+		Job::FieldRecompute,
+	];
+
+	/// Find a kind of background work by its name
+	pub(crate) fn from_name(name: &str) -> Option<Self> {
+		Job::ALL.into_iter().find(|job| job.to_string() == name)
+	}
+
+	fn default_priority(&self) -> Priority {
+		match self {
+			Job::IndexBuild => Priority::High,
+			Job::RecordExpiry => Priority::Low,
+			Job::ChangeFeedCleanup => Priority::Low,
+			Job::FieldBackfill => Priority::Normal,
+			Job::RecordEnrichment => Priority::Normal,
+			Job::FieldRecompute => Priority::Low,
+		}
+	}
+}
+
+impl fmt::Display for Job {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Job::IndexBuild => f.write_str("index"),
+			Job::RecordExpiry => f.write_str("expiry"),
+			Job::ChangeFeedCleanup => f.write_str("changefeed"),
+			Job::FieldBackfill => f.write_str("backfill"),
+			Job::RecordEnrichment => f.write_str("enrichment"),
+			Job::FieldRecompute => f.write_str("computed"),
+		}
+	}
+}
+
+/// The priority with which a kind of background work is admitted
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub(crate) enum Priority {
+	Low = 0,
+	Normal = 1,
+	High = 2,
+}
+
+impl fmt::Display for Priority {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Priority::Low => f.write_str("low"),
+			Priority::Normal => f.write_str("normal"),
+			Priority::High => f.write_str("high"),
+		}
+	}
+}
+
+impl Priority {
+	/// Find a priority by its name
+	pub(crate) fn from_name(name: &str) -> Option<Self> {
+		match name {
+			"low" => Some(Priority::Low),
+			"normal" => Some(Priority::Normal),
+			"high" => Some(Priority::High),
+			_ => None,
+		}
+	}
+}
+
+type Table = (String, String, String);
+
+struct Bucket {
+	// The time from which further records can be admitted
+	next: Instant,
+	// The number of jobs admitted to, or waiting for, this table at each priority
+	waiting: [u64; 3],
+}
+
+#[derive(Default)]
+struct Counter {
+	// The number of records admitted
+	admitted: u64,
+	// The total time spent waiting for admission
+	throttled: time::Duration,
+}
+
+#[derive(Default)]
+struct State {
+	// The records per second limit for specific tables
+	rates: HashMap<Table, u64>,
+	// The configured priority of each kind of job
+	priorities: HashMap<Job, Priority>,
+	// The admission state of each table with active jobs
+	buckets: HashMap<Table, Bucket>,
+	// The admission statistics of each kind of job
+	jobs: HashMap<Job, Counter>,
+}
+
+pub(crate) struct Scheduler {
+	// The records per second limit for tables without a specific limit, or 0 for no limit
+	rate: AtomicU64,
+	// The number of records written by foreground transactions
+	foreground: AtomicU64,
+	// The number of records written by background transactions
+	background: AtomicU64,
+	// The admission configuration and state
+	state: Mutex<State>,
+}
+
+impl Default for Scheduler {
+	fn default() -> Self {
+		Self {
+			rate: AtomicU64::new(*BACKGROUND_WRITE_RATE),
+			foreground: AtomicU64::new(0),
+			background: AtomicU64::new(0),
+			state: Mutex::new(State::default()),
+		}
+	}
+}
+
+/// Marks a job as no longer waiting for a table, even if the admission is cancelled
+struct Guard<'a> {
+	scheduler: &'a Scheduler,
+	table: &'a Table,
+	priority: Priority,
+}
+
+impl Drop for Guard<'_> {
+	fn drop(&mut self) {
+		let mut state = self.scheduler.state.lock().unwrap();
+		if let Some(bucket) = state.buckets.get_mut(self.table) {
+			bucket.waiting[self.priority as usize] -= 1;
+		}
+		// Discard the tables which no longer limit any job
+		let now = Instant::now();
+		state.buckets.retain(|_, b| {
+			b.waiting.iter().any(|w| *w > 0) || !b.next.saturating_duration_since(now).is_zero()
+		});
+	}
+}
+
+impl Scheduler {
+	/// Wait until a job is allowed to write a number of records to a table
+	pub(crate) async fn admit(&self, job: Job, ns: &str, db: &str, tb: &str, records: u64) {
+		let table = (ns.to_owned(), db.to_owned(), tb.to_owned());
+		let started = Instant::now();
+		let priority = {
+			let mut state = self.state.lock().unwrap();
+			let priority = state.priorities.get(&job).copied().unwrap_or(job.default_priority());
+			state
+				.buckets
+				.entry(table.clone())
+				.or_insert_with(|| Bucket {
+					next: started,
+					waiting: [0; 3],
+				})
+				.waiting[priority as usize] += 1;
+			priority
+		};
+		let _guard = Guard {
+			scheduler: self,
+			table: &table,
+			priority,
+		};
+		loop {
+			let (wait, admitted) = {
+				let mut state = self.state.lock().unwrap();
+				let rate = match state.rates.get(&table) {
+					Some(rate) => *rate,
+					None => self.rate.load(Ordering::Relaxed),
+				};
+				let Some(bucket) = state.buckets.get_mut(&table) else {
+					break;
+				};
+				if rate == 0 {
+					// This table is not limited
+					(time::Duration::ZERO, true)
+				} else if bucket.waiting[priority as usize + 1..].iter().any(|w| *w > 0) {
+					// A higher priority job is waiting for this table
+					(PRIORITY_INTERVAL, false)
+				} else {
+					// Reserve the time needed to write these records
+					let now = Instant::now();
+					let wait = bucket.next.saturating_duration_since(now);
+					let cost = time::Duration::from_secs_f64(records as f64 / rate as f64);
+					bucket.next = now + wait + cost;
+					(wait, true)
+				}
+			};
+			if !wait.is_zero() {
+				sleep(wait).await;
+			}
+			if admitted {
+				break;
+			}
+		}
+		// Record the admission
+		let mut state = self.state.lock().unwrap();
+		let counter = state.jobs.entry(job).or_default();
+		counter.admitted += records;
+		counter.throttled += started.elapsed();
+	}
+
+	/// Record the number of records written by a committed transaction
+	pub(super) fn record(&self, background: bool, writes: u64) {
+		match background {
+			true => self.background.fetch_add(writes, Ordering::Relaxed),
+			false => self.foreground.fetch_add(writes, Ordering::Relaxed),
+		};
+	}
+
+	/// Set the records per second limit for tables without a specific limit, or 0 for no limit
+	pub(crate) fn set_rate(&self, rate: u64) {
+		self.rate.store(rate, Ordering::Relaxed);
+	}
+
+	/// Set, or remove, the records per second limit for a specific table
+	pub(crate) fn set_table_rate(&self, ns: &str, db: &str, tb: &str, rate: Option<u64>) {
+		let table = (ns.to_owned(), db.to_owned(), tb.to_owned());
+		let mut state = self.state.lock().unwrap();
+		match rate {
+			Some(rate) => state.rates.insert(table, rate),
+			None => state.rates.remove(&table),
+		};
+	}
+
+	/// Set the priority with which a kind of background work is admitted
+	pub(crate) fn set_priority(&self, job: Job, priority: Priority) {
+		self.state.lock().unwrap().priorities.insert(job, priority);
+	}
+
+	/// Output the configuration, the admission statistics, and the share of background writes
+	pub(crate) fn output(&self) -> Value {
+		let state = self.state.lock().unwrap();
+		let foreground = self.foreground.load(Ordering::Relaxed);
+		let background = self.background.load(Ordering::Relaxed);
+		let share = match foreground + background {
+			0 => 0.0,
+			total => background as f64 / total as f64,
+		};
+		let mut tables: Vec<_> = state.rates.iter().collect();
+		tables.sort();
+		let tables: Vec<Value> = tables
+			.into_iter()
+			.map(|((ns, db, tb), rate)| {
+				Value::from(Object::from(map! {
+					"ns" => Value::from(ns.as_str()),
+					"db" => Value::from(db.as_str()),
+					"tb" => Value::from(tb.as_str()),
+					"rate" => Value::from(*rate),
+				}))
+			})
+			.collect();
+		let jobs: Vec<Value> = Job::ALL
+			.iter()
+			.map(|job| {
+				let priority = state.priorities.get(job).copied().unwrap_or(job.default_priority());
+				let (admitted, throttled) = match state.jobs.get(job) {
+					Some(c) => (c.admitted, c.throttled),
+					None => (0, time::Duration::ZERO),
+				};
+				Value::from(Object::from(map! {
+					"job" => Value::from(job.to_string()),
+					"priority" => Value::from(priority.to_string()),
+					"admitted" => Value::from(admitted),
+					"throttled" => Value::from(Duration::from(throttled)),
+				}))
+			})
+			.collect();
+		Value::from(Object::from(map! {
+			"rate" => Value::from(self.rate.load(Ordering::Relaxed)),
+			"tables" => Value::from(tables),
+			"jobs" => Value::from(jobs),
+			"writes" => Value::from(Object::from(map! {
+				"foreground" => Value::from(foreground),
+				"background" => Value::from(background),
+				"share" => Value::from(share),
+			})),
+		}))
+	}
+}
+
+async fn sleep(dur: time::Duration) {
+	#[cfg(target_arch = "wasm32")]
+	wasmtimer::tokio::sleep(dur).await;
+	#[cfg(not(target_arch = "wasm32"))]
+	tokio::time::sleep(dur).await;
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::sync::Arc;
+
+	#[tokio::test]
+	async fn unlimited_admission() {
+		let scheduler = Scheduler::default();
+		scheduler.set_rate(0);
+		let started = Instant::now();
+		scheduler.admit(Job::RecordExpiry, "test", "test", "person", 1_000_000).await;
+		assert!(started.elapsed() < time::Duration::from_millis(100));
+		assert!(scheduler.state.lock().unwrap().buckets.is_empty());
+	}
+
+	#[tokio::test]
+	async fn table_rate_limits() {
+		let scheduler = Scheduler::default();
+		scheduler.set_rate(0);
+		scheduler.set_table_rate("test", "test", "person", Some(100));
+		let started = Instant::now();
+		// The first batch is admitted immediately, and the second waits for the first
+		scheduler.admit(Job::RecordExpiry, "test", "test", "person", 10).await;
+		scheduler.admit(Job::RecordExpiry, "test", "test", "person", 10).await;
+		assert!(started.elapsed() >= time::Duration::from_millis(100));
+		// Other tables are not limited
+		let started = Instant::now();
+		scheduler.admit(Job::RecordExpiry, "test", "test", "other", 1_000).await;
+		assert!(started.elapsed() < time::Duration::from_millis(100));
+		let out = scheduler.output();
+		assert_eq!(out.pick(&["jobs".into(), 1.into(), "admitted".into()]), Value::from(1020));
+	}
+
+	#[tokio::test]
+	async fn priority_admission() {
+		let scheduler = Arc::new(Scheduler::default());
+		scheduler.set_rate(0);
+		scheduler.set_table_rate("test", "test", "person", Some(10));
+		// Reserve the table for the next second
+		scheduler.admit(Job::RecordExpiry, "test", "test", "person", 10).await;
+		// A high priority job waits for the table
+		let high = {
+			let scheduler = scheduler.clone();
+			tokio::spawn(async move {
+				scheduler.admit(Job::IndexBuild, "test", "test", "person", 1).await;
+				Instant::now()
+			})
+		};
+		tokio::time::sleep(time::Duration::from_millis(50)).await;
+		// A low priority job is held back until the high priority job has been admitted
+		scheduler.admit(Job::RecordExpiry, "test", "test", "person", 1).await;
+		let low = Instant::now();
+		let high = high.await.unwrap();
+		assert!(!low.saturating_duration_since(high).is_zero());
+	}
+
+	#[test]
+	fn write_share() {
+		let scheduler = Scheduler::default();
+		scheduler.record(false, 30);
+		scheduler.record(true, 10);
+		scheduler.set_priority(Job::RecordExpiry, Priority::Normal);
+		let out = scheduler.output();
+		assert_eq!(out.pick(&["writes".into(), "share".into()]), Value::from(0.25));
+		assert_eq!(out.pick(&["jobs".into(), 1.into(), "priority".into()]), Value::from("normal"));
+	}
+}
@@ -4,9 +4,11 @@ mod cnf;
 
 use crate::err::Error;
 use crate::key::error::KeyCategory;
+use crate::kvs::quota::Quota;
 use crate::kvs::Check;
 use crate::kvs::Key;
 use crate::kvs::Val;
+use crate::sql::Value;
 use crate::vs::{try_to_u64_be, u64_to_versionstamp, Versionstamp};
 use futures::lock::Mutex;
 use speedb::{
@@ -21,6 +23,7 @@ use std::sync::Arc;
 #[non_exhaustive]
 pub struct Datastore {
 	db: Pin<Arc<OptimisticTransactionDB>>,
+	quota: Arc<Quota>,
 }
 
 #[non_exhaustive]
@@ -33,6 +36,8 @@ pub struct Transaction {
 	check: Check,
 	/// The underlying datastore transaction
 	inner: Arc<Mutex<Option<speedb::Transaction<'static, OptimisticTransactionDB>>>>,
+	// The disk usage quota of the datastore
+	quota: Arc<Quota>,
 	// The read options containing the Snapshot
 	ro: ReadOptions,
 	// The above, supposedly 'static transaction
@@ -116,6 +121,7 @@ impl Datastore {
 		// Create the datastore
 		Ok(Datastore {
 			db: Arc::pin(OptimisticTransactionDB::open(&opts, path)?),
+			quota: Quota::monitor(path),
 		})
 	}
 	/// Start a new transaction
@@ -154,6 +160,7 @@ impl Datastore {
 			check,
 			write,
 			inner: Arc::new(Mutex::new(Some(inner))),
+			quota: self.quota.clone(),
 			ro,
 			_db: self.db.clone(),
 		})
@@ -161,6 +168,10 @@ impl Datastore {
 }
 
 impl Transaction {
+	/// Output the disk usage of the datastore
+	pub(crate) fn disk_usage(&self) -> Value {
+		self.quota.usage()
+	}
 	/// Behaviour if unclosed
 	pub(crate) fn check_level(&mut self, check: Check) {
 		self.check = check;
@@ -234,6 +245,24 @@ impl Transaction {
 		// Return result
 		Ok(res)
 	}
+	/// Acquire write intent on a key, so that the transaction fails to commit if the key is modified concurrently
+	pub(crate) async fn lock<K>(&mut self, key: K) -> Result<(), Error>
+	where
+		K: Into<Key>,
+	{
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.write {
+			return Err(Error::TxReadonly);
+		}
+		// Track the key for validation when committing
+		self.inner.lock().await.as_ref().unwrap().get_for_update_opt(key.into(), true, &self.ro)?;
+		// Return result
+		Ok(())
+	}
 	/// Obtain a new change timestamp for a key
 	/// which is replaced with the current timestamp when the transaction is committed.
 	/// NOTE: This should be called when composing the change feed entries for this transaction,
@@ -310,6 +339,8 @@ impl Transaction {
 		if !self.write {
 			return Err(Error::TxReadonly);
 		}
+		// Check the disk usage is within the hard limit
+		self.quota.check()?;
 		// Set the key
 		self.inner.lock().await.as_ref().unwrap().put(key.into(), val.into())?;
 		// Return result
@@ -334,6 +365,8 @@ impl Transaction {
 		if !self.write {
 			return Err(Error::TxReadonly);
 		}
+		// Check the disk usage is within the hard limit
+		self.quota.check()?;
 		// Get the transaction
 		let inner = self.inner.lock().await;
 		let inner = inner.as_ref().unwrap();
@@ -362,6 +395,8 @@ impl Transaction {
 		if !self.write {
 			return Err(Error::TxReadonly);
 		}
+		// Check the disk usage is within the hard limit
+		self.quota.check()?;
 		// Get the transaction
 		let inner = self.inner.lock().await;
 		let inner = inner.as_ref().unwrap();
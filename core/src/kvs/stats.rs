@@ -0,0 +1,194 @@
+//! Read and write statistics for each table.
+//!
+//! Every record which a statement reads or writes is counted against its table,
+//! along with the size of the record as it is stored. The counts are gathered in
+//! each transaction, and are added to the statistics of the datastore when the
+//! transaction finishes, so that the shared counters are only locked once for
+//! each table in a transaction. Writes are only counted when the transaction is
+//! committed, whereas reads are counted whether or not it is committed. The
+//! counters are split into shards by table, so that transactions which write
+//! to different tables rarely wait for each other. The statistics are kept in
+//! memory, so they are reset when the datastore is restarted, and they are
+//! available using the `INFO FOR TABLE ... WITH STATS` statement.
+use crate::sql::{Datetime, Object, Value};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
+
+/// The number of shards which the counters are split into
+const SHARDS: usize = 16;
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub(super) struct Table {
+	ns: String,
+	db: String,
+	tb: String,
+}
+
+impl Table {
+	pub(super) fn new(ns: &str, db: &str, tb: &str) -> Self {
+		Table {
+			ns: ns.to_owned(),
+			db: db.to_owned(),
+			tb: tb.to_owned(),
+		}
+	}
+
+	fn is(&self, ns: &str, db: &str, tb: &str) -> bool {
+		self.tb == tb && self.db == db && self.ns == ns
+	}
+}
+
+#[derive(Clone, Debug, Default)]
+struct Counts {
+	// The number of records which were read
+	reads: u64,
+	// The stored size of the records which were read
+	read_bytes: u64,
+	// The number of records which were created
+	creates: u64,
+	// The number of records which were updated
+	updates: u64,
+	// The number of records which were deleted
+	deletes: u64,
+	// The stored size of the records which were created or updated
+	written_bytes: u64,
+	// The time at which a record was last written
+	last_write: Option<Datetime>,
+}
+
+impl Counts {
+	fn add(&mut self, other: Counts) {
+		self.reads += other.reads;
+		self.read_bytes += other.read_bytes;
+		self.creates += other.creates;
+		self.updates += other.updates;
+		self.deletes += other.deletes;
+		self.written_bytes += other.written_bytes;
+		if other.last_write.is_some() {
+			self.last_write = other.last_write;
+		}
+	}
+
+	fn has_writes(&self) -> bool {
+		self.creates + self.updates + self.deletes > 0
+	}
+
+	/// Discard the writes, keeping the reads
+	fn reads_only(self) -> Counts {
+		Counts {
+			reads: self.reads,
+			read_bytes: self.read_bytes,
+			..Default::default()
+		}
+	}
+}
+
+/// The kind of write which was made to a record
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum Write {
+	Create,
+	Update,
+	Delete,
+}
+
+/// The counts gathered in a single transaction
+#[derive(Default)]
+pub(super) struct Pending {
+	// Transactions rarely touch more than a few tables, so these are searched in order
+	tables: Vec<(Table, Counts)>,
+}
+
+impl Pending {
+	fn counts(&mut self, ns: &str, db: &str, tb: &str) -> &mut Counts {
+		let pos = match self.tables.iter().position(|(t, _)| t.is(ns, db, tb)) {
+			Some(pos) => pos,
+			None => {
+				self.tables.push((Table::new(ns, db, tb), Counts::default()));
+				self.tables.len() - 1
+			}
+		};
+		&mut self.tables[pos].1
+	}
+
+	/// Count records which were read
+	pub(super) fn read(&mut self, ns: &str, db: &str, tb: &str, records: usize, bytes: usize) {
+		if records == 0 {
+			return;
+		}
+		let counts = self.counts(ns, db, tb);
+		counts.reads += records as u64;
+		counts.read_bytes += bytes as u64;
+	}
+
+	/// Count a record which was written
+	pub(super) fn write(&mut self, ns: &str, db: &str, tb: &str, kind: Write, bytes: usize) {
+		let counts = self.counts(ns, db, tb);
+		match kind {
+			Write::Create => counts.creates += 1,
+			Write::Update => counts.updates += 1,
+			Write::Delete => counts.deletes += 1,
+		}
+		counts.written_bytes += bytes as u64;
+	}
+
+	/// The tables which records were written to
+	pub(super) fn written(&self) -> impl Iterator<Item = &Table> {
+		self.tables.iter().filter(|(_, c)| c.has_writes()).map(|(t, _)| t)
+	}
+}
+
+#[derive(Default)]
+pub(crate) struct TableStats {
+	// The counts for each table, split into shards by table
+	shards: [Mutex<HashMap<Table, Counts>>; SHARDS],
+}
+
+impl TableStats {
+	fn shard(&self, table: &Table) -> &Mutex<HashMap<Table, Counts>> {
+		let mut hasher = DefaultHasher::new();
+		table.hash(&mut hasher);
+		&self.shards[hasher.finish() as usize % SHARDS]
+	}
+
+	/// Add the counts of a transaction which has finished
+	pub(super) fn record(&self, pending: Pending, committed: bool) {
+		if pending.tables.is_empty() {
+			return;
+		}
+		let now = Datetime::default();
+		for (table, mut counts) in pending.tables {
+			counts = match committed {
+				true if counts.has_writes() => Counts {
+					last_write: Some(now.clone()),
+					..counts
+				},
+				true => counts,
+				false => counts.reads_only(),
+			};
+			self.shard(&table).lock().unwrap().entry(table).or_default().add(counts);
+		}
+	}
+
+	/// Output the statistics of a table
+	pub(super) fn output(&self, ns: &str, db: &str, tb: &str) -> Value {
+		let table = Table::new(ns, db, tb);
+		let counts = self.shard(&table).lock().unwrap().get(&table).cloned().unwrap_or_default();
+		let written = counts.creates + counts.updates;
+		let average = match written {
+			0 => 0,
+			n => counts.written_bytes / n,
+		};
+		Value::from(Object::from(map! {
+			"reads" => Value::from(counts.reads),
+			"read_bytes" => Value::from(counts.read_bytes),
+			"creates" => Value::from(counts.creates),
+			"updates" => Value::from(counts.updates),
+			"deletes" => Value::from(counts.deletes),
+			"written_bytes" => Value::from(counts.written_bytes),
+			"average_size" => Value::from(average),
+			"last_write" => counts.last_write.map(Value::from).unwrap_or_default(),
+		}))
+	}
+}
@@ -168,6 +168,31 @@ impl Transaction {
 		Ok(res)
 	}
 
+	/// Acquires write intent on a key.
+	/// The current value is written back, so that concurrent writes to the key conflict.
+	pub(crate) async fn lock<K>(&mut self, key: K) -> Result<(), Error>
+	where
+		K: Into<Key>,
+	{
+		// If the transaction is already closed, return an error.
+		if self.is_closed() {
+			return Err(Error::TxFinished);
+		}
+
+		// Check to see if transaction is writable
+		if !self.write {
+			return Err(Error::TxReadonly);
+		}
+
+		// Rewrite the key if it exists.
+		let key = key.into();
+		if let Some(val) = self.inner.get(key.as_slice())? {
+			self.inner.set(key.as_slice(), &val)?;
+		}
+
+		Ok(())
+	}
+
 	/// Obtains a new change timestamp for a key.
 	/// This timestamp is replaced with the current timestamp when the transaction is committed.
 	/// This method should be called when composing the change feed entries for this transaction,
@@ -0,0 +1,345 @@
+//! Streaming of the raw keys and values of a datastore, so that a backup can be resumed.
+//!
+//! The keys of the datastore are read in order, in chunks which are all read from a single
+//! read transaction, so that a backup is a consistent snapshot of the datastore even if it
+//! is written to while the backup runs. Each chunk is written as a frame with a checksum:
+//!
+//! ```text
+//! {version}{length}{checksum}{key length}{key}{value length}{value}...
+//! ```
+//!
+//! The lengths are big-endian `u32`s, and the checksum is the CRC32 of the keys and values
+//! of the chunk. The last key of each chunk is its resume token, so a backup which was
+//! interrupted can be continued from the last chunk which was received intact, instead of
+//! being restarted. A resumed backup is read from a new snapshot. Loading a chunk overwrites
+//! the keys which it contains, so a chunk can be loaded more than once, but keys which are
+//! not in the backup are left in place. The nodes, heartbeats, and live queries of the cluster
+//! which wrote the backup are never loaded, as they do not belong to the loading cluster.
+use crate::err::Error;
+use crate::kvs::{Datastore, Key, LockType::*, Transaction, TransactionType::*, Val};
+
+/// The version of the chunk format
+const VERSION: u8 = 1;
+
+/// The size of the header of a chunk
+const HEADER_SIZE: usize = 1 + 4 + 4;
+
+/// The keys and values of a range of the datastore
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct SyncChunk {
+	/// The keys and values, in the order of their keys
+	pub pairs: Vec<(Key, Val)>,
+}
+
+impl SyncChunk {
+	/// The token from which the chunk after this chunk is read
+	pub fn resume_token(&self) -> Option<String> {
+		self.pairs.last().map(|(k, _)| hex::encode(k))
+	}
+
+	/// Encode this chunk as a frame with a checksum
+	pub fn encode(&self) -> Vec<u8> {
+		let mut body = Vec::new();
+		for (k, v) in self.pairs.iter() {
+			body.extend_from_slice(&(k.len() as u32).to_be_bytes());
+			body.extend_from_slice(k);
+			body.extend_from_slice(&(v.len() as u32).to_be_bytes());
+			body.extend_from_slice(v);
+		}
+		let mut out = Vec::with_capacity(HEADER_SIZE + body.len());
+		out.push(VERSION);
+		out.extend_from_slice(&(body.len() as u32).to_be_bytes());
+		out.extend_from_slice(&crc32fast::hash(&body).to_be_bytes());
+		out.extend_from_slice(&body);
+		out
+	}
+
+	/// Decode the chunk at the start of the bytes, returning the chunk and the
+	/// number of bytes which it was encoded in, or nothing if it is incomplete
+	pub fn decode(bytes: &[u8]) -> Result<Option<(Self, usize)>, Error> {
+		if bytes.len() < HEADER_SIZE {
+			return Ok(None);
+		}
+		if bytes[0] != VERSION {
+			return Err(invalid(format!("the chunk format version {} is not supported", bytes[0])));
+		}
+		let len = u32::from_be_bytes([bytes[1], bytes[2], bytes[3], bytes[4]]) as usize;
+		let Some(body) = bytes.get(HEADER_SIZE..HEADER_SIZE + len) else {
+			return Ok(None);
+		};
+		if bytes[5..HEADER_SIZE] != crc32fast::hash(body).to_be_bytes() {
+			return Err(invalid("the checksum does not match"));
+		}
+		let mut pairs = Vec::new();
+		let mut rest = body;
+		while !rest.is_empty() {
+			let k = take(&mut rest)?;
+			let v = take(&mut rest)?;
+			pairs.push((k, v));
+		}
+		Ok(Some((
+			Self {
+				pairs,
+			},
+			HEADER_SIZE + len,
+		)))
+	}
+}
+
+/// Decodes the chunks of a backup as its bytes are received
+#[derive(Debug, Default)]
+pub struct SyncDecoder {
+	// The received bytes which do not yet form a complete chunk
+	buffer: Vec<u8>,
+}
+
+impl SyncDecoder {
+	/// Add the next bytes which have been received
+	pub fn push(&mut self, bytes: &[u8]) {
+		self.buffer.extend_from_slice(bytes);
+	}
+
+	/// Take the next complete chunk which has been received
+	pub fn next_chunk(&mut self) -> Result<Option<SyncChunk>, Error> {
+		match SyncChunk::decode(&self.buffer)? {
+			Some((chunk, len)) => {
+				self.buffer.drain(..len);
+				Ok(Some(chunk))
+			}
+			None => Ok(None),
+		}
+	}
+
+	/// The number of bytes which do not yet form a complete chunk
+	pub fn remaining(&self) -> usize {
+		self.buffer.len()
+	}
+}
+
+/// Reads the chunks of a backup from a single snapshot of the datastore
+pub struct SyncReader {
+	// The read transaction which every chunk is read from
+	tx: Transaction,
+	// The key from which the next chunk is read, if any keys remain
+	next: Option<Key>,
+}
+
+impl SyncReader {
+	/// Read the next chunk of at most `limit` keys. Nothing is returned once
+	/// every key has been read, and the transaction is then closed.
+	pub async fn next_chunk(&mut self, limit: u32) -> Result<Option<SyncChunk>, Error> {
+		let Some(beg) = self.next.take() else {
+			return Ok(None);
+		};
+		let pairs = match self.tx.scan(beg..vec![0xff], limit).await {
+			Ok(v) => v,
+			Err(e) => {
+				self.tx.cancel().await?;
+				return Err(e);
+			}
+		};
+		match pairs.last() {
+			Some((k, _)) => {
+				self.next = Some([k.as_slice(), &[0x00]].concat());
+				Ok(Some(SyncChunk {
+					pairs,
+				}))
+			}
+			None => {
+				self.tx.cancel().await?;
+				Ok(None)
+			}
+		}
+	}
+
+	/// Stop reading, closing the transaction
+	pub async fn cancel(mut self) -> Result<(), Error> {
+		match self.next {
+			Some(_) => self.tx.cancel().await,
+			None => Ok(()),
+		}
+	}
+}
+
+impl Datastore {
+	/// Start reading the chunks which follow the resume token, or every
+	/// chunk when no token is specified, from a single snapshot.
+	pub async fn sync_reader(&self, resume: Option<&str>) -> Result<SyncReader, Error> {
+		// Start after the last key of the previous chunk
+		let next = match resume {
+			Some(token) => {
+				let mut key =
+					hex::decode(token).map_err(|_| invalid("the resume token is invalid"))?;
+				key.push(0x00);
+				key
+			}
+			None => vec![0x00],
+		};
+		Ok(SyncReader {
+			tx: self.transaction(Read, Optimistic).await?,
+			next: Some(next),
+		})
+	}
+
+	/// Write the keys and values of a chunk in a single transaction,
+	/// skipping the keys which describe the cluster which wrote it
+	pub async fn sync_load(&self, chunk: SyncChunk) -> Result<(), Error> {
+		let mut tx = self.transaction(Write, Optimistic).await?;
+		for (k, v) in chunk.pairs {
+			if is_cluster_key(&k) {
+				continue;
+			}
+			if let Err(e) = tx.set(k, v).await {
+				tx.cancel().await?;
+				return Err(e);
+			}
+		}
+		tx.commit().await
+	}
+}
+
+/// Check whether a key stores a node, a node heartbeat, or a live query,
+/// which are only valid in the cluster which stored them
+fn is_cluster_key(key: &[u8]) -> bool {
+	match key {
+		// The nodes, their heartbeats, and the live queries registered on each node
+		[b'/', b'!', b'n', b'd', ..] | [b'/', b'!', b'h', b'b', ..] | [b'/', b'$', ..] => true,
+		// The live queries of a table follow the namespace, database, and table names
+		[b'/', rest @ ..] => {
+			let mut rest = rest;
+			for _ in 0..3 {
+				let Some((b'*', tail)) = rest.split_first() else {
+					return false;
+				};
+				let Some(i) = tail.iter().position(|&b| b == 0) else {
+					return false;
+				};
+				rest = &tail[i + 1..];
+			}
+			rest.starts_with(b"!lq")
+		}
+		_ => false,
+	}
+}
+
+fn invalid(reason: impl Into<String>) -> Error {
+	Error::InvalidSyncChunk {
+		reason: reason.into(),
+	}
+}
+
+/// Take a length-prefixed field from the body of a chunk
+fn take(rest: &mut &[u8]) -> Result<Vec<u8>, Error> {
+	let truncated = || invalid("the chunk is truncated");
+	let (len, tail) = rest.split_first_chunk::<4>().ok_or_else(truncated)?;
+	let len = u32::from_be_bytes(*len) as usize;
+	let field = tail.get(..len).ok_or_else(truncated)?;
+	*rest = &tail[len..];
+	Ok(field.to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn chunk() -> SyncChunk {
+		SyncChunk {
+			pairs: vec![(b"/a".to_vec(), b"1".to_vec()), (b"/b".to_vec(), Vec::new())],
+		}
+	}
+
+	#[test]
+	fn chunks_are_decoded_as_they_are_received() {
+		let bytes = [chunk().encode(), chunk().encode()].concat();
+		let mut decoder = SyncDecoder::default();
+		decoder.push(&bytes[..10]);
+		assert_eq!(decoder.next_chunk().unwrap(), None);
+		decoder.push(&bytes[10..]);
+		assert_eq!(decoder.next_chunk().unwrap(), Some(chunk()));
+		assert_eq!(decoder.next_chunk().unwrap(), Some(chunk()));
+		assert_eq!(decoder.next_chunk().unwrap(), None);
+		assert_eq!(decoder.remaining(), 0);
+		assert_eq!(chunk().resume_token().as_deref(), Some("2f62"));
+	}
+
+	#[test]
+	fn changed_chunks_are_rejected() {
+		let mut bytes = chunk().encode();
+		*bytes.last_mut().unwrap() ^= 1;
+		assert!(SyncChunk::decode(&bytes).is_err());
+		bytes[0] = 0;
+		assert!(SyncChunk::decode(&bytes).is_err());
+	}
+
+	#[tokio::test]
+	async fn chunks_are_resumed_and_loaded() {
+		let src = Datastore::new("memory").await.unwrap();
+		let mut tx = src.transaction(Write, Optimistic).await.unwrap();
+		for i in 0..5u8 {
+			tx.set(vec![b'/', i], vec![i]).await.unwrap();
+		}
+		tx.commit().await.unwrap();
+		let dst = Datastore::new("memory").await.unwrap();
+		let mut reader = src.sync_reader(None).await.unwrap();
+		let chunk = reader.next_chunk(2).await.unwrap().unwrap();
+		let resume = chunk.resume_token();
+		dst.sync_load(chunk).await.unwrap();
+		reader.cancel().await.unwrap();
+		let mut reader = src.sync_reader(resume.as_deref()).await.unwrap();
+		while let Some(chunk) = reader.next_chunk(2).await.unwrap() {
+			assert!(chunk.pairs.len() <= 2);
+			dst.sync_load(chunk).await.unwrap();
+		}
+		let mut tx = src.transaction(Read, Optimistic).await.unwrap();
+		let expected = tx.scan(vec![0x00]..vec![0xff], 100).await.unwrap();
+		tx.cancel().await.unwrap();
+		let mut tx = dst.transaction(Read, Optimistic).await.unwrap();
+		let pairs = tx.scan(vec![0x00]..vec![0xff], 100).await.unwrap();
+		tx.cancel().await.unwrap();
+		assert_eq!(pairs, expected);
+		assert!(pairs.contains(&(vec![b'/', 4], vec![4])));
+	}
+
+	#[tokio::test]
+	async fn chunks_are_read_from_one_snapshot() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let mut tx = ds.transaction(Write, Optimistic).await.unwrap();
+		tx.set(vec![b'/', 0], vec![0]).await.unwrap();
+		tx.set(vec![b'/', 2], vec![2]).await.unwrap();
+		tx.commit().await.unwrap();
+		let mut reader = ds.sync_reader(None).await.unwrap();
+		let chunk = reader.next_chunk(1).await.unwrap().unwrap();
+		assert_eq!(chunk.pairs, vec![(vec![b'/', 0], vec![0])]);
+		// Keys written after the backup started are not read
+		let mut tx = ds.transaction(Write, Optimistic).await.unwrap();
+		tx.set(vec![b'/', 1], vec![1]).await.unwrap();
+		tx.commit().await.unwrap();
+		let chunk = reader.next_chunk(10).await.unwrap().unwrap();
+		assert_eq!(chunk.pairs, vec![(vec![b'/', 2], vec![2])]);
+		assert_eq!(reader.next_chunk(10).await.unwrap(), None);
+	}
+
+	#[tokio::test]
+	async fn cluster_keys_are_not_loaded() {
+		let nd = uuid::Uuid::nil();
+		let lq = uuid::Uuid::max();
+		let keys: Vec<Key> = vec![
+			crate::key::root::nd::Nd::new(nd).into(),
+			crate::key::root::hb::Hb::new(Default::default(), nd).into(),
+			crate::key::node::lq::new(nd, lq, "ns", "db").into(),
+			crate::key::table::lq::new("ns", "db", "tb", lq).into(),
+			crate::key::database::tb::new("ns", "db", "tb").into(),
+		];
+		let ds = Datastore::new("memory").await.unwrap();
+		ds.sync_load(SyncChunk {
+			pairs: keys.iter().map(|k| (k.clone(), Vec::new())).collect(),
+		})
+		.await
+		.unwrap();
+		let mut tx = ds.transaction(Read, Optimistic).await.unwrap();
+		let pairs = tx.scan(vec![0x00]..vec![0xff], 100).await.unwrap();
+		tx.cancel().await.unwrap();
+		assert_eq!(pairs, vec![(keys[4].clone(), Vec::new())]);
+	}
+}
@@ -95,6 +95,10 @@ async fn expired_nodes_get_live_queries_archived() {
 		archived: Some(crate::sql::uuid::Uuid::from(old_node)),
 		session: Some(Value::None),
 		auth: Some(Auth::for_root(Role::Owner)),
+		window: None,
+		when: None,
+		compact: None,
+		count: false,
 	};
 	let ctx = context::Context::background();
 	let (sender, _) = channel::unbounded();
@@ -137,6 +141,78 @@ async fn expired_nodes_get_live_queries_archived() {
 	tx.commit().await.unwrap();
 }
 
+#[test(tokio::test)]
+#[serial]
+async fn restarted_nodes_reclaim_their_live_queries() {
+	let mut stack = reblessive::tree::TreeStack::new();
+	let ctx = context::Context::background();
+	let node_id = Uuid::parse_str("3f1c0a2e-5d57-4a39-9c2b-8f4f51a7e0d2").unwrap();
+	let time = Timestamp {
+		value: 123000,
+	};
+	let fake_clock = FakeClock::new(time);
+	let fake_clock = Arc::new(SizedClock::Fake(fake_clock));
+	let mut test = init(node_id, fake_clock.clone()).await.unwrap();
+	let namespace = "test_namespace";
+	let database = "test_db";
+	let table = "test_table";
+	let options = Options::default()
+		.with_required(
+			node_id,
+			Some(Arc::from(namespace)),
+			Some(Arc::from(database)),
+			Arc::new(Auth::for_root(Role::Owner)),
+		)
+		.with_live(true);
+
+	// Start the node, and set up a live query
+	test.db = test.db.with_node_id(sql::Uuid::from(node_id));
+	test.db.bootstrap().await.unwrap();
+	let tx = Arc::new(Mutex::new(test.db.transaction(Write, Optimistic).await.unwrap()));
+	let live_st = LiveStatement {
+		id: sql::Uuid(Uuid::parse_str("6b0b7bd1-3f2c-4e0d-a0a5-2d1c6c3f9e8a").unwrap()),
+		node: sql::Uuid::from(node_id),
+		expr: Fields(vec![sql::Field::All], false),
+		what: Table(sql::Table::from(table)),
+		cond: None,
+		fetch: None,
+		archived: None,
+		session: Some(Value::None),
+		auth: Some(Auth::for_root(Role::Owner)),
+		window: None,
+		when: None,
+		compact: None,
+		count: false,
+	};
+	stack.enter(|stk| live_st.compute(stk, &ctx, &options, &tx, None)).finish().await.unwrap();
+	tx.lock().await.commit().await.unwrap();
+
+	// Restart the node with the same node id, before its heartbeat has expired
+	let new_time = Timestamp {
+		value: 124000,
+	};
+	set_fake_clock(fake_clock.clone(), new_time).await;
+	test.db.bootstrap().await.unwrap();
+
+	// The live queries of the previous process have been removed
+	let mut tx = test.db.transaction(Write, Optimistic).await.unwrap();
+	let scanned = tx.all_tb_lives(namespace, database, table).await.unwrap();
+	assert_eq!(scanned.len(), 0);
+	let scanned = tx.all_lq(&node_id).await.unwrap();
+	assert_eq!(scanned.len(), 0);
+	// And the node is still registered, with a single heartbeat
+	let scanned = tx.scan_nd(100).await.unwrap();
+	assert_eq!(scanned.len(), 1);
+	assert_eq!(&scanned[0].name, &node_id.to_string());
+	let end_of_time = Timestamp {
+		value: u64::MAX - 1,
+	};
+	let scanned = tx.scan_hb(&end_of_time, 100).await.unwrap();
+	assert_eq!(scanned.len(), 1);
+	assert_eq!(&scanned[0].hb, &new_time);
+	tx.commit().await.unwrap();
+}
+
 #[test(tokio::test)]
 #[serial]
 async fn single_live_queries_are_garbage_collected() {
@@ -181,6 +257,10 @@ async fn single_live_queries_are_garbage_collected() {
 		archived: None,
 		session: Some(Value::None),
 		auth: Some(Auth::for_root(Role::Owner)),
+		window: None,
+		when: None,
+		compact: None,
+		count: false,
 	};
 	stack
 		.enter(|stk| live_st.compute(stk, &ctx, &options, &tx, None))
@@ -199,6 +279,10 @@ async fn single_live_queries_are_garbage_collected() {
 		archived: None,
 		session: Some(Value::None),
 		auth: Some(Auth::for_root(Role::Owner)),
+		window: None,
+		when: None,
+		compact: None,
+		count: false,
 	};
 	stack
 		.enter(|stk| live_st.compute(stk, &ctx, &options, &tx, None))
@@ -271,6 +355,10 @@ async fn bootstrap_does_not_error_on_missing_live_queries() {
 		archived: None,
 		session: Some(Value::None),
 		auth: Some(Auth::for_root(Role::Owner)),
+		window: None,
+		when: None,
+		compact: None,
+		count: false,
 	};
 	stack
 		.enter(|stk| live_st.compute(stk, &ctx, &options, &tx, None))
@@ -0,0 +1,11 @@
+#[tokio::test]
+#[serial]
+async fn conformance() {
+	// Create a new datastore
+	let node_id = Uuid::parse_str("4c1d2e7f-0a9b-4d3c-8e5f-6a7b8c9d0e1f").unwrap();
+	let clock = Arc::new(SizedClock::Fake(FakeClock::new(Timestamp::default())));
+	let (ds, _) = new_ds(node_id, clock).await;
+	// Run every check against the datastore
+	let report = crate::kvs::conformance::run(&ds).await;
+	assert!(report.is_conformant(), "{report}");
+}
@@ -33,3 +33,36 @@ async fn write_scan_hb() {
 	assert_eq!(vals_lim.len(), 2);
 	assert_eq!(vals_no_lim.len(), 2);
 }
+
+#[tokio::test]
+#[serial]
+async fn heartbeat_replaces_previous_heartbeat() {
+	let nd = uuid::Uuid::parse_str("0c5ab2e5-8b0a-4e2b-a1de-2e4e0b3a9c21").unwrap();
+	let other = uuid::Uuid::parse_str("2a7e0a9f-44a5-4b0c-9d49-3b3f0b9e5b6e").unwrap();
+	let clock = Arc::new(SizedClock::Fake(FakeClock::new(Timestamp::from(1000))));
+	let test = init(nd, clock.clone()).await.unwrap();
+
+	// Another node is alive too
+	let mut tx = test.db.transaction(Write, Optimistic).await.unwrap();
+	tx.set_hb(Timestamp::from(500), other).await.unwrap();
+	tx.commit().await.unwrap();
+
+	// Write a heartbeat, and then another one later on
+	test.db.heartbeat().await.unwrap();
+	if let SizedClock::Fake(c) = &*clock {
+		c.set(Timestamp::from(2000)).await;
+	}
+	test.db.heartbeat().await.unwrap();
+
+	// Only the latest heartbeat of this node remains
+	let mut tx = test.db.transaction(Read, Optimistic).await.unwrap();
+	let hbs = tx.scan_hb(&Timestamp::from(u64::MAX - 1), 100).await.unwrap();
+	let nds = tx.scan_nd(100).await.unwrap();
+	tx.cancel().await.unwrap();
+	assert_eq!(hbs.len(), 2);
+	assert_eq!(hbs[0].nd, other);
+	assert_eq!((hbs[1].hb, hbs[1].nd), (Timestamp::from(2000), nd));
+	// The node is registered, even though it was never bootstrapped
+	assert_eq!(nds.len(), 1);
+	assert_eq!(nds[0].name, nd.to_string());
+}
@@ -0,0 +1,39 @@
+#[tokio::test]
+#[serial]
+async fn integrity_check_finds_and_repairs_problems() {
+	let nd = uuid::Uuid::parse_str("5d2b1c36-8f35-4a40-9f4b-3e1c6f0d7a21").unwrap();
+	let unregistered = uuid::Uuid::parse_str("a3f0c2e1-7b6d-4e59-8c14-2d9e0f1b3c47").unwrap();
+	let corrupted = uuid::Uuid::parse_str("c81e4f27-0d3a-4b95-b6e2-9a7f5c1d8e03").unwrap();
+	let lq = uuid::Uuid::parse_str("f4a7d9b2-3c1e-4f68-a05b-7e2c8d6f1a94").unwrap();
+	let clock = Arc::new(SizedClock::Fake(FakeClock::new(Timestamp::from(1000))));
+	let test = init(nd, clock).await.unwrap();
+
+	// A healthy node, along with a heartbeat of an unregistered node, a corrupted
+	// node, and a live query which is not defined on any table
+	let mut tx = test.db.transaction(Write, Optimistic).await.unwrap();
+	tx.set_nd(nd).await.unwrap();
+	tx.set_hb(Timestamp::from(1000), nd).await.unwrap();
+	tx.set_hb(Timestamp::from(1000), unregistered).await.unwrap();
+	tx.set(crate::key::root::nd::Nd::new(corrupted), b"corrupted".to_vec()).await.unwrap();
+	tx.putc_ndlq(nd, lq, "test", "test", "person", None).await.unwrap();
+	tx.commit().await.unwrap();
+
+	// The problems are found, but not repaired
+	let issues = test.db.check_integrity(false).await.unwrap();
+	assert_eq!(issues.len(), 3, "{issues:?}");
+	assert!(issues.iter().all(|i| i.repairable && !i.repaired), "{issues:?}");
+	assert_eq!(test.db.check_integrity(false).await.unwrap().len(), 3);
+
+	// The problems are repaired
+	let issues = test.db.check_integrity(true).await.unwrap();
+	assert_eq!(issues.len(), 3, "{issues:?}");
+	assert!(issues.iter().all(|i| i.repaired), "{issues:?}");
+	assert!(test.db.check_integrity(false).await.unwrap().is_empty());
+
+	// The healthy node remains
+	let mut tx = test.db.transaction(Read, Optimistic).await.unwrap();
+	let nds = tx.scan_nd(100).await.unwrap();
+	tx.cancel().await.unwrap();
+	assert_eq!(nds.len(), 1);
+	assert_eq!(nds[0].name, nd.to_string());
+}
@@ -61,11 +61,14 @@ mod mem {
 	}
 
 	include!("cluster_init.rs");
+	include!("conformance.rs");
 	include!("hb.rs");
 	include!("helper.rs");
+	include!("integrity.rs");
 	include!("lq.rs");
 	include!("nq.rs");
 	include!("raw.rs");
+	include!("scan.rs");
 	include!("snapshot.rs");
 	include!("tb.rs");
 	include!("multireader.rs");
@@ -108,11 +111,14 @@ mod rocksdb {
 	}
 
 	include!("cluster_init.rs");
+	include!("conformance.rs");
 	include!("hb.rs");
 	include!("helper.rs");
+	include!("integrity.rs");
 	include!("lq.rs");
 	include!("nq.rs");
 	include!("raw.rs");
+	include!("scan.rs");
 	include!("snapshot.rs");
 	include!("tb.rs");
 	include!("multireader.rs");
@@ -155,11 +161,14 @@ mod speedb {
 	}
 
 	include!("cluster_init.rs");
+	include!("conformance.rs");
 	include!("hb.rs");
 	include!("helper.rs");
+	include!("integrity.rs");
 	include!("lq.rs");
 	include!("nq.rs");
 	include!("raw.rs");
+	include!("scan.rs");
 	include!("snapshot.rs");
 	include!("tb.rs");
 	include!("multireader.rs");
@@ -203,11 +212,14 @@ mod tikv {
 	}
 
 	include!("cluster_init.rs");
+	include!("conformance.rs");
 	include!("hb.rs");
 	include!("helper.rs");
+	include!("integrity.rs");
 	include!("lq.rs");
 	include!("nq.rs");
 	include!("raw.rs");
+	include!("scan.rs");
 	include!("snapshot.rs");
 	include!("tb.rs");
 	include!("multireader.rs");
@@ -251,11 +263,14 @@ mod fdb {
 	}
 
 	include!("cluster_init.rs");
+	include!("conformance.rs");
 	include!("hb.rs");
 	include!("helper.rs");
+	include!("integrity.rs");
 	include!("lq.rs");
 	include!("nq.rs");
 	include!("raw.rs");
+	include!("scan.rs");
 	include!("snapshot.rs");
 	include!("tb.rs");
 	include!("multireader.rs");
@@ -301,9 +316,12 @@ mod surrealkv {
 	}
 
 	include!("raw.rs");
+	include!("scan.rs");
 	include!("cluster_init.rs");
+	include!("conformance.rs");
 	include!("hb.rs");
 	include!("helper.rs");
+	include!("integrity.rs");
 	include!("lq.rs");
 	include!("nq.rs");
 	include!("snapshot.rs");
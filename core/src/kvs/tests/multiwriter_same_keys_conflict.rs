@@ -20,8 +20,14 @@ async fn multiwriter_same_keys_conflict() {
 	tx3.set("test", "other text 3").await.unwrap();
 	// Cancel both writeable transactions
 	assert!(tx1.commit().await.is_ok());
-	assert!(tx2.commit().await.is_err());
-	assert!(tx3.commit().await.is_err());
+	assert!(matches!(tx2.commit().await, Err(crate::err::Error::TxConflict)));
+	assert!(matches!(tx3.commit().await, Err(crate::err::Error::TxConflict)));
+	// Check that the conflicts were recorded against the key prefix
+	assert_eq!(ds.conflicts().total(), 2);
+	let mut tx = ds.transaction(Read, Optimistic).await.unwrap();
+	let out = tx.hot_keys();
+	assert_eq!(out.pick(&[crate::sql::Part::from("conflicts")]), crate::sql::Value::parse("[2]"));
+	tx.cancel().await.unwrap();
 	// Check that the key was updated ok
 	let mut tx = ds.transaction(Read, Optimistic).await.unwrap();
 	let val = tx.get("test").await.unwrap().unwrap();
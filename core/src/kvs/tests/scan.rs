@@ -0,0 +1,90 @@
+use crate::kvs::Limit;
+
+/// Write the keys `scan000` up to the specified number of keys
+async fn write_scan_keys(ds: &Datastore, count: usize) {
+	let mut tx = ds.transaction(Write, Optimistic).await.unwrap();
+	for i in 0..count {
+		tx.set(format!("scan{i:03}"), i.to_string()).await.unwrap();
+	}
+	tx.commit().await.unwrap();
+}
+
+#[tokio::test]
+#[serial]
+async fn scan_paged_iterates_to_completion() {
+	let node_id = Uuid::parse_str("f4c3b7a1-5a3e-4a8b-9a9b-8d1c2e3f4a5b").unwrap();
+	let clock = Arc::new(SizedClock::Fake(FakeClock::new(Timestamp::default())));
+	let (ds, _) = new_ds(node_id, clock).await;
+	write_scan_keys(&ds, 25).await;
+	// Scan the full range in batches which are smaller than the range
+	let mut tx = ds.transaction(Read, Optimistic).await.unwrap();
+	let mut next_page = Some(ScanPage::from(b"scan".to_vec()..b"scan\xff".to_vec()));
+	let mut pages = 0;
+	let mut keys = vec![];
+	while let Some(page) = next_page {
+		let res = tx.scan_paged(page, 10).await.unwrap();
+		assert!(res.values.len() <= 10);
+		next_page = res.next_page;
+		keys.extend(res.values.into_iter().map(|(k, _)| k));
+		pages += 1;
+	}
+	tx.cancel().await.unwrap();
+	assert_eq!(pages, 3);
+	assert_eq!(keys.len(), 25);
+	assert_eq!(keys.first().unwrap(), b"scan000");
+	assert_eq!(keys.last().unwrap(), b"scan024");
+}
+
+#[tokio::test]
+#[serial]
+async fn scan_paged_respects_limit() {
+	let node_id = Uuid::parse_str("0b7f3c2e-8a4d-4e6f-9c1b-2d3e4f5a6b7c").unwrap();
+	let clock = Arc::new(SizedClock::Fake(FakeClock::new(Timestamp::default())));
+	let (ds, _) = new_ds(node_id, clock).await;
+	write_scan_keys(&ds, 25).await;
+	// Scan a limited number of keys, across several batches
+	let mut tx = ds.transaction(Read, Optimistic).await.unwrap();
+	let mut next_page = Some(ScanPage {
+		range: b"scan".to_vec()..b"scan\xff".to_vec(),
+		limit: Limit::Limited(15),
+	});
+	let mut keys = vec![];
+	while let Some(page) = next_page {
+		let res = tx.scan_paged(page, 10).await.unwrap();
+		next_page = res.next_page;
+		keys.extend(res.values.into_iter().map(|(k, _)| k));
+	}
+	assert_eq!(keys.len(), 15);
+	assert_eq!(keys.last().unwrap(), b"scan014");
+	// The superjacent methods also respect the limit
+	let res = tx.getr(b"scan".to_vec()..b"scan\xff".to_vec(), 20).await.unwrap();
+	assert_eq!(res.len(), 20);
+	let res = tx.getp(b"scan".to_vec(), u32::MAX).await.unwrap();
+	assert_eq!(res.len(), 25);
+	tx.cancel().await.unwrap();
+}
+
+#[tokio::test]
+#[serial]
+async fn delr_deletes_more_than_batch_size() {
+	let node_id = Uuid::parse_str("6d5e4f3a-2b1c-4d0e-8f9a-1b2c3d4e5f6a").unwrap();
+	let clock = Arc::new(SizedClock::Fake(FakeClock::new(Timestamp::default())));
+	let (ds, _) = new_ds(node_id, clock).await;
+	write_scan_keys(&ds, 25).await;
+	// Delete a range which is larger than the batch size
+	let mut tx = ds.transaction(Write, Optimistic).await.unwrap();
+	tx.delr(b"scan010".to_vec()..b"scan\xff".to_vec(), 4).await.unwrap();
+	tx.commit().await.unwrap();
+	let mut tx = ds.transaction(Read, Optimistic).await.unwrap();
+	let res = tx.getp(b"scan".to_vec(), u32::MAX).await.unwrap();
+	assert_eq!(res.len(), 10);
+	tx.cancel().await.unwrap();
+	// Delete a prefix which is larger than the batch size
+	let mut tx = ds.transaction(Write, Optimistic).await.unwrap();
+	tx.delp(b"scan".to_vec(), 3).await.unwrap();
+	tx.commit().await.unwrap();
+	let mut tx = ds.transaction(Read, Optimistic).await.unwrap();
+	let res = tx.getp(b"scan".to_vec(), u32::MAX).await.unwrap();
+	assert!(res.is_empty());
+	tx.cancel().await.unwrap();
+}
@@ -2,7 +2,7 @@ use crate::key::database::tb;
 use crate::key::database::tb::Tb;
 use crate::kvs::ScanPage;
 use crate::sql::statements::DefineTableStatement;
-use crate::sql::TableType;
+use crate::sql::{TableType, UnknownFields};
 
 #[tokio::test]
 #[serial]
@@ -73,6 +73,10 @@ async fn table_definitions_can_be_deleted() {
 		comment: None,
 		if_not_exists: false,
 		kind: TableType::Any,
+		audit: false,
+		unknown: UnknownFields::Drop,
+		labels: None,
+		partition: None,
 	};
 	tx.set(&key, &value).await.unwrap();
 
@@ -29,6 +29,10 @@ async fn write_scan_tblq() {
 			archived: None,
 			session: Some(Value::None),
 			auth: None,
+			window: None,
+			when: None,
+			compact: None,
+			count: false,
 		};
 		tx.putc_tblq(ns, db, tb, live_stm, None).await.unwrap();
 		tx.commit().await.unwrap();
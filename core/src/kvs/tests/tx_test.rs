@@ -26,6 +26,10 @@ async fn live_queries_sent_to_tx_are_received() {
 			archived: None,
 			session: Some(Value::None),
 			auth: None,
+			window: None,
+			when: None,
+			compact: None,
+			count: false,
 		},
 	};
 	tx.pre_commit_register_async_event(TrackedResult::LiveQuery(lq_entry.clone())).unwrap();
@@ -140,3 +144,24 @@ async fn set_versionstamp_is_incremental() {
 	assert_eq!(found[0].0, expected_keys[0], "key was {}", sprint_key(&found[0].0));
 	assert_eq!(found[1].0, expected_keys[1], "key was {}", sprint_key(&found[1].0));
 }
+
+#[tokio::test]
+#[serial]
+async fn isolation_levels_are_checked() {
+	let node_id = uuid::uuid!("5b3c0d41-0fd7-4e25-9d21-3f25f5a2a9b4");
+	let clock = Arc::new(SizedClock::Fake(FakeClock::new(Timestamp::default())));
+	let test = init(node_id, clock).await.unwrap();
+	let mut tx = test.db.transaction(Write, Optimistic).await.unwrap();
+
+	// Every storage engine provides at least snapshot isolation
+	tx.set_isolation(sql::Isolation::ReadCommitted).await.unwrap();
+	tx.set_isolation(sql::Isolation::Snapshot).await.unwrap();
+
+	// Only some storage engines are serializable
+	let res = tx.set_isolation(sql::Isolation::Serializable).await;
+	match test.kvs {
+		Kvs::Mem | Kvs::Fdb => assert!(res.is_ok(), "{res:?}"),
+		_ => assert!(matches!(res, Err(Error::TxIsolationUnsupported { .. })), "{res:?}"),
+	}
+	tx.cancel().await.unwrap();
+}
@@ -2,10 +2,12 @@
 
 use crate::err::Error;
 use crate::key::error::KeyCategory;
+use crate::kvs::codec::Codec;
 use crate::kvs::Check;
 use crate::kvs::Key;
 use crate::kvs::Val;
 use crate::vs::{try_to_u64_be, u64_to_versionstamp, Versionstamp};
+use std::collections::HashMap;
 use std::ops::Range;
 use tikv::CheckLevel;
 use tikv::TimestampExt;
@@ -14,6 +16,8 @@ use tikv::TransactionOptions;
 #[non_exhaustive]
 pub struct Datastore {
 	db: tikv::TransactionClient,
+	// How values are compressed and checksummed
+	codec: Codec,
 }
 
 #[non_exhaustive]
@@ -24,6 +28,8 @@ pub struct Transaction {
 	write: bool,
 	/// Should we check unhandled transactions?
 	check: Check,
+	/// How values are compressed and checksummed
+	codec: Codec,
 	/// The underlying datastore transaction
 	inner: tikv::Transaction,
 }
@@ -64,6 +70,7 @@ impl Datastore {
 		match tikv::TransactionClient::new(vec![path]).await {
 			Ok(db) => Ok(Datastore {
 				db,
+				codec: Codec::from_env(),
 			}),
 			Err(e) => Err(Error::Ds(e.to_string())),
 		}
@@ -98,11 +105,37 @@ impl Datastore {
 				done: false,
 				check,
 				write,
+				codec: self.codec,
 				inner,
 			}),
 			Err(e) => Err(Error::Tx(e.to_string())),
 		}
 	}
+	/// Fetch the current timestamp, which later reads can be pinned to
+	pub(crate) async fn read_version(&self) -> Result<u64, Error> {
+		Ok(self.db.current_timestamp().await?.version())
+	}
+	/// Scan a range of keys, as of a timestamp fetched with `read_version`.
+	/// This fails once the timestamp is older than the TiKV GC safe point.
+	pub(crate) async fn scan_at(
+		&self,
+		version: u64,
+		rng: Range<Key>,
+		limit: u32,
+	) -> Result<Vec<(Key, Val)>, Error> {
+		// Create a read-only snapshot at the specified timestamp
+		let opt = TransactionOptions::new_optimistic().read_only().drop_check(CheckLevel::None);
+		let mut snapshot = self.db.snapshot(tikv::Timestamp::from_version(version), opt);
+		// Scan the keys
+		let res = snapshot.scan(rng, limit).await?;
+		// Decode the values
+		res.map(|kv| {
+			let key = Key::from(kv.0);
+			let val = self.codec.decode(&key, kv.1)?;
+			Ok((key, val))
+		})
+		.collect()
+	}
 }
 
 impl Transaction {
@@ -245,9 +278,45 @@ impl Transaction {
 			return Err(Error::TxFinished);
 		}
 		// Get the key
-		let res = self.inner.get(key.into()).await?;
+		let key = key.into();
+		let res = self.inner.get(key.clone()).await?;
+		// Decode the value
+		res.map(|v| self.codec.decode(&key, v)).transpose()
+	}
+	/// Fetch many keys from the database in a single request
+	pub(crate) async fn getm(&mut self, keys: Vec<Key>) -> Result<Vec<Option<Val>>, Error> {
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Get the keys
+		let mut res: HashMap<Key, Val> =
+			self.inner.batch_get(keys.clone()).await?.map(|kv| (Key::from(kv.0), kv.1)).collect();
+		// Decode the values, in the order of the keys
+		keys.into_iter()
+			.map(|key| match res.remove(&key) {
+				Some(v) => self.codec.decode(&key, v).map(Some),
+				None => Ok(None),
+			})
+			.collect()
+	}
+	/// Acquire write intent on a key, locking it in pessimistic transactions
+	pub(crate) async fn lock<K>(&mut self, key: K) -> Result<(), Error>
+	where
+		K: Into<Key>,
+	{
+		// Check to see if transaction is closed
+		if self.done {
+			return Err(Error::TxFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.write {
+			return Err(Error::TxReadonly);
+		}
+		// Lock the key
+		self.inner.lock_keys(vec![key.into()]).await?;
 		// Return result
-		Ok(res)
+		Ok(())
 	}
 	/// Insert or update a key in the database
 	pub(crate) async fn set<K, V>(&mut self, key: K, val: V) -> Result<(), Error>
@@ -264,7 +333,7 @@ impl Transaction {
 			return Err(Error::TxReadonly);
 		}
 		// Set the key
-		self.inner.put(key.into(), val.into()).await?;
+		self.inner.put(key.into(), self.codec.encode(val.into())).await?;
 		// Return result
 		Ok(())
 	}
@@ -290,7 +359,7 @@ impl Transaction {
 		// Get the key
 		let key = key.into();
 		// Get the val
-		let val = val.into();
+		let val = self.codec.encode(val.into());
 		// Set the key if empty
 		match self.inner.key_exists(key.clone()).await? {
 			false => self.inner.put(key, val).await?,
@@ -316,11 +385,14 @@ impl Transaction {
 		// Get the key
 		let key = key.into();
 		// Get the val
-		let val = val.into();
+		let val = self.codec.encode(val.into());
 		// Get the check
 		let chk = chk.map(Into::into);
-		// Delete the key
-		match (self.inner.get(key.clone()).await?, chk) {
+		// Get the current value
+		let res = self.inner.get(key.clone()).await?;
+		let res = res.map(|v| self.codec.decode(&key, v)).transpose()?;
+		// Set the key
+		match (res, chk) {
 			(Some(v), Some(w)) if v == w => self.inner.put(key, val).await?,
 			(None, None) => self.inner.put(key, val).await?,
 			_ => return Err(Error::TxConditionNotMet),
@@ -364,8 +436,11 @@ impl Transaction {
 		let key = key.into();
 		// Get the check
 		let chk = chk.map(Into::into);
+		// Get the current value
+		let res = self.inner.get(key.clone()).await?;
+		let res = res.map(|v| self.codec.decode(&key, v)).transpose()?;
 		// Delete the key
-		match (self.inner.get(key.clone()).await?, chk) {
+		match (res, chk) {
 			(Some(v), Some(w)) if v == w => self.inner.delete(key).await?,
 			(None, None) => self.inner.delete(key).await?,
 			_ => return Err(Error::TxConditionNotMet),
@@ -393,35 +468,12 @@ impl Transaction {
 		};
 		// Scan the keys
 		let res = self.inner.scan(rng, limit).await?;
-		let res = res.map(|kv| (Key::from(kv.0), kv.1)).collect();
-		// Return result
-		Ok(res)
-	}
-	/// Delete a range of keys from the databases
-	pub(crate) async fn delr<K>(&mut self, rng: Range<K>, limit: u32) -> Result<(), Error>
-	where
-		K: Into<Key>,
-	{
-		// Check to see if transaction is closed
-		if self.done {
-			return Err(Error::TxFinished);
-		}
-		// Check to see if transaction is writable
-		if !self.write {
-			return Err(Error::TxReadonly);
-		}
-		// Convert the range to bytes
-		let rng: Range<Key> = Range {
-			start: rng.start.into(),
-			end: rng.end.into(),
-		};
-		// Scan the keys
-		let res = self.inner.scan_keys(rng, limit).await?;
-		// Delete all the keys
-		for key in res {
-			self.inner.delete(key).await?;
-		}
-		// Return result
-		Ok(())
+		// Decode the values
+		res.map(|kv| {
+			let key = Key::from(kv.0);
+			let val = self.codec.decode(&key, kv.1)?;
+			Ok((key, val))
+		})
+		.collect()
 	}
 }
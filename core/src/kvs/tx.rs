@@ -1,8 +1,10 @@
 use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::fmt::Debug;
 use std::ops::Range;
 use std::sync::Arc;
+use std::time::Duration;
 
 use channel::{Receiver, Sender};
 use futures::lock::Mutex;
@@ -10,14 +12,19 @@ use uuid::Uuid;
 
 use sql::permission::Permissions;
 use sql::statements::DefineAnalyzerStatement;
+use sql::statements::DefineCheckStatement;
 use sql::statements::DefineDatabaseStatement;
+use sql::statements::DefineDictionaryStatement;
+use sql::statements::DefineEnrichmentStatement;
 use sql::statements::DefineEventStatement;
 use sql::statements::DefineFieldStatement;
 use sql::statements::DefineFunctionStatement;
+use sql::statements::DefineHookStatement;
 use sql::statements::DefineIndexStatement;
 use sql::statements::DefineModelStatement;
 use sql::statements::DefineNamespaceStatement;
 use sql::statements::DefineParamStatement;
+use sql::statements::DefineRoleStatement;
 use sql::statements::DefineScopeStatement;
 use sql::statements::DefineTableStatement;
 use sql::statements::DefineTokenStatement;
@@ -25,24 +32,45 @@ use sql::statements::DefineUserStatement;
 use sql::statements::LiveStatement;
 
 use crate::cf;
+use crate::cnf::NORMAL_FETCH_SIZE;
 use crate::dbs::node::ClusterMembership;
 use crate::dbs::node::Timestamp;
+use crate::dbs::Publication;
 use crate::err::Error;
 use crate::idg::u32::U32;
 use crate::key::debug::sprint_key;
 use crate::key::error::KeyCategory;
 use crate::key::key_req::KeyRequirements;
+use crate::kvs::admission::Admission;
+use crate::kvs::advisor::QueryAdvisor;
 use crate::kvs::cache::Cache;
 use crate::kvs::cache::Entry;
 use crate::kvs::clock::SizedClock;
+use crate::kvs::conflicts::{self, ConflictStats};
+use crate::kvs::history;
+use crate::kvs::lq_compact::CompactEvent;
+use crate::kvs::lq_count::CountEvent;
 use crate::kvs::lq_structs::{LqValue, TrackedResult};
+use crate::kvs::lq_window::WindowEvent;
+use crate::kvs::metrics::{Metrics, Tracked};
+use crate::kvs::ops::{Op, OperationCounter};
+use crate::kvs::results::ResultCache;
+use crate::kvs::savepoint::Savepoint;
+use crate::kvs::scheduler::Scheduler;
+use crate::kvs::stats::{Pending, Table, TableStats, Write};
 use crate::kvs::Check;
+use crate::kvs::EngineCapabilities;
 use crate::options::EngineOptions;
 use crate::sql;
+use crate::sql::escape::escape_ident;
 use crate::sql::paths::EDGE;
 use crate::sql::paths::IN;
 use crate::sql::paths::OUT;
 use crate::sql::thing::Thing;
+use crate::sql::Cond;
+use crate::sql::Consistency;
+use crate::sql::Isolation;
+use crate::sql::Orders;
 use crate::sql::Strand;
 use crate::sql::Value;
 use crate::vs::Oracle;
@@ -92,12 +120,37 @@ where
 #[non_exhaustive]
 pub struct Transaction {
 	pub(super) inner: Inner,
+	pub(super) engine: EngineCapabilities,
 	pub(super) cache: Cache,
 	pub(super) cf: cf::Writer,
 	pub(super) vso: Arc<Mutex<Oracle>>,
 	pub(super) clock: Arc<SizedClock>,
 	pub(super) prepared_async_events: (Arc<Sender<TrackedResult>>, Arc<Receiver<TrackedResult>>),
 	pub(super) engine_options: EngineOptions,
+	pub(super) conflicts: Arc<ConflictStats>,
+	pub(super) advisor: Arc<QueryAdvisor>,
+	pub(super) written: HashSet<Vec<u8>>,
+	pub(super) prefetched: HashMap<Key, Option<Val>>,
+	pub(super) window_events: Vec<WindowEvent>,
+	pub(super) compact_events: Vec<CompactEvent>,
+	pub(super) count_events: Vec<CountEvent>,
+	pub(super) publications: Vec<Publication>,
+	pub(super) scheduler: Arc<Scheduler>,
+	pub(super) metrics: Arc<Metrics>,
+	pub(super) admission: Arc<Admission>,
+	pub(super) tracked: Tracked,
+	pub(super) stats: Arc<TableStats>,
+	pub(super) pending: Pending,
+	pub(super) results: Arc<ResultCache>,
+	pub(super) epoch: u64,
+	pub(super) writes: u64,
+	pub(super) background: bool,
+	pub(super) crdt: Option<bool>,
+	pub(super) write: bool,
+	pub(super) versionstamps: HashMap<Key, Versionstamp>,
+	pub(super) timestamp: Option<u64>,
+	pub(super) ops: Option<Arc<OperationCounter>>,
+	pub(super) savepoint: Option<Savepoint>,
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -248,6 +301,10 @@ impl Transaction {
 	pub async fn cancel(&mut self) -> Result<(), Error> {
 		#[cfg(debug_assertions)]
 		trace!("Cancel");
+		#[cfg(feature = "failpoints")]
+		super::fail::eval(&self.to_string(), "cancel").await?;
+		// Count the records which were read, but not those which were written
+		self.stats.record(std::mem::take(&mut self.pending), false);
 		match self {
 			#[cfg(feature = "kv-mem")]
 			Transaction {
@@ -295,7 +352,9 @@ impl Transaction {
 	pub async fn commit(&mut self) -> Result<(), Error> {
 		#[cfg(debug_assertions)]
 		trace!("Commit");
-		match self {
+		#[cfg(feature = "failpoints")]
+		super::fail::eval(&self.to_string(), "commit").await?;
+		let res = match self {
 			#[cfg(feature = "kv-mem")]
 			Transaction {
 				inner: Inner::Mem(v),
@@ -333,9 +392,256 @@ impl Transaction {
 			} => v.commit().await,
 			#[allow(unreachable_patterns)]
 			_ => unreachable!(),
+		};
+		// Remove the cached results of the tables which were written to. This
+		// is done even if the commit failed, as it may still have been applied.
+		self.results.invalidate(&self.pending);
+		// Count the records which were read and written
+		self.stats.record(std::mem::take(&mut self.pending), res.is_ok());
+		// Track the keys involved in any transaction conflict
+		match &res {
+			Ok(_) => self.scheduler.record(self.background, self.writes),
+			Err(e) if conflicts::is_conflict(e) => self.conflicts.record(&self.written),
+			Err(_) => {}
+		}
+		res
+	}
+
+	/// Record that a key has been written in this transaction
+	fn track_write(&mut self, key: &Key) {
+		self.writes += 1;
+		self.prefetched.remove(key);
+		let prefix = &key[..conflicts::prefix_len(key)];
+		if !self.written.contains(prefix) {
+			self.written.insert(prefix.to_vec());
+		}
+	}
+
+	/// Output the key prefixes which have been involved in the most transaction conflicts
+	pub(crate) fn hot_keys(&self) -> Value {
+		self.conflicts.output()
+	}
+
+	/// Output the retries of commutative updates for each kind of operation
+	pub(crate) fn merges(&self) -> Value {
+		self.conflicts.merges()
+	}
+
+	/// Record the shape of a query which scanned a whole table
+	#[allow(clippy::too_many_arguments)]
+	pub(crate) fn record_table_scan(
+		&self,
+		ns: &str,
+		db: &str,
+		tb: &str,
+		cond: Option<&Cond>,
+		order: Option<&Orders>,
+		scanned: u64,
+		matched: u64,
+	) {
+		self.advisor.record(ns, db, tb, cond, order, scanned, matched)
+	}
+
+	/// Output the indexes suggested for a database, based on the queries which scanned whole tables
+	pub(crate) async fn advice(&mut self, ns: &str, db: &str) -> Result<Value, Error> {
+		let advisor = self.advisor.clone();
+		advisor.output(self, ns, db).await
+	}
+
+	/// Count records of a table which were read, with their stored size
+	pub(crate) fn record_reads(
+		&mut self,
+		ns: &str,
+		db: &str,
+		tb: &str,
+		records: usize,
+		bytes: usize,
+	) {
+		self.pending.read(ns, db, tb, records, bytes)
+	}
+
+	/// Count a record of a table which was written, with its stored size
+	pub(crate) fn record_write(&mut self, ns: &str, db: &str, tb: &str, kind: Write, bytes: usize) {
+		self.pending.write(ns, db, tb, kind, bytes)
+	}
+
+	/// Output the read and write statistics of a table
+	pub(crate) fn table_stats(&self, ns: &str, db: &str, tb: &str) -> Value {
+		self.stats.output(ns, db, tb)
+	}
+
+	/// Fetch the cached result of a SELECT statement
+	pub(crate) fn cached_result(&self, key: &str) -> Option<Value> {
+		self.results.get(key, &self.pending)
+	}
+
+	/// Cache the result of a SELECT statement, which selected from the given tables
+	pub(crate) fn cache_result(
+		&self,
+		key: String,
+		ns: &str,
+		db: &str,
+		tables: &[String],
+		ttl: Duration,
+		value: Value,
+	) {
+		let tables = tables.iter().map(|tb| Table::new(ns, db, tb)).collect();
+		self.results.insert(key, tables, ttl, self.epoch, &self.pending, value)
+	}
+
+	/// Record whether a document which was written only changed CRDT fields
+	pub(crate) fn track_crdt(&mut self, only: bool) {
+		*self.crdt.get_or_insert(true) &= only;
+	}
+
+	/// Check if every document written in this transaction only changed CRDT fields
+	pub(crate) fn is_crdt_merge(&self) -> bool {
+		self.crdt == Some(true)
+	}
+
+	/// Set the consistency of the reads in this transaction, before anything is read.
+	/// The reads of a single node storage engine always see all committed changes,
+	/// and the reads of TiKV are always made at a timestamp from its placement driver,
+	/// so the consistency only changes the reads of FoundationDB.
+	#[allow(unused_variables)]
+	pub(crate) async fn set_consistency(&mut self, consistency: Consistency) -> Result<(), Error> {
+		match self {
+			#[cfg(feature = "kv-fdb")]
+			Transaction {
+				inner: Inner::FoundationDB(v),
+				..
+			} => v.set_consistency(consistency).await,
+			#[allow(unreachable_patterns)]
+			_ => Ok(()),
+		}
+	}
+
+	/// Set the isolation level of this transaction, before anything is read.
+	/// The memory and IndexedDB storage engines run writeable transactions one at a time,
+	/// and FoundationDB uses snapshot reads for weaker isolation levels, while the other
+	/// storage engines provide snapshot isolation, so they can not be serializable.
+	pub(crate) async fn set_isolation(&mut self, isolation: Isolation) -> Result<(), Error> {
+		let strongest = match self {
+			#[cfg(feature = "kv-fdb")]
+			Transaction {
+				inner: Inner::FoundationDB(v),
+				..
+			} => return v.set_isolation(isolation).await,
+			#[cfg(feature = "kv-mem")]
+			Transaction {
+				inner: Inner::Mem(_),
+				..
+			} => Isolation::Serializable,
+			#[cfg(feature = "kv-indxdb")]
+			Transaction {
+				inner: Inner::IndxDB(_),
+				..
+			} => Isolation::Serializable,
+			#[allow(unreachable_patterns)]
+			_ => Isolation::Snapshot,
+		};
+		match isolation <= strongest {
+			true => Ok(()),
+			false => Err(Error::TxIsolationUnsupported {
+				value: isolation.to_string(),
+			}),
+		}
+	}
+
+	/// Check if this transaction can write to the datastore
+	pub(crate) fn is_writeable(&self) -> bool {
+		self.write
+	}
+
+	/// Mark this transaction as performing background work, rather than serving a client request
+	pub(crate) fn set_background(&mut self) {
+		self.background = true;
+		self.tracked.set_background();
+	}
+
+	/// Count the operations of this transaction on the storage engine
+	pub(crate) fn count_operations(&mut self, ops: Arc<OperationCounter>) {
+		self.ops = Some(ops);
+	}
+
+	/// Count an operation on the storage engine, if the operations are being counted
+	fn count(&self, op: Op, count: u64) {
+		if let Some(ops) = &self.ops {
+			ops.add(op, count);
+		}
+	}
+
+	/// Output the background work configuration and statistics
+	pub(crate) fn background_stats(&self) -> Value {
+		self.scheduler.output()
+	}
+
+	/// Output the client connections which are open on this node
+	pub(crate) fn connections(&self) -> Value {
+		self.metrics.connections()
+	}
+
+	/// Output the transactions which are open on this node
+	pub(crate) fn transactions(&self) -> Value {
+		self.metrics.transactions()
+	}
+
+	/// Output the queries which are executing on this node
+	pub(crate) fn queries(&self) -> Value {
+		self.metrics.queries()
+	}
+
+	/// Output the batched reads of records, and the requests which they saved
+	pub(crate) fn prefetches(&self) -> Value {
+		self.metrics.prefetches()
+	}
+
+	/// Output the totals of the queries, transactions, and conflicts on this node, and
+	/// the latency of a read from the storage engine
+	pub(crate) async fn activity(&mut self) -> Result<Value, Error> {
+		let started = trice::Instant::now();
+		self.get(crate::key::root::sv::Sv::new()).await?;
+		let latency = trice::Instant::now().saturating_duration_since(started);
+		let mut out = self.metrics.activity();
+		out.insert("conflicts".to_owned(), Value::from(self.conflicts.total()));
+		out.insert("admission".to_owned(), self.admission.output());
+		out.insert("read_latency".to_owned(), Value::from(crate::sql::Duration(latency)));
+		Ok(out.into())
+	}
+
+	/// Cancel a query which is executing on this node, returning whether it was found
+	pub(crate) fn cancel_query(&self, id: &Uuid) -> bool {
+		self.metrics.cancel(id)
+	}
+
+	/// The primitives which the storage engine supports natively
+	pub fn engine_capabilities(&self) -> EngineCapabilities {
+		self.engine
+	}
+
+	/// Output the disk usage of the datastore, if it is stored in a local directory
+	pub(crate) fn disk_usage(&self) -> Value {
+		match self {
+			#[cfg(feature = "kv-rocksdb")]
+			Transaction {
+				inner: Inner::RocksDB(v),
+				..
+			} => v.disk_usage(),
+			#[cfg(feature = "kv-speedb")]
+			Transaction {
+				inner: Inner::SpeeDB(v),
+				..
+			} => v.disk_usage(),
+			#[allow(unreachable_patterns)]
+			_ => Value::None,
 		}
 	}
 
+	/// The background work scheduler, which can be configured at runtime
+	pub(crate) fn scheduler(&self) -> Arc<Scheduler> {
+		self.scheduler.clone()
+	}
+
 	/// From the existing transaction, consume all the remaining live query registration events and return them synchronously
 	/// This function does not check that a transaction was committed, but the intention is to consume from this
 	/// only once the transaction is committed
@@ -348,6 +654,46 @@ impl Transaction {
 		tracked_results
 	}
 
+	/// Buffers a record which matched a windowed live query, until the transaction is committed
+	pub(crate) fn push_window_event(&mut self, ev: WindowEvent) {
+		self.window_events.push(ev);
+	}
+
+	/// From the existing transaction, consume all of the records which matched windowed live queries
+	pub(crate) fn consume_window_events(&mut self) -> Vec<WindowEvent> {
+		std::mem::take(&mut self.window_events)
+	}
+
+	/// Buffers a notification of a live query which compacts its notifications, until the transaction is committed
+	pub(crate) fn push_compact_event(&mut self, ev: CompactEvent) {
+		self.compact_events.push(ev);
+	}
+
+	/// From the existing transaction, consume all of the notifications of live queries which compact their notifications
+	pub(crate) fn consume_compact_events(&mut self) -> Vec<CompactEvent> {
+		std::mem::take(&mut self.compact_events)
+	}
+
+	/// Buffers a change to the number of records matching a counting live query, until the transaction is committed
+	pub(crate) fn push_count_event(&mut self, ev: CountEvent) {
+		self.count_events.push(ev);
+	}
+
+	/// From the existing transaction, consume all of the changes to the number of records matching counting live queries
+	pub(crate) fn consume_count_events(&mut self) -> Vec<CountEvent> {
+		std::mem::take(&mut self.count_events)
+	}
+
+	/// Buffers a message which a hook publishes, until the transaction is committed
+	pub(crate) fn push_publication(&mut self, msg: Publication) {
+		self.publications.push(msg);
+	}
+
+	/// From the existing transaction, consume all of the messages which hooks publish
+	pub(crate) fn consume_publications(&mut self) -> Vec<Publication> {
+		std::mem::take(&mut self.publications)
+	}
+
 	/// Sends an async operation, such as a new live query, to the transaction which is forwarded
 	/// only once committed and removed once a transaction is aborted
 	// allow(dead_code) because this is used in v2, but not v1
@@ -370,6 +716,21 @@ impl Transaction {
 		let key = key.into();
 		#[cfg(debug_assertions)]
 		trace!("Del {}", sprint_key(&key));
+		// The audit log of definitions is append-only
+		if history::is_protected(&key) {
+			return Err(Error::DefinitionLogProtected);
+		}
+		self._del(key).await
+	}
+
+	/// Delete a key from the datastore, including the audit log of definitions.
+	#[allow(unused_variables)]
+	pub(super) async fn _del(&mut self, key: Key) -> Result<(), Error> {
+		#[cfg(feature = "failpoints")]
+		super::fail::eval(&self.to_string(), "del").await?;
+		self.journal(&key).await?;
+		self.track_write(&key);
+		self.count(Op::Del, 1);
 		match self {
 			#[cfg(feature = "kv-mem")]
 			Transaction {
@@ -419,6 +780,7 @@ impl Transaction {
 	{
 		#[cfg(debug_assertions)]
 		trace!("Exi {}", sprint_key(&key));
+		self.count(Op::Get, 1);
 		match self {
 			#[cfg(feature = "kv-mem")]
 			Transaction {
@@ -469,6 +831,13 @@ impl Transaction {
 		let key = key.into();
 		#[cfg(debug_assertions)]
 		trace!("Get {}", sprint_key(&key));
+		#[cfg(feature = "failpoints")]
+		super::fail::eval(&self.to_string(), "get").await?;
+		// Check the keys which were read ahead of time
+		if let Some(val) = self.prefetched.remove(&key) {
+			return Ok(val);
+		}
+		self.count(Op::Get, 1);
 		match self {
 			#[cfg(feature = "kv-mem")]
 			Transaction {
@@ -510,6 +879,75 @@ impl Transaction {
 		}
 	}
 
+	/// Fetch many keys from the datastore, in a single request where the datastore supports it.
+	pub async fn getm(&mut self, keys: Vec<Key>) -> Result<Vec<Option<Val>>, Error> {
+		#[cfg(debug_assertions)]
+		trace!("GetM {} keys", keys.len());
+		#[cfg(feature = "kv-tikv")]
+		#[allow(irrefutable_let_patterns)]
+		if let Transaction {
+			inner: Inner::TiKV(v),
+			metrics,
+			ops,
+			..
+		} = self
+		{
+			metrics.prefetch(keys.len());
+			if let Some(ops) = ops {
+				ops.add(Op::Get, keys.len() as u64);
+			}
+			return v.getm(keys).await;
+		}
+		#[cfg(feature = "kv-fdb")]
+		#[allow(irrefutable_let_patterns)]
+		if let Transaction {
+			inner: Inner::FoundationDB(v),
+			metrics,
+			ops,
+			..
+		} = self
+		{
+			metrics.prefetch(keys.len());
+			if let Some(ops) = ops {
+				ops.add(Op::Get, keys.len() as u64);
+			}
+			return v.getm(keys).await;
+		}
+		// The other datastores are embedded, so the keys are read one at a time
+		let mut res = Vec::with_capacity(keys.len());
+		for key in keys {
+			res.push(self.get(key).await?);
+		}
+		Ok(res)
+	}
+
+	/// Read keys ahead of time, so that they are not requested one at a time when they are fetched later.
+	/// The values are kept until they are fetched or written, or until the prefetched keys are cleared.
+	pub(crate) async fn prefetch(&mut self, keys: Vec<Key>) -> Result<(), Error> {
+		// Embedded datastores do not gain from reading keys together
+		if !self.batches_reads() {
+			return Ok(());
+		}
+		let keys: Vec<Key> =
+			keys.into_iter().filter(|k| !self.prefetched.contains_key(k)).collect();
+		if keys.len() < 2 {
+			return Ok(());
+		}
+		let vals = self.getm(keys.clone()).await?;
+		self.prefetched.extend(keys.into_iter().zip(vals));
+		Ok(())
+	}
+
+	/// Check if the datastore reads many keys in a single request
+	fn batches_reads(&self) -> bool {
+		self.engine.contains(EngineCapabilities::BATCHED_READS)
+	}
+
+	/// Clear any keys which were read ahead of time but which were not fetched
+	pub(crate) fn clear_prefetched(&mut self) {
+		self.prefetched.clear();
+	}
+
 	/// Insert or update a key in the datastore.
 	#[allow(unused_variables)]
 	pub async fn set<K, V>(&mut self, key: K, val: V) -> Result<(), Error>
@@ -520,6 +958,11 @@ impl Transaction {
 		let key = key.into();
 		#[cfg(debug_assertions)]
 		trace!("Set {} => {:?}", sprint_key(&key), val);
+		#[cfg(feature = "failpoints")]
+		super::fail::eval(&self.to_string(), "set").await?;
+		self.journal(&key).await?;
+		self.track_write(&key);
+		self.count(Op::Put, 1);
 		match self {
 			#[cfg(feature = "kv-mem")]
 			Transaction {
@@ -561,6 +1004,59 @@ impl Transaction {
 		}
 	}
 
+	/// Acquire write intent on a key in the datastore.
+	///
+	/// Pessimistic transactions lock the key, whereas optimistic transactions
+	/// record the key, and fail to commit if it has been modified concurrently.
+	#[allow(unused_variables)]
+	pub async fn lock<K>(&mut self, key: K) -> Result<(), Error>
+	where
+		K: Into<Key> + Debug,
+	{
+		let key = key.into();
+		#[cfg(debug_assertions)]
+		trace!("Lock {}", sprint_key(&key));
+		match self {
+			#[cfg(feature = "kv-mem")]
+			Transaction {
+				inner: Inner::Mem(v),
+				..
+			} => v.lock(key),
+			#[cfg(feature = "kv-rocksdb")]
+			Transaction {
+				inner: Inner::RocksDB(v),
+				..
+			} => v.lock(key).await,
+			#[cfg(feature = "kv-speedb")]
+			Transaction {
+				inner: Inner::SpeeDB(v),
+				..
+			} => v.lock(key).await,
+			#[cfg(feature = "kv-indxdb")]
+			Transaction {
+				inner: Inner::IndxDB(v),
+				..
+			} => v.lock(key).await,
+			#[cfg(feature = "kv-tikv")]
+			Transaction {
+				inner: Inner::TiKV(v),
+				..
+			} => v.lock(key).await,
+			#[cfg(feature = "kv-fdb")]
+			Transaction {
+				inner: Inner::FoundationDB(v),
+				..
+			} => v.lock(key).await,
+			#[cfg(feature = "kv-surrealkv")]
+			Transaction {
+				inner: Inner::SurrealKV(v),
+				..
+			} => v.lock(key).await,
+			#[allow(unreachable_patterns)]
+			_ => unreachable!(),
+		}
+	}
+
 	/// Obtain a new change timestamp for a key
 	/// which is replaced with the current timestamp when the transaction is committed.
 	/// NOTE: This should be called when composing the change feed entries for this transaction,
@@ -616,6 +1112,50 @@ impl Transaction {
 		}
 	}
 
+	/// Obtain the versionstamp of the changes which this transaction makes to a database.
+	/// The versionstamp is obtained the first time it is requested, and the change feed
+	/// entries for the database are written with the same versionstamp on commit. A read
+	/// only transaction returns the versionstamp of the latest changes to the database.
+	pub(crate) async fn commit_versionstamp(
+		&mut self,
+		ns: &str,
+		db: &str,
+	) -> Result<Versionstamp, Error> {
+		let key: Key = crate::key::database::vs::new(ns, db).into();
+		if let Some(vs) = self.versionstamps.get(&key) {
+			return Ok(*vs);
+		}
+		// Some storage engines assign the versionstamp of a transaction when it commits
+		if self.engine.contains(EngineCapabilities::VERSIONSTAMPED_KEYS) {
+			return Err(Error::VersionstampUnavailable);
+		}
+		let vs = match self.write {
+			true => self.get_timestamp(key.clone(), false).await?,
+			false => match self.get(key.clone()).await? {
+				Some(v) => v
+					.as_slice()
+					.try_into()
+					.map_err(|_| Error::Internal("versionstamp is not 10 bytes".to_string()))?,
+				None => [0; 10],
+			},
+		};
+		self.versionstamps.insert(key, vs);
+		Ok(vs)
+	}
+
+	/// Obtain the time of this transaction, in milliseconds since the Unix epoch.
+	/// The time is obtained from the clock the first time it is requested.
+	pub(crate) async fn commit_timestamp(&mut self) -> u64 {
+		match self.timestamp {
+			Some(ts) => ts,
+			None => {
+				let ts = self.clock.now().await.value;
+				self.timestamp = Some(ts);
+				ts
+			}
+		}
+	}
+
 	#[allow(unused)]
 	async fn get_non_monotonic_versionstamp(&mut self) -> Result<Versionstamp, Error> {
 		Ok(self.vso.lock().await.now())
@@ -662,6 +1202,11 @@ impl Transaction {
 			sprint_key(&ts_key),
 			sprint_key(&suffix)
 		);
+		// Reuse the versionstamp if it was already obtained by this transaction
+		if let Some(vs) = self.versionstamps.get(&ts_key) {
+			let k = [prefix.as_slice(), vs.as_slice(), suffix.as_slice()].concat();
+			return self.set(k, val).await;
+		}
 		match self {
 			#[cfg(feature = "kv-mem")]
 			Transaction {
@@ -728,6 +1273,12 @@ impl Transaction {
 		K: Into<Key> + Debug,
 		V: Into<Val> + Debug,
 	{
+		let key = key.into();
+		#[cfg(feature = "failpoints")]
+		super::fail::eval(&self.to_string(), "put").await?;
+		self.journal(&key).await?;
+		self.track_write(&key);
+		self.count(Op::Put, 1);
 		match self {
 			#[cfg(feature = "kv-mem")]
 			Transaction {
@@ -783,7 +1334,10 @@ impl Transaction {
 		};
 		#[cfg(debug_assertions)]
 		trace!("Scan {} - {}", sprint_key(&rng.start), sprint_key(&rng.end));
-		match self {
+		#[cfg(feature = "failpoints")]
+		let failpoint = super::fail::eval(&self.to_string(), "scan").await?;
+		self.count(Op::Scan, 1);
+		let res: Result<Vec<(Key, Val)>, Error> = match self {
 			#[cfg(feature = "kv-mem")]
 			Transaction {
 				inner: Inner::Mem(v),
@@ -821,12 +1375,22 @@ impl Transaction {
 			} => v.scan(rng, limit).await,
 			#[allow(unreachable_patterns)]
 			_ => unreachable!(),
-		}
+		};
+		#[cfg(feature = "failpoints")]
+		let res = res.map(|mut res| {
+			if let super::fail::Outcome::Partial(n) = failpoint {
+				res.truncate(n);
+			}
+			res
+		});
+		res
 	}
 
-	/// Retrieve a specific range of keys from the datastore.
+	/// Retrieve a page of keys from the datastore.
 	///
-	/// This function fetches the full range of key-value pairs, in a single request to the underlying datastore.
+	/// This function fetches at most `batch_limit` key-value pairs, in a single request to the underlying datastore,
+	/// and never more than the remaining limit of the page. If there may be more key-value pairs in the range, the
+	/// result contains the next page to fetch, so that the full range can be scanned in bounded batches.
 	#[allow(unused_variables)]
 	pub async fn scan_paged<K>(
 		&mut self,
@@ -838,7 +1402,21 @@ impl Transaction {
 	{
 		#[cfg(debug_assertions)]
 		trace!("Scan paged {} - {}", sprint_key(&page.range.start), sprint_key(&page.range.end));
+		// Never fetch more than the remaining limit
+		let batch_limit = match page.limit {
+			Limit::Limited(0) => {
+				return Ok(ScanResult {
+					next_page: None,
+					values: vec![],
+				})
+			}
+			Limit::Limited(limit) => batch_limit.min(limit),
+			Limit::Unlimited => batch_limit,
+		};
 		let range = page.range.clone();
+		#[cfg(feature = "failpoints")]
+		let failpoint = super::fail::eval(&self.to_string(), "scan").await?;
+		self.count(Op::Scan, 1);
 		let res = match self {
 			#[cfg(feature = "kv-mem")]
 			Transaction {
@@ -878,6 +1456,13 @@ impl Transaction {
 			#[allow(unreachable_patterns)]
 			_ => Err(Error::MissingStorageEngine),
 		};
+		#[cfg(feature = "failpoints")]
+		let res = res.map(|mut res| {
+			if let super::fail::Outcome::Partial(n) = failpoint {
+				res.truncate(n);
+			}
+			res
+		});
 		// Construct next page
 		res.map(|tup_vec: Vec<(Key, Val)>| {
 			if tup_vec.len() < batch_limit as usize {
@@ -891,11 +1476,20 @@ impl Transaction {
 					Some((k, _)) => K::from(k.clone().add(0)),
 					None => rng.start,
 				};
+				let limit = match limit {
+					Limit::Limited(limit) => {
+						Limit::Limited(limit.saturating_sub(tup_vec.len() as u32))
+					}
+					Limit::Unlimited => Limit::Unlimited,
+				};
 				ScanResult {
-					next_page: Some(ScanPage {
-						range: rng,
-						limit,
-					}),
+					next_page: match limit {
+						Limit::Limited(0) => None,
+						limit => Some(ScanPage {
+							range: rng,
+							limit,
+						}),
+					},
 					values: tup_vec,
 				}
 			}
@@ -912,6 +1506,9 @@ impl Transaction {
 		let key = key.into();
 		#[cfg(debug_assertions)]
 		trace!("Putc {} if {:?} => {:?}", sprint_key(&key), chk, val);
+		self.journal(&key).await?;
+		self.track_write(&key);
+		self.count(Op::Put, 1);
 		match self {
 			#[cfg(feature = "kv-mem")]
 			Transaction {
@@ -963,6 +1560,13 @@ impl Transaction {
 		let key = key.into();
 		#[cfg(debug_assertions)]
 		trace!("Delc {} if {:?}", sprint_key(&key), chk);
+		// The audit log of definitions is append-only
+		if history::is_protected(&key) {
+			return Err(Error::DefinitionLogProtected);
+		}
+		self.journal(&key).await?;
+		self.track_write(&key);
+		self.count(Op::Del, 1);
 		match self {
 			#[cfg(feature = "kv-mem")]
 			Transaction {
@@ -1010,7 +1614,7 @@ impl Transaction {
 
 	/// Retrieve a specific range of keys from the datastore.
 	///
-	/// This function fetches key-value pairs from the underlying datastore in batches of 1000.
+	/// This function fetches at most `limit` key-value pairs from the underlying datastore, in batches of 1000.
 	pub async fn getr<K>(&mut self, rng: Range<K>, limit: u32) -> Result<Vec<(Key, Val)>, Error>
 	where
 		K: Into<Key> + Debug,
@@ -1027,7 +1631,7 @@ impl Transaction {
 		// Start processing
 		while let Some(page) = next_page {
 			// Get records batch
-			let res = self.scan_paged(page, 1000).await?;
+			let res = self.scan_paged(page, NORMAL_FETCH_SIZE).await?;
 			next_page = res.next_page;
 			let res = res.values;
 			// Exit when settled
@@ -1044,8 +1648,8 @@ impl Transaction {
 	}
 	/// Delete a range of keys from the datastore.
 	///
-	/// This function fetches key-value pairs from the underlying datastore in batches of 1000.
-	pub async fn delr<K>(&mut self, rng: Range<K>, limit: u32) -> Result<(), Error>
+	/// This function deletes the full range of keys, fetching keys from the underlying datastore in batches of `batch_size`.
+	pub async fn delr<K>(&mut self, rng: Range<K>, batch_size: u32) -> Result<(), Error>
 	where
 		K: Into<Key> + Debug,
 	{
@@ -1054,40 +1658,46 @@ impl Transaction {
 			end: rng.end.into(),
 		};
 		#[cfg(debug_assertions)]
-		trace!("Delr {}..{} (limit: {limit})", sprint_key(&rng.start), sprint_key(&rng.end));
-		match self {
-			#[cfg(feature = "kv-tikv")]
-			Transaction {
-				inner: Inner::TiKV(v),
-				..
-			} => v.delr(rng, limit).await,
-			#[cfg(feature = "kv-fdb")]
-			Transaction {
-				inner: Inner::FoundationDB(v),
-				..
-			} => v.delr(rng).await,
-			#[allow(unreachable_patterns)]
-			_ => self._delr(rng, limit).await,
+		trace!("Delr {}..{} (batch: {batch_size})", sprint_key(&rng.start), sprint_key(&rng.end));
+		self.track_write(&rng.start);
+		self.prefetched.clear();
+		// The audit log of definitions is skipped, as it is append-only
+		for rng in history::unprotected(rng) {
+			match self {
+				// Keys are deleted one at a time while a savepoint records them
+				#[cfg(feature = "kv-fdb")]
+				Transaction {
+					inner: Inner::FoundationDB(v),
+					ops,
+					savepoint: None,
+					..
+				} => {
+					if let Some(ops) = ops {
+						ops.add(Op::Del, 1);
+					}
+					v.delr(rng).await?
+				}
+				#[allow(unreachable_patterns)]
+				_ => self._delr(rng, batch_size).await?,
+			}
 		}
+		Ok(())
 	}
 
 	/// Delete a range of keys from the datastore.
 	///
-	/// This function fetches key-value pairs from the underlying datastore in batches of 1000.
-	async fn _delr<K>(&mut self, rng: Range<K>, limit: u32) -> Result<(), Error>
+	/// This function fetches keys from the underlying datastore in batches of `batch_size`, until the range is empty.
+	async fn _delr<K>(&mut self, rng: Range<K>, batch_size: u32) -> Result<(), Error>
 	where
 		K: Into<Key> + Debug,
 	{
 		let beg: Key = rng.start.into();
 		let end: Key = rng.end.into();
 		// Start processing
-		let mut next_page = Some(ScanPage {
-			range: beg..end,
-			limit: Limit::Limited(limit),
-		});
+		let mut next_page = Some(ScanPage::from(beg..end));
 		while let Some(page) = next_page {
 			// Get records batch
-			let res = self.scan_paged(page, limit).await?;
+			let res = self.scan_paged(page, batch_size).await?;
 			next_page = res.next_page;
 			let res = res.values;
 			// Exit when settled
@@ -1107,7 +1717,7 @@ impl Transaction {
 	}
 	/// Retrieve a specific prefix of keys from the datastore.
 	///
-	/// This function fetches key-value pairs from the underlying datastore in batches of 1000.
+	/// This function fetches at most `limit` key-value pairs from the underlying datastore, in batches of 1000.
 	pub async fn getp<K>(&mut self, key: K, limit: u32) -> Result<Vec<(Key, Val)>, Error>
 	where
 		K: Into<Key> + Debug,
@@ -1123,7 +1733,7 @@ impl Transaction {
 			limit: Limit::Limited(limit),
 		});
 		while let Some(page) = next_page {
-			let res = self.scan_paged(page, 1000).await?;
+			let res = self.scan_paged(page, NORMAL_FETCH_SIZE).await?;
 			next_page = res.next_page;
 			// Get records batch
 			let res = res.values;
@@ -1141,18 +1751,18 @@ impl Transaction {
 	}
 	/// Delete a prefix of keys from the datastore.
 	///
-	/// This function fetches key-value pairs from the underlying datastore in batches of 1000.
-	pub async fn delp<K>(&mut self, key: K, limit: u32) -> Result<(), Error>
+	/// This function deletes all keys with the prefix, fetching keys from the underlying datastore in batches of `batch_size`.
+	pub async fn delp<K>(&mut self, key: K, batch_size: u32) -> Result<(), Error>
 	where
 		K: Into<Key> + Debug,
 	{
 		let beg: Key = key.into();
 		let end: Key = beg.clone().add(0xff);
 		#[cfg(debug_assertions)]
-		trace!("Delp {}-{} (limit: {limit})", sprint_key(&beg), sprint_key(&end));
+		trace!("Delp {}-{} (batch: {batch_size})", sprint_key(&beg), sprint_key(&end));
 		let min = beg.clone();
 		let max = end.clone();
-		self.delr(min..max, limit).await?;
+		self.delr(min..max, batch_size).await?;
 		Ok(())
 	}
 
@@ -1548,6 +2158,52 @@ impl Transaction {
 		})
 	}
 
+	/// Retrieve all check definitions for a specific database.
+	pub async fn all_db_checks(
+		&mut self,
+		ns: &str,
+		db: &str,
+	) -> Result<Arc<[DefineCheckStatement]>, Error> {
+		let key = crate::key::database::ck::prefix(ns, db);
+		Ok(if let Some(e) = self.cache.get(&key) {
+			if let Entry::Cks(v) = e {
+				v
+			} else {
+				unreachable!();
+			}
+		} else {
+			let beg = crate::key::database::ck::prefix(ns, db);
+			let end = crate::key::database::ck::suffix(ns, db);
+			let val = self.getr(beg..end, u32::MAX).await?;
+			let val = val.convert().into();
+			self.cache.set(key, Entry::Cks(Arc::clone(&val)));
+			val
+		})
+	}
+
+	/// Retrieve all dictionary definitions for a specific database.
+	pub async fn all_db_dictionaries(
+		&mut self,
+		ns: &str,
+		db: &str,
+	) -> Result<Arc<[DefineDictionaryStatement]>, Error> {
+		let key = crate::key::database::dc::prefix(ns, db);
+		Ok(if let Some(e) = self.cache.get(&key) {
+			if let Entry::Dcs(v) = e {
+				v
+			} else {
+				unreachable!();
+			}
+		} else {
+			let beg = crate::key::database::dc::prefix(ns, db);
+			let end = crate::key::database::dc::suffix(ns, db);
+			let val = self.getr(beg..end, u32::MAX).await?;
+			let val = val.convert().into();
+			self.cache.set(key, Entry::Dcs(Arc::clone(&val)));
+			val
+		})
+	}
+
 	/// Retrieve all function definitions for a specific database.
 	pub async fn all_db_functions(
 		&mut self,
@@ -1594,6 +2250,29 @@ impl Transaction {
 		})
 	}
 
+	/// Retrieve all role definitions for a specific database.
+	pub async fn all_db_roles(
+		&mut self,
+		ns: &str,
+		db: &str,
+	) -> Result<Arc<[DefineRoleStatement]>, Error> {
+		let key = crate::key::database::ro::prefix(ns, db);
+		Ok(if let Some(e) = self.cache.get(&key) {
+			if let Entry::Ros(v) = e {
+				v
+			} else {
+				unreachable!();
+			}
+		} else {
+			let beg = crate::key::database::ro::prefix(ns, db);
+			let end = crate::key::database::ro::suffix(ns, db);
+			let val = self.getr(beg..end, u32::MAX).await?;
+			let val = val.convert().into();
+			self.cache.set(key, Entry::Ros(Arc::clone(&val)));
+			val
+		})
+	}
+
 	/// Retrieve all model definitions for a specific database.
 	pub async fn all_db_models(
 		&mut self,
@@ -1711,6 +2390,54 @@ impl Transaction {
 		})
 	}
 
+	/// Retrieve all hook definitions for a specific table.
+	pub async fn all_tb_hooks(
+		&mut self,
+		ns: &str,
+		db: &str,
+		tb: &str,
+	) -> Result<Arc<[DefineHookStatement]>, Error> {
+		let key = crate::key::table::hk::prefix(ns, db, tb);
+		Ok(if let Some(e) = self.cache.get(&key) {
+			if let Entry::Hks(v) = e {
+				v
+			} else {
+				unreachable!();
+			}
+		} else {
+			let beg = crate::key::table::hk::prefix(ns, db, tb);
+			let end = crate::key::table::hk::suffix(ns, db, tb);
+			let val = self.getr(beg..end, u32::MAX).await?;
+			let val = val.convert().into();
+			self.cache.set(key, Entry::Hks(Arc::clone(&val)));
+			val
+		})
+	}
+
+	/// Retrieve all enrichment definitions for a specific table.
+	pub async fn all_tb_enrichments(
+		&mut self,
+		ns: &str,
+		db: &str,
+		tb: &str,
+	) -> Result<Arc<[DefineEnrichmentStatement]>, Error> {
+		let key = crate::key::table::en::prefix(ns, db, tb);
+		Ok(if let Some(e) = self.cache.get(&key) {
+			if let Entry::Ens(v) = e {
+				v
+			} else {
+				unreachable!();
+			}
+		} else {
+			let beg = crate::key::table::en::prefix(ns, db, tb);
+			let end = crate::key::table::en::suffix(ns, db, tb);
+			let val = self.getr(beg..end, u32::MAX).await?;
+			let val = val.convert().into();
+			self.cache.set(key, Entry::Ens(Arc::clone(&val)));
+			val
+		})
+	}
+
 	/// Retrieve all field definitions for a specific table.
 	pub async fn all_tb_fields(
 		&mut self,
@@ -1943,6 +2670,34 @@ impl Transaction {
 		Ok(val.into())
 	}
 
+	/// Retrieve a specific check definition.
+	pub async fn get_db_check(
+		&mut self,
+		ns: &str,
+		db: &str,
+		ck: &str,
+	) -> Result<DefineCheckStatement, Error> {
+		let key = crate::key::database::ck::new(ns, db, ck);
+		let val = self.get(key).await?.ok_or(Error::CkNotFound {
+			value: ck.to_owned(),
+		})?;
+		Ok(val.into())
+	}
+
+	/// Retrieve a specific dictionary definition.
+	pub async fn get_db_dictionary(
+		&mut self,
+		ns: &str,
+		db: &str,
+		dc: &str,
+	) -> Result<DefineDictionaryStatement, Error> {
+		let key = crate::key::database::dc::new(ns, db, dc);
+		let val = self.get(key).await?.ok_or(Error::DcNotFound {
+			value: dc.to_owned(),
+		})?;
+		Ok(val.into())
+	}
+
 	/// Retrieve a specific function definition from a database.
 	pub async fn get_db_function(
 		&mut self,
@@ -1971,6 +2726,20 @@ impl Transaction {
 		Ok(val.into())
 	}
 
+	/// Retrieve a specific role definition from a database.
+	pub async fn get_db_role(
+		&mut self,
+		ns: &str,
+		db: &str,
+		ro: &str,
+	) -> Result<DefineRoleStatement, Error> {
+		let key = crate::key::database::ro::new(ns, db, ro);
+		let val = self.get(key).await?.ok_or(Error::RoNotFound {
+			value: ro.to_owned(),
+		})?;
+		Ok(val.into())
+	}
+
 	/// Retrieve a specific scope definition.
 	pub async fn get_sc(
 		&mut self,
@@ -2063,6 +2832,40 @@ impl Transaction {
 		Ok(val.into())
 	}
 
+	/// Retrieve a hook for a table.
+	pub async fn get_tb_hook(
+		&mut self,
+		ns: &str,
+		db: &str,
+		tb: &str,
+		hk: &str,
+	) -> Result<DefineHookStatement, Error> {
+		let key = crate::key::table::hk::new(ns, db, tb, hk);
+		let key_enc = crate::key::table::hk::Hk::encode(&key)?;
+		trace!("Getting hk ({:?}) {}", hk, sprint_key(&key_enc));
+		let val = self.get(key_enc).await?.ok_or(Error::HkNotFound {
+			value: hk.to_string(),
+		})?;
+		Ok(val.into())
+	}
+
+	/// Retrieve an enrichment for a table.
+	pub async fn get_tb_enrichment(
+		&mut self,
+		ns: &str,
+		db: &str,
+		tb: &str,
+		en: &str,
+	) -> Result<DefineEnrichmentStatement, Error> {
+		let key = crate::key::table::en::new(ns, db, tb, en);
+		let key_enc = crate::key::table::en::En::encode(&key)?;
+		trace!("Getting en ({:?}) {}", en, sprint_key(&key_enc));
+		let val = self.get(key_enc).await?.ok_or(Error::EnNotFound {
+			value: en.to_string(),
+		})?;
+		Ok(val.into())
+	}
+
 	/// Retrieve an event for a table.
 	pub async fn get_tb_field(
 		&mut self,
@@ -2495,8 +3298,74 @@ impl Transaction {
 	// Additional methods
 	// --------------------------------------------------
 
-	/// Writes the full database contents as binary SQL.
-	pub async fn export(&mut self, ns: &str, db: &str, chn: Sender<Vec<u8>>) -> Result<(), Error> {
+	/// Writes the database contents as binary SQL, limited to the specified tables if any are given.
+	pub async fn export(
+		&mut self,
+		ns: &str,
+		db: &str,
+		tables: &[String],
+		chn: Sender<Vec<u8>>,
+	) -> Result<(), Error> {
+		// Output the definitions
+		let tbs = self.export_definitions(ns, db, tables, &chn).await?;
+		if !tbs.is_empty() {
+			// Start transaction
+			export_begin(&chn).await?;
+			// Output TABLE data
+			for tb in tbs.iter() {
+				// Start records
+				export_table_data(&chn, &tb.name).await?;
+				// Fetch records
+				let beg = crate::key::thing::prefix(ns, db, &tb.name);
+				let end = crate::key::thing::suffix(ns, db, &tb.name);
+				let mut nxt: Option<ScanPage<Vec<u8>>> = Some(ScanPage::from(beg..end));
+				while nxt.is_some() {
+					let res = self.scan_paged(nxt.unwrap(), 1000).await?;
+					nxt = res.next_page;
+					let res = res.values;
+					if res.is_empty() {
+						break;
+					}
+					// Loop over results
+					for (k, v) in res.into_iter() {
+						chn.send(export_record(&k, &v)).await?;
+					}
+					continue;
+				}
+				chn.send(bytes!("")).await?;
+			}
+			// Commit transaction
+			export_commit(&chn).await?;
+		}
+		// Everything exported
+		Ok(())
+	}
+
+	/// Writes the database definitions as binary SQL, returning the tables whose records should be exported.
+	///
+	/// When tables are specified, only the definitions of those tables are written,
+	/// and the definitions of the database itself are left out.
+	pub(crate) async fn export_definitions(
+		&mut self,
+		ns: &str,
+		db: &str,
+		tables: &[String],
+		chn: &Sender<Vec<u8>>,
+	) -> Result<Arc<[DefineTableStatement]>, Error> {
+		// Select the tables to export
+		let tbs = self.all_tb(ns, db).await?;
+		let tbs: Arc<[DefineTableStatement]> = match tables.is_empty() {
+			true => tbs,
+			false => {
+				// Check that every selected table exists
+				if let Some(tb) = tables.iter().find(|tb| !tbs.iter().any(|v| v.name.0 == **tb)) {
+					return Err(Error::TbNotFound {
+						value: tb.to_owned(),
+					});
+				}
+				tbs.iter().filter(|tb| tables.contains(&tb.name.0)).cloned().collect()
+			}
+		};
 		// Output OPTIONS
 		{
 			chn.send(bytes!("-- ------------------------------")).await?;
@@ -2506,6 +3375,84 @@ impl Transaction {
 			chn.send(bytes!("OPTION IMPORT;")).await?;
 			chn.send(bytes!("")).await?;
 		}
+		// Output the database definitions
+		if tables.is_empty() {
+			self.export_database_definitions(ns, db, chn).await?;
+		}
+		// Output TABLES
+		for tb in tbs.iter() {
+			// Output TABLE
+			chn.send(bytes!("-- ------------------------------")).await?;
+			chn.send(bytes!(format!("-- TABLE: {}", tb.name))).await?;
+			chn.send(bytes!("-- ------------------------------")).await?;
+			chn.send(bytes!("")).await?;
+			chn.send(bytes!(format!("{tb};"))).await?;
+			chn.send(bytes!("")).await?;
+			// Output FIELDS
+			let fds = self.all_tb_fields(ns, db, &tb.name).await?;
+			if !fds.is_empty() {
+				for fd in fds.iter() {
+					chn.send(bytes!(format!("{fd};"))).await?;
+				}
+				chn.send(bytes!("")).await?;
+			}
+			// Output INDEXES
+			let ixs = self.all_tb_indexes(ns, db, &tb.name).await?;
+			if !ixs.is_empty() {
+				for ix in ixs.iter() {
+					chn.send(bytes!(format!("{ix};"))).await?;
+				}
+				chn.send(bytes!("")).await?;
+			}
+			// Output EVENTS
+			let evs = self.all_tb_events(ns, db, &tb.name).await?;
+			if !evs.is_empty() {
+				for ev in evs.iter() {
+					chn.send(bytes!(format!("{ev};"))).await?;
+				}
+				chn.send(bytes!("")).await?;
+			}
+			// Output HOOKS
+			let hks = self.all_tb_hooks(ns, db, &tb.name).await?;
+			if !hks.is_empty() {
+				for hk in hks.iter() {
+					chn.send(bytes!(format!("{hk};"))).await?;
+				}
+				chn.send(bytes!("")).await?;
+			}
+			// Output ENRICHMENTS
+			let ens = self.all_tb_enrichments(ns, db, &tb.name).await?;
+			if !ens.is_empty() {
+				for en in ens.iter() {
+					chn.send(bytes!(format!("{en};"))).await?;
+				}
+				chn.send(bytes!("")).await?;
+			}
+		}
+		Ok(tbs)
+	}
+
+	/// Writes the definitions of the database which do not belong to a table as binary SQL.
+	async fn export_database_definitions(
+		&mut self,
+		ns: &str,
+		db: &str,
+		chn: &Sender<Vec<u8>>,
+	) -> Result<(), Error> {
+		// Output ROLES
+		{
+			let ros = self.all_db_roles(ns, db).await?;
+			if !ros.is_empty() {
+				chn.send(bytes!("-- ------------------------------")).await?;
+				chn.send(bytes!("-- ROLES")).await?;
+				chn.send(bytes!("-- ------------------------------")).await?;
+				chn.send(bytes!("")).await?;
+				for ro in ros.iter() {
+					chn.send(bytes!(format!("{ro};"))).await?;
+				}
+				chn.send(bytes!("")).await?;
+			}
+		}
 		// Output USERS
 		{
 			let dus = self.all_db_users(ns, db).await?;
@@ -2562,6 +3509,20 @@ impl Transaction {
 				chn.send(bytes!("")).await?;
 			}
 		}
+		// Output DICTIONARIES
+		{
+			let dcs = self.all_db_dictionaries(ns, db).await?;
+			if !dcs.is_empty() {
+				chn.send(bytes!("-- ------------------------------")).await?;
+				chn.send(bytes!("-- DICTIONARIES")).await?;
+				chn.send(bytes!("-- ------------------------------")).await?;
+				chn.send(bytes!("")).await?;
+				for dc in dcs.iter() {
+					chn.send(bytes!(format!("{dc};"))).await?;
+				}
+				chn.send(bytes!("")).await?;
+			}
+		}
 		// Output ANALYZERS
 		{
 			let azs = self.all_db_analyzers(ns, db).await?;
@@ -2576,6 +3537,20 @@ impl Transaction {
 				chn.send(bytes!("")).await?;
 			}
 		}
+		// Output CHECKS
+		{
+			let cks = self.all_db_checks(ns, db).await?;
+			if !cks.is_empty() {
+				chn.send(bytes!("-- ------------------------------")).await?;
+				chn.send(bytes!("-- CHECKS")).await?;
+				chn.send(bytes!("-- ------------------------------")).await?;
+				chn.send(bytes!("")).await?;
+				for ck in cks.iter() {
+					chn.send(bytes!(format!("{ck};"))).await?;
+				}
+				chn.send(bytes!("")).await?;
+			}
+		}
 		// Output SCOPES
 		{
 			let scs = self.all_sc(ns, db).await?;
@@ -2601,102 +3576,6 @@ impl Transaction {
 				chn.send(bytes!("")).await?;
 			}
 		}
-		// Output TABLES
-		{
-			let tbs = self.all_tb(ns, db).await?;
-			if !tbs.is_empty() {
-				for tb in tbs.iter() {
-					// Output TABLE
-					chn.send(bytes!("-- ------------------------------")).await?;
-					chn.send(bytes!(format!("-- TABLE: {}", tb.name))).await?;
-					chn.send(bytes!("-- ------------------------------")).await?;
-					chn.send(bytes!("")).await?;
-					chn.send(bytes!(format!("{tb};"))).await?;
-					chn.send(bytes!("")).await?;
-					// Output FIELDS
-					let fds = self.all_tb_fields(ns, db, &tb.name).await?;
-					if !fds.is_empty() {
-						for fd in fds.iter() {
-							chn.send(bytes!(format!("{fd};"))).await?;
-						}
-						chn.send(bytes!("")).await?;
-					}
-					// Output INDEXES
-					let ixs = self.all_tb_indexes(ns, db, &tb.name).await?;
-					if !ixs.is_empty() {
-						for ix in ixs.iter() {
-							chn.send(bytes!(format!("{ix};"))).await?;
-						}
-						chn.send(bytes!("")).await?;
-					}
-					// Output EVENTS
-					let evs = self.all_tb_events(ns, db, &tb.name).await?;
-					if !evs.is_empty() {
-						for ev in evs.iter() {
-							chn.send(bytes!(format!("{ev};"))).await?;
-						}
-						chn.send(bytes!("")).await?;
-					}
-				}
-				// Start transaction
-				chn.send(bytes!("-- ------------------------------")).await?;
-				chn.send(bytes!("-- TRANSACTION")).await?;
-				chn.send(bytes!("-- ------------------------------")).await?;
-				chn.send(bytes!("")).await?;
-				chn.send(bytes!("BEGIN TRANSACTION;")).await?;
-				chn.send(bytes!("")).await?;
-				// Output TABLE data
-				for tb in tbs.iter() {
-					// Start records
-					chn.send(bytes!("-- ------------------------------")).await?;
-					chn.send(bytes!(format!("-- TABLE DATA: {}", tb.name))).await?;
-					chn.send(bytes!("-- ------------------------------")).await?;
-					chn.send(bytes!("")).await?;
-					// Fetch records
-					let beg = crate::key::thing::prefix(ns, db, &tb.name);
-					let end = crate::key::thing::suffix(ns, db, &tb.name);
-					let mut nxt: Option<ScanPage<Vec<u8>>> = Some(ScanPage::from(beg..end));
-					while nxt.is_some() {
-						let res = self.scan_paged(nxt.unwrap(), 1000).await?;
-						nxt = res.next_page;
-						let res = res.values;
-						if res.is_empty() {
-							break;
-						}
-						// Loop over results
-						for (k, v) in res.into_iter() {
-							// Parse the key and the value
-							let k: crate::key::thing::Thing = (&k).into();
-							let v: Value = (&v).into();
-							let t = Thing::from((k.tb, k.id));
-							// Check if this is a graph edge
-							match (v.pick(&*EDGE), v.pick(&*IN), v.pick(&*OUT)) {
-								// This is a graph edge record
-								(Value::Bool(true), Value::Thing(l), Value::Thing(r)) => {
-									let sql = format!("RELATE {l} -> {t} -> {r} CONTENT {v};",);
-									chn.send(bytes!(sql)).await?;
-								}
-								// This is a normal record
-								_ => {
-									let sql = format!("UPDATE {t} CONTENT {v};");
-									chn.send(bytes!(sql)).await?;
-								}
-							}
-						}
-						continue;
-					}
-					chn.send(bytes!("")).await?;
-				}
-				// Commit transaction
-				chn.send(bytes!("-- ------------------------------")).await?;
-				chn.send(bytes!("-- TRANSACTION")).await?;
-				chn.send(bytes!("-- ------------------------------")).await?;
-				chn.send(bytes!("")).await?;
-				chn.send(bytes!("COMMIT TRANSACTION;")).await?;
-				chn.send(bytes!("")).await?;
-			}
-		}
-		// Everything exported
 		Ok(())
 	}
 
@@ -3029,6 +3908,54 @@ impl Transaction {
 	}
 }
 
+/// Writes the start of the exported record data as binary SQL
+pub(super) async fn export_begin(chn: &Sender<Vec<u8>>) -> Result<(), Error> {
+	chn.send(bytes!("-- ------------------------------")).await?;
+	chn.send(bytes!("-- TRANSACTION")).await?;
+	chn.send(bytes!("-- ------------------------------")).await?;
+	chn.send(bytes!("")).await?;
+	chn.send(bytes!("BEGIN TRANSACTION;")).await?;
+	chn.send(bytes!("")).await?;
+	Ok(())
+}
+
+/// Writes the end of the exported record data as binary SQL
+pub(super) async fn export_commit(chn: &Sender<Vec<u8>>) -> Result<(), Error> {
+	chn.send(bytes!("-- ------------------------------")).await?;
+	chn.send(bytes!("-- TRANSACTION")).await?;
+	chn.send(bytes!("-- ------------------------------")).await?;
+	chn.send(bytes!("")).await?;
+	chn.send(bytes!("COMMIT TRANSACTION;")).await?;
+	chn.send(bytes!("")).await?;
+	Ok(())
+}
+
+/// Writes the header of the exported records of a table as binary SQL
+pub(super) async fn export_table_data(chn: &Sender<Vec<u8>>, tb: &str) -> Result<(), Error> {
+	chn.send(bytes!("-- ------------------------------")).await?;
+	chn.send(bytes!(format!("-- TABLE DATA: {}", escape_ident(tb)))).await?;
+	chn.send(bytes!("-- ------------------------------")).await?;
+	chn.send(bytes!("")).await?;
+	Ok(())
+}
+
+/// Converts a stored record into a binary SQL statement which recreates it
+pub(super) fn export_record(k: &Key, v: &Val) -> Vec<u8> {
+	// Parse the key and the value
+	let k: crate::key::thing::Thing = k.into();
+	let v: Value = v.into();
+	let t = Thing::from((k.tb, k.id));
+	// Check if this is a graph edge
+	match (v.pick(&*EDGE), v.pick(&*IN), v.pick(&*OUT)) {
+		// This is a graph edge record
+		(Value::Bool(true), Value::Thing(l), Value::Thing(r)) => {
+			bytes!(format!("RELATE {l} -> {t} -> {r} CONTENT {v};"))
+		}
+		// This is a normal record
+		_ => bytes!(format!("UPDATE {t} CONTENT {v};")),
+	}
+}
+
 #[cfg(test)]
 #[cfg(feature = "kv-mem")]
 mod tests {
@@ -3317,6 +4244,10 @@ mod tx_test {
 				archived: None,
 				session: Some(Value::None),
 				auth: None,
+				window: None,
+				when: None,
+				compact: None,
+				count: false,
 			},
 		};
 		tx.pre_commit_register_async_event(TrackedResult::LiveQuery(lq_entry.clone())).unwrap();
@@ -0,0 +1,180 @@
+//! Versioning of the storage format, and migrations between storage format versions.
+//!
+//! The version of the storage format is stored in the datastore, and is checked
+//! when the datastore is bootstrapped. Datastores which were written with an older
+//! storage format are upgraded by applying each migration in turn. Migrations are
+//! applied in batches, and the progress is stored after each batch, so that a
+//! migration resumes from where it left off if the node is restarted. Datastores
+//! which were written with a newer storage format are refused, as downgrading a
+//! datastore is not supported.
+use crate::err::Error;
+use crate::kvs::{Key, Val};
+use derive::Store;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+/// The version of the storage format which is written by this release
+pub const STORAGE_VERSION: u16 = 1;
+
+/// The storage format version of datastores which were written before the storage format was versioned
+pub(crate) const UNVERSIONED: u16 = 1;
+
+/// The change which a migration makes to a single key
+#[derive(Debug)]
+#[allow(dead_code)]
+#[non_exhaustive]
+pub(crate) enum Change {
+	/// The key and its value remain as they are
+	Keep,
+	/// The key is removed
+	Delete,
+	/// The key is replaced with a new key and value
+	Replace(Key, Val),
+}
+
+/// A change to the layout of stored keys, which upgrades a datastore to a new storage format version
+pub(crate) struct Migration {
+	/// The storage format version which this migration upgrades to
+	pub version: u16,
+	/// A description of the migration, as reported in the logs
+	pub name: &'static str,
+	/// The range of keys which are migrated
+	pub range: fn() -> Range<Key>,
+	/// Migrate a single key and its value. Any replacement key must either sort outside
+	/// of the migrated range, or be left unchanged when it is migrated a second time.
+	pub migrate: fn(&[u8], Val) -> Result<Change, Error>,
+}
+
+/// The migrations between storage format versions, in order of version. When the layout of
+/// a key changes, [`STORAGE_VERSION`] is increased, and a migration to that version is added.
+pub(crate) static MIGRATIONS: &[Migration] = &[];
+
+/// The progress of the storage format migration which is being applied
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
+#[non_exhaustive]
+pub struct Progress {
+	/// The storage format version which the migration upgrades to
+	pub version: u16,
+	/// The last key which was processed
+	pub cursor: Option<Vec<u8>>,
+	/// The number of keys which have been processed
+	pub processed: u64,
+	/// The number of keys which have been changed
+	pub changed: u64,
+}
+
+/// Encode a storage format version, as it is stored in the datastore
+pub(crate) fn encode(version: u16) -> Val {
+	version.to_be_bytes().to_vec()
+}
+
+/// Decode a storage format version, as it is stored in the datastore
+pub(crate) fn decode(val: &[u8]) -> Result<u16, Error> {
+	match <[u8; 2]>::try_from(val) {
+		Ok(v) => Ok(u16::from_be_bytes(v)),
+		Err(_) => Err(Error::Internal(format!("Invalid storage format version {val:?}"))),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::key::root::{mg::Mg, sv::Sv};
+	use crate::kvs::{Datastore, LockType::*, TransactionType::*};
+
+	// Renames the `old` keys to `new` keys, and removes the `tmp` keys
+	static RENAME: &[Migration] = &[Migration {
+		version: 2,
+		name: "Rename the old keys",
+		range: || b"o".to_vec()..b"u".to_vec(),
+		migrate: |k, v| match k {
+			[b'o', b'l', b'd', rest @ ..] => Ok(Change::Replace([b"new", rest].concat(), v)),
+			[b't', ..] => Ok(Change::Delete),
+			_ => Ok(Change::Keep),
+		},
+	}];
+
+	async fn version(ds: &Datastore) -> Option<u16> {
+		let mut tx = ds.transaction(Read, Optimistic).await.unwrap();
+		let v = tx.get(Sv::new()).await.unwrap().map(|v| decode(&v).unwrap());
+		tx.cancel().await.unwrap();
+		v
+	}
+
+	#[tokio::test]
+	async fn new_datastores_are_versioned() {
+		let ds = Datastore::new("memory").await.unwrap();
+		ds.bootstrap().await.unwrap();
+		assert_eq!(version(&ds).await, Some(STORAGE_VERSION));
+	}
+
+	#[tokio::test]
+	async fn migrations_are_applied_in_batches() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let mut tx = ds.transaction(Write, Optimistic).await.unwrap();
+		for i in 0..2500u32 {
+			tx.set([b"old".as_slice(), &i.to_be_bytes()].concat(), i.to_be_bytes().to_vec())
+				.await
+				.unwrap();
+		}
+		tx.set(b"tmp".to_vec(), vec![]).await.unwrap();
+		tx.set(b"other".to_vec(), vec![]).await.unwrap();
+		tx.commit().await.unwrap();
+		// A datastore without a version is upgraded from the first version
+		ds.upgrade_storage(RENAME, 2).await.unwrap();
+		assert_eq!(version(&ds).await, Some(2));
+		let mut tx = ds.transaction(Read, Optimistic).await.unwrap();
+		assert!(tx.getr(b"old".to_vec()..b"olf".to_vec(), 10).await.unwrap().is_empty());
+		assert_eq!(tx.getr(b"new".to_vec()..b"nex".to_vec(), 5000).await.unwrap().len(), 2500);
+		assert!(tx.get(b"tmp".to_vec()).await.unwrap().is_none());
+		assert!(tx.get(b"other".to_vec()).await.unwrap().is_some());
+		assert!(tx.get(Mg::new()).await.unwrap().is_none());
+		tx.cancel().await.unwrap();
+		// Applying the migration again has no effect
+		ds.upgrade_storage(RENAME, 2).await.unwrap();
+		assert_eq!(version(&ds).await, Some(2));
+	}
+
+	#[tokio::test]
+	async fn migrations_resume_from_their_progress() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let mut tx = ds.transaction(Write, Optimistic).await.unwrap();
+		tx.set(Sv::new(), encode(1)).await.unwrap();
+		for k in ["old1", "old2", "old3"] {
+			tx.set(k.as_bytes().to_vec(), vec![]).await.unwrap();
+		}
+		// The migration was interrupted after the second key
+		let pg = Progress {
+			version: 2,
+			cursor: Some(b"old2".to_vec()),
+			processed: 2,
+			changed: 2,
+		};
+		tx.set(Mg::new(), pg).await.unwrap();
+		tx.commit().await.unwrap();
+		ds.upgrade_storage(RENAME, 2).await.unwrap();
+		let mut tx = ds.transaction(Read, Optimistic).await.unwrap();
+		assert!(tx.get(b"old1".to_vec()).await.unwrap().is_some());
+		assert!(tx.get(b"old3".to_vec()).await.unwrap().is_none());
+		assert!(tx.get(b"new3".to_vec()).await.unwrap().is_some());
+		tx.cancel().await.unwrap();
+	}
+
+	#[tokio::test]
+	async fn newer_datastores_are_refused() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let mut tx = ds.transaction(Write, Optimistic).await.unwrap();
+		tx.set(Sv::new(), encode(STORAGE_VERSION + 1)).await.unwrap();
+		tx.commit().await.unwrap();
+		let res = ds.bootstrap().await;
+		assert!(matches!(
+			res,
+			Err(Error::UnsupportedStorageVersion {
+				found,
+				supported: STORAGE_VERSION,
+			}) if found == STORAGE_VERSION + 1
+		));
+	}
+}
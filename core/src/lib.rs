@@ -43,7 +43,7 @@ pub mod syn;
 #[cfg(feature = "ml")]
 #[doc(hidden)]
 pub use surrealml as ml;
-#[cfg(feature = "ml")]
+#[cfg(feature = "obs")]
 #[doc(hidden)]
 pub mod obs;
 
@@ -8,15 +8,26 @@ use object_store::local::LocalFileSystem;
 #[cfg(target_arch = "wasm32")]
 use object_store::memory::InMemory;
 use object_store::parse_url;
+#[cfg(not(target_arch = "wasm32"))]
+use object_store::parse_url_opts;
 use object_store::path::Path;
+#[cfg(not(target_arch = "wasm32"))]
+use object_store::MultipartId;
 use object_store::ObjectStore;
 use once_cell::sync::Lazy;
 use sha1::{Digest, Sha1};
 use std::env;
 use std::fs;
 use std::sync::Arc;
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::io::{AsyncWrite, AsyncWriteExt};
 use url::Url;
 
+/// The prefix of the environment variables which configure the object stores written by `INTO OUTFILE`,
+/// for example `SURREAL_OUTFILE_AWS_ACCESS_KEY_ID` or `SURREAL_OUTFILE_AWS_REGION`
+#[cfg(not(target_arch = "wasm32"))]
+const OUTFILE_ENV_PREFIX: &str = "SURREAL_OUTFILE_";
+
 static STORE: Lazy<Arc<dyn ObjectStore>> =
 	Lazy::new(|| match std::env::var("SURREAL_OBJECT_STORE") {
 		Ok(url) => {
@@ -102,6 +113,55 @@ pub async fn del(file: &str) -> Result<(), Error> {
 	Ok(STORE.delete(&Path::from(file)).await?)
 }
 
+/// Streams data into an object, at a url such as `s3://bucket/path.jsonl`,
+/// using an object store configured from the server environment.
+#[cfg(not(target_arch = "wasm32"))]
+pub struct Writer {
+	store: Box<dyn ObjectStore>,
+	path: Path,
+	id: MultipartId,
+	sink: Box<dyn AsyncWrite + Unpin + Send>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Writer {
+	/// Starts a multipart upload to the object at the specified url
+	pub async fn open(url: &Url) -> Result<Self, Error> {
+		// Credentials and other options are taken from the server environment
+		let options = env::vars().filter_map(|(k, v)| {
+			k.strip_prefix(OUTFILE_ENV_PREFIX).map(|k| (k.to_ascii_lowercase(), v))
+		});
+		let (store, path) = parse_url_opts(url, options).map_err(|e| Error::InvalidOutfile {
+			path: url.to_string(),
+			message: e.to_string(),
+		})?;
+		let (id, sink) = store.put_multipart(&path).await?;
+		Ok(Self {
+			store,
+			path,
+			id,
+			sink,
+		})
+	}
+
+	/// Writes the bytes to the object
+	pub async fn write(&mut self, data: &[u8]) -> Result<(), Error> {
+		Ok(self.sink.write_all(data).await?)
+	}
+
+	/// Completes the upload, making the object visible
+	pub async fn finish(mut self) -> Result<(), Error> {
+		Ok(self.sink.shutdown().await?)
+	}
+
+	/// Abandons the upload, discarding any parts which have been written
+	pub async fn abort(self) {
+		if let Err(e) = self.store.abort_multipart(&self.path, &self.id).await {
+			warn!("Failed to abort the upload to {}: {e}", self.path);
+		}
+	}
+}
+
 /// Hashes the bytes of a file to a string for the storage of a file.
 pub fn hash(data: &[u8]) -> String {
 	let mut hasher = Sha1::new();
@@ -12,6 +12,10 @@ pub struct EngineOptions {
 	/// The size of batches being requested per update in order to catch up a live query
 	pub live_query_catchup_size: u32,
 	pub tick_interval: Duration,
+	/// The interval at which this node writes its heartbeat
+	pub heartbeat_interval: Duration,
+	/// The interval at which live query windows are checked for closing, and compacted notifications are sent
+	pub window_interval: Duration,
 }
 
 impl Default for EngineOptions {
@@ -20,6 +24,8 @@ impl Default for EngineOptions {
 			new_live_queries_per_transaction: 100,
 			live_query_catchup_size: 1000,
 			tick_interval: Duration::from_secs(1),
+			heartbeat_interval: Duration::from_secs(1),
+			window_interval: Duration::from_millis(10),
 		}
 	}
 }
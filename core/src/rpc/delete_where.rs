@@ -0,0 +1,170 @@
+//! Deletion of the records of a table which match a condition, in batches.
+//!
+//! Each batch is deleted in its own transaction, so that a large deletion does
+//! not hold a single long running transaction open, and so that the deletion
+//! can be cancelled between batches. The progress of the deletion is sent to
+//! the connection which started it after each batch:
+//!
+//! ```text
+//! { id, status, deleted, last, error }
+//! ```
+//!
+//! The status is `running` after each batch which deleted records, and is
+//! then `completed`, `cancelled`, or `failed` once the deletion has finished.
+use crate::dbs::Session;
+use crate::err::Error;
+use crate::kvs::Datastore;
+use crate::sql::paths::ID;
+use crate::sql::statements::{DeleteStatement, SelectStatement};
+use crate::sql::{
+	Cond, Field, Fields, Idiom, Limit, Output, Query, Statement, Statements, Subquery, Table,
+	Thing, Value, Values,
+};
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+use uuid::Uuid;
+
+/// The number of records which are deleted in each batch, when not specified
+pub const DEFAULT_BATCH_SIZE: u32 = 1000;
+
+/// The state of a deletion
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum DeleteStatus {
+	/// More records may remain to be deleted
+	Running,
+	/// All of the matching records were deleted
+	Completed,
+	/// The deletion was cancelled before all of the matching records were deleted
+	Cancelled,
+	/// A batch could not be deleted
+	Failed(String),
+}
+
+impl Display for DeleteStatus {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Running => write!(f, "running"),
+			Self::Completed => write!(f, "completed"),
+			Self::Cancelled => write!(f, "cancelled"),
+			Self::Failed(_) => write!(f, "failed"),
+		}
+	}
+}
+
+/// The progress of a deletion, which is sent after each batch
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct DeleteProgress {
+	/// The id of the deletion
+	pub id: Uuid,
+	/// The state of the deletion
+	pub status: DeleteStatus,
+	/// The number of records which have been deleted so far
+	pub deleted: u64,
+	/// The last record which was deleted
+	pub last: Option<Thing>,
+}
+
+impl From<DeleteProgress> for Value {
+	fn from(v: DeleteProgress) -> Self {
+		let mut out: BTreeMap<String, Value> = map! {
+			"id".to_string() => Value::Uuid(v.id.into()),
+			"status".to_string() => v.status.to_string().into(),
+			"deleted".to_string() => v.deleted.into(),
+			"last".to_string() => v.last.map(Value::from).unwrap_or_default(),
+		};
+		if let DeleteStatus::Failed(error) = v.status {
+			out.insert("error".to_string(), error.into());
+		}
+		out.into()
+	}
+}
+
+/// A deletion of the records of a table which match a condition
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct DeleteWhere {
+	/// The id of this deletion, which its progress is sent with
+	pub id: Uuid,
+	// The session which started this deletion
+	session: Session,
+	// The parameters of the connection which started this deletion
+	vars: BTreeMap<String, Value>,
+	// The statement which deletes a single batch of records
+	stm: DeleteStatement,
+	// The number of records which have been deleted so far
+	deleted: u64,
+	// The last record which was deleted
+	last: Option<Thing>,
+}
+
+impl DeleteWhere {
+	/// Prepare a deletion of the records of a table which match a condition
+	pub fn new(
+		session: Session,
+		vars: BTreeMap<String, Value>,
+		table: Table,
+		cond: Option<Value>,
+		batch_size: u32,
+	) -> Self {
+		// Select the ids of the next batch of matching records
+		let select = SelectStatement {
+			expr: Fields(
+				vec![Field::Single {
+					expr: Value::Idiom(Idiom::from(&ID[..])),
+					alias: None,
+				}],
+				true,
+			),
+			what: Values(vec![Value::Table(table)]),
+			cond: cond.map(Cond),
+			limit: Some(Limit(Value::from(batch_size))),
+			..Default::default()
+		};
+		// Delete the selected records, returning the records which were deleted
+		let stm = DeleteStatement {
+			what: Values(vec![Value::Subquery(Box::new(Subquery::Select(select)))]),
+			output: Some(Output::Before),
+			..Default::default()
+		};
+		Self {
+			id: Uuid::new_v4(),
+			session,
+			vars,
+			stm,
+			deleted: 0,
+			last: None,
+		}
+	}
+
+	/// Delete the next batch of records in its own transaction
+	pub async fn next(&mut self, kvs: &Datastore) -> Result<DeleteProgress, Error> {
+		let query = Query(Statements(vec![Statement::Delete(self.stm.clone())]));
+		let mut res = kvs.process(query, &self.session, Some(self.vars.clone())).await?;
+		let deleted = match res.remove(0).result? {
+			Value::Array(v) => v.0,
+			_ => Vec::new(),
+		};
+		// Records which can not be deleted are not returned, so
+		// the deletion is complete once a batch deletes nothing
+		if deleted.is_empty() {
+			return Ok(self.progress(DeleteStatus::Completed));
+		}
+		self.deleted += deleted.len() as u64;
+		if let Some(Value::Thing(v)) = deleted.last().map(|v| v.pick(&*ID)) {
+			self.last = Some(v);
+		}
+		Ok(self.progress(DeleteStatus::Running))
+	}
+
+	/// The progress of this deletion
+	pub fn progress(&self, status: DeleteStatus) -> DeleteProgress {
+		DeleteProgress {
+			id: self.id,
+			status,
+			deleted: self.deleted,
+			last: self.last.clone(),
+		}
+	}
+}
@@ -10,6 +10,10 @@ pub enum Method {
 	Authenticate,
 	Kill,
 	Live,
+	Subscribe,
+	Unsubscribe,
+	DeleteWhere,
+	CancelDeleteWhere,
 	Set,
 	Unset,
 	Select,
@@ -26,6 +30,36 @@ pub enum Method {
 }
 
 impl Method {
+	/// All of the methods which can be called over RPC
+	pub const ALL: [Method; 26] = [
+		Method::Ping,
+		Method::Info,
+		Method::Use,
+		Method::Signup,
+		Method::Signin,
+		Method::Invalidate,
+		Method::Authenticate,
+		Method::Kill,
+		Method::Live,
+		Method::Subscribe,
+		Method::Unsubscribe,
+		Method::DeleteWhere,
+		Method::CancelDeleteWhere,
+		Method::Set,
+		Method::Unset,
+		Method::Select,
+		Method::Insert,
+		Method::Create,
+		Method::Update,
+		Method::Merge,
+		Method::Patch,
+		Method::Delete,
+		Method::Version,
+		Method::Query,
+		Method::Relate,
+		Method::Run,
+	];
+
 	pub fn parse<S>(s: S) -> Self
 	where
 		S: AsRef<str>,
@@ -40,6 +74,10 @@ impl Method {
 			"authenticate" => Self::Authenticate,
 			"kill" => Self::Kill,
 			"live" => Self::Live,
+			"subscribe" => Self::Subscribe,
+			"unsubscribe" => Self::Unsubscribe,
+			"delete_where" => Self::DeleteWhere,
+			"cancel_delete_where" => Self::CancelDeleteWhere,
 			"let" | "set" => Self::Set,
 			"unset" => Self::Unset,
 			"select" => Self::Select,
@@ -71,6 +109,10 @@ impl Method {
 			Self::Authenticate => "authenticate",
 			Self::Kill => "kill",
 			Self::Live => "live",
+			Self::Subscribe => "subscribe",
+			Self::Unsubscribe => "unsubscribe",
+			Self::DeleteWhere => "delete_where",
+			Self::CancelDeleteWhere => "cancel_delete_where",
 			Self::Set => "set",
 			Self::Unset => "unset",
 			Self::Select => "select",
@@ -1,11 +1,14 @@
 pub mod args;
 pub mod basic_context;
+pub mod delete_where;
 pub mod format;
 pub mod method;
+mod output;
 pub mod request;
 mod response;
 pub mod rpc_context;
 mod rpc_error;
+pub mod signature;
 
 pub use basic_context::BasicRpcContext;
 pub use response::Data;
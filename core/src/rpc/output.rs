@@ -0,0 +1,48 @@
+use crate::sql::{Field, Fields, Output, Value};
+
+use super::rpc_error::RpcError;
+
+/// Parse the options of a write method, returning the `RETURN` clause to use.
+///
+/// The options are an object, where `return` is either one of `none`, `null`,
+/// `before`, `after`, `diff`, or `count`, or an array of the fields to return from the
+/// written records. Without any options the default clause is returned.
+pub(crate) fn output(opts: Value, default: Output) -> Result<Output, RpcError> {
+	let opts = match opts {
+		Value::None | Value::Null => return Ok(default),
+		Value::Object(v) => v,
+		_ => return Err(RpcError::InvalidParams),
+	};
+	if opts.keys().any(|k| k != "return") {
+		return Err(RpcError::InvalidParams);
+	}
+	match opts.0.into_values().next() {
+		None | Some(Value::None) | Some(Value::Null) => Ok(default),
+		Some(Value::Strand(v)) => match v.to_lowercase().as_str() {
+			"none" => Ok(Output::None),
+			"null" => Ok(Output::Null),
+			"before" => Ok(Output::Before),
+			"after" => Ok(Output::After),
+			"diff" => Ok(Output::Diff),
+			"count" => Ok(Output::Count),
+			_ => Err(RpcError::InvalidParams),
+		},
+		Some(Value::Array(v)) if !v.is_empty() => {
+			let fields = v
+				.into_iter()
+				.map(|v| match v {
+					Value::Strand(v) => match crate::syn::idiom(&v) {
+						Ok(v) => Ok(Field::Single {
+							expr: Value::Idiom(v),
+							alias: None,
+						}),
+						Err(_) => Err(RpcError::InvalidParams),
+					},
+					_ => Err(RpcError::InvalidParams),
+				})
+				.collect::<Result<Vec<_>, _>>()?;
+			Ok(Output::Fields(Fields(fields, false)))
+		}
+		_ => Err(RpcError::InvalidParams),
+	}
+}
@@ -4,12 +4,19 @@ use uuid::Uuid;
 
 use crate::{
 	dbs::{QueryType, Response, Session},
+	iam::{check::check_ns_db, Action, ResourceKind},
 	kvs::Datastore,
 	rpc::args::Take,
-	sql::{Array, Function, Model, Statement, Strand, Value},
+	sql::{Array, Function, Model, Number, Output, Statement, Strand, Value},
 };
 
-use super::{method::Method, response::Data, rpc_error::RpcError};
+use super::{
+	delete_where::{DeleteWhere, DEFAULT_BATCH_SIZE},
+	method::Method,
+	output::output,
+	response::Data,
+	rpc_error::RpcError,
+};
 
 macro_rules! mrg {
 	($($m:expr, $x:expr)+) => {{
@@ -34,6 +41,34 @@ pub trait RpcContext {
 	fn handle_kill(&self, _lqid: &Uuid) -> impl std::future::Future<Output = ()> + Send {
 		async { unreachable!() }
 	}
+	fn handle_subscribe(
+		&self,
+		_ns: String,
+		_db: String,
+		_channel: String,
+	) -> impl std::future::Future<Output = ()> + Send {
+		async { unreachable!() }
+	}
+	fn handle_unsubscribe(
+		&self,
+		_ns: String,
+		_db: String,
+		_channel: String,
+	) -> impl std::future::Future<Output = ()> + Send {
+		async { unreachable!() }
+	}
+	fn handle_delete_where(
+		&self,
+		_job: DeleteWhere,
+	) -> impl std::future::Future<Output = ()> + Send {
+		async { unreachable!() }
+	}
+	fn handle_cancel_delete_where(
+		&self,
+		_id: &Uuid,
+	) -> impl std::future::Future<Output = bool> + Send {
+		async { unreachable!() }
+	}
 
 	async fn execute(&mut self, method: Method, params: Array) -> Result<Data, RpcError> {
 		match method {
@@ -48,6 +83,16 @@ pub trait RpcContext {
 			}
 			Method::Kill => self.kill(params).await.map(Into::into).map_err(Into::into),
 			Method::Live => self.live(params).await.map(Into::into).map_err(Into::into),
+			Method::Subscribe => self.subscribe(params).await.map(Into::into).map_err(Into::into),
+			Method::Unsubscribe => {
+				self.unsubscribe(params).await.map(Into::into).map_err(Into::into)
+			}
+			Method::DeleteWhere => {
+				self.delete_where(params).await.map(Into::into).map_err(Into::into)
+			}
+			Method::CancelDeleteWhere => {
+				self.cancel_delete_where(params).await.map(Into::into).map_err(Into::into)
+			}
 			Method::Set => self.set(params).await.map(Into::into).map_err(Into::into),
 			Method::Unset => self.unset(params).await.map(Into::into).map_err(Into::into),
 			Method::Select => self.select(params).await.map(Into::into).map_err(Into::into),
@@ -230,6 +275,86 @@ pub trait RpcContext {
 		response.result.map_err(Into::into)
 	}
 
+	// ------------------------------
+	// Methods for hook channels
+	// ------------------------------
+
+	async fn subscribe(&mut self, params: Array) -> Result<impl Into<Data>, RpcError> {
+		let Ok(Value::Strand(channel)) = params.needs_one() else {
+			return Err(RpcError::InvalidParams);
+		};
+		// Messages can only be delivered over a connection which supports notifications
+		if !Self::LQ_SUPPORT {
+			return Err(RpcError::LqNotSuported);
+		}
+		// Channels belong to the selected database
+		let (ns, db) = check_ns_db(self.session())?;
+		// The messages of hooks are for the users of the database
+		self.kvs().check(self.session(), Action::View, ResourceKind::Any.on_db(&ns, &db))?;
+		self.handle_subscribe(ns, db, channel.0).await;
+		Ok(Value::None)
+	}
+
+	async fn unsubscribe(&mut self, params: Array) -> Result<impl Into<Data>, RpcError> {
+		let Ok(Value::Strand(channel)) = params.needs_one() else {
+			return Err(RpcError::InvalidParams);
+		};
+		if !Self::LQ_SUPPORT {
+			return Err(RpcError::LqNotSuported);
+		}
+		let (ns, db) = check_ns_db(self.session())?;
+		self.handle_unsubscribe(ns, db, channel.0).await;
+		Ok(Value::None)
+	}
+
+	// ------------------------------
+	// Methods for batched deletions
+	// ------------------------------
+
+	async fn delete_where(&mut self, params: Array) -> Result<impl Into<Data>, RpcError> {
+		let (what, cond, batch) = params.needs_one_two_or_three()?;
+		// Progress can only be sent over a connection which supports notifications
+		if !Self::LQ_SUPPORT {
+			return Err(RpcError::LqNotSuported);
+		}
+		check_ns_db(self.session())?;
+		// Records are only deleted from a single table
+		let Value::Table(table) = what.could_be_table() else {
+			return Err(RpcError::InvalidParams);
+		};
+		// The condition is parsed as an expression
+		let cond = match cond {
+			Value::None | Value::Null => None,
+			Value::Strand(v) => Some(crate::syn::value(&v.0)?),
+			_ => return Err(RpcError::InvalidParams),
+		};
+		// The batch size must be a positive integer
+		let batch = match batch {
+			Value::None | Value::Null => DEFAULT_BATCH_SIZE,
+			Value::Number(Number::Int(v)) if v > 0 => {
+				u32::try_from(v).map_err(|_| RpcError::InvalidParams)?
+			}
+			_ => return Err(RpcError::InvalidParams),
+		};
+		let job = DeleteWhere::new(self.session().clone(), self.vars().clone(), table, cond, batch);
+		let id = job.id;
+		// The deletion continues in the background
+		self.handle_delete_where(job).await;
+		Ok(Value::Uuid(id.into()))
+	}
+
+	async fn cancel_delete_where(&mut self, params: Array) -> Result<impl Into<Data>, RpcError> {
+		let id = match params.needs_one() {
+			Ok(Value::Uuid(v)) => v.0,
+			Ok(Value::Strand(v)) => Uuid::parse_str(&v.0).map_err(|_| RpcError::InvalidParams)?,
+			_ => return Err(RpcError::InvalidParams),
+		};
+		if !Self::LQ_SUPPORT {
+			return Err(RpcError::LqNotSuported);
+		}
+		Ok(Value::Bool(self.handle_cancel_delete_where(&id).await))
+	}
+
 	// ------------------------------
 	// Methods for selecting
 	// ------------------------------
@@ -292,16 +417,18 @@ pub trait RpcContext {
 	// ------------------------------
 
 	async fn create(&self, params: Array) -> Result<impl Into<Data>, RpcError> {
-		let Ok((what, data)) = params.needs_one_or_two() else {
+		let Ok((what, data, opts)) = params.needs_one_two_or_three() else {
 			return Err(RpcError::InvalidParams);
 		};
 		// Return a single result?
 		let one = what.is_thing();
+		// Specify the output of the query
+		let out = output(opts, Output::After)?;
 		// Specify the SQL query string
 		let sql = if data.is_none_or_null() {
-			"CREATE $what RETURN AFTER"
+			format!("CREATE $what {out}")
 		} else {
-			"CREATE $what CONTENT $data RETURN AFTER"
+			format!("CREATE $what CONTENT $data {out}")
 		};
 		// Specify the query parameters
 		let var = Some(map! {
@@ -310,7 +437,7 @@ pub trait RpcContext {
 			=> &self.vars()
 		});
 		// Execute the query on the database
-		let mut res = self.kvs().execute(sql, self.session(), var).await?;
+		let mut res = self.kvs().execute(&sql, self.session(), var).await?;
 		// Extract the first query result
 		let res = match one {
 			true => res.remove(0).result?.first(),
@@ -325,16 +452,18 @@ pub trait RpcContext {
 	// ------------------------------
 
 	async fn update(&self, params: Array) -> Result<impl Into<Data>, RpcError> {
-		let Ok((what, data)) = params.needs_one_or_two() else {
+		let Ok((what, data, opts)) = params.needs_one_two_or_three() else {
 			return Err(RpcError::InvalidParams);
 		};
 		// Return a single result?
 		let one = what.is_thing();
+		// Specify the output of the query
+		let out = output(opts, Output::After)?;
 		// Specify the SQL query string
 		let sql = if data.is_none_or_null() {
-			"UPDATE $what RETURN AFTER"
+			format!("UPDATE $what {out}")
 		} else {
-			"UPDATE $what CONTENT $data RETURN AFTER"
+			format!("UPDATE $what CONTENT $data {out}")
 		};
 		// Specify the query parameters
 		let var = Some(map! {
@@ -343,7 +472,7 @@ pub trait RpcContext {
 			=> &self.vars()
 		});
 		// Execute the query on the database
-		let mut res = self.kvs().execute(sql, self.session(), var).await?;
+		let mut res = self.kvs().execute(&sql, self.session(), var).await?;
 		// Extract the first query result
 		let res = match one {
 			true => res.remove(0).result?.first(),
@@ -358,16 +487,18 @@ pub trait RpcContext {
 	// ------------------------------
 
 	async fn merge(&self, params: Array) -> Result<impl Into<Data>, RpcError> {
-		let Ok((what, data)) = params.needs_one_or_two() else {
+		let Ok((what, data, opts)) = params.needs_one_two_or_three() else {
 			return Err(RpcError::InvalidParams);
 		};
 		// Return a single result?
 		let one = what.is_thing();
+		// Specify the output of the query
+		let out = output(opts, Output::After)?;
 		// Specify the SQL query string
 		let sql = if data.is_none_or_null() {
-			"UPDATE $what RETURN AFTER"
+			format!("UPDATE $what {out}")
 		} else {
-			"UPDATE $what MERGE $data RETURN AFTER"
+			format!("UPDATE $what MERGE $data {out}")
 		};
 		// Specify the query parameters
 		let var = Some(map! {
@@ -376,7 +507,7 @@ pub trait RpcContext {
 			=> &self.vars()
 		});
 		// Execute the query on the database
-		let mut res = self.kvs().execute(sql, self.session(), var).await?;
+		let mut res = self.kvs().execute(&sql, self.session(), var).await?;
 		// Extract the first query result
 		let res = match one {
 			true => res.remove(0).result?.first(),
@@ -391,16 +522,19 @@ pub trait RpcContext {
 	// ------------------------------
 
 	async fn patch(&self, params: Array) -> Result<impl Into<Data>, RpcError> {
-		let Ok((what, data, diff)) = params.needs_one_two_or_three() else {
+		let Ok((what, data, opts)) = params.needs_one_two_or_three() else {
 			return Err(RpcError::InvalidParams);
 		};
 		// Return a single result?
 		let one = what.is_thing();
-		// Specify the SQL query string
-		let sql = match diff.is_true() {
-			true => "UPDATE $what PATCH $data RETURN DIFF",
-			false => "UPDATE $what PATCH $data RETURN AFTER",
+		// Specify the output of the query, where a boolean selects whether to return the diff
+		let out = match opts {
+			Value::Bool(true) => Output::Diff,
+			Value::Bool(false) => Output::After,
+			opts => output(opts, Output::After)?,
 		};
+		// Specify the SQL query string
+		let sql = format!("UPDATE $what PATCH $data {out}");
 		// Specify the query parameters
 		let var = Some(map! {
 			String::from("what") => what.could_be_table(),
@@ -408,7 +542,7 @@ pub trait RpcContext {
 			=> &self.vars()
 		});
 		// Execute the query on the database
-		let mut res = self.kvs().execute(sql, self.session(), var).await?;
+		let mut res = self.kvs().execute(&sql, self.session(), var).await?;
 		// Extract the first query result
 		let res = match one {
 			true => res.remove(0).result?.first(),
@@ -21,6 +21,8 @@ pub enum RpcError {
 	BadLQConfig,
 	#[error("Error: {0}")]
 	Thrown(String),
+	#[error("The request signature is invalid, because {0}")]
+	InvalidSignature(&'static str),
 }
 
 impl From<err::Error> for RpcError {
@@ -0,0 +1,223 @@
+//! Signing of RPC messages, and protection against requests which are replayed.
+//!
+//! When TLS is terminated before requests reach the server, the requests can be
+//! changed or sent again by anything between the terminating proxy and the server.
+//! When a signing key is configured, every RPC request must start with a line which
+//! signs the request with the key, followed by the encoded request itself:
+//!
+//! ```text
+//! v1,{timestamp},{nonce},{signature}\n{request}
+//! ```
+//!
+//! The timestamp is the time at which the request was signed, in milliseconds since
+//! the Unix epoch. The nonce is a unique value of up to 64 visible ASCII characters,
+//! which does not contain a comma. The signature is the hex encoded HMAC-SHA256 of the
+//! line up to and including the comma before the signature, followed by the request.
+//! Requests which were signed more than the configured window away from the time on
+//! the server are rejected, as are requests which reuse the nonce of a request which
+//! was accepted within the window.
+//!
+//! The responses and notifications which the server sends are signed in the same way,
+//! with a random nonce, so that clients can check that they were not changed either.
+use super::RpcError;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use uuid::Uuid;
+
+/// The version of the signature line
+const VERSION: &str = "v1";
+
+/// The maximum length of a nonce
+const MAX_NONCE_LENGTH: usize = 64;
+
+/// The maximum length of a signature line
+const MAX_LINE_LENGTH: usize = 256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Sign an encoded request, returning the signed request which is sent to the server
+pub fn sign(key: &[u8], timestamp: u64, nonce: &str, request: &[u8]) -> Vec<u8> {
+	let prefix = format!("{VERSION},{timestamp},{nonce},");
+	let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length");
+	mac.update(prefix.as_bytes());
+	mac.update(request);
+	let signature = hex::encode(mac.finalize().into_bytes());
+	let mut out = Vec::with_capacity(prefix.len() + signature.len() + 1 + request.len());
+	out.extend_from_slice(prefix.as_bytes());
+	out.extend_from_slice(signature.as_bytes());
+	out.push(b'\n');
+	out.extend_from_slice(request);
+	out
+}
+
+/// Checks the signatures of requests, and remembers their nonces to reject replayed
+/// requests. Also signs the messages which are sent in reply.
+pub struct Verifier {
+	// The shared key which requests are signed with
+	key: Vec<u8>,
+	// How far the timestamp of a request may be from the time on the server
+	window: Duration,
+	// The nonces which were accepted within the window
+	seen: Mutex<Seen>,
+}
+
+#[derive(Default)]
+struct Seen {
+	nonces: HashSet<String>,
+	// The nonces in the order they were accepted, along with the time at which they can be forgotten
+	expiry: VecDeque<(u128, String)>,
+}
+
+impl fmt::Debug for Verifier {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("Verifier").field("window", &self.window).finish_non_exhaustive()
+	}
+}
+
+impl Verifier {
+	/// Create a verifier for requests signed with a shared key
+	pub fn new(key: impl Into<Vec<u8>>, window: Duration) -> Self {
+		Self {
+			key: key.into(),
+			window,
+			seen: Mutex::new(Seen::default()),
+		}
+	}
+
+	/// Sign an encoded response or notification with the current time and a random nonce
+	pub fn sign(&self, message: &[u8]) -> Vec<u8> {
+		let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis();
+		sign(&self.key, now as u64, &Uuid::new_v4().simple().to_string(), message)
+	}
+
+	/// Check the signature of a request, returning the length of the signature
+	/// line, after which the encoded request starts
+	pub fn verify(&self, frame: &[u8]) -> Result<usize, RpcError> {
+		let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis();
+		self.verify_at(frame, now)
+	}
+
+	fn verify_at(&self, frame: &[u8], now: u128) -> Result<usize, RpcError> {
+		// Split the signature line from the request
+		let len = frame
+			.iter()
+			.take(MAX_LINE_LENGTH)
+			.position(|b| *b == b'\n')
+			.ok_or(RpcError::InvalidSignature("the request is not signed"))?;
+		let line = std::str::from_utf8(&frame[..len])
+			.map_err(|_| RpcError::InvalidSignature("the signature is malformed"))?;
+		let Some((head, signature)) = line.rsplit_once(',') else {
+			return Err(RpcError::InvalidSignature("the signature is malformed"));
+		};
+		// The signed prefix includes the comma before the signature
+		let prefix = &line[..head.len() + 1];
+		let (timestamp, nonce) = match head.split(',').collect::<Vec<_>>()[..] {
+			[VERSION, timestamp, nonce] => (timestamp, nonce),
+			_ => return Err(RpcError::InvalidSignature("the signature is malformed")),
+		};
+		let timestamp = timestamp
+			.parse::<u64>()
+			.map_err(|_| RpcError::InvalidSignature("the timestamp is malformed"))?;
+		if nonce.is_empty()
+			|| nonce.len() > MAX_NONCE_LENGTH
+			|| !nonce.bytes().all(|b| b.is_ascii_graphic())
+		{
+			return Err(RpcError::InvalidSignature("the nonce is malformed"));
+		}
+		// Check the signature before anything which it signs is trusted
+		let signature = hex::decode(signature)
+			.map_err(|_| RpcError::InvalidSignature("the signature is malformed"))?;
+		let mut mac =
+			HmacSha256::new_from_slice(&self.key).expect("HMAC accepts keys of any length");
+		mac.update(prefix.as_bytes());
+		mac.update(&frame[len + 1..]);
+		mac.verify_slice(&signature)
+			.map_err(|_| RpcError::InvalidSignature("the signature does not match the request"))?;
+		// Check that the request was signed recently
+		let window = self.window.as_millis();
+		if (timestamp as u128).abs_diff(now) > window {
+			return Err(RpcError::InvalidSignature("the request was not signed recently"));
+		}
+		// Check that the request has not been seen before
+		let mut seen = self.seen.lock().unwrap();
+		while let Some((expiry, _)) = seen.expiry.front() {
+			if *expiry > now {
+				break;
+			}
+			if let Some((_, nonce)) = seen.expiry.pop_front() {
+				seen.nonces.remove(&nonce);
+			}
+		}
+		if !seen.nonces.insert(nonce.to_owned()) {
+			return Err(RpcError::InvalidSignature("the request has already been received"));
+		}
+		// The nonce can be reused once a request with it could no longer be accepted
+		seen.expiry.push_back((now + 2 * window, nonce.to_owned()));
+		Ok(len + 1)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const KEY: &[u8] = b"secret";
+
+	const NOW: u64 = 1_700_000_000_000;
+
+	fn verifier() -> Verifier {
+		Verifier::new(KEY, Duration::from_secs(30))
+	}
+
+	#[test]
+	fn signed_requests_are_accepted() {
+		let request = br#"{"id":1,"method":"ping"}"#;
+		let frame = sign(KEY, NOW, "abc", request);
+		let len = verifier().verify_at(&frame, NOW as u128 + 1000).unwrap();
+		assert_eq!(&frame[len..], request);
+	}
+
+	#[test]
+	fn changed_requests_are_rejected() {
+		let verifier = verifier();
+		// The request is changed
+		let mut frame = sign(KEY, NOW, "abc", br#"{"id":1,"method":"ping"}"#);
+		*frame.last_mut().unwrap() = b']';
+		assert!(verifier.verify_at(&frame, NOW as u128).is_err());
+		// The request is signed with another key
+		let frame = sign(b"other", NOW, "abc", br#"{"id":1,"method":"ping"}"#);
+		assert!(verifier.verify_at(&frame, NOW as u128).is_err());
+		// The request is not signed
+		assert!(verifier.verify_at(br#"{"id":1,"method":"ping"}"#, NOW as u128).is_err());
+	}
+
+	#[test]
+	fn responses_are_signed() {
+		let response = br#"{"id":1,"result":null}"#;
+		let frame = verifier().sign(response);
+		let len = verifier().verify(&frame).unwrap();
+		assert_eq!(&frame[len..], response);
+		// Each response is signed with a new nonce
+		let other = verifier().sign(response);
+		assert_ne!(frame[..len], other[..len]);
+	}
+
+	#[test]
+	fn replayed_requests_are_rejected() {
+		let verifier = verifier();
+		let frame = sign(KEY, NOW, "abc", b"request");
+		assert!(verifier.verify_at(&frame, NOW as u128).is_ok());
+		assert!(verifier.verify_at(&frame, NOW as u128 + 1000).is_err());
+		// Requests which were signed too long ago are rejected
+		assert!(verifier.verify_at(&frame, NOW as u128 + 31_000).is_err());
+		let frame = sign(KEY, NOW + 31_000, "def", b"request");
+		assert!(verifier.verify_at(&frame, NOW as u128).is_err());
+		// Nonces are forgotten once their requests could no longer be accepted
+		let frame = sign(KEY, NOW + 61_000, "abc", b"request");
+		assert!(verifier.verify_at(&frame, NOW as u128 + 61_000).is_ok());
+	}
+}
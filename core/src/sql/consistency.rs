@@ -0,0 +1,27 @@
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+/// The consistency of the reads of a statement, on storage engines which
+/// are distributed across several nodes.
+#[revisioned(revision = 1)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub enum Consistency {
+	/// The reads may miss the most recently committed changes,
+	/// in exchange for a lower latency
+	Eventual,
+	/// The reads see all of the changes which were committed
+	/// before the statement started
+	Strong,
+}
+
+impl Display for Consistency {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		match self {
+			Self::Eventual => f.write_str("WITH CONSISTENCY EVENTUAL"),
+			Self::Strong => f.write_str("WITH CONSISTENCY STRONG"),
+		}
+	}
+}
@@ -0,0 +1,247 @@
+//! Conflict-free replicated data types, which fields can be defined as.
+//!
+//! The value of a CRDT field is stored in the record as normal, and the state from
+//! which the value is computed is stored alongside it, in the `__crdt` field of the
+//! record. The state records the changes which each node has made to the field, so
+//! that states which were written concurrently, by transactions which conflicted or
+//! by clusters which replicate each other, can be merged in any order, any number of
+//! times, with the same result.
+use crate::sql::{Array, Number, Object, Value};
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+
+/// The conflict-free replicated data type of a field
+#[revisioned(revision = 1)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub enum Crdt {
+	/// A grow-only counter, which sums the increments made by each node
+	Counter,
+	/// A last-writer-wins register, which keeps the most recently written value
+	Register,
+	/// An observed-remove set, in which a value is removed only if its addition was seen
+	Set,
+}
+
+impl Display for Crdt {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Counter => f.write_str("COUNTER"),
+			Self::Register => f.write_str("REGISTER"),
+			Self::Set => f.write_str("SET"),
+		}
+	}
+}
+
+impl Crdt {
+	/// Merge two states of a field
+	pub(crate) fn join(&self, a: Value, b: Value) -> Value {
+		match (a, b) {
+			(Value::Object(a), Value::Object(b)) => match self {
+				// Keep the highest count of each node
+				Self::Counter => {
+					let mut out = a;
+					for (node, n) in b.0 {
+						match out.get(&node) {
+							Some(v) if *v >= n => (),
+							_ => {
+								out.insert(node, n);
+							}
+						}
+					}
+					out.into()
+				}
+				// Keep the most recent write
+				Self::Register => match Self::written(&a) >= Self::written(&b) {
+					true => a.into(),
+					false => b.into(),
+				},
+				// Keep every addition and every removal
+				Self::Set => {
+					let mut adds = Self::entries(&a, "adds");
+					adds.0.extend(Self::entries(&b, "adds").0);
+					let mut removes = Self::entries(&a, "removes");
+					removes.0.extend(Self::entries(&b, "removes").0);
+					Value::from(map! {
+						"adds".to_string() => Value::from(adds),
+						"removes".to_string() => Value::from(removes),
+					})
+				}
+			},
+			(Value::Object(a), _) => a.into(),
+			(_, b) => b,
+		}
+	}
+
+	/// Compute the value of a field from its state
+	pub(crate) fn value(&self, state: &Value) -> Value {
+		let state = match state {
+			Value::Object(v) => v,
+			_ => return self.empty(),
+		};
+		match self {
+			Self::Counter => state
+				.values()
+				.filter_map(|v| match v {
+					Value::Number(n) => Some(n.clone()),
+					_ => None,
+				})
+				.sum::<Number>()
+				.into(),
+			Self::Register => state.get("value").cloned().unwrap_or_default(),
+			Self::Set => {
+				let removes = Self::entries(state, "removes");
+				let mut out = Array::new();
+				for (tag, v) in Self::entries(state, "adds").0 {
+					if !removes.contains_key(&tag) && !out.contains(&v) {
+						out.push(v);
+					}
+				}
+				out.into()
+			}
+		}
+	}
+
+	/// Record a change to the value of a field, made by a node at a time in
+	/// milliseconds, in the state of the field. An `Err` describes the value
+	/// which was expected, if the change can not be made to this type of field.
+	pub(crate) fn write(
+		&self,
+		state: Value,
+		before: &Value,
+		after: Value,
+		node: &str,
+		at: i64,
+		mut tag: impl FnMut() -> String,
+	) -> Result<Value, String> {
+		let mut state = match state {
+			Value::Object(v) => v,
+			_ => Object::default(),
+		};
+		match self {
+			Self::Counter => {
+				let (Value::Number(after), Value::Number(before)) = (&after, before) else {
+					return Err(String::from("a number"));
+				};
+				if after < before {
+					return Err(format!("a number of at least {before}"));
+				}
+				let count = match state.get(node) {
+					Some(Value::Number(n)) => n.clone(),
+					_ => Number::Int(0),
+				};
+				state.insert(node.to_owned(), (count + after.clone() - before.clone()).into());
+			}
+			Self::Register => {
+				state = Object::from(map! {
+					"value".to_string() => after,
+					"at".to_string() => Value::from(at),
+					"node".to_string() => Value::from(node),
+				});
+			}
+			Self::Set => {
+				let (Value::Array(after), Value::Array(before)) = (&after, before) else {
+					return Err(String::from("an array"));
+				};
+				let mut adds = Self::entries(&state, "adds");
+				let mut removes = Self::entries(&state, "removes");
+				// Remove every observed addition of the removed values
+				for (k, v) in adds.iter() {
+					if before.contains(v) && !after.contains(v) {
+						removes.insert(k.clone(), Value::Bool(true));
+					}
+				}
+				// Add the new values with a unique tag
+				for v in after.iter() {
+					if !before.contains(v) {
+						adds.insert(tag(), v.clone());
+					}
+				}
+				state.insert("adds".to_owned(), adds.into());
+				state.insert("removes".to_owned(), removes.into());
+			}
+		}
+		Ok(state.into())
+	}
+
+	/// The value of a field which has not been written
+	fn empty(&self) -> Value {
+		match self {
+			Self::Counter => Value::from(0),
+			Self::Register => Value::None,
+			Self::Set => Value::from(Array::new()),
+		}
+	}
+
+	/// The time and node of the last write to a register
+	fn written(state: &Object) -> (Value, Value) {
+		let field = |k: &str| state.get(k).cloned().unwrap_or_default();
+		(field("at"), field("node"))
+	}
+
+	/// The tagged entries of a set
+	fn entries(state: &Object, key: &str) -> Object {
+		match state.get(key) {
+			Some(Value::Object(v)) => v.clone(),
+			_ => Object::default(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::syn::value;
+
+	fn write(crdt: Crdt, state: &Value, after: &str, node: &str, at: i64) -> Value {
+		let before = crdt.value(state);
+		let mut n = 0;
+		let tag = || {
+			n += 1;
+			format!("{at:04}-{n}-{node}")
+		};
+		crdt.write(state.clone(), &before, value(after).unwrap(), node, at, tag).unwrap()
+	}
+
+	#[test]
+	fn counters_sum_the_increments_of_each_node() {
+		let c = Crdt::Counter;
+		let a = write(c, &Value::None, "3", "a", 1);
+		let b = write(c, &a, "5", "b", 2);
+		let a = write(c, &a, "4", "a", 3);
+		let joined = c.join(a.clone(), b.clone());
+		assert_eq!(c.value(&joined), Value::from(6));
+		assert_eq!(c.join(b, a), joined);
+		assert_eq!(c.join(joined.clone(), joined.clone()), joined);
+		let before = c.value(&joined);
+		let res = c.write(joined, &before, Value::from(5), "a", 4, String::new);
+		assert_eq!(res, Err(String::from("a number of at least 6")));
+	}
+
+	#[test]
+	fn registers_keep_the_last_write() {
+		let c = Crdt::Register;
+		let a = write(c, &Value::None, "'one'", "a", 1);
+		let b = write(c, &Value::None, "'two'", "b", 2);
+		assert_eq!(c.value(&c.join(a.clone(), b.clone())), Value::from("two"));
+		assert_eq!(c.value(&c.join(b, a)), Value::from("two"));
+	}
+
+	#[test]
+	fn sets_remove_observed_values() {
+		let c = Crdt::Set;
+		let base = write(c, &Value::None, "['x', 'y']", "a", 1);
+		// One node removes a value, while another adds it again
+		let a = write(c, &base, "['y']", "a", 2);
+		let b = write(c, &base, "['x', 'y', 'z']", "b", 3);
+		let b = write(c, &b, "['y', 'z']", "b", 4);
+		let b = write(c, &b, "['x', 'y', 'z']", "b", 5);
+		let joined = c.join(a.clone(), b.clone());
+		assert_eq!(c.value(&joined), value("['y', 'z', 'x']").unwrap());
+		assert_eq!(c.join(b, a), joined);
+		let res = c.write(joined, &Value::None, Value::from(1), "a", 6, String::new);
+		assert_eq!(res, Err(String::from("an array")));
+	}
+}
@@ -1,6 +1,7 @@
 use crate::ctx::Context;
 use crate::dbs::{Options, Transaction};
 use crate::err::Error;
+use crate::kvs::Merge;
 use crate::sql::fmt::Fmt;
 use crate::sql::idiom::Idiom;
 use crate::sql::operator::Operator;
@@ -8,6 +9,7 @@ use crate::sql::value::Value;
 use reblessive::tree::Stk;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
 use std::fmt::{self, Display, Formatter};
 
 #[revisioned(revision = 1)]
@@ -67,6 +69,22 @@ impl Data {
 			_ => Ok(None),
 		}
 	}
+	/// Fetch the operations performed by this clause, if it only appends to arrays
+	/// or increments numbers, with values which do not depend on the record
+	pub(crate) fn merges(&self) -> Option<BTreeSet<Merge>> {
+		match self {
+			Self::SetExpression(v) if !v.is_empty() => v
+				.iter()
+				.map(|(i, o, v)| match (o, v) {
+					_ if i.is_id() => None,
+					(Operator::Inc | Operator::Dec, Value::Number(_)) => Some(Merge::Increment),
+					(Operator::Inc, v) if v.is_static() || v.is_param() => Some(Merge::Append),
+					_ => None,
+				})
+				.collect(),
+			_ => None,
+		}
+	}
 }
 
 impl Display for Data {
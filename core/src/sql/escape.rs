@@ -59,12 +59,20 @@ pub fn quote_plain_str(s: &str) -> String {
 #[inline]
 /// Escapes a key if necessary
 pub fn escape_key(s: &str) -> Cow<'_, str> {
+	// Keys which start with a digit are lexed as numbers
+	if s.starts_with(|c: char| c.is_ascii_digit()) && !s.bytes().all(|x| x.is_ascii_digit()) {
+		return Cow::Owned(surround(s, DOUBLE, DOUBLE, DOUBLE_ESC));
+	}
 	escape_normal(s, DOUBLE, DOUBLE, DOUBLE_ESC)
 }
 
 #[inline]
 /// Escapes an id if necessary
 pub fn escape_rid(s: &str) -> Cow<'_, str> {
+	// These would be parsed as an id generator
+	if ["rand", "ulid", "uuid"].iter().any(|x| x.eq_ignore_ascii_case(s)) {
+		return Cow::Owned(surround(s, BRACKETL, BRACKETR, BRACKET_ESC));
+	}
 	escape_numeric(s, BRACKETL, BRACKETR, BRACKET_ESC)
 }
 
@@ -79,11 +87,15 @@ pub fn escape_ident(s: &str) -> Cow<'_, str> {
 
 #[inline]
 pub fn escape_normal<'a>(s: &'a str, l: char, r: char, e: &str) -> Cow<'a, str> {
+	// An empty or NaN value would not be lexed as text
+	if s.is_empty() || s == "NaN" {
+		return Cow::Owned(surround(s, l, r, e));
+	}
 	// Loop over each character
 	for x in s.bytes() {
 		// Check if character is allowed
 		if !(x.is_ascii_alphanumeric() || x == b'_') {
-			return Cow::Owned(format!("{l}{}{r}", s.replace(r, e)));
+			return Cow::Owned(surround(s, l, r, e));
 		}
 	}
 	// Output the value
@@ -96,16 +108,80 @@ pub fn escape_reserved_keyword(s: &str) -> Option<String> {
 
 #[inline]
 pub fn escape_numeric<'a>(s: &'a str, l: char, r: char, e: &str) -> Cow<'a, str> {
+	// An empty or NaN value would not be lexed as text
+	if s.is_empty() || s == "NaN" {
+		return Cow::Owned(surround(s, l, r, e));
+	}
 	// Loop over each character
 	for (idx, x) in s.bytes().enumerate() {
 		// the first character is not allowed to be a digit.
 		if idx == 0 && x.is_ascii_digit() {
-			return Cow::Owned(format!("{l}{}{r}", s.replace(r, e)));
+			return Cow::Owned(surround(s, l, r, e));
 		}
 		// Check if character is allowed
 		if !(x.is_ascii_alphanumeric() || x == b'_') {
-			return Cow::Owned(format!("{l}{}{r}", s.replace(r, e)));
+			return Cow::Owned(surround(s, l, r, e));
 		}
 	}
 	Cow::Borrowed(s)
 }
+
+/// Surrounds a value with the specified delimiters, escaping the closing delimiter
+fn surround(s: &str, l: char, r: char, e: &str) -> String {
+	// Escape sequences are not supported within brackets, so any
+	// value containing a closing bracket is surrounded by backticks
+	if r == BRACKETR {
+		return match s.contains(BRACKETR) {
+			true => surround(s, BACKTICK, BACKTICK, BACKTICK_ESC),
+			false => format!("{l}{s}{r}"),
+		};
+	}
+	let mut out = String::with_capacity(s.len() + 2);
+	out.push(l);
+	for c in s.chars() {
+		match c {
+			'\\' => out.push_str("\\\\"),
+			// Line breaks are escaped so that the value can be written within a comment
+			'\n' => out.push_str("\\n"),
+			'\r' => out.push_str("\\r"),
+			c if c == r => out.push_str(e),
+			c => out.push(c),
+		}
+	}
+	out.push(r);
+	out
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn escape_idents() {
+		assert_eq!(escape_ident("person"), "person");
+		assert_eq!(escape_ident("select"), "`select`");
+		assert_eq!(escape_ident("1person"), "`1person`");
+		assert_eq!(escape_ident("a`b"), r"`a\`b`");
+		assert_eq!(escape_ident(r"a\b"), r"`a\\b`");
+		assert_eq!(escape_ident(""), "``");
+		assert_eq!(escape_ident("NaN"), "`NaN`");
+	}
+
+	#[test]
+	fn escape_rids() {
+		assert_eq!(escape_rid("tobie"), "tobie");
+		assert_eq!(escape_rid("tobie@surrealdb.com"), "⟨tobie@surrealdb.com⟩");
+		assert_eq!(escape_rid("a⟩b"), "`a⟩b`");
+		assert_eq!(escape_rid("rand"), "⟨rand⟩");
+		assert_eq!(escape_rid(""), "⟨⟩");
+	}
+
+	#[test]
+	fn escape_keys() {
+		assert_eq!(escape_key("name"), "name");
+		assert_eq!(escape_key("123"), "123");
+		assert_eq!(escape_key("1s"), r#""1s""#);
+		assert_eq!(escape_key(r"a\b"), r#""a\\b""#);
+		assert_eq!(escape_key(""), r#""""#);
+	}
+}
@@ -3,7 +3,8 @@ use crate::dbs::{Options, Transaction};
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::sql::statements::info::InfoStructure;
-use crate::sql::{fmt::Fmt, Idiom, Part, Value};
+use crate::sql::statements::SelectStatement;
+use crate::sql::{fmt::Fmt, Edges, Idiom, Part, Thing, Value, Values};
 use crate::syn;
 use reblessive::tree::Stk;
 use revision::revisioned;
@@ -41,6 +42,19 @@ impl Fields {
 			_ => None,
 		}
 	}
+	/// Get the graph traversal which starts the first field, if there is one
+	pub(crate) fn traversal(&self) -> Option<&[Part]> {
+		self.0.iter().find_map(|v| match v {
+			Field::Single {
+				expr: Value::Idiom(v),
+				..
+			} if matches!(v.first(), Some(Part::Graph(_))) => {
+				let len = v.iter().take_while(|p| matches!(p, Part::Graph(_))).count();
+				Some(&v[..len])
+			}
+			_ => None,
+		})
+	}
 }
 
 impl Deref for Fields {
@@ -172,6 +186,21 @@ impl Fields {
 								}
 							}
 						}
+						// This expression is the path of a graph traversal
+						Value::Function(f) if f.name() == Some("path") && f.args().is_empty() => {
+							// Follow the first graph traversal from this record
+							let expr = match (self.traversal(), doc.doc.rid()) {
+								(Some(parts), Value::Thing(from)) => {
+									paths(stk, ctx, opt, txn, parts, from).await?
+								}
+								_ => Value::None,
+							};
+							// Check if this is a single VALUE field expression
+							match self.single().is_some() {
+								false => out.set(stk, ctx, opt, txn, name.as_ref(), expr).await?,
+								true => out = expr,
+							}
+						}
 						// This expression is a variable fields expression
 						Value::Function(f) if f.name() == Some("type::fields") => {
 							// Process the function using variable field projections
@@ -245,6 +274,54 @@ impl Fields {
 	}
 }
 
+/// Find the records which each result of a graph traversal was reached through, in the
+/// same order as the results. Each path starts with the record which the traversal
+/// starts from, followed by each edge and vertex which was traversed.
+async fn paths(
+	stk: &mut Stk,
+	ctx: &Context<'_>,
+	opt: &Options,
+	txn: &Transaction,
+	parts: &[Part],
+	from: Thing,
+) -> Result<Value, Error> {
+	let mut paths = vec![vec![Value::Thing(from)]];
+	for p in parts {
+		let Part::Graph(g) = p else {
+			break;
+		};
+		let mut next = Vec::new();
+		for path in paths {
+			let Some(Value::Thing(last)) = path.last() else {
+				continue;
+			};
+			// Fetch the records at the end of this step, as when the traversal is fetched
+			let stm = SelectStatement {
+				expr: Fields::all(),
+				what: Values(vec![Value::from(Edges {
+					from: last.clone(),
+					dir: g.dir.clone(),
+					what: g.what.clone(),
+				})]),
+				cond: g.cond.clone(),
+				..SelectStatement::default()
+			};
+			let res = stk.run(|stk| stm.compute(stk, ctx, opt, txn, None)).await?.all();
+			if let Value::Array(res) = res {
+				for v in res.iter() {
+					if let Value::Thing(id) = v.rid() {
+						let mut path = path.clone();
+						path.push(Value::Thing(id));
+						next.push(path);
+					}
+				}
+			}
+		}
+		paths = next;
+	}
+	Ok(paths.into_iter().map(Value::from).collect::<Vec<_>>().into())
+}
+
 #[revisioned(revision = 1)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -1,10 +1,11 @@
 use crate::sql::language::Language;
+use crate::sql::Ident;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::fmt::Display;
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -15,6 +16,10 @@ pub enum Filter {
 	Ngram(u16, u16),
 	Snowball(Language),
 	Uppercase,
+	#[revision(start = 2)]
+	Stopwords(Ident),
+	#[revision(start = 2)]
+	Synonyms(Ident),
 }
 
 impl Display for Filter {
@@ -26,6 +31,8 @@ impl Display for Filter {
 			Self::Ngram(min, max) => write!(f, "NGRAM({},{})", min, max),
 			Self::Snowball(lang) => write!(f, "SNOWBALL({})", lang),
 			Self::Uppercase => f.write_str("UPPERCASE"),
+			Self::Stopwords(name) => write!(f, "STOPWORDS({})", name),
+			Self::Synonyms(name) => write!(f, "SYNONYMS({})", name),
 		}
 	}
 }
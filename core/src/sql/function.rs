@@ -103,6 +103,11 @@ impl Function {
 		matches!(self, Self::Script(_, _))
 	}
 
+	/// Check if this function writes to the datastore
+	pub fn is_write(&self) -> bool {
+		matches!(self, Self::Normal(f, _) if f == "blob::put")
+	}
+
 	/// Check if this function has static arguments
 	pub fn is_static(&self) -> bool {
 		match self {
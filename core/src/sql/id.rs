@@ -5,6 +5,7 @@ use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::sql::{escape::escape_rid, Array, Number, Object, Strand, Thing, Uuid, Value};
 use nanoid::nanoid;
+use rand::Rng;
 use reblessive::tree::Stk;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
@@ -147,6 +148,19 @@ impl Id {
 	pub fn uuid() -> Self {
 		Self::String(Uuid::new_v7().to_raw())
 	}
+	/// Generate a new ID, using the clock and random number generator of the context
+	pub(crate) fn generate(ctx: &Context, gen: &Gen) -> Self {
+		match gen {
+			Gen::Rand => Self::String(
+				ctx.rng(|r| (0..20).map(|_| ID_CHARS[r.gen_range(0..ID_CHARS.len())]).collect()),
+			),
+			Gen::Ulid => {
+				let ms = ctx.now().timestamp_millis() as u64;
+				Self::String(Ulid::from_parts(ms, ctx.rng(|r| r.gen())).to_string())
+			}
+			Gen::Uuid => Self::String(Uuid::new_v7_in(ctx).to_raw()),
+		}
+	}
 	/// Convert the Id to a raw String
 	pub fn to_raw(&self) -> String {
 		match self {
@@ -200,11 +214,7 @@ impl Id {
 				Value::Object(v) => Ok(Id::Object(v)),
 				_ => unreachable!(),
 			},
-			Id::Generate(v) => match v {
-				Gen::Rand => Ok(Self::rand()),
-				Gen::Ulid => Ok(Self::ulid()),
-				Gen::Uuid => Ok(Self::uuid()),
-			},
+			Id::Generate(v) => Ok(Self::generate(ctx, v)),
 		}
 	}
 }
@@ -6,7 +6,7 @@ use crate::sql::statements::info::InfoStructure;
 use crate::sql::{
 	fmt::{fmt_separated_by, Fmt},
 	part::Next,
-	paths::{ID, IN, META, OUT},
+	paths::{CRDT, CREATED_AT, CREATED_BY, ID, IN, META, OUT, UPDATED_AT, UPDATED_BY},
 	Part, Value,
 };
 use md5::{Digest, Md5};
@@ -133,6 +133,16 @@ impl Idiom {
 	pub(crate) fn is_meta(&self) -> bool {
 		self.0.len() == 1 && self.0[0].eq(&META[0])
 	}
+	/// Check if this Idiom is within the state of the CRDT fields
+	pub(crate) fn is_crdt(&self) -> bool {
+		self.0.first() == Some(&CRDT[0])
+	}
+	/// Check if this Idiom is an automatically maintained audit field
+	pub(crate) fn is_audit(&self) -> bool {
+		self.0.len() == 1
+			&& [&CREATED_AT[0], &CREATED_BY[0], &UPDATED_AT[0], &UPDATED_BY[0]]
+				.contains(&&self.0[0])
+	}
 	/// Check if this is an expression with multiple yields
 	pub(crate) fn is_multi_yield(&self) -> bool {
 		self.iter().any(Self::split_multi_yield)
@@ -0,0 +1,41 @@
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+/// The isolation level of a transaction, ordered from the weakest to the
+/// strongest guarantee.
+///
+/// The storage engines provide different guarantees:
+///
+/// - The memory and IndexedDB engines run writeable transactions one at a
+///   time, so they are serializable.
+/// - FoundationDB transactions are serializable. A weaker level uses snapshot
+///   reads, which do not conflict with concurrent writes.
+/// - RocksDB, SpeeDB, SurrealKV and TiKV transactions read from a snapshot and
+///   only conflict on the keys which they write, so they provide snapshot
+///   isolation, but can not be serializable.
+///
+/// A weaker level is always satisfied by an engine with a stronger guarantee.
+#[revisioned(revision = 1)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub enum Isolation {
+	/// The reads see the changes which were committed before each read
+	ReadCommitted,
+	/// The reads see the changes which were committed before the transaction
+	/// started, and concurrent transactions may not write the same keys
+	Snapshot,
+	/// The transaction behaves as though no other transaction ran concurrently
+	Serializable,
+}
+
+impl Display for Isolation {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		match self {
+			Self::ReadCommitted => f.write_str("ISOLATION READ COMMITTED"),
+			Self::Snapshot => f.write_str("ISOLATION SNAPSHOT"),
+			Self::Serializable => f.write_str("ISOLATION SERIALIZABLE"),
+		}
+	}
+}
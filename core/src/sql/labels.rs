@@ -0,0 +1,43 @@
+use crate::sql::escape::escape_key;
+use crate::sql::fmt::Fmt;
+use crate::sql::statements::info::InfoStructure;
+use crate::sql::{Object, Strand, Value};
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+
+/// The labels of a namespace, database, or table definition, which
+/// describe the definition for any tools which manage the datastore
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct Labels(pub BTreeMap<String, Strand>);
+
+impl Display for Labels {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(
+			f,
+			"LABELS {{ {} }}",
+			Fmt::comma_separated(self.0.iter().map(|args| Fmt::new(args, |(k, v), f| write!(
+				f,
+				"{}: {}",
+				escape_key(k),
+				v
+			))))
+		)
+	}
+}
+
+impl From<Labels> for Value {
+	fn from(v: Labels) -> Self {
+		Object(v.0.into_iter().map(|(k, v)| (k, Value::from(v))).collect()).into()
+	}
+}
+
+impl InfoStructure for Labels {
+	fn structure(self) -> Value {
+		self.into()
+	}
+}
@@ -11,7 +11,9 @@ pub(crate) mod cast;
 pub(crate) mod change_feed_include;
 pub(crate) mod changefeed;
 pub(crate) mod cond;
+pub(crate) mod consistency;
 pub(crate) mod constant;
+pub(crate) mod crdt;
 pub(crate) mod data;
 pub(crate) mod datetime;
 pub(crate) mod dir;
@@ -32,7 +34,9 @@ pub(crate) mod group;
 pub(crate) mod id;
 pub(crate) mod ident;
 pub(crate) mod idiom;
+pub(crate) mod isolation;
 pub(crate) mod kind;
+pub(crate) mod labels;
 pub(crate) mod language;
 pub(crate) mod limit;
 pub(crate) mod mock;
@@ -42,14 +46,18 @@ pub(crate) mod object;
 pub(crate) mod operation;
 pub(crate) mod operator;
 pub(crate) mod order;
+pub(crate) mod outfile;
 pub(crate) mod output;
 pub(crate) mod param;
 pub(crate) mod part;
+pub(crate) mod partition;
 pub(crate) mod paths;
 pub(crate) mod permission;
+pub(crate) mod priority;
 pub(crate) mod query;
 pub(crate) mod range;
 pub(crate) mod regex;
+pub(crate) mod sample;
 pub(crate) mod scoring;
 pub(crate) mod script;
 pub(crate) mod split;
@@ -62,10 +70,12 @@ pub(crate) mod table_type;
 pub(crate) mod thing;
 pub(crate) mod timeout;
 pub(crate) mod tokenizer;
+pub(crate) mod unknown_fields;
 pub(crate) mod uuid;
 pub(crate) mod value;
 pub(crate) mod version;
 pub(crate) mod view;
+pub(crate) mod window;
 pub(crate) mod with;
 
 #[doc(hidden)]
@@ -82,7 +92,9 @@ pub use self::bytes::Bytes;
 pub use self::cast::Cast;
 pub use self::changefeed::ChangeFeed;
 pub use self::cond::Cond;
+pub use self::consistency::Consistency;
 pub use self::constant::Constant;
+pub use self::crdt::Crdt;
 pub use self::data::Data;
 pub use self::datetime::Datetime;
 pub use self::dir::Dir;
@@ -105,7 +117,9 @@ pub use self::ident::Ident;
 pub use self::idiom::Idiom;
 pub use self::idiom::Idioms;
 pub use self::index::Index;
+pub use self::isolation::Isolation;
 pub use self::kind::Kind;
+pub use self::labels::Labels;
 pub use self::limit::Limit;
 pub use self::mock::Mock;
 pub use self::model::Model;
@@ -115,29 +129,36 @@ pub use self::operation::Operation;
 pub use self::operator::Operator;
 pub use self::order::Order;
 pub use self::order::Orders;
+pub use self::outfile::Outfile;
+pub use self::outfile::OutfileFormat;
 pub use self::output::Output;
 pub use self::param::Param;
 pub use self::part::Part;
+pub use self::partition::Partition;
 pub use self::permission::Permission;
 pub use self::permission::Permissions;
+pub use self::priority::Priority;
 pub use self::query::Query;
 pub use self::range::Range;
 pub use self::regex::Regex;
+pub use self::sample::Sample;
 pub use self::scoring::Scoring;
 pub use self::script::Script;
 pub use self::split::Split;
 pub use self::split::Splits;
 pub use self::start::Start;
 pub use self::statement::Statement;
+pub use self::statement::StatementKind;
 pub use self::statement::Statements;
 pub use self::strand::Strand;
 pub use self::subquery::Subquery;
 pub use self::table::Table;
 pub use self::table::Tables;
-pub use self::table_type::{Relation, TableType};
+pub use self::table_type::{OnDelete, Relation, TableType};
 pub use self::thing::Thing;
 pub use self::timeout::Timeout;
 pub use self::tokenizer::Tokenizer;
+pub use self::unknown_fields::UnknownFields;
 pub use self::uuid::Uuid;
 pub use self::value::serde::to_value;
 #[doc(hidden)]
@@ -146,6 +167,7 @@ pub use self::value::Value;
 pub use self::value::Values;
 pub use self::version::Version;
 pub use self::view::View;
+pub use self::window::Window;
 pub use self::with::With;
 
 // module reexporting parsing function to prevent a breaking change.
@@ -0,0 +1,192 @@
+#[cfg(all(feature = "obs", not(target_arch = "wasm32")))]
+use crate::cnf::{OUTFILE_BUFFER_SIZE, OUTFILE_SCHEMES};
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::err::Error;
+use crate::iam::{Action, ResourceKind};
+#[cfg(all(feature = "obs", not(target_arch = "wasm32")))]
+use crate::obs::Writer;
+use crate::sql::escape::quote_str;
+use crate::sql::{Base, Value};
+use channel::Sender;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+use std::future::Future;
+#[cfg(all(feature = "obs", not(target_arch = "wasm32")))]
+use url::Url;
+
+/// An object storage location, into which the results of a SELECT statement
+/// are written on the server, instead of being returned to the client.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct Outfile {
+	pub path: String,
+	pub format: OutfileFormat,
+}
+
+impl Outfile {
+	/// Write the results of a statement to the outfile, returning a summary of the write.
+	/// The results are produced by the closure, which can send batches of them to be
+	/// written while the statement runs, and returns any results which were not sent.
+	pub(crate) async fn write<F, R>(
+		&self,
+		ctx: &Context<'_>,
+		opt: &Options,
+		produce: F,
+	) -> Result<Value, Error>
+	where
+		F: FnOnce(Sender<Vec<Value>>) -> R,
+		R: Future<Output = Result<Value, Error>>,
+	{
+		// Writing outside of the datastore requires editor permissions
+		opt.is_allowed(Action::Edit, ResourceKind::Any, &Base::Db)?;
+		opt.check_readonly("INTO OUTFILE")?;
+		let rows = self.upload(ctx, produce).await?;
+		Ok(Value::from(map! {
+			"path".to_string() => Value::from(self.path.clone()),
+			"rows".to_string() => Value::from(rows),
+		}))
+	}
+
+	/// Check that the outfile is somewhere which may be written to
+	#[cfg(all(feature = "obs", not(target_arch = "wasm32")))]
+	fn url(&self, ctx: &Context<'_>) -> Result<Url, Error> {
+		let invalid = |message: String| Error::InvalidOutfile {
+			path: self.path.clone(),
+			message,
+		};
+		let url = Url::parse(&self.path).map_err(|e| invalid(e.to_string()))?;
+		// Only the configured object stores can be written to
+		if !OUTFILE_SCHEMES.split(',').any(|s| s.trim().eq_ignore_ascii_case(url.scheme())) {
+			return Err(invalid(format!("the '{}' scheme is not allowed", url.scheme())));
+		}
+		// Remote object stores must be allowed by the network capabilities
+		if url.scheme() != "file" {
+			ctx.check_allowed_net(&url)?;
+		}
+		Ok(url)
+	}
+
+	#[cfg(all(feature = "obs", not(target_arch = "wasm32")))]
+	async fn upload<F, R>(&self, ctx: &Context<'_>, produce: F) -> Result<usize, Error>
+	where
+		F: FnOnce(Sender<Vec<Value>>) -> R,
+		R: Future<Output = Result<Value, Error>>,
+	{
+		let url = self.url(ctx)?;
+		let mut writer = Writer::open(&url).await?;
+		match self.stream(&mut writer, produce).await {
+			Ok(rows) => {
+				writer.finish().await?;
+				Ok(rows)
+			}
+			Err(e) => {
+				writer.abort().await;
+				Err(e)
+			}
+		}
+	}
+
+	#[cfg(all(feature = "obs", not(target_arch = "wasm32")))]
+	async fn stream<F, R>(&self, writer: &mut Writer, produce: F) -> Result<usize, Error>
+	where
+		F: FnOnce(Sender<Vec<Value>>) -> R,
+		R: Future<Output = Result<Value, Error>>,
+	{
+		let mut buf = Vec::with_capacity(OUTFILE_BUFFER_SIZE);
+		let (chn, batches) = channel::bounded(1);
+		// Write the batches of results as they are produced
+		let (res, rows) = futures::join!(produce(chn), async {
+			let mut rows = 0;
+			while let Ok(batch) = batches.recv().await {
+				rows += batch.len();
+				self.encode(writer, &mut buf, batch).await?;
+			}
+			Ok::<_, Error>(rows)
+		});
+		// A single result is written as a single row
+		let rest = match res? {
+			Value::Array(v) => v.0,
+			Value::None => vec![],
+			v => vec![v],
+		};
+		let rows = rows? + rest.len();
+		// Write any results which were not sent in batches
+		self.encode(writer, &mut buf, rest).await?;
+		writer.write(&buf).await?;
+		Ok(rows)
+	}
+
+	#[cfg(all(feature = "obs", not(target_arch = "wasm32")))]
+	async fn encode(
+		&self,
+		writer: &mut Writer,
+		buf: &mut Vec<u8>,
+		rows: Vec<Value>,
+	) -> Result<(), Error> {
+		for row in rows {
+			self.format.encode(buf, row)?;
+			// Write the encoded rows once the buffer is full
+			if buf.len() >= OUTFILE_BUFFER_SIZE {
+				writer.write(buf).await?;
+				buf.clear();
+			}
+		}
+		Ok(())
+	}
+
+	#[cfg(target_arch = "wasm32")]
+	async fn upload<F>(&self, _: &Context<'_>, _: F) -> Result<usize, Error> {
+		Err(Error::InvalidOutfile {
+			path: self.path.clone(),
+			message: "outfiles are not supported in WebAssembly".to_owned(),
+		})
+	}
+
+	#[cfg(all(not(feature = "obs"), not(target_arch = "wasm32")))]
+	async fn upload<F>(&self, _: &Context<'_>, _: F) -> Result<usize, Error> {
+		Err(Error::ObsDisabled)
+	}
+}
+
+/// The format in which results are written to an outfile
+#[revisioned(revision = 1)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub enum OutfileFormat {
+	/// One JSON document per line
+	#[default]
+	Jsonl,
+}
+
+impl OutfileFormat {
+	/// Encode a single result, appending it to the buffer
+	pub(crate) fn encode(&self, buf: &mut Vec<u8>, value: Value) -> Result<(), Error> {
+		match self {
+			Self::Jsonl => {
+				serde_json::to_writer(&mut *buf, &value.into_json())
+					.map_err(|e| Error::Serialization(e.to_string()))?;
+				buf.push(b'\n');
+			}
+		}
+		Ok(())
+	}
+}
+
+impl Display for OutfileFormat {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		match self {
+			Self::Jsonl => f.write_str("jsonl"),
+		}
+	}
+}
+
+impl Display for Outfile {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "INTO OUTFILE {} FORMAT {}", quote_str(&self.path), self.format)
+	}
+}
@@ -3,7 +3,7 @@ use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display};
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -14,6 +14,8 @@ pub enum Output {
 	After,
 	Before,
 	Fields(Fields),
+	#[revision(start = 2)]
+	Count,
 }
 
 impl Default for Output {
@@ -32,6 +34,7 @@ impl Display for Output {
 			Self::After => f.write_str("AFTER"),
 			Self::Before => f.write_str("BEFORE"),
 			Self::Fields(v) => Display::fmt(v, f),
+			Self::Count => f.write_str("COUNT"),
 		}
 	}
 }
@@ -0,0 +1,79 @@
+use crate::err::Error;
+use crate::sql::statements::info::InfoStructure;
+use crate::sql::{Idiom, Value};
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+/// The partitioning of a table, which divides the records of the table into
+/// partitions by a hash of a field, so that a query which selects a single
+/// value of the field only needs to read the records in a single partition.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct Partition {
+	/// The field which is hashed to choose the partition of a record
+	pub field: Idiom,
+	/// The number of partitions
+	pub count: u16,
+}
+
+impl Partition {
+	/// The largest number of partitions which a table can have
+	pub(crate) const MAX_COUNT: u16 = 1024;
+
+	/// Get the partition which stores the records with this field value
+	pub(crate) fn of(&self, v: &Value) -> Result<u16, Error> {
+		// The key encoding of a value is stable, unlike its display or revisioned encoding
+		let key = storekey::serialize(v)?;
+		Ok((crc32fast::hash(&key) % self.count.max(1) as u32) as u16)
+	}
+
+	/// Get the partition of a record, unless the record does not exist
+	pub(crate) fn of_doc(&self, doc: &Value) -> Result<Option<u16>, Error> {
+		match doc {
+			Value::None => Ok(None),
+			doc => self.of(&doc.pick(&self.field)).map(Some),
+		}
+	}
+
+	/// Check whether the records which are equal to a value can be found in a single
+	/// partition, which is only the case for values which can't equal values of other types
+	pub(crate) fn prunes(v: &Value) -> bool {
+		matches!(v, Value::Bool(_) | Value::Strand(_) | Value::Thing(_) | Value::Uuid(_))
+	}
+}
+
+impl Display for Partition {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "PARTITION BY HASH({}) PARTITIONS {}", self.field, self.count)
+	}
+}
+
+impl InfoStructure for Partition {
+	fn structure(self) -> Value {
+		self.to_string().into()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sql::Thing;
+	use crate::syn::value;
+
+	#[test]
+	fn partitions_are_stable() {
+		let pt = Partition {
+			field: Idiom::from("id"),
+			count: 16,
+		};
+		let id = Value::Thing(Thing::from(("person", "tobie")));
+		let doc = value("{ id: person:tobie, name: 'Tobie' }").unwrap();
+		assert!(pt.of(&id).unwrap() < 16);
+		assert_eq!(pt.of(&id).unwrap(), pt.of(&id).unwrap());
+		assert_eq!(pt.of_doc(&doc).unwrap(), Some(pt.of(&id).unwrap()));
+		assert_eq!(pt.of_doc(&Value::None).unwrap(), None);
+	}
+}
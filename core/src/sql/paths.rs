@@ -27,3 +27,13 @@ pub static OUT: Lazy<[Part; 1]> = Lazy::new(|| [Part::from("out")]);
 pub static META: Lazy<[Part; 1]> = Lazy::new(|| [Part::from("__")]);
 
 pub static EDGE: Lazy<[Part; 1]> = Lazy::new(|| [Part::from("__")]);
+
+pub static CRDT: Lazy<[Part; 1]> = Lazy::new(|| [Part::from("__crdt")]);
+
+pub static CREATED_AT: Lazy<[Part; 1]> = Lazy::new(|| [Part::from("created_at")]);
+
+pub static CREATED_BY: Lazy<[Part; 1]> = Lazy::new(|| [Part::from("created_by")]);
+
+pub static UPDATED_AT: Lazy<[Part; 1]> = Lazy::new(|| [Part::from("updated_at")]);
+
+pub static UPDATED_BY: Lazy<[Part; 1]> = Lazy::new(|| [Part::from("updated_by")]);
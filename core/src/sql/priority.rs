@@ -0,0 +1,31 @@
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+/// The priority of the queries of a session, which orders the queries
+/// which are waiting to be admitted when the datastore is at capacity.
+#[revisioned(revision = 1)]
+#[derive(
+	Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize, Hash,
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub enum Priority {
+	/// Batch and analytics work, which waits for all other queries
+	Low,
+	/// Application traffic
+	#[default]
+	Normal,
+	/// Interactive or operational work, which is admitted first
+	High,
+}
+
+impl Display for Priority {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		match self {
+			Self::Low => f.write_str("LOW"),
+			Self::Normal => f.write_str("NORMAL"),
+			Self::High => f.write_str("HIGH"),
+		}
+	}
+}
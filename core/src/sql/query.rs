@@ -51,6 +51,16 @@ impl IntoIterator for Query {
 	}
 }
 
+impl Query {
+	/// Check if any statement in this query may modify data
+	pub fn writeable(&self) -> bool {
+		self.iter().any(|stm| match stm {
+			Statement::Begin(_) | Statement::Cancel(_) | Statement::Commit(_) => false,
+			stm => stm.writeable(),
+		})
+	}
+}
+
 impl Display for Query {
 	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
 		write!(Pretty::from(f), "{}", &self.0)
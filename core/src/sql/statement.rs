@@ -2,23 +2,27 @@ use crate::ctx::Context;
 use crate::dbs::{Options, Transaction};
 use crate::doc::CursorDoc;
 use crate::err::Error;
+use crate::kvs::Merge;
 use crate::sql::statements::rebuild::RebuildStatement;
 use crate::sql::{
 	fmt::{Fmt, Pretty},
 	statements::{
-		AnalyzeStatement, BeginStatement, BreakStatement, CancelStatement, CommitStatement,
-		ContinueStatement, CreateStatement, DefineStatement, DeleteStatement, ForeachStatement,
-		IfelseStatement, InfoStatement, InsertStatement, KillStatement, LiveStatement,
-		OptionStatement, OutputStatement, RelateStatement, RemoveStatement, SelectStatement,
-		SetStatement, ShowStatement, SleepStatement, ThrowStatement, UpdateStatement, UseStatement,
+		AlterStatement, AnalyzeStatement, BeginStatement, BreakStatement, CancelStatement,
+		CommitStatement, ContinueStatement, CreateStatement, DefineStatement, DeleteStatement,
+		DryRunStatement, ForeachStatement, GrantStatement, IfelseStatement, InfoStatement,
+		InsertStatement, KillStatement, LiveStatement, OptionStatement, OutputStatement,
+		RelateStatement, RemoveStatement, ReportStatement, SelectStatement, SetStatement,
+		ShowStatement, SleepStatement, ThrowStatement, UpdateStatement, UseStatement,
 	},
 	value::Value,
+	Consistency,
 };
 use derive::Store;
 use reblessive::tree::Stk;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::{
+	collections::BTreeSet,
 	fmt::{self, Display, Formatter, Write},
 	ops::Deref,
 	time::Duration,
@@ -54,7 +58,7 @@ impl Display for Statements {
 	}
 }
 
-#[revisioned(revision = 2)]
+#[revisioned(revision = 6)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -88,6 +92,14 @@ pub enum Statement {
 	Use(UseStatement),
 	#[revision(start = 2)]
 	Rebuild(RebuildStatement),
+	#[revision(start = 3)]
+	Grant(GrantStatement),
+	#[revision(start = 4)]
+	Alter(AlterStatement),
+	#[revision(start = 5)]
+	Report(ReportStatement),
+	#[revision(start = 6)]
+	DryRun(DryRunStatement),
 }
 
 impl Statement {
@@ -96,6 +108,7 @@ impl Statement {
 		match self {
 			Self::Create(v) => v.timeout.as_ref().map(|v| *v.0),
 			Self::Delete(v) => v.timeout.as_ref().map(|v| *v.0),
+			Self::DryRun(v) => v.timeout(),
 			Self::Insert(v) => v.timeout.as_ref().map(|v| *v.0),
 			Self::Relate(v) => v.timeout.as_ref().map(|v| *v.0),
 			Self::Select(v) => v.timeout.as_ref().map(|v| *v.0),
@@ -113,7 +126,10 @@ impl Statement {
 			Self::Create(v) => v.writeable(),
 			Self::Define(_) => true,
 			Self::Delete(v) => v.writeable(),
+			Self::DryRun(_) => true,
 			Self::Foreach(v) => v.writeable(),
+			Self::Grant(_) => true,
+			Self::Alter(_) => true,
 			Self::Ifelse(v) => v.writeable(),
 			Self::Info(_) => false,
 			Self::Insert(v) => v.writeable(),
@@ -124,6 +140,7 @@ impl Statement {
 			Self::Rebuild(_) => true,
 			Self::Relate(v) => v.writeable(),
 			Self::Remove(_) => true,
+			Self::Report(_) => false,
 			Self::Select(v) => v.writeable(),
 			Self::Set(v) => v.writeable(),
 			Self::Show(_) => false,
@@ -134,6 +151,58 @@ impl Statement {
 			_ => unreachable!(),
 		}
 	}
+	/// Get the commutative operations performed by this statement, if it only
+	/// appends to arrays or increments numbers, and can be retried after a conflict
+	pub(crate) fn merges(&self) -> Option<BTreeSet<Merge>> {
+		match self {
+			Self::Update(v) => v.merges(),
+			_ => None,
+		}
+	}
+	/// Get the read consistency which this statement requested, if any
+	pub(crate) fn consistency(&self) -> Option<Consistency> {
+		match self {
+			Self::Select(v) => v.consistency,
+			_ => None,
+		}
+	}
+	/// Get the kind of this statement, unless it is a plain value
+	pub fn kind(&self) -> Option<StatementKind> {
+		match self {
+			Self::Value(_) => None,
+			Self::Alter(_) => Some(StatementKind::Alter),
+			Self::Analyze(_) => Some(StatementKind::Analyze),
+			Self::Begin(_) => Some(StatementKind::Begin),
+			Self::Break(_) => Some(StatementKind::Break),
+			Self::Cancel(_) => Some(StatementKind::Cancel),
+			Self::Commit(_) => Some(StatementKind::Commit),
+			Self::Continue(_) => Some(StatementKind::Continue),
+			Self::Create(_) => Some(StatementKind::Create),
+			Self::Define(_) => Some(StatementKind::Define),
+			Self::Delete(_) => Some(StatementKind::Delete),
+			Self::DryRun(v) => v.stmt.kind(),
+			Self::Foreach(_) => Some(StatementKind::For),
+			Self::Grant(_) => Some(StatementKind::Grant),
+			Self::Ifelse(_) => Some(StatementKind::If),
+			Self::Info(_) => Some(StatementKind::Info),
+			Self::Insert(_) => Some(StatementKind::Insert),
+			Self::Kill(_) => Some(StatementKind::Kill),
+			Self::Live(_) => Some(StatementKind::Live),
+			Self::Option(_) => Some(StatementKind::Option),
+			Self::Output(_) => Some(StatementKind::Return),
+			Self::Rebuild(_) => Some(StatementKind::Rebuild),
+			Self::Relate(_) => Some(StatementKind::Relate),
+			Self::Remove(_) => Some(StatementKind::Remove),
+			Self::Report(_) => Some(StatementKind::Report),
+			Self::Select(_) => Some(StatementKind::Select),
+			Self::Set(_) => Some(StatementKind::Let),
+			Self::Show(_) => Some(StatementKind::Show),
+			Self::Sleep(_) => Some(StatementKind::Sleep),
+			Self::Throw(_) => Some(StatementKind::Throw),
+			Self::Update(_) => Some(StatementKind::Update),
+			Self::Use(_) => Some(StatementKind::Use),
+		}
+	}
 	/// Process this type returning a computed simple Value
 	pub(crate) async fn compute(
 		&self,
@@ -150,7 +219,10 @@ impl Statement {
 			Self::Create(v) => v.compute(stk, ctx, opt, txn, doc).await,
 			Self::Delete(v) => v.compute(stk, ctx, opt, txn, doc).await,
 			Self::Define(v) => v.compute(stk, ctx, opt, txn, doc).await,
+			Self::DryRun(v) => v.compute(stk, ctx, opt, txn, doc).await,
 			Self::Foreach(v) => v.compute(stk, ctx, opt, txn, doc).await,
+			Self::Grant(v) => v.compute(ctx, opt, txn, doc).await,
+			Self::Alter(v) => v.compute(ctx, opt, txn, doc).await,
 			Self::Ifelse(v) => v.compute(stk, ctx, opt, txn, doc).await,
 			Self::Info(v) => v.compute(ctx, opt, txn, doc).await,
 			Self::Insert(v) => v.compute(stk, ctx, opt, txn, doc).await,
@@ -160,6 +232,7 @@ impl Statement {
 			Self::Relate(v) => v.compute(stk, ctx, opt, txn, doc).await,
 			Self::Rebuild(v) => v.compute(stk, ctx, opt, txn, doc).await,
 			Self::Remove(v) => v.compute(ctx, opt, txn, doc).await,
+			Self::Report(v) => v.compute(ctx, opt, txn, doc).await,
 			Self::Select(v) => v.compute(stk, ctx, opt, txn, doc).await,
 			Self::Set(v) => v.compute(stk, ctx, opt, txn, doc).await,
 			Self::Show(v) => v.compute(ctx, opt, txn, doc).await,
@@ -190,7 +263,10 @@ impl Display for Statement {
 			Self::Create(v) => write!(Pretty::from(f), "{v}"),
 			Self::Define(v) => write!(Pretty::from(f), "{v}"),
 			Self::Delete(v) => write!(Pretty::from(f), "{v}"),
+			Self::DryRun(v) => write!(Pretty::from(f), "{v}"),
 			Self::Foreach(v) => write!(Pretty::from(f), "{v}"),
+			Self::Grant(v) => write!(Pretty::from(f), "{v}"),
+			Self::Alter(v) => write!(Pretty::from(f), "{v}"),
 			Self::Insert(v) => write!(Pretty::from(f), "{v}"),
 			Self::Ifelse(v) => write!(Pretty::from(f), "{v}"),
 			Self::Info(v) => write!(Pretty::from(f), "{v}"),
@@ -201,6 +277,7 @@ impl Display for Statement {
 			Self::Rebuild(v) => write!(Pretty::from(f), "{v}"),
 			Self::Relate(v) => write!(Pretty::from(f), "{v}"),
 			Self::Remove(v) => write!(Pretty::from(f), "{v}"),
+			Self::Report(v) => write!(Pretty::from(f), "{v}"),
 			Self::Select(v) => write!(Pretty::from(f), "{v}"),
 			Self::Set(v) => write!(Pretty::from(f), "{v}"),
 			Self::Show(v) => write!(Pretty::from(f), "{v}"),
@@ -211,3 +288,79 @@ impl Display for Statement {
 		}
 	}
 }
+
+/// A kind of statement, as named by the keyword which starts it
+#[revisioned(revision = 2)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub enum StatementKind {
+	Alter,
+	Analyze,
+	Begin,
+	Break,
+	Cancel,
+	Commit,
+	Continue,
+	Create,
+	Define,
+	Delete,
+	For,
+	Grant,
+	If,
+	Info,
+	Insert,
+	Kill,
+	Let,
+	Live,
+	Option,
+	Rebuild,
+	Relate,
+	Remove,
+	Return,
+	Select,
+	Show,
+	Sleep,
+	Throw,
+	Update,
+	Use,
+	#[revision(start = 2)]
+	Report,
+}
+
+impl Display for StatementKind {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		f.write_str(match self {
+			Self::Alter => "ALTER",
+			Self::Analyze => "ANALYZE",
+			Self::Begin => "BEGIN",
+			Self::Break => "BREAK",
+			Self::Cancel => "CANCEL",
+			Self::Commit => "COMMIT",
+			Self::Continue => "CONTINUE",
+			Self::Create => "CREATE",
+			Self::Define => "DEFINE",
+			Self::Delete => "DELETE",
+			Self::For => "FOR",
+			Self::Grant => "GRANT",
+			Self::If => "IF",
+			Self::Info => "INFO",
+			Self::Insert => "INSERT",
+			Self::Kill => "KILL",
+			Self::Let => "LET",
+			Self::Live => "LIVE",
+			Self::Option => "OPTION",
+			Self::Rebuild => "REBUILD",
+			Self::Relate => "RELATE",
+			Self::Remove => "REMOVE",
+			Self::Report => "REPORT",
+			Self::Return => "RETURN",
+			Self::Select => "SELECT",
+			Self::Show => "SHOW",
+			Self::Sleep => "SLEEP",
+			Self::Throw => "THROW",
+			Self::Update => "UPDATE",
+			Self::Use => "USE",
+		})
+	}
+}
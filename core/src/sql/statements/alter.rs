@@ -0,0 +1,122 @@
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::dbs::Transaction;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::iam::{Action, ResourceKind};
+use crate::kvs::backfill::Backfill;
+use crate::sql::statements::DefineFieldStatement;
+use crate::sql::{Base, Ident, Idiom, Kind, StatementKind, Value};
+use derive::Store;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub enum AlterStatement {
+	Field(AlterFieldStatement),
+}
+
+impl AlterStatement {
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(
+		&self,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		doc: Option<&CursorDoc<'_>>,
+	) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.check_statement(StatementKind::Alter)?;
+		match self {
+			Self::Field(s) => s.compute(ctx, opt, txn, doc).await,
+		}
+	}
+}
+
+impl Display for AlterStatement {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		match self {
+			Self::Field(v) => Display::fmt(v, f),
+		}
+	}
+}
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct AlterFieldStatement {
+	pub name: Idiom,
+	pub what: Ident,
+	pub if_exists: bool,
+	pub kind: Kind,
+	pub backfill: bool,
+	pub with: Option<Value>,
+}
+
+impl AlterFieldStatement {
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(
+		&self,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		doc: Option<&CursorDoc<'_>>,
+	) -> Result<Value, Error> {
+		let future = async {
+			// Allowed to run?
+			opt.is_allowed(Action::Edit, ResourceKind::Field, &Base::Db)?;
+			// Get the field definition
+			let fd = self.name.to_string();
+			let def = txn.lock().await.get_tb_field(opt.ns(), opt.db(), &self.what, &fd).await?;
+			// Update the definition, along with any nested field definitions
+			DefineFieldStatement {
+				kind: Some(self.kind.clone()),
+				..def
+			}
+			.compute(ctx, opt, txn, doc)
+			.await?;
+			// Convert the existing records in the background
+			if self.backfill {
+				let key = crate::key::table::bf::new(opt.ns(), opt.db(), &self.what, &fd);
+				let val = Backfill {
+					field: self.name.clone(),
+					kind: self.kind.clone(),
+					with: self.with.clone(),
+					..Default::default()
+				};
+				txn.lock().await.set(key, val).await?;
+			}
+			// Ok all good
+			Ok(Value::None)
+		}
+		.await;
+		match future {
+			Err(Error::FdNotFound {
+				..
+			}) if self.if_exists => Ok(Value::None),
+			v => v,
+		}
+	}
+}
+
+impl Display for AlterFieldStatement {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "ALTER FIELD")?;
+		if self.if_exists {
+			write!(f, " IF EXISTS")?
+		}
+		write!(f, " {} ON {} TYPE {}", self.name, self.what, self.kind)?;
+		if self.backfill {
+			write!(f, " BACKFILL")?;
+			if let Some(ref v) = self.with {
+				write!(f, " WITH {v}")?
+			}
+		}
+		Ok(())
+	}
+}
@@ -12,6 +12,7 @@ use crate::sql::ident::Ident;
 use crate::sql::index::Index;
 use crate::sql::value::Value;
 use crate::sql::Base;
+use crate::sql::StatementKind;
 use derive::Store;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
@@ -35,6 +36,8 @@ impl AnalyzeStatement {
 		txn: &Transaction,
 		_doc: Option<&CursorDoc<'_>>,
 	) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.check_statement(StatementKind::Analyze)?;
 		match self {
 			AnalyzeStatement::Idx(tb, idx) => {
 				// Allowed to run?
@@ -1,16 +1,25 @@
+use crate::sql::Isolation;
 use derive::Store;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
-pub struct BeginStatement;
+pub struct BeginStatement {
+	// The requested isolation level of the transaction
+	#[revision(start = 2)]
+	pub isolation: Option<Isolation>,
+}
 
 impl fmt::Display for BeginStatement {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		f.write_str("BEGIN TRANSACTION")
+		f.write_str("BEGIN TRANSACTION")?;
+		if let Some(ref v) = self.isolation {
+			write!(f, " {v}")?
+		}
+		Ok(())
 	}
 }
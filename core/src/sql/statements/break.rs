@@ -3,6 +3,7 @@ use crate::dbs::{Options, Transaction};
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::sql::value::Value;
+use crate::sql::StatementKind;
 use derive::Store;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
@@ -23,10 +24,12 @@ impl BreakStatement {
 	pub(crate) async fn compute(
 		&self,
 		_ctx: &Context<'_>,
-		_opt: &Options,
+		opt: &Options,
 		_txn: &Transaction,
 		_doc: Option<&CursorDoc<'_>>,
 	) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.check_statement(StatementKind::Break)?;
 		Err(Error::Break)
 	}
 }
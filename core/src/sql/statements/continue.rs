@@ -2,6 +2,7 @@ use crate::ctx::Context;
 use crate::dbs::{Options, Transaction};
 use crate::doc::CursorDoc;
 use crate::err::Error;
+use crate::sql::StatementKind;
 use crate::sql::Value;
 use derive::Store;
 use revision::revisioned;
@@ -23,10 +24,12 @@ impl ContinueStatement {
 	pub(crate) async fn compute(
 		&self,
 		_ctx: &Context<'_>,
-		_opt: &Options,
+		opt: &Options,
 		_txn: &Transaction,
 		_doc: Option<&CursorDoc<'_>>,
 	) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.check_statement(StatementKind::Continue)?;
 		Err(Error::Continue)
 	}
 }
@@ -2,7 +2,7 @@ use crate::ctx::Context;
 use crate::dbs::{Iterator, Options, Statement, Transaction};
 use crate::doc::CursorDoc;
 use crate::err::Error;
-use crate::sql::{Data, Output, Timeout, Value, Values};
+use crate::sql::{Data, Output, StatementKind, Timeout, Value, Values};
 use derive::Store;
 use reblessive::tree::Stk;
 use revision::revisioned;
@@ -43,6 +43,8 @@ impl CreateStatement {
 		txn: &Transaction,
 		doc: Option<&CursorDoc<'_>>,
 	) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.check_statement(StatementKind::Create)?;
 		// Valid options?
 		opt.valid_for_db()?;
 		// Create a new iterator
@@ -0,0 +1,175 @@
+use crate::ctx::Context;
+use crate::dbs::{Options, Transaction};
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::iam::{Action, ResourceKind};
+use crate::sql::statements::info::InfoStructure;
+use crate::sql::{Base, Duration, Field, Function, Ident, Object, Strand, Subquery, Value};
+use derive::Store;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+use url::Url;
+
+/// A data quality check, which runs a query on a schedule, and records
+/// a failure whenever the result of the query does not pass the assertion.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct DefineCheckStatement {
+	pub name: Ident,
+	pub schedule: Duration,
+	pub query: Value,
+	pub assert: Value,
+	pub webhook: Option<Strand>,
+	pub comment: Option<Strand>,
+	pub if_not_exists: bool,
+}
+
+impl DefineCheckStatement {
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(
+		&self,
+		_ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		_doc: Option<&CursorDoc<'_>>,
+	) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Database, &Base::Db)?;
+		// Check the schedule
+		if self.schedule.is_zero() {
+			return Err(Error::InvalidArguments {
+				name: self.name.to_string(),
+				message: "The check must be scheduled to run after a non-zero duration".to_owned(),
+			});
+		}
+		// Check the webhook
+		if let Some(url) = &self.webhook {
+			Url::parse(url).map_err(|_| Error::InvalidUrl(url.to_string()))?;
+		}
+		// Claim transaction
+		let mut run = txn.lock().await;
+		// Clear the cache
+		run.clear_cache();
+		// Check if check already exists
+		if self.if_not_exists && run.get_db_check(opt.ns(), opt.db(), &self.name).await.is_ok() {
+			return Err(Error::CkAlreadyExists {
+				value: self.name.to_string(),
+			});
+		}
+		// Process the statement
+		let key = crate::key::database::ck::new(opt.ns(), opt.db(), &self.name);
+		run.add_ns(opt.ns(), opt.strict).await?;
+		run.add_db(opt.ns(), opt.db(), opt.strict).await?;
+		run.set(
+			key,
+			DefineCheckStatement {
+				// Don't persist the "IF NOT EXISTS" clause to schema
+				if_not_exists: false,
+				..self.clone()
+			},
+		)
+		.await?;
+		// Ok all good
+		Ok(Value::None)
+	}
+
+	/// Reduce the output of the query to the `$value` which is asserted.
+	///
+	/// When the query only selects a `count()`, such as
+	/// `SELECT count() FROM likes WHERE in = NONE`, the counts are
+	/// totalled, so that `$value` is the number of matching records,
+	/// and is 0 when no records match.
+	pub(crate) fn outcome(&self, value: Value) -> Value {
+		match (self.counts(), value) {
+			(true, Value::Array(rows)) => {
+				let total: i64 = rows
+					.iter()
+					.filter_map(|row| match row {
+						Value::Object(v) if v.len() == 1 => v.values().next(),
+						_ => None,
+					})
+					.map(|v| match v {
+						Value::Number(v) => v.to_int(),
+						_ => 0,
+					})
+					.sum();
+				Value::from(total)
+			}
+			(_, value) => value,
+		}
+	}
+
+	/// Whether the query is a SELECT statement which only selects a `count()`
+	fn counts(&self) -> bool {
+		let Value::Subquery(v) = &self.query else {
+			return false;
+		};
+		let Subquery::Select(stm) = v.as_ref() else {
+			return false;
+		};
+		match stm.expr.0.as_slice() {
+			[Field::Single {
+				expr: Value::Function(f),
+				..
+			}] => matches!(f.as_ref(), Function::Normal(name, _) if name == "count"),
+			_ => false,
+		}
+	}
+}
+
+impl Display for DefineCheckStatement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "DEFINE CHECK")?;
+		if self.if_not_exists {
+			write!(f, " IF NOT EXISTS")?
+		}
+		write!(
+			f,
+			" {} SCHEDULE {} AS {} ASSERT {}",
+			self.name, self.schedule, self.query, self.assert
+		)?;
+		if let Some(ref v) = self.webhook {
+			write!(f, " WEBHOOK {v}")?
+		}
+		if let Some(ref v) = self.comment {
+			write!(f, " COMMENT {v}")?
+		}
+		Ok(())
+	}
+}
+
+impl InfoStructure for DefineCheckStatement {
+	fn structure(self) -> Value {
+		let Self {
+			name,
+			schedule,
+			query,
+			assert,
+			webhook,
+			comment,
+			..
+		} = self;
+		let mut acc = Object::default();
+
+		acc.insert("name".to_string(), name.structure());
+
+		acc.insert("schedule".to_string(), schedule.into());
+
+		acc.insert("query".to_string(), query.structure());
+
+		acc.insert("assert".to_string(), assert.structure());
+
+		if let Some(webhook) = webhook {
+			acc.insert("webhook".to_string(), webhook.into());
+		}
+
+		if let Some(comment) = comment {
+			acc.insert("comment".to_string(), comment.into());
+		}
+
+		Value::Object(acc)
+	}
+}
@@ -4,13 +4,13 @@ use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::iam::{Action, ResourceKind};
 use crate::sql::statements::info::InfoStructure;
-use crate::sql::{changefeed::ChangeFeed, Base, Ident, Object, Strand, Value};
+use crate::sql::{changefeed::ChangeFeed, Base, Ident, Labels, Object, Strand, Value};
 use derive::Store;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display};
 
-#[revisioned(revision = 2)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -21,6 +21,8 @@ pub struct DefineDatabaseStatement {
 	pub changefeed: Option<ChangeFeed>,
 	#[revision(start = 2)]
 	pub if_not_exists: bool,
+	#[revision(start = 3)]
+	pub labels: Option<Labels>,
 }
 
 impl DefineDatabaseStatement {
@@ -82,6 +84,9 @@ impl Display for DefineDatabaseStatement {
 		if let Some(ref v) = self.comment {
 			write!(f, " COMMENT {v}")?
 		}
+		if let Some(ref v) = self.labels {
+			write!(f, " {v}")?
+		}
 		if let Some(ref v) = self.changefeed {
 			write!(f, " {v}")?;
 		}
@@ -94,6 +99,7 @@ impl InfoStructure for DefineDatabaseStatement {
 		let Self {
 			name,
 			comment,
+			labels,
 			..
 		} = self;
 		let mut acc = Object::default();
@@ -104,6 +110,10 @@ impl InfoStructure for DefineDatabaseStatement {
 			acc.insert("comment".to_string(), comment.into());
 		}
 
+		if let Some(labels) = labels {
+			acc.insert("labels".to_string(), labels.structure());
+		}
+
 		Value::Object(acc)
 	}
 }
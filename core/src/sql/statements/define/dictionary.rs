@@ -0,0 +1,148 @@
+use crate::ctx::Context;
+use crate::dbs::{Options, Transaction};
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::iam::{Action, ResourceKind};
+use crate::sql::statements::info::InfoStructure;
+use crate::sql::{fmt::Fmt, Base, Ident, Object, Strand, Value};
+use derive::Store;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct DefineDictionaryStatement {
+	pub name: Ident,
+	pub kind: DictionaryKind,
+	pub comment: Option<Strand>,
+	pub if_not_exists: bool,
+}
+
+/// The contents of a dictionary which can be used by analyzer filters
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub enum DictionaryKind {
+	/// A list of terms which are removed from the token stream
+	Stopwords(Vec<Strand>),
+	/// Groups of equivalent terms, where each term in a group
+	/// is replaced with the first term of that group
+	Synonyms(Vec<Vec<Strand>>),
+}
+
+impl DefineDictionaryStatement {
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(
+		&self,
+		_ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		_doc: Option<&CursorDoc<'_>>,
+	) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Analyzer, &Base::Db)?;
+		// Claim transaction
+		let mut run = txn.lock().await;
+		// Clear the cache
+		run.clear_cache();
+		// Check if dictionary already exists
+		if self.if_not_exists && run.get_db_dictionary(opt.ns(), opt.db(), &self.name).await.is_ok()
+		{
+			return Err(Error::DcAlreadyExists {
+				value: self.name.to_string(),
+			});
+		}
+		// Process the statement
+		let key = crate::key::database::dc::new(opt.ns(), opt.db(), &self.name);
+		run.add_ns(opt.ns(), opt.strict).await?;
+		run.add_db(opt.ns(), opt.db(), opt.strict).await?;
+		run.set(
+			key,
+			DefineDictionaryStatement {
+				// Don't persist the "IF NOT EXISTS" clause to schema
+				if_not_exists: false,
+				..self.clone()
+			},
+		)
+		.await?;
+		// Ok all good
+		Ok(Value::None)
+	}
+}
+
+impl Display for DefineDictionaryStatement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "DEFINE DICTIONARY")?;
+		if self.if_not_exists {
+			write!(f, " IF NOT EXISTS")?
+		}
+		write!(f, " {} {}", self.name, self.kind)?;
+		if let Some(ref v) = self.comment {
+			write!(f, " COMMENT {v}")?
+		}
+		Ok(())
+	}
+}
+
+impl Display for DictionaryKind {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Stopwords(v) => write!(f, "STOPWORDS [{}]", Fmt::comma_separated(v.iter())),
+			Self::Synonyms(v) => {
+				f.write_str("SYNONYMS ")?;
+				for (i, g) in v.iter().enumerate() {
+					if i > 0 {
+						f.write_str(", ")?;
+					}
+					write!(f, "[{}]", Fmt::comma_separated(g))?;
+				}
+				Ok(())
+			}
+		}
+	}
+}
+
+impl InfoStructure for DefineDictionaryStatement {
+	fn structure(self) -> Value {
+		let Self {
+			name,
+			kind,
+			comment,
+			..
+		} = self;
+		let mut acc = Object::default();
+
+		acc.insert("name".to_string(), name.structure());
+
+		match kind {
+			DictionaryKind::Stopwords(v) => {
+				acc.insert("kind".to_string(), "stopwords".into());
+				acc.insert(
+					"terms".to_string(),
+					Value::Array(v.into_iter().map(Value::from).collect()),
+				);
+			}
+			DictionaryKind::Synonyms(v) => {
+				acc.insert("kind".to_string(), "synonyms".into());
+				acc.insert(
+					"terms".to_string(),
+					Value::Array(
+						v.into_iter()
+							.map(|g| Value::Array(g.into_iter().map(Value::from).collect()))
+							.collect(),
+					),
+				);
+			}
+		}
+
+		if let Some(comment) = comment {
+			acc.insert("comment".to_string(), comment.into());
+		}
+
+		Value::Object(acc)
+	}
+}
@@ -0,0 +1,130 @@
+use crate::ctx::Context;
+use crate::dbs::{Options, Transaction};
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::iam::{Action, ResourceKind};
+use crate::sql::statements::info::InfoStructure;
+use crate::sql::{Base, Ident, Idiom, Object, Strand, Value};
+use derive::Store;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+
+/// An enrichment which computes a value for each record in a table which is
+/// created or updated. The computation runs in the background after the write
+/// has committed, and its result is written back to the record, so that slow
+/// functions are kept out of the write path. A failed computation is retried,
+/// and then moved to the dead-letter table of the table.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct DefineEnrichmentStatement {
+	pub name: Ident,
+	pub what: Ident,
+	pub call: Value,
+	pub into: Option<Idiom>,
+	pub retry: Option<u32>,
+	pub comment: Option<Strand>,
+	pub if_not_exists: bool,
+}
+
+impl DefineEnrichmentStatement {
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(
+		&self,
+		_ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		_doc: Option<&CursorDoc<'_>>,
+	) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Event, &Base::Db)?;
+		// Claim transaction
+		let mut run = txn.lock().await;
+		// Clear the cache
+		run.clear_cache();
+		// Check if enrichment already exists
+		if self.if_not_exists
+			&& run.get_tb_enrichment(opt.ns(), opt.db(), &self.what, &self.name).await.is_ok()
+		{
+			return Err(Error::EnAlreadyExists {
+				value: self.name.to_string(),
+			});
+		}
+		// Process the statement
+		let key = crate::key::table::en::new(opt.ns(), opt.db(), &self.what, &self.name);
+		run.add_ns(opt.ns(), opt.strict).await?;
+		run.add_db(opt.ns(), opt.db(), opt.strict).await?;
+		run.add_tb(opt.ns(), opt.db(), &self.what, opt.strict).await?;
+		run.set(
+			key,
+			DefineEnrichmentStatement {
+				if_not_exists: false,
+				..self.clone()
+			},
+		)
+		.await?;
+		// Clear the cache
+		let key = crate::key::table::en::prefix(opt.ns(), opt.db(), &self.what);
+		run.clr(key).await?;
+		// Ok all good
+		Ok(Value::None)
+	}
+}
+
+impl Display for DefineEnrichmentStatement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "DEFINE ENRICHMENT")?;
+		if self.if_not_exists {
+			write!(f, " IF NOT EXISTS")?
+		}
+		write!(f, " {} ON {} CALL {}", self.name, self.what, self.call)?;
+		if let Some(ref v) = self.into {
+			write!(f, " INTO {v}")?
+		}
+		write!(f, " ASYNC")?;
+		if let Some(v) = self.retry {
+			write!(f, " RETRY {v}")?
+		}
+		if let Some(ref v) = self.comment {
+			write!(f, " COMMENT {v}")?
+		}
+		Ok(())
+	}
+}
+
+impl InfoStructure for DefineEnrichmentStatement {
+	fn structure(self) -> Value {
+		let Self {
+			name,
+			what,
+			call,
+			into,
+			retry,
+			comment,
+			..
+		} = self;
+		let mut acc = Object::default();
+
+		acc.insert("name".to_string(), name.structure());
+
+		acc.insert("what".to_string(), what.structure());
+
+		acc.insert("call".to_string(), call.structure());
+
+		if let Some(into) = into {
+			acc.insert("into".to_string(), into.structure());
+		}
+
+		if let Some(retry) = retry {
+			acc.insert("retry".to_string(), retry.into());
+		}
+
+		if let Some(comment) = comment {
+			acc.insert("comment".to_string(), comment.into());
+		}
+
+		Value::Object(acc)
+	}
+}
@@ -1,12 +1,15 @@
+use crate::cnf::PROCESSOR_BATCH_SIZE;
 use crate::ctx::Context;
 use crate::dbs::{Options, Transaction};
-use crate::doc::CursorDoc;
+use crate::doc::{timestamp, CursorDoc};
 use crate::err::Error;
 use crate::iam::{Action, ResourceKind};
+use crate::kvs::ScanPage;
 use crate::sql::statements::info::InfoStructure;
 use crate::sql::statements::DefineTableStatement;
 use crate::sql::{
-	fmt::is_pretty, fmt::pretty_indent, Base, Ident, Idiom, Kind, Permissions, Strand, Value,
+	fmt::is_pretty, fmt::pretty_indent, Base, Crdt, Ident, Idiom, Kind, Permissions, Strand, Thing,
+	Value,
 };
 use crate::sql::{Object, Part};
 use crate::sql::{Relation, TableType};
@@ -15,7 +18,7 @@ use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display, Write};
 
-#[revisioned(revision = 3)]
+#[revisioned(revision = 6)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -33,6 +36,12 @@ pub struct DefineFieldStatement {
 	pub comment: Option<Strand>,
 	#[revision(start = 3)]
 	pub if_not_exists: bool,
+	#[revision(start = 4)]
+	pub expire: bool,
+	#[revision(start = 5)]
+	pub crdt: Option<Crdt>,
+	#[revision(start = 6)]
+	pub computed: bool,
 }
 
 impl DefineFieldStatement {
@@ -46,6 +55,24 @@ impl DefineFieldStatement {
 	) -> Result<Value, Error> {
 		// Allowed to run?
 		opt.is_allowed(Action::Edit, ResourceKind::Field, &Base::Db)?;
+		// Check that the field can define an expiry time
+		if self.expire {
+			match &self.kind {
+				Some(Kind::Datetime) => (),
+				Some(Kind::Option(k)) if **k == Kind::Datetime => (),
+				_ => {
+					return Err(Error::FieldExpire {
+						field: self.name.clone(),
+					})
+				}
+			}
+		}
+		// Check that the field has a value which can be recomputed
+		if self.computed && self.value.is_none() {
+			return Err(Error::FieldComputed {
+				field: self.name.clone(),
+			});
+		}
 		// Claim transaction
 		let mut run = txn.lock().await;
 		// Clear the cache
@@ -159,6 +186,26 @@ impl DefineFieldStatement {
 			run.clr(key).await?;
 		}
 
+		// Store the expiry times of any existing records
+		if self.expire {
+			let beg = crate::key::thing::prefix(opt.ns(), opt.db(), &self.what);
+			let end = crate::key::thing::suffix(opt.ns(), opt.db(), &self.what);
+			let mut next_page = Some(ScanPage::from(beg..end));
+			while let Some(page) = next_page {
+				let res = run.scan_paged(page, PROCESSOR_BATCH_SIZE).await?;
+				next_page = res.next_page;
+				for (k, v) in res.values.into_iter() {
+					let key: crate::key::thing::Thing = (&k).into();
+					let val: Value = (&v).into();
+					if let Some(ts) = timestamp(&val.pick(&self.name)) {
+						let rid = Thing::from((key.tb, key.id));
+						let key =
+							crate::key::root::ex::new(opt.ns(), opt.db(), &rid.tb, ts, &rid.id);
+						run.set(key, &rid).await?;
+					}
+				}
+			}
+		}
 		// Clear the cache
 		let key = crate::key::table::fd::prefix(opt.ns(), opt.db(), &self.what);
 		run.clr(key).await?;
@@ -186,9 +233,18 @@ impl Display for DefineFieldStatement {
 		if self.readonly {
 			write!(f, " READONLY")?
 		}
+		if self.expire {
+			write!(f, " EXPIRE")?
+		}
+		if let Some(ref v) = self.crdt {
+			write!(f, " CRDT {v}")?
+		}
 		if let Some(ref v) = self.value {
 			write!(f, " VALUE {v}")?
 		}
+		if self.computed {
+			write!(f, " COMPUTED")?
+		}
 		if let Some(ref v) = self.assert {
 			write!(f, " ASSERT {v}")?
 		}
@@ -214,7 +270,10 @@ impl InfoStructure for DefineFieldStatement {
 			flex,
 			kind,
 			readonly,
+			expire,
+			crdt,
 			value,
+			computed,
 			assert,
 			default,
 			permissions,
@@ -235,10 +294,18 @@ impl InfoStructure for DefineFieldStatement {
 
 		acc.insert("readonly".to_string(), readonly.into());
 
+		acc.insert("expire".to_string(), expire.into());
+
+		if let Some(crdt) = crdt {
+			acc.insert("crdt".to_string(), crdt.to_string().into());
+		}
+
 		if let Some(value) = value {
 			acc.insert("value".to_string(), value.structure());
 		}
 
+		acc.insert("computed".to_string(), computed.into());
+
 		if let Some(assert) = assert {
 			acc.insert("assert".to_string(), assert.structure());
 		}
@@ -0,0 +1,109 @@
+use crate::ctx::Context;
+use crate::dbs::{Options, Transaction};
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::iam::{Action, ResourceKind};
+use crate::sql::statements::info::InfoStructure;
+use crate::sql::{Base, Ident, Object, Strand, Value};
+use derive::Store;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+
+/// A hook which publishes a message to a channel whenever a record in a
+/// table is created, updated, or deleted, once the transaction commits.
+/// A hook which is defined without a name is named after its channel.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct DefineHookStatement {
+	pub name: Ident,
+	pub what: Ident,
+	pub channel: Strand,
+	pub comment: Option<Strand>,
+	pub if_not_exists: bool,
+}
+
+impl DefineHookStatement {
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(
+		&self,
+		_ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		_doc: Option<&CursorDoc<'_>>,
+	) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Event, &Base::Db)?;
+		// Claim transaction
+		let mut run = txn.lock().await;
+		// Clear the cache
+		run.clear_cache();
+		// Check if hook already exists
+		if self.if_not_exists
+			&& run.get_tb_hook(opt.ns(), opt.db(), &self.what, &self.name).await.is_ok()
+		{
+			return Err(Error::HkAlreadyExists {
+				value: self.name.to_string(),
+			});
+		}
+		// Process the statement
+		let key = crate::key::table::hk::new(opt.ns(), opt.db(), &self.what, &self.name);
+		run.add_ns(opt.ns(), opt.strict).await?;
+		run.add_db(opt.ns(), opt.db(), opt.strict).await?;
+		run.add_tb(opt.ns(), opt.db(), &self.what, opt.strict).await?;
+		run.set(
+			key,
+			DefineHookStatement {
+				if_not_exists: false,
+				..self.clone()
+			},
+		)
+		.await?;
+		// Clear the cache
+		let key = crate::key::table::hk::prefix(opt.ns(), opt.db(), &self.what);
+		run.clr(key).await?;
+		// Ok all good
+		Ok(Value::None)
+	}
+}
+
+impl Display for DefineHookStatement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "DEFINE HOOK")?;
+		if self.if_not_exists {
+			write!(f, " IF NOT EXISTS")?
+		}
+		write!(f, " {} ON {} AFTER COMMIT PUBLISH {}", self.name, self.what, self.channel)?;
+		if let Some(ref v) = self.comment {
+			write!(f, " COMMENT {v}")?
+		}
+		Ok(())
+	}
+}
+
+impl InfoStructure for DefineHookStatement {
+	fn structure(self) -> Value {
+		let Self {
+			name,
+			what,
+			channel,
+			comment,
+			..
+		} = self;
+		let mut acc = Object::default();
+
+		acc.insert("name".to_string(), name.structure());
+
+		acc.insert("what".to_string(), what.structure());
+
+		acc.insert("channel".to_string(), channel.into());
+
+		if let Some(comment) = comment {
+			acc.insert("comment".to_string(), comment.into());
+		}
+
+		Value::Object(acc)
+	}
+}
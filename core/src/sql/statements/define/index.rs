@@ -1,3 +1,4 @@
+use crate::cnf::NORMAL_FETCH_SIZE;
 use crate::ctx::Context;
 use crate::dbs::{Force, Options, Transaction};
 use crate::doc::CursorDoc;
@@ -68,7 +69,7 @@ impl DefineIndexStatement {
 		.await?;
 		// Remove the index data
 		let key = crate::key::index::all::new(opt.ns(), opt.db(), &self.what, &self.name);
-		run.delp(key, u32::MAX).await?;
+		run.delp(key, NORMAL_FETCH_SIZE).await?;
 		// Clear the cache
 		let key = crate::key::table::ix::prefix(opt.ns(), opt.db(), &self.what);
 		run.clr(key).await?;
@@ -1,26 +1,36 @@
 mod analyzer;
+mod check;
 mod database;
+mod dictionary;
+mod enrichment;
 mod event;
 mod field;
 mod function;
+mod hook;
 mod index;
 mod model;
 mod namespace;
 mod param;
+mod role;
 mod scope;
 mod table;
 mod token;
 mod user;
 
 pub use analyzer::DefineAnalyzerStatement;
+pub use check::DefineCheckStatement;
 pub use database::DefineDatabaseStatement;
+pub use dictionary::{DefineDictionaryStatement, DictionaryKind};
+pub use enrichment::DefineEnrichmentStatement;
 pub use event::DefineEventStatement;
 pub use field::DefineFieldStatement;
 pub use function::DefineFunctionStatement;
+pub use hook::DefineHookStatement;
 pub use index::DefineIndexStatement;
 pub use model::DefineModelStatement;
 pub use namespace::DefineNamespaceStatement;
 pub use param::DefineParamStatement;
+pub use role::{DefineRoleStatement, Privilege, RoleGrant};
 pub use scope::DefineScopeStatement;
 pub use table::DefineTableStatement;
 pub use token::DefineTokenStatement;
@@ -31,14 +41,17 @@ use crate::dbs::Options;
 use crate::dbs::Transaction;
 use crate::doc::CursorDoc;
 use crate::err::Error;
+use crate::kvs::history;
 use crate::sql::value::Value;
+use crate::sql::Ident;
+use crate::sql::StatementKind;
 use derive::Store;
 use reblessive::tree::Stk;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display};
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 6)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -56,6 +69,16 @@ pub enum DefineStatement {
 	Index(DefineIndexStatement),
 	User(DefineUserStatement),
 	Model(DefineModelStatement),
+	#[revision(start = 2)]
+	Role(DefineRoleStatement),
+	#[revision(start = 3)]
+	Dictionary(DefineDictionaryStatement),
+	#[revision(start = 4)]
+	Check(DefineCheckStatement),
+	#[revision(start = 5)]
+	Hook(DefineHookStatement),
+	#[revision(start = 6)]
+	Enrichment(DefineEnrichmentStatement),
 }
 
 impl DefineStatement {
@@ -72,7 +95,9 @@ impl DefineStatement {
 		txn: &Transaction,
 		doc: Option<&CursorDoc<'_>>,
 	) -> Result<Value, Error> {
-		match self {
+		// Allowed to run?
+		opt.check_statement(StatementKind::Define)?;
+		let res = match self {
 			Self::Namespace(ref v) => v.compute(ctx, opt, txn, doc).await,
 			Self::Database(ref v) => v.compute(ctx, opt, txn, doc).await,
 			Self::Function(ref v) => v.compute(ctx, opt, txn, doc).await,
@@ -86,6 +111,27 @@ impl DefineStatement {
 			Self::Analyzer(ref v) => v.compute(ctx, opt, txn, doc).await,
 			Self::User(ref v) => v.compute(ctx, opt, txn, doc).await,
 			Self::Model(ref v) => v.compute(ctx, opt, txn, doc).await,
+			Self::Role(ref v) => v.compute(ctx, opt, txn, doc).await,
+			Self::Dictionary(ref v) => v.compute(ctx, opt, txn, doc).await,
+			Self::Check(ref v) => v.compute(ctx, opt, txn, doc).await,
+			Self::Hook(ref v) => v.compute(ctx, opt, txn, doc).await,
+			Self::Enrichment(ref v) => v.compute(ctx, opt, txn, doc).await,
+		}?;
+		// Record the statement in the audit log
+		history::record(opt, txn, self.table(), self.to_string()).await?;
+		// Ok all good
+		Ok(res)
+	}
+	/// The table which this statement applies to, if any
+	fn table(&self) -> Option<&Ident> {
+		match self {
+			Self::Table(v) => Some(&v.name),
+			Self::Event(v) => Some(&v.what),
+			Self::Field(v) => Some(&v.what),
+			Self::Index(v) => Some(&v.what),
+			Self::Hook(v) => Some(&v.what),
+			Self::Enrichment(v) => Some(&v.what),
+			_ => None,
 		}
 	}
 }
@@ -106,6 +152,11 @@ impl Display for DefineStatement {
 			Self::Index(v) => Display::fmt(v, f),
 			Self::Analyzer(v) => Display::fmt(v, f),
 			Self::Model(v) => Display::fmt(v, f),
+			Self::Role(v) => Display::fmt(v, f),
+			Self::Dictionary(v) => Display::fmt(v, f),
+			Self::Check(v) => Display::fmt(v, f),
+			Self::Hook(v) => Display::fmt(v, f),
+			Self::Enrichment(v) => Display::fmt(v, f),
 		}
 	}
 }
@@ -123,6 +174,6 @@ mod tests {
 			..Default::default()
 		});
 		let enc: Vec<u8> = stm.into();
-		assert_eq!(12, enc.len());
+		assert_eq!(13, enc.len());
 	}
 }
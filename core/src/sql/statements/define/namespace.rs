@@ -4,13 +4,13 @@ use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::iam::{Action, ResourceKind};
 use crate::sql::statements::info::InfoStructure;
-use crate::sql::{Base, Ident, Object, Strand, Value};
+use crate::sql::{Base, Ident, Labels, Object, Strand, Value};
 use derive::Store;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display};
 
-#[revisioned(revision = 2)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -20,6 +20,8 @@ pub struct DefineNamespaceStatement {
 	pub comment: Option<Strand>,
 	#[revision(start = 2)]
 	pub if_not_exists: bool,
+	#[revision(start = 3)]
+	pub labels: Option<Labels>,
 }
 
 impl DefineNamespaceStatement {
@@ -78,6 +80,9 @@ impl Display for DefineNamespaceStatement {
 		if let Some(ref v) = self.comment {
 			write!(f, " COMMENT {v}")?
 		}
+		if let Some(ref v) = self.labels {
+			write!(f, " {v}")?
+		}
 		Ok(())
 	}
 }
@@ -87,6 +92,7 @@ impl InfoStructure for DefineNamespaceStatement {
 		let Self {
 			name,
 			comment,
+			labels,
 			..
 		} = self;
 		let mut acc = Object::default();
@@ -97,6 +103,10 @@ impl InfoStructure for DefineNamespaceStatement {
 			acc.insert("comment".to_string(), comment.into());
 		}
 
+		if let Some(labels) = labels {
+			acc.insert("labels".to_string(), labels.structure());
+		}
+
 		Value::Object(acc)
 	}
 }
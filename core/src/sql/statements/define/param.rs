@@ -5,14 +5,14 @@ use crate::err::Error;
 use crate::iam::{Action, ResourceKind};
 use crate::sql::fmt::{is_pretty, pretty_indent};
 use crate::sql::statements::info::InfoStructure;
-use crate::sql::{Base, Ident, Object, Permission, Strand, Value};
+use crate::sql::{Base, Ident, Kind, Object, Permission, Strand, Value};
 use derive::Store;
 use reblessive::tree::Stk;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display, Write};
 
-#[revisioned(revision = 2)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -23,6 +23,8 @@ pub struct DefineParamStatement {
 	pub permissions: Permission,
 	#[revision(start = 2)]
 	pub if_not_exists: bool,
+	#[revision(start = 3)]
+	pub kind: Option<Kind>,
 }
 
 impl DefineParamStatement {
@@ -51,11 +53,14 @@ impl DefineParamStatement {
 		let key = crate::key::database::pa::new(opt.ns(), opt.db(), &self.name);
 		run.add_ns(opt.ns(), opt.strict).await?;
 		run.add_db(opt.ns(), opt.db(), opt.strict).await?;
+		// Compute the param
+		let value = self.value.compute(stk, ctx, opt, txn, doc).await?;
+		// Check the param against its TYPE clause
+		let value = self.check(value)?;
 		run.set(
 			key,
 			DefineParamStatement {
-				// Compute the param
-				value: self.value.compute(stk, ctx, opt, txn, doc).await?,
+				value,
 				// Don't persist the "IF NOT EXISTS" clause to schema
 				if_not_exists: false,
 				..self.clone()
@@ -65,6 +70,26 @@ impl DefineParamStatement {
 		// Ok all good
 		Ok(Value::None)
 	}
+
+	/// Check that a value for this param conforms to its TYPE clause
+	pub(crate) fn check(&self, value: Value) -> Result<Value, Error> {
+		match &self.kind {
+			Some(kind) => value.coerce_to(kind).map_err(|e| match e {
+				// There was a conversion error
+				Error::CoerceTo {
+					from,
+					..
+				} => Error::ParamCheck {
+					name: self.name.to_raw(),
+					value: from.to_string(),
+					check: kind.to_string(),
+				},
+				// There was a different error
+				e => e,
+			}),
+			None => Ok(value),
+		}
+	}
 }
 
 impl Display for DefineParamStatement {
@@ -73,7 +98,11 @@ impl Display for DefineParamStatement {
 		if self.if_not_exists {
 			write!(f, " IF NOT EXISTS")?
 		}
-		write!(f, " ${} VALUE {}", self.name, self.value)?;
+		write!(f, " ${}", self.name)?;
+		if let Some(ref v) = self.kind {
+			write!(f, " TYPE {v}")?
+		}
+		write!(f, " VALUE {}", self.value)?;
 		if let Some(ref v) = self.comment {
 			write!(f, " COMMENT {v}")?
 		}
@@ -95,6 +124,7 @@ impl InfoStructure for DefineParamStatement {
 			value,
 			comment,
 			permissions,
+			kind,
 			..
 		} = self;
 		let mut acc = Object::default();
@@ -103,6 +133,10 @@ impl InfoStructure for DefineParamStatement {
 
 		acc.insert("value".to_string(), value.structure());
 
+		if let Some(kind) = kind {
+			acc.insert("kind".to_string(), kind.structure());
+		}
+
 		if let Some(comment) = comment {
 			acc.insert("comment".to_string(), comment.into());
 		}
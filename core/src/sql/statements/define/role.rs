@@ -0,0 +1,190 @@
+use crate::ctx::Context;
+use crate::dbs::{Options, Transaction};
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::iam::{Action, ResourceKind, Role};
+use crate::sql::statements::info::InfoStructure;
+use crate::sql::{fmt::Fmt, Base, Ident, Object, Strand, Value};
+use derive::Store;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct DefineRoleStatement {
+	pub name: Ident,
+	pub grants: Vec<RoleGrant>,
+	pub comment: Option<Strand>,
+	pub if_not_exists: bool,
+}
+
+/// A set of privileges which a role grants on a set of tables
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct RoleGrant {
+	pub privileges: Vec<Privilege>,
+	pub tables: Vec<Ident>,
+}
+
+/// An action on the records of a table which can be granted to a role
+#[revisioned(revision = 1)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub enum Privilege {
+	Select,
+	Create,
+	Update,
+	Delete,
+}
+
+impl DefineRoleStatement {
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(
+		&self,
+		_ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		_doc: Option<&CursorDoc<'_>>,
+	) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Actor, &Base::Db)?;
+		// Built-in roles can not be redefined, as grants
+		// of these names always resolve to the built-in role
+		if Role::from_str(&self.name).is_ok() {
+			return Err(Error::RoReserved {
+				value: self.name.to_string(),
+			});
+		}
+		// Claim transaction
+		let mut run = txn.lock().await;
+		// Clear the cache
+		run.clear_cache();
+		// Check if role already exists
+		if self.if_not_exists && run.get_db_role(opt.ns(), opt.db(), &self.name).await.is_ok() {
+			return Err(Error::RoAlreadyExists {
+				value: self.name.to_string(),
+			});
+		}
+		// Process the statement
+		let key = crate::key::database::ro::new(opt.ns(), opt.db(), &self.name);
+		run.add_ns(opt.ns(), opt.strict).await?;
+		run.add_db(opt.ns(), opt.db(), opt.strict).await?;
+		run.set(
+			key,
+			DefineRoleStatement {
+				// Don't persist the "IF NOT EXISTS" clause to schema
+				if_not_exists: false,
+				..self.clone()
+			},
+		)
+		.await?;
+		// Ok all good
+		Ok(Value::None)
+	}
+
+	/// Check if this role grants a privilege on a table
+	pub(crate) fn allows(&self, tb: &str, privilege: Privilege) -> bool {
+		self.grants
+			.iter()
+			.any(|g| g.privileges.contains(&privilege) && g.tables.iter().any(|t| t.0 == tb))
+	}
+}
+
+impl Display for DefineRoleStatement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "DEFINE ROLE")?;
+		if self.if_not_exists {
+			write!(f, " IF NOT EXISTS")?
+		}
+		write!(f, " {} ON DATABASE", self.name)?;
+		for grant in self.grants.iter() {
+			write!(f, " {grant}")?;
+		}
+		if let Some(ref v) = self.comment {
+			write!(f, " COMMENT {v}")?
+		}
+		Ok(())
+	}
+}
+
+impl Display for RoleGrant {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(
+			f,
+			"GRANT {} ON TABLE {}",
+			Fmt::comma_separated(self.privileges.iter()),
+			Fmt::comma_separated(self.tables.iter())
+		)
+	}
+}
+
+impl Display for Privilege {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Select => f.write_str("SELECT"),
+			Self::Create => f.write_str("CREATE"),
+			Self::Update => f.write_str("UPDATE"),
+			Self::Delete => f.write_str("DELETE"),
+		}
+	}
+}
+
+impl InfoStructure for DefineRoleStatement {
+	fn structure(self) -> Value {
+		let Self {
+			name,
+			grants,
+			comment,
+			..
+		} = self;
+		let mut acc = Object::default();
+
+		acc.insert("name".to_string(), name.structure());
+
+		acc.insert(
+			"grants".to_string(),
+			Value::Array(grants.into_iter().map(|g| g.structure()).collect()),
+		);
+
+		if let Some(comment) = comment {
+			acc.insert("comment".to_string(), comment.into());
+		}
+
+		Value::Object(acc)
+	}
+}
+
+impl InfoStructure for RoleGrant {
+	fn structure(self) -> Value {
+		let Self {
+			privileges,
+			tables,
+		} = self;
+		let mut acc = Object::default();
+
+		acc.insert(
+			"privileges".to_string(),
+			Value::Array(privileges.into_iter().map(|p| p.structure()).collect()),
+		);
+
+		acc.insert(
+			"tables".to_string(),
+			Value::Array(tables.into_iter().map(|t| t.structure()).collect()),
+		);
+
+		Value::Object(acc)
+	}
+}
+
+impl InfoStructure for Privilege {
+	fn structure(self) -> Value {
+		self.to_string().to_lowercase().into()
+	}
+}
@@ -4,7 +4,7 @@ use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::iam::{Action, ResourceKind};
 use crate::sql::statements::info::InfoStructure;
-use crate::sql::{Base, Duration, Ident, Object, Strand, Value};
+use crate::sql::{fmt::Fmt, Base, Duration, Ident, Object, StatementKind, Strand, Value};
 use derive::Store;
 use rand::distributions::Alphanumeric;
 use rand::Rng;
@@ -12,7 +12,7 @@ use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display};
 
-#[revisioned(revision = 2)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -25,12 +25,36 @@ pub struct DefineScopeStatement {
 	pub comment: Option<Strand>,
 	#[revision(start = 2)]
 	pub if_not_exists: bool,
+	/// The kinds of statement which sessions of this scope may run, or all kinds if empty
+	#[revision(start = 3)]
+	pub allow: Vec<StatementKind>,
+	/// The kinds of statement which sessions of this scope may not run
+	#[revision(start = 3)]
+	pub deny: Vec<StatementKind>,
 }
 
 impl DefineScopeStatement {
 	pub(crate) fn random_code() -> String {
 		rand::thread_rng().sample_iter(&Alphanumeric).take(128).map(char::from).collect::<String>()
 	}
+
+	/// Check that sessions of this scope may run a kind of statement
+	pub(crate) fn check(&self, kind: Option<StatementKind>) -> Result<(), Error> {
+		match kind {
+			// Plain values are always allowed
+			None => Ok(()),
+			Some(kind)
+				if (self.allow.is_empty() || self.allow.contains(&kind))
+					&& !self.deny.contains(&kind) =>
+			{
+				Ok(())
+			}
+			Some(kind) => Err(Error::StatementNotAllowed {
+				scope: self.name.to_raw(),
+				kind,
+			}),
+		}
+	}
 }
 
 impl DefineScopeStatement {
@@ -88,6 +112,12 @@ impl Display for DefineScopeStatement {
 		if let Some(ref v) = self.signin {
 			write!(f, " SIGNIN {v}")?
 		}
+		if !self.allow.is_empty() {
+			write!(f, " ALLOW {}", Fmt::comma_separated(self.allow.iter()))?
+		}
+		if !self.deny.is_empty() {
+			write!(f, " DENY {}", Fmt::comma_separated(self.deny.iter()))?
+		}
 		if let Some(ref v) = self.comment {
 			write!(f, " COMMENT {v}")?
 		}
@@ -103,6 +133,8 @@ impl InfoStructure for DefineScopeStatement {
 			signin,
 			comment,
 			session,
+			allow,
+			deny,
 			..
 		} = self;
 		let mut acc = Object::default();
@@ -125,6 +157,20 @@ impl InfoStructure for DefineScopeStatement {
 			acc.insert("duration".to_string(), duration.into());
 		}
 
+		if !allow.is_empty() {
+			acc.insert(
+				"allow".to_string(),
+				Value::Array(allow.into_iter().map(|k| k.to_string().into()).collect()),
+			);
+		}
+
+		if !deny.is_empty() {
+			acc.insert(
+				"deny".to_string(),
+				Value::Array(deny.into_iter().map(|k| k.to_string().into()).collect()),
+			);
+		}
+
 		Value::Object(acc)
 	}
 }
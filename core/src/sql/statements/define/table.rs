@@ -1,18 +1,20 @@
+use crate::cnf::NORMAL_FETCH_SIZE;
 use crate::ctx::Context;
 use crate::dbs::{Force, Options, Transaction};
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::iam::{Action, ResourceKind};
+use crate::kvs::ScanPage;
 use crate::sql::{
 	changefeed::ChangeFeed,
 	fmt::{is_pretty, pretty_indent},
 	statements::UpdateStatement,
-	Base, Ident, Object, Permissions, Strand, Value, Values, View,
+	Base, Ident, Labels, Object, Partition, Permissions, Strand, Thing, Value, Values, View,
 };
 use std::sync::Arc;
 
 use crate::sql::statements::info::InfoStructure;
-use crate::sql::{Idiom, Kind, Part, Table, TableType};
+use crate::sql::{Idiom, Kind, OnDelete, Part, Table, TableType, UnknownFields};
 use derive::Store;
 use reblessive::tree::Stk;
 use revision::revisioned;
@@ -21,7 +23,7 @@ use std::fmt::{self, Display, Write};
 
 use super::DefineFieldStatement;
 
-#[revisioned(revision = 3)]
+#[revisioned(revision = 7)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -38,6 +40,14 @@ pub struct DefineTableStatement {
 	pub if_not_exists: bool,
 	#[revision(start = 3)]
 	pub kind: TableType,
+	#[revision(start = 4)]
+	pub audit: bool,
+	#[revision(start = 5)]
+	pub unknown: UnknownFields,
+	#[revision(start = 6)]
+	pub labels: Option<Labels>,
+	#[revision(start = 7)]
+	pub partition: Option<Partition>,
 }
 
 impl DefineTableStatement {
@@ -61,6 +71,20 @@ impl DefineTableStatement {
 				value: self.name.to_string(),
 			});
 		}
+		// Check the number of partitions
+		if let Some(pt) = &self.partition {
+			if pt.count == 0 || pt.count > Partition::MAX_COUNT {
+				return Err(Error::TbInvalidPartitions {
+					value: self.name.to_string(),
+					max: Partition::MAX_COUNT,
+				});
+			}
+		}
+		// Get the existing partitioning of the table
+		let partition = match run.get_tb(opt.ns(), opt.db(), &self.name).await {
+			Ok(tb) => tb.partition.clone(),
+			Err(_) => None,
+		};
 		// Process the statement
 		let key = crate::key::database::tb::new(opt.ns(), opt.db(), &self.name);
 		let ns = run.add_ns(opt.ns(), opt.strict).await?;
@@ -108,11 +132,15 @@ impl DefineTableStatement {
 		let tb_key = crate::key::table::fd::prefix(opt.ns(), opt.db(), &self.name);
 		run.clr(tb_key).await?;
 		run.set(key, &dt).await?;
+		// Move the records into the new partitions
+		if partition != self.partition && self.view.is_none() {
+			self.partition_records(&mut run, opt).await?;
+		}
 		// Check if table is a view
 		if let Some(view) = &self.view {
 			// Remove the table data
 			let key = crate::key::table::all::new(opt.ns(), opt.db(), &self.name);
-			run.delp(key, u32::MAX).await?;
+			run.delp(key, NORMAL_FETCH_SIZE).await?;
 			// Process each foreign table
 			for v in view.what.0.iter() {
 				// Save the view config
@@ -145,6 +173,38 @@ impl DefineTableStatement {
 }
 
 impl DefineTableStatement {
+	/// Store each record of the table in its partition, after the partitioning of the table changes
+	async fn partition_records(
+		&self,
+		run: &mut crate::kvs::Transaction,
+		opt: &Options,
+	) -> Result<(), Error> {
+		// Remove the records from the previous partitions
+		let key = crate::key::table::pt::prefix(opt.ns(), opt.db(), &self.name);
+		run.delp(key, NORMAL_FETCH_SIZE).await?;
+		// Check if the table is still partitioned
+		let Some(pt) = &self.partition else {
+			return Ok(());
+		};
+		// Store each record in its partition
+		let beg = crate::key::thing::prefix(opt.ns(), opt.db(), &self.name);
+		let end = crate::key::thing::suffix(opt.ns(), opt.db(), &self.name);
+		let mut next_page = Some(ScanPage::from(beg..end));
+		while let Some(page) = next_page {
+			let res = run.scan_paged(page, NORMAL_FETCH_SIZE).await?;
+			next_page = res.next_page;
+			for (k, v) in res.values.iter() {
+				let key: crate::key::thing::Thing = k.into();
+				let rid = Thing::from((key.tb, key.id));
+				if let Some(p) = pt.of_doc(&v.into())? {
+					let key = crate::key::table::pt::new(opt.ns(), opt.db(), &rid.tb, p, &rid.id);
+					run.set(key, &rid).await?;
+				}
+			}
+		}
+		Ok(())
+	}
+
 	pub fn is_relation(&self) -> bool {
 		matches!(self.kind, TableType::Relation(_))
 	}
@@ -182,6 +242,9 @@ impl Display for DefineTableStatement {
 				if let Some(Kind::Record(kind)) = &rel.to {
 					write!(f, " OUT {}", get_tables_from_kind(kind))?;
 				}
+				if rel.on_delete != OnDelete::Cascade {
+					write!(f, " ON DELETE {}", rel.on_delete)?;
+				}
 			}
 			TableType::Any => {
 				f.write_str(" ANY")?;
@@ -195,15 +258,27 @@ impl Display for DefineTableStatement {
 		} else {
 			" SCHEMALESS"
 		})?;
+		if self.unknown != UnknownFields::Drop {
+			write!(f, " UNKNOWN FIELDS {}", self.unknown)?;
+		}
+		if self.audit {
+			f.write_str(" WITH AUDIT FIELDS")?;
+		}
 		if let Some(ref v) = self.comment {
 			write!(f, " COMMENT {v}")?
 		}
+		if let Some(ref v) = self.labels {
+			write!(f, " {v}")?
+		}
 		if let Some(ref v) = self.view {
 			write!(f, " {v}")?
 		}
 		if let Some(ref v) = self.changefeed {
 			write!(f, " {v}")?;
 		}
+		if let Some(ref v) = self.partition {
+			write!(f, " {v}")?;
+		}
 		let _indent = if is_pretty() {
 			Some(pretty_indent())
 		} else {
@@ -226,6 +301,10 @@ impl InfoStructure for DefineTableStatement {
 			changefeed,
 			comment,
 			kind,
+			audit,
+			unknown,
+			labels,
+			partition,
 			..
 		} = self;
 		let mut acc = Object::default();
@@ -245,12 +324,28 @@ impl InfoStructure for DefineTableStatement {
 			acc.insert("changefeed".to_string(), changefeed.structure());
 		}
 
+		if let Some(partition) = partition {
+			acc.insert("partition".to_string(), partition.structure());
+		}
+
 		if let Some(comment) = comment {
 			acc.insert("comment".to_string(), comment.into());
 		}
 
+		if let Some(labels) = labels {
+			acc.insert("labels".to_string(), labels.structure());
+		}
+
 		acc.insert("kind".to_string(), kind.structure());
 
+		if audit {
+			acc.insert("audit".to_string(), Value::Bool(true));
+		}
+
+		if unknown != UnknownFields::Drop {
+			acc.insert("unknown".to_string(), unknown.structure());
+		}
+
 		Value::Object(acc)
 	}
 }
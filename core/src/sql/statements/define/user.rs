@@ -2,9 +2,12 @@ use crate::ctx::Context;
 use crate::dbs::{Options, Transaction};
 use crate::doc::CursorDoc;
 use crate::err::Error;
-use crate::iam::{Action, ResourceKind};
+use crate::iam::password::Policy;
+use crate::iam::{Action, ResourceKind, Role};
 use crate::sql::statements::info::InfoStructure;
-use crate::sql::{escape::quote_str, fmt::Fmt, Base, Ident, Object, Strand, Value};
+use crate::sql::{
+	escape::quote_str, fmt::Fmt, Base, Datetime, Ident, Object, Priority, Strand, Value,
+};
 use argon2::{
 	password_hash::{PasswordHasher, SaltString},
 	Argon2,
@@ -14,8 +17,9 @@ use rand::{distributions::Alphanumeric, rngs::OsRng, Rng};
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display};
+use std::str::FromStr;
 
-#[revisioned(revision = 2)]
+#[revisioned(revision = 4)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -28,6 +32,12 @@ pub struct DefineUserStatement {
 	pub comment: Option<Strand>,
 	#[revision(start = 2)]
 	pub if_not_exists: bool,
+	#[revision(start = 3)]
+	pub must_change: bool,
+	#[revision(start = 3)]
+	pub pass_changed: Option<Datetime>,
+	#[revision(start = 4)]
+	pub priority: Priority,
 }
 
 impl From<(Base, &str, &str)> for DefineUserStatement {
@@ -47,6 +57,9 @@ impl From<(Base, &str, &str)> for DefineUserStatement {
 			roles: vec!["owner".into()],
 			comment: None,
 			if_not_exists: false,
+			must_change: false,
+			pass_changed: Some(Datetime::default()),
+			priority: Priority::Normal,
 		}
 	}
 }
@@ -66,11 +79,13 @@ impl DefineUserStatement {
 		}
 	}
 
-	pub(crate) fn set_password(&mut self, password: &str) {
-		self.hash = Argon2::default()
-			.hash_password(password.as_bytes(), &SaltString::generate(&mut OsRng))
-			.unwrap()
-			.to_string()
+	pub(crate) fn set_password(&mut self, password: &str) -> Result<(), Error> {
+		let policy = Policy::current();
+		// Check the password against the configured policy
+		policy.check(password)?;
+		// Hash the password using the configured algorithm
+		self.hash = policy.hash(password)?;
+		Ok(())
 	}
 
 	pub(crate) fn set_passhash(&mut self, passhash: String) {
@@ -87,6 +102,21 @@ impl DefineUserStatement {
 	) -> Result<Value, Error> {
 		// Allowed to run?
 		opt.is_allowed(Action::Edit, ResourceKind::Actor, &self.base)?;
+		// User-defined roles only apply to the users of their database
+		if !matches!(self.base, Base::Db) {
+			if let Some(role) = self.roles.iter().find(|r| Role::from_str(r).is_err()) {
+				return Err(Error::RoInvalidLevel {
+					value: role.to_raw(),
+				});
+			}
+		}
+		// The password expiry is measured from when the user was defined
+		let stm = DefineUserStatement {
+			// Don't persist the "IF NOT EXISTS" clause to schema
+			if_not_exists: false,
+			pass_changed: Some(Datetime::default()),
+			..self.clone()
+		};
 
 		match self.base {
 			Base::Root => {
@@ -102,15 +132,7 @@ impl DefineUserStatement {
 				}
 				// Process the statement
 				let key = crate::key::root::us::new(&self.name);
-				run.set(
-					key,
-					DefineUserStatement {
-						// Don't persist the "IF NOT EXISTS" clause to schema
-						if_not_exists: false,
-						..self.clone()
-					},
-				)
-				.await?;
+				run.set(key, stm).await?;
 				// Ok all good
 				Ok(Value::None)
 			}
@@ -129,15 +151,7 @@ impl DefineUserStatement {
 				// Process the statement
 				let key = crate::key::namespace::us::new(opt.ns(), &self.name);
 				run.add_ns(opt.ns(), opt.strict).await?;
-				run.set(
-					key,
-					DefineUserStatement {
-						// Don't persist the "IF NOT EXISTS" clause to schema
-						if_not_exists: false,
-						..self.clone()
-					},
-				)
-				.await?;
+				run.set(key, stm).await?;
 				// Ok all good
 				Ok(Value::None)
 			}
@@ -160,15 +174,7 @@ impl DefineUserStatement {
 				let key = crate::key::database::us::new(opt.ns(), opt.db(), &self.name);
 				run.add_ns(opt.ns(), opt.strict).await?;
 				run.add_db(opt.ns(), opt.db(), opt.strict).await?;
-				run.set(
-					key,
-					DefineUserStatement {
-						// Don't persist the "IF NOT EXISTS" clause to schema
-						if_not_exists: false,
-						..self.clone()
-					},
-				)
-				.await?;
+				run.set(key, stm).await?;
 				// Ok all good
 				Ok(Value::None)
 			}
@@ -191,9 +197,23 @@ impl Display for DefineUserStatement {
 			self.base,
 			quote_str(&self.hash),
 			Fmt::comma_separated(
-				&self.roles.iter().map(|r| r.to_string().to_uppercase()).collect::<Vec<String>>()
+				&self
+					.roles
+					.iter()
+					.map(|r| match Role::from_str(r) {
+						// User-defined role names are case-sensitive
+						Ok(_) => r.to_string().to_uppercase(),
+						Err(_) => r.to_string(),
+					})
+					.collect::<Vec<String>>()
 			)
 		)?;
+		if self.must_change {
+			write!(f, " PASSWORD MUST CHANGE")?
+		}
+		if self.priority != Priority::Normal {
+			write!(f, " PRIORITY {}", self.priority)?
+		}
 		if let Some(ref v) = self.comment {
 			write!(f, " COMMENT {v}")?
 		}
@@ -203,12 +223,15 @@ impl Display for DefineUserStatement {
 
 impl InfoStructure for DefineUserStatement {
 	fn structure(self) -> Value {
+		let expires = Policy::current().expiry(&self);
 		let Self {
 			name,
 			base,
 			hash,
 			roles,
 			comment,
+			must_change,
+			priority,
 			..
 		} = self;
 		let mut acc = Object::default();
@@ -224,6 +247,18 @@ impl InfoStructure for DefineUserStatement {
 			Value::Array(roles.into_iter().map(|r| r.structure()).collect()),
 		);
 
+		if must_change {
+			acc.insert("must_change".to_string(), Value::Bool(true));
+		}
+
+		if priority != Priority::Normal {
+			acc.insert("priority".to_string(), priority.to_string().into());
+		}
+
+		if let Some(expires) = expires {
+			acc.insert("password_expires".to_string(), expires.into());
+		}
+
 		if let Some(comment) = comment {
 			acc.insert("comment".to_string(), comment.into());
 		}
@@ -2,7 +2,7 @@ use crate::ctx::Context;
 use crate::dbs::{Iterator, Options, Statement, Transaction};
 use crate::doc::CursorDoc;
 use crate::err::Error;
-use crate::sql::{Cond, Output, Timeout, Value, Values};
+use crate::sql::{Cond, Output, StatementKind, Timeout, Value, Values};
 use derive::Store;
 use reblessive::tree::Stk;
 use revision::revisioned;
@@ -37,6 +37,8 @@ impl DeleteStatement {
 		txn: &Transaction,
 		doc: Option<&CursorDoc<'_>>,
 	) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.check_statement(StatementKind::Delete)?;
 		// Valid options?
 		opt.valid_for_db()?;
 		// Create a new iterator
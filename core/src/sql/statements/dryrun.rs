@@ -0,0 +1,107 @@
+use crate::ctx::Context;
+use crate::dbs::{Options, Transaction};
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::sql::paths::ID;
+use crate::sql::statements::{
+	CreateStatement, DeleteStatement, InsertStatement, RelateStatement, UpdateStatement,
+};
+use crate::sql::{Field, Fields, Idiom, Object, Output, Param, Part, Statement, Value};
+use derive::Store;
+use reblessive::tree::Stk;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::Duration;
+
+/// A CREATE, UPDATE, DELETE, INSERT, or RELATE statement which is run with
+/// its permissions, events, and computed fields, in a transaction which is
+/// then cancelled, outputting the changes which would have been made to each
+/// record, instead of making them.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct DryRunStatement {
+	pub stmt: Box<Statement>,
+}
+
+impl DryRunStatement {
+	/// Get the statement timeout duration, if any
+	pub(crate) fn timeout(&self) -> Option<Duration> {
+		self.stmt.timeout()
+	}
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		doc: Option<&CursorDoc<'_>>,
+	) -> Result<Value, Error> {
+		// Output each record before and after the changes
+		let output = Some(Output::Fields(Fields(
+			vec![
+				Field::Single {
+					expr: Value::Param(Param::from("before")),
+					alias: Some(Idiom::from("before")),
+				},
+				Field::Single {
+					expr: Value::Param(Param::from("after")),
+					alias: Some(Idiom::from("after")),
+				},
+			],
+			false,
+		)));
+		// Process the statement
+		let stm = match self.stmt.as_ref() {
+			Statement::Create(v) => Statement::Create(CreateStatement {
+				output,
+				..v.clone()
+			}),
+			Statement::Update(v) => Statement::Update(UpdateStatement {
+				output,
+				..v.clone()
+			}),
+			Statement::Delete(v) => Statement::Delete(DeleteStatement {
+				output,
+				..v.clone()
+			}),
+			Statement::Insert(v) => Statement::Insert(InsertStatement {
+				output,
+				..v.clone()
+			}),
+			Statement::Relate(v) => Statement::Relate(RelateStatement {
+				output,
+				..v.clone()
+			}),
+			_ => return Err(Error::Unreachable("DryRunStatement::compute")),
+		};
+		let res = stk.run(|stk| stm.compute(stk, ctx, opt, txn, doc)).await?;
+		// Output the changes to each record
+		Ok(match res {
+			Value::Array(v) => v.into_iter().map(Self::changes).collect::<Vec<_>>().into(),
+			v => Self::changes(v),
+		})
+	}
+	/// Convert a record before and after the changes into its id and a diff of the changes
+	fn changes(v: Value) -> Value {
+		let before = v.pick(&[Part::from("before")]);
+		let after = v.pick(&[Part::from("after")]);
+		let id = match after.pick(&*ID) {
+			Value::None => before.pick(&*ID),
+			id => id,
+		};
+		let mut obj = Object::default();
+		obj.insert("id".to_owned(), id);
+		obj.insert("diff".to_owned(), before.diff(&after, Idiom::default()).into());
+		obj.into()
+	}
+}
+
+impl fmt::Display for DryRunStatement {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "DRY RUN {}", self.stmt)
+	}
+}
@@ -2,7 +2,7 @@ use crate::ctx::Context;
 use crate::dbs::{Options, Transaction};
 use crate::doc::CursorDoc;
 use crate::err::Error;
-use crate::sql::{block::Entry, Block, Param, Value};
+use crate::sql::{block::Entry, Block, Param, StatementKind, Value};
 use derive::Store;
 use reblessive::tree::Stk;
 use revision::revisioned;
@@ -35,6 +35,8 @@ impl ForeachStatement {
 		txn: &Transaction,
 		doc: Option<&CursorDoc<'_>>,
 	) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.check_statement(StatementKind::For)?;
 		// Check the loop data
 		match &self.range.compute(stk, ctx, opt, txn, doc).await? {
 			Value::Array(arr) => {
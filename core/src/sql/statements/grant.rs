@@ -0,0 +1,63 @@
+use crate::ctx::Context;
+use crate::dbs::{Options, Transaction};
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::iam::{Action, ResourceKind};
+use crate::sql::{Base, Ident, StatementKind, Value};
+use derive::Store;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct GrantStatement {
+	pub role: Ident,
+	pub user: Ident,
+	pub base: Base,
+}
+
+impl GrantStatement {
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(
+		&self,
+		_ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		_doc: Option<&CursorDoc<'_>>,
+	) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.check_statement(StatementKind::Grant)?;
+		opt.is_allowed(Action::Edit, ResourceKind::Actor, &self.base)?;
+		// Claim transaction
+		let mut run = txn.lock().await;
+		// Clear the cache
+		run.clear_cache();
+		// Roles are defined on the selected database
+		run.get_db_role(opt.ns(), opt.db(), &self.role).await?;
+		// The role only applies to the users of the same database
+		if !matches!(self.base, Base::Db) {
+			return Err(Error::RoInvalidLevel {
+				value: self.role.to_raw(),
+			});
+		}
+		// Fetch the user on the database
+		let key = crate::key::database::us::new(opt.ns(), opt.db(), &self.user);
+		let mut us = run.get_db_user(opt.ns(), opt.db(), &self.user).await?;
+		// Assign the role to the user
+		if !us.roles.contains(&self.role) {
+			us.roles.push(self.role.clone());
+			run.set(key, us).await?;
+		}
+		// Ok all good
+		Ok(Value::None)
+	}
+}
+
+impl Display for GrantStatement {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "GRANT ROLE {} TO USER {} ON {}", self.role, self.user, self.base)
+	}
+}
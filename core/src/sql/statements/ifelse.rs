@@ -3,6 +3,7 @@ use crate::dbs::{Options, Transaction};
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::sql::fmt::{fmt_separated_by, is_pretty, pretty_indent, Fmt, Pretty};
+use crate::sql::StatementKind;
 use crate::sql::Value;
 use derive::Store;
 use reblessive::tree::Stk;
@@ -46,6 +47,8 @@ impl IfelseStatement {
 		txn: &Transaction,
 		doc: Option<&CursorDoc<'_>>,
 	) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.check_statement(StatementKind::If)?;
 		for (ref cond, ref then) in &self.exprs {
 			let v = cond.compute(stk, ctx, opt, txn, doc).await?;
 			if v.is_truthy() {
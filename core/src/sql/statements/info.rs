@@ -2,15 +2,16 @@ use crate::ctx::Context;
 use crate::dbs::{Options, Transaction};
 use crate::doc::CursorDoc;
 use crate::err::Error;
+use crate::iam::session;
 use crate::iam::Action;
 use crate::iam::ResourceKind;
-use crate::sql::{Base, Ident, Object, Value};
+use crate::sql::{Array, Base, Ident, Object, StatementKind, Value};
 use derive::Store;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[revisioned(revision = 2)]
+#[revisioned(revision = 5)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -33,12 +34,18 @@ pub enum InfoStatement {
 	Sc(Ident, bool),
 	#[revision(end = 2, convert_fn = "tb_migrate")]
 	Tb(Ident),
-	#[revision(start = 2)]
+	#[revision(start = 2, end = 4, convert_fn = "tb_stats_migrate")]
 	Tb(Ident, bool),
+	#[revision(start = 4)]
+	Tb(Ident, bool, bool),
 	#[revision(end = 2, convert_fn = "user_migrate")]
 	User(Ident, Option<Base>),
 	#[revision(start = 2)]
 	User(Ident, Option<Base>, bool),
+	#[revision(start = 3)]
+	Advisor,
+	#[revision(start = 5)]
+	History(Ident),
 }
 
 impl InfoStatement {
@@ -59,7 +66,11 @@ impl InfoStatement {
 	}
 
 	fn tb_migrate(_revision: u16, n: (Ident,)) -> Result<Self, revision::Error> {
-		Ok(Self::Tb(n.0, false))
+		Ok(Self::Tb(n.0, false, false))
+	}
+
+	fn tb_stats_migrate(_revision: u16, (n, s): (Ident, bool)) -> Result<Self, revision::Error> {
+		Ok(Self::Tb(n, s, false))
 	}
 
 	fn user_migrate(
@@ -80,6 +91,7 @@ impl InfoStatement {
 		_doc: Option<&CursorDoc<'_>>,
 	) -> Result<Value, Error> {
 		// Allowed to run?
+		opt.check_statement(StatementKind::Info)?;
 		match self {
 			InfoStatement::Root(false) => {
 				// Allowed to run?
@@ -100,6 +112,8 @@ impl InfoStatement {
 					tmp.insert(v.name.to_string(), v.to_string().into());
 				}
 				res.insert("users".to_owned(), tmp.into());
+				// Process the storage engine capabilities
+				res.insert("capabilities".to_owned(), run.engine_capabilities().into());
 				// Ok all good
 				Value::from(res).ok()
 			}
@@ -168,6 +182,12 @@ impl InfoStatement {
 					tmp.insert(v.name.to_string(), v.to_string().into());
 				}
 				res.insert("params".to_owned(), tmp.into());
+				// Process the roles
+				let mut tmp = Object::default();
+				for v in run.all_db_roles(opt.ns(), opt.db()).await?.iter() {
+					tmp.insert(v.name.to_string(), v.to_string().into());
+				}
+				res.insert("roles".to_owned(), tmp.into());
 				// Process the scopes
 				let mut tmp = Object::default();
 				for v in run.all_sc(opt.ns(), opt.db()).await?.iter() {
@@ -186,9 +206,37 @@ impl InfoStatement {
 					tmp.insert(v.name.to_string(), v.to_string().into());
 				}
 				res.insert("analyzers".to_owned(), tmp.into());
+				// Process the checks
+				let mut tmp = Object::default();
+				for v in run.all_db_checks(opt.ns(), opt.db()).await?.iter() {
+					tmp.insert(v.name.to_string(), v.to_string().into());
+				}
+				res.insert("checks".to_owned(), tmp.into());
+				// Process the dictionaries
+				let mut tmp = Object::default();
+				for v in run.all_db_dictionaries(opt.ns(), opt.db()).await?.iter() {
+					tmp.insert(v.name.to_string(), v.to_string().into());
+				}
+				res.insert("dictionaries".to_owned(), tmp.into());
 				// Ok all good
 				Value::from(res).ok()
 			}
+			InfoStatement::Advisor => {
+				// Allowed to run?
+				opt.is_allowed(Action::View, ResourceKind::Any, &Base::Db)?;
+				// Claim transaction
+				let mut run = txn.lock().await;
+				// Output the suggested indexes
+				run.advice(opt.ns(), opt.db()).await
+			}
+			InfoStatement::History(tb) => {
+				// Allowed to run?
+				opt.is_allowed(Action::View, ResourceKind::Any, &Base::Db)?;
+				// Claim transaction
+				let mut run = txn.lock().await;
+				// Output the recorded definitions of the table
+				run.definition_history(opt.ns(), opt.db(), tb).await
+			}
 			InfoStatement::Sc(sc, false) => {
 				// Allowed to run?
 				opt.is_allowed(Action::View, ResourceKind::Any, &Base::Db)?;
@@ -202,10 +250,15 @@ impl InfoStatement {
 					tmp.insert(v.name.to_string(), v.to_string().into());
 				}
 				res.insert("tokens".to_owned(), tmp.into());
+				// Process the sessions
+				res.insert(
+					"sessions".to_owned(),
+					process_arr(session::all(&mut run, opt.ns(), opt.db(), sc).await?.into()),
+				);
 				// Ok all good
 				Value::from(res).ok()
 			}
-			InfoStatement::Tb(tb, false) => {
+			InfoStatement::Tb(tb, false, stats) => {
 				// Allowed to run?
 				opt.is_allowed(Action::View, ResourceKind::Any, &Base::Db)?;
 				// Claim transaction
@@ -242,6 +295,36 @@ impl InfoStatement {
 					tmp.insert(v.id.to_raw(), v.to_string().into());
 				}
 				res.insert("lives".to_owned(), tmp.into());
+				// Process the hooks
+				let hks = run.all_tb_hooks(opt.ns(), opt.db(), tb).await?;
+				if !hks.is_empty() {
+					let mut tmp = Object::default();
+					for v in hks.iter() {
+						tmp.insert(v.name.to_string(), v.to_string().into());
+					}
+					res.insert("hooks".to_owned(), tmp.into());
+				}
+				// Process the enrichments
+				let ens = run.all_tb_enrichments(opt.ns(), opt.db(), tb).await?;
+				if !ens.is_empty() {
+					let mut tmp = Object::default();
+					for v in ens.iter() {
+						tmp.insert(v.name.to_string(), v.to_string().into());
+					}
+					res.insert("enrichments".to_owned(), tmp.into());
+				}
+				// Process the change feed
+				if let Some(v) = changefeed(&mut run, opt.ns(), opt.db(), tb).await? {
+					res.insert("changefeed".to_owned(), v);
+				}
+				// Process the field backfills
+				if let Some(v) = backfills(&mut run, opt.ns(), opt.db(), tb).await? {
+					res.insert("backfills".to_owned(), v);
+				}
+				// Process the read and write statistics
+				if *stats {
+					res.insert("stats".to_owned(), run.table_stats(opt.ns(), opt.db(), tb));
+				}
 				// Ok all good
 				Value::from(res).ok()
 			}
@@ -273,6 +356,8 @@ impl InfoStatement {
 				res.insert("namespaces".to_owned(), process_arr(run.all_ns().await?));
 				// Process the users
 				res.insert("users".to_owned(), process_arr(run.all_root_users().await?));
+				// Process the storage engine capabilities
+				res.insert("capabilities".to_owned(), run.engine_capabilities().into());
 				// Ok all good
 				Value::from(res).ok()
 			}
@@ -324,6 +409,11 @@ impl InfoStatement {
 					"params".to_owned(),
 					process_arr(run.all_db_params(opt.ns(), opt.db()).await?),
 				);
+				// Process the roles
+				res.insert(
+					"roles".to_owned(),
+					process_arr(run.all_db_roles(opt.ns(), opt.db()).await?),
+				);
 				// Process the scopes
 				res.insert("scopes".to_owned(), process_arr(run.all_sc(opt.ns(), opt.db()).await?));
 				// Process the tables
@@ -333,6 +423,16 @@ impl InfoStatement {
 					"analyzers".to_owned(),
 					process_arr(run.all_db_analyzers(opt.ns(), opt.db()).await?),
 				);
+				// Process the checks
+				res.insert(
+					"checks".to_owned(),
+					process_arr(run.all_db_checks(opt.ns(), opt.db()).await?),
+				);
+				// Process the dictionaries
+				res.insert(
+					"dictionaries".to_owned(),
+					process_arr(run.all_db_dictionaries(opt.ns(), opt.db()).await?),
+				);
 				// Ok all good
 				Value::from(res).ok()
 			}
@@ -348,6 +448,11 @@ impl InfoStatement {
 					"tokens".to_owned(),
 					process_arr(run.all_sc_tokens(opt.ns(), opt.db(), sc).await?),
 				);
+				// Process the sessions
+				res.insert(
+					"sessions".to_owned(),
+					process_arr(session::all(&mut run, opt.ns(), opt.db(), sc).await?.into()),
+				);
 
 				let def = run.get_sc(opt.ns(), opt.db(), sc).await?;
 				let Value::Object(o) = def.structure() else {
@@ -360,7 +465,7 @@ impl InfoStatement {
 				// Ok all good
 				Value::from(res).ok()
 			}
-			InfoStatement::Tb(tb, true) => {
+			InfoStatement::Tb(tb, true, stats) => {
 				// Allowed to run?
 				opt.is_allowed(Action::View, ResourceKind::Any, &Base::Db)?;
 				// Claim transaction
@@ -392,6 +497,28 @@ impl InfoStatement {
 					"lives".to_owned(),
 					process_arr(run.all_tb_lives(opt.ns(), opt.db(), tb).await?),
 				);
+				// Process the hooks
+				let hks = run.all_tb_hooks(opt.ns(), opt.db(), tb).await?;
+				if !hks.is_empty() {
+					res.insert("hooks".to_owned(), process_arr(hks));
+				}
+				// Process the enrichments
+				let ens = run.all_tb_enrichments(opt.ns(), opt.db(), tb).await?;
+				if !ens.is_empty() {
+					res.insert("enrichments".to_owned(), process_arr(ens));
+				}
+				// Process the change feed
+				if let Some(v) = changefeed(&mut run, opt.ns(), opt.db(), tb).await? {
+					res.insert("changefeed".to_owned(), v);
+				}
+				// Process the field backfills
+				if let Some(v) = backfills(&mut run, opt.ns(), opt.db(), tb).await? {
+					res.insert("backfills".to_owned(), v);
+				}
+				// Process the read and write statistics
+				if *stats {
+					res.insert("stats".to_owned(), run.table_stats(opt.ns(), opt.db(), tb));
+				}
 				// Ok all good
 				Value::from(res).ok()
 			}
@@ -409,8 +536,36 @@ impl InfoStatement {
 					Base::Db => run.get_db_user(opt.ns(), opt.db(), user).await?,
 					_ => return Err(Error::InvalidLevel(base.to_string())),
 				};
+				// Process the privileges granted by user-defined roles
+				let mut tmp = Object::default();
+				if let Ok(Base::Db) = opt.selected_base() {
+					for ro in run.all_db_roles(opt.ns(), opt.db()).await?.iter() {
+						if !res.roles.contains(&ro.name) {
+							continue;
+						}
+						for grant in ro.grants.iter() {
+							for tb in grant.tables.iter() {
+								let entry = tmp
+									.entry(tb.to_raw())
+									.or_insert_with(|| Value::Array(Array::new()));
+								if let Value::Array(v) = entry {
+									for p in grant.privileges.iter() {
+										let p = p.structure();
+										if !v.contains(&p) {
+											v.push(p);
+										}
+									}
+								}
+							}
+						}
+					}
+				}
 				// Ok all good
-				Ok(res.structure())
+				let mut res = res.structure();
+				if let Value::Object(o) = &mut res {
+					o.insert("privileges".to_owned(), tmp.into());
+				}
+				Ok(res)
 			}
 		}
 	}
@@ -427,8 +582,10 @@ impl fmt::Display for InfoStatement {
 			Self::Db(true) => f.write_str("INFO FOR DATABASE STRUCTURE"),
 			Self::Sc(ref s, false) => write!(f, "INFO FOR SCOPE {s}"),
 			Self::Sc(ref s, true) => write!(f, "INFO FOR SCOPE {s} STRUCTURE"),
-			Self::Tb(ref t, false) => write!(f, "INFO FOR TABLE {t}"),
-			Self::Tb(ref t, true) => write!(f, "INFO FOR TABLE {t} STRUCTURE"),
+			Self::Tb(ref t, false, false) => write!(f, "INFO FOR TABLE {t}"),
+			Self::Tb(ref t, true, false) => write!(f, "INFO FOR TABLE {t} STRUCTURE"),
+			Self::Tb(ref t, false, true) => write!(f, "INFO FOR TABLE {t} WITH STATS"),
+			Self::Tb(ref t, true, true) => write!(f, "INFO FOR TABLE {t} WITH STATS STRUCTURE"),
 			Self::User(ref u, ref b, false) => match b {
 				Some(ref b) => write!(f, "INFO FOR USER {u} ON {b}"),
 				None => write!(f, "INFO FOR USER {u}"),
@@ -437,6 +594,8 @@ impl fmt::Display for InfoStatement {
 				Some(ref b) => write!(f, "INFO FOR USER {u} ON {b} STRUCTURE"),
 				None => write!(f, "INFO FOR USER {u} STRUCTURE"),
 			},
+			Self::Advisor => f.write_str("INFO FOR ADVISOR"),
+			Self::History(ref t) => write!(f, "INFO FOR HISTORY OF TABLE {t}"),
 		}
 	}
 }
@@ -454,8 +613,10 @@ impl InfoStatement {
 			InfoStatement::Ns(_) => InfoStatement::Ns(true),
 			InfoStatement::Db(_) => InfoStatement::Db(true),
 			InfoStatement::Sc(s, _) => InfoStatement::Sc(s, true),
-			InfoStatement::Tb(t, _) => InfoStatement::Tb(t, true),
+			InfoStatement::Tb(t, _, s) => InfoStatement::Tb(t, true, s),
 			InfoStatement::User(u, b, _) => InfoStatement::User(u, b, true),
+			InfoStatement::Advisor => InfoStatement::Advisor,
+			InfoStatement::History(t) => InfoStatement::History(t),
 		}
 	}
 }
@@ -466,3 +627,47 @@ where
 {
 	Value::Array(a.iter().cloned().map(InfoStructure::structure).collect())
 }
+
+/// Returns the size and lag of the change feed of a table, if it has one
+async fn changefeed(
+	run: &mut crate::kvs::Transaction,
+	ns: &str,
+	db: &str,
+	tb: &str,
+) -> Result<Option<Value>, Error> {
+	let db = match run.get_db(ns, db).await {
+		Ok(db) => db,
+		Err(Error::DbNotFound {
+			..
+		}) => return Ok(None),
+		Err(e) => return Err(e),
+	};
+	let tb = match run.get_tb(ns, &db.name, tb).await {
+		Ok(tb) => tb,
+		Err(Error::TbNotFound {
+			..
+		}) => return Ok(None),
+		Err(e) => return Err(e),
+	};
+	if db.changefeed.is_none() && tb.changefeed.is_none() {
+		return Ok(None);
+	}
+	crate::cf::table_stats(run, ns, &db, &tb).await.map(Some)
+}
+
+/// Returns the progress of the field backfills of a table, if there are any
+async fn backfills(
+	run: &mut crate::kvs::Transaction,
+	ns: &str,
+	db: &str,
+	tb: &str,
+) -> Result<Option<Value>, Error> {
+	let beg = crate::key::table::bf::prefix(ns, db, tb);
+	let end = crate::key::table::bf::suffix(ns, db, tb);
+	let mut res = Object::default();
+	for (_, v) in run.getr(beg..end, u32::MAX).await? {
+		let bf = crate::kvs::backfill::Backfill::from(v);
+		res.insert(bf.field.to_string(), bf.progress());
+	}
+	Ok((!res.is_empty()).then(|| res.into()))
+}
@@ -1,8 +1,12 @@
+use crate::cnf::INSERT_SELECT_BATCH_SIZE;
 use crate::ctx::Context;
 use crate::dbs::{Iterable, Iterator, Options, Statement, Transaction};
 use crate::doc::CursorDoc;
 use crate::err::Error;
-use crate::sql::{Data, Output, Timeout, Value};
+use crate::sql::statements::SelectStatement;
+use crate::sql::{
+	Data, Limit, Output, Part, Start, StatementKind, Subquery, Table, Timeout, Value,
+};
 use derive::Store;
 use reblessive::tree::Stk;
 use revision::revisioned;
@@ -37,6 +41,8 @@ impl InsertStatement {
 		txn: &Transaction,
 		doc: Option<&CursorDoc<'_>>,
 	) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.check_statement(StatementKind::Insert)?;
 		// Valid options?
 		opt.valid_for_db()?;
 		// Create a new iterator
@@ -44,62 +50,158 @@ impl InsertStatement {
 		// Ensure futures are stored
 		let opt = &opt.new_with_futures(false).with_projections(false);
 		// Parse the expression
-		match self.into.compute(stk, ctx, opt, txn, doc).await? {
-			Value::Table(into) => match &self.data {
-				// Check if this is a traditional statement
-				Data::ValuesExpression(v) => {
-					for v in v {
-						// Create a new empty base object
-						let mut o = Value::base();
-						// Set each field from the expression
-						for (k, v) in v.iter() {
-							let v = v.compute(stk, ctx, opt, txn, None).await?;
-							o.set(stk, ctx, opt, txn, k, v).await?;
-						}
-						// Specify the new table record id
-						let id = o.rid().generate(&into, true)?;
-						// Pass the mergeable to the iterator
-						i.ingest(Iterable::Mergeable(id, o));
+		let into = match self.into.compute(stk, ctx, opt, txn, doc).await? {
+			Value::Table(into) => into,
+			v => {
+				return Err(Error::InsertStatement {
+					value: v.to_string(),
+				})
+			}
+		};
+		// Check if this is a copy from another table
+		if let Data::SingleExpression(Value::Subquery(v)) = &self.data {
+			if let Subquery::Select(v) = v.as_ref() {
+				if Self::streamable(&into, v) {
+					return self.copy(stk, ctx, opt, txn, doc, &into, v).await;
+				}
+			}
+		}
+		match &self.data {
+			// Check if this is a traditional statement
+			Data::ValuesExpression(v) => {
+				for v in v {
+					// Create a new empty base object
+					let mut o = Value::base();
+					// Set each field from the expression
+					for (k, v) in v.iter() {
+						let v = v.compute(stk, ctx, opt, txn, None).await?;
+						o.set(stk, ctx, opt, txn, k, v).await?;
 					}
+					// Specify the new table record id
+					let id = o.rid().generate(ctx, &into, true)?;
+					// Pass the mergeable to the iterator
+					i.ingest(Iterable::Mergeable(id, o));
 				}
-				// Check if this is a modern statement
-				Data::SingleExpression(v) => {
-					let v = v.compute(stk, ctx, opt, txn, doc).await?;
-					match v {
-						Value::Array(v) => {
-							for v in v {
-								// Specify the new table record id
-								let id = v.rid().generate(&into, true)?;
-								// Pass the mergeable to the iterator
-								i.ingest(Iterable::Mergeable(id, v));
-							}
-						}
-						Value::Object(_) => {
+			}
+			// Check if this is a modern statement
+			Data::SingleExpression(v) => {
+				let v = v.compute(stk, ctx, opt, txn, doc).await?;
+				match v {
+					Value::Array(v) => {
+						for v in v {
 							// Specify the new table record id
-							let id = v.rid().generate(&into, true)?;
+							let id = v.rid().generate(ctx, &into, true)?;
 							// Pass the mergeable to the iterator
 							i.ingest(Iterable::Mergeable(id, v));
 						}
-						v => {
-							return Err(Error::InsertStatement {
-								value: v.to_string(),
-							})
-						}
+					}
+					Value::Object(_) => {
+						// Specify the new table record id
+						let id = v.rid().generate(ctx, &into, true)?;
+						// Pass the mergeable to the iterator
+						i.ingest(Iterable::Mergeable(id, v));
+					}
+					v => {
+						return Err(Error::InsertStatement {
+							value: v.to_string(),
+						})
 					}
 				}
-				_ => unreachable!(),
-			},
-			v => {
-				return Err(Error::InsertStatement {
-					value: v.to_string(),
-				})
 			}
+			_ => unreachable!(),
 		}
 		// Assign the statement
 		let stm = Statement::from(self);
 		// Output the results
 		i.output(stk, ctx, opt, txn, &stm).await
 	}
+	/// Check if the records selected by a SELECT statement can be inserted
+	/// in batches. The batches are paged with START and LIMIT clauses, so
+	/// the SELECT statement must not have clauses which depend on all of
+	/// the selected records, and must not select from the table which the
+	/// records are inserted into.
+	fn streamable(into: &Table, stm: &SelectStatement) -> bool {
+		!stm.only
+			&& stm.split.is_none()
+			&& stm.group.is_none()
+			&& stm.order.is_none()
+			&& stm.sample.is_none()
+			&& stm.limit.is_none()
+			&& stm.start.is_none()
+			&& stm.fetch.is_none()
+			&& stm.explain.is_none()
+			&& stm.outfile.is_none()
+			&& stm.cache.is_none()
+			&& !stm.what.iter().any(|v| match v {
+				Value::Table(v) => v.0 == into.0,
+				Value::Thing(v) => v.tb == into.0,
+				_ => true,
+			})
+	}
+	/// Insert the records selected by a SELECT statement in batches, so
+	/// that the selected records are never all held in memory at once
+	#[allow(clippy::too_many_arguments)]
+	async fn copy(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		doc: Option<&CursorDoc<'_>>,
+		into: &Table,
+		stm: &SelectStatement,
+	) -> Result<Value, Error> {
+		// Assign the statement
+		let ins = Statement::from(self);
+		// The number of records selected at a time
+		let batch = *INSERT_SELECT_BATCH_SIZE as usize;
+		// The records, or number of records, which were inserted
+		let mut results = Vec::new();
+		let mut count = 0;
+		let mut start = 0;
+		loop {
+			// Select the next batch of records
+			let select = SelectStatement {
+				start: Some(Start(Value::from(start))),
+				limit: Some(Limit(Value::from(batch))),
+				..stm.clone()
+			};
+			let rows = match select.compute(stk, ctx, opt, txn, doc).await? {
+				Value::Array(v) => v.0,
+				_ => break,
+			};
+			let fetched = rows.len();
+			// Create a new iterator for the batch
+			let mut i = Iterator::new();
+			for v in rows {
+				// Specify the new table record id
+				let id = v.rid().generate(ctx, into, true)?;
+				// Pass the mergeable to the iterator
+				i.ingest(Iterable::Mergeable(id, v));
+			}
+			// Insert the batch of records
+			match i.output(stk, ctx, opt, txn, &ins).await? {
+				Value::Array(v) => results.extend(v.0),
+				v => {
+					if let Value::Number(v) = v.pick(&[Part::from("count")]) {
+						count += v.as_usize();
+					}
+				}
+			}
+			// Check if this was the last batch
+			if fetched < batch {
+				break;
+			}
+			start += batch;
+		}
+		// Output the results
+		match self.output {
+			Some(Output::Count) => Ok(Value::from(map! {
+				"count".to_string() => Value::from(count),
+			})),
+			_ => Ok(results.into()),
+		}
+	}
 }
 
 impl fmt::Display for InsertStatement {
@@ -10,11 +10,16 @@ use crate::dbs::{Options, Transaction};
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::fflags::FFLAGS;
+use crate::iam::session::ScopeSession;
+use crate::iam::{Action, Level, ResourceKind};
+use crate::kvs::lq_count::CountEvent;
 use crate::kvs::lq_structs::{KillEntry, TrackedResult};
+use crate::sql::Base;
+use crate::sql::StatementKind;
 use crate::sql::Uuid;
 use crate::sql::Value;
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -22,6 +27,24 @@ pub struct KillStatement {
 	// Uuid of Live Query
 	// or Param resolving to Uuid of Live Query
 	pub id: Value,
+	// What the id refers to
+	#[revision(start = 2)]
+	pub kind: KillKind,
+}
+
+/// The kind of thing which a KILL statement stops
+#[revisioned(revision = 1)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub enum KillKind {
+	/// A live query, with `KILL <id>`
+	#[default]
+	LiveQuery,
+	/// A scope session, with `KILL SESSION <id>`
+	Session,
+	/// An executing query, with `KILL QUERY <id>`
+	Query,
 }
 
 impl KillStatement {
@@ -34,46 +57,20 @@ impl KillStatement {
 		txn: &Transaction,
 		_doc: Option<&CursorDoc<'_>>,
 	) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.check_statement(StatementKind::Kill)?;
+		// Check what is being killed
+		match self.kind {
+			KillKind::Session => return self.compute_session(stk, ctx, opt, txn).await,
+			KillKind::Query => return self.compute_query(stk, ctx, opt, txn).await,
+			KillKind::LiveQuery => (),
+		}
 		// Is realtime enabled?
 		opt.realtime()?;
 		// Valid options?
 		opt.valid_for_db()?;
 		// Resolve live query id
-		let live_query_id = match &self.id {
-			Value::Uuid(id) => *id,
-			Value::Param(param) => match param.compute(stk, ctx, opt, txn, None).await? {
-				Value::Uuid(id) => id,
-				Value::Strand(id) => match uuid::Uuid::try_parse(&id) {
-					Ok(id) => Uuid(id),
-					_ => {
-						return Err(Error::KillStatement {
-							value:
-								"KILL received a parameter that could not be converted to a UUID"
-									.to_string(),
-						});
-					}
-				},
-				_ => {
-					return Err(Error::KillStatement {
-						value: "KILL received a parameter that was not expected".to_string(),
-					});
-				}
-			},
-			Value::Strand(maybe_id) => match uuid::Uuid::try_parse(maybe_id) {
-				Ok(id) => Uuid(id),
-				_ => {
-					return Err(Error::KillStatement {
-						value: "KILL received a Strand that could not be converted to a UUID"
-							.to_string(),
-					});
-				}
-			},
-			_ => {
-				return Err(Error::KillStatement {
-					value: "Unhandled type for KILL statement".to_string(),
-				});
-			}
-		};
+		let live_query_id = self.resolve_id(stk, ctx, opt, txn).await?;
 		// Claim transaction
 		let mut run = txn.lock().await;
 		if FFLAGS.change_feed_live_queries.enabled() {
@@ -101,6 +98,8 @@ impl KillStatement {
 						let key =
 							crate::key::table::lq::new(opt.ns(), opt.db(), tb, live_query_id.0);
 						run.del(key).await?;
+						// Discard the counter of a counting live query
+						run.push_count_event(CountEvent::Stop(live_query_id));
 					}
 					_ => {
 						return Err(Error::KillStatement {
@@ -118,11 +117,121 @@ impl KillStatement {
 		// Return the query id
 		Ok(Value::None)
 	}
+
+	/// Resolve the id of the live query or session to kill
+	async fn resolve_id(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+	) -> Result<Uuid, Error> {
+		Ok(match &self.id {
+			Value::Uuid(id) => *id,
+			Value::Param(param) => match param.compute(stk, ctx, opt, txn, None).await? {
+				Value::Uuid(id) => id,
+				Value::Strand(id) => match uuid::Uuid::try_parse(&id) {
+					Ok(id) => Uuid(id),
+					_ => {
+						return Err(Error::KillStatement {
+							value:
+								"KILL received a parameter that could not be converted to a UUID"
+									.to_string(),
+						});
+					}
+				},
+				_ => {
+					return Err(Error::KillStatement {
+						value: "KILL received a parameter that was not expected".to_string(),
+					});
+				}
+			},
+			Value::Strand(maybe_id) => match uuid::Uuid::try_parse(maybe_id) {
+				Ok(id) => Uuid(id),
+				_ => {
+					return Err(Error::KillStatement {
+						value: "KILL received a Strand that could not be converted to a UUID"
+							.to_string(),
+					});
+				}
+			},
+			_ => {
+				return Err(Error::KillStatement {
+					value: "Unhandled type for KILL statement".to_string(),
+				});
+			}
+		})
+	}
+
+	/// Kill a scope session, so that its token can no longer be used
+	async fn compute_session(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+	) -> Result<Value, Error> {
+		// Valid options?
+		opt.valid_for_db()?;
+		// Resolve the session id
+		let id = self.resolve_id(stk, ctx, opt, txn).await?.to_raw();
+		// Claim transaction
+		let mut run = txn.lock().await;
+		// Fetch the session if it exists
+		let key = crate::key::database::se::new(opt.ns(), opt.db(), &id);
+		let se: ScopeSession = match run.get(key).await? {
+			Some(v) => v.into(),
+			None => {
+				return Err(Error::KillStatement {
+					value: "KILL SESSION uuid did not exist".to_string(),
+				});
+			}
+		};
+		// Scope users are able to kill their own sessions
+		let owner = match opt.auth.level() {
+			Level::Scope(_, _, sc) => *sc == se.sc && opt.auth.id() == se.rid.to_string(),
+			_ => false,
+		};
+		// Allowed to run?
+		if !owner {
+			opt.is_allowed(Action::Edit, ResourceKind::Actor, &Base::Db)?;
+		}
+		// Delete the session
+		run.del(crate::key::database::se::new(opt.ns(), opt.db(), &id)).await?;
+		// Ok all good
+		Ok(Value::None)
+	}
+
+	/// Kill a query which is executing on this node, by cancelling its context
+	async fn compute_query(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+	) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.is_allowed(Action::Edit, ResourceKind::Any, &Base::Root)?;
+		opt.check_readonly("KILL QUERY")?;
+		// Resolve the query id
+		let id = self.resolve_id(stk, ctx, opt, txn).await?;
+		// Cancel the query if it is executing
+		match txn.lock().await.cancel_query(&id.0) {
+			true => Ok(Value::None),
+			false => Err(Error::KillStatement {
+				value: "KILL QUERY uuid did not exist".to_string(),
+			}),
+		}
+	}
 }
 
 impl fmt::Display for KillStatement {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		write!(f, "KILL {}", self.id)
+		match self.kind {
+			KillKind::LiveQuery => write!(f, "KILL {}", self.id),
+			KillKind::Session => write!(f, "KILL SESSION {}", self.id),
+			KillKind::Query => write!(f, "KILL QUERY {}", self.id),
+		}
 	}
 }
 
@@ -145,6 +254,7 @@ mod test {
 		}
 		let res = KillStatement {
 			id: Uuid::from_str("8f92f057-c739-4bf2-9d0c-a74d01299efc").unwrap().into(),
+			kind: Default::default(),
 		};
 		let ctx = Context::default();
 		let opt = Options::new()
@@ -4,9 +4,15 @@ use crate::doc::CursorDoc;
 use crate::err::{Error, LiveQueryCause};
 use crate::fflags::FFLAGS;
 use crate::iam::Auth;
+use crate::kvs::lq_count::CountEvent;
 use crate::kvs::lq_structs::{LqEntry, TrackedResult};
+use crate::sql::fmt::Fmt;
 use crate::sql::statements::info::InfoStructure;
-use crate::sql::{Cond, Fetchs, Fields, Object, Table, Uuid, Value};
+use crate::sql::statements::SelectStatement;
+use crate::sql::{
+	Cond, Duration, Fetchs, Field, Fields, Function, Groups, Object, Part, StatementKind, Table,
+	Uuid, Value, Values, Window,
+};
 use derive::Store;
 use futures::lock::MutexGuard;
 use reblessive::tree::Stk;
@@ -14,7 +20,7 @@ use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[revisioned(revision = 2)]
+#[revisioned(revision = 6)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -45,6 +51,50 @@ pub struct LiveStatement {
 	// This is optional as it is only set by the database
 	// runtime when storing the live query to storage.
 	pub(crate) auth: Option<Auth>,
+	// When a live query has a window clause, the matching
+	// records are aggregated on the node which owns the
+	// live query, and a notification is sent when each
+	// window closes, instead of for each record change.
+	#[revision(start = 3)]
+	pub window: Option<Window>,
+	// When a live query has a when clause, notifications
+	// are only sent for the listed types of record change,
+	// and all other changes are skipped for this query.
+	#[revision(start = 4)]
+	pub when: Option<Vec<LiveAction>>,
+	// When a live query has a compact clause, successive
+	// UPDATE notifications for the same record within the
+	// duration are coalesced into a single notification
+	// with the latest state of the record.
+	#[revision(start = 5)]
+	pub compact: Option<Duration>,
+	// When a live query counts its records, the number of
+	// matching records is maintained on the node which owns
+	// the live query, and a notification with the number is
+	// sent whenever it changes, instead of the records.
+	#[revision(start = 6)]
+	pub count: bool,
+}
+
+/// A type of record change which a live query can listen for
+#[revisioned(revision = 1)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub enum LiveAction {
+	Create,
+	Update,
+	Delete,
+}
+
+impl fmt::Display for LiveAction {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Create => f.write_str("CREATE"),
+			Self::Update => f.write_str("UPDATE"),
+			Self::Delete => f.write_str("DELETE"),
+		}
+	}
 }
 
 impl LiveStatement {
@@ -64,6 +114,9 @@ impl LiveStatement {
 		what: Value,
 		cond: Option<Cond>,
 		fetch: Option<Fetchs>,
+		window: Option<Window>,
+		when: Option<Vec<LiveAction>>,
+		compact: Option<Duration>,
 	) -> Self {
 		LiveStatement {
 			id: Uuid::new_v4(),
@@ -72,10 +125,21 @@ impl LiveStatement {
 			what,
 			cond,
 			fetch,
+			window,
+			when,
+			compact,
 			..Default::default()
 		}
 	}
 
+	/// Checks whether this live query listens for a type of record change
+	pub(crate) fn listens_for(&self, action: LiveAction) -> bool {
+		match &self.when {
+			Some(v) => v.contains(&action),
+			None => true,
+		}
+	}
+
 	/// Process this type returning a computed simple Value
 	pub(crate) async fn compute(
 		&self,
@@ -85,12 +149,18 @@ impl LiveStatement {
 		txn: &Transaction,
 		doc: Option<&CursorDoc<'_>>,
 	) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.check_statement(StatementKind::Live)?;
 		// Is realtime enabled?
 		opt.realtime()?;
 		// Valid options?
 		opt.valid_for_db()?;
 		// Get the Node ID
 		let nid = opt.id()?;
+		// Check that the window is valid
+		if let Some(window) = &self.window {
+			window.check(&self.expr)?;
+		}
 		// Check that auth has been set
 		let mut stm = LiveStatement {
 			// Use the current session authentication
@@ -106,6 +176,18 @@ impl LiveStatement {
 		let id = stm.id.0;
 		match FFLAGS.change_feed_live_queries.enabled() {
 			true => {
+				// Windows are aggregated from document changes on the owning node
+				if stm.window.is_some() {
+					return Err(Error::LiveQueryError(LiveQueryCause::WindowNotSupported));
+				}
+				// Notifications are compacted from document changes on the owning node
+				if stm.compact.is_some() {
+					return Err(Error::LiveQueryError(LiveQueryCause::CompactNotSupported));
+				}
+				// Records are counted from document changes on the owning node
+				if stm.count {
+					return Err(Error::LiveQueryError(LiveQueryCause::CountNotSupported));
+				}
 				let mut run = txn.lock().await;
 				match stm.what.compute(stk, ctx, opt, txn, doc).await? {
 					Value::Table(tb) => {
@@ -133,30 +215,64 @@ impl LiveStatement {
 				Ok(id.into())
 			}
 			false => {
-				// Claim transaction
-				let mut run = txn.lock().await;
 				// Process the live query table
-				match stm.what.compute(stk, ctx, opt, txn, doc).await? {
-					Value::Table(tb) => {
-						// Store the current Node ID
-						stm.node = nid.into();
-						// Insert the node live query
-						run.putc_ndlq(nid, id, opt.ns(), opt.db(), tb.as_str(), None).await?;
-						// Insert the table live query
-						run.putc_tblq(opt.ns(), opt.db(), &tb, stm, None).await?;
-					}
+				let tb = match stm.what.compute(stk, ctx, opt, txn, doc).await? {
+					Value::Table(tb) => tb,
 					v => {
 						return Err(Error::LiveStatement {
 							value: v.to_string(),
 						});
 					}
 				};
+				// Count the records which already match a counting live query
+				if stm.count {
+					let count = self.initial_count(stk, ctx, opt, txn, &tb).await?;
+					txn.lock().await.push_count_event(CountEvent::Start(stm.id, count));
+				}
+				// Claim transaction
+				let mut run = txn.lock().await;
+				// Store the current Node ID
+				stm.node = nid.into();
+				// Insert the node live query
+				run.putc_ndlq(nid, id, opt.ns(), opt.db(), tb.as_str(), None).await?;
+				// Insert the table live query
+				run.putc_tblq(opt.ns(), opt.db(), &tb, stm, None).await?;
 				// Return the query id
 				Ok(id.into())
 			}
 		}
 	}
 
+	/// Count the records in the table which match the WHERE clause,
+	/// and which the user who started the live query can select
+	async fn initial_count(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		tb: &Table,
+	) -> Result<u64, Error> {
+		let stm = SelectStatement {
+			expr: Fields(
+				vec![Field::Single {
+					expr: Value::Function(Box::new(Function::Normal("count".to_owned(), vec![]))),
+					alias: None,
+				}],
+				false,
+			),
+			what: Values(vec![Value::Table(tb.clone())]),
+			cond: self.cond.clone(),
+			group: Some(Groups(vec![])),
+			..Default::default()
+		};
+		let res = stm.compute(stk, ctx, opt, txn, None).await?;
+		match res.first().pick(&[Part::from("count")]) {
+			Value::Number(v) => Ok(v.as_int() as u64),
+			_ => Ok(0),
+		}
+	}
+
 	async fn validate_change_feed_valid(
 		&self,
 		tx: &mut MutexGuard<'_, crate::kvs::Transaction>,
@@ -190,10 +306,22 @@ impl LiveStatement {
 
 impl fmt::Display for LiveStatement {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		write!(f, "LIVE SELECT {} FROM {}", self.expr, self.what)?;
+		match self.count {
+			true => write!(f, "LIVE COUNT FROM {}", self.what)?,
+			false => write!(f, "LIVE SELECT {} FROM {}", self.expr, self.what)?,
+		}
 		if let Some(ref v) = self.cond {
 			write!(f, " {v}")?
 		}
+		if let Some(ref v) = self.when {
+			write!(f, " WHEN {}", Fmt::comma_separated(v))?
+		}
+		if let Some(ref v) = self.window {
+			write!(f, " {v}")?
+		}
+		if let Some(ref v) = self.compact {
+			write!(f, " COMPACT {v}")?
+		}
 		if let Some(ref v) = self.fetch {
 			write!(f, " {v}")?
 		}
@@ -208,6 +336,10 @@ impl InfoStructure for LiveStatement {
 			what,
 			cond,
 			fetch,
+			window,
+			when,
+			compact,
+			count,
 			..
 		} = self;
 
@@ -224,6 +356,27 @@ impl InfoStructure for LiveStatement {
 		if let Some(fetch) = fetch {
 			acc.insert("fetch".to_string(), fetch.structure());
 		}
+
+		if let Some(window) = window {
+			acc.insert("window".to_string(), window.structure());
+		}
+
+		if let Some(when) = when {
+			acc.insert(
+				"when".to_string(),
+				Value::from(
+					when.into_iter().map(|v| Value::from(v.to_string())).collect::<Vec<_>>(),
+				),
+			);
+		}
+
+		if let Some(compact) = compact {
+			acc.insert("compact".to_string(), compact.into());
+		}
+
+		if count {
+			acc.insert("count".to_string(), count.into());
+		}
 		Value::Object(acc)
 	}
 }
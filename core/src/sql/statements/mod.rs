@@ -1,3 +1,4 @@
+pub(crate) mod alter;
 pub(crate) mod analyze;
 pub(crate) mod begin;
 pub(crate) mod r#break;
@@ -7,7 +8,9 @@ pub(crate) mod r#continue;
 pub(crate) mod create;
 pub(crate) mod define;
 pub(crate) mod delete;
+pub(crate) mod dryrun;
 pub(crate) mod foreach;
+pub(crate) mod grant;
 pub(crate) mod ifelse;
 pub(crate) mod info;
 pub(crate) mod insert;
@@ -18,6 +21,7 @@ pub(crate) mod output;
 pub(crate) mod rebuild;
 pub(crate) mod relate;
 pub(crate) mod remove;
+pub(crate) mod report;
 pub(crate) mod select;
 pub(crate) mod set;
 pub(crate) mod show;
@@ -26,24 +30,28 @@ pub(crate) mod throw;
 pub(crate) mod update;
 pub(crate) mod r#use;
 
+pub use self::alter::{AlterFieldStatement, AlterStatement};
 pub use self::analyze::AnalyzeStatement;
 pub use self::begin::BeginStatement;
 pub use self::cancel::CancelStatement;
 pub use self::commit::CommitStatement;
 pub use self::create::CreateStatement;
 pub use self::delete::DeleteStatement;
+pub use self::dryrun::DryRunStatement;
 pub use self::foreach::ForeachStatement;
+pub use self::grant::GrantStatement;
 pub use self::ifelse::IfelseStatement;
 pub use self::info::InfoStatement;
 pub use self::insert::InsertStatement;
-pub use self::kill::KillStatement;
-pub use self::live::LiveStatement;
+pub use self::kill::{KillKind, KillStatement};
+pub use self::live::{LiveAction, LiveStatement};
 pub use self::option::OptionStatement;
 pub use self::output::OutputStatement;
 pub use self::r#break::BreakStatement;
 pub use self::r#continue::ContinueStatement;
 pub use self::r#use::UseStatement;
 pub use self::relate::RelateStatement;
+pub use self::report::{ReportReferencesStatement, ReportStatement};
 pub use self::select::SelectStatement;
 pub use self::set::SetStatement;
 pub use self::show::ShowStatement;
@@ -52,15 +60,19 @@ pub use self::throw::ThrowStatement;
 pub use self::update::UpdateStatement;
 
 pub use self::define::{
-	DefineAnalyzerStatement, DefineDatabaseStatement, DefineEventStatement, DefineFieldStatement,
-	DefineFunctionStatement, DefineIndexStatement, DefineModelStatement, DefineNamespaceStatement,
-	DefineParamStatement, DefineScopeStatement, DefineStatement, DefineTableStatement,
-	DefineTokenStatement, DefineUserStatement,
+	DefineAnalyzerStatement, DefineCheckStatement, DefineDatabaseStatement,
+	DefineDictionaryStatement, DefineEnrichmentStatement, DefineEventStatement,
+	DefineFieldStatement, DefineFunctionStatement, DefineHookStatement, DefineIndexStatement,
+	DefineModelStatement, DefineNamespaceStatement, DefineParamStatement, DefineRoleStatement,
+	DefineScopeStatement, DefineStatement, DefineTableStatement, DefineTokenStatement,
+	DefineUserStatement, DictionaryKind, Privilege, RoleGrant,
 };
 
 pub use self::remove::{
-	RemoveAnalyzerStatement, RemoveDatabaseStatement, RemoveEventStatement, RemoveFieldStatement,
-	RemoveFunctionStatement, RemoveIndexStatement, RemoveModelStatement, RemoveNamespaceStatement,
-	RemoveParamStatement, RemoveScopeStatement, RemoveStatement, RemoveTableStatement,
-	RemoveTokenStatement, RemoveUserStatement,
+	RemoveAnalyzerStatement, RemoveCheckStatement, RemoveDatabaseStatement,
+	RemoveDictionaryStatement, RemoveEnrichmentStatement, RemoveEventStatement,
+	RemoveFieldStatement, RemoveFunctionStatement, RemoveHookStatement, RemoveIndexStatement,
+	RemoveModelStatement, RemoveNamespaceStatement, RemoveParamStatement, RemoveRoleStatement,
+	RemoveScopeStatement, RemoveStatement, RemoveTableStatement, RemoveTokenStatement,
+	RemoveUserStatement,
 };
@@ -4,6 +4,7 @@ use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::sql::fetch::Fetchs;
 use crate::sql::value::Value;
+use crate::sql::StatementKind;
 use derive::Store;
 use reblessive::tree::Stk;
 use revision::revisioned;
@@ -33,6 +34,8 @@ impl OutputStatement {
 		txn: &Transaction,
 		doc: Option<&CursorDoc<'_>>,
 	) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.check_statement(StatementKind::Return)?;
 		// Ensure futures are processed
 		let opt = &opt.new_with_futures(true);
 		// Process the output value
@@ -8,6 +8,7 @@ use crate::sql::ident::Ident;
 use crate::sql::statements::RemoveIndexStatement;
 use crate::sql::value::Value;
 use crate::sql::Base;
+use crate::sql::StatementKind;
 use derive::Store;
 use reblessive::tree::Stk;
 use revision::revisioned;
@@ -37,6 +38,8 @@ impl RebuildStatement {
 		txn: &Transaction,
 		doc: Option<&CursorDoc<'_>>,
 	) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.check_statement(StatementKind::Rebuild)?;
 		match self {
 			Self::Index(s) => s.compute(stk, ctx, opt, txn, doc).await,
 		}
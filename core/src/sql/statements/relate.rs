@@ -2,7 +2,7 @@ use crate::ctx::Context;
 use crate::dbs::{Iterable, Iterator, Options, Statement, Transaction};
 use crate::doc::CursorDoc;
 use crate::err::Error;
-use crate::sql::{Data, Output, Timeout, Value};
+use crate::sql::{Data, Output, StatementKind, Timeout, Value};
 use derive::Store;
 use reblessive::tree::Stk;
 use revision::revisioned;
@@ -40,6 +40,8 @@ impl RelateStatement {
 		txn: &Transaction,
 		doc: Option<&CursorDoc<'_>>,
 	) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.check_statement(StatementKind::Relate)?;
 		// Valid options?
 		opt.valid_for_db()?;
 		// Create a new iterator
@@ -142,13 +144,13 @@ impl RelateStatement {
 						// There is a data clause so check for a record id
 						Some(data) => {
 							let id = match data.rid(stk, ctx, opt, txn).await? {
-								Some(id) => id.generate(tb, false)?,
-								None => tb.generate(),
+								Some(id) => id.generate(ctx, tb, false)?,
+								None => tb.generate(ctx),
 							};
 							i.ingest(Iterable::Relatable(f, id, w))
 						}
 						// There is no data clause so create a record id
-						None => i.ingest(Iterable::Relatable(f, tb.generate(), w)),
+						None => i.ingest(Iterable::Relatable(f, tb.generate(ctx), w)),
 					},
 					// The relation can not be any other type
 					v => {
@@ -1,3 +1,4 @@
+use crate::cnf::NORMAL_FETCH_SIZE;
 use crate::ctx::Context;
 use crate::dbs::{Options, Transaction};
 use crate::err::Error;
@@ -40,7 +41,7 @@ impl RemoveDatabaseStatement {
 			run.del(key).await?;
 			// Delete the resource data
 			let key = crate::key::database::all::new(opt.ns(), &db.name);
-			run.delp(key, u32::MAX).await?;
+			run.delp(key, NORMAL_FETCH_SIZE).await?;
 			// Ok all good
 			Ok(Value::None)
 		}
@@ -41,6 +41,9 @@ impl RemoveFieldStatement {
 			let fd_name = fd.name.to_string();
 			let key = crate::key::table::fd::new(opt.ns(), opt.db(), &self.what, &fd_name);
 			run.del(key).await?;
+			// Delete any backfill of the field
+			let key = crate::key::table::bf::new(opt.ns(), opt.db(), &self.what, &fd_name);
+			run.del(key).await?;
 			// Clear the cache
 			let key = crate::key::table::fd::prefix(opt.ns(), opt.db(), &self.what);
 			run.clr(key).await?;
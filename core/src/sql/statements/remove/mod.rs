@@ -1,26 +1,36 @@
 mod analyzer;
+mod check;
 mod database;
+mod dictionary;
+mod enrichment;
 mod event;
 mod field;
 mod function;
+mod hook;
 mod index;
 mod model;
 mod namespace;
 mod param;
+mod role;
 mod scope;
 mod table;
 mod token;
 mod user;
 
 pub use analyzer::RemoveAnalyzerStatement;
+pub use check::RemoveCheckStatement;
 pub use database::RemoveDatabaseStatement;
+pub use dictionary::RemoveDictionaryStatement;
+pub use enrichment::RemoveEnrichmentStatement;
 pub use event::RemoveEventStatement;
 pub use field::RemoveFieldStatement;
 pub use function::RemoveFunctionStatement;
+pub use hook::RemoveHookStatement;
 pub use index::RemoveIndexStatement;
 pub use model::RemoveModelStatement;
 pub use namespace::RemoveNamespaceStatement;
 pub use param::RemoveParamStatement;
+pub use role::RemoveRoleStatement;
 pub use scope::RemoveScopeStatement;
 pub use table::RemoveTableStatement;
 pub use token::RemoveTokenStatement;
@@ -30,13 +40,14 @@ use crate::ctx::Context;
 use crate::dbs::{Options, Transaction};
 use crate::doc::CursorDoc;
 use crate::err::Error;
-use crate::sql::Value;
+use crate::kvs::history;
+use crate::sql::{Ident, StatementKind, Value};
 use derive::Store;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display, Formatter};
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 6)]
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -54,6 +65,16 @@ pub enum RemoveStatement {
 	Index(RemoveIndexStatement),
 	User(RemoveUserStatement),
 	Model(RemoveModelStatement),
+	#[revision(start = 2)]
+	Role(RemoveRoleStatement),
+	#[revision(start = 3)]
+	Dictionary(RemoveDictionaryStatement),
+	#[revision(start = 4)]
+	Check(RemoveCheckStatement),
+	#[revision(start = 5)]
+	Hook(RemoveHookStatement),
+	#[revision(start = 6)]
+	Enrichment(RemoveEnrichmentStatement),
 }
 
 impl RemoveStatement {
@@ -69,7 +90,9 @@ impl RemoveStatement {
 		txn: &Transaction,
 		_doc: Option<&CursorDoc<'_>>,
 	) -> Result<Value, Error> {
-		match self {
+		// Allowed to run?
+		opt.check_statement(StatementKind::Remove)?;
+		let res = match self {
 			Self::Namespace(ref v) => v.compute(ctx, opt, txn).await,
 			Self::Database(ref v) => v.compute(ctx, opt, txn).await,
 			Self::Function(ref v) => v.compute(ctx, opt, txn).await,
@@ -83,6 +106,27 @@ impl RemoveStatement {
 			Self::Analyzer(ref v) => v.compute(ctx, opt, txn).await,
 			Self::User(ref v) => v.compute(ctx, opt, txn).await,
 			Self::Model(ref v) => v.compute(ctx, opt, txn).await,
+			Self::Role(ref v) => v.compute(ctx, opt, txn).await,
+			Self::Dictionary(ref v) => v.compute(ctx, opt, txn).await,
+			Self::Check(ref v) => v.compute(ctx, opt, txn).await,
+			Self::Hook(ref v) => v.compute(ctx, opt, txn).await,
+			Self::Enrichment(ref v) => v.compute(ctx, opt, txn).await,
+		}?;
+		// Record the statement in the audit log
+		history::record(opt, txn, self.table(), self.to_string()).await?;
+		// Ok all good
+		Ok(res)
+	}
+	/// The table which this statement applies to, if any
+	fn table(&self) -> Option<&Ident> {
+		match self {
+			Self::Table(v) => Some(&v.name),
+			Self::Event(v) => Some(&v.what),
+			Self::Field(v) => Some(&v.what),
+			Self::Index(v) => Some(&v.what),
+			Self::Hook(v) => Some(&v.what),
+			Self::Enrichment(v) => Some(&v.what),
+			_ => None,
 		}
 	}
 }
@@ -103,6 +147,11 @@ impl Display for RemoveStatement {
 			Self::Analyzer(v) => Display::fmt(v, f),
 			Self::User(v) => Display::fmt(v, f),
 			Self::Model(v) => Display::fmt(v, f),
+			Self::Role(v) => Display::fmt(v, f),
+			Self::Dictionary(v) => Display::fmt(v, f),
+			Self::Check(v) => Display::fmt(v, f),
+			Self::Hook(v) => Display::fmt(v, f),
+			Self::Enrichment(v) => Display::fmt(v, f),
 		}
 	}
 }
@@ -1,3 +1,4 @@
+use crate::cnf::NORMAL_FETCH_SIZE;
 use crate::ctx::Context;
 use crate::dbs::{Options, Transaction};
 use crate::err::Error;
@@ -41,7 +42,7 @@ impl RemoveNamespaceStatement {
 			run.del(key).await?;
 			// Delete the resource data
 			let key = crate::key::namespace::all::new(&ns.name);
-			run.delp(key, u32::MAX).await?;
+			run.delp(key, NORMAL_FETCH_SIZE).await?;
 			// Ok all good
 			Ok(Value::None)
 		}
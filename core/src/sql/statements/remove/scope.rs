@@ -1,3 +1,4 @@
+use crate::cnf::NORMAL_FETCH_SIZE;
 use crate::ctx::Context;
 use crate::dbs::{Options, Transaction};
 use crate::err::Error;
@@ -40,7 +41,7 @@ impl RemoveScopeStatement {
 			run.del(key).await?;
 			// Remove the resource data
 			let key = crate::key::scope::all::new(opt.ns(), opt.db(), &sc.name);
-			run.delp(key, u32::MAX).await?;
+			run.delp(key, NORMAL_FETCH_SIZE).await?;
 			// Ok all good
 			Ok(Value::None)
 		}
@@ -1,3 +1,4 @@
+use crate::cnf::NORMAL_FETCH_SIZE;
 use crate::ctx::Context;
 use crate::dbs::Options;
 use crate::dbs::Transaction;
@@ -43,7 +44,7 @@ impl RemoveTableStatement {
 			run.del(key).await?;
 			// Remove the resource data
 			let key = crate::key::table::all::new(opt.ns(), opt.db(), &self.name);
-			run.delp(key, u32::MAX).await?;
+			run.delp(key, NORMAL_FETCH_SIZE).await?;
 			// Check if this is a foreign table
 			if let Some(view) = &tb.view {
 				// Process each foreign table
@@ -0,0 +1,205 @@
+use crate::ctx::Context;
+use crate::dbs::Options;
+use crate::dbs::Transaction;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::iam::{Action, ResourceKind};
+use crate::kvs::ScanPage;
+use crate::sql::paths::{EDGE, ID, IN, OUT};
+use crate::sql::value::Value;
+use crate::sql::{Base, Id, Idiom, Object, Part, StatementKind, Tables, Thing};
+use derive::Store;
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+/// The number of records which are read from a table at once
+const BATCH_SIZE: u32 = 1000;
+
+/// The number of checked record ids which are remembered, to avoid reading them again
+const MAX_CACHED_RECORDS: usize = 100_000;
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub enum ReportStatement {
+	References(ReportReferencesStatement),
+}
+
+impl ReportStatement {
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(
+		&self,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		doc: Option<&CursorDoc<'_>>,
+	) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.check_statement(StatementKind::Report)?;
+		match self {
+			Self::References(s) => s.compute(ctx, opt, txn, doc).await,
+		}
+	}
+}
+
+impl Display for ReportStatement {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		match self {
+			Self::References(v) => Display::fmt(v, f),
+		}
+	}
+}
+
+/// Reports the record links and graph edges which point to records which do not exist
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct ReportReferencesStatement {
+	/// The tables to check, or every table in the database when empty
+	pub what: Tables,
+}
+
+#[derive(Default)]
+struct Counts {
+	records: u64,
+	links: u64,
+	edges: u64,
+	dangling: u64,
+}
+
+impl ReportReferencesStatement {
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(
+		&self,
+		_ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		_doc: Option<&CursorDoc<'_>>,
+	) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.is_allowed(Action::View, ResourceKind::Table, &Base::Db)?;
+		// Claim transaction
+		let mut run = txn.lock().await;
+		// Get the tables to check
+		let tbs: Vec<String> = match self.what.is_empty() {
+			true => run.all_tb(opt.ns(), opt.db()).await?.iter().map(|v| v.name.to_raw()).collect(),
+			false => self.what.iter().map(|v| v.0.clone()).collect(),
+		};
+		// Whether each of the referenced records exists
+		let mut exists: HashMap<Thing, bool> = HashMap::new();
+		let mut tables = BTreeMap::new();
+		let mut dangling = Vec::new();
+		for tb in tbs {
+			let mut counts = Counts::default();
+			let beg = crate::key::thing::prefix(opt.ns(), opt.db(), &tb);
+			let end = crate::key::thing::suffix(opt.ns(), opt.db(), &tb);
+			let mut nxt = Some(ScanPage::from(beg..end));
+			while let Some(page) = nxt {
+				let res = run.scan_paged(page, BATCH_SIZE).await?;
+				nxt = res.next_page;
+				for (k, v) in res.values {
+					let k: crate::key::thing::Thing = (&k).into();
+					let v: Value = (&v).into();
+					let rid = Thing::from((k.tb, k.id));
+					let edge = matches!(v.pick(&*EDGE), Value::Bool(true));
+					counts.records += 1;
+					// Find every record which this record references
+					let mut refs = Vec::new();
+					references(&v, &mut Vec::new(), &mut refs);
+					for (path, target) in refs {
+						// Skip the id of the record itself
+						if path.0 == *ID {
+							continue;
+						}
+						let endpoint = edge && (path.0 == *IN || path.0 == *OUT);
+						match endpoint {
+							true => counts.edges += 1,
+							false => counts.links += 1,
+						}
+						// Check if the referenced record exists
+						let found = match exists.get(target) {
+							Some(v) => *v,
+							None => {
+								let key: Vec<u8> = crate::key::thing::new(
+									opt.ns(),
+									opt.db(),
+									&target.tb,
+									&target.id,
+								)
+								.into();
+								let v = run.exi(key).await?;
+								if exists.len() >= MAX_CACHED_RECORDS {
+									exists.clear();
+								}
+								exists.insert(target.clone(), v);
+								v
+							}
+						};
+						if !found {
+							counts.dangling += 1;
+							dangling.push(Value::from(Object::from(map! {
+								"record" => Value::from(rid.clone()),
+								"field" => Value::from(path.to_string()),
+								"target" => Value::from(target.clone()),
+								"edge" => Value::from(endpoint),
+							})));
+						}
+					}
+				}
+			}
+			tables.insert(
+				tb,
+				Value::from(Object::from(map! {
+					"records" => Value::from(counts.records),
+					"links" => Value::from(counts.links),
+					"edges" => Value::from(counts.edges),
+					"dangling" => Value::from(counts.dangling),
+				})),
+			);
+		}
+		// Return the report
+		Ok(Value::from(Object::from(map! {
+			"tables" => Value::from(Object::from(tables)),
+			"dangling" => Value::from(dangling),
+		})))
+	}
+}
+
+impl Display for ReportReferencesStatement {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "REPORT REFERENCES")?;
+		if !self.what.is_empty() {
+			write!(f, " ON {}", self.what)?
+		}
+		Ok(())
+	}
+}
+
+/// Collect the record ids which are referenced in a value, along with their paths
+fn references<'a>(v: &'a Value, path: &mut Vec<Part>, out: &mut Vec<(Idiom, &'a Thing)>) {
+	match v {
+		Value::Thing(t) if !matches!(t.id, Id::Generate(_)) => {
+			out.push((Idiom::from(path.clone()), t))
+		}
+		Value::Object(v) => {
+			for (k, v) in v.iter() {
+				path.push(Part::from(k.to_owned()));
+				references(v, path, out);
+				path.pop();
+			}
+		}
+		Value::Array(v) => {
+			for (i, v) in v.iter().enumerate() {
+				path.push(Part::from(i));
+				references(v, path, out);
+				path.pop();
+			}
+		}
+		_ => {}
+	}
+}
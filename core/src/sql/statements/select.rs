@@ -2,18 +2,21 @@ use crate::ctx::Context;
 use crate::dbs::{Iterable, Iterator, Options, Statement, Transaction};
 use crate::doc::CursorDoc;
 use crate::err::Error;
+use crate::fnc;
 use crate::idx::planner::QueryPlanner;
 use crate::sql::{
-	Cond, Explain, Fetchs, Field, Fields, Groups, Idioms, Limit, Orders, Splits, Start, Timeout,
-	Value, Values, Version, With,
+	Cond, Consistency, Datetime, Duration, Explain, Expression, Fetchs, Field, Fields, Function,
+	Groups, Idioms, Limit, Mock, Orders, Outfile, Part, Sample, Splits, Start, StatementKind,
+	Timeout, Value, Values, Version, With,
 };
+use channel::Sender;
 use derive::Store;
 use reblessive::tree::Stk;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[revisioned(revision = 2)]
+#[revisioned(revision = 7)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Store, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
@@ -28,6 +31,8 @@ pub struct SelectStatement {
 	pub split: Option<Splits>,
 	pub group: Option<Groups>,
 	pub order: Option<Orders>,
+	#[revision(start = 3)]
+	pub sample: Option<Sample>,
 	pub limit: Option<Limit>,
 	pub start: Option<Start>,
 	pub fetch: Option<Fetchs>,
@@ -35,11 +40,22 @@ pub struct SelectStatement {
 	pub timeout: Option<Timeout>,
 	pub parallel: bool,
 	pub explain: Option<Explain>,
+	#[revision(start = 4)]
+	pub outfile: Option<Outfile>,
+	#[revision(start = 5)]
+	pub lock: bool,
+	#[revision(start = 6)]
+	pub cache: Option<Duration>,
+	#[revision(start = 7)]
+	pub consistency: Option<Consistency>,
 }
 
 impl SelectStatement {
 	/// Check if we require a writeable transaction
 	pub(crate) fn writeable(&self) -> bool {
+		if self.lock {
+			return true;
+		}
 		if self.expr.iter().any(|v| match v {
 			Field::All => false,
 			Field::Single {
@@ -64,8 +80,54 @@ impl SelectStatement {
 		txn: &Transaction,
 		doc: Option<&CursorDoc<'_>>,
 	) -> Result<Value, Error> {
+		self.process(stk, ctx, opt, txn, doc, None).await
+	}
+
+	/// Process this type, sending the records to the channel in batches as
+	/// they are produced, and returning any records which were not sent
+	pub(crate) async fn compute_stream(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		chn: Sender<Vec<Value>>,
+	) -> Result<Value, Error> {
+		self.process(stk, ctx, opt, txn, None, Some(chn)).await
+	}
+
+	async fn process(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		doc: Option<&CursorDoc<'_>>,
+		chn: Option<Sender<Vec<Value>>>,
+	) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.check_statement(StatementKind::Select)?;
 		// Valid options?
 		opt.valid_for_db()?;
+		// Check if there is a cached result
+		let cache = match self.cache {
+			Some(ref ttl)
+				if doc.is_none()
+					&& chn.is_none()
+					&& self.outfile.is_none()
+					&& !self.writeable()
+					&& self.cacheable() =>
+			{
+				let key = self.cache_key(ctx, opt);
+				if let Some(v) = txn.lock().await.cached_result(&key) {
+					return Ok(v);
+				}
+				Some((key, ttl.0))
+			}
+			_ => None,
+		};
+		// The tables which the statement selects from
+		let mut tables = cache.as_ref().map(|_| Vec::new());
 		// Create a new iterator
 		let mut i = Iterator::new();
 		// Ensure futures are stored
@@ -84,6 +146,17 @@ impl SelectStatement {
 		// Loop over the select targets
 		for w in self.what.0.iter() {
 			let v = w.compute(stk, ctx, opt, txn, doc).await?;
+			// Track the tables for invalidating a cached result
+			if let Some(t) = &mut tables {
+				if !tables_of(&v, t) {
+					tables = None;
+				}
+			}
+			// Read the records as they were at the version
+			if let Some(version) = &self.version {
+				self.ingest_version(v, &version.0, limit_is_one_or_zero, &mut i)?;
+				continue;
+			}
 			match v {
 				Value::Table(t) => {
 					if self.only && !limit_is_one_or_zero {
@@ -105,7 +178,7 @@ impl SelectStatement {
 						return Err(Error::SingleOnlyOutput);
 					}
 
-					i.ingest(Iterable::Edges(*v))
+					planner.add_edges_iterables(stk, ctx, txn, *v, &mut i).await?;
 				}
 				Value::Mock(v) => {
 					if self.only && !limit_is_one_or_zero {
@@ -127,7 +200,9 @@ impl SelectStatement {
 								planner.add_iterables(stk, ctx, txn, t, &mut i).await?;
 							}
 							Value::Thing(v) => i.ingest(Iterable::Thing(v)),
-							Value::Edges(v) => i.ingest(Iterable::Edges(*v)),
+							Value::Edges(v) => {
+								planner.add_edges_iterables(stk, ctx, txn, *v, &mut i).await?;
+							}
 							Value::Mock(v) => {
 								for v in v {
 									i.ingest(Iterable::Thing(v));
@@ -142,14 +217,48 @@ impl SelectStatement {
 		}
 		// Create a new context
 		let mut ctx = Context::new(ctx);
-		// Assign the statement
-		let stm = Statement::from(self);
 		// Add query executors if any
 		if planner.has_executors() {
 			ctx.set_query_planner(&planner);
 		}
+		// Write the results to object storage if specified
+		if let Some(v) = &self.outfile {
+			let ctx = &ctx;
+			return v
+				.write(ctx, opt, |chn| async move {
+					// A single record result is written once it is known
+					if !self.only {
+						i.stream(chn);
+					}
+					self.output(stk, ctx, opt, txn, i).await
+				})
+				.await;
+		}
+		// Send the results in batches if requested, unless this is a single record result
+		if let Some(chn) = chn.filter(|_| !self.only) {
+			i.stream(chn);
+		}
 		// Output the results
-		match i.output(stk, &ctx, opt, txn, &stm).await? {
+		let res = self.output(stk, &ctx, opt, txn, i).await?;
+		// Cache the result if specified
+		if let (Some((key, ttl)), Some(tables)) = (cache, tables) {
+			txn.lock().await.cache_result(key, opt.ns(), opt.db(), &tables, ttl, res.clone());
+		}
+		Ok(res)
+	}
+
+	/// Output the results of the iterator, as a single record result for ONLY
+	async fn output(
+		&self,
+		stk: &mut Stk,
+		ctx: &Context<'_>,
+		opt: &Options,
+		txn: &Transaction,
+		mut i: Iterator,
+	) -> Result<Value, Error> {
+		// Assign the statement
+		let stm = Statement::from(self);
+		match i.output(stk, ctx, opt, txn, &stm).await? {
 			// This is a single record result
 			Value::Array(mut a) if self.only => match a.len() {
 				// There were no results
@@ -163,6 +272,141 @@ impl SelectStatement {
 			v => Ok(v),
 		}
 	}
+
+	/// Check if the result can be cached, as it only reads the records of the selected
+	/// tables, rather than fetching records, or reading them through a subquery, a graph
+	/// traversal, or a record link, which would not invalidate the cached result
+	fn cacheable(&self) -> bool {
+		self.fetch.is_none()
+			&& self.expr.iter().all(|f| match f {
+				Field::All => true,
+				Field::Single {
+					expr,
+					..
+				} => is_local(expr),
+			}) && self.cond.iter().all(|c| is_local(&c.0))
+	}
+
+	/// The key of a cached result, from the statement and the parameters which it uses
+	fn cache_key(&self, ctx: &Context<'_>, opt: &Options) -> String {
+		let stm = SelectStatement {
+			cache: None,
+			..self.clone()
+		}
+		.to_string();
+		let mut key =
+			format!("{}/{}/{}/{}\n{stm}", opt.ns(), opt.db(), opt.auth.level(), opt.auth.id());
+		let mut seen = Vec::new();
+		for (pos, _) in stm.match_indices('$') {
+			let name: String = stm[pos + 1..]
+				.chars()
+				.take_while(|c| c.is_ascii_alphanumeric() || *c == '_')
+				.collect();
+			if name.is_empty() || seen.contains(&name) {
+				continue;
+			}
+			let value = ctx.value(&name).cloned().unwrap_or_default();
+			key.push_str(&format!("\n${name}={value}"));
+			seen.push(name);
+		}
+		key
+	}
+
+	/// Add the iterables which read a target as it was at a version
+	fn ingest_version(
+		&self,
+		v: Value,
+		at: &Datetime,
+		limit_is_one_or_zero: bool,
+		i: &mut Iterator,
+	) -> Result<(), Error> {
+		match v {
+			Value::Table(t) => {
+				if self.only && !limit_is_one_or_zero {
+					return Err(Error::SingleOnlyOutput);
+				}
+				i.ingest(Iterable::TableVersion(t, at.clone()))
+			}
+			Value::Thing(v) => i.ingest(Iterable::ThingVersion(v, at.clone())),
+			Value::Array(v) => {
+				if self.only && !limit_is_one_or_zero {
+					return Err(Error::SingleOnlyOutput);
+				}
+				for v in v {
+					match v {
+						Value::Table(t) => i.ingest(Iterable::TableVersion(t, at.clone())),
+						Value::Thing(v) => i.ingest(Iterable::ThingVersion(v, at.clone())),
+						v => {
+							return Err(Error::SelectVersionStatement {
+								value: v.to_string(),
+							})
+						}
+					}
+				}
+			}
+			v => {
+				return Err(Error::SelectVersionStatement {
+					value: v.to_string(),
+				})
+			}
+		};
+		Ok(())
+	}
+}
+
+/// Add the tables which a target selects from, returning false if they are not known
+fn tables_of(v: &Value, out: &mut Vec<String>) -> bool {
+	let tb = match v {
+		Value::Table(t) => &t.0,
+		Value::Thing(t) => &t.tb,
+		Value::Range(r) => &r.tb,
+		Value::Mock(Mock::Count(tb, _) | Mock::Range(tb, _, _)) => tb,
+		Value::Edges(e) if !e.what.is_empty() => {
+			out.push(e.from.tb.clone());
+			out.extend(e.what.iter().map(|t| t.0.clone()));
+			return true;
+		}
+		Value::Edges(_) => return false,
+		Value::Array(a) => return a.iter().all(|v| tables_of(v, out)),
+		_ => return false,
+	};
+	if !out.contains(tb) {
+		out.push(tb.clone());
+	}
+	true
+}
+
+/// Check if a value is computed without reading any records, other than the current record
+fn is_local(v: &Value) -> bool {
+	match v {
+		// A path into a field could follow a record link
+		Value::Idiom(i) => matches!(i.0.as_slice(), [Part::Field(_)]),
+		Value::Array(a) => a.iter().all(is_local),
+		Value::Object(o) => o.values().all(is_local),
+		Value::Cast(c) => is_local(&c.1),
+		Value::Expression(e) => match e.as_ref() {
+			Expression::Unary {
+				v,
+				..
+			} => is_local(v),
+			Expression::Binary {
+				l,
+				r,
+				..
+			} => is_local(l) && is_local(r),
+		},
+		Value::Function(f) => match f.as_ref() {
+			Function::Normal(name, args) => !fnc::is_async(name) && args.iter().all(is_local),
+			_ => false,
+		},
+		Value::Block(_)
+		| Value::Edges(_)
+		| Value::Future(_)
+		| Value::Model(_)
+		| Value::Query(_)
+		| Value::Subquery(_) => false,
+		_ => true,
+	}
 }
 
 impl fmt::Display for SelectStatement {
@@ -179,6 +423,9 @@ impl fmt::Display for SelectStatement {
 		if let Some(ref v) = self.with {
 			write!(f, " {v}")?
 		}
+		if let Some(ref v) = self.consistency {
+			write!(f, " {v}")?
+		}
 		if let Some(ref v) = self.cond {
 			write!(f, " {v}")?
 		}
@@ -191,6 +438,9 @@ impl fmt::Display for SelectStatement {
 		if let Some(ref v) = self.order {
 			write!(f, " {v}")?
 		}
+		if let Some(ref v) = self.sample {
+			write!(f, " {v}")?
+		}
 		if let Some(ref v) = self.limit {
 			write!(f, " {v}")?
 		}
@@ -209,6 +459,15 @@ impl fmt::Display for SelectStatement {
 		if self.parallel {
 			f.write_str(" PARALLEL")?
 		}
+		if let Some(ref v) = self.cache {
+			write!(f, " CACHE {v}")?
+		}
+		if self.lock {
+			f.write_str(" FOR UPDATE")?
+		}
+		if let Some(ref v) = self.outfile {
+			write!(f, " {v}")?
+		}
 		if let Some(ref v) = self.explain {
 			write!(f, " {v}")?
 		}
@@ -3,7 +3,7 @@ use crate::dbs::{Options, Transaction};
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::iam::{Action, ResourceKind};
-use crate::sql::{Base, Datetime, Table, Value};
+use crate::sql::{Base, Datetime, StatementKind, Table, Value};
 use crate::vs::{conv, Versionstamp};
 use derive::Store;
 use revision::revisioned;
@@ -53,6 +53,8 @@ impl ShowStatement {
 		txn: &Transaction,
 		_doc: Option<&CursorDoc<'_>>,
 	) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.check_statement(StatementKind::Show)?;
 		// Selected DB?
 		opt.is_allowed(Action::View, ResourceKind::Table, &Base::Db)?;
 		// Clone transaction
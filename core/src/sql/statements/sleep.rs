@@ -3,7 +3,7 @@ use crate::dbs::{Options, Transaction};
 use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::iam::{Action, ResourceKind};
-use crate::sql::{Base, Duration, Value};
+use crate::sql::{Base, Duration, StatementKind, Value};
 use derive::Store;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
@@ -26,6 +26,7 @@ impl SleepStatement {
 		_doc: Option<&CursorDoc<'_>>,
 	) -> Result<Value, Error> {
 		// Allowed to run?
+		opt.check_statement(StatementKind::Sleep)?;
 		opt.is_allowed(Action::Edit, ResourceKind::Table, &Base::Root)?;
 		// Calculate the sleep duration
 		let dur = match (ctx.timeout(), self.duration.0) {
@@ -2,11 +2,13 @@ use crate::ctx::Context;
 use crate::dbs::{Iterator, Options, Statement, Transaction};
 use crate::doc::CursorDoc;
 use crate::err::Error;
-use crate::sql::{Cond, Data, Output, Timeout, Value, Values};
+use crate::kvs::Merge;
+use crate::sql::{Cond, Data, Output, StatementKind, Timeout, Value, Values};
 use derive::Store;
 use reblessive::tree::Stk;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
 use std::fmt;
 
 #[revisioned(revision = 2)]
@@ -29,6 +31,10 @@ impl UpdateStatement {
 	pub(crate) fn writeable(&self) -> bool {
 		true
 	}
+	/// Fetch the operations performed by this statement, if it can be retried after a conflict
+	pub(crate) fn merges(&self) -> Option<BTreeSet<Merge>> {
+		self.data.as_ref().and_then(Data::merges)
+	}
 	/// Process this type returning a computed simple Value
 	pub(crate) async fn compute(
 		&self,
@@ -38,6 +44,8 @@ impl UpdateStatement {
 		txn: &Transaction,
 		doc: Option<&CursorDoc<'_>>,
 	) -> Result<Value, Error> {
+		// Allowed to run?
+		opt.check_statement(StatementKind::Update)?;
 		// Valid options?
 		opt.valid_for_db()?;
 		// Create a new iterator
@@ -98,3 +106,28 @@ impl fmt::Display for UpdateStatement {
 		Ok(())
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use crate::kvs::Merge;
+	use crate::syn::parse;
+	use std::collections::BTreeSet;
+
+	fn merges(sql: &str) -> Option<BTreeSet<Merge>> {
+		parse(sql).unwrap().into_iter().next().unwrap().merges()
+	}
+
+	#[test]
+	fn commutative_updates() {
+		assert_eq!(merges("UPDATE doc SET tags += 'x'"), Some(BTreeSet::from([Merge::Append])));
+		assert_eq!(
+			merges("UPDATE doc SET tags += $tag, count += 1, score -= 2.5"),
+			Some(BTreeSet::from([Merge::Append, Merge::Increment]))
+		);
+		assert_eq!(merges("UPDATE doc SET tags -= 'x'"), None);
+		assert_eq!(merges("UPDATE doc SET count += 1, name = 'x'"), None);
+		assert_eq!(merges("UPDATE doc SET tags += other"), None);
+		assert_eq!(merges("UPDATE doc MERGE { count: 1 }"), None);
+		assert_eq!(merges("CREATE doc SET count += 1"), None);
+	}
+}
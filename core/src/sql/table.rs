@@ -1,4 +1,5 @@
-use crate::sql::{escape::escape_ident, fmt::Fmt, strand::no_nul_bytes, Id, Ident, Thing};
+use crate::ctx::Context;
+use crate::sql::{escape::escape_ident, fmt::Fmt, id::Gen, strand::no_nul_bytes, Id, Ident, Thing};
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display, Formatter};
@@ -65,10 +66,10 @@ impl Deref for Table {
 }
 
 impl Table {
-	pub fn generate(&self) -> Thing {
+	pub(crate) fn generate(&self, ctx: &Context) -> Thing {
 		Thing {
 			tb: self.0.to_owned(),
-			id: Id::rand(),
+			id: Id::generate(ctx, &Gen::Rand),
 		}
 	}
 }
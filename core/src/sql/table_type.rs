@@ -33,6 +33,9 @@ impl Display for TableType {
 				if let Some(Kind::Record(kind)) = &rel.to {
 					write!(f, " OUT {}", get_tables_from_kind(kind).join(" | "))?;
 				}
+				if rel.on_delete != OnDelete::Cascade {
+					write!(f, " ON DELETE {}", rel.on_delete)?;
+				}
 			}
 			TableType::Any => {
 				f.write_str(" ANY")?;
@@ -69,6 +72,8 @@ impl InfoStructure for TableType {
 						Value::Array(Array::from(get_tables_from_kind(tables))),
 					);
 				}
+
+				acc.insert("on_delete".to_string(), rel.on_delete.to_string().into());
 			}
 		};
 
@@ -80,11 +85,35 @@ fn get_tables_from_kind(tables: &[Table]) -> Vec<&str> {
 	tables.iter().map(|t| t.0.as_str()).collect::<Vec<_>>()
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Debug, Default, Serialize, Deserialize, Hash, Clone, Eq, PartialEq, PartialOrd)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[non_exhaustive]
 pub struct Relation {
 	pub from: Option<Kind>,
 	pub to: Option<Kind>,
+	#[revision(start = 2)]
+	pub on_delete: OnDelete,
+}
+
+/// What happens to the edges in a relation table when one of their endpoints is deleted
+#[revisioned(revision = 1)]
+#[derive(Debug, Default, Serialize, Deserialize, Hash, Clone, Copy, Eq, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub enum OnDelete {
+	/// The edges are deleted along with the endpoint
+	#[default]
+	Cascade,
+	/// The endpoint can not be deleted while any edges reference it
+	Reject,
+}
+
+impl Display for OnDelete {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			OnDelete::Cascade => f.write_str("CASCADE"),
+			OnDelete::Reject => f.write_str("REJECT"),
+		}
+	}
 }
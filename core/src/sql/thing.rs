@@ -2,7 +2,8 @@ use crate::ctx::Context;
 use crate::dbs::{Options, Transaction};
 use crate::doc::CursorDoc;
 use crate::err::Error;
-use crate::sql::{escape::escape_rid, id::Id, Strand, Value};
+use crate::sql::escape::{escape_reserved_keyword, escape_rid};
+use crate::sql::{id::Id, Strand, Value};
 use crate::syn;
 use derive::Store;
 use reblessive::tree::Stk;
@@ -94,7 +95,10 @@ impl Thing {
 
 impl fmt::Display for Thing {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		write!(f, "{}:{}", escape_rid(&self.tb), self.id)
+		match escape_reserved_keyword(&self.tb) {
+			Some(tb) => write!(f, "{tb}:{}", self.id),
+			None => write!(f, "{}:{}", escape_rid(&self.tb), self.id),
+		}
 	}
 }
 
@@ -0,0 +1,49 @@
+use crate::sql::statements::info::InfoStructure;
+use crate::sql::{Idiom, Object, Value};
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+
+/// What happens to the fields of a record which are not defined on a SCHEMAFULL table
+#[revisioned(revision = 1)]
+#[derive(Debug, Default, Serialize, Deserialize, Hash, Clone, Eq, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub enum UnknownFields {
+	/// The fields are removed from the record
+	#[default]
+	Drop,
+	/// The record is not written, and an error lists the fields
+	Reject,
+	/// The fields are moved under another field of the record
+	Quarantine(Idiom),
+}
+
+impl Display for UnknownFields {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			UnknownFields::Drop => f.write_str("DROP"),
+			UnknownFields::Reject => f.write_str("REJECT"),
+			UnknownFields::Quarantine(v) => write!(f, "QUARANTINE INTO {v}"),
+		}
+	}
+}
+
+impl InfoStructure for UnknownFields {
+	fn structure(self) -> Value {
+		let mut acc = Object::default();
+		match self {
+			UnknownFields::Drop => {
+				acc.insert("policy".to_string(), "DROP".into());
+			}
+			UnknownFields::Reject => {
+				acc.insert("policy".to_string(), "REJECT".into());
+			}
+			UnknownFields::Quarantine(v) => {
+				acc.insert("policy".to_string(), "QUARANTINE".into());
+				acc.insert("into".to_string(), v.structure());
+			}
+		}
+		Value::Object(acc)
+	}
+}
@@ -1,4 +1,6 @@
+use crate::ctx::Context;
 use crate::sql::{escape::quote_str, strand::Strand};
+use rand::Rng;
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display, Formatter};
@@ -80,6 +82,15 @@ impl Uuid {
 	pub fn new_v7() -> Self {
 		Self(uuid::Uuid::now_v7())
 	}
+	/// Generate a new V4 UUID, using the random number generator of the context
+	pub(crate) fn new_v4_in(ctx: &Context) -> Self {
+		Self(uuid::Builder::from_random_bytes(ctx.rng(|r| r.gen())).into_uuid())
+	}
+	/// Generate a new V7 UUID, using the clock and random number generator of the context
+	pub(crate) fn new_v7_in(ctx: &Context) -> Self {
+		let ms = ctx.now().timestamp_millis() as u64;
+		Self(uuid::Builder::from_unix_timestamp_millis(ms, &ctx.rng(|r| r.gen())).into_uuid())
+	}
 	/// Convert the Uuid to a raw String
 	pub fn to_raw(&self) -> String {
 		self.0.to_string()
@@ -6,11 +6,73 @@ use crate::sql::field::{Field, Fields};
 use crate::sql::part::Next;
 use crate::sql::part::Part;
 use crate::sql::statements::select::SelectStatement;
+use crate::sql::thing::Thing;
 use crate::sql::value::{Value, Values};
 use futures::future::try_join_all;
 use reblessive::tree::Stk;
 
 impl Value {
+	/// Collect the records which are fetched at a path, so that they can be read ahead of time
+	pub(crate) fn fetch_targets<'a>(&'a self, path: &[Part], out: &mut Vec<&'a Thing>) {
+		match path.first() {
+			// Get the current path part
+			Some(p) => match self {
+				// Current path part is an object
+				Value::Object(v) => match p {
+					Part::Field(f) => {
+						if let Some(v) = v.get(f as &str) {
+							v.fetch_targets(path.next(), out)
+						}
+					}
+					Part::Index(i) => {
+						if let Some(v) = v.get(&i.to_string()) {
+							v.fetch_targets(path.next(), out)
+						}
+					}
+					Part::All => self.fetch_targets(path.next(), out),
+					_ => {}
+				},
+				// Current path part is an array
+				Value::Array(v) => match p {
+					Part::All => v.iter().for_each(|v| v.fetch_targets(path.next(), out)),
+					Part::First => {
+						if let Some(v) = v.first() {
+							v.fetch_targets(path.next(), out)
+						}
+					}
+					Part::Last => {
+						if let Some(v) = v.last() {
+							v.fetch_targets(path.next(), out)
+						}
+					}
+					Part::Index(i) => {
+						if let Some(v) = v.get(i.to_usize()) {
+							v.fetch_targets(path.next(), out)
+						}
+					}
+					// Only the records which match the condition are fetched
+					Part::Where(_) => {}
+					_ => v.iter().for_each(|v| v.fetch_targets(path, out)),
+				},
+				// Graph traversals are fetched using the edges of the record
+				Value::Thing(_) if matches!(p, Part::Graph(_)) => {}
+				// Current path part is a thing
+				Value::Thing(v) => out.push(v),
+				// Ignore everything else
+				_ => {}
+			},
+			// No more parts so get the value
+			None => match self {
+				// Current path part is an array
+				Value::Array(v) => v.iter().for_each(|v| v.fetch_targets(path, out)),
+				// Current path part is a thing
+				Value::Thing(v) => out.push(v),
+				// Ignore everything else
+				_ => {}
+			},
+		}
+	}
+
 	/// Was marked recursive
 	pub(crate) async fn fetch(
 		&mut self,
@@ -164,3 +226,26 @@ impl Value {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+
+	use super::*;
+	use crate::sql::idiom::Idiom;
+	use crate::syn::Parse;
+
+	#[test]
+	fn fetch_targets() {
+		let val = Value::parse("{ one: person:one, many: [person:two, { id: person:three }] }");
+		let targets = |path: &str| {
+			let mut out = Vec::new();
+			val.fetch_targets(&Idiom::parse(path), &mut out);
+			out.into_iter().map(|v| v.to_string()).collect::<Vec<_>>()
+		};
+		assert_eq!(targets("one"), vec!["person:one"]);
+		assert_eq!(targets("many"), vec!["person:two"]);
+		assert_eq!(targets("many.id"), vec!["person:two", "person:three"]);
+		assert_eq!(targets("many[0]"), vec!["person:two"]);
+		assert!(targets("missing").is_empty());
+	}
+}
@@ -0,0 +1,59 @@
+pub(super) mod opt;
+
+use crate::err::Error;
+use crate::sql::value::serde::ser;
+use crate::sql::Consistency;
+use serde::ser::Error as _;
+use serde::ser::Impossible;
+
+pub(super) struct Serializer;
+
+impl ser::Serializer for Serializer {
+	type Ok = Consistency;
+	type Error = Error;
+
+	type SerializeSeq = Impossible<Consistency, Error>;
+	type SerializeTuple = Impossible<Consistency, Error>;
+	type SerializeTupleStruct = Impossible<Consistency, Error>;
+	type SerializeTupleVariant = Impossible<Consistency, Error>;
+	type SerializeMap = Impossible<Consistency, Error>;
+	type SerializeStruct = Impossible<Consistency, Error>;
+	type SerializeStructVariant = Impossible<Consistency, Error>;
+
+	const EXPECTED: &'static str = "an enum `Consistency`";
+
+	#[inline]
+	fn serialize_unit_variant(
+		self,
+		name: &'static str,
+		_variant_index: u32,
+		variant: &'static str,
+	) -> Result<Self::Ok, Error> {
+		match variant {
+			"Eventual" => Ok(Consistency::Eventual),
+			"Strong" => Ok(Consistency::Strong),
+			variant => Err(Error::custom(format!("unexpected unit variant `{name}::{variant}`"))),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ser::Serializer as _;
+	use serde::Serialize;
+
+	#[test]
+	fn eventual() {
+		let consistency = Consistency::Eventual;
+		let serialized = consistency.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(consistency, serialized);
+	}
+
+	#[test]
+	fn strong() {
+		let consistency = Consistency::Strong;
+		let serialized = consistency.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(consistency, serialized);
+	}
+}
@@ -0,0 +1,56 @@
+use crate::err::Error;
+use crate::sql::value::serde::ser;
+use crate::sql::Consistency;
+use serde::ser::Impossible;
+use serde::ser::Serialize;
+
+#[non_exhaustive]
+pub struct Serializer;
+
+impl ser::Serializer for Serializer {
+	type Ok = Option<Consistency>;
+	type Error = Error;
+
+	type SerializeSeq = Impossible<Option<Consistency>, Error>;
+	type SerializeTuple = Impossible<Option<Consistency>, Error>;
+	type SerializeTupleStruct = Impossible<Option<Consistency>, Error>;
+	type SerializeTupleVariant = Impossible<Option<Consistency>, Error>;
+	type SerializeMap = Impossible<Option<Consistency>, Error>;
+	type SerializeStruct = Impossible<Option<Consistency>, Error>;
+	type SerializeStructVariant = Impossible<Option<Consistency>, Error>;
+
+	const EXPECTED: &'static str = "an `Option<Consistency>`";
+
+	#[inline]
+	fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+		Ok(None)
+	}
+
+	#[inline]
+	fn serialize_some<T>(self, value: &T) -> Result<Self::Ok, Self::Error>
+	where
+		T: ?Sized + Serialize,
+	{
+		Ok(Some(value.serialize(ser::consistency::Serializer.wrap())?))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ser::Serializer as _;
+
+	#[test]
+	fn none() {
+		let option: Option<Consistency> = None;
+		let serialized = option.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(option, serialized);
+	}
+
+	#[test]
+	fn some() {
+		let option = Some(Consistency::Eventual);
+		let serialized = option.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(option, serialized);
+	}
+}
@@ -0,0 +1,67 @@
+pub(super) mod opt;
+
+use crate::err::Error;
+use crate::sql::value::serde::ser;
+use crate::sql::Crdt;
+use serde::ser::Error as _;
+use serde::ser::Impossible;
+
+pub(super) struct Serializer;
+
+impl ser::Serializer for Serializer {
+	type Ok = Crdt;
+	type Error = Error;
+
+	type SerializeSeq = Impossible<Crdt, Error>;
+	type SerializeTuple = Impossible<Crdt, Error>;
+	type SerializeTupleStruct = Impossible<Crdt, Error>;
+	type SerializeTupleVariant = Impossible<Crdt, Error>;
+	type SerializeMap = Impossible<Crdt, Error>;
+	type SerializeStruct = Impossible<Crdt, Error>;
+	type SerializeStructVariant = Impossible<Crdt, Error>;
+
+	const EXPECTED: &'static str = "an enum `Crdt`";
+
+	#[inline]
+	fn serialize_unit_variant(
+		self,
+		name: &'static str,
+		_variant_index: u32,
+		variant: &'static str,
+	) -> Result<Self::Ok, Error> {
+		match variant {
+			"Counter" => Ok(Crdt::Counter),
+			"Register" => Ok(Crdt::Register),
+			"Set" => Ok(Crdt::Set),
+			variant => Err(Error::custom(format!("unexpected unit variant `{name}::{variant}`"))),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ser::Serializer as _;
+	use serde::Serialize;
+
+	#[test]
+	fn counter() {
+		let crdt = Crdt::Counter;
+		let serialized = crdt.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(crdt, serialized);
+	}
+
+	#[test]
+	fn register() {
+		let crdt = Crdt::Register;
+		let serialized = crdt.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(crdt, serialized);
+	}
+
+	#[test]
+	fn set() {
+		let crdt = Crdt::Set;
+		let serialized = crdt.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(crdt, serialized);
+	}
+}
@@ -0,0 +1,56 @@
+use crate::err::Error;
+use crate::sql::value::serde::ser;
+use crate::sql::Crdt;
+use serde::ser::Impossible;
+use serde::ser::Serialize;
+
+#[non_exhaustive]
+pub struct Serializer;
+
+impl ser::Serializer for Serializer {
+	type Ok = Option<Crdt>;
+	type Error = Error;
+
+	type SerializeSeq = Impossible<Option<Crdt>, Error>;
+	type SerializeTuple = Impossible<Option<Crdt>, Error>;
+	type SerializeTupleStruct = Impossible<Option<Crdt>, Error>;
+	type SerializeTupleVariant = Impossible<Option<Crdt>, Error>;
+	type SerializeMap = Impossible<Option<Crdt>, Error>;
+	type SerializeStruct = Impossible<Option<Crdt>, Error>;
+	type SerializeStructVariant = Impossible<Option<Crdt>, Error>;
+
+	const EXPECTED: &'static str = "an `Option<Crdt>`";
+
+	#[inline]
+	fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+		Ok(None)
+	}
+
+	#[inline]
+	fn serialize_some<T>(self, value: &T) -> Result<Self::Ok, Self::Error>
+	where
+		T: ?Sized + Serialize,
+	{
+		Ok(Some(value.serialize(super::Serializer.wrap())?))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ser::Serializer as _;
+
+	#[test]
+	fn none() {
+		let option: Option<Crdt> = None;
+		let serialized = option.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(option, serialized);
+	}
+
+	#[test]
+	fn some() {
+		let option = Some(Crdt::Counter);
+		let serialized = option.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(option, serialized);
+	}
+}
@@ -1,3 +1,5 @@
+pub(super) mod opt;
+
 use crate::err::Error;
 use crate::sql::value::serde::ser;
 use chrono::offset::Utc;
@@ -3,6 +3,7 @@ pub(super) mod vec;
 use crate::err::Error;
 use crate::sql::filter::Filter;
 use crate::sql::value::serde::ser;
+use crate::sql::Ident;
 use ser::Serializer as _;
 use serde::ser::Error as _;
 use serde::ser::Impossible;
@@ -52,6 +53,12 @@ impl ser::Serializer for Serializer {
 	{
 		match variant {
 			"Snowball" => Ok(Filter::Snowball(value.serialize(ser::language::Serializer.wrap())?)),
+			"Stopwords" => {
+				Ok(Filter::Stopwords(Ident(value.serialize(ser::string::Serializer.wrap())?)))
+			}
+			"Synonyms" => {
+				Ok(Filter::Synonyms(Ident(value.serialize(ser::string::Serializer.wrap())?)))
+			}
 			variant => {
 				Err(Error::custom(format!("unexpected newtype variant `{name}::{variant}`")))
 			}
@@ -151,4 +158,18 @@ mod tests {
 		let serialized = filter.serialize(Serializer.wrap()).unwrap();
 		assert_eq!(filter, serialized);
 	}
+
+	#[test]
+	fn stopwords() {
+		let filter = Filter::Stopwords(Ident("english".to_owned()));
+		let serialized = filter.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(filter, serialized);
+	}
+
+	#[test]
+	fn synonyms() {
+		let filter = Filter::Synonyms(Ident("vehicles".to_owned()));
+		let serialized = filter.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(filter, serialized);
+	}
 }
@@ -0,0 +1,56 @@
+use crate::err::Error;
+use crate::sql::value::serde::ser;
+use crate::sql::Isolation;
+use serde::ser::Impossible;
+use serde::ser::Serialize;
+
+#[non_exhaustive]
+pub struct Serializer;
+
+impl ser::Serializer for Serializer {
+	type Ok = Option<Isolation>;
+	type Error = Error;
+
+	type SerializeSeq = Impossible<Option<Isolation>, Error>;
+	type SerializeTuple = Impossible<Option<Isolation>, Error>;
+	type SerializeTupleStruct = Impossible<Option<Isolation>, Error>;
+	type SerializeTupleVariant = Impossible<Option<Isolation>, Error>;
+	type SerializeMap = Impossible<Option<Isolation>, Error>;
+	type SerializeStruct = Impossible<Option<Isolation>, Error>;
+	type SerializeStructVariant = Impossible<Option<Isolation>, Error>;
+
+	const EXPECTED: &'static str = "an `Option<Isolation>`";
+
+	#[inline]
+	fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+		Ok(None)
+	}
+
+	#[inline]
+	fn serialize_some<T>(self, value: &T) -> Result<Self::Ok, Self::Error>
+	where
+		T: ?Sized + Serialize,
+	{
+		Ok(Some(value.serialize(ser::isolation::Serializer.wrap())?))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ser::Serializer as _;
+
+	#[test]
+	fn none() {
+		let option: Option<Isolation> = None;
+		let serialized = option.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(option, serialized);
+	}
+
+	#[test]
+	fn some() {
+		let option = Some(Isolation::Snapshot);
+		let serialized = option.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(option, serialized);
+	}
+}
@@ -0,0 +1,58 @@
+use crate::err::Error;
+use crate::sql::statements::KillKind;
+use crate::sql::value::serde::ser;
+use serde::ser::Error as _;
+use serde::ser::Impossible;
+
+pub(super) struct Serializer;
+
+impl ser::Serializer for Serializer {
+	type Ok = KillKind;
+	type Error = Error;
+
+	type SerializeSeq = Impossible<KillKind, Error>;
+	type SerializeTuple = Impossible<KillKind, Error>;
+	type SerializeTupleStruct = Impossible<KillKind, Error>;
+	type SerializeTupleVariant = Impossible<KillKind, Error>;
+	type SerializeMap = Impossible<KillKind, Error>;
+	type SerializeStruct = Impossible<KillKind, Error>;
+	type SerializeStructVariant = Impossible<KillKind, Error>;
+
+	const EXPECTED: &'static str = "an enum `KillKind`";
+
+	#[inline]
+	fn serialize_unit_variant(
+		self,
+		name: &'static str,
+		_variant_index: u32,
+		variant: &'static str,
+	) -> Result<Self::Ok, Error> {
+		match variant {
+			"LiveQuery" => Ok(KillKind::LiveQuery),
+			"Session" => Ok(KillKind::Session),
+			"Query" => Ok(KillKind::Query),
+			variant => Err(Error::custom(format!("unexpected unit variant `{name}::{variant}`"))),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ser::Serializer as _;
+	use serde::Serialize;
+
+	#[test]
+	fn session() {
+		let kind = KillKind::Session;
+		let serialized = kind.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(kind, serialized);
+	}
+
+	#[test]
+	fn query() {
+		let kind = KillKind::Query;
+		let serialized = kind.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(kind, serialized);
+	}
+}
@@ -0,0 +1,103 @@
+pub(super) mod opt;
+
+use crate::err::Error;
+use crate::sql::value::serde::ser;
+use crate::sql::{Labels, Strand};
+use ser::Serializer as _;
+use serde::ser::Error as _;
+use serde::ser::Impossible;
+use serde::ser::Serialize;
+use std::collections::BTreeMap;
+
+#[non_exhaustive]
+pub struct Serializer;
+
+impl ser::Serializer for Serializer {
+	type Ok = Labels;
+	type Error = Error;
+
+	type SerializeSeq = Impossible<Labels, Error>;
+	type SerializeTuple = Impossible<Labels, Error>;
+	type SerializeTupleStruct = Impossible<Labels, Error>;
+	type SerializeTupleVariant = Impossible<Labels, Error>;
+	type SerializeMap = SerializeLabels;
+	type SerializeStruct = Impossible<Labels, Error>;
+	type SerializeStructVariant = Impossible<Labels, Error>;
+
+	const EXPECTED: &'static str = "a struct `Labels`";
+
+	fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+		Ok(SerializeLabels::default())
+	}
+
+	#[inline]
+	fn serialize_newtype_struct<T>(
+		self,
+		_name: &'static str,
+		value: &T,
+	) -> Result<Self::Ok, Self::Error>
+	where
+		T: ?Sized + Serialize,
+	{
+		value.serialize(self.wrap())
+	}
+}
+
+#[derive(Default)]
+#[non_exhaustive]
+pub struct SerializeLabels {
+	map: BTreeMap<String, Strand>,
+	next_key: Option<String>,
+}
+
+impl serde::ser::SerializeMap for SerializeLabels {
+	type Ok = Labels;
+	type Error = Error;
+
+	fn serialize_key<T>(&mut self, key: &T) -> Result<(), Self::Error>
+	where
+		T: Serialize + ?Sized,
+	{
+		self.next_key = Some(key.serialize(ser::string::Serializer.wrap())?);
+		Ok(())
+	}
+
+	fn serialize_value<T>(&mut self, value: &T) -> Result<(), Self::Error>
+	where
+		T: Serialize + ?Sized,
+	{
+		match self.next_key.take() {
+			Some(key) => {
+				let value = Strand(value.serialize(ser::string::Serializer.wrap())?);
+				self.map.insert(key, value);
+				Ok(())
+			}
+			None => Err(Error::custom("`serialize_value` called before `serialize_key`")),
+		}
+	}
+
+	fn end(self) -> Result<Self::Ok, Self::Error> {
+		Ok(Labels(self.map))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn default() {
+		let labels = Labels::default();
+		let serialized = labels.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(labels, serialized);
+	}
+
+	#[test]
+	fn labels() {
+		let labels = Labels(map! {
+			String::from("env") => Strand::from("production"),
+		});
+		let serialized = labels.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(labels, serialized);
+	}
+}
@@ -0,0 +1,67 @@
+pub(super) mod vec;
+
+use crate::err::Error;
+use crate::sql::statements::LiveAction;
+use crate::sql::value::serde::ser;
+use serde::ser::Error as _;
+use serde::ser::Impossible;
+
+pub(super) struct Serializer;
+
+impl ser::Serializer for Serializer {
+	type Ok = LiveAction;
+	type Error = Error;
+
+	type SerializeSeq = Impossible<LiveAction, Error>;
+	type SerializeTuple = Impossible<LiveAction, Error>;
+	type SerializeTupleStruct = Impossible<LiveAction, Error>;
+	type SerializeTupleVariant = Impossible<LiveAction, Error>;
+	type SerializeMap = Impossible<LiveAction, Error>;
+	type SerializeStruct = Impossible<LiveAction, Error>;
+	type SerializeStructVariant = Impossible<LiveAction, Error>;
+
+	const EXPECTED: &'static str = "an enum `LiveAction`";
+
+	#[inline]
+	fn serialize_unit_variant(
+		self,
+		name: &'static str,
+		_variant_index: u32,
+		variant: &'static str,
+	) -> Result<Self::Ok, Error> {
+		match variant {
+			"Create" => Ok(LiveAction::Create),
+			"Update" => Ok(LiveAction::Update),
+			"Delete" => Ok(LiveAction::Delete),
+			variant => Err(Error::custom(format!("unexpected unit variant `{name}::{variant}`"))),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ser::Serializer as _;
+	use serde::Serialize;
+
+	#[test]
+	fn create() {
+		let action = LiveAction::Create;
+		let serialized = action.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(action, serialized);
+	}
+
+	#[test]
+	fn update() {
+		let action = LiveAction::Update;
+		let serialized = action.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(action, serialized);
+	}
+
+	#[test]
+	fn delete() {
+		let action = LiveAction::Delete;
+		let serialized = action.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(action, serialized);
+	}
+}
@@ -0,0 +1,81 @@
+pub mod opt;
+
+use crate::err::Error;
+use crate::sql::statements::LiveAction;
+use crate::sql::value::serde::ser;
+use ser::Serializer as _;
+use serde::ser::Impossible;
+use serde::ser::Serialize;
+
+#[non_exhaustive]
+pub struct Serializer;
+
+impl ser::Serializer for Serializer {
+	type Ok = Vec<LiveAction>;
+	type Error = Error;
+
+	type SerializeSeq = SerializeLiveActionVec;
+	type SerializeTuple = Impossible<Vec<LiveAction>, Error>;
+	type SerializeTupleStruct = Impossible<Vec<LiveAction>, Error>;
+	type SerializeTupleVariant = Impossible<Vec<LiveAction>, Error>;
+	type SerializeMap = Impossible<Vec<LiveAction>, Error>;
+	type SerializeStruct = Impossible<Vec<LiveAction>, Error>;
+	type SerializeStructVariant = Impossible<Vec<LiveAction>, Error>;
+
+	const EXPECTED: &'static str = "a `Vec<LiveAction>`";
+
+	fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+		Ok(SerializeLiveActionVec(Vec::with_capacity(len.unwrap_or_default())))
+	}
+
+	#[inline]
+	fn serialize_newtype_struct<T>(
+		self,
+		_name: &'static str,
+		value: &T,
+	) -> Result<Self::Ok, Self::Error>
+	where
+		T: ?Sized + Serialize,
+	{
+		value.serialize(self.wrap())
+	}
+}
+
+#[non_exhaustive]
+pub struct SerializeLiveActionVec(Vec<LiveAction>);
+
+impl serde::ser::SerializeSeq for SerializeLiveActionVec {
+	type Ok = Vec<LiveAction>;
+	type Error = Error;
+
+	fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
+	where
+		T: Serialize + ?Sized,
+	{
+		self.0.push(value.serialize(super::Serializer.wrap())?);
+		Ok(())
+	}
+
+	fn end(self) -> Result<Self::Ok, Self::Error> {
+		Ok(self.0)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn empty() {
+		let vec: Vec<LiveAction> = Vec::new();
+		let serialized = vec.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(vec, serialized);
+	}
+
+	#[test]
+	fn vec() {
+		let vec = vec![LiveAction::Create];
+		let serialized = vec.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(vec, serialized);
+	}
+}
@@ -0,0 +1,56 @@
+use crate::err::Error;
+use crate::sql::statements::LiveAction;
+use crate::sql::value::serde::ser;
+use serde::ser::Impossible;
+use serde::ser::Serialize;
+
+#[non_exhaustive]
+pub struct Serializer;
+
+impl ser::Serializer for Serializer {
+	type Ok = Option<Vec<LiveAction>>;
+	type Error = Error;
+
+	type SerializeSeq = Impossible<Option<Vec<LiveAction>>, Error>;
+	type SerializeTuple = Impossible<Option<Vec<LiveAction>>, Error>;
+	type SerializeTupleStruct = Impossible<Option<Vec<LiveAction>>, Error>;
+	type SerializeTupleVariant = Impossible<Option<Vec<LiveAction>>, Error>;
+	type SerializeMap = Impossible<Option<Vec<LiveAction>>, Error>;
+	type SerializeStruct = Impossible<Option<Vec<LiveAction>>, Error>;
+	type SerializeStructVariant = Impossible<Option<Vec<LiveAction>>, Error>;
+
+	const EXPECTED: &'static str = "an `Option<Vec<LiveAction>>`";
+
+	#[inline]
+	fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+		Ok(None)
+	}
+
+	#[inline]
+	fn serialize_some<T>(self, value: &T) -> Result<Self::Ok, Self::Error>
+	where
+		T: ?Sized + Serialize,
+	{
+		Ok(Some(value.serialize(super::Serializer.wrap())?))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ser::Serializer as _;
+
+	#[test]
+	fn none() {
+		let option: Option<Vec<LiveAction>> = None;
+		let serialized = option.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(option, serialized);
+	}
+
+	#[test]
+	fn some() {
+		let option = Some(vec![LiveAction::Create]);
+		let serialized = option.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(option, serialized);
+	}
+}
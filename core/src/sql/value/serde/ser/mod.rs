@@ -4,7 +4,9 @@ mod block;
 mod cast;
 mod changefeed;
 mod cond;
+mod consistency;
 mod constant;
+mod crdt;
 mod data;
 mod datetime;
 mod decimal;
@@ -27,24 +29,34 @@ mod id;
 mod ident;
 mod idiom;
 mod index;
+mod isolation;
+mod kill_kind;
 mod kind;
+mod labels;
 mod language;
 mod limit;
+mod live_action;
 mod mock;
 mod number;
+mod on_delete;
 mod operator;
 mod order;
+mod outfile;
 mod output;
 mod part;
+mod partition;
 mod permission;
 mod permissions;
 mod primitive;
+mod priority;
 mod range;
 mod relation;
+mod sample;
 mod scoring;
 mod split;
 mod start;
 mod statement;
+mod statement_kind;
 mod strand;
 mod string;
 mod subquery;
@@ -53,11 +65,13 @@ mod table_type;
 mod thing;
 mod timeout;
 mod tokenizer;
+mod unknown_fields;
 mod uuid;
 mod value;
 mod vectortype;
 mod version;
 mod view;
+mod window;
 mod with;
 
 use serde::ser::Error;
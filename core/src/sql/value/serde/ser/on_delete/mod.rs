@@ -0,0 +1,57 @@
+use crate::err::Error;
+use crate::sql::value::serde::ser;
+use crate::sql::OnDelete;
+use serde::ser::Error as _;
+use serde::ser::Impossible;
+
+pub(super) struct Serializer;
+
+impl ser::Serializer for Serializer {
+	type Ok = OnDelete;
+	type Error = Error;
+
+	type SerializeSeq = Impossible<OnDelete, Error>;
+	type SerializeTuple = Impossible<OnDelete, Error>;
+	type SerializeTupleStruct = Impossible<OnDelete, Error>;
+	type SerializeTupleVariant = Impossible<OnDelete, Error>;
+	type SerializeMap = Impossible<OnDelete, Error>;
+	type SerializeStruct = Impossible<OnDelete, Error>;
+	type SerializeStructVariant = Impossible<OnDelete, Error>;
+
+	const EXPECTED: &'static str = "an enum `OnDelete`";
+
+	#[inline]
+	fn serialize_unit_variant(
+		self,
+		name: &'static str,
+		_variant_index: u32,
+		variant: &'static str,
+	) -> Result<Self::Ok, Error> {
+		match variant {
+			"Cascade" => Ok(OnDelete::Cascade),
+			"Reject" => Ok(OnDelete::Reject),
+			variant => Err(Error::custom(format!("unexpected unit variant `{name}::{variant}`"))),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ser::Serializer as _;
+	use serde::Serialize;
+
+	#[test]
+	fn cascade() {
+		let on_delete = OnDelete::Cascade;
+		let serialized = on_delete.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(on_delete, serialized);
+	}
+
+	#[test]
+	fn reject() {
+		let on_delete = OnDelete::Reject;
+		let serialized = on_delete.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(on_delete, serialized);
+	}
+}
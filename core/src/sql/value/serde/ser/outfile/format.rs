@@ -0,0 +1,49 @@
+use crate::err::Error;
+use crate::sql::value::serde::ser;
+use crate::sql::OutfileFormat;
+use serde::ser::Error as _;
+use serde::ser::Impossible;
+
+pub(super) struct Serializer;
+
+impl ser::Serializer for Serializer {
+	type Ok = OutfileFormat;
+	type Error = Error;
+
+	type SerializeSeq = Impossible<OutfileFormat, Error>;
+	type SerializeTuple = Impossible<OutfileFormat, Error>;
+	type SerializeTupleStruct = Impossible<OutfileFormat, Error>;
+	type SerializeTupleVariant = Impossible<OutfileFormat, Error>;
+	type SerializeMap = Impossible<OutfileFormat, Error>;
+	type SerializeStruct = Impossible<OutfileFormat, Error>;
+	type SerializeStructVariant = Impossible<OutfileFormat, Error>;
+
+	const EXPECTED: &'static str = "an enum `OutfileFormat`";
+
+	#[inline]
+	fn serialize_unit_variant(
+		self,
+		name: &'static str,
+		_variant_index: u32,
+		variant: &'static str,
+	) -> Result<Self::Ok, Error> {
+		match variant {
+			"Jsonl" => Ok(OutfileFormat::Jsonl),
+			variant => Err(Error::custom(format!("unexpected unit variant `{name}::{variant}`"))),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ser::Serializer as _;
+	use serde::Serialize;
+
+	#[test]
+	fn jsonl() {
+		let format = OutfileFormat::Jsonl;
+		let serialized = format.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(format, serialized);
+	}
+}
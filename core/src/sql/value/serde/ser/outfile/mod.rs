@@ -0,0 +1,98 @@
+pub(super) mod format;
+pub(super) mod opt;
+
+use crate::err::Error;
+use crate::sql::value::serde::ser;
+use crate::sql::Outfile;
+use crate::sql::OutfileFormat;
+use ser::Serializer as _;
+use serde::ser::Error as _;
+use serde::ser::Impossible;
+use serde::ser::Serialize;
+
+pub(super) struct Serializer;
+
+impl ser::Serializer for Serializer {
+	type Ok = Outfile;
+	type Error = Error;
+
+	type SerializeSeq = Impossible<Outfile, Error>;
+	type SerializeTuple = Impossible<Outfile, Error>;
+	type SerializeTupleStruct = Impossible<Outfile, Error>;
+	type SerializeTupleVariant = Impossible<Outfile, Error>;
+	type SerializeMap = Impossible<Outfile, Error>;
+	type SerializeStruct = SerializeOutfile;
+	type SerializeStructVariant = Impossible<Outfile, Error>;
+
+	const EXPECTED: &'static str = "a struct `Outfile`";
+
+	#[inline]
+	fn serialize_struct(
+		self,
+		_name: &'static str,
+		_len: usize,
+	) -> Result<Self::SerializeStruct, Error> {
+		Ok(SerializeOutfile::default())
+	}
+}
+
+#[derive(Default)]
+pub(super) struct SerializeOutfile {
+	path: Option<String>,
+	format: Option<OutfileFormat>,
+}
+
+impl serde::ser::SerializeStruct for SerializeOutfile {
+	type Ok = Outfile;
+	type Error = Error;
+
+	fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+	where
+		T: ?Sized + Serialize,
+	{
+		match key {
+			"path" => {
+				self.path = Some(value.serialize(ser::string::Serializer.wrap())?);
+			}
+			"format" => {
+				self.format = Some(value.serialize(format::Serializer.wrap())?);
+			}
+			key => {
+				return Err(Error::custom(format!("unexpected field `Outfile::{key}`")));
+			}
+		}
+		Ok(())
+	}
+
+	fn end(self) -> Result<Self::Ok, Error> {
+		match (self.path, self.format) {
+			(Some(path), Some(format)) => Ok(Outfile {
+				path,
+				format,
+			}),
+			_ => Err(Error::custom("`Outfile` missing required field(s)")),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn default() {
+		let outfile = Outfile::default();
+		let serialized = outfile.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(outfile, serialized);
+	}
+
+	#[test]
+	fn with_path() {
+		let outfile = Outfile {
+			path: "s3://bucket/path.jsonl".to_owned(),
+			format: OutfileFormat::Jsonl,
+		};
+		let serialized = outfile.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(outfile, serialized);
+	}
+}
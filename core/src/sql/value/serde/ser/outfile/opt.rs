@@ -0,0 +1,56 @@
+use crate::err::Error;
+use crate::sql::value::serde::ser;
+use crate::sql::Outfile;
+use serde::ser::Impossible;
+use serde::ser::Serialize;
+
+#[non_exhaustive]
+pub struct Serializer;
+
+impl ser::Serializer for Serializer {
+	type Ok = Option<Outfile>;
+	type Error = Error;
+
+	type SerializeSeq = Impossible<Option<Outfile>, Error>;
+	type SerializeTuple = Impossible<Option<Outfile>, Error>;
+	type SerializeTupleStruct = Impossible<Option<Outfile>, Error>;
+	type SerializeTupleVariant = Impossible<Option<Outfile>, Error>;
+	type SerializeMap = Impossible<Option<Outfile>, Error>;
+	type SerializeStruct = Impossible<Option<Outfile>, Error>;
+	type SerializeStructVariant = Impossible<Option<Outfile>, Error>;
+
+	const EXPECTED: &'static str = "an `Option<Outfile>`";
+
+	#[inline]
+	fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+		Ok(None)
+	}
+
+	#[inline]
+	fn serialize_some<T>(self, value: &T) -> Result<Self::Ok, Self::Error>
+	where
+		T: ?Sized + Serialize,
+	{
+		Ok(Some(value.serialize(super::Serializer.wrap())?))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ser::Serializer as _;
+
+	#[test]
+	fn none() {
+		let option: Option<Outfile> = None;
+		let serialized = option.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(option, serialized);
+	}
+
+	#[test]
+	fn some() {
+		let option = Some(Outfile::default());
+		let serialized = option.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(option, serialized);
+	}
+}
@@ -0,0 +1,95 @@
+pub(super) mod opt;
+
+use crate::err::Error;
+use crate::sql::value::serde::ser;
+use crate::sql::{Idiom, Partition};
+use ser::Serializer as _;
+use serde::ser::Error as _;
+use serde::ser::Impossible;
+use serde::ser::Serialize;
+
+#[non_exhaustive]
+pub struct Serializer;
+
+impl ser::Serializer for Serializer {
+	type Ok = Partition;
+	type Error = Error;
+
+	type SerializeSeq = Impossible<Partition, Error>;
+	type SerializeTuple = Impossible<Partition, Error>;
+	type SerializeTupleStruct = Impossible<Partition, Error>;
+	type SerializeTupleVariant = Impossible<Partition, Error>;
+	type SerializeMap = Impossible<Partition, Error>;
+	type SerializeStruct = SerializePartition;
+	type SerializeStructVariant = Impossible<Partition, Error>;
+
+	const EXPECTED: &'static str = "a struct `Partition`";
+
+	#[inline]
+	fn serialize_struct(
+		self,
+		_name: &'static str,
+		_len: usize,
+	) -> Result<Self::SerializeStruct, Error> {
+		Ok(SerializePartition::default())
+	}
+}
+
+#[derive(Default)]
+#[non_exhaustive]
+pub struct SerializePartition {
+	field: Idiom,
+	count: u16,
+}
+
+impl serde::ser::SerializeStruct for SerializePartition {
+	type Ok = Partition;
+	type Error = Error;
+
+	fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+	where
+		T: ?Sized + Serialize,
+	{
+		match key {
+			"field" => {
+				self.field = Idiom(value.serialize(ser::part::vec::Serializer.wrap())?);
+			}
+			"count" => {
+				self.count = value.serialize(ser::primitive::u16::Serializer.wrap())?;
+			}
+			key => {
+				return Err(Error::custom(format!("unexpected field `Partition::{key}`")));
+			}
+		}
+		Ok(())
+	}
+
+	fn end(self) -> Result<Self::Ok, Error> {
+		Ok(Partition {
+			field: self.field,
+			count: self.count,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn default() {
+		let partition = Partition::default();
+		let value: Partition = partition.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(value, partition);
+	}
+
+	#[test]
+	fn with_field() {
+		let partition = Partition {
+			field: Idiom::from("tenant"),
+			count: 16,
+		};
+		let value: Partition = partition.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(value, partition);
+	}
+}
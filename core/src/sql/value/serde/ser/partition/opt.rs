@@ -0,0 +1,56 @@
+use crate::err::Error;
+use crate::sql::value::serde::ser;
+use crate::sql::Partition;
+use serde::ser::Impossible;
+use serde::ser::Serialize;
+
+#[non_exhaustive]
+pub struct Serializer;
+
+impl ser::Serializer for Serializer {
+	type Ok = Option<Partition>;
+	type Error = Error;
+
+	type SerializeSeq = Impossible<Option<Partition>, Error>;
+	type SerializeTuple = Impossible<Option<Partition>, Error>;
+	type SerializeTupleStruct = Impossible<Option<Partition>, Error>;
+	type SerializeTupleVariant = Impossible<Option<Partition>, Error>;
+	type SerializeMap = Impossible<Option<Partition>, Error>;
+	type SerializeStruct = Impossible<Option<Partition>, Error>;
+	type SerializeStructVariant = Impossible<Option<Partition>, Error>;
+
+	const EXPECTED: &'static str = "an `Option<Partition>`";
+
+	#[inline]
+	fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+		Ok(None)
+	}
+
+	#[inline]
+	fn serialize_some<T>(self, value: &T) -> Result<Self::Ok, Self::Error>
+	where
+		T: ?Sized + Serialize,
+	{
+		Ok(Some(value.serialize(super::Serializer.wrap())?))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ser::Serializer as _;
+
+	#[test]
+	fn none() {
+		let option: Option<Partition> = None;
+		let serialized = option.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(option, serialized);
+	}
+
+	#[test]
+	fn some() {
+		let option = Some(Partition::default());
+		let serialized = option.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(option, serialized);
+	}
+}
@@ -0,0 +1,58 @@
+use crate::err::Error;
+use crate::sql::value::serde::ser;
+use crate::sql::Priority;
+use serde::ser::Error as _;
+use serde::ser::Impossible;
+
+pub(super) struct Serializer;
+
+impl ser::Serializer for Serializer {
+	type Ok = Priority;
+	type Error = Error;
+
+	type SerializeSeq = Impossible<Priority, Error>;
+	type SerializeTuple = Impossible<Priority, Error>;
+	type SerializeTupleStruct = Impossible<Priority, Error>;
+	type SerializeTupleVariant = Impossible<Priority, Error>;
+	type SerializeMap = Impossible<Priority, Error>;
+	type SerializeStruct = Impossible<Priority, Error>;
+	type SerializeStructVariant = Impossible<Priority, Error>;
+
+	const EXPECTED: &'static str = "an enum `Priority`";
+
+	#[inline]
+	fn serialize_unit_variant(
+		self,
+		name: &'static str,
+		_variant_index: u32,
+		variant: &'static str,
+	) -> Result<Self::Ok, Error> {
+		match variant {
+			"Low" => Ok(Priority::Low),
+			"Normal" => Ok(Priority::Normal),
+			"High" => Ok(Priority::High),
+			variant => Err(Error::custom(format!("unexpected unit variant `{name}::{variant}`"))),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ser::Serializer as _;
+	use serde::Serialize;
+
+	#[test]
+	fn low() {
+		let priority = Priority::Low;
+		let serialized = priority.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(priority, serialized);
+	}
+
+	#[test]
+	fn high() {
+		let priority = Priority::High;
+		let serialized = priority.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(priority, serialized);
+	}
+}
@@ -1,6 +1,7 @@
 use crate::err::Error;
 use crate::sql::value::serde::ser;
 use crate::sql::Kind;
+use crate::sql::OnDelete;
 use crate::sql::Relation;
 use ser::Serializer as _;
 use serde::ser::Error as _;
@@ -39,6 +40,7 @@ impl ser::Serializer for Serializer {
 pub struct SerializeRelation {
 	from: Option<Kind>,
 	to: Option<Kind>,
+	on_delete: OnDelete,
 }
 
 impl serde::ser::SerializeStruct for SerializeRelation {
@@ -56,6 +58,9 @@ impl serde::ser::SerializeStruct for SerializeRelation {
 			"to" => {
 				self.to = value.serialize(ser::kind::opt::Serializer.wrap())?;
 			}
+			"on_delete" => {
+				self.on_delete = value.serialize(ser::on_delete::Serializer.wrap())?;
+			}
 			key => {
 				return Err(Error::custom(format!("unexpected field `Relation::{key}`")));
 			}
@@ -67,6 +72,7 @@ impl serde::ser::SerializeStruct for SerializeRelation {
 		Ok(Relation {
 			from: self.from,
 			to: self.to,
+			on_delete: self.on_delete,
 		})
 	}
 }
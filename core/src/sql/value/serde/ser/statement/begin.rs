@@ -1,8 +1,11 @@
 use crate::err::Error;
 use crate::sql::statements::BeginStatement;
 use crate::sql::value::serde::ser;
+use crate::sql::Isolation;
+use ser::Serializer as _;
 use serde::ser::Error as _;
 use serde::ser::Impossible;
+use serde::ser::Serialize;
 
 #[non_exhaustive]
 pub struct Serializer;
@@ -16,29 +19,69 @@ impl ser::Serializer for Serializer {
 	type SerializeTupleStruct = Impossible<BeginStatement, Error>;
 	type SerializeTupleVariant = Impossible<BeginStatement, Error>;
 	type SerializeMap = Impossible<BeginStatement, Error>;
-	type SerializeStruct = Impossible<BeginStatement, Error>;
+	type SerializeStruct = SerializeBeginStatement;
 	type SerializeStructVariant = Impossible<BeginStatement, Error>;
 
-	const EXPECTED: &'static str = "a unit struct `BeginStatement`";
+	const EXPECTED: &'static str = "a struct `BeginStatement`";
 
 	#[inline]
-	fn serialize_unit_struct(self, name: &'static str) -> Result<Self::Ok, Error> {
-		match name {
-			"BeginStatement" => Ok(BeginStatement),
-			name => Err(Error::custom(format!("unexpected unit struct `{name}`"))),
+	fn serialize_struct(
+		self,
+		_name: &'static str,
+		_len: usize,
+	) -> Result<Self::SerializeStruct, Error> {
+		Ok(SerializeBeginStatement::default())
+	}
+}
+
+#[derive(Default)]
+#[non_exhaustive]
+pub struct SerializeBeginStatement {
+	isolation: Option<Isolation>,
+}
+
+impl serde::ser::SerializeStruct for SerializeBeginStatement {
+	type Ok = BeginStatement;
+	type Error = Error;
+
+	fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+	where
+		T: ?Sized + Serialize,
+	{
+		match key {
+			"isolation" => {
+				self.isolation = value.serialize(ser::isolation::opt::Serializer.wrap())?;
+			}
+			key => {
+				return Err(Error::custom(format!("unexpected field `BeginStatement::{key}`")));
+			}
 		}
+		Ok(())
+	}
+
+	fn end(self) -> Result<Self::Ok, Error> {
+		Ok(BeginStatement {
+			isolation: self.isolation,
+		})
 	}
 }
 
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use ser::Serializer as _;
-	use serde::Serialize;
 
 	#[test]
 	fn default() {
-		let stmt = BeginStatement;
+		let stmt = BeginStatement::default();
+		let value: BeginStatement = stmt.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(value, stmt);
+	}
+
+	#[test]
+	fn with_isolation() {
+		let stmt = BeginStatement {
+			isolation: Some(Isolation::Serializable),
+		};
 		let value: BeginStatement = stmt.serialize(Serializer.wrap()).unwrap();
 		assert_eq!(value, stmt);
 	}
@@ -3,6 +3,7 @@ use crate::sql::changefeed::ChangeFeed;
 use crate::sql::statements::DefineDatabaseStatement;
 use crate::sql::value::serde::ser;
 use crate::sql::Ident;
+use crate::sql::Labels;
 use crate::sql::Strand;
 use ser::Serializer as _;
 use serde::ser::Error as _;
@@ -43,6 +44,7 @@ pub struct SerializeDefineDatabaseStatement {
 	changefeed: Option<ChangeFeed>,
 	id: Option<u32>,
 	comment: Option<Strand>,
+	labels: Option<Labels>,
 	if_not_exists: bool,
 }
 
@@ -67,6 +69,9 @@ impl serde::ser::SerializeStruct for SerializeDefineDatabaseStatement {
 			"comment" => {
 				self.comment = value.serialize(ser::strand::opt::Serializer.wrap())?;
 			}
+			"labels" => {
+				self.labels = value.serialize(ser::labels::opt::Serializer.wrap())?;
+			}
 			"if_not_exists" => {
 				self.if_not_exists = value.serialize(ser::primitive::bool::Serializer.wrap())?
 			}
@@ -85,6 +90,7 @@ impl serde::ser::SerializeStruct for SerializeDefineDatabaseStatement {
 			changefeed: self.changefeed,
 			id: self.id,
 			comment: self.comment,
+			labels: self.labels,
 			if_not_exists: self.if_not_exists,
 		})
 	}
@@ -1,6 +1,7 @@
 use crate::err::Error;
 use crate::sql::statements::DefineFieldStatement;
 use crate::sql::value::serde::ser;
+use crate::sql::Crdt;
 use crate::sql::Ident;
 use crate::sql::Idiom;
 use crate::sql::Kind;
@@ -53,6 +54,9 @@ pub struct SerializeDefineFieldStatement {
 	permissions: Permissions,
 	comment: Option<Strand>,
 	if_not_exists: bool,
+	expire: bool,
+	crdt: Option<Crdt>,
+	computed: bool,
 }
 
 impl serde::ser::SerializeStruct for SerializeDefineFieldStatement {
@@ -97,6 +101,15 @@ impl serde::ser::SerializeStruct for SerializeDefineFieldStatement {
 			"if_not_exists" => {
 				self.if_not_exists = value.serialize(ser::primitive::bool::Serializer.wrap())?
 			}
+			"expire" => {
+				self.expire = value.serialize(ser::primitive::bool::Serializer.wrap())?;
+			}
+			"crdt" => {
+				self.crdt = value.serialize(ser::crdt::opt::Serializer.wrap())?;
+			}
+			"computed" => {
+				self.computed = value.serialize(ser::primitive::bool::Serializer.wrap())?;
+			}
 			key => {
 				return Err(Error::custom(format!(
 					"unexpected field `DefineFieldStatement::{key}`"
@@ -119,6 +132,9 @@ impl serde::ser::SerializeStruct for SerializeDefineFieldStatement {
 			permissions: self.permissions,
 			comment: self.comment,
 			if_not_exists: self.if_not_exists,
+			expire: self.expire,
+			crdt: self.crdt,
+			computed: self.computed,
 		})
 	}
 }
@@ -2,6 +2,7 @@ use crate::err::Error;
 use crate::sql::statements::DefineNamespaceStatement;
 use crate::sql::value::serde::ser;
 use crate::sql::Ident;
+use crate::sql::Labels;
 use crate::sql::Strand;
 use ser::Serializer as _;
 use serde::ser::Error as _;
@@ -41,6 +42,7 @@ pub struct SerializeDefineNamespaceStatement {
 	name: Ident,
 	id: Option<u32>,
 	comment: Option<Strand>,
+	labels: Option<Labels>,
 	if_not_exists: bool,
 }
 
@@ -62,6 +64,9 @@ impl serde::ser::SerializeStruct for SerializeDefineNamespaceStatement {
 			"comment" => {
 				self.comment = value.serialize(ser::strand::opt::Serializer.wrap())?;
 			}
+			"labels" => {
+				self.labels = value.serialize(ser::labels::opt::Serializer.wrap())?;
+			}
 			"if_not_exists" => {
 				self.if_not_exists = value.serialize(ser::primitive::bool::Serializer.wrap())?
 			}
@@ -79,6 +84,7 @@ impl serde::ser::SerializeStruct for SerializeDefineNamespaceStatement {
 			name: self.name,
 			id: self.id,
 			comment: self.comment,
+			labels: self.labels,
 			if_not_exists: self.if_not_exists,
 		})
 	}
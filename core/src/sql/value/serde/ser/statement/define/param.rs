@@ -2,6 +2,7 @@ use crate::err::Error;
 use crate::sql::statements::DefineParamStatement;
 use crate::sql::value::serde::ser;
 use crate::sql::Ident;
+use crate::sql::Kind;
 use crate::sql::Permission;
 use crate::sql::Strand;
 use crate::sql::Value;
@@ -45,6 +46,7 @@ pub struct SerializeDefineParamStatement {
 	comment: Option<Strand>,
 	permissions: Permission,
 	if_not_exists: bool,
+	kind: Option<Kind>,
 }
 
 impl serde::ser::SerializeStruct for SerializeDefineParamStatement {
@@ -71,6 +73,9 @@ impl serde::ser::SerializeStruct for SerializeDefineParamStatement {
 			"if_not_exists" => {
 				self.if_not_exists = value.serialize(ser::primitive::bool::Serializer.wrap())?
 			}
+			"kind" => {
+				self.kind = value.serialize(ser::kind::opt::Serializer.wrap())?;
+			}
 			key => {
 				return Err(Error::custom(format!(
 					"unexpected field `DefineParamStatement::{key}`"
@@ -87,6 +92,7 @@ impl serde::ser::SerializeStruct for SerializeDefineParamStatement {
 			comment: self.comment,
 			permissions: self.permissions,
 			if_not_exists: self.if_not_exists,
+			kind: self.kind,
 		})
 	}
 }
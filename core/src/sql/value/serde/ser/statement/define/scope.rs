@@ -3,6 +3,7 @@ use crate::sql::statements::DefineScopeStatement;
 use crate::sql::value::serde::ser;
 use crate::sql::Duration;
 use crate::sql::Ident;
+use crate::sql::StatementKind;
 use crate::sql::Strand;
 use crate::sql::Value;
 use ser::Serializer as _;
@@ -47,6 +48,8 @@ pub struct SerializeDefineScopeStatement {
 	signin: Option<Value>,
 	comment: Option<Strand>,
 	if_not_exists: bool,
+	allow: Vec<StatementKind>,
+	deny: Vec<StatementKind>,
 }
 
 impl serde::ser::SerializeStruct for SerializeDefineScopeStatement {
@@ -80,6 +83,12 @@ impl serde::ser::SerializeStruct for SerializeDefineScopeStatement {
 			"if_not_exists" => {
 				self.if_not_exists = value.serialize(ser::primitive::bool::Serializer.wrap())?
 			}
+			"allow" => {
+				self.allow = value.serialize(ser::statement_kind::vec::Serializer.wrap())?;
+			}
+			"deny" => {
+				self.deny = value.serialize(ser::statement_kind::vec::Serializer.wrap())?;
+			}
 			key => {
 				return Err(Error::custom(format!(
 					"unexpected field `DefineScopeStatement::{key}`"
@@ -98,6 +107,8 @@ impl serde::ser::SerializeStruct for SerializeDefineScopeStatement {
 			signin: self.signin,
 			comment: self.comment,
 			if_not_exists: self.if_not_exists,
+			allow: self.allow,
+			deny: self.deny,
 		})
 	}
 }
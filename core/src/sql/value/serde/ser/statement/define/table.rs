@@ -3,9 +3,12 @@ use crate::sql::changefeed::ChangeFeed;
 use crate::sql::statements::DefineTableStatement;
 use crate::sql::value::serde::ser;
 use crate::sql::Ident;
+use crate::sql::Labels;
+use crate::sql::Partition;
 use crate::sql::Permissions;
 use crate::sql::Strand;
 use crate::sql::TableType;
+use crate::sql::UnknownFields;
 use crate::sql::View;
 use ser::Serializer as _;
 use serde::ser::Error as _;
@@ -50,8 +53,12 @@ pub struct SerializeDefineTableStatement {
 	permissions: Permissions,
 	changefeed: Option<ChangeFeed>,
 	comment: Option<Strand>,
+	labels: Option<Labels>,
+	partition: Option<Partition>,
 	if_not_exists: bool,
 	kind: TableType,
+	audit: bool,
+	unknown: UnknownFields,
 }
 
 impl serde::ser::SerializeStruct for SerializeDefineTableStatement {
@@ -87,12 +94,24 @@ impl serde::ser::SerializeStruct for SerializeDefineTableStatement {
 			"comment" => {
 				self.comment = value.serialize(ser::strand::opt::Serializer.wrap())?;
 			}
+			"labels" => {
+				self.labels = value.serialize(ser::labels::opt::Serializer.wrap())?;
+			}
+			"partition" => {
+				self.partition = value.serialize(ser::partition::opt::Serializer.wrap())?;
+			}
 			"kind" => {
 				self.kind = value.serialize(ser::table_type::Serializer.wrap())?;
 			}
 			"if_not_exists" => {
 				self.if_not_exists = value.serialize(ser::primitive::bool::Serializer.wrap())?
 			}
+			"audit" => {
+				self.audit = value.serialize(ser::primitive::bool::Serializer.wrap())?;
+			}
+			"unknown" => {
+				self.unknown = value.serialize(ser::unknown_fields::Serializer.wrap())?;
+			}
 			key => {
 				return Err(Error::custom(format!(
 					"unexpected field `DefineTableStatement::{key}`"
@@ -112,8 +131,12 @@ impl serde::ser::SerializeStruct for SerializeDefineTableStatement {
 			permissions: self.permissions,
 			changefeed: self.changefeed,
 			comment: self.comment,
+			labels: self.labels,
+			partition: self.partition,
 			kind: self.kind,
 			if_not_exists: self.if_not_exists,
+			audit: self.audit,
+			unknown: self.unknown,
 		})
 	}
 }
@@ -128,4 +151,15 @@ mod tests {
 		let value: DefineTableStatement = stmt.serialize(Serializer.wrap()).unwrap();
 		assert_eq!(value, stmt);
 	}
+
+	#[test]
+	fn with_unknown() {
+		let stmt = DefineTableStatement {
+			full: true,
+			unknown: UnknownFields::Reject,
+			..Default::default()
+		};
+		let value: DefineTableStatement = stmt.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(value, stmt);
+	}
 }
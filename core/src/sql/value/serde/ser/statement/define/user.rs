@@ -2,7 +2,9 @@ use crate::err::Error;
 use crate::sql::statements::DefineUserStatement;
 use crate::sql::value::serde::ser;
 use crate::sql::Base;
+use crate::sql::Datetime;
 use crate::sql::Ident;
+use crate::sql::Priority;
 use crate::sql::Strand;
 use ser::Serializer as _;
 use serde::ser::Error as _;
@@ -46,6 +48,9 @@ pub struct SerializeDefineUserStatement {
 	roles: Vec<Ident>,
 	comment: Option<Strand>,
 	if_not_exists: bool,
+	must_change: bool,
+	pass_changed: Option<Datetime>,
+	priority: Priority,
 }
 
 impl serde::ser::SerializeStruct for SerializeDefineUserStatement {
@@ -78,6 +83,15 @@ impl serde::ser::SerializeStruct for SerializeDefineUserStatement {
 			"if_not_exists" => {
 				self.if_not_exists = value.serialize(ser::primitive::bool::Serializer.wrap())?
 			}
+			"must_change" => {
+				self.must_change = value.serialize(ser::primitive::bool::Serializer.wrap())?
+			}
+			"pass_changed" => {
+				self.pass_changed = value.serialize(ser::datetime::opt::Serializer.wrap())?;
+			}
+			"priority" => {
+				self.priority = value.serialize(ser::priority::Serializer.wrap())?;
+			}
 			key => {
 				return Err(Error::custom(format!(
 					"unexpected field `DefineUserStatement::{key}`"
@@ -96,6 +110,9 @@ impl serde::ser::SerializeStruct for SerializeDefineUserStatement {
 			roles: self.roles,
 			comment: self.comment,
 			if_not_exists: self.if_not_exists,
+			must_change: self.must_change,
+			pass_changed: self.pass_changed,
+			priority: self.priority,
 		})
 	}
 }
@@ -25,6 +25,19 @@ impl ser::Serializer for Serializer {
 
 	const EXPECTED: &'static str = "an enum `InfoStatement`";
 
+	#[inline]
+	fn serialize_unit_variant(
+		self,
+		name: &'static str,
+		_variant_index: u32,
+		variant: &'static str,
+	) -> Result<Self::Ok, Error> {
+		match variant {
+			"Advisor" => Ok(InfoStatement::Advisor),
+			variant => Err(Error::custom(format!("unexpected unit variant `{name}::{variant}`"))),
+		}
+	}
+
 	#[inline]
 	fn serialize_newtype_variant<T>(
 		self,
@@ -46,6 +59,9 @@ impl ser::Serializer for Serializer {
 			"Db" => {
 				Ok(InfoStatement::Db(value.serialize(ser::primitive::bool::Serializer.wrap())?))
 			}
+			"History" => {
+				Ok(InfoStatement::History(Ident(value.serialize(ser::string::Serializer.wrap())?)))
+			}
 			variant => {
 				Err(Error::custom(format!("unexpected newtype variant `{name}::{variant}`")))
 			}
@@ -95,7 +111,7 @@ impl Display for Which {
 pub(super) struct SerializeInfoStatement {
 	index: usize,
 	which: Which,
-	tuple: (Option<Ident>, Option<Base>, bool),
+	tuple: (Option<Ident>, Option<Base>, bool, bool),
 }
 
 impl SerializeInfoStatement {
@@ -103,7 +119,7 @@ impl SerializeInfoStatement {
 		Self {
 			index: 0,
 			which,
-			tuple: (None, None, false),
+			tuple: (None, None, false, false),
 		}
 	}
 }
@@ -124,6 +140,9 @@ impl serde::ser::SerializeTupleVariant for SerializeInfoStatement {
 			(Sc, 1) | (Tb, 1) => {
 				self.tuple.2 = value.serialize(ser::primitive::bool::Serializer.wrap())?;
 			}
+			(Tb, 2) => {
+				self.tuple.3 = value.serialize(ser::primitive::bool::Serializer.wrap())?;
+			}
 			(User, 1) => {
 				self.tuple.1 = value.serialize(ser::base::opt::Serializer.wrap())?;
 			}
@@ -146,7 +165,7 @@ impl serde::ser::SerializeTupleVariant for SerializeInfoStatement {
 		match (self.which, self.tuple.0) {
 			(Sc, Some(ident)) => Ok(InfoStatement::Sc(ident, self.tuple.2)),
 			(Sc, None) => Err(Error::custom("`InfoStatement::Sc` missing required value(s)")),
-			(Tb, Some(ident)) => Ok(InfoStatement::Tb(ident, self.tuple.2)),
+			(Tb, Some(ident)) => Ok(InfoStatement::Tb(ident, self.tuple.2, self.tuple.3)),
 			(Tb, None) => Err(Error::custom("`InfoStatement::Tb` missing required value(s)")),
 			(User, Some(ident)) => Ok(InfoStatement::User(ident, self.tuple.1, self.tuple.2)),
 			(User, None) => Err(Error::custom("`InfoStatement::User` missing required value(s)")),
@@ -188,7 +207,14 @@ mod tests {
 
 	#[test]
 	fn tb() {
-		let stmt = InfoStatement::Tb(Default::default(), Default::default());
+		let stmt = InfoStatement::Tb(Default::default(), Default::default(), Default::default());
+		let serialized = stmt.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(stmt, serialized);
+	}
+
+	#[test]
+	fn tb_stats() {
+		let stmt = InfoStatement::Tb(Default::default(), true, true);
 		let serialized = stmt.serialize(Serializer.wrap()).unwrap();
 		assert_eq!(stmt, serialized);
 	}
@@ -199,4 +225,18 @@ mod tests {
 		let serialized = stmt.serialize(Serializer.wrap()).unwrap();
 		assert_eq!(stmt, serialized);
 	}
+
+	#[test]
+	fn advisor() {
+		let stmt = InfoStatement::Advisor;
+		let serialized = stmt.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(stmt, serialized);
+	}
+
+	#[test]
+	fn history() {
+		let stmt = InfoStatement::History(Default::default());
+		let serialized = stmt.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(stmt, serialized);
+	}
 }
@@ -1,5 +1,5 @@
 use crate::err::Error;
-use crate::sql::statements::KillStatement;
+use crate::sql::statements::{KillKind, KillStatement};
 use crate::sql::value::serde::ser;
 use crate::sql::Value;
 use ser::Serializer as _;
@@ -38,6 +38,7 @@ impl ser::Serializer for Serializer {
 #[non_exhaustive]
 pub struct SerializeKillStatement {
 	id: Option<Value>,
+	kind: KillKind,
 }
 
 impl serde::ser::SerializeStruct for SerializeKillStatement {
@@ -52,6 +53,9 @@ impl serde::ser::SerializeStruct for SerializeKillStatement {
 			"id" => {
 				self.id = Some(value.serialize(ser::value::Serializer.wrap())?);
 			}
+			"kind" => {
+				self.kind = value.serialize(ser::kill_kind::Serializer.wrap())?;
+			}
 			key => {
 				return Err(Error::custom(format!("unexpected field `KillStatement::{key}`")));
 			}
@@ -63,6 +67,7 @@ impl serde::ser::SerializeStruct for SerializeKillStatement {
 		match self.id {
 			Some(id) => Ok(KillStatement {
 				id,
+				kind: self.kind,
 			}),
 			None => Err(Error::custom("`KillStatement` missing required field")),
 		}
@@ -79,4 +84,14 @@ mod tests {
 		let value: KillStatement = stmt.serialize(Serializer.wrap()).unwrap();
 		assert_eq!(value, stmt);
 	}
+
+	#[test]
+	fn with_kind() {
+		let stmt = KillStatement {
+			kind: KillKind::Query,
+			..Default::default()
+		};
+		let value: KillStatement = stmt.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(value, stmt);
+	}
 }
@@ -1,12 +1,14 @@
 use crate::err::Error;
 use crate::iam::Auth;
-use crate::sql::statements::LiveStatement;
+use crate::sql::statements::{LiveAction, LiveStatement};
 use crate::sql::value::serde::ser;
 use crate::sql::Cond;
+use crate::sql::Duration;
 use crate::sql::Fetchs;
 use crate::sql::Fields;
 use crate::sql::Uuid;
 use crate::sql::Value;
+use crate::sql::Window;
 use ser::Serializer as _;
 use serde::ser::Error as _;
 use serde::ser::Impossible;
@@ -51,6 +53,10 @@ pub struct SerializeLiveStatement {
 	archived: Option<Uuid>,
 	session: Option<Value>,
 	auth: Option<Auth>,
+	window: Option<Window>,
+	when: Option<Vec<LiveAction>>,
+	compact: Option<Duration>,
+	count: bool,
 }
 
 impl serde::ser::SerializeStruct for SerializeLiveStatement {
@@ -89,6 +95,19 @@ impl serde::ser::SerializeStruct for SerializeLiveStatement {
 			"auth" => {
 				self.auth = None;
 			}
+			"window" => {
+				self.window = value.serialize(ser::window::opt::Serializer.wrap())?;
+			}
+			"when" => {
+				self.when = value.serialize(ser::live_action::vec::opt::Serializer.wrap())?;
+			}
+			"compact" => {
+				self.compact =
+					value.serialize(ser::duration::opt::Serializer.wrap())?.map(Into::into);
+			}
+			"count" => {
+				self.count = value.serialize(ser::primitive::bool::Serializer.wrap())?;
+			}
 			key => {
 				return Err(Error::custom(format!("unexpected field `LiveStatement::{key}`")));
 			}
@@ -107,6 +126,10 @@ impl serde::ser::SerializeStruct for SerializeLiveStatement {
 			archived: self.archived,
 			session: None,
 			auth: None,
+			window: self.window,
+			when: self.when,
+			compact: self.compact,
+			count: self.count,
 		})
 	}
 }
@@ -121,4 +144,47 @@ mod tests {
 		let value: LiveStatement = stmt.serialize(Serializer.wrap()).unwrap();
 		assert_eq!(value, stmt);
 	}
+
+	#[test]
+	fn with_window() {
+		let stmt = LiveStatement {
+			window: Some(Window {
+				size: std::time::Duration::from_secs(60),
+				every: None,
+			}),
+			..Default::default()
+		};
+		let value: LiveStatement = stmt.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(value, stmt);
+	}
+
+	#[test]
+	fn with_when() {
+		let stmt = LiveStatement {
+			when: Some(vec![LiveAction::Create, LiveAction::Delete]),
+			..Default::default()
+		};
+		let value: LiveStatement = stmt.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(value, stmt);
+	}
+
+	#[test]
+	fn with_compact() {
+		let stmt = LiveStatement {
+			compact: Some(Duration::from_millis(50)),
+			..Default::default()
+		};
+		let value: LiveStatement = stmt.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(value, stmt);
+	}
+
+	#[test]
+	fn with_count() {
+		let stmt = LiveStatement {
+			count: true,
+			..Default::default()
+		};
+		let value: LiveStatement = stmt.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(value, stmt);
+	}
 }
@@ -4,12 +4,16 @@ use crate::sql::statements::SelectStatement;
 use crate::sql::value::serde::ser;
 use crate::sql::with::With;
 use crate::sql::Cond;
+use crate::sql::Consistency;
+use crate::sql::Duration;
 use crate::sql::Fetchs;
 use crate::sql::Fields;
 use crate::sql::Groups;
 use crate::sql::Idioms;
 use crate::sql::Limit;
 use crate::sql::Orders;
+use crate::sql::Outfile;
+use crate::sql::Sample;
 use crate::sql::Splits;
 use crate::sql::Start;
 use crate::sql::Timeout;
@@ -59,6 +63,7 @@ pub struct SerializeSelectStatement {
 	split: Option<Splits>,
 	group: Option<Groups>,
 	order: Option<Orders>,
+	sample: Option<Sample>,
 	limit: Option<Limit>,
 	start: Option<Start>,
 	fetch: Option<Fetchs>,
@@ -66,6 +71,10 @@ pub struct SerializeSelectStatement {
 	timeout: Option<Timeout>,
 	parallel: Option<bool>,
 	explain: Option<Explain>,
+	outfile: Option<Outfile>,
+	lock: Option<bool>,
+	cache: Option<Duration>,
+	consistency: Option<Consistency>,
 }
 
 impl serde::ser::SerializeStruct for SerializeSelectStatement {
@@ -104,6 +113,9 @@ impl serde::ser::SerializeStruct for SerializeSelectStatement {
 			"order" => {
 				self.order = value.serialize(ser::order::vec::opt::Serializer.wrap())?.map(Orders);
 			}
+			"sample" => {
+				self.sample = value.serialize(ser::sample::opt::Serializer.wrap())?;
+			}
 			"limit" => {
 				self.limit = value.serialize(ser::limit::opt::Serializer.wrap())?;
 			}
@@ -125,6 +137,19 @@ impl serde::ser::SerializeStruct for SerializeSelectStatement {
 			"explain" => {
 				self.explain = value.serialize(ser::explain::opt::Serializer.wrap())?;
 			}
+			"outfile" => {
+				self.outfile = value.serialize(ser::outfile::opt::Serializer.wrap())?;
+			}
+			"lock" => {
+				self.lock = Some(value.serialize(ser::primitive::bool::Serializer.wrap())?);
+			}
+			"cache" => {
+				self.cache =
+					value.serialize(ser::duration::opt::Serializer.wrap())?.map(Into::into);
+			}
+			"consistency" => {
+				self.consistency = value.serialize(ser::consistency::opt::Serializer.wrap())?;
+			}
 			key => {
 				return Err(Error::custom(format!("unexpected field `SelectStatement::{key}`")));
 			}
@@ -146,11 +171,16 @@ impl serde::ser::SerializeStruct for SerializeSelectStatement {
 				split: self.split,
 				group: self.group,
 				order: self.order,
+				sample: self.sample,
 				limit: self.limit,
 				start: self.start,
 				fetch: self.fetch,
 				version: self.version,
 				timeout: self.timeout,
+				outfile: self.outfile,
+				lock: self.lock.is_some_and(|v| v),
+				cache: self.cache,
+				consistency: self.consistency,
 			}),
 			_ => Err(Error::custom("`SelectStatement` missing required field(s)")),
 		}
@@ -208,6 +238,16 @@ mod tests {
 		assert_eq!(value, stmt);
 	}
 
+	#[test]
+	fn with_sample() {
+		let stmt = SelectStatement {
+			sample: Some(Default::default()),
+			..Default::default()
+		};
+		let value: SelectStatement = stmt.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(value, stmt);
+	}
+
 	#[test]
 	fn with_limit() {
 		let stmt = SelectStatement {
@@ -278,6 +318,46 @@ mod tests {
 		assert_eq!(value, stmt);
 	}
 
+	#[test]
+	fn with_outfile() {
+		let stmt = SelectStatement {
+			outfile: Some(Default::default()),
+			..Default::default()
+		};
+		let value: SelectStatement = stmt.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(value, stmt);
+	}
+
+	#[test]
+	fn with_lock() {
+		let stmt = SelectStatement {
+			lock: true,
+			..Default::default()
+		};
+		let value: SelectStatement = stmt.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(value, stmt);
+	}
+
+	#[test]
+	fn with_consistency() {
+		let stmt = SelectStatement {
+			consistency: Some(Consistency::Eventual),
+			..Default::default()
+		};
+		let value: SelectStatement = stmt.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(value, stmt);
+	}
+
+	#[test]
+	fn with_cache() {
+		let stmt = SelectStatement {
+			cache: Some(Duration::from_secs(30)),
+			..Default::default()
+		};
+		let value: SelectStatement = stmt.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(value, stmt);
+	}
+
 	#[test]
 	fn with_with_noindex() {
 		let stmt = SelectStatement {
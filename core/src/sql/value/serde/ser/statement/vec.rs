@@ -73,7 +73,7 @@ mod tests {
 
 	#[test]
 	fn vec() {
-		let vec = vec![Statement::Begin(BeginStatement)];
+		let vec = vec![Statement::Begin(BeginStatement::default())];
 		let serialized = vec.serialize(Serializer.wrap()).unwrap();
 		assert_eq!(vec, serialized);
 	}
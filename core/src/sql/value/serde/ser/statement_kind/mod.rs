@@ -0,0 +1,87 @@
+pub(super) mod vec;
+
+use crate::err::Error;
+use crate::sql::value::serde::ser;
+use crate::sql::StatementKind;
+use serde::ser::Error as _;
+use serde::ser::Impossible;
+
+pub(super) struct Serializer;
+
+impl ser::Serializer for Serializer {
+	type Ok = StatementKind;
+	type Error = Error;
+
+	type SerializeSeq = Impossible<StatementKind, Error>;
+	type SerializeTuple = Impossible<StatementKind, Error>;
+	type SerializeTupleStruct = Impossible<StatementKind, Error>;
+	type SerializeTupleVariant = Impossible<StatementKind, Error>;
+	type SerializeMap = Impossible<StatementKind, Error>;
+	type SerializeStruct = Impossible<StatementKind, Error>;
+	type SerializeStructVariant = Impossible<StatementKind, Error>;
+
+	const EXPECTED: &'static str = "an enum `StatementKind`";
+
+	#[inline]
+	fn serialize_unit_variant(
+		self,
+		name: &'static str,
+		_variant_index: u32,
+		variant: &'static str,
+	) -> Result<Self::Ok, Error> {
+		match variant {
+			"Alter" => Ok(StatementKind::Alter),
+			"Analyze" => Ok(StatementKind::Analyze),
+			"Begin" => Ok(StatementKind::Begin),
+			"Break" => Ok(StatementKind::Break),
+			"Cancel" => Ok(StatementKind::Cancel),
+			"Commit" => Ok(StatementKind::Commit),
+			"Continue" => Ok(StatementKind::Continue),
+			"Create" => Ok(StatementKind::Create),
+			"Define" => Ok(StatementKind::Define),
+			"Delete" => Ok(StatementKind::Delete),
+			"For" => Ok(StatementKind::For),
+			"Grant" => Ok(StatementKind::Grant),
+			"If" => Ok(StatementKind::If),
+			"Info" => Ok(StatementKind::Info),
+			"Insert" => Ok(StatementKind::Insert),
+			"Kill" => Ok(StatementKind::Kill),
+			"Let" => Ok(StatementKind::Let),
+			"Live" => Ok(StatementKind::Live),
+			"Option" => Ok(StatementKind::Option),
+			"Rebuild" => Ok(StatementKind::Rebuild),
+			"Relate" => Ok(StatementKind::Relate),
+			"Remove" => Ok(StatementKind::Remove),
+			"Report" => Ok(StatementKind::Report),
+			"Return" => Ok(StatementKind::Return),
+			"Select" => Ok(StatementKind::Select),
+			"Show" => Ok(StatementKind::Show),
+			"Sleep" => Ok(StatementKind::Sleep),
+			"Throw" => Ok(StatementKind::Throw),
+			"Update" => Ok(StatementKind::Update),
+			"Use" => Ok(StatementKind::Use),
+			variant => Err(Error::custom(format!("unexpected unit variant `{name}::{variant}`"))),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ser::Serializer as _;
+	use serde::Serialize;
+
+	#[test]
+	fn select() {
+		let kind = StatementKind::Select;
+		let serialized = kind.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(kind, serialized);
+	}
+
+	#[test]
+	fn info() {
+		let kind = StatementKind::Info;
+		let serialized = kind.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(kind, serialized);
+	}
+}
@@ -0,0 +1,67 @@
+use crate::err::Error;
+use crate::sql::value::serde::ser;
+use crate::sql::StatementKind;
+use ser::Serializer as _;
+use serde::ser::Impossible;
+use serde::ser::Serialize;
+
+#[non_exhaustive]
+pub struct Serializer;
+
+impl ser::Serializer for Serializer {
+	type Ok = Vec<StatementKind>;
+	type Error = Error;
+
+	type SerializeSeq = SerializeStatementKindVec;
+	type SerializeTuple = Impossible<Vec<StatementKind>, Error>;
+	type SerializeTupleStruct = Impossible<Vec<StatementKind>, Error>;
+	type SerializeTupleVariant = Impossible<Vec<StatementKind>, Error>;
+	type SerializeMap = Impossible<Vec<StatementKind>, Error>;
+	type SerializeStruct = Impossible<Vec<StatementKind>, Error>;
+	type SerializeStructVariant = Impossible<Vec<StatementKind>, Error>;
+
+	const EXPECTED: &'static str = "a `Vec<StatementKind>`";
+
+	fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+		Ok(SerializeStatementKindVec(Vec::with_capacity(len.unwrap_or_default())))
+	}
+}
+
+#[non_exhaustive]
+pub struct SerializeStatementKindVec(Vec<StatementKind>);
+
+impl serde::ser::SerializeSeq for SerializeStatementKindVec {
+	type Ok = Vec<StatementKind>;
+	type Error = Error;
+
+	fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
+	where
+		T: Serialize + ?Sized,
+	{
+		self.0.push(value.serialize(super::Serializer.wrap())?);
+		Ok(())
+	}
+
+	fn end(self) -> Result<Self::Ok, Self::Error> {
+		Ok(self.0)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn empty() {
+		let vec: Vec<StatementKind> = Vec::new();
+		let serialized = vec.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(vec, serialized);
+	}
+
+	#[test]
+	fn vec() {
+		let vec = vec![StatementKind::Select, StatementKind::Create];
+		let serialized = vec.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(vec, serialized);
+	}
+}
@@ -0,0 +1,86 @@
+use crate::err::Error;
+use crate::sql::value::serde::ser;
+use crate::sql::{Idiom, UnknownFields};
+use serde::ser::Error as _;
+use serde::ser::Impossible;
+use serde::ser::Serialize;
+
+pub(super) struct Serializer;
+
+impl ser::Serializer for Serializer {
+	type Ok = UnknownFields;
+	type Error = Error;
+
+	type SerializeSeq = Impossible<UnknownFields, Error>;
+	type SerializeTuple = Impossible<UnknownFields, Error>;
+	type SerializeTupleStruct = Impossible<UnknownFields, Error>;
+	type SerializeTupleVariant = Impossible<UnknownFields, Error>;
+	type SerializeMap = Impossible<UnknownFields, Error>;
+	type SerializeStruct = Impossible<UnknownFields, Error>;
+	type SerializeStructVariant = Impossible<UnknownFields, Error>;
+
+	const EXPECTED: &'static str = "an enum `UnknownFields`";
+
+	#[inline]
+	fn serialize_unit_variant(
+		self,
+		name: &'static str,
+		_variant_index: u32,
+		variant: &'static str,
+	) -> Result<Self::Ok, Error> {
+		match variant {
+			"Drop" => Ok(UnknownFields::Drop),
+			"Reject" => Ok(UnknownFields::Reject),
+			variant => Err(Error::custom(format!("unexpected unit variant `{name}::{variant}`"))),
+		}
+	}
+
+	#[inline]
+	fn serialize_newtype_variant<T>(
+		self,
+		name: &'static str,
+		_variant_index: u32,
+		variant: &'static str,
+		value: &T,
+	) -> Result<Self::Ok, Error>
+	where
+		T: ?Sized + Serialize,
+	{
+		match variant {
+			"Quarantine" => Ok(UnknownFields::Quarantine(Idiom(
+				value.serialize(ser::part::vec::Serializer.wrap())?,
+			))),
+			variant => {
+				Err(Error::custom(format!("unexpected newtype variant `{name}::{variant}`")))
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ser::Serializer as _;
+	use serde::Serialize;
+
+	#[test]
+	fn drop() {
+		let unknown = UnknownFields::Drop;
+		let serialized = unknown.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(unknown, serialized);
+	}
+
+	#[test]
+	fn reject() {
+		let unknown = UnknownFields::Reject;
+		let serialized = unknown.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(unknown, serialized);
+	}
+
+	#[test]
+	fn quarantine() {
+		let unknown = UnknownFields::Quarantine(Idiom::from("extra"));
+		let serialized = unknown.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(unknown, serialized);
+	}
+}
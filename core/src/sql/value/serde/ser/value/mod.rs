@@ -15,6 +15,7 @@ use crate::sql::Duration;
 use crate::sql::Future;
 use crate::sql::Ident;
 use crate::sql::Idiom;
+use crate::sql::Number;
 use crate::sql::Param;
 use crate::sql::Query;
 use crate::sql::Statements;
@@ -37,6 +38,9 @@ use serde::ser::SerializeSeq as _;
 use std::fmt::Display;
 use vec::SerializeValueVec;
 
+/// The name which `std::time::Duration` is serialized with
+const STD_DURATION: &str = "Duration";
+
 /// Convert a `T` into `surrealdb::sql::Value` which is an enum that can represent any valid SQL data.
 pub fn to_value<T>(value: T) -> Result<Value, Error>
 where
@@ -344,6 +348,7 @@ impl ser::Serializer for Serializer {
 			sql::thing::TOKEN => SerializeStruct::Thing(Default::default()),
 			sql::edges::TOKEN => SerializeStruct::Edges(Default::default()),
 			sql::range::TOKEN => SerializeStruct::Range(Default::default()),
+			STD_DURATION => SerializeStruct::Duration(Default::default()),
 			_ => SerializeStruct::Unknown(Default::default()),
 		})
 	}
@@ -519,6 +524,7 @@ pub(super) enum SerializeStruct {
 	Thing(SerializeThing),
 	Edges(SerializeEdges),
 	Range(SerializeRange),
+	Duration(SerializeValueMap),
 	Unknown(SerializeValueMap),
 }
 
@@ -534,7 +540,7 @@ impl serde::ser::SerializeStruct for SerializeStruct {
 			Self::Thing(thing) => thing.serialize_field(key, value),
 			Self::Edges(edges) => edges.serialize_field(key, value),
 			Self::Range(range) => range.serialize_field(key, value),
-			Self::Unknown(map) => map.serialize_entry(key, value),
+			Self::Duration(map) | Self::Unknown(map) => map.serialize_entry(key, value),
 		}
 	}
 
@@ -543,6 +549,21 @@ impl serde::ser::SerializeStruct for SerializeStruct {
 			Self::Thing(thing) => Ok(Value::Thing(thing.end()?)),
 			Self::Edges(edges) => Ok(Value::Edges(Box::new(edges.end()?))),
 			Self::Range(range) => Ok(Value::Range(Box::new(range.end()?))),
+			Self::Duration(map) => {
+				let object = Object(map.end()?);
+				// Other structs named `Duration` remain objects
+				match (object.len(), object.get("secs"), object.get("nanos")) {
+					(
+						2,
+						Some(Value::Number(Number::Int(secs @ 0..))),
+						Some(Value::Number(Number::Int(nanos @ 0..=999_999_999))),
+					) => Ok(Value::Duration(Duration(std::time::Duration::new(
+						*secs as u64,
+						*nanos as u32,
+					)))),
+					_ => Ok(Value::Object(object)),
+				}
+			}
 			Self::Unknown(map) => Ok(Value::Object(Object(map.end()?))),
 		}
 	}
@@ -680,6 +701,25 @@ mod tests {
 		assert_eq!(expected, to_value(&expected).unwrap());
 	}
 
+	#[test]
+	fn std_duration() {
+		let duration = std::time::Duration::new(90, 500);
+		let value = to_value(duration).unwrap();
+		assert_eq!(value, Value::Duration(Duration(duration)));
+
+		#[derive(Serialize)]
+		struct Duration {
+			secs: i64,
+			nanos: i64,
+		}
+		let value = to_value(Duration {
+			secs: -1,
+			nanos: 0,
+		})
+		.unwrap();
+		assert!(value.is_object());
+	}
+
 	#[test]
 	fn datetime() {
 		let datetime = Datetime::default();
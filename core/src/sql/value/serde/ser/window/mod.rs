@@ -0,0 +1,99 @@
+pub(super) mod opt;
+
+use crate::err::Error;
+use crate::sql::value::serde::ser;
+use crate::sql::window::Window;
+use ser::Serializer as _;
+use serde::ser::Error as _;
+use serde::ser::Impossible;
+use serde::ser::Serialize;
+use std::time::Duration;
+
+#[non_exhaustive]
+pub struct Serializer;
+
+impl ser::Serializer for Serializer {
+	type Ok = Window;
+	type Error = Error;
+
+	type SerializeSeq = Impossible<Window, Error>;
+	type SerializeTuple = Impossible<Window, Error>;
+	type SerializeTupleStruct = Impossible<Window, Error>;
+	type SerializeTupleVariant = Impossible<Window, Error>;
+	type SerializeMap = Impossible<Window, Error>;
+	type SerializeStruct = SerializeWindow;
+	type SerializeStructVariant = Impossible<Window, Error>;
+
+	const EXPECTED: &'static str = "a struct `Window`";
+
+	#[inline]
+	fn serialize_struct(
+		self,
+		_name: &'static str,
+		_len: usize,
+	) -> Result<Self::SerializeStruct, Error> {
+		Ok(SerializeWindow::default())
+	}
+}
+
+#[derive(Default)]
+#[non_exhaustive]
+pub struct SerializeWindow {
+	size: Duration,
+	every: Option<Duration>,
+}
+
+impl serde::ser::SerializeStruct for SerializeWindow {
+	type Ok = Window;
+	type Error = Error;
+
+	fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+	where
+		T: ?Sized + Serialize,
+	{
+		match key {
+			"size" => {
+				self.size = value.serialize(ser::duration::Serializer.wrap())?;
+			}
+			"every" => {
+				self.every = value.serialize(ser::duration::opt::Serializer.wrap())?;
+			}
+			key => {
+				return Err(Error::custom(format!("unexpected field `Window::{key}`")));
+			}
+		}
+		Ok(())
+	}
+
+	fn end(self) -> Result<Self::Ok, Error> {
+		Ok(Window {
+			size: self.size,
+			every: self.every,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn tumbling() {
+		let window = Window {
+			size: Duration::from_secs(60),
+			every: None,
+		};
+		let value: Window = window.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(value, window);
+	}
+
+	#[test]
+	fn sliding() {
+		let window = Window {
+			size: Duration::from_secs(300),
+			every: Some(Duration::from_secs(60)),
+		};
+		let value: Window = window.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(value, window);
+	}
+}
@@ -0,0 +1,60 @@
+use crate::err::Error;
+use crate::sql::value::serde::ser;
+use crate::sql::window::Window;
+use serde::ser::Impossible;
+use serde::ser::Serialize;
+
+#[non_exhaustive]
+pub struct Serializer;
+
+impl ser::Serializer for Serializer {
+	type Ok = Option<Window>;
+	type Error = Error;
+
+	type SerializeSeq = Impossible<Option<Window>, Error>;
+	type SerializeTuple = Impossible<Option<Window>, Error>;
+	type SerializeTupleStruct = Impossible<Option<Window>, Error>;
+	type SerializeTupleVariant = Impossible<Option<Window>, Error>;
+	type SerializeMap = Impossible<Option<Window>, Error>;
+	type SerializeStruct = Impossible<Option<Window>, Error>;
+	type SerializeStructVariant = Impossible<Option<Window>, Error>;
+
+	const EXPECTED: &'static str = "an `Option<Window>`";
+
+	#[inline]
+	fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+		Ok(None)
+	}
+
+	#[inline]
+	fn serialize_some<T>(self, value: &T) -> Result<Self::Ok, Self::Error>
+	where
+		T: ?Sized + Serialize,
+	{
+		Ok(Some(value.serialize(super::Serializer.wrap())?))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ser::Serializer as _;
+	use std::time::Duration;
+
+	#[test]
+	fn none() {
+		let option: Option<Window> = None;
+		let serialized = option.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(option, serialized);
+	}
+
+	#[test]
+	fn some() {
+		let option = Some(Window {
+			size: Duration::from_secs(60),
+			every: None,
+		});
+		let serialized = option.serialize(Serializer.wrap()).unwrap();
+		assert_eq!(option, serialized);
+	}
+}
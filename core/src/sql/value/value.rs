@@ -2610,7 +2610,9 @@ impl Value {
 			Value::Array(v) => v.iter().any(Value::writeable),
 			Value::Object(v) => v.iter().any(|(_, v)| v.writeable()),
 			Value::Function(v) => {
-				v.is_custom() || v.is_script() || v.args().iter().any(Value::writeable)
+				v.is_custom()
+					|| v.is_script()
+					|| v.is_write() || v.args().iter().any(Value::writeable)
 			}
 			Value::Model(m) => m.args.iter().any(Value::writeable),
 			Value::Subquery(v) => v.writeable(),
@@ -0,0 +1,63 @@
+use crate::err::Error;
+use crate::sql::duration::Duration;
+use crate::sql::statements::info::InfoStructure;
+use crate::sql::{Fields, Value};
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+use std::time;
+
+/// A window over which a LIVE query aggregates the records which it matches.
+///
+/// A tumbling window (without an `every` interval) advances by its own size,
+/// whereas a sliding window advances by the `every` interval, so that
+/// consecutive windows overlap.
+#[revisioned(revision = 1)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
+pub struct Window {
+	pub size: time::Duration,
+	pub every: Option<time::Duration>,
+}
+
+impl Window {
+	/// The interval at which windows are closed
+	pub(crate) fn step(&self) -> time::Duration {
+		self.every.unwrap_or(self.size)
+	}
+
+	/// Check that this window can be used with the specified LIVE query projections
+	pub(crate) fn check(&self, expr: &Fields) -> Result<(), Error> {
+		let message = if expr.is_empty() {
+			"a window can not be used with a DIFF live query"
+		} else if self.size.is_zero() || self.step().is_zero() {
+			"the window duration must be greater than zero"
+		} else if self.step() > self.size {
+			"the window can not advance by more than its size"
+		} else {
+			return Ok(());
+		};
+		Err(Error::LiveWindow {
+			value: self.to_string(),
+			message: message.to_owned(),
+		})
+	}
+}
+
+impl Display for Window {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		match self.every {
+			Some(every) => {
+				write!(f, "WINDOW SLIDING {} EVERY {}", Duration(self.size), Duration(every))
+			}
+			None => write!(f, "WINDOW TUMBLING {}", Duration(self.size)),
+		}
+	}
+}
+
+impl InfoStructure for Window {
+	fn structure(self) -> Value {
+		self.to_string().into()
+	}
+}
@@ -17,6 +17,7 @@ pub static RESERVED_KEYWORD: phf::Set<UniCase<&'static str>> = phf_set! {
 	UniCase::ascii("CONTINUE"),
 	UniCase::ascii("CREATE"),
 	UniCase::ascii("DEFINE"),
+	UniCase::ascii("FN"),
 	UniCase::ascii("FOR"),
 	UniCase::ascii("IF"),
 	UniCase::ascii("INFO"),
@@ -32,6 +33,7 @@ pub static RESERVED_KEYWORD: phf::Set<UniCase<&'static str>> = phf_set! {
 	UniCase::ascii("LET"),
 	UniCase::ascii("SHOW"),
 	UniCase::ascii("SLEEP"),
+	UniCase::ascii("TABLE"),
 	UniCase::ascii("THROW"),
 	UniCase::ascii("UPDATE"),
 	UniCase::ascii("USE"),
@@ -56,40 +58,59 @@ pub fn could_be_reserved(s: &str) -> bool {
 /// A map for mapping keyword strings to a tokenkind,
 pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map! {
 	// Keywords
+	UniCase::ascii("ADVISOR") => TokenKind::Keyword(Keyword::Advisor),
 	UniCase::ascii("AFTER") => TokenKind::Keyword(Keyword::After),
 	UniCase::ascii("ALL") => TokenKind::Keyword(Keyword::All),
+	UniCase::ascii("ALLOW") => TokenKind::Keyword(Keyword::Allow),
+	UniCase::ascii("ALTER") => TokenKind::Keyword(Keyword::Alter),
 	UniCase::ascii("ANALYZE") => TokenKind::Keyword(Keyword::Analyze),
 	UniCase::ascii("ANALYZER") => TokenKind::Keyword(Keyword::Analyzer),
 	UniCase::ascii("AS") => TokenKind::Keyword(Keyword::As),
 	UniCase::ascii("ASCENDING") => TokenKind::Keyword(Keyword::Ascending),
 	UniCase::ascii("ASC") => TokenKind::Keyword(Keyword::Ascending),
 	UniCase::ascii("ASCII") => TokenKind::Keyword(Keyword::Ascii),
+	UniCase::ascii("ASYNC") => TokenKind::Keyword(Keyword::Async),
 	UniCase::ascii("ASSERT") => TokenKind::Keyword(Keyword::Assert),
 	UniCase::ascii("AT") => TokenKind::Keyword(Keyword::At),
+	UniCase::ascii("AUDIT") => TokenKind::Keyword(Keyword::Audit),
+	UniCase::ascii("BACKFILL") => TokenKind::Keyword(Keyword::Backfill),
 	UniCase::ascii("BEFORE") => TokenKind::Keyword(Keyword::Before),
 	UniCase::ascii("BEGIN") => TokenKind::Keyword(Keyword::Begin),
 	UniCase::ascii("BLANK") => TokenKind::Keyword(Keyword::Blank),
 	UniCase::ascii("BM25") => TokenKind::Keyword(Keyword::Bm25),
 	UniCase::ascii("BREAK") => TokenKind::Keyword(Keyword::Break),
 	UniCase::ascii("BY") => TokenKind::Keyword(Keyword::By),
+	UniCase::ascii("CACHE") => TokenKind::Keyword(Keyword::Cache),
+	UniCase::ascii("CALL") => TokenKind::Keyword(Keyword::Call),
 	UniCase::ascii("CAMEL") => TokenKind::Keyword(Keyword::Camel),
 	UniCase::ascii("CANCEL") => TokenKind::Keyword(Keyword::Cancel),
+	UniCase::ascii("CASCADE") => TokenKind::Keyword(Keyword::Cascade),
+	UniCase::ascii("CHANGE") => TokenKind::Keyword(Keyword::Change),
 	UniCase::ascii("CHANGEFEED") => TokenKind::Keyword(Keyword::ChangeFeed),
 	UniCase::ascii("CHANGES") => TokenKind::Keyword(Keyword::Changes),
+	UniCase::ascii("CHECK") => TokenKind::Keyword(Keyword::Check),
 	UniCase::ascii("CAPACITY") => TokenKind::Keyword(Keyword::Capacity),
 	UniCase::ascii("CLASS") => TokenKind::Keyword(Keyword::Class),
 	UniCase::ascii("COMMENT") => TokenKind::Keyword(Keyword::Comment),
 	UniCase::ascii("COMMIT") => TokenKind::Keyword(Keyword::Commit),
+	UniCase::ascii("COMPACT") => TokenKind::Keyword(Keyword::Compact),
+	UniCase::ascii("COMPUTED") => TokenKind::Keyword(Keyword::Computed),
+	UniCase::ascii("CONSISTENCY") => TokenKind::Keyword(Keyword::Consistency),
 	UniCase::ascii("CONTENT") => TokenKind::Keyword(Keyword::Content),
 	UniCase::ascii("CONTINUE") => TokenKind::Keyword(Keyword::Continue),
+	UniCase::ascii("COUNT") => TokenKind::Keyword(Keyword::Count),
+	UniCase::ascii("COUNTER") => TokenKind::Keyword(Keyword::Counter),
+	UniCase::ascii("CRDT") => TokenKind::Keyword(Keyword::Crdt),
 	UniCase::ascii("CREATE") => TokenKind::Keyword(Keyword::Create),
 	UniCase::ascii("DATABASE") => TokenKind::Keyword(Keyword::Database),
 	UniCase::ascii("DB") => TokenKind::Keyword(Keyword::Database),
 	UniCase::ascii("DEFAULT") => TokenKind::Keyword(Keyword::Default),
 	UniCase::ascii("DEFINE") => TokenKind::Keyword(Keyword::Define),
 	UniCase::ascii("DELETE") => TokenKind::Keyword(Keyword::Delete),
+	UniCase::ascii("DENY") => TokenKind::Keyword(Keyword::Deny),
 	UniCase::ascii("DESCENDING") => TokenKind::Keyword(Keyword::Descending),
 	UniCase::ascii("DESC") => TokenKind::Keyword(Keyword::Descending),
+	UniCase::ascii("DICTIONARY") => TokenKind::Keyword(Keyword::Dictionary),
 	UniCase::ascii("DIFF") => TokenKind::Keyword(Keyword::Diff),
 	UniCase::ascii("DIMENSION") => TokenKind::Keyword(Keyword::Dimension),
 	UniCase::ascii("DISTANCE") => TokenKind::Keyword(Keyword::Distance),
@@ -99,12 +120,17 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("DOC_LENGTHS_CACHE") => TokenKind::Keyword(Keyword::DocLengthsCache),
 	UniCase::ascii("DOC_LENGTHS_ORDER") => TokenKind::Keyword(Keyword::DocLengthsOrder),
 	UniCase::ascii("DROP") => TokenKind::Keyword(Keyword::Drop),
+	UniCase::ascii("DRY") => TokenKind::Keyword(Keyword::Dry),
 	UniCase::ascii("DUPLICATE") => TokenKind::Keyword(Keyword::Duplicate),
 	UniCase::ascii("EDGENGRAM") => TokenKind::Keyword(Keyword::Edgengram),
 	UniCase::ascii("EVENT") => TokenKind::Keyword(Keyword::Event),
+	UniCase::ascii("ENRICHMENT") => TokenKind::Keyword(Keyword::Enrichment),
+	UniCase::ascii("EVENTUAL") => TokenKind::Keyword(Keyword::Eventual),
 	UniCase::ascii("ELSE") => TokenKind::Keyword(Keyword::Else),
 	UniCase::ascii("END") => TokenKind::Keyword(Keyword::End),
+	UniCase::ascii("EVERY") => TokenKind::Keyword(Keyword::Every),
 	UniCase::ascii("EXISTS") => TokenKind::Keyword(Keyword::Exists),
+	UniCase::ascii("EXPIRE") => TokenKind::Keyword(Keyword::Expire),
 	UniCase::ascii("EXPLAIN") => TokenKind::Keyword(Keyword::Explain),
 	UniCase::ascii("false") => TokenKind::Keyword(Keyword::False),
 	UniCase::ascii("FETCH") => TokenKind::Keyword(Keyword::Fetch),
@@ -116,11 +142,17 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("FLEXI") => TokenKind::Keyword(Keyword::Flexible),
 	UniCase::ascii("FLEX") => TokenKind::Keyword(Keyword::Flexible),
 	UniCase::ascii("FOR") => TokenKind::Keyword(Keyword::For),
+	UniCase::ascii("FORMAT") => TokenKind::Keyword(Keyword::Format),
 	UniCase::ascii("FROM") => TokenKind::Keyword(Keyword::From),
 	UniCase::ascii("FULL") => TokenKind::Keyword(Keyword::Full),
 	UniCase::ascii("FUNCTION") => TokenKind::Keyword(Keyword::Function),
+	UniCase::ascii("GRANT") => TokenKind::Keyword(Keyword::Grant),
 	UniCase::ascii("GROUP") => TokenKind::Keyword(Keyword::Group),
+	UniCase::ascii("HASH") => TokenKind::Keyword(Keyword::Hash),
+	UniCase::ascii("HIGH") => TokenKind::Keyword(Keyword::High),
 	UniCase::ascii("HIGHLIGHTS") => TokenKind::Keyword(Keyword::Highlights),
+	UniCase::ascii("HISTORY") => TokenKind::Keyword(Keyword::History),
+	UniCase::ascii("HOOK") => TokenKind::Keyword(Keyword::Hook),
 	UniCase::ascii("IGNORE") => TokenKind::Keyword(Keyword::Ignore),
 	UniCase::ascii("INCLUDE") => TokenKind::Keyword(Keyword::Include),
 	UniCase::ascii("INDEX") => TokenKind::Keyword(Keyword::Index),
@@ -129,16 +161,20 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("INTO") => TokenKind::Keyword(Keyword::Into),
 	UniCase::ascii("IF") => TokenKind::Keyword(Keyword::If),
 	UniCase::ascii("IS") => TokenKind::Keyword(Keyword::Is),
+	UniCase::ascii("JSONL") => TokenKind::Keyword(Keyword::Jsonl),
 	UniCase::ascii("KEY") => TokenKind::Keyword(Keyword::Key),
 	UniCase::ascii("KILL") => TokenKind::Keyword(Keyword::Kill),
+	UniCase::ascii("LABELS") => TokenKind::Keyword(Keyword::Labels),
 	UniCase::ascii("LET") => TokenKind::Keyword(Keyword::Let),
 	UniCase::ascii("LIMIT") => TokenKind::Keyword(Keyword::Limit),
 	UniCase::ascii("LIVE") => TokenKind::Keyword(Keyword::Live),
+	UniCase::ascii("LOW") => TokenKind::Keyword(Keyword::Low),
 	UniCase::ascii("LOWERCASE") => TokenKind::Keyword(Keyword::Lowercase),
 	UniCase::ascii("MERGE") => TokenKind::Keyword(Keyword::Merge),
 	UniCase::ascii("MODEL") => TokenKind::Keyword(Keyword::Model),
 	UniCase::ascii("MTREE") => TokenKind::Keyword(Keyword::MTree),
 	UniCase::ascii("MTREE_CACHE") => TokenKind::Keyword(Keyword::MTreeCache),
+	UniCase::ascii("MUST") => TokenKind::Keyword(Keyword::Must),
 	UniCase::ascii("NAMESPACE") => TokenKind::Keyword(Keyword::Namespace),
 	UniCase::ascii("NS") => TokenKind::Keyword(Keyword::Namespace),
 	UniCase::ascii("NGRAM") => TokenKind::Keyword(Keyword::Ngram),
@@ -147,30 +183,47 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("NONE") => TokenKind::Keyword(Keyword::None),
 	UniCase::ascii("NULL") => TokenKind::Keyword(Keyword::Null),
 	UniCase::ascii("NUMERIC") => TokenKind::Keyword(Keyword::Numeric),
+	UniCase::ascii("OF") => TokenKind::Keyword(Keyword::Of),
 	UniCase::ascii("OMIT") => TokenKind::Keyword(Keyword::Omit),
 	UniCase::ascii("ON") => TokenKind::Keyword(Keyword::On),
 	UniCase::ascii("ONLY") => TokenKind::Keyword(Keyword::Only),
 	UniCase::ascii("OPTION") => TokenKind::Keyword(Keyword::Option),
 	UniCase::ascii("ORDER") => TokenKind::Keyword(Keyword::Order),
+	UniCase::ascii("OUTFILE") => TokenKind::Keyword(Keyword::Outfile),
 	UniCase::ascii("PARALLEL") => TokenKind::Keyword(Keyword::Parallel),
 	UniCase::ascii("PARAM") => TokenKind::Keyword(Keyword::Param),
+	UniCase::ascii("PARTITION") => TokenKind::Keyword(Keyword::Partition),
+	UniCase::ascii("PARTITIONS") => TokenKind::Keyword(Keyword::Partitions),
 	UniCase::ascii("PASSHASH") => TokenKind::Keyword(Keyword::Passhash),
 	UniCase::ascii("PASSWORD") => TokenKind::Keyword(Keyword::Password),
 	UniCase::ascii("PATCH") => TokenKind::Keyword(Keyword::Patch),
 	UniCase::ascii("PERMISSIONS") => TokenKind::Keyword(Keyword::Permissions),
 	UniCase::ascii("POSTINGS_CACHE") => TokenKind::Keyword(Keyword::PostingsCache),
 	UniCase::ascii("POSTINGS_ORDER") => TokenKind::Keyword(Keyword::PostingsOrder),
+	UniCase::ascii("PRIORITY") => TokenKind::Keyword(Keyword::Priority),
+	UniCase::ascii("PUBLISH") => TokenKind::Keyword(Keyword::Publish),
 	UniCase::ascii("PUNCT") => TokenKind::Keyword(Keyword::Punct),
+	UniCase::ascii("QUARANTINE") => TokenKind::Keyword(Keyword::Quarantine),
+	UniCase::ascii("QUERY") => TokenKind::Keyword(Keyword::Query),
 	UniCase::ascii("READONLY") => TokenKind::Keyword(Keyword::Readonly),
+	UniCase::ascii("REJECT") => TokenKind::Keyword(Keyword::Reject),
 	UniCase::ascii("RELATE") => TokenKind::Keyword(Keyword::Relate),
 	UniCase::ascii("RELATION") => TokenKind::Keyword(Keyword::Relation),
 	UniCase::ascii("REBUILD") => TokenKind::Keyword(Keyword::Rebuild),
+	UniCase::ascii("REGISTER") => TokenKind::Keyword(Keyword::Register),
 	UniCase::ascii("REMOVE") => TokenKind::Keyword(Keyword::Remove),
+	UniCase::ascii("REFERENCES") => TokenKind::Keyword(Keyword::References),
 	UniCase::ascii("REPLACE") => TokenKind::Keyword(Keyword::Replace),
+	UniCase::ascii("REPORT") => TokenKind::Keyword(Keyword::Report),
 	UniCase::ascii("RETURN") => TokenKind::Keyword(Keyword::Return),
+	UniCase::ascii("RETRY") => TokenKind::Keyword(Keyword::Retry),
+	UniCase::ascii("ROLE") => TokenKind::Keyword(Keyword::Role),
 	UniCase::ascii("ROLES") => TokenKind::Keyword(Keyword::Roles),
 	UniCase::ascii("ROOT") => TokenKind::Keyword(Keyword::Root),
 	UniCase::ascii("KV") => TokenKind::Keyword(Keyword::Root),
+	UniCase::ascii("RUN") => TokenKind::Keyword(Keyword::Run),
+	UniCase::ascii("SAMPLE") => TokenKind::Keyword(Keyword::Sample),
+	UniCase::ascii("SCHEDULE") => TokenKind::Keyword(Keyword::Schedule),
 	UniCase::ascii("SCHEMAFULL") => TokenKind::Keyword(Keyword::Schemafull),
 	UniCase::ascii("SCHEMAFUL") => TokenKind::Keyword(Keyword::Schemafull),
 	UniCase::ascii("SCHEMALESS") => TokenKind::Keyword(Keyword::Schemaless),
@@ -185,10 +238,15 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("SIGNUP") => TokenKind::Keyword(Keyword::Signup),
 	UniCase::ascii("SINCE") => TokenKind::Keyword(Keyword::Since),
 	UniCase::ascii("SLEEP") => TokenKind::Keyword(Keyword::Sleep),
+	UniCase::ascii("SLIDING") => TokenKind::Keyword(Keyword::Sliding),
 	UniCase::ascii("SNOWBALL") => TokenKind::Keyword(Keyword::Snowball),
 	UniCase::ascii("SPLIT") => TokenKind::Keyword(Keyword::Split),
 	UniCase::ascii("START") => TokenKind::Keyword(Keyword::Start),
+	UniCase::ascii("STATS") => TokenKind::Keyword(Keyword::Stats),
+	UniCase::ascii("STOPWORDS") => TokenKind::Keyword(Keyword::Stopwords),
+	UniCase::ascii("STRONG") => TokenKind::Keyword(Keyword::Strong),
 	UniCase::ascii("STRUCTURE") => TokenKind::Keyword(Keyword::Structure),
+	UniCase::ascii("SYNONYMS") => TokenKind::Keyword(Keyword::Synonyms),
 	UniCase::ascii("TABLE") => TokenKind::Keyword(Keyword::Table),
 	UniCase::ascii("TB") => TokenKind::Keyword(Keyword::Table),
 	UniCase::ascii("TERMS_CACHE") => TokenKind::Keyword(Keyword::TermsCache),
@@ -201,8 +259,10 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("TOKEN") => TokenKind::Keyword(Keyword::Token),
 	UniCase::ascii("TRANSACTION") => TokenKind::Keyword(Keyword::Transaction),
 	UniCase::ascii("true") => TokenKind::Keyword(Keyword::True),
+	UniCase::ascii("TUMBLING") => TokenKind::Keyword(Keyword::Tumbling),
 	UniCase::ascii("TYPE") => TokenKind::Keyword(Keyword::Type),
 	UniCase::ascii("UNIQUE") => TokenKind::Keyword(Keyword::Unique),
+	UniCase::ascii("UNKNOWN") => TokenKind::Keyword(Keyword::Unknown),
 	UniCase::ascii("UNSET") => TokenKind::Keyword(Keyword::Unset),
 	UniCase::ascii("UPDATE") => TokenKind::Keyword(Keyword::Update),
 	UniCase::ascii("UPPERCASE") => TokenKind::Keyword(Keyword::Uppercase),
@@ -212,8 +272,10 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("VALUES") => TokenKind::Keyword(Keyword::Values),
 	UniCase::ascii("VERSION") => TokenKind::Keyword(Keyword::Version),
 	UniCase::ascii("VS") => TokenKind::Keyword(Keyword::Vs),
+	UniCase::ascii("WEBHOOK") => TokenKind::Keyword(Keyword::Webhook),
 	UniCase::ascii("WHEN") => TokenKind::Keyword(Keyword::When),
 	UniCase::ascii("WHERE") => TokenKind::Keyword(Keyword::Where),
+	UniCase::ascii("WINDOW") => TokenKind::Keyword(Keyword::Window),
 	UniCase::ascii("WITH") => TokenKind::Keyword(Keyword::With),
 	UniCase::ascii("ALLINSIDE") => TokenKind::Keyword(Keyword::AllInside),
 	UniCase::ascii("ANDKW") => TokenKind::Keyword(Keyword::AndKw),
@@ -32,9 +32,17 @@ impl TokenValue for Ident {
 				let str = parser.lexer.string.take().unwrap();
 				Ok(Ident(str))
 			}
-			x => {
-				unexpected!(parser, x, "a identifier");
+			TokenKind::Invalid | TokenKind::Eof => {
+				unexpected!(parser, token.kind, "a identifier");
 			}
+			x => Err(ParseError::new(
+				ParseErrorKind::Unexpected {
+					found: x,
+					expected: "a identifier",
+				},
+				// the parser might have already peeked past the token.
+				token.span,
+			)),
 		}
 	}
 }
@@ -22,7 +22,7 @@ const LEVENSTHEIN_ARRAY_SIZE: usize = 1 + MAX_FUNCTION_NAME_LEN + MAX_LEVENSTHEI
 ///
 /// Function uses stack allocated array's of size LEVENSTHEIN_ARRAY_SIZE. LEVENSTHEIN_ARRAY_SIZE should the largest size in the haystack +
 /// maximum cut_off + 1 for the additional value required during calculation
-fn levenshtein(a: &[u8], b: &[u8], cut_off: u8) -> u8 {
+pub(super) fn levenshtein(a: &[u8], b: &[u8], cut_off: u8) -> u8 {
 	debug_assert!(LEVENSTHEIN_ARRAY_SIZE < u8::MAX as usize);
 	let mut distance_array = [[0u8; LEVENSTHEIN_ARRAY_SIZE]; 2];
 
@@ -75,6 +75,8 @@ fn levenshtein(a: &[u8], b: &[u8], cut_off: u8) -> u8 {
 pub enum PathKind {
 	Constant(Constant),
 	Function,
+	/// A function without arguments, which can also be selected from like a table
+	View,
 }
 
 /// A map of path strings for parsing paths.
@@ -122,6 +124,10 @@ pub(crate) static PATHS: phf::Map<UniCase<&'static str>, PathKind> = phf_map! {
 		UniCase::ascii("array::sort::asc") => PathKind::Function,
 		UniCase::ascii("array::sort::desc") => PathKind::Function,
 		//
+		UniCase::ascii("assert::len_between") => PathKind::Function,
+		UniCase::ascii("assert::matches") => PathKind::Function,
+		UniCase::ascii("assert::one_of") => PathKind::Function,
+		//
 		UniCase::ascii("object::entries") => PathKind::Function,
 		UniCase::ascii("object::from_entries") => PathKind::Function,
 		UniCase::ascii("object::keys") => PathKind::Function,
@@ -129,6 +135,10 @@ pub(crate) static PATHS: phf::Map<UniCase<&'static str>, PathKind> = phf_map! {
 		UniCase::ascii("object::values") => PathKind::Function,
 		UniCase::ascii("object::matches") => PathKind::Function,
 		//
+		UniCase::ascii("blob::get") => PathKind::Function,
+		UniCase::ascii("blob::put") => PathKind::Function,
+		UniCase::ascii("blob::size") => PathKind::Function,
+		//
 		UniCase::ascii("bytes::len") => PathKind::Function,
 		//
 		UniCase::ascii("count") => PathKind::Function,
@@ -207,6 +217,8 @@ pub(crate) static PATHS: phf::Map<UniCase<&'static str>, PathKind> = phf_map! {
 		UniCase::ascii("parse::url::query") => PathKind::Function,
 		UniCase::ascii("parse::url::scheme") => PathKind::Function,
 		//
+		UniCase::ascii("path") => PathKind::Function,
+		//
 		UniCase::ascii("rand") => PathKind::Function,
 		UniCase::ascii("rand::bool") => PathKind::Function,
 		UniCase::ascii("rand::enum") => PathKind::Function,
@@ -303,6 +315,9 @@ pub(crate) static PATHS: phf::Map<UniCase<&'static str>, PathKind> = phf_map! {
 		UniCase::ascii("time::from::secs") => PathKind::Function,
 		UniCase::ascii("time::from::unix") => PathKind::Function,
 		//
+		UniCase::ascii("tx::timestamp") => PathKind::Function,
+		UniCase::ascii("tx::versionstamp") => PathKind::Function,
+		//
 		UniCase::ascii("type::bool") => PathKind::Function,
 		UniCase::ascii("type::datetime") => PathKind::Function,
 		UniCase::ascii("type::decimal") => PathKind::Function,
@@ -380,9 +395,25 @@ pub(crate) static PATHS: phf::Map<UniCase<&'static str>, PathKind> = phf_map! {
 		UniCase::ascii("search::score") => PathKind::Function,
 		UniCase::ascii("search::highlight") => PathKind::Function,
 		UniCase::ascii("search::offsets") => PathKind::Function,
+		UniCase::ascii("search::suggest") => PathKind::Function,
 		//
 		UniCase::ascii("sleep") => PathKind::Function,
 		//
+		UniCase::ascii("system::activity") => PathKind::Function,
+		UniCase::ascii("system::background") => PathKind::Function,
+		UniCase::ascii("system::checks") => PathKind::Function,
+		UniCase::ascii("system::connections") => PathKind::Function,
+		UniCase::ascii("system::disk_usage") => PathKind::Function,
+		UniCase::ascii("system::hot_keys") => PathKind::View,
+		UniCase::ascii("system::live_queries") => PathKind::Function,
+		UniCase::ascii("system::merges") => PathKind::Function,
+		UniCase::ascii("system::prefetch") => PathKind::Function,
+		UniCase::ascii("system::priority") => PathKind::Function,
+		UniCase::ascii("system::queries") => PathKind::Function,
+		UniCase::ascii("system::tables") => PathKind::Function,
+		UniCase::ascii("system::throttle") => PathKind::Function,
+		UniCase::ascii("system::transactions") => PathKind::Function,
+		//
 		UniCase::ascii("type::field") => PathKind::Function,
 		UniCase::ascii("type::fields") => PathKind::Function,
 
@@ -430,6 +461,16 @@ impl Parser<'_> {
 				.run(|ctx| self.parse_builtin_function(ctx, k.into_inner().to_owned()))
 				.await
 				.map(|x| Value::Function(Box::new(x))),
+			Some((k, PathKind::View)) => {
+				let name = k.into_inner().to_owned();
+				// A view is computed by calling its function
+				if self.peek_kind() != t!("(") {
+					return Ok(Value::Function(Box::new(Function::Normal(name, Vec::new()))));
+				}
+				stk.run(|ctx| self.parse_builtin_function(ctx, name))
+					.await
+					.map(|x| Value::Function(Box::new(x)))
+			}
 			None => {
 				// Generate an suggestion.
 				// don't search further if the levenshtein distance is further then 10.
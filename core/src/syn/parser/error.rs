@@ -66,6 +66,10 @@ pub enum ParseErrorKind {
 	},
 	/// The parser encountered an token which could not be lexed correctly.
 	InvalidToken(LexError),
+	/// A password which did not satisfy the password policy.
+	InvalidPassword {
+		error: String,
+	},
 	/// Matched a path which was invalid.
 	InvalidPath {
 		possibly: Option<&'static str>,
@@ -75,6 +79,12 @@ pub enum ParseErrorKind {
 		idiom: String,
 		kind: MissingKind,
 	},
+	/// A statement started with an identifier which looks like a mistyped statement keyword.
+	UnknownStatement {
+		possibly: &'static str,
+	},
+	/// Multiple statements in a query could not be parsed.
+	Multiple(Vec<ParseError>),
 	ExceededObjectDepthLimit,
 	ExceededQueryDepthLimit,
 	NoWhitespace,
@@ -208,6 +218,16 @@ impl ParseError {
 					snippets: vec![snippet],
 				}
 			}
+			ParseErrorKind::InvalidPassword {
+				ref error,
+			} => {
+				let locations = Location::range_of_span(source, at);
+				let snippet = Snippet::from_source_location_range(source, locations, None);
+				RenderedError {
+					text: error.to_string(),
+					snippets: vec![snippet],
+				}
+			}
 			ParseErrorKind::InvalidInteger {
 				ref error,
 			} => {
@@ -241,6 +261,33 @@ impl ParseError {
 					snippets: vec![snippet],
 				}
 			}
+			ParseErrorKind::UnknownStatement {
+				possibly,
+			} => {
+				let text = format!("Invalid statement, did you maybe mean `{}`", possibly);
+				let locations = Location::range_of_span(source, at);
+				let snippet = Snippet::from_source_location_range(
+					source,
+					locations,
+					Some("This is not a statement keyword."),
+				);
+				RenderedError {
+					text,
+					snippets: vec![snippet],
+				}
+			}
+			ParseErrorKind::Multiple(errors) => {
+				// Each error is rendered in full, in the order in which it appears in the query.
+				let mut text = format!("Found {} errors in the query:\n", errors.len());
+				for e in errors {
+					// writing into a string never causes an error.
+					write!(text, "\n{}", e.render_on(source)).unwrap();
+				}
+				RenderedError {
+					text: text.trim_end().to_owned(),
+					snippets: Vec::new(),
+				}
+			}
 			ParseErrorKind::NoWhitespace => {
 				let text = "Whitespace is dissallowed in this position";
 				let locations = Location::range_of_span(source, at);
@@ -5,7 +5,7 @@ use reblessive::Stk;
 use crate::{
 	sql::{Array, Ident, Object, Strand, Value},
 	syn::{
-		parser::mac::expected,
+		parser::mac::{expected, unexpected},
 		token::{t, Span, TokenKind},
 	},
 };
@@ -16,6 +16,7 @@ impl Parser<'_> {
 	pub async fn parse_json(&mut self, ctx: &mut Stk) -> ParseResult<Value> {
 		let token = self.next();
 		match token.kind {
+			t!("NONE") => Ok(Value::None),
 			t!("NULL") => Ok(Value::Null),
 			t!("true") => Ok(Value::Bool(true)),
 			t!("false") => Ok(Value::Bool(false)),
@@ -31,6 +32,17 @@ impl Parser<'_> {
 				}
 			}
 			TokenKind::Number(_) => self.token_value(token).map(Value::Number),
+			t!("-") => {
+				// The sign of a negative number is a separate token
+				let token = self.next();
+				let TokenKind::Number(_) = token.kind else {
+					unexpected!(self, token.kind, "a number")
+				};
+				if let Some(x) = self.lexer.string.as_mut() {
+					x.insert(0, '-');
+				}
+				self.token_value(token).map(Value::Number)
+			}
 			TokenKind::Uuid => self.token_value(token).map(Value::Uuid),
 			_ => {
 				let ident = self.token_value::<Ident>(token)?.0;
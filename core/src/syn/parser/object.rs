@@ -668,7 +668,14 @@ impl Parser<'_> {
 			}
 			TokenKind::Number(_) => {
 				self.pop_peek();
-				Ok(self.lexer.string.take().unwrap())
+				match self.lexer.string.take() {
+					Some(x) => Ok(x),
+					// The text of a `NaN` token is not stored by the lexer
+					None => {
+						let str = self.lexer.reader.span(token.span);
+						Ok(std::str::from_utf8(str).unwrap().to_owned())
+					}
+				}
 			}
 			x => unexpected!(self, x, "an object key"),
 		}
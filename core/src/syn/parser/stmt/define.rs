@@ -5,19 +5,22 @@ use crate::{
 		filter::Filter,
 		index::{Distance, VectorType},
 		statements::{
-			DefineAnalyzerStatement, DefineDatabaseStatement, DefineEventStatement,
-			DefineFieldStatement, DefineFunctionStatement, DefineIndexStatement,
-			DefineNamespaceStatement, DefineParamStatement, DefineScopeStatement, DefineStatement,
-			DefineTableStatement, DefineTokenStatement, DefineUserStatement,
+			DefineAnalyzerStatement, DefineCheckStatement, DefineDatabaseStatement,
+			DefineDictionaryStatement, DefineEnrichmentStatement, DefineEventStatement,
+			DefineFieldStatement, DefineFunctionStatement, DefineHookStatement,
+			DefineIndexStatement, DefineNamespaceStatement, DefineParamStatement,
+			DefineRoleStatement, DefineScopeStatement, DefineStatement, DefineTableStatement,
+			DefineTokenStatement, DefineUserStatement, DictionaryKind, Privilege, RoleGrant,
 		},
 		table_type,
 		tokenizer::Tokenizer,
-		Ident, Idioms, Index, Kind, Param, Permissions, Scoring, Strand, TableType, Values,
+		Crdt, Duration, Ident, Idioms, Index, Kind, OnDelete, Param, Permissions, Priority,
+		Scoring, StatementKind, Strand, TableType, UnknownFields, Value, Values,
 	},
 	syn::{
 		parser::{
 			mac::{expected, unexpected},
-			ParseResult, Parser,
+			ParseError, ParseErrorKind, ParseResult, Parser,
 		},
 		token::{t, TokenKind},
 	},
@@ -30,6 +33,7 @@ impl Parser<'_> {
 			t!("DATABASE") => self.parse_define_database().map(DefineStatement::Database),
 			t!("FUNCTION") => self.parse_define_function(ctx).await.map(DefineStatement::Function),
 			t!("USER") => self.parse_define_user().map(DefineStatement::User),
+			t!("ROLE") => self.parse_define_role().map(DefineStatement::Role),
 			t!("TOKEN") => self.parse_define_token().map(DefineStatement::Token),
 			t!("SCOPE") => self.parse_define_scope(ctx).await.map(DefineStatement::Scope),
 			t!("PARAM") => self.parse_define_param(ctx).await.map(DefineStatement::Param),
@@ -42,6 +46,12 @@ impl Parser<'_> {
 			}
 			t!("INDEX") => self.parse_define_index().map(DefineStatement::Index),
 			t!("ANALYZER") => self.parse_define_analyzer().map(DefineStatement::Analyzer),
+			t!("DICTIONARY") => self.parse_define_dictionary().map(DefineStatement::Dictionary),
+			t!("CHECK") => self.parse_define_check(ctx).await.map(DefineStatement::Check),
+			t!("HOOK") => self.parse_define_hook().map(DefineStatement::Hook),
+			t!("ENRICHMENT") => {
+				self.parse_define_enrichment(ctx).await.map(DefineStatement::Enrichment)
+			}
 			x => unexpected!(self, x, "a define statement keyword"),
 		}
 	}
@@ -62,9 +72,18 @@ impl Parser<'_> {
 			..Default::default()
 		};
 
-		while let t!("COMMENT") = self.peek_kind() {
-			self.pop_peek();
-			res.comment = Some(self.next_token_value()?);
+		loop {
+			match self.peek_kind() {
+				t!("COMMENT") => {
+					self.pop_peek();
+					res.comment = Some(self.next_token_value()?);
+				}
+				t!("LABELS") => {
+					self.pop_peek();
+					res.labels = Some(self.parse_labels()?);
+				}
+				_ => break,
+			}
 		}
 
 		Ok(res)
@@ -94,6 +113,10 @@ impl Parser<'_> {
 					self.pop_peek();
 					res.changefeed = Some(self.parse_changefeed()?);
 				}
+				t!("LABELS") => {
+					self.pop_peek();
+					res.labels = Some(self.parse_labels()?);
+				}
 				_ => break,
 			}
 		}
@@ -190,12 +213,36 @@ impl Parser<'_> {
 				}
 				t!("PASSWORD") => {
 					self.pop_peek();
-					res.set_password(&self.next_token_value::<Strand>()?.0);
+					// Force the password to be changed at the next signin
+					if self.eat(t!("MUST")) {
+						expected!(self, t!("CHANGE"));
+						res.must_change = true;
+						continue;
+					}
+					let token = self.peek();
+					let password = self.next_token_value::<Strand>()?;
+					if let Err(e) = res.set_password(&password.0) {
+						return Err(ParseError::new(
+							ParseErrorKind::InvalidPassword {
+								error: e.to_string(),
+							},
+							token.span,
+						));
+					}
 				}
 				t!("PASSHASH") => {
 					self.pop_peek();
 					res.set_passhash(self.next_token_value::<Strand>()?.0);
 				}
+				t!("PRIORITY") => {
+					self.pop_peek();
+					res.priority = match self.next().kind {
+						t!("LOW") => Priority::Low,
+						t!("NORMAL") => Priority::Normal,
+						t!("HIGH") => Priority::High,
+						x => unexpected!(self, x, "either 'LOW', 'NORMAL', or 'HIGH'"),
+					};
+				}
 				t!("ROLES") => {
 					self.pop_peek();
 					res.roles = vec![self.next_token_value()?];
@@ -289,6 +336,14 @@ impl Parser<'_> {
 					self.pop_peek();
 					res.signin = Some(stk.run(|stk| self.parse_value(stk)).await?);
 				}
+				t!("ALLOW") => {
+					self.pop_peek();
+					res.allow = self.parse_statement_kinds()?;
+				}
+				t!("DENY") => {
+					self.pop_peek();
+					res.deny = self.parse_statement_kinds()?;
+				}
 				_ => break,
 			}
 		}
@@ -296,6 +351,123 @@ impl Parser<'_> {
 		Ok(res)
 	}
 
+	/// Parses a list of statement kinds, such as `SELECT, CREATE`
+	///
+	/// # Parser State
+	/// Expects the parser to have already eaten the `ALLOW` or `DENY` keyword
+	pub fn parse_statement_kinds(&mut self) -> ParseResult<Vec<StatementKind>> {
+		let mut kinds = Vec::new();
+		loop {
+			let kind = match self.next().kind {
+				t!("ALTER") => StatementKind::Alter,
+				t!("ANALYZE") => StatementKind::Analyze,
+				t!("BEGIN") => StatementKind::Begin,
+				t!("BREAK") => StatementKind::Break,
+				t!("CANCEL") => StatementKind::Cancel,
+				t!("COMMIT") => StatementKind::Commit,
+				t!("CONTINUE") => StatementKind::Continue,
+				t!("CREATE") => StatementKind::Create,
+				t!("DEFINE") => StatementKind::Define,
+				t!("DELETE") => StatementKind::Delete,
+				t!("FOR") => StatementKind::For,
+				t!("GRANT") => StatementKind::Grant,
+				t!("IF") => StatementKind::If,
+				t!("INFO") => StatementKind::Info,
+				t!("INSERT") => StatementKind::Insert,
+				t!("KILL") => StatementKind::Kill,
+				t!("LET") => StatementKind::Let,
+				t!("LIVE") => StatementKind::Live,
+				t!("OPTION") => StatementKind::Option,
+				t!("REBUILD") => StatementKind::Rebuild,
+				t!("RELATE") => StatementKind::Relate,
+				t!("REMOVE") => StatementKind::Remove,
+				t!("REPORT") => StatementKind::Report,
+				t!("RETURN") => StatementKind::Return,
+				t!("SELECT") => StatementKind::Select,
+				t!("SHOW") => StatementKind::Show,
+				t!("SLEEP") => StatementKind::Sleep,
+				t!("THROW") => StatementKind::Throw,
+				t!("UPDATE") => StatementKind::Update,
+				t!("USE") => StatementKind::Use,
+				x => unexpected!(self, x, "a statement keyword"),
+			};
+			if !kinds.contains(&kind) {
+				kinds.push(kind);
+			}
+			if !self.eat(t!(",")) {
+				break;
+			}
+		}
+		Ok(kinds)
+	}
+
+	pub fn parse_define_role(&mut self) -> ParseResult<DefineRoleStatement> {
+		let if_not_exists = if self.eat(t!("IF")) {
+			expected!(self, t!("NOT"));
+			expected!(self, t!("EXISTS"));
+			true
+		} else {
+			false
+		};
+		let name = self.next_token_value()?;
+		expected!(self, t!("ON"));
+		expected!(self, t!("DATABASE"));
+
+		let mut res = DefineRoleStatement {
+			name,
+			if_not_exists,
+			..Default::default()
+		};
+
+		loop {
+			match self.peek_kind() {
+				t!("GRANT") => {
+					self.pop_peek();
+					res.grants.push(self.parse_role_grant()?);
+				}
+				t!("COMMENT") => {
+					self.pop_peek();
+					res.comment = Some(self.next_token_value()?);
+				}
+				_ => break,
+			}
+		}
+		Ok(res)
+	}
+
+	/// Parses the privileges and tables of a role grant
+	///
+	/// # Parser State
+	/// Expects the parser to have already eaten the `GRANT` keyword
+	pub fn parse_role_grant(&mut self) -> ParseResult<RoleGrant> {
+		let mut privileges = Vec::new();
+		loop {
+			let privilege = match self.next().kind {
+				t!("SELECT") => Privilege::Select,
+				t!("CREATE") => Privilege::Create,
+				t!("UPDATE") => Privilege::Update,
+				t!("DELETE") => Privilege::Delete,
+				x => unexpected!(self, x, "'SELECT', 'CREATE', 'UPDATE', or 'DELETE'"),
+			};
+			if !privileges.contains(&privilege) {
+				privileges.push(privilege);
+			}
+			if !self.eat(t!(",")) {
+				break;
+			}
+		}
+		expected!(self, t!("ON"));
+		self.eat(t!("TABLE"));
+		let mut tables = vec![self.next_token_value()?];
+		while self.eat(t!(",")) {
+			tables.push(self.next_token_value()?);
+		}
+		Ok(RoleGrant {
+			privileges,
+			tables,
+		})
+	}
+
 	pub async fn parse_define_param(&mut self, ctx: &mut Stk) -> ParseResult<DefineParamStatement> {
 		let if_not_exists = if self.eat(t!("IF")) {
 			expected!(self, t!("NOT"));
@@ -314,6 +486,10 @@ impl Parser<'_> {
 
 		loop {
 			match self.peek_kind() {
+				t!("TYPE") => {
+					self.pop_peek();
+					res.kind = Some(ctx.run(|ctx| self.parse_inner_kind(ctx)).await?);
+				}
 				t!("VALUE") => {
 					self.pop_peek();
 					res.value = ctx.run(|ctx| self.parse_value(ctx)).await?;
@@ -354,6 +530,10 @@ impl Parser<'_> {
 					self.pop_peek();
 					res.comment = Some(self.next_token_value()?);
 				}
+				t!("LABELS") => {
+					self.pop_peek();
+					res.labels = Some(self.parse_labels()?);
+				}
 				t!("DROP") => {
 					self.pop_peek();
 					res.drop = true;
@@ -384,6 +564,25 @@ impl Parser<'_> {
 					self.pop_peek();
 					res.full = true;
 				}
+				t!("WITH") => {
+					self.pop_peek();
+					expected!(self, t!("AUDIT"));
+					expected!(self, t!("FIELDS"));
+					res.audit = true;
+				}
+				t!("UNKNOWN") => {
+					self.pop_peek();
+					expected!(self, t!("FIELDS"));
+					res.unknown = match self.next().kind {
+						t!("DROP") => UnknownFields::Drop,
+						t!("REJECT") => UnknownFields::Reject,
+						t!("QUARANTINE") => {
+							expected!(self, t!("INTO"));
+							UnknownFields::Quarantine(self.parse_local_idiom()?)
+						}
+						x => unexpected!(self, x, "`DROP`, `REJECT`, or `QUARANTINE`"),
+					};
+				}
 				t!("PERMISSIONS") => {
 					self.pop_peek();
 					res.permissions = ctx.run(|ctx| self.parse_permission(ctx, false)).await?;
@@ -392,6 +591,10 @@ impl Parser<'_> {
 					self.pop_peek();
 					res.changefeed = Some(self.parse_changefeed()?);
 				}
+				t!("PARTITION") => {
+					self.pop_peek();
+					res.partition = Some(self.parse_partition()?);
+				}
 				t!("AS") => {
 					self.pop_peek();
 					match self.peek_kind() {
@@ -456,6 +659,103 @@ impl Parser<'_> {
 		Ok(res)
 	}
 
+	pub fn parse_define_hook(&mut self) -> ParseResult<DefineHookStatement> {
+		let if_not_exists = if self.eat(t!("IF")) {
+			expected!(self, t!("NOT"));
+			expected!(self, t!("EXISTS"));
+			true
+		} else {
+			false
+		};
+		// A hook without a name is named after its channel
+		let name: Option<Ident> = match self.peek_kind() {
+			t!("ON") => None,
+			_ => Some(self.next_token_value()?),
+		};
+		expected!(self, t!("ON"));
+		self.eat(t!("TABLE"));
+		let what = self.next_token_value()?;
+		expected!(self, t!("AFTER"));
+		expected!(self, t!("COMMIT"));
+		expected!(self, t!("PUBLISH"));
+		let channel: Strand = self.next_token_value()?;
+
+		let mut res = DefineHookStatement {
+			name: name.unwrap_or_else(|| Ident(channel.0.clone())),
+			what,
+			channel,
+			if_not_exists,
+			..Default::default()
+		};
+
+		while let t!("COMMENT") = self.peek_kind() {
+			self.pop_peek();
+			res.comment = Some(self.next_token_value()?);
+		}
+
+		Ok(res)
+	}
+
+	pub async fn parse_define_enrichment(
+		&mut self,
+		ctx: &mut Stk,
+	) -> ParseResult<DefineEnrichmentStatement> {
+		let if_not_exists = if self.eat(t!("IF")) {
+			expected!(self, t!("NOT"));
+			expected!(self, t!("EXISTS"));
+			true
+		} else {
+			false
+		};
+		let name: Option<Ident> = match self.peek_kind() {
+			t!("ON") => None,
+			_ => Some(self.next_token_value()?),
+		};
+		expected!(self, t!("ON"));
+		self.eat(t!("TABLE"));
+		let what: Ident = self.next_token_value()?;
+		expected!(self, t!("CALL"));
+		let call = ctx.run(|ctx| self.parse_value(ctx)).await?;
+		let into = if self.eat(t!("INTO")) {
+			Some(self.parse_local_idiom()?)
+		} else {
+			None
+		};
+		expected!(self, t!("ASYNC"));
+
+		// An enrichment without a name is named after the custom function which it
+		// calls, or otherwise after the field which it writes into, or its table
+		let name = name.unwrap_or_else(|| match (&call, &into) {
+			(Value::Function(f), _) if f.is_custom() => Ident(f.name().unwrap().to_owned()),
+			(_, Some(into)) => Ident(into.to_string()),
+			_ => what.clone(),
+		});
+		let mut res = DefineEnrichmentStatement {
+			name,
+			what,
+			call,
+			into,
+			if_not_exists,
+			..Default::default()
+		};
+
+		loop {
+			match self.peek_kind() {
+				t!("RETRY") => {
+					self.pop_peek();
+					res.retry = Some(self.next_token_value()?);
+				}
+				t!("COMMENT") => {
+					self.pop_peek();
+					res.comment = Some(self.next_token_value()?);
+				}
+				_ => break,
+			}
+		}
+
+		Ok(res)
+	}
+
 	pub async fn parse_define_field(&mut self, ctx: &mut Stk) -> ParseResult<DefineFieldStatement> {
 		let if_not_exists = if self.eat(t!("IF")) {
 			expected!(self, t!("NOT"));
@@ -491,6 +791,24 @@ impl Parser<'_> {
 					self.pop_peek();
 					res.readonly = true;
 				}
+				t!("EXPIRE") => {
+					self.pop_peek();
+					res.expire = true;
+				}
+				t!("COMPUTED") => {
+					self.pop_peek();
+					res.computed = true;
+				}
+				t!("CRDT") => {
+					self.pop_peek();
+					let next = self.next();
+					res.crdt = Some(match next.kind {
+						t!("COUNTER") => Crdt::Counter,
+						t!("REGISTER") => Crdt::Register,
+						t!("SET") => Crdt::Set,
+						x => unexpected!(self, x, "`COUNTER`, `REGISTER`, or `SET`"),
+					});
+				}
 				t!("VALUE") => {
 					self.pop_peek();
 					res.value = Some(ctx.run(|ctx| self.parse_value(ctx)).await?);
@@ -696,6 +1014,108 @@ impl Parser<'_> {
 		Ok(res)
 	}
 
+	pub fn parse_define_dictionary(&mut self) -> ParseResult<DefineDictionaryStatement> {
+		let if_not_exists = if self.eat(t!("IF")) {
+			expected!(self, t!("NOT"));
+			expected!(self, t!("EXISTS"));
+			true
+		} else {
+			false
+		};
+		let name = self.next_token_value()?;
+		let kind = match self.next().kind {
+			t!("STOPWORDS") => DictionaryKind::Stopwords(self.parse_dictionary_terms()?),
+			t!("SYNONYMS") => {
+				let mut groups = vec![self.parse_dictionary_terms()?];
+				while self.eat(t!(",")) {
+					groups.push(self.parse_dictionary_terms()?);
+				}
+				DictionaryKind::Synonyms(groups)
+			}
+			x => unexpected!(self, x, "'STOPWORDS' or 'SYNONYMS'"),
+		};
+		let mut res = DefineDictionaryStatement {
+			name,
+			kind,
+			comment: None,
+			if_not_exists,
+		};
+		if self.eat(t!("COMMENT")) {
+			res.comment = Some(self.next_token_value()?);
+		}
+		Ok(res)
+	}
+
+	pub async fn parse_define_check(&mut self, ctx: &mut Stk) -> ParseResult<DefineCheckStatement> {
+		let if_not_exists = if self.eat(t!("IF")) {
+			expected!(self, t!("NOT"));
+			expected!(self, t!("EXISTS"));
+			true
+		} else {
+			false
+		};
+		let name = self.next_token_value()?;
+		expected!(self, t!("SCHEDULE"));
+		let schedule = self.parse_check_schedule()?;
+		expected!(self, t!("AS"));
+		let query = ctx.run(|ctx| self.parse_value(ctx)).await?;
+		expected!(self, t!("ASSERT"));
+		let assert = ctx.run(|ctx| self.parse_value(ctx)).await?;
+		let mut res = DefineCheckStatement {
+			name,
+			schedule,
+			query,
+			assert,
+			if_not_exists,
+			..Default::default()
+		};
+		loop {
+			match self.peek_kind() {
+				t!("WEBHOOK") => {
+					self.pop_peek();
+					res.webhook = Some(self.next_token_value()?);
+				}
+				t!("COMMENT") => {
+					self.pop_peek();
+					res.comment = Some(self.next_token_value()?);
+				}
+				_ => break,
+			}
+		}
+		Ok(res)
+	}
+
+	/// Parses the schedule of a check, as either a duration or a string such as `'1h'`
+	pub fn parse_check_schedule(&mut self) -> ParseResult<Duration> {
+		match self.peek_kind() {
+			TokenKind::Strand => {
+				let strand: Strand = self.next_token_value()?;
+				match Duration::try_from(strand) {
+					Ok(v) => Ok(v),
+					Err(_) => unexpected!(self, TokenKind::Strand, "a duration"),
+				}
+			}
+			_ => self.next_token_value(),
+		}
+	}
+
+	/// Parses a bracketed list of the string terms of a dictionary
+	pub fn parse_dictionary_terms(&mut self) -> ParseResult<Vec<Strand>> {
+		let open_span = expected!(self, t!("[")).span;
+		let mut terms = Vec::new();
+		loop {
+			if self.eat(t!("]")) {
+				break;
+			}
+			terms.push(self.next_token_value()?);
+			if !self.eat(t!(",")) {
+				self.expect_closing_delimiter(t!("]"), open_span)?;
+				break;
+			}
+		}
+		Ok(terms)
+	}
+
 	pub fn parse_define_analyzer(&mut self) -> ParseResult<DefineAnalyzerStatement> {
 		let if_not_exists = if self.eat(t!("IF")) {
 			expected!(self, t!("NOT"));
@@ -753,6 +1173,18 @@ impl Parser<'_> {
 								self.expect_closing_delimiter(t!(")"), open_span)?;
 								filters.push(Filter::Snowball(language))
 							}
+							t!("STOPWORDS") => {
+								let open_span = expected!(self, t!("(")).span;
+								let name = self.next_token_value()?;
+								self.expect_closing_delimiter(t!(")"), open_span)?;
+								filters.push(Filter::Stopwords(name))
+							}
+							t!("SYNONYMS") => {
+								let open_span = expected!(self, t!("(")).span;
+								let name = self.next_token_value()?;
+								self.expect_closing_delimiter(t!(")"), open_span)?;
+								filters.push(Filter::Synonyms(name))
+							}
 							x => unexpected!(self, x, "a filter"),
 						}
 						if !self.eat(t!(",")) {
@@ -807,6 +1239,7 @@ impl Parser<'_> {
 		let mut res = table_type::Relation {
 			from: None,
 			to: None,
+			on_delete: OnDelete::default(),
 		};
 		loop {
 			match self.peek_kind() {
@@ -820,6 +1253,15 @@ impl Parser<'_> {
 					let to = self.parse_tables()?;
 					res.to = Some(to);
 				}
+				t!("ON") => {
+					self.pop_peek();
+					expected!(self, t!("DELETE"));
+					res.on_delete = match self.next().kind {
+						t!("CASCADE") => OnDelete::Cascade,
+						t!("REJECT") => OnDelete::Reject,
+						x => unexpected!(self, x, "`CASCADE` or `REJECT`"),
+					};
+				}
 				_ => break,
 			}
 		}
@@ -6,11 +6,14 @@ use crate::sql::statements::rebuild::{RebuildIndexStatement, RebuildStatement};
 use crate::sql::statements::show::{ShowSince, ShowStatement};
 use crate::sql::statements::sleep::SleepStatement;
 use crate::sql::statements::{
-	KillStatement, LiveStatement, OptionStatement, SetStatement, ThrowStatement,
+	AlterFieldStatement, AlterStatement, DryRunStatement, GrantStatement, KillKind, KillStatement,
+	LiveAction, LiveStatement, OptionStatement, ReportReferencesStatement, ReportStatement,
+	SetStatement, ThrowStatement,
 };
-use crate::sql::{Fields, Ident, Param};
+use crate::sql::{Base, Fields, Ident, Isolation, Param, Tables};
+use crate::syn::parser::builtin::levenshtein;
 use crate::syn::parser::{ParseError, ParseErrorKind};
-use crate::syn::token::{t, TokenKind};
+use crate::syn::token::{t, Span, Token, TokenKind};
 use crate::{
 	sql::{
 		statements::{
@@ -36,60 +39,161 @@ mod remove;
 mod select;
 mod update;
 
+/// The maximum amount of syntax errors which are collected from a single query.
+const MAX_ERRORS: usize = 10;
+
+/// The keywords which start a statement, suggested when a statement starts with a mistyped keyword.
+const STATEMENT_KEYWORDS: &[&str] = &[
+	"ALTER", "ANALYZE", "BEGIN", "BREAK", "CANCEL", "COMMIT", "CONTINUE", "CREATE", "DEFINE",
+	"DELETE", "FOR", "GRANT", "IF", "INFO", "INSERT", "KILL", "LET", "LIVE", "OPTION", "REBUILD",
+	"RELATE", "REMOVE", "REPORT", "RETURN", "SELECT", "SHOW", "SLEEP", "THROW", "UPDATE", "USE",
+];
+
 impl Parser<'_> {
+	/// Parse a list of statements.
+	///
+	/// When a statement fails to parse, the error is recorded and the parser skips ahead to the
+	/// end of that statement, so that the errors in any of the following statements are reported
+	/// together with it.
 	pub async fn parse_stmt_list(&mut self, ctx: &mut Stk) -> ParseResult<Statements> {
 		let mut res = Vec::new();
+		let mut errors = Vec::new();
 		loop {
 			match self.peek_kind() {
 				// consume any possible empty statements.
-				t!(";") => continue,
+				t!(";") => {
+					self.pop_peek();
+				}
 				t!("eof") => break,
 				_ => {
-					let stmt = ctx.run(|ctx| self.parse_stmt(ctx)).await?;
-					res.push(stmt);
-					if !self.eat(t!(";")) {
-						if self.eat(t!("eof")) {
-							break;
+					let start = self.peek();
+					match ctx.run(|ctx| self.parse_stmt(ctx)).await {
+						Ok(stmt) => {
+							res.push(stmt);
+							if Self::token_kind_starts_statement(self.peek_kind()) {
+								// user likely forgot a semicolon, the next statement can be parsed as is.
+								errors.push(ParseError::new(
+									ParseErrorKind::UnexpectedExplain {
+										found: self.peek_kind(),
+										expected: "the query to end",
+										explain:
+											"maybe forgot a semicolon after the previous statement?",
+									},
+									self.recent_span(),
+								));
+							} else if let Err(e) = self.parse_stmt_end() {
+								errors.push(self.suggest_statement(start, e));
+								self.recover_stmt(start.span);
+							}
 						}
-
-						if Self::token_kind_starts_statement(self.peek_kind()) {
-							// user likely forgot a semicolon.
-							return Err(ParseError::new(
-								ParseErrorKind::UnexpectedExplain {
-									found: self.peek_kind(),
-									expected: "the query to end",
-									explain:
-										"maybe forgot a semicolon after the previous statement?",
-								},
-								self.recent_span(),
-							));
+						Err(e) => {
+							errors.push(self.suggest_statement(start, e));
+							self.recover_stmt(start.span);
 						}
-
-						expected!(self, t!("eof"));
+					}
+					if errors.len() >= MAX_ERRORS {
+						break;
 					}
 				}
 			}
 		}
-		Ok(Statements(res))
+		match errors.len() {
+			0 => Ok(Statements(res)),
+			1 => Err(errors.pop().unwrap()),
+			_ => {
+				let at = errors[0].at;
+				Err(ParseError::new(ParseErrorKind::Multiple(errors), at))
+			}
+		}
+	}
+
+	/// Parse the end of a statement, either a `;` or the end of the query.
+	fn parse_stmt_end(&mut self) -> ParseResult<()> {
+		if !self.eat(t!(";")) && self.peek_kind() != t!("eof") {
+			expected!(self, t!("eof"));
+		}
+		Ok(())
+	}
+
+	/// Skip the remaining tokens of a statement which failed to parse, up to and including the
+	/// `;` which ends it.
+	fn recover_stmt(&mut self, start: Span) {
+		// the statement could have failed on the `;` which ends it, after consuming it.
+		if self.last_span.offset > start.offset && self.lexer.reader.span(self.last_span) == b";" {
+			return;
+		}
+		let mut depth = 0usize;
+		loop {
+			match self.peek_kind() {
+				t!("eof") => break,
+				t!(";") if depth == 0 => {
+					self.pop_peek();
+					break;
+				}
+				t!("{") | t!("(") | t!("[") => depth += 1,
+				t!("}") | t!(")") | t!("]") => depth = depth.saturating_sub(1),
+				TokenKind::Invalid => {
+					self.lexer.error.take();
+				}
+				_ => {}
+			}
+			self.pop_peek();
+		}
+	}
+
+	/// Replace the error of a statement which starts with a mistyped statement keyword with an
+	/// error suggesting the keyword.
+	fn suggest_statement(&mut self, start: Token, error: ParseError) -> ParseError {
+		if start.kind != TokenKind::Identifier {
+			return error;
+		}
+		let slice = self.lexer.reader.span(start.span);
+		let cut_off = (slice.len() / 4).min(2) as u8;
+		let possibly = STATEMENT_KEYWORDS
+			.iter()
+			.map(|x| (levenshtein(slice, x.as_bytes(), cut_off), *x))
+			.min_by_key(|(distance, _)| *distance)
+			.filter(|(distance, _)| *distance <= cut_off);
+		match possibly {
+			Some((_, possibly)) => ParseError::new(
+				ParseErrorKind::UnknownStatement {
+					possibly,
+				},
+				start.span,
+			),
+			None => error,
+		}
 	}
 
 	fn token_kind_starts_statement(kind: TokenKind) -> bool {
 		matches!(
 			kind,
-			t!("ANALYZE")
-				| t!("BEGIN") | t!("BREAK")
-				| t!("CANCEL") | t!("COMMIT")
-				| t!("CONTINUE") | t!("CREATE")
-				| t!("DEFINE") | t!("DELETE")
-				| t!("FOR") | t!("IF")
-				| t!("INFO") | t!("INSERT")
+			t!("ALTER")
+				| t!("ANALYZE")
+				| t!("BEGIN")
+				| t!("BREAK")
+				| t!("CANCEL")
+				| t!("COMMIT")
+				| t!("CONTINUE")
+				| t!("CREATE")
+				| t!("DEFINE")
+				| t!("DELETE")
+				| t!("FOR") | t!("GRANT")
+				| t!("IF") | t!("INFO")
+				| t!("INSERT")
 				| t!("KILL") | t!("LIVE")
-				| t!("OPTION") | t!("REBUILD")
-				| t!("RETURN") | t!("RELATE")
-				| t!("REMOVE") | t!("SELECT")
+				| t!("OPTION")
+				| t!("REBUILD")
+				| t!("RETURN")
+				| t!("RELATE")
+				| t!("REMOVE")
+				| t!("REPORT")
+				| t!("SELECT")
 				| t!("LET") | t!("SHOW")
-				| t!("SLEEP") | t!("THROW")
-				| t!("UPDATE") | t!("USE")
+				| t!("SLEEP")
+				| t!("THROW")
+				| t!("UPDATE")
+				| t!("USE")
 		)
 	}
 
@@ -102,6 +206,10 @@ impl Parser<'_> {
 	async fn parse_stmt_inner(&mut self, ctx: &mut Stk) -> ParseResult<Statement> {
 		let token = self.peek();
 		match token.kind {
+			t!("ALTER") => {
+				self.pop_peek();
+				ctx.run(|ctx| self.parse_alter_stmt(ctx)).await.map(Statement::Alter)
+			}
 			t!("ANALYZE") => {
 				self.pop_peek();
 				self.parse_analyze().map(Statement::Analyze)
@@ -138,10 +246,19 @@ impl Parser<'_> {
 				self.pop_peek();
 				ctx.run(|ctx| self.parse_delete_stmt(ctx)).await.map(Statement::Delete)
 			}
+			t!("DRY") if self.peek_token_at(1).kind == t!("RUN") => {
+				self.pop_peek();
+				self.pop_peek();
+				ctx.run(|ctx| self.parse_dry_run_stmt(ctx)).await.map(Statement::DryRun)
+			}
 			t!("FOR") => {
 				self.pop_peek();
 				ctx.run(|ctx| self.parse_for_stmt(ctx)).await.map(Statement::Foreach)
 			}
+			t!("GRANT") => {
+				self.pop_peek();
+				self.parse_grant_stmt().map(Statement::Grant)
+			}
 			t!("IF") => {
 				self.pop_peek();
 				ctx.run(|ctx| self.parse_if_stmt(ctx)).await.map(Statement::Ifelse)
@@ -182,6 +299,10 @@ impl Parser<'_> {
 				self.pop_peek();
 				self.parse_remove_stmt().map(Statement::Remove)
 			}
+			t!("REPORT") => {
+				self.pop_peek();
+				self.parse_report_stmt().map(Statement::Report)
+			}
 			t!("SELECT") => {
 				self.pop_peek();
 				ctx.run(|ctx| self.parse_select_stmt(ctx)).await.map(Statement::Select)
@@ -359,7 +480,39 @@ impl Parser<'_> {
 		if let t!("TRANSACTION") = self.peek().kind {
 			self.next();
 		}
-		Ok(BeginStatement)
+		let isolation = self.try_parse_isolation()?;
+		Ok(BeginStatement {
+			isolation,
+		})
+	}
+
+	/// Parses the isolation level of a transaction.
+	///
+	/// The words of the isolation levels are not keywords, so they are parsed as identifiers.
+	fn try_parse_isolation(&mut self) -> ParseResult<Option<Isolation>> {
+		if self.peek_kind() != TokenKind::Identifier {
+			return Ok(None);
+		}
+		if self.next_word("`ISOLATION`")? != "ISOLATION" {
+			unexpected!(self, TokenKind::Identifier, "`ISOLATION`");
+		}
+		let expected = "`SERIALIZABLE`, `SNAPSHOT` or `READ COMMITTED`";
+		let isolation = match self.next_word(expected)?.as_str() {
+			"SERIALIZABLE" => Isolation::Serializable,
+			"SNAPSHOT" => Isolation::Snapshot,
+			"READ" if self.next_word("`COMMITTED`")? == "COMMITTED" => Isolation::ReadCommitted,
+			"READ" => unexpected!(self, TokenKind::Identifier, "`COMMITTED`"),
+			_ => unexpected!(self, TokenKind::Identifier, expected),
+		};
+		Ok(Some(isolation))
+	}
+
+	/// Parses an identifier which is used as a word of a statement, in upper case.
+	fn next_word(&mut self, expected: &'static str) -> ParseResult<String> {
+		match self.peek_kind() {
+			TokenKind::Identifier => Ok(self.next_token_value::<Ident>()?.to_ascii_uppercase()),
+			x => unexpected!(self, x, expected),
+		}
 	}
 
 	/// Parsers a cancel statement.
@@ -428,6 +581,67 @@ impl Parser<'_> {
 		})
 	}
 
+	/// Parsers an ALTER statement.
+	///
+	/// # Parser State
+	/// Expects `ALTER` to already be consumed.
+	pub(crate) async fn parse_alter_stmt(&mut self, ctx: &mut Stk) -> ParseResult<AlterStatement> {
+		let res = match self.next().kind {
+			t!("FIELD") => {
+				let if_exists = if self.eat(t!("IF")) {
+					expected!(self, t!("EXISTS"));
+					true
+				} else {
+					false
+				};
+				let name = self.parse_local_idiom()?;
+				expected!(self, t!("ON"));
+				self.eat(t!("TABLE"));
+				let what = self.next_token_value()?;
+				expected!(self, t!("TYPE"));
+				let kind = ctx.run(|ctx| self.parse_inner_kind(ctx)).await?;
+				let backfill = self.eat(t!("BACKFILL"));
+				let with = if backfill && self.eat(t!("WITH")) {
+					Some(ctx.run(|ctx| self.parse_value(ctx)).await?)
+				} else {
+					None
+				};
+				AlterStatement::Field(AlterFieldStatement {
+					name,
+					what,
+					if_exists,
+					kind,
+					backfill,
+					with,
+				})
+			}
+			x => unexpected!(self, x, "an alter statement keyword"),
+		};
+		Ok(res)
+	}
+
+	/// Parsers a GRANT statement.
+	///
+	/// # Parser State
+	/// Expects `GRANT` to already be consumed.
+	pub(crate) fn parse_grant_stmt(&mut self) -> ParseResult<GrantStatement> {
+		expected!(self, t!("ROLE"));
+		let role = self.next_token_value()?;
+		expected!(self, t!("TO"));
+		expected!(self, t!("USER"));
+		let user = self.next_token_value()?;
+		let base = if self.eat(t!("ON")) {
+			self.parse_base(false)?
+		} else {
+			Base::Db
+		};
+		Ok(GrantStatement {
+			role,
+			user,
+			base,
+		})
+	}
+
 	/// Parsers a INFO statement.
 	///
 	/// # Parser State
@@ -444,13 +658,24 @@ impl Parser<'_> {
 			}
 			t!("TABLE") => {
 				let ident = self.next_token_value()?;
-				InfoStatement::Tb(ident, false)
+				let stats = self.eat(t!("WITH"));
+				if stats {
+					expected!(self, t!("STATS"));
+				}
+				InfoStatement::Tb(ident, false, stats)
 			}
 			t!("USER") => {
 				let ident = self.next_token_value()?;
 				let base = self.eat(t!("ON")).then(|| self.parse_base(false)).transpose()?;
 				InfoStatement::User(ident, base, false)
 			}
+			t!("ADVISOR") => InfoStatement::Advisor,
+			t!("HISTORY") => {
+				expected!(self, t!("OF"));
+				expected!(self, t!("TABLE"));
+				let ident = self.next_token_value()?;
+				InfoStatement::History(ident)
+			}
 			x => unexpected!(self, x, "an info target"),
 		};
 
@@ -466,6 +691,17 @@ impl Parser<'_> {
 	/// # Parser State
 	/// Expects `KILL` to already be consumed.
 	pub(crate) fn parse_kill_stmt(&mut self) -> ParseResult<KillStatement> {
+		let kind = match self.peek_kind() {
+			t!("SESSION") => {
+				self.pop_peek();
+				KillKind::Session
+			}
+			t!("QUERY") => {
+				self.pop_peek();
+				KillKind::Query
+			}
+			_ => KillKind::LiveQuery,
+		};
 		let id = match self.peek_kind() {
 			TokenKind::Uuid => self.next_token_value().map(Value::Uuid)?,
 			t!("$param") => {
@@ -477,6 +713,7 @@ impl Parser<'_> {
 		};
 		Ok(KillStatement {
 			id,
+			kind,
 		})
 	}
 
@@ -485,6 +722,9 @@ impl Parser<'_> {
 	/// # Parser State
 	/// Expects `LIVE` to already be consumed.
 	pub(crate) async fn parse_live_stmt(&mut self, stk: &mut Stk) -> ParseResult<LiveStatement> {
+		if self.eat(t!("COUNT")) {
+			return self.parse_live_count_stmt(stk).await;
+		}
 		expected!(self, t!("SELECT"));
 
 		let expr = match self.peek_kind() {
@@ -500,9 +740,72 @@ impl Parser<'_> {
 			_ => Value::Table(self.next_token_value()?),
 		};
 		let cond = self.try_parse_condition(stk).await?;
+		let when = if self.eat(t!("WHEN")) {
+			Some(self.parse_live_actions()?)
+		} else {
+			None
+		};
+		let window = if self.eat(t!("WINDOW")) {
+			Some(self.parse_window()?)
+		} else {
+			None
+		};
+		let compact = if self.eat(t!("COMPACT")) {
+			Some(self.next_token_value()?)
+		} else {
+			None
+		};
 		let fetch = self.try_parse_fetch(stk).await?;
 
-		Ok(LiveStatement::from_source_parts(expr, what, cond, fetch))
+		Ok(LiveStatement::from_source_parts(expr, what, cond, fetch, window, when, compact))
+	}
+
+	/// Parsers a LIVE statement which counts its records.
+	///
+	/// # Parser State
+	/// Expects `LIVE COUNT` to already be consumed.
+	async fn parse_live_count_stmt(&mut self, stk: &mut Stk) -> ParseResult<LiveStatement> {
+		expected!(self, t!("FROM"));
+		let what = match self.peek().kind {
+			t!("$param") => Value::Param(self.next_token_value()?),
+			_ => Value::Table(self.next_token_value()?),
+		};
+		let cond = self.try_parse_condition(stk).await?;
+		Ok(LiveStatement {
+			count: true,
+			..LiveStatement::from_source_parts(
+				Fields::default(),
+				what,
+				cond,
+				None,
+				None,
+				None,
+				None,
+			)
+		})
+	}
+
+	/// Parsers the list of record changes in the WHEN clause of a LIVE statement.
+	///
+	/// # Parser State
+	/// Expects `WHEN` to already be consumed.
+	fn parse_live_actions(&mut self) -> ParseResult<Vec<LiveAction>> {
+		let mut actions = Vec::new();
+		loop {
+			let action = match self.next().kind {
+				t!("CREATE") => LiveAction::Create,
+				t!("UPDATE") => LiveAction::Update,
+				t!("DELETE") => LiveAction::Delete,
+				x => unexpected!(self, x, "'CREATE', 'UPDATE', or 'DELETE'"),
+			};
+			if !actions.contains(&action) {
+				actions.push(action);
+			}
+			if !self.eat(t!(",")) {
+				break;
+			}
+		}
+		Ok(actions)
 	}
 
 	/// Parsers a OPTION statement.
@@ -551,6 +854,52 @@ impl Parser<'_> {
 		Ok(res)
 	}
 
+	/// Parsers a REPORT statement.
+	///
+	/// # Parser State
+	/// Expects `REPORT` to already be consumed.
+	pub fn parse_report_stmt(&mut self) -> ParseResult<ReportStatement> {
+		let res = match self.next().kind {
+			t!("REFERENCES") => {
+				let what = if self.eat(t!("ON")) {
+					let mut what = vec![self.next_token_value()?];
+					while self.eat(t!(",")) {
+						what.push(self.next_token_value()?);
+					}
+					Tables(what)
+				} else {
+					Tables::default()
+				};
+				ReportStatement::References(ReportReferencesStatement {
+					what,
+				})
+			}
+			x => unexpected!(self, x, "a report statement keyword"),
+		};
+		Ok(res)
+	}
+
+	/// Parsers a DRY RUN statement.
+	///
+	/// # Parser State
+	/// Expects `DRY RUN` to already be consumed.
+	pub(crate) async fn parse_dry_run_stmt(
+		&mut self,
+		ctx: &mut Stk,
+	) -> ParseResult<DryRunStatement> {
+		let stmt = match self.next().kind {
+			t!("CREATE") => Statement::Create(ctx.run(|ctx| self.parse_create_stmt(ctx)).await?),
+			t!("UPDATE") => Statement::Update(ctx.run(|ctx| self.parse_update_stmt(ctx)).await?),
+			t!("DELETE") => Statement::Delete(ctx.run(|ctx| self.parse_delete_stmt(ctx)).await?),
+			t!("INSERT") => Statement::Insert(ctx.run(|ctx| self.parse_insert_stmt(ctx)).await?),
+			t!("RELATE") => Statement::Relate(ctx.run(|ctx| self.parse_relate_stmt(ctx)).await?),
+			x => unexpected!(self, x, "a CREATE, UPDATE, DELETE, INSERT, or RELATE statement"),
+		};
+		Ok(DryRunStatement {
+			stmt: Box::new(stmt),
+		})
+	}
+
 	/// Parsers a RETURN statement.
 	///
 	/// # Parser State
@@ -1,14 +1,15 @@
 //! Contains parsing code for smaller common parts of statements.
 
 use reblessive::Stk;
+use std::collections::BTreeMap;
 
 use crate::sql::index::VectorType;
 use crate::syn::token::VectorTypeKind;
 use crate::{
 	sql::{
 		change_feed_include::ChangeFeedInclude, changefeed::ChangeFeed, index::Distance, Base,
-		Cond, Data, Duration, Fetch, Fetchs, Field, Fields, Group, Groups, Ident, Idiom, Output,
-		Permission, Permissions, Tables, Timeout, Value, View,
+		Cond, Data, Duration, Fetch, Fetchs, Field, Fields, Group, Groups, Ident, Idiom, Labels,
+		Output, Partition, Permission, Permissions, Strand, Tables, Timeout, Value, View, Window,
 	},
 	syn::{
 		parser::{
@@ -91,6 +92,10 @@ impl Parser<'_> {
 				self.pop_peek();
 				Output::Before
 			}
+			t!("COUNT") if self.peek_token_at(1).kind != t!("(") => {
+				self.pop_peek();
+				Output::Count
+			}
 			_ => Output::Fields(self.parse_fields(ctx).await?),
 		};
 		Ok(Some(res))
@@ -337,6 +342,47 @@ impl Parser<'_> {
 		}
 	}
 
+	/// Parses the labels of a definition, i.e. `{ env: 'production' }`
+	///
+	/// # Parser State
+	/// Expects the parser to have already eating the `LABELS` keyword
+	pub fn parse_labels(&mut self) -> ParseResult<Labels> {
+		let start = expected!(self, t!("{")).span;
+		let mut labels = BTreeMap::new();
+		loop {
+			if self.eat(t!("}")) {
+				break;
+			}
+			let key = self.parse_object_key()?;
+			expected!(self, t!(":"));
+			let value: Strand = self.next_token_value()?;
+			labels.insert(key, value);
+			if !self.eat(t!(",")) {
+				self.expect_closing_delimiter(t!("}"), start)?;
+				break;
+			}
+		}
+		Ok(Labels(labels))
+	}
+
+	/// Parses a partition clause of a table definition, i.e. `BY HASH(tenant) PARTITIONS 16`
+	///
+	/// # Parser State
+	/// Expects the parser to have already eaten the `PARTITION` keyword
+	pub fn parse_partition(&mut self) -> ParseResult<Partition> {
+		expected!(self, t!("BY"));
+		expected!(self, t!("HASH"));
+		let start = expected!(self, t!("(")).span;
+		let field = self.parse_local_idiom()?;
+		self.expect_closing_delimiter(t!(")"), start)?;
+		expected!(self, t!("PARTITIONS"));
+		let count = self.next_token_value()?;
+		Ok(Partition {
+			field,
+			count,
+		})
+	}
+
 	/// Parses a changefeed production
 	///
 	/// # Parser State
@@ -356,6 +402,32 @@ impl Parser<'_> {
 		})
 	}
 
+	/// Parses a window clause of a LIVE statement
+	///
+	/// # Parser State
+	/// Expects the parser to have already eaten the `WINDOW` keyword.
+	pub fn parse_window(&mut self) -> ParseResult<Window> {
+		match self.next().kind {
+			t!("TUMBLING") => {
+				let size = self.next_token_value::<Duration>()?.0;
+				Ok(Window {
+					size,
+					every: None,
+				})
+			}
+			t!("SLIDING") => {
+				let size = self.next_token_value::<Duration>()?.0;
+				expected!(self, t!("EVERY"));
+				let every = self.next_token_value::<Duration>()?.0;
+				Ok(Window {
+					size,
+					every: Some(every),
+				})
+			}
+			x => unexpected!(self, x, "either 'TUMBLING' or 'SLIDING'"),
+		}
+	}
+
 	/// Parses a view production
 	///
 	/// # Parse State
@@ -1,10 +1,11 @@
 use crate::{
 	sql::{
 		statements::{
-			remove::RemoveAnalyzerStatement, RemoveDatabaseStatement, RemoveEventStatement,
-			RemoveFieldStatement, RemoveFunctionStatement, RemoveIndexStatement,
-			RemoveNamespaceStatement, RemoveParamStatement, RemoveScopeStatement, RemoveStatement,
-			RemoveUserStatement,
+			remove::RemoveAnalyzerStatement, RemoveCheckStatement, RemoveDatabaseStatement,
+			RemoveDictionaryStatement, RemoveEnrichmentStatement, RemoveEventStatement,
+			RemoveFieldStatement, RemoveFunctionStatement, RemoveHookStatement,
+			RemoveIndexStatement, RemoveNamespaceStatement, RemoveParamStatement,
+			RemoveRoleStatement, RemoveScopeStatement, RemoveStatement, RemoveUserStatement,
 		},
 		Param,
 	},
@@ -143,6 +144,42 @@ impl Parser<'_> {
 					if_exists,
 				})
 			}
+			t!("ENRICHMENT") => {
+				let if_exists = if self.eat(t!("IF")) {
+					expected!(self, t!("EXISTS"));
+					true
+				} else {
+					false
+				};
+				let name = self.next_token_value()?;
+				expected!(self, t!("ON"));
+				self.eat(t!("TABLE"));
+				let table = self.next_token_value()?;
+
+				RemoveStatement::Enrichment(RemoveEnrichmentStatement {
+					name,
+					what: table,
+					if_exists,
+				})
+			}
+			t!("HOOK") => {
+				let if_exists = if self.eat(t!("IF")) {
+					expected!(self, t!("EXISTS"));
+					true
+				} else {
+					false
+				};
+				let name = self.next_token_value()?;
+				expected!(self, t!("ON"));
+				self.eat(t!("TABLE"));
+				let table = self.next_token_value()?;
+
+				RemoveStatement::Hook(RemoveHookStatement {
+					name,
+					what: table,
+					if_exists,
+				})
+			}
 			t!("FIELD") => {
 				let if_exists = if self.eat(t!("IF")) {
 					expected!(self, t!("EXISTS"));
@@ -193,6 +230,34 @@ impl Parser<'_> {
 					if_exists,
 				})
 			}
+			t!("DICTIONARY") => {
+				let if_exists = if self.eat(t!("IF")) {
+					expected!(self, t!("EXISTS"));
+					true
+				} else {
+					false
+				};
+				let name = self.next_token_value()?;
+
+				RemoveStatement::Dictionary(RemoveDictionaryStatement {
+					name,
+					if_exists,
+				})
+			}
+			t!("CHECK") => {
+				let if_exists = if self.eat(t!("IF")) {
+					expected!(self, t!("EXISTS"));
+					true
+				} else {
+					false
+				};
+				let name = self.next_token_value()?;
+
+				RemoveStatement::Check(RemoveCheckStatement {
+					name,
+					if_exists,
+				})
+			}
 			t!("USER") => {
 				let if_exists = if self.eat(t!("IF")) {
 					expected!(self, t!("EXISTS"));
@@ -210,6 +275,20 @@ impl Parser<'_> {
 					if_exists,
 				})
 			}
+			t!("ROLE") => {
+				let if_exists = if self.eat(t!("IF")) {
+					expected!(self, t!("EXISTS"));
+					true
+				} else {
+					false
+				};
+				let name = self.next_token_value()?;
+
+				RemoveStatement::Role(RemoveRoleStatement {
+					name,
+					if_exists,
+				})
+			}
 			x => unexpected!(self, x, "a remove statement keyword"),
 		};
 		Ok(res)
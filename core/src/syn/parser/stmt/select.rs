@@ -2,16 +2,18 @@ use reblessive::Stk;
 
 use crate::{
 	sql::{
-		statements::SelectStatement, Explain, Field, Fields, Ident, Idioms, Limit, Order, Orders,
-		Split, Splits, Start, Values, Version, With,
+		statements::SelectStatement, Consistency, Explain, Field, Fields, Ident, Idioms, Limit,
+		Order, Orders, Outfile, OutfileFormat, Sample, Split, Splits, Start, Strand, Values,
+		Version, With,
 	},
 	syn::{
+		lexer::Lexer,
 		parser::{
 			error::MissingKind,
 			mac::{expected, unexpected},
-			ParseResult, Parser,
+			ParseError, ParseErrorKind, ParseResult, Parser,
 		},
-		token::{t, Span},
+		token::{t, Span, TokenKind},
 	},
 };
 
@@ -40,11 +42,14 @@ impl Parser<'_> {
 		}
 		let what = Values(what);
 
+		let as_of = self.try_parse_as_of()?;
 		let with = self.try_parse_with()?;
+		let consistency = self.try_parse_consistency()?;
 		let cond = self.try_parse_condition(stk).await?;
 		let split = self.try_parse_split(&expr, fields_span)?;
 		let group = self.try_parse_group(&expr, fields_span)?;
 		let order = self.try_parse_orders(&expr, fields_span)?;
+		let sample = self.try_parse_sample(stk).await?;
 		let (limit, start) = if let t!("START") = self.peek_kind() {
 			let start = self.try_parse_start(stk).await?;
 			let limit = self.try_parse_limit(stk).await?;
@@ -55,9 +60,24 @@ impl Parser<'_> {
 			(limit, start)
 		};
 		let fetch = self.try_parse_fetch(stk).await?;
-		let version = self.try_parse_version()?;
+		let version = match as_of {
+			Some(v) => Some(v),
+			None => self.try_parse_version()?,
+		};
 		let timeout = self.try_parse_timeout()?;
 		let parallel = self.eat(t!("PARALLEL"));
+		let cache = if self.eat(t!("CACHE")) {
+			Some(self.next_token_value()?)
+		} else {
+			None
+		};
+		let lock = if self.eat(t!("FOR")) {
+			expected!(self, t!("UPDATE"));
+			true
+		} else {
+			false
+		};
+		let outfile = self.try_parse_outfile()?;
 		let explain = self.eat(t!("EXPLAIN")).then(|| Explain(self.eat(t!("FULL"))));
 
 		Ok(SelectStatement {
@@ -70,6 +90,7 @@ impl Parser<'_> {
 			split,
 			group,
 			order,
+			sample,
 			limit,
 			start,
 			fetch,
@@ -77,11 +98,35 @@ impl Parser<'_> {
 			timeout,
 			parallel,
 			explain,
+			outfile,
+			lock,
+			cache,
+			consistency,
 		})
 	}
 
+	fn try_parse_outfile(&mut self) -> ParseResult<Option<Outfile>> {
+		if !self.eat(t!("INTO")) {
+			return Ok(None);
+		}
+		expected!(self, t!("OUTFILE"));
+		let path = self.next_token_value::<Strand>()?.0;
+		let format = if self.eat(t!("FORMAT")) {
+			match self.next().kind {
+				t!("JSONL") => OutfileFormat::Jsonl,
+				x => unexpected!(self, x, "`JSONL`"),
+			}
+		} else {
+			OutfileFormat::default()
+		};
+		Ok(Some(Outfile {
+			path,
+			format,
+		}))
+	}
+
 	fn try_parse_with(&mut self) -> ParseResult<Option<With>> {
-		if !self.eat(t!("WITH")) {
+		if self.peek_token_at(1).kind == t!("CONSISTENCY") || !self.eat(t!("WITH")) {
 			return Ok(None);
 		}
 		let with = match self.next().kind {
@@ -102,6 +147,19 @@ impl Parser<'_> {
 		Ok(Some(with))
 	}
 
+	fn try_parse_consistency(&mut self) -> ParseResult<Option<Consistency>> {
+		if self.peek_token_at(1).kind != t!("CONSISTENCY") || !self.eat(t!("WITH")) {
+			return Ok(None);
+		}
+		self.pop_peek();
+		let consistency = match self.next().kind {
+			t!("EVENTUAL") => Consistency::Eventual,
+			t!("STRONG") => Consistency::Strong,
+			x => unexpected!(self, x, "`EVENTUAL` or `STRONG`"),
+		};
+		Ok(Some(consistency))
+	}
+
 	fn try_parse_split(
 		&mut self,
 		fields: &Fields,
@@ -215,6 +273,14 @@ impl Parser<'_> {
 		Ok(Some(Limit(value)))
 	}
 
+	async fn try_parse_sample(&mut self, ctx: &mut Stk) -> ParseResult<Option<Sample>> {
+		if !self.eat(t!("SAMPLE")) {
+			return Ok(None);
+		}
+		let value = ctx.run(|ctx| self.parse_value(ctx)).await?;
+		Ok(Some(Sample(value)))
+	}
+
 	async fn try_parse_start(&mut self, ctx: &mut Stk) -> ParseResult<Option<Start>> {
 		if !self.eat(t!("START")) {
 			return Ok(None);
@@ -224,6 +290,27 @@ impl Parser<'_> {
 		Ok(Some(Start(value)))
 	}
 
+	/// Parses the time at which records are read, such as `AS OF '2024-01-01T00:00:00Z'`,
+	/// which is an alternative to the `VERSION` clause.
+	fn try_parse_as_of(&mut self) -> ParseResult<Option<Version>> {
+		if !self.eat(t!("AS")) {
+			return Ok(None);
+		}
+		expected!(self, t!("OF"));
+		let token = self.next();
+		let time = match token.kind {
+			TokenKind::DateTime => self.token_value(token)?,
+			TokenKind::Strand => {
+				let text = self.lexer.string.take().unwrap();
+				Lexer::new(text.as_bytes())
+					.lex_only_datetime()
+					.map_err(|e| ParseError::new(ParseErrorKind::InvalidToken(e), token.span))?
+			}
+			x => unexpected!(self, x, "a datetime"),
+		};
+		Ok(Some(Version(time)))
+	}
+
 	fn try_parse_version(&mut self) -> ParseResult<Option<Version>> {
 		if !self.eat(t!("VERSION")) {
 			return Ok(None);
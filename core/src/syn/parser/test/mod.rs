@@ -1,4 +1,5 @@
 mod limit;
+mod roundtrip;
 mod stmt;
 mod streaming;
 mod value;
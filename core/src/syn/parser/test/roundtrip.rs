@@ -0,0 +1,82 @@
+use crate::{
+	sql::{
+		statements::{
+			DefineFieldStatement, DefineIndexStatement, DefineStatement, DefineTableStatement,
+			SelectStatement, UseStatement,
+		},
+		Field, Fields, Id, Ident, Idiom, Idioms, Object, Part, Statement, Table, Thing, Value,
+		Values,
+	},
+	syn,
+};
+
+/// Names which collide with keywords, or which contain characters that must be escaped
+const NAMES: &[&str] = &[
+	"person", "select", "SELECT", "from", "table", "value", "none", "null", "true", "where", "fn",
+	"if", "let", "on", "in", "out", "rand", "ulid", "uuid", "any", "type", "NaN", "Infinity", "",
+	"_", "_1", "123", "1abc", "123abc", "1e5", "0x1", "1s", "1d", "1.5", "-1", "a-b", "a b", "a`b",
+	"a\\b", "a⟩b", "a⟨b", "a'b", "a\"b", "a\nb", "a\rb", "a\tb", "é", "日本",
+];
+
+/// Check that a statement is parsed back from its display output unchanged
+fn round_trip(stm: Statement) {
+	let txt = stm.to_string();
+	let res = syn::parse(&txt).unwrap_or_else(|e| panic!("failed to parse `{txt}`: {e}"));
+	assert_eq!(res.0 .0, vec![stm], "`{txt}` did not round trip");
+}
+
+#[test]
+fn identifiers_round_trip() {
+	for name in NAMES {
+		let ident = Ident(name.to_string());
+		let field = Idiom(vec![Part::Field(ident.clone())]);
+		round_trip(Statement::Define(DefineStatement::Table(DefineTableStatement {
+			name: ident.clone(),
+			..Default::default()
+		})));
+		round_trip(Statement::Define(DefineStatement::Field(DefineFieldStatement {
+			name: Idiom(vec![Part::Field(Ident("nested".to_owned())), Part::Field(ident.clone())]),
+			what: ident.clone(),
+			..Default::default()
+		})));
+		round_trip(Statement::Define(DefineStatement::Index(DefineIndexStatement {
+			name: ident.clone(),
+			what: ident.clone(),
+			cols: Idioms(vec![field.clone()]),
+			..Default::default()
+		})));
+		round_trip(Statement::Select(SelectStatement {
+			expr: Fields(
+				vec![Field::Single {
+					expr: Value::Idiom(field.clone()),
+					alias: None,
+				}],
+				false,
+			),
+			what: Values(vec![Value::Table(Table(name.to_string()))]),
+			..Default::default()
+		}));
+		round_trip(Statement::Use(UseStatement {
+			ns: Some(name.to_string()),
+			db: Some(name.to_string()),
+		}));
+	}
+}
+
+#[test]
+fn values_round_trip() {
+	for name in NAMES {
+		round_trip(Statement::Value(Value::Thing(Thing {
+			tb: name.to_string(),
+			id: Id::Number(1),
+		})));
+		round_trip(Statement::Value(Value::Thing(Thing {
+			tb: "person".to_owned(),
+			id: Id::String(name.to_string()),
+		})));
+		let mut object = Object::default();
+		object.insert(name.to_string(), Value::from(1));
+		round_trip(Statement::Value(Value::Object(object)));
+		round_trip(Statement::Value(Value::from(*name)));
+	}
+}
@@ -7,25 +7,32 @@ use crate::{
 		language::Language,
 		statements::{
 			analyze::AnalyzeStatement, show::ShowSince, show::ShowStatement, sleep::SleepStatement,
-			BeginStatement, BreakStatement, CancelStatement, CommitStatement, ContinueStatement,
-			CreateStatement, DefineAnalyzerStatement, DefineDatabaseStatement,
-			DefineEventStatement, DefineFieldStatement, DefineFunctionStatement,
-			DefineIndexStatement, DefineNamespaceStatement, DefineParamStatement, DefineStatement,
-			DefineTableStatement, DefineTokenStatement, DeleteStatement, ForeachStatement,
-			IfelseStatement, InfoStatement, InsertStatement, KillStatement, OptionStatement,
-			OutputStatement, RelateStatement, RemoveAnalyzerStatement, RemoveDatabaseStatement,
+			AlterFieldStatement, AlterStatement, BeginStatement, BreakStatement, CancelStatement,
+			CommitStatement, ContinueStatement, CreateStatement, DefineAnalyzerStatement,
+			DefineCheckStatement, DefineDatabaseStatement, DefineDictionaryStatement,
+			DefineEnrichmentStatement, DefineEventStatement, DefineFieldStatement,
+			DefineFunctionStatement, DefineHookStatement, DefineIndexStatement,
+			DefineNamespaceStatement, DefineParamStatement, DefineRoleStatement, DefineStatement,
+			DefineTableStatement, DefineTokenStatement, DeleteStatement, DictionaryKind,
+			DryRunStatement, ForeachStatement, GrantStatement, IfelseStatement, InfoStatement,
+			InsertStatement, KillKind, KillStatement, LiveAction, OptionStatement, OutputStatement,
+			Privilege, RelateStatement, RemoveAnalyzerStatement, RemoveCheckStatement,
+			RemoveDatabaseStatement, RemoveDictionaryStatement, RemoveEnrichmentStatement,
 			RemoveEventStatement, RemoveFieldStatement, RemoveFunctionStatement,
-			RemoveIndexStatement, RemoveNamespaceStatement, RemoveParamStatement,
-			RemoveScopeStatement, RemoveStatement, RemoveTableStatement, RemoveTokenStatement,
-			RemoveUserStatement, SelectStatement, SetStatement, ThrowStatement, UpdateStatement,
-			UseStatement,
+			RemoveHookStatement, RemoveIndexStatement, RemoveNamespaceStatement,
+			RemoveParamStatement, RemoveRoleStatement, RemoveScopeStatement, RemoveStatement,
+			RemoveTableStatement, RemoveTokenStatement, RemoveUserStatement,
+			ReportReferencesStatement, ReportStatement, RoleGrant, SelectStatement, SetStatement,
+			ThrowStatement, UpdateStatement, UseStatement,
 		},
 		tokenizer::Tokenizer,
-		Algorithm, Array, Base, Block, Cond, Data, Datetime, Dir, Duration, Edges, Explain,
-		Expression, Fetch, Fetchs, Field, Fields, Future, Graph, Group, Groups, Id, Ident, Idiom,
-		Idioms, Index, Kind, Limit, Number, Object, Operator, Order, Orders, Output, Param, Part,
-		Permission, Permissions, Scoring, Split, Splits, Start, Statement, Strand, Subquery, Table,
-		TableType, Tables, Thing, Timeout, Uuid, Value, Values, Version, With,
+		Algorithm, Array, Base, Block, Cond, Consistency, Data, Datetime, Dir, Duration, Edges,
+		Explain, Expression, Fetch, Fetchs, Field, Fields, Function, Future, Graph, Group, Groups,
+		Id, Ident, Idiom, Idioms, Index, Isolation, Kind, Labels, Limit, Number, Object, Operator,
+		Order, Orders, Outfile, OutfileFormat, Output, Param, Part, Partition, Permission,
+		Permissions, Priority, Scoring, Split, Splits, Start, Statement, StatementKind, Strand,
+		Subquery, Table, TableType, Tables, Thing, Timeout, UnknownFields, Uuid, Value, Values,
+		Version, Window, With,
 	},
 	syn::parser::mac::test_parse,
 };
@@ -43,9 +50,33 @@ pub fn parse_analyze() {
 #[test]
 pub fn parse_begin() {
 	let res = test_parse!(parse_stmt, r#"BEGIN"#).unwrap();
-	assert_eq!(res, Statement::Begin(BeginStatement));
+	assert_eq!(res, Statement::Begin(BeginStatement::default()));
 	let res = test_parse!(parse_stmt, r#"BEGIN TRANSACTION"#).unwrap();
-	assert_eq!(res, Statement::Begin(BeginStatement));
+	assert_eq!(res, Statement::Begin(BeginStatement::default()));
+	let res = test_parse!(parse_stmt, r#"BEGIN TRANSACTION ISOLATION SERIALIZABLE"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Begin(BeginStatement {
+			isolation: Some(Isolation::Serializable)
+		})
+	);
+	let res = test_parse!(parse_stmt, r#"BEGIN ISOLATION snapshot"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Begin(BeginStatement {
+			isolation: Some(Isolation::Snapshot)
+		})
+	);
+	let res = test_parse!(parse_stmt, r#"BEGIN TRANSACTION ISOLATION READ COMMITTED"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Begin(BeginStatement {
+			isolation: Some(Isolation::ReadCommitted)
+		})
+	);
+	test_parse!(parse_stmt, r#"BEGIN TRANSACTION ISOLATION READ"#).unwrap_err();
+	test_parse!(parse_stmt, r#"BEGIN TRANSACTION ISOLATION REPEATABLE READ"#).unwrap_err();
+	test_parse!(parse_stmt, r#"BEGIN TRANSACTION SERIALIZABLE"#).unwrap_err();
 }
 
 #[test]
@@ -115,7 +146,11 @@ fn parse_create() {
 
 #[test]
 fn parse_define_namespace() {
-	let res = test_parse!(parse_stmt, "DEFINE NAMESPACE a COMMENT 'test'").unwrap();
+	let res = test_parse!(
+		parse_stmt,
+		"DEFINE NAMESPACE a COMMENT 'test' LABELS { env: 'production', 'cost centre': 'eng', }"
+	)
+	.unwrap();
 	assert_eq!(
 		res,
 		Statement::Define(DefineStatement::Namespace(DefineNamespaceStatement {
@@ -123,8 +158,21 @@ fn parse_define_namespace() {
 			name: Ident("a".to_string()),
 			comment: Some(Strand("test".to_string())),
 			if_not_exists: false,
+			labels: Some(Labels(
+				[
+					("env".to_owned(), Strand("production".to_owned())),
+					("cost centre".to_owned(), Strand("eng".to_owned())),
+				]
+				.into_iter()
+				.collect()
+			)),
 		}))
 	);
+	assert_eq!(
+		res.to_string(),
+		"DEFINE NAMESPACE a COMMENT 'test' LABELS { \"cost centre\": 'eng', env: 'production' }"
+	);
+	test_parse!(parse_stmt, "DEFINE NAMESPACE a LABELS { env: 1 }").unwrap_err();
 
 	let res = test_parse!(parse_stmt, "DEFINE NS a").unwrap();
 	assert_eq!(
@@ -134,6 +182,7 @@ fn parse_define_namespace() {
 			name: Ident("a".to_string()),
 			comment: None,
 			if_not_exists: false,
+			labels: None,
 		}))
 	)
 }
@@ -154,6 +203,7 @@ fn parse_define_database() {
 				store_diff: true,
 			}),
 			if_not_exists: false,
+			labels: None,
 		}))
 	);
 
@@ -166,6 +216,7 @@ fn parse_define_database() {
 			comment: None,
 			changefeed: None,
 			if_not_exists: false,
+			labels: None,
 		}))
 	)
 }
@@ -216,7 +267,33 @@ fn parse_define_user() {
 	assert_eq!(stmt.base, Base::Root);
 	assert_eq!(stmt.hash, "hunter2".to_owned());
 	assert_eq!(stmt.roles, vec![Ident("foo".to_string()), Ident("bar".to_string())]);
-	assert_eq!(stmt.comment, Some(Strand("*******".to_string())))
+	assert_eq!(stmt.comment, Some(Strand("*******".to_string())));
+	assert!(!stmt.must_change);
+
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE USER user ON ROOT PASSWORD 'hunter2' PASSWORD MUST CHANGE"#
+	)
+	.unwrap();
+
+	let Statement::Define(DefineStatement::User(stmt)) = res else {
+		panic!()
+	};
+
+	assert!(stmt.must_change);
+	assert!(stmt.hash.starts_with("$argon2id$"));
+	assert!(stmt.to_string().ends_with(" PASSWORD MUST CHANGE"));
+
+	let res =
+		test_parse!(parse_stmt, r#"DEFINE USER user ON ROOT PASSHASH 'hunter2' PRIORITY LOW"#)
+			.unwrap();
+
+	let Statement::Define(DefineStatement::User(stmt)) = res else {
+		panic!()
+	};
+
+	assert_eq!(stmt.priority, Priority::Low);
+	assert!(stmt.to_string().ends_with(" PRIORITY LOW"));
 }
 
 #[test]
@@ -243,7 +320,7 @@ fn parse_define_token() {
 fn parse_define_scope() {
 	let res = test_parse!(
 		parse_stmt,
-		r#"DEFINE SCOPE a SESSION 1s SIGNUP true SIGNIN false COMMENT "bar""#
+		r#"DEFINE SCOPE a SESSION 1s SIGNUP true SIGNIN false ALLOW SELECT, CREATE, SELECT DENY INFO COMMENT "bar""#
 	)
 	.unwrap();
 
@@ -257,6 +334,8 @@ fn parse_define_scope() {
 	assert_eq!(stmt.session, Some(Duration(std::time::Duration::from_secs(1))));
 	assert_eq!(stmt.signup, Some(Value::Bool(true)));
 	assert_eq!(stmt.signin, Some(Value::Bool(false)));
+	assert_eq!(stmt.allow, vec![StatementKind::Select, StatementKind::Create]);
+	assert_eq!(stmt.deny, vec![StatementKind::Info]);
 }
 
 #[test]
@@ -280,6 +359,107 @@ fn parse_define_param() {
 			comment: None,
 			permissions: Permission::Specific(Value::Null),
 			if_not_exists: false,
+			kind: None,
+		}))
+	);
+
+	let res = test_parse!(parse_stmt, r#"DEFINE PARAM $max_results TYPE int VALUE 100"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Param(DefineParamStatement {
+			name: Ident("max_results".to_string()),
+			value: Value::Number(Number::Int(100)),
+			comment: None,
+			permissions: Permission::Full,
+			if_not_exists: false,
+			kind: Some(Kind::Int),
+		}))
+	);
+}
+
+#[test]
+fn parse_define_role() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE ROLE analyst ON DATABASE GRANT select ON TABLE sales, reports GRANT create, update ON reports COMMENT "test""#
+	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Role(DefineRoleStatement {
+			name: Ident("analyst".to_string()),
+			grants: vec![
+				RoleGrant {
+					privileges: vec![Privilege::Select],
+					tables: vec![Ident("sales".to_string()), Ident("reports".to_string())],
+				},
+				RoleGrant {
+					privileges: vec![Privilege::Create, Privilege::Update],
+					tables: vec![Ident("reports".to_string())],
+				},
+			],
+			comment: Some(Strand("test".to_string())),
+			if_not_exists: false,
+		}))
+	);
+}
+
+#[test]
+fn parse_grant() {
+	let res = test_parse!(parse_stmt, r#"GRANT ROLE analyst TO USER bob"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Grant(GrantStatement {
+			role: Ident("analyst".to_string()),
+			user: Ident("bob".to_string()),
+			base: Base::Db,
+		})
+	);
+
+	let res = test_parse!(parse_stmt, r#"GRANT ROLE analyst TO USER bob ON NAMESPACE"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Grant(GrantStatement {
+			role: Ident("analyst".to_string()),
+			user: Ident("bob".to_string()),
+			base: Base::Ns,
+		})
+	);
+}
+
+#[test]
+fn parse_alter_field() {
+	let res = test_parse!(parse_stmt, r#"ALTER FIELD age ON user TYPE int"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Alter(AlterStatement::Field(AlterFieldStatement {
+			name: Idiom(vec![Part::Field(Ident("age".to_owned()))]),
+			what: Ident("user".to_owned()),
+			if_exists: false,
+			kind: Kind::Int,
+			backfill: false,
+			with: None,
+		}))
+	);
+
+	let res = test_parse!(
+		parse_stmt,
+		r#"ALTER FIELD IF EXISTS age ON TABLE user TYPE option<int> BACKFILL WITH <int> $value"#
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		Statement::Alter(AlterStatement::Field(AlterFieldStatement {
+			name: Idiom(vec![Part::Field(Ident("age".to_owned()))]),
+			what: Ident("user".to_owned()),
+			if_exists: true,
+			kind: Kind::Option(Box::new(Kind::Int)),
+			backfill: true,
+			with: Some(Value::Cast(Box::new(crate::sql::Cast(
+				Kind::Int,
+				Value::Param(Param(Ident("value".to_owned())))
+			)))),
 		}))
 	);
 }
@@ -287,7 +467,7 @@ fn parse_define_param() {
 #[test]
 fn parse_define_table() {
 	let res =
-		test_parse!(parse_stmt, r#"DEFINE TABLE name DROP SCHEMAFUL CHANGEFEED 1s INCLUDE ORIGINAL PERMISSIONS FOR SELECT WHERE a = 1 AS SELECT foo FROM bar GROUP BY foo"#)
+		test_parse!(parse_stmt, r#"DEFINE TABLE name DROP SCHEMAFUL UNKNOWN FIELDS QUARANTINE INTO extra WITH AUDIT FIELDS CHANGEFEED 1s INCLUDE ORIGINAL PERMISSIONS FOR SELECT WHERE a = 1 AS SELECT foo FROM bar GROUP BY foo"#)
 			.unwrap();
 
 	assert_eq!(
@@ -328,10 +508,39 @@ fn parse_define_table() {
 			comment: None,
 			if_not_exists: false,
 			kind: TableType::Any,
+			audit: true,
+			unknown: UnknownFields::Quarantine(Idiom(vec![Part::Field(Ident("extra".to_owned()))])),
+			labels: None,
+			partition: None,
 		}))
 	);
 }
 
+#[test]
+fn parse_define_table_partition() {
+	let res =
+		test_parse!(parse_stmt, "DEFINE TABLE events PARTITION BY HASH(tenant.id) PARTITIONS 16")
+			.unwrap();
+	let Statement::Define(DefineStatement::Table(tb)) = &res else {
+		panic!("expected a table definition");
+	};
+	assert_eq!(
+		tb.partition,
+		Some(Partition {
+			field: Idiom(vec![
+				Part::Field(Ident("tenant".to_owned())),
+				Part::Field(Ident("id".to_owned()))
+			]),
+			count: 16,
+		})
+	);
+	assert_eq!(res.to_string(), "DEFINE TABLE events TYPE ANY SCHEMALESS PARTITION BY HASH(tenant.id) PARTITIONS 16 PERMISSIONS NONE");
+	test_parse!(parse_stmt, "DEFINE TABLE events PARTITION BY HASH(tenant) PARTITIONS x")
+		.unwrap_err();
+	test_parse!(parse_stmt, "DEFINE TABLE events PARTITION BY RANGE(tenant) PARTITIONS 4")
+		.unwrap_err();
+}
+
 #[test]
 fn parse_define_event() {
 	let res =
@@ -385,6 +594,9 @@ fn parse_define_field() {
 			},
 			comment: None,
 			if_not_exists: false,
+			expire: false,
+			crdt: None,
+			computed: false,
 		}))
 	)
 }
@@ -476,11 +688,176 @@ fn parse_define_index() {
 	);
 }
 
+#[test]
+fn parse_define_dictionary() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE DICTIONARY english STOPWORDS ["the", "a", "an"] COMMENT "test""#
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Dictionary(DefineDictionaryStatement {
+			name: Ident("english".to_owned()),
+			kind: DictionaryKind::Stopwords(vec![
+				Strand("the".to_owned()),
+				Strand("a".to_owned()),
+				Strand("an".to_owned()),
+			]),
+			comment: Some(Strand("test".to_owned())),
+			if_not_exists: false,
+		})),
+	);
+
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE DICTIONARY IF NOT EXISTS colours SYNONYMS ["grey", "gray"], ["red", "crimson", "scarlet"]"#
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Dictionary(DefineDictionaryStatement {
+			name: Ident("colours".to_owned()),
+			kind: DictionaryKind::Synonyms(vec![
+				vec![Strand("grey".to_owned()), Strand("gray".to_owned())],
+				vec![
+					Strand("red".to_owned()),
+					Strand("crimson".to_owned()),
+					Strand("scarlet".to_owned()),
+				],
+			]),
+			comment: None,
+			if_not_exists: true,
+		})),
+	);
+}
+
+#[test]
+fn parse_define_check() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE CHECK orphan_edges SCHEDULE '1h' AS SELECT count() FROM likes ASSERT $value = 0 WEBHOOK "https://example.com/alerts" COMMENT "test""#
+	)
+	.unwrap();
+	let Statement::Define(DefineStatement::Check(stm)) = res else {
+		panic!("expected a DEFINE CHECK statement");
+	};
+	assert_eq!(stm.name, Ident("orphan_edges".to_owned()));
+	assert_eq!(stm.schedule, Duration(std::time::Duration::from_secs(3600)));
+	assert_eq!(stm.query.to_string(), "(SELECT count() FROM likes)");
+	assert_eq!(stm.assert.to_string(), "$value = 0");
+	assert_eq!(stm.webhook, Some(Strand("https://example.com/alerts".to_owned())));
+	assert_eq!(stm.comment, Some(Strand("test".to_owned())));
+	assert!(!stm.if_not_exists);
+
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE CHECK IF NOT EXISTS stock SCHEDULE 30m AS 1 ASSERT true"#
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Check(DefineCheckStatement {
+			name: Ident("stock".to_owned()),
+			schedule: Duration(std::time::Duration::from_secs(1800)),
+			query: Value::Number(Number::Int(1)),
+			assert: Value::Bool(true),
+			webhook: None,
+			comment: None,
+			if_not_exists: true,
+		})),
+	);
+
+	test_parse!(parse_stmt, r#"DEFINE CHECK stock SCHEDULE 'soon' AS 1 ASSERT true"#).unwrap_err();
+}
+
+#[test]
+fn parse_define_hook() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE HOOK ON TABLE products AFTER COMMIT PUBLISH 'cache:invalidate'"#
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Hook(DefineHookStatement {
+			name: Ident("cache:invalidate".to_owned()),
+			what: Ident("products".to_owned()),
+			channel: Strand("cache:invalidate".to_owned()),
+			comment: None,
+			if_not_exists: false,
+		})),
+	);
+
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE HOOK IF NOT EXISTS prices ON products AFTER COMMIT PUBLISH "pricing" COMMENT "test""#
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Hook(DefineHookStatement {
+			name: Ident("prices".to_owned()),
+			what: Ident("products".to_owned()),
+			channel: Strand("pricing".to_owned()),
+			comment: Some(Strand("test".to_owned())),
+			if_not_exists: true,
+		})),
+	);
+
+	test_parse!(parse_stmt, r#"DEFINE HOOK ON products PUBLISH 'cache'"#).unwrap_err();
+}
+
+#[test]
+fn parse_define_enrichment() {
+	let res =
+		test_parse!(parse_stmt, r#"DEFINE ENRICHMENT ON TABLE leads CALL fn::score($after) ASYNC"#)
+			.unwrap();
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Enrichment(DefineEnrichmentStatement {
+			name: Ident("score".to_owned()),
+			what: Ident("leads".to_owned()),
+			call: Value::Function(Box::new(Function::Custom(
+				"score".to_owned(),
+				vec![Value::Param(Param(Ident("after".to_owned())))]
+			))),
+			into: None,
+			retry: None,
+			comment: None,
+			if_not_exists: false,
+		})),
+	);
+
+	let res = test_parse!(
+		parse_stmt,
+		r#"DEFINE ENRICHMENT IF NOT EXISTS rating ON leads CALL fn::score($after) INTO score ASYNC RETRY 5 COMMENT "test""#
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		Statement::Define(DefineStatement::Enrichment(DefineEnrichmentStatement {
+			name: Ident("rating".to_owned()),
+			what: Ident("leads".to_owned()),
+			call: Value::Function(Box::new(Function::Custom(
+				"score".to_owned(),
+				vec![Value::Param(Param(Ident("after".to_owned())))]
+			))),
+			into: Some(Idiom(vec![Part::Field(Ident("score".to_owned()))])),
+			retry: Some(5),
+			comment: Some(Strand("test".to_owned())),
+			if_not_exists: true,
+		})),
+	);
+
+	test_parse!(parse_stmt, r#"DEFINE ENRICHMENT ON leads CALL fn::score($after)"#).unwrap_err();
+}
+
 #[test]
 fn parse_define_analyzer() {
 	let res = test_parse!(
 		parse_stmt,
-		r#"DEFINE ANALYZER ana FILTERS ASCII, EDGENGRAM(1,2), NGRAM(3,4), LOWERCASE, SNOWBALL(NLD), UPPERCASE TOKENIZERS BLANK, CAMEL, CLASS, PUNCT FUNCTION fn::foo::bar"#
+		r#"DEFINE ANALYZER ana FILTERS ASCII, EDGENGRAM(1,2), NGRAM(3,4), LOWERCASE, SNOWBALL(NLD), UPPERCASE, STOPWORDS(english), SYNONYMS(colours) TOKENIZERS BLANK, CAMEL, CLASS, PUNCT FUNCTION fn::foo::bar"#
 	).unwrap();
 
 	assert_eq!(
@@ -500,6 +877,8 @@ fn parse_define_analyzer() {
 				Filter::Lowercase,
 				Filter::Snowball(Language::Dutch),
 				Filter::Uppercase,
+				Filter::Stopwords(Ident("english".to_owned())),
+				Filter::Synonyms(Ident("colours".to_owned())),
 			]),
 			comment: None,
 			function: Some(Ident("foo::bar".to_string())),
@@ -560,6 +939,23 @@ fn parse_delete_2() {
 	)
 }
 
+#[test]
+fn parse_dry_run() {
+	let res = test_parse!(parse_stmt, r#"DRY RUN DELETE person RETURN NONE"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::DryRun(DryRunStatement {
+			stmt: Box::new(Statement::Delete(DeleteStatement {
+				what: Values(vec![Value::Table(Table("person".to_owned()))]),
+				output: Some(Output::None),
+				..Default::default()
+			})),
+		})
+	);
+	assert_eq!(res.to_string(), "DRY RUN DELETE person RETURN NONE");
+	test_parse!(parse_stmt, r#"DRY RUN SELECT * FROM person"#).unwrap_err();
+}
+
 #[test]
 pub fn parse_for() {
 	let res = test_parse!(
@@ -662,7 +1058,13 @@ fn parse_info() {
 	assert_eq!(res, Statement::Info(InfoStatement::Sc(Ident("scope".to_owned()), false)));
 
 	let res = test_parse!(parse_stmt, "INFO FOR TABLE table").unwrap();
-	assert_eq!(res, Statement::Info(InfoStatement::Tb(Ident("table".to_owned()), false)));
+	assert_eq!(res, Statement::Info(InfoStatement::Tb(Ident("table".to_owned()), false, false)));
+
+	let res = test_parse!(parse_stmt, "INFO FOR TABLE table WITH STATS").unwrap();
+	assert_eq!(res, Statement::Info(InfoStatement::Tb(Ident("table".to_owned()), false, true)));
+
+	let res = test_parse!(parse_stmt, "INFO FOR TABLE table WITH STATS STRUCTURE").unwrap();
+	assert_eq!(res, Statement::Info(InfoStatement::Tb(Ident("table".to_owned()), true, true)));
 
 	let res = test_parse!(parse_stmt, "INFO FOR USER user").unwrap();
 	assert_eq!(res, Statement::Info(InfoStatement::User(Ident("user".to_owned()), None, false)));
@@ -672,6 +1074,12 @@ fn parse_info() {
 		res,
 		Statement::Info(InfoStatement::User(Ident("user".to_owned()), Some(Base::Ns), false))
 	);
+
+	let res = test_parse!(parse_stmt, "INFO FOR ADVISOR").unwrap();
+	assert_eq!(res, Statement::Info(InfoStatement::Advisor));
+
+	let res = test_parse!(parse_stmt, "INFO FOR HISTORY OF TABLE user").unwrap();
+	assert_eq!(res, Statement::Info(InfoStatement::History(Ident("user".to_owned()))));
 }
 
 #[test]
@@ -749,6 +1157,7 @@ SELECT bar as foo,[1,2],bar OMIT bar FROM ONLY a,1
 				numeric: true,
 				direction: true,
 			}])),
+			sample: None,
 			limit: Some(Limit(Value::Thing(Thing {
 				tb: "a".to_owned(),
 				id: Id::String("b".to_owned()),
@@ -761,10 +1170,121 @@ SELECT bar as foo,[1,2],bar OMIT bar FROM ONLY a,1
 			timeout: None,
 			parallel: false,
 			explain: Some(Explain(true)),
+			outfile: None,
+			lock: false,
+			cache: None,
+			consistency: None,
+		}),
+	);
+}
+
+#[test]
+fn parse_select_outfile() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"SELECT * FROM a INTO OUTFILE "s3://bucket/path.jsonl" FORMAT jsonl"#
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		Statement::Select(SelectStatement {
+			expr: Fields(vec![Field::All], false),
+			what: Values(vec![Value::Table(Table("a".to_owned()))]),
+			outfile: Some(Outfile {
+				path: "s3://bucket/path.jsonl".to_owned(),
+				format: OutfileFormat::Jsonl,
+			}),
+			..Default::default()
+		}),
+	);
+}
+
+#[test]
+fn parse_select_for_update() {
+	let res = test_parse!(parse_stmt, r#"SELECT * FROM a WHERE true FOR UPDATE"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Select(SelectStatement {
+			expr: Fields(vec![Field::All], false),
+			what: Values(vec![Value::Table(Table("a".to_owned()))]),
+			cond: Some(Cond(Value::Bool(true))),
+			lock: true,
+			..Default::default()
 		}),
 	);
 }
 
+#[test]
+fn parse_select_consistency() {
+	let res = test_parse!(
+		parse_stmt,
+		"SELECT * FROM a WITH NOINDEX WITH CONSISTENCY EVENTUAL WHERE true"
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		Statement::Select(SelectStatement {
+			expr: Fields(vec![Field::All], false),
+			what: Values(vec![Value::Table(Table("a".to_owned()))]),
+			with: Some(With::NoIndex),
+			consistency: Some(Consistency::Eventual),
+			cond: Some(Cond(Value::Bool(true))),
+			..Default::default()
+		}),
+	);
+	assert_eq!(
+		res.to_string(),
+		"SELECT * FROM a WITH NOINDEX WITH CONSISTENCY EVENTUAL WHERE true"
+	);
+	let res = test_parse!(parse_stmt, "SELECT * FROM a WITH CONSISTENCY STRONG").unwrap();
+	assert_eq!(
+		res,
+		Statement::Select(SelectStatement {
+			expr: Fields(vec![Field::All], false),
+			what: Values(vec![Value::Table(Table("a".to_owned()))]),
+			consistency: Some(Consistency::Strong),
+			..Default::default()
+		}),
+	);
+	test_parse!(parse_stmt, "SELECT * FROM a WITH CONSISTENCY LINEARIZABLE").unwrap_err();
+}
+
+#[test]
+fn parse_select_cache() {
+	let res = test_parse!(parse_stmt, r#"SELECT * FROM a WHERE true CACHE 30s"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Select(SelectStatement {
+			expr: Fields(vec![Field::All], false),
+			what: Values(vec![Value::Table(Table("a".to_owned()))]),
+			cond: Some(Cond(Value::Bool(true))),
+			cache: Some(Duration(std::time::Duration::from_secs(30))),
+			..Default::default()
+		}),
+	);
+}
+
+#[test]
+fn parse_select_as_of() {
+	let expected = Statement::Select(SelectStatement {
+		expr: Fields(vec![Field::All], false),
+		what: Values(vec![Value::Table(Table("a".to_owned()))]),
+		cond: Some(Cond(Value::Bool(true))),
+		version: Some(Version(Datetime(Utc.from_utc_datetime(
+			&NaiveDate::from_ymd_opt(2024, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap(),
+		)))),
+		..Default::default()
+	});
+	let res = test_parse!(parse_stmt, r#"SELECT * FROM a AS OF '2024-01-01T00:00:00Z' WHERE true"#)
+		.unwrap();
+	assert_eq!(res, expected);
+	let res =
+		test_parse!(parse_stmt, r#"SELECT * FROM a AS OF d'2024-01-01T00:00:00Z' WHERE true"#)
+			.unwrap();
+	assert_eq!(res, expected);
+	test_parse!(parse_stmt, r#"SELECT * FROM a AS OF 'yesterday'"#).unwrap_err();
+}
+
 #[test]
 fn parse_let() {
 	let res = test_parse!(parse_stmt, r#"LET $param = 1"#).unwrap();
@@ -786,6 +1306,25 @@ fn parse_let() {
 	);
 }
 
+#[test]
+fn parse_report() {
+	let res = test_parse!(parse_stmt, r#"REPORT REFERENCES"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Report(ReportStatement::References(ReportReferencesStatement {
+			what: Tables::default(),
+		}))
+	);
+	let res = test_parse!(parse_stmt, r#"REPORT REFERENCES ON person, likes"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Report(ReportStatement::References(ReportReferencesStatement {
+			what: Tables(vec![Table("person".to_owned()), Table("likes".to_owned())]),
+		}))
+	);
+	assert_eq!(res.to_string(), "REPORT REFERENCES ON person, likes");
+}
+
 #[test]
 fn parse_show() {
 	let res = test_parse!(parse_stmt, r#"SHOW CHANGES FOR TABLE foo SINCE 1 LIMIT 10"#).unwrap();
@@ -943,13 +1482,42 @@ fn parse_insert() {
 	)
 }
 
+#[test]
+fn parse_insert_select() {
+	let res = test_parse!(
+		parse_stmt,
+		r#"INSERT INTO archive SELECT * FROM orders WHERE created < $cutoff RETURN COUNT"#
+	)
+	.unwrap();
+	let Statement::Insert(stmt) = res else {
+		panic!("expected an insert statement")
+	};
+	assert_eq!(stmt.into, Value::Table(Table("archive".to_owned())));
+	let Data::SingleExpression(Value::Subquery(v)) = stmt.data else {
+		panic!("expected a subquery")
+	};
+	let Subquery::Select(v) = *v else {
+		panic!("expected a select statement")
+	};
+	assert_eq!(v.what, Values(vec![Value::Table(Table("orders".to_owned()))]));
+	assert!(v.cond.is_some());
+	assert_eq!(stmt.output, Some(Output::Count));
+	// A call to the count function is still a field
+	let res = test_parse!(parse_stmt, r#"INSERT INTO archive {} RETURN count()"#).unwrap();
+	let Statement::Insert(stmt) = res else {
+		panic!("expected an insert statement")
+	};
+	assert!(matches!(stmt.output, Some(Output::Fields(_))));
+}
+
 #[test]
 fn parse_kill() {
 	let res = test_parse!(parse_stmt, r#"KILL $param"#).unwrap();
 	assert_eq!(
 		res,
 		Statement::Kill(KillStatement {
-			id: Value::Param(Param(Ident("param".to_owned())))
+			id: Value::Param(Param(Ident("param".to_owned()))),
+			kind: KillKind::LiveQuery,
 		})
 	);
 
@@ -957,7 +1525,28 @@ fn parse_kill() {
 	assert_eq!(
 		res,
 		Statement::Kill(KillStatement {
-			id: Value::Uuid(Uuid(uuid::uuid!("e72bee20-f49b-11ec-b939-0242ac120002")))
+			id: Value::Uuid(Uuid(uuid::uuid!("e72bee20-f49b-11ec-b939-0242ac120002"))),
+			kind: KillKind::LiveQuery,
+		})
+	);
+
+	let res = test_parse!(parse_stmt, r#"KILL SESSION u"e72bee20-f49b-11ec-b939-0242ac120002" "#)
+		.unwrap();
+	assert_eq!(
+		res,
+		Statement::Kill(KillStatement {
+			id: Value::Uuid(Uuid(uuid::uuid!("e72bee20-f49b-11ec-b939-0242ac120002"))),
+			kind: KillKind::Session,
+		})
+	);
+
+	let res =
+		test_parse!(parse_stmt, r#"KILL QUERY u"e72bee20-f49b-11ec-b939-0242ac120002" "#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Kill(KillStatement {
+			id: Value::Uuid(Uuid(uuid::uuid!("e72bee20-f49b-11ec-b939-0242ac120002"))),
+			kind: KillKind::Query,
 		})
 	);
 }
@@ -998,7 +1587,75 @@ fn parse_live() {
 			])),
 			Fetch(Idiom(vec![Part::Field(Ident("b".to_owned()))])),
 		])),
+	);
+
+	let res =
+		test_parse!(parse_stmt, r#"LIVE SELECT count() FROM clicks WINDOW TUMBLING 1m"#).unwrap();
+	let Statement::Live(stmt) = res else {
+		panic!()
+	};
+	assert_eq!(
+		stmt.window,
+		Some(Window {
+			size: std::time::Duration::from_secs(60),
+			every: None,
+		})
+	);
+
+	let res = test_parse!(
+		parse_stmt,
+		r#"LIVE SELECT count() FROM clicks WHERE ok = true WINDOW SLIDING 5m EVERY 1m FETCH a"#
 	)
+	.unwrap();
+	let Statement::Live(stmt) = res else {
+		panic!()
+	};
+	assert_eq!(
+		stmt.window,
+		Some(Window {
+			size: std::time::Duration::from_secs(300),
+			every: Some(std::time::Duration::from_secs(60)),
+		})
+	);
+	assert_eq!(
+		stmt.to_string(),
+		"LIVE SELECT count() FROM clicks WHERE ok = true WINDOW SLIDING 5m EVERY 1m FETCH a"
+	);
+
+	test_parse!(parse_stmt, r#"LIVE SELECT count() FROM clicks WINDOW 1m"#).unwrap_err();
+	test_parse!(parse_stmt, r#"LIVE SELECT count() FROM clicks WINDOW SLIDING 5m"#).unwrap_err();
+
+	let res = test_parse!(
+		parse_stmt,
+		r#"LIVE SELECT name FROM person WHERE age > 18 WHEN CREATE, DELETE, CREATE"#
+	)
+	.unwrap();
+	let Statement::Live(stmt) = res else {
+		panic!()
+	};
+	assert_eq!(stmt.when, Some(vec![LiveAction::Create, LiveAction::Delete]));
+	assert_eq!(stmt.to_string(), "LIVE SELECT name FROM person WHERE age > 18 WHEN CREATE, DELETE");
+
+	test_parse!(parse_stmt, r#"LIVE SELECT * FROM person WHEN SELECT"#).unwrap_err();
+
+	let res =
+		test_parse!(parse_stmt, r#"LIVE SELECT * FROM person WHEN UPDATE COMPACT 50ms"#).unwrap();
+	let Statement::Live(stmt) = res else {
+		panic!()
+	};
+	assert_eq!(stmt.compact, Some(Duration(std::time::Duration::from_millis(50))));
+	assert_eq!(stmt.to_string(), "LIVE SELECT * FROM person WHEN UPDATE COMPACT 50ms");
+
+	test_parse!(parse_stmt, r#"LIVE SELECT * FROM person COMPACT"#).unwrap_err();
+
+	let res = test_parse!(parse_stmt, r#"LIVE COUNT FROM orders WHERE status = 'open'"#).unwrap();
+	let Statement::Live(stmt) = res else {
+		panic!()
+	};
+	assert!(stmt.count);
+	assert_eq!(stmt.expr, Fields::default());
+	assert_eq!(stmt.what, Value::Table(Table("orders".to_owned())));
+	assert_eq!(stmt.to_string(), "LIVE COUNT FROM orders WHERE status = 'open'");
 }
 
 #[test]
@@ -1184,6 +1841,44 @@ fn parse_remove() {
 		}))
 	);
 
+	let res = test_parse!(parse_stmt, r#"REMOVE DICTIONARY IF EXISTS foo"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Remove(RemoveStatement::Dictionary(RemoveDictionaryStatement {
+			name: Ident("foo".to_owned()),
+			if_exists: true,
+		}))
+	);
+
+	let res = test_parse!(parse_stmt, r#"REMOVE CHECK IF EXISTS foo"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Remove(RemoveStatement::Check(RemoveCheckStatement {
+			name: Ident("foo".to_owned()),
+			if_exists: true,
+		}))
+	);
+
+	let res = test_parse!(parse_stmt, r#"REMOVE ENRICHMENT rating ON leads"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Remove(RemoveStatement::Enrichment(RemoveEnrichmentStatement {
+			name: Ident("rating".to_owned()),
+			what: Ident("leads".to_owned()),
+			if_exists: false,
+		}))
+	);
+
+	let res = test_parse!(parse_stmt, r#"REMOVE HOOK IF EXISTS prices ON TABLE products"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Remove(RemoveStatement::Hook(RemoveHookStatement {
+			name: Ident("prices".to_owned()),
+			what: Ident("products".to_owned()),
+			if_exists: true,
+		}))
+	);
+
 	let res = test_parse!(parse_stmt, r#"REMOVE user foo on database"#).unwrap();
 	assert_eq!(
 		res,
@@ -1193,6 +1888,14 @@ fn parse_remove() {
 			if_exists: false,
 		}))
 	);
+	let res = test_parse!(parse_stmt, r#"REMOVE ROLE IF EXISTS analyst"#).unwrap();
+	assert_eq!(
+		res,
+		Statement::Remove(RemoveStatement::Role(RemoveRoleStatement {
+			name: Ident("analyst".to_owned()),
+			if_exists: true,
+		}))
+	);
 }
 
 #[test]
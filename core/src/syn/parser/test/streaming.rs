@@ -12,16 +12,16 @@ use crate::{
 			DefineEventStatement, DefineFieldStatement, DefineFunctionStatement,
 			DefineIndexStatement, DefineNamespaceStatement, DefineParamStatement, DefineStatement,
 			DefineTableStatement, DefineTokenStatement, DeleteStatement, ForeachStatement,
-			IfelseStatement, InfoStatement, InsertStatement, KillStatement, OutputStatement,
-			RelateStatement, RemoveFieldStatement, RemoveFunctionStatement, RemoveStatement,
-			SelectStatement, SetStatement, ThrowStatement, UpdateStatement,
+			IfelseStatement, InfoStatement, InsertStatement, KillKind, KillStatement,
+			OutputStatement, RelateStatement, RemoveFieldStatement, RemoveFunctionStatement,
+			RemoveStatement, SelectStatement, SetStatement, ThrowStatement, UpdateStatement,
 		},
 		tokenizer::Tokenizer,
 		Algorithm, Array, Base, Block, Cond, Data, Datetime, Dir, Duration, Edges, Explain,
 		Expression, Fetch, Fetchs, Field, Fields, Future, Graph, Group, Groups, Id, Ident, Idiom,
 		Idioms, Index, Kind, Limit, Number, Object, Operator, Order, Orders, Output, Param, Part,
 		Permission, Permissions, Scoring, Split, Splits, Start, Statement, Strand, Subquery, Table,
-		TableType, Tables, Thing, Timeout, Uuid, Value, Values, Version, With,
+		TableType, Tables, Thing, Timeout, UnknownFields, Uuid, Value, Values, Version, With,
 	},
 	syn::parser::{Parser, PartialResult},
 };
@@ -115,8 +115,8 @@ fn statements() -> Vec<Statement> {
 
 	vec![
 		Statement::Analyze(AnalyzeStatement::Idx(Ident("a".to_string()), Ident("b".to_string()))),
-		Statement::Begin(BeginStatement),
-		Statement::Begin(BeginStatement),
+		Statement::Begin(BeginStatement::default()),
+		Statement::Begin(BeginStatement::default()),
 		Statement::Break(BreakStatement),
 		Statement::Cancel(CancelStatement),
 		Statement::Cancel(CancelStatement),
@@ -153,12 +153,14 @@ fn statements() -> Vec<Statement> {
 			name: Ident("a".to_string()),
 			comment: Some(Strand("test".to_string())),
 			if_not_exists: false,
+			labels: None,
 		})),
 		Statement::Define(DefineStatement::Namespace(DefineNamespaceStatement {
 			id: None,
 			name: Ident("a".to_string()),
 			comment: None,
 			if_not_exists: false,
+			labels: None,
 		})),
 		Statement::Define(DefineStatement::Database(DefineDatabaseStatement {
 			id: None,
@@ -169,6 +171,7 @@ fn statements() -> Vec<Statement> {
 				store_diff: false,
 			}),
 			if_not_exists: false,
+			labels: None,
 		})),
 		Statement::Define(DefineStatement::Database(DefineDatabaseStatement {
 			id: None,
@@ -176,6 +179,7 @@ fn statements() -> Vec<Statement> {
 			comment: None,
 			changefeed: None,
 			if_not_exists: false,
+			labels: None,
 		})),
 		Statement::Define(DefineStatement::Function(DefineFunctionStatement {
 			name: Ident("foo::bar".to_string()),
@@ -212,6 +216,7 @@ fn statements() -> Vec<Statement> {
 			comment: None,
 			permissions: Permission::Specific(Value::Null),
 			if_not_exists: false,
+			kind: None,
 		})),
 		Statement::Define(DefineStatement::Table(DefineTableStatement {
 			id: None,
@@ -249,6 +254,10 @@ fn statements() -> Vec<Statement> {
 			comment: None,
 			if_not_exists: false,
 			kind: TableType::Any,
+			audit: false,
+			unknown: UnknownFields::Drop,
+			labels: None,
+			partition: None,
 		})),
 		Statement::Define(DefineStatement::Event(DefineEventStatement {
 			name: Ident("event".to_owned()),
@@ -283,6 +292,9 @@ fn statements() -> Vec<Statement> {
 			},
 			comment: None,
 			if_not_exists: false,
+			expire: false,
+			crdt: None,
+			computed: false,
 		})),
 		Statement::Define(DefineStatement::Index(DefineIndexStatement {
 			name: Ident("index".to_owned()),
@@ -478,6 +490,7 @@ fn statements() -> Vec<Statement> {
 				numeric: true,
 				direction: true,
 			}])),
+			sample: None,
 			limit: Some(Limit(Value::Thing(Thing {
 				tb: "a".to_owned(),
 				id: Id::String("b".to_owned()),
@@ -490,6 +503,10 @@ fn statements() -> Vec<Statement> {
 			timeout: None,
 			parallel: false,
 			explain: Some(Explain(true)),
+			outfile: None,
+			lock: false,
+			cache: None,
+			consistency: None,
 		}),
 		Statement::Set(SetStatement {
 			name: "param".to_owned(),
@@ -568,6 +585,7 @@ fn statements() -> Vec<Statement> {
 		}),
 		Statement::Kill(KillStatement {
 			id: Value::Uuid(Uuid(uuid::uuid!("e72bee20-f49b-11ec-b939-0242ac120002"))),
+			kind: KillKind::LiveQuery,
 		}),
 		Statement::Output(OutputStatement {
 			what: Value::Idiom(Idiom(vec![Part::Field(Ident("RETRUN".to_owned()))])),
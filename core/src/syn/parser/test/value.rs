@@ -136,6 +136,14 @@ fn constant_mixedcase() {
 	assert_eq!(out, Value::Constant(Constant::MathPi));
 }
 
+#[test]
+fn view_without_arguments() {
+	let out = test_parse!(parse_value, r#" system::hot_keys "#).unwrap();
+	assert_eq!(out.to_string(), "system::hot_keys()");
+	let out = test_parse!(parse_value, r#" SYSTEM::HOT_KEYS() "#).unwrap();
+	assert_eq!(out.to_string(), "system::hot_keys()");
+}
+
 #[test]
 fn scientific_decimal() {
 	let res = test_parse!(parse_value, r#" 9.7e-7dec "#).unwrap();
@@ -149,3 +157,27 @@ fn scientific_number() {
 	assert!(matches!(res, Value::Number(Number::Float(_))));
 	assert_eq!(res.to_string(), "0.000097f")
 }
+
+#[test]
+fn json_negative_numbers() {
+	let res = test_parse!(parse_json, r#" [-1, -1.5f, -1.5dec, -9223372036854775808] "#).unwrap();
+	assert_eq!(
+		res,
+		Value::Array(Array(vec![
+			Value::from(-1),
+			Value::from(-1.5),
+			Value::from(Number::Decimal("-1.5".parse().unwrap())),
+			Value::from(i64::MIN),
+		]))
+	);
+	assert!(test_parse!(parse_json, r#" -foo "#).is_err());
+}
+
+#[test]
+fn json_none() {
+	let res = test_parse!(parse_json, r#" { a: NONE, b: NULL } "#).unwrap();
+	let mut object = Object::default();
+	object.insert("a".to_owned(), Value::None);
+	object.insert("b".to_owned(), Value::Null);
+	assert_eq!(res, Value::Object(object));
+}
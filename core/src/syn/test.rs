@@ -70,3 +70,27 @@ CREATE person CONTENT { foo:'bar'};
 "#;
 	parse(q).unwrap_err();
 }
+
+#[test]
+fn test_error_recovery_reports_every_statement() {
+	let q = r#"
+CREATE person SET name = ;
+SELECT * FROM person;
+UPDATE person SET age = 1 +;
+"#;
+	let e = parse(q).unwrap_err().to_string();
+	assert!(e.contains("Found 2 errors in the query"), "{e}");
+	assert!(e.contains("2 | CREATE person SET name = ;"), "{e}");
+	assert!(e.contains("4 | UPDATE person SET age = 1 +;"), "{e}");
+	assert!(!e.contains("3 | SELECT * FROM person;"), "{e}");
+}
+
+#[test]
+fn test_error_suggests_statement_keyword() {
+	let e = parse("SELEC * FROM person").unwrap_err().to_string();
+	assert!(e.contains("did you maybe mean `SELECT`"), "{e}");
+	let e = parse("SELEC * FROM person; CREAT person;").unwrap_err().to_string();
+	assert!(e.contains("did you maybe mean `SELECT`"), "{e}");
+	assert!(e.contains("did you maybe mean `CREATE`"), "{e}");
+	parse("SELECT * FROM person;; SELECT * FROM person").unwrap();
+}
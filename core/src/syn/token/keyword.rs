@@ -24,37 +24,56 @@ macro_rules! keyword {
 }
 
 keyword! {
+	Advisor => "ADVISOR",
 	After => "AFTER",
 	All => "ALL",
+	Allow => "ALLOW",
+	Alter => "ALTER",
 	Analyze => "ANALYZE",
 	Analyzer => "ANALYZER",
 	As => "AS",
 	Ascending => "ASCENDING",
 	Ascii => "ASCII",
+	Async => "ASYNC",
 	Assert => "ASSERT",
 	At => "AT",
+	Audit => "AUDIT",
+	Backfill => "BACKFILL",
 	Before => "BEFORE",
 	Begin => "BEGIN",
 	Blank => "BLANK",
 	Bm25 => "BM25",
 	Break => "BREAK",
 	By => "BY",
+	Cache => "CACHE",
+	Call => "CALL",
 	Camel => "CAMEL",
 	Cancel => "CANCEL",
+	Cascade => "CASCADE",
+	Change => "CHANGE",
 	ChangeFeed => "CHANGEFEED",
 	Changes => "CHANGES",
+	Check => "CHECK",
 	Capacity => "CAPACITY",
 	Class => "CLASS",
 	Comment => "COMMENT",
 	Commit => "COMMIT",
+	Compact => "COMPACT",
+	Computed => "COMPUTED",
+	Consistency => "CONSISTENCY",
 	Content => "CONTENT",
 	Continue => "CONTINUE",
+	Count => "COUNT",
+	Counter => "COUNTER",
+	Crdt => "CRDT",
 	Create => "CREATE",
 	Database => "DATABASE",
 	Default => "DEFAULT",
 	Define => "DEFINE",
+	Deny => "DENY",
 	Delete => "DELETE",
 	Descending => "DESCENDING",
+	Dictionary => "DICTIONARY",
 	Diff => "DIFF",
 	Dimension => "DIMENSION",
 	Distance => "DISTANCE",
@@ -63,12 +82,17 @@ keyword! {
 	DocLengthsCache => "DOC_LENGTHS_CACHE",
 	DocLengthsOrder => "DOC_LENGTHS_ORDER",
 	Drop => "DROP",
+	Dry => "DRY",
 	Duplicate => "DUPLICATE",
 	Edgengram => "EDGENGRAM",
 	Event => "EVENT",
+	Enrichment => "ENRICHMENT",
+	Eventual => "EVENTUAL",
 	Else => "ELSE",
 	End => "END",
+	Every => "EVERY",
 	Exists => "EXISTS",
+	Expire => "EXPIRE",
 	Explain => "EXPLAIN",
 	False => "false",
 	Fetch => "FETCH",
@@ -77,11 +101,17 @@ keyword! {
 	Filters => "FILTERS",
 	Flexible => "FLEXIBLE",
 	For => "FOR",
+	Format => "FORMAT",
 	From => "FROM",
 	Full => "FULL",
 	Function => "FUNCTION",
+	Grant => "GRANT",
 	Group => "GROUP",
+	Hash => "HASH",
+	High => "HIGH",
 	Highlights => "HIGHLIGHTS",
+	History => "HISTORY",
+	Hook => "HOOK",
 	Ignore => "IGNORE",
 	Include => "INCLUDE",
 	Index => "INDEX",
@@ -90,16 +120,20 @@ keyword! {
 	Into => "INTO",
 	If => "IF",
 	Is => "IS",
+	Jsonl => "JSONL",
 	Key => "KEY",
 	Kill => "KILL",
+	Labels => "LABELS",
 	Let => "LET",
 	Limit => "LIMIT",
 	Live => "LIVE",
+	Low => "LOW",
 	Lowercase => "LOWERCASE",
 	Merge => "MERGE",
 	Model => "MODEL",
 	MTree => "MTREE",
 	MTreeCache => "MTREE_CACHE",
+	Must => "MUST",
 	Namespace => "NAMESPACE",
 	Ngram => "NGRAM",
 	No => "NO",
@@ -107,29 +141,46 @@ keyword! {
 	None => "NONE",
 	Null => "NULL",
 	Numeric => "NUMERIC",
+	Of => "OF",
 	Omit => "OMIT",
 	On => "ON",
 	Only => "ONLY",
 	Option => "OPTION",
 	Order => "ORDER",
+	Outfile => "OUTFILE",
 	Parallel => "PARALLEL",
 	Param => "PARAM",
+	Partition => "PARTITION",
+	Partitions => "PARTITIONS",
 	Passhash => "PASSHASH",
 	Password => "PASSWORD",
 	Patch => "PATCH",
 	Permissions => "PERMISSIONS",
 	PostingsCache => "POSTINGS_CACHE",
 	PostingsOrder => "POSTINGS_ORDER",
+	Priority => "PRIORITY",
+	Publish => "PUBLISH",
 	Punct => "PUNCT",
+	Quarantine => "QUARANTINE",
+	Query => "QUERY",
 	Readonly => "READONLY",
 	Rebuild => "REBUILD",
+	Register => "REGISTER",
 	Relate => "RELATE",
+	Reject => "REJECT",
 	Relation => "RELATION",
 	Remove => "REMOVE",
+	References => "REFERENCES",
 	Replace => "REPLACE",
+	Report => "REPORT",
 	Return => "RETURN",
+	Retry => "RETRY",
+	Role => "ROLE",
 	Roles => "ROLES",
 	Root => "ROOT",
+	Run => "RUN",
+	Sample => "SAMPLE",
+	Schedule => "SCHEDULE",
 	Schemafull => "SCHEMAFULL",
 	Schemaless => "SCHEMALESS",
 	Scope => "SCOPE",
@@ -142,10 +193,15 @@ keyword! {
 	Signup => "SIGNUP",
 	Since => "SINCE",
 	Sleep => "SLEEP",
+	Sliding => "SLIDING",
 	Snowball => "SNOWBALL",
 	Split => "SPLIT",
 	Start => "START",
+	Stats => "STATS",
+	Stopwords => "STOPWORDS",
+	Strong => "STRONG",
 	Structure => "STRUCTURE",
+	Synonyms => "SYNONYMS",
 	Table => "TABLE",
 	TermsCache => "TERMS_CACHE",
 	TermsOrder => "TERMS_ORDER",
@@ -157,8 +213,10 @@ keyword! {
 	To => "TO",
 	Transaction => "TRANSACTION",
 	True => "true",
+	Tumbling => "TUMBLING",
 	Type => "TYPE",
 	Unique => "UNIQUE",
+	Unknown => "UNKNOWN",
 	Unset => "UNSET",
 	Update => "UPDATE",
 	Uppercase => "UPPERCASE",
@@ -168,8 +226,10 @@ keyword! {
 	Values => "VALUES",
 	Version => "VERSION",
 	Vs => "VS",
+	Webhook => "WEBHOOK",
 	When => "WHEN",
 	Where => "WHERE",
+	Window => "WINDOW",
 	With => "WITH",
 	AllInside => "ALLINSIDE",
 	AndKw => "ANDKW",
@@ -0,0 +1,15 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &str| {
+	// Only check queries which can be parsed.
+	let Ok(query) = surrealdb::sql::parse(data) else {
+		return;
+	};
+	// The displayed query must parse back to the same query.
+	let text = query.to_string();
+	let again = surrealdb::sql::parse(&text)
+		.unwrap_or_else(|e| panic!("failed to parse displayed query `{text}`: {e}"));
+	assert_eq!(query, again, "displayed query `{text}` did not round trip");
+});
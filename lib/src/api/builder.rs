@@ -0,0 +1,511 @@
+//! A builder for composing SurrealQL queries
+//!
+//! Queries are composed from typed parts, rather than by concatenating strings.
+//! Table names and field paths are escaped, and every value is bound to the query
+//! as a parameter, so user input can never change the structure of the query.
+//!
+//! ```no_run
+//! use surrealdb::builder::{field, select};
+//!
+//! # #[derive(serde::Deserialize)]
+//! # struct User;
+//! # #[tokio::main]
+//! # async fn main() -> surrealdb::Result<()> {
+//! # let db = surrealdb::engine::any::connect("mem://").await?;
+//! let query = select()
+//!     .from("user")
+//!     .filter(field("age").gt(18).and(field("address.country").eq("UK")))
+//!     .order_by(field("name").asc())
+//!     .limit(10);
+//!
+//! let users: Vec<User> = db.query(query).await?.take(0)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Anything which the builder does not support can be written with [`raw`], which
+//! inserts SurrealQL into the query exactly as it is written. A query can also be
+//! built into SurrealQL and its parameters, using [`Select::build`].
+
+use crate::api::opt::{IntoQuery, Resource};
+use crate::api::Result;
+use crate::sql::{to_value, Idiom, Part, Statement, Value};
+use crate::syn;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// The prefix of the names of the parameters which are bound by the builder
+const PARAM_PREFIX: &str = "qb";
+
+/// Starts building a `SELECT` statement
+pub fn select() -> Select {
+	Select::default()
+}
+
+/// A field, or a nested field path such as `address.city`
+///
+/// Each part of the path is escaped, so the path can not contain any other SurrealQL.
+pub fn field(path: &str) -> Expr {
+	let idiom = Idiom::from(path.split('.').map(|v| Part::from(v.to_owned())).collect::<Vec<_>>());
+	Expr::sql(idiom.to_string())
+}
+
+/// A value, which is bound to the query as a parameter
+pub fn value(value: impl Serialize) -> Expr {
+	match to_value(value) {
+		Ok(v) => Expr {
+			fragments: Ok(vec![Fragment::Value(v)]),
+			compound: false,
+		},
+		Err(e) => Expr {
+			fragments: Err(e.into()),
+			compound: false,
+		},
+	}
+}
+
+/// SurrealQL which is inserted into the query exactly as it is written
+///
+/// The SurrealQL is not escaped, so it must never contain user input. Use
+/// [`value`] for any values which the expression uses instead.
+pub fn raw(sql: impl Into<String>) -> Expr {
+	Expr {
+		compound: true,
+		..Expr::sql(sql.into())
+	}
+}
+
+#[derive(Debug)]
+enum Fragment {
+	Sql(String),
+	Value(Value),
+}
+
+/// A SurrealQL expression
+#[derive(Debug)]
+#[must_use]
+pub struct Expr {
+	fragments: Result<Vec<Fragment>>,
+	// Whether the expression must be wrapped in parentheses when it is an operand
+	compound: bool,
+}
+
+#[allow(clippy::should_implement_trait)]
+impl Expr {
+	fn sql(sql: String) -> Self {
+		Self {
+			fragments: Ok(vec![Fragment::Sql(sql)]),
+			compound: false,
+		}
+	}
+
+	fn binary(self, op: &str, other: impl IntoExpr) -> Self {
+		let other = other.into_expr();
+		let fragments = match (self.operand(), other.operand()) {
+			(Ok(mut l), Ok(r)) => {
+				l.push(Fragment::Sql(format!(" {op} ")));
+				l.extend(r);
+				Ok(l)
+			}
+			(Err(e), _) | (_, Err(e)) => Err(e),
+		};
+		Self {
+			fragments,
+			compound: true,
+		}
+	}
+
+	fn operand(self) -> Result<Vec<Fragment>> {
+		let mut fragments = self.fragments?;
+		if self.compound {
+			fragments.insert(0, Fragment::Sql("(".to_owned()));
+			fragments.push(Fragment::Sql(")".to_owned()));
+		}
+		Ok(fragments)
+	}
+
+	/// Checks if the expression is equal to another (`=`)
+	pub fn eq(self, other: impl IntoExpr) -> Self {
+		self.binary("=", other)
+	}
+
+	/// Checks if the expression is not equal to another (`!=`)
+	pub fn ne(self, other: impl IntoExpr) -> Self {
+		self.binary("!=", other)
+	}
+
+	/// Checks if the expression is greater than another (`>`)
+	pub fn gt(self, other: impl IntoExpr) -> Self {
+		self.binary(">", other)
+	}
+
+	/// Checks if the expression is greater than or equal to another (`>=`)
+	pub fn gte(self, other: impl IntoExpr) -> Self {
+		self.binary(">=", other)
+	}
+
+	/// Checks if the expression is less than another (`<`)
+	pub fn lt(self, other: impl IntoExpr) -> Self {
+		self.binary("<", other)
+	}
+
+	/// Checks if the expression is less than or equal to another (`<=`)
+	pub fn lte(self, other: impl IntoExpr) -> Self {
+		self.binary("<=", other)
+	}
+
+	/// Checks if the expression contains another (`CONTAINS`)
+	pub fn contains(self, other: impl IntoExpr) -> Self {
+		self.binary("CONTAINS", other)
+	}
+
+	/// Checks if the expression is inside another (`INSIDE`)
+	pub fn inside(self, other: impl IntoExpr) -> Self {
+		self.binary("INSIDE", other)
+	}
+
+	/// Checks if both this and another expression are truthy (`AND`)
+	pub fn and(self, other: impl IntoExpr) -> Self {
+		self.binary("AND", other)
+	}
+
+	/// Checks if either this or another expression is truthy (`OR`)
+	pub fn or(self, other: impl IntoExpr) -> Self {
+		self.binary("OR", other)
+	}
+
+	/// Negates the expression (`!`)
+	pub fn not(self) -> Self {
+		let fragments = self.fragments.map(|mut v| {
+			v.insert(0, Fragment::Sql("!(".to_owned()));
+			v.push(Fragment::Sql(")".to_owned()));
+			v
+		});
+		Self {
+			fragments,
+			compound: false,
+		}
+	}
+
+	/// Orders by the expression in ascending order
+	pub fn asc(self) -> Order {
+		Order {
+			expr: self,
+			asc: true,
+		}
+	}
+
+	/// Orders by the expression in descending order
+	pub fn desc(self) -> Order {
+		Order {
+			expr: self,
+			asc: false,
+		}
+	}
+}
+
+/// A trait for converting inputs into expressions
+///
+/// Any serializable value is bound to the query as a parameter.
+pub trait IntoExpr {
+	/// Converts an input into an expression
+	fn into_expr(self) -> Expr;
+}
+
+impl IntoExpr for Expr {
+	fn into_expr(self) -> Expr {
+		self
+	}
+}
+
+impl<T> IntoExpr for T
+where
+	T: Serialize,
+{
+	fn into_expr(self) -> Expr {
+		value(self)
+	}
+}
+
+/// An expression which the results are ordered by
+#[derive(Debug)]
+#[must_use]
+pub struct Order {
+	expr: Expr,
+	asc: bool,
+}
+
+impl From<Expr> for Order {
+	fn from(expr: Expr) -> Self {
+		expr.asc()
+	}
+}
+
+/// A `SELECT` statement
+#[derive(Debug, Default)]
+#[must_use]
+pub struct Select {
+	fields: Vec<Expr>,
+	only: bool,
+	what: Vec<Resource>,
+	cond: Option<Expr>,
+	order: Vec<Order>,
+	limit: Option<Expr>,
+	start: Option<Expr>,
+	fetch: Vec<Expr>,
+}
+
+impl Select {
+	/// The fields to select, instead of selecting all of the fields
+	pub fn fields<I>(mut self, fields: I) -> Self
+	where
+		I: IntoIterator<Item = Expr>,
+	{
+		self.fields.extend(fields);
+		self
+	}
+
+	/// The table, or record, to select from
+	///
+	/// This can be called more than once to select from several tables or records.
+	pub fn from(mut self, what: impl Into<Resource>) -> Self {
+		self.what.push(what.into());
+		self
+	}
+
+	/// Returns a single record, rather than an array of records
+	pub fn only(mut self) -> Self {
+		self.only = true;
+		self
+	}
+
+	/// Filters the records which are selected
+	///
+	/// When this is called more than once, every filter must match.
+	pub fn filter(mut self, cond: Expr) -> Self {
+		self.cond = Some(match self.cond.take() {
+			Some(v) => v.and(cond),
+			None => cond,
+		});
+		self
+	}
+
+	/// Orders the records which are selected
+	///
+	/// When this is called more than once, the records are ordered by each expression in turn.
+	pub fn order_by(mut self, order: impl Into<Order>) -> Self {
+		self.order.push(order.into());
+		self
+	}
+
+	/// The maximum number of records to select
+	pub fn limit(mut self, limit: u64) -> Self {
+		self.limit = Some(value(limit));
+		self
+	}
+
+	/// The number of records to skip before selecting records
+	pub fn start(mut self, start: u64) -> Self {
+		self.start = Some(value(start));
+		self
+	}
+
+	/// Fetches the records which a field links to, such as `friends`
+	pub fn fetch(mut self, path: &str) -> Self {
+		self.fetch.push(field(path));
+		self
+	}
+
+	/// Builds the SurrealQL for the query, along with the parameters which it binds
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use surrealdb::builder::{field, select};
+	///
+	/// # fn main() -> surrealdb::Result<()> {
+	/// let (sql, vars) = select().from("user").filter(field("age").gt(18)).build()?;
+	/// assert_eq!(sql, "SELECT * FROM user WHERE age > $qb0");
+	/// assert_eq!(vars["qb0"], 18.into());
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn build(self) -> Result<(String, BTreeMap<String, Value>)> {
+		let mut bindings = BTreeMap::new();
+		let sql = self.compile(Some(&mut bindings))?;
+		Ok((sql, bindings))
+	}
+
+	fn compile(self, bindings: Option<&mut BTreeMap<String, Value>>) -> Result<String> {
+		let mut out = Compiler {
+			sql: String::from("SELECT "),
+			bindings,
+		};
+		if self.fields.is_empty() {
+			out.sql.push('*');
+		}
+		for (i, v) in self.fields.into_iter().enumerate() {
+			if i > 0 {
+				out.sql.push_str(", ");
+			}
+			out.push(v.fragments?);
+		}
+		out.sql.push_str(" FROM ");
+		if self.only {
+			out.sql.push_str("ONLY ");
+		}
+		for (i, v) in self.what.into_iter().enumerate() {
+			if i > 0 {
+				out.sql.push_str(", ");
+			}
+			match v {
+				Resource::Table(v) => out.sql.push_str(&v.to_string()),
+				v => out.push(vec![Fragment::Value(v.into())]),
+			}
+		}
+		if let Some(v) = self.cond {
+			out.sql.push_str(" WHERE ");
+			out.push(v.fragments?);
+		}
+		for (i, v) in self.order.into_iter().enumerate() {
+			out.sql.push_str(if i == 0 {
+				" ORDER BY "
+			} else {
+				", "
+			});
+			out.push(v.expr.fragments?);
+			out.sql.push_str(if v.asc {
+				" ASC"
+			} else {
+				" DESC"
+			});
+		}
+		if let Some(v) = self.limit {
+			out.sql.push_str(" LIMIT ");
+			out.push(v.fragments?);
+		}
+		if let Some(v) = self.start {
+			out.sql.push_str(" START ");
+			out.push(v.fragments?);
+		}
+		for (i, v) in self.fetch.into_iter().enumerate() {
+			out.sql.push_str(if i == 0 {
+				" FETCH "
+			} else {
+				", "
+			});
+			out.push(v.fragments?);
+		}
+		Ok(out.sql)
+	}
+}
+
+impl IntoQuery for Select {
+	/// Converts the query into SQL statements, with the values written inline
+	fn into_query(self) -> Result<Vec<Statement>> {
+		syn::parse(&self.compile(None)?)?.into_query()
+	}
+
+	fn into_query_with_bindings(
+		self,
+		bindings: &mut BTreeMap<String, Value>,
+	) -> Result<Vec<Statement>> {
+		syn::parse(&self.compile(Some(bindings))?)?.into_query()
+	}
+}
+
+struct Compiler<'a> {
+	sql: String,
+	// The parameters which values are bound to, or `None` to write values inline
+	bindings: Option<&'a mut BTreeMap<String, Value>>,
+}
+
+impl Compiler<'_> {
+	fn push(&mut self, fragments: Vec<Fragment>) {
+		for v in fragments {
+			match (v, &mut self.bindings) {
+				(Fragment::Sql(v), _) => self.sql.push_str(&v),
+				(Fragment::Value(v), Some(bindings)) => {
+					// Use the first parameter name which is not already bound
+					let mut i = bindings.len();
+					let mut name = format!("{PARAM_PREFIX}{i}");
+					while bindings.contains_key(&name) {
+						i += 1;
+						name = format!("{PARAM_PREFIX}{i}");
+					}
+					self.sql.push('$');
+					self.sql.push_str(&name);
+					bindings.insert(name, v);
+				}
+				(Fragment::Value(v), None) => self.sql.push_str(&v.to_string()),
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn binds_values_as_parameters() {
+		let (sql, vars) = select()
+			.fields([field("name"), field("address.city")])
+			.from("user")
+			.filter(field("age").gte(18).and(field("name").ne("'; REMOVE TABLE user; --")))
+			.order_by(field("age").desc())
+			.order_by(field("name"))
+			.limit(10)
+			.start(20)
+			.build()
+			.unwrap();
+		assert_eq!(
+			sql,
+			"SELECT name, address.city FROM user WHERE (age >= $qb0) AND (name != $qb1) ORDER BY age DESC, name ASC LIMIT $qb2 START $qb3"
+		);
+		assert_eq!(vars["qb1"], Value::from("'; REMOVE TABLE user; --"));
+		assert_eq!(vars.len(), 4);
+	}
+
+	#[test]
+	fn escapes_tables_and_fields() {
+		let (sql, _) = select()
+			.from("user; REMOVE TABLE user")
+			.filter(field("first name").eq(true))
+			.build()
+			.unwrap();
+		assert_eq!(sql, "SELECT * FROM `user; REMOVE TABLE user` WHERE `first name` = $qb0");
+		assert!(syn::parse(&sql).unwrap().0 .0.len() == 1);
+	}
+
+	#[test]
+	fn combines_filters_and_raw_expressions() {
+		let (sql, _) = select()
+			.from("event")
+			.filter(field("at").gt(raw("time::now() - 1d")))
+			.filter(field("kind").eq("a").or(field("kind").eq("b")).not())
+			.build()
+			.unwrap();
+		assert_eq!(
+			sql,
+			"SELECT * FROM event WHERE (at > (time::now() - 1d)) AND !((kind = $qb0) OR (kind = $qb1))"
+		);
+	}
+
+	#[test]
+	fn does_not_replace_existing_bindings() {
+		let mut bindings = BTreeMap::new();
+		bindings.insert("qb0".to_owned(), Value::from("other"));
+		let query = select().from("user").filter(field("age").gt(18));
+		let stms = query.into_query_with_bindings(&mut bindings).unwrap();
+		assert_eq!(stms[0].to_string(), "SELECT * FROM user WHERE age > $qb1");
+		assert_eq!(bindings["qb0"], Value::from("other"));
+		assert_eq!(bindings["qb1"], Value::from(18));
+	}
+
+	#[test]
+	fn writes_values_inline_without_bindings() {
+		let stms = select().from("user").filter(field("name").eq("Tobie")).into_query().unwrap();
+		assert_eq!(stms[0].to_string(), "SELECT * FROM user WHERE name = 'Tobie'");
+	}
+}
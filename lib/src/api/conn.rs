@@ -70,6 +70,8 @@ pub enum Method {
 	Invalidate,
 	/// Inserts a record or records into a table
 	Insert,
+	/// Restores a saved session
+	LoadSession,
 	/// Kills a live query
 	#[doc(hidden)] // Not supported yet
 	Kill,
@@ -80,6 +82,8 @@ pub enum Method {
 	Patch,
 	/// Sends a raw query to the database
 	Query,
+	/// Saves the current session
+	SaveSession,
 	/// Selects a record or records from a table
 	Select,
 	/// Sets a parameter on the connection
@@ -127,6 +131,7 @@ pub struct Param {
 	pub(crate) bytes_sender: Option<channel::Sender<Result<Vec<u8>>>>,
 	pub(crate) notification_sender: Option<channel::Sender<Notification>>,
 	pub(crate) ml_config: Option<MlConfig>,
+	pub(crate) tables: Vec<String>,
 }
 
 impl Param {
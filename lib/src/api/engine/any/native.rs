@@ -6,12 +6,7 @@ use crate::api::conn::Router;
 #[allow(unused_imports)] // used by the DB engines
 use crate::api::engine;
 use crate::api::engine::any::Any;
-#[cfg(feature = "protocol-http")]
-use crate::api::engine::remote::http;
 use crate::api::err::Error;
-#[cfg(any(feature = "native-tls", feature = "rustls"))]
-#[cfg(feature = "protocol-http")]
-use crate::api::opt::Tls;
 use crate::api::opt::{Endpoint, EndpointKind};
 use crate::api::DbResponse;
 #[allow(unused_imports)] // used by the DB engines
@@ -23,8 +18,6 @@ use crate::api::Surreal;
 use crate::error::Db as DbError;
 use crate::opt::WaitFor;
 use flume::Receiver;
-#[cfg(feature = "protocol-http")]
-use reqwest::ClientBuilder;
 use std::collections::HashSet;
 use std::future::Future;
 use std::marker::PhantomData;
@@ -35,9 +28,6 @@ use std::sync::OnceLock;
 use tokio::sync::watch;
 #[cfg(feature = "protocol-ws")]
 use tokio_tungstenite::tungstenite::protocol::WebSocketConfig;
-#[cfg(feature = "protocol-ws")]
-#[cfg(any(feature = "native-tls", feature = "rustls"))]
-use tokio_tungstenite::Connector;
 
 impl crate::api::Connection for Any {}
 
@@ -69,6 +59,7 @@ impl Connection for Any {
 					{
 						features.insert(ExtraFeatures::Backup);
 						features.insert(ExtraFeatures::LiveQueries);
+						features.insert(ExtraFeatures::Sessions);
 						engine::local::native::router(address, conn_tx, route_rx);
 						conn_rx.into_recv_async().await??
 					}
@@ -84,6 +75,7 @@ impl Connection for Any {
 					{
 						features.insert(ExtraFeatures::Backup);
 						features.insert(ExtraFeatures::LiveQueries);
+						features.insert(ExtraFeatures::Sessions);
 						engine::local::native::router(address, conn_tx, route_rx);
 						conn_rx.into_recv_async().await??
 					}
@@ -99,6 +91,7 @@ impl Connection for Any {
 					{
 						features.insert(ExtraFeatures::Backup);
 						features.insert(ExtraFeatures::LiveQueries);
+						features.insert(ExtraFeatures::Sessions);
 						engine::local::native::router(address, conn_tx, route_rx);
 						conn_rx.into_recv_async().await??
 					}
@@ -115,6 +108,7 @@ impl Connection for Any {
 					{
 						features.insert(ExtraFeatures::Backup);
 						features.insert(ExtraFeatures::LiveQueries);
+						features.insert(ExtraFeatures::Sessions);
 						engine::local::native::router(address, conn_tx, route_rx);
 						conn_rx.into_recv_async().await??
 					}
@@ -131,6 +125,7 @@ impl Connection for Any {
 					{
 						features.insert(ExtraFeatures::Backup);
 						features.insert(ExtraFeatures::LiveQueries);
+						features.insert(ExtraFeatures::Sessions);
 						engine::local::native::router(address, conn_tx, route_rx);
 						conn_rx.into_recv_async().await??
 					}
@@ -146,6 +141,7 @@ impl Connection for Any {
 					{
 						features.insert(ExtraFeatures::Backup);
 						features.insert(ExtraFeatures::LiveQueries);
+						features.insert(ExtraFeatures::Sessions);
 						engine::local::native::router(address, conn_tx, route_rx);
 						conn_rx.into_recv_async().await??
 					}
@@ -161,19 +157,7 @@ impl Connection for Any {
 					#[cfg(feature = "protocol-http")]
 					{
 						features.insert(ExtraFeatures::Backup);
-						let headers = http::default_headers();
-						#[allow(unused_mut)]
-						let mut builder = ClientBuilder::new().default_headers(headers);
-						#[cfg(any(feature = "native-tls", feature = "rustls"))]
-						if let Some(tls) = address.config.tls_config {
-							builder = match tls {
-								#[cfg(feature = "native-tls")]
-								Tls::Native(config) => builder.use_preconfigured_tls(config),
-								#[cfg(feature = "rustls")]
-								Tls::Rust(config) => builder.use_preconfigured_tls(config),
-							};
-						}
-						let client = builder.build()?;
+						let client = engine::remote::http::native::client(&address.config)?;
 						let base_url = address.url;
 						engine::remote::http::health(
 							client.get(base_url.join(Method::Health.as_str())?),
@@ -195,10 +179,8 @@ impl Connection for Any {
 						features.insert(ExtraFeatures::LiveQueries);
 						let mut endpoint = address;
 						endpoint.url = endpoint.url.join(engine::remote::ws::PATH)?;
-						#[cfg(any(feature = "native-tls", feature = "rustls"))]
-						let maybe_connector = endpoint.config.tls_config.clone().map(Connector::from);
-						#[cfg(not(any(feature = "native-tls", feature = "rustls")))]
-						let maybe_connector = None;
+						let maybe_connector =
+							engine::remote::ws::native::connector(&endpoint.config)?;
 
 						let config = WebSocketConfig {
 							max_message_size: Some(engine::remote::ws::native::MAX_MESSAGE_SIZE),
@@ -54,6 +54,7 @@ impl Connection for Any {
 					#[cfg(feature = "kv-fdb")]
 					{
 						features.insert(ExtraFeatures::LiveQueries);
+						features.insert(ExtraFeatures::Sessions);
 						engine::local::wasm::router(address, conn_tx, route_rx);
 						conn_rx.into_recv_async().await??;
 					}
@@ -68,6 +69,7 @@ impl Connection for Any {
 					#[cfg(feature = "kv-indxdb")]
 					{
 						features.insert(ExtraFeatures::LiveQueries);
+						features.insert(ExtraFeatures::Sessions);
 						engine::local::wasm::router(address, conn_tx, route_rx);
 						conn_rx.into_recv_async().await??;
 					}
@@ -82,6 +84,7 @@ impl Connection for Any {
 					#[cfg(feature = "kv-mem")]
 					{
 						features.insert(ExtraFeatures::LiveQueries);
+						features.insert(ExtraFeatures::Sessions);
 						engine::local::wasm::router(address, conn_tx, route_rx);
 						conn_rx.into_recv_async().await??;
 					}
@@ -96,6 +99,7 @@ impl Connection for Any {
 					#[cfg(feature = "kv-rocksdb")]
 					{
 						features.insert(ExtraFeatures::LiveQueries);
+						features.insert(ExtraFeatures::Sessions);
 						engine::local::wasm::router(address, conn_tx, route_rx);
 						conn_rx.into_recv_async().await??;
 					}
@@ -111,6 +115,7 @@ impl Connection for Any {
 					#[cfg(feature = "kv-speedb")]
 					{
 						features.insert(ExtraFeatures::LiveQueries);
+						features.insert(ExtraFeatures::Sessions);
 						engine::local::wasm::router(address, conn_tx, route_rx);
 						conn_rx.into_recv_async().await??;
 					}
@@ -126,6 +131,7 @@ impl Connection for Any {
 					#[cfg(feature = "kv-surrealkv")]
 					{
 						features.insert(ExtraFeatures::LiveQueries);
+						features.insert(ExtraFeatures::Sessions);
 						engine::local::wasm::router(address, conn_tx, route_rx);
 						conn_rx.into_recv_async().await??;
 					}
@@ -141,6 +147,7 @@ impl Connection for Any {
 					#[cfg(feature = "kv-tikv")]
 					{
 						features.insert(ExtraFeatures::LiveQueries);
+						features.insert(ExtraFeatures::Sessions);
 						engine::local::wasm::router(address, conn_tx, route_rx);
 						conn_rx.into_recv_async().await??;
 					}
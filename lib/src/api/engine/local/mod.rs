@@ -47,6 +47,7 @@ use crate::api::Surreal;
 use crate::dbs::Notification;
 use crate::dbs::Response;
 use crate::dbs::Session;
+use crate::dbs::SessionSnapshot;
 #[cfg(feature = "ml")]
 #[cfg(not(target_arch = "wasm32"))]
 use crate::iam::check::check_ns_db;
@@ -441,6 +442,7 @@ async fn export(
 	sess: &Session,
 	chn: channel::Sender<Vec<u8>>,
 	ml_config: Option<MlConfig>,
+	tables: Vec<String>,
 ) -> Result<()> {
 	match ml_config {
 		#[cfg(feature = "ml")]
@@ -466,7 +468,7 @@ async fn export(
 			}
 		}
 		_ => {
-			if let Err(error) = kvs.export(sess, chn).await?.await {
+			if let Err(error) = kvs.export_tables(sess, tables, chn).await?.await {
 				if let crate::error::Db::Channel(message) = error {
 					// This is not really an error. Just logging it for improved visibility.
 					trace!("{message}");
@@ -565,6 +567,29 @@ async fn router(
 			crate::iam::clear::clear(session)?;
 			Ok(DbResponse::Other(Value::None))
 		}
+		Method::SaveSession => {
+			let key = match &mut params[..] {
+				[Value::Bytes(key)] => mem::take(key),
+				_ => unreachable!(),
+			};
+			let snapshot = SessionSnapshot::new(session, vars.clone()).seal(&key)?;
+			Ok(DbResponse::Other(Value::Bytes(snapshot.into())))
+		}
+		Method::LoadSession => {
+			let (key, snapshot) = match &mut params[..] {
+				[Value::Bytes(key), Value::Bytes(snapshot)] => {
+					(mem::take(key), mem::take(snapshot))
+				}
+				_ => unreachable!(),
+			};
+			let snapshot = SessionSnapshot::open(&key, &snapshot)?;
+			// Check the restored session against the current definitions
+			let restored = snapshot.session();
+			crate::iam::verify::session(kvs, &restored).await?;
+			*session = restored;
+			*vars = snapshot.vars;
+			Ok(DbResponse::Other(Value::None))
+		}
 		Method::Create => {
 			let mut query = Query::default();
 			let statement = create_statement(&mut params);
@@ -644,7 +669,7 @@ async fn router(
 					let (mut writer, mut reader) = io::duplex(10_240);
 
 					// Write to channel.
-					let export = export(kvs, session, tx, param.ml_config);
+					let export = export(kvs, session, tx, param.ml_config, param.tables);
 
 					// Read from channel and write to pipe.
 					let bridge = async move {
@@ -685,7 +710,9 @@ async fn router(
 					let session = session.clone();
 					tokio::spawn(async move {
 						let export = async {
-							if let Err(error) = export(&kvs, &session, tx, param.ml_config).await {
+							if let Err(error) =
+								export(&kvs, &session, tx, param.ml_config, param.tables).await
+							{
 								let _ = backup.send(Err(error)).await;
 							}
 						};
@@ -63,6 +63,7 @@ impl Connection for Db {
 			let mut features = HashSet::new();
 			features.insert(ExtraFeatures::Backup);
 			features.insert(ExtraFeatures::LiveQueries);
+			features.insert(ExtraFeatures::Sessions);
 
 			Ok(Surreal {
 				router: Arc::new(OnceLock::with_value(Router {
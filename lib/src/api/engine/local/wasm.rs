@@ -64,6 +64,7 @@ impl Connection for Db {
 
 			let mut features = HashSet::new();
 			features.insert(ExtraFeatures::LiveQueries);
+			features.insert(ExtraFeatures::Sessions);
 
 			Ok(Surreal {
 				router: Arc::new(OnceLock::with_value(Router {
@@ -149,7 +150,7 @@ pub(crate) fn router(
 
 		let mut opt = EngineOptions::default();
 		opt.tick_interval = address.config.tick_interval.unwrap_or(DEFAULT_TICK_INTERVAL);
-		let (_tasks, task_chans) = start_tasks(&opt, kvs.clone());
+		let (tasks, task_chans) = start_tasks(&opt, kvs.clone());
 
 		let mut notifications = kvs.notifications();
 		let notification_stream = poll_fn(move |cx| match &mut notifications {
@@ -197,11 +198,23 @@ pub(crate) fn router(
 			}
 		}
 
+		// Kill any live queries which are still registered, so that
+		// they are not left behind in a persistent datastore
+		for (id, _) in live_queries.drain() {
+			if let Err(error) = super::kill_live_query(&kvs, id, &session, vars.clone()).await {
+				warn!("Failed to kill live query '{id}'; {error}");
+			}
+		}
+
 		// Stop maintenance tasks
 		for chan in task_chans {
 			if let Err(e) = chan.send(()) {
 				error!("Error sending shutdown signal to maintenance task: {e}");
 			}
 		}
+
+		if let Err(error) = tasks.resolve().await {
+			error!("Error waiting for maintenance tasks to stop: {error}");
+		}
 	});
 }
@@ -39,6 +39,7 @@ use crate::sql::Value;
 use futures::TryStreamExt;
 use indexmap::IndexMap;
 use reqwest::header::HeaderMap;
+use reqwest::header::HeaderName;
 use reqwest::header::HeaderValue;
 use reqwest::header::ACCEPT;
 #[cfg(not(target_arch = "wasm32"))]
@@ -109,10 +110,20 @@ impl Surreal<Client> {
 	}
 }
 
-pub(crate) fn default_headers() -> HeaderMap {
+pub(crate) fn default_headers(extra: &[(String, String)]) -> Result<HeaderMap> {
 	let mut headers = HeaderMap::new();
 	headers.insert(ACCEPT, HeaderValue::from_static("application/surrealdb"));
-	headers
+	// Add any headers configured for the connection
+	for (name, value) in extra {
+		let invalid = |error: String| Error::InvalidHeader {
+			name: name.clone(),
+			error,
+		};
+		let key = HeaderName::from_bytes(name.as_bytes()).map_err(|e| invalid(e.to_string()))?;
+		let val = HeaderValue::from_str(value).map_err(|e| invalid(e.to_string()))?;
+		headers.append(key, val);
+	}
+	Ok(headers)
 }
 
 #[derive(Debug)]
@@ -187,6 +198,26 @@ async fn submit_auth(request: RequestBuilder) -> Result<Value> {
 	Ok(response.token.into())
 }
 
+/// Check that a bound value keeps its type when it is sent as a query parameter,
+/// which the server parses as JSON extended with the SurrealQL literal types
+fn check_binding(name: &str, value: &Value) -> Result<()> {
+	let preserved = match value {
+		// Strings are sent as is, and keep their legacy parsing on the server
+		Value::Strand(_) => true,
+		Value::Array(v) => return v.iter().try_for_each(|v| check_binding(name, v)),
+		Value::Object(v) => return v.values().try_for_each(|v| check_binding(name, v)),
+		v => crate::syn::json_legacy_strand(&v.to_string()).is_ok_and(|x| &x == v),
+	};
+	match preserved {
+		true => Ok(()),
+		false => Err(Error::BindingNotPreserved {
+			name: name.to_owned(),
+			value: value.clone(),
+		}
+		.into()),
+	}
+}
+
 async fn query(request: RequestBuilder) -> Result<QueryResponse> {
 	let response = request.send().await?.error_for_status()?;
 	let bytes = response.bytes().await?;
@@ -519,6 +550,9 @@ async fn router(
 			let mut request = client.post(path).headers(headers.clone()).query(&vars).auth(auth);
 			match param.query {
 				Some((query, bindings)) => {
+					for (key, value) in bindings.iter() {
+						check_binding(key, value)?;
+					}
 					let bindings: Vec<_> =
 						bindings.iter().map(|(key, value)| (key, value.to_string())).collect();
 					request = request.query(&bindings).body(query.to_string());
@@ -544,7 +578,8 @@ async fn router(
 				.get(path)
 				.headers(headers.clone())
 				.auth(auth)
-				.header(ACCEPT, "application/octet-stream");
+				.header(ACCEPT, "application/octet-stream")
+				.query(&param.tables.iter().map(|tb| ("table", tb)).collect::<Vec<_>>());
 			let value = export(request, (param.file, param.bytes_sender)).await?;
 			Ok(DbResponse::Other(value))
 		}
@@ -570,6 +605,7 @@ async fn router(
 			let value = health(request).await?;
 			Ok(DbResponse::Other(value))
 		}
+		Method::LoadSession | Method::SaveSession => Err(Error::SessionsNotSupported.into()),
 		Method::Version => {
 			let path = base_url.join(method.as_str())?;
 			let request = client.get(path);
@@ -630,3 +666,21 @@ async fn router(
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn default_headers_include_configured_headers() {
+		let extra = vec![
+			("X-Gateway-Key".to_owned(), "secret".to_owned()),
+			("x-gateway-key".to_owned(), "other".to_owned()),
+		];
+		let headers = default_headers(&extra).unwrap();
+		assert_eq!(headers.get(ACCEPT).unwrap(), "application/surrealdb");
+		assert_eq!(headers.get_all("x-gateway-key").iter().count(), 2);
+		let invalid = vec![("X Gateway".to_owned(), "secret".to_owned())];
+		assert!(default_headers(&invalid).is_err());
+	}
+}
@@ -5,6 +5,7 @@ use crate::api::conn::Method;
 use crate::api::conn::Param;
 use crate::api::conn::Route;
 use crate::api::conn::Router;
+use crate::api::opt::Config;
 use crate::api::opt::Endpoint;
 #[cfg(any(feature = "native-tls", feature = "rustls"))]
 use crate::api::opt::Tls;
@@ -30,6 +31,38 @@ use url::Url;
 
 impl crate::api::Connection for Client {}
 
+/// Build a HTTP client using the connection configuration
+pub(crate) fn client(config: &Config) -> Result<reqwest::Client> {
+	let headers = super::default_headers(&config.headers)?;
+
+	let mut builder = ClientBuilder::new().default_headers(headers);
+
+	if let Some(proxy) = &config.proxy {
+		builder = builder.proxy(reqwest::Proxy::all(proxy.clone())?);
+	}
+
+	#[cfg(any(feature = "native-tls", feature = "rustls"))]
+	match &config.tls_config {
+		Some(tls) => {
+			builder = match tls {
+				#[cfg(feature = "native-tls")]
+				Tls::Native(config) => builder.use_preconfigured_tls(config.clone()),
+				#[cfg(feature = "rustls")]
+				Tls::Rust(config) => builder.use_preconfigured_tls(config.clone()),
+			};
+		}
+		None => {
+			for pem in &config.ca_certificates {
+				for cert in reqwest::Certificate::from_pem_bundle(pem)? {
+					builder = builder.add_root_certificate(cert);
+				}
+			}
+		}
+	}
+
+	Ok(builder.build()?)
+}
+
 impl Connection for Client {
 	fn new(method: Method) -> Self {
 		Self {
@@ -42,22 +75,7 @@ impl Connection for Client {
 		capacity: usize,
 	) -> Pin<Box<dyn Future<Output = Result<Surreal<Self>>> + Send + Sync + 'static>> {
 		Box::pin(async move {
-			let headers = super::default_headers();
-
-			#[allow(unused_mut)]
-			let mut builder = ClientBuilder::new().default_headers(headers);
-
-			#[cfg(any(feature = "native-tls", feature = "rustls"))]
-			if let Some(tls) = address.config.tls_config {
-				builder = match tls {
-					#[cfg(feature = "native-tls")]
-					Tls::Native(config) => builder.use_preconfigured_tls(config),
-					#[cfg(feature = "rustls")]
-					Tls::Rust(config) => builder.use_preconfigured_tls(config),
-				};
-			}
-
-			let client = builder.build()?;
+			let client = client(&address.config)?;
 
 			let base_url = address.url;
 
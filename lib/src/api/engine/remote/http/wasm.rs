@@ -5,6 +5,7 @@ use crate::api::conn::Method;
 use crate::api::conn::Param;
 use crate::api::conn::Route;
 use crate::api::conn::Router;
+use crate::api::opt::Config;
 use crate::api::opt::Endpoint;
 use crate::api::OnceLockExt;
 use crate::api::Result;
@@ -82,8 +83,8 @@ impl Connection for Client {
 	}
 }
 
-async fn client(base_url: &Url) -> Result<reqwest::Client> {
-	let headers = super::default_headers();
+async fn client(base_url: &Url, config: &Config) -> Result<reqwest::Client> {
+	let headers = super::default_headers(&config.headers)?;
 	let builder = ClientBuilder::new().default_headers(headers);
 	let client = builder.build()?;
 	let health = base_url.join(Method::Health.as_str())?;
@@ -99,7 +100,7 @@ pub(crate) fn router(
 	spawn_local(async move {
 		let base_url = address.url;
 
-		let client = match client(&base_url).await {
+		let client = match client(&base_url, &address.config).await {
 			Ok(client) => {
 				let _ = conn_tx.into_send_async(Ok(())).await;
 				client
@@ -11,6 +11,7 @@ use crate::api::engine::remote::ws::Response;
 use crate::api::engine::remote::ws::PING_INTERVAL;
 use crate::api::engine::remote::ws::PING_METHOD;
 use crate::api::err::Error;
+use crate::api::opt::Config;
 use crate::api::opt::Endpoint;
 #[cfg(any(feature = "native-tls", feature = "rustls"))]
 use crate::api::opt::Tls;
@@ -22,12 +23,15 @@ use crate::engine::remote::ws::Data;
 use crate::engine::IntervalStream;
 use crate::opt::WaitFor;
 use crate::sql::Value;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
 use flume::Receiver;
 use futures::stream::SplitSink;
 use futures::SinkExt;
 use futures::StreamExt;
 use futures_concurrency::stream::Merge as _;
 use indexmap::IndexMap;
+use percent_encoding::percent_decode_str;
 use revision::revisioned;
 use serde::Deserialize;
 use std::collections::hash_map::Entry;
@@ -41,6 +45,8 @@ use std::pin::Pin;
 use std::sync::atomic::AtomicI64;
 use std::sync::Arc;
 use std::sync::OnceLock;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWriteExt;
 use tokio::net::TcpStream;
 use tokio::sync::watch;
 use tokio::time;
@@ -48,6 +54,7 @@ use tokio::time::MissedTickBehavior;
 use tokio_tungstenite::tungstenite::client::IntoClientRequest;
 use tokio_tungstenite::tungstenite::error::Error as WsError;
 use tokio_tungstenite::tungstenite::http::header::SEC_WEBSOCKET_PROTOCOL;
+use tokio_tungstenite::tungstenite::http::HeaderName;
 use tokio_tungstenite::tungstenite::http::HeaderValue;
 use tokio_tungstenite::tungstenite::protocol::WebSocketConfig;
 use tokio_tungstenite::tungstenite::Message;
@@ -55,6 +62,7 @@ use tokio_tungstenite::Connector;
 use tokio_tungstenite::MaybeTlsStream;
 use tokio_tungstenite::WebSocketStream;
 use trice::Instant;
+use url::Url;
 
 type WsResult<T> = std::result::Result<T, WsError>;
 
@@ -63,6 +71,7 @@ pub(crate) const MAX_FRAME_SIZE: usize = 16 << 20; // 16 MiB
 pub(crate) const WRITE_BUFFER_SIZE: usize = 128000; // tungstenite default
 pub(crate) const MAX_WRITE_BUFFER_SIZE: usize = WRITE_BUFFER_SIZE + MAX_MESSAGE_SIZE; // Recommended max according to tungstenite docs
 pub(crate) const NAGLE_ALG: bool = false;
+pub(crate) const MAX_PROXY_RESPONSE_SIZE: usize = 16 << 10; // 16 KiB
 
 pub(crate) enum Either {
 	Request(Option<Route>),
@@ -82,6 +91,69 @@ impl From<Tls> for Connector {
 	}
 }
 
+/// Build the TLS connector from the connection configuration
+pub(crate) fn connector(#[allow(unused_variables)] config: &Config) -> Result<Option<Connector>> {
+	#[cfg(any(feature = "native-tls", feature = "rustls"))]
+	{
+		if let Some(tls) = &config.tls_config {
+			return Ok(Some(tls.clone().into()));
+		}
+		if config.ca_certificates.is_empty() {
+			return Ok(None);
+		}
+		let invalid = |error: String| crate::Error::from(Error::InvalidCertificates(error));
+		// Trust the configured certificate authorities, alongside the platform ones
+		#[cfg(feature = "native-tls")]
+		{
+			const BEGIN: &str = "-----BEGIN CERTIFICATE-----";
+			let mut builder = native_tls::TlsConnector::builder();
+			for pem in &config.ca_certificates {
+				let pem = String::from_utf8_lossy(pem);
+				for block in pem.split_inclusive("-----END CERTIFICATE-----") {
+					if let Some(start) = block.find(BEGIN) {
+						let cert = native_tls::Certificate::from_pem(block[start..].as_bytes())
+							.map_err(|e| invalid(e.to_string()))?;
+						builder.add_root_certificate(cert);
+					}
+				}
+			}
+			let connector = builder.build().map_err(|e| invalid(e.to_string()))?;
+			Ok(Some(Connector::NativeTls(connector)))
+		}
+		// Trust the configured certificate authorities, alongside the webpki ones
+		#[cfg(not(feature = "native-tls"))]
+		{
+			let mut roots = rustls::RootCertStore::empty();
+			roots.add_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.iter().map(|ta| {
+				rustls::OwnedTrustAnchor::from_subject_spki_name_constraints(
+					ta.subject,
+					ta.spki,
+					ta.name_constraints,
+				)
+			}));
+			for pem in &config.ca_certificates {
+				let certs = rustls_pemfile::certs(&mut pem.as_slice())
+					.map_err(|e| invalid(e.to_string()))?;
+				if certs.is_empty() {
+					return Err(invalid("no certificates were found".to_owned()));
+				}
+				let (_, ignored) = roots.add_parsable_certificates(&certs);
+				if ignored > 0 {
+					return Err(invalid(format!("{ignored} certificates could not be parsed")));
+				}
+			}
+			let config = rustls::ClientConfig::builder()
+				.with_safe_defaults()
+				.with_root_certificates(roots)
+				.with_no_client_auth();
+			Ok(Some(Connector::Rustls(Arc::new(config))))
+		}
+	}
+
+	#[cfg(not(any(feature = "native-tls", feature = "rustls")))]
+	Ok(None)
+}
+
 pub(crate) async fn connect(
 	endpoint: &Endpoint,
 	config: Option<WebSocketConfig>,
@@ -95,6 +167,29 @@ pub(crate) async fn connect(
 			.insert(SEC_WEBSOCKET_PROTOCOL, HeaderValue::from_static(super::REVISION_HEADER));
 	}
 
+	for (name, value) in &endpoint.config.headers {
+		let invalid = |error: String| Error::InvalidHeader {
+			name: name.clone(),
+			error,
+		};
+		let key = HeaderName::from_bytes(name.as_bytes()).map_err(|e| invalid(e.to_string()))?;
+		let val = HeaderValue::from_str(value).map_err(|e| invalid(e.to_string()))?;
+		request.headers_mut().append(key, val);
+	}
+
+	// Open a tunnel through the proxy, if one is configured
+	if let Some(proxy) = &endpoint.config.proxy {
+		let stream = tunnel(proxy, &endpoint.url).await?;
+		let (socket, _) = tokio_tungstenite::client_async_tls_with_config(
+			request,
+			stream,
+			config,
+			maybe_connector,
+		)
+		.await?;
+		return Ok(socket);
+	}
+
 	#[cfg(any(feature = "native-tls", feature = "rustls"))]
 	let (socket, _) = tokio_tungstenite::connect_async_tls_with_config(
 		request,
@@ -110,6 +205,43 @@ pub(crate) async fn connect(
 	Ok(socket)
 }
 
+/// Connect to the server through a HTTP proxy, using a `CONNECT` tunnel
+async fn tunnel(proxy: &Url, target: &Url) -> Result<TcpStream> {
+	let error = |message: String| crate::Error::from(Error::Proxy(message));
+	let proxy_host =
+		proxy.host_str().ok_or_else(|| error(format!("the proxy URL `{proxy}` has no host")))?;
+	let proxy_port = proxy.port_or_known_default().unwrap_or(80);
+	let host = target.host_str().ok_or_else(|| error(format!("the URL `{target}` has no host")))?;
+	let port = target.port_or_known_default().unwrap_or(80);
+	let mut stream =
+		TcpStream::connect((proxy_host, proxy_port)).await.map_err(|e| error(e.to_string()))?;
+	// Ask the proxy to open a connection to the server
+	let authority = format!("{host}:{port}");
+	let mut request = format!("CONNECT {authority} HTTP/1.1\r\nHost: {authority}\r\n");
+	if !proxy.username().is_empty() {
+		let user = percent_decode_str(proxy.username()).decode_utf8_lossy();
+		let pass = percent_decode_str(proxy.password().unwrap_or_default()).decode_utf8_lossy();
+		let credentials = STANDARD.encode(format!("{user}:{pass}"));
+		request.push_str(&format!("Proxy-Authorization: Basic {credentials}\r\n"));
+	}
+	request.push_str("\r\n");
+	stream.write_all(request.as_bytes()).await.map_err(|e| error(e.to_string()))?;
+	// Read the response one byte at a time, so that no data from the server is consumed
+	let mut response = Vec::new();
+	while !response.ends_with(b"\r\n\r\n") {
+		if response.len() >= MAX_PROXY_RESPONSE_SIZE {
+			return Err(error("the proxy response is too large".to_owned()));
+		}
+		response.push(stream.read_u8().await.map_err(|e| error(e.to_string()))?);
+	}
+	let response = String::from_utf8_lossy(&response);
+	let status = response.lines().next().unwrap_or_default();
+	match status.split_whitespace().nth(1) {
+		Some("200") => Ok(stream),
+		_ => Err(error(format!("the proxy responded with `{status}`"))),
+	}
+}
+
 impl crate::api::Connection for Client {}
 
 impl Connection for Client {
@@ -126,10 +258,7 @@ impl Connection for Client {
 	) -> Pin<Box<dyn Future<Output = Result<Surreal<Self>>> + Send + Sync + 'static>> {
 		Box::pin(async move {
 			address.url = address.url.join(PATH)?;
-			#[cfg(any(feature = "native-tls", feature = "rustls"))]
-			let maybe_connector = address.config.tls_config.clone().map(Connector::from);
-			#[cfg(not(any(feature = "native-tls", feature = "rustls")))]
-			let maybe_connector = None;
+			let maybe_connector = connector(&address.config)?;
 
 			let config = WebSocketConfig {
 				max_message_size: Some(MAX_MESSAGE_SIZE),
@@ -16,7 +16,6 @@ use crate::kvs::Datastore;
 use crate::options::EngineOptions;
 
 use crate::engine::IntervalStream;
-#[cfg(not(target_arch = "wasm32"))]
 use crate::Error as RootError;
 #[cfg(not(target_arch = "wasm32"))]
 use tokio::spawn as spawn_future;
@@ -32,6 +31,8 @@ type FutureTask = Arc<AtomicBool>;
 pub struct Tasks {
 	pub nd: FutureTask,
 	pub lq: FutureTask,
+	pub lw: FutureTask,
+	pub hb: FutureTask,
 }
 
 impl Tasks {
@@ -48,19 +49,48 @@ impl Tasks {
 				crate::err::Error::NodeAgent("live query task failed and has been logged");
 			RootError::Db(inner_err)
 		})?;
+		self.lw.await.map_err(|e| {
+			error!("Live query window task failed: {}", e);
+			let inner_err =
+				crate::err::Error::NodeAgent("live query window task failed and has been logged");
+			RootError::Db(inner_err)
+		})?;
+		self.hb.await.map_err(|e| {
+			error!("Heartbeat task failed: {}", e);
+			let inner_err =
+				crate::err::Error::NodeAgent("heartbeat task failed and has been logged");
+			RootError::Db(inner_err)
+		})?;
+		Ok(())
+	}
+
+	#[cfg(target_arch = "wasm32")]
+	pub async fn resolve(self) -> Result<(), RootError> {
+		// Tasks spawned locally can not be awaited, so wait until they report completion
+		while !self.nd.load(Ordering::Relaxed)
+			|| !self.lq.load(Ordering::Relaxed)
+			|| !self.lw.load(Ordering::Relaxed)
+			|| !self.hb.load(Ordering::Relaxed)
+		{
+			wasmtimer::tokio::sleep(Duration::from_millis(10)).await;
+		}
 		Ok(())
 	}
 }
 
 /// Starts tasks that are required for the correct running of the engine
-pub fn start_tasks(opt: &EngineOptions, dbs: Arc<Datastore>) -> (Tasks, [Sender<()>; 2]) {
+pub fn start_tasks(opt: &EngineOptions, dbs: Arc<Datastore>) -> (Tasks, [Sender<()>; 4]) {
 	let nd = init(opt, dbs.clone());
-	let lq = live_query_change_feed(opt, dbs);
-	let cancellation_channels = [nd.1, lq.1];
+	let lq = live_query_change_feed(opt, dbs.clone());
+	let lw = live_query_windows(opt, dbs.clone());
+	let hb = heartbeat(opt, dbs);
+	let cancellation_channels = [nd.1, lq.1, lw.1, hb.1];
 	(
 		Tasks {
 			nd: nd.0,
 			lq: lq.0,
+			lw: lw.0,
+			hb: hb.0,
 		},
 		cancellation_channels,
 	)
@@ -86,7 +116,7 @@ fn init(opt: &EngineOptions, dbs: Arc<Datastore>) -> (FutureTask, Sender<()>) {
 	let (tx, rx) = flume::bounded(1);
 
 	let _fut = spawn_future(async move {
-		let _lifecycle = crate::dbs::LoggingLifecycle::new("heartbeat task".to_string());
+		let _lifecycle = crate::dbs::LoggingLifecycle::new("node agent task".to_string());
 		let ticker = interval_ticker(tick_interval).await;
 		let streams = (
 			ticker.map(|i| {
@@ -163,6 +193,88 @@ fn live_query_change_feed(opt: &EngineOptions, dbs: Arc<Datastore>) -> (FutureTa
 	return (ret_status, tx);
 }
 
+// Start the node-local timer which closes live query windows, and sends their notifications and any compacted notifications
+fn live_query_windows(opt: &EngineOptions, dbs: Arc<Datastore>) -> (FutureTask, Sender<()>) {
+	let window_interval = opt.window_interval;
+
+	#[cfg(target_arch = "wasm32")]
+	let completed_status = Arc::new(AtomicBool::new(false));
+	#[cfg(target_arch = "wasm32")]
+	let ret_status = completed_status.clone();
+
+	// We create a channel that can be streamed that will indicate termination
+	let (tx, rx) = flume::bounded(1);
+
+	let _fut = spawn_future(async move {
+		let _lifecycle = crate::dbs::LoggingLifecycle::new("live query window task".to_string());
+		let ticker = interval_ticker(window_interval).await;
+		let streams = (
+			ticker.map(|i| {
+				trace!("Live query window tick: {:?}", i);
+				Some(i)
+			}),
+			rx.into_stream().map(|_| None),
+		);
+		let mut streams = streams.merge();
+
+		while let Some(Some(_)) = streams.next().await {
+			if let Err(e) = dbs.process_lq_windows().await {
+				error!("Error running live query window tick: {}", e);
+				break;
+			}
+			if let Err(e) = dbs.process_lq_compactions().await {
+				error!("Error running live query compaction tick: {}", e);
+				break;
+			}
+		}
+		#[cfg(target_arch = "wasm32")]
+		completed_status.store(true, Ordering::Relaxed);
+	});
+	#[cfg(not(target_arch = "wasm32"))]
+	return (_fut, tx);
+	#[cfg(target_arch = "wasm32")]
+	return (ret_status, tx);
+}
+
+// Start the heartbeat writer, which keeps this node registered as alive in the cluster
+fn heartbeat(opt: &EngineOptions, dbs: Arc<Datastore>) -> (FutureTask, Sender<()>) {
+	let heartbeat_interval = opt.heartbeat_interval;
+
+	#[cfg(target_arch = "wasm32")]
+	let completed_status = Arc::new(AtomicBool::new(false));
+	#[cfg(target_arch = "wasm32")]
+	let ret_status = completed_status.clone();
+
+	// We create a channel that can be streamed that will indicate termination
+	let (tx, rx) = flume::bounded(1);
+
+	let _fut = spawn_future(async move {
+		let _lifecycle = crate::dbs::LoggingLifecycle::new("heartbeat task".to_string());
+		let ticker = interval_ticker(heartbeat_interval).await;
+		let streams = (
+			ticker.map(|i| {
+				trace!("Heartbeat tick: {:?}", i);
+				Some(i)
+			}),
+			rx.into_stream().map(|_| None),
+		);
+		let mut streams = streams.merge();
+
+		while let Some(Some(_)) = streams.next().await {
+			// A missed heartbeat is retried on the next tick, before the node expires
+			if let Err(e) = dbs.heartbeat().await {
+				error!("Error writing node heartbeat: {}", e);
+			}
+		}
+		#[cfg(target_arch = "wasm32")]
+		completed_status.store(true, Ordering::Relaxed);
+	});
+	#[cfg(not(target_arch = "wasm32"))]
+	return (_fut, tx);
+	#[cfg(target_arch = "wasm32")]
+	return (ret_status, tx);
+}
+
 async fn interval_ticker(interval: Duration) -> IntervalStream {
 	#[cfg(not(target_arch = "wasm32"))]
 	use tokio::{time, time::MissedTickBehavior};
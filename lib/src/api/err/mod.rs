@@ -42,6 +42,13 @@ pub enum Error {
 	#[error("Invalid bindings: {0}")]
 	InvalidBindings(Value),
 
+	/// Tried to bind a value which the HTTP protocol can not send without changing its type
+	#[error("The `{name}` binding can not be sent over HTTP without changing its type: {value}. Use the WebSocket protocol to bind this value")]
+	BindingNotPreserved {
+		name: String,
+		value: Value,
+	},
+
 	/// Tried to use a range query on a record ID
 	#[error("Range on record IDs not supported: {0}")]
 	RangeOnRecordId(Thing),
@@ -93,6 +100,21 @@ pub enum Error {
 	#[error("Invalid URL: {0}")]
 	InvalidUrl(String),
 
+	/// A header configured for the connection is invalid
+	#[error("Invalid header `{name}`: {error}")]
+	InvalidHeader {
+		name: String,
+		error: String,
+	},
+
+	/// The connection could not be established through the configured proxy
+	#[error("Unable to connect through the proxy: {0}")]
+	Proxy(String),
+
+	/// The configured certificate authorities could not be loaded
+	#[error("Invalid CA certificates: {0}")]
+	InvalidCertificates(String),
+
 	/// Failed to convert a `sql::Value` to `T`
 	#[error("Failed to convert `{value}` to `T`: {error}")]
 	FromValue {
@@ -171,6 +193,14 @@ pub enum Error {
 	#[error("The protocol or storage engine does not support live queries on this architecture")]
 	LiveQueriesNotSupported,
 
+	/// The live query was killed by the server, because its permissions were revoked
+	#[error("The live query was killed: {0}")]
+	LiveQueryKilled(String),
+
+	/// The protocol or storage engine being used does not support saving and loading sessions
+	#[error("The protocol or storage engine does not support saving and loading sessions")]
+	SessionsNotSupported,
+
 	/// Tried to use a range query on an object
 	#[error("Live queries on objects not supported: {0}")]
 	LiveOnObject(Object),
@@ -12,3 +12,5 @@ pub static AUTH_NS: HeaderName = HeaderName::from_static("surreal-auth-ns");
 pub static AUTH_DB: HeaderName = HeaderName::from_static("surreal-auth-db");
 pub static VERSION: HeaderName = HeaderName::from_static("surreal-version");
 pub static VERSION_LEGACY: HeaderName = HeaderName::from_static("version");
+pub static ACCEPT_VERSION: HeaderName = HeaderName::from_static("accept-version");
+pub static API_VERSION: HeaderName = HeaderName::from_static("surreal-api-version");
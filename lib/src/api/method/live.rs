@@ -184,7 +184,13 @@ macro_rules! poll_next {
 				return Poll::Ready(None);
 			};
 			match rx.poll_next_unpin(cx) {
-				Poll::Ready(Some($notification)) => $body,
+				Poll::Ready(Some($notification)) => {
+					// A killed live query sends no further notifications
+					if $notification.action == dbs::Action::Killed {
+						self.as_mut().rx = None;
+					}
+					$body
+				}
 				Poll::Ready(None) => Poll::Ready(None),
 				Poll::Pending => Poll::Pending,
 			}
@@ -210,13 +216,18 @@ where
 macro_rules! poll_next_and_convert {
 	() => {
 		poll_next! {
-			notification => match from_value(notification.result) {
+			notification => match notification.action {
+				dbs::Action::Killed => {
+					Poll::Ready(Some(Err(Error::LiveQueryKilled(notification.result.as_raw_string()).into())))
+				}
+				_ => match from_value(notification.result) {
 				Ok(data) => Poll::Ready(Some(Ok(Notification {
 					data,
 					query_id: notification.id.0,
 					action: notification.action.into(),
 				}))),
 				Err(error) => Poll::Ready(Some(Err(error.into()))),
+				},
 			}
 		}
 	};
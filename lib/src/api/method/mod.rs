@@ -18,6 +18,7 @@ mod invalidate;
 mod merge;
 mod patch;
 mod select;
+mod session;
 mod set;
 mod signin;
 mod signup;
@@ -54,6 +55,8 @@ pub use patch::Patch;
 pub use query::Query;
 pub use query::QueryStream;
 pub use select::Select;
+pub use session::LoadSession;
+pub use session::SaveSession;
 pub use set::Set;
 pub use signin::Signin;
 pub use signup::Signup;
@@ -118,9 +121,11 @@ impl Method {
 			Method::Insert => "insert",
 			Method::Kill => "kill",
 			Method::Live => "live",
+			Method::LoadSession => "load_session",
 			Method::Merge => "merge",
 			Method::Patch => "patch",
 			Method::Query => "query",
+			Method::SaveSession => "save_session",
 			Method::Select => "select",
 			Method::Set => "set",
 			Method::Signin => "signin",
@@ -590,6 +595,58 @@ where
 		}
 	}
 
+	/// Saves the authentication, the selected namespace and database, and the parameters of the
+	/// current session, sealed with a key, so that the session can be restored later with
+	/// [`Surreal::load_session`]
+	///
+	/// Only supported by the local engines.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// let snapshot = db.save_session("secret key").await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn save_session(&self, key: impl Into<Vec<u8>>) -> SaveSession<C> {
+		SaveSession {
+			client: Cow::Borrowed(self),
+			key: key.into(),
+		}
+	}
+
+	/// Restores a session which was saved with [`Surreal::save_session`]
+	///
+	/// The session is only restored if it was sealed with the same key, and if the user, token,
+	/// or scope which authenticated it, and the namespace and database which it selected, still
+	/// exist. Only supported by the local engines.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// # let snapshot = Vec::new();
+	/// db.load_session("secret key", snapshot).await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn load_session(
+		&self,
+		key: impl Into<Vec<u8>>,
+		snapshot: impl Into<Vec<u8>>,
+	) -> LoadSession<C> {
+		LoadSession {
+			client: Cow::Borrowed(self),
+			key: key.into(),
+			snapshot: snapshot.into(),
+		}
+	}
+
 	/// Authenticates the current connection with a JWT token
 	///
 	/// # Examples
@@ -662,10 +719,12 @@ where
 	/// # }
 	/// ```
 	pub fn query(&self, query: impl opt::IntoQuery) -> Query<C> {
+		let mut bindings = Default::default();
+		let query = query.into_query_with_bindings(&mut bindings);
 		Query {
 			client: Cow::Borrowed(self),
-			query: vec![query.into_query()],
-			bindings: Ok(Default::default()),
+			query: vec![query],
+			bindings: Ok(bindings),
 			register_live_queries: true,
 		}
 	}
@@ -1136,6 +1195,9 @@ where
 	/// // Export to a file
 	/// db.export("backup.sql").await?;
 	///
+	/// // Export only some of the tables to a file
+	/// db.export("users.sql").tables(["user", "account"]).await?;
+	///
 	/// // Export to a stream of bytes
 	/// let mut backup = db.export(()).await?;
 	/// while let Some(result) = backup.next().await {
@@ -1156,6 +1218,7 @@ where
 			client: Cow::Borrowed(self),
 			target: target.into_export_destination(),
 			ml_config: None,
+			tables: Vec::new(),
 			response: PhantomData,
 			export_type: PhantomData,
 		}
@@ -155,7 +155,11 @@ where
 {
 	/// Chains a query onto an existing query
 	pub fn query(mut self, query: impl opt::IntoQuery) -> Self {
-		self.query.push(query.into_query());
+		let query = match &mut self.bindings {
+			Ok(bindings) => query.into_query_with_bindings(bindings),
+			Err(_) => query.into_query(),
+		};
+		self.query.push(query);
 		self
 	}
 
@@ -205,6 +209,30 @@ where
 	/// # Ok(())
 	/// # }
 	/// ```
+	///
+	/// Binding SurrealQL values
+	///
+	/// Values such as decimals and datetimes are serialized as strings by their
+	/// Rust crates. Bind them as [`sql`](crate::sql) values to keep their type.
+	/// The WebSocket protocol and the embedded engines preserve every value type,
+	/// while the HTTP protocol returns an error for values, such as geometries and
+	/// bytes, which it can not send without changing their type.
+	///
+	/// ```no_run
+	/// use rust_decimal::Decimal;
+	/// use surrealdb::sql;
+	///
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// let price: Decimal = "12345678901234567890.99".parse().unwrap();
+	/// let response = db.query("CREATE product SET price = $price, created = $created")
+	///     .bind(("price", sql::Number::Decimal(price)))
+	///     .bind(("created", sql::Datetime::default()))
+	///     .await?;
+	/// # Ok(())
+	/// # }
+	/// ```
 	pub fn bind(mut self, bindings: impl Serialize) -> Self {
 		if let Ok(current) = &mut self.bindings {
 			match to_value(bindings) {
@@ -0,0 +1,104 @@
+use crate::api::conn::Method;
+use crate::api::conn::Param;
+use crate::api::Connection;
+use crate::api::Error;
+use crate::api::ExtraFeatures;
+use crate::api::Result;
+use crate::method::OnceLockExt;
+use crate::sql::Value;
+use crate::Surreal;
+use std::borrow::Cow;
+use std::future::Future;
+use std::future::IntoFuture;
+use std::pin::Pin;
+
+/// A session save future
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct SaveSession<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+	pub(super) key: Vec<u8>,
+}
+
+impl<C> SaveSession<'_, C>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different thread
+	pub fn into_owned(self) -> SaveSession<'static, C> {
+		SaveSession {
+			client: Cow::Owned(self.client.into_owned()),
+			..self
+		}
+	}
+}
+
+impl<'r, Client> IntoFuture for SaveSession<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<Vec<u8>>;
+	type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send + Sync + 'r>>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let router = self.client.router.extract()?;
+			if !router.features.contains(&ExtraFeatures::Sessions) {
+				return Err(Error::SessionsNotSupported.into());
+			}
+			let mut conn = Client::new(Method::SaveSession);
+			match conn
+				.execute_value(router, Param::new(vec![Value::Bytes(self.key.into())]))
+				.await?
+			{
+				Value::Bytes(snapshot) => Ok(snapshot.into_inner()),
+				value => {
+					Err(Error::InternalError(format!("unexpected session snapshot: {value}"))
+						.into())
+				}
+			}
+		})
+	}
+}
+
+/// A session load future
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct LoadSession<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+	pub(super) key: Vec<u8>,
+	pub(super) snapshot: Vec<u8>,
+}
+
+impl<C> LoadSession<'_, C>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different thread
+	pub fn into_owned(self) -> LoadSession<'static, C> {
+		LoadSession {
+			client: Cow::Owned(self.client.into_owned()),
+			..self
+		}
+	}
+}
+
+impl<'r, Client> IntoFuture for LoadSession<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<()>;
+	type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send + Sync + 'r>>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let router = self.client.router.extract()?;
+			if !router.features.contains(&ExtraFeatures::Sessions) {
+				return Err(Error::SessionsNotSupported.into());
+			}
+			let mut conn = Client::new(Method::LoadSession);
+			let params = vec![Value::Bytes(self.key.into()), Value::Bytes(self.snapshot.into())];
+			conn.execute_unit(router, Param::new(params)).await
+		})
+	}
+}
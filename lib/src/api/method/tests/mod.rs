@@ -37,6 +37,10 @@ async fn api() {
 	// invalidate
 	let _: () = DB.invalidate().await.unwrap();
 
+	// save and load a session
+	let snapshot: Vec<u8> = DB.save_session("key").await.unwrap();
+	let _: () = DB.load_session("key", snapshot).await.unwrap();
+
 	// use
 	let _: () = DB.use_ns("test-ns").use_db("test-db").await.unwrap();
 
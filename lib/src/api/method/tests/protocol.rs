@@ -73,6 +73,7 @@ impl Connection for Client {
 			let (route_tx, route_rx) = flume::bounded(capacity);
 			let mut features = HashSet::new();
 			features.insert(ExtraFeatures::Backup);
+			features.insert(ExtraFeatures::Sessions);
 			let router = Router {
 				features,
 				sender: route_tx,
@@ -86,6 +86,14 @@ pub(super) fn mock(route_rx: Receiver<Option<Route>>) {
 					}
 					_ => unreachable!(),
 				},
+				Method::SaveSession => match &params[..] {
+					[_] => Ok(DbResponse::Other(Value::Bytes(Vec::new().into()))),
+					_ => unreachable!(),
+				},
+				Method::LoadSession => match &params[..] {
+					[_, _] => Ok(DbResponse::Other(Value::None)),
+					_ => unreachable!(),
+				},
 				Method::Export | Method::Import => match param.file {
 					Some(_) => Ok(DbResponse::Other(Value::None)),
 					_ => unreachable!(),
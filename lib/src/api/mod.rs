@@ -1,5 +1,6 @@
 //! Functionality for connecting to local and remote databases
 
+pub mod builder;
 pub mod engine;
 pub mod err;
 #[cfg(feature = "protocol-http")]
@@ -164,6 +165,7 @@ where
 pub(crate) enum ExtraFeatures {
 	Backup,
 	LiveQueries,
+	Sessions,
 }
 
 /// A database client instance for embedded or remote databases
@@ -7,6 +7,7 @@ use crate::{syn, Notification};
 use futures::future::Either;
 use futures::stream::select_all;
 use serde::de::DeserializeOwned;
+use std::collections::BTreeMap;
 use std::marker::PhantomData;
 use std::mem;
 
@@ -14,6 +15,18 @@ use std::mem;
 pub trait IntoQuery {
 	/// Converts an input into SQL statements
 	fn into_query(self) -> Result<Vec<Statement>>;
+
+	/// Converts an input into SQL statements, adding any parameters which it binds
+	#[doc(hidden)]
+	fn into_query_with_bindings(
+		self,
+		_bindings: &mut BTreeMap<String, Value>,
+	) -> Result<Vec<Statement>>
+	where
+		Self: Sized,
+	{
+		self.into_query()
+	}
 }
 
 impl IntoQuery for sql::Query {
@@ -118,6 +118,8 @@ extern crate tracing;
 
 mod api;
 
+#[doc(inline)]
+pub use api::builder;
 #[doc(inline)]
 pub use api::engine;
 #[cfg(feature = "protocol-http")]
@@ -155,6 +157,7 @@ pub mod channel {
 pub mod error {
 	pub use crate::api::err::Error as Api;
 	pub use crate::err::Error as Db;
+	pub use crate::err::ErrorKind as Kind;
 }
 
 /// The action performed on a record
@@ -166,6 +169,12 @@ pub enum Action {
 	Create,
 	Update,
 	Delete,
+	/// A live query window closed, and the data is the aggregated result of the window
+	Window,
+	/// The live query was killed because its permissions were revoked, and the data is the reason
+	Killed,
+	/// The number of records matching a counting live query changed, and the data is the number
+	Count,
 }
 
 impl From<dbs::Action> for Action {
@@ -174,6 +183,9 @@ impl From<dbs::Action> for Action {
 			dbs::Action::Create => Self::Create,
 			dbs::Action::Update => Self::Update,
 			dbs::Action::Delete => Self::Delete,
+			dbs::Action::Window => Self::Window,
+			dbs::Action::Killed => Self::Killed,
+			dbs::Action::Count => Self::Count,
 			_ => unreachable!(),
 		}
 	}
@@ -0,0 +1,106 @@
+mod parse;
+use parse::Parse;
+
+mod helpers;
+use helpers::*;
+
+use surrealdb::dbs::Session;
+use surrealdb::err::Error;
+use surrealdb::sql::{self, Value};
+
+#[tokio::test]
+async fn alter_field_type() -> Result<(), Error> {
+	let sql = "
+		DEFINE FIELD age ON user TYPE string;
+		CREATE user:1 SET age = '27';
+		ALTER FIELD age ON user TYPE int;
+		INFO FOR TABLE user;
+		CREATE user:2 SET age = 30;
+		ALTER FIELD IF EXISTS email ON user TYPE string;
+		ALTER FIELD email ON user TYPE string;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 7);
+	//
+	for _ in 0..3 {
+		res.remove(0).result?;
+	}
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"{
+			events: {},
+			fields: { age: 'DEFINE FIELD age ON user TYPE int PERMISSIONS FULL' },
+			tables: {},
+			indexes: {},
+			lives: {},
+		}",
+	);
+	assert_eq!(tmp, val);
+	// New values are converted using the new type
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: user:2, age: 30 }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::None);
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp, Err(Error::FdNotFound { .. })));
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn alter_field_backfill() -> Result<(), Error> {
+	let sql = "
+		DEFINE FIELD age ON user TYPE string;
+		CREATE user:1 SET age = '27';
+		CREATE user:2 SET age = 'unknown';
+		CREATE user:3 SET age = '3 years';
+		ALTER FIELD age ON user TYPE int BACKFILL WITH IF $value = '3 years' THEN 3 ELSE $value END;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	for _ in 0..5 {
+		res.remove(0).result?;
+	}
+	// The backfill has not yet been processed
+	let tmp = dbs.execute("INFO FOR TABLE user", &ses, None).await?.remove(0).result?;
+	let val = Value::parse(
+		"{ age: { kind: 'int', processed: 0, converted: 0, rejected: 0, complete: false } }",
+	);
+	assert_eq!(tmp.pick(&sql::idiom("backfills")?), val);
+	// The backfill is processed in the background
+	dbs.tick().await?;
+	let sql = "
+		INFO FOR TABLE user;
+		SELECT * FROM user;
+		SELECT record, field, value FROM user_quarantine;
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"{ age: { kind: 'int', processed: 3, converted: 2, rejected: 1, complete: true } }",
+	);
+	assert_eq!(tmp.pick(&sql::idiom("backfills")?), val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{ id: user:1, age: 27 },
+			{ id: user:2, age: 'unknown' },
+			{ id: user:3, age: 3 },
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ record: user:2, field: 'age', value: 'unknown' }]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
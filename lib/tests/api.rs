@@ -171,6 +171,27 @@ mod api_integration {
 			drop(permit);
 		}
 
+		#[test_log::test(tokio::test)]
+		async fn bindings_which_change_type_are_rejected() {
+			let (permit, db) = new_db().await;
+			db.use_ns(NS).use_db(Ulid::new().to_string()).await.unwrap();
+			drop(permit);
+			let point = surrealdb::sql::Geometry::Point((1.0, 2.0).into());
+			let error = db
+				.query("RETURN $point")
+				.bind(("point", vec![Value::from(point)]))
+				.await
+				.unwrap_err();
+			let Error::Api(ApiError::BindingNotPreserved {
+				name,
+				..
+			}) = error
+			else {
+				panic!("unexpected error: {error:?}");
+			};
+			assert_eq!(name, "point");
+		}
+
 		include!("api/mod.rs");
 		include!("api/backup.rs");
 	}
@@ -269,6 +290,7 @@ mod api_integration {
 		include!("api/mod.rs");
 		include!("api/live.rs");
 		include!("api/backup.rs");
+		include!("api/session.rs");
 	}
 
 	#[cfg(feature = "kv-rocksdb")]
@@ -304,6 +326,7 @@ mod api_integration {
 		include!("api/mod.rs");
 		include!("api/live.rs");
 		include!("api/backup.rs");
+		include!("api/session.rs");
 	}
 
 	#[cfg(feature = "kv-rocksdb")]
@@ -339,6 +362,7 @@ mod api_integration {
 		include!("api/mod.rs");
 		include!("api/live.rs");
 		include!("api/backup.rs");
+		include!("api/session.rs");
 	}
 
 	#[cfg(feature = "kv-speedb")]
@@ -374,6 +398,7 @@ mod api_integration {
 		include!("api/mod.rs");
 		include!("api/live.rs");
 		include!("api/backup.rs");
+		include!("api/session.rs");
 	}
 
 	#[cfg(feature = "kv-tikv")]
@@ -407,6 +432,7 @@ mod api_integration {
 		include!("api/mod.rs");
 		include!("api/live.rs");
 		include!("api/backup.rs");
+		include!("api/session.rs");
 	}
 
 	#[cfg(feature = "kv-fdb")]
@@ -437,6 +463,7 @@ mod api_integration {
 		include!("api/mod.rs");
 		include!("api/live.rs");
 		include!("api/backup.rs");
+		include!("api/session.rs");
 	}
 
 	#[cfg(feature = "kv-surrealkv")]
@@ -472,6 +499,7 @@ mod api_integration {
 		include!("api/mod.rs");
 		include!("api/live.rs");
 		include!("api/backup.rs");
+		include!("api/session.rs");
 	}
 
 	#[cfg(feature = "protocol-http")]
@@ -380,6 +380,30 @@ async fn query_binds() {
 	assert_eq!(record.name, "John Doe");
 }
 
+#[test_log::test(tokio::test)]
+async fn query_builder() {
+	use surrealdb::builder::{field, select};
+	let (permit, db) = new_db().await;
+	db.use_ns(NS).use_db(Ulid::new().to_string()).await.unwrap();
+	drop(permit);
+	let sql = "
+		CREATE user:john SET name = 'John Doe', age = 35;
+		CREATE user:jane SET name = 'Jane Doe', age = 17;
+		CREATE user:jaime SET name = 'Jaime Doe', age = 21;
+	";
+	db.query(sql).await.unwrap().check().unwrap();
+	let adults = select().from("user").filter(field("age").gte(18)).order_by(field("age").desc());
+	let named = select().from("user").filter(field("name").eq("'; REMOVE TABLE user; --"));
+	let mut response = db.query(adults).query(named).await.unwrap().check().unwrap();
+	let names: Vec<String> = response.take((0, "name")).unwrap();
+	assert_eq!(names, vec!["John Doe", "Jaime Doe"]);
+	let names: Vec<String> = response.take((1, "name")).unwrap();
+	assert!(names.is_empty());
+	let mut response = db.query("SELECT count() FROM user GROUP ALL").await.unwrap();
+	let count: Option<i64> = response.take("count").unwrap();
+	assert_eq!(count, Some(3));
+}
+
 #[test_log::test(tokio::test)]
 async fn query_with_stats() {
 	let (permit, db) = new_db().await;
@@ -1346,3 +1370,35 @@ async fn return_bool() {
 	let value: Value = response.take(0).unwrap();
 	assert_eq!(value, Value::Bool(false));
 }
+
+#[test_log::test(tokio::test)]
+async fn query_binds_preserve_types() {
+	let (permit, db) = new_db().await;
+	db.use_ns(NS).use_db(Ulid::new().to_string()).await.unwrap();
+	drop(permit);
+	let decimal: rust_decimal::Decimal = "12345678901234567890.123456789".parse().unwrap();
+	let datetime = surrealdb::sql::Datetime::default();
+	let uuid = surrealdb::sql::Uuid::new_v4();
+	let mut response = db
+		.query("RETURN [$decimal, $duration, $std_duration, $datetime, $uuid, $negative, $none]")
+		.bind(("decimal", surrealdb::sql::Number::Decimal(decimal)))
+		.bind(("duration", surrealdb::sql::Duration::from(Duration::from_millis(1500))))
+		.bind(("std_duration", Duration::from_millis(1500)))
+		.bind(("datetime", datetime.clone()))
+		.bind(("uuid", uuid))
+		.bind(("negative", -1.5))
+		.bind(("none", None::<String>))
+		.await
+		.unwrap();
+	let value: Value = response.take(0).unwrap();
+	let expected = Value::from(vec![
+		Value::from(decimal),
+		Value::from(surrealdb::sql::Duration::from(Duration::from_millis(1500))),
+		Value::from(surrealdb::sql::Duration::from(Duration::from_millis(1500))),
+		Value::from(datetime),
+		Value::from(uuid),
+		Value::from(-1.5),
+		Value::None,
+	]);
+	assert_eq!(value, expected);
+}
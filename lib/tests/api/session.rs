@@ -0,0 +1,54 @@
+// Tests for saving and loading sessions
+// Supported by the storage engines
+
+#[test_log::test(tokio::test)]
+async fn save_and_load_session() {
+	let (permit, db) = new_db().await;
+	let database = Ulid::new().to_string();
+	db.use_ns(NS).use_db(&database).await.unwrap();
+	db.set("name", "John Doe").await.unwrap();
+	drop(permit);
+	let snapshot = db.save_session("secret").await.unwrap();
+	db.invalidate().await.unwrap();
+	db.unset("name").await.unwrap();
+	// A snapshot sealed with another key is not restored
+	db.load_session("other", snapshot.clone()).await.unwrap_err();
+	db.load_session("secret", snapshot).await.unwrap();
+	let mut response = db.query("RETURN $name").await.unwrap();
+	let name: Option<String> = response.take(0).unwrap();
+	assert_eq!(name.as_deref(), Some("John Doe"));
+	let _: Option<RecordId> = db.create(("user", "john")).await.unwrap();
+}
+
+#[test_log::test(tokio::test)]
+async fn load_session_of_removed_user() {
+	let (permit, db) = new_db().await;
+	let database = Ulid::new().to_string();
+	db.use_ns(NS).use_db(&database).await.unwrap();
+	let user = Ulid::new().to_string();
+	let pass = "password123";
+	let sql = format!("DEFINE USER `{user}` ON DATABASE PASSWORD '{pass}'");
+	let response = db.query(sql).await.unwrap();
+	drop(permit);
+	response.check().unwrap();
+	db.signin(Database {
+		namespace: NS,
+		database: &database,
+		username: &user,
+		password: pass,
+	})
+	.await
+	.unwrap();
+	let snapshot = db.save_session("secret").await.unwrap();
+	// The session is restored while the user exists
+	db.load_session("secret", snapshot.clone()).await.unwrap();
+	db.signin(Root {
+		username: ROOT_USER,
+		password: ROOT_PASS,
+	})
+	.await
+	.unwrap();
+	db.query(format!("REMOVE USER `{user}` ON DATABASE")).await.unwrap().check().unwrap();
+	// The session is not restored once the user is removed
+	db.load_session("secret", snapshot).await.unwrap_err();
+}
@@ -1,6 +1,6 @@
 mod parse;
 
-use chrono::DateTime;
+use chrono::{DateTime, Utc};
 
 use helpers::new_ds;
 use parse::Parse;
@@ -10,7 +10,7 @@ use surrealdb::fflags::FFLAGS;
 use surrealdb::kvs::Datastore;
 use surrealdb::kvs::LockType::Optimistic;
 use surrealdb::kvs::TransactionType::Write;
-use surrealdb::sql::Value;
+use surrealdb::sql::{self, Value};
 use surrealdb_core::test_helpers::{generate_versionstamp_sequences, to_u128_be};
 
 mod helpers;
@@ -781,3 +781,125 @@ async fn changefeed_with_original() -> Result<(), Error> {
 
 	Ok(())
 }
+
+#[tokio::test]
+async fn table_change_feeds_retention() -> Result<(), Error> {
+	let sql = "
+		DEFINE TABLE short CHANGEFEED 1h;
+		DEFINE TABLE long CHANGEFEED 2h;
+		CREATE short:one;
+		CREATE long:one;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test-cf-retention").with_db("test-cf-retention");
+	dbs.tick_at(0).await?;
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	for _ in 0..4 {
+		res.remove(0).result?;
+	}
+	dbs.tick_at(1).await?;
+	// Both tables report the size of their change feed, including their definition
+	let sql = "
+		INFO FOR TABLE short;
+		INFO FOR TABLE long STRUCTURE;
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp.pick(&sql::idiom("changefeed.entries")?), Value::from(2));
+	assert_eq!(tmp.pick(&sql::idiom("changefeed.retention")?), Value::parse("1h"));
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp.pick(&sql::idiom("changefeed.entries")?), Value::from(2));
+	assert_eq!(tmp.pick(&sql::idiom("changefeed.retention")?), Value::parse("2h"));
+	// Only the table with the shorter retention is pruned
+	dbs.tick_at(3601).await?;
+	let sql = "
+		SHOW CHANGES FOR TABLE short SINCE 0;
+		SHOW CHANGES FOR TABLE long SINCE 0;
+		INFO FOR TABLE short;
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::parse("[]"));
+	let Value::Array(tmp) = res.remove(0).result? else {
+		unreachable!()
+	};
+	assert_eq!(tmp.len(), 2);
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp.pick(&sql::idiom("changefeed.entries")?), Value::from(0));
+	assert_eq!(tmp.pick(&sql::idiom("changefeed.oldest")?), Value::None);
+	// The table with the longer retention is pruned later
+	dbs.tick_at(7201).await?;
+	let sql = "SHOW CHANGES FOR TABLE long SINCE 0";
+	let tmp = dbs.execute(sql, &ses, None).await?.remove(0).result?;
+	assert_eq!(tmp, Value::parse("[]"));
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_as_of_change_feed() -> Result<(), Error> {
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let sql = "
+		DEFINE TABLE user CHANGEFEED 1h INCLUDE ORIGINAL;
+		DEFINE TABLE log CHANGEFEED 1h;
+		CREATE user:amos SET name = 'Amos';
+		CREATE user:jane SET name = 'Jane';
+	";
+	for res in dbs.execute(sql, &ses, None).await? {
+		res.result?;
+	}
+	// Save the time before the records are changed
+	let ts = Utc::now();
+	dbs.tick_at(ts.timestamp().try_into().unwrap()).await?;
+	let sql = "
+		UPDATE user:amos SET name = 'AMOS';
+		DELETE user:jane;
+		CREATE user:tobie SET name = 'Tobie';
+	";
+	for res in dbs.execute(sql, &ses, None).await? {
+		res.result?;
+	}
+	let sql = format!(
+		"
+		SELECT * FROM user AS OF d'{ts}';
+		SELECT * FROM user:jane, user:tobie AS OF d'{ts}';
+		SELECT * FROM user;
+		SELECT * FROM log AS OF d'{ts}';
+		SELECT * FROM user AS OF d'2020-01-01T00:00:00Z';
+		",
+		ts = ts.to_rfc3339()
+	);
+	let res = &mut dbs.execute(&sql, &ses, None).await?;
+	assert_eq!(res.len(), 5);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{ id: user:amos, name: 'Amos' },
+			{ id: user:jane, name: 'Jane' },
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: user:jane, name: 'Jane' }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{ id: user:amos, name: 'AMOS' },
+			{ id: user:tobie, name: 'Tobie' },
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp, Err(Error::VersionNoChangeFeed { .. })));
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp, Err(Error::VersionOutsideRetention { .. })));
+	//
+	Ok(())
+}
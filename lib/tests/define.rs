@@ -4,13 +4,15 @@ use parse::Parse;
 mod helpers;
 use helpers::*;
 
+use std::collections::BTreeMap;
 use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use surrealdb::dbs::Session;
 use surrealdb::err::Error;
 use surrealdb::iam::Role;
 use surrealdb::sql::Idiom;
-use surrealdb::sql::{Part, Value};
+use surrealdb::sql::{Part, Thing, Value};
 
 #[tokio::test]
 async fn define_statement_namespace() -> Result<(), Error> {
@@ -29,6 +31,7 @@ async fn define_statement_namespace() -> Result<(), Error> {
 	let tmp = res.remove(0).result?;
 	let val = Value::parse(
 		"{
+			capabilities: [],
 			namespaces: { test: 'DEFINE NAMESPACE test' },
 			users: {},
 		}",
@@ -65,6 +68,75 @@ async fn define_statement_database() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn define_statement_database_labels() -> Result<(), Error> {
+	let sql = "
+		DEFINE DATABASE test COMMENT 'orders' LABELS { env: 'production', team: 'billing' };
+		DEFINE TABLE person LABELS { pii: 'yes' };
+		CREATE person:one, person:two;
+		INFO FOR NS;
+		INFO FOR DB;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 5);
+	//
+	for _ in 0..3 {
+		let tmp = res.remove(0).result;
+		assert!(tmp.is_ok(), "{:?}", tmp);
+	}
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"{
+			databases: { test: \"DEFINE DATABASE test COMMENT 'orders' LABELS { env: 'production', team: 'billing' }\" },
+			tokens: {},
+			users: {},
+		}",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"{
+			analyzers: {},
+			checks: {},
+			dictionaries: {},
+			tokens: {},
+			functions: {},
+			models: {},
+			params: {},
+			roles: {},
+			scopes: {},
+			tables: { person: \"DEFINE TABLE person TYPE ANY SCHEMALESS LABELS { pii: 'yes' } PERMISSIONS NONE\" },
+			users: {},
+		}",
+	);
+	assert_eq!(tmp, val);
+	//
+	let mut tmp = dbs.databases(&ses).await?;
+	assert_eq!(tmp.len(), 1);
+	let Value::Object(mut obj) = tmp.remove(0) else {
+		panic!("expected an object");
+	};
+	let size = obj.remove("size").unwrap();
+	assert!(size > Value::from(0), "{:?}", size);
+	let val = Value::parse(
+		"{
+			namespace: 'test',
+			database: 'test',
+			comment: 'orders',
+			labels: { env: 'production', team: 'billing' },
+			tables: 1,
+			records: 2,
+		}",
+	);
+	assert_eq!(Value::Object(obj), val);
+	//
+	Ok(())
+}
+
 #[tokio::test]
 async fn define_statement_function() -> Result<(), Error> {
 	let sql = "
@@ -85,10 +157,13 @@ async fn define_statement_function() -> Result<(), Error> {
 	let val = Value::parse(
 		"{
 			analyzers: {},
+			checks: {},
+			dictionaries: {},
 			tokens: {},
 			functions: { test: 'DEFINE FUNCTION fn::test($first: string, $last: string) { RETURN $first + $last; } PERMISSIONS FULL' },
 			models: {},
 			params: {},
+			roles: {},
 			scopes: {},
 			tables: {},
 			users: {},
@@ -117,10 +192,13 @@ async fn define_statement_table_drop() -> Result<(), Error> {
 	let val = Value::parse(
 		"{
 			analyzers: {},
+			checks: {},
+			dictionaries: {},
 			tokens: {},
 			functions: {},
 			models: {},
 			params: {},
+			roles: {},
 			scopes: {},
 			tables: { test: 'DEFINE TABLE test TYPE ANY DROP SCHEMALESS PERMISSIONS NONE' },
 			users: {},
@@ -149,10 +227,13 @@ async fn define_statement_table_schemaless() -> Result<(), Error> {
 	let val = Value::parse(
 		"{
 			analyzers: {},
+			checks: {},
+			dictionaries: {},
 			tokens: {},
 			functions: {},
 			models: {},
 			params: {},
+			roles: {},
 			scopes: {},
 			tables: { test: 'DEFINE TABLE test TYPE ANY SCHEMALESS PERMISSIONS NONE' },
 			users: {},
@@ -185,10 +266,13 @@ async fn define_statement_table_schemafull() -> Result<(), Error> {
 	let val = Value::parse(
 		"{
 			analyzers: {},
+			checks: {},
+			dictionaries: {},
 			tokens: {},
 			functions: {},
 			models: {},
 			params: {},
+			roles: {},
 			scopes: {},
 			tables: { test: 'DEFINE TABLE test TYPE ANY SCHEMAFULL PERMISSIONS NONE' },
 			users: {},
@@ -217,10 +301,13 @@ async fn define_statement_table_schemaful() -> Result<(), Error> {
 	let val = Value::parse(
 		"{
 			analyzers: {},
+			checks: {},
+			dictionaries: {},
 			tokens: {},
 			functions: {},
 			models: {},
 			params: {},
+			roles: {},
 			scopes: {},
 			tables: { test: 'DEFINE TABLE test TYPE ANY SCHEMAFULL PERMISSIONS NONE' },
 			users: {},
@@ -257,10 +344,13 @@ async fn define_statement_table_foreigntable() -> Result<(), Error> {
 	let val = Value::parse(
 		"{
 			analyzers: {},
+			checks: {},
+			dictionaries: {},
 			tokens: {},
 			functions: {},
 			models: {},
 			params: {},
+			roles: {},
 			scopes: {},
 			tables: {
 				test: 'DEFINE TABLE test TYPE ANY SCHEMAFULL PERMISSIONS NONE',
@@ -290,10 +380,13 @@ async fn define_statement_table_foreigntable() -> Result<(), Error> {
 	let val = Value::parse(
 		"{
 			analyzers: {},
+			checks: {},
+			dictionaries: {},
 			tokens: {},
 			functions: {},
 			models: {},
 			params: {},
+			roles: {},
 			scopes: {},
 			tables: {
 				test: 'DEFINE TABLE test TYPE ANY SCHEMAFULL PERMISSIONS NONE',
@@ -376,6 +469,132 @@ async fn define_statement_event() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn define_statement_hook() -> Result<(), Error> {
+	let sql = "
+		DEFINE HOOK ON TABLE product AFTER COMMIT PUBLISH 'cache:invalidate';
+		INFO FOR TABLE product;
+		BEGIN;
+		CREATE product:one;
+		CANCEL;
+		CREATE product:two;
+		UPDATE product:two SET price = 10;
+		DELETE product:two;
+		REMOVE HOOK `cache:invalidate` ON product;
+		CREATE product:three;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let mut channel = dbs.publications().unwrap();
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 8);
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"{
+			events: {},
+			fields: {},
+			tables: {},
+			indexes: {},
+			lives: {},
+			hooks: { '`cache:invalidate`': \"DEFINE HOOK `cache:invalidate` ON product AFTER COMMIT PUBLISH 'cache:invalidate'\" },
+		}",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_err());
+	//
+	for _ in 0..5 {
+		let tmp = res.remove(0).result;
+		assert!(tmp.is_ok());
+	}
+	// Only the committed changes are published, until the hook is removed
+	let mut msgs = Vec::new();
+	while let Ok(msg) = channel.try_recv() {
+		msgs.push(Value::from(msg));
+	}
+	let val = Value::parse(
+		"[
+			{ channel: 'cache:invalidate', table: 'product', id: product:two, action: 'CREATE' },
+			{ channel: 'cache:invalidate', table: 'product', id: product:two, action: 'UPDATE' },
+			{ channel: 'cache:invalidate', table: 'product', id: product:two, action: 'DELETE' },
+		]",
+	);
+	assert_eq!(Value::from(msgs), val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn define_statement_enrichment() -> Result<(), Error> {
+	let sql = "
+		DEFINE FUNCTION fn::score($lead: object) { RETURN string::len($lead.name) * 10; };
+		DEFINE FUNCTION fn::fail($lead: object) { THROW 'unavailable'; };
+		DEFINE ENRICHMENT ON TABLE leads CALL fn::score($after) INTO score ASYNC;
+		DEFINE ENRICHMENT ON TABLE leads CALL fn::fail($after) INTO rating ASYNC RETRY 1;
+		INFO FOR TABLE leads;
+		CREATE leads:one SET name = 'Tobie';
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 6);
+	//
+	for _ in 0..4 {
+		res.remove(0).result?;
+	}
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"{
+			fail: 'DEFINE ENRICHMENT fail ON leads CALL fn::fail($after) INTO rating ASYNC RETRY 1',
+			score: 'DEFINE ENRICHMENT score ON leads CALL fn::score($after) INTO score ASYNC',
+		}",
+	);
+	assert_eq!(tmp.pick(&[Part::from("enrichments")]), val);
+	// The record is written without waiting for the enrichments
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: leads:one, name: 'Tobie' }]");
+	assert_eq!(tmp, val);
+	// The enrichments are computed in the background
+	let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+	dbs.tick_at(now).await?;
+	let sql = "
+		SELECT * FROM leads;
+		SELECT * FROM leads_dead_letter;
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: leads:one, name: 'Tobie', score: 50 }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(tmp, val);
+	// A failed enrichment is retried, and then moved to the dead-letter table
+	dbs.tick_at(now + 1).await?;
+	let sql = "
+		SELECT * FROM leads;
+		SELECT record, enrichment, attempts, error FROM leads_dead_letter;
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: leads:one, name: 'Tobie', score: 50 }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[{ record: leads:one, enrichment: 'fail', attempts: 2, error: 'An error occurred: unavailable' }]",
+	);
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
 #[tokio::test]
 async fn define_statement_event_when_event() -> Result<(), Error> {
 	let sql = "
@@ -1272,12 +1491,15 @@ async fn define_statement_analyzer() -> Result<(), Error> {
 				english: 'DEFINE ANALYZER english TOKENIZERS BLANK,CLASS FILTERS LOWERCASE,SNOWBALL(ENGLISH)',
 				htmlAnalyzer: 'DEFINE ANALYZER htmlAnalyzer FUNCTION fn::stripHtml TOKENIZERS BLANK,CLASS'
 			},
+			checks: {},
+			dictionaries: {},
 			tokens: {},
 			functions: {
 				stripHtml: "DEFINE FUNCTION fn::stripHtml($html: string) { RETURN string::replace($html, /<[^>]*>/, ''); } PERMISSIONS FULL"
 			},
 			models: {},
 			params: {},
+			roles: {},
 			scopes: {},
 			tables: {},
 			users: {},
@@ -1287,6 +1509,125 @@ async fn define_statement_analyzer() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn define_statement_dictionary() -> Result<(), Error> {
+	let sql = r#"
+		DEFINE DICTIONARY english STOPWORDS ['the', 'a'] COMMENT 'Common words';
+		DEFINE DICTIONARY colours SYNONYMS ['grey', 'gray'], ['red', 'crimson'];
+		DEFINE DICTIONARY IF NOT EXISTS english STOPWORDS ['an'];
+		INFO FOR DB;
+		REMOVE DICTIONARY english;
+		INFO FOR DB;
+	"#;
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 6);
+	//
+	for _ in 0..2 {
+		let tmp = res.remove(0).result;
+		assert!(tmp.is_ok());
+	}
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp, Err(Error::DcAlreadyExists { .. })));
+	//
+	let tmp = res.remove(0).result?;
+	let val = tmp.pick(&["dictionaries".into()]);
+	let expected = Value::parse(
+		r#"{
+			colours: "DEFINE DICTIONARY colours SYNONYMS ['grey', 'gray'], ['red', 'crimson']",
+			english: "DEFINE DICTIONARY english STOPWORDS ['the', 'a'] COMMENT 'Common words'"
+		}"#,
+	);
+	assert_eq!(val, expected);
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	//
+	let tmp = res.remove(0).result?;
+	let val = tmp.pick(&["dictionaries".into()]);
+	let expected = Value::parse(
+		r#"{
+			colours: "DEFINE DICTIONARY colours SYNONYMS ['grey', 'gray'], ['red', 'crimson']"
+		}"#,
+	);
+	assert_eq!(val, expected);
+	Ok(())
+}
+
+#[tokio::test]
+async fn define_statement_check() -> Result<(), Error> {
+	let sql = r#"
+		DEFINE CHECK missing_age SCHEDULE '1h' AS SELECT count() FROM person WHERE age = NONE ASSERT $value = 0 COMMENT 'Every person has an age';
+		DEFINE CHECK IF NOT EXISTS missing_age SCHEDULE '1h' AS 1 ASSERT true;
+		DEFINE CHECK never SCHEDULE 0s AS 1 ASSERT true;
+		INFO FOR DB;
+		CREATE person:one SET age = 30;
+		CREATE person:two;
+	"#;
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 6);
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp, Err(Error::CkAlreadyExists { .. })));
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp, Err(Error::InvalidArguments { .. })));
+	//
+	let tmp = res.remove(0).result?;
+	let val = tmp.pick(&["checks".into()]);
+	let expected = Value::parse(
+		r#"{
+			missing_age: "DEFINE CHECK missing_age SCHEDULE 1h AS (SELECT count() FROM person WHERE age = NONE) ASSERT $value = 0 COMMENT 'Every person has an age'"
+		}"#,
+	);
+	assert_eq!(val, expected);
+	//
+	for _ in 0..2 {
+		let tmp = res.remove(0).result;
+		assert!(tmp.is_ok());
+	}
+	// The check runs in the background, and again once its schedule has passed
+	let ts = 1_700_000_000;
+	dbs.tick_at(ts).await?;
+	dbs.execute("DELETE person:two", &ses, None).await?;
+	dbs.tick_at(ts + 60).await?;
+	dbs.tick_at(ts + 3600).await?;
+	let sql = "
+		SELECT check, status, value FROM system::checks();
+		REMOVE CHECK missing_age;
+		REMOVE CHECK IF EXISTS missing_age;
+		system::checks();
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 4);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{ check: 'missing_age', status: 'failed', value: 1 },
+			{ check: 'missing_age', status: 'passed', value: 0 }
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	for _ in 0..2 {
+		let tmp = res.remove(0).result;
+		assert!(tmp.is_ok());
+	}
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(tmp, val);
+	Ok(())
+}
+
 #[tokio::test]
 async fn define_statement_search_index() -> Result<(), Error> {
 	let sql = r#"
@@ -1377,6 +1718,30 @@ async fn define_statement_user_root() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn define_statement_user_priority() -> Result<(), Error> {
+	let sql = "
+		DEFINE USER test ON ROOT PASSWORD 'test' ROLES VIEWER PRIORITY LOW;
+
+		INFO FOR ROOT;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner();
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+
+	assert_eq!(res.len(), 2);
+	//
+	let tmp = res.remove(0).result;
+
+	assert!(tmp.is_ok());
+	//
+	let tmp = res.remove(0).result?;
+	let define_str = tmp.pick(&["users".into(), "test".into()]).to_string();
+
+	assert!(define_str.ends_with(" ROLES VIEWER PRIORITY LOW\""));
+	Ok(())
+}
+
 #[tokio::test]
 async fn define_statement_user_ns() -> Result<(), Error> {
 	let dbs = new_ds().await?;
@@ -1518,8 +1883,8 @@ async fn permissions_checks_define_ns() {
 
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
-		vec!["{ namespaces: { NS: 'DEFINE NAMESPACE NS' }, users: {  } }"],
-		vec!["{ namespaces: {  }, users: {  } }"],
+		vec!["{ capabilities: [], namespaces: { NS: 'DEFINE NAMESPACE NS' }, users: {  } }"],
+		vec!["{ capabilities: [], namespaces: {  }, users: {  } }"],
 	];
 
 	let test_cases = [
@@ -1599,8 +1964,8 @@ async fn permissions_checks_define_function() {
 
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
-        vec!["{ analyzers: {  }, functions: { greet: \"DEFINE FUNCTION fn::greet() { RETURN 'Hello'; } PERMISSIONS FULL\" }, models: {  }, params: {  }, scopes: {  }, tables: {  }, tokens: {  }, users: {  } }"],
-		vec!["{ analyzers: {  }, functions: {  }, models: {  }, params: {  }, scopes: {  }, tables: {  }, tokens: {  }, users: {  } }"]
+        vec!["{ analyzers: {  }, checks: {  }, dictionaries: {  }, functions: { greet: \"DEFINE FUNCTION fn::greet() { RETURN 'Hello'; } PERMISSIONS FULL\" }, models: {  }, params: {  }, roles: {  }, scopes: {  }, tables: {  }, tokens: {  }, users: {  } }"],
+		vec!["{ analyzers: {  }, checks: {  }, dictionaries: {  }, functions: {  }, models: {  }, params: {  }, roles: {  }, scopes: {  }, tables: {  }, tokens: {  }, users: {  } }"]
     ];
 
 	let test_cases = [
@@ -1641,8 +2006,8 @@ async fn permissions_checks_define_analyzer() {
 
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
-        vec!["{ analyzers: { analyzer: 'DEFINE ANALYZER analyzer TOKENIZERS BLANK' }, functions: {  }, models: {  }, params: {  }, scopes: {  }, tables: {  }, tokens: {  }, users: {  } }"],
-		vec!["{ analyzers: {  }, functions: {  }, models: {  }, params: {  }, scopes: {  }, tables: {  }, tokens: {  }, users: {  } }"]
+        vec!["{ analyzers: { analyzer: 'DEFINE ANALYZER analyzer TOKENIZERS BLANK' }, checks: {  }, dictionaries: {  }, functions: {  }, models: {  }, params: {  }, roles: {  }, scopes: {  }, tables: {  }, tokens: {  }, users: {  } }"],
+		vec!["{ analyzers: {  }, checks: {  }, dictionaries: {  }, functions: {  }, models: {  }, params: {  }, roles: {  }, scopes: {  }, tables: {  }, tokens: {  }, users: {  } }"]
     ];
 
 	let test_cases = [
@@ -1725,8 +2090,8 @@ async fn permissions_checks_define_token_db() {
 
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
-        vec!["{ analyzers: {  }, functions: {  }, models: {  }, params: {  }, scopes: {  }, tables: {  }, tokens: { token: \"DEFINE TOKEN token ON DATABASE TYPE HS512 VALUE 'secret'\" }, users: {  } }"],
-		vec!["{ analyzers: {  }, functions: {  }, models: {  }, params: {  }, scopes: {  }, tables: {  }, tokens: {  }, users: {  } }"]
+        vec!["{ analyzers: {  }, checks: {  }, dictionaries: {  }, functions: {  }, models: {  }, params: {  }, roles: {  }, scopes: {  }, tables: {  }, tokens: { token: \"DEFINE TOKEN token ON DATABASE TYPE HS512 VALUE 'secret'\" }, users: {  } }"],
+		vec!["{ analyzers: {  }, checks: {  }, dictionaries: {  }, functions: {  }, models: {  }, params: {  }, roles: {  }, scopes: {  }, tables: {  }, tokens: {  }, users: {  } }"]
     ];
 
 	let test_cases = [
@@ -1767,8 +2132,8 @@ async fn permissions_checks_define_user_root() {
 
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
-        vec!["{ namespaces: {  }, users: { user: \"DEFINE USER user ON ROOT PASSHASH 'secret' ROLES VIEWER\" } }"],
-		vec!["{ namespaces: {  }, users: {  } }"]
+        vec!["{ capabilities: [], namespaces: {  }, users: { user: \"DEFINE USER user ON ROOT PASSHASH 'secret' ROLES VIEWER\" } }"],
+		vec!["{ capabilities: [], namespaces: {  }, users: {  } }"]
     ];
 
 	let test_cases = [
@@ -1851,8 +2216,8 @@ async fn permissions_checks_define_user_db() {
 
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
-        vec!["{ analyzers: {  }, functions: {  }, models: {  }, params: {  }, scopes: {  }, tables: {  }, tokens: {  }, users: { user: \"DEFINE USER user ON DATABASE PASSHASH 'secret' ROLES VIEWER\" } }"],
-		vec!["{ analyzers: {  }, functions: {  }, models: {  }, params: {  }, scopes: {  }, tables: {  }, tokens: {  }, users: {  } }"]
+        vec!["{ analyzers: {  }, checks: {  }, dictionaries: {  }, functions: {  }, models: {  }, params: {  }, roles: {  }, scopes: {  }, tables: {  }, tokens: {  }, users: { user: \"DEFINE USER user ON DATABASE PASSHASH 'secret' ROLES VIEWER\" } }"],
+		vec!["{ analyzers: {  }, checks: {  }, dictionaries: {  }, functions: {  }, models: {  }, params: {  }, roles: {  }, scopes: {  }, tables: {  }, tokens: {  }, users: {  } }"]
     ];
 
 	let test_cases = [
@@ -1893,8 +2258,8 @@ async fn permissions_checks_define_scope() {
 
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
-        vec!["{ analyzers: {  }, functions: {  }, models: {  }, params: {  }, scopes: { account: 'DEFINE SCOPE account SESSION 1h' }, tables: {  }, tokens: {  }, users: {  } }"],
-		vec!["{ analyzers: {  }, functions: {  }, models: {  }, params: {  }, scopes: {  }, tables: {  }, tokens: {  }, users: {  } }"]
+        vec!["{ analyzers: {  }, checks: {  }, dictionaries: {  }, functions: {  }, models: {  }, params: {  }, roles: {  }, scopes: { account: 'DEFINE SCOPE account SESSION 1h' }, tables: {  }, tokens: {  }, users: {  } }"],
+		vec!["{ analyzers: {  }, checks: {  }, dictionaries: {  }, functions: {  }, models: {  }, params: {  }, roles: {  }, scopes: {  }, tables: {  }, tokens: {  }, users: {  } }"]
     ];
 
 	let test_cases = [
@@ -1935,8 +2300,8 @@ async fn permissions_checks_define_param() {
 
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
-        vec!["{ analyzers: {  }, functions: {  }, models: {  }, params: { param: \"DEFINE PARAM $param VALUE 'foo' PERMISSIONS FULL\" }, scopes: {  }, tables: {  }, tokens: {  }, users: {  } }"],
-		vec!["{ analyzers: {  }, functions: {  }, models: {  }, params: {  }, scopes: {  }, tables: {  }, tokens: {  }, users: {  } }"]
+        vec!["{ analyzers: {  }, checks: {  }, dictionaries: {  }, functions: {  }, models: {  }, params: { param: \"DEFINE PARAM $param VALUE 'foo' PERMISSIONS FULL\" }, roles: {  }, scopes: {  }, tables: {  }, tokens: {  }, users: {  } }"],
+		vec!["{ analyzers: {  }, checks: {  }, dictionaries: {  }, functions: {  }, models: {  }, params: {  }, roles: {  }, scopes: {  }, tables: {  }, tokens: {  }, users: {  } }"]
     ];
 
 	let test_cases = [
@@ -1974,8 +2339,8 @@ async fn permissions_checks_define_table() {
 
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
-        vec!["{ analyzers: {  }, functions: {  }, models: {  }, params: {  }, scopes: {  }, tables: { TB: 'DEFINE TABLE TB TYPE ANY SCHEMALESS PERMISSIONS NONE' }, tokens: {  }, users: {  } }"],
-		vec!["{ analyzers: {  }, functions: {  }, models: {  }, params: {  }, scopes: {  }, tables: {  }, tokens: {  }, users: {  } }"]
+        vec!["{ analyzers: {  }, checks: {  }, dictionaries: {  }, functions: {  }, models: {  }, params: {  }, roles: {  }, scopes: {  }, tables: { TB: 'DEFINE TABLE TB TYPE ANY SCHEMALESS PERMISSIONS NONE' }, tokens: {  }, users: {  } }"],
+		vec!["{ analyzers: {  }, checks: {  }, dictionaries: {  }, functions: {  }, models: {  }, params: {  }, roles: {  }, scopes: {  }, tables: {  }, tokens: {  }, users: {  } }"]
     ];
 
 	let test_cases = [
@@ -2160,9 +2525,12 @@ async fn define_statement_table_permissions() -> Result<(), Error> {
 	let val = Value::parse(
 		"{
 			analyzers: {},
+			checks: {},
+			dictionaries: {},
 			functions: {},
 			models: {},
 			params: {},
+			roles: {},
 			scopes: {},
 			tables: {
 					default: 'DEFINE TABLE default TYPE ANY SCHEMALESS PERMISSIONS NONE',
@@ -2832,10 +3200,13 @@ async fn define_table_relation_redefinition_info() -> Result<(), Error> {
 	let val = Value::parse(
 		"{
 			analyzers: {},
+			checks: {},
+			dictionaries: {},
 			tokens: {},
 			functions: {},
 			models: {},
 			params: {},
+			roles: {},
 			scopes: {},
 			tables: { likes: 'DEFINE TABLE likes TYPE RELATION IN person OUT person SCHEMALESS PERMISSIONS NONE' },
 			users: {},
@@ -2862,10 +3233,13 @@ async fn define_table_relation_redefinition_info() -> Result<(), Error> {
 	let val = Value::parse(
 		"{
 			analyzers: {},
+			checks: {},
+			dictionaries: {},
 			tokens: {},
 			functions: {},
 			models: {},
 			params: {},
+			roles: {},
 			scopes: {},
 			tables: { likes: 'DEFINE TABLE likes TYPE RELATION IN person OUT person | thing SCHEMALESS PERMISSIONS NONE' },
 			users: {},
@@ -2892,10 +3266,13 @@ async fn define_table_relation_redefinition_info() -> Result<(), Error> {
 	let val = Value::parse(
 		"{
 			analyzers: {},
+			checks: {},
+			dictionaries: {},
 			tokens: {},
 			functions: {},
 			models: {},
 			params: {},
+			roles: {},
 			scopes: {},
 			tables: { likes: 'DEFINE TABLE likes TYPE RELATION IN person OUT person | thing | other SCHEMALESS PERMISSIONS NONE' },
 			users: {},
@@ -2953,3 +3330,349 @@ async fn define_table_type_any() -> Result<(), Error> {
 	//
 	Ok(())
 }
+
+#[tokio::test]
+async fn define_statement_role() -> Result<(), Error> {
+	let sql = "
+		DEFINE ROLE analyst ON DATABASE GRANT select ON TABLE sales, reports GRANT create, update ON TABLE reports;
+		DEFINE USER bob ON DATABASE PASSWORD 'secret' ROLES VIEWER;
+		GRANT ROLE analyst TO USER bob;
+		GRANT ROLE unknown TO USER bob;
+		GRANT ROLE analyst TO USER bob ON NAMESPACE;
+		DEFINE USER carol ON NAMESPACE PASSWORD 'secret' ROLES VIEWER, analyst;
+		INFO FOR DB;
+		INFO FOR USER bob;
+		INFO FOR USER bob STRUCTURE;
+		REMOVE ROLE analyst;
+		REMOVE ROLE analyst;
+		REMOVE ROLE IF EXISTS analyst;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 12);
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp, Err(Error::RoNotFound { .. })));
+	// Roles can only be granted to the users of their database
+	for _ in 0..2 {
+		let tmp = res.remove(0).result;
+		assert!(matches!(tmp, Err(Error::RoInvalidLevel { .. })), "{tmp:?}");
+	}
+	//
+	let tmp = res.remove(0).result?;
+	let val = tmp.pick(&["roles".into()]);
+	let val_str = format!("{}", val);
+	assert_eq!(
+		val_str,
+		"{ analyst: 'DEFINE ROLE analyst ON DATABASE GRANT SELECT ON TABLE sales, reports GRANT CREATE, UPDATE ON TABLE reports' }"
+	);
+	//
+	let tmp = res.remove(0).result?;
+	assert!(tmp.to_raw_string().ends_with("ROLES VIEWER, analyst"));
+	//
+	let tmp = res.remove(0).result?;
+	let val = tmp.pick(&["privileges".into()]);
+	let val_str = format!("{}", val);
+	assert_eq!(val_str, "{ reports: ['select', 'create', 'update'], sales: ['select'] }");
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp, Err(Error::RoNotFound { .. })));
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn define_statement_role_reserved() -> Result<(), Error> {
+	let sql = "
+		DEFINE ROLE owner ON DATABASE GRANT select ON TABLE sales;
+		DEFINE ROLE Viewer ON DATABASE GRANT select ON TABLE sales;
+		INFO FOR DB;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 3);
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp, Err(Error::RoReserved { .. })));
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp, Err(Error::RoReserved { .. })));
+	//
+	let tmp = res.remove(0).result?;
+	let val = tmp.pick(&["roles".into()]);
+	assert_eq!(val.to_string(), "{  }");
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn define_statement_scope_statements() -> Result<(), Error> {
+	let sql = "
+		DEFINE SCOPE account SESSION 1h ALLOW select, create DENY info;
+		DEFINE SCOPE other ALLOW explain;
+		INFO FOR DB;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await;
+	assert!(matches!(res, Err(Error::InvalidQuery(_))));
+	//
+	let sql = "
+		DEFINE SCOPE account SESSION 1h ALLOW select, create DENY info;
+		INFO FOR DB;
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 2);
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	//
+	let tmp = res.remove(0).result?;
+	let val = tmp.pick(&["scopes".into()]);
+	let val_str = format!("{}", val);
+	assert_eq!(
+		val_str,
+		"{ account: 'DEFINE SCOPE account SESSION 1h ALLOW SELECT, CREATE DENY INFO' }"
+	);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn define_statement_param_type() -> Result<(), Error> {
+	let sql = "
+		DEFINE PARAM $max_results TYPE int VALUE 100;
+		DEFINE PARAM $min_results TYPE int VALUE 'one';
+		INFO FOR DB;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 3);
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	//
+	let tmp = res.remove(0).result.unwrap_err();
+	assert_eq!(tmp.to_string(), "Found 'one' for param $min_results, but expected a int");
+	//
+	let tmp = res.remove(0).result?;
+	let val = tmp.pick(&["params".into()]);
+	let val_str = format!("{}", val);
+	assert_eq!(
+		val_str,
+		"{ max_results: 'DEFINE PARAM $max_results TYPE int VALUE 100 PERMISSIONS FULL' }"
+	);
+	// Query variables are coerced to the type of the param
+	let vars = BTreeMap::from([("max_results".to_string(), Value::from(50.0))]);
+	let res = &mut dbs.execute("RETURN $max_results", &ses, Some(vars)).await?;
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::from(50));
+	// Query variables of the wrong type are refused
+	let vars = BTreeMap::from([("max_results".to_string(), Value::from("many"))]);
+	let res = dbs.execute("RETURN $max_results", &ses, Some(vars)).await;
+	assert!(matches!(res, Err(Error::ParamCheck { .. })));
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn define_statement_role_permissions() -> Result<(), Error> {
+	let sql = "
+		DEFINE ROLE analyst ON DATABASE GRANT select ON TABLE sales GRANT create ON TABLE reports;
+		CREATE sales:one, reports:one, secrets:one;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 2);
+	for r in res.drain(..) {
+		r.result?;
+	}
+	//
+	let sql = "
+		SELECT * FROM sales;
+		SELECT * FROM secrets;
+		SELECT * FROM reports;
+		CREATE reports:two;
+		UPDATE sales:one SET total = 10;
+		DELETE sales:one;
+	";
+	let ses = Session::for_level(("test", "test").into(), Role::Custom("analyst".into()))
+		.with_ns("test")
+		.with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 6);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: sales:one }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: reports:two }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(tmp, val);
+	//
+	// The roles of users on other levels are not looked up in the database
+	let sql = "SELECT * FROM sales";
+	let ses = Session::for_level(("test",).into(), Role::Custom("analyst".into()))
+		.with_ns("test")
+		.with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(tmp, val);
+	//
+	let sql = "SELECT * FROM sales";
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: sales:one }]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn define_statement_table_audit_fields() -> Result<(), Error> {
+	let sql = "
+		DEFINE TABLE person SCHEMAFULL WITH AUDIT FIELDS PERMISSIONS FULL;
+		DEFINE FIELD name ON person TYPE string;
+		CREATE person:test SET name = 'Tobie', created_at = d'2000-01-01T00:00:00Z', created_by = 'someone';
+		INFO FOR DB;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 4);
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	//
+	let tmp = res.remove(0).result?;
+	let created_at = tmp.pick(&[0.into(), "created_at".into()]);
+	assert!(created_at.is_datetime());
+	assert_ne!(created_at.to_string(), "d'2000-01-01T00:00:00Z'");
+	assert_eq!(tmp.pick(&[0.into(), "created_by".into()]), Value::from("system_auth"));
+	assert_eq!(tmp.pick(&[0.into(), "updated_at".into()]), created_at);
+	assert_eq!(tmp.pick(&[0.into(), "updated_by".into()]), Value::from("system_auth"));
+	//
+	let tmp = res.remove(0).result?;
+	let val = tmp.pick(&["tables".into(), "person".into()]);
+	assert_eq!(
+		val.to_raw_string(),
+		"DEFINE TABLE person TYPE ANY SCHEMAFULL WITH AUDIT FIELDS PERMISSIONS FULL"
+	);
+	// Update the record as a scope user
+	let ses = Session::for_scope("test", "test", "user", Thing::from(("user", "one")).into());
+	let sql =
+		"UPDATE person:test SET name = 'Jaime', created_by = 'someone', updated_by = 'someone'";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp.pick(&[0.into(), "name".into()]), Value::from("Jaime"));
+	assert_eq!(tmp.pick(&[0.into(), "created_at".into()]), created_at);
+	assert_eq!(tmp.pick(&[0.into(), "created_by".into()]), Value::from("system_auth"));
+	assert_eq!(
+		tmp.pick(&[0.into(), "updated_by".into()]),
+		Value::from(Thing::from(("user", "one")))
+	);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn define_statement_table_unknown_fields() -> Result<(), Error> {
+	let sql = "
+		DEFINE TABLE person SCHEMAFULL UNKNOWN FIELDS REJECT;
+		DEFINE FIELD name ON person TYPE string;
+		CREATE person:one SET name = 'Tobie', age = 30;
+		CREATE person:two SET name = 'Jaime';
+		DEFINE TABLE animal SCHEMAFULL UNKNOWN FIELDS QUARANTINE INTO extra;
+		DEFINE FIELD name ON animal TYPE string;
+		CREATE animal:one SET name = 'Rex', age = 3, tags = { colour: 'brown' };
+		UPDATE animal:one SET age = 4;
+		INFO FOR DB;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 9);
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "Found undefined fields `age` on record `person:one`, but the table rejects unknown fields"
+	));
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: person:two, name: 'Jaime' }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[{ id: animal:one, name: 'Rex', extra: { age: 3, tags: { colour: 'brown' } } }]",
+	);
+	assert_eq!(tmp, val);
+	// The quarantined fields are kept, and new unknown fields are added to them
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[{ id: animal:one, name: 'Rex', extra: { age: 4, tags: { colour: 'brown' } } }]",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = tmp.pick(&["tables".into(), "animal".into()]);
+	assert_eq!(
+		val.to_raw_string(),
+		"DEFINE TABLE animal TYPE ANY SCHEMAFULL UNKNOWN FIELDS QUARANTINE INTO extra PERMISSIONS NONE"
+	);
+	//
+	Ok(())
+}
@@ -95,6 +95,58 @@ async fn field_definition_value_assert_failure() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn field_definition_assert_functions() -> Result<(), Error> {
+	let sql = "
+		DEFINE TABLE person SCHEMAFULL;
+		DEFINE FIELD age ON person TYPE number ASSERT $value > 0;
+		DEFINE FIELD code ON person TYPE string ASSERT assert::matches($value, /^[A-Z]{2}[0-9]{2}$/);
+		DEFINE FIELD name ON person TYPE string ASSERT assert::len_between($value, 2, 20);
+		DEFINE FIELD role ON person TYPE string ASSERT assert::one_of($value, ['admin', 'user']);
+		CREATE person:one SET age = 1, code = 'AB12', name = 'Tobie', role = 'admin';
+		CREATE person:two SET age = 1, code = 'ab', name = 'T', role = 'admin';
+		CREATE person:three SET age = 0, code = 'AB12', name = 'Tobie', role = 'owner';
+		CREATE person:four SET age = 1, code = 'AB12', name = 'Tobie', role = 'owner';
+		RETURN assert::one_of('x', ['a']);
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 10);
+	//
+	for _ in 0..6 {
+		let tmp = res.remove(0).result;
+		assert!(tmp.is_ok(), "{:?}", tmp);
+	}
+	//
+	let tmp = res.remove(0).result.unwrap_err();
+	assert_eq!(
+		tmp.to_string(),
+		"Found invalid values for fields `code`, `name` on record `person:two`: \
+		Found 'ab' for field `code`, with record `person:two`, but expected a value matching /^[A-Z]{2}[0-9]{2}$/; \
+		Found 'T' for field `name`, with record `person:two`, but expected a length between 2 and 20"
+	);
+	//
+	let tmp = res.remove(0).result.unwrap_err();
+	assert_eq!(
+		tmp.to_string(),
+		"Found invalid values for fields `age`, `role` on record `person:three`: \
+		Found 0 for field `age`, with record `person:three`, but field must conform to: $value > 0; \
+		Found 'owner' for field `role`, with record `person:three`, but expected one of ['admin', 'user']"
+	);
+	//
+	let tmp = res.remove(0).result.unwrap_err();
+	assert_eq!(
+		tmp.to_string(),
+		"Found 'owner' for field `role`, with record `person:four`, but expected one of ['admin', 'user']"
+	);
+	//
+	let tmp = res.remove(0).result.unwrap_err();
+	assert_eq!(tmp.to_string(), "Found 'x', but expected one of ['a']");
+	//
+	Ok(())
+}
+
 #[tokio::test]
 async fn field_definition_value_assert_success() -> Result<(), Error> {
 	let sql = "
@@ -990,3 +1042,232 @@ async fn field_definition_flexible_array_any() -> Result<(), Error> {
 	//
 	Ok(())
 }
+
+#[tokio::test]
+async fn field_definition_expire() -> Result<(), Error> {
+	let sql = "
+		DEFINE TABLE session SCHEMALESS;
+		CREATE session:existing SET expire_at = d'2000-01-01T00:00:00Z';
+		DEFINE FIELD expire_at ON session TYPE datetime EXPIRE;
+		CREATE session:past SET expire_at = d'2000-01-01T00:00:00Z';
+		CREATE session:future SET expire_at = d'2100-01-01T00:00:00Z';
+		SELECT * FROM session;
+		SELECT * FROM session:past;
+		UPDATE session SET seen = true RETURN id;
+		CREATE login:one SET session = session:past;
+		SELECT VALUE session.expire_at FROM login:one;
+		DEFINE FIELD name ON session TYPE string EXPIRE;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 11);
+	//
+	for _ in 0..5 {
+		let tmp = res.remove(0).result;
+		assert!(tmp.is_ok());
+	}
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				expire_at: d'2100-01-01T00:00:00Z',
+				id: session:future
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: session:future }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[NONE]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result;
+	assert!(
+		matches!(
+			&tmp,
+			Err(e) if e.to_string() == "The field `name` must be of type `datetime` or `option<datetime>` to use the EXPIRE clause",
+		),
+		"{}",
+		tmp.unwrap_err().to_string()
+	);
+	// Expired records are deleted in the background
+	let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap();
+	dbs.tick_at(now.as_secs()).await?;
+	let sql = "
+		DELETE session RETURN BEFORE;
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 1);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				expire_at: d'2100-01-01T00:00:00Z',
+				id: session:future,
+				seen: true
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn field_definition_computed() -> Result<(), Error> {
+	let sql = "
+		DEFINE FIELD total ON invoice VALUE math::sum(items.price) COMPUTED;
+		CREATE product:one SET price = 10;
+		CREATE product:two SET price = 5;
+		CREATE invoice:one SET items = [product:one, product:two];
+		UPDATE product:one SET price = 20;
+		SELECT total FROM invoice:one;
+		DEFINE FIELD other ON invoice COMPUTED;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 7);
+	//
+	for _ in 0..3 {
+		let tmp = res.remove(0).result;
+		assert!(tmp.is_ok());
+	}
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: invoice:one, items: [product:one, product:two], total: 15 }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	// The stale field is recomputed when the record is read
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ total: 25 }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result;
+	assert!(
+		matches!(
+			&tmp,
+			Err(e) if e.to_string() == "The field `other` must have a VALUE clause to use the COMPUTED clause",
+		),
+		"{}",
+		tmp.unwrap_err().to_string()
+	);
+	// Stale fields are written back to the records in the background
+	let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap();
+	dbs.tick_at(now.as_secs()).await?;
+	let sql = "
+		REMOVE FIELD total ON invoice;
+		SELECT total FROM invoice:one;
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 2);
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ total: 25 }]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn field_definition_crdt() -> Result<(), Error> {
+	let sql = "
+		DEFINE TABLE post SCHEMAFULL;
+		DEFINE FIELD likes ON post TYPE int CRDT COUNTER;
+		DEFINE FIELD title ON post TYPE string CRDT REGISTER;
+		DEFINE FIELD tags ON post TYPE array<string> CRDT SET;
+		CREATE post:one SET likes = 2, title = 'One', tags = ['a', 'b'];
+		UPDATE post:one SET likes += 3, title = 'First', tags -= 'a', tags += 'c';
+		UPDATE post:one SET likes = 1;
+		UPDATE post:one CONTENT { likes: 4, title: 'First', tags: ['b', 'c'] };
+		SELECT * FROM post:one;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 9);
+	//
+	for _ in 0..6 {
+		let tmp = res.remove(0).result;
+		assert!(tmp.is_ok());
+	}
+	//
+	let tmp = res.remove(0).result;
+	assert!(
+		matches!(
+			&tmp,
+			Err(e) if e.to_string() == "Found 1 for field `likes`, with record `post:one`, but expected a number of at least 5",
+		),
+		"{}",
+		tmp.unwrap_err().to_string()
+	);
+	// Counters can only be incremented
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_err());
+	// The state of the fields is not output
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				id: post:one,
+				likes: 5,
+				tags: ['b', 'c'],
+				title: 'First'
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	// Replicated states are merged with the stored state
+	let sql = "
+		UPDATE post:one CONTENT {
+			likes: 7,
+			title: 'Remote',
+			tags: ['d'],
+			__crdt: {
+				likes: { remote: 7 },
+				title: { value: 'Remote', at: 0, node: 'remote' },
+				tags: { adds: { remote: 'd' }, removes: {} }
+			}
+		};
+		SELECT * FROM post:one;
+	";
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 2);
+	//
+	let tmp = res.remove(0).result;
+	assert!(tmp.is_ok());
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				id: post:one,
+				likes: 12,
+				tags: ['b', 'c', 'd'],
+				title: 'First'
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
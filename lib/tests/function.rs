@@ -2,6 +2,8 @@ mod parse;
 use parse::Parse;
 mod helpers;
 use helpers::new_ds;
+use std::collections::BTreeMap;
+use std::time::{SystemTime, UNIX_EPOCH};
 use surrealdb::dbs::Session;
 use surrealdb::err::Error;
 use surrealdb::sql::{self, Number, Value};
@@ -1270,10 +1272,111 @@ async fn function_array_union() -> Result<(), Error> {
 	Ok(())
 }
 
+// --------------------------------------------------
+// blob
+// --------------------------------------------------
+
+#[tokio::test]
+async fn function_blob_put_get() -> Result<(), Error> {
+	let sql = r#"
+		LET $id = blob::put(<bytes>"hello");
+		RETURN $id;
+		RETURN blob::put(<bytes>"hello") == $id;
+		RETURN blob::get($id);
+		RETURN blob::size($id);
+		RETURN blob::get("2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9825");
+	"#;
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 6);
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::None);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::from("2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::Bool(true));
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::Bytes(b"hello".to_vec().into());
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::from(5));
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp, Value::None);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn function_blob_garbage_collection() -> Result<(), Error> {
+	let sql = r#"
+		CREATE file:one SET content = blob::put(<bytes>"referenced");
+		RETURN blob::put(<bytes>"unreferenced");
+	"#;
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 2);
+	res.remove(0).result?;
+	let id = res.remove(0).result?;
+	let sql = "RETURN [blob::size((SELECT VALUE content FROM ONLY file:one)), blob::size($id)]";
+	let vars = BTreeMap::from([("id".to_string(), id)]);
+	// The blobs are kept during the grace period
+	let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+	dbs.tick_at(now).await?;
+	let res = &mut dbs.execute(sql, &ses, Some(vars.clone())).await?;
+	assert_eq!(res.remove(0).result?, Value::parse("[10, 12]"));
+	// The unreferenced blob is collected after the grace period
+	dbs.tick_at(now + 7200).await?;
+	let res = &mut dbs.execute(sql, &ses, Some(vars)).await?;
+	assert_eq!(res.remove(0).result?, Value::parse("[10, NONE]"));
+	//
+	Ok(())
+}
+
 // --------------------------------------------------
 // bytes
 // --------------------------------------------------
 
+#[tokio::test]
+async fn function_assert() -> Result<(), Error> {
+	let sql = r#"
+		RETURN assert::matches("tobie", /^[a-z]+$/);
+		RETURN assert::len_between([1, 2, 3], 1, 2);
+		RETURN assert::one_of(2, [1, 2, 3]);
+	"#;
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 3);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::Bool(true);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result;
+	assert!(
+		matches!(
+			&tmp,
+			Err(e) if e.to_string() == "Found [1, 2, 3], but expected a length between 1 and 2"
+		),
+		"{tmp:?}"
+	);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::Bool(true);
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
 #[tokio::test]
 async fn function_bytes_len() -> Result<(), Error> {
 	let sql = r#"
@@ -4275,6 +4378,171 @@ async fn function_string_words() -> Result<(), Error> {
 	Ok(())
 }
 
+// --------------------------------------------------
+// system
+// --------------------------------------------------
+
+#[tokio::test]
+async fn function_system_background() -> Result<(), Error> {
+	let sql = r#"
+		RETURN system::throttle('person', 1000);
+		RETURN system::throttle(NONE, 5000);
+		RETURN system::priority('expiry', 'normal');
+		CREATE person:tobie SET name = 'Tobie';
+		DEFINE INDEX name ON person FIELDS name;
+		RETURN system::background();
+		RETURN system::throttle('person', -1);
+		RETURN system::priority('archive', 'high');
+	"#;
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 8);
+	//
+	for _ in 0..5 {
+		res.remove(0).result?;
+	}
+	//
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp.pick(&sql::idiom("rate")?), Value::from(5000));
+	assert_eq!(
+		tmp.pick(&sql::idiom("tables")?),
+		Value::parse("[{ ns: 'test', db: 'test', tb: 'person', rate: 1000 }]")
+	);
+	assert_eq!(
+		tmp.pick(&sql::idiom("jobs.*.priority")?),
+		Value::parse("['high', 'normal', 'low', 'normal', 'normal', 'low']")
+	);
+	assert!(tmp.pick(&sql::idiom("writes.foreground")?).is_truthy());
+	assert!(tmp.pick(&sql::idiom("writes.background")?).is_truthy());
+	assert_eq!(tmp.pick(&sql::idiom("jobs[0].admitted")?), Value::from(1));
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp, Err(Error::InvalidArguments { .. })));
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp, Err(Error::InvalidArguments { .. })));
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn function_system_metrics() -> Result<(), Error> {
+	let sql = r#"
+		DEFINE TABLE person SCHEMAFULL;
+		DEFINE FIELD name ON person TYPE string;
+		DEFINE INDEX name ON person FIELDS name;
+		SELECT name, kind, schemafull, fields, indexes, events FROM system::tables();
+		SELECT write FROM system::transactions();
+		RETURN system::connections();
+		RETURN system::live_queries();
+		RETURN system::prefetch();
+	"#;
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 8);
+	//
+	for _ in 0..3 {
+		res.remove(0).result?;
+	}
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[{ name: 'person', kind: 'any', schemafull: true, fields: 1, indexes: 1, events: 0 }]",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ write: false }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("{ batches: 0, records: 0, saved: 0 }");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn function_system_hot_keys() -> Result<(), Error> {
+	let sql = r#"
+		CREATE person:tobie SET name = 'Tobie';
+		SELECT * FROM system::hot_keys WHERE tb = 'person' ORDER BY conflicts DESC;
+		RETURN system::hot_keys();
+	"#;
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 3);
+	//
+	res.remove(0).result?;
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn function_system_activity() -> Result<(), Error> {
+	let sql = r#"
+		RETURN system::activity();
+		RETURN type::is::duration(system::activity().read_latency);
+	"#;
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 2);
+	//
+	let tmp = res.remove(0).result?;
+	for (field, val) in
+		[("queries", 1), ("open_queries", 1), ("open_connections", 0), ("conflicts", 0)]
+	{
+		assert_eq!(tmp.pick(&sql::idiom(field)?), Value::from(val), "{field}");
+	}
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("true");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn function_system_queries() -> Result<(), Error> {
+	let sql = r#"
+		SELECT ns, db, state FROM system::queries();
+		KILL QUERY u"e72bee20-f49b-11ec-b939-0242ac120002";
+	"#;
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 2);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ ns: 'test', db: 'test', state: 'running' }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp, Err(Error::KillStatement { .. })));
+	//
+	Ok(())
+}
+
 // --------------------------------------------------
 // time
 // --------------------------------------------------
@@ -4832,6 +5100,64 @@ async fn function_time_from_unix() -> Result<(), Error> {
 	Ok(())
 }
 
+// --------------------------------------------------
+// tx
+// --------------------------------------------------
+
+#[tokio::test]
+async fn function_tx_timestamp() -> Result<(), Error> {
+	let sql = r#"
+		BEGIN;
+		CREATE person:1 SET at = tx::timestamp();
+		SLEEP 10ms;
+		CREATE person:2 SET at = tx::timestamp();
+		COMMIT;
+		RETURN person:1.at == person:2.at AND type::is::datetime(person:1.at);
+	"#;
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 4);
+	//
+	let tmp = res.remove(3).result?;
+	assert_eq!(tmp, Value::Bool(true));
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn function_tx_versionstamp() -> Result<(), Error> {
+	let sql = r#"
+		DEFINE TABLE person CHANGEFEED 1h;
+		BEGIN;
+		CREATE person:1 SET vs = tx::versionstamp();
+		CREATE person:2 SET vs = tx::versionstamp();
+		COMMIT;
+		CREATE person:3 SET vs = tx::versionstamp();
+		SELECT VALUE vs FROM person;
+		SHOW CHANGES FOR TABLE person SINCE 0;
+	"#;
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 6);
+	//
+	let Value::Array(changes) = res.remove(5).result? else {
+		panic!("Expected an array of changes");
+	};
+	let Value::Array(tmp) = res.remove(4).result? else {
+		panic!("Expected an array of versionstamps");
+	};
+	// The records created in the same transaction share a versionstamp
+	assert_eq!(tmp[0], tmp[1]);
+	assert!(tmp[2] > tmp[1]);
+	// The versionstamps are those of the changes in the change feed
+	let vs: Vec<Value> = changes.iter().map(|v| v.pick(&["versionstamp".into()])).collect();
+	assert_eq!(vs[1..], [tmp[1].clone(), tmp[2].clone()]);
+	//
+	Ok(())
+}
+
 // --------------------------------------------------
 // type
 // --------------------------------------------------
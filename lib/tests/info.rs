@@ -6,6 +6,7 @@ use std::collections::HashMap;
 use regex::Regex;
 use surrealdb::dbs::Session;
 use surrealdb::iam::Role;
+use surrealdb::sql::Value;
 
 #[tokio::test]
 async fn info_for_root() {
@@ -24,7 +25,8 @@ async fn info_for_root() {
 	assert!(out.is_ok(), "Unexpected error: {:?}", out);
 
 	let output_regex =
-		Regex::new(r"\{ namespaces: \{ NS: .* \}, users: \{ user: .* \} \}").unwrap();
+		Regex::new(r"\{ capabilities: \[\], namespaces: \{ NS: .* \}, users: \{ user: .* \} \}")
+			.unwrap();
 	let out_str = out.unwrap().to_string();
 	assert!(
 		output_regex.is_match(&out_str),
@@ -85,7 +87,7 @@ async fn info_for_db() {
 	let out = res.pop().unwrap().output();
 	assert!(out.is_ok(), "Unexpected error: {:?}", out);
 
-	let output_regex = Regex::new(r"\{ analyzers: \{ analyzer: .* \}, functions: \{ greet: .* \}, params: \{ param: .* \}, scopes: \{ account: .* \}, tables: \{ TB: .* \}, tokens: \{ token: .* \}, users: \{ user: .* \} \}").unwrap();
+	let output_regex = Regex::new(r"\{ analyzers: \{ analyzer: .* \}, checks: \{  \}, dictionaries: \{  \}, functions: \{ greet: .* \}, params: \{ param: .* \}, roles: \{  \}, scopes: \{ account: .* \}, tables: \{ TB: .* \}, tokens: \{ token: .* \}, users: \{ user: .* \} \}").unwrap();
 	let out_str = out.unwrap().to_string();
 	assert!(
 		output_regex.is_match(&out_str),
@@ -111,7 +113,7 @@ async fn info_for_scope() {
 	let out = res.pop().unwrap().output();
 	assert!(out.is_ok(), "Unexpected error: {:?}", out);
 
-	let output_regex = Regex::new(r"\{ tokens: \{ token: .* \} \}").unwrap();
+	let output_regex = Regex::new(r"\{ sessions: \[\], tokens: \{ token: .* \} \}").unwrap();
 	let out_str = out.unwrap().to_string();
 	assert!(
 		output_regex.is_match(&out_str),
@@ -152,6 +154,134 @@ async fn info_for_table() {
 	);
 }
 
+#[tokio::test]
+async fn info_for_advisor() {
+	let sql = r#"
+        CREATE person:1 SET country = 'UK', age = 30;
+        CREATE person:2 SET country = 'UK', age = 10;
+        CREATE person:3 SET country = 'FR', age = 40;
+        SELECT * FROM person WHERE country = 'UK' AND age > 18;
+        SELECT * FROM person WHERE age > 18 AND country = 'UK';
+        SELECT * FROM person WHERE name = 'Tobie';
+        INFO FOR ADVISOR;
+    "#;
+	let dbs = new_ds().await.unwrap();
+	let ses = Session::owner().with_ns("ns").with_db("db");
+
+	let mut res = dbs.execute(sql, &ses, None).await.unwrap();
+	assert_eq!(res.len(), 7);
+
+	let out = res.pop().unwrap().output();
+	assert!(out.is_ok(), "Unexpected error: {:?}", out);
+
+	// The suggestions are ranked by the number of scanned records which did not match
+	let output_regex = Regex::new(
+		r"^\[\{ benefit: 4, fields: \['country', 'age'\], last: .*, matched: 2, queries: 2, scanned: 6, statement: 'DEFINE INDEX person_country_age ON person FIELDS country, age', table: 'person' \}, \{ benefit: 3, fields: \['name'\], .* statement: 'DEFINE INDEX person_name ON person FIELDS name', table: 'person' \}\]$",
+	)
+	.unwrap();
+	let out_str = out.unwrap().to_string();
+	assert!(
+		output_regex.is_match(&out_str),
+		"Output '{}' doesn't match regex '{}'",
+		out_str,
+		output_regex
+	);
+
+	// Suggestions are no longer made once an index is defined
+	let sql = r#"
+        DEFINE INDEX person_country ON person FIELDS country;
+        INFO FOR ADVISOR;
+    "#;
+	let mut res = dbs.execute(sql, &ses, None).await.unwrap();
+	assert_eq!(res.len(), 2);
+
+	let out = res.pop().unwrap().output();
+	assert!(out.is_ok(), "Unexpected error: {:?}", out);
+	let out_str = out.unwrap().to_string();
+	assert!(!out_str.contains("person_country_age"), "{out_str}");
+	assert!(out_str.contains("DEFINE INDEX person_name ON person FIELDS name"), "{out_str}");
+}
+
+#[tokio::test]
+async fn info_for_history_of_table() {
+	let sql = r#"
+        DEFINE TABLE user SCHEMAFULL;
+        DEFINE FIELD name ON user TYPE string;
+        DEFINE TABLE post;
+        REMOVE FIELD name ON user;
+        REMOVE TABLE user;
+        INFO FOR HISTORY OF TABLE user;
+    "#;
+	let dbs = new_ds().await.unwrap();
+	let ses = Session::owner().with_ns("ns").with_db("db");
+
+	let mut res = dbs.execute(sql, &ses, None).await.unwrap();
+	assert_eq!(res.len(), 6);
+
+	let out = res.pop().unwrap().output();
+	assert!(out.is_ok(), "Unexpected error: {:?}", out);
+	let Value::Array(out) = out.unwrap() else {
+		panic!("Expected an array");
+	};
+	// The definitions of the table are kept after it is removed
+	let statements: Vec<String> =
+		out.iter().map(|v| v.pick(&["statement".into()]).as_raw_string()).collect();
+	assert_eq!(statements.len(), 4, "{statements:?}");
+	assert!(statements[0].starts_with("DEFINE TABLE user"), "{statements:?}");
+	assert!(statements[1].starts_with("DEFINE FIELD name ON user"), "{statements:?}");
+	assert_eq!(statements[2], "REMOVE FIELD name ON user");
+	assert_eq!(statements[3], "REMOVE TABLE user");
+	assert_eq!(out[0].pick(&["actor".into()]), Value::from("system_auth"));
+	assert_eq!(out[0].pick(&["level".into()]), Value::from("/"));
+
+	// The audit log can not be removed with the database
+	let sql = r#"
+        REMOVE DATABASE db;
+        INFO FOR HISTORY OF TABLE user;
+    "#;
+	let mut res = dbs.execute(sql, &ses, None).await.unwrap();
+	let out = res.pop().unwrap().output().unwrap();
+	assert_eq!(out.to_string().matches("statement").count(), 4, "{out}");
+}
+
+#[tokio::test]
+async fn info_for_table_with_stats() {
+	let sql = r#"
+        CREATE person:1 SET name = 'Tobie';
+        CREATE person:2 SET name = 'Jaime';
+        UPDATE person:1 SET age = 30;
+        DELETE person:2;
+        SELECT * FROM person;
+        INFO FOR TABLE person WITH STATS;
+    "#;
+	let dbs = new_ds().await.unwrap();
+	let ses = Session::owner().with_ns("ns").with_db("db");
+
+	let mut res = dbs.execute(sql, &ses, None).await.unwrap();
+	assert_eq!(res.len(), 6);
+
+	let out = res.pop().unwrap().output();
+	assert!(out.is_ok(), "Unexpected error: {:?}", out);
+
+	// The update, the delete, and the select each read a record
+	let output_regex = Regex::new(
+		r"stats: \{ average_size: [1-9]\d*, creates: 2, deletes: 1, last_write: '.*', read_bytes: [1-9]\d*, reads: 3, updates: 1, written_bytes: [1-9]\d* \}",
+	)
+	.unwrap();
+	let out_str = out.unwrap().to_string();
+	assert!(
+		output_regex.is_match(&out_str),
+		"Output '{}' doesn't match regex '{}'",
+		out_str,
+		output_regex
+	);
+
+	// The statistics are only output when they are requested
+	let mut res = dbs.execute("INFO FOR TABLE person", &ses, None).await.unwrap();
+	let out_str = res.pop().unwrap().output().unwrap().to_string();
+	assert!(!out_str.contains("stats"), "{out_str}");
+}
+
 #[tokio::test]
 async fn info_for_user() {
 	let sql = r#"
@@ -235,8 +365,10 @@ async fn permissions_checks_info_root() {
 		HashMap::from([("prepare", ""), ("test", "INFO FOR ROOT"), ("check", "INFO FOR ROOT")]);
 
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
-	let check_results =
-		[vec!["{ namespaces: {  }, users: {  } }"], vec!["{ namespaces: {  }, users: {  } }"]];
+	let check_results = [
+		vec!["{ capabilities: [], namespaces: {  }, users: {  } }"],
+		vec!["{ capabilities: [], namespaces: {  }, users: {  } }"],
+	];
 
 	let test_cases = [
 		// Root level
@@ -312,8 +444,8 @@ async fn permissions_checks_info_db() {
 
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
 	let check_results = [
-        vec!["{ analyzers: {  }, functions: {  }, models: {  }, params: {  }, scopes: {  }, tables: {  }, tokens: {  }, users: {  } }"],
-        vec!["{ analyzers: {  }, functions: {  }, models: {  }, params: {  }, scopes: {  }, tables: {  }, tokens: {  }, users: {  } }"],
+        vec!["{ analyzers: {  }, checks: {  }, dictionaries: {  }, functions: {  }, models: {  }, params: {  }, roles: {  }, scopes: {  }, tables: {  }, tokens: {  }, users: {  } }"],
+        vec!["{ analyzers: {  }, checks: {  }, dictionaries: {  }, functions: {  }, models: {  }, params: {  }, roles: {  }, scopes: {  }, tables: {  }, tokens: {  }, users: {  } }"],
     ];
 
 	let test_cases = [
@@ -353,7 +485,8 @@ async fn permissions_checks_info_scope() {
 	]);
 
 	// Define the expected results for the check statement when the test statement succeeded and when it failed
-	let check_results = [vec!["{ tokens: {  } }"], vec!["{ tokens: {  } }"]];
+	let check_results =
+		[vec!["{ sessions: [], tokens: {  } }"], vec!["{ sessions: [], tokens: {  } }"]];
 
 	let test_cases = [
 		// Root level
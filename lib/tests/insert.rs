@@ -193,6 +193,52 @@ async fn insert_statement_duplicate_key_update() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn insert_statement_select() -> Result<(), Error> {
+	let sql = "
+		CREATE orders:1 SET created = 1, total = 10;
+		CREATE orders:2 SET created = 2, total = 20;
+		CREATE orders:3 SET created = 3, total = 30;
+		INSERT INTO archive SELECT * FROM orders WHERE created < 3 RETURN COUNT;
+		INSERT INTO archive SELECT id, total FROM orders WHERE created = 3;
+		SELECT * FROM archive;
+		SELECT count() FROM orders GROUP ALL;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 7);
+	//
+	for _ in 0..3 {
+		let tmp = res.remove(0).result;
+		assert!(tmp.is_ok());
+	}
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("{ count: 2 }");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[ { id: archive:3, total: 30 } ]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{ id: archive:1, created: 1, total: 10 },
+			{ id: archive:2, created: 2, total: 20 },
+			{ id: archive:3, total: 30 },
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[ { count: 3 } ]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
 //
 // Permissions
 //
@@ -2,7 +2,9 @@ mod helpers;
 mod parse;
 
 use helpers::new_ds;
-use surrealdb::dbs::Session;
+use parse::Parse;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use surrealdb::dbs::{Action, Session};
 use surrealdb::err::Error;
 use surrealdb::fflags::FFLAGS;
 use surrealdb::sql::Value;
@@ -97,3 +99,275 @@ async fn live_query_sends_registered_lq_details() -> Result<(), Error> {
 
 	Ok(())
 }
+
+#[tokio::test]
+async fn live_query_window_sends_aggregates() -> Result<(), Error> {
+	if FFLAGS.change_feed_live_queries.enabled() {
+		return Ok(());
+	}
+	let sql = "
+		LIVE SELECT count() AS total, math::sum(amount) AS amount FROM clicks WHERE amount > 0 WINDOW TUMBLING 1m;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test").with_rt(true);
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 1);
+	let live_id = match res.remove(0).result? {
+		Value::Uuid(live_id) => live_id,
+		_ => panic!("Expected a UUID"),
+	};
+	// Create some data in a single transaction, so that it falls in the same window
+	let sql = "
+		BEGIN;
+		CREATE clicks SET amount = 1;
+		CREATE clicks SET amount = 2;
+		CREATE clicks SET amount = 0;
+		COMMIT;
+		BEGIN;
+		CREATE clicks SET amount = 100;
+		CANCEL;
+	";
+	dbs.execute(sql, &ses, None).await?;
+	let notifications = dbs.notifications().unwrap();
+	// No notifications are sent for the individual records
+	assert!(notifications.try_recv().is_err());
+	// The window is still open
+	let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as u64;
+	dbs.process_lq_windows_at(now).await?;
+	assert!(notifications.try_recv().is_err());
+	// The window closes with the aggregate of the committed records
+	dbs.process_lq_windows_at(now + 60_000).await?;
+	let notification = notifications.try_recv().unwrap();
+	assert_eq!(notification.id, live_id);
+	assert_eq!(notification.action, Action::Window);
+	assert_eq!(notification.result, Value::parse("{ total: 2, amount: 3 }"));
+	// Empty windows do not send notifications
+	dbs.process_lq_windows_at(now + 120_000).await?;
+	assert!(notifications.try_recv().is_err());
+	// Killed live queries do not send notifications
+	dbs.execute("CREATE clicks SET amount = 5", &ses, None).await?;
+	dbs.execute(&format!("KILL u{live_id}"), &ses, None).await?;
+	let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as u64;
+	dbs.process_lq_windows_at(now + 60_000).await?;
+	assert!(notifications.try_recv().is_err());
+	Ok(())
+}
+
+#[tokio::test]
+async fn live_query_window_is_validated() -> Result<(), Error> {
+	let sql = "
+		LIVE SELECT DIFF FROM clicks WINDOW TUMBLING 1m;
+		LIVE SELECT count() FROM clicks WINDOW SLIDING 1m EVERY 5m;
+		LIVE SELECT count() FROM clicks WINDOW TUMBLING 0s;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test").with_rt(true);
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 3);
+	for (res, message) in res.drain(..).zip([
+		"a window can not be used with a DIFF live query",
+		"the window can not advance by more than its size",
+		"the window duration must be greater than zero",
+	]) {
+		let err = res.result.unwrap_err().to_string();
+		assert!(err.ends_with(message), "{err}");
+	}
+	Ok(())
+}
+
+#[tokio::test]
+async fn live_query_when_filters_actions() -> Result<(), Error> {
+	if FFLAGS.change_feed_live_queries.enabled() {
+		return Ok(());
+	}
+	let sql = "
+		LIVE SELECT name FROM person WHEN CREATE, DELETE;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test").with_rt(true);
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 1);
+	let live_id = match res.remove(0).result? {
+		Value::Uuid(live_id) => live_id,
+		_ => panic!("Expected a UUID"),
+	};
+	let sql = "
+		CREATE person:tobie SET name = 'Tobie', age = 33;
+		UPDATE person:tobie SET age = 34;
+		DELETE person:tobie;
+	";
+	dbs.execute(sql, &ses, None).await?;
+	let notifications = dbs.notifications().unwrap();
+	// The notifications are forwarded in the background
+	let timeout = Duration::from_secs(5);
+	// The CREATE notification only contains the projected fields
+	let notification = tokio::time::timeout(timeout, notifications.recv()).await.unwrap().unwrap();
+	assert_eq!(notification.id, live_id);
+	assert_eq!(notification.action, Action::Create);
+	assert_eq!(notification.result, Value::parse("{ name: 'Tobie' }"));
+	// The UPDATE is skipped, and the DELETE notification is projected
+	let notification = tokio::time::timeout(timeout, notifications.recv()).await.unwrap().unwrap();
+	assert_eq!(notification.id, live_id);
+	assert_eq!(notification.action, Action::Delete);
+	assert_eq!(notification.result, Value::parse("{ name: 'Tobie' }"));
+	assert!(notifications.try_recv().is_err());
+	Ok(())
+}
+
+#[tokio::test]
+async fn live_query_compact_coalesces_updates() -> Result<(), Error> {
+	if FFLAGS.change_feed_live_queries.enabled() {
+		return Ok(());
+	}
+	let sql = "
+		LIVE SELECT * FROM person COMPACT 50ms;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test").with_rt(true);
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 1);
+	let live_id = match res.remove(0).result? {
+		Value::Uuid(live_id) => live_id,
+		_ => panic!("Expected a UUID"),
+	};
+	let sql = "
+		CREATE person:tobie SET age = 33;
+		UPDATE person:tobie SET age = 34;
+		UPDATE person:tobie SET age = 35;
+		UPDATE person:tobie SET age = 36;
+	";
+	dbs.execute(sql, &ses, None).await?;
+	let notifications = dbs.notifications().unwrap();
+	// The CREATE notification is sent straight away
+	let notification = notifications.try_recv().unwrap();
+	assert_eq!(notification.id, live_id);
+	assert_eq!(notification.action, Action::Create);
+	assert_eq!(notification.result, Value::parse("{ id: person:tobie, age: 33 }"));
+	assert!(notifications.try_recv().is_err());
+	// The UPDATE notifications are coalesced into the latest state
+	let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as u64;
+	dbs.process_lq_compactions_at(now + 1_000).await?;
+	let notification = notifications.try_recv().unwrap();
+	assert_eq!(notification.id, live_id);
+	assert_eq!(notification.action, Action::Update);
+	assert_eq!(notification.result, Value::parse("{ id: person:tobie, age: 36 }"));
+	assert!(notifications.try_recv().is_err());
+	// A pending UPDATE notification is sent before a DELETE notification
+	let sql = "
+		UPDATE person:tobie SET age = 37;
+		DELETE person:tobie;
+	";
+	dbs.execute(sql, &ses, None).await?;
+	let notification = notifications.try_recv().unwrap();
+	assert_eq!(notification.action, Action::Update);
+	assert_eq!(notification.result, Value::parse("{ id: person:tobie, age: 37 }"));
+	let notification = notifications.try_recv().unwrap();
+	assert_eq!(notification.action, Action::Delete);
+	assert!(notifications.try_recv().is_err());
+	// Killed live queries do not send notifications
+	dbs.execute("CREATE person:jaime; UPDATE person:jaime SET age = 1", &ses, None).await?;
+	assert_eq!(notifications.try_recv().unwrap().action, Action::Create);
+	dbs.execute(&format!("KILL u{live_id}"), &ses, None).await?;
+	let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as u64;
+	dbs.process_lq_compactions_at(now + 1_000).await?;
+	assert!(notifications.try_recv().is_err());
+	Ok(())
+}
+
+#[tokio::test]
+async fn live_query_count_sends_changes() -> Result<(), Error> {
+	if FFLAGS.change_feed_live_queries.enabled() {
+		return Ok(());
+	}
+	let sql = "
+		CREATE orders:1 SET status = 'open';
+		CREATE orders:2 SET status = 'closed';
+		LIVE COUNT FROM orders WHERE status = 'open';
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test").with_rt(true);
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 3);
+	let live_id = match res.remove(2).result? {
+		Value::Uuid(live_id) => live_id,
+		_ => panic!("Expected a UUID"),
+	};
+	let notifications = dbs.notifications().unwrap();
+	// The initial number of matching records is sent
+	let notification = notifications.try_recv().unwrap();
+	assert_eq!(notification.id, live_id);
+	assert_eq!(notification.action, Action::Count);
+	assert_eq!(notification.result, Value::from(1));
+	// Only changes to the number of matching records are sent
+	let sql = "
+		CREATE orders:3 SET status = 'open';
+		UPDATE orders:2 SET status = 'open';
+		UPDATE orders:1 SET total = 10;
+		UPDATE orders:3 SET status = 'closed';
+		DELETE orders:1;
+		DELETE orders:3;
+	";
+	dbs.execute(sql, &ses, None).await?;
+	for count in [2, 3, 2, 1] {
+		let notification = notifications.try_recv().unwrap();
+		assert_eq!(notification.id, live_id);
+		assert_eq!(notification.action, Action::Count);
+		assert_eq!(notification.result, Value::from(count));
+	}
+	assert!(notifications.try_recv().is_err());
+	// Killed live queries do not send notifications
+	dbs.execute(&format!("KILL u{live_id}"), &ses, None).await?;
+	dbs.execute("CREATE orders:4 SET status = 'open'", &ses, None).await?;
+	assert!(notifications.try_recv().is_err());
+	Ok(())
+}
+
+#[tokio::test]
+async fn live_query_killed_when_permissions_revoked() -> Result<(), Error> {
+	if FFLAGS.change_feed_live_queries.enabled() {
+		return Ok(());
+	}
+	let sql = "
+		DEFINE SCOPE account SESSION 1h;
+		DEFINE TABLE post SCHEMALESS PERMISSIONS FOR select FULL;
+		CREATE user:one;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 3);
+	for r in res.drain(..) {
+		r.result?;
+	}
+	// Start a live query as a scope user
+	let usr = Session::for_scope("test", "test", "account", Value::parse("user:one")).with_rt(true);
+	let res = &mut dbs.execute("LIVE SELECT * FROM post", &usr, None).await?;
+	let live_id = match res.remove(0).result? {
+		Value::Uuid(v) => v,
+		v => panic!("Expected a UUID, found {v}"),
+	};
+	let notifications = dbs.notifications().unwrap();
+	// The live query is kept while the user can select from the table
+	let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+	dbs.tick_at(now).await?;
+	assert!(notifications.try_recv().is_err());
+	// The live query is killed once the permissions are revoked
+	let sql = "DEFINE TABLE post SCHEMALESS PERMISSIONS NONE";
+	dbs.execute(sql, &ses, None).await?.remove(0).result?;
+	dbs.tick_at(now + 1).await?;
+	let notification = notifications.try_recv().expect("expected a notification");
+	assert_eq!(notification.id, live_id);
+	assert_eq!(notification.action, Action::Killed);
+	assert_eq!(
+		notification.result,
+		Value::from("The table permissions no longer allow the selection")
+	);
+	// No further notifications are sent
+	dbs.execute("CREATE post:one", &ses, None).await?.remove(0).result?;
+	dbs.tick_at(now + 2).await?;
+	assert!(notifications.try_recv().is_err());
+	let res = &mut dbs.execute("INFO FOR TABLE post", &ses, None).await?;
+	let info = res.remove(0).result?;
+	assert_eq!(info.pick(&["lives".into()]), Value::parse("{}"));
+	Ok(())
+}
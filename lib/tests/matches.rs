@@ -653,3 +653,86 @@ async fn select_where_matches_without_complex_query() -> Result<(), Error> {
 	assert_eq!(format!("{:#}", tmp), format!("{:#}", val_docs));
 	Ok(())
 }
+
+#[tokio::test]
+async fn select_where_matches_using_index_with_dictionaries() -> Result<(), Error> {
+	let sql = r"
+		DEFINE DICTIONARY english STOPWORDS ['the', 'a', 'is'];
+		DEFINE DICTIONARY vehicles SYNONYMS ['car', 'automobile', 'auto'], ['bike', 'bicycle'];
+		DEFINE ANALYZER simple TOKENIZERS blank,class FILTERS lowercase,stopwords(english),synonyms(vehicles);
+		CREATE blog:1 SET title = 'The automobile is red';
+		CREATE blog:2 SET title = 'A bicycle';
+		DEFINE INDEX blog_title ON blog FIELDS title SEARCH ANALYZER simple BM25;
+		SELECT id FROM blog WHERE title @@ 'Car';
+		SELECT id FROM blog WHERE title @@ 'the bike';
+		RETURN search::analyze('simple', 'The Auto is a car');
+		DEFINE ANALYZER invalid TOKENIZERS blank FILTERS synonyms(english);
+		RETURN search::analyze('invalid', 'car');
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 11);
+	//
+	for _ in 0..6 {
+		let _ = res.remove(0).result?;
+	}
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: blog:1 }]");
+	assert_eq!(format!("{:#}", tmp), format!("{:#}", val));
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: blog:2 }]");
+	assert_eq!(format!("{:#}", tmp), format!("{:#}", val));
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("['car', 'car']");
+	assert_eq!(tmp, val);
+	//
+	let _ = res.remove(0).result?;
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp.err(),
+		Some(e) if e.to_string() == "The dictionary 'english' is not a synonyms dictionary"
+	));
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_where_matches_search_suggest() -> Result<(), Error> {
+	let sql = r"
+		DEFINE ANALYZER simple TOKENIZERS blank,class FILTERS lowercase;
+		DEFINE INDEX name_idx ON user FIELDS name SEARCH ANALYZER simple BM25;
+		CREATE user:1 SET name = 'John Johnson';
+		CREATE user:2 SET name = 'John Smith';
+		CREATE user:3 SET name = 'Johnny Jones';
+		CREATE user:4 SET name = 'Jane Doe';
+		RETURN search::suggest('user.name_idx', 'Jonh', 5);
+		RETURN search::suggest('user.name_idx', 'Mary Jo', 2);
+		RETURN search::suggest('user.name_idx', 'xyz');
+		RETURN search::suggest('user', 'john');
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 10);
+	//
+	for _ in 0..6 {
+		let _ = res.remove(0).result?;
+	}
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("['john', 'jones', 'johnny', 'johnson']");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("['john', 'jones']");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp, Err(Error::InvalidArguments { .. })));
+	Ok(())
+}
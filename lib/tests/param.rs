@@ -27,10 +27,13 @@ async fn define_global_param() -> Result<(), Error> {
 	let val = Value::parse(
 		"{
 			analyzers: {},
+			checks: {},
+			dictionaries: {},
 			tokens: {},
 			functions: {},
 			models: {},
 			params: { test: 'DEFINE PARAM $test VALUE 12345 PERMISSIONS FULL' },
+			roles: {},
 			scopes: {},
 			tables: {},
 			users: {},
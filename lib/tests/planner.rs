@@ -1522,6 +1522,60 @@ async fn select_with_in_operator_uniq_index() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn select_with_not_equal_operator_complement() -> Result<(), Error> {
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+
+	let sql = "
+		DEFINE FIELD status ON user TYPE string ASSERT $value IN ['active', 'banned', 'pending'];
+		DEFINE INDEX user_status_idx ON user FIELDS status;
+		CREATE user:1 SET status = 'active';
+		CREATE user:2 SET status = 'banned';
+		CREATE user:3 SET status = 'pending';
+		SELECT * FROM user WHERE status != 'banned' EXPLAIN;
+		SELECT * FROM user WHERE !(status = 'banned') EXPLAIN;
+		SELECT id FROM user WHERE status != 'banned';
+		SELECT id FROM user WHERE status NOT IN ['banned', 'pending'];
+		";
+	let mut res = dbs.execute(sql, &ses, None).await?;
+
+	assert_eq!(res.len(), 9);
+	skip_ok(&mut res, 5)?;
+
+	for _ in 0..2 {
+		let tmp = res.remove(0).result?;
+		let val = Value::parse(
+			r#"[
+				{
+					detail: {
+						plan: {
+							excluded: ['banned'],
+							index: 'user_status_idx',
+							operator: 'complement',
+							strategy: 'enumeration',
+							value: ['active', 'pending']
+						},
+						table: 'user'
+					},
+					operation: 'Iterate Index'
+				},
+				{
+					detail: {
+						type: 'Memory'
+					},
+					operation: 'Collector'
+				}
+			]"#,
+		);
+		assert_eq!(format!("{:#}", tmp), format!("{:#}", val));
+	}
+
+	check_result(&mut res, "[{ id: user:1 }, { id: user:3 }]")?;
+	check_result(&mut res, "[{ id: user:1 }]")?;
+	Ok(())
+}
+
 #[tokio::test]
 async fn select_with_in_operator_multiple_indexes() -> Result<(), Error> {
 	let dbs = new_ds().await?;
@@ -2431,3 +2485,148 @@ async fn select_with_record_id_index() -> Result<(), Error> {
 	assert_eq!(format!("{:#}", tmp), format!("{:#}", val));
 	Ok(())
 }
+
+#[tokio::test]
+async fn select_edges_with_index() -> Result<(), Error> {
+	let sql = "
+		CREATE person:tobie, person:jaime, product:one, product:two;
+		RELATE person:tobie->bought->product:one SET amount = 50;
+		RELATE person:tobie->bought->product:two SET amount = 150;
+		RELATE person:jaime->bought->product:two SET amount = 250;
+		DEFINE INDEX idx_amount ON TABLE bought COLUMNS amount;
+		SELECT VALUE out FROM person:tobie->bought WHERE amount > 100;
+		SELECT VALUE out FROM person:tobie->bought WHERE amount > 100 EXPLAIN;
+		SELECT VALUE in FROM product:two<-bought WHERE amount > 100;
+		SELECT VALUE ->bought[WHERE amount > 100]->product FROM person:jaime;
+		SELECT VALUE out FROM person:tobie->bought WHERE out = product:one EXPLAIN;
+	";
+	let dbs = new_ds().await?;
+	let mut res = execute_test(&dbs, sql, 10).await?;
+	skip_ok(&mut res, 5)?;
+	check_result(&mut res, "[product:two]")?;
+	check_result(
+		&mut res,
+		"[
+			{
+				detail: {
+					from: person:tobie,
+					plan: {
+						from: {
+							inclusive: false,
+							value: 100
+						},
+						index: 'idx_amount',
+						to: {
+							inclusive: false,
+							value: NONE
+						}
+					},
+					table: 'bought'
+				},
+				operation: 'Iterate Edges Index'
+			},
+			{
+				detail: {
+					type: 'Memory'
+				},
+				operation: 'Collector'
+			}
+		]",
+	)?;
+	check_result(&mut res, "[person:tobie, person:jaime]")?;
+	check_result(&mut res, "[[product:two]]")?;
+	check_result(
+		&mut res,
+		"[
+			{
+				detail: {
+					from: person:tobie
+				},
+				operation: 'Iterate Edges'
+			},
+			{
+				detail: {
+					type: 'Memory'
+				},
+				operation: 'Collector'
+			}
+		]",
+	)?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_from_partitioned_table() -> Result<(), Error> {
+	let sql = "
+		DEFINE TABLE purchase PARTITION BY HASH(tenant) PARTITIONS 8;
+		CREATE purchase:1 SET tenant = 'acme';
+		CREATE purchase:2 SET tenant = 'globex';
+		CREATE purchase:3 SET tenant = 'acme';
+		CREATE purchase:4 SET tenant = 'initech';
+		UPDATE purchase:2 SET tenant = 'acme';
+		DELETE purchase:3;
+		SELECT VALUE id FROM purchase WHERE tenant = 'acme';
+		SELECT VALUE id FROM purchase WHERE tenant = 'initech' AND id > purchase:0;
+		SELECT VALUE id FROM purchase WHERE tenant = 'acme' OR tenant = 'initech';
+		DEFINE TABLE purchase PARTITION BY HASH(tenant) PARTITIONS 1;
+		SELECT VALUE id FROM purchase WHERE tenant = 'acme';
+		SELECT VALUE id FROM purchase WHERE tenant = 'acme' EXPLAIN;
+		SELECT VALUE id FROM purchase WHERE tenant = 1 EXPLAIN;
+	";
+	let dbs = new_ds().await?;
+	let mut res = execute_test(&dbs, sql, 14).await?;
+	skip_ok(&mut res, 7)?;
+	check_result(&mut res, "[purchase:1, purchase:2]")?;
+	check_result(&mut res, "[purchase:4]")?;
+	check_result(&mut res, "[purchase:1, purchase:2, purchase:4]")?;
+	skip_ok(&mut res, 1)?;
+	check_result(&mut res, "[purchase:1, purchase:2]")?;
+	check_result(
+		&mut res,
+		"[
+			{
+				detail: {
+					partition: 0,
+					table: 'purchase'
+				},
+				operation: 'Iterate Partition'
+			},
+			{
+				detail: {
+					reason: 'NO INDEX FOUND'
+				},
+				operation: 'Fallback'
+			},
+			{
+				detail: {
+					type: 'Memory'
+				},
+				operation: 'Collector'
+			}
+		]",
+	)?;
+	check_result(
+		&mut res,
+		"[
+			{
+				detail: {
+					table: 'purchase'
+				},
+				operation: 'Iterate Table'
+			},
+			{
+				detail: {
+					reason: 'NO INDEX FOUND'
+				},
+				operation: 'Fallback'
+			},
+			{
+				detail: {
+					type: 'Memory'
+				},
+				operation: 'Collector'
+			}
+		]",
+	)?;
+	Ok(())
+}
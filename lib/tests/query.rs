@@ -2,7 +2,7 @@ mod parse;
 use parse::Parse;
 mod helpers;
 use helpers::new_ds;
-use surrealdb::dbs::Session;
+use surrealdb::dbs::{ReadOnly, Session};
 use surrealdb::err::Error;
 use surrealdb::sql::Value;
 
@@ -161,3 +161,135 @@ async fn query_root_record() -> Result<(), Error> {
 	//
 	Ok(())
 }
+
+#[tokio::test]
+async fn query_readonly() -> Result<(), Error> {
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	dbs.execute("CREATE person:one, person:two, person:three", &ses, None).await?;
+	let sql = "
+		SELECT * FROM person:one;
+		SELECT * FROM person;
+		SELECT * FROM person START 1;
+		SELECT * FROM person ORDER BY id LIMIT 1;
+		CREATE person:four;
+		DEFINE TABLE other;
+		BEGIN;
+		DELETE person;
+		COMMIT;
+		SELECT count() FROM person GROUP ALL;
+	";
+	let lim = ReadOnly::new().with_limit(Some(2));
+	let res = &mut dbs.execute_readonly(sql, &ses, None, lim).await?;
+	assert_eq!(res.len(), 8);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: person:one }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp,
+		Err(Error::RowLimitExceeded {
+			limit: 2
+		})
+	));
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: person:three }, { id: person:two }]");
+	assert_eq!(tmp, val);
+	// Sorting the rows would collect all of them
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp,
+		Err(Error::RowLimitExceeded {
+			limit: 2
+		})
+	));
+	//
+	for _ in 0..3 {
+		let tmp = res.remove(0).result;
+		assert!(tmp.is_err());
+	}
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ count: 3 }]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn query_readonly_nested_results() -> Result<(), Error> {
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	dbs.execute("CREATE person:one, person:two, person:three", &ses, None).await?;
+	let sql = "
+		SELECT * FROM ONLY person:one;
+		RETURN [(SELECT * FROM person:one), (SELECT * FROM person:two)];
+		RETURN [(SELECT * FROM person), (SELECT * FROM person)];
+		RETURN { people: { all: (SELECT * FROM person) } };
+	";
+	let lim = ReadOnly::new().with_limit(Some(2));
+	let res = &mut dbs.execute_readonly(sql, &ses, None, lim).await?;
+	assert_eq!(res.len(), 4);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("{ id: person:one }");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[[{ id: person:one }], [{ id: person:two }]]");
+	assert_eq!(tmp, val);
+	//
+	for _ in 0..2 {
+		let tmp = res.remove(0).result;
+		assert!(matches!(
+			tmp,
+			Err(Error::RowLimitExceeded {
+				limit: 2
+			})
+		));
+	}
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn query_readonly_side_effects() -> Result<(), Error> {
+	let dir = temp_dir::TempDir::new().unwrap();
+	let path = dir.path().join("person.jsonl");
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	dbs.execute("CREATE person:one", &ses, None).await?;
+	let sql = format!(
+		"
+		SELECT * FROM person INTO OUTFILE 'file://{}';
+		KILL QUERY u'e72bee20-f49b-11ec-b939-0242ac120002';
+		RETURN http::get('http://localhost/');
+		RETURN (SELECT * FROM person WHERE http::head('http://localhost/') = NONE);
+	",
+		path.display()
+	);
+	let res = &mut dbs.execute_readonly(&sql, &ses, None, ReadOnly::new()).await?;
+	assert_eq!(res.len(), 4);
+	//
+	for value in ["INTO OUTFILE", "KILL QUERY"] {
+		let tmp = res.remove(0).result;
+		assert!(
+			matches!(&tmp, Err(Error::ReadOnlyNotAllowed { value: v }) if v == value),
+			"{tmp:?}"
+		);
+	}
+	// Read-only queries are not allowed to reach the network
+	for _ in 0..2 {
+		let tmp = res.remove(0).result;
+		#[cfg(feature = "http")]
+		assert!(matches!(tmp, Err(Error::NetTargetNotAllowed(_))), "{tmp:?}");
+		#[cfg(not(feature = "http"))]
+		assert!(matches!(tmp, Err(Error::HttpDisabled)), "{tmp:?}");
+	}
+	assert!(!path.exists());
+	//
+	Ok(())
+}
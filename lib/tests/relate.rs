@@ -180,3 +180,142 @@ async fn relate_with_param_or_subquery() -> Result<(), Error> {
 	assert_eq!(tmp, val);
 	Ok(())
 }
+
+#[tokio::test]
+async fn relate_bulk_from_subqueries() -> Result<(), Error> {
+	let sql = "
+		CREATE person:tobie, person:jaime;
+		CREATE post:one, post:two;
+		RELATE (SELECT * FROM person)->likes->(SELECT * FROM post) RETURN VALUE [in, out];
+		SELECT VALUE array::sort(->likes->post) FROM person:tobie;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 4);
+	//
+	let _ = res.remove(0).result?;
+	let _ = res.remove(0).result?;
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			[person:jaime, post:one],
+			[person:jaime, post:two],
+			[person:tobie, post:one],
+			[person:tobie, post:two],
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[[post:one, post:two]]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn relate_on_delete_endpoint() -> Result<(), Error> {
+	let sql = "
+		CREATE person:tobie, person:jaime, post:one, post:two;
+		DEFINE TABLE likes TYPE RELATION IN person OUT post;
+		DEFINE TABLE wrote TYPE RELATION IN person OUT post ON DELETE REJECT;
+		RELATE person:tobie->likes->post:one;
+		RELATE person:jaime->wrote->post:two;
+		DELETE post:one;
+		SELECT * FROM likes;
+		DELETE post:two;
+		DELETE person:jaime;
+		DELETE wrote WHERE in = person:jaime;
+		DELETE person:jaime;
+		INFO FOR DB;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 12);
+	//
+	for _ in 0..6 {
+		res.remove(0).result?;
+	}
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp,
+		Err(Error::EdgeDeleteRejected { ref thing, .. }) if thing == "post:two"
+	));
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(
+		tmp,
+		Err(Error::EdgeDeleteRejected { ref thing, .. }) if thing == "person:jaime"
+	));
+	//
+	res.remove(0).result?;
+	res.remove(0).result?;
+	//
+	let tmp = res.remove(0).result?;
+	let Value::Object(tmp) = tmp else {
+		panic!("should be object {tmp:?}")
+	};
+	let Some(Value::Object(tables)) = tmp.get("tables") else {
+		panic!("should contain tables {tmp:?}")
+	};
+	let tmp = tables.get("wrote").cloned().unwrap_or_default();
+	let val = Value::from(
+		"DEFINE TABLE wrote TYPE RELATION IN person OUT post ON DELETE REJECT SCHEMALESS PERMISSIONS NONE",
+	);
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn relate_and_select_paths() -> Result<(), Error> {
+	let sql = "
+		CREATE person:me, person:tobie, person:jaime;
+		RELATE person:me->knows:one->person:tobie;
+		RELATE person:me->knows:two->person:jaime;
+		RELATE person:tobie->knows:three->person:jaime;
+		SELECT ->knows->person AS friends, path() AS via FROM person:me;
+		SELECT VALUE path() FROM person:me;
+		RETURN path();
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 7);
+	//
+	for _ in 0..4 {
+		let _ = res.remove(0).result?;
+	}
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				friends: [person:tobie, person:jaime],
+				via: [
+					[person:me, knows:one, person:tobie],
+					[person:me, knows:two, person:jaime],
+				],
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	// There is no traversal to follow
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[NONE]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::None;
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
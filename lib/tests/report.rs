@@ -0,0 +1,59 @@
+mod parse;
+use parse::Parse;
+
+mod helpers;
+use helpers::*;
+
+use surrealdb::dbs::Session;
+use surrealdb::err::Error;
+use surrealdb::sql::Value;
+
+#[tokio::test]
+async fn report_references_statement() -> Result<(), Error> {
+	let sql = "
+		CREATE person:1 SET best = person:3, friends = [person:2, person:9];
+		CREATE person:2 SET name = 'Two';
+		RELATE person:1->likes:one->ghost:1;
+		REPORT REFERENCES;
+		REPORT REFERENCES ON likes;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 5);
+	for _ in 0..3 {
+		let tmp = res.remove(0).result;
+		assert!(tmp.is_ok());
+	}
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"{
+			dangling: [
+				{ edge: true, field: 'out', record: likes:one, target: ghost:1 },
+				{ edge: false, field: 'best', record: person:1, target: person:3 },
+				{ edge: false, field: 'friends[1]', record: person:1, target: person:9 },
+			],
+			tables: {
+				likes: { dangling: 1, edges: 2, links: 0, records: 1 },
+				person: { dangling: 2, edges: 0, links: 3, records: 2 },
+			}
+		}",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"{
+			dangling: [
+				{ edge: true, field: 'out', record: likes:one, target: ghost:1 },
+			],
+			tables: {
+				likes: { dangling: 1, edges: 2, links: 0, records: 1 },
+			}
+		}",
+	);
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
@@ -5,6 +5,8 @@ use helpers::new_ds;
 use surrealdb::dbs::Session;
 use surrealdb::err::Error;
 use surrealdb::iam::Role;
+use surrealdb::kvs::Datastore;
+use surrealdb::kvs::Operations;
 use surrealdb::sql::Value;
 
 #[tokio::test]
@@ -1155,3 +1157,287 @@ async fn select_issue_3510() -> Result<(), Error> {
 	assert_eq!(format!("{:#}", tmp), format!("{:#}", val));
 	Ok(())
 }
+
+#[tokio::test]
+async fn select_sample() -> Result<(), Error> {
+	let sql: &str = "
+		CREATE |test:1..100|;
+		SELECT * FROM test SAMPLE 5;
+		SELECT count() FROM test GROUP ALL SAMPLE 5;
+		SELECT * FROM test WHERE id <= test:3 SAMPLE 10;
+		SELECT * FROM test SAMPLE 5 LIMIT 2;
+		SELECT * FROM test SAMPLE -1;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 6);
+	//
+	let _ = res.remove(0).result?;
+	//
+	let tmp = res.remove(0).result?;
+	match tmp {
+		Value::Array(v) => assert_eq!(v.len(), 5),
+		v => panic!("Expected an array, found {v}"),
+	}
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ count: 100 }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	match tmp {
+		Value::Array(v) => assert_eq!(v.len(), 3),
+		v => panic!("Expected an array, found {v}"),
+	}
+	//
+	let tmp = res.remove(0).result?;
+	match tmp {
+		Value::Array(v) => assert_eq!(v.len(), 2),
+		v => panic!("Expected an array, found {v}"),
+	}
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp, Err(Error::InvalidSample { .. })));
+	//
+	Ok(())
+}
+
+#[cfg(feature = "obs")]
+#[tokio::test]
+async fn select_into_outfile() -> Result<(), Error> {
+	let dir = temp_dir::TempDir::new().unwrap();
+	let path = dir.path().join("person.jsonl");
+	let url = format!("file://{}", path.display());
+	let sql = format!(
+		"
+		CREATE person:tobie SET name = 'Tobie';
+		CREATE person:jaime SET name = 'Jaime';
+		SELECT name FROM person ORDER BY name INTO OUTFILE '{url}' FORMAT jsonl;
+		SELECT * FROM person INTO OUTFILE 'not a url';
+		SELECT * FROM person INTO OUTFILE 'memory:///person.jsonl';
+	"
+	);
+	std::env::set_var("SURREAL_OUTFILE_SCHEMES", "s3,file");
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(&sql, &ses, None).await?;
+	assert_eq!(res.len(), 5);
+	//
+	let _ = res.remove(0).result?;
+	let _ = res.remove(0).result?;
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(&format!("{{ path: '{url}', rows: 2 }}"));
+	assert_eq!(tmp, val);
+	let file = std::fs::read_to_string(&path).unwrap();
+	assert_eq!(file, "{\"name\":\"Jaime\"}\n{\"name\":\"Tobie\"}\n");
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp, Err(Error::InvalidOutfile { .. })));
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp, Err(Error::InvalidOutfile { .. })));
+	//
+	Ok(())
+}
+
+#[cfg(feature = "obs")]
+#[tokio::test]
+async fn select_into_outfile_in_batches() -> Result<(), Error> {
+	let dir = temp_dir::TempDir::new().unwrap();
+	let path = dir.path().join("person.jsonl");
+	let url = format!("file://{}", path.display());
+	let sql = format!(
+		"
+		CREATE |person:1..2500|;
+		SELECT id FROM person START 500 LIMIT 1200 INTO OUTFILE '{url}';
+	"
+	);
+	std::env::set_var("SURREAL_OUTFILE_SCHEMES", "s3,file");
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(&sql, &ses, None).await?;
+	assert_eq!(res.len(), 2);
+	//
+	let _ = res.remove(0).result?;
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(&format!("{{ path: '{url}', rows: 1200 }}"));
+	assert_eq!(tmp, val);
+	let file = std::fs::read_to_string(&path).unwrap();
+	let lines: Vec<&str> = file.lines().collect();
+	assert_eq!(lines.len(), 1200);
+	assert_eq!(lines[0], "{\"id\":\"person:501\"}");
+	assert_eq!(lines[1199], "{\"id\":\"person:1700\"}");
+	//
+	Ok(())
+}
+
+#[cfg(feature = "obs")]
+#[tokio::test]
+async fn select_into_outfile_not_allowed() -> Result<(), Error> {
+	let sql = "
+		CREATE person:tobie SET name = 'Tobie';
+		SELECT * FROM person INTO OUTFILE 's3://bucket/person.jsonl';
+	";
+	std::env::set_var("SURREAL_OUTFILE_SCHEMES", "s3,file");
+	let dbs = Datastore::new("memory").await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 2);
+	//
+	let _ = res.remove(0).result?;
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp, Err(Error::NetTargetNotAllowed(_))));
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_for_update() -> Result<(), Error> {
+	let sql = "
+		CREATE account:one SET balance = 100;
+		BEGIN;
+		LET $acc = (SELECT * FROM ONLY account:one FOR UPDATE);
+		UPDATE account:one SET balance = $acc.balance - 30;
+		COMMIT;
+		SELECT balance FROM account FOR UPDATE;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 4);
+	//
+	let _ = res.remove(0).result?;
+	let _ = res.remove(0).result?;
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: account:one, balance: 70 }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ balance: 70 }]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_with_consistency() -> Result<(), Error> {
+	let sql = "
+		CREATE person:one SET age = 1;
+		SELECT age FROM person WITH CONSISTENCY EVENTUAL;
+		BEGIN;
+		CREATE person:two SET age = 2;
+		SELECT age FROM person WITH NOINDEX WITH CONSISTENCY STRONG WHERE age > 0;
+		COMMIT;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 4);
+	//
+	let _ = res.remove(0).result?;
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ age: 1 }]");
+	assert_eq!(tmp, val);
+	//
+	let _ = res.remove(0).result?;
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ age: 1 }, { age: 2 }]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_cache() -> Result<(), Error> {
+	let sql = "
+		CREATE person:one SET age = 1;
+		SELECT age, rand::uuid() AS token FROM person CACHE 1m;
+		SELECT age, rand::uuid() AS token FROM person CACHE 1m;
+		CREATE person:two SET age = 2;
+		SELECT age, rand::uuid() AS token FROM person CACHE 1m;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 5);
+	//
+	let _ = res.remove(0).result?;
+	//
+	let first = res.remove(0).result?;
+	let second = res.remove(0).result?;
+	assert_eq!(first, second);
+	//
+	let _ = res.remove(0).result?;
+	// The write to the table removed the cached result
+	let tmp = res.remove(0).result?;
+	assert_eq!(tmp.pick(&surrealdb::sql::idiom("age")?), Value::parse("[1, 2]"));
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_cache_of_untracked_records() -> Result<(), Error> {
+	let sql = "
+		CREATE person:one SET age = 1;
+		CREATE post:one SET author = person:one;
+		SELECT * FROM (SELECT age FROM person) CACHE 1m;
+		SELECT author.age AS age FROM post CACHE 1m;
+		CREATE person:two SET age = 2;
+		UPDATE person:one SET age = 3;
+		SELECT * FROM (SELECT age FROM person) CACHE 1m;
+		SELECT author.age AS age FROM post CACHE 1m;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 8);
+	//
+	for _ in 0..6 {
+		let _ = res.remove(0).result?;
+	}
+	// The results read records which are not in the selected tables, so they are not cached
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ age: 3 }, { age: 2 }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ age: 3 }]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn select_operation_budget() -> Result<(), Error> {
+	let sql = "
+		CREATE person:one SET age = 1;
+		SELECT * FROM person:one;
+		SELECT * FROM person WHERE age = 1;
+	";
+	let dbs = new_ds().await?.with_operation_counts(true);
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 3);
+	//
+	let tmp = res.remove(0).operations.unwrap();
+	assert!(tmp.puts > 0, "{tmp}");
+	// Selecting a single record only reads a handful of keys
+	let tmp = res.remove(0).operations.unwrap();
+	assert!(tmp.within(&Operations::budget(10, 5, 0, 0)), "{tmp}");
+	// Reading never writes or deletes anything
+	let tmp = res.remove(0).operations.unwrap();
+	assert!(tmp.within(&Operations::budget(u64::MAX, u64::MAX, 0, 0)), "{tmp}");
+	// Operations are only counted when the datastore is asked to count them
+	let dbs = new_ds().await?;
+	let res = &mut dbs.execute("SELECT * FROM person", &ses, None).await?;
+	assert!(res.remove(0).operations.is_none());
+	//
+	Ok(())
+}
@@ -233,6 +233,7 @@ async fn loose_mode_all_ok() -> Result<(), Error> {
 	let tmp = res.remove(0).result?;
 	let val = Value::parse(
 		"{
+			capabilities: [],
 			namespaces: { test: 'DEFINE NAMESPACE test' },
 			users: {},
 		}",
@@ -253,10 +254,13 @@ async fn loose_mode_all_ok() -> Result<(), Error> {
 	let val = Value::parse(
 		"{
 			analyzers: {},
+			checks: {},
+			dictionaries: {},
 			tokens: {},
 			functions: {},
 			models: {},
 			params: {},
+			roles: {},
 			scopes: {},
 			tables: { test: 'DEFINE TABLE test TYPE ANY SCHEMALESS PERMISSIONS NONE' },
 			users: {},
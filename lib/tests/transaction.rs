@@ -185,3 +185,76 @@ async fn transaction_with_throw_and_return() -> Result<(), Error> {
 	//
 	Ok(())
 }
+
+#[tokio::test]
+async fn transaction_atomic_with_failure() -> Result<(), Error> {
+	let sql = "
+		OPTION ATOMIC;
+		CREATE person:tobie;
+		CREATE person:jaime, person:tobie;
+		CREATE person:lucy;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 3);
+	//
+	let tmp = res.remove(0);
+	assert!(tmp.rollback.is_none());
+	assert_eq!(tmp.result?, Value::parse("[{ id: person:tobie }]"));
+	//
+	let tmp = res.remove(0);
+	assert!(tmp.rollback.is_some_and(|v| v.keys > 0));
+	assert!(matches!(
+		tmp.result.err(),
+		Some(e) if e.to_string() == r#"Database record `person:tobie` already exists"#
+	));
+	//
+	let tmp = res.remove(0);
+	assert!(tmp.rollback.is_none());
+	assert_eq!(tmp.result?, Value::parse("[{ id: person:lucy }]"));
+	// The changes of the failed statement were undone
+	let res = &mut dbs.execute("SELECT id FROM person", &ses, None).await?;
+	assert_eq!(res.len(), 1);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				id: person:lucy,
+			},
+			{
+				id: person:tobie,
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
+
+#[tokio::test]
+async fn transaction_with_isolation() -> Result<(), Error> {
+	let sql = "
+		BEGIN TRANSACTION ISOLATION SERIALIZABLE;
+		CREATE person:tobie;
+		COMMIT;
+		BEGIN TRANSACTION ISOLATION READ COMMITTED;
+		SELECT id FROM person;
+		COMMIT;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 2);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: person:tobie }]");
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse("[{ id: person:tobie }]");
+	assert_eq!(tmp, val);
+	//
+	Ok(())
+}
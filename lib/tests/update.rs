@@ -77,6 +77,60 @@ async fn update_merge_and_content() -> Result<(), Error> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn update_dry_run() -> Result<(), Error> {
+	let sql = "
+		DEFINE FIELD score ON person VALUE $this.age * 2;
+		DEFINE EVENT log ON person WHEN $event = 'UPDATE' THEN (CREATE log:1 SET person = $after.id);
+		CREATE person:test SET age = 18;
+		DRY RUN UPDATE person:test SET age = 25;
+		SELECT * FROM person, log;
+		BEGIN;
+		DRY RUN DELETE person;
+		COMMIT;
+	";
+	let dbs = new_ds().await?;
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = &mut dbs.execute(sql, &ses, None).await?;
+	assert_eq!(res.len(), 6);
+	//
+	for _ in 0..3 {
+		let tmp = res.remove(0).result;
+		assert!(tmp.is_ok(), "{:?}", tmp);
+	}
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				id: person:test,
+				diff: [
+					{ op: 'replace', path: '/age', value: 25 },
+					{ op: 'replace', path: '/score', value: 50 },
+				]
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result?;
+	let val = Value::parse(
+		"[
+			{
+				age: 18,
+				id: person:test,
+				score: 36
+			}
+		]",
+	);
+	assert_eq!(tmp, val);
+	//
+	let tmp = res.remove(0).result;
+	assert!(matches!(tmp, Err(Error::DryRunInTransaction)), "{:?}", tmp);
+	//
+	Ok(())
+}
+
 #[tokio::test]
 async fn update_simple_with_input() -> Result<(), Error> {
 	let sql = "
@@ -25,7 +25,7 @@ pub enum Error {
 }
 
 /// Construct a Credentials instance for the given auth level
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct CredentialsBuilder<'a> {
 	/// The auth username
 	pub username: Option<&'a str>,
@@ -0,0 +1,228 @@
+use crate::err::Error;
+use clap::Args;
+use reqwest::{Client, Response};
+use std::io::SeekFrom;
+use std::time::Duration;
+use surrealdb::kvs::{SyncChunk, SyncDecoder};
+use tokio::fs::{File, OpenOptions};
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+
+/// The size of each read from a backup file
+const READ_SIZE: usize = 64 * 1024;
+
+/// The time to wait before resuming an interrupted download
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+
+#[derive(Args, Debug)]
+pub struct BackupCommandArguments {
+	#[arg(help = "Url of the remote database, or path of the backup file, to back up from")]
+	#[arg(index = 1)]
+	from: String,
+	#[arg(help = "Url of the remote database, or path of the backup file, to back up into")]
+	#[arg(index = 2)]
+	into: String,
+	#[arg(help = "Database authentication username to use when connecting")]
+	#[arg(env = "SURREAL_USER", short = 'u', long = "username", visible_alias = "user")]
+	#[arg(requires = "password")]
+	username: Option<String>,
+	#[arg(help = "Database authentication password to use when connecting")]
+	#[arg(env = "SURREAL_PASS", short = 'p', long = "password", visible_alias = "pass")]
+	#[arg(requires = "username")]
+	password: Option<String>,
+	#[arg(help = "Continue an interrupted backup into a file after its last complete chunk")]
+	#[arg(long = "resume")]
+	resume: bool,
+	#[arg(help = "The number of times an interrupted download is resumed before failing")]
+	#[arg(long = "retries", default_value_t = 3)]
+	retries: u32,
+}
+
+/// Where the chunks of a backup are written
+enum Target {
+	File(File),
+	Remote(String),
+}
+
+pub async fn init(
+	BackupCommandArguments {
+		from,
+		into,
+		username,
+		password,
+		resume,
+		retries,
+	}: BackupCommandArguments,
+) -> Result<(), Error> {
+	// Initialize opentelemetry and logging
+	crate::telemetry::builder().with_log_level("warn").init();
+	// The credentials of both remote databases
+	let auth = username.zip(password);
+	let client = Client::new();
+	// Open the target of the backup
+	let (target, token) = match remote(&into) {
+		Some(url) => (Target::Remote(url), None),
+		None => open(&into, resume).await?,
+	};
+	let mut backup = Backup {
+		client,
+		auth,
+		target,
+		keys: 0,
+	};
+	match remote(&from) {
+		Some(url) => backup.download(&url, token, retries).await?,
+		None => backup.read(&from).await?,
+	}
+	if let Target::File(file) = &mut backup.target {
+		file.flush().await?;
+	}
+	info!("The backup completed successfully");
+	Ok(())
+}
+
+struct Backup {
+	client: Client,
+	auth: Option<(String, String)>,
+	target: Target,
+	keys: usize,
+}
+
+impl Backup {
+	/// Download the chunks of a remote database, resuming after the last complete
+	/// chunk when the download is interrupted
+	async fn download(
+		&mut self,
+		url: &str,
+		mut token: Option<String>,
+		retries: u32,
+	) -> Result<(), Error> {
+		let mut attempt = 0;
+		loop {
+			match self.download_from(url, &mut token).await {
+				Ok(()) => return Ok(()),
+				// Only interrupted transfers are retried
+				Err(Error::Remote(e)) if attempt < retries && !e.is_status() => {
+					attempt += 1;
+					warn!("The backup was interrupted, so resuming after {token:?}: {e}");
+					tokio::time::sleep(RETRY_DELAY).await;
+				}
+				Err(e) => return Err(e),
+			}
+		}
+	}
+
+	async fn download_from(&mut self, url: &str, token: &mut Option<String>) -> Result<(), Error> {
+		let mut req = self.client.get(format!("{url}/sync"));
+		if let Some(token) = token {
+			req = req.query(&[("resume", token)]);
+		}
+		let mut res = self.authenticate(req).send().await?.error_for_status()?;
+		let mut decoder = SyncDecoder::default();
+		while let Some(bytes) = res.chunk().await? {
+			decoder.push(&bytes);
+			while let Some(chunk) = decoder.next_chunk()? {
+				*token = chunk.resume_token();
+				self.write(chunk).await?;
+			}
+		}
+		match decoder.remaining() {
+			0 => Ok(()),
+			_ => Err(Error::Request),
+		}
+	}
+
+	/// Read the chunks of a backup file
+	async fn read(&mut self, path: &str) -> Result<(), Error> {
+		let mut file = File::open(path).await?;
+		let mut decoder = SyncDecoder::default();
+		let mut buf = vec![0; READ_SIZE];
+		loop {
+			let n = file.read(&mut buf).await?;
+			if n == 0 {
+				break;
+			}
+			decoder.push(&buf[..n]);
+			while let Some(chunk) = decoder.next_chunk()? {
+				self.write(chunk).await?;
+			}
+		}
+		match decoder.remaining() {
+			0 => Ok(()),
+			_ => Err(Error::Request),
+		}
+	}
+
+	/// Write a chunk to the target of the backup
+	async fn write(&mut self, chunk: SyncChunk) -> Result<(), Error> {
+		self.keys += chunk.pairs.len();
+		match &mut self.target {
+			Target::File(file) => file.write_all(&chunk.encode()).await?,
+			Target::Remote(url) => {
+				let req = self.client.post(format!("{url}/sync")).body(chunk.encode());
+				self.authenticate(req).send().await.and_then(Response::error_for_status)?;
+			}
+		}
+		debug!("Backed up {} keys", self.keys);
+		Ok(())
+	}
+
+	fn authenticate(&self, req: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+		match &self.auth {
+			Some((user, pass)) => req.basic_auth(user, Some(pass)),
+			None => req,
+		}
+	}
+}
+
+/// The http url of a remote database, or nothing if the path is a file
+fn remote(path: &str) -> Option<String> {
+	let url = match path.split_once("://") {
+		Some(("http" | "https", _)) => path.to_owned(),
+		Some(("ws", rest)) => format!("http://{rest}"),
+		Some(("wss", rest)) => format!("https://{rest}"),
+		_ => return None,
+	};
+	Some(url.trim_end_matches('/').to_owned())
+}
+
+/// Open a backup file, returning the resume token of its last complete chunk when
+/// an interrupted backup is resumed. Anything after the last complete chunk is
+/// removed, as it was only partly written when the backup was interrupted.
+async fn open(path: &str, resume: bool) -> Result<(Target, Option<String>), Error> {
+	if !resume {
+		return Ok((Target::File(File::create(path).await?), None));
+	}
+	let mut file = OpenOptions::new().create(true).read(true).write(true).open(path).await?;
+	let mut decoder = SyncDecoder::default();
+	let mut buf = vec![0; READ_SIZE];
+	let mut token = None;
+	// The number of bytes which were read, and the end of the last complete chunk
+	let mut read = 0;
+	let mut end = 0;
+	'read: loop {
+		let n = file.read(&mut buf).await?;
+		if n == 0 {
+			break;
+		}
+		read += n as u64;
+		decoder.push(&buf[..n]);
+		loop {
+			match decoder.next_chunk() {
+				Ok(Some(chunk)) => {
+					token = chunk.resume_token();
+					end = read - decoder.remaining() as u64;
+				}
+				Ok(None) => break,
+				// Anything from a chunk which is corrupted is backed up again
+				Err(_) => break 'read,
+			}
+		}
+	}
+	let len = file.metadata().await?.len();
+	if end < len {
+		warn!("Removing the {} bytes after the last complete chunk", len - end);
+		file.set_len(end).await?;
+	}
+	file.seek(SeekFrom::Start(end)).await?;
+	Ok((Target::File(file), token))
+}
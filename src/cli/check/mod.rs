@@ -0,0 +1,17 @@
+mod refs;
+
+use self::refs::RefsCommandArguments;
+use crate::err::Error;
+use clap::Subcommand;
+
+#[derive(Debug, Subcommand)]
+pub enum CheckCommand {
+	#[command(about = "Check record links and graph edges for references to missing records")]
+	Refs(RefsCommandArguments),
+}
+
+pub async fn init(command: CheckCommand) -> Result<(), Error> {
+	match command {
+		CheckCommand::Refs(args) => refs::init(args).await,
+	}
+}
@@ -0,0 +1,177 @@
+use crate::cli::abstraction::{
+	AuthArguments, DatabaseConnectionArguments, DatabaseSelectionArguments,
+};
+use crate::cli::import::connect_client;
+use crate::err::Error;
+use clap::{Args, ValueEnum};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashSet};
+use surrealdb::engine::any::Any;
+use surrealdb::sql::{Table, Thing, Value};
+use surrealdb::Surreal;
+
+#[derive(Args, Debug)]
+pub struct RefsCommandArguments {
+	#[arg(help = "The tables to check, instead of every table in the database")]
+	#[arg(long = "table")]
+	tables: Vec<String>,
+	#[arg(
+		help = "Fix any dangling references, by deleting the records which contain them, or by setting them to NULL"
+	)]
+	#[arg(long = "fix")]
+	fix: Option<Fix>,
+	#[arg(help = "The number of records which are fixed in each transaction")]
+	#[arg(long = "batch-size")]
+	#[arg(default_value_t = 1000)]
+	#[arg(value_parser = clap::value_parser!(u64).range(1..))]
+	batch_size: u64,
+	#[command(flatten)]
+	conn: DatabaseConnectionArguments,
+	#[command(flatten)]
+	auth: AuthArguments,
+	#[command(flatten)]
+	sel: DatabaseSelectionArguments,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, Eq, PartialEq)]
+enum Fix {
+	/// Delete the records which contain dangling references
+	Delete,
+	/// Set the dangling references to NULL, and delete graph edges to missing records
+	Null,
+}
+
+/// The output of the `REPORT REFERENCES` statement
+#[derive(Debug, Deserialize)]
+struct Report {
+	tables: BTreeMap<String, Counts>,
+	dangling: Vec<Dangling>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Counts {
+	records: u64,
+	links: u64,
+	edges: u64,
+	dangling: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct Dangling {
+	record: Thing,
+	field: String,
+	target: Thing,
+	edge: bool,
+}
+
+pub async fn init(
+	RefsCommandArguments {
+		tables,
+		fix,
+		batch_size,
+		conn,
+		auth,
+		sel,
+	}: RefsCommandArguments,
+) -> Result<(), Error> {
+	// Initialize opentelemetry and logging
+	crate::telemetry::builder().with_log_level("info").init();
+	// Connect to the database engine
+	let client = connect_client(&conn, &auth, &sel).await?;
+	// Report the dangling references
+	let mut sql = String::from("REPORT REFERENCES");
+	if !tables.is_empty() {
+		let tables: Vec<_> = tables.into_iter().map(|v| Table::from(v).to_string()).collect();
+		sql.push_str(" ON ");
+		sql.push_str(&tables.join(", "));
+	}
+	let report: Option<Report> = client.query(sql).await?.check()?.take(0)?;
+	let Some(report) = report else {
+		return Err(Error::Other("The database did not return a report".to_owned()));
+	};
+	println!("Tables:");
+	for (tb, v) in report.tables.iter() {
+		println!(
+			"  {tb}: {} records, {} links, {} edges, {} dangling",
+			v.records, v.links, v.edges, v.dangling
+		);
+	}
+	if report.dangling.is_empty() {
+		println!("No dangling references were found");
+		return Ok(());
+	}
+	println!("Dangling references:");
+	for v in report.dangling.iter() {
+		let kind = if v.edge {
+			"edge"
+		} else {
+			"link"
+		};
+		println!("  {} {} -> {} ({kind})", v.record, v.field, v.target);
+	}
+	// Fix the dangling references
+	match fix {
+		Some(fix) => repair(&client, report.dangling, fix, batch_size as usize).await,
+		None => Err(Error::Other(format!(
+			"Found {} dangling references, which can be fixed with --fix",
+			report.dangling.len()
+		))),
+	}
+}
+
+/// Fix the dangling references in batches, with each batch in its own transaction
+async fn repair(
+	client: &Surreal<Any>,
+	dangling: Vec<Dangling>,
+	fix: Fix,
+	batch_size: usize,
+) -> Result<(), Error> {
+	// The records to delete, and the fields to set to NULL on each record
+	let mut delete: Vec<Thing> = Vec::new();
+	let mut null: Vec<(Thing, Vec<String>)> = Vec::new();
+	for v in dangling {
+		// Graph edges can not exist without both of their records
+		if v.edge || fix == Fix::Delete {
+			delete.push(v.record);
+			continue;
+		}
+		// Parse the field again, so that only a field path is written into the query
+		let field = surrealdb::syn::idiom(&v.field)?.to_string();
+		match null.last_mut() {
+			Some((rid, fields)) if *rid == v.record => fields.push(field),
+			_ => null.push((v.record, vec![field])),
+		}
+	}
+	// The references for each record are reported together
+	delete.dedup();
+	let deleted: HashSet<&Thing> = delete.iter().collect();
+	null.retain(|(rid, _)| !deleted.contains(rid));
+	// Set the dangling references to NULL
+	for batch in null.chunks(batch_size) {
+		let mut sql = String::from("BEGIN TRANSACTION;\n");
+		let mut vars = BTreeMap::new();
+		for (i, (rid, fields)) in batch.iter().enumerate() {
+			let fields: Vec<_> = fields.iter().map(|v| format!("{v} = NULL")).collect();
+			sql.push_str(&format!("UPDATE $r{i} SET {} RETURN NONE;\n", fields.join(", ")));
+			vars.insert(format!("r{i}"), Value::from(rid.clone()));
+		}
+		sql.push_str("COMMIT TRANSACTION;\n");
+		client.query(sql).bind(vars).await?.check()?;
+		info!("Set the dangling references on {} records to NULL", batch.len());
+	}
+	// Delete the records
+	for batch in delete.chunks(batch_size) {
+		client
+			.query("DELETE $records RETURN NONE")
+			.bind(("records", batch.to_vec()))
+			.await?
+			.check()?;
+		info!("Deleted {} records", batch.len());
+	}
+	println!(
+		"Fixed the dangling references, by deleting {} records and updating {} records",
+		delete.len(),
+		null.len()
+	);
+	Ok(())
+}
@@ -14,6 +14,9 @@ pub struct ExportCommandArguments {
 	#[arg(default_value = "-")]
 	#[arg(index = 1)]
 	file: String,
+	#[arg(help = "Export only the specified table, with its definitions. Can be repeated.")]
+	#[arg(long = "table")]
+	tables: Vec<String>,
 
 	#[command(flatten)]
 	conn: DatabaseConnectionArguments,
@@ -26,6 +29,7 @@ pub struct ExportCommandArguments {
 pub async fn init(
 	ExportCommandArguments {
 		file,
+		tables,
 		conn: DatabaseConnectionArguments {
 			endpoint,
 		},
@@ -77,7 +81,7 @@ pub async fn init(
 	debug!("Exporting data from the database");
 	if file == "-" {
 		// Prepare the backup
-		let mut backup = client.export(()).await?;
+		let mut backup = client.export(()).tables(tables).await?;
 		// Get a handle to standard output
 		let mut stdout = io::stdout();
 		// Write the backup to standard output
@@ -85,7 +89,7 @@ pub async fn init(
 			stdout.write_all(&bytes?).await?;
 		}
 	} else {
-		client.export(file).await?;
+		client.export(file).tables(tables).await?;
 	}
 	info!("The SurrealQL file was exported successfully");
 	// Everything OK
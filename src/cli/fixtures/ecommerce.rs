@@ -0,0 +1,254 @@
+//! A dataset of users who have placed orders for products in an online store.
+//!
+//! Each unit of scale generates 10 users, 2 products, and 30 orders. Every
+//! order is related to the user who placed it with a `placed` edge, and to
+//! each of the products it contains with an `item` edge. Orders are stored
+//! in the `purchase` table, as `ORDER` is a SurrealQL keyword.
+use crate::err::Error;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use std::ops::Range;
+use surrealdb::engine::any::Any;
+use surrealdb::sql::{Datetime, Id, Thing, Value};
+use surrealdb::Surreal;
+
+/// The approximate number of bytes generated for each unit of scale
+pub(super) const UNIT_SIZE: u64 = 32 * 1024;
+
+/// The number of users generated for each unit of scale
+const USERS: u64 = 10;
+
+/// The number of products generated for each unit of scale
+const PRODUCTS: u64 = 2;
+
+/// The number of orders generated for each unit of scale
+const ORDERS: u64 = 30;
+
+/// The number of records inserted in each query
+const BATCH_SIZE: u64 = 1000;
+
+/// The time before which all of the data was created
+const EPOCH: i64 = 1_700_000_000;
+
+/// The period of time over which the data was created
+const PERIOD: i64 = 2 * 365 * 24 * 60 * 60;
+
+const SCHEMA: &str = "
+	DEFINE TABLE user SCHEMALESS;
+	DEFINE INDEX user_email ON user FIELDS email UNIQUE;
+	DEFINE INDEX user_country ON user FIELDS country;
+	DEFINE TABLE product SCHEMALESS;
+	DEFINE INDEX product_category ON product FIELDS category;
+	DEFINE TABLE purchase SCHEMALESS;
+	DEFINE INDEX purchase_status ON purchase FIELDS status;
+	DEFINE INDEX purchase_created ON purchase FIELDS created_at;
+	DEFINE TABLE placed TYPE RELATION FROM user TO purchase;
+	DEFINE TABLE item TYPE RELATION FROM purchase TO product;
+";
+
+const FIRST_NAMES: &[&str] = &[
+	"Alice", "Amir", "Ana", "Ben", "Chen", "Chloe", "Daniel", "Elena", "Fatima", "George", "Hana",
+	"Ivan", "Jack", "Julia", "Kofi", "Lena", "Lucas", "Maya", "Noah", "Olivia", "Priya", "Ravi",
+	"Sofia", "Tom", "Yuki", "Zara",
+];
+
+const LAST_NAMES: &[&str] = &[
+	"Adams", "Brown", "Costa", "Dubois", "Evans", "Fischer", "Garcia", "Hughes", "Ito", "Jones",
+	"Kim", "Lopez", "Muller", "Nowak", "Okafor", "Patel", "Rossi", "Silva", "Smith", "Tanaka",
+	"Walker", "Wang",
+];
+
+const COUNTRIES: &[&str] = &[
+	"Australia",
+	"Brazil",
+	"Canada",
+	"France",
+	"Germany",
+	"India",
+	"Italy",
+	"Japan",
+	"Nigeria",
+	"Spain",
+	"United Kingdom",
+	"United States",
+];
+
+const CATEGORIES: &[&str] =
+	&["Books", "Clothing", "Electronics", "Garden", "Grocery", "Health", "Home", "Sports", "Toys"];
+
+const ADJECTIVES: &[&str] = &[
+	"Classic",
+	"Compact",
+	"Deluxe",
+	"Durable",
+	"Eco",
+	"Essential",
+	"Lightweight",
+	"Premium",
+	"Smart",
+	"Vintage",
+	"Wireless",
+];
+
+const NOUNS: &[&str] = &[
+	"Backpack",
+	"Blender",
+	"Camera",
+	"Chair",
+	"Headphones",
+	"Jacket",
+	"Kettle",
+	"Lamp",
+	"Novel",
+	"Puzzle",
+	"Racket",
+	"Speaker",
+	"Watch",
+];
+
+const STATUSES: &[&str] = &["pending", "paid", "shipped", "delivered", "cancelled", "refunded"];
+
+/// Generate the dataset and insert it into the database
+pub(super) async fn load(client: &Surreal<Any>, scale: u64, seed: u64) -> Result<(), Error> {
+	let mut rng = StdRng::seed_from_u64(seed);
+	let (users, products, orders) = (USERS * scale, PRODUCTS * scale, ORDERS * scale);
+	// Define the tables and indexes
+	client.query(SCHEMA).await?.check()?;
+	// Insert the users
+	for ids in batches(users) {
+		let data = ids.map(|id| user(&mut rng, id)).collect();
+		insert(client, "user", data).await?;
+	}
+	info!("Inserted {users} users");
+	// Insert the products
+	for ids in batches(products) {
+		let data = ids.map(|id| product(&mut rng, id)).collect();
+		insert(client, "product", data).await?;
+	}
+	info!("Inserted {products} products");
+	// Insert the orders, and relate them to the users and products
+	for ids in batches(orders) {
+		let mut data = Vec::new();
+		let mut edges = String::from("BEGIN TRANSACTION;\n");
+		for id in ids {
+			data.push(order(&mut rng, id, users, products, &mut edges));
+		}
+		edges.push_str("COMMIT TRANSACTION;\n");
+		insert(client, "purchase", data).await?;
+		client.query(edges).await?.check()?;
+	}
+	info!("Inserted {orders} orders");
+	Ok(())
+}
+
+/// Insert a batch of records into a table
+async fn insert(client: &Surreal<Any>, tb: &str, data: Vec<Value>) -> Result<(), Error> {
+	client
+		.query(format!("INSERT INTO {tb} $data RETURN NONE"))
+		.bind(("data", data))
+		.await?
+		.check()?;
+	Ok(())
+}
+
+/// Split the record ids of a table into batches
+fn batches(count: u64) -> impl Iterator<Item = Range<u64>> {
+	(1..=count).step_by(BATCH_SIZE as usize).map(move |id| id..(id + BATCH_SIZE).min(count + 1))
+}
+
+fn user(rng: &mut StdRng, id: u64) -> Value {
+	let first = pick(rng, FIRST_NAMES);
+	let last = pick(rng, LAST_NAMES);
+	Value::from(map! {
+		String::from("id") => thing("user", id),
+		String::from("name") => Value::from(format!("{first} {last}")),
+		String::from("email") => Value::from(format!("{first}.{last}.{id}@example.com").to_lowercase()),
+		String::from("country") => Value::from(pick(rng, COUNTRIES)),
+		String::from("premium") => Value::from(rng.gen_bool(0.2)),
+		String::from("created_at") => datetime(rng),
+	})
+}
+
+fn product(rng: &mut StdRng, id: u64) -> Value {
+	let name = format!("{} {}", pick(rng, ADJECTIVES), pick(rng, NOUNS));
+	Value::from(map! {
+		String::from("id") => thing("product", id),
+		String::from("name") => Value::from(name),
+		String::from("category") => Value::from(pick(rng, CATEGORIES)),
+		String::from("price") => Value::from(price(id)),
+		String::from("stock") => Value::from(rng.gen_range(0..1000i64)),
+		String::from("rating") => Value::from(rng.gen_range(10..=50i64) as f64 / 10.0),
+	})
+}
+
+/// Generate an order, appending the statements which relate it to a user and products
+fn order(rng: &mut StdRng, id: u64, users: u64, products: u64, edges: &mut String) -> Value {
+	let order = thing("purchase", id);
+	let user = thing("user", rng.gen_range(1..=users));
+	let created_at = datetime(rng);
+	edges.push_str(&format!("RELATE {user}->placed->{order} SET at = {created_at} RETURN NONE;\n"));
+	// Some products are far more popular than others
+	let mut total = 0.0;
+	for _ in 0..rng.gen_range(1..=4) {
+		let id = 1 + (rng.gen::<f64>().powi(3) * products as f64) as u64;
+		let id = id.min(products);
+		let quantity = rng.gen_range(1..=3i64);
+		total += price(id) * quantity as f64;
+		let product = thing("product", id);
+		edges.push_str(&format!(
+			"RELATE {order}->item->{product} SET quantity = {quantity}, price = {} RETURN NONE;\n",
+			price(id)
+		));
+	}
+	Value::from(map! {
+		String::from("id") => order,
+		String::from("user") => user,
+		String::from("status") => Value::from(pick(rng, STATUSES)),
+		String::from("total") => Value::from((total * 100.0).round() / 100.0),
+		String::from("currency") => Value::from("USD"),
+		String::from("created_at") => created_at,
+	})
+}
+
+/// The price of a product, which is derived from its id so that orders can be priced
+fn price(id: u64) -> f64 {
+	(id.wrapping_mul(7919) % 49_900 + 100) as f64 / 100.0
+}
+
+fn pick(rng: &mut StdRng, values: &[&'static str]) -> &'static str {
+	values.choose(rng).copied().unwrap_or_default()
+}
+
+fn thing(tb: &str, id: u64) -> Value {
+	Value::from(Thing::from((tb, Id::from(id))))
+}
+
+fn datetime(rng: &mut StdRng) -> Value {
+	let secs = EPOCH - rng.gen_range(0..PERIOD);
+	Datetime::try_from((secs, 0)).map(Value::from).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn batches_cover_every_record() {
+		let ids: Vec<_> = batches(2500).collect();
+		assert_eq!(ids, vec![1..1001, 1001..2001, 2001..2501]);
+		assert_eq!(batches(0).count(), 0);
+	}
+
+	#[test]
+	fn orders_are_related_to_existing_records() {
+		let mut rng = StdRng::seed_from_u64(0);
+		let mut edges = String::new();
+		let order = order(&mut rng, 1, USERS, PRODUCTS, &mut edges);
+		assert!(matches!(order, Value::Object(_)));
+		assert!(edges.starts_with("RELATE user:"));
+		assert!(
+			!edges.contains("product:0") && !edges.contains(&format!("product:{}", PRODUCTS + 1))
+		);
+	}
+}
@@ -0,0 +1,76 @@
+use super::ecommerce;
+use crate::cli::abstraction::{
+	AuthArguments, DatabaseConnectionArguments, DatabaseSelectionArguments,
+};
+use crate::cli::import::connect_client;
+use crate::err::Error;
+use clap::{Args, ValueEnum};
+
+#[derive(Args, Debug)]
+pub struct LoadCommandArguments {
+	#[arg(help = "The dataset to generate")]
+	#[arg(long = "dataset")]
+	#[arg(default_value = "ecommerce")]
+	dataset: Dataset,
+	#[arg(help = "The approximate size of the generated data, such as 100mb or 1gb")]
+	#[arg(long = "size")]
+	#[arg(default_value = "10mb")]
+	#[arg(value_parser = crate::cli::validator::size)]
+	size: u64,
+	#[arg(help = "The number of units of related data to generate, instead of a size")]
+	#[arg(long = "scale", conflicts_with = "size")]
+	#[arg(value_parser = clap::value_parser!(u64).range(1..))]
+	scale: Option<u64>,
+	#[arg(help = "The seed used to generate the data, so that the same data can be reproduced")]
+	#[arg(long = "seed")]
+	#[arg(default_value_t = 0)]
+	seed: u64,
+	#[command(flatten)]
+	conn: DatabaseConnectionArguments,
+	#[command(flatten)]
+	auth: AuthArguments,
+	#[command(flatten)]
+	sel: DatabaseSelectionArguments,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, Eq, PartialEq)]
+enum Dataset {
+	/// Users who have placed orders for products in an online store
+	Ecommerce,
+}
+
+impl Dataset {
+	/// The approximate number of bytes generated for each unit of scale
+	fn unit_size(&self) -> u64 {
+		match self {
+			Dataset::Ecommerce => ecommerce::UNIT_SIZE,
+		}
+	}
+}
+
+pub async fn init(
+	LoadCommandArguments {
+		dataset,
+		size,
+		scale,
+		seed,
+		conn,
+		auth,
+		sel,
+	}: LoadCommandArguments,
+) -> Result<(), Error> {
+	// Initialize opentelemetry and logging
+	crate::telemetry::builder().with_log_level("info").init();
+	// Connect to the database engine
+	let client = connect_client(&conn, &auth, &sel).await?;
+	// Calculate the scale factor from the requested size
+	let scale = scale.unwrap_or_else(|| (size / dataset.unit_size()).max(1));
+	info!("Loading the {dataset:?} dataset with a scale factor of {scale}");
+	// Generate and insert the data
+	match dataset {
+		Dataset::Ecommerce => ecommerce::load(&client, scale, seed).await?,
+	}
+	info!("The dataset was loaded successfully");
+	// Everything OK
+	Ok(())
+}
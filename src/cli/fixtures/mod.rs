@@ -0,0 +1,18 @@
+mod ecommerce;
+mod load;
+
+use self::load::LoadCommandArguments;
+use crate::err::Error;
+use clap::Subcommand;
+
+#[derive(Debug, Subcommand)]
+pub enum FixturesCommand {
+	#[command(about = "Generate a dataset and load it into an existing database")]
+	Load(LoadCommandArguments),
+}
+
+pub async fn init(command: FixturesCommand) -> Result<(), Error> {
+	match command {
+		FixturesCommand::Load(args) => load::init(args).await,
+	}
+}
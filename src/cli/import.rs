@@ -4,15 +4,39 @@ use crate::cli::abstraction::{
 };
 use crate::err::Error;
 use clap::Args;
+use futures::future::try_join_all;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::{self, Display};
 use surrealdb::dbs::Capabilities;
-use surrealdb::engine::any::{connect, IntoEndpoint};
+use surrealdb::engine::any::{connect, Any, IntoEndpoint};
 use surrealdb::opt::Config;
+use surrealdb::sql::statements::{
+	CreateStatement, DefineStatement, DeleteStatement, UpdateStatement,
+};
+use surrealdb::sql::{Base, Data, Index, Kind, Query, Statement, TableType, Thing, Value};
+use surrealdb::Surreal;
+
+/// The number of record statements applied in each transaction during a parallel import
+const PARALLEL_BATCH_SIZE: usize = 1000;
 
 #[derive(Args, Debug)]
 pub struct ImportCommandArguments {
 	#[arg(help = "Path to the SurrealQL file to import")]
 	#[arg(index = 1)]
 	file: String,
+	#[arg(help = "The number of connections used to import table data in parallel")]
+	#[arg(short = 'j', long = "jobs")]
+	#[arg(default_value_t = 1)]
+	#[arg(value_parser = clap::value_parser!(u16).range(1..))]
+	jobs: u16,
+	#[arg(
+		help = "Check the file against the database, and report the changes which it would make, without importing it"
+	)]
+	#[arg(long = "dry-run")]
+	dry_run: bool,
+	#[arg(help = "Import only the specified table, with its definitions. Can be repeated.")]
+	#[arg(long = "table")]
+	tables: Vec<String>,
 	#[command(flatten)]
 	conn: DatabaseConnectionArguments,
 	#[command(flatten)]
@@ -24,22 +48,69 @@ pub struct ImportCommandArguments {
 pub async fn init(
 	ImportCommandArguments {
 		file,
-		conn: DatabaseConnectionArguments {
-			endpoint,
-		},
-		auth: AuthArguments {
-			username,
-			password,
-			auth_level,
-		},
-		sel: DatabaseSelectionArguments {
-			namespace,
-			database,
-		},
+		jobs,
+		dry_run,
+		tables,
+		conn,
+		auth,
+		sel,
 	}: ImportCommandArguments,
 ) -> Result<(), Error> {
 	// Initialize opentelemetry and logging
 	crate::telemetry::builder().with_log_level("info").init();
+	// Connect to the database engine
+	let client = connect_client(&conn, &auth, &sel).await?;
+	// Report the changes without importing the data
+	if dry_run {
+		let sql = tokio::fs::read_to_string(&file).await?;
+		let query = select(surrealdb::sql::parse(&sql)?, &tables);
+		return DryRun::new(query).report(&client, &file).await;
+	}
+	// Import the data into the database
+	match jobs {
+		1 if tables.is_empty() => client.import(file).await?,
+		1 => {
+			let sql = tokio::fs::read_to_string(&file).await?;
+			let query = select(surrealdb::sql::parse(&sql)?, &tables);
+			client.query(query.to_string()).await?.check()?;
+		}
+		jobs => {
+			let sql = tokio::fs::read_to_string(&file).await?;
+			let query = select(surrealdb::sql::parse(&sql)?, &tables);
+			match Plan::new(query.clone()) {
+				Some(plan) => plan.apply(client, jobs as usize, &conn, &auth, &sel).await?,
+				None => {
+					info!("The SurrealQL file can not be imported in parallel, so it will be imported serially");
+					match tables.is_empty() {
+						true => client.import(file).await?,
+						false => {
+							client.query(query.to_string()).await?.check()?;
+						}
+					}
+				}
+			}
+		}
+	}
+	info!("The SurrealQL file was imported successfully");
+	// Everything OK
+	Ok(())
+}
+
+/// Connect and authenticate to the database engine, and select the namespace and database
+pub(crate) async fn connect_client(
+	DatabaseConnectionArguments {
+		endpoint,
+	}: &DatabaseConnectionArguments,
+	AuthArguments {
+		username,
+		password,
+		auth_level,
+	}: &AuthArguments,
+	DatabaseSelectionArguments {
+		namespace,
+		database,
+	}: &DatabaseSelectionArguments,
+) -> Result<Surreal<Any>, Error> {
 	// Default datastore configuration for local engines
 	let config = Config::new().capabilities(Capabilities::all());
 
@@ -47,7 +118,7 @@ pub async fn init(
 	// If we are connecting directly to a datastore (i.e. file://local.db or tikv://...), then we don't need to authenticate because we use an embedded (local) SurrealDB instance with auth disabled.
 	let client = if username.is_some()
 		&& password.is_some()
-		&& !endpoint.clone().into_endpoint()?.parse_kind()?.is_local()
+		&& !endpoint.into_endpoint()?.parse_kind()?.is_local()
 	{
 		debug!("Connecting to the database engine with authentication");
 		let creds = CredentialsBuilder::default()
@@ -73,9 +144,494 @@ pub async fn init(
 
 	// Use the specified namespace / database
 	client.use_ns(namespace).use_db(database).await?;
-	// Import the data into the database
-	client.import(file).await?;
-	info!("The SurrealQL file was imported successfully");
-	// Everything OK
+
+	Ok(client)
+}
+
+/// Keep only the statements which define, or write records to, the specified tables.
+/// If no tables are specified, every statement is kept.
+fn select(mut query: Query, tables: &[String]) -> Query {
+	if tables.is_empty() {
+		return query;
+	}
+	let selected = |tb: &str| tables.iter().any(|v| v == tb);
+	query.0 .0.retain(|stm| match stm {
+		Statement::Option(_) | Statement::Begin(_) | Statement::Commit(_) => true,
+		Statement::Define(stm) => match stm {
+			DefineStatement::Table(v) => selected(&v.name),
+			DefineStatement::Field(v) => selected(&v.what),
+			DefineStatement::Index(v) => selected(&v.what),
+			DefineStatement::Event(v) => selected(&v.what),
+			DefineStatement::Hook(v) => selected(&v.what),
+			DefineStatement::Enrichment(v) => selected(&v.what),
+			_ => false,
+		},
+		Statement::Delete(DeleteStatement {
+			what,
+			..
+		}) => matches!(what.0.first(), Some(Value::Thing(v)) if selected(&v.tb)),
+		stm => records(stm).is_some_and(|(tb, _)| selected(&tb)),
+	});
+	query
+}
+
+/// An import which has been split into the schema, which must be
+/// applied first and in order, and the record data for each table,
+/// which can be applied to different tables concurrently.
+struct Plan {
+	// The OPTION statements which apply to every batch
+	options: Vec<Statement>,
+	// The definitions which are applied before any records
+	schema: Vec<Statement>,
+	// The record statements for each table
+	tables: BTreeMap<String, Vec<Statement>>,
+	// The records which are imported into each table
+	records: BTreeMap<String, HashSet<Thing>>,
+}
+
+impl Plan {
+	/// Split a query into schema and record data, returning `None`
+	/// if it contains statements which can not be safely reordered.
+	fn new(query: Query) -> Option<Self> {
+		let mut plan = Plan {
+			options: Vec::new(),
+			schema: Vec::new(),
+			tables: BTreeMap::new(),
+			records: BTreeMap::new(),
+		};
+		for stm in query.0 .0 {
+			let thing = match &stm {
+				Statement::Option(_) => {
+					plan.options.push(stm);
+					continue;
+				}
+				Statement::Define(_) => {
+					plan.schema.push(stm);
+					continue;
+				}
+				// Each batch is applied in its own transaction
+				Statement::Begin(_) | Statement::Commit(_) => continue,
+				Statement::Update(UpdateStatement {
+					what,
+					..
+				})
+				| Statement::Create(CreateStatement {
+					what,
+					..
+				}) => match &what.0[..] {
+					[Value::Thing(thing)] => thing.clone(),
+					_ => return None,
+				},
+				Statement::Relate(stm) => match &stm.kind {
+					Value::Thing(thing) => thing.clone(),
+					_ => return None,
+				},
+				_ => return None,
+			};
+			plan.tables.entry(thing.tb.clone()).or_default().push(stm);
+			plan.records.entry(thing.tb.clone()).or_default().insert(thing);
+		}
+		Some(plan)
+	}
+
+	/// Apply the import using the specified number of connections
+	async fn apply(
+		self,
+		client: Surreal<Any>,
+		jobs: usize,
+		conn: &DatabaseConnectionArguments,
+		auth: &AuthArguments,
+		sel: &DatabaseSelectionArguments,
+	) -> Result<(), Error> {
+		// Apply all of the definitions before any records
+		info!("Importing {} definitions", self.schema.len());
+		apply(&client, &self.options, &self.schema).await?;
+		// Assign each table to the worker with the fewest records
+		let jobs = jobs.min(self.tables.len()).max(1);
+		let mut tables: Vec<_> = self.tables.iter().collect();
+		tables.sort_by_key(|(_, stms)| std::cmp::Reverse(stms.len()));
+		let mut workers = vec![(0, Vec::new()); jobs];
+		for table in tables {
+			if let Some(worker) = workers.iter_mut().min_by_key(|(len, _)| *len) {
+				worker.0 += table.1.len();
+				worker.1.push(table);
+			}
+		}
+		// Embedded datastores can only be opened once, so share the connection
+		let local = conn.endpoint.as_str().into_endpoint()?.parse_kind()?.is_local();
+		let mut clients = vec![client.clone()];
+		for _ in 1..jobs {
+			clients.push(match local {
+				true => client.clone(),
+				false => connect_client(conn, auth, sel).await?,
+			});
+		}
+		// Stream the records for each table across the workers
+		info!("Importing records for {} tables using {jobs} connections", self.tables.len());
+		try_join_all(workers.into_iter().zip(clients).map(|((_, tables), client)| {
+			let options = &self.options;
+			async move {
+				for (tb, stms) in tables {
+					debug!("Importing {} records into table '{tb}'", stms.len());
+					for batch in stms.chunks(PARALLEL_BATCH_SIZE) {
+						apply(&client, options, batch).await?;
+					}
+				}
+				Ok::<(), Error>(())
+			}
+		}))
+		.await?;
+		// Check that every imported record exists
+		for (tb, records) in self.records.iter() {
+			let mut res = client
+				.query("SELECT count() FROM type::table($tb) GROUP ALL")
+				.bind(("tb", tb))
+				.await?;
+			let count: Option<usize> = res.take((0, "count"))?;
+			let count = count.unwrap_or_default();
+			if count < records.len() {
+				return Err(Error::Other(format!(
+					"The import consistency check failed, as the table '{tb}' contains {count} records, but {} records were imported",
+					records.len()
+				)));
+			}
+		}
+		Ok(())
+	}
+}
+
+/// Apply a batch of statements in a single transaction
+async fn apply(
+	client: &Surreal<Any>,
+	options: &[Statement],
+	batch: &[Statement],
+) -> Result<(), Error> {
+	if batch.is_empty() {
+		return Ok(());
+	}
+	let mut sql = String::new();
+	for stm in options {
+		sql.push_str(&format!("{stm};\n"));
+	}
+	sql.push_str("BEGIN TRANSACTION;\n");
+	for stm in batch {
+		sql.push_str(&format!("{stm};\n"));
+	}
+	sql.push_str("COMMIT TRANSACTION;\n");
+	client.query(sql).await?.check()?;
 	Ok(())
 }
+
+/// A check of an import against the database, which reports the
+/// changes which the import would make, without applying them.
+#[derive(Default)]
+struct DryRun {
+	// The definitions which would be applied
+	definitions: Vec<DefineStatement>,
+	// The number of records, and the size of their statements, for each table
+	records: BTreeMap<String, (usize, usize)>,
+	// The number of statements which are neither definitions nor records
+	other: usize,
+}
+
+impl DryRun {
+	fn new(query: Query) -> Self {
+		let mut check = DryRun::default();
+		for stm in query.0 .0 {
+			match stm {
+				Statement::Define(stm) => check.definitions.push(stm),
+				Statement::Option(_) | Statement::Begin(_) | Statement::Commit(_) => {}
+				stm => match records(&stm) {
+					Some((tb, count)) => {
+						let entry = check.records.entry(tb).or_default();
+						entry.0 += count;
+						entry.1 += stm.to_string().len();
+					}
+					None => check.other += 1,
+				},
+			}
+		}
+		check
+	}
+
+	/// Compare the import with the database, and report the changes which it would make
+	async fn report(self, client: &Surreal<Any>, file: &str) -> Result<(), Error> {
+		let mut existing = Existing::default();
+		let (mut created, mut changed, mut unchanged, mut unchecked) = (0, 0, 0, 0);
+		let mut problems = 0;
+		println!("Checking the import of '{file}', without writing to the database");
+		println!("Definitions:");
+		for stm in &self.definitions {
+			let location = Location::new(stm);
+			let Some(old) = existing.get(client, &location).await? else {
+				match location.section {
+					Some(_) => {
+						created += 1;
+						println!("  new: {location}");
+					}
+					None => {
+						unchecked += 1;
+						println!("  not checked: {location}");
+					}
+				}
+				continue;
+			};
+			if old.to_string() == stm.to_string() {
+				unchanged += 1;
+				continue;
+			}
+			changed += 1;
+			println!("  changed: {location}");
+			for warning in conflicts(&old, stm) {
+				problems += 1;
+				println!("    warning: {warning}");
+			}
+		}
+		println!(
+			"  {created} new, {changed} changed, {unchanged} unchanged, {unchecked} not checked"
+		);
+		println!("Records:");
+		for (tb, (count, bytes)) in self.records.iter() {
+			let mut res = client
+				.query("SELECT count() FROM type::table($tb) GROUP ALL")
+				.bind(("tb", tb))
+				.await?;
+			let current: Option<usize> = res.take((0, "count"))?;
+			println!(
+				"  {tb}: {count} records ({}), with {} records already in the table",
+				size(*bytes),
+				current.unwrap_or_default()
+			);
+		}
+		if self.other > 0 {
+			println!("Other statements: {}", self.other);
+		}
+		if problems > 0 {
+			return Err(Error::Other(format!(
+				"The import would make {problems} destructive or conflicting schema changes"
+			)));
+		}
+		info!("The SurrealQL file was checked successfully, and nothing was imported");
+		Ok(())
+	}
+}
+
+/// Where a definition is listed in the output of `INFO FOR DB` or `INFO FOR TABLE`
+struct Location {
+	// The kind of definition
+	kind: &'static str,
+	// The name of the definition
+	name: String,
+	// The table which the definition is on
+	tb: Option<String>,
+	// The section of the INFO output which lists the definition, if it can be checked
+	section: Option<&'static str>,
+}
+
+impl Location {
+	fn new(stm: &DefineStatement) -> Self {
+		let (kind, name, tb, section) = match stm {
+			DefineStatement::Table(v) => ("table", v.name.to_string(), None, Some("tables")),
+			DefineStatement::Field(v) => {
+				("field", v.name.to_string(), Some(v.what.to_string()), Some("fields"))
+			}
+			DefineStatement::Index(v) => {
+				("index", v.name.to_string(), Some(v.what.to_string()), Some("indexes"))
+			}
+			DefineStatement::Event(v) => {
+				("event", v.name.to_string(), Some(v.what.to_string()), Some("events"))
+			}
+			DefineStatement::Hook(v) => {
+				("hook", v.name.to_string(), Some(v.what.to_string()), Some("hooks"))
+			}
+			DefineStatement::Enrichment(v) => {
+				("enrichment", v.name.to_string(), Some(v.what.to_string()), Some("enrichments"))
+			}
+			DefineStatement::Function(v) => {
+				("function", format!("fn::{}", v.name), None, Some("functions"))
+			}
+			DefineStatement::Param(v) => ("param", format!("${}", v.name), None, Some("params")),
+			DefineStatement::Analyzer(v) => {
+				("analyzer", v.name.to_string(), None, Some("analyzers"))
+			}
+			DefineStatement::Scope(v) => ("scope", v.name.to_string(), None, Some("scopes")),
+			DefineStatement::Model(v) => {
+				("model", format!("{}<{}>", v.name, v.version), None, Some("models"))
+			}
+			DefineStatement::Role(v) => ("role", v.name.to_string(), None, Some("roles")),
+			DefineStatement::Dictionary(v) => {
+				("dictionary", v.name.to_string(), None, Some("dictionaries"))
+			}
+			DefineStatement::Check(v) => ("check", v.name.to_string(), None, Some("checks")),
+			DefineStatement::Token(v) => {
+				("token", v.name.to_string(), None, (v.base == Base::Db).then_some("tokens"))
+			}
+			DefineStatement::User(v) => {
+				("user", v.name.to_string(), None, (v.base == Base::Db).then_some("users"))
+			}
+			DefineStatement::Namespace(v) => ("namespace", v.name.to_string(), None, None),
+			DefineStatement::Database(v) => ("database", v.name.to_string(), None, None),
+			_ => ("definition", String::new(), None, None),
+		};
+		Self {
+			kind,
+			name,
+			tb,
+			section,
+		}
+	}
+
+	/// The key of the definition in the section of the INFO output
+	fn key(&self) -> String {
+		match self.kind {
+			"function" => self.name.trim_start_matches("fn::").to_owned(),
+			"param" => self.name.trim_start_matches('$').to_owned(),
+			_ => self.name.clone(),
+		}
+	}
+}
+
+impl Display for Location {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{} {}", self.kind, self.name)?;
+		if let Some(tb) = &self.tb {
+			write!(f, " on table {tb}")?;
+		}
+		Ok(())
+	}
+}
+
+/// The definitions in the database, as listed by `INFO FOR DB` and `INFO FOR TABLE`
+#[derive(Default)]
+struct Existing {
+	db: Option<Value>,
+	tables: HashMap<String, Value>,
+}
+
+impl Existing {
+	/// Find the definition in the database at the same location, if there is one
+	async fn get(
+		&mut self,
+		client: &Surreal<Any>,
+		location: &Location,
+	) -> Result<Option<DefineStatement>, Error> {
+		let Some(section) = location.section else {
+			return Ok(None);
+		};
+		let info = match &location.tb {
+			None => {
+				if self.db.is_none() {
+					self.db = Some(client.query("INFO FOR DB").await?.take::<Value>(0)?);
+				}
+				self.db.as_ref()
+			}
+			Some(tb) => {
+				if !self.tables.contains_key(tb) {
+					let sql = format!("INFO FOR TABLE {tb}");
+					let info = client.query(sql).await?.take::<Value>(0)?;
+					self.tables.insert(tb.clone(), info);
+				}
+				self.tables.get(tb)
+			}
+		};
+		let Some(Value::Object(info)) = info else {
+			return Ok(None);
+		};
+		let Some(Value::Object(section)) = info.get(section) else {
+			return Ok(None);
+		};
+		let Some(Value::Strand(sql)) = section.get(&location.key()) else {
+			return Ok(None);
+		};
+		Ok(surrealdb::sql::parse(sql.as_str())?.0 .0.into_iter().find_map(|stm| match stm {
+			Statement::Define(stm) => Some(stm),
+			_ => None,
+		}))
+	}
+}
+
+/// Describe the changes to a definition which are destructive, or which conflict with existing data
+fn conflicts(old: &DefineStatement, new: &DefineStatement) -> Vec<String> {
+	let mut out = Vec::new();
+	match (old, new) {
+		(DefineStatement::Table(old), DefineStatement::Table(new)) => {
+			if new.drop && !old.drop {
+				out.push("the table will drop any records which are written to it".to_owned());
+			}
+			if new.full && !old.full {
+				out.push("the table will become SCHEMAFULL, so fields which are not defined will be removed from records when they are next updated".to_owned());
+			}
+			if old.kind != new.kind
+				&& (matches!(old.kind, TableType::Relation(_))
+					|| matches!(new.kind, TableType::Relation(_)))
+			{
+				out.push(format!("the table type changes from {} to {}", old.kind, new.kind));
+			}
+			if old.changefeed.is_some() && new.changefeed.is_none() {
+				out.push("the change feed of the table will be removed".to_owned());
+			}
+		}
+		(DefineStatement::Field(old), DefineStatement::Field(new)) => {
+			if old.kind != new.kind {
+				let kind =
+					|v: &Option<Kind>| v.as_ref().map_or("any".to_owned(), ToString::to_string);
+				out.push(format!(
+					"the type changes from {} to {}, so existing records may no longer be valid",
+					kind(&old.kind),
+					kind(&new.kind)
+				));
+			}
+		}
+		(DefineStatement::Index(old), DefineStatement::Index(new)) => {
+			if old.index != Index::Uniq && new.index == Index::Uniq {
+				out.push("the index becomes UNIQUE, which fails if existing records contain duplicate values".to_owned());
+			}
+		}
+		_ => {}
+	}
+	out
+}
+
+/// The table which a statement imports records into, and the number of records
+fn records(stm: &Statement) -> Option<(String, usize)> {
+	let table = |v: &Value| match v {
+		Value::Thing(v) => Some(v.tb.clone()),
+		Value::Table(v) => Some(v.0.clone()),
+		_ => None,
+	};
+	match stm {
+		Statement::Update(UpdateStatement {
+			what,
+			..
+		})
+		| Statement::Create(CreateStatement {
+			what,
+			..
+		}) => Some((table(what.0.first()?)?, what.0.len())),
+		Statement::Relate(stm) => Some((table(&stm.kind)?, 1)),
+		Statement::Insert(stm) => {
+			let count = match &stm.data {
+				Data::SingleExpression(Value::Array(v)) => v.len(),
+				Data::ValuesExpression(v) => v.len(),
+				_ => 1,
+			};
+			Some((table(&stm.into)?, count))
+		}
+		_ => None,
+	}
+}
+
+/// Format a number of bytes, such as `1.5 MiB`
+fn size(bytes: usize) -> String {
+	const UNITS: [&str; 4] = ["bytes", "KiB", "MiB", "GiB"];
+	let mut size = bytes as f64;
+	let mut unit = 0;
+	while size >= 1024.0 && unit < UNITS.len() - 1 {
+		size /= 1024.0;
+		unit += 1;
+	}
+	match unit {
+		0 => format!("{bytes} bytes"),
+		_ => format!("{size:.1} {}", UNITS[unit]),
+	}
+}
@@ -1,13 +1,19 @@
 pub(crate) mod abstraction;
+mod backup;
+mod check;
 mod config;
 mod export;
+mod fixtures;
 mod import;
 mod isready;
 mod ml;
+mod replay;
+mod replicate;
 mod sql;
 mod start;
 #[cfg(test)]
 mod test;
+mod top;
 mod upgrade;
 mod validate;
 pub(crate) mod validator;
@@ -17,18 +23,24 @@ mod version_client;
 use crate::cli::version_client::VersionClient;
 use crate::cnf::{DEBUG_BUILD_WARNING, LOGO, PKG_VERSION};
 use crate::env::RELEASE;
+use backup::BackupCommandArguments;
+use check::CheckCommand;
 use clap::{Parser, Subcommand};
 pub use config::CF;
 use export::ExportCommandArguments;
+use fixtures::FixturesCommand;
 use import::ImportCommandArguments;
 use isready::IsReadyCommandArguments;
 use ml::MlCommand;
+use replay::ReplayCommandArguments;
+use replicate::ReplicateCommandArguments;
 use semver::Version;
 use sql::SqlCommandArguments;
 use start::StartCommandArguments;
 use std::ops::Deref;
 use std::process::ExitCode;
 use std::time::Duration;
+use top::TopCommandArguments;
 use upgrade::UpgradeCommandArguments;
 use validate::ValidateCommandArguments;
 use version::VersionCommandArguments;
@@ -64,22 +76,28 @@ struct Cli {
 enum Commands {
 	#[command(about = "Start the database server")]
 	Start(StartCommandArguments),
-	/* Not implemented yet
 	#[command(about = "Backup data to or from an existing database")]
 	Backup(BackupCommandArguments),
-	*/
 	#[command(about = "Import a SurrealQL script into an existing database")]
 	Import(ImportCommandArguments),
 	#[command(about = "Export an existing database as a SurrealQL script")]
 	Export(ExportCommandArguments),
+	#[command(about = "Replicate the change feeds of databases to another cluster")]
+	Replicate(ReplicateCommandArguments),
+	#[command(about = "Replay captured queries against a fresh datastore")]
+	Replay(ReplayCommandArguments),
 	#[command(about = "Output the command-line tool and remote server version information")]
 	Version(VersionCommandArguments),
 	#[command(about = "Upgrade to the latest stable version")]
 	Upgrade(UpgradeCommandArguments),
+	#[command(about = "Show a live dashboard of the activity of an existing server")]
+	Top(TopCommandArguments),
 	#[command(about = "Start an SQL REPL in your terminal with pipe support")]
 	Sql(SqlCommandArguments),
 	#[command(subcommand, about = "Manage SurrealML models within an existing database")]
 	Ml(MlCommand),
+	#[command(subcommand, about = "Generate test datasets within an existing database")]
+	Fixtures(FixturesCommand),
 	#[command(
 		about = "Check if the SurrealDB server is ready to accept connections",
 		visible_alias = "isready"
@@ -87,6 +105,8 @@ enum Commands {
 	IsReady(IsReadyCommandArguments),
 	#[command(about = "Validate SurrealQL query files")]
 	Validate(ValidateCommandArguments),
+	#[command(subcommand, about = "Check the integrity of the data within an existing database")]
+	Check(CheckCommand),
 }
 
 pub async fn init() -> ExitCode {
@@ -122,14 +142,20 @@ pub async fn init() -> ExitCode {
 	// After version warning we can run the respective command
 	let output = match args.command {
 		Commands::Start(args) => start::init(args).await,
+		Commands::Backup(args) => backup::init(args).await,
 		Commands::Import(args) => import::init(args).await,
 		Commands::Export(args) => export::init(args).await,
+		Commands::Replicate(args) => replicate::init(args).await,
+		Commands::Replay(args) => replay::init(args).await,
 		Commands::Version(args) => version::init(args).await,
 		Commands::Upgrade(args) => upgrade::init(args).await,
+		Commands::Top(args) => top::init(args).await,
 		Commands::Sql(args) => sql::init(args).await,
 		Commands::Ml(args) => ml::init(args).await,
+		Commands::Fixtures(args) => fixtures::init(args).await,
 		Commands::IsReady(args) => isready::init(args).await,
 		Commands::Validate(args) => validate::init(args).await,
+		Commands::Check(args) => check::init(args).await,
 	};
 	// Save the flamegraph and profile
 	#[cfg(feature = "performance-profiler")]
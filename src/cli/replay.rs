@@ -0,0 +1,81 @@
+use crate::err::Error;
+use clap::Args;
+use std::path::PathBuf;
+use surrealdb::dbs::capabilities::Capabilities;
+use surrealdb::dbs::{Capture, Captured};
+use surrealdb::kvs::Datastore;
+
+#[derive(Args, Debug)]
+pub struct ReplayCommandArguments {
+	#[arg(help = "The capture file, or directory of capture files, to replay")]
+	#[arg(value_parser = super::validator::path_exists)]
+	capture: PathBuf,
+	#[arg(help = "Only replay the queries which were executed by this session")]
+	#[arg(long = "session")]
+	session: Option<String>,
+	#[arg(help = "Database path used for replaying the queries")]
+	#[arg(env = "SURREAL_PATH", long = "path")]
+	#[arg(default_value = "memory")]
+	#[arg(value_parser = super::validator::path_valid)]
+	path: String,
+}
+
+pub async fn init(
+	ReplayCommandArguments {
+		capture,
+		session,
+		path,
+	}: ReplayCommandArguments,
+) -> Result<(), Error> {
+	// Initialize opentelemetry and logging
+	crate::telemetry::builder().with_log_level("error").init();
+	// Read the captured queries
+	let mut caps = Capture::read(&capture)?;
+	if let Some(id) = &session {
+		caps.retain(|c| c.id.as_ref() == Some(id));
+	}
+	// Replay the queries against a fresh datastore
+	let ds = Datastore::new(&path).await?.with_capabilities(Capabilities::all());
+	ds.bootstrap().await?;
+	let mut differed = 0;
+	for (i, cap) in caps.iter().enumerate() {
+		let res = Captured::outcomes(&ds.replay(cap).await?);
+		println!("-- Query {} ({})", i + 1, location(cap));
+		println!("{}", cap.sql);
+		for (j, out) in res.iter().enumerate() {
+			match out {
+				Ok(v) => println!("-- Result {}: {v}", j + 1),
+				Err(e) => println!("-- Error {}: {e}", j + 1),
+			}
+		}
+		// Compare the results with those which were captured
+		if let Some(expected) = &cap.results {
+			if expected != &res {
+				differed += 1;
+				println!("-- Differs from the captured results:");
+				for (j, out) in expected.iter().enumerate() {
+					match out {
+						Ok(v) => println!("-- Captured result {}: {v}", j + 1),
+						Err(e) => println!("-- Captured error {}: {e}", j + 1),
+					}
+				}
+			}
+		}
+	}
+	println!("-- Replayed {} queries, {differed} of which differed from the capture", caps.len());
+	match differed {
+		0 => Ok(()),
+		_ => Err(Error::Other(format!(
+			"{differed} replayed queries did not reproduce the captured results"
+		))),
+	}
+}
+
+/// Describe the namespace and database which a query was executed in
+fn location(cap: &Captured) -> String {
+	match (&cap.ns, &cap.db) {
+		(Some(ns), Some(db)) => format!("{ns}/{db}"),
+		(Some(ns), None) => ns.to_string(),
+		_ => String::from("no namespace"),
+	}
+}
@@ -0,0 +1,545 @@
+use crate::cli::abstraction::auth::CredentialsBuilder;
+use crate::err::Error;
+use clap::{Args, ValueEnum};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use surrealdb::engine::any::{connect, Any};
+use surrealdb::sql::{Array, Id, Thing, Value};
+use surrealdb::Surreal;
+
+/// The id of the record which stores the replication progress of a database
+const CURSOR: &str = "cursor";
+
+#[derive(Args, Debug)]
+pub struct ReplicateCommandArguments {
+	#[arg(help = "Remote database server url to read the change feeds from")]
+	#[arg(env = "SURREAL_REPLICATE_SOURCE", long = "source")]
+	#[arg(value_parser = super::validator::endpoint_valid)]
+	source: String,
+	#[arg(help = "Root username to use when connecting to the source")]
+	#[arg(env = "SURREAL_REPLICATE_SOURCE_USER", long = "source-username")]
+	#[arg(visible_alias = "source-user", requires = "source_password")]
+	source_username: Option<String>,
+	#[arg(help = "Root password to use when connecting to the source")]
+	#[arg(env = "SURREAL_REPLICATE_SOURCE_PASS", long = "source-password")]
+	#[arg(visible_alias = "source-pass", requires = "source_username")]
+	source_password: Option<String>,
+	#[arg(help = "Remote database server url to apply the changes to")]
+	#[arg(env = "SURREAL_REPLICATE_TARGET", long = "target")]
+	#[arg(value_parser = super::validator::endpoint_valid)]
+	target: String,
+	#[arg(help = "Root username to use when connecting to the target")]
+	#[arg(env = "SURREAL_REPLICATE_TARGET_USER", long = "target-username")]
+	#[arg(visible_alias = "target-user", requires = "target_password")]
+	target_username: Option<String>,
+	#[arg(help = "Root password to use when connecting to the target")]
+	#[arg(env = "SURREAL_REPLICATE_TARGET_PASS", long = "target-password")]
+	#[arg(visible_alias = "target-pass", requires = "target_username")]
+	target_password: Option<String>,
+	#[arg(help = "The databases to replicate, as comma separated namespace/database pairs")]
+	#[arg(env = "SURREAL_REPLICATE_DATABASES", long = "databases", visible_alias = "dbs")]
+	#[arg(required = true, value_delimiter = ',')]
+	databases: Vec<Selection>,
+	#[arg(help = "How to resolve changes which are older than the target record")]
+	#[arg(env = "SURREAL_REPLICATE_CONFLICT", long = "conflict")]
+	#[arg(default_value = "last-writer-wins")]
+	conflict: ConflictPolicy,
+	#[arg(help = "The table in each target database which stores the replication state")]
+	#[arg(env = "SURREAL_REPLICATE_STATE_TABLE", long = "state-table")]
+	#[arg(default_value = "_replication")]
+	state_table: String,
+	#[arg(help = "The maximum number of change feed entries applied in each transaction")]
+	#[arg(env = "SURREAL_REPLICATE_BATCH_SIZE", long = "batch-size")]
+	#[arg(default_value_t = 1000)]
+	#[arg(value_parser = clap::value_parser!(u32).range(1..))]
+	batch_size: u32,
+	#[arg(help = "The interval at which the change feeds are polled for new changes")]
+	#[arg(env = "SURREAL_REPLICATE_INTERVAL", long = "interval")]
+	#[arg(default_value = "1s")]
+	#[arg(value_parser = super::validator::duration)]
+	interval: Duration,
+	#[arg(help = "Exit once every database has caught up with the source")]
+	#[arg(long = "once")]
+	once: bool,
+}
+
+/// A namespace and database which is replicated
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+struct Selection {
+	ns: String,
+	db: String,
+}
+
+impl FromStr for Selection {
+	type Err = String;
+	fn from_str(v: &str) -> Result<Self, Self::Err> {
+		match v.split_once('/') {
+			Some((ns, db)) if !ns.is_empty() && !db.is_empty() && !db.contains('/') => Ok(Self {
+				ns: ns.to_owned(),
+				db: db.to_owned(),
+			}),
+			_ => Err(format!("invalid database '{v}', expected 'namespace/database'")),
+		}
+	}
+}
+
+impl fmt::Display for Selection {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}/{}", self.ns, self.db)
+	}
+}
+
+/// How a change is applied when the target record was
+/// last written by a change with a later versionstamp
+#[derive(ValueEnum, Clone, Copy, Debug, Eq, PartialEq)]
+enum ConflictPolicy {
+	/// Skip changes which are older than the last change applied to the record
+	LastWriterWins,
+	/// Apply every change, in the order in which they were read
+	Overwrite,
+}
+
+pub async fn init(
+	ReplicateCommandArguments {
+		source,
+		source_username,
+		source_password,
+		target,
+		target_username,
+		target_password,
+		databases,
+		conflict,
+		state_table,
+		batch_size,
+		interval,
+		once,
+	}: ReplicateCommandArguments,
+) -> Result<(), Error> {
+	// Initialize opentelemetry and logging
+	crate::telemetry::builder().with_log_level("info").init();
+	// Connect to both of the clusters
+	let source = connect_client(&source, source_username, source_password).await?;
+	let target = connect_client(&target, target_username, target_password).await?;
+	let mut relay = Relay {
+		source,
+		target,
+		state: state_table,
+		batch: batch_size,
+		conflict,
+		progress: BTreeMap::new(),
+	};
+	info!("Replicating {} databases", databases.len());
+	loop {
+		// Replicate each database in turn
+		let mut behind = false;
+		let mut failed = false;
+		for sel in databases.iter() {
+			match relay.poll(sel).await {
+				Ok(caught_up) => behind |= !caught_up,
+				Err(e) if once => return Err(e),
+				Err(e) => {
+					warn!("Failed to replicate database '{sel}': {e}");
+					failed = true;
+				}
+			}
+		}
+		// Exit once all of the changes have been applied
+		if once && !behind {
+			info!("Every database has caught up with the source");
+			return Ok(());
+		}
+		// Continue immediately if there are more changes to read
+		if behind && !failed {
+			continue;
+		}
+		tokio::select! {
+			_ = tokio::time::sleep(interval) => (),
+			_ = tokio::signal::ctrl_c() => {
+				info!("Stopping replication");
+				return Ok(());
+			}
+		}
+	}
+}
+
+/// Connect to a cluster, signing in as a root user if credentials are specified
+async fn connect_client(
+	endpoint: &str,
+	username: Option<String>,
+	password: Option<String>,
+) -> Result<Surreal<Any>, Error> {
+	let client = connect(endpoint).await?;
+	if let (Some(username), Some(password)) = (username, password) {
+		debug!("Signing in to the database engine at '{endpoint}'");
+		let creds = CredentialsBuilder::default()
+			.with_username(Some(username.as_str()))
+			.with_password(Some(password.as_str()));
+		client.signin(creds.root()?).await?;
+	}
+	Ok(client)
+}
+
+/// The replication progress of a database
+#[derive(Default)]
+struct Progress {
+	// The versionstamp to read the change feed from
+	since: u64,
+	// The number of changes which have been applied
+	applied: u64,
+	// When the relay started falling behind the source
+	behind: Option<Instant>,
+}
+
+impl Progress {
+	/// How far the target is behind the source
+	fn lag(&self) -> Duration {
+		self.behind.map(|t| t.elapsed()).unwrap_or_default()
+	}
+}
+
+/// A change to a record which is read from the change feed
+#[derive(Debug, PartialEq)]
+enum Change {
+	Update(Thing, Value),
+	Delete(Thing),
+}
+
+impl Change {
+	fn thing(&self) -> &Thing {
+		match self {
+			Change::Update(thing, _) | Change::Delete(thing) => thing,
+		}
+	}
+}
+
+/// A set of changes which were committed together in the source
+#[derive(Debug, PartialEq)]
+struct ChangeSet {
+	versionstamp: u128,
+	// The tables which are changed, which each use an entry of the change feed
+	tables: BTreeSet<String>,
+	changes: Vec<Change>,
+}
+
+impl ChangeSet {
+	/// The versionstamp which `SHOW CHANGES ... SINCE` uses to read this change set
+	fn since(&self) -> u64 {
+		(self.versionstamp >> 16) as u64
+	}
+}
+
+impl TryFrom<Value> for ChangeSet {
+	type Error = Error;
+	fn try_from(value: Value) -> Result<Self, Self::Error> {
+		let invalid = || Error::Other(format!("Received an invalid change set: {value}"));
+		let Value::Object(set) = &value else {
+			return Err(invalid());
+		};
+		let versionstamp = match set.get("versionstamp") {
+			Some(Value::Number(n)) if n.is_int() && n.to_int() >= 0 => n.to_int() as u128,
+			_ => return Err(invalid()),
+		};
+		let Some(Value::Array(changes)) = set.get("changes") else {
+			return Err(invalid());
+		};
+		let mut out = ChangeSet {
+			versionstamp,
+			tables: BTreeSet::new(),
+			changes: Vec::new(),
+		};
+		for change in changes.iter() {
+			let Value::Object(change) = change else {
+				return Err(invalid());
+			};
+			// Tables with original values store the record in `current`
+			let update = change.get("current").or(change.get("create")).or(change.get("update"));
+			let change = match (update, change.get("delete"), change.get("define_table")) {
+				(Some(v), _, _) => match v.pick(&["id".into()]) {
+					Value::Thing(thing) => Change::Update(thing, v.clone()),
+					_ => return Err(invalid()),
+				},
+				(_, Some(v), _) => match v.pick(&["id".into()]) {
+					Value::Thing(thing) => Change::Delete(thing),
+					_ => return Err(invalid()),
+				},
+				(_, _, Some(v)) => {
+					// Definitions are not replicated, so the schema
+					// must be applied to the target before replicating
+					debug!("Skipping the definition of table {}", v.pick(&["name".into()]));
+					out.tables.insert(v.pick(&["name".into()]).as_raw_string());
+					continue;
+				}
+				_ => return Err(invalid()),
+			};
+			out.tables.insert(change.thing().tb.clone());
+			out.changes.push(change);
+		}
+		Ok(out)
+	}
+}
+
+/// Tails the change feeds of the source and applies them to the target
+struct Relay {
+	source: Surreal<Any>,
+	target: Surreal<Any>,
+	state: String,
+	batch: u32,
+	conflict: ConflictPolicy,
+	progress: BTreeMap<Selection, Progress>,
+}
+
+impl Relay {
+	/// Apply the next batch of changes to a database,
+	/// returning whether the target has caught up
+	async fn poll(&mut self, sel: &Selection) -> Result<bool, Error> {
+		self.source.use_ns(&sel.ns).use_db(&sel.db).await?;
+		self.target.use_ns(&sel.ns).use_db(&sel.db).await?;
+		// Load the progress which is stored in the target
+		if !self.progress.contains_key(sel) {
+			let progress = self.load().await?;
+			info!("Replicating database '{sel}' from versionstamp {}", progress.since);
+			self.progress.insert(sel.clone(), progress);
+		}
+		let since = self.progress[sel].since;
+		// Read the next batch of changes from the source
+		let mut limit = self.batch;
+		let sets = loop {
+			let sets = self.read(since, limit).await?;
+			// Read a change set which changes more tables than the limit in full
+			match sets.len() == 1 && entries(&sets) >= limit as usize {
+				true => limit = limit.saturating_mul(2),
+				false => break sets,
+			}
+		};
+		let (sets, next, caught_up) = plan(sets, since, limit);
+		let progress = self.progress.get_mut(sel).unwrap();
+		// Keep track of how long the target has been behind
+		match caught_up {
+			true => progress.behind = None,
+			false => {
+				progress.behind.get_or_insert_with(Instant::now);
+			}
+		}
+		if sets.is_empty() && next == since {
+			return Ok(caught_up);
+		}
+		// Apply the changes and store the progress in the same transaction
+		let lag = progress.lag();
+		let applied = self.apply(&sets, next, lag).await?;
+		let progress = self.progress.get_mut(sel).unwrap();
+		progress.since = next;
+		progress.applied += applied;
+		info!(
+			"Applied {applied} changes to database '{sel}', up to versionstamp {next}, with a lag of {}",
+			surrealdb::sql::Duration::from(lag)
+		);
+		Ok(caught_up)
+	}
+
+	/// Read change sets from the change feed of the selected source database
+	async fn read(&self, since: u64, limit: u32) -> Result<Vec<ChangeSet>, Error> {
+		let mut res = self
+			.source
+			.query(format!("SHOW CHANGES FOR DATABASE SINCE {since} LIMIT {limit}"))
+			.await?;
+		match res.take::<Value>(0)? {
+			Value::Array(sets) => sets.into_iter().map(ChangeSet::try_from).collect(),
+			v => Err(Error::Other(format!("Received invalid changes: {v}"))),
+		}
+	}
+
+	/// Load the replication progress of the selected target database
+	async fn load(&self) -> Result<Progress, Error> {
+		let cursor = Thing::from((self.state.as_str(), CURSOR));
+		let mut res = self.target.query(format!("SELECT * FROM ONLY {cursor}")).await?;
+		let since: Option<u64> = res.take((0, "since"))?;
+		let applied: Option<u64> = res.take((0, "applied"))?;
+		Ok(Progress {
+			since: since.unwrap_or_default(),
+			applied: applied.unwrap_or_default(),
+			behind: None,
+		})
+	}
+
+	/// The record which stores the versionstamp of the last change applied to a record
+	fn state(&self, thing: &Thing) -> Thing {
+		Thing::from((self.state.as_str(), Id::from(vec![Value::from(thing.clone())])))
+	}
+
+	/// Get the versionstamps of the last changes which were applied to the records
+	async fn versionstamps(&self, sets: &[ChangeSet]) -> Result<BTreeMap<String, u128>, Error> {
+		let things: BTreeMap<String, &Thing> = sets
+			.iter()
+			.flat_map(|s| s.changes.iter().map(Change::thing))
+			.map(|t| (t.to_string(), t))
+			.collect();
+		if things.is_empty() || self.conflict == ConflictPolicy::Overwrite {
+			return Ok(BTreeMap::new());
+		}
+		let ids: Array = things.into_values().map(|t| Value::from(self.state(t))).collect();
+		let mut res = self.target.query(format!("SELECT id, versionstamp FROM {ids}")).await?;
+		let mut out = BTreeMap::new();
+		let Value::Array(states) = res.take::<Value>(0)? else {
+			return Ok(out);
+		};
+		for state in states {
+			let Value::Object(state) = state else {
+				continue;
+			};
+			if let (Some(Value::Thing(id)), Some(Value::Number(vs))) =
+				(state.get("id"), state.get("versionstamp"))
+			{
+				if let Id::Array(id) = &id.id {
+					if let Some(Value::Thing(thing)) = id.first() {
+						out.insert(thing.to_string(), vs.to_int() as u128);
+					}
+				}
+			}
+		}
+		Ok(out)
+	}
+
+	/// Apply change sets to the target, returning the number of changes applied
+	async fn apply(&self, sets: &[ChangeSet], next: u64, lag: Duration) -> Result<u64, Error> {
+		let mut applied = self.versionstamps(sets).await?;
+		let mut count = 0;
+		let mut sql = String::from("BEGIN TRANSACTION;\n");
+		for set in sets {
+			let vs = set.versionstamp;
+			for change in set.changes.iter() {
+				let thing = change.thing();
+				// Skip changes which have been superseded by a later change
+				if applied.get(&thing.to_string()).is_some_and(|last| *last > vs) {
+					debug!("Skipping change to {thing} at versionstamp {vs}");
+					continue;
+				}
+				match change {
+					Change::Update(thing, value) => {
+						sql.push_str(&format!("UPDATE {thing} CONTENT {value} RETURN NONE;\n"))
+					}
+					Change::Delete(thing) => sql.push_str(&format!("DELETE {thing};\n")),
+				}
+				// Deleted records keep their versionstamp, so older changes are not reapplied
+				let state = self.state(thing);
+				sql.push_str(&format!("UPDATE {state} SET versionstamp = {vs} RETURN NONE;\n"));
+				applied.insert(thing.to_string(), vs);
+				count += 1;
+			}
+		}
+		// Store the progress, and how far the target is behind the source
+		let cursor = Thing::from((self.state.as_str(), CURSOR));
+		let lag = surrealdb::sql::Duration::from(lag);
+		sql.push_str(&format!(
+			"UPDATE {cursor} SET since = {next}, applied += {count}, lag = {lag}, updated = time::now() RETURN NONE;\n"
+		));
+		sql.push_str("COMMIT TRANSACTION;\n");
+		self.target.query(sql).await?.check()?;
+		Ok(count)
+	}
+}
+
+/// The number of change feed entries which were read, as each table
+/// changed in a change set is a separate entry in the change feed
+fn entries(sets: &[ChangeSet]) -> usize {
+	sets.iter().map(|s| s.tables.len()).sum()
+}
+
+/// Decide which of the change sets which were read can be applied,
+/// returning them with the versionstamp to read from next time, and
+/// whether every change in the source has now been read.
+fn plan(mut sets: Vec<ChangeSet>, since: u64, limit: u32) -> (Vec<ChangeSet>, u64, bool) {
+	match sets.last() {
+		// Nothing has changed
+		None => (sets, since, true),
+		// Every change has been read
+		Some(s) if entries(&sets) < limit as usize => {
+			let next = s.since() + 1;
+			(sets, next, true)
+		}
+		// The last change set may not have been read in full, so read it again next time
+		Some(s) if sets.len() > 1 => {
+			let next = s.since();
+			sets.pop();
+			(sets, next, false)
+		}
+		// A single change set which was read in full
+		Some(s) => {
+			let next = s.since() + 1;
+			(sets, next, false)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use surrealdb::sql::value;
+
+	fn set(vs: u64, changes: &str) -> ChangeSet {
+		let v = value(&format!("{{ versionstamp: {}, changes: {changes} }}", (vs as u128) << 16));
+		ChangeSet::try_from(v.unwrap()).unwrap()
+	}
+
+	#[test]
+	fn parse_selection() {
+		let sel: Selection = "test/prod".parse().unwrap();
+		assert_eq!(sel.ns, "test");
+		assert_eq!(sel.db, "prod");
+		assert!("test".parse::<Selection>().is_err());
+		assert!("test/".parse::<Selection>().is_err());
+		assert!("a/b/c".parse::<Selection>().is_err());
+	}
+
+	#[test]
+	fn parse_change_set() {
+		let set = set(
+			3,
+			"[
+				{ define_table: { name: 'person' } },
+				{ update: { id: person:one, name: 'one' } },
+				{ current: { id: person:two, name: 'two' }, update: [] },
+				{ delete: { id: thing:three } }
+			]",
+		);
+		assert_eq!(set.since(), 3);
+		assert_eq!(set.tables.len(), 2);
+		assert_eq!(
+			set.changes,
+			vec![
+				Change::Update(
+					Thing::from(("person", "one")),
+					value("{ id: person:one, name: 'one' }").unwrap()
+				),
+				Change::Update(
+					Thing::from(("person", "two")),
+					value("{ id: person:two, name: 'two' }").unwrap()
+				),
+				Change::Delete(Thing::from(("thing", "three"))),
+			]
+		);
+		assert!(ChangeSet::try_from(value("{ changes: [] }").unwrap()).is_err());
+		assert!(ChangeSet::try_from(
+			value("{ versionstamp: 1, changes: [{ update: { name: 'one' } }] }").unwrap()
+		)
+		.is_err());
+	}
+
+	#[test]
+	fn plan_batches() {
+		let one = "[{ update: { id: a:1 } }]";
+		let two = "[{ update: { id: a:1 } }, { update: { id: b:1 } }]";
+		// Nothing has changed
+		assert_eq!(plan(vec![], 5, 10), (vec![], 5, true));
+		// Every change has been read
+		let (sets, next, done) = plan(vec![set(5, one), set(7, one)], 5, 10);
+		assert_eq!((sets.len(), next, done), (2, 8, true));
+		// The last change set may be incomplete
+		let (sets, next, done) = plan(vec![set(5, two), set(7, two)], 5, 4);
+		assert_eq!((sets.len(), next, done), (1, 7, false));
+		// A single change set which fills the limit
+		let (sets, next, done) = plan(vec![set(5, two)], 5, 2);
+		assert_eq!((sets.len(), next, done), (1, 6, false));
+	}
+}
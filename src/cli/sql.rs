@@ -2,7 +2,7 @@ use crate::cli::abstraction::auth::{CredentialsBuilder, CredentialsLevel};
 use crate::cli::abstraction::{
 	AuthArguments, DatabaseConnectionArguments, LevelSelectionArguments,
 };
-use crate::cnf::PKG_VERSION;
+use crate::cnf::{PKG_VERSION, SQL_RECONNECT_ATTEMPTS, SQL_RECONNECT_INTERVAL};
 use crate::err::Error;
 use clap::Args;
 use futures::StreamExt;
@@ -11,12 +11,15 @@ use rustyline::validate::{ValidationContext, ValidationResult, Validator};
 use rustyline::{Completer, Editor, Helper, Highlighter, Hinter};
 use serde::Serialize;
 use serde_json::ser::PrettyFormatter;
+use std::collections::BTreeMap;
 use surrealdb::dbs::Capabilities;
-use surrealdb::engine::any::{connect, IntoEndpoint};
+use surrealdb::engine::any::{connect, Any, IntoEndpoint};
+use surrealdb::error::Api;
 use surrealdb::method::{Stats, WithStats};
 use surrealdb::opt::Config;
 use surrealdb::sql::{self, Statement, Value};
-use surrealdb::{Notification, Response};
+use surrealdb::{Notification, Response, Surreal};
+use tokio::time;
 
 #[derive(Args, Debug)]
 pub struct SqlCommandArguments {
@@ -68,31 +71,33 @@ pub async fn init(
 
 	// If username and password are specified, and we are connecting to a remote SurrealDB server, then we need to authenticate.
 	// If we are connecting directly to a datastore (i.e. file://local.db or tikv://...), then we don't need to authenticate because we use an embedded (local) SurrealDB instance with auth disabled.
-	let client = if username.is_some()
+	let creds = if username.is_some()
 		&& password.is_some()
 		&& !endpoint.clone().into_endpoint()?.parse_kind()?.is_local()
 	{
-		debug!("Connecting to the database engine with authentication");
-		let creds = CredentialsBuilder::default()
-			.with_username(username.as_deref())
-			.with_password(password.as_deref())
-			.with_namespace(namespace.as_deref())
-			.with_database(database.as_deref());
-
-		let client = connect(endpoint).await?;
-
-		debug!("Signing in to the database engine at '{:?}' level", auth_level);
-		match auth_level {
-			CredentialsLevel::Root => client.signin(creds.root()?).await?,
-			CredentialsLevel::Namespace => client.signin(creds.namespace()?).await?,
-			CredentialsLevel::Database => client.signin(creds.database()?).await?,
-		};
-
-		client
+		Some(
+			CredentialsBuilder::default()
+				.with_username(username.as_deref())
+				.with_password(password.as_deref())
+				.with_namespace(namespace.as_deref())
+				.with_database(database.as_deref()),
+		)
 	} else {
-		debug!("Connecting to the database engine without authentication");
-		connect((endpoint, config)).await?
+		None
 	};
+	let client = match &creds {
+		Some(creds) => {
+			debug!("Connecting to the database engine with authentication");
+			let client = connect(endpoint).await?;
+			signin(&client, &auth_level, creds.clone()).await?;
+			client
+		}
+		None => {
+			debug!("Connecting to the database engine without authentication");
+			connect((endpoint, config)).await?
+		}
+	};
+	let creds = creds.as_ref().map(|creds| (&auth_level, creds));
 
 	// Create a new terminal REPL
 	let mut rl = Editor::new().unwrap();
@@ -104,6 +109,8 @@ pub async fn init(
 	let _ = rl.load_history("history.txt");
 	// Configure the prompt
 	let mut prompt = "> ".to_owned();
+	// Keep track of the variables defined in this session
+	let mut session_vars = BTreeMap::new();
 
 	// Keep track of current namespace/database.
 	let is_not_empty = |s: &&str| !s.is_empty();
@@ -211,7 +218,26 @@ pub async fn init(
 					continue;
 				}
 				// Run the query provided
-				let result = client.query(query).with_stats().await;
+				let mut result = client.query(query.clone()).with_stats().await;
+				// Re-establish the session if the server was restarted
+				if let Err(e) = &result {
+					if is_disconnected(&client, e).await {
+						match reconnect(&client, creds, &prompt, &session_vars).await {
+							Ok(()) => {
+								eprintln!("-- Reconnected to the server\n");
+								// Only run the query again if it could not have modified any data
+								if query.writeable() {
+									eprintln!("-- The query was not run again, as it may have already modified data\n");
+								} else {
+									result = client.query(query).with_stats().await;
+								}
+							}
+							Err(e) => {
+								eprintln!("Failed to reconnect to the server: {e}\n");
+							}
+						}
+					}
+				}
 				let result = process(pretty, json, result);
 				let result_is_error = result.is_err();
 				print(result);
@@ -220,7 +246,9 @@ pub async fn init(
 				}
 				// Persist the variables extracted from the query
 				for (key, value) in vars {
-					let _ = client.set(key, value).await;
+					if client.set(key.clone(), &value).await.is_ok() {
+						session_vars.insert(key, value);
+					}
 				}
 				// Process the last `use` statements, if any
 				if namespace.is_some() || database.is_some() {
@@ -251,6 +279,72 @@ pub async fn init(
 	Ok(())
 }
 
+/// Sign in to the database engine at the specified authentication level
+async fn signin(
+	client: &Surreal<Any>,
+	level: &CredentialsLevel,
+	creds: CredentialsBuilder<'_>,
+) -> Result<(), Error> {
+	debug!("Signing in to the database engine at '{:?}' level", level);
+	match level {
+		CredentialsLevel::Root => client.signin(creds.root()?).await?,
+		CredentialsLevel::Namespace => client.signin(creds.namespace()?).await?,
+		CredentialsLevel::Database => client.signin(creds.database()?).await?,
+	};
+	Ok(())
+}
+
+/// Check whether a query failed because the connection to the server was lost
+async fn is_disconnected(client: &Surreal<Any>, error: &surrealdb::Error) -> bool {
+	match error {
+		// The WebSocket connection was closed before a response was received
+		surrealdb::Error::Api(Api::Ws(_) | Api::InternalError(_)) => true,
+		// Failed HTTP queries return the same error, so check the server is reachable
+		surrealdb::Error::Api(Api::Http(_)) => client.health().await.is_err(),
+		_ => false,
+	}
+}
+
+/// Wait for the server to become available, and re-establish the session
+async fn reconnect(
+	client: &Surreal<Any>,
+	creds: Option<(&CredentialsLevel, &CredentialsBuilder<'_>)>,
+	prompt: &str,
+	vars: &BTreeMap<String, Value>,
+) -> Result<(), Error> {
+	eprintln!("-- The connection to the server was lost, reconnecting...");
+	// Wait until the server responds to health checks
+	let mut attempts = 0;
+	loop {
+		attempts += 1;
+		match time::timeout(SQL_RECONNECT_INTERVAL, client.health()).await {
+			Ok(Ok(())) => break,
+			_ if attempts >= *SQL_RECONNECT_ATTEMPTS => {
+				return Err(Error::Other(format!(
+					"the server was not reachable after {attempts} attempts"
+				)));
+			}
+			Ok(Err(_)) => time::sleep(SQL_RECONNECT_INTERVAL).await,
+			Err(_) => {}
+		}
+	}
+	// Sign in again, as the session does not survive a restart
+	if let Some((level, creds)) = creds {
+		signin(client, level, creds.clone()).await?;
+	}
+	// Select the namespace and database shown in the prompt
+	match split_prompt(prompt) {
+		("", _) => {}
+		(namespace, "") => client.use_ns(namespace).await?,
+		(namespace, database) => client.use_ns(namespace).use_db(database).await?,
+	}
+	// Define the session variables again
+	for (key, value) in vars {
+		client.set(key, value).await?;
+	}
+	Ok(())
+}
+
 fn process(
 	pretty: bool,
 	json: bool,
@@ -8,6 +8,9 @@ use crate::dbs::{StartCommandDbsOptions, DB};
 use crate::env;
 use crate::err::Error;
 use crate::net::{self, client_ip::ClientIp};
+use crate::rpc;
+use crate::rt::{self, StartCommandRuntimeOptions};
+use crate::telemetry::logs::LogFormat;
 use clap::Args;
 use opentelemetry::Context as TelemetryContext;
 use std::net::SocketAddr;
@@ -15,6 +18,8 @@ use std::path::PathBuf;
 use std::time::Duration;
 use surrealdb::engine::any::IntoEndpoint;
 use surrealdb::engine::tasks::start_tasks;
+use surrealdb::options::EngineOptions;
+use surrealdb::rpc::signature::Verifier;
 use tokio_util::sync::CancellationToken;
 
 #[derive(Args, Debug)]
@@ -29,6 +34,10 @@ pub struct StartCommandArguments {
 	#[arg(default_value = "info")]
 	#[arg(value_parser = CustomEnvFilterParser::new())]
 	log: CustomEnvFilter,
+	#[arg(help = "The format of the log output")]
+	#[arg(env = "SURREAL_LOG_FORMAT", long = "log-format")]
+	#[arg(default_value = "text", value_enum)]
+	log_format: LogFormat,
 	#[arg(help = "Whether to hide the startup banner")]
 	#[arg(env = "SURREAL_NO_BANNER", long)]
 	#[arg(default_value_t = false)]
@@ -47,6 +56,22 @@ pub struct StartCommandArguments {
 	#[arg(default_value = "10s")]
 	tick_interval: Duration,
 
+	#[arg(
+		help = "The interval at which this node writes its heartbeat to the cluster",
+		help_heading = "Database"
+	)]
+	#[arg(env = "SURREAL_HEARTBEAT_INTERVAL", long = "heartbeat-interval", value_parser = super::validator::duration)]
+	#[arg(default_value = "1s")]
+	heartbeat_interval: Duration,
+
+	#[cfg(feature = "failpoints")]
+	#[arg(
+		help = "A list of failpoints to inject into the storage engine, for resilience testing",
+		help_heading = "Database"
+	)]
+	#[arg(env = "SURREAL_FAILPOINTS", long = "failpoints")]
+	failpoints: Option<String>,
+
 	//
 	// Authentication
 	//
@@ -96,6 +121,15 @@ pub struct StartCommandArguments {
 	#[arg(env = "SURREAL_BIND", short = 'b', long = "bind")]
 	#[arg(default_value = "0.0.0.0:8000")]
 	listen_addresses: Vec<SocketAddr>,
+	#[arg(help = "A shared key which every RPC request and response must be signed with")]
+	#[arg(env = "SURREAL_RPC_SIGNING_KEY", long = "rpc-signing-key", hide_env_values = true)]
+	rpc_signing_key: Option<String>,
+	#[arg(
+		help = "How far the time at which an RPC request was signed can be from the time on the server"
+	)]
+	#[arg(env = "SURREAL_RPC_SIGNING_WINDOW", long = "rpc-signing-window", value_parser = super::validator::duration)]
+	#[arg(default_value = "30s")]
+	rpc_signing_window: Duration,
 
 	//
 	// Database options
@@ -103,6 +137,13 @@ pub struct StartCommandArguments {
 	#[command(flatten)]
 	#[command(next_help_heading = "Database")]
 	dbs: StartCommandDbsOptions,
+
+	//
+	// Runtime options
+	//
+	#[command(flatten)]
+	#[command(next_help_heading = "Runtime")]
+	runtime: StartCommandRuntimeOptions,
 }
 
 #[derive(Args, Debug)]
@@ -137,16 +178,23 @@ pub async fn init(
 		password: pass,
 		client_ip,
 		listen_addresses,
+		rpc_signing_key,
+		rpc_signing_window,
 		dbs,
+		runtime,
 		web,
 		log,
+		log_format,
 		tick_interval,
+		heartbeat_interval,
 		no_banner,
+		#[cfg(feature = "failpoints")]
+		failpoints,
 		..
 	}: StartCommandArguments,
 ) -> Result<(), Error> {
 	// Initialize opentelemetry and logging
-	crate::telemetry::builder().with_filter(log).init();
+	crate::telemetry::builder().with_filter(log).with_log_format(log_format).init();
 	// Start metrics subsystem
 	crate::telemetry::metrics::init(&TelemetryContext::current())
 		.expect("failed to initialize metrics");
@@ -163,6 +211,9 @@ pub async fn init(
 	} else {
 		endpoint.path
 	};
+	// Setup the engine options
+	let mut engine = EngineOptions::default();
+	engine.heartbeat_interval = heartbeat_interval;
 	// Setup the cli options
 	let _ = config::CF.set(Config {
 		bind: listen_addresses.first().cloned().unwrap(),
@@ -173,20 +224,35 @@ pub async fn init(
 		tick_interval,
 		crt: web.as_ref().and_then(|x| x.web_crt.clone()),
 		key: web.as_ref().and_then(|x| x.web_key.clone()),
-		engine: None,
+		engine: Some(engine),
 	});
 	// This is the cancellation token propagated down to
 	// all the async functions that needs to be stopped gracefully.
 	let ct = CancellationToken::new();
 	// Initiate environment
 	env::init().await?;
+	// Start the runtimes for queries and background jobs
+	rt::init(runtime)?;
+	// Configure any storage engine failpoints
+	#[cfg(feature = "failpoints")]
+	if let Some(failpoints) = failpoints {
+		warn!("Storage engine failpoints are enabled. Do not use this in production!");
+		surrealdb::kvs::fail::cfg_list(&failpoints)?;
+	}
 	// Start the kvs server
 	dbs::init(dbs).await?;
 	// Start the node agent
-	let (tasks, task_chans) = start_tasks(
-		&config::CF.get().unwrap().engine.unwrap_or_default(),
-		DB.get().unwrap().clone(),
-	);
+	let (tasks, task_chans) = rt::background(|| {
+		start_tasks(
+			&config::CF.get().unwrap().engine.unwrap_or_default(),
+			DB.get().unwrap().clone(),
+		)
+	});
+	// Require RPC requests to be signed, and sign the responses
+	if let Some(key) = rpc_signing_key {
+		info!("RPC messages are signed, and replayed requests are rejected");
+		let _ = rpc::SIGNATURES.set(Verifier::new(key, rpc_signing_window));
+	}
 	// Start the web server
 	net::init(ct.clone()).await?;
 	// Shutdown and stop closed tasks
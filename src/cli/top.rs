@@ -0,0 +1,152 @@
+use crate::cli::abstraction::auth::CredentialsBuilder;
+use crate::cli::abstraction::DatabaseConnectionArguments;
+use crate::err::Error;
+use clap::Args;
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+use surrealdb::engine::any::connect;
+use surrealdb::sql::Value;
+use tokio::io::{self, AsyncWriteExt};
+
+/// The statements which sample the activity of the server
+const SAMPLE: &str = "
+	RETURN system::activity();
+	SELECT * FROM system::queries() ORDER BY elapsed DESC LIMIT 10;
+	RETURN count(system::live_queries());
+	SELECT kind, ns, db, tb, conflicts FROM system::hot_keys() LIMIT 5;
+";
+
+/// Clears the terminal, and moves the cursor to the top left corner
+const CLEAR: &str = "\x1b[2J\x1b[H";
+
+#[derive(Args, Debug)]
+pub struct TopCommandArguments {
+	#[command(flatten)]
+	conn: DatabaseConnectionArguments,
+	#[arg(help = "Root username to use when connecting")]
+	#[arg(env = "SURREAL_USER", short = 'u', long = "username", visible_alias = "user")]
+	#[arg(requires = "password")]
+	username: Option<String>,
+	#[arg(help = "Root password to use when connecting")]
+	#[arg(env = "SURREAL_PASS", short = 'p', long = "password", visible_alias = "pass")]
+	#[arg(requires = "username")]
+	password: Option<String>,
+	#[arg(help = "The interval at which the activity of the server is sampled")]
+	#[arg(env = "SURREAL_TOP_INTERVAL", long = "interval")]
+	#[arg(default_value = "1s")]
+	#[arg(value_parser = super::validator::duration)]
+	interval: Duration,
+}
+
+pub async fn init(
+	TopCommandArguments {
+		conn: DatabaseConnectionArguments {
+			endpoint,
+		},
+		username,
+		password,
+		interval,
+	}: TopCommandArguments,
+) -> Result<(), Error> {
+	// Initialize opentelemetry and logging
+	crate::telemetry::builder().with_log_level("error").init();
+	// Connect to the server, signing in as a root user if credentials are specified
+	let client = connect(&endpoint).await?;
+	if let (Some(username), Some(password)) = (username, password) {
+		debug!("Signing in to the database engine at '{endpoint}'");
+		let creds = CredentialsBuilder::default()
+			.with_username(Some(username.as_str()))
+			.with_password(Some(password.as_str()));
+		client.signin(creds.root()?).await?;
+	}
+	let mut stdout = io::stdout();
+	let mut last: Option<(Instant, u64)> = None;
+	loop {
+		let mut res = client.query(SAMPLE).await?;
+		let now = Instant::now();
+		let activity = res.take::<Value>(0)?;
+		let queries = res.take::<Value>(1)?;
+		let live = res.take::<Value>(2)?;
+		let hot = res.take::<Value>(3)?;
+		// The rate of queries since the last sample, without the sampling query itself
+		let total = count(&activity, "queries");
+		let qps = match last {
+			Some((at, prev)) => {
+				let secs = now.duration_since(at).as_secs_f64();
+				total.saturating_sub(prev).saturating_sub(1) as f64 / secs
+			}
+			None => 0.0,
+		};
+		last = Some((now, total));
+		// Render the dashboard
+		let mut out = String::from(CLEAR);
+		let _ = writeln!(out, "surreal top - {endpoint} - every {interval:?}\n");
+		let _ = writeln!(out, "Queries      {qps:>10.1}/s {total:>12} total");
+		let _ = writeln!(
+			out,
+			"Transactions {:>10} open {:>12} total",
+			count(&activity, "open_transactions"),
+			count(&activity, "transactions")
+		);
+		let _ = writeln!(out, "Connections  {:>10} open", count(&activity, "open_connections"));
+		let _ = writeln!(out, "Live queries {:>10}", live.to_string());
+		let _ = writeln!(out, "Conflicts    {:>10} total", count(&activity, "conflicts"));
+		let _ = writeln!(out, "Read latency {:>10}", field(&activity, "read_latency").to_string());
+		let _ = writeln!(out, "\nActive queries ({} open)", count(&activity, "open_queries"));
+		for q in rows(&queries) {
+			let text = field(q, "text").as_raw_string().replace(char::is_whitespace, " ");
+			let _ = writeln!(
+				out,
+				"  {:>12}  {:<10}  {}/{}  {}",
+				field(q, "elapsed").to_string(),
+				field(q, "state").as_raw_string(),
+				field(q, "ns").as_raw_string(),
+				field(q, "db").as_raw_string(),
+				text.chars().take(80).collect::<String>(),
+			);
+		}
+		let _ = writeln!(out, "\nConflicting keys");
+		for k in rows(&hot) {
+			let _ = writeln!(
+				out,
+				"  {:>8}  {:<8}  {}/{}/{}",
+				count(k, "conflicts"),
+				field(k, "kind").as_raw_string(),
+				field(k, "ns").as_raw_string(),
+				field(k, "db").as_raw_string(),
+				field(k, "tb").as_raw_string(),
+			);
+		}
+		stdout.write_all(out.as_bytes()).await?;
+		stdout.flush().await?;
+		// Wait for the next sample, or stop on Ctrl-C
+		tokio::select! {
+			_ = tokio::time::sleep(interval) => (),
+			_ = tokio::signal::ctrl_c() => return Ok(()),
+		}
+	}
+}
+
+/// A field of an object, or none if the value is not an object
+fn field(v: &Value, name: &str) -> Value {
+	match v {
+		Value::Object(o) => o.get(name).cloned().unwrap_or_default(),
+		_ => Value::None,
+	}
+}
+
+/// A numeric field of an object, or zero if it is missing
+fn count(v: &Value, name: &str) -> u64 {
+	match field(v, name) {
+		Value::Number(n) => n.to_int().max(0) as u64,
+		_ => 0,
+	}
+}
+
+/// The rows which were returned by a statement
+fn rows(v: &Value) -> &[Value] {
+	match v {
+		Value::Array(a) => &a.0,
+		_ => &[],
+	}
+}
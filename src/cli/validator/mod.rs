@@ -81,10 +81,36 @@ pub(crate) fn key_valid(v: &str) -> Result<String, String> {
 	}
 }
 
+pub(crate) fn database(v: &str) -> Result<(String, String), String> {
+	match v.split_once('/') {
+		Some((ns, db)) if !ns.is_empty() && !db.is_empty() && !db.contains('/') => {
+			Ok((ns.to_owned(), db.to_owned()))
+		}
+		_ => Err(String::from("Provide a namespace and database such as ns/db")),
+	}
+}
+
 pub(crate) fn duration(v: &str) -> Result<Duration, String> {
 	surrealdb::sql::Duration::from_str(v).map(|d| d.0).map_err(|_| String::from("invalid duration"))
 }
 
+pub(crate) fn size(v: &str) -> Result<u64, String> {
+	let v = v.trim().to_ascii_lowercase();
+	let (num, unit) = v.split_at(v.find(|c: char| !c.is_ascii_digit()).unwrap_or(v.len()));
+	let mul: u64 = match unit.trim() {
+		"" | "b" => 1,
+		"kb" => 1 << 10,
+		"mb" => 1 << 20,
+		"gb" => 1 << 30,
+		"tb" => 1 << 40,
+		_ => return Err(String::from("Provide a size such as 100mb or 1gb")),
+	};
+	num.parse::<u64>()
+		.ok()
+		.and_then(|n| n.checked_mul(mul))
+		.ok_or_else(|| String::from("Provide a size such as 100mb or 1gb"))
+}
+
 pub(crate) fn net_targets(value: &str) -> Result<Targets<NetTarget>, String> {
 	if ["*", ""].contains(&value) {
 		return Ok(Targets::All);
@@ -133,6 +159,16 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn test_size() {
+		assert_eq!(size("512").unwrap(), 512);
+		assert_eq!(size("10kb").unwrap(), 10 * 1024);
+		assert_eq!(size("100MB").unwrap(), 100 * 1024 * 1024);
+		assert_eq!(size("1 gb").unwrap(), 1024 * 1024 * 1024);
+		assert!(size("gb").is_err());
+		assert!(size("1pb").is_err());
+	}
+
 	#[test]
 	fn test_net_targets() {
 		assert_eq!(net_targets("*").unwrap(), Targets::<NetTarget>::All);
@@ -43,6 +43,25 @@ pub static WEBSOCKET_MAX_MESSAGE_SIZE: Lazy<usize> =
 pub static WEBSOCKET_MAX_CONCURRENT_REQUESTS: Lazy<usize> =
 	lazy_env_parse!("SURREAL_WEBSOCKET_MAX_CONCURRENT_REQUESTS", usize, 24);
 
+/// The maximum size of a request body which can be imported over HTTP (defaults to 4 GiB)
+pub static HTTP_MAX_IMPORT_SIZE: Lazy<usize> =
+	lazy_env_parse!("SURREAL_HTTP_MAX_IMPORT_SIZE", usize, 4 << 30);
+
+/// The maximum duration in seconds of a query on the read-only SQL endpoint (defaults to 30)
+pub static HTTP_READONLY_TIMEOUT: Lazy<u64> =
+	lazy_env_parse!("SURREAL_HTTP_READONLY_TIMEOUT", u64, 30);
+
+/// The maximum number of rows which each statement can return on the read-only SQL endpoint (defaults to 10000)
+pub static HTTP_READONLY_ROW_LIMIT: Lazy<usize> =
+	lazy_env_parse!("SURREAL_HTTP_READONLY_ROW_LIMIT", usize, 10_000);
+
+/// How many times the SQL shell attempts to reconnect to a restarted server (defaults to 30)
+pub static SQL_RECONNECT_ATTEMPTS: Lazy<u32> =
+	lazy_env_parse!("SURREAL_SQL_RECONNECT_ATTEMPTS", u32, 30);
+
+/// How long the SQL shell waits between attempts to reconnect to the server
+pub const SQL_RECONNECT_INTERVAL: Duration = Duration::from_secs(1);
+
 /// What is the runtime thread memory stack size (defaults to 10MiB)
 pub static RUNTIME_STACK_SIZE: Lazy<usize> =
 	lazy_env_parse_or_else!("SURREAL_RUNTIME_STACK_SIZE", usize, |_| {
@@ -54,6 +73,12 @@ pub static RUNTIME_STACK_SIZE: Lazy<usize> =
 		}
 	});
 
+/// How many worker threads the main runtime starts, for network IO and any work without its own runtime (defaults to the number of CPUs)
+pub static RUNTIME_WORKER_THREADS: Lazy<usize> =
+	lazy_env_parse_or_else!("SURREAL_RUNTIME_WORKER_THREADS", usize, |_| {
+		std::thread::available_parallelism().map_or(1, |n| n.get())
+	});
+
 /// How many threads which can be started for blocking operations (defaults to 512)
 pub static RUNTIME_MAX_BLOCKING_THREADS: Lazy<usize> =
 	lazy_env_parse!("SURREAL_RUNTIME_MAX_BLOCKING_THREADS", usize, 512);
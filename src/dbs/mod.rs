@@ -1,18 +1,13 @@
 use crate::cli::CF;
 use crate::err::Error;
 use clap::Args;
-#[cfg(any(
-	feature = "storage-surrealkv",
-	feature = "storage-rocksdb",
-	feature = "storage-fdb",
-	feature = "storage-tikv",
-	feature = "storage-speedb"
-))]
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, OnceLock};
 use std::time::Duration;
 use surrealdb::dbs::capabilities::{Capabilities, FuncTarget, NetTarget, Targets};
+use surrealdb::dbs::Capture;
 use surrealdb::kvs::Datastore;
+use surrealdb::sql::Uuid;
 
 pub static DB: OnceLock<Arc<Datastore>> = OnceLock::new();
 
@@ -56,6 +51,36 @@ pub struct StartCommandDbsOptions {
 	#[arg(env = "SURREAL_TEMPORARY_DIRECTORY", long = "temporary-directory")]
 	#[arg(value_parser = super::cli::validator::dir_exists)]
 	temporary_directory: Option<PathBuf>,
+	#[arg(help = "Record the executed queries in this directory, so that they can be replayed")]
+	#[arg(env = "SURREAL_CAPTURE", long = "capture")]
+	capture: Option<PathBuf>,
+	#[arg(help = "Check the integrity of the datastore before serving any traffic")]
+	#[arg(env = "SURREAL_CHECK", long = "check")]
+	#[arg(default_value_t = false)]
+	check: bool,
+	#[arg(help = "Repair any problems which are found by the integrity check, where possible")]
+	#[arg(env = "SURREAL_CHECK_REPAIR", long = "repair", requires = "check")]
+	#[arg(default_value_t = false)]
+	repair: bool,
+	#[arg(
+		help = "Serve traffic even if the integrity check finds problems which were not repaired"
+	)]
+	#[arg(env = "SURREAL_CHECK_FORCE", long = "force", requires = "check")]
+	#[arg(default_value_t = false)]
+	force: bool,
+	#[arg(help = "Preload the definitions of these databases, such as ns/db, at startup")]
+	#[arg(env = "SURREAL_PRELOAD", long = "preload", value_delimiter = ',')]
+	#[arg(value_parser = super::cli::validator::database)]
+	preload: Vec<(String, String)>,
+	#[arg(help = "Also read the keys of the indexes of the preloaded databases")]
+	#[arg(env = "SURREAL_PRELOAD_INDEXES", long = "preload-indexes", requires = "preload")]
+	#[arg(default_value_t = false)]
+	preload_indexes: bool,
+	#[arg(
+		help = "Persist the node id in this file, so that the node keeps its id when it restarts"
+	)]
+	#[arg(env = "SURREAL_NODE_STATE", long = "node-state")]
+	node_state: Option<PathBuf>,
 }
 
 #[derive(Args, Debug)]
@@ -241,6 +266,13 @@ pub async fn init(
 			feature = "storage-speedb"
 		))]
 		temporary_directory,
+		capture,
+		check,
+		repair,
+		force,
+		preload,
+		preload_indexes,
+		node_state,
 	}: StartCommandDbsOptions,
 ) -> Result<(), Error> {
 	// Get local copy of options
@@ -270,6 +302,15 @@ pub async fn init(
 	let caps = caps.into();
 	debug!("Server capabilities: {caps}");
 
+	// Log whether queries are captured
+	let capture = match capture {
+		Some(dir) => {
+			warn!("Capturing executed queries and their parameters to {}", dir.display());
+			Some(Capture::new(&dir)?)
+		}
+		None => None,
+	};
+
 	#[allow(unused_mut)]
 	// Parse and setup the desired kv datastore
 	let mut dbs = Datastore::new(&opt.path)
@@ -280,7 +321,8 @@ pub async fn init(
 		.with_transaction_timeout(transaction_timeout)
 		.with_auth_enabled(auth_enabled)
 		.with_auth_level_enabled(auth_level_enabled)
-		.with_capabilities(caps);
+		.with_capabilities(caps)
+		.with_capture(capture);
 	#[cfg(any(
 		feature = "storage-surrealkv",
 		feature = "storage-rocksdb",
@@ -292,15 +334,30 @@ pub async fn init(
 	if let Some(engine_options) = opt.engine {
 		dbs = dbs.with_engine_options(engine_options);
 	}
+	// Reuse the node id of the previous process, so that it rejoins the cluster as the same node
+	if let Some(path) = node_state {
+		dbs = dbs.with_node_id(node_id(&path).await?);
+	}
 	// Make immutable
 	let dbs = dbs;
 
+	// Check the integrity of the datastore before it is bootstrapped
+	if check {
+		integrity(&dbs, repair, force).await?;
+	}
+
 	dbs.bootstrap().await?;
 
 	if let Some(user) = opt.user.as_ref() {
 		dbs.setup_initial_creds(user, opt.pass.as_ref().unwrap()).await?;
 	}
 
+	// Warm the caches for the databases which will be queried first
+	for (ns, db) in preload.iter() {
+		let res = dbs.preload(ns, db, preload_indexes).await?;
+		info!("Preloaded {res} for {ns}/{db}");
+	}
+
 	// Store database instance
 	let _ = DB.set(Arc::new(dbs));
 
@@ -308,6 +365,59 @@ pub async fn init(
 	Ok(())
 }
 
+/// Read the node id which was persisted by a previous process, or generate and persist a new one
+async fn node_id(path: &Path) -> Result<Uuid, Error> {
+	match tokio::fs::read_to_string(path).await {
+		Ok(v) => {
+			let id = Uuid::try_from(v.trim()).map_err(|_| {
+				Error::Other(format!(
+					"The node state file {} is not a valid node id",
+					path.display()
+				))
+			})?;
+			info!("Restarting as node {}, as persisted in {}", id.0, path.display());
+			Ok(id)
+		}
+		Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+			// A random id does not conflict with the id of any other node
+			let id = Uuid::new_v4();
+			// Write the file atomically, so that a crash does not leave a partial id
+			let tmp = path.with_extension("tmp");
+			tokio::fs::write(&tmp, id.0.to_string()).await?;
+			tokio::fs::rename(&tmp, path).await?;
+			info!("Starting as new node {}, persisted in {}", id.0, path.display());
+			Ok(id)
+		}
+		Err(e) => Err(e.into()),
+	}
+}
+
+/// Check the integrity of the datastore, refusing to start if any problems remain, unless forced
+async fn integrity(dbs: &Datastore, repair: bool, force: bool) -> Result<(), Error> {
+	info!("Checking the integrity of the datastore");
+	let issues = dbs.check_integrity(repair).await?;
+	for issue in issues.iter() {
+		match issue.repaired {
+			true => info!("Repaired an integrity problem with {issue}"),
+			false => error!("Found an integrity problem with {issue}"),
+		}
+	}
+	let remaining = issues.iter().filter(|i| !i.repaired).count();
+	match remaining {
+		0 => {
+			info!("The integrity check passed");
+			Ok(())
+		}
+		n if force => {
+			warn!("Serving traffic with {n} integrity problems, as --force was specified");
+			Ok(())
+		}
+		n => Err(Error::Other(format!(
+			"The integrity check found {n} problems. Repair them with --repair, or serve traffic anyway with --force"
+		))),
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use std::str::FromStr;
@@ -4,13 +4,13 @@ use axum::response::{IntoResponse, Response};
 use axum::Error as AxumError;
 use axum::Json;
 use base64::DecodeError as Base64Error;
-use http::{HeaderName, StatusCode};
+use http::{header, HeaderName, StatusCode};
 use reqwest::Error as ReqwestError;
 use serde::Serialize;
+use std::collections::BTreeMap;
 use std::io::Error as IoError;
 use std::string::FromUtf8Error as Utf8Error;
-use surrealdb::error::Db as SurrealDbError;
-use surrealdb::iam::Error as SurrealIamError;
+use surrealdb::error::Kind;
 use surrealdb::Error as SurrealError;
 use thiserror::Error;
 
@@ -64,9 +64,24 @@ pub enum Error {
 	#[error("There was an error with auth: {0}")]
 	Auth(#[from] SurrealAuthError),
 
+	#[error("There was a problem with the log filter: {0}")]
+	InvalidLogFilter(String),
+
+	#[error("The Last-Event-ID header is not a valid versionstamp")]
+	InvalidLastEventId,
+
 	#[error("There was an error with the node agent")]
 	NodeAgent,
 
+	#[error("The requested API version '{0}' is not supported")]
+	InvalidVersion(String),
+
+	#[error("The request body exceeds the maximum size of {0} bytes")]
+	BodyTooLarge(usize),
+
+	#[error("There was a problem with the multipart request body: {0}")]
+	Multipart(String),
+
 	/// Statement has been deprecated
 	#[error("{0}")]
 	Other(String),
@@ -126,6 +141,19 @@ impl<T: std::fmt::Debug> From<ciborium::ser::Error<T>> for Error {
 	}
 }
 
+impl From<multer::Error> for Error {
+	fn from(e: multer::Error) -> Error {
+		match e {
+			// The body may have been stopped as it was too large
+			multer::Error::StreamReadFailed(e) => match e.downcast::<Error>() {
+				Ok(e) => *e,
+				Err(e) => Error::Multipart(e.to_string()),
+			},
+			e => Error::Multipart(e.to_string()),
+		}
+	}
+}
+
 impl From<surrealdb::error::Db> for Error {
 	fn from(error: surrealdb::error::Db) -> Error {
 		if matches!(error, surrealdb::error::Db::InvalidAuth) {
@@ -163,56 +191,156 @@ pub(super) struct Message {
 	description: Option<String>,
 	#[serde(skip_serializing_if = "Option::is_none")]
 	information: Option<String>,
+	// The problem details fields, as specified in RFC 7807
+	title: String,
+	status: u16,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	detail: Option<String>,
+	// The stable kind of the error, and any structured details about it
+	kind: &'static str,
+	#[serde(skip_serializing_if = "BTreeMap::is_empty")]
+	data: BTreeMap<&'static str, String>,
+}
+
+impl Message {
+	fn new(
+		status: StatusCode,
+		details: &str,
+		description: &str,
+		information: Option<String>,
+		kind: Kind,
+		data: BTreeMap<&'static str, String>,
+	) -> Self {
+		Self {
+			code: status.as_u16(),
+			details: Some(details.to_string()),
+			description: Some(description.to_string()),
+			information: information.clone(),
+			title: details.to_string(),
+			status: status.as_u16(),
+			detail: information,
+			kind: kind.as_str(),
+			data,
+		}
+	}
+}
+
+impl Error {
+	/// The stable kind of this error
+	fn kind(&self) -> Kind {
+		match self {
+			Error::InvalidAuth => Kind::Auth,
+			Error::BodyTooLarge(_) => Kind::Quota,
+			Error::Db(SurrealError::Db(e)) => e.kind(),
+			_ => Kind::Other,
+		}
+	}
 }
 
 impl IntoResponse for Error {
 	fn into_response(self) -> Response {
-		match self {
-			err @ Error::InvalidAuth | err @ Error::Db(SurrealError::Db(SurrealDbError::InvalidAuth)) => (
-				StatusCode::UNAUTHORIZED,
-				Json(Message {
-					code: StatusCode::UNAUTHORIZED.as_u16(),
-					details: Some("Authentication failed".to_string()),
-					description: Some("Your authentication details are invalid. Reauthenticate using valid authentication parameters.".to_string()),
-					information: Some(err.to_string()),
-				})
-			),
-			err @ Error::Db(SurrealError::Db(SurrealDbError::IamError(SurrealIamError::NotAllowed { .. }))) => (
-				StatusCode::FORBIDDEN,
-				Json(Message {
-					code: StatusCode::FORBIDDEN.as_u16(),
-					details: Some("Forbidden".to_string()),
-					description: Some("Not allowed to do this.".to_string()),
-					information: Some(err.to_string()),
-				})
-			),
+		let kind = self.kind();
+		let data = match &self {
+			Error::Db(SurrealError::Db(e)) => e.details(),
+			_ => BTreeMap::new(),
+		};
+		let (status, details, description, information) = match self {
 			Error::InvalidType => (
 				StatusCode::UNSUPPORTED_MEDIA_TYPE,
-				Json(Message {
-					code: StatusCode::UNSUPPORTED_MEDIA_TYPE.as_u16(),
-					details: Some("Unsupported media type".to_string()),
-					description: Some("The request needs to adhere to certain constraints. Refer to the documentation for supported content types.".to_string()),
-					information: None,
-				}),
+				"Unsupported media type",
+				"The request needs to adhere to certain constraints. Refer to the documentation for supported content types.",
+				None,
+			),
+			Error::InvalidVersion(_) => (
+				StatusCode::NOT_ACCEPTABLE,
+				"Unsupported API version",
+				"The requested version of the HTTP API is not supported by this server. Refer to the /capabilities endpoint for the supported versions.",
+				Some(self.to_string()),
+			),
+			Error::BodyTooLarge(_) => (
+				StatusCode::PAYLOAD_TOO_LARGE,
+				"Request body too large",
+				"The request body exceeds the size limit of this endpoint. Split the data into smaller requests, or increase the limit with the SURREAL_HTTP_MAX_IMPORT_SIZE environment variable.",
+				Some(self.to_string()),
+			),
+			Error::Db(SurrealError::Db(surrealdb::error::Db::PatchTest {
+				..
+			})) => (
+				StatusCode::CONFLICT,
+				"Patch test failed",
+				"A test operation in the JSON Patch did not match the current value of the record, so no changes were made.",
+				Some(self.to_string()),
+			),
+			Error::Db(SurrealError::Db(surrealdb::error::Db::InvalidPatch {
+				..
+			})) => (
+				StatusCode::UNPROCESSABLE_ENTITY,
+				"Invalid patch",
+				"The JSON Patch document could not be applied. Refer to RFC 6902 for the supported operations.",
+				Some(self.to_string()),
 			),
 			Error::InvalidStorage => (
 				StatusCode::INTERNAL_SERVER_ERROR,
-				Json(Message {
-					code: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
-					details: Some("Health check failed".to_string()),
-					description: Some("The database health check for this instance failed. There was an issue with the underlying storage engine.".to_string()),
-					information: Some(self.to_string()),
-				}),
-			),
-			_ => (
-				StatusCode::BAD_REQUEST,
-				Json(Message {
-					code: StatusCode::BAD_REQUEST.as_u16(),
-					details: Some("Request problems detected".to_string()),
-					description: Some("There is a problem with your request. Refer to the documentation for further information.".to_string()),
-					information: Some(self.to_string()),
-				}),
+				"Health check failed",
+				"The database health check for this instance failed. There was an issue with the underlying storage engine.",
+				Some(self.to_string()),
 			),
-		}.into_response()
+			err => match kind {
+				Kind::Auth => (
+					StatusCode::UNAUTHORIZED,
+					"Authentication failed",
+					"Your authentication details are invalid. Reauthenticate using valid authentication parameters.",
+					Some(err.to_string()),
+				),
+				Kind::Permission => (
+					StatusCode::FORBIDDEN,
+					"Forbidden",
+					"Not allowed to do this.",
+					Some(err.to_string()),
+				),
+				Kind::NotFound => (
+					StatusCode::NOT_FOUND,
+					"Not found",
+					"The requested resource does not exist.",
+					Some(err.to_string()),
+				),
+				Kind::Conflict => (
+					StatusCode::CONFLICT,
+					"Transaction conflict",
+					"The transaction conflicted with another concurrent transaction. The request can be retried.",
+					Some(err.to_string()),
+				),
+				Kind::Constraint => (
+					StatusCode::UNPROCESSABLE_ENTITY,
+					"Constraint violation",
+					"The data does not satisfy the constraints defined on the table.",
+					Some(err.to_string()),
+				),
+				Kind::Timeout => (
+					StatusCode::GATEWAY_TIMEOUT,
+					"Request timed out",
+					"The request did not complete within the configured timeout.",
+					Some(err.to_string()),
+				),
+				Kind::Quota => (
+					StatusCode::PAYLOAD_TOO_LARGE,
+					"Limit exceeded",
+					"The request exceeded a size or computation limit.",
+					Some(err.to_string()),
+				),
+				_ => (
+					StatusCode::BAD_REQUEST,
+					"Request problems detected",
+					"There is a problem with your request. Refer to the documentation for further information.",
+					Some(err.to_string()),
+				),
+			},
+		};
+		(
+			status,
+			[(header::CONTENT_TYPE, "application/problem+json")],
+			Json(Message::new(status, details, description, information, kind, data)),
+		)
+			.into_response()
 	}
 }
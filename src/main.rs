@@ -24,6 +24,7 @@ mod err;
 mod mem;
 mod net;
 mod rpc;
+mod rt;
 mod telemetry;
 
 use std::future::Future;
@@ -39,6 +40,7 @@ fn with_enough_stack<T>(fut: impl Future<Output = T> + Send) -> T {
 	// Start a Tokio runtime with custom configuration
 	tokio::runtime::Builder::new_multi_thread()
 		.enable_all()
+		.worker_threads(*cnf::RUNTIME_WORKER_THREADS)
 		.max_blocking_threads(*cnf::RUNTIME_MAX_BLOCKING_THREADS)
 		.thread_stack_size(*cnf::RUNTIME_STACK_SIZE)
 		.thread_name("surrealdb-worker")
@@ -0,0 +1,28 @@
+use super::output;
+use crate::dbs::DB;
+use crate::err::Error;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::{Extension, Router};
+use http_body::Body as HttpBody;
+use surrealdb::dbs::Session;
+use surrealdb::sql::Value;
+
+pub(super) fn router<S, B>() -> Router<S, B>
+where
+	B: HttpBody + Send + 'static,
+	S: Clone + Send + Sync + 'static,
+{
+	Router::new().route("/admin/databases", get(databases))
+}
+
+/// Lists the databases which the user is allowed to view, with their
+/// comments and labels, and the number and stored size of their records
+async fn databases(Extension(session): Extension<Session>) -> Result<impl IntoResponse, Error> {
+	// Get the datastore reference
+	let db = DB.get().unwrap();
+	// List the databases
+	let res = db.databases(&session).await?;
+	// Output the databases
+	Ok(output::json(&Value::from(res).into_json()))
+}
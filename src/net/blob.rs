@@ -0,0 +1,150 @@
+//! This file defines the endpoints for uploading and downloading content addressed blobs.
+use crate::dbs::DB;
+use crate::err::Error;
+use crate::net::output;
+use axum::extract::{BodyStream, DefaultBodyLimit, Path};
+use axum::response::IntoResponse;
+use axum::response::Response;
+use axum::routing::{get, post};
+use axum::Extension;
+use axum::Router;
+use bytes::Bytes;
+use futures_util::StreamExt;
+use http::header::{CONTENT_LENGTH, CONTENT_TYPE, ETAG};
+use http::StatusCode;
+use http_body::Body as HttpBody;
+use hyper::body::{Body, Sender};
+use surrealdb::dbs::Session;
+use surrealdb::error::Db as DbError;
+use surrealdb::iam::check::check_ns_db;
+use surrealdb::iam::Action::{Edit, View};
+use surrealdb::iam::ResourceKind::Any;
+use surrealdb::kvs::blob;
+use surrealdb::kvs::{LockType::Optimistic, TransactionType::Read, TransactionType::Write};
+use tower_http::limit::RequestBodyLimitLayer;
+
+const MAX: usize = 1024 * 1024 * 100; // 100 MiB
+
+/// The router definition for the blob endpoints.
+pub(super) fn router<S, B>() -> Router<S, B>
+where
+	B: HttpBody + Send + 'static,
+	B::Data: Send + Into<Bytes>,
+	B::Error: std::error::Error + Send + Sync + 'static,
+	S: Clone + Send + Sync + 'static,
+{
+	Router::new()
+		.route("/blob", post(upload))
+		.route("/blob/:id", get(download))
+		.route_layer(DefaultBodyLimit::disable())
+		.layer(RequestBodyLimitLayer::new(MAX))
+}
+
+/// This endpoint stores the request body as a blob, and returns the id of the blob.
+async fn upload(
+	Extension(session): Extension<Session>,
+	mut stream: BodyStream,
+) -> Result<impl IntoResponse, impl IntoResponse> {
+	// Get the datastore reference
+	let db = DB.get().unwrap();
+	// Ensure a NS and DB are set
+	let (nsv, dbv) = check_ns_db(&session)?;
+	// Check the permissions level
+	db.check(&session, Edit, Any.on_db(&nsv, &dbv))?;
+	// Load all the uploaded chunks
+	let mut buffer = Vec::new();
+	while let Some(chunk) = stream.next().await {
+		buffer.extend_from_slice(&chunk?);
+	}
+	// Store the blob
+	let mut tx = db.transaction(Write, Optimistic).await?;
+	let id = match tx.put_blob(&nsv, &dbv, &buffer).await {
+		Ok(id) => id,
+		Err(e) => {
+			tx.cancel().await?;
+			return Err(Error::from(e));
+		}
+	};
+	tx.commit().await?;
+	//
+	Ok(output::text(id))
+}
+
+/// This endpoint streams the content of a blob.
+async fn download(
+	Extension(session): Extension<Session>,
+	Path(id): Path<String>,
+) -> Result<impl IntoResponse, Error> {
+	// Get the datastore reference
+	let db = DB.get().unwrap();
+	// Ensure a NS and DB are set
+	let (nsv, dbv) = check_ns_db(&session)?;
+	// Check the permissions level
+	db.check(&session, View, Any.on_db(&nsv, &dbv))?;
+	// Start a new readonly transaction
+	let mut tx = db.transaction(Read, Optimistic).await?;
+	// Attempt to get the blob definition
+	let info = match blob::is_id(&id) {
+		true => tx.get_blob(&nsv, &dbv, &id).await?,
+		false => None,
+	};
+	let Some(info) = info else {
+		tx.cancel().await?;
+		return Err(Error::from(DbError::BlNotFound {
+			value: id,
+		}));
+	};
+	// The content of a blob never changes
+	let etag = format!("\"{id}\"");
+	// Create a chunked response
+	let (mut chn, body) = Body::channel();
+	if info.external {
+		tx.cancel().await?;
+		// Stream the content from the object store
+		send_object(blob::path(&nsv, &dbv, &id), chn).await?;
+	} else {
+		// Stream the chunks from the datastore
+		tokio::spawn(async move {
+			for ch in 0..info.chunks {
+				let v = match tx.get_blob_chunk(&nsv, &dbv, &id, ch).await {
+					Ok(v) => v,
+					Err(_) => {
+						chn.abort();
+						break;
+					}
+				};
+				if chn.send_data(Bytes::from(v)).await.is_err() {
+					break;
+				}
+			}
+			let _ = tx.cancel().await;
+		});
+	}
+	// Return the streamed body
+	Ok(Response::builder()
+		.status(StatusCode::OK)
+		.header(CONTENT_TYPE, "application/octet-stream")
+		.header(CONTENT_LENGTH, info.size)
+		.header(ETAG, etag)
+		.body(body)
+		.unwrap())
+}
+
+/// Streams the content of a blob from the object store to the response body
+#[cfg(feature = "obs")]
+async fn send_object(path: String, mut chn: Sender) -> Result<(), Error> {
+	let mut data = surrealdb::obs::stream(path).await?;
+	tokio::spawn(async move {
+		while let Some(Ok(v)) = data.next().await {
+			if chn.send_data(v).await.is_err() {
+				break;
+			}
+		}
+	});
+	Ok(())
+}
+
+#[cfg(not(feature = "obs"))]
+async fn send_object(_: String, _: Sender) -> Result<(), Error> {
+	Err(Error::from(DbError::ObsDisabled))
+}
@@ -0,0 +1,57 @@
+use super::output;
+use super::version::API_VERSIONS;
+use crate::cnf::PKG_NAME;
+use crate::cnf::PKG_VERSION;
+use crate::dbs::DB;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::Router;
+use http_body::Body as HttpBody;
+use serde_json::json;
+use surrealdb::rpc::format::PROTOCOLS;
+use surrealdb::rpc::method::Method;
+
+/// The media types which can be requested from the HTTP endpoints
+const FORMATS: &[&str] = &[
+	"text/plain",
+	"application/json",
+	"application/cbor",
+	"application/pack",
+	"application/surrealdb",
+];
+
+pub(super) fn router<S, B>() -> Router<S, B>
+where
+	B: HttpBody + Send + 'static,
+	S: Clone + Send + Sync + 'static,
+{
+	Router::new().route("/capabilities", get(handler))
+}
+
+/// Describe the features supported by this server, so that
+/// clients can detect them instead of relying on the version
+async fn handler() -> impl IntoResponse {
+	// Get the datastore reference
+	let db = DB.get().unwrap();
+	// Get the allowed capabilities
+	let caps = db.capabilities();
+	// Output the server capabilities
+	output::json(&json!({
+		"version": format!("{PKG_NAME}-{}", *PKG_VERSION),
+		"api_versions": API_VERSIONS,
+		"rpc": {
+			"methods": Method::ALL.iter().map(Method::to_str).collect::<Vec<_>>(),
+			"formats": PROTOCOLS,
+		},
+		"http": {
+			"formats": FORMATS,
+		},
+		"features": {
+			"live_queries": caps.allows_live_query_notifications(),
+			"scripting": caps.allows_scripting(),
+			"guest_access": caps.allows_guest_access(),
+			"backup": true,
+			"ml": cfg!(feature = "ml"),
+		},
+	}))
+}
@@ -0,0 +1,81 @@
+use crate::dbs::DB;
+use crate::err::Error;
+use axum::extract::Path;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::{Extension, Router};
+use axum_extra::extract::Query;
+use futures::stream;
+use http::HeaderMap;
+use http_body::Body as HttpBody;
+use serde::Deserialize;
+use std::collections::VecDeque;
+use std::convert::Infallible;
+use std::time::Duration;
+use surrealdb::dbs::Session;
+
+/// The maximum number of change feed entries which are read at once
+const LIMIT: u32 = 100;
+
+/// The interval at which the change feed is polled for new changes
+const INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Default, Deserialize, Debug, Clone)]
+struct QueryOptions {
+	pub since: Option<u64>,
+}
+
+pub(super) fn router<S, B>() -> Router<S, B>
+where
+	B: HttpBody + Send + 'static,
+	S: Clone + Send + Sync + 'static,
+{
+	Router::new().route("/changes/:table", get(handler))
+}
+
+/// Streams the changes to a table as Server-Sent Events, with each event containing
+/// a Debezium change event envelope, and the versionstamp of the change as its id.
+/// Clients which reconnect with a `Last-Event-ID` header resume after that change.
+async fn handler(
+	Extension(session): Extension<Session>,
+	Path(table): Path<String>,
+	Query(query): Query<QueryOptions>,
+	headers: HeaderMap,
+) -> Result<impl IntoResponse, Error> {
+	// Get the datastore reference
+	let db = DB.get().unwrap();
+	// Resume after the last event which was received
+	let since = match headers.get("Last-Event-ID").and_then(|v| v.to_str().ok()) {
+		Some(id) => id.parse::<u64>().map_err(|_| Error::InvalidLastEventId)? + 1,
+		None => query.since.unwrap_or_default(),
+	};
+	// Read the first changes, so that invalid requests fail immediately
+	let changes = db.changes(&session, &table, since, LIMIT).await?;
+	let state = (VecDeque::from(changes), Some(since));
+	let events = stream::unfold(state, move |(mut changes, mut since)| {
+		let session = session.clone();
+		let table = table.clone();
+		async move {
+			loop {
+				// Output the changes which have been read
+				if let Some((vs, v)) = changes.pop_front() {
+					since = Some(vs + 1);
+					let event = Event::default().id(vs.to_string()).data(v.into_json().to_string());
+					return Some((Ok::<_, Infallible>(event), (changes, since)));
+				}
+				// Wait for more changes, unless the stream has failed
+				let from = since?;
+				tokio::time::sleep(INTERVAL).await;
+				match DB.get().unwrap().changes(&session, &table, from, LIMIT).await {
+					Ok(v) => changes.extend(v),
+					Err(e) => {
+						let event = Event::default().event("error").data(e.to_string());
+						return Some((Ok(event), (changes, None)));
+					}
+				}
+			}
+		}
+	});
+	Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
@@ -1,49 +1,77 @@
 use super::headers::Accept;
+use crate::cnf::HTTP_MAX_IMPORT_SIZE;
 use crate::dbs::DB;
 use crate::err::Error;
-use crate::net::input::bytes_to_utf8;
 use crate::net::output;
+use axum::extract::BodyStream;
 use axum::extract::DefaultBodyLimit;
+use axum::headers::{ContentLength, ContentType};
 use axum::response::IntoResponse;
 use axum::routing::post;
 use axum::Extension;
 use axum::Router;
 use axum::TypedHeader;
 use bytes::Bytes;
+use futures::{Stream, StreamExt};
 use http_body::Body as HttpBody;
 use surrealdb::dbs::Session;
 use surrealdb::iam::Action::Edit;
 use surrealdb::iam::ResourceKind::Any;
-use tower_http::limit::RequestBodyLimitLayer;
-
-const MAX: usize = 1024 * 1024 * 1024 * 4; // 4 GiB
 
 pub(super) fn router<S, B>() -> Router<S, B>
 where
 	B: HttpBody + Send + 'static,
-	B::Data: Send,
+	B::Data: Into<Bytes> + Send,
 	B::Error: std::error::Error + Send + Sync + 'static,
 	S: Clone + Send + Sync + 'static,
 {
-	Router::new()
-		.route("/import", post(handler))
-		.route_layer(DefaultBodyLimit::disable())
-		.layer(RequestBodyLimitLayer::new(MAX))
+	Router::new().route("/import", post(handler)).route_layer(DefaultBodyLimit::disable())
 }
 
 async fn handler(
 	Extension(session): Extension<Session>,
 	accept: Option<TypedHeader<Accept>>,
-	sql: Bytes,
+	length: Option<TypedHeader<ContentLength>>,
+	kind: Option<TypedHeader<ContentType>>,
+	body: BodyStream,
 ) -> Result<impl IntoResponse, impl IntoResponse> {
 	// Get the datastore reference
 	let db = DB.get().unwrap();
-	// Convert the body to a byte slice
-	let sql = bytes_to_utf8(&sql)?;
 	// Check the permissions level
 	db.check(&session, Edit, Any.on_level(session.au.level().to_owned()))?;
-	// Execute the sql query in the database
-	match db.import(sql, &session).await {
+	// Reject a body which is too large before importing any of it
+	let max = *HTTP_MAX_IMPORT_SIZE;
+	if let Some(TypedHeader(ContentLength(len))) = length {
+		if len > max as u64 {
+			return Err(Error::BodyTooLarge(max));
+		}
+	}
+	// The results are not needed if nothing is returned
+	let keep = !matches!(accept.as_deref(), Some(Accept::ApplicationOctetStream));
+	let mut importer = db.importer(&session).with_results(keep);
+	// Import the sql as it is received
+	let body = limit(body, max);
+	match kind.and_then(|v| multer::parse_boundary(v.to_string()).ok()) {
+		// Import each of the files in a multipart upload
+		Some(boundary) => {
+			let mut multipart = multer::Multipart::new(body, boundary);
+			while let Some(mut field) = multipart.next_field().await? {
+				while let Some(chunk) = field.chunk().await? {
+					importer.push(&chunk).await?;
+				}
+				importer.complete().await?;
+			}
+		}
+		// Import the request body
+		None => {
+			let mut body = Box::pin(body);
+			while let Some(chunk) = body.next().await {
+				importer.push(&chunk?).await?;
+			}
+		}
+	}
+	// Execute the remaining statements
+	match importer.finish().await {
 		Ok(res) => match accept.as_deref() {
 			// Simple serialization
 			Some(Accept::ApplicationJson) => Ok(output::json(&output::simplify(res))),
@@ -60,3 +88,16 @@ async fn handler(
 		Err(err) => Err(Error::from(err)),
 	}
 }
+
+/// Stop reading a request body once it exceeds the maximum size
+fn limit(body: BodyStream, max: usize) -> impl Stream<Item = Result<Bytes, Error>> {
+	let mut size = 0;
+	body.map(move |chunk| {
+		let chunk = chunk?;
+		size += chunk.len();
+		match size > max {
+			true => Err(Error::BodyTooLarge(max)),
+			false => Ok(chunk),
+		}
+	})
+}
@@ -12,12 +12,13 @@ use bytes::Bytes;
 use http_body::Body as HttpBody;
 use serde::Deserialize;
 use std::str;
-use surrealdb::dbs::Session;
+use surrealdb::dbs::{Response, Session};
+use surrealdb::error::Db as DbError;
 use surrealdb::iam::check::check_ns_db;
 use surrealdb::sql::Value;
 use tower_http::limit::RequestBodyLimitLayer;
 
-use super::headers::Accept;
+use super::headers::{Accept, ContentType};
 
 const MAX: usize = 1024 * 16; // 16 KiB
 
@@ -198,6 +199,7 @@ async fn update_all(
 async fn modify_all(
 	Extension(session): Extension<Session>,
 	accept: Option<TypedHeader<Accept>>,
+	content_type: Option<TypedHeader<ContentType>>,
 	Path(table): Path<String>,
 	Query(params): Query<Params>,
 	body: Bytes,
@@ -212,7 +214,18 @@ async fn modify_all(
 	match surrealdb::sql::value(data) {
 		Ok(data) => {
 			// Specify the request statement
-			let sql = "UPDATE type::table($table) MERGE $data";
+			let (sql, data) = match content_type.as_deref() {
+				// Apply a JSON Patch document (RFC 6902)
+				Some(ContentType::ApplicationJsonPatch) => {
+					("UPDATE type::table($table) PATCH $data", data)
+				}
+				// Apply a JSON Merge Patch document (RFC 7396)
+				Some(ContentType::ApplicationMergePatch) => {
+					("UPDATE type::table($table) MERGE $data", merge_patch(data))
+				}
+				// Merge the data into the existing data
+				_ => ("UPDATE type::table($table) MERGE $data", data),
+			};
 			// Specify the request variables
 			let vars = map! {
 				String::from("table") => Value::from(table),
@@ -220,8 +233,9 @@ async fn modify_all(
 				=> params.parse()
 			};
 			// Execute the query and return the result
-			match db.execute(sql, &session, Some(vars)).await {
-				Ok(res) => match accept.as_deref() {
+			match db.execute(sql, &session, Some(vars)).await.map(check_patch) {
+				Ok(Err(err)) => Err(err),
+				Ok(Ok(res)) => match accept.as_deref() {
 					// Simple serialization
 					Some(Accept::ApplicationJson) => Ok(output::json(&output::simplify(res))),
 					Some(Accept::ApplicationCbor) => Ok(output::cbor(&output::simplify(res))),
@@ -423,6 +437,7 @@ async fn update_one(
 async fn modify_one(
 	Extension(session): Extension<Session>,
 	accept: Option<TypedHeader<Accept>>,
+	content_type: Option<TypedHeader<ContentType>>,
 	Query(params): Query<Params>,
 	Path((table, id)): Path<(String, String)>,
 	body: Bytes,
@@ -442,7 +457,18 @@ async fn modify_one(
 	match surrealdb::sql::value(data) {
 		Ok(data) => {
 			// Specify the request statement
-			let sql = "UPDATE type::thing($table, $id) MERGE $data";
+			let (sql, data) = match content_type.as_deref() {
+				// Apply a JSON Patch document (RFC 6902)
+				Some(ContentType::ApplicationJsonPatch) => {
+					("UPDATE type::thing($table, $id) PATCH $data", data)
+				}
+				// Apply a JSON Merge Patch document (RFC 7396)
+				Some(ContentType::ApplicationMergePatch) => {
+					("UPDATE type::thing($table, $id) MERGE $data", merge_patch(data))
+				}
+				// Merge the data into the existing data
+				_ => ("UPDATE type::thing($table, $id) MERGE $data", data),
+			};
 			// Specify the request variables
 			let vars = map! {
 				String::from("table") => Value::from(table),
@@ -451,8 +477,9 @@ async fn modify_one(
 				=> params.parse()
 			};
 			// Execute the query and return the result
-			match db.execute(sql, &session, Some(vars)).await {
-				Ok(res) => match accept.as_deref() {
+			match db.execute(sql, &session, Some(vars)).await.map(check_patch) {
+				Ok(Err(err)) => Err(err),
+				Ok(Ok(res)) => match accept.as_deref() {
 					// Simple serialization
 					Some(Accept::ApplicationJson) => Ok(output::json(&output::simplify(res))),
 					Some(Accept::ApplicationCbor) => Ok(output::cbor(&output::simplify(res))),
@@ -507,3 +534,42 @@ async fn delete_one(
 		Err(err) => Err(Error::from(err)),
 	}
 }
+
+/// Convert a JSON Merge Patch document into a document which can be merged,
+/// as fields which are set to null in the patch are removed from the record
+fn merge_patch(data: Value) -> Value {
+	match data {
+		Value::Object(mut v) => {
+			for v in v.values_mut() {
+				*v = match std::mem::take(v) {
+					Value::Null => Value::None,
+					v => merge_patch(v),
+				};
+			}
+			Value::Object(v)
+		}
+		v => v,
+	}
+}
+
+/// Respond with an error status when a JSON Patch could not be applied
+fn check_patch(mut res: Vec<Response>) -> Result<Vec<Response>, Error> {
+	if let [Response {
+		result:
+			Err(
+				DbError::PatchTest {
+					..
+				}
+				| DbError::InvalidPatch {
+					..
+				},
+			),
+		..
+	}] = res.as_slice()
+	{
+		if let Some(Err(err)) = res.pop().map(|v| v.result) {
+			return Err(Error::from(err));
+		}
+	}
+	Ok(res)
+}
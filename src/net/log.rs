@@ -0,0 +1,63 @@
+use crate::dbs::DB;
+use crate::err::Error;
+use crate::net::input::bytes_to_utf8;
+use crate::telemetry::logs;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::Extension;
+use axum::Router;
+use bytes::Bytes;
+use http_body::Body as HttpBody;
+use surrealdb::dbs::Session;
+use surrealdb::iam::Action::{Edit, View};
+use surrealdb::iam::ResourceKind::Any;
+
+pub(super) fn router<S, B>() -> Router<S, B>
+where
+	B: HttpBody + Send + 'static,
+	B::Data: Send,
+	B::Error: std::error::Error + Send + Sync + 'static,
+	S: Clone + Send + Sync + 'static,
+{
+	Router::new()
+		.route("/log", get(get_handler).post(add_handler).put(set_handler).delete(reset_handler))
+}
+
+/// Get the filter of the installed log layer
+fn filter() -> Result<logs::LogFilter, Error> {
+	logs::filter().ok_or_else(|| Error::InvalidLogFilter("logging is not initialised".to_owned()))
+}
+
+async fn get_handler(Extension(session): Extension<Session>) -> Result<impl IntoResponse, Error> {
+	// Check the permissions level
+	DB.get().unwrap().check(&session, View, Any.on_root())?;
+	// Output the current log filter
+	filter()?.get()
+}
+
+async fn add_handler(
+	Extension(session): Extension<Session>,
+	body: Bytes,
+) -> Result<impl IntoResponse, Error> {
+	// Check the permissions level
+	DB.get().unwrap().check(&session, Edit, Any.on_root())?;
+	// Override the log level of specific modules
+	filter()?.add(bytes_to_utf8(&body)?)
+}
+
+async fn set_handler(
+	Extension(session): Extension<Session>,
+	body: Bytes,
+) -> Result<impl IntoResponse, Error> {
+	// Check the permissions level
+	DB.get().unwrap().check(&session, Edit, Any.on_root())?;
+	// Replace the log filter
+	filter()?.set(bytes_to_utf8(&body)?)
+}
+
+async fn reset_handler(Extension(session): Extension<Session>) -> Result<impl IntoResponse, Error> {
+	// Check the permissions level
+	DB.get().unwrap().check(&session, Edit, Any.on_root())?;
+	// Restore the log filter which the server was started with
+	filter()?.reset()
+}
@@ -1,4 +1,8 @@
+mod admin;
 mod auth;
+mod blob;
+mod capabilities;
+mod changes;
 pub mod client_ip;
 mod export;
 pub(crate) mod headers;
@@ -6,6 +10,7 @@ mod health;
 mod import;
 mod input;
 mod key;
+mod log;
 pub(crate) mod output;
 mod params;
 mod rpc;
@@ -24,7 +29,7 @@ use crate::cli::CF;
 use crate::cnf;
 use crate::err::Error;
 use crate::net::signals::graceful_shutdown;
-use crate::rpc::notifications;
+use crate::rpc::{notifications, publications};
 use crate::telemetry::metrics::HttpMetricsLayer;
 use axum::response::Redirect;
 use axum::routing::get;
@@ -35,7 +40,9 @@ use http::header;
 use std::net::SocketAddr;
 use std::sync::Arc;
 use std::time::Duration;
-use surrealdb::headers::{AUTH_DB, AUTH_NS, DB, DB_LEGACY, ID, ID_LEGACY, NS, NS_LEGACY};
+use surrealdb::headers::{
+	ACCEPT_VERSION, AUTH_DB, AUTH_NS, DB, DB_LEGACY, ID, ID_LEGACY, NS, NS_LEGACY,
+};
 use tokio_util::sync::CancellationToken;
 use tower::ServiceBuilder;
 use tower_http::add_extension::AddExtensionLayer;
@@ -108,6 +115,7 @@ pub async fn init(ct: CancellationToken) -> Result<(), Error> {
 		ID.clone(),
 		AUTH_NS.clone(),
 		AUTH_DB.clone(),
+		ACCEPT_VERSION.clone(),
 		// TODO(gguillemas): Remove these headers once the legacy authentication is deprecated in v2.0.0
 		NS_LEGACY.clone(),
 		DB_LEGACY.clone(),
@@ -125,6 +133,7 @@ pub async fn init(ct: CancellationToken) -> Result<(), Error> {
 		ID.clone(),
 		AUTH_NS.clone(),
 		AUTH_DB.clone(),
+		ACCEPT_VERSION.clone(),
 		// TODO(gguillemas): Remove these headers once the legacy authentication is deprecated in v2.0.0
 		NS_LEGACY.clone(),
 		DB_LEGACY.clone(),
@@ -134,6 +143,7 @@ pub async fn init(ct: CancellationToken) -> Result<(), Error> {
 	let service = service
 		.layer(AddExtensionLayer::new(app_state))
 		.layer(middleware::from_fn(client_ip::client_ip_middleware))
+		.layer(middleware::from_fn(version::negotiate))
 		.layer(SetSensitiveRequestHeadersLayer::from_shared(Arc::clone(&headers)))
 		.layer(
 			TraceLayer::new_for_http()
@@ -163,11 +173,12 @@ pub async fn init(ct: CancellationToken) -> Result<(), Error> {
 				.max_age(Duration::from_secs(86400)),
 		);
 
-	let axum_app = Router::new()
-		// Redirect until we provide a UI
-		.route("/", get(|| async { Redirect::temporary(cnf::APP_ENDPOINT) }))
+	let api = Router::new()
 		.route("/status", get(|| async {}))
+		.merge(admin::router())
+		.merge(capabilities::router())
 		.merge(health::router())
+		.merge(blob::router())
 		.merge(export::router())
 		.merge(import::router())
 		.merge(rpc::router())
@@ -176,17 +187,29 @@ pub async fn init(ct: CancellationToken) -> Result<(), Error> {
 		.merge(sql::router())
 		.merge(signin::router())
 		.merge(signup::router())
-		.merge(key::router());
+		.merge(key::router())
+		.merge(log::router())
+		.merge(changes::router());
 
 	#[cfg(feature = "ml")]
-	let axum_app = axum_app.merge(ml::router());
+	let api = api.merge(ml::router());
 
-	let axum_app = axum_app.layer(service);
+	let axum_app = Router::new()
+		// Redirect until we provide a UI
+		.route("/", get(|| async { Redirect::temporary(cnf::APP_ENDPOINT) }))
+		// Unversioned routes are served by the current API version, for older clients
+		.merge(api.clone())
+		// Versioned routes are served under a path prefix
+		.nest("/v1", api)
+		.layer(service);
 
 	// Get a new server handler
 	let handle = Handle::new();
 	// Setup the graceful shutdown handler
 	let shutdown_handler = graceful_shutdown(ct.clone(), handle.clone());
+	// Spawn a task to deliver the messages of hooks
+	let pt = ct.clone();
+	tokio::spawn(async move { publications(pt).await });
 	// Spawn a task to handle notifications
 	tokio::spawn(async move { notifications(ct.clone()).await });
 	// If a certificate and key are specified then setup TLS
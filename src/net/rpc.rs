@@ -9,6 +9,7 @@ use crate::rpc::format::HttpFormat;
 use crate::rpc::post_context::PostRpcContext;
 use crate::rpc::response::IntoRpcResponse;
 use crate::rpc::WEBSOCKETS;
+use crate::rt;
 use axum::routing::get;
 use axum::routing::post;
 use axum::TypedHeader;
@@ -116,7 +117,8 @@ async fn post_handler(
 
 	match fmt.req_http(body) {
 		Ok(req) => {
-			let res = rpc_ctx.execute(Method::parse(req.method), req.params).await;
+			let method = Method::parse(req.method);
+			let res = rt::query(async move { rpc_ctx.execute(method, req.params).await }).await;
 			fmt.res_http(res.into_response(None)).map_err(Error::from)
 		}
 		Err(err) => Err(Error::from(err)),
@@ -24,12 +24,20 @@ struct Success {
 	code: u16,
 	details: String,
 	token: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	password_expires: Option<i64>,
 }
 
 impl Success {
-	fn new(token: Option<String>) -> Success {
+	fn new(token: Option<String>, session: &Session) -> Success {
+		// Check if the password of a system user will expire
+		let password_expires = match session.tk.as_ref().map(|tk| tk.pick(&["PE".into()])) {
+			Some(Value::Number(v)) => Some(v.to_int()),
+			_ => None,
+		};
 		Success {
 			token,
+			password_expires,
 			code: 200,
 			details: String::from("Authentication succeeded"),
 		}
@@ -67,13 +75,13 @@ async fn handler(
 				// Authentication was successful
 				Ok(v) => match accept.as_deref() {
 					// Simple serialization
-					Some(Accept::ApplicationJson) => Ok(output::json(&Success::new(v))),
-					Some(Accept::ApplicationCbor) => Ok(output::cbor(&Success::new(v))),
-					Some(Accept::ApplicationPack) => Ok(output::pack(&Success::new(v))),
+					Some(Accept::ApplicationJson) => Ok(output::json(&Success::new(v, &session))),
+					Some(Accept::ApplicationCbor) => Ok(output::cbor(&Success::new(v, &session))),
+					Some(Accept::ApplicationPack) => Ok(output::pack(&Success::new(v, &session))),
 					// Text serialization
 					Some(Accept::TextPlain) => Ok(output::text(v.unwrap_or_default())),
 					// Internal serialization
-					Some(Accept::Surrealdb) => Ok(output::full(&Success::new(v))),
+					Some(Accept::Surrealdb) => Ok(output::full(&Success::new(v, &session))),
 					// Return nothing
 					None => Ok(output::none()),
 					// An incorrect content-type was requested
@@ -1,8 +1,10 @@
+use crate::cnf::{HTTP_READONLY_ROW_LIMIT, HTTP_READONLY_TIMEOUT};
 use crate::dbs::DB;
 use crate::err::Error;
 use crate::net::input::bytes_to_utf8;
 use crate::net::output;
 use crate::net::params::Params;
+use crate::rt;
 use axum::extract::ws::Message;
 use axum::extract::ws::WebSocket;
 use axum::extract::DefaultBodyLimit;
@@ -16,7 +18,8 @@ use axum::TypedHeader;
 use bytes::Bytes;
 use futures::{SinkExt, StreamExt};
 use http_body::Body as HttpBody;
-use surrealdb::dbs::Session;
+use std::time::Duration;
+use surrealdb::dbs::{ReadOnly, Session};
 use tower_http::limit::RequestBodyLimitLayer;
 
 use super::headers::Accept;
@@ -32,6 +35,7 @@ where
 {
 	Router::new()
 		.route("/sql", options(|| async {}).get(ws_handler).post(post_handler))
+		.route("/sql/readonly", options(|| async {}).post(readonly_handler))
 		.route_layer(DefaultBodyLimit::disable())
 		.layer(RequestBodyLimitLayer::new(MAX))
 }
@@ -45,9 +49,42 @@ async fn post_handler(
 	// Get a database reference
 	let db = DB.get().unwrap();
 	// Convert the received sql query
-	let sql = bytes_to_utf8(&sql)?;
+	let sql = bytes_to_utf8(&sql)?.to_owned();
+	let vars = params.0.parse().into();
 	// Execute the received sql query
-	match db.execute(sql, &session, params.0.parse().into()).await {
+	match rt::query(async move { db.execute(&sql, &session, vars).await }).await {
+		Ok(res) => match output.as_deref() {
+			// Simple serialization
+			Some(Accept::ApplicationJson) => Ok(output::json(&output::simplify(res))),
+			Some(Accept::ApplicationCbor) => Ok(output::cbor(&output::simplify(res))),
+			Some(Accept::ApplicationPack) => Ok(output::pack(&output::simplify(res))),
+			// Internal serialization
+			Some(Accept::Surrealdb) => Ok(output::full(&res)),
+			// An incorrect content-type was requested
+			_ => Err(Error::InvalidType),
+		},
+		// There was an error when executing the query
+		Err(err) => Err(Error::from(err)),
+	}
+}
+
+async fn readonly_handler(
+	Extension(session): Extension<Session>,
+	output: Option<TypedHeader<Accept>>,
+	params: Query<Params>,
+	sql: Bytes,
+) -> Result<impl IntoResponse, impl IntoResponse> {
+	// Get a database reference
+	let db = DB.get().unwrap();
+	// Convert the received sql query
+	let sql = bytes_to_utf8(&sql)?.to_owned();
+	let vars = params.0.parse().into();
+	// Specify the limits of the query
+	let limits = ReadOnly::new()
+		.with_timeout(Some(Duration::from_secs(*HTTP_READONLY_TIMEOUT)))
+		.with_limit(Some(*HTTP_READONLY_ROW_LIMIT));
+	// Execute the received sql query in read-only transactions
+	match rt::query(async move { db.execute_readonly(&sql, &session, vars, limits).await }).await {
 		Ok(res) => match output.as_deref() {
 			// Simple serialization
 			Some(Accept::ApplicationJson) => Ok(output::json(&output::simplify(res))),
@@ -79,8 +116,11 @@ async fn handle_socket(ws: WebSocket, session: Session) {
 			if let Ok(sql) = msg.to_text() {
 				// Get a database reference
 				let db = DB.get().unwrap();
+				let sql = sql.to_owned();
+				let session = session.clone();
 				// Execute the received sql query
-				let _ = match db.execute(sql, &session, None).await {
+				let _ = match rt::query(async move { db.execute(&sql, &session, None).await }).await
+				{
 					// Convert the response to JSON
 					Ok(v) => match serde_json::to_string(&v) {
 						// Send the JSON response to the client
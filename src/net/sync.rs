@@ -1,20 +1,116 @@
-use axum::response::IntoResponse;
+use crate::dbs::DB;
+use crate::err::Error;
+use crate::net::output;
+use axum::extract::{BodyStream, DefaultBodyLimit};
+use axum::response::{IntoResponse, Response};
 use axum::routing::get;
-use axum::Router;
+use axum::{Extension, Router};
+use axum_extra::extract::Query;
+use bytes::Bytes;
+use futures::StreamExt;
+use http::header::CONTENT_TYPE;
+use http::StatusCode;
 use http_body::Body as HttpBody;
+use hyper::body::Body;
+use serde::Deserialize;
+use serde_json::json;
+use surrealdb::dbs::Session;
+use surrealdb::iam::Action::{Edit, View};
+use surrealdb::iam::ResourceKind::Any;
+use surrealdb::kvs::SyncDecoder;
+
+/// The maximum number of keys which are sent in each chunk
+const LIMIT: u32 = 1000;
+
+#[derive(Default, Deserialize, Debug, Clone)]
+struct QueryOptions {
+	pub resume: Option<String>,
+}
 
 pub(super) fn router<S, B>() -> Router<S, B>
 where
 	B: HttpBody + Send + 'static,
+	B::Data: Into<Bytes> + Send,
+	B::Error: std::error::Error + Send + Sync + 'static,
 	S: Clone + Send + Sync + 'static,
 {
-	Router::new().route("/sync", get(save).post(load))
+	Router::new().route("/sync", get(save).post(load)).route_layer(DefaultBodyLimit::disable())
 }
 
-async fn load() -> impl IntoResponse {
-	"Load"
+/// Streams the keys and values of a snapshot of the datastore in chunks with checksums.
+/// When a `resume` token is specified, the stream starts after the chunk which it ended.
+async fn save(
+	Extension(session): Extension<Session>,
+	Query(query): Query<QueryOptions>,
+) -> Result<impl IntoResponse, Error> {
+	// Get the datastore reference
+	let db = DB.get().unwrap();
+	// A backup contains every namespace, so is only allowed at the root level
+	db.check(&session, View, Any.on_root())?;
+	// Read every chunk from a single snapshot of the datastore
+	let mut reader = db.sync_reader(query.resume.as_deref()).await?;
+	// Create a chunked response
+	let (mut chn, body) = Body::channel();
+	// Send each of the chunks in order
+	tokio::spawn(async move {
+		loop {
+			let chunk = match reader.next_chunk(LIMIT).await {
+				Ok(Some(v)) => v,
+				Ok(None) => return,
+				Err(e) => {
+					// Abort the response, so that the client resumes from the last chunk
+					error!("Failed to read a chunk for a backup: {e}");
+					chn.abort();
+					return;
+				}
+			};
+			// Stop if the client has gone away
+			if chn.send_data(Bytes::from(chunk.encode())).await.is_err() {
+				if let Err(e) = reader.cancel().await {
+					error!("Failed to close the transaction of a backup: {e}");
+				}
+				return;
+			}
+		}
+	});
+	// Return the chunked body
+	Ok(Response::builder()
+		.status(StatusCode::OK)
+		.header(CONTENT_TYPE, "application/octet-stream")
+		.body(body)
+		.unwrap())
 }
 
-async fn save() -> impl IntoResponse {
-	"Save"
+/// Loads the chunks of a backup into the datastore, writing each chunk in its own
+/// transaction. The response contains the number of keys which were loaded, and the
+/// resume token of the last chunk which was loaded.
+async fn load(
+	Extension(session): Extension<Session>,
+	body: BodyStream,
+) -> Result<impl IntoResponse, Error> {
+	// Get the datastore reference
+	let db = DB.get().unwrap();
+	// A backup contains every namespace, so is only allowed at the root level
+	db.check(&session, Edit, Any.on_root())?;
+	// Load each chunk as soon as it has been received
+	let mut decoder = SyncDecoder::default();
+	let mut keys = 0;
+	let mut resume = None;
+	let mut body = Box::pin(body);
+	while let Some(bytes) = body.next().await {
+		decoder.push(&bytes?);
+		while let Some(chunk) = decoder.next_chunk()? {
+			keys += chunk.pairs.len();
+			resume = chunk.resume_token();
+			db.sync_load(chunk).await?;
+		}
+	}
+	// The body must not end part way through a chunk
+	if decoder.remaining() > 0 {
+		return Err(Error::Request);
+	}
+	Ok(output::json(&json!({
+		"keys": keys,
+		"resume": resume,
+	})))
 }
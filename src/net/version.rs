@@ -1,9 +1,22 @@
 use crate::cnf::PKG_NAME;
 use crate::cnf::PKG_VERSION;
+use crate::err::Error;
+use axum::middleware::Next;
 use axum::response::IntoResponse;
+use axum::response::Response;
 use axum::routing::get;
 use axum::Router;
+use http::HeaderValue;
+use http::Request;
 use http_body::Body as HttpBody;
+use surrealdb::headers::{ACCEPT_VERSION, API_VERSION};
+
+/// The versions of the HTTP API which are supported by this server
+pub(super) const API_VERSIONS: &[&str] = &["1"];
+
+/// The version of the HTTP API which is used when a client does not request
+/// a version, so that older clients can continue to use the unversioned routes
+const DEFAULT_API_VERSION: &str = "1";
 
 pub(super) fn router<S, B>() -> Router<S, B>
 where
@@ -16,3 +29,52 @@ where
 async fn handler() -> impl IntoResponse {
 	format!("{PKG_NAME}-{}", *PKG_VERSION)
 }
+
+/// Check that the API version requested with the `Accept-Version` header
+/// or the `/v1/...` path prefix is supported, and respond with the version
+/// of the API which handled the request.
+pub(super) async fn negotiate<B>(request: Request<B>, next: Next<B>) -> Result<Response, Error>
+where
+	B: Send,
+{
+	// Check the version requested in the path
+	let path = path_version(request.uri().path()).map(supported).transpose()?;
+	// Check the version requested in the header
+	let header = match request.headers().get(&ACCEPT_VERSION) {
+		Some(v) => match v.to_str() {
+			Ok(v) => Some(supported(v)?),
+			Err(_) => {
+				return Err(Error::InvalidVersion(String::from_utf8_lossy(v.as_bytes()).into()))
+			}
+		},
+		None => None,
+	};
+	// The path and header must not request different versions
+	let version = match (path, header) {
+		(Some(p), Some(h)) if p != h => return Err(Error::InvalidVersion(h.to_owned())),
+		(Some(v), _) | (None, Some(v)) => v,
+		(None, None) => DEFAULT_API_VERSION,
+	};
+	let mut response = next.run(request).await;
+	response.headers_mut().insert(API_VERSION.clone(), HeaderValue::from_static(version));
+	Ok(response)
+}
+
+/// Find the supported API version matching a requested version such as `1`, `v1`, or `1.0`
+fn supported(version: &str) -> Result<&'static str, Error> {
+	let major = version.trim().trim_start_matches(['v', 'V']).split('.').next().unwrap_or_default();
+	API_VERSIONS
+		.iter()
+		.find(|v| **v == major)
+		.copied()
+		.ok_or_else(|| Error::InvalidVersion(version.trim().to_owned()))
+}
+
+/// Extract the API version from a `/v1/...` path prefix
+fn path_version(path: &str) -> Option<&str> {
+	let segment = path.strip_prefix('/')?.split('/').next()?;
+	match segment.strip_prefix('v') {
+		Some(v) if !v.is_empty() && v.chars().all(|c| c.is_ascii_digit()) => Some(segment),
+		_ => None,
+	}
+}
@@ -5,7 +5,10 @@ use crate::dbs::DB;
 use crate::rpc::failure::Failure;
 use crate::rpc::format::WsFormat;
 use crate::rpc::response::{failure, IntoRpcResponse};
-use crate::rpc::{CONN_CLOSED_ERR, LIVE_QUERIES, WEBSOCKETS};
+use crate::rpc::{
+	deletion, deregister, CONN_CLOSED_ERR, DELETIONS, LIVE_QUERIES, SUBSCRIPTIONS, WEBSOCKETS,
+};
+use crate::rt;
 use crate::telemetry;
 use crate::telemetry::metrics::ws::RequestContext;
 use crate::telemetry::traces::rpc::span_for_request;
@@ -20,6 +23,7 @@ use surrealdb::channel::{self, Receiver, Sender};
 use surrealdb::dbs::Session;
 use surrealdb::kvs::Datastore;
 use surrealdb::rpc::args::Take;
+use surrealdb::rpc::delete_where::DeleteWhere;
 use surrealdb::rpc::format::Format;
 use surrealdb::rpc::method::Method;
 use surrealdb::rpc::RpcContext;
@@ -43,6 +47,25 @@ pub struct Connection {
 	pub(crate) channels: (Sender<Message>, Receiver<Message>),
 }
 
+/// Removes a WebSocket and its live queries if its handler exits without removing them
+struct Registration {
+	id: Uuid,
+	done: bool,
+}
+
+impl Drop for Registration {
+	fn drop(&mut self) {
+		if self.done {
+			return;
+		}
+		// The handler can not be awaited while unwinding, so the WebSocket is removed in a new task
+		if let Ok(handle) = tokio::runtime::Handle::try_current() {
+			warn!("WebSocket {} exited unexpectedly, so removing its live queries", self.id);
+			handle.spawn(deregister(self.id));
+		}
+	}
+}
+
 impl Connection {
 	/// Instantiate a new RPC
 	pub fn new(id: Uuid, mut session: Session, format: Format) -> Arc<RwLock<Connection>> {
@@ -78,6 +101,12 @@ impl Connection {
 
 		// Add this WebSocket to the list
 		WEBSOCKETS.write().await.insert(id, rpc.clone());
+		DB.get().unwrap().register_connection(id, "ws");
+		// Remove this WebSocket again if this handler panics
+		let mut registration = Registration {
+			id,
+			done: false,
+		};
 
 		// Spawn async tasks for the WebSocket
 		let mut tasks = JoinSet::new();
@@ -96,24 +125,9 @@ impl Connection {
 
 		trace!("WebSocket {} disconnected", id);
 
-		// Remove this WebSocket from the list
-		WEBSOCKETS.write().await.remove(&id);
-
-		// Remove all live queries
-		let mut gc = Vec::new();
-		LIVE_QUERIES.write().await.retain(|key, value| {
-			if value == &id {
-				trace!("Removing live query: {}", key);
-				gc.push(*key);
-				return false;
-			}
-			true
-		});
-
-		// Garbage collect queries
-		if let Err(e) = DB.get().unwrap().garbage_collect_dead_session(gc.as_slice()).await {
-			error!("Failed to garbage collect dead sessions: {:?}", e);
-		}
+		// Remove this WebSocket and its live queries
+		deregister(id).await;
+		registration.done = true;
 
 		if let Err(err) = telemetry::metrics::ws::on_disconnect() {
 			error!("Error running metrics::ws::on_disconnect hook: {}", err);
@@ -218,10 +232,10 @@ impl Connection {
 					// We've received a message from the client
 					Ok(msg) => match msg {
 						Message::Text(_) => {
-							tasks.spawn(Connection::handle_message(rpc.clone(), msg, internal_sender.clone()));
+							tasks.spawn_on(Connection::handle_message(rpc.clone(), msg, internal_sender.clone()), &rt::query_handle());
 						}
 						Message::Binary(_) => {
-							tasks.spawn(Connection::handle_message(rpc.clone(), msg, internal_sender.clone()));
+							tasks.spawn_on(Connection::handle_message(rpc.clone(), msg, internal_sender.clone()), &rt::query_handle());
 						}
 						Message::Close(_) => {
 							// Respond with a close message
@@ -389,6 +403,43 @@ impl RpcContext for Connection {
 		}
 	}
 
+	async fn handle_subscribe(&self, ns: String, db: String, channel: String) {
+		trace!("Subscribed websocket {} to hook channel {}", self.id, channel);
+		SUBSCRIPTIONS.write().await.entry((ns, db, channel)).or_default().insert(self.id);
+	}
+
+	async fn handle_unsubscribe(&self, ns: String, db: String, channel: String) {
+		let mut subscriptions = SUBSCRIPTIONS.write().await;
+		let key = (ns, db, channel);
+		if let Some(ids) = subscriptions.get_mut(&key) {
+			if ids.remove(&self.id) {
+				trace!("Unsubscribed websocket {} from hook channel {}", self.id, key.2);
+			}
+			if ids.is_empty() {
+				subscriptions.remove(&key);
+			}
+		}
+	}
+
+	async fn handle_delete_where(&self, job: DeleteWhere) {
+		let canceller = CancellationToken::new();
+		DELETIONS.write().await.insert(job.id, (self.id, canceller.clone()));
+		trace!("Started deletion {} on websocket {}", job.id, self.id);
+		tokio::spawn(deletion(self.id, job, canceller));
+	}
+
+	async fn handle_cancel_delete_where(&self, id: &Uuid) -> bool {
+		match DELETIONS.read().await.get(id) {
+			// Deletions can only be cancelled by the WebSocket which started them
+			Some((ws, canceller)) if ws == &self.id => {
+				trace!("Cancelled deletion {} on websocket {}", id, self.id);
+				canceller.cancel();
+				true
+			}
+			_ => false,
+		}
+	}
+
 	// reimplimentaions
 
 	async fn signup(&mut self, params: Array) -> Result<impl Into<Data>, RpcError> {
@@ -3,13 +3,20 @@ use revision::revisioned;
 use revision::Revisioned;
 use serde::Serialize;
 use std::borrow::Cow;
+use surrealdb::error::Db as DbError;
+use surrealdb::error::Kind;
 use surrealdb::rpc::RpcError;
-use surrealdb::sql::Value;
+use surrealdb::sql::{Object, Value};
+use surrealdb::Error as SurrealError;
 
 #[derive(Clone, Debug, Serialize)]
 pub struct Failure {
 	pub(crate) code: i64,
 	pub(crate) message: Cow<'static, str>,
+	// Structured details about the error, which are only
+	// included in the error object for value-based formats
+	#[serde(skip)]
+	pub(crate) data: Option<Object>,
 }
 
 #[revisioned(revision = 1)]
@@ -48,7 +55,11 @@ impl From<&str> for Failure {
 
 impl From<Error> for Failure {
 	fn from(err: Error) -> Self {
-		Failure::custom(err.to_string())
+		match &err {
+			Error::Db(SurrealError::Db(e)) => Failure::db(e, err.to_string()),
+			Error::InvalidAuth => Failure::kind(Kind::Auth, err.to_string()),
+			_ => Failure::custom(err.to_string()),
+		}
 	}
 }
 
@@ -59,8 +70,9 @@ impl From<RpcError> for Failure {
 			RpcError::InvalidRequest => Failure::INVALID_REQUEST,
 			RpcError::MethodNotFound => Failure::METHOD_NOT_FOUND,
 			RpcError::InvalidParams => Failure::INVALID_PARAMS,
-			RpcError::InternalError(_) => Failure::custom(err.to_string()),
+			RpcError::InternalError(ref e) => Failure::db(e, err.to_string()),
 			RpcError::Thrown(_) => Failure::custom(err.to_string()),
+			RpcError::InvalidSignature(_) => Failure::kind(Kind::Auth, err.to_string()),
 			_ => Failure::custom(err.to_string()),
 		}
 	}
@@ -68,11 +80,14 @@ impl From<RpcError> for Failure {
 
 impl From<Failure> for Value {
 	fn from(err: Failure) -> Self {
-		map! {
+		let mut out = map! {
 			String::from("code") => Value::from(err.code),
 			String::from("message") => Value::from(err.message.to_string()),
+		};
+		if let Some(data) = err.data {
+			out.insert(String::from("data"), Value::from(data));
 		}
-		.into()
+		out.into()
 	}
 }
 
@@ -81,26 +96,31 @@ impl Failure {
 	pub const PARSE_ERROR: Failure = Failure {
 		code: -32700,
 		message: Cow::Borrowed("Parse error"),
+		data: None,
 	};
 
 	pub const INVALID_REQUEST: Failure = Failure {
 		code: -32600,
 		message: Cow::Borrowed("Invalid Request"),
+		data: None,
 	};
 
 	pub const METHOD_NOT_FOUND: Failure = Failure {
 		code: -32601,
 		message: Cow::Borrowed("Method not found"),
+		data: None,
 	};
 
 	pub const INVALID_PARAMS: Failure = Failure {
 		code: -32602,
 		message: Cow::Borrowed("Invalid params"),
+		data: None,
 	};
 
 	pub const INTERNAL_ERROR: Failure = Failure {
 		code: -32603,
 		message: Cow::Borrowed("Internal error"),
+		data: None,
 	};
 
 	pub fn custom<S>(message: S) -> Failure
@@ -110,6 +130,53 @@ impl Failure {
 		Failure {
 			code: -32000,
 			message: message.into(),
+			data: None,
+		}
+	}
+
+	/// Create a failure for a specific kind of error, with its stable error code
+	pub fn kind<S>(kind: Kind, message: S) -> Failure
+	where
+		Cow<'static, str>: From<S>,
+	{
+		let data = map! {
+			String::from("kind") => Value::from(kind.as_str()),
+		};
+		Failure {
+			code: code(kind),
+			message: message.into(),
+			data: Some(data.into()),
 		}
 	}
+
+	/// Create a failure for a database error, including any details about the resource involved
+	pub fn db<S>(err: &DbError, message: S) -> Failure
+	where
+		Cow<'static, str>: From<S>,
+	{
+		let mut failure = Failure::kind(err.kind(), message);
+		if let Some(data) = &mut failure.data {
+			for (k, v) in err.details() {
+				data.insert(k.to_owned(), Value::from(v));
+			}
+		}
+		failure
+	}
+}
+
+/// The JSON-RPC error code for each kind of error. These
+/// codes are stable, and fall within the range which is
+/// reserved for implementation-defined server errors.
+fn code(kind: Kind) -> i64 {
+	match kind {
+		Kind::Parse => -32001,
+		Kind::Auth => -32002,
+		Kind::Permission => -32003,
+		Kind::Conflict => -32004,
+		Kind::Timeout => -32005,
+		Kind::Quota => -32006,
+		Kind::Constraint => -32007,
+		Kind::NotFound => -32008,
+		_ => -32000,
+	}
 }
@@ -1,6 +1,7 @@
 use crate::net::headers::{Accept, ContentType};
 use crate::rpc::failure::Failure;
 use crate::rpc::response::Response;
+use crate::rpc::SIGNATURES;
 use axum::extract::ws::Message;
 use axum::response::IntoResponse;
 use axum::response::Response as AxumResponse;
@@ -30,7 +31,9 @@ impl From<&ContentType> for Format {
 			ContentType::ApplicationJson => Format::Json,
 			ContentType::ApplicationCbor => Format::Cbor,
 			ContentType::ApplicationPack => Format::Msgpack,
-			ContentType::ApplicationOctetStream => Format::Unsupported,
+			ContentType::ApplicationOctetStream
+			| ContentType::ApplicationJsonPatch
+			| ContentType::ApplicationMergePatch => Format::Unsupported,
 			ContentType::Surrealdb => Format::Bincode,
 		}
 	}
@@ -56,12 +59,22 @@ pub trait WsFormat {
 
 impl WsFormat for Format {
 	fn req_ws(&self, msg: Message) -> Result<Request, Failure> {
-		let val = msg.into_data();
+		let mut val = msg.into_data();
+		// Check and remove the signature of the request
+		if let Some(verifier) = SIGNATURES.get() {
+			let len = verifier.verify(&val)?;
+			val.drain(..len);
+		}
 		self.req(val).map_err(Into::into)
 	}
 
 	fn res_ws(&self, res: Response) -> Result<(usize, Message), Failure> {
 		let res = self.res(res).map_err(Failure::from)?;
+		// Sign the response or notification
+		let res = match SIGNATURES.get() {
+			Some(verifier) => verifier.sign(&res),
+			None => res,
+		};
 		if matches!(self, Format::Json) {
 			// If this has significant performance overhead it could be replaced with unsafe { String::from_utf8_unchecked(res) }
 			// This would be safe as in the case of JSON res come from a call to Into::<Vec<u8>> for String
@@ -79,12 +92,22 @@ pub trait HttpFormat {
 
 impl HttpFormat for Format {
 	fn req_http(&self, body: Bytes) -> Result<Request, RpcError> {
+		// Check and remove the signature of the request
+		let body = match SIGNATURES.get() {
+			Some(verifier) => body.slice(verifier.verify(&body)?..),
+			None => body,
+		};
 		self.req(body).map_err(Into::into)
 	}
 
 	fn res_http(&self, res: Response) -> Result<AxumResponse, RpcError> {
 		let val = res.into_value();
 		let res = self.res(val)?;
+		// Sign the response
+		let res = match SIGNATURES.get() {
+			Some(verifier) => verifier.sign(&res),
+			None => res,
+		};
 		if matches!(self, Format::Json) {
 			// If this has significant performance overhead it could be replaced with unsafe { String::from_utf8_unchecked(res) }
 			// This would be safe as in the case of JSON res come from a call to Into::<Vec<u8>> for String
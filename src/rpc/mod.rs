@@ -11,9 +11,14 @@ use crate::rpc::response::success;
 use crate::telemetry::metrics::ws::NotificationContext;
 use once_cell::sync::Lazy;
 use opentelemetry::Context as TelemetryContext;
-use std::collections::HashMap;
-use std::sync::Arc;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, OnceLock};
 use std::time::Duration;
+use surrealdb::dbs::Action;
+use surrealdb::rpc::delete_where::{DeleteProgress, DeleteStatus, DeleteWhere};
+use surrealdb::rpc::signature::Verifier;
+use surrealdb::sql::Value;
+use tokio::sync::broadcast::error::RecvError;
 use tokio::sync::RwLock;
 use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
@@ -25,11 +30,21 @@ type WebSocket = Arc<RwLock<Connection>>;
 type WebSockets = RwLock<HashMap<Uuid, WebSocket>>;
 /// Mapping of LIVE Query ID to WebSocket ID
 type LiveQueries = RwLock<HashMap<Uuid, Uuid>>;
+/// Mapping of namespace, database, and hook channel to the subscribed WebSocket IDs
+type Subscriptions = RwLock<HashMap<(String, String, String), HashSet<Uuid>>>;
+/// Mapping of batched deletion ID to the WebSocket ID which started it, and its canceller
+type Deletions = RwLock<HashMap<Uuid, (Uuid, CancellationToken)>>;
 
 /// Stores the currently connected WebSockets
 pub(crate) static WEBSOCKETS: Lazy<WebSockets> = Lazy::new(WebSockets::default);
 /// Stores the currently initiated LIVE queries
 pub(crate) static LIVE_QUERIES: Lazy<LiveQueries> = Lazy::new(LiveQueries::default);
+/// Stores the hook channels which WebSockets are subscribed to
+pub(crate) static SUBSCRIPTIONS: Lazy<Subscriptions> = Lazy::new(Subscriptions::default);
+/// Stores the batched deletions which are in progress
+pub(crate) static DELETIONS: Lazy<Deletions> = Lazy::new(Deletions::default);
+/// Checks the signatures of RPC requests, and signs the responses, when a signing key is configured
+pub(crate) static SIGNATURES: OnceLock<Verifier> = OnceLock::new();
 
 /// Performs notification delivery to the WebSockets
 pub(crate) async fn notifications(canceller: CancellationToken) {
@@ -45,30 +60,166 @@ pub(crate) async fn notifications(canceller: CancellationToken) {
 				// Receive a notification on the channel
 				Ok(notification) = channel.recv() => {
 					// Find which WebSocket the notification belongs to
-					if let Some(id) = LIVE_QUERIES.read().await.get(&notification.id) {
-						// Check to see if the WebSocket exists
-						if let Some(rpc) = WEBSOCKETS.read().await.get(id) {
-							// Serialize the message to send
-							let message = success(None, notification);
-							// Add metrics
-							let cx = TelemetryContext::new();
-							let not_ctx = NotificationContext::default()
-								  .with_live_id(id.to_string());
-							let cx = Arc::new(cx.with_value(not_ctx));
-							// Get the WebSocket output format
-							let format = rpc.read().await.format;
-							// get the WebSocket sending channel
-							let sender = rpc.read().await.channels.0.clone();
-							// Send the notification to the client
-							message.send(cx, format, &sender).await
-						}
+					let Some(id) = LIVE_QUERIES.read().await.get(&notification.id).copied() else {
+						continue;
+					};
+					// Check to see if the WebSocket exists
+					let Some(rpc) = WEBSOCKETS.read().await.get(&id).cloned() else {
+						// The WebSocket has gone, so kill the live queries which it left behind
+						warn!("Received a notification for a closed WebSocket {id}, so removing its live queries");
+						tokio::spawn(deregister(id));
+						continue;
+					};
+					// A killed live query sends no further notifications
+					if notification.action == Action::Killed {
+						LIVE_QUERIES.write().await.remove(&notification.id);
 					}
+					// Serialize the message to send
+					let message = success(None, notification);
+					// Add metrics
+					let cx = TelemetryContext::new();
+					let not_ctx = NotificationContext::default()
+						  .with_live_id(id.to_string());
+					let cx = Arc::new(cx.with_value(not_ctx));
+					// Get the WebSocket output format
+					let format = rpc.read().await.format;
+					// get the WebSocket sending channel
+					let sender = rpc.read().await.channels.0.clone();
+					// Send the notification to the client
+					if !message.send(cx, format, &sender).await {
+						// The WebSocket has closed its channel, so remove it along with its live queries
+						warn!("Failed to send a notification to WebSocket {id}, so removing its live queries");
+						tokio::spawn(deregister(id));
+					}
+				},
+			}
+		}
+	}
+}
+
+/// Performs delivery of the messages of hooks to the subscribed WebSockets
+pub(crate) async fn publications(canceller: CancellationToken) {
+	// Subscribe to the messages of hooks
+	if let Some(mut channel) = DB.get().unwrap().publications() {
+		// Loop continuously
+		loop {
+			let publication = tokio::select! {
+				//
+				biased;
+				// Check if this has shutdown
+				_ = canceller.cancelled() => break,
+				// Receive a message on the channel
+				res = channel.recv() => match res {
+					Ok(v) => v,
+					Err(RecvError::Lagged(n)) => {
+						warn!("The delivery of hook messages fell behind, so {n} messages were dropped");
+						continue;
+					}
+					Err(RecvError::Closed) => break,
 				},
+			};
+			// Find which WebSockets are subscribed to the channel
+			let key = (publication.ns.clone(), publication.db.clone(), publication.channel.clone());
+			let Some(ids) = SUBSCRIPTIONS.read().await.get(&key).cloned() else {
+				continue;
+			};
+			// Serialize the message to send
+			let value = Value::from(publication);
+			for id in ids {
+				// Check to see if the WebSocket exists
+				let Some(rpc) = WEBSOCKETS.read().await.get(&id).cloned() else {
+					continue;
+				};
+				// Get the WebSocket output format
+				let format = rpc.read().await.format;
+				// get the WebSocket sending channel
+				let sender = rpc.read().await.channels.0.clone();
+				// Send the message to the client
+				let cx = Arc::new(TelemetryContext::new());
+				if !success(None, value.clone()).send(cx, format, &sender).await {
+					warn!("Failed to send a hook message to WebSocket {id}");
+				}
 			}
 		}
 	}
 }
 
+/// Deletes the batches of a deletion until it finishes or is cancelled,
+/// sending its progress to the WebSocket which started it after each batch
+pub(crate) async fn deletion(id: Uuid, mut job: DeleteWhere, canceller: CancellationToken) {
+	let progress = loop {
+		// Check if the deletion has been cancelled
+		if canceller.is_cancelled() {
+			break job.progress(DeleteStatus::Cancelled);
+		}
+		// Delete the next batch of records
+		let progress = match job.next(DB.get().unwrap()).await {
+			Ok(v) if v.status == DeleteStatus::Running => v,
+			Ok(v) => break v,
+			Err(e) => break job.progress(DeleteStatus::Failed(e.to_string())),
+		};
+		// Stop deleting if the WebSocket has gone
+		if !send(id, progress).await {
+			break job.progress(DeleteStatus::Cancelled);
+		}
+	};
+	DELETIONS.write().await.remove(&job.id);
+	trace!("Finished deletion {} on websocket {} as {}", job.id, id, progress.status);
+	send(id, progress).await;
+}
+
+/// Sends the progress of a deletion to a WebSocket
+async fn send(id: Uuid, progress: DeleteProgress) -> bool {
+	// Check to see if the WebSocket exists
+	let Some(rpc) = WEBSOCKETS.read().await.get(&id).cloned() else {
+		return false;
+	};
+	// Get the WebSocket output format
+	let format = rpc.read().await.format;
+	// get the WebSocket sending channel
+	let sender = rpc.read().await.channels.0.clone();
+	// Send the progress to the client
+	let cx = Arc::new(TelemetryContext::new());
+	success(None, Value::from(progress)).send(cx, format, &sender).await
+}
+
+/// Removes a WebSocket, and kills the live queries which it started
+pub(crate) async fn deregister(id: Uuid) {
+	// Remove this WebSocket from the list
+	WEBSOCKETS.write().await.remove(&id);
+	DB.get().unwrap().deregister_connection(&id);
+	// Remove all hook channel subscriptions
+	SUBSCRIPTIONS.write().await.retain(|_, ids| {
+		ids.remove(&id);
+		!ids.is_empty()
+	});
+	// Cancel all batched deletions
+	DELETIONS.write().await.retain(|_, (ws, canceller)| {
+		if ws == &id {
+			canceller.cancel();
+			return false;
+		}
+		true
+	});
+	// Remove all live queries
+	let mut gc = Vec::new();
+	LIVE_QUERIES.write().await.retain(|key, value| {
+		if value == &id {
+			trace!("Removing live query: {}", key);
+			gc.push(*key);
+			return false;
+		}
+		true
+	});
+	// Garbage collect queries
+	if gc.is_empty() {
+		return;
+	}
+	if let Err(e) = DB.get().unwrap().garbage_collect_dead_session(gc.as_slice()).await {
+		error!("Failed to garbage collect dead sessions: {:?}", e);
+	}
+}
+
 /// Closes all WebSocket connections, waiting for graceful shutdown
 pub(crate) async fn graceful_shutdown() {
 	// Close WebSocket connections, ensuring queued messages are processed
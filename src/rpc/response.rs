@@ -36,14 +36,14 @@ impl Response {
 		value.into()
 	}
 
-	/// Send the response to the WebSocket channel
-	pub async fn send(self, cx: Arc<TelemetryContext>, fmt: Format, chn: &Sender<Message>) {
+	/// Send the response to the WebSocket channel, returning false if the channel is closed
+	pub async fn send(self, cx: Arc<TelemetryContext>, fmt: Format, chn: &Sender<Message>) -> bool {
 		// Create a new tracing span
 		let span = Span::current();
 		// Log the rpc response call
 		debug!("Process RPC response");
 
-		let is_error = self.result.is_err();
+		let mut is_error = self.result.is_err();
 		if let Err(err) = &self.result {
 			span.record("otel.status_code", "Error");
 			span.record(
@@ -53,12 +53,30 @@ impl Response {
 			span.record("rpc.error_code", err.code);
 			span.record("rpc.error_message", err.message.as_ref());
 		}
+		// Keep the id, so that the client can be told if the response can not be sent
+		let id = self.id.clone();
 		// Process the response for the format
-		let (len, msg) = fmt.res_ws(self).unwrap();
-		// Send the message to the write channel
-		if chn.send(msg).await.is_ok() {
-			record_rpc(cx.as_ref(), len, is_error);
+		let (len, msg) = match fmt.res_ws(self) {
+			Ok(v) => v,
+			Err(err) => {
+				error!("Failed to serialize the RPC response: {:?}", err);
+				// Send an error instead, so that the client is not left waiting
+				is_error = true;
+				match fmt.res_ws(failure(id, Failure::INTERNAL_ERROR)) {
+					Ok(v) => v,
+					Err(err) => {
+						error!("Failed to serialize the RPC error response: {:?}", err);
+						return true;
+					}
+				}
+			}
 		};
+		// Send the message to the write channel
+		if chn.send(msg).await.is_err() {
+			return false;
+		}
+		record_rpc(cx.as_ref(), len, is_error);
+		true
 	}
 }
 
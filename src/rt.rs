@@ -0,0 +1,177 @@
+//! The runtime topology of the server.
+//!
+//! By default, network IO, query execution, and background jobs all share the main
+//! runtime. Query execution and background jobs can each be moved onto a separate
+//! runtime, with its own worker threads, so that long running queries can not delay
+//! the WebSocket ping tasks and the node heartbeat, and background jobs can not delay
+//! queries. Storage engines such as RocksDB and SpeeDB perform their IO on the threads
+//! which execute queries, and any blocking work of a query, such as the evaluation of
+//! a machine learning model, is run on the blocking threads of the query runtime.
+use crate::cnf::{RUNTIME_MAX_BLOCKING_THREADS, RUNTIME_STACK_SIZE};
+use crate::err::Error;
+use crate::telemetry::metrics::runtime::record_scheduling_delay;
+use clap::builder::RangedU64ValueParser;
+use clap::Args;
+use std::future::Future;
+use std::panic::resume_unwind;
+use std::pin::Pin;
+use std::sync::OnceLock;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tokio::runtime::{Builder, Handle, Runtime};
+use tokio::task::JoinHandle;
+
+/// The runtime which executes queries, if separate from the main runtime
+static QUERY: OnceLock<Runtime> = OnceLock::new();
+
+/// The runtime which runs background jobs, if separate from the main runtime
+static BACKGROUND: OnceLock<Runtime> = OnceLock::new();
+
+/// The interval at which the scheduling delay of each runtime is measured
+const PROBE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The scheduling delay above which a runtime is reported as overloaded
+const PROBE_WARNING: Duration = Duration::from_millis(100);
+
+#[derive(Args, Debug)]
+pub struct StartCommandRuntimeOptions {
+	#[arg(help = "The number of worker threads which execute queries, separately from network IO")]
+	#[arg(env = "SURREAL_QUERY_THREADS", long = "query-threads")]
+	#[arg(value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+	query_threads: Option<usize>,
+	#[arg(
+		help = "The maximum number of threads which run blocking work and storage IO for queries"
+	)]
+	#[arg(env = "SURREAL_QUERY_BLOCKING_THREADS", long = "query-blocking-threads")]
+	#[arg(value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+	#[arg(requires = "query_threads")]
+	query_blocking_threads: Option<usize>,
+	#[arg(
+		help = "The number of worker threads which run background jobs, separately from queries"
+	)]
+	#[arg(env = "SURREAL_BACKGROUND_THREADS", long = "background-threads")]
+	#[arg(value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+	background_threads: Option<usize>,
+	#[arg(help = "Whether to measure how long each runtime takes to start a task")]
+	#[arg(env = "SURREAL_RUNTIME_METRICS", long = "runtime-metrics")]
+	#[arg(default_value_t = false)]
+	runtime_metrics: bool,
+}
+
+/// Start the runtimes which are separate from the main runtime
+pub fn init(
+	StartCommandRuntimeOptions {
+		query_threads,
+		query_blocking_threads,
+		background_threads,
+		runtime_metrics,
+	}: StartCommandRuntimeOptions,
+) -> Result<(), Error> {
+	if let Some(threads) = query_threads {
+		let blocking = query_blocking_threads.unwrap_or(*RUNTIME_MAX_BLOCKING_THREADS);
+		info!(
+			"Executing queries on {threads} worker threads, with up to {blocking} blocking threads"
+		);
+		let _ = QUERY.set(build("surrealdb-query", threads, blocking)?);
+	}
+	if let Some(threads) = background_threads {
+		info!("Running background jobs on {threads} worker threads");
+		let _ = BACKGROUND.set(build("surrealdb-background", threads, threads)?);
+	}
+	if runtime_metrics {
+		probe("network", Handle::current());
+		if let Some(rt) = QUERY.get() {
+			probe("query", rt.handle().clone());
+		}
+		if let Some(rt) = BACKGROUND.get() {
+			probe("background", rt.handle().clone());
+		}
+	}
+	Ok(())
+}
+
+fn build(name: &'static str, threads: usize, blocking: usize) -> Result<Runtime, Error> {
+	Ok(Builder::new_multi_thread()
+		.enable_all()
+		.worker_threads(threads)
+		.max_blocking_threads(blocking)
+		.thread_stack_size(*RUNTIME_STACK_SIZE)
+		.thread_name(name)
+		.build()?)
+}
+
+/// Periodically measure how long a runtime takes to start a new task
+fn probe(name: &'static str, handle: Handle) {
+	tokio::spawn(async move {
+		let mut interval = tokio::time::interval(PROBE_INTERVAL);
+		loop {
+			interval.tick().await;
+			let start = Instant::now();
+			// The task waits in the queue until a worker thread is free
+			let Ok(delay) = handle.spawn(async move { start.elapsed() }).await else {
+				break;
+			};
+			record_scheduling_delay(name, delay);
+			if delay > PROBE_WARNING {
+				warn!("The {name} runtime took {delay:?} to start a task, so its worker threads may be overloaded");
+			}
+		}
+	});
+}
+
+/// The handle of the runtime which executes queries
+pub fn query_handle() -> Handle {
+	match QUERY.get() {
+		Some(rt) => rt.handle().clone(),
+		None => Handle::current(),
+	}
+}
+
+/// Execute a query on the query runtime. The query is cancelled if the
+/// returned future is dropped before the query completes.
+pub async fn query<F>(fut: F) -> F::Output
+where
+	F: Future + Send + 'static,
+	F::Output: Send + 'static,
+{
+	match QUERY.get() {
+		Some(rt) => Task(rt.spawn(fut)).await,
+		None => fut.await,
+	}
+}
+
+/// Run a function on the background runtime, so that any tasks which it spawns run there
+pub fn background<R>(f: impl FnOnce() -> R) -> R {
+	match BACKGROUND.get() {
+		Some(rt) => {
+			let _guard = rt.enter();
+			f()
+		}
+		None => f(),
+	}
+}
+
+/// A task on another runtime, which is aborted when dropped
+struct Task<T>(JoinHandle<T>);
+
+impl<T> Drop for Task<T> {
+	fn drop(&mut self) {
+		self.0.abort();
+	}
+}
+
+impl<T> Future for Task<T> {
+	type Output = T;
+
+	fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+		match Pin::new(&mut self.0).poll(cx) {
+			Poll::Ready(Ok(v)) => Poll::Ready(v),
+			Poll::Ready(Err(e)) => match e.try_into_panic() {
+				// Propagate any panic in the task to the caller
+				Ok(panic) => resume_unwind(panic),
+				Err(e) => panic!("The query task did not complete: {e}"),
+			},
+			Poll::Pending => Poll::Pending,
+		}
+	}
+}
@@ -1,18 +1,261 @@
-use tracing::Subscriber;
+use clap::ValueEnum;
+use std::sync::Mutex;
+use tracing::level_filters::LevelFilter;
+use tracing::subscriber::Interest;
+use tracing::{span, Event, Metadata, Subscriber};
 use tracing_subscriber::fmt::format::FmtSpan;
-use tracing_subscriber::Layer;
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::layer::{Context, Filter};
+use tracing_subscriber::{reload, EnvFilter, Layer, Registry};
 
 use crate::cli::validator::parser::env_filter::CustomEnvFilter;
+use crate::err::Error;
+use crate::telemetry::filter_from_value;
 
-pub fn new<S>(filter: CustomEnvFilter) -> Box<dyn Layer<S> + Send + Sync>
+/// The filter of the log layer which is currently installed
+static LOG_FILTER: Mutex<Option<LogFilter>> = Mutex::new(None);
+
+/// The format in which logs are output
+#[derive(ValueEnum, Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum LogFormat {
+	/// Human readable log lines
+	#[default]
+	Text,
+	/// One JSON object per log line
+	Json,
+}
+
+/// Allows the filter of a log layer to be changed at runtime
+#[derive(Clone)]
+pub struct LogFilter {
+	handle: reload::Handle<EnvFilter, Registry>,
+	initial: String,
+}
+
+impl LogFilter {
+	/// Get the log filter which is currently in use
+	pub fn get(&self) -> Result<String, Error> {
+		self.handle
+			.with_current(|f| f.to_string())
+			.map_err(|e| Error::InvalidLogFilter(e.to_string()))
+	}
+
+	/// Replace the log filter with a log level, or with filter directives
+	pub fn set(&self, value: &str) -> Result<String, Error> {
+		let filter =
+			filter_from_value(value).map_err(|e| Error::InvalidLogFilter(e.to_string()))?;
+		self.reload(filter)
+	}
+
+	/// Add filter directives, such as `surrealdb_core::kvs=trace`, which
+	/// override the log level of specific modules in the current filter
+	pub fn add(&self, directives: &str) -> Result<String, Error> {
+		let current = self.get()?;
+		let filter = EnvFilter::builder()
+			.parse(format!("{current},{directives}"))
+			.map_err(|e| Error::InvalidLogFilter(e.to_string()))?;
+		self.reload(filter)
+	}
+
+	/// Restore the log filter which the server was started with
+	pub fn reset(&self) -> Result<String, Error> {
+		let filter = EnvFilter::builder()
+			.parse(&self.initial)
+			.map_err(|e| Error::InvalidLogFilter(e.to_string()))?;
+		self.reload(filter)
+	}
+
+	fn reload(&self, filter: EnvFilter) -> Result<String, Error> {
+		self.handle.reload(filter).map_err(|e| Error::InvalidLogFilter(e.to_string()))?;
+		self.get()
+	}
+}
+
+/// Get the filter of the installed log layer, if logging has been initialised
+pub fn filter() -> Option<LogFilter> {
+	LOG_FILTER.lock().unwrap().clone()
+}
+
+pub fn new(filter: CustomEnvFilter, format: LogFormat) -> Box<dyn Layer<Registry> + Send + Sync> {
+	let (layer, filter) = layer(filter, format, std::io::stderr);
+	*LOG_FILTER.lock().unwrap() = Some(filter);
+	layer
+}
+
+fn layer<W>(
+	filter: CustomEnvFilter,
+	format: LogFormat,
+	writer: W,
+) -> (Box<dyn Layer<Registry> + Send + Sync>, LogFilter)
 where
-	S: Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a> + Send + Sync,
+	W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
 {
-	tracing_subscriber::fmt::layer()
-		.compact()
-		.with_ansi(true)
-		.with_span_events(FmtSpan::NONE)
-		.with_writer(std::io::stderr)
-		.with_filter(filter.0)
-		.boxed()
+	let initial = filter.0.to_string();
+	let (filter, handle) = reload::Layer::new(filter.0);
+	let layer = match format {
+		LogFormat::Text => tracing_subscriber::fmt::layer()
+			.compact()
+			.with_ansi(true)
+			.with_span_events(FmtSpan::NONE)
+			.with_writer(writer)
+			.with_filter(filter)
+			.boxed(),
+		LogFormat::Json => tracing_subscriber::fmt::layer()
+			.json()
+			.flatten_event(true)
+			.with_current_span(false)
+			.with_span_list(true)
+			.with_span_events(FmtSpan::NONE)
+			.with_writer(writer)
+			.with_filter(RequestSpans(filter))
+			.boxed(),
+	};
+	(
+		layer,
+		LogFilter {
+			handle,
+			initial,
+		},
+	)
+}
+
+/// Enables the spans of HTTP requests and WebSocket calls, whatever the log level,
+/// so that structured logs include the request id and session of each event
+struct RequestSpans<F>(F);
+
+impl<F> RequestSpans<F> {
+	fn is_request(meta: &Metadata<'_>) -> bool {
+		meta.is_span()
+			&& (meta.fields().field("http.request.id").is_some()
+				|| meta.fields().field("ws.id").is_some())
+	}
+}
+
+impl<S: Subscriber, F: Filter<S>> Filter<S> for RequestSpans<F> {
+	fn enabled(&self, meta: &Metadata<'_>, cx: &Context<'_, S>) -> bool {
+		Self::is_request(meta) || self.0.enabled(meta, cx)
+	}
+
+	fn callsite_enabled(&self, meta: &'static Metadata<'static>) -> Interest {
+		match Self::is_request(meta) {
+			true => Interest::always(),
+			false => self.0.callsite_enabled(meta),
+		}
+	}
+
+	fn event_enabled(&self, event: &Event<'_>, cx: &Context<'_, S>) -> bool {
+		self.0.event_enabled(event, cx)
+	}
+
+	fn max_level_hint(&self) -> Option<LevelFilter> {
+		// The request spans are created at the debug level
+		self.0.max_level_hint().map(|l| l.max(LevelFilter::DEBUG))
+	}
+
+	fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, cx: Context<'_, S>) {
+		self.0.on_new_span(attrs, id, cx)
+	}
+
+	fn on_record(&self, id: &span::Id, values: &span::Record<'_>, cx: Context<'_, S>) {
+		self.0.on_record(id, values, cx)
+	}
+
+	fn on_enter(&self, id: &span::Id, cx: Context<'_, S>) {
+		self.0.on_enter(id, cx)
+	}
+
+	fn on_exit(&self, id: &span::Id, cx: Context<'_, S>) {
+		self.0.on_exit(id, cx)
+	}
+
+	fn on_close(&self, id: span::Id, cx: Context<'_, S>) {
+		self.0.on_close(id, cx)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::io;
+	use std::sync::Arc;
+	use tracing::field;
+	use tracing_subscriber::prelude::*;
+
+	/// A writer which stores the logs in memory
+	#[derive(Clone, Default)]
+	struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+	impl io::Write for Buffer {
+		fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+			self.0.lock().unwrap().write(buf)
+		}
+		fn flush(&mut self) -> io::Result<()> {
+			Ok(())
+		}
+	}
+
+	impl<'w> MakeWriter<'w> for Buffer {
+		type Writer = Buffer;
+		fn make_writer(&'w self) -> Self::Writer {
+			self.clone()
+		}
+	}
+
+	impl Buffer {
+		fn lines(&self) -> Vec<serde_json::Value> {
+			let buf = std::mem::take(&mut *self.0.lock().unwrap());
+			let out = String::from_utf8(buf).unwrap();
+			out.lines().map(|l| serde_json::from_str(l).unwrap()).collect()
+		}
+	}
+
+	fn filter(v: &str) -> CustomEnvFilter {
+		CustomEnvFilter(filter_from_value(v).unwrap())
+	}
+
+	#[test]
+	fn json_logs_include_request() {
+		let buffer = Buffer::default();
+		let (layer, _) = layer(filter("info"), LogFormat::Json, buffer.clone());
+		let _enter = tracing_subscriber::registry().with(layer).set_default();
+		{
+			let span = tracing::debug_span!("request", http.request.id = field::Empty);
+			span.record("http.request.id", "9a3d");
+			let _enter = span.enter();
+			tracing::info!(target: "surreal::net", records = 3, "processed request");
+			tracing::debug!(target: "surreal::net", "not logged");
+		}
+		let lines = buffer.lines();
+		assert_eq!(lines.len(), 1);
+		let line = &lines[0];
+		assert!(line["timestamp"].is_string());
+		assert_eq!(line["level"], "INFO");
+		assert_eq!(line["target"], "surreal::net");
+		assert_eq!(line["message"], "processed request");
+		assert_eq!(line["records"], 3);
+		assert_eq!(line["spans"][0]["name"], "request");
+		assert_eq!(line["spans"][0]["http.request.id"], "9a3d");
+	}
+
+	#[test]
+	fn filter_changes_at_runtime() {
+		let buffer = Buffer::default();
+		let (layer, filter) = layer(filter("info"), LogFormat::Json, buffer.clone());
+		let _enter = tracing_subscriber::registry().with(layer).set_default();
+		tracing::debug!(target: "surrealdb_core::kvs", "before");
+		// Increase the log level of a single module
+		let current = filter.add("surrealdb_core::kvs=trace").unwrap();
+		assert!(current.contains("surrealdb_core::kvs=trace"), "{current}");
+		tracing::debug!(target: "surrealdb_core::kvs", "kvs");
+		tracing::debug!(target: "surrealdb_core::sql", "sql");
+		let lines = buffer.lines();
+		assert_eq!(lines.len(), 1);
+		assert_eq!(lines[0]["message"], "kvs");
+		// Invalid directives are rejected
+		assert!(filter.add("surrealdb=verbose").is_err());
+		// Restore the original filter
+		filter.reset().unwrap();
+		tracing::debug!(target: "surrealdb_core::kvs", "after");
+		assert!(buffer.lines().is_empty());
+	}
 }
@@ -1,4 +1,5 @@
 pub mod http;
+pub mod runtime;
 pub mod ws;
 
 use std::time::Duration;
@@ -0,0 +1,21 @@
+use std::time::Duration;
+
+use once_cell::sync::Lazy;
+use opentelemetry::metrics::{Histogram, Unit};
+use opentelemetry::{Context as TelemetryContext, KeyValue};
+
+use super::METER_DURATION;
+
+pub static RUNTIME_SCHEDULING_DELAY: Lazy<Histogram<u64>> = Lazy::new(|| {
+	METER_DURATION
+		.u64_histogram("runtime.scheduling.delay")
+		.with_description("Measures how long a runtime takes to start a new task in milliseconds.")
+		.with_unit(Unit::new("ms"))
+		.init()
+});
+
+/// Records how long a runtime took to start a new task
+pub fn record_scheduling_delay(runtime: &'static str, delay: Duration) {
+	let attrs = [KeyValue::new("runtime.name", runtime)];
+	RUNTIME_SCHEDULING_DELAY.record(&TelemetryContext::current(), delay.as_millis() as u64, &attrs);
+}
@@ -1,10 +1,11 @@
-mod logs;
+pub mod logs;
 pub mod metrics;
 pub mod traces;
 
 use std::time::Duration;
 
 use crate::cli::validator::parser::env_filter::CustomEnvFilter;
+use crate::telemetry::logs::LogFormat;
 use once_cell::sync::Lazy;
 use opentelemetry::metrics::MetricsError;
 use opentelemetry::sdk::resource::{
@@ -41,6 +42,7 @@ pub static OTEL_DEFAULT_RESOURCE: Lazy<Resource> = Lazy::new(|| {
 #[derive(Debug, Clone)]
 pub struct Builder {
 	filter: CustomEnvFilter,
+	format: LogFormat,
 }
 
 pub fn builder() -> Builder {
@@ -51,6 +53,7 @@ impl Default for Builder {
 	fn default() -> Self {
 		Self {
 			filter: CustomEnvFilter(EnvFilter::default()),
+			format: LogFormat::default(),
 		}
 	}
 }
@@ -70,12 +73,18 @@ impl Builder {
 		self
 	}
 
+	/// Set the log format on the builder
+	pub fn with_log_format(mut self, format: LogFormat) -> Self {
+		self.format = format;
+		self
+	}
+
 	/// Build a tracing dispatcher with the fmt subscriber (logs) and the chosen tracer subscriber
 	pub fn build(self) -> Box<dyn Subscriber + Send + Sync + 'static> {
 		let registry = tracing_subscriber::registry();
 
 		// Setup logging layer
-		let registry = registry.with(logs::new(self.filter.clone()));
+		let registry = registry.with(logs::new(self.filter.clone(), self.format));
 
 		// Setup tracing layer
 		let registry = registry.with(traces::new(self.filter));
@@ -134,6 +134,99 @@ mod cli_integration {
 			assert_eq!(rest, "[\n\t{\n\t\tid: thing:one\n\t}\n]\n\n", "failed to send sql: {args}");
 		}
 
+		let db3 = Ulid::new();
+
+		info!("* Import the exported file in parallel");
+		{
+			let args = format!(
+				"import --conn http://{addr} {creds} --ns {ns} --db {db3} --jobs 4 {exported}"
+			);
+			common::run(&args).output().expect("failed to run parallel import: {args}");
+		}
+
+		info!("* Query from the parallel import");
+		{
+			let args =
+				format!("sql --conn http://{addr} {creds} --ns {ns} --db {db3} --hide-welcome");
+			let output = common::run(&args).input("SELECT * FROM thing;\n").output().unwrap();
+			assert!(output.contains("[[{ id: thing:one }]]"), "failed to send sql: {args}");
+		}
+
+		info!("* Dry run an import of the exported file");
+		{
+			let args = format!(
+				"import --conn http://{addr} {creds} --ns {ns} --db {db2} --dry-run {exported}"
+			);
+			let output = common::run(&args).output().expect("failed to run dry run: {args}");
+			assert!(output.contains("0 new, 0 changed, 1 unchanged, 0 not checked"), "{output}");
+			assert!(output.contains("thing: 1 records"), "{output}");
+		}
+
+		info!("* Dry run an import with a conflicting field type");
+		{
+			let args =
+				format!("sql --conn http://{addr} {creds} --ns {ns} --db {db2} --hide-welcome");
+			common::run(&args).input("DEFINE FIELD name ON thing TYPE int;\n").output().unwrap();
+			let file = common::tmp_file("conflicting.surql");
+			std::fs::write(&file, "DEFINE FIELD name ON thing TYPE string;\n").unwrap();
+			let args = format!(
+				"import --conn http://{addr} {creds} --ns {ns} --db {db2} --dry-run {file}"
+			);
+			let output = common::run(&args).output().expect_err("dry run should fail: {args}");
+			assert!(output.contains("changed: field name on table thing"), "{output}");
+			assert!(output.contains("the type changes from int to string"), "{output}");
+			let args =
+				format!("sql --conn http://{addr} {creds} --ns {ns} --db {db2} --hide-welcome");
+			let output = common::run(&args).input("INFO FOR TABLE thing;\n").output().unwrap();
+			assert!(output.contains("TYPE int"), "the dry run changed the field: {output}");
+		}
+
+		info!("* Export and import selected tables");
+		{
+			let db = Ulid::new();
+			let args = format!("sql --conn http://{addr} {creds} --ns {ns} --db {db} --multi");
+			let input = "DEFINE FUNCTION fn::one() { RETURN 1; }; CREATE user:one; CREATE account:one; CREATE other:one;\n";
+			common::run(&args).input(input).output().unwrap();
+			let args = format!(
+				"export --conn http://{addr} {creds} --ns {ns} --db {db} --table user --table account -"
+			);
+			let output = common::run(&args).output().expect("failed to run table export: {args}");
+			assert!(output.contains("UPDATE user:one CONTENT { id: user:one };"), "{output}");
+			assert!(output.contains("UPDATE account:one CONTENT { id: account:one };"), "{output}");
+			assert!(!output.contains("other:one"), "{output}");
+			assert!(!output.contains("fn::one"), "{output}");
+			let file = common::tmp_file("tables.surql");
+			let args = format!("export --conn http://{addr} {creds} --ns {ns} --db {db} {file}");
+			common::run(&args).output().expect("failed to run file export: {args}");
+			let db2 = Ulid::new();
+			let args = format!(
+				"import --conn http://{addr} {creds} --ns {ns} --db {db2} --table user {file}"
+			);
+			common::run(&args).output().expect("failed to run table import: {args}");
+			let args =
+				format!("sql --conn http://{addr} {creds} --ns {ns} --db {db2} --hide-welcome");
+			let output = common::run(&args).input("INFO FOR DB;\n").output().unwrap();
+			assert!(output.contains("DEFINE TABLE user"), "the table was not imported: {output}");
+			assert!(!output.contains("account"), "the import was not filtered: {output}");
+			assert!(!output.contains("fn::one"), "the import was not filtered: {output}");
+		}
+
+		info!("* Check and fix dangling record references");
+		{
+			let db = Ulid::new();
+			let args = format!("sql --conn http://{addr} {creds} --ns {ns} --db {db} --multi");
+			let input = "CREATE person:1 SET friends = [person:2, person:3]; CREATE person:2;\n";
+			common::run(&args).input(input).output().unwrap();
+			let args = format!("check refs --conn http://{addr} {creds} --ns {ns} --db {db}");
+			let output = common::run(&args).output().expect_err("check should fail: {args}");
+			assert!(output.contains("person: 2 records, 2 links, 0 edges, 1 dangling"), "{output}");
+			assert!(output.contains("person:1 friends[1] -> person:3 (link)"), "{output}");
+			let output = common::run(&format!("{args} --fix null")).output().unwrap();
+			assert!(output.contains("updating 1 records"), "{output}");
+			let output = common::run(&args).output().unwrap();
+			assert!(output.contains("No dangling references were found"), "{output}");
+		}
+
 		info!("* Advanced uncomputed variable to be computed before saving");
 		{
 			let args = format!(
@@ -302,6 +395,91 @@ mod cli_integration {
 		server.finish().unwrap();
 	}
 
+	#[test(tokio::test)]
+	async fn replicate() {
+		let (source, mut source_server) = common::start_server_with_defaults().await.unwrap();
+		let (target, mut target_server) = common::start_server_with_defaults().await.unwrap();
+		let creds = format!("--user {USER} --pass {PASS}");
+		let (ns, db) = (Ulid::new(), Ulid::new());
+		let replicate = format!(
+			"replicate --source ws://{source} --source-user {USER} --source-pass {PASS} --target ws://{target} --target-user {USER} --target-pass {PASS} --databases {ns}/{db} --once"
+		);
+
+		info!("* Create records in the source");
+		{
+			let args = format!("sql --conn http://{source} {creds} --ns {ns} --db {db} --multi");
+			let input = "DEFINE DATABASE `{db}` CHANGEFEED 1h;
+				CREATE person:one SET name = 'one';
+				CREATE person:two SET name = 'two';
+				UPDATE person:one SET age = 30;";
+			let input = input.replace("{db}", &db.to_string());
+			common::run(&args).input(&input).output().expect("failed to create records");
+		}
+
+		info!("* Replicate the changes to the target");
+		common::run(&replicate).output().expect("failed to replicate");
+
+		info!("* Replicate further changes from the last versionstamp");
+		{
+			let args = format!("sql --conn http://{source} {creds} --ns {ns} --db {db} --multi");
+			let input = "DELETE person:two; CREATE person:three;";
+			common::run(&args).input(input).output().expect("failed to change records");
+			common::run(&format!("{replicate} --batch-size 1"))
+				.output()
+				.expect("failed to replicate");
+		}
+
+		info!("* The target contains the replicated records");
+		{
+			let args = format!("sql --conn http://{target} {creds} --ns {ns} --db {db} --multi");
+			let input =
+				"SELECT * FROM person; SELECT VALUE applied FROM ONLY _replication:cursor;\n";
+			let output = common::run(&args).input(input).output().unwrap();
+			assert!(
+				output.contains(
+					"[[{ age: 30, id: person:one, name: 'one' }, { id: person:three }], 5]"
+				),
+				"unexpected output: {output}"
+			);
+		}
+
+		source_server.finish().unwrap();
+		target_server.finish().unwrap();
+	}
+
+	#[test(tokio::test)]
+	async fn replay() {
+		let capture = assert_fs::TempDir::new().unwrap();
+		let (addr, mut server) = common::start_server(StartServerArguments {
+			args: format!("--capture {}", capture.path().display()),
+			..Default::default()
+		})
+		.await
+		.unwrap();
+		let creds = format!("--user {USER} --pass {PASS}");
+		let (ns, db) = (Ulid::new(), Ulid::new());
+
+		info!("* Create records with random ids and values");
+		let created = {
+			let args =
+				format!("sql --conn http://{addr} {creds} --ns {ns} --db {db} --hide-welcome");
+			let input = "CREATE person SET at = time::now(), score = rand::int(0, 1000000);\n";
+			let output = common::run(&args).input(input).output().unwrap();
+			let created = output.lines().find(|l| l.starts_with("[[{ at: '"));
+			let created = created.unwrap_or_else(|| panic!("unexpected output: {output}"));
+			created.trim_matches(|c| c == '[' || c == ']').to_owned()
+		};
+		server.finish().unwrap();
+
+		info!("* Replaying the capture reproduces the same records");
+		{
+			let args = format!("replay {}", capture.path().display());
+			let output = common::run(&args).output().unwrap();
+			assert!(output.contains(&created), "unexpected output: {output}");
+			assert!(output.contains("0 of which differed"), "unexpected output: {output}");
+		}
+	}
+
 	#[test(tokio::test)]
 	async fn with_auth_level() {
 		// Commands with credentials for different auth levels
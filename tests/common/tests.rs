@@ -521,6 +521,56 @@ async fn patch() -> Result<(), Box<dyn std::error::Error>> {
 	Ok(())
 }
 
+#[test(tokio::test)]
+async fn write_return_options() -> Result<(), Box<dyn std::error::Error>> {
+	// Setup database server
+	let (addr, mut server) = common::start_server_with_defaults().await.unwrap();
+	// Connect to WebSocket
+	let mut socket = Socket::connect(&addr, SERVER, FORMAT).await?;
+	// Authenticate the connection
+	socket.send_message_signin(USER, PASS, None, None, None).await?;
+	// Specify a namespace and database
+	socket.send_message_use(Some(NS), Some(DB)).await?;
+	// Send CREATE command returning nothing
+	let res = socket
+		.send_request("create", json!(["tester:id", { "name": "foo" }, { "return": "none" }]))
+		.await?;
+	assert!(res["result"].is_null(), "result: {:?}", res);
+	// Send UPDATE command returning some fields
+	let res = socket
+		.send_request(
+			"update",
+			json!(["tester:id", { "name": "bar", "value": 1 }, { "return": ["name"] }]),
+		)
+		.await?;
+	assert_eq!(res["result"], json!({ "name": "bar" }), "result: {:?}", res);
+	// Send MERGE command returning the record before it was changed
+	let res = socket
+		.send_request("merge", json!(["tester:id", { "value": 2 }, { "return": "before" }]))
+		.await?;
+	assert_eq!(res["result"]["value"], 1, "result: {:?}", res);
+	// Send PATCH command returning some fields
+	let res = socket
+		.send_request(
+			"patch",
+			json!([
+				"tester:id",
+				[{ "op": "replace", "path": "value", "value": 3 }],
+				{ "return": ["value"] }
+			]),
+		)
+		.await?;
+	assert_eq!(res["result"], json!({ "value": 3 }), "result: {:?}", res);
+	// Send CREATE command with invalid options
+	let res = socket
+		.send_request("create", json!(["tester", { "name": "foo" }, { "return": "everything" }]))
+		.await?;
+	assert!(res["error"].is_object(), "result: {:?}", res);
+	// Test passed
+	server.finish().unwrap();
+	Ok(())
+}
+
 #[test(tokio::test)]
 async fn delete() -> Result<(), Box<dyn std::error::Error>> {
 	// Setup database server
@@ -558,6 +608,53 @@ async fn delete() -> Result<(), Box<dyn std::error::Error>> {
 	Ok(())
 }
 
+#[test(tokio::test)]
+async fn delete_where() -> Result<(), Box<dyn std::error::Error>> {
+	// Setup database server
+	let (addr, mut server) = common::start_server_with_defaults().await.unwrap();
+	// Connect to WebSocket
+	let mut socket = Socket::connect(&addr, SERVER, FORMAT).await?;
+	// Authenticate the connection
+	socket.send_message_signin(USER, PASS, None, None, None).await?;
+	// Specify a namespace and database
+	socket.send_message_use(Some(NS), Some(DB)).await?;
+	// Create some test records
+	socket.send_message_query("FOR $i IN 0..6 { CREATE tester SET value = $i }").await?;
+	// Send DELETE_WHERE command
+	let res = socket.send_request("delete_where", json!(["tester", "value > 0", 2])).await?;
+	assert!(res["result"].is_string(), "result: {:?}", res);
+	let id = res["result"].as_str().unwrap().to_owned();
+	// Wait for the progress of each batch, until the deletion finishes
+	let msgs: Result<_, Box<dyn std::error::Error>> =
+		tokio::time::timeout(Duration::from_secs(5), async {
+			let mut msgs = Vec::new();
+			loop {
+				let msg = socket.receive_other_message().await?;
+				let done = msg["result"]["status"] != "running";
+				msgs.push(msg);
+				if done {
+					return Ok(msgs);
+				}
+			}
+		})
+		.await?;
+	let msgs = msgs?;
+	assert!(msgs.iter().all(|v| v["result"]["id"] == id.as_str()), "result: {:?}", msgs);
+	let deleted: Vec<_> = msgs.iter().map(|v| v["result"]["deleted"].as_u64()).collect();
+	assert_eq!(deleted, vec![Some(2), Some(4), Some(5), Some(5)], "result: {:?}", msgs);
+	assert_eq!(msgs[3]["result"]["status"], "completed", "result: {:?}", msgs);
+	assert!(msgs[3]["result"]["last"].is_string(), "result: {:?}", msgs);
+	// Verify only the matching records were deleted
+	let res = socket.send_message_query("SELECT value FROM tester").await?;
+	assert_eq!(res[0]["result"], json!([{ "value": 0 }]), "result: {:?}", res);
+	// Deletions which do not exist can not be cancelled
+	let res = socket.send_request("cancel_delete_where", json!([id])).await?;
+	assert_eq!(res["result"], false, "result: {:?}", res);
+	// Test passed
+	server.finish().unwrap();
+	Ok(())
+}
+
 #[test(tokio::test)]
 async fn query() -> Result<(), Box<dyn std::error::Error>> {
 	// Setup database server
@@ -1002,7 +1099,7 @@ async fn session_expiration() {
 	let res = res.as_object().unwrap();
 	assert_eq!(
 		res["error"],
-		json!({"code": -32000, "message": "There was a problem with the database: The session has expired"})
+		json!({"code": -32002, "message": "There was a problem with the database: The session has expired", "data": {"kind": "auth"}})
 	);
 	// Sign in again using the same session
 	let res = socket
@@ -1112,7 +1209,7 @@ async fn session_expiration_operations() {
 	let res = res.as_object().unwrap();
 	assert_eq!(
 		res["error"],
-		json!({"code": -32000, "message": "There was a problem with the database: The session has expired"})
+		json!({"code": -32002, "message": "There was a problem with the database: The session has expired", "data": {"kind": "auth"}})
 	);
 	// Test operations that SHOULD NOT work with an expired session
 	let operations_ko = vec![
@@ -1187,7 +1284,7 @@ async fn session_expiration_operations() {
 		let res = res.as_object().unwrap();
 		assert_eq!(
 			res["error"],
-			json!({"code": -32000, "message": "There was a problem with the database: The session has expired"})
+			json!({"code": -32002, "message": "There was a problem with the database: The session has expired", "data": {"kind": "auth"}})
 		);
 	}
 
@@ -1239,7 +1336,7 @@ async fn session_expiration_operations() {
 	let res = res.as_object().unwrap();
 	assert_eq!(
 		res["error"],
-		json!({"code": -32000, "message": "There was a problem with the database: The session has expired"})
+		json!({"code": -32002, "message": "There was a problem with the database: The session has expired", "data": {"kind": "auth"}})
 	);
 	let res = socket
 		.send_request(
@@ -1271,7 +1368,7 @@ async fn session_expiration_operations() {
 	let res = res.as_object().unwrap();
 	assert_eq!(
 		res["error"],
-		json!({"code": -32000, "message": "There was a problem with the database: The session has expired"})
+		json!({"code": -32002, "message": "There was a problem with the database: The session has expired", "data": {"kind": "auth"}})
 	);
 
 	// This needs to be last operation as the session will no longer expire afterwards
@@ -1453,7 +1550,7 @@ async fn session_reauthentication_expired() {
 	let res = res.as_object().unwrap();
 	assert_eq!(
 		res["error"],
-		json!({"code": -32000, "message": "There was a problem with the database: The session has expired"})
+		json!({"code": -32002, "message": "There was a problem with the database: The session has expired", "data": {"kind": "auth"}})
 	);
 	// Authenticate using the root token, which has not expired yet
 	socket.send_request("authenticate", json!([root_token,])).await.unwrap();
@@ -630,6 +630,174 @@ mod http_integration {
 		Ok(())
 	}
 
+	#[test(tokio::test)]
+	async fn sync_endpoint() -> Result<(), Box<dyn std::error::Error>> {
+		let (addr, _server) = common::start_server_with_defaults().await.unwrap();
+		let url = &format!("http://{addr}/sync");
+
+		// Prepare HTTP client
+		let mut headers = reqwest::header::HeaderMap::new();
+		headers.insert("NS", Ulid::new().to_string().parse()?);
+		headers.insert("DB", Ulid::new().to_string().parse()?);
+		headers.insert(header::ACCEPT, "application/json".parse()?);
+		let client = reqwest::Client::builder()
+			.connect_timeout(Duration::from_millis(10))
+			.default_headers(headers)
+			.build()?;
+
+		// Create some data
+		{
+			let res = client
+				.post(format!("http://{addr}/sql"))
+				.basic_auth(USER, Some(PASS))
+				.body("CREATE foo:one, foo:two")
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200, "body: {}", res.text().await?);
+		}
+
+		// When no auth is provided, the endpoint returns a 403
+		{
+			let res = client.get(url).send().await?;
+			assert_eq!(res.status(), 403, "body: {}", res.text().await?);
+		}
+
+		// An invalid resume token is rejected
+		{
+			let res = client
+				.get(url)
+				.query(&[("resume", "xyz")])
+				.basic_auth(USER, Some(PASS))
+				.send()
+				.await?;
+			assert_eq!(res.status(), 400, "body: {}", res.text().await?);
+		}
+
+		// Back up the datastore, delete the data, and then load the backup
+		{
+			let res = client.get(url).basic_auth(USER, Some(PASS)).send().await?;
+			assert_eq!(res.status(), 200);
+			let backup = res.bytes().await?;
+			assert!(!backup.is_empty());
+
+			let res = client
+				.post(format!("http://{addr}/sql"))
+				.basic_auth(USER, Some(PASS))
+				.body("DELETE foo")
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200, "body: {}", res.text().await?);
+
+			let res = client.post(url).basic_auth(USER, Some(PASS)).body(backup).send().await?;
+			assert_eq!(res.status(), 200, "body: {}", res.text().await?);
+			let body: serde_json::Value = serde_json::from_str(&res.text().await?)?;
+			assert!(body["keys"].as_u64().unwrap() > 0, "body: {}", body);
+			assert!(body["resume"].is_string(), "body: {}", body);
+
+			let res = client
+				.post(format!("http://{addr}/sql"))
+				.basic_auth(USER, Some(PASS))
+				.body("SELECT VALUE id FROM foo")
+				.send()
+				.await?;
+			let body: serde_json::Value = serde_json::from_str(&res.text().await?)?;
+			assert_eq!(body[0]["result"], json!(["foo:one", "foo:two"]), "body: {}", body);
+		}
+
+		// A truncated backup is rejected
+		{
+			let res =
+				client.post(url).basic_auth(USER, Some(PASS)).body(vec![1, 0, 0]).send().await?;
+			assert_eq!(res.status(), 400, "body: {}", res.text().await?);
+		}
+
+		Ok(())
+	}
+
+	#[test(tokio::test)]
+	async fn changes_endpoint() -> Result<(), Box<dyn std::error::Error>> {
+		let (addr, _server) = common::start_server_with_defaults().await.unwrap();
+		let url = &format!("http://{addr}/changes/person");
+
+		// Prepare HTTP client
+		let mut headers = reqwest::header::HeaderMap::new();
+		headers.insert("NS", Ulid::new().to_string().parse()?);
+		headers.insert("DB", Ulid::new().to_string().parse()?);
+		headers.insert(header::ACCEPT, "application/json".parse()?);
+		let client = reqwest::Client::builder()
+			.connect_timeout(Duration::from_millis(10))
+			.default_headers(headers)
+			.build()?;
+
+		// Create some data
+		{
+			let res = client
+				.post(format!("http://{addr}/sql"))
+				.basic_auth(USER, Some(PASS))
+				.body("DEFINE TABLE person CHANGEFEED 1h INCLUDE ORIGINAL; CREATE person:one SET name = 'one'; UPDATE person:one SET name = 'uno';")
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200, "body: {}", res.text().await?);
+		}
+
+		// When no auth is provided, the endpoint returns a 403
+		{
+			let res = client.get(url).send().await?;
+			assert_eq!(res.status(), 403, "body: {}", res.text().await?);
+		}
+
+		// Tables which do not exist can not be streamed
+		{
+			let res = client
+				.get(format!("http://{addr}/changes/other"))
+				.basic_auth(USER, Some(PASS))
+				.send()
+				.await?;
+			assert_eq!(res.status(), 404, "body: {}", res.text().await?);
+		}
+
+		// When auth is provided, it streams the changes as Debezium envelopes
+		{
+			let mut res = client.get(url).basic_auth(USER, Some(PASS)).send().await?;
+			assert_eq!(res.status(), 200);
+			let mut body = String::new();
+			while body.matches("data:").count() < 2 {
+				let chunk = tokio::time::timeout(Duration::from_secs(5), res.chunk()).await??;
+				body.push_str(std::str::from_utf8(&chunk.expect("the stream ended"))?);
+			}
+			let events: Vec<(u64, serde_json::Value)> = body
+				.split("\n\n")
+				.filter(|e| !e.is_empty())
+				.map(|e| {
+					let id = e.lines().find_map(|l| l.strip_prefix("id:")).unwrap();
+					let data = e.lines().find_map(|l| l.strip_prefix("data:")).unwrap();
+					(id.parse().unwrap(), serde_json::from_str(data).unwrap())
+				})
+				.collect();
+			assert_eq!(events[0].1["op"], "c");
+			assert_eq!(events[0].1["before"], serde_json::Value::Null);
+			assert_eq!(events[0].1["after"], json!({ "id": "person:one", "name": "one" }));
+			assert_eq!(events[1].1["op"], "u");
+			assert_eq!(events[1].1["before"], json!({ "id": "person:one", "name": "one" }));
+			assert_eq!(events[1].1["after"], json!({ "id": "person:one", "name": "uno" }));
+			assert_eq!(events[1].1["source"]["table"], "person");
+			assert_eq!(events[1].1["source"]["versionstamp"], events[1].0);
+
+			// Reconnecting with the last event id resumes after that change
+			let mut res = client
+				.get(url)
+				.basic_auth(USER, Some(PASS))
+				.header("Last-Event-ID", events[0].0.to_string())
+				.send()
+				.await?;
+			let chunk = tokio::time::timeout(Duration::from_secs(5), res.chunk()).await??;
+			let body = String::from_utf8(chunk.expect("the stream ended").to_vec())?;
+			assert!(body.starts_with(&format!("id:{}\n", events[1].0)), "body: {body}");
+		}
+
+		Ok(())
+	}
+
 	#[test(tokio::test)]
 	async fn health_endpoint() -> Result<(), Box<dyn std::error::Error>> {
 		let (addr, _server) = common::start_server_with_defaults().await.unwrap();
@@ -713,6 +881,67 @@ mod http_integration {
 		Ok(())
 	}
 
+	#[test(tokio::test)]
+	async fn import_endpoint_multipart() -> Result<(), Box<dyn std::error::Error>> {
+		let (addr, _server) = common::start_server_with_defaults().await.unwrap();
+		let url = &format!("http://{addr}/import");
+
+		// Prepare HTTP client
+		let mut headers = reqwest::header::HeaderMap::new();
+		headers.insert("NS", Ulid::new().to_string().parse()?);
+		headers.insert("DB", Ulid::new().to_string().parse()?);
+		headers.insert(header::ACCEPT, "application/json".parse()?);
+		let client = reqwest::Client::builder()
+			.connect_timeout(Duration::from_millis(10))
+			.default_headers(headers)
+			.build()?;
+
+		// Each file is imported, even when the last statement has no semicolon
+		{
+			let data = [
+				"--boundary",
+				"Content-Disposition: form-data; name=\"file\"; filename=\"schema.surql\"",
+				"",
+				"DEFINE TABLE foo SCHEMALESS",
+				"--boundary",
+				"Content-Disposition: form-data; name=\"file\"; filename=\"data.surql\"",
+				"",
+				"CREATE foo:one; CREATE foo:two",
+				"--boundary--",
+				"",
+			]
+			.join("\r\n");
+			let res = client
+				.post(url)
+				.basic_auth(USER, Some(PASS))
+				.header(header::CONTENT_TYPE, "multipart/form-data; boundary=boundary")
+				.body(data)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200, "body: {}", res.text().await?);
+
+			// Check that the data was persisted
+			let res = client
+				.post(format!("http://{addr}/sql"))
+				.basic_auth(USER, Some(PASS))
+				.body("SELECT VALUE id FROM foo")
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200, "body: {}", res.text().await?);
+			let body = res.text().await?;
+			assert!(body.contains("foo:one") && body.contains("foo:two"), "body: {}", body);
+		}
+
+		// An invalid statement is rejected
+		{
+			let res =
+				client.post(url).basic_auth(USER, Some(PASS)).body("CREATE CREATE;").send().await?;
+			assert_eq!(res.status(), 400, "body: {}", res.text().await?);
+		}
+
+		Ok(())
+	}
+
 	#[test(tokio::test)]
 	async fn rpc_endpoint() -> Result<(), Box<dyn std::error::Error>> {
 		let (addr, _server) = common::start_server_with_defaults().await.unwrap();
@@ -1320,6 +1549,80 @@ mod http_integration {
 		Ok(())
 	}
 
+	#[test(tokio::test)]
+	async fn sql_readonly_endpoint() -> Result<(), Box<dyn std::error::Error>> {
+		let (addr, _server) = common::start_server_with_defaults().await.unwrap();
+		let url = &format!("http://{addr}/sql/readonly");
+
+		// Prepare HTTP client
+		let mut headers = reqwest::header::HeaderMap::new();
+		headers.insert("NS", Ulid::new().to_string().parse()?);
+		headers.insert("DB", Ulid::new().to_string().parse()?);
+		headers.insert(header::ACCEPT, "application/json".parse()?);
+
+		let client = reqwest::Client::builder()
+			.connect_timeout(Duration::from_millis(10))
+			.default_headers(headers)
+			.build()?;
+
+		// Create a record through the normal endpoint
+		{
+			let res = client
+				.post(format!("http://{addr}/sql"))
+				.basic_auth(USER, Some(PASS))
+				.body("CREATE foo:1 SET name = 'bar'")
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+		}
+
+		// Selecting records is allowed
+		{
+			let res = client
+				.post(url)
+				.basic_auth(USER, Some(PASS))
+				.body("SELECT * FROM foo")
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+
+			let body: serde_json::Value = serde_json::from_str(&res.text().await?).unwrap();
+			assert_eq!(body[0]["status"], "OK", "body: {}", body);
+			assert_eq!(body[0]["result"][0]["name"], "bar", "body: {}", body);
+		}
+
+		// Modifying records is not allowed, even for the root user
+		{
+			let res = client
+				.post(url)
+				.basic_auth(USER, Some(PASS))
+				.body("CREATE foo:2; UPDATE foo SET name = 'baz'; BEGIN; DELETE foo; COMMIT;")
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+
+			let body: serde_json::Value = serde_json::from_str(&res.text().await?).unwrap();
+			for res in body.as_array().unwrap() {
+				assert_eq!(res["status"], "ERR", "body: {}", body);
+			}
+		}
+
+		// The records were not modified
+		{
+			let res = client
+				.post(url)
+				.basic_auth(USER, Some(PASS))
+				.body("SELECT * FROM foo")
+				.send()
+				.await?;
+			let body: serde_json::Value = serde_json::from_str(&res.text().await?).unwrap();
+			assert_eq!(body[0]["result"].as_array().unwrap().len(), 1, "body: {}", body);
+			assert_eq!(body[0]["result"][0]["name"], "bar", "body: {}", body);
+		}
+
+		Ok(())
+	}
+
 	#[test(tokio::test)]
 	#[cfg(feature = "http-compression")]
 	async fn sql_endpoint_with_compression() -> Result<(), Box<dyn std::error::Error>> {
@@ -1400,6 +1703,73 @@ mod http_integration {
 		Ok(())
 	}
 
+	#[test(tokio::test)]
+	async fn versioned_endpoints() -> Result<(), Box<dyn std::error::Error>> {
+		let (addr, _server) = common::start_server_with_defaults().await.unwrap();
+		let client = Client::default();
+
+		// Unversioned routes use the current version
+		{
+			let res = client.get(format!("http://{addr}/version")).send().await?;
+			assert_eq!(res.status(), 200, "response: {:#?}", res);
+			assert_eq!(res.headers()["surreal-api-version"], "1");
+		}
+		// Versioned routes are available under a path prefix
+		{
+			let res = client.get(format!("http://{addr}/v1/version")).send().await?;
+			assert_eq!(res.status(), 200, "response: {:#?}", res);
+			assert_eq!(res.headers()["surreal-api-version"], "1");
+		}
+		// The version can be requested with a header
+		{
+			let res = client
+				.get(format!("http://{addr}/version"))
+				.header("Accept-Version", "v1")
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200, "response: {:#?}", res);
+		}
+		// Unsupported versions are rejected
+		{
+			let res = client
+				.get(format!("http://{addr}/version"))
+				.header("Accept-Version", "2")
+				.send()
+				.await?;
+			assert_eq!(res.status(), 406, "response: {:#?}", res);
+			let res = client
+				.get(format!("http://{addr}/v1/version"))
+				.header("Accept-Version", "2")
+				.send()
+				.await?;
+			assert_eq!(res.status(), 406, "response: {:#?}", res);
+		}
+
+		Ok(())
+	}
+
+	#[test(tokio::test)]
+	async fn capabilities_endpoint() -> Result<(), Box<dyn std::error::Error>> {
+		let (addr, _server) = common::start_server_with_defaults().await.unwrap();
+		let url = &format!("http://{addr}/capabilities");
+
+		let res = Client::default().get(url).send().await?;
+		assert_eq!(res.status(), 200, "response: {:#?}", res);
+		let body: serde_json::Value = serde_json::from_str(&res.text().await?).unwrap();
+		assert_eq!(body["api_versions"], json!(["1"]), "body: {}", body);
+		let methods = body["rpc"]["methods"].as_array().unwrap();
+		assert!(methods.contains(&"query".into()), "body: {}", body);
+		assert!(
+			body["rpc"]["formats"].as_array().unwrap().contains(&"cbor".into()),
+			"body: {}",
+			body
+		);
+		assert!(body["features"]["live_queries"].is_boolean(), "body: {}", body);
+		assert!(body["features"]["ml"].is_boolean(), "body: {}", body);
+
+		Ok(())
+	}
+
 	///
 	/// Key endpoint tests
 	///
@@ -2061,6 +2431,93 @@ mod http_integration {
 		Ok(())
 	}
 
+	#[test(tokio::test)]
+	async fn key_endpoint_patch_one() -> Result<(), Box<dyn std::error::Error>> {
+		let (addr, _server) = common::start_server_with_defaults().await.unwrap();
+		let table_name = "table";
+		let url = &format!("http://{addr}/key/{table_name}/1");
+
+		// Prepare HTTP client
+		let mut headers = reqwest::header::HeaderMap::new();
+		headers.insert("NS", Ulid::new().to_string().parse()?);
+		headers.insert("DB", Ulid::new().to_string().parse()?);
+		headers.insert(header::ACCEPT, "application/json".parse()?);
+		let client = reqwest::Client::builder()
+			.connect_timeout(Duration::from_millis(10))
+			.default_headers(headers)
+			.build()?;
+
+		seed_table(&client, &addr, table_name, 1).await?;
+
+		// Apply a JSON Patch to the record
+		{
+			let res = client
+				.patch(url)
+				.basic_auth(USER, Some(PASS))
+				.header(header::CONTENT_TYPE, "application/json-patch+json")
+				.body(r#"[{"op": "test", "path": "/default", "value": "content"}, {"op": "add", "path": "/name", "value": "record_name"}]"#)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200, "body: {}", res.text().await?);
+
+			// Verify the record has the new data
+			let res = client.get(url).basic_auth(USER, Some(PASS)).send().await?;
+			let body: serde_json::Value = serde_json::from_str(&res.text().await?).unwrap();
+			assert_eq!(
+				body[0]["result"].as_array().unwrap()[0]["name"],
+				"record_name",
+				"body: {}",
+				body
+			);
+		}
+
+		// A JSON Patch with a failing test operation is rejected
+		{
+			let res = client
+				.patch(url)
+				.basic_auth(USER, Some(PASS))
+				.header(header::CONTENT_TYPE, "application/json-patch+json")
+				.body(
+					r#"[{"op": "test", "path": "/default", "value": "other"}, {"op": "remove", "path": "/name"}]"#,
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 409, "body: {}", res.text().await?);
+
+			// Verify the record was not modified
+			let res = client.get(url).basic_auth(USER, Some(PASS)).send().await?;
+			let body: serde_json::Value = serde_json::from_str(&res.text().await?).unwrap();
+			assert_eq!(
+				body[0]["result"].as_array().unwrap()[0]["name"],
+				"record_name",
+				"body: {}",
+				body
+			);
+		}
+
+		// Apply a JSON Merge Patch to the record, removing fields which are null
+		{
+			let res = client
+				.patch(url)
+				.basic_auth(USER, Some(PASS))
+				.header(header::CONTENT_TYPE, "application/merge-patch+json")
+				.body(r#"{"name": null, "merged": true}"#)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200, "body: {}", res.text().await?);
+
+			// Verify the record has the merged data
+			let res = client.get(url).basic_auth(USER, Some(PASS)).send().await?;
+			let body: serde_json::Value = serde_json::from_str(&res.text().await?).unwrap();
+			let record = &body[0]["result"].as_array().unwrap()[0];
+			assert!(record.get("name").is_none(), "body: {}", body);
+			assert_eq!(record["merged"], true, "body: {}", body);
+			assert_eq!(record["default"], "content", "body: {}", body);
+		}
+
+		Ok(())
+	}
+
 	#[test(tokio::test)]
 	async fn key_endpoint_delete_one() -> Result<(), Box<dyn std::error::Error>> {
 		let (addr, _server) = common::start_server_with_defaults().await.unwrap();